@@ -100,13 +100,20 @@ pub struct ConstraintCompositionCoefficients<E: FieldElement> {
 /// https://eprint.iacr.org/2022/1216 and it relies on two points:
 ///
 /// 1. The evaluation proofs for each trace polynomial at $z$ and $g \cdot z$ can be batched using
-/// the non-normalized Lagrange kernel over the set $\{z, g \cdot z\}$. This, however, requires
-/// that the FRI protocol is run with rate $\rho^{+} := \frac{\kappa + 2}{\nu}$ where $\kappa$ and
-/// $\nu$ are the length of the execution trace and the LDE domain size, respectively.
+///    the non-normalized Lagrange kernel over the set $\{z, g \cdot z\}$. This, however, requires
+///    that the FRI protocol is run with rate $\rho^{+} := \frac{\kappa + 2}{\nu}$ where $\kappa$ and
+///    $\nu$ are the length of the execution trace and the LDE domain size, respectively.
 /// 2. The resulting $Y(x)$ do not need to be degree adjusted but the soundness error of the
-/// protocol needs to be updated. For most combinations of batching parameters, this leads to a
-/// negligible increase in soundness error. The formula for the updated error can be found in
-/// Theorem 8 of https://eprint.iacr.org/2022/1216.
+///    protocol needs to be updated. For most combinations of batching parameters, this leads to a
+///    negligible increase in soundness error. The formula for the updated error can be found in
+///    Theorem 8 of https://eprint.iacr.org/2022/1216.
+///
+/// If more than one out-of-domain point is drawn (see
+/// [ProofOptions::num_ood_points()](crate::ProofOptions::num_ood_points)), the same coefficients
+/// are used for the openings at all points $z_j$, and $Y(x)$ is the sum of the above expressions
+/// computed for every $z_j$. In this case, the evaluation proofs are batched using the Lagrange
+/// kernel over the set $\{z_j, g \cdot z_j\}$ for all $j$, and $\rho^{+}$ is updated to
+/// $\frac{\kappa + 2k}{\nu}$ where $k$ is the number of out-of-domain points.
 #[derive(Debug, Clone)]
 pub struct DeepCompositionCoefficients<E: FieldElement> {
     /// Trace polynomial composition coefficients $\alpha_i$.
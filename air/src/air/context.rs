@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{
+    air::{LookupTable, TransitionConstraintDegree},
+    ProofOptions, TraceInfo,
+};
 use core::cmp;
 use math::StarkField;
 use utils::collections::Vec;
@@ -23,6 +26,7 @@ pub struct AirContext<B: StarkField> {
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
     pub(super) num_transition_exemptions: usize,
+    pub(super) lookup_tables: Vec<LookupTable>,
 }
 
 impl<B: StarkField> AirContext<B> {
@@ -93,6 +97,94 @@ impl<B: StarkField> AirContext<B> {
         num_aux_assertions: usize,
         options: ProofOptions,
     ) -> Self {
+        Self::new_with_lookups(
+            trace_info,
+            main_transition_constraint_degrees,
+            aux_transition_constraint_degrees,
+            num_main_assertions,
+            num_aux_assertions,
+            Vec::new(),
+            options,
+        )
+    }
+
+    /// Returns a new instance of [AirContext] instantiated for computations which require multiple
+    /// execution trace segments and make use of the specified lookup tables.
+    ///
+    /// This is similar to [AirContext::new_multi_segment()] constructor, but the context also
+    /// contains a transition constraint and two assertions for every lookup table (see
+    /// [LookupTable] for details). These constraints are placed after the auxiliary transition
+    /// constraints and assertions defined by `aux_transition_constraint_degrees` and
+    /// `num_aux_assertions`. Thus, computations which rely only on lookup tables do not need to
+    /// define any other auxiliary transition constraints or assertions.
+    ///
+    /// The layout of the execution trace must include the running-sum columns of all tables as
+    /// the last columns of the first auxiliary trace segment, and the random elements required
+    /// by lookup tables as the last random elements of this segment.
+    ///
+    /// # Panics
+    /// Panics if
+    /// * `main_transition_constraint_degrees` is an empty vector.
+    /// * `num_main_assertions` is zero.
+    /// * `trace_info.is_multi_segment() == true` but, together with lookup tables, no auxiliary
+    ///   transition constraints or no auxiliary assertions are specified.
+    /// * `trace_info.is_multi_segment() == false` but auxiliary transition constraints,
+    ///   auxiliary assertions, or lookup tables are specified.
+    /// * `lookup_tables` is not empty but:
+    ///   - the first auxiliary trace segment contains fewer columns than there are lookup tables.
+    ///   - the first auxiliary trace segment requires fewer random elements than
+    ///     [LookupTable::NUM_RAND_ELEMENTS].
+    ///   - any of the tables contains no queries, or references a column outside of the main
+    ///     trace segment.
+    /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
+    ///   of the specified transition constraints.
+    pub fn new_with_lookups(
+        trace_info: TraceInfo,
+        main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
+        mut aux_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
+        num_main_assertions: usize,
+        mut num_aux_assertions: usize,
+        lookup_tables: Vec<LookupTable>,
+        options: ProofOptions,
+    ) -> Self {
+        if !lookup_tables.is_empty() {
+            let layout = trace_info.layout();
+            assert!(
+                trace_info.is_multi_segment(),
+                "lookup tables specified for a single-segment trace"
+            );
+            assert!(
+                layout.get_aux_segment_width(0) >= lookup_tables.len(),
+                "auxiliary trace segment must contain at least {} columns for lookup tables, but was {}",
+                lookup_tables.len(),
+                layout.get_aux_segment_width(0)
+            );
+            assert!(
+                layout.get_aux_segment_rand_elements(0) >= LookupTable::NUM_RAND_ELEMENTS,
+                "auxiliary trace segment must require at least {} random elements for lookup tables, but was {}",
+                LookupTable::NUM_RAND_ELEMENTS,
+                layout.get_aux_segment_rand_elements(0)
+            );
+            for (i, table) in lookup_tables.iter().enumerate() {
+                assert!(
+                    table.num_queries() > 0,
+                    "lookup table {i} must contain at least one query"
+                );
+                assert!(
+                    table.max_column() < layout.main_trace_width(),
+                    "lookup table {} references column {}, but the main trace segment consists of {} columns",
+                    i,
+                    table.max_column(),
+                    layout.main_trace_width()
+                );
+            }
+
+            // constraints of lookup tables are placed after the auxiliary constraints of the AIR
+            aux_transition_constraint_degrees
+                .extend(lookup_tables.iter().map(|table| table.constraint_degree()));
+            num_aux_assertions += 2 * lookup_tables.len();
+        }
+
         assert!(
             !main_transition_constraint_degrees.is_empty(),
             "at least one transition constraint degree must be specified"
@@ -158,6 +250,7 @@ impl<B: StarkField> AirContext<B> {
             trace_domain_generator: B::get_root_of_unity(trace_length.ilog2()),
             lde_domain_generator: B::get_root_of_unity(lde_domain_size.ilog2()),
             num_transition_exemptions: 1,
+            lookup_tables,
         }
     }
 
@@ -208,6 +301,8 @@ impl<B: StarkField> AirContext<B> {
     }
 
     /// Returns the number of transition constraints placed against all auxiliary trace segments.
+    ///
+    /// This includes transition constraints of all lookup tables.
     pub fn num_aux_transition_constraints(&self) -> usize {
         self.aux_transition_constraint_degrees.len()
     }
@@ -220,6 +315,11 @@ impl<B: StarkField> AirContext<B> {
         self.num_main_assertions + self.num_aux_assertions
     }
 
+    /// Returns lookup tables defined for a computation.
+    pub fn lookup_tables(&self) -> &[LookupTable] {
+        &self.lookup_tables
+    }
+
     /// Returns the number of rows at the end of an execution trace to which transition constraints
     /// do not apply.
     ///
@@ -234,7 +334,7 @@ impl<B: StarkField> AirContext<B> {
     ///
     /// This is the maximum of:
     /// 1. The maximum evaluation degree over all transition constraints minus the degree
-    /// of the transition constraint divisor divided by trace length.
+    ///    of the transition constraint divisor divided by trace length.
     /// 2. `1`, because the constraint composition polynomial requires at least one column.
     ///
     /// Since the degree of a constraint `C(x)` can be well approximated by
@@ -0,0 +1,232 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{EvaluationFrame, TransitionConstraintDegree};
+use math::{ExtensionOf, FieldElement};
+use utils::collections::Vec;
+
+// LOOKUP TABLE
+// ================================================================================================
+/// Describes a lookup argument over columns of the main trace segment.
+///
+/// A lookup table is defined by a set of *table* columns and a *multiplicity* column: every row of
+/// the table columns contains a tuple of values, and the multiplicity column specifies how many
+/// times the tuple in the same row is looked up. Lookups are defined by *query* columns; every
+/// query consists of the same number of columns as the table, and the tuple of values in every
+/// row of a query must be present in the table.
+///
+/// Lookups are proven using the LogUp argument. Given random elements $\alpha$ and $\beta$, every
+/// tuple $v$ is reduced to a single value $c(v) = \sum_k \alpha^k \cdot v_k$, and a running-sum
+/// column $s$ in the auxiliary trace segment is built such that:
+/// $$
+/// s_{i+1} = s_i + \sum_j \frac{1}{\beta - c(q_{j, i})} - \frac{m_i}{\beta - c(t_i)}
+/// $$
+/// where $q_{j, i}$ is the tuple of the $j$th query at row $i$, $t_i$ is the tuple of the table
+/// at row $i$, and $m_i$ is the multiplicity of this tuple. The running sum starts at zero, and
+/// all queries are present in the table if (with high probability) the running sum returns to
+/// zero after the last row.
+///
+/// Lookup tables are specified when building an [AirContext](crate::AirContext) via
+/// [AirContext::new_with_lookups()](crate::AirContext::new_with_lookups). After that:
+/// * Running-sum columns are built by the prover automatically. They occupy the last columns of
+///   the first auxiliary trace segment (one column per table, in the order in which the tables
+///   were specified), and are built using the last [LookupTable::NUM_RAND_ELEMENTS] random
+///   elements of this segment as $\alpha$ and $\beta$.
+/// * A transition constraint enforcing the above relation is added for every table after the
+///   auxiliary transition constraints defined by the AIR.
+/// * Two assertions are added for every table after the auxiliary assertions defined by the AIR:
+///   the running sum must be zero at the first step, and at the first step to which transition
+///   constraints do not apply. Thus, lookups are enforced over the same rows as transition
+///   constraints; the last [num_transition_exemptions()](crate::AirContext::num_transition_exemptions)
+///   rows of the trace are not included into the argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupTable {
+    table_columns: Vec<usize>,
+    multiplicity_column: usize,
+    queries: Vec<Vec<usize>>,
+}
+
+impl LookupTable {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Number of random elements required by lookup tables.
+    pub const NUM_RAND_ELEMENTS: usize = 2;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new lookup table consisting of the specified columns of the main trace segment
+    /// with multiplicities of its rows in the specified `multiplicity_column`.
+    ///
+    /// The table does not contain any queries; these should be added via
+    /// [with_query()](LookupTable::with_query) method.
+    ///
+    /// # Panics
+    /// Panics if `table_columns` is empty.
+    pub fn new(table_columns: Vec<usize>, multiplicity_column: usize) -> Self {
+        assert!(
+            !table_columns.is_empty(),
+            "lookup table must consist of at least one column"
+        );
+        LookupTable {
+            table_columns,
+            multiplicity_column,
+            queries: Vec::new(),
+        }
+    }
+
+    /// Returns this lookup table with a query consisting of the specified columns of the main
+    /// trace segment added to it.
+    ///
+    /// # Panics
+    /// Panics if the number of query columns is different from the number of table columns.
+    pub fn with_query(mut self, query_columns: Vec<usize>) -> Self {
+        assert_eq!(
+            query_columns.len(),
+            self.table_columns.len(),
+            "lookup query must consist of {} columns, but was {}",
+            self.table_columns.len(),
+            query_columns.len()
+        );
+        self.queries.push(query_columns);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns indexes of the main trace columns which form this table.
+    pub fn table_columns(&self) -> &[usize] {
+        &self.table_columns
+    }
+
+    /// Returns index of the main trace column which contains multiplicities of the table rows.
+    pub fn multiplicity_column(&self) -> usize {
+        self.multiplicity_column
+    }
+
+    /// Returns indexes of the main trace columns for all queries against this table.
+    pub fn queries(&self) -> &[Vec<usize>] {
+        &self.queries
+    }
+
+    /// Returns the number of queries against this table.
+    pub fn num_queries(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// Returns the degree of the transition constraint for the running-sum column of this table.
+    ///
+    /// The constraint is multiplied by the denominators of all fractions, and thus, its degree
+    /// is equal to the number of queries plus 2.
+    pub fn constraint_degree(&self) -> TransitionConstraintDegree {
+        TransitionConstraintDegree::new(self.num_queries() + 2)
+    }
+
+    /// Returns the largest index of a main trace column referenced by this table.
+    pub(crate) fn max_column(&self) -> usize {
+        self.queries
+            .iter()
+            .flatten()
+            .chain(self.table_columns.iter())
+            .fold(self.multiplicity_column, |acc, &column| acc.max(column))
+    }
+
+    // RANDOMNESS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the random elements $\alpha$ and $\beta$ used by lookup tables given random
+    /// elements of the first auxiliary trace segment.
+    ///
+    /// # Panics
+    /// Panics if fewer than [LookupTable::NUM_RAND_ELEMENTS] random elements are provided.
+    pub fn get_rand_elements<E: FieldElement>(segment_rand_elements: &[E]) -> (E, E) {
+        let n = segment_rand_elements.len();
+        assert!(
+            n >= Self::NUM_RAND_ELEMENTS,
+            "lookup tables require at least {} random elements, but {} were provided",
+            Self::NUM_RAND_ELEMENTS,
+            n
+        );
+        (segment_rand_elements[n - 2], segment_rand_elements[n - 1])
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes denominators of all fractions accumulated into the running sum at the specified
+    /// main trace `row` into `result`; denominators for queries are written first (in the order
+    /// in which the queries were added), followed by the denominator for the table.
+    ///
+    /// # Panics
+    /// Panics if the length of `result` is not equal to the number of queries plus one.
+    pub fn evaluate_denominators<F, E>(&self, row: &[F], alpha: E, beta: E, result: &mut [E])
+    where
+        F: FieldElement,
+        E: FieldElement + ExtensionOf<F>,
+    {
+        assert_eq!(
+            result.len(),
+            self.num_queries() + 1,
+            "expected {} denominators, but result has {} slots",
+            self.num_queries() + 1,
+            result.len()
+        );
+        for (query, value) in self.queries.iter().zip(result.iter_mut()) {
+            *value = beta - reduce_tuple(query, row, alpha);
+        }
+        result[self.num_queries()] = beta - reduce_tuple(&self.table_columns, row, alpha);
+    }
+
+    /// Evaluates the transition constraint for the running-sum column of this table given the
+    /// main trace frame and the `current` and `next` values of the running sum.
+    pub fn evaluate_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        current: E,
+        next: E,
+        alpha: E,
+        beta: E,
+    ) -> E
+    where
+        F: FieldElement,
+        E: FieldElement + ExtensionOf<F>,
+    {
+        let row = main_frame.current();
+
+        // compute the product of query denominators, and the sum of products of all query
+        // denominators but one; this way, the sum of query fractions is numerator / product
+        let mut numerator = E::ZERO;
+        let mut product = E::ONE;
+        for query in self.queries.iter() {
+            let denominator = beta - reduce_tuple(query, row, alpha);
+            numerator = numerator * denominator + product;
+            product *= denominator;
+        }
+
+        let table_denominator = beta - reduce_tuple(&self.table_columns, row, alpha);
+        let multiplicity = E::from(row[self.multiplicity_column]);
+
+        (next - current) * product * table_denominator
+            - (numerator * table_denominator - multiplicity * product)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reduces the tuple of values in the specified columns of the `row` to a single value by
+/// computing $\sum_k \alpha^k \cdot v_k$.
+fn reduce_tuple<F, E>(columns: &[usize], row: &[F], alpha: E) -> E
+where
+    F: FieldElement,
+    E: FieldElement + ExtensionOf<F>,
+{
+    columns
+        .iter()
+        .rev()
+        .fold(E::ZERO, |acc, &column| acc * alpha + E::from(row[column]))
+}
@@ -5,10 +5,11 @@
 
 use crate::ProofOptions;
 use crypto::{RandomCoin, RandomCoinError};
-use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
+use math::{fft, Domain, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
 use utils::collections::{BTreeMap, Vec};
 
 mod trace_info;
+pub(crate) use trace_info::NUM_AUX_SEGMENTS;
 pub use trace_info::{TraceInfo, TraceLayout};
 
 mod context;
@@ -31,6 +32,9 @@ pub use coefficients::{
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod lookup;
+pub use lookup::LookupTable;
+
 #[cfg(test)]
 mod tests;
 
@@ -172,11 +176,20 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// * Override [Air::get_aux_assertions()] method. This method is similar to the
 ///   [Air::get_assertions()] method, but it should return assertions against columns of the
 ///   auxiliary trace segments.
+///
+/// ### Lookup tables
+/// A common use of Randomized AIR is a lookup argument: proving that tuples of values in some
+/// columns of the main trace segment are present in a table formed by other columns. Instead of
+/// building auxiliary columns and constraints for such arguments by hand, lookup tables can be
+/// described via [LookupTable] structs and passed to the [AirContext::new_with_lookups()]
+/// constructor. Running-sum columns for these tables are then built by the prover automatically,
+/// and the corresponding auxiliary transition constraints and assertions are added to the ones
+/// defined by [Air::evaluate_aux_transition()] and [Air::get_aux_assertions()] methods.
 pub trait Air: Send + Sync {
     /// Base field for the computation described by this AIR. STARK protocol for this computation
     /// may be executed in the base field, or in an extension of the base fields as specified
     /// by [ProofOptions] struct.
-    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3>;
+    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<4>;
 
     /// A type defining shape of public inputs for the computation described by this protocol.
     /// This could be any type as long as it can be serialized into a sequence of field elements.
@@ -302,6 +315,10 @@ pub trait Air: Send + Sync {
     ///
     /// These polynomials are interpolated from the values returned from the
     /// [get_periodic_column_values()](Air::get_periodic_column_values) method.
+    ///
+    /// The verifier does not use these polynomials: it evaluates periodic columns directly from
+    /// the values returned from [get_periodic_column_values()](Air::get_periodic_column_values)
+    /// using the barycentric formula. Thus, this method should not be overridden.
     fn get_periodic_column_polys(&self) -> Vec<Vec<Self::BaseField>> {
         // cache inverse twiddles for each cycle length so that we don't have to re-build them
         // for columns with identical cycle lengths
@@ -362,11 +379,88 @@ pub trait Air: Send + Sync {
         BoundaryConstraints::new(
             self.context(),
             self.get_assertions(),
-            self.get_aux_assertions(aux_rand_elements),
+            self.get_aux_assertions_with_lookups(aux_rand_elements),
             composition_coefficients,
         )
     }
 
+    // LOOKUP TABLES
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates auxiliary transition constraints over the specified evaluation frames for the
+    /// main and auxiliary trace segments, including transition constraints of lookup tables.
+    ///
+    /// Evaluations of the constraints defined by
+    /// [evaluate_aux_transition()](Air::evaluate_aux_transition) are written into the first part
+    /// of the `result` slice, followed by evaluations of lookup table constraints (one per table).
+    /// For computations relying only on lookup tables,
+    /// [evaluate_aux_transition()](Air::evaluate_aux_transition) is not invoked.
+    ///
+    /// This method should not be overridden.
+    fn evaluate_aux_transition_with_lookups<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let lookup_tables = self.context().lookup_tables();
+        let (aux_result, lookup_result) = result.split_at_mut(result.len() - lookup_tables.len());
+        if !aux_result.is_empty() {
+            self.evaluate_aux_transition(
+                main_frame,
+                aux_frame,
+                periodic_values,
+                aux_rand_elements,
+                aux_result,
+            );
+        }
+
+        if !lookup_tables.is_empty() {
+            let (alpha, beta) =
+                LookupTable::get_rand_elements(aux_rand_elements.get_segment_elements(0));
+            let offset = aux_frame.current().len() - lookup_tables.len();
+            for (i, (table, result)) in lookup_tables.iter().zip(lookup_result).enumerate() {
+                *result = table.evaluate_transition(
+                    main_frame,
+                    aux_frame.current()[offset + i],
+                    aux_frame.next()[offset + i],
+                    alpha,
+                    beta,
+                );
+            }
+        }
+    }
+
+    /// Returns a set of assertions placed against auxiliary trace segments, including assertions
+    /// against running-sum columns of lookup tables.
+    ///
+    /// Assertions returned from [get_aux_assertions()](Air::get_aux_assertions) are followed by
+    /// two assertions for every lookup table: the running sum must be zero at the first step of
+    /// the trace, and at the first step to which transition constraints do not apply.
+    ///
+    /// This method should not be overridden.
+    fn get_aux_assertions_with_lookups<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        let mut result = self.get_aux_assertions(aux_rand_elements);
+
+        let lookup_tables = self.context().lookup_tables();
+        let offset = self.trace_layout().aux_trace_width() - lookup_tables.len();
+        let last_step = self.trace_length() - self.context().num_transition_exemptions();
+        for column in offset..offset + lookup_tables.len() {
+            result.push(Assertion::single(column, 0, E::ZERO));
+            result.push(Assertion::single(column, last_step, E::ZERO));
+        }
+
+        result
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -454,6 +548,22 @@ pub trait Air: Send + Sync {
         self.context().lde_domain_generator
     }
 
+    /// Returns the trace domain for an instance of the computation described by this AIR.
+    ///
+    /// This is the multiplicative subgroup of size equal to the length of the execution trace.
+    fn trace_domain(&self) -> Domain<Self::BaseField> {
+        Domain::new(self.trace_length())
+    }
+
+    /// Returns the low-degree extension domain for an instance of the computation described by
+    /// this AIR.
+    ///
+    /// This is the multiplicative subgroup of size equal to the size of the low-degree extension
+    /// domain shifted by the [domain offset](Air::domain_offset).
+    fn lde_domain(&self) -> Domain<Self::BaseField> {
+        Domain::with_offset(self.lde_domain_size(), self.domain_offset())
+    }
+
     /// Returns the offset by which the domain for low-degree extension is shifted in relation
     /// to the execution trace domain.
     fn domain_offset(&self) -> Self::BaseField {
@@ -502,12 +612,12 @@ pub trait Air: Send + Sync {
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.context().num_transition_constraints() {
-            t_coefficients.push(public_coin.draw()?);
+            t_coefficients.push(public_coin.draw_ext()?);
         }
 
         let mut b_coefficients = Vec::new();
         for _ in 0..self.context().num_assertions() {
-            b_coefficients.push(public_coin.draw()?);
+            b_coefficients.push(public_coin.draw_ext()?);
         }
 
         Ok(ConstraintCompositionCoefficients {
@@ -528,12 +638,12 @@ pub trait Air: Send + Sync {
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.trace_info().width() {
-            t_coefficients.push(public_coin.draw()?);
+            t_coefficients.push(public_coin.draw_ext()?);
         }
 
         let mut c_coefficients = Vec::new();
         for _ in 0..self.context().num_constraint_composition_columns() {
-            c_coefficients.push(public_coin.draw()?);
+            c_coefficients.push(public_coin.draw_ext()?);
         }
 
         Ok(DeepCompositionCoefficients {
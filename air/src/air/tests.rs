@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    Air, AirContext, Assertion, EvaluationFrame, LookupTable, ProofOptions, TraceInfo, TraceLayout,
     TransitionConstraintDegree,
 };
 use crate::{AuxTraceRandElements, FieldExtension};
@@ -205,6 +205,100 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&5], constraint.cc().clone());
 }
 
+// LOOKUP TABLES
+// ================================================================================================
+
+#[test]
+fn lookup_table_context() {
+    let trace_info = TraceInfo::new_multi_segment(TraceLayout::new(4, [2], [3]), 16, vec![]);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let table = LookupTable::new(vec![0, 1], 2).with_query(vec![3, 3]);
+    let context = AirContext::<BaseElement>::new_with_lookups(
+        trace_info.clone(),
+        vec![TransitionConstraintDegree::new(2)],
+        vec![TransitionConstraintDegree::new(1)],
+        1,
+        1,
+        vec![table.clone()],
+        options.clone(),
+    );
+
+    // a constraint and two assertions are added for the table
+    assert_eq!(2, context.num_aux_transition_constraints());
+    assert_eq!(4, context.num_assertions());
+    assert_eq!(&[table], context.lookup_tables());
+
+    // computations may rely on lookup tables only
+    let table = LookupTable::new(vec![0], 1)
+        .with_query(vec![2])
+        .with_query(vec![3]);
+    let context = AirContext::<BaseElement>::new_with_lookups(
+        trace_info,
+        vec![TransitionConstraintDegree::new(2)],
+        Vec::new(),
+        1,
+        0,
+        vec![table],
+        options,
+    );
+    assert_eq!(1, context.num_aux_transition_constraints());
+    assert_eq!(3, context.num_assertions());
+    assert_eq!(4, context.ce_domain_size() / context.trace_len());
+}
+
+#[test]
+#[should_panic(expected = "lookup tables specified for a single-segment trace")]
+fn lookup_table_context_single_segment() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let table = LookupTable::new(vec![0], 1).with_query(vec![2]);
+    let _ = AirContext::<BaseElement>::new_with_lookups(
+        TraceInfo::new(4, 16),
+        vec![TransitionConstraintDegree::new(2)],
+        Vec::new(),
+        1,
+        0,
+        vec![table],
+        options,
+    );
+}
+
+#[test]
+fn lookup_table_transition() {
+    let table = LookupTable::new(vec![0, 1], 2)
+        .with_query(vec![3, 4])
+        .with_query(vec![4, 3]);
+    let alpha = BaseElement::new(5);
+    let beta = BaseElement::new(1_000_003);
+    let row = [1u64, 2, 3, 2, 1].map(BaseElement::new).to_vec();
+    let frame = EvaluationFrame::from_rows(row.clone(), row.clone());
+
+    // tuples are reduced as t_0 + alpha * t_1
+    let mut denominators = vec![BaseElement::ZERO; 3];
+    table.evaluate_denominators(&row, alpha, beta, &mut denominators);
+    assert_eq!(beta - BaseElement::new(2 + 5), denominators[0]);
+    assert_eq!(beta - BaseElement::new(1 + 10), denominators[1]);
+    assert_eq!(beta - BaseElement::new(1 + 10), denominators[2]);
+
+    // the constraint is satisfied only if the running sum accumulates the fractions of the row
+    let current = BaseElement::new(42);
+    let delta = denominators[0].inv() + denominators[1].inv() - row[2] * denominators[2].inv();
+    assert_eq!(
+        BaseElement::ZERO,
+        table.evaluate_transition(&frame, current, current + delta, alpha, beta)
+    );
+    assert_ne!(
+        BaseElement::ZERO,
+        table.evaluate_transition(
+            &frame,
+            current,
+            current + delta + BaseElement::ONE,
+            alpha,
+            beta
+        )
+    );
+    assert_eq!(4, table.constraint_degree().min_blowup_factor());
+}
+
 // MOCK AIR
 // ================================================================================================
 
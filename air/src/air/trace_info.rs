@@ -13,7 +13,7 @@ use utils::{
 // ================================================================================================
 
 /// Number of allowed auxiliary trace segments.
-const NUM_AUX_SEGMENTS: usize = 1;
+pub(crate) const NUM_AUX_SEGMENTS: usize = 1;
 
 // TRACE INFO
 // ================================================================================================
@@ -3,10 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use fri::FriOptions;
+use crate::proof::estimate_security_level;
+use crypto::{Hasher, QuerySampling};
+use fri::{stir::StirOptions, FriOptions};
 use math::{StarkField, ToElements};
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, SliceReader,
 };
 
 // CONSTANTS
@@ -21,9 +24,27 @@ const MAX_BLOWUP_FACTOR: usize = 128;
 
 const MAX_GRINDING_FACTOR: u32 = 32;
 
+// grinding factors fit into the lower 6 bits of a byte; the top bit of the byte encodes the
+// query sampling method, and the next bit encodes the low-degree test
+const BIT_SLICE_SAMPLING_FLAG: u8 = 0x80;
+const STIR_LDT_FLAG: u8 = 0x40;
+
+// blowup factors are powers of two greater than 1, and thus, the lowest bit of a blowup factor
+// byte is free; this bit is set if queries are authenticated via Merkle multi-proofs
+const MERKLE_MULTI_PROOFS_FLAG: u8 = 0x01;
+
+// field extension degrees fit into the lower 3 bits of a byte; the next bit of the byte is set
+// if FRI layer grinding is enabled, and the upper 4 bits of the byte encode the number of
+// out-of-domain points minus one
+const MAX_NUM_OOD_POINTS: usize = 16;
+const NUM_OOD_POINTS_SHIFT: u8 = 4;
+const FIELD_EXTENSION_MASK: u8 = 0x07;
+pub(crate) const FRI_LAYER_GRINDING_FLAG: u8 = 0x08;
+
 const FRI_MIN_FOLDING_FACTOR: usize = 2;
 const FRI_MAX_FOLDING_FACTOR: usize = 16;
 const FRI_MAX_REMAINDER_DEGREE: usize = 255;
+const FRI_MAX_FOLDING_SCHEDULE_LEN: usize = 32;
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -37,7 +58,8 @@ const FRI_MAX_REMAINDER_DEGREE: usize = 255;
 ///
 /// For example, if the size of base field is ~64-bits, a quadratic extension must be use to
 /// achieve ~100 bits of soundness, and a cubic extension must be used to achieve 128+ bits
-/// of soundness.
+/// of soundness. For ~31-bit fields, a quartic extension is required to achieve ~100 bits of
+/// soundness.
 ///
 /// However, increasing extension degree will increase proof generation time and proof size by
 /// as much as 50%.
@@ -50,6 +72,41 @@ pub enum FieldExtension {
     Quadratic = 2,
     /// Composition polynomial is constructed in the cubic extension of the base field.
     Cubic = 3,
+    /// Composition polynomial is constructed in the quartic extension of the base field.
+    Quartic = 4,
+}
+
+/// Defines how the security level of a STARK proof is estimated.
+///
+/// The two estimates differ in the soundness bound assumed for the FRI protocol. Usually, the
+/// number of queries needed for proven security is 2x - 3x higher than the number of queries
+/// needed for conjectured security at the same security level.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SecurityEstimate {
+    /// Security level implied by the conjecture that Reed-Solomon codes can be decoded up to
+    /// their capacity bound; under this conjecture, every query contributes `log2(blowup_factor)`
+    /// bits of security.
+    Conjectured,
+    /// Security level implied by the soundness bounds proven for the protocol in the list-decoding
+    /// regime (see https://eprint.iacr.org/2021/582 and https://eprint.iacr.org/2022/1216).
+    ///
+    /// This estimate is available only when the crate is compiled with `std` feature enabled.
+    Proven,
+}
+
+/// Defines the low-degree test used to prove that evaluations of the DEEP composition polynomial
+/// are evaluations of a polynomial of low degree.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LdtKind {
+    /// The FRI protocol (see [FriOptions]).
+    Fri,
+    /// The STIR protocol (see [StirOptions]).
+    ///
+    /// Similarly to FRI, STIR reduces the degree of a polynomial by the FRI folding factor in
+    /// every round, but it shrinks the evaluation domain only by a factor of 2; thus, fewer
+    /// queries are needed in later rounds, which usually results in smaller proofs. STIR does not
+    /// support FRI folding schedules.
+    Stir,
 }
 
 /// STARK protocol parameters.
@@ -69,6 +126,26 @@ pub enum FieldExtension {
 /// 4. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, conjectured proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
+/// 5. FRI folding factor - higher values reduce the number of FRI layers (and thus, the number of
+///    layer commitments a verifier needs to check), but increase proof size. A folding schedule
+///    set via [with_fri_folding_schedule()](ProofOptions::with_fri_folding_schedule) allows
+///    using different folding factors for different FRI layers.
+/// 6. Query sampling - defines how query positions are derived from the public coin; this does
+///    not affect proof soundness, but a verifier needs to derive query positions in the same way
+///    as the prover did (see [QuerySampling]).
+/// 7. Number of out-of-domain points - the number of points at which the prover evaluates trace
+///    and composition polynomials during DEEP composition. Drawing more than one point reduces
+///    the soundness error of the DEEP step in the list-decoding regime, but increases proof size
+///    and verifier time (see [with_num_ood_points()](ProofOptions::with_num_ood_points)).
+/// 8. Low-degree test - the protocol used to prove that the DEEP composition polynomial is of
+///    low degree; FRI is used by default, and STIR can be selected via
+///    [with_ldt()](ProofOptions::with_ldt) to reduce the number of queries in later rounds.
+/// 9. FRI layer grinding factor - higher values increase the soundness of the FRI commit phase,
+///    but also increase proof generation time as the prover needs to perform a proof-of-work for
+///    every FRI layer (see [with_fri_layer_grinding()](ProofOptions::with_fri_layer_grinding)).
+/// 10. Merkle multi-proofs - authenticating trace and constraint queries via Merkle multi-proofs
+///     does not affect proof soundness, but makes serialized queries slightly smaller (see
+///     [with_merkle_multi_proofs()](ProofOptions::with_merkle_multi_proofs)).
 ///
 /// Another important parameter in defining STARK security level, which is not a part of [ProofOptions]
 /// is the hash function used in the protocol. The soundness of a STARK proof is limited by the
@@ -81,7 +158,13 @@ pub struct ProofOptions {
     grinding_factor: u8,
     field_extension: FieldExtension,
     fri_folding_factor: u8,
+    fri_folding_schedule: Vec<u8>,
     fri_remainder_max_degree: u8,
+    query_sampling: QuerySampling,
+    num_ood_points: u8,
+    ldt: LdtKind,
+    fri_layer_grinding_factor: u8,
+    merkle_multi_proofs: bool,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -146,10 +229,152 @@ impl ProofOptions {
             grinding_factor: grinding_factor as u8,
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
+            fri_folding_schedule: Vec::new(),
             fri_remainder_max_degree: fri_remainder_max_degree as u8,
+            query_sampling: QuerySampling::Modulo,
+            num_ood_points: 1,
+            ldt: LdtKind::Fri,
+            fri_layer_grinding_factor: 0,
+            merkle_multi_proofs: false,
         }
     }
 
+    /// Updates these options to fold the first FRI layers by the factors specified in
+    /// `folding_schedule`, and all subsequent layers by the last factor in the schedule.
+    ///
+    /// This overrides the `fri_folding_factor` these options were instantiated with. For example,
+    /// schedule `[8, 4]` folds the first FRI layer by 8 and all other layers by 4; see
+    /// [FriOptions::with_folding_schedule()] for more info.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `folding_schedule` is empty or contains more than 32 factors.
+    /// - Any of the factors in `folding_schedule` is not 2, 4, 8, or 16.
+    /// - These options use [LdtKind::Stir] and `folding_schedule` contains different factors.
+    pub fn with_fri_folding_schedule(mut self, folding_schedule: &[usize]) -> ProofOptions {
+        assert!(
+            !folding_schedule.is_empty(),
+            "FRI folding schedule cannot be empty"
+        );
+        assert!(
+            folding_schedule.len() <= FRI_MAX_FOLDING_SCHEDULE_LEN,
+            "FRI folding schedule cannot contain more than {FRI_MAX_FOLDING_SCHEDULE_LEN} factors"
+        );
+        for &folding_factor in folding_schedule {
+            assert!(
+                is_valid_folding_factor(folding_factor),
+                "FRI folding factor must be a power of 2 between {FRI_MIN_FOLDING_FACTOR} and {FRI_MAX_FOLDING_FACTOR}, but was {folding_factor}"
+            );
+        }
+
+        // use the same normalization as FriOptions so that equivalent schedules are encoded
+        // the same way
+        let fri_options = FriOptions::with_folding_schedule(
+            self.blowup_factor(),
+            folding_schedule,
+            self.fri_remainder_max_degree as usize,
+        );
+        self.fri_folding_factor = fri_options.folding_factor() as u8;
+        self.fri_folding_schedule = fri_options
+            .folding_schedule()
+            .iter()
+            .map(|&factor| factor as u8)
+            .collect();
+        assert!(
+            self.ldt == LdtKind::Fri || self.fri_folding_schedule.is_empty(),
+            "FRI folding schedules are not supported by STIR"
+        );
+        self
+    }
+
+    /// Updates these options to use the specified low-degree test.
+    ///
+    /// By default, [LdtKind::Fri] is used. With [LdtKind::Stir], the FRI folding factor, the FRI
+    /// remainder degree, and the number of queries of these options are used as the parameters
+    /// of the STIR protocol (see [to_stir_options()](ProofOptions::to_stir_options)).
+    ///
+    /// # Panics
+    /// Panics if `ldt` is [LdtKind::Stir] and these options specify a FRI folding schedule or a
+    /// non-zero FRI layer grinding factor.
+    pub fn with_ldt(mut self, ldt: LdtKind) -> ProofOptions {
+        assert!(
+            ldt == LdtKind::Fri || self.fri_folding_schedule.is_empty(),
+            "FRI folding schedules are not supported by STIR"
+        );
+        assert!(
+            ldt == LdtKind::Fri || self.fri_layer_grinding_factor == 0,
+            "FRI layer grinding is not supported by STIR"
+        );
+        self.ldt = ldt;
+        self
+    }
+
+    /// Updates these options to draw query positions using the specified sampling method.
+    ///
+    /// By default, query positions are drawn via [QuerySampling::Modulo].
+    pub fn with_query_sampling(mut self, query_sampling: QuerySampling) -> ProofOptions {
+        self.query_sampling = query_sampling;
+        self
+    }
+
+    /// Updates these options to authenticate trace and constraint queries via Merkle multi-proofs
+    /// if `merkle_multi_proofs` is true, or via batch Merkle proofs otherwise.
+    ///
+    /// By default, batch Merkle proofs are used. A [MerkleMultiProof](crypto::MerkleMultiProof)
+    /// contains the same internal nodes as a batch proof, but it stores them as a flat list, and
+    /// thus, does not need to record the number of nodes contributed by each authentication path.
+    /// FRI layer queries are always authenticated via batch Merkle proofs.
+    pub fn with_merkle_multi_proofs(mut self, merkle_multi_proofs: bool) -> ProofOptions {
+        self.merkle_multi_proofs = merkle_multi_proofs;
+        self
+    }
+
+    /// Updates these options to draw the specified number of out-of-domain points during DEEP
+    /// composition.
+    ///
+    /// By default, a single out-of-domain point is drawn. With *k* points, trace polynomials are
+    /// opened at *z_j* and *z_j * g* for every point *z_j*, and the composition polynomial is
+    /// opened at every *z_j*; all openings are included into the DEEP composition polynomial.
+    ///
+    /// # Panics
+    /// Panics if `num_ood_points` is zero or greater than 16.
+    pub fn with_num_ood_points(mut self, num_ood_points: usize) -> ProofOptions {
+        assert!(
+            num_ood_points > 0,
+            "number of out-of-domain points must be greater than 0"
+        );
+        assert!(
+            num_ood_points <= MAX_NUM_OOD_POINTS,
+            "number of out-of-domain points cannot be greater than {MAX_NUM_OOD_POINTS}"
+        );
+        self.num_ood_points = num_ood_points as u8;
+        self
+    }
+
+    /// Updates these options to require a proof-of-work with the specified grinding factor for
+    /// every FRI layer.
+    ///
+    /// By default, no proof-of-work is required for FRI layers. The factor is passed to the FRI
+    /// protocol via [to_fri_options()](ProofOptions::to_fri_options), and every bit of it is
+    /// added to the soundness of the FRI commit phase in the proven security estimate; see
+    /// [FriOptions::with_layer_grinding()] for more info.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 32, or if these options use [LdtKind::Stir]
+    /// and `grinding_factor` is not zero.
+    pub fn with_fri_layer_grinding(mut self, grinding_factor: u32) -> ProofOptions {
+        assert!(
+            grinding_factor <= MAX_GRINDING_FACTOR,
+            "FRI layer grinding factor cannot be greater than {MAX_GRINDING_FACTOR}"
+        );
+        assert!(
+            self.ldt == LdtKind::Fri || grinding_factor == 0,
+            "FRI layer grinding is not supported by STIR"
+        );
+        self.fri_layer_grinding_factor = grinding_factor as u8;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -183,6 +408,20 @@ impl ProofOptions {
         self.grinding_factor as u32
     }
 
+    /// Returns the grinding factor of the proof-of-work the prover performs for every FRI layer
+    /// of a STARK proof; zero means that no proof-of-work is required for FRI layers.
+    pub fn fri_layer_grinding_factor(&self) -> u32 {
+        self.fri_layer_grinding_factor as u32
+    }
+
+    /// Returns the method used to draw query positions for a STARK proof.
+    ///
+    /// Both the prover and the verifier draw query positions via this method; the positions are
+    /// listed in the order in which they were drawn.
+    pub fn query_sampling(&self) -> QuerySampling {
+        self.query_sampling
+    }
+
     /// Specifies whether composition polynomial should be constructed in an extension field
     /// of STARK protocol.
     ///
@@ -192,6 +431,26 @@ impl ProofOptions {
         self.field_extension
     }
 
+    /// Returns the low-degree test used to prove that the DEEP composition polynomial is of low
+    /// degree.
+    pub fn ldt(&self) -> LdtKind {
+        self.ldt
+    }
+
+    /// Returns true if trace and constraint queries of a STARK proof are authenticated via Merkle
+    /// multi-proofs rather than via batch Merkle proofs.
+    pub fn merkle_multi_proofs(&self) -> bool {
+        self.merkle_multi_proofs
+    }
+
+    /// Returns the number of out-of-domain points drawn during DEEP composition.
+    ///
+    /// Each additional point increases proof size by the size of one out-of-domain frame of
+    /// trace and composition polynomial evaluations.
+    pub fn num_ood_points(&self) -> usize {
+        self.num_ood_points as usize
+    }
+
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
@@ -200,39 +459,180 @@ impl ProofOptions {
         B::GENERATOR
     }
 
+    /// Returns security level (in bits) of a proof generated with these options for a computation
+    /// with the specified trace length in the base field `B` using hash function `H`.
+    ///
+    /// The security level is estimated as specified by `mode`; conjectured and proven security
+    /// levels of a proof are also available via
+    /// [StarkProof::security_level()](crate::proof::StarkProof::security_level).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `trace_length` is not a power of two.
+    /// - `mode` is [SecurityEstimate::Proven] and the crate is compiled without `std` feature.
+    pub fn security_level<B: StarkField, H: Hasher>(
+        &self,
+        trace_length: usize,
+        mode: SecurityEstimate,
+    ) -> u32 {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must be a power of two"
+        );
+        estimate_security_level::<H>(self, B::MODULUS_BITS, trace_length, mode)
+    }
+
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let remainder_max_degree = self.fri_remainder_max_degree as usize;
-        FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+        let fri_options = if self.fri_folding_schedule.is_empty() {
+            FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+        } else {
+            let folding_schedule = self
+                .fri_folding_schedule
+                .iter()
+                .map(|&factor| factor as usize)
+                .chain(core::iter::once(folding_factor))
+                .collect::<Vec<_>>();
+            FriOptions::with_folding_schedule(
+                self.blowup_factor(),
+                &folding_schedule,
+                remainder_max_degree,
+            )
+        };
+        fri_options.with_layer_grinding(self.fri_layer_grinding_factor())
+    }
+
+    /// Returns options for STIR protocol instantiated with parameters from this proof options.
+    ///
+    /// The degree of a polynomial is reduced by the FRI folding factor in every STIR round, and
+    /// the number of queries of these options is used as the number of shift queries in the first
+    /// round (see [StirOptions::num_queries()]).
+    pub fn to_stir_options(&self) -> StirOptions {
+        StirOptions::new(
+            self.blowup_factor(),
+            self.fri_folding_factor as usize,
+            self.fri_remainder_max_degree as usize,
+            self.num_queries(),
+        )
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if these options use a FRI folding schedule, bit-slice query sampling, STIR,
+    /// more than one out-of-domain point, FRI layer grinding, or Merkle multi-proofs; such options
+    /// are serialized using extensions of the original encoding which older verifiers cannot
+    /// parse.
+    pub(crate) fn has_extensions(&self) -> bool {
+        !self.fri_folding_schedule.is_empty()
+            || self.query_sampling != QuerySampling::Modulo
+            || self.ldt != LdtKind::Fri
+            || self.num_ood_points > 1
+            || self.fri_layer_grinding_factor > 0
+            || self.merkle_multi_proofs
+    }
+
+    /// Returns the blowup factor with the Merkle multi-proofs flag encoded into the lowest bit;
+    /// if batch Merkle proofs are used, this is just the blowup factor.
+    pub(crate) fn blowup_byte(&self) -> u8 {
+        if self.merkle_multi_proofs {
+            self.blowup_factor | MERKLE_MULTI_PROOFS_FLAG
+        } else {
+            self.blowup_factor
+        }
+    }
+
+    /// Returns the grinding factor with the query sampling method encoded into the top bit and
+    /// the low-degree test encoded into the next bit; for the default sampling method and FRI,
+    /// this is just the grinding factor.
+    pub(crate) fn grinding_byte(&self) -> u8 {
+        let mut result = self.grinding_factor;
+        if self.query_sampling == QuerySampling::BitSlice {
+            result |= BIT_SLICE_SAMPLING_FLAG;
+        }
+        if self.ldt == LdtKind::Stir {
+            result |= STIR_LDT_FLAG;
+        }
+        result
+    }
+
+    /// Returns the field extension with the FRI layer grinding flag encoded into the 4th bit and
+    /// the number of out-of-domain points minus one encoded into the upper 4 bits; for a single
+    /// out-of-domain point and no FRI layer grinding, this is just the field extension.
+    pub(crate) fn field_extension_byte(&self) -> u8 {
+        let mut result =
+            self.field_extension as u8 | ((self.num_ood_points - 1) << NUM_OOD_POINTS_SHIFT);
+        if self.fri_layer_grinding_factor > 0 {
+            result |= FRI_LAYER_GRINDING_FLAG;
+        }
+        result
     }
 }
 
 impl<E: StarkField> ToElements<E> for ProofOptions {
     fn to_elements(&self) -> Vec<E> {
         // encode field extension and FRI parameters into a single field element
-        let mut buf = self.field_extension as u32;
+        let mut buf = self.field_extension_byte() as u32;
         buf = (buf << 8) | self.fri_folding_factor as u32;
         buf = (buf << 8) | self.fri_remainder_max_degree as u32;
 
-        vec![
+        let mut result = vec![
             E::from(buf),
-            E::from(self.grinding_factor),
-            E::from(self.blowup_factor),
+            E::from(self.grinding_byte()),
+            E::from(self.blowup_byte()),
             E::from(self.num_queries),
-        ]
+        ];
+
+        // folding schedule is bound only if it is specified so that elements for options with
+        // a uniform folding factor stay the same
+        if !self.fri_folding_schedule.is_empty() {
+            result.push(E::from(self.fri_folding_schedule.len() as u8));
+            result.extend(
+                self.fri_folding_schedule
+                    .iter()
+                    .map(|&factor| E::from(factor)),
+            );
+        }
+
+        // similarly, FRI layer grinding factor is bound only if layer grinding is enabled
+        if self.fri_layer_grinding_factor > 0 {
+            result.push(E::from(self.fri_layer_grinding_factor));
+        }
+        result
     }
 }
 
 impl Serializable for ProofOptions {
     /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// If a FRI folding schedule is specified, the folding factor is written as 0, and is
+    /// followed by the number of factors in the full schedule and the factors themselves; thus,
+    /// options with a uniform folding factor are serialized the same way as before schedules
+    /// were supported. Similarly, a non-default query sampling method is encoded by setting the
+    /// top bit of the grinding factor, STIR is encoded by setting the next bit of the grinding
+    /// factor, and the number of out-of-domain points is encoded in the upper 4 bits of the field
+    /// extension. If FRI layer grinding is enabled, the 4th bit of the field extension is set,
+    /// and the FRI layer grinding factor is written after the FRI remainder degree. Merkle
+    /// multi-proofs are encoded by setting the lowest bit of the blowup factor.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write_u8(self.num_queries);
-        target.write_u8(self.blowup_factor);
-        target.write_u8(self.grinding_factor);
-        target.write(self.field_extension);
-        target.write_u8(self.fri_folding_factor);
+        target.write_u8(self.blowup_byte());
+        target.write_u8(self.grinding_byte());
+        target.write_u8(self.field_extension_byte());
+        if self.fri_folding_schedule.is_empty() {
+            target.write_u8(self.fri_folding_factor);
+        } else {
+            target.write_u8(0);
+            target.write_u8(self.fri_folding_schedule.len() as u8 + 1);
+            target.write_bytes(&self.fri_folding_schedule);
+            target.write_u8(self.fri_folding_factor);
+        }
         target.write_u8(self.fri_remainder_max_degree);
+        if self.fri_layer_grinding_factor > 0 {
+            target.write_u8(self.fri_layer_grinding_factor);
+        }
     }
 }
 
@@ -242,14 +642,153 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-            source.read_u8()? as u32,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-        ))
+        let num_queries = source.read_u8()? as usize;
+        let blowup_byte = source.read_u8()?;
+        let blowup_factor = (blowup_byte & !MERKLE_MULTI_PROOFS_FLAG) as usize;
+        let merkle_multi_proofs = blowup_byte & MERKLE_MULTI_PROOFS_FLAG != 0;
+        let grinding_byte = source.read_u8()?;
+        let grinding_factor = (grinding_byte & !(BIT_SLICE_SAMPLING_FLAG | STIR_LDT_FLAG)) as u32;
+        let query_sampling = if grinding_byte & BIT_SLICE_SAMPLING_FLAG == 0 {
+            QuerySampling::Modulo
+        } else {
+            QuerySampling::BitSlice
+        };
+        let ldt = if grinding_byte & STIR_LDT_FLAG == 0 {
+            LdtKind::Fri
+        } else {
+            LdtKind::Stir
+        };
+        let extension_byte = source.read_u8()?;
+        let num_ood_points = (extension_byte >> NUM_OOD_POINTS_SHIFT) as usize + 1;
+        let field_extension = FieldExtension::read_from(&mut SliceReader::new(&[
+            extension_byte & FIELD_EXTENSION_MASK
+        ]))?;
+        let fri_folding_schedule = match source.read_u8()? {
+            0 => {
+                let num_factors = source.read_u8()? as usize;
+                if num_factors < 2 {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "FRI folding schedule must contain at least 2 factors, but was {num_factors}"
+                    )));
+                }
+                source
+                    .read_vec(num_factors)?
+                    .into_iter()
+                    .map(|factor| factor as usize)
+                    .collect::<Vec<_>>()
+            }
+            fri_folding_factor => vec![fri_folding_factor as usize],
+        };
+        let fri_remainder_max_degree = source.read_u8()? as usize;
+        let fri_layer_grinding_factor = if extension_byte & FRI_LAYER_GRINDING_FLAG == 0 {
+            0
+        } else {
+            source.read_u8()? as u32
+        };
+
+        // make sure the options are valid so that the constructor does not panic; upper bounds of
+        // the number of queries and of the FRI remainder degree are implied by the u8 encoding
+        let error = if num_queries == 0 {
+            Some("number of queries must be greater than 0".to_string())
+        } else if !blowup_factor.is_power_of_two()
+            || !(MIN_BLOWUP_FACTOR..=MAX_BLOWUP_FACTOR).contains(&blowup_factor)
+        {
+            Some(format!(
+                "blowup factor must be a power of 2 between {MIN_BLOWUP_FACTOR} and {MAX_BLOWUP_FACTOR}, but was {blowup_factor}"
+            ))
+        } else if grinding_factor > MAX_GRINDING_FACTOR {
+            Some(format!(
+                "grinding factor cannot be greater than {MAX_GRINDING_FACTOR}, but was {grinding_factor}"
+            ))
+        } else if let Some(fri_folding_factor) = fri_folding_schedule
+            .iter()
+            .find(|&&factor| !is_valid_folding_factor(factor))
+        {
+            Some(format!(
+                "FRI folding factor must be a power of 2 between {FRI_MIN_FOLDING_FACTOR} and {FRI_MAX_FOLDING_FACTOR}, but was {fri_folding_factor}"
+            ))
+        } else if !is_normalized_folding_schedule(&fri_folding_schedule) {
+            Some(format!(
+                "FRI folding schedule must contain at most {FRI_MAX_FOLDING_SCHEDULE_LEN} factors with the last two factors being different, but was {fri_folding_schedule:?}"
+            ))
+        } else if !(fri_remainder_max_degree + 1).is_power_of_two() {
+            Some(format!(
+                "FRI polynomial remainder degree must be one less than a power of two, but was {fri_remainder_max_degree}"
+            ))
+        } else if ldt == LdtKind::Stir && fri_folding_schedule.len() > 1 {
+            Some("FRI folding schedules are not supported by STIR".to_string())
+        } else if extension_byte & FRI_LAYER_GRINDING_FLAG != 0
+            && !(1..=MAX_GRINDING_FACTOR).contains(&fri_layer_grinding_factor)
+        {
+            Some(format!(
+                "FRI layer grinding factor must be between 1 and {MAX_GRINDING_FACTOR}, but was {fri_layer_grinding_factor}"
+            ))
+        } else if ldt == LdtKind::Stir && fri_layer_grinding_factor > 0 {
+            Some("FRI layer grinding is not supported by STIR".to_string())
+        } else {
+            None
+        };
+        if let Some(error) = error {
+            return Err(DeserializationError::InvalidValue(error));
+        }
+
+        let fri_folding_factor = fri_folding_schedule[fri_folding_schedule.len() - 1];
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            fri_remainder_max_degree,
+        )
+        .with_query_sampling(query_sampling)
+        .with_num_ood_points(num_ood_points)
+        .with_ldt(ldt)
+        .with_fri_layer_grinding(fri_layer_grinding_factor)
+        .with_merkle_multi_proofs(merkle_multi_proofs);
+        if fri_folding_schedule.len() == 1 {
+            Ok(options)
+        } else {
+            Ok(options.with_fri_folding_schedule(&fri_folding_schedule))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ProofOptions {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ProofOptions {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the specified FRI folding factor is supported.
+fn is_valid_folding_factor(folding_factor: usize) -> bool {
+    folding_factor.is_power_of_two()
+        && (FRI_MIN_FOLDING_FACTOR..=FRI_MAX_FOLDING_FACTOR).contains(&folding_factor)
+}
+
+/// Returns true if the specified folding schedule (which includes the folding factor for the
+/// layers following the schedule) is in the form produced by [ProofOptions] serialization; that
+/// is, it is either a single folding factor, or a schedule of at most 32 factors in which the
+/// last two factors differ.
+fn is_normalized_folding_schedule(folding_schedule: &[usize]) -> bool {
+    match folding_schedule.len() {
+        0 => false,
+        1 => true,
+        len => {
+            len <= FRI_MAX_FOLDING_SCHEDULE_LEN
+                && folding_schedule[len - 2] != folding_schedule[len - 1]
+        }
     }
 }
 
@@ -268,6 +807,7 @@ impl FieldExtension {
             Self::None => 1,
             Self::Quadratic => 2,
             Self::Cubic => 3,
+            Self::Quartic => 4,
         }
     }
 }
@@ -286,6 +826,7 @@ impl Deserializable for FieldExtension {
             1 => Ok(FieldExtension::None),
             2 => Ok(FieldExtension::Quadratic),
             3 => Ok(FieldExtension::Cubic),
+            4 => Ok(FieldExtension::Quartic),
             value => Err(DeserializationError::InvalidValue(format!(
                 "value {value} cannot be deserialized as FieldExtension enum"
             ))),
@@ -298,8 +839,10 @@ impl Deserializable for FieldExtension {
 
 #[cfg(test)]
 mod tests {
-    use super::{FieldExtension, ProofOptions, ToElements};
+    use super::{Deserializable, FieldExtension, LdtKind, ProofOptions, Serializable, ToElements};
+    use crypto::QuerySampling;
     use math::fields::f64::BaseElement;
+    use utils::SliceReader;
 
     #[test]
     fn proof_options_to_elements() {
@@ -318,7 +861,7 @@ mod tests {
         ]);
         let expected = vec![
             BaseElement::from(ext_fri),
-            BaseElement::from(grinding_factor as u32),
+            BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
         ];
@@ -333,4 +876,252 @@ mod tests {
         );
         assert_eq!(expected, options.to_elements());
     }
+
+    #[test]
+    fn proof_options_folding_schedule() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 4, 7);
+        let scheduled = options.clone().with_fri_folding_schedule(&[8, 4]);
+        assert_ne!(options, scheduled);
+        assert_eq!(
+            vec![8, 4, 4],
+            scheduled.to_fri_options().layer_folding_factors(1 << 12)
+        );
+
+        // trailing factors equal to the last factor do not change the schedule
+        assert_eq!(
+            scheduled,
+            options.clone().with_fri_folding_schedule(&[8, 4, 4])
+        );
+        assert_eq!(options, options.clone().with_fri_folding_schedule(&[4, 4]));
+
+        // the schedule is bound to the elements of the options
+        let elements: Vec<BaseElement> = scheduled.to_elements();
+        let mut expected: Vec<BaseElement> = options.to_elements();
+        expected.extend([BaseElement::from(1_u8), BaseElement::from(8_u8)]);
+        assert_eq!(expected, elements);
+    }
+
+    #[test]
+    fn proof_options_serialization() {
+        // options with a uniform folding factor are serialized as before
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        let bytes = options.to_bytes();
+        assert_eq!(vec![30, 8, 20, 2, 8, 127], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+
+        // folding schedule is serialized after a zero folding factor
+        let options = options.with_fri_folding_schedule(&[16, 8, 4]);
+        let bytes = options.to_bytes();
+        assert_eq!(vec![30, 8, 20, 2, 0, 3, 16, 8, 4, 127], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+
+        // schedules which are not normalized or contain invalid factors are rejected
+        for bytes in [
+            vec![30, 8, 20, 2, 0, 0, 127],
+            vec![30, 8, 20, 2, 0, 1, 4, 127],
+            vec![30, 8, 20, 2, 0, 2, 4, 4, 127],
+            vec![30, 8, 20, 2, 0, 2, 8, 3, 127],
+        ] {
+            let mut reader = SliceReader::new(&bytes);
+            assert!(ProofOptions::read_from(&mut reader).is_err());
+        }
+    }
+
+    #[test]
+    fn proof_options_query_sampling() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(QuerySampling::Modulo, options.query_sampling());
+
+        // bit-slice sampling is encoded in the top bit of the grinding factor
+        let bit_slice = options.clone().with_query_sampling(QuerySampling::BitSlice);
+        assert_ne!(options, bit_slice);
+        assert_eq!(20, bit_slice.grinding_factor());
+        let bytes = bit_slice.to_bytes();
+        assert_eq!(vec![30, 8, 0x80 | 20, 2, 8, 127], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(bit_slice, ProofOptions::read_from(&mut reader).unwrap());
+
+        // the sampling method is bound to the elements of the options
+        let elements: Vec<BaseElement> = bit_slice.to_elements();
+        let mut expected: Vec<BaseElement> = options.to_elements();
+        expected[1] = BaseElement::from(0x80_u8 | 20);
+        assert_eq!(expected, elements);
+
+        // grinding factors above the maximum are still rejected
+        let mut reader = SliceReader::new(&[30, 8, 0x80 | 33, 2, 8, 127]);
+        assert!(ProofOptions::read_from(&mut reader).is_err());
+    }
+
+    #[test]
+    fn proof_options_num_ood_points() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(1, options.num_ood_points());
+
+        // the number of points minus one is encoded in the upper 4 bits of the field extension
+        let multi_point = options.clone().with_num_ood_points(3);
+        assert_ne!(options, multi_point);
+        assert_eq!(FieldExtension::Quadratic, multi_point.field_extension());
+        let bytes = multi_point.to_bytes();
+        assert_eq!(vec![30, 8, 20, 0x20 | 2, 8, 127], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(multi_point, ProofOptions::read_from(&mut reader).unwrap());
+
+        // the number of points is bound to the elements of the options
+        let elements: Vec<BaseElement> = multi_point.to_elements();
+        let mut expected: Vec<BaseElement> = options.to_elements();
+        expected[0] += BaseElement::from(0x20_u32 << 16);
+        assert_eq!(expected, elements);
+
+        // invalid field extensions are still rejected
+        let mut reader = SliceReader::new(&[30, 8, 20, 0x20 | 5, 8, 127]);
+        assert!(ProofOptions::read_from(&mut reader).is_err());
+    }
+
+    #[test]
+    fn proof_options_ldt() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(LdtKind::Fri, options.ldt());
+
+        // STIR is encoded in the second highest bit of the grinding factor
+        let stir = options.clone().with_ldt(LdtKind::Stir);
+        assert_ne!(options, stir);
+        assert_eq!(20, stir.grinding_factor());
+        let bytes = stir.to_bytes();
+        assert_eq!(vec![30, 8, 0x40 | 20, 2, 8, 127], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(stir, ProofOptions::read_from(&mut reader).unwrap());
+
+        // the flag can be combined with bit-slice query sampling
+        let bit_slice = stir.clone().with_query_sampling(QuerySampling::BitSlice);
+        let bytes = bit_slice.to_bytes();
+        assert_eq!(vec![30, 8, 0xc0 | 20, 2, 8, 127], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(bit_slice, ProofOptions::read_from(&mut reader).unwrap());
+
+        // the low-degree test is bound to the elements of the options
+        let elements: Vec<BaseElement> = stir.to_elements();
+        let mut expected: Vec<BaseElement> = options.to_elements();
+        expected[1] = BaseElement::from(0x40_u8 | 20);
+        assert_eq!(expected, elements);
+
+        // STIR parameters are taken from the FRI parameters of the options
+        let stir_options = stir.to_stir_options();
+        assert_eq!(8, stir_options.blowup_factor());
+        assert_eq!(8, stir_options.folding_factor());
+        assert_eq!(127, stir_options.remainder_max_degree());
+        assert_eq!(30, stir_options.num_queries());
+
+        // folding schedules cannot be used with STIR
+        let mut reader = SliceReader::new(&[30, 8, 0x40 | 20, 2, 0, 2, 16, 8, 127]);
+        assert!(ProofOptions::read_from(&mut reader).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "FRI folding schedules are not supported by STIR")]
+    fn proof_options_stir_folding_schedule() {
+        ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_fri_folding_schedule(&[16, 8])
+            .with_ldt(LdtKind::Stir);
+    }
+
+    #[test]
+    fn proof_options_merkle_multi_proofs() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert!(!options.merkle_multi_proofs());
+
+        // Merkle multi-proofs are encoded in the lowest bit of the blowup factor
+        let multi_proofs = options.clone().with_merkle_multi_proofs(true);
+        assert_ne!(options, multi_proofs);
+        assert_eq!(8, multi_proofs.blowup_factor());
+        let bytes = multi_proofs.to_bytes();
+        assert_eq!(vec![30, 8 | 1, 20, 2, 8, 127], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(multi_proofs, ProofOptions::read_from(&mut reader).unwrap());
+
+        // the flag is bound to the elements of the options
+        let elements: Vec<BaseElement> = multi_proofs.to_elements();
+        let mut expected: Vec<BaseElement> = options.to_elements();
+        expected[2] = BaseElement::from(8_u8 | 1);
+        assert_eq!(expected, elements);
+
+        // a blowup factor of 1 is still rejected
+        let mut reader = SliceReader::new(&[30, 1, 20, 2, 8, 127]);
+        assert!(ProofOptions::read_from(&mut reader).is_err());
+    }
+
+    #[test]
+    fn proof_options_fri_layer_grinding() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(0, options.fri_layer_grinding_factor());
+        assert_eq!(0, options.to_fri_options().layer_grinding_factor());
+
+        // layer grinding is flagged in the 4th bit of the field extension, and the factor is
+        // written after the FRI remainder degree
+        let layer_grinding = options
+            .clone()
+            .with_fri_layer_grinding(12)
+            .with_num_ood_points(2);
+        assert_eq!(FieldExtension::Quadratic, layer_grinding.field_extension());
+        assert_eq!(12, layer_grinding.to_fri_options().layer_grinding_factor());
+        let bytes = layer_grinding.to_bytes();
+        assert_eq!(vec![30, 8, 20, 0x10 | 0x08 | 2, 8, 127, 12], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(
+            layer_grinding,
+            ProofOptions::read_from(&mut reader).unwrap()
+        );
+
+        // the factor is also written after a folding schedule
+        let scheduled = layer_grinding.clone().with_fri_folding_schedule(&[16, 8]);
+        let bytes = scheduled.to_bytes();
+        assert_eq!(vec![30, 8, 20, 0x18 | 2, 0, 2, 16, 8, 127, 12], bytes);
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(scheduled, ProofOptions::read_from(&mut reader).unwrap());
+
+        // the flag and the factor are bound to the elements of the options
+        let elements: Vec<BaseElement> = options.clone().with_fri_layer_grinding(12).to_elements();
+        let mut expected: Vec<BaseElement> = options.to_elements();
+        expected[0] += BaseElement::from(0x08_u32 << 16);
+        expected.push(BaseElement::from(12_u8));
+        assert_eq!(expected, elements);
+
+        // missing, zero, and too large factors, as well as layer grinding with STIR, are rejected
+        for bytes in [
+            vec![30, 8, 20, 0x08 | 2, 8, 127],
+            vec![30, 8, 20, 0x08 | 2, 8, 127, 0],
+            vec![30, 8, 20, 0x08 | 2, 8, 127, 33],
+            vec![30, 8, 0x40 | 20, 0x08 | 2, 8, 127, 12],
+        ] {
+            let mut reader = SliceReader::new(&bytes);
+            assert!(ProofOptions::read_from(&mut reader).is_err());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "FRI layer grinding is not supported by STIR")]
+    fn proof_options_stir_fri_layer_grinding() {
+        ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_fri_layer_grinding(8)
+            .with_ldt(LdtKind::Stir);
+    }
+
+    #[test]
+    fn field_extension_serialization() {
+        for extension in [
+            FieldExtension::None,
+            FieldExtension::Quadratic,
+            FieldExtension::Cubic,
+            FieldExtension::Quartic,
+        ] {
+            let bytes = extension.to_bytes();
+            assert_eq!(vec![extension.degree() as u8], bytes);
+            let mut reader = SliceReader::new(&bytes);
+            assert_eq!(extension, FieldExtension::read_from(&mut reader).unwrap());
+        }
+
+        let mut reader = SliceReader::new(&[5]);
+        assert!(FieldExtension::read_from(&mut reader).is_err());
+    }
 }
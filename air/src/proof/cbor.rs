@@ -0,0 +1,606 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! A minimal [CBOR](https://www.rfc-editor.org/rfc/rfc8949) codec used to encode STARK proofs.
+//!
+//! Only the subset of CBOR needed to describe a proof is supported: unsigned integers (major
+//! type 0), byte strings (major type 2), text strings (major type 3), and definite-length arrays
+//! (major type 4). All values
+//! are encoded using the shortest possible form (i.e., according to the core deterministic
+//! encoding requirements of RFC 8949), and the decoder rejects anything else. This means every
+//! proof has exactly one valid CBOR encoding.
+//!
+//! The structure of the encoded proof is described by the CDDL schema in
+//! [CDDL_SCHEMA](super::CDDL_SCHEMA).
+
+use crate::{options::FRI_LAYER_GRINDING_FLAG, ProofOptions, TraceLayout};
+use fri::{stir::StirProof, FriProof, FriProofLayer};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, SliceReader,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+const MAJOR_TYPE_UINT: u8 = 0;
+const MAJOR_TYPE_BYTES: u8 = 2;
+const MAJOR_TYPE_TEXT: u8 = 3;
+const MAJOR_TYPE_ARRAY: u8 = 4;
+
+/// Additional info values 0..=23 encode the argument directly in the initial byte.
+const MAX_IMMEDIATE_ARG: u64 = 23;
+const ARG_ONE_BYTE: u8 = 24;
+const ARG_TWO_BYTES: u8 = 25;
+const ARG_FOUR_BYTES: u8 = 26;
+const ARG_EIGHT_BYTES: u8 = 27;
+
+// ENCODING
+// ================================================================================================
+
+/// Writes an unsigned integer into the `target`.
+pub(super) fn write_uint<W: ByteWriter>(target: &mut W, value: u64) {
+    write_header(target, MAJOR_TYPE_UINT, value);
+}
+
+/// Writes a byte string into the `target`.
+pub(super) fn write_bytes<W: ByteWriter>(target: &mut W, bytes: &[u8]) {
+    write_header(target, MAJOR_TYPE_BYTES, bytes.len() as u64);
+    target.write_bytes(bytes);
+}
+
+/// Writes a UTF-8 text string into the `target`.
+pub(super) fn write_text<W: ByteWriter>(target: &mut W, text: &str) {
+    write_header(target, MAJOR_TYPE_TEXT, text.len() as u64);
+    target.write_bytes(text.as_bytes());
+}
+
+/// Writes a header for an array of `len` items into the `target`; the items must be written
+/// into the target immediately after the header.
+pub(super) fn write_array_header<W: ByteWriter>(target: &mut W, len: usize) {
+    write_header(target, MAJOR_TYPE_ARRAY, len as u64);
+}
+
+/// Writes the initial byte (and the argument bytes, if needed) of a data item.
+fn write_header<W: ByteWriter>(target: &mut W, major_type: u8, arg: u64) {
+    let major_type = major_type << 5;
+    if arg <= MAX_IMMEDIATE_ARG {
+        target.write_u8(major_type | arg as u8);
+    } else if arg <= u8::MAX as u64 {
+        target.write_u8(major_type | ARG_ONE_BYTE);
+        target.write_u8(arg as u8);
+    } else if arg <= u16::MAX as u64 {
+        target.write_u8(major_type | ARG_TWO_BYTES);
+        target.write_bytes(&(arg as u16).to_be_bytes());
+    } else if arg <= u32::MAX as u64 {
+        target.write_u8(major_type | ARG_FOUR_BYTES);
+        target.write_bytes(&(arg as u32).to_be_bytes());
+    } else {
+        target.write_u8(major_type | ARG_EIGHT_BYTES);
+        target.write_bytes(&arg.to_be_bytes());
+    }
+}
+
+// DECODING
+// ================================================================================================
+
+/// Reads an unsigned integer from the `source`.
+///
+/// # Errors
+/// Returns an error if the next data item in the source is not an unsigned integer.
+pub(super) fn read_uint<R: ByteReader>(source: &mut R) -> Result<u64, DeserializationError> {
+    read_header(source, MAJOR_TYPE_UINT)
+}
+
+/// Reads an unsigned integer from the `source` and makes sure it does not exceed `max_value`.
+///
+/// # Errors
+/// Returns an error if the next data item in the source is not an unsigned integer, or if the
+/// integer is greater than `max_value`.
+pub(super) fn read_bounded_uint<R: ByteReader>(
+    source: &mut R,
+    max_value: u64,
+    name: &str,
+) -> Result<u64, DeserializationError> {
+    let value = read_uint(source)?;
+    if value > max_value {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{name} cannot be greater than {max_value}, but was {value}"
+        )));
+    }
+    Ok(value)
+}
+
+/// Reads a byte string from the `source`.
+///
+/// # Errors
+/// Returns an error if the next data item in the source is not a byte string, or if the source
+/// does not contain enough bytes to read the entire string.
+pub(super) fn read_bytes<R: ByteReader>(source: &mut R) -> Result<Vec<u8>, DeserializationError> {
+    let len = read_len(source, MAJOR_TYPE_BYTES)?;
+    source.check_eor(len)?;
+    source.read_vec(len)
+}
+
+/// Reads a UTF-8 text string from the `source`.
+///
+/// # Errors
+/// Returns an error if the next data item in the source is not a text string, if the source does
+/// not contain enough bytes to read the entire string, or if the string is not valid UTF-8.
+pub(super) fn read_text<R: ByteReader>(source: &mut R) -> Result<String, DeserializationError> {
+    let len = read_len(source, MAJOR_TYPE_TEXT)?;
+    source.check_eor(len)?;
+    String::from_utf8(source.read_vec(len)?)
+        .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+}
+
+/// Reads a header of an array from the `source` and returns the number of items in the array.
+///
+/// # Errors
+/// Returns an error if the next data item in the source is not an array.
+pub(super) fn read_array_header<R: ByteReader>(
+    source: &mut R,
+) -> Result<usize, DeserializationError> {
+    read_len(source, MAJOR_TYPE_ARRAY)
+}
+
+/// Reads a header of an array from the `source` and makes sure that the array contains exactly
+/// `expected_len` items.
+///
+/// # Errors
+/// Returns an error if the next data item in the source is not an array of `expected_len` items.
+pub(super) fn read_struct_header<R: ByteReader>(
+    source: &mut R,
+    expected_len: usize,
+    name: &str,
+) -> Result<(), DeserializationError> {
+    let len = read_array_header(source)?;
+    if len != expected_len {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{name} must be encoded as an array of {expected_len} items, but was {len} items"
+        )));
+    }
+    Ok(())
+}
+
+/// Reads the argument of a header for a data item which defines its length (i.e., a byte string
+/// or an array).
+fn read_len<R: ByteReader>(source: &mut R, major_type: u8) -> Result<usize, DeserializationError> {
+    let len = read_header(source, major_type)?;
+    usize::try_from(len).map_err(|_| {
+        DeserializationError::InvalidValue(format!("length {len} does not fit into usize"))
+    })
+}
+
+/// Reads the initial byte of a data item and returns its argument; the argument must be encoded
+/// in the shortest possible form.
+fn read_header<R: ByteReader>(source: &mut R, major_type: u8) -> Result<u64, DeserializationError> {
+    let initial_byte = source.read_u8()?;
+    if initial_byte >> 5 != major_type {
+        return Err(DeserializationError::InvalidValue(format!(
+            "expected CBOR major type {}, but was {}",
+            major_type,
+            initial_byte >> 5
+        )));
+    }
+
+    let (arg, min_arg) = match initial_byte & 0x1f {
+        info if info as u64 <= MAX_IMMEDIATE_ARG => return Ok(info as u64),
+        ARG_ONE_BYTE => (source.read_u8()? as u64, MAX_IMMEDIATE_ARG + 1),
+        ARG_TWO_BYTES => {
+            let arg = u16::from_be_bytes(source.read_array()?) as u64;
+            (arg, u8::MAX as u64 + 1)
+        }
+        ARG_FOUR_BYTES => {
+            let arg = u32::from_be_bytes(source.read_array()?) as u64;
+            (arg, u16::MAX as u64 + 1)
+        }
+        ARG_EIGHT_BYTES => (
+            u64::from_be_bytes(source.read_array()?),
+            u32::MAX as u64 + 1,
+        ),
+        info => {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported CBOR additional info value {info}"
+            )))
+        }
+    };
+
+    if arg < min_arg {
+        return Err(DeserializationError::InvalidValue(
+            "CBOR argument is not encoded in the shortest possible form".to_string(),
+        ));
+    }
+    Ok(arg)
+}
+
+// TRACE LAYOUT
+// ================================================================================================
+
+/// Writes the specified trace layout into the `target` as
+/// `[main_width, [* aux_width], [* aux_rand_elements]]`; only non-empty auxiliary segments are
+/// included.
+pub(super) fn write_trace_layout<W: ByteWriter>(target: &mut W, layout: &TraceLayout) {
+    let num_aux_segments = layout.num_aux_segments();
+    write_array_header(target, 3);
+    write_uint(target, layout.main_trace_width() as u64);
+    write_array_header(target, num_aux_segments);
+    for i in 0..num_aux_segments {
+        write_uint(target, layout.get_aux_segment_width(i) as u64);
+    }
+    write_array_header(target, num_aux_segments);
+    for i in 0..num_aux_segments {
+        write_uint(target, layout.get_aux_segment_rand_elements(i) as u64);
+    }
+}
+
+/// Reads a trace layout from the `source`.
+///
+/// # Errors
+/// Returns an error if a valid trace layout could not be read from the `source`.
+pub(super) fn read_trace_layout<R: ByteReader>(
+    source: &mut R,
+) -> Result<TraceLayout, DeserializationError> {
+    read_struct_header(source, 3, "trace layout")?;
+    let main_width = read_bounded_uint(source, u8::MAX as u64, "main trace width")? as u8;
+
+    // the binary encoding of the layout has a fixed number of auxiliary segments; we re-use its
+    // decoder so that both encodings are subject to exactly the same validation rules
+    let mut aux_widths = [0u8; crate::air::NUM_AUX_SEGMENTS];
+    let num_aux_segments = read_aux_segment_params(source, &mut aux_widths, "aux segment width")?;
+    let mut aux_rands = [0u8; crate::air::NUM_AUX_SEGMENTS];
+    let num_aux_rands =
+        read_aux_segment_params(source, &mut aux_rands, "aux segment random elements")?;
+    if num_aux_segments != num_aux_rands || aux_widths[..num_aux_segments].contains(&0) {
+        return Err(DeserializationError::InvalidValue(
+            "auxiliary segment widths and random element counts are inconsistent".to_string(),
+        ));
+    }
+
+    let mut bytes = Vec::with_capacity(1 + 2 * crate::air::NUM_AUX_SEGMENTS);
+    bytes.push(main_width);
+    bytes.extend_from_slice(&aux_widths);
+    bytes.extend_from_slice(&aux_rands);
+    TraceLayout::read_from(&mut SliceReader::new(&bytes))
+}
+
+/// Reads an array of auxiliary segment parameters into `params` and returns the number of
+/// parameters read.
+fn read_aux_segment_params<R: ByteReader>(
+    source: &mut R,
+    params: &mut [u8],
+    name: &str,
+) -> Result<usize, DeserializationError> {
+    let num_params = read_array_header(source)?;
+    if num_params > params.len() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of auxiliary segments cannot be greater than {}, but was {}",
+            params.len(),
+            num_params
+        )));
+    }
+    for param in params.iter_mut().take(num_params) {
+        *param = read_bounded_uint(source, u8::MAX as u64, name)? as u8;
+    }
+    Ok(num_params)
+}
+
+// PROOF OPTIONS
+// ================================================================================================
+
+/// Writes the specified proof options into the `target` as an array of 6 integers.
+///
+/// If the options specify a FRI folding schedule, the folding factors of the first FRI layers
+/// (i.e., the layers preceding the layers folded by the FRI folding factor) are written as a
+/// 7th item of the array. If FRI layer grinding is enabled, the 7th item is written even if it
+/// is empty, and the FRI layer grinding factor is written as an 8th item. Same as in the binary
+/// encoding, a non-default query sampling method is encoded by setting the top bit of the
+/// grinding factor, the number of out-of-domain points and the FRI layer grinding flag are
+/// encoded in the upper bits of the field extension, and the Merkle multi-proof flag is encoded
+/// in the lowest bit of the blowup factor.
+pub(super) fn write_options<W: ByteWriter>(target: &mut W, options: &ProofOptions) {
+    let fri_options = options.to_fri_options();
+    let folding_schedule = fri_options.folding_schedule();
+    let layer_grinding_factor = options.fri_layer_grinding_factor();
+    let num_items = if layer_grinding_factor > 0 {
+        8
+    } else if folding_schedule.is_empty() {
+        6
+    } else {
+        7
+    };
+    write_array_header(target, num_items);
+    write_uint(target, options.num_queries() as u64);
+    write_uint(target, options.blowup_byte() as u64);
+    write_uint(target, options.grinding_byte() as u64);
+    write_uint(target, options.field_extension_byte() as u64);
+    write_uint(target, fri_options.folding_factor() as u64);
+    write_uint(target, fri_options.remainder_max_degree() as u64);
+    if num_items > 6 {
+        write_array_header(target, folding_schedule.len());
+        for &folding_factor in folding_schedule {
+            write_uint(target, folding_factor as u64);
+        }
+    }
+    if num_items > 7 {
+        write_uint(target, layer_grinding_factor as u64);
+    }
+}
+
+/// Reads proof options from the `source`.
+///
+/// # Errors
+/// Returns an error if valid proof options could not be read from the `source`.
+pub(super) fn read_options<R: ByteReader>(
+    source: &mut R,
+) -> Result<ProofOptions, DeserializationError> {
+    let num_items = read_array_header(source)?;
+    if !(6..=8).contains(&num_items) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "proof options must be encoded as an array of 6 to 8 items, but was {num_items} items"
+        )));
+    }
+    let num_queries = read_bounded_uint(source, u8::MAX as u64, "number of queries")?;
+    let blowup_factor = read_bounded_uint(source, u8::MAX as u64, "blowup factor")?;
+    let grinding_factor = read_bounded_uint(source, u8::MAX as u64, "grinding factor")?;
+    let field_extension = read_bounded_uint(source, u8::MAX as u64, "field extension")?;
+    let folding_factor = read_bounded_uint(source, u8::MAX as u64, "FRI folding factor")?;
+    let remainder_max_degree =
+        read_bounded_uint(source, u8::MAX as u64, "FRI remainder max degree")?;
+
+    // all values fit into a byte, and thus, validation of the options is delegated to the binary
+    // deserializer (which makes sure the options are valid); in the binary encoding, a folding
+    // schedule is written in place of the folding factor: a zero followed by the number of
+    // factors and the factors themselves, and the FRI layer grinding factor is written last
+    let mut bytes = vec![
+        num_queries as u8,
+        blowup_factor as u8,
+        grinding_factor as u8,
+        field_extension as u8,
+    ];
+    if num_items > 6 {
+        // an empty schedule is allowed only as a placeholder preceding the FRI layer grinding
+        // factor
+        let num_factors = read_array_header(source)?;
+        if (num_factors == 0 && num_items == 7) || num_factors >= u8::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI folding schedule must contain between 1 and {} factors, but was {}",
+                u8::MAX - 1,
+                num_factors
+            )));
+        }
+        if num_factors > 0 {
+            bytes.push(0);
+            bytes.push(num_factors as u8 + 1);
+        }
+        for _ in 0..num_factors {
+            bytes.push(read_bounded_uint(source, u8::MAX as u64, "FRI folding factor")? as u8);
+        }
+    }
+    bytes.push(folding_factor as u8);
+    bytes.push(remainder_max_degree as u8);
+    if num_items > 7 {
+        bytes.push(read_bounded_uint(source, u8::MAX as u64, "FRI layer grinding factor")? as u8);
+    }
+
+    // the FRI layer grinding flag must be consistent with the number of items; otherwise, the
+    // factor would be either missing or left unread in the binary encoding
+    if (num_items > 7) != (field_extension as u8 & FRI_LAYER_GRINDING_FLAG != 0) {
+        return Err(DeserializationError::InvalidValue(
+            "FRI layer grinding flag is inconsistent with the number of proof options".to_string(),
+        ));
+    }
+    ProofOptions::read_from(&mut SliceReader::new(&bytes))
+}
+
+// FRI PROOF
+// ================================================================================================
+
+/// Writes the specified FRI proof into the `target` as
+/// `[[* [values, paths]], remainder, num_partitions, ? [+ layer_nonce]]`.
+///
+/// FRI layer proof-of-work nonces are written only if the proof contains any.
+pub(super) fn write_fri_proof<W: ByteWriter>(target: &mut W, proof: &FriProof) {
+    let layer_nonces = proof.layer_nonces();
+    write_array_header(target, if layer_nonces.is_empty() { 3 } else { 4 });
+    write_array_header(target, proof.num_layers());
+    for layer in proof.layers() {
+        write_proof_layer(target, layer);
+    }
+    write_bytes(target, proof.remainder_bytes());
+    write_uint(target, proof.num_partitions() as u64);
+    if !layer_nonces.is_empty() {
+        write_array_header(target, layer_nonces.len());
+        for &nonce in layer_nonces {
+            write_uint(target, nonce);
+        }
+    }
+}
+
+/// Reads a FRI proof from the `source`.
+///
+/// # Errors
+/// Returns an error if a valid FRI proof could not be read from the `source`.
+pub(super) fn read_fri_proof<R: ByteReader>(
+    source: &mut R,
+) -> Result<FriProof, DeserializationError> {
+    let num_items = read_array_header(source)?;
+    if num_items != 3 && num_items != 4 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "FRI proof must be encoded as an array of 3 or 4 items, but was {num_items} items"
+        )));
+    }
+
+    let num_layers = read_array_header(source)?;
+    if num_layers > u8::MAX as usize {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of FRI layers cannot be greater than {}, but was {}",
+            u8::MAX,
+            num_layers
+        )));
+    }
+    let mut layers = Vec::with_capacity(num_layers);
+    for _ in 0..num_layers {
+        layers.push(read_proof_layer(source, "FRI proof layer")?);
+    }
+
+    let remainder = read_bytes(source)?;
+    if remainder.len() > u16::MAX as usize {
+        return Err(DeserializationError::InvalidValue(format!(
+            "FRI remainder cannot be longer than {} bytes, but was {} bytes",
+            u16::MAX,
+            remainder.len()
+        )));
+    }
+    let num_partitions = read_uint(source)?;
+    if num_partitions == 0
+        || !num_partitions.is_power_of_two()
+        || num_partitions.trailing_zeros() >= usize::BITS
+    {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of FRI partitions must be a power of two smaller than 2^{}, but was {num_partitions}",
+            usize::BITS
+        )));
+    }
+
+    let mut layer_nonces = Vec::new();
+    if num_items == 4 {
+        let num_nonces = read_array_header(source)?;
+        if num_nonces == 0 || num_nonces > u8::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of FRI layer nonces must be between 1 and {}, but was {num_nonces}",
+                u8::MAX
+            )));
+        }
+        for _ in 0..num_nonces {
+            layer_nonces.push(read_uint(source)?);
+        }
+    }
+
+    Ok(
+        FriProof::from_raw_parts(layers, remainder, num_partitions as usize)
+            .with_layer_nonces(layer_nonces),
+    )
+}
+
+// STIR PROOF
+// ================================================================================================
+
+/// Writes the specified STIR proof into the `target` as
+/// `[[+ [values, paths]], commitments, ood_answers, final_poly, ? [values, paths]]`.
+///
+/// Queries of the first round evaluations are written only if the proof contains them.
+pub(super) fn write_stir_proof<W: ByteWriter>(target: &mut W, proof: &StirProof) {
+    let input_queries = proof.input_queries();
+    write_array_header(target, if input_queries.is_none() { 4 } else { 5 });
+    write_array_header(target, proof.num_rounds());
+    for round in proof.rounds() {
+        write_proof_layer(target, round);
+    }
+    write_bytes(target, proof.commitments_bytes());
+    write_bytes(target, proof.ood_answers_bytes());
+    write_bytes(target, proof.final_poly_bytes());
+    if let Some(queries) = input_queries {
+        write_proof_layer(target, queries);
+    }
+}
+
+/// Reads a STIR proof from the `source`.
+///
+/// # Errors
+/// Returns an error if a valid STIR proof could not be read from the `source`.
+pub(super) fn read_stir_proof<R: ByteReader>(
+    source: &mut R,
+) -> Result<StirProof, DeserializationError> {
+    let num_items = read_array_header(source)?;
+    if num_items != 4 && num_items != 5 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "STIR proof must be encoded as an array of 4 or 5 items, but was {num_items} items"
+        )));
+    }
+
+    let num_rounds = read_array_header(source)?;
+    if num_rounds == 0 || num_rounds > u8::MAX as usize {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of STIR rounds must be between 1 and {}, but was {}",
+            u8::MAX,
+            num_rounds
+        )));
+    }
+    let mut rounds = Vec::with_capacity(num_rounds);
+    for _ in 0..num_rounds {
+        rounds.push(read_proof_layer(source, "STIR round")?);
+    }
+
+    let commitments = read_short_bytes(source, "STIR commitments")?;
+    let ood_answers = read_short_bytes(source, "STIR out-of-domain answers")?;
+    let final_poly = read_short_bytes(source, "STIR final polynomial")?;
+    let input_queries = if num_items == 5 {
+        Some(read_proof_layer(source, "STIR input queries")?)
+    } else {
+        None
+    };
+
+    Ok(StirProof::from_raw_parts(
+        commitments,
+        ood_answers,
+        rounds,
+        final_poly,
+        input_queries,
+    ))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the specified proof layer into the `target` as `[values, paths]`.
+fn write_proof_layer<W: ByteWriter>(target: &mut W, layer: &FriProofLayer) {
+    write_array_header(target, 2);
+    write_bytes(target, layer.values_bytes());
+    write_bytes(target, layer.paths_bytes());
+}
+
+/// Reads a proof layer from the `source`; `name` is used to describe the layer in error
+/// messages.
+fn read_proof_layer<R: ByteReader>(
+    source: &mut R,
+    name: &str,
+) -> Result<FriProofLayer, DeserializationError> {
+    read_struct_header(source, 2, name)?;
+    let values = read_bytes(source)?;
+    if values.is_empty() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "a {name} must contain at least one queried evaluation"
+        )));
+    }
+    let paths = read_bytes(source)?;
+    if values.len() > FriProofLayer::MAX_NUM_BYTES || paths.len() > FriProofLayer::MAX_NUM_BYTES {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{} parts cannot be longer than {} bytes",
+            name,
+            FriProofLayer::MAX_NUM_BYTES
+        )));
+    }
+    Ok(FriProofLayer::from_raw_parts(values, paths))
+}
+
+/// Reads a byte string which must fit into the u16 length prefix of the binary encoding from the
+/// `source`; `name` is used to describe the byte string in error messages.
+fn read_short_bytes<R: ByteReader>(
+    source: &mut R,
+    name: &str,
+) -> Result<Vec<u8>, DeserializationError> {
+    let bytes = read_bytes(source)?;
+    if bytes.len() > u16::MAX as usize {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{} cannot be longer than {} bytes, but was {} bytes",
+            name,
+            u16::MAX,
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
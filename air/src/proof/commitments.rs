@@ -3,10 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::cbor;
 use crypto::Hasher;
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    collections::Vec, AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
 };
 
 // COMMITMENTS
@@ -17,7 +18,8 @@ use utils::{
 /// * Commitment to the extended execution trace, which may include commitments to one or more
 ///   execution trace segments.
 /// * Commitment to the evaluations of constraint composition polynomial over LDE domain.
-/// * Commitments to the evaluations of polynomials at all FRI layers.
+/// * Commitments to the evaluations of polynomials at all FRI layers. These are absent when
+///   STIR is used as the low-degree test, as STIR round commitments are a part of the STIR proof.
 ///
 /// Internally, the commitments are stored as a sequence of bytes. Thus, to retrieve the
 /// commitments, [parse()](Commitments::parse) function should be used.
@@ -40,6 +42,22 @@ impl Commitments {
         Commitments(bytes)
     }
 
+    /// Returns commitments constructed from the specified concatenation of serialized digests.
+    ///
+    /// No validation of the provided bytes is performed; the bytes are parsed only when
+    /// [parse()](Commitments::parse) is invoked.
+    ///
+    /// # Panics
+    /// Panics if `bytes` is longer than 65534 bytes.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        assert!(
+            bytes.len() < u16::MAX as usize,
+            "commitments cannot be longer than {} bytes",
+            u16::MAX - 1
+        );
+        Commitments(bytes)
+    }
+
     // PUBLIC METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -48,6 +66,31 @@ impl Commitments {
         commitment.write_into(&mut self.0);
     }
 
+    // CBOR ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes these commitments into the `target` as a single CBOR byte string.
+    pub(super) fn write_cbor<W: ByteWriter>(&self, target: &mut W) {
+        cbor::write_bytes(target, &self.0);
+    }
+
+    /// Reads CBOR-encoded commitments from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a byte string of at most 65535 bytes could not be read from the
+    /// `source`.
+    pub(super) fn read_cbor<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes = cbor::read_bytes(source)?;
+        if bytes.len() > u16::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "commitments cannot be longer than {} bytes, but were {} bytes",
+                u16::MAX,
+                bytes.len()
+            )));
+        }
+        Ok(Commitments(bytes))
+    }
+
     // PARSING
     // --------------------------------------------------------------------------------------------
 
@@ -67,22 +110,39 @@ impl Commitments {
         num_trace_segments: usize,
         num_fri_layers: usize,
     ) -> Result<(Vec<H::Digest>, H::Digest, Vec<H::Digest>), DeserializationError> {
-        let mut reader = SliceReader::new(&self.0);
+        self.view().parse::<H>(num_trace_segments, num_fri_layers)
+    }
 
-        // parse trace commitments
-        let trace_commitments = H::Digest::read_batch_from(&mut reader, num_trace_segments)?;
+    /// Parses the serialized commitments of a proof which does not contain FRI layer commitments
+    /// (i.e., a proof which uses STIR as its low-degree test).
+    ///
+    /// The parts are (in the order in which they appear in the tuple):
+    /// 1. Extended execution trace commitments.
+    /// 2. Constraint composition polynomial evaluation commitment.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes stored in self could not be parsed into the requested number
+    /// of commitments, or if there are any unconsumed bytes remaining after the parsing completes.
+    pub fn parse_without_fri<H: Hasher>(
+        self,
+        num_trace_segments: usize,
+    ) -> Result<(Vec<H::Digest>, H::Digest), DeserializationError> {
+        self.view().parse_without_fri::<H>(num_trace_segments)
+    }
 
-        // parse constraint evaluation commitment:
-        let constraint_commitment = H::Digest::read_from(&mut reader)?;
+    // VIEW
+    // --------------------------------------------------------------------------------------------
 
-        // read FRI commitments (+ 1 for remainder polynomial commitment)
-        let fri_commitments = H::Digest::read_batch_from(&mut reader, num_fri_layers + 1)?;
+    /// Returns a view of these commitments which borrows the serialized commitments from `self`.
+    pub fn view(&self) -> CommitmentsRef<'_> {
+        CommitmentsRef(&self.0)
+    }
+}
 
-        // make sure we consumed all available commitment bytes
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
-        Ok((trace_commitments, constraint_commitment, fri_commitments))
+impl AsBytes for Commitments {
+    /// Returns all commitments concatenated together into a single slice of bytes.
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
     }
 }
 
@@ -107,3 +167,89 @@ impl Deserializable for Commitments {
         Ok(Commitments(result))
     }
 }
+
+// COMMITMENTS VIEW
+// ================================================================================================
+/// A view of serialized [Commitments] which borrows the commitment bytes from the underlying
+/// source.
+///
+/// Similarly to [Commitments], the commitments are parsed only when
+/// [parse()](CommitmentsRef::parse) function is invoked.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CommitmentsRef<'a>(&'a [u8]);
+
+impl<'a> CommitmentsRef<'a> {
+    /// Returns an owned copy of the commitments described by this view.
+    pub fn into_owned(self) -> Commitments {
+        Commitments(self.0.to_vec())
+    }
+
+    /// Parses the serialized commitments into distinct parts.
+    ///
+    /// This works in the same way as [Commitments::parse()].
+    ///
+    /// # Errors
+    /// Returns an error if the bytes referenced by `self` could not be parsed into the requested
+    /// number of commitments, or if there are any unconsumed bytes remaining after the parsing
+    /// completes.
+    #[allow(clippy::type_complexity)]
+    pub fn parse<H: Hasher>(
+        &self,
+        num_trace_segments: usize,
+        num_fri_layers: usize,
+    ) -> Result<(Vec<H::Digest>, H::Digest, Vec<H::Digest>), DeserializationError> {
+        let mut reader = SliceReader::new(self.0);
+
+        // parse trace commitments
+        let trace_commitments = H::Digest::read_batch_from(&mut reader, num_trace_segments)?;
+
+        // parse constraint evaluation commitment:
+        let constraint_commitment = H::Digest::read_from(&mut reader)?;
+
+        // read FRI commitments (+ 1 for remainder polynomial commitment)
+        let fri_commitments = H::Digest::read_batch_from(&mut reader, num_fri_layers + 1)?;
+
+        // make sure we consumed all available commitment bytes
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok((trace_commitments, constraint_commitment, fri_commitments))
+    }
+
+    /// Parses the serialized commitments of a proof which does not contain FRI layer commitments.
+    ///
+    /// This works in the same way as [Commitments::parse_without_fri()].
+    ///
+    /// # Errors
+    /// Returns an error if the bytes referenced by `self` could not be parsed into the requested
+    /// number of commitments, or if there are any unconsumed bytes remaining after the parsing
+    /// completes.
+    pub fn parse_without_fri<H: Hasher>(
+        &self,
+        num_trace_segments: usize,
+    ) -> Result<(Vec<H::Digest>, H::Digest), DeserializationError> {
+        let mut reader = SliceReader::new(self.0);
+        let trace_commitments = H::Digest::read_batch_from(&mut reader, num_trace_segments)?;
+        let constraint_commitment = H::Digest::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok((trace_commitments, constraint_commitment))
+    }
+
+    /// Reads a view of commitments from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error of valid commitments could not be read from the specified `source`.
+    pub fn read_from(source: &mut SliceReader<'a>) -> Result<Self, DeserializationError> {
+        let num_bytes = source.read_u16()? as usize;
+        Ok(CommitmentsRef(source.read_slice(num_bytes)?))
+    }
+}
+
+impl<'a> AsBytes for CommitmentsRef<'a> {
+    /// Returns all commitments concatenated together into a single slice of bytes.
+    fn as_bytes(&self) -> &[u8] {
+        self.0
+    }
+}
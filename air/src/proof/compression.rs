@@ -0,0 +1,498 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Compression of serialized STARK proofs.
+//!
+//! Compression is done in two stages:
+//! 1. Structural compression, during which repeated digests in Merkle authentication paths (for
+//!    trace queries, constraint queries, and FRI layers or STIR rounds) are replaced with references to their
+//!    first occurrence. Identical Merkle nodes are common, for example, in traces which contain
+//!    many repeated rows.
+//! 2. General-purpose compression of the resulting bytes using one of the supported algorithms.
+//!    Currently, [DEFLATE](https://www.rfc-editor.org/rfc/rfc1951) is available via `deflate`
+//!    feature, and [Zstandard](https://www.rfc-editor.org/rfc/rfc8878) is available via `zstd`
+//!    feature.
+//!
+//! A compressed proof starts with a byte which identifies the general-purpose compression
+//! algorithm followed by the compressed payload. The payload consists of the size of digests
+//! used in Merkle paths (or 0 if structural compression was not applied) followed by the proof
+//! serialized via [StarkProof::to_bytes()] but with Merkle paths replaced by their structurally
+//! compressed versions.
+
+use super::{Queries, StarkProof};
+use fri::{stir::StirProof, FriProof, FriProofLayer};
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::ToString,
+    AsBytes, ByteReader, ByteWriter, DeserializationError, SliceReader,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum size of a decompressed proof; this protects against decompression bombs.
+#[cfg(any(feature = "deflate", feature = "zstd"))]
+const MAX_DECOMPRESSED_PROOF_SIZE: usize = 1 << 26;
+
+/// A token which indicates that a digest in a Merkle path is written out in full; all other
+/// tokens are references to previously encountered digests.
+const LITERAL_DIGEST: u64 = 0;
+
+#[cfg(feature = "deflate")]
+const DEFLATE_LEVEL: u8 = 9;
+
+#[cfg(feature = "zstd")]
+const ZSTD_LEVEL: i32 = 19;
+
+// COMPRESSION ALGORITHM
+// ================================================================================================
+
+/// Defines the general-purpose compression algorithm applied to a structurally compressed proof.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u8)]
+pub enum CompressionAlgorithm {
+    /// No general-purpose compression; only structural compression is applied.
+    None = 0,
+    /// DEFLATE compression (available via `deflate` feature).
+    #[cfg(feature = "deflate")]
+    Deflate = 1,
+    /// Zstandard compression (available via `zstd` feature).
+    #[cfg(feature = "zstd")]
+    Zstd = 2,
+}
+
+impl Default for CompressionAlgorithm {
+    /// Returns the algorithm which produces the smallest proofs out of all enabled algorithms.
+    #[allow(unreachable_code)]
+    fn default() -> Self {
+        #[cfg(feature = "zstd")]
+        return Self::Zstd;
+
+        #[cfg(feature = "deflate")]
+        return Self::Deflate;
+
+        Self::None
+    }
+}
+
+// COMPRESSION
+// ================================================================================================
+
+/// Compresses the specified proof using the specified general-purpose compression `algorithm`.
+pub(super) fn compress(proof: &StarkProof, algorithm: CompressionAlgorithm) -> Vec<u8> {
+    let mut payload = Vec::new();
+    match compress_paths(proof) {
+        Some((digest_size, compressed_proof)) => {
+            payload.write_u8(digest_size as u8);
+            payload.extend_from_slice(&compressed_proof.to_bytes());
+        }
+        None => {
+            payload.write_u8(0);
+            payload.extend_from_slice(&proof.to_bytes());
+        }
+    }
+
+    let mut result = vec![algorithm as u8];
+    match algorithm {
+        CompressionAlgorithm::None => result.append(&mut payload),
+        #[cfg(feature = "deflate")]
+        CompressionAlgorithm::Deflate => result.append(&mut miniz_oxide::deflate::compress_to_vec(
+            &payload,
+            DEFLATE_LEVEL,
+        )),
+        #[cfg(feature = "zstd")]
+        CompressionAlgorithm::Zstd => result.append(
+            &mut zstd::bulk::compress(&payload, ZSTD_LEVEL).expect("zstd compression failed"),
+        ),
+    }
+    result
+}
+
+/// Returns a STARK proof decompressed from the specified `source`.
+///
+/// # Errors
+/// Returns an error if:
+/// * The `source` was compressed using an algorithm which is unknown or is not enabled.
+/// * The decompressed proof would be larger than 64 MB.
+/// * A valid STARK proof could not be decompressed from the `source`.
+pub(super) fn decompress(source: &[u8]) -> Result<StarkProof, DeserializationError> {
+    let (&algorithm, compressed) = source
+        .split_first()
+        .ok_or(DeserializationError::UnexpectedEOF)?;
+
+    let payload = match algorithm {
+        0 => compressed.to_vec(),
+        1 => decompress_deflate(compressed)?,
+        2 => decompress_zstd(compressed)?,
+        _ => {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unknown proof compression algorithm {algorithm}"
+            )))
+        }
+    };
+
+    let (&digest_size, proof_bytes) = payload
+        .split_first()
+        .ok_or(DeserializationError::UnexpectedEOF)?;
+    let proof = StarkProof::from_bytes(proof_bytes)?;
+    if digest_size == 0 {
+        Ok(proof)
+    } else {
+        decompress_paths(proof, digest_size as usize)
+    }
+}
+
+#[cfg(feature = "deflate")]
+fn decompress_deflate(source: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    miniz_oxide::inflate::decompress_to_vec_with_limit(source, MAX_DECOMPRESSED_PROOF_SIZE).map_err(
+        |err| {
+            DeserializationError::InvalidValue(format!(
+                "failed to decompress DEFLATE-compressed proof: {:?}",
+                err.status
+            ))
+        },
+    )
+}
+
+#[cfg(not(feature = "deflate"))]
+fn decompress_deflate(_source: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    Err(DeserializationError::InvalidValue(
+        "proof was compressed using DEFLATE, but deflate feature is not enabled".to_string(),
+    ))
+}
+
+#[cfg(feature = "zstd")]
+fn decompress_zstd(source: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    use std::io::Read;
+
+    let decoder = zstd::stream::read::Decoder::new(source).map_err(|err| {
+        DeserializationError::InvalidValue(format!("failed to initialize zstd decoder: {err}"))
+    })?;
+
+    let mut result = Vec::new();
+    decoder
+        .take(MAX_DECOMPRESSED_PROOF_SIZE as u64 + 1)
+        .read_to_end(&mut result)
+        .map_err(|err| {
+            DeserializationError::InvalidValue(format!(
+                "failed to decompress zstd-compressed proof: {err}"
+            ))
+        })?;
+    if result.len() > MAX_DECOMPRESSED_PROOF_SIZE {
+        return Err(DeserializationError::InvalidValue(format!(
+            "decompressed proof cannot be larger than {MAX_DECOMPRESSED_PROOF_SIZE} bytes"
+        )));
+    }
+    Ok(result)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress_zstd(_source: &[u8]) -> Result<Vec<u8>, DeserializationError> {
+    Err(DeserializationError::InvalidValue(
+        "proof was compressed using zstd, but zstd feature is not enabled".to_string(),
+    ))
+}
+
+// STRUCTURAL COMPRESSION
+// ================================================================================================
+
+/// Returns a copy of the specified proof in which all Merkle paths have been compressed, together
+/// with the size of digests in these paths.
+///
+/// Returns `None` if digest size could not be inferred from the proof, or if Merkle paths could
+/// not be parsed using the inferred digest size.
+fn compress_paths(proof: &StarkProof) -> Option<(usize, StarkProof)> {
+    let digest_size = infer_digest_size(proof)?;
+    let mut digest_map = BTreeMap::new();
+
+    let mut trace_queries = Vec::with_capacity(proof.trace_queries.len());
+    for queries in proof.trace_queries.iter() {
+        let paths = compress_nodes(queries.paths_bytes(), digest_size, &mut digest_map)?;
+        trace_queries.push(Queries::from_raw_parts_salted(
+            queries.values_bytes().to_vec(),
+            paths,
+            queries.salts_bytes().to_vec(),
+        ));
+    }
+
+    let queries = &proof.constraint_queries;
+    let paths = compress_nodes(queries.paths_bytes(), digest_size, &mut digest_map)?;
+    let constraint_queries = Queries::from_raw_parts_salted(
+        queries.values_bytes().to_vec(),
+        paths,
+        queries.salts_bytes().to_vec(),
+    );
+
+    let mut fri_layers = Vec::with_capacity(proof.fri_proof.num_layers());
+    for layer in proof.fri_proof.layers() {
+        let paths = compress_nodes(layer.paths_bytes(), digest_size, &mut digest_map)?;
+        fri_layers.push(FriProofLayer::from_raw_parts(
+            layer.values_bytes().to_vec(),
+            paths,
+        ));
+    }
+
+    let stir_proof = match proof.stir_proof.as_ref() {
+        Some(stir_proof) => {
+            let mut rounds = Vec::with_capacity(stir_proof.num_rounds());
+            for round in stir_proof.rounds() {
+                let paths = compress_nodes(round.paths_bytes(), digest_size, &mut digest_map)?;
+                rounds.push(FriProofLayer::from_raw_parts(
+                    round.values_bytes().to_vec(),
+                    paths,
+                ));
+            }
+            let input_queries = match stir_proof.input_queries() {
+                Some(queries) => {
+                    let paths =
+                        compress_nodes(queries.paths_bytes(), digest_size, &mut digest_map)?;
+                    Some(FriProofLayer::from_raw_parts(
+                        queries.values_bytes().to_vec(),
+                        paths,
+                    ))
+                }
+                None => None,
+            };
+            Some(with_stir_layers(stir_proof, rounds, input_queries))
+        }
+        None => None,
+    };
+
+    let compressed_proof = StarkProof {
+        context: proof.context.clone(),
+        commitments: proof.commitments.clone(),
+        trace_queries,
+        constraint_queries,
+        ood_frame: proof.ood_frame.clone(),
+        fri_proof: FriProof::from_raw_parts(
+            fri_layers,
+            proof.fri_proof.remainder_bytes().to_vec(),
+            proof.fri_proof.num_partitions(),
+        )
+        .with_layer_nonces(proof.fri_proof.layer_nonces().to_vec()),
+        stir_proof,
+        pow_nonce: proof.pow_nonce,
+        metadata: proof.metadata.clone(),
+    };
+    Some((digest_size, compressed_proof))
+}
+
+/// Reverses the structural compression of Merkle paths in the specified proof.
+fn decompress_paths(
+    proof: StarkProof,
+    digest_size: usize,
+) -> Result<StarkProof, DeserializationError> {
+    let mut digests = Vec::new();
+
+    let mut trace_queries = Vec::with_capacity(proof.trace_queries.len());
+    for queries in proof.trace_queries.iter() {
+        let paths = decompress_nodes(queries.paths_bytes(), digest_size, &mut digests)?;
+        trace_queries.push(Queries::from_raw_parts_salted(
+            queries.values_bytes().to_vec(),
+            paths,
+            queries.salts_bytes().to_vec(),
+        ));
+    }
+
+    let queries = &proof.constraint_queries;
+    let paths = decompress_nodes(queries.paths_bytes(), digest_size, &mut digests)?;
+    let constraint_queries = Queries::from_raw_parts_salted(
+        queries.values_bytes().to_vec(),
+        paths,
+        queries.salts_bytes().to_vec(),
+    );
+
+    let mut fri_layers = Vec::with_capacity(proof.fri_proof.num_layers());
+    for layer in proof.fri_proof.layers() {
+        let paths = decompress_nodes(layer.paths_bytes(), digest_size, &mut digests)?;
+        fri_layers.push(FriProofLayer::from_raw_parts(
+            layer.values_bytes().to_vec(),
+            paths,
+        ));
+    }
+
+    let stir_proof = match proof.stir_proof.as_ref() {
+        Some(stir_proof) => {
+            let mut rounds = Vec::with_capacity(stir_proof.num_rounds());
+            for round in stir_proof.rounds() {
+                let paths = decompress_nodes(round.paths_bytes(), digest_size, &mut digests)?;
+                rounds.push(FriProofLayer::from_raw_parts(
+                    round.values_bytes().to_vec(),
+                    paths,
+                ));
+            }
+            let input_queries = match stir_proof.input_queries() {
+                Some(queries) => {
+                    let paths = decompress_nodes(queries.paths_bytes(), digest_size, &mut digests)?;
+                    Some(FriProofLayer::from_raw_parts(
+                        queries.values_bytes().to_vec(),
+                        paths,
+                    ))
+                }
+                None => None,
+            };
+            Some(with_stir_layers(stir_proof, rounds, input_queries))
+        }
+        None => None,
+    };
+
+    Ok(StarkProof {
+        trace_queries,
+        constraint_queries,
+        stir_proof,
+        fri_proof: FriProof::from_raw_parts(
+            fri_layers,
+            proof.fri_proof.remainder_bytes().to_vec(),
+            proof.fri_proof.num_partitions(),
+        )
+        .with_layer_nonces(proof.fri_proof.layer_nonces().to_vec()),
+        ..proof
+    })
+}
+
+/// Returns a copy of the specified STIR proof with round queries and queries of the first round
+/// evaluations replaced by the specified ones.
+fn with_stir_layers(
+    proof: &StirProof,
+    rounds: Vec<FriProofLayer>,
+    input_queries: Option<FriProofLayer>,
+) -> StirProof {
+    StirProof::from_raw_parts(
+        proof.commitments_bytes().to_vec(),
+        proof.ood_answers_bytes().to_vec(),
+        rounds,
+        proof.final_poly_bytes().to_vec(),
+        input_queries,
+    )
+}
+
+/// Infers the size of a digest from the number of commitments in the specified proof.
+///
+/// A proof contains a commitment for each trace segment, a commitment to the constraint
+/// evaluations, and, unless a STIR proof is used, a commitment for each FRI layer and a
+/// commitment to the FRI remainder (STIR round commitments are a part of the STIR proof).
+fn infer_digest_size(proof: &StarkProof) -> Option<usize> {
+    let num_ldt_commitments = match proof.stir_proof {
+        Some(_) => 0,
+        None => proof.fri_proof.num_layers() + 1,
+    };
+    let num_commitments = proof.trace_layout().num_segments() + num_ldt_commitments + 1;
+    let num_bytes = proof.commitments.as_bytes().len();
+    let digest_size = num_bytes / num_commitments;
+    if num_bytes % num_commitments != 0 || digest_size == 0 || digest_size > u8::MAX as usize {
+        return None;
+    }
+    Some(digest_size)
+}
+
+/// Compresses serialized internal nodes of a batch Merkle proof by replacing each digest which
+/// has already been encountered with a reference to its first occurrence.
+///
+/// Returns `None` if `node_bytes` could not be parsed as a sequence of digests of the specified
+/// size.
+fn compress_nodes<'a>(
+    node_bytes: &'a [u8],
+    digest_size: usize,
+    digest_map: &mut BTreeMap<&'a [u8], usize>,
+) -> Option<Vec<u8>> {
+    let mut result = Vec::with_capacity(node_bytes.len());
+    let (&num_node_vectors, mut node_bytes) = node_bytes.split_first()?;
+    result.push(num_node_vectors);
+
+    for _ in 0..num_node_vectors {
+        let (&num_digests, rest) = node_bytes.split_first()?;
+        result.push(num_digests);
+        node_bytes = rest;
+
+        for _ in 0..num_digests {
+            if node_bytes.len() < digest_size {
+                return None;
+            }
+            let (digest, rest) = node_bytes.split_at(digest_size);
+            node_bytes = rest;
+
+            match digest_map.get(digest) {
+                Some(&index) => write_varint(&mut result, index as u64 + 1),
+                None => {
+                    digest_map.insert(digest, digest_map.len());
+                    write_varint(&mut result, LITERAL_DIGEST);
+                    result.extend_from_slice(digest);
+                }
+            }
+        }
+    }
+
+    if !node_bytes.is_empty() {
+        return None;
+    }
+    Some(result)
+}
+
+/// Reverses compression performed by [compress_nodes()] function.
+fn decompress_nodes(
+    node_bytes: &[u8],
+    digest_size: usize,
+    digests: &mut Vec<Vec<u8>>,
+) -> Result<Vec<u8>, DeserializationError> {
+    let mut source = SliceReader::new(node_bytes);
+    let mut result = Vec::with_capacity(node_bytes.len());
+
+    let num_node_vectors = source.read_u8()?;
+    result.push(num_node_vectors);
+    for _ in 0..num_node_vectors {
+        let num_digests = source.read_u8()?;
+        result.push(num_digests);
+        for _ in 0..num_digests {
+            let token = read_varint(&mut source)?;
+            if token == LITERAL_DIGEST {
+                let digest = source.read_vec(digest_size)?;
+                result.extend_from_slice(&digest);
+                digests.push(digest);
+            } else {
+                let digest = usize::try_from(token - 1)
+                    .ok()
+                    .and_then(|index| digests.get(index))
+                    .ok_or_else(|| {
+                        DeserializationError::InvalidValue(format!(
+                            "invalid digest reference {token} in compressed Merkle path"
+                        ))
+                    })?;
+                result.extend_from_slice(digest);
+            }
+        }
+    }
+
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the specified value into the `target` using LEB128 encoding.
+fn write_varint<W: ByteWriter>(target: &mut W, mut value: u64) {
+    while value >= 0x80 {
+        target.write_u8((value as u8) | 0x80);
+        value >>= 7;
+    }
+    target.write_u8(value as u8);
+}
+
+/// Reads a LEB128-encoded value from the `source`.
+fn read_varint<R: ByteReader>(source: &mut R) -> Result<u64, DeserializationError> {
+    let mut result = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = source.read_u8()?;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+    Err(DeserializationError::InvalidValue(
+        "LEB128-encoded value is too large".to_string(),
+    ))
+}
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::cbor;
 use crate::{ProofOptions, TraceInfo, TraceLayout};
 use math::{StarkField, ToElements};
 use utils::{
@@ -10,6 +11,13 @@ use utils::{
     DeserializationError, Serializable,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Base-2 logarithm of the largest trace length which can be read from a serialized context;
+/// this matches the largest power of two which fits into a u32 integer.
+const MAX_TRACE_LENGTH_LOG2: u8 = 31;
+
 // PROOF CONTEXT
 // ================================================================================================
 /// Basic metadata about a specific execution of a computation.
@@ -91,6 +99,99 @@ impl Context {
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    /// Returns the seed of the public coin for a proof in this context generated for the
+    /// specified public inputs.
+    ///
+    /// The seed consists of the elements of this context (see [ToElements] implementation for
+    /// [Context]) followed by the elements of the public inputs. Both, the prover and the
+    /// verifier instantiate the public coin with this seed, and thus, external verifiers need to
+    /// reproduce it exactly.
+    pub fn get_public_coin_seed<E, P>(&self, pub_inputs: &P) -> Vec<E>
+    where
+        E: StarkField,
+        P: ToElements<E> + ?Sized,
+    {
+        let mut result = self.to_elements();
+        result.append(&mut pub_inputs.to_elements());
+        result
+    }
+
+    // CBOR ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this context into the `target` using CBOR encoding.
+    pub(super) fn write_cbor<W: ByteWriter>(&self, target: &mut W) {
+        cbor::write_array_header(target, 5);
+        cbor::write_trace_layout(target, &self.trace_layout);
+        cbor::write_uint(target, self.trace_length as u64);
+        cbor::write_bytes(target, &self.trace_meta);
+        cbor::write_bytes(target, &self.field_modulus_bytes);
+        cbor::write_options(target, &self.options);
+    }
+
+    /// Reads a CBOR-encoded proof context from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error of a valid Context struct could not be read from the specified `source`.
+    pub(super) fn read_cbor<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        cbor::read_struct_header(source, 5, "proof context")?;
+
+        // read and validate trace layout info
+        let trace_layout = cbor::read_trace_layout(source)?;
+
+        // read and validate trace length
+        let trace_length = cbor::read_bounded_uint(source, u32::MAX as u64, "trace length")?;
+        if !trace_length.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length must be a power of two, but was {trace_length}"
+            )));
+        }
+        if trace_length < TraceInfo::MIN_TRACE_LENGTH as u64 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be smaller than {}, but was {}",
+                TraceInfo::MIN_TRACE_LENGTH,
+                trace_length
+            )));
+        }
+
+        // read trace metadata
+        let trace_meta = cbor::read_bytes(source)?;
+        if trace_meta.len() > TraceInfo::MAX_META_LENGTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of metadata bytes cannot be greater than {}, but was {}",
+                TraceInfo::MAX_META_LENGTH,
+                trace_meta.len()
+            )));
+        }
+
+        // read and validate field modulus bytes
+        let field_modulus_bytes = cbor::read_bytes(source)?;
+        if field_modulus_bytes.is_empty() {
+            return Err(DeserializationError::InvalidValue(
+                "field modulus cannot be an empty value".to_string(),
+            ));
+        }
+        if field_modulus_bytes.len() >= u8::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "field modulus cannot be longer than {} bytes, but was {} bytes",
+                u8::MAX - 1,
+                field_modulus_bytes.len()
+            )));
+        }
+
+        // read options
+        let options = cbor::read_options(source)?;
+        validate_lde_domain_size(trace_length as usize, &options)?;
+
+        Ok(Context {
+            trace_layout,
+            trace_length: trace_length as usize,
+            trace_meta,
+            field_modulus_bytes,
+            options,
+        })
+    }
 }
 
 impl<E: StarkField> ToElements<E> for Context {
@@ -164,10 +265,22 @@ impl Deserializable for Context {
                 trace_length
             )));
         }
+        if trace_length > MAX_TRACE_LENGTH_LOG2 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be greater than 2^{MAX_TRACE_LENGTH_LOG2}, but was 2^{trace_length}"
+            )));
+        }
         let trace_length = 2_usize.pow(trace_length as u32);
 
         // read trace metadata
         let num_meta_bytes = source.read_u16()? as usize;
+        if num_meta_bytes > TraceInfo::MAX_META_LENGTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of metadata bytes cannot be greater than {}, but was {}",
+                TraceInfo::MAX_META_LENGTH,
+                num_meta_bytes
+            )));
+        }
         let trace_meta = if num_meta_bytes != 0 {
             source.read_vec(num_meta_bytes)?
         } else {
@@ -181,10 +294,18 @@ impl Deserializable for Context {
                 "field modulus cannot be an empty value".to_string(),
             ));
         }
+        if num_modulus_bytes == u8::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "field modulus cannot be longer than {} bytes, but was {} bytes",
+                u8::MAX - 1,
+                num_modulus_bytes
+            )));
+        }
         let field_modulus_bytes = source.read_vec(num_modulus_bytes)?;
 
         // read options
         let options = ProofOptions::read_from(source)?;
+        validate_lde_domain_size(trace_length, &options)?;
 
         Ok(Context {
             trace_layout,
@@ -196,9 +317,39 @@ impl Deserializable for Context {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Context {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Context {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Makes sure the size of the LDE domain implied by the specified trace length and proof options
+/// can be represented by a `usize` on the current platform.
+fn validate_lde_domain_size(
+    trace_length: usize,
+    options: &ProofOptions,
+) -> Result<(), DeserializationError> {
+    if trace_length.checked_mul(options.blowup_factor()).is_none() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "LDE domain size for trace length {} and blowup factor {} is too large",
+            trace_length,
+            options.blowup_factor()
+        )));
+    }
+    Ok(())
+}
+
 /// Converts a slice of bytes into a field element.
 ///
 /// Assumes that the length of `bytes` is smaller than the number of bytes needed to encode an
@@ -254,7 +405,7 @@ mod tests {
             BaseElement::from(1_u32),    // lower bits of field modulus
             BaseElement::from(u32::MAX), // upper bits of field modulus
             BaseElement::from(ext_fri),
-            BaseElement::from(grinding_factor as u32),
+            BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
             BaseElement::from(trace_length as u32),
@@ -0,0 +1,357 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Queries, StarkProof, PROOF_FORMAT_VERSION};
+use core::{fmt, marker::PhantomData};
+use crypto::{Hasher, QuerySampling};
+use fri::stir::StirProof;
+use utils::{string::String, Serializable};
+
+// PROOF DESCRIPTION
+// ================================================================================================
+
+/// A human-readable breakdown of a [StarkProof] returned by
+/// [StarkProof::describe()](StarkProof::describe).
+///
+/// The description is produced when the value is formatted via [Display](fmt::Display), and it
+/// includes:
+/// * Proof context: trace layout and length, base field, and proof options.
+/// * Conjectured (and, when `std` feature is enabled, proven) security level of the proof.
+/// * Sizes of all sections of the proof serialized via [StarkProof::to_bytes()].
+/// * Trace, constraint, and FRI commitments as hex-encoded digests of hash function `H`.
+/// * Structure of the FRI proof: domain size and number of queried cosets for each FRI layer,
+///   and the size of the remainder. For proofs which use STIR as the low-degree test, sizes of
+///   the STIR rounds and the length of the final polynomial are described instead.
+///
+/// The description does not require the proof to be valid; parts of the proof which cannot be
+/// interpreted (e.g., commitments which are not digests of `H`) are described as raw bytes.
+pub struct ProofDescription<'a, H: Hasher> {
+    proof: &'a StarkProof,
+    _hasher: PhantomData<H>,
+}
+
+impl<'a, H: Hasher> ProofDescription<'a, H> {
+    pub(super) fn new(proof: &'a StarkProof) -> Self {
+        ProofDescription {
+            proof,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<'a, H: Hasher> fmt::Display for ProofDescription<'a, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let proof = self.proof;
+        let trace_layout = proof.trace_layout();
+        let options = proof.options();
+        let fri_options = options.to_fri_options();
+        let lde_domain_size = proof.lde_domain_size();
+        let num_trace_segments = trace_layout.num_segments();
+
+        // main trace values are in the base field, while all other values are in the extension
+        // field specified by proof options
+        let base_element_size = proof.context.field_modulus_bytes().len();
+        let element_size = base_element_size * options.field_extension().degree() as usize;
+
+        let proof_size = proof.to_bytes().len();
+        writeln!(f, "STARK proof: {proof_size} bytes")?;
+
+        // context
+        writeln!(f, "Context:")?;
+        writeln!(
+            f,
+            "  trace: 2^{} steps, {} main columns, {} auxiliary columns",
+            proof.trace_length().ilog2(),
+            trace_layout.main_trace_width(),
+            trace_layout.aux_trace_width()
+        )?;
+        writeln!(
+            f,
+            "  auxiliary trace segments: {}",
+            trace_layout.num_aux_segments()
+        )?;
+        writeln!(
+            f,
+            "  trace metadata: {} bytes",
+            proof.context.get_trace_info().meta().len()
+        )?;
+        writeln!(
+            f,
+            "  base field: {}-bit modulus",
+            proof.context.num_modulus_bits()
+        )?;
+        writeln!(f, "  LDE domain size: 2^{}", lde_domain_size.ilog2())?;
+
+        // options
+        writeln!(f, "Options:")?;
+        writeln!(f, "  number of queries: {}", options.num_queries())?;
+        writeln!(f, "  blowup factor: {}", options.blowup_factor())?;
+        writeln!(f, "  grinding factor: {}", options.grinding_factor())?;
+        if options.query_sampling() != QuerySampling::Modulo {
+            writeln!(f, "  query sampling: {:?}", options.query_sampling())?;
+        }
+        writeln!(
+            f,
+            "  field extension degree: {}",
+            options.field_extension().degree()
+        )?;
+        if options.num_ood_points() > 1 {
+            writeln!(f, "  out-of-domain points: {}", options.num_ood_points())?;
+        }
+        if options.merkle_multi_proofs() {
+            writeln!(f, "  Merkle proofs: multi-proofs")?;
+        }
+        let ldt_name = match proof.stir_proof {
+            Some(_) => {
+                writeln!(f, "  low-degree test: STIR")?;
+                "STIR"
+            }
+            None => "FRI",
+        };
+        writeln!(
+            f,
+            "  {ldt_name} folding factor: {}",
+            fri_options.folding_factor()
+        )?;
+        if !fri_options.is_uniform() {
+            writeln!(
+                f,
+                "  FRI folding schedule: {:?}",
+                fri_options.layer_folding_factors(lde_domain_size)
+            )?;
+        }
+        writeln!(
+            f,
+            "  {ldt_name} remainder max degree: {}",
+            fri_options.remainder_max_degree()
+        )?;
+        if options.fri_layer_grinding_factor() > 0 {
+            writeln!(
+                f,
+                "  FRI layer grinding factor: {}",
+                options.fri_layer_grinding_factor()
+            )?;
+        }
+
+        // security level
+        writeln!(f, "Security:")?;
+        writeln!(f, "  conjectured: {} bits", proof.security_level::<H>(true))?;
+        #[cfg(feature = "std")]
+        writeln!(f, "  proven: {} bits", proof.security_level::<H>(false))?;
+
+        // section sizes
+        writeln!(f, "Sections:")?;
+        writeln!(
+            f,
+            "  version header: 2 bytes (format version {PROOF_FORMAT_VERSION})"
+        )?;
+        writeln!(f, "  context: {} bytes", proof.context.to_bytes().len())?;
+        writeln!(
+            f,
+            "  commitments: {} bytes",
+            proof.commitments.to_bytes().len()
+        )?;
+        for (i, queries) in proof.trace_queries.iter().enumerate() {
+            let (num_columns, value_size) = if i == 0 {
+                (trace_layout.main_trace_width(), base_element_size)
+            } else {
+                (trace_layout.get_aux_segment_width(i - 1), element_size)
+            };
+            writeln!(
+                f,
+                "  {} trace queries: {}",
+                segment_name(i),
+                describe_queries(queries, num_columns * value_size)
+            )?;
+        }
+        let num_constraint_columns =
+            proof.ood_frame.evaluations_bytes().len() / (element_size * options.num_ood_points());
+        writeln!(
+            f,
+            "  constraint queries: {}",
+            describe_queries(
+                &proof.constraint_queries,
+                num_constraint_columns * element_size
+            )
+        )?;
+        writeln!(
+            f,
+            "  out-of-domain frame: {} bytes ({} trace state bytes, {} evaluation bytes)",
+            proof.ood_frame.to_bytes().len(),
+            proof.ood_frame.trace_states_bytes().len(),
+            proof.ood_frame.evaluations_bytes().len()
+        )?;
+        match &proof.stir_proof {
+            Some(stir_proof) => writeln!(f, "  STIR proof: {} bytes", stir_proof.to_bytes().len())?,
+            None => writeln!(f, "  FRI proof: {} bytes", proof.fri_proof.to_bytes().len())?,
+        }
+        writeln!(f, "  proof-of-work nonce: 8 bytes")?;
+        match &proof.metadata {
+            Some(metadata) => writeln!(f, "  metadata: {} bytes", metadata.to_bytes().len() + 1)?,
+            None => writeln!(f, "  metadata: 1 byte (no metadata)")?,
+        }
+
+        if let Some(stir_proof) = &proof.stir_proof {
+            describe_stir_proof::<H>(f, proof, stir_proof, element_size)?;
+            return write!(f, "Proof-of-work nonce: {}", proof.pow_nonce);
+        }
+
+        // commitments
+        writeln!(f, "Commitments:")?;
+        let num_fri_layers = proof.fri_proof.num_layers();
+        match proof
+            .commitments
+            .clone()
+            .parse::<H>(num_trace_segments, num_fri_layers)
+        {
+            Ok((trace_roots, constraint_root, fri_roots)) => {
+                for (i, root) in trace_roots.iter().enumerate() {
+                    writeln!(f, "  {} trace: {}", segment_name(i), root.to_hex())?;
+                }
+                writeln!(f, "  constraint evaluations: {}", constraint_root.to_hex())?;
+                let (remainder_root, layer_roots) =
+                    fri_roots.split_last().expect("no FRI remainder commitment");
+                for (i, root) in layer_roots.iter().enumerate() {
+                    writeln!(f, "  FRI layer {i}: {}", root.to_hex())?;
+                }
+                writeln!(f, "  FRI remainder: {}", remainder_root.to_hex())?;
+            }
+            Err(err) => {
+                writeln!(f, "  could not be parsed: {err}")?;
+                writeln!(f, "  raw bytes: {}", proof.commitments.to_hex())?;
+            }
+        }
+
+        // FRI proof structure
+        writeln!(f, "FRI:")?;
+        writeln!(
+            f,
+            "  {} layers, {} partitions",
+            num_fri_layers,
+            proof.fri_proof.num_partitions()
+        )?;
+        let mut domain_size = lde_domain_size;
+        for (i, layer) in proof.fri_proof.layers().iter().enumerate() {
+            let folding_factor = fri_options.folding_factor_at(i);
+            let num_values = layer.values_bytes().len() / element_size;
+            writeln!(
+                f,
+                "  layer {i}: domain size {}, {} queried cosets, {} bytes ({} value bytes, {} \
+                path bytes)",
+                domain_size,
+                num_values / folding_factor,
+                layer.size(),
+                layer.values_bytes().len(),
+                layer.paths_bytes().len()
+            )?;
+            domain_size /= folding_factor;
+        }
+        writeln!(
+            f,
+            "  remainder: domain size {}, {} elements, {} bytes",
+            domain_size,
+            proof.fri_proof.remainder_bytes().len() / element_size,
+            proof.fri_proof.remainder_bytes().len()
+        )?;
+        write!(f, "Proof-of-work nonce: {}", proof.pow_nonce)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes commitments and the structure of the STIR proof of the specified STARK proof into the
+/// formatter.
+fn describe_stir_proof<H: Hasher>(
+    f: &mut fmt::Formatter<'_>,
+    proof: &StarkProof,
+    stir_proof: &StirProof,
+    element_size: usize,
+) -> fmt::Result {
+    // commitments
+    writeln!(f, "Commitments:")?;
+    match proof
+        .commitments
+        .clone()
+        .parse_without_fri::<H>(proof.trace_layout().num_segments())
+    {
+        Ok((trace_roots, constraint_root)) => {
+            for (i, root) in trace_roots.iter().enumerate() {
+                writeln!(f, "  {} trace: {}", segment_name(i), root.to_hex())?;
+            }
+            writeln!(f, "  constraint evaluations: {}", constraint_root.to_hex())?;
+        }
+        Err(err) => {
+            writeln!(f, "  could not be parsed: {err}")?;
+            writeln!(f, "  raw bytes: {}", proof.commitments.to_hex())?;
+        }
+    }
+    match stir_proof.parse_commitments::<H>() {
+        Ok(roots) => {
+            for (i, root) in roots.iter().enumerate() {
+                writeln!(f, "  STIR round {i}: {}", root.to_hex())?;
+            }
+        }
+        Err(err) => writeln!(f, "  STIR rounds could not be parsed: {err}")?,
+    }
+
+    // STIR proof structure
+    writeln!(f, "STIR:")?;
+    writeln!(f, "  {} rounds", stir_proof.num_rounds())?;
+    for (i, round) in stir_proof.rounds().iter().enumerate() {
+        writeln!(
+            f,
+            "  round {i}: {} bytes ({} value bytes, {} path bytes)",
+            round.size(),
+            round.values_bytes().len(),
+            round.paths_bytes().len()
+        )?;
+    }
+    if let Some(queries) = stir_proof.input_queries() {
+        writeln!(
+            f,
+            "  input queries: {} bytes ({} value bytes, {} path bytes)",
+            queries.size(),
+            queries.values_bytes().len(),
+            queries.paths_bytes().len()
+        )?;
+    }
+    writeln!(
+        f,
+        "  final polynomial: {} coefficients, {} bytes",
+        stir_proof.final_poly_bytes().len() / element_size,
+        stir_proof.final_poly_bytes().len()
+    )
+}
+
+/// Returns the name of the trace segment at the specified index.
+fn segment_name(segment_idx: usize) -> String {
+    match segment_idx {
+        0 => "main".into(),
+        i => format!("auxiliary segment {i}"),
+    }
+}
+
+/// Describes the size of the specified queries given the number of bytes in each queried row.
+fn describe_queries(queries: &Queries, row_size: usize) -> String {
+    let num_rows = queries
+        .values_bytes()
+        .len()
+        .checked_div(row_size)
+        .unwrap_or(0);
+    let salts = if queries.is_salted() {
+        format!(", {} salt bytes", queries.salts_bytes().len())
+    } else {
+        String::new()
+    };
+    format!(
+        "{} bytes ({} queried rows, {} value bytes, {} path bytes{})",
+        queries.to_bytes().len(),
+        num_rows,
+        queries.values_bytes().len(),
+        queries.paths_bytes().len(),
+        salts
+    )
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{get_security_level, read_context, read_format_version, Commitments, Context};
+use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crypto::Hasher;
+use utils::{Deserializable, DeserializationError, SliceReader};
+
+// PROOF HEADER
+// ================================================================================================
+/// Header of a serialized STARK proof.
+///
+/// The header consists of the proof context and the commitments made by the prover; it is
+/// located at the start of a serialized proof, and thus, it can be read without deserializing
+/// query decommitments, out-of-domain evaluations, and the FRI proof (see
+/// [StarkProof::read_context()](super::StarkProof::read_context)). This makes it possible to
+/// inspect basic properties of a proof (e.g., to check whether the proof satisfies a given
+/// security policy) much more cheaply than by deserializing the entire proof.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofHeader {
+    context: Context,
+    commitments: Commitments,
+}
+
+impl ProofHeader {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Reads a proof header from the start of the specified serialized proof.
+    ///
+    /// Bytes following the header are not read.
+    pub(super) fn read(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let version = read_format_version(&mut source)?;
+        let context = read_context(&mut source, version)?;
+        let commitments = Commitments::read_from(&mut source)?;
+        Ok(ProofHeader {
+            context,
+            commitments,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the context of the proof.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns commitments made by the prover during the commit phase of the protocol.
+    ///
+    /// The commitments are not parsed into digests; this can be done via
+    /// [Commitments::parse()] once the hash function used by the proof is known.
+    pub fn commitments(&self) -> &Commitments {
+        &self.commitments
+    }
+
+    /// Returns STARK protocol parameters used to generate the proof.
+    pub fn options(&self) -> &ProofOptions {
+        self.context.options()
+    }
+
+    /// Returns a layout describing how columns of the execution trace are arranged into
+    /// segments.
+    pub fn trace_layout(&self) -> &TraceLayout {
+        self.context.trace_layout()
+    }
+
+    /// Returns trace info for the computation described by the proof.
+    pub fn get_trace_info(&self) -> TraceInfo {
+        self.context.get_trace_info()
+    }
+
+    /// Returns security level of the proof (in bits).
+    ///
+    /// This returns the same value as
+    /// [StarkProof::security_level()](super::StarkProof::security_level) would return for the
+    /// full proof.
+    ///
+    /// # Panics
+    /// Panics if `conjectured` is false and the `std` feature is not enabled.
+    pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
+        get_security_level::<H>(&self.context, conjectured)
+    }
+}
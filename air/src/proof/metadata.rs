@@ -0,0 +1,261 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::cbor;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// PROOF METADATA
+// ================================================================================================
+/// Provenance information attached to a STARK proof.
+///
+/// Metadata contains the version of the prover which generated the proof, the time at which the
+/// proof was generated, and an arbitrary list of user-defined key-value pairs. Metadata does not
+/// affect validity of the proof, and can be either:
+/// * Unbound (the default) - in this case, metadata is carried in the proof as is, and can be
+///   modified by anyone without invalidating the proof.
+/// * Bound - in this case, a hash of the serialized metadata is absorbed into the public coin
+///   right after the coin is instantiated. Thus, a proof verifies successfully only if the
+///   metadata has not been modified after the proof was generated.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofMetadata {
+    prover_version: String,
+    timestamp: u64,
+    entries: Vec<(String, String)>,
+    bound: bool,
+}
+
+impl ProofMetadata {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns new unbound metadata with the specified prover version and creation timestamp.
+    ///
+    /// The timestamp is not interpreted in any way, though it is expected to be a number of
+    /// seconds since the Unix epoch.
+    ///
+    /// # Panics
+    /// Panics if `prover_version` is longer than 65535 bytes.
+    pub fn new(prover_version: &str, timestamp: u64) -> Self {
+        assert!(
+            prover_version.len() <= u16::MAX as usize,
+            "prover version cannot be longer than {} bytes",
+            u16::MAX
+        );
+        ProofMetadata {
+            prover_version: prover_version.to_string(),
+            timestamp,
+            entries: Vec::new(),
+            bound: false,
+        }
+    }
+
+    /// Appends the specified key-value pair to this metadata and returns the updated metadata.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * An entry with the same key already exists in this metadata.
+    /// * This metadata already contains 65535 entries.
+    /// * Either `key` or `value` is longer than 65535 bytes.
+    pub fn with_entry(mut self, key: &str, value: &str) -> Self {
+        assert!(
+            self.get(key).is_none(),
+            "metadata entry with key {key} already exists"
+        );
+        assert!(
+            self.entries.len() < u16::MAX as usize,
+            "metadata cannot contain more than {} entries",
+            u16::MAX
+        );
+        assert!(
+            key.len() <= u16::MAX as usize && value.len() <= u16::MAX as usize,
+            "metadata keys and values cannot be longer than {} bytes",
+            u16::MAX
+        );
+        self.entries.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Marks this metadata as bound to the proof and returns the updated metadata.
+    ///
+    /// Bound metadata is absorbed into the public coin, and thus, cannot be modified without
+    /// invalidating the proof.
+    pub fn bind(mut self) -> Self {
+        self.bound = true;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the version of the prover which generated the proof.
+    pub fn prover_version(&self) -> &str {
+        &self.prover_version
+    }
+
+    /// Returns the time at which the proof was generated.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns user-defined key-value pairs of this metadata in the order in which they were
+    /// added.
+    pub fn entries(&self) -> &[(String, String)] {
+        &self.entries
+    }
+
+    /// Returns the value of the entry with the specified key, or None if this metadata does not
+    /// contain such entry.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns true if this metadata is bound to the proof.
+    pub fn is_bound(&self) -> bool {
+        self.bound
+    }
+
+    // CBOR ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this metadata into the `target` as a CBOR array.
+    pub(super) fn write_cbor<W: ByteWriter>(&self, target: &mut W) {
+        cbor::write_array_header(target, 4);
+        cbor::write_uint(target, self.bound as u64);
+        cbor::write_text(target, &self.prover_version);
+        cbor::write_uint(target, self.timestamp);
+        cbor::write_array_header(target, self.entries.len());
+        for (key, value) in self.entries.iter() {
+            cbor::write_array_header(target, 2);
+            cbor::write_text(target, key);
+            cbor::write_text(target, value);
+        }
+    }
+
+    /// Reads metadata encoded as a CBOR array from the `source`.
+    pub(super) fn read_cbor<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        cbor::read_struct_header(source, 4, "proof metadata")?;
+        let bound = cbor::read_bounded_uint(source, 1, "metadata binding flag")? == 1;
+        let prover_version = cbor::read_text(source)?;
+        let timestamp = cbor::read_uint(source)?;
+
+        let num_entries = cbor::read_array_header(source)?;
+        if num_entries > u16::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "metadata cannot contain more than {} entries, but was {num_entries}",
+                u16::MAX
+            )));
+        }
+        let mut entries = Vec::new();
+        for _ in 0..num_entries {
+            cbor::read_struct_header(source, 2, "metadata entry")?;
+            entries.push((cbor::read_text(source)?, cbor::read_text(source)?));
+        }
+
+        Self::from_parts(prover_version, timestamp, entries, bound)
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds metadata from deserialized parts making sure the parts satisfy the same
+    /// constraints as the ones enforced by the constructors.
+    fn from_parts(
+        prover_version: String,
+        timestamp: u64,
+        entries: Vec<(String, String)>,
+        bound: bool,
+    ) -> Result<Self, DeserializationError> {
+        let too_long = |value: &String| value.len() > u16::MAX as usize;
+        if too_long(&prover_version)
+            || entries.len() > u16::MAX as usize
+            || entries
+                .iter()
+                .any(|(key, value)| too_long(key) || too_long(value))
+        {
+            return Err(DeserializationError::InvalidValue(
+                "metadata exceeds maximum allowed size".to_string(),
+            ));
+        }
+        for (i, (key, _)) in entries.iter().enumerate() {
+            if entries[..i].iter().any(|(other, _)| other == key) {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "metadata entry with key {key} is duplicated"
+                )));
+            }
+        }
+
+        Ok(ProofMetadata {
+            prover_version,
+            timestamp,
+            entries,
+            bound,
+        })
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ProofMetadata {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.bound as u8);
+        write_string(target, &self.prover_version);
+        target.write_u64(self.timestamp);
+        target.write_u16(self.entries.len() as u16);
+        for (key, value) in self.entries.iter() {
+            write_string(target, key);
+            write_string(target, value);
+        }
+    }
+}
+
+impl Deserializable for ProofMetadata {
+    /// Reads proof metadata from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid metadata could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bound = match source.read_u8()? {
+            0 => false,
+            1 => true,
+            value => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "metadata binding flag must be 0 or 1, but was {value}"
+                )))
+            }
+        };
+        let prover_version = read_string(source)?;
+        let timestamp = source.read_u64()?;
+
+        // each entry takes at least 4 bytes (for lengths of the key and the value); making sure
+        // the source contains these bytes bounds the amount of memory allocated for the entries
+        let num_entries = source.read_u16()? as usize;
+        source.check_eor(num_entries * 4)?;
+        let mut entries = Vec::with_capacity(num_entries);
+        for _ in 0..num_entries {
+            entries.push((read_string(source)?, read_string(source)?));
+        }
+
+        Self::from_parts(prover_version, timestamp, entries, bound)
+    }
+}
+
+fn write_string<W: ByteWriter>(target: &mut W, value: &str) {
+    target.write_u16(value.len() as u16);
+    target.write_bytes(value.as_bytes());
+}
+
+fn read_string<R: ByteReader>(source: &mut R) -> Result<String, DeserializationError> {
+    let len = source.read_u16()? as usize;
+    let bytes = source.read_vec(len)?;
+    String::from_utf8(bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+}
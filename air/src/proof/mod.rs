@@ -5,34 +5,92 @@
 
 //! Contains STARK proof struct and associated components.
 
-use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crate::{Air, FieldExtension, LdtKind, ProofOptions, SecurityEstimate, TraceInfo, TraceLayout};
 use core::cmp;
-use crypto::Hasher;
-use fri::FriProof;
+use crypto::{BatchMerkleProof, Digest, Hasher, MerkleMultiProof};
+use fri::{stir::StirProof, FriProof};
+use math::{
+    fields::{CubeExtension, QuadExtension, QuartExtension},
+    FieldElement,
+};
 use utils::{
-    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+    collections::Vec, hex, string::String, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, SliceReader,
 };
 
 mod context;
 pub use context::Context;
 
 mod commitments;
-pub use commitments::Commitments;
+pub use commitments::{Commitments, CommitmentsRef};
 
 mod queries;
-pub use queries::Queries;
+pub use queries::{Queries, QueriesRef};
 
 mod ood_frame;
-pub use ood_frame::OodFrame;
+pub use ood_frame::{OodFrame, OodFrameRef};
 
 mod table;
 pub use table::Table;
 
+mod metadata;
+pub use metadata::ProofMetadata;
+
+mod header;
+pub use header::ProofHeader;
+
+mod view;
+pub use view::StarkProofRef;
+
+mod cbor;
+
+mod compression;
+pub use compression::CompressionAlgorithm;
+
+mod stone;
+pub use stone::AnnotatedProof;
+
+mod description;
+pub use description::ProofDescription;
+
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
+/// CDDL schema (see [RFC 8610](https://www.rfc-editor.org/rfc/rfc8610)) describing the structure
+/// of proofs encoded via [StarkProof::to_cbor()].
+pub const CDDL_SCHEMA: &str = include_str!("../../schema/proof.cddl");
+
 const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 
+/// Version of the binary proof format produced by [StarkProof::to_bytes()].
+pub const PROOF_FORMAT_VERSION: u8 = 4;
+
+/// Oldest version of the binary proof format which can be read by [StarkProof::from_bytes()].
+///
+/// Version 0 refers to the original format which did not include an explicit version header.
+/// Version 1 added the version header, version 2 added the optional metadata section (see
+/// [ProofMetadata]), version 3 added salts of queried leaves to trace and constraint queries
+/// (see [Queries]), and version 4 added extensions of the proof options encoding (i.e., FRI
+/// folding schedules, bit-slice query sampling, STIR, multiple out-of-domain points, FRI layer
+/// grinding, and Merkle multi-proofs). Proofs serialized using versions 0 and 1 cannot carry metadata, proofs
+/// serialized using versions 0 through 2 cannot have salted commitments, and proofs serialized
+/// using versions 0 through 3 cannot use any of the proof options extensions.
+pub const MIN_PROOF_FORMAT_VERSION: u8 = 0;
+
+/// Oldest version of the binary proof format in which proof options can use extensions of the
+/// original proof options encoding.
+const OPTIONS_EXTENSIONS_FORMAT_VERSION: u8 = 4;
+
+/// Marker byte which precedes the format version in proofs serialized using version 1 or later.
+///
+/// Proofs serialized using version 0 of the format start with the width of the main trace
+/// segment, and since this width can never be zero, the marker unambiguously distinguishes
+/// versioned proofs from the original ones.
+const VERSION_MARKER: u8 = 0;
+
 // STARK PROOF
 // ================================================================================================
 /// A proof generated by Winterfell prover.
@@ -43,10 +101,18 @@ const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 ///
 /// A proof can be serialized into a sequence of bytes using [to_bytes()](StarkProof::to_bytes)
 /// function, and deserialized from a sequence of bytes using [from_bytes()](StarkProof::from_bytes)
-/// function.
+/// function. Alternatively, a proof can be encoded as CBOR using [to_cbor()](StarkProof::to_cbor)
+/// function; the structure of the CBOR encoding is described by [CDDL_SCHEMA], and thus, the
+/// encoded proof can be parsed by tools which are not aware of Winterfell's binary format. A proof
+/// can also be converted into the annotated proof layout used by StarkWare's stone toolchain
+/// using [to_annotated()](StarkProof::to_annotated) function.
 ///
 /// To estimate soundness of a proof (in bits), [security_level()](StarkProof::security_level)
-/// function can be used.
+/// function can be used. The size of a proof can be predicted before the proof is generated
+/// using [estimate_size()](StarkProof::estimate_size) function.
+///
+/// When a proof only needs to be verified, a [StarkProofRef] view which borrows most of the
+/// proof data directly from the serialized bytes can be used instead of an owned proof.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct StarkProof {
     /// Basic metadata about the execution of the computation described by this proof.
@@ -61,10 +127,16 @@ pub struct StarkProof {
     pub constraint_queries: Queries,
     /// Trace and constraint polynomial evaluations at an out-of-domain point.
     pub ood_frame: OodFrame,
-    /// Low-degree proof for a DEEP composition polynomial.
+    /// Low-degree proof for a DEEP composition polynomial generated via FRI; if the proof options
+    /// select [LdtKind::Stir], this is an empty (default) FRI proof.
     pub fri_proof: FriProof,
+    /// Low-degree proof for a DEEP composition polynomial generated via STIR; this is set if and
+    /// only if the proof options select [LdtKind::Stir].
+    pub stir_proof: Option<StirProof>,
     /// Proof-of-work nonce for query seed grinding.
     pub pow_nonce: u64,
+    /// Optional provenance information about the proof.
+    pub metadata: Option<ProofMetadata>,
 }
 
 impl StarkProof {
@@ -101,86 +173,649 @@ impl StarkProof {
     /// When `conjectured` is true, conjectured security level is returned; otherwise, provable
     /// security level is returned. Usually, the number of queries needed for provable security is
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
-    /// security level.
+    /// security level (see [SecurityEstimate]).
     pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
-        if conjectured {
-            get_conjectured_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.trace_length() as u64,
-                H::COLLISION_RESISTANCE,
-            )
-        } else {
-            #[cfg(not(feature = "std"))]
-            panic!("proven security level is not available in no_std mode");
+        get_security_level::<H>(&self.context, conjectured)
+    }
 
-            #[cfg(feature = "std")]
-            get_proven_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.lde_domain_size() as u64,
-                self.trace_length() as u64,
-                H::COLLISION_RESISTANCE,
-            )
+    // SIZE ESTIMATION
+    // --------------------------------------------------------------------------------------------
+    /// Returns the expected size (in bytes) of a proof generated for the computation described by
+    /// `air` using the specified `options`.
+    ///
+    /// This can be used to predict the size of a proof without running the prover (e.g., to
+    /// budget calldata costs of on-chain verification while tuning blowup factor, number of
+    /// queries, or FRI folding factors). Trace layout and the number of constraint composition
+    /// columns are taken from `air`, while all protocol parameters are taken from `options`.
+    ///
+    /// The estimate assumes that all commitments are Merkle trees built with hash function `H`,
+    /// and that queried trace rows are not salted; proof metadata is not included. See
+    /// [FriProof::estimated_size()] for how the size of the FRI proof is estimated, and
+    /// [StirProof::estimated_size()] for proofs which use STIR as the low-degree test.
+    ///
+    /// # Panics
+    /// Panics if STIR is selected as the low-degree test and the LDE domain of the computation is
+    /// too small for the folding factor or blowup factor specified by `options`.
+    pub fn estimate_size<A: Air, H: Hasher>(air: &A, options: &ProofOptions) -> usize {
+        // static dispatch for the extension field parameter
+        match options.field_extension() {
+            FieldExtension::None => estimate_proof_size::<A, A::BaseField, H>(air, options),
+            FieldExtension::Quadratic => {
+                estimate_proof_size::<A, QuadExtension<A::BaseField>, H>(air, options)
+            }
+            FieldExtension::Cubic => {
+                estimate_proof_size::<A, CubeExtension<A::BaseField>, H>(air, options)
+            }
+            FieldExtension::Quartic => {
+                estimate_proof_size::<A, QuartExtension<A::BaseField>, H>(air, options)
+            }
+        }
+    }
+
+    /// Returns a human-readable breakdown of this proof (see [ProofDescription]); the breakdown
+    /// is produced when the returned value is formatted (e.g., via `println!("{}", ...)`).
+    ///
+    /// Commitments in this proof are expected to be digests produced by hash function `H`; this
+    /// is needed to print each commitment separately and to compute security level of the proof.
+    pub fn describe<H: Hasher>(&self) -> ProofDescription<'_, H> {
+        ProofDescription::new(self)
+    }
+
+    // VIEW
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a view of this proof which borrows commitments, query decommitments, out-of-domain
+    /// evaluations, and the FRI proof from `self`.
+    pub fn view(&self) -> StarkProofRef<'_> {
+        StarkProofRef {
+            context: self.context.clone(),
+            commitments: self.commitments.view(),
+            trace_queries: self.trace_queries.iter().map(Queries::view).collect(),
+            constraint_queries: self.constraint_queries.view(),
+            ood_frame: self.ood_frame.view(),
+            fri_proof: self.fri_proof.view(),
+            stir_proof: self.stir_proof.clone(),
+            pow_nonce: self.pow_nonce,
+            metadata: self.metadata.clone(),
         }
     }
 
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
-    /// Serializes this proof into a vector of bytes.
+    /// Serializes this proof into a vector of bytes using the latest version of the binary proof
+    /// format (i.e., [PROOF_FORMAT_VERSION]).
     pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_with_version(PROOF_FORMAT_VERSION)
+    }
+
+    /// Serializes this proof into a vector of bytes using the specified version of the binary
+    /// proof format.
+    ///
+    /// This can be used to produce proofs for verifiers which have not yet been upgraded to the
+    /// latest version of the format.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `version` is smaller than [MIN_PROOF_FORMAT_VERSION] or greater than
+    ///   [PROOF_FORMAT_VERSION].
+    /// * This proof contains metadata and `version` is smaller than 2.
+    /// * Trace or constraint queries of this proof are salted and `version` is smaller than 3.
+    /// * This proof was generated with options which use a FRI folding schedule, bit-slice query
+    ///   sampling, STIR, multiple out-of-domain points, FRI layer grinding, or Merkle
+    ///   multi-proofs, and `version` is smaller than 4.
+    pub fn to_bytes_with_version(&self, version: u8) -> Vec<u8> {
         let mut result = Vec::new();
-        self.context.write_into(&mut result);
-        self.commitments.write_into(&mut result);
-        self.trace_queries.write_into(&mut result);
-        self.constraint_queries.write_into(&mut result);
-        self.ood_frame.write_into(&mut result);
-        self.fri_proof.write_into(&mut result);
-        result.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        self.write_into_with_version(&mut result, version);
         result
     }
 
+    /// Serializes this proof using the current version of the binary proof format and writes
+    /// the resulting bytes into the `target`.
+    ///
+    /// This produces the same bytes as [to_bytes()](StarkProof::to_bytes) function, but allows
+    /// writing the proof directly into any [ByteWriter] (e.g., a file via `utils::IoWriter` when
+    /// `std` feature is enabled).
+    pub fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into_with_version(target, PROOF_FORMAT_VERSION)
+    }
+
+    /// Serializes this proof using the specified version of the binary proof format and writes
+    /// the resulting bytes into the `target`.
+    fn write_into_with_version<W: ByteWriter>(&self, target: &mut W, version: u8) {
+        assert!(
+            (MIN_PROOF_FORMAT_VERSION..=PROOF_FORMAT_VERSION).contains(&version),
+            "proof format version must be between {MIN_PROOF_FORMAT_VERSION} and {PROOF_FORMAT_VERSION}, but was {version}"
+        );
+        assert!(
+            version >= 2 || self.metadata.is_none(),
+            "proof metadata cannot be serialized using proof format version {version}"
+        );
+        assert!(
+            version >= OPTIONS_EXTENSIONS_FORMAT_VERSION || !self.options().has_extensions(),
+            "proof options extensions cannot be serialized using proof format version {version}"
+        );
+
+        if version > 0 {
+            target.write_u8(VERSION_MARKER);
+            target.write_u8(version);
+        }
+        self.context.write_into(target);
+        self.commitments.write_into(target);
+        for queries in self.trace_queries.iter() {
+            queries.write_into_with_version(target, version);
+        }
+        self.constraint_queries
+            .write_into_with_version(target, version);
+        self.ood_frame.write_into(target);
+        match &self.stir_proof {
+            Some(stir_proof) => stir_proof.write_into(target),
+            None => self.fri_proof.write_into(target),
+        }
+        target.write_u64(self.pow_nonce);
+        if version >= 2 {
+            match &self.metadata {
+                Some(metadata) => {
+                    target.write_u8(1);
+                    metadata.write_into(target);
+                }
+                None => target.write_u8(0),
+            }
+        }
+    }
+
     /// Returns a STARK proof read from the specified `source`.
     ///
+    /// Proofs serialized using any version of the binary proof format between
+    /// [MIN_PROOF_FORMAT_VERSION] and [PROOF_FORMAT_VERSION] can be read.
+    ///
     /// # Errors
-    /// Returns an error of a valid STARK proof could not be read from the specified `source`.
+    /// Returns an error if:
+    /// * The proof was serialized using an unsupported version of the proof format; in this case
+    ///   [DeserializationError::UnsupportedVersion] is returned.
+    /// * A valid STARK proof could not be read from the specified `source`.
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
         let mut source = SliceReader::new(source);
+        let proof = Self::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(proof)
+    }
+
+    /// Reads a STARK proof from the specified `source` and returns the result.
+    ///
+    /// This accepts the same proofs as [from_bytes()](StarkProof::from_bytes) function, but allows
+    /// reading a proof from any [ByteReader] (e.g., a file via `utils::IoReader` when `std`
+    /// feature is enabled). Unlike [from_bytes()](StarkProof::from_bytes), this function does not
+    /// require the `source` to be fully consumed; bytes following the proof are left unread.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The proof was serialized using an unsupported version of the proof format; in this case
+    ///   [DeserializationError::UnsupportedVersion] is returned.
+    /// * A valid STARK proof could not be read from the specified `source`.
+    pub fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // read and validate the format version; all currently supported versions share the same
+        // layout for the rest of the proof, except for the metadata section which is present
+        // only in version 2 and later, salts of queried leaves which are present only in
+        // version 3 and later, and proof options extensions which are allowed only in version 4
+        // and later
+        let version = read_format_version(source)?;
 
         // parse the context
-        let context = Context::read_from(&mut source)?;
+        let context = read_context(source, version)?;
 
         // parse the commitments
-        let commitments = Commitments::read_from(&mut source)?;
+        let commitments = Commitments::read_from(source)?;
 
         // parse trace queries
         let num_trace_segments = context.trace_layout().num_segments();
         let mut trace_queries = Vec::with_capacity(num_trace_segments);
         for _ in 0..num_trace_segments {
-            trace_queries.push(Queries::read_from(&mut source)?);
+            trace_queries.push(Queries::read_from_with_version(source, version)?);
         }
 
-        // parse the rest of the proof
-        let proof = StarkProof {
+        // parse the rest of the proof; the low-degree proof is a STIR proof if the options select
+        // STIR, and a FRI proof otherwise
+        let constraint_queries = Queries::read_from_with_version(source, version)?;
+        let ood_frame = OodFrame::read_from(source)?;
+        let (fri_proof, stir_proof) = match context.options().ldt() {
+            LdtKind::Fri => (FriProof::read_from(source)?, None),
+            LdtKind::Stir => (FriProof::default(), Some(StirProof::read_from(source)?)),
+        };
+        Ok(StarkProof {
             context,
             commitments,
             trace_queries,
-            constraint_queries: Queries::read_from(&mut source)?,
-            ood_frame: OodFrame::read_from(&mut source)?,
-            fri_proof: FriProof::read_from(&mut source)?,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            stir_proof,
             pow_nonce: source.read_u64()?,
+            metadata: if version >= 2 {
+                read_metadata(source)?
+            } else {
+                None
+            },
+        })
+    }
+
+    /// Returns true if the specified `source` contains the canonical binary encoding of a STARK
+    /// proof.
+    ///
+    /// An encoding is canonical if the proof can be read from it via
+    /// [from_bytes()](StarkProof::from_bytes), and serializing the resulting proof via
+    /// [to_bytes()](StarkProof::to_bytes) yields exactly the same bytes. Thus, every proof has
+    /// exactly one canonical encoding, and proofs serialized using older versions of the proof
+    /// format (see [MIN_PROOF_FORMAT_VERSION]) are not canonical.
+    ///
+    /// This can be used to make sure that the bytes of a proof can be used to identify the proof
+    /// (e.g., for content-addressed storage of proofs).
+    pub fn is_canonical(source: &[u8]) -> bool {
+        match Self::from_bytes(source) {
+            Ok(proof) => proof.to_bytes() == source,
+            Err(_) => false,
+        }
+    }
+
+    /// Serializes this proof into a lowercase hex string prefixed with `0x`.
+    ///
+    /// The string encodes the same bytes as would be returned by
+    /// [to_bytes()](StarkProof::to_bytes) function (see [utils::hex] module for details).
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.to_bytes())
+    }
+
+    /// Returns a STARK proof read from the specified hex string.
+    ///
+    /// The `source` is expected to encode bytes of a proof serialized via
+    /// [to_bytes()](StarkProof::to_bytes) function; both, prefixed and unprefixed strings are
+    /// accepted.
+    ///
+    /// # Errors
+    /// Returns an error if the `source` is not a valid hex string, or if a valid STARK proof
+    /// could not be read from the decoded bytes.
+    pub fn from_hex(source: &str) -> Result<Self, DeserializationError> {
+        Self::from_bytes(&hex::decode(source)?)
+    }
+
+    /// Returns the version of the binary proof format used to serialize the proof in the
+    /// specified `source`.
+    ///
+    /// Only the header of the proof is read, and thus, this can be used to determine whether a
+    /// proof can be read before attempting to deserialize it.
+    ///
+    /// # Errors
+    /// Returns an error if the header of the proof could not be read, or if the proof was
+    /// serialized using an unsupported version of the proof format.
+    pub fn read_format_version(source: &[u8]) -> Result<u8, DeserializationError> {
+        read_format_version(&mut SliceReader::new(source))
+    }
+
+    /// Returns the header of the serialized proof in the specified `source`.
+    ///
+    /// Only the context and the commitments of the proof are read; query decommitments,
+    /// out-of-domain evaluations, and the FRI proof are not parsed. The returned [ProofHeader]
+    /// provides access to trace info, protocol parameters, and security level of the proof, and
+    /// thus, can be used to check whether a proof should be processed before deserializing it in
+    /// full.
+    ///
+    /// Since the rest of the proof is not read, a successfully read header does not imply that
+    /// [from_bytes()](StarkProof::from_bytes) would succeed for the same `source`.
+    ///
+    /// # Errors
+    /// Returns an error if the proof format version, the context, or the commitments could not be
+    /// read from the `source`.
+    pub fn read_context(source: &[u8]) -> Result<ProofHeader, DeserializationError> {
+        ProofHeader::read(source)
+    }
+
+    /// Serializes this proof into a compressed vector of bytes.
+    ///
+    /// Repeated digests in Merkle authentication paths are de-duplicated first, and then the
+    /// result is compressed using the most efficient of the enabled compression algorithms (see
+    /// [CompressionAlgorithm]). If neither `zstd` nor `deflate` feature is enabled, only
+    /// de-duplication of digests is performed.
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        compression::compress(self, CompressionAlgorithm::default())
+    }
+
+    /// Serializes this proof into a vector of bytes compressed using the specified `algorithm`.
+    ///
+    /// Repeated digests in Merkle authentication paths are de-duplicated before the specified
+    /// compression algorithm is applied.
+    pub fn to_bytes_compressed_with(&self, algorithm: CompressionAlgorithm) -> Vec<u8> {
+        compression::compress(self, algorithm)
+    }
+
+    /// Returns a STARK proof read from the specified `source` which was produced by
+    /// [to_bytes_compressed()](StarkProof::to_bytes_compressed) or
+    /// [to_bytes_compressed_with()](StarkProof::to_bytes_compressed_with) functions.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `source` was compressed using an algorithm which is not enabled in this build.
+    /// * The decompressed proof would be larger than 64 MB.
+    /// * A valid STARK proof could not be read from the specified `source`.
+    pub fn from_bytes_compressed(source: &[u8]) -> Result<Self, DeserializationError> {
+        compression::decompress(source)
+    }
+
+    /// Serializes this proof into a vector of bytes using CBOR encoding.
+    ///
+    /// The proof is encoded as a CBOR array with the structure defined by [CDDL_SCHEMA]. All
+    /// data items are encoded in the shortest possible form, and thus, the encoding of a given
+    /// proof is deterministic.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        cbor::write_array_header(&mut result, 7 + self.metadata.is_some() as usize);
+        self.context.write_cbor(&mut result);
+        self.commitments.write_cbor(&mut result);
+        cbor::write_array_header(&mut result, self.trace_queries.len());
+        for queries in self.trace_queries.iter() {
+            queries.write_cbor(&mut result);
+        }
+        self.constraint_queries.write_cbor(&mut result);
+        self.ood_frame.write_cbor(&mut result);
+        match &self.stir_proof {
+            Some(stir_proof) => cbor::write_stir_proof(&mut result, stir_proof),
+            None => cbor::write_fri_proof(&mut result, &self.fri_proof),
+        }
+        cbor::write_uint(&mut result, self.pow_nonce);
+        if let Some(metadata) = &self.metadata {
+            metadata.write_cbor(&mut result);
+        }
+        result
+    }
+
+    /// Returns a STARK proof read from the specified CBOR-encoded `source`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `source` does not conform to the schema defined by [CDDL_SCHEMA].
+    /// * Any of the data items in the `source` is not encoded in the shortest possible form.
+    /// * The number of trace queries is not consistent with the trace layout of the proof.
+    /// * Not all bytes of the `source` have been consumed.
+    pub fn from_cbor(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let num_items = cbor::read_array_header(&mut source)?;
+        if num_items != 7 && num_items != 8 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "STARK proof must be encoded as an array of 7 or 8 items, but was {num_items} items"
+            )));
+        }
+
+        // parse the context
+        let context = Context::read_cbor(&mut source)?;
+
+        // parse the commitments
+        let commitments = Commitments::read_cbor(&mut source)?;
+
+        // parse trace queries
+        let num_trace_segments = context.trace_layout().num_segments();
+        let num_trace_queries = cbor::read_array_header(&mut source)?;
+        if num_trace_queries != num_trace_segments {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected trace queries for {num_trace_segments} trace segments, but was {num_trace_queries}"
+            )));
+        }
+        let mut trace_queries = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            trace_queries.push(Queries::read_cbor(&mut source)?);
+        }
+
+        // parse the rest of the proof; the low-degree proof is a STIR proof if the options select
+        // STIR, and a FRI proof otherwise
+        let constraint_queries = Queries::read_cbor(&mut source)?;
+        let ood_frame = OodFrame::read_cbor(&mut source)?;
+        let (fri_proof, stir_proof) = match context.options().ldt() {
+            LdtKind::Fri => (cbor::read_fri_proof(&mut source)?, None),
+            LdtKind::Stir => (
+                FriProof::default(),
+                Some(cbor::read_stir_proof(&mut source)?),
+            ),
+        };
+        let proof = StarkProof {
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            stir_proof,
+            pow_nonce: cbor::read_uint(&mut source)?,
+            metadata: if num_items == 8 {
+                Some(ProofMetadata::read_cbor(&mut source)?)
+            } else {
+                None
+            },
         };
         if source.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
         Ok(proof)
     }
+
+    /// Converts this proof into the annotated proof layout used by StarkWare's stone prover and
+    /// verifier (see [AnnotatedProof]).
+    ///
+    /// Commitments in this proof are expected to be digests produced by hash function `H`; this
+    /// is needed to annotate each commitment separately.
+    ///
+    /// # Errors
+    /// Returns an error if commitments of this proof could not be parsed into digests of `H`, if
+    /// the number of trace queries is not consistent with the trace layout of the proof, or if
+    /// trace or constraint commitments of this proof are salted. Proofs which use STIR as the
+    /// low-degree test cannot be annotated either.
+    pub fn to_annotated<H: Hasher>(&self) -> Result<AnnotatedProof, DeserializationError> {
+        AnnotatedProof::new::<H>(self)
+    }
+
+    /// Returns a STARK proof reconstructed from the specified annotated proof.
+    ///
+    /// The proof can be reconstructed only if the annotated proof was produced by
+    /// [to_annotated()](StarkProof::to_annotated) function (possibly after being written into a
+    /// JSON file via [AnnotatedProof::to_json()]), since proofs generated by stone's prover use a
+    /// different protocol.
+    ///
+    /// # Errors
+    /// Returns an error if any of the prover annotations is malformed or does not describe a part
+    /// of a Winterfell proof, if the annotations do not cover all proof bytes, or if any of the
+    /// required parts of the proof is missing.
+    pub fn from_annotated(proof: &AnnotatedProof) -> Result<Self, DeserializationError> {
+        proof.to_stark_proof()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StarkProof {
+    /// Serializes the proof into the same bytes as produced by [StarkProof::to_bytes()]; for
+    /// human-readable formats (e.g., JSON), the bytes are encoded as a hex string.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StarkProof {
+    /// Deserializes a proof serialized via [serde::Serialize] implementation for [StarkProof];
+    /// the underlying bytes are parsed using [StarkProof::from_bytes()].
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = utils::serde_compat::deserialize_bytes(deserializer)?;
+        Self::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Reads the version header of a serialized proof from the `source` and returns the version of
+/// the proof format.
+fn read_format_version<R: ByteReader>(source: &mut R) -> Result<u8, DeserializationError> {
+    if source.peek_u8()? != VERSION_MARKER {
+        return Ok(0);
+    }
+    source.read_u8()?;
+
+    let version = source.read_u8()?;
+    if version == 0 || version > PROOF_FORMAT_VERSION {
+        return Err(DeserializationError::UnsupportedVersion(version));
+    }
+    Ok(version)
+}
+
+/// Reads the context of a proof serialized using the specified version of the proof format from
+/// the `source`.
+///
+/// # Errors
+/// Returns an error if a valid context could not be read from the `source`, or if proof options
+/// in the context use extensions of the options encoding not supported by the specified version.
+fn read_context<R: ByteReader>(
+    source: &mut R,
+    version: u8,
+) -> Result<Context, DeserializationError> {
+    let context = Context::read_from(source)?;
+    if version < OPTIONS_EXTENSIONS_FORMAT_VERSION && context.options().has_extensions() {
+        return Err(DeserializationError::InvalidValue(format!(
+            "proof options extensions cannot be used in proof format version {version}"
+        )));
+    }
+    Ok(context)
+}
+
+/// Reads the optional metadata section of a proof from the `source`; the section consists of a
+/// byte indicating whether metadata is present, followed by the serialized metadata (if any).
+fn read_metadata<R: ByteReader>(
+    source: &mut R,
+) -> Result<Option<ProofMetadata>, DeserializationError> {
+    match source.read_u8()? {
+        0 => Ok(None),
+        1 => Ok(Some(ProofMetadata::read_from(source)?)),
+        value => Err(DeserializationError::InvalidValue(format!(
+            "metadata presence flag must be 0 or 1, but was {value}"
+        ))),
+    }
+}
+
+/// Estimates the size (in bytes) of a proof for the specified AIR and proof options when the
+/// proof is generated in field `E`.
+fn estimate_proof_size<A, E, H>(air: &A, options: &ProofOptions) -> usize
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: Hasher,
+{
+    let trace_info = air.trace_info();
+    let layout = trace_info.layout();
+    let lde_domain_size = trace_info.length() * options.blowup_factor();
+    let num_queries = options.num_queries();
+    let fri_options = options.to_fri_options();
+    let digest_size = H::Digest::SIZE;
+
+    // +2 for version marker and format version
+    let context = Context::new::<A::BaseField>(trace_info, options.clone());
+    let mut result = 2 + context.to_bytes().len();
+
+    // trace commitments, constraint commitment, FRI layer commitments, and remainder commitment;
+    // STIR round commitments are a part of the STIR proof; +2 for the length of commitments
+    let num_ldt_commitments = match options.ldt() {
+        LdtKind::Fri => fri_options.num_fri_layers(lde_domain_size) + 1,
+        LdtKind::Stir => 0,
+    };
+    let num_commitments = layout.num_segments() + num_ldt_commitments + 1;
+    result += 2 + num_commitments * digest_size;
+
+    // trace and constraint queries; values in the main trace segment are in the base field, and
+    // all other values are in field E
+    let depth = lde_domain_size.trailing_zeros() as u8;
+    let paths_size = if options.merkle_multi_proofs() {
+        MerkleMultiProof::<H>::estimate_serialized_size(depth, num_queries, lde_domain_size)
+    } else {
+        BatchMerkleProof::<H>::estimate_serialized_size(depth, num_queries, lde_domain_size)
+    };
+    let ce_width = air.context().num_constraint_composition_columns();
+    let mut row_sizes = vec![layout.main_trace_width() * A::BaseField::ELEMENT_BYTES];
+    for i in 0..layout.num_aux_segments() {
+        row_sizes.push(layout.get_aux_segment_width(i) * E::ELEMENT_BYTES);
+    }
+    row_sizes.push(ce_width * E::ELEMENT_BYTES);
+    for row_size in row_sizes {
+        // +4 for the length of values, +4 for the length of paths, +4 for the length of salts
+        result += 4 + num_queries * row_size + 4 + paths_size + 4;
+    }
+
+    // out-of-domain frame: current and next trace rows (+1 for frame size) and constraint
+    // composition column evaluations for each out-of-domain point; +2 for the length of each part
+    let trace_width = layout.main_trace_width() + layout.aux_trace_width();
+    let num_ood_points = options.num_ood_points();
+    result += 2 + 1 + 2 * num_ood_points * trace_width * E::ELEMENT_BYTES;
+    result += 2 + num_ood_points * ce_width * E::ELEMENT_BYTES;
+
+    // low-degree proof; the first round of a STIR proof is queried at the same positions as the
+    // trace and constraint queries
+    result += match options.ldt() {
+        LdtKind::Fri => {
+            FriProof::estimated_size::<E, H>(&fri_options, lde_domain_size, num_queries)
+        }
+        LdtKind::Stir => StirProof::estimated_size::<E, H>(
+            &options.to_stir_options(),
+            lde_domain_size,
+            num_queries,
+        ),
+    };
+
+    // +8 for proof-of-work nonce, +1 for the metadata flag
+    result + 8 + 1
+}
+
+/// Computes security level (in bits) of a proof with the specified context.
+fn get_security_level<H: Hasher>(context: &Context, conjectured: bool) -> u32 {
+    let mode = if conjectured {
+        SecurityEstimate::Conjectured
+    } else {
+        SecurityEstimate::Proven
+    };
+    estimate_security_level::<H>(
+        context.options(),
+        context.num_modulus_bits(),
+        context.trace_length(),
+        mode,
+    )
+}
+
+/// Computes security level (in bits) of a proof generated with the specified options for a
+/// computation with the specified trace length in a field with the specified number of modulus
+/// bits.
+pub(crate) fn estimate_security_level<H: Hasher>(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    trace_length: usize,
+    mode: SecurityEstimate,
+) -> u32 {
+    match mode {
+        SecurityEstimate::Conjectured => get_conjectured_security(
+            options,
+            base_field_bits,
+            trace_length as u64,
+            H::COLLISION_RESISTANCE,
+        ),
+        SecurityEstimate::Proven => {
+            #[cfg(not(feature = "std"))]
+            panic!("proven security level is not available in no_std mode");
+
+            #[cfg(feature = "std")]
+            get_proven_security(
+                options,
+                base_field_bits,
+                (trace_length * options.blowup_factor()) as u64,
+                trace_length as u64,
+                H::COLLISION_RESISTANCE,
+            )
+        }
+    }
+}
+
 /// Computes conjectured security level for the specified proof parameters.
 fn get_conjectured_security(
     options: &ProofOptions,
@@ -227,7 +862,11 @@ fn get_proven_security(
     // agreement parameter `\alpha^+ := (1 + 1/2m)\sqrt{rho^+} := 1 - \theta^+`. The reason for
     // running FRI with a larger agreement parameter is to account for the simplified
     // DEEP composition polynomial. See Protocol 3 in https://eprint.iacr.org/2022/1216.
-    let blowup_plus_bits = ((lde_domain_size as f64) / (trace_domain_size as f64 + 2_f64)).log2();
+    // With k out-of-domain points, each trace polynomial is opened at 2k points, and thus,
+    // `\rho^+ := (trace_domain_size + 2k) / lde_domain_size`.
+    let num_openings = 2 * options.num_ood_points() as u64;
+    let blowup_plus_bits =
+        ((lde_domain_size as f64) / ((trace_domain_size + num_openings) as f64)).log2();
 
     // m is a parameter greater or equal to 3.
     // A larger m gives a worse field security bound but a better query security bound.
@@ -236,6 +875,7 @@ fn get_proven_security(
     // This sets m so that field security is equal to the best query security for any value
     // of m, unless the calculated value is less than 3 in which case it gets rounded up to 3.
     let mut m = extension_field_bits + 1.0;
+    m += options.fri_layer_grinding_factor() as f64;
     m -= options.grinding_factor() as f64;
     m -= 1.5 * blowup_bits;
     m -= 0.5 * num_fri_queries * blowup_plus_bits;
@@ -248,11 +888,13 @@ fn get_proven_security(
     // compute pre-FRI query security
     // this considers only the third component given in the corresponding part of eq. 20
     // in https://eprint.iacr.org/2021/582, i.e. (m+1/2)^7.n^2 / (2\rho^1.5.q) as all
-    // other terms are negligible in comparison.
-    let pre_query_security = (extension_field_bits + 1.0
-        - 3.0 / 2.0 * blowup_bits
-        - 2.0 * lde_size_bits
-        - 7.0 * (m + 0.5).log2()) as u32;
+    // other terms are negligible in comparison. this term bounds the soundness error of the FRI
+    // commit phase, and thus, a proof-of-work for every FRI layer adds its bits to it.
+    let pre_query_security =
+        (extension_field_bits + 1.0 + options.fri_layer_grinding_factor() as f64
+            - 3.0 / 2.0 * blowup_bits
+            - 2.0 * lde_size_bits
+            - 7.0 * (m + 0.5).log2()) as u32;
 
     // compute security we get by executing multiple query rounds
     let security_per_query = 0.5 * blowup_plus_bits - (1.0 + 1.0 / (2.0 * m)).log2();
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::cbor;
 use math::FieldElement;
 use utils::{
     collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
@@ -25,6 +26,11 @@ type ParsedOodFrame<E> = (Vec<E>, Vec<E>);
 ///
 /// where *z* is an out-of-domain point and *g* is the generator of the trace domain.
 ///
+/// If more than one out-of-domain point is used (see
+/// [ProofOptions::num_ood_points()](crate::ProofOptions::num_ood_points)), the frame contains
+/// the above evaluations for every point *z_j*: trace rows at *z_1*, *z_1 * g*, *z_2*,
+/// *z_2 * g* etc., followed by constraint composition column evaluations at *z_1*, *z_2* etc.
+///
 /// Internally, the evaluations are stored as a sequence of bytes. Thus, to retrieve the
 /// evaluations, [parse()](OodFrame::parse) function should be used.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -34,6 +40,42 @@ pub struct OodFrame {
 }
 
 impl OodFrame {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an out-of-domain frame constructed from serialized trace states and serialized
+    /// constraint evaluations.
+    ///
+    /// No validation of the provided bytes is performed; the bytes are parsed only when
+    /// [parse()](OodFrame::parse) is invoked.
+    ///
+    /// # Panics
+    /// Panics if either `trace_states` or `evaluations` is longer than 65535 bytes.
+    pub fn from_raw_parts(trace_states: Vec<u8>, evaluations: Vec<u8>) -> Self {
+        assert!(
+            trace_states.len() <= u16::MAX as usize && evaluations.len() <= u16::MAX as usize,
+            "OOD frame parts cannot be longer than {} bytes",
+            u16::MAX
+        );
+        OodFrame {
+            trace_states,
+            evaluations,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns serialized trace states of this out-of-domain frame.
+    pub fn trace_states_bytes(&self) -> &[u8] {
+        &self.trace_states
+    }
+
+    /// Returns serialized constraint evaluations of this out-of-domain frame.
+    pub fn evaluations_bytes(&self) -> &[u8] {
+        &self.evaluations
+    }
+
     // UPDATERS
     // --------------------------------------------------------------------------------------------
 
@@ -105,28 +147,51 @@ impl OodFrame {
         aux_trace_width: usize,
         num_evaluations: usize,
     ) -> Result<ParsedOodFrame<E>, DeserializationError> {
-        assert!(main_trace_width > 0, "trace width cannot be zero");
-        assert!(num_evaluations > 0, "number of evaluations cannot be zero");
+        self.view()
+            .parse(main_trace_width, aux_trace_width, num_evaluations)
+    }
 
-        // parse main and auxiliary trace evaluation frames
-        let mut reader = SliceReader::new(&self.trace_states);
-        let frame_size = reader.read_u8()? as usize;
-        let trace = E::read_batch_from(
-            &mut reader,
-            (main_trace_width + aux_trace_width) * frame_size,
-        )?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
+    // VIEW
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a view of this frame which borrows the serialized trace states and constraint
+    /// evaluations from `self`.
+    pub fn view(&self) -> OodFrameRef<'_> {
+        OodFrameRef {
+            trace_states: &self.trace_states,
+            evaluations: &self.evaluations,
         }
+    }
 
-        // parse the constraint evaluations
-        let mut reader = SliceReader::new(&self.evaluations);
-        let evaluations = E::read_batch_from(&mut reader, num_evaluations)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
+    // CBOR ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this frame into the `target` as a CBOR array `[trace_states, evaluations]`.
+    pub(super) fn write_cbor<W: ByteWriter>(&self, target: &mut W) {
+        cbor::write_array_header(target, 2);
+        cbor::write_bytes(target, &self.trace_states);
+        cbor::write_bytes(target, &self.evaluations);
+    }
+
+    /// Reads a CBOR-encoded OOD frame from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error of a valid OOD frame could not be read from the specified `source`.
+    pub(super) fn read_cbor<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        cbor::read_struct_header(source, 2, "OOD frame")?;
+        let trace_states = cbor::read_bytes(source)?;
+        let evaluations = cbor::read_bytes(source)?;
+        if trace_states.len() > u16::MAX as usize || evaluations.len() > u16::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "OOD frame parts cannot be longer than {} bytes",
+                u16::MAX
+            )));
         }
 
-        Ok((trace, evaluations))
+        Ok(OodFrame {
+            trace_states,
+            evaluations,
+        })
     }
 }
 
@@ -163,3 +228,104 @@ impl Deserializable for OodFrame {
         })
     }
 }
+
+// OUT-OF-DOMAIN FRAME VIEW
+// ================================================================================================
+/// A view of a serialized [OodFrame] which borrows trace states and constraint evaluations from
+/// the underlying source.
+///
+/// Similarly to [OodFrame], the evaluations are parsed only when [parse()](OodFrameRef::parse)
+/// function is invoked.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OodFrameRef<'a> {
+    trace_states: &'a [u8],
+    evaluations: &'a [u8],
+}
+
+impl<'a> OodFrameRef<'a> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns serialized trace states of this out-of-domain frame.
+    pub fn trace_states_bytes(&self) -> &'a [u8] {
+        self.trace_states
+    }
+
+    /// Returns serialized constraint evaluations of this out-of-domain frame.
+    pub fn evaluations_bytes(&self) -> &'a [u8] {
+        self.evaluations
+    }
+
+    /// Returns an owned copy of the frame described by this view.
+    pub fn into_owned(self) -> OodFrame {
+        OodFrame {
+            trace_states: self.trace_states.to_vec(),
+            evaluations: self.evaluations.to_vec(),
+        }
+    }
+
+    // PARSER
+    // --------------------------------------------------------------------------------------------
+    /// Returns main and auxiliary (if any) trace evaluation frames and a vector of out-of-domain
+    /// constraint evaluations referenced by `self`.
+    ///
+    /// This works in the same way as [OodFrame::parse()].
+    ///
+    /// # Panics
+    /// Panics if either `main_trace_width` or `num_evaluations` are equal to zero.
+    ///
+    /// # Errors
+    /// Returns an error if the evaluations could not be parsed from the referenced bytes, or if
+    /// any unconsumed bytes remained after the parsing was complete.
+    pub fn parse<E: FieldElement>(
+        &self,
+        main_trace_width: usize,
+        aux_trace_width: usize,
+        num_evaluations: usize,
+    ) -> Result<ParsedOodFrame<E>, DeserializationError> {
+        assert!(main_trace_width > 0, "trace width cannot be zero");
+        assert!(num_evaluations > 0, "number of evaluations cannot be zero");
+
+        // parse main and auxiliary trace evaluation frames
+        let mut reader = SliceReader::new(self.trace_states);
+        let frame_size = reader.read_u8()? as usize;
+        let trace = E::read_batch_from(
+            &mut reader,
+            (main_trace_width + aux_trace_width) * frame_size,
+        )?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        // parse the constraint evaluations
+        let mut reader = SliceReader::new(self.evaluations);
+        let evaluations = E::read_batch_from(&mut reader, num_evaluations)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok((trace, evaluations))
+    }
+
+    // DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads a view of an OOD frame from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error of a valid OOD frame could not be read from the specified `source`.
+    pub fn read_from(source: &mut SliceReader<'a>) -> Result<Self, DeserializationError> {
+        // read trace rows
+        let num_trace_state_bytes = source.read_u16()? as usize;
+        let trace_states = source.read_slice(num_trace_state_bytes)?;
+
+        // read constraint evaluations row
+        let num_constraint_evaluation_bytes = source.read_u16()? as usize;
+        let evaluations = source.read_slice(num_constraint_evaluation_bytes)?;
+
+        Ok(OodFrameRef {
+            trace_states,
+            evaluations,
+        })
+    }
+}
@@ -3,14 +3,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Table;
-use crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use super::{cbor, Table, PROOF_FORMAT_VERSION};
+use crypto::{
+    BatchMerkleProof, Digest, ElementHasher, Hasher, MerkleMultiProof, MerkleTree, VectorCommitment,
+};
 use math::FieldElement;
 use utils::{
     collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
     SliceReader,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Oldest version of the binary proof format in which serialized queries include salts.
+const SALTS_FORMAT_VERSION: u8 = 3;
+
 // QUERIES
 // ================================================================================================
 /// Decommitments to evaluations of a set of functions at multiple points.
@@ -24,31 +32,47 @@ use utils::{
 /// This struct can contain one or more queries. In cases when more than one query is stored,
 /// Merkle authentication paths are compressed to remove redundant nodes.
 ///
-/// Internally, all Merkle paths and query values are stored as a sequence of bytes. Thus, to
-/// retrieve query values and the corresponding Merkle authentication paths,
-/// [parse()](Queries::parse) function should be used.
+/// If the commitment is a [SaltedMerkleTree](crypto::SaltedMerkleTree), each query also includes
+/// the salt of the queried leaf; in this case, the leaf is a merge of the hash of the query
+/// values with the salt.
+///
+/// Internally, all Merkle paths, query values, and salts are stored as a sequence of bytes. Thus,
+/// to retrieve query values and the corresponding Merkle authentication paths,
+/// [parse()](Queries::parse) function should be used. If the proof options select Merkle
+/// multi-proofs, the paths are serialized as a [MerkleMultiProof] instead, and
+/// [parse_multi()](Queries::parse_multi) function should be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Queries {
     paths: Vec<u8>,
     values: Vec<u8>,
+    salts: Vec<u8>,
 }
 
 impl Queries {
+    /// Maximum number of bytes in either serialized query values, serialized Merkle paths, or
+    /// serialized salts which can be read from a serialized proof.
+    ///
+    /// This bounds the amount of memory allocated when a proof is read from an untrusted source;
+    /// the bound is well above the size of queries in proofs generated with valid proof options.
+    pub const MAX_NUM_BYTES: usize = 1 << 24;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns queries constructed from evaluations of a set of functions at some number of points
-    /// in a domain and their corresponding Merkle authentication paths.
+    /// in a domain and the proof of opening the corresponding items of vector commitment `V`.
     ///
     /// For each evaluation point, the same number of values must be provided, and a hash of
-    /// these values must be equal to a leaf node in the corresponding Merkle authentication path.
+    /// these values must be equal to the corresponding item opened by the `opening_proof`. For
+    /// Merkle tree commitments, the opening proof is a batch Merkle proof, and the items are leaf
+    /// nodes of the authentication paths.
     ///
     /// # Panics
     /// Panics if:
     /// * No queries were provided (`query_values` is an empty vector).
     /// * Any of the queries does not contain any evaluations.
     /// * Not all queries contain the same number of evaluations.
-    pub fn new<H: Hasher, E: FieldElement>(
-        merkle_proof: BatchMerkleProof<H>,
+    pub fn new<H: Hasher, E: FieldElement, V: VectorCommitment<H>>(
+        opening_proof: V::MultiProof,
         query_values: Vec<Vec<E>>,
     ) -> Self {
         assert!(!query_values.is_empty(), "query values cannot be empty");
@@ -72,11 +96,127 @@ impl Queries {
             values.write(elements);
         }
 
-        // serialize internal nodes of the batch Merkle proof; we care about internal nodes only
-        // because leaf nodes can be reconstructed from hashes of query values
-        let paths = merkle_proof.serialize_nodes();
+        // serialize the opening proof without the opened items; the items can be reconstructed
+        // from hashes of query values
+        let mut paths = Vec::new();
+        V::write_multi_proof(&opening_proof, &mut paths);
+
+        Queries {
+            paths,
+            values,
+            salts: Vec::new(),
+        }
+    }
+
+    /// Returns queries constructed from evaluations of a set of functions at some number of points
+    /// in a domain, salts of the leaves corresponding to these points, and their Merkle
+    /// authentication paths.
+    ///
+    /// This works in the same way as [Queries::new()], but leaf nodes in the Merkle
+    /// authentication paths must be equal to a merge of the hash of the evaluations at a given
+    /// point with the salt for this point (as done by [SaltedMerkleTree](crypto::SaltedMerkleTree)).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Any of the conditions described for [Queries::new()] are not satisfied.
+    /// * The number of salts is different from the number of queries.
+    pub fn new_salted<H: Hasher, E: FieldElement>(
+        merkle_proof: BatchMerkleProof<H>,
+        query_values: Vec<Vec<E>>,
+        salts: Vec<H::Digest>,
+    ) -> Self {
+        assert_eq!(
+            query_values.len(),
+            salts.len(),
+            "number of salts must be the same as the number of queries"
+        );
+
+        let mut queries = Self::new::<H, E, MerkleTree<H>>(merkle_proof, query_values);
+        H::Digest::write_batch_into(&salts, &mut queries.salts);
+        queries
+    }
+
+    /// Returns queries constructed from serialized query values and serialized internal nodes of
+    /// the corresponding batch Merkle proof.
+    ///
+    /// No validation of the provided bytes is performed; the bytes are parsed only when
+    /// [parse()](Queries::parse) is invoked.
+    pub fn from_raw_parts(values: Vec<u8>, paths: Vec<u8>) -> Self {
+        Self::from_raw_parts_salted(values, paths, Vec::new())
+    }
+
+    /// Returns queries constructed from serialized query values, serialized internal nodes of
+    /// the corresponding batch Merkle proof, and serialized salts of the queried leaves.
+    ///
+    /// If `salts` is empty, the queries are assumed to be against a commitment without salts.
+    /// No validation of the provided bytes is performed; the bytes are parsed only when
+    /// [parse()](Queries::parse) is invoked.
+    pub fn from_raw_parts_salted(values: Vec<u8>, paths: Vec<u8>, salts: Vec<u8>) -> Self {
+        Queries {
+            paths,
+            values,
+            salts,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns serialized query values of these queries.
+    pub fn values_bytes(&self) -> &[u8] {
+        &self.values
+    }
+
+    /// Returns serialized internal nodes of the batch Merkle proof for these queries.
+    pub fn paths_bytes(&self) -> &[u8] {
+        &self.paths
+    }
+
+    /// Returns serialized salts of the queried leaves; the returned slice is empty if the
+    /// queries are against a commitment without salts.
+    pub fn salts_bytes(&self) -> &[u8] {
+        &self.salts
+    }
+
+    /// Returns true if these queries are against a commitment with salted leaves.
+    pub fn is_salted(&self) -> bool {
+        !self.salts.is_empty()
+    }
+
+    // CBOR ENCODING
+    // --------------------------------------------------------------------------------------------
 
-        Queries { paths, values }
+    /// Writes these queries into the `target` as a CBOR array `[values, paths, salts]`.
+    pub(super) fn write_cbor<W: ByteWriter>(&self, target: &mut W) {
+        cbor::write_array_header(target, 3);
+        cbor::write_bytes(target, &self.values);
+        cbor::write_bytes(target, &self.paths);
+        cbor::write_bytes(target, &self.salts);
+    }
+
+    /// Reads CBOR-encoded queries from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error of a valid query struct could not be read from the specified source.
+    pub(super) fn read_cbor<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        cbor::read_struct_header(source, 3, "queries")?;
+        let values = cbor::read_bytes(source)?;
+        let paths = cbor::read_bytes(source)?;
+        let salts = cbor::read_bytes(source)?;
+        if values.len() > Self::MAX_NUM_BYTES
+            || paths.len() > Self::MAX_NUM_BYTES
+            || salts.len() > Self::MAX_NUM_BYTES
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "query values, Merkle paths, and salts cannot be longer than {} bytes",
+                Self::MAX_NUM_BYTES
+            )));
+        }
+        Ok(Queries {
+            paths,
+            values,
+            salts,
+        })
     }
 
     // PARSER
@@ -84,6 +224,9 @@ impl Queries {
     /// Convert internally stored bytes into a set of query values and the corresponding Merkle
     /// authentication paths.
     ///
+    /// If these queries are salted, leaf nodes of the Merkle authentication paths are computed by
+    /// merging hashes of query values with the corresponding salts.
+    ///
     /// # Panics
     /// Panics if:
     /// * `domain_size` is not a power of two.
@@ -95,6 +238,239 @@ impl Queries {
         num_queries: usize,
         values_per_query: usize,
     ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.view()
+            .parse(domain_size, num_queries, values_per_query)
+    }
+
+    /// Convert internally stored bytes into a set of query values, a Merkle multi-proof for
+    /// these queries, and the leaves of the multi-proof.
+    ///
+    /// This works in the same way as [Queries::parse()] but expects the paths to be serialized as
+    /// a [MerkleMultiProof]; see [QueriesRef::parse_multi()] for details.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `num_queries` is zero.
+    /// * `values_per_query` is zero.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_multi<H, E>(
+        self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+    ) -> Result<(MerkleMultiProof<H>, Vec<H::Digest>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.view()
+            .parse_multi(domain_size, num_queries, values_per_query)
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns these queries with the batch Merkle proof replaced by an equivalent
+    /// [MerkleMultiProof] for the leaves at the specified `positions`.
+    ///
+    /// Query values and salts are left unchanged. The resulting queries must be parsed using
+    /// [parse_multi()](Queries::parse_multi).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `positions` is empty or contains duplicates.
+    /// * The paths of these queries are not a batch Merkle proof for the specified `positions`
+    ///   in a tree with `domain_size` leaves.
+    pub fn into_multi_proof<H, E>(self, domain_size: usize, positions: &[usize]) -> Self
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        assert!(!positions.is_empty(), "there must be at least one query");
+        let values_per_query = self.values.len() / (positions.len() * E::ELEMENT_BYTES);
+        let (merkle_proof, _) = self
+            .view()
+            .parse::<H, E>(domain_size, positions.len(), values_per_query)
+            .expect("failed to parse batch Merkle proof");
+        let multi_proof = merkle_proof
+            .into_multi_proof(positions)
+            .expect("failed to convert batch Merkle proof into a multi-proof");
+
+        Queries {
+            paths: multi_proof.to_bytes(),
+            values: self.values,
+            salts: self.salts,
+        }
+    }
+
+    // VIEW
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a view of these queries which borrows the serialized query values and Merkle
+    /// paths from `self`.
+    pub fn view(&self) -> QueriesRef<'_> {
+        QueriesRef {
+            paths: &self.paths,
+            values: &self.values,
+            salts: &self.salts,
+        }
+    }
+
+    // VERSIONED SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes `self` using the specified version of the binary proof format and writes the
+    /// resulting bytes into the `target`.
+    ///
+    /// Salts are written only in version 3 and later of the proof format.
+    ///
+    /// # Panics
+    /// Panics if these queries are salted and `version` is smaller than 3.
+    pub(super) fn write_into_with_version<W: ByteWriter>(&self, target: &mut W, version: u8) {
+        assert!(
+            version >= SALTS_FORMAT_VERSION || !self.is_salted(),
+            "salted queries cannot be serialized using proof format version {version}"
+        );
+
+        // write value bytes
+        target.write_u32(self.values.len() as u32);
+        target.write_bytes(&self.values);
+
+        // write path bytes
+        target.write_u32(self.paths.len() as u32);
+        target.write_bytes(&self.paths);
+
+        // write salt bytes
+        if version >= SALTS_FORMAT_VERSION {
+            target.write_u32(self.salts.len() as u32);
+            target.write_bytes(&self.salts);
+        }
+    }
+
+    /// Reads a query struct serialized using the specified version of the binary proof format
+    /// from the `source`.
+    ///
+    /// # Errors
+    /// Returns an error of a valid query struct could not be read from the specified source.
+    pub(super) fn read_from_with_version<R: ByteReader>(
+        source: &mut R,
+        version: u8,
+    ) -> Result<Self, DeserializationError> {
+        // read values
+        let num_value_bytes = read_len(source, "query values")?;
+        let values = source.read_vec(num_value_bytes)?;
+
+        // read paths
+        let num_paths_bytes = read_len(source, "Merkle paths")?;
+        let paths = source.read_vec(num_paths_bytes)?;
+
+        // read salts
+        let salts = if version >= SALTS_FORMAT_VERSION {
+            let num_salt_bytes = read_len(source, "salts")?;
+            source.read_vec(num_salt_bytes)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Queries {
+            paths,
+            values,
+            salts,
+        })
+    }
+}
+
+impl Serializable for Queries {
+    /// Serializes `self` using the latest version of the binary proof format and writes the
+    /// resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into_with_version(target, PROOF_FORMAT_VERSION)
+    }
+}
+
+impl Deserializable for Queries {
+    /// Reads a query struct serialized using the latest version of the binary proof format from
+    /// the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error of a valid query struct could not be read from the specified source.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_from_with_version(source, PROOF_FORMAT_VERSION)
+    }
+}
+
+// QUERIES VIEW
+// ================================================================================================
+/// A view of serialized [Queries] which borrows query values, Merkle paths, and salts from the
+/// underlying source.
+///
+/// Similarly to [Queries], the values and Merkle paths are parsed only when
+/// [parse()](QueriesRef::parse) function is invoked.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct QueriesRef<'a> {
+    paths: &'a [u8],
+    values: &'a [u8],
+    salts: &'a [u8],
+}
+
+impl<'a> QueriesRef<'a> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns serialized query values of these queries.
+    pub fn values_bytes(&self) -> &'a [u8] {
+        self.values
+    }
+
+    /// Returns serialized internal nodes of the batch Merkle proof for these queries.
+    pub fn paths_bytes(&self) -> &'a [u8] {
+        self.paths
+    }
+
+    /// Returns serialized salts of the queried leaves; the returned slice is empty if the
+    /// queries are against a commitment without salts.
+    pub fn salts_bytes(&self) -> &'a [u8] {
+        self.salts
+    }
+
+    /// Returns true if these queries are against a commitment with salted leaves.
+    pub fn is_salted(&self) -> bool {
+        !self.salts.is_empty()
+    }
+
+    /// Returns an owned copy of the queries described by this view.
+    pub fn into_owned(self) -> Queries {
+        Queries {
+            paths: self.paths.to_vec(),
+            values: self.values.to_vec(),
+            salts: self.salts.to_vec(),
+        }
+    }
+
+    // PARSER
+    // --------------------------------------------------------------------------------------------
+    /// Convert referenced bytes into a set of query values and the corresponding Merkle
+    /// authentication paths.
+    ///
+    /// This works in the same way as [Queries::parse()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `num_queries` is zero.
+    /// * `values_per_query` is zero.
+    pub fn parse<H, E>(
+        &self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+    ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
@@ -103,6 +479,82 @@ impl Queries {
             domain_size.is_power_of_two(),
             "domain size must be a power of two"
         );
+        let (hashed_queries, query_values) =
+            self.parse_leaves::<H, E>(num_queries, values_per_query)?;
+
+        // build batch Merkle proof
+        let mut reader = SliceReader::new(self.paths);
+        let tree_depth = domain_size.ilog2() as u8;
+        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok((merkle_proof, query_values))
+    }
+
+    /// Convert referenced bytes into a set of query values, a Merkle multi-proof for these
+    /// queries, and the leaves of the multi-proof.
+    ///
+    /// This works in the same way as [QueriesRef::parse()] but expects the paths to be serialized
+    /// as a [MerkleMultiProof] (as done when
+    /// [ProofOptions::merkle_multi_proofs()](crate::ProofOptions::merkle_multi_proofs) is set).
+    /// Since a multi-proof does not contain the leaves, the leaves are returned separately in
+    /// query order.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `num_queries` is zero.
+    /// * `values_per_query` is zero.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_multi<H, E>(
+        &self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+    ) -> Result<(MerkleMultiProof<H>, Vec<H::Digest>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+        let (leaves, query_values) = self.parse_leaves::<H, E>(num_queries, values_per_query)?;
+
+        // read the multi-proof and make sure it was built for a tree over the expected domain
+        let mut reader = SliceReader::new(self.paths);
+        let merkle_proof = MerkleMultiProof::<H>::read_from(&mut reader)?;
+        let tree_depth = domain_size.ilog2() as u8;
+        if merkle_proof.depth != tree_depth {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected Merkle multi-proof of depth {}, but was {}",
+                tree_depth, merkle_proof.depth
+            )));
+        }
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok((merkle_proof, leaves, query_values))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads query values from the referenced bytes and hashes them (merging the hashes with the
+    /// salts for salted queries) to build the leaves opened by these queries.
+    fn parse_leaves<H, E>(
+        &self,
+        num_queries: usize,
+        values_per_query: usize,
+    ) -> Result<(Vec<H::Digest>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
         assert!(num_queries > 0, "there must be at least one query");
         assert!(
             values_per_query > 0,
@@ -121,52 +573,89 @@ impl Queries {
         }
 
         // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
-        let query_values = Table::<E>::from_bytes(&self.values, num_queries, values_per_query)?;
-        let hashed_queries = query_values
+        // and also hash them to build leaf nodes of the Merkle proof
+        let query_values = Table::<E>::from_bytes(self.values, num_queries, values_per_query)?;
+        let mut hashed_queries: Vec<H::Digest> = query_values
             .rows()
             .map(|row| H::hash_elements(row))
             .collect();
 
-        // build batch Merkle proof
-        let mut reader = SliceReader::new(&self.paths);
-        let tree_depth = domain_size.ilog2() as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
+        // if the commitment has salted leaves, merge the hashed queries with their salts
+        if self.is_salted() {
+            let expected_bytes = num_queries * H::Digest::SIZE;
+            if self.salts.len() != expected_bytes {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "expected {} salt bytes, but was {}",
+                    expected_bytes,
+                    self.salts.len()
+                )));
+            }
+            let salts = H::Digest::read_batch_from(&mut SliceReader::new(self.salts), num_queries)?;
+            for (leaf, salt) in hashed_queries.iter_mut().zip(salts) {
+                *leaf = H::merge(&[*leaf, salt]);
+            }
         }
 
-        Ok((merkle_proof, query_values))
+        Ok((hashed_queries, query_values))
     }
-}
 
-impl Serializable for Queries {
-    /// Serializes `self` and writes the resulting bytes into the `target`.
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        // write value bytes
-        target.write_u32(self.values.len() as u32);
-        target.write_bytes(&self.values);
+    // DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
 
-        // write path bytes
-        target.write_u32(self.paths.len() as u32);
-        target.write_bytes(&self.paths);
+    /// Reads a view of a query struct serialized using the latest version of the binary proof
+    /// format from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error of a valid query struct could not be read from the specified source.
+    pub fn read_from(source: &mut SliceReader<'a>) -> Result<Self, DeserializationError> {
+        Self::read_from_with_version(source, PROOF_FORMAT_VERSION)
     }
-}
 
-impl Deserializable for Queries {
-    /// Reads a query struct from the specified `source` and returns the result
+    /// Reads a view of a query struct serialized using the specified version of the binary proof
+    /// format from the `source`.
     ///
     /// # Errors
     /// Returns an error of a valid query struct could not be read from the specified source.
-    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+    pub(super) fn read_from_with_version(
+        source: &mut SliceReader<'a>,
+        version: u8,
+    ) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
-        let values = source.read_vec(num_value_bytes as usize)?;
+        let num_value_bytes = read_len(source, "query values")?;
+        let values = source.read_slice(num_value_bytes)?;
 
         // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_vec(num_paths_bytes as usize)?;
+        let num_paths_bytes = read_len(source, "Merkle paths")?;
+        let paths = source.read_slice(num_paths_bytes)?;
+
+        // read salts
+        let salts: &[u8] = if version >= SALTS_FORMAT_VERSION {
+            let num_salt_bytes = read_len(source, "salts")?;
+            source.read_slice(num_salt_bytes)?
+        } else {
+            &[]
+        };
+
+        Ok(QueriesRef {
+            paths,
+            values,
+            salts,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
 
-        Ok(Queries { paths, values })
+/// Reads the number of bytes in a serialized part of a query struct from the `source` making
+/// sure the number does not exceed [Queries::MAX_NUM_BYTES].
+fn read_len<R: ByteReader>(source: &mut R, name: &str) -> Result<usize, DeserializationError> {
+    let len = source.read_u32()? as usize;
+    if len > Queries::MAX_NUM_BYTES {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{name} cannot be longer than {} bytes, but were {len} bytes",
+            Queries::MAX_NUM_BYTES
+        )));
     }
+    Ok(len)
 }
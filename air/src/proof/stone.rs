@@ -0,0 +1,940 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Commitments, Context, OodFrame, ProofMetadata, Queries, StarkProof};
+use core::ops::Range;
+use crypto::Hasher;
+use fri::{FriProof, FriProofLayer};
+use utils::{
+    collections::Vec,
+    hex,
+    string::{String, ToString},
+    ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// TYPE ALIASES
+// ================================================================================================
+
+/// Range of proof bytes described by an annotation, together with its path and description.
+type ParsedAnnotation<'a> = (Range<usize>, &'a str, &'a str);
+
+// CONSTANTS
+// ================================================================================================
+
+/// Prefix of the paths of all annotations describing parts of a Winterfell proof.
+const ROOT: &str = "/winterfell/STARK";
+
+/// Maximum nesting depth of JSON values accepted by [AnnotatedProof::from_json()].
+const MAX_JSON_DEPTH: usize = 64;
+
+// ANNOTATED PROOF
+// ================================================================================================
+/// A STARK proof arranged into the annotated proof layout used by StarkWare's stone prover and
+/// verifier.
+///
+/// In this layout, all data sent from the prover to the verifier is concatenated into a single
+/// byte string, and every part of this string is described by an annotation of the form
+/// `P->V[start:end]: /path: description: Kind(0x...)`. The parts are listed in the order in
+/// which they are sent by the prover:
+/// 1. Commitments to the main and auxiliary trace segments, and to the evaluations of the
+///    constraint composition polynomial.
+/// 2. Out-of-domain trace states and constraint composition evaluations.
+/// 3. Commitments to FRI layers and to the FRI remainder, followed by the remainder values.
+/// 4. Proof-of-work nonce (in big-endian byte order).
+/// 5. Decommitments of trace and constraint composition evaluations at the queried positions,
+///    followed by decommitments of all FRI layers.
+///
+/// Verifier messages (`V->P` annotations) are not included because they can be derived only by
+/// replaying the public coin of a specific AIR. The proof context, which is public in stone's
+/// model, is carried in the `proof_parameters` section of the JSON file so that the original
+/// proof can be recovered via [StarkProof::from_annotated()].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AnnotatedProof {
+    context: Context,
+    num_fri_partitions: usize,
+    annotations: Vec<String>,
+    proof: Vec<u8>,
+}
+
+impl AnnotatedProof {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Arranges the specified `proof` into the annotated proof layout. Commitments in the proof
+    /// are expected to be digests produced by hash function `H`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Commitments of the proof could not be parsed into digests of the hash function `H`.
+    /// * The number of trace queries is not consistent with the trace layout of the proof.
+    /// * Trace or constraint commitments of the proof are salted.
+    /// * The proof uses STIR as its low-degree test.
+    /// * The proof contains proof-of-work nonces for FRI layers.
+    pub(super) fn new<H: Hasher>(proof: &StarkProof) -> Result<Self, DeserializationError> {
+        let trace_layout = proof.trace_layout();
+        let num_trace_segments = trace_layout.num_segments();
+        let num_fri_layers = proof.fri_proof.num_layers();
+        if proof.trace_queries.len() != num_trace_segments {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected trace queries for {num_trace_segments} trace segments, but was {}",
+                proof.trace_queries.len()
+            )));
+        }
+        if proof.stir_proof.is_some() {
+            return Err(DeserializationError::InvalidValue(
+                "proofs with STIR low-degree tests cannot be arranged into the annotated proof layout"
+                    .to_string(),
+            ));
+        }
+        if proof.options().fri_layer_grinding_factor() > 0 {
+            return Err(DeserializationError::InvalidValue(
+                "proofs with FRI layer grinding cannot be arranged into the annotated proof layout"
+                    .to_string(),
+            ));
+        }
+        if proof.trace_queries.iter().any(Queries::is_salted)
+            || proof.constraint_queries.is_salted()
+        {
+            return Err(DeserializationError::InvalidValue(
+                "proofs with salted commitments cannot be arranged into the annotated proof layout"
+                    .to_string(),
+            ));
+        }
+
+        // main trace values are in the base field, while all other values are in the extension
+        // field specified by proof options
+        let base_element_size = proof.context.field_modulus_bytes().len();
+        let element_size = base_element_size * proof.options().field_extension().degree() as usize;
+
+        let (trace_roots, constraint_root, fri_roots) = proof
+            .commitments
+            .clone()
+            .parse::<H>(num_trace_segments, num_fri_layers)?;
+        let (remainder_root, layer_roots) = fri_roots
+            .split_last()
+            .expect("FRI commitments must include a remainder commitment");
+
+        let mut writer = AnnotationWriter::default();
+
+        // trace and constraint composition commitments
+        for (i, root) in trace_roots.iter().enumerate() {
+            let path = format!("{ROOT}/{}/Commit on Trace", segment_name(i));
+            writer.write(&path, "Commitment", "Hash", &root.to_bytes());
+        }
+        let path = format!("{ROOT}/Composition/Commit on Trace");
+        writer.write(&path, "Commitment", "Hash", &constraint_root.to_bytes());
+
+        // out-of-domain frame
+        let path = format!("{ROOT}/Out Of Domain Sampling/Trace States");
+        writer.write_elements(&path, proof.ood_frame.trace_states_bytes(), element_size);
+        let path = format!("{ROOT}/Out Of Domain Sampling/Composition Evaluations");
+        writer.write_elements(&path, proof.ood_frame.evaluations_bytes(), element_size);
+
+        // FRI commitments and remainder
+        for (i, root) in layer_roots.iter().enumerate() {
+            let path = format!("{ROOT}/FRI/Commitment/Layer {i}");
+            writer.write(&path, "Commitment", "Hash", &root.to_bytes());
+        }
+        let path = format!("{ROOT}/FRI/Commitment/Last Layer");
+        writer.write(&path, "Commitment", "Hash", &remainder_root.to_bytes());
+        let path = format!("{ROOT}/FRI/Last Layer");
+        writer.write_elements(&path, proof.fri_proof.remainder_bytes(), element_size);
+
+        // proof-of-work nonce
+        let path = format!("{ROOT}/Proof of Work");
+        writer.write(&path, "Nonce", "POW", &proof.pow_nonce.to_be_bytes());
+
+        // metadata
+        if let Some(metadata) = &proof.metadata {
+            let path = format!("{ROOT}/Metadata");
+            writer.write(&path, "Metadata", "Data", &metadata.to_bytes());
+        }
+
+        // trace and constraint composition decommitments
+        for (i, queries) in proof.trace_queries.iter().enumerate() {
+            let (num_columns, value_size) = if i == 0 {
+                (trace_layout.main_trace_width(), base_element_size)
+            } else {
+                (trace_layout.get_aux_segment_width(i - 1), element_size)
+            };
+            let path = format!("{ROOT}/{}/Decommitment", segment_name(i));
+            writer.write_queries(&path, queries, num_columns, value_size);
+        }
+        let num_columns = proof.ood_frame.evaluations_bytes().len()
+            / (element_size * proof.options().num_ood_points());
+        let path = format!("{ROOT}/Composition/Decommitment");
+        writer.write_queries(&path, &proof.constraint_queries, num_columns, element_size);
+
+        // FRI decommitments
+        let fri_options = proof.options().to_fri_options();
+        for (i, layer) in proof.fri_proof.layers().iter().enumerate() {
+            let folding_factor = fri_options.folding_factor_at(i);
+            let queries = Queries::from_raw_parts(
+                layer.values_bytes().to_vec(),
+                layer.paths_bytes().to_vec(),
+            );
+            let path = format!("{ROOT}/FRI/Decommitment/Layer {i}");
+            writer.write_queries(&path, &queries, folding_factor, element_size);
+        }
+
+        Ok(AnnotatedProof {
+            context: proof.context.clone(),
+            num_fri_partitions: proof.fri_proof.num_partitions(),
+            annotations: writer.annotations,
+            proof: writer.proof,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the context of the annotated proof.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns annotations describing all parts of the proof sent from the prover to the
+    /// verifier.
+    pub fn annotations(&self) -> &[String] {
+        &self.annotations
+    }
+
+    /// Returns a concatenation of all parts of the proof sent from the prover to the verifier.
+    pub fn proof_bytes(&self) -> &[u8] {
+        &self.proof
+    }
+
+    // JSON ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns this proof encoded as a JSON proof file in the format used by stone's prover and
+    /// verifier.
+    ///
+    /// Besides the `annotations` and the `proof_hex` fields, the file contains a
+    /// `proof_parameters` object with STARK and FRI parameters expressed in stone's terms, and a
+    /// `proof_parameters.winterfell` object with the serialized proof context and the number of
+    /// FRI partitions.
+    pub fn to_json(&self) -> String {
+        let options = self.context.options();
+        let fri_options = options.to_fri_options();
+
+        // in stone, the first FRI step is always 0 since the first layer is committed to as a
+        // part of the DEEP composition
+        let mut fri_steps = vec!["0".to_string()];
+        fri_steps.extend(
+            fri_options
+                .layer_folding_factors(self.context.lde_domain_size())
+                .into_iter()
+                .map(|folding_factor| folding_factor.ilog2().to_string()),
+        );
+
+        let mut result = String::from("{\n");
+        result.push_str("    \"proof_parameters\": {\n");
+        result.push_str("        \"stark\": {\n");
+        result.push_str("            \"fri\": {\n");
+        result.push_str(&format!(
+            "                \"fri_step_list\": [{}],\n",
+            fri_steps.join(", ")
+        ));
+        result.push_str(&format!(
+            "                \"last_layer_degree_bound\": {},\n",
+            fri_options.remainder_max_degree() + 1
+        ));
+        result.push_str(&format!(
+            "                \"n_queries\": {},\n",
+            options.num_queries()
+        ));
+        result.push_str(&format!(
+            "                \"proof_of_work_bits\": {}\n",
+            options.grinding_factor()
+        ));
+        result.push_str("            },\n");
+        result.push_str(&format!(
+            "            \"log_n_cosets\": {}\n",
+            options.blowup_factor().ilog2()
+        ));
+        result.push_str("        },\n");
+        result.push_str(&format!(
+            "        \"use_extension_field\": {},\n",
+            !options.field_extension().is_none()
+        ));
+        result.push_str("        \"winterfell\": {\n");
+        result.push_str(&format!(
+            "            \"context\": \"{}\",\n",
+            self.context.to_hex()
+        ));
+        result.push_str(&format!(
+            "            \"num_fri_partitions\": {}\n",
+            self.num_fri_partitions
+        ));
+        result.push_str("        }\n");
+        result.push_str("    },\n");
+        result.push_str("    \"annotations\": [");
+        for (i, annotation) in self.annotations.iter().enumerate() {
+            if i > 0 {
+                result.push(',');
+            }
+            result.push_str("\n        ");
+            write_json_string(&mut result, annotation);
+        }
+        result.push_str("\n    ],\n");
+        result.push_str(&format!(
+            "    \"proof_hex\": \"{}\"\n",
+            hex::encode(&self.proof)
+        ));
+        result.push_str("}\n");
+        result
+    }
+
+    /// Returns an annotated proof read from the specified JSON proof file.
+    ///
+    /// Only the `proof_parameters.winterfell`, `annotations`, and `proof_hex` fields are read;
+    /// all other fields (e.g., public and private inputs which stone writes into the same file)
+    /// are ignored. Annotations of verifier messages (`V->P`) are ignored as well.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `source` is not a valid JSON document, or any of the required fields is missing.
+    /// * The proof context or the proof bytes are not valid hex strings.
+    /// * The proof context could not be deserialized.
+    /// * The number of FRI partitions is not a power of two.
+    pub fn from_json(source: &str) -> Result<Self, DeserializationError> {
+        let json = JsonParser::new(source).parse()?;
+        let parameters = json.get("proof_parameters")?.get("winterfell")?;
+
+        let context_bytes = hex::decode(parameters.get("context")?.as_str()?)?;
+        let mut reader = SliceReader::new(&context_bytes);
+        let context = Context::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        let num_fri_partitions = parameters.get("num_fri_partitions")?.as_usize()?;
+        if !num_fri_partitions.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of FRI partitions must be a power of two, but was {num_fri_partitions}"
+            )));
+        }
+
+        let annotations = json
+            .get("annotations")?
+            .as_array()?
+            .iter()
+            .map(|annotation| annotation.as_str().map(String::from))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let proof = hex::decode(json.get("proof_hex")?.as_str()?)?;
+
+        Ok(AnnotatedProof {
+            context,
+            num_fri_partitions,
+            annotations,
+            proof,
+        })
+    }
+
+    // CONVERSION
+    // --------------------------------------------------------------------------------------------
+
+    /// Reconstructs the STARK proof described by this annotated proof.
+    ///
+    /// The parts of the proof are identified by paths of the annotations, and their contents are
+    /// taken from the proof bytes; the values embedded in the annotations are ignored.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Any of the prover annotations is malformed or has a path which does not describe a
+    ///   part of a Winterfell proof.
+    /// * Prover annotations do not cover the proof bytes contiguously and completely.
+    /// * Any of the required parts of the proof is missing.
+    pub(super) fn to_stark_proof(&self) -> Result<StarkProof, DeserializationError> {
+        let mut commitments = Vec::new();
+        let mut pow_nonce = None;
+        let mut metadata = None;
+        let mut sections = Sections::default();
+
+        let pow_path = format!("{ROOT}/Proof of Work");
+        let metadata_path = format!("{ROOT}/Metadata");
+        let mut offset = 0;
+        for annotation in self.annotations.iter() {
+            let (range, path, description) = match parse_annotation(annotation)? {
+                Some(annotation) => annotation,
+                None => continue,
+            };
+            if range.start != offset || range.end > self.proof.len() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "annotation {annotation} does not describe the next {} proof bytes",
+                    range.len()
+                )));
+            }
+            offset = range.end;
+
+            let bytes = &self.proof[range];
+            if description == "Commitment" {
+                commitments.extend_from_slice(bytes);
+            } else if path == pow_path {
+                let nonce = bytes.try_into().map_err(|_| {
+                    DeserializationError::InvalidValue(format!(
+                        "proof-of-work nonce must be 8 bytes long, but was {} bytes",
+                        bytes.len()
+                    ))
+                })?;
+                pow_nonce = Some(u64::from_be_bytes(nonce));
+            } else if path == metadata_path {
+                let mut reader = SliceReader::new(bytes);
+                metadata = Some(ProofMetadata::read_from(&mut reader)?);
+                if reader.has_more_bytes() {
+                    return Err(DeserializationError::UnconsumedBytes);
+                }
+            } else {
+                sections.append(path, description == "Authentication Paths", bytes);
+            }
+        }
+        if offset != self.proof.len() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        // trace and constraint composition decommitments
+        let num_trace_segments = self.context.trace_layout().num_segments();
+        let trace_queries = (0..num_trace_segments)
+            .map(|i| sections.take_queries(&format!("{ROOT}/{}/Decommitment", segment_name(i))))
+            .collect::<Result<Vec<_>, _>>()?;
+        let constraint_queries =
+            sections.take_queries(&format!("{ROOT}/Composition/Decommitment"))?;
+
+        // out-of-domain frame
+        let (trace_states, _) =
+            sections.take(&format!("{ROOT}/Out Of Domain Sampling/Trace States"));
+        let (evaluations, _) = sections.take(&format!(
+            "{ROOT}/Out Of Domain Sampling/Composition Evaluations"
+        ));
+        if trace_states.len() > u16::MAX as usize || evaluations.len() > u16::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "OOD frame parts cannot be longer than {} bytes",
+                u16::MAX
+            )));
+        }
+
+        // FRI proof
+        let (remainder, _) = sections.take(&format!("{ROOT}/FRI/Last Layer"));
+        let mut fri_layers = Vec::new();
+        while let Some(section) = sections.find(&format!(
+            "{ROOT}/FRI/Decommitment/Layer {}",
+            fri_layers.len()
+        )) {
+            let (values, paths) = sections.remove(section);
+            fri_layers.push(FriProofLayer::from_raw_parts(values, paths));
+        }
+
+        if let Some((path, _, _)) = sections.0.first() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "annotation path {path} does not describe a part of a Winterfell proof"
+            )));
+        }
+        if commitments.len() >= u16::MAX as usize {
+            return Err(DeserializationError::InvalidValue(format!(
+                "commitments cannot be longer than {} bytes",
+                u16::MAX - 1
+            )));
+        }
+
+        Ok(StarkProof {
+            context: self.context.clone(),
+            commitments: Commitments::from_bytes(commitments),
+            trace_queries,
+            constraint_queries,
+            ood_frame: OodFrame::from_raw_parts(trace_states, evaluations),
+            fri_proof: FriProof::from_raw_parts(fri_layers, remainder, self.num_fri_partitions),
+            stir_proof: None,
+            pow_nonce: pow_nonce.ok_or_else(|| {
+                DeserializationError::InvalidValue("proof-of-work nonce is missing".to_string())
+            })?,
+            metadata,
+        })
+    }
+}
+
+// ANNOTATION WRITER
+// ================================================================================================
+
+/// Accumulates proof bytes together with annotations describing them.
+#[derive(Default)]
+struct AnnotationWriter {
+    annotations: Vec<String>,
+    proof: Vec<u8>,
+}
+
+impl AnnotationWriter {
+    /// Appends the specified bytes to the proof and annotates them.
+    fn write(&mut self, path: &str, description: &str, kind: &str, bytes: &[u8]) {
+        let start = self.proof.len();
+        self.proof.extend_from_slice(bytes);
+        self.annotations.push(format!(
+            "P->V[{start}:{}]: {path}: {description}: {kind}({})",
+            self.proof.len(),
+            hex::encode(bytes)
+        ));
+    }
+
+    /// Appends a sequence of field elements to the proof annotating each element separately. If
+    /// `elements` cannot be split into elements of the specified size, all bytes are annotated
+    /// as a single data item.
+    fn write_elements(&mut self, path: &str, elements: &[u8], element_size: usize) {
+        if element_size == 0 || elements.len() % element_size != 0 {
+            self.write(path, "Elements", "Data", elements);
+            return;
+        }
+        for (i, element) in elements.chunks(element_size).enumerate() {
+            self.write(path, &format!("Element {i}"), "Field Element", element);
+        }
+    }
+
+    /// Appends query values and Merkle authentication paths to the proof. Query values are
+    /// annotated row by row and column by column; if the values cannot be split into rows of
+    /// `num_columns` values of the specified size, they are annotated as a single data item.
+    fn write_queries(
+        &mut self,
+        path: &str,
+        queries: &Queries,
+        num_columns: usize,
+        value_size: usize,
+    ) {
+        let values = queries.values_bytes();
+        let row_size = num_columns * value_size;
+        if row_size == 0 || values.len() % row_size != 0 {
+            self.write(path, "Values", "Data", values);
+        } else {
+            for (row_idx, row) in values.chunks(row_size).enumerate() {
+                for (col_idx, value) in row.chunks(value_size).enumerate() {
+                    let description = format!("Row {row_idx}, Column {col_idx}");
+                    self.write(path, &description, "Field Element", value);
+                }
+            }
+        }
+        self.write(path, "Authentication Paths", "Data", queries.paths_bytes());
+    }
+}
+
+// PROOF SECTIONS
+// ================================================================================================
+
+/// Proof bytes grouped by annotation path; for each path, query values (or field elements) and
+/// authentication paths are accumulated separately.
+#[derive(Default)]
+struct Sections(Vec<(String, Vec<u8>, Vec<u8>)>);
+
+impl Sections {
+    fn append(&mut self, path: &str, is_auth_path: bool, bytes: &[u8]) {
+        let idx = match self.find(path) {
+            Some(idx) => idx,
+            None => {
+                self.0.push((path.to_string(), Vec::new(), Vec::new()));
+                self.0.len() - 1
+            }
+        };
+        let (_, values, paths) = &mut self.0[idx];
+        if is_auth_path {
+            paths.extend_from_slice(bytes);
+        } else {
+            values.extend_from_slice(bytes);
+        }
+    }
+
+    fn find(&self, path: &str) -> Option<usize> {
+        self.0.iter().position(|(section, _, _)| section == path)
+    }
+
+    fn remove(&mut self, idx: usize) -> (Vec<u8>, Vec<u8>) {
+        let (_, values, paths) = self.0.remove(idx);
+        (values, paths)
+    }
+
+    /// Removes the section with the specified path; if there is no such section, empty values
+    /// are returned.
+    fn take(&mut self, path: &str) -> (Vec<u8>, Vec<u8>) {
+        match self.find(path) {
+            Some(idx) => self.remove(idx),
+            None => (Vec::new(), Vec::new()),
+        }
+    }
+
+    /// Removes the section with the specified path and converts it into queries.
+    fn take_queries(&mut self, path: &str) -> Result<Queries, DeserializationError> {
+        match self.find(path) {
+            Some(idx) => {
+                let (values, paths) = self.remove(idx);
+                Ok(Queries::from_raw_parts(values, paths))
+            }
+            None => Err(DeserializationError::InvalidValue(format!(
+                "annotations for {path} are missing"
+            ))),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the name used for the trace segment with the specified index in annotation paths.
+fn segment_name(segment_idx: usize) -> String {
+    if segment_idx == 0 {
+        "Original".to_string()
+    } else {
+        format!("Interaction {segment_idx}")
+    }
+}
+
+/// Parses a prover annotation into the range of proof bytes it describes, its path, and its
+/// description. Returns `None` for annotations of verifier messages.
+fn parse_annotation(
+    annotation: &str,
+) -> Result<Option<ParsedAnnotation<'_>>, DeserializationError> {
+    let rest = match annotation.strip_prefix("P->V[") {
+        Some(rest) => rest,
+        None if annotation.starts_with("V->P") => return Ok(None),
+        None => return Err(invalid_annotation(annotation)),
+    };
+
+    let (range, rest) = rest
+        .split_once("]: ")
+        .ok_or_else(|| invalid_annotation(annotation))?;
+    let (start, end) = range
+        .split_once(':')
+        .ok_or_else(|| invalid_annotation(annotation))?;
+    let start = start
+        .parse::<usize>()
+        .map_err(|_| invalid_annotation(annotation))?;
+    let end = end
+        .parse::<usize>()
+        .map_err(|_| invalid_annotation(annotation))?;
+    if start > end {
+        return Err(invalid_annotation(annotation));
+    }
+
+    let mut parts = rest.splitn(3, ": ");
+    let path = parts.next().ok_or_else(|| invalid_annotation(annotation))?;
+    let description = parts.next().ok_or_else(|| invalid_annotation(annotation))?;
+    Ok(Some((start..end, path, description)))
+}
+
+fn invalid_annotation(annotation: &str) -> DeserializationError {
+    DeserializationError::InvalidValue(format!("annotation {annotation} is malformed"))
+}
+
+/// Writes the specified string into the `target` as a quoted JSON string.
+fn write_json_string(target: &mut String, value: &str) {
+    target.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => target.push_str("\\\""),
+            '\\' => target.push_str("\\\\"),
+            '\n' => target.push_str("\\n"),
+            '\r' => target.push_str("\\r"),
+            '\t' => target.push_str("\\t"),
+            c if (c as u32) < 0x20 => target.push_str(&format!("\\u{:04x}", c as u32)),
+            c => target.push(c),
+        }
+    }
+    target.push('"');
+}
+
+// JSON PARSER
+// ================================================================================================
+
+/// A JSON value; numbers are kept in their textual form, and `true`, `false`, and `null` literals
+/// are not distinguished since none of them are read from proof files.
+enum JsonValue {
+    Literal,
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    fn get(&self, key: &str) -> Result<&JsonValue, DeserializationError> {
+        match self {
+            JsonValue::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value)
+                .ok_or_else(|| {
+                    DeserializationError::InvalidValue(format!("JSON field {key} is missing"))
+                }),
+            _ => Err(DeserializationError::InvalidValue(format!(
+                "expected a JSON object with field {key}"
+            ))),
+        }
+    }
+
+    fn as_str(&self) -> Result<&str, DeserializationError> {
+        match self {
+            JsonValue::String(value) => Ok(value),
+            _ => Err(DeserializationError::InvalidValue(
+                "expected a JSON string".to_string(),
+            )),
+        }
+    }
+
+    fn as_usize(&self) -> Result<usize, DeserializationError> {
+        match self {
+            JsonValue::Number(value) => value.parse().map_err(|_| {
+                DeserializationError::InvalidValue(format!(
+                    "expected a non-negative JSON integer, but was {value}"
+                ))
+            }),
+            _ => Err(DeserializationError::InvalidValue(
+                "expected a JSON number".to_string(),
+            )),
+        }
+    }
+
+    fn as_array(&self) -> Result<&[JsonValue], DeserializationError> {
+        match self {
+            JsonValue::Array(values) => Ok(values),
+            _ => Err(DeserializationError::InvalidValue(
+                "expected a JSON array".to_string(),
+            )),
+        }
+    }
+}
+
+/// A minimal recursive-descent parser of JSON documents (RFC 8259).
+struct JsonParser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(source: &'a str) -> Self {
+        JsonParser { source, pos: 0 }
+    }
+
+    /// Parses the entire source as a single JSON value.
+    fn parse(mut self) -> Result<JsonValue, DeserializationError> {
+        let value = self.parse_value(0)?;
+        self.skip_whitespace();
+        if self.pos != self.source.len() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue, DeserializationError> {
+        if depth > MAX_JSON_DEPTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "JSON values cannot be nested deeper than {MAX_JSON_DEPTH} levels"
+            )));
+        }
+
+        self.skip_whitespace();
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                if self.consume_if(b'}') {
+                    return Ok(JsonValue::Object(fields));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.parse_string()?;
+                    self.skip_whitespace();
+                    self.expect(b':')?;
+                    fields.push((key, self.parse_value(depth + 1)?));
+                    self.skip_whitespace();
+                    if self.consume_if(b'}') {
+                        return Ok(JsonValue::Object(fields));
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut values = Vec::new();
+                self.skip_whitespace();
+                if self.consume_if(b']') {
+                    return Ok(JsonValue::Array(values));
+                }
+                loop {
+                    values.push(self.parse_value(depth + 1)?);
+                    self.skip_whitespace();
+                    if self.consume_if(b']') {
+                        return Ok(JsonValue::Array(values));
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            b'"' => Ok(JsonValue::String(self.parse_string()?)),
+            b't' => self.parse_literal("true"),
+            b'f' => self.parse_literal("false"),
+            b'n' => self.parse_literal("null"),
+            b'-' | b'0'..=b'9' => self.parse_number(),
+            c => Err(self.unexpected(c)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, DeserializationError> {
+        self.expect(b'"')?;
+        let mut result = String::new();
+        loop {
+            let rest = &self.source[self.pos..];
+            let end = rest
+                .find(|c: char| c == '"' || c == '\\' || (c as u32) < 0x20)
+                .ok_or(DeserializationError::UnexpectedEOF)?;
+            result.push_str(&rest[..end]);
+            self.pos += end;
+
+            match self.next()? {
+                b'"' => return Ok(result),
+                b'\\' => match self.next()? {
+                    b'"' => result.push('"'),
+                    b'\\' => result.push('\\'),
+                    b'/' => result.push('/'),
+                    b'b' => result.push('\u{8}'),
+                    b'f' => result.push('\u{c}'),
+                    b'n' => result.push('\n'),
+                    b'r' => result.push('\r'),
+                    b't' => result.push('\t'),
+                    b'u' => result.push(self.parse_unicode_escape()?),
+                    c => return Err(self.unexpected(c)),
+                },
+                c => return Err(self.unexpected(c)),
+            }
+        }
+    }
+
+    /// Parses the code point of a `\uXXXX` escape sequence (which must be followed by another
+    /// escape sequence if it encodes the high half of a surrogate pair).
+    fn parse_unicode_escape(&mut self) -> Result<char, DeserializationError> {
+        let mut code_point = self.parse_hex_u16()? as u32;
+        if (0xd800..0xdc00).contains(&code_point) {
+            self.expect(b'\\')?;
+            self.expect(b'u')?;
+            let low = self.parse_hex_u16()? as u32;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid UTF-16 surrogate pair in JSON string".to_string(),
+                ));
+            }
+            code_point = 0x10000 + ((code_point - 0xd800) << 10) + (low - 0xdc00);
+        }
+        char::from_u32(code_point).ok_or_else(|| {
+            DeserializationError::InvalidValue(format!(
+                "invalid code point {code_point:#x} in JSON string"
+            ))
+        })
+    }
+
+    fn parse_hex_u16(&mut self) -> Result<u16, DeserializationError> {
+        let digits = self
+            .source
+            .get(self.pos..self.pos + 4)
+            .ok_or(DeserializationError::UnexpectedEOF)?;
+        let value = u16::from_str_radix(digits, 16).map_err(|_| {
+            DeserializationError::InvalidValue(format!("invalid escape sequence \\u{digits}"))
+        })?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, DeserializationError> {
+        let start = self.pos;
+        self.consume_if(b'-');
+        let int_start = self.pos;
+        self.skip_digits();
+        let int_part = &self.source[int_start..self.pos];
+        if int_part.is_empty() || (int_part.len() > 1 && int_part.starts_with('0')) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid JSON number {}",
+                &self.source[start..self.pos]
+            )));
+        }
+        if self.consume_if(b'.') {
+            self.expect_digits()?;
+        }
+        if self.consume_if(b'e') || self.consume_if(b'E') {
+            if !self.consume_if(b'+') {
+                self.consume_if(b'-');
+            }
+            self.expect_digits()?;
+        }
+        Ok(JsonValue::Number(self.source[start..self.pos].to_string()))
+    }
+
+    fn parse_literal(&mut self, literal: &str) -> Result<JsonValue, DeserializationError> {
+        if !self.source[self.pos..].starts_with(literal) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid JSON literal at position {}",
+                self.pos
+            )));
+        }
+        self.pos += literal.len();
+        Ok(JsonValue::Literal)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.source.as_bytes().get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.source.as_bytes().get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect_digits(&mut self) -> Result<(), DeserializationError> {
+        let start = self.pos;
+        self.skip_digits();
+        if self.pos == start {
+            return Err(match self.source.as_bytes().get(self.pos) {
+                Some(&c) => self.unexpected(c),
+                None => DeserializationError::UnexpectedEOF,
+            });
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Result<u8, DeserializationError> {
+        self.source
+            .as_bytes()
+            .get(self.pos)
+            .copied()
+            .ok_or(DeserializationError::UnexpectedEOF)
+    }
+
+    fn next(&mut self) -> Result<u8, DeserializationError> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn consume_if(&mut self, expected: u8) -> bool {
+        if self.source.as_bytes().get(self.pos) == Some(&expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), DeserializationError> {
+        match self.next()? {
+            c if c == expected => Ok(()),
+            c => {
+                self.pos -= 1;
+                Err(self.unexpected(c))
+            }
+        }
+    }
+
+    fn unexpected(&self, c: u8) -> DeserializationError {
+        DeserializationError::InvalidValue(format!(
+            "unexpected character {} at position {} of JSON document",
+            c as char, self.pos
+        ))
+    }
+}
@@ -0,0 +1,1186 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    cbor, AnnotatedProof, Commitments, CompressionAlgorithm, Context, OodFrame, ProofMetadata,
+    Queries, StarkProof, StarkProofRef, CDDL_SCHEMA, MIN_PROOF_FORMAT_VERSION,
+    PROOF_FORMAT_VERSION,
+};
+use crate::{FieldExtension, LdtKind, ProofOptions, SecurityEstimate, TraceInfo, TraceLayout};
+use crypto::{
+    hashers::{Blake3_192, Blake3_256},
+    ElementHasher, Hasher, MerkleTree, QuerySampling,
+};
+use fri::{stir::StirProof, FriProof, FriProofLayer};
+use math::fields::f64::BaseElement;
+use rand_utils::rand_vector;
+use utils::{
+    collections::Vec, AsBytes, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROOF HEADER TESTS
+// ================================================================================================
+
+#[test]
+fn read_proof_context() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let bytes = proof.to_bytes();
+
+    // the header is read correctly even if query and FRI data is missing
+    let header_len = 2 + proof.context.to_bytes().len() + proof.commitments.to_bytes().len();
+    for source in [&bytes[..], &bytes[..header_len]] {
+        let header = StarkProof::read_context(source).unwrap();
+        assert_eq!(&proof.context, header.context());
+        assert_eq!(&proof.commitments, header.commitments());
+        assert_eq!(proof.get_trace_info(), header.get_trace_info());
+        assert_eq!(proof.options(), header.options());
+        assert_eq!(
+            proof.security_level::<Blake3>(true),
+            header.security_level::<Blake3>(true)
+        );
+        assert_eq!(
+            proof.security_level::<Blake3>(false),
+            header.security_level::<Blake3>(false)
+        );
+    }
+
+    // unversioned proofs are supported as well
+    let header = StarkProof::read_context(&proof.to_bytes_with_version(0)).unwrap();
+    assert_eq!(&proof.context, header.context());
+
+    // truncated headers cannot be read
+    assert!(StarkProof::read_context(&bytes[..header_len - 1]).is_err());
+}
+
+// SECURITY LEVEL TESTS
+// ================================================================================================
+
+#[test]
+fn proof_options_security_level() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let options = proof.options();
+    let trace_length = proof.trace_length();
+
+    // security levels estimated from the options match the levels of the proof
+    let conjectured =
+        options.security_level::<BaseElement, Blake3>(trace_length, SecurityEstimate::Conjectured);
+    let proven =
+        options.security_level::<BaseElement, Blake3>(trace_length, SecurityEstimate::Proven);
+    assert_eq!(proof.security_level::<Blake3>(true), conjectured);
+    assert_eq!(proof.security_level::<Blake3>(false), proven);
+    assert!(proven < conjectured);
+
+    // FRI layer grinding increases the proven security level since it is bounded by the
+    // soundness of the FRI commit phase for a base field of this size
+    let layer_grinding = options.clone().with_fri_layer_grinding(16);
+    assert!(
+        proven
+            < layer_grinding
+                .security_level::<BaseElement, Blake3>(trace_length, SecurityEstimate::Proven)
+    );
+
+    // security levels are bounded by the collision resistance of the hash function
+    let options = ProofOptions::new(255, 128, 32, FieldExtension::Cubic, 8, 31);
+    assert_eq!(
+        96,
+        options.security_level::<BaseElement, Blake3_192<BaseElement>>(
+            trace_length,
+            SecurityEstimate::Conjectured
+        )
+    );
+}
+
+// HEX ENCODING TESTS
+// ================================================================================================
+
+#[test]
+fn hex_round_trip() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let encoded = proof.to_hex();
+    assert_eq!(utils::hex::encode(&proof.to_bytes()), encoded);
+    assert_eq!(proof, StarkProof::from_hex(&encoded).unwrap());
+    assert_eq!(
+        proof,
+        StarkProof::from_hex(&encoded[2..].to_uppercase()).unwrap()
+    );
+
+    let commitments = proof.commitments.to_hex();
+    assert_eq!(
+        Ok(proof.commitments.clone()),
+        Commitments::from_hex(&commitments)
+    );
+
+    let digest = rand_digest();
+    assert_eq!(
+        Ok(digest),
+        <Blake3 as Hasher>::Digest::from_hex(&digest.to_hex())
+    );
+
+    assert!(StarkProof::from_hex(&encoded[..encoded.len() - 1]).is_err());
+    assert!(StarkProof::from_hex(&format!("{encoded}00")).is_err());
+}
+
+// PROOF METADATA TESTS
+// ================================================================================================
+
+#[test]
+fn metadata_round_trip() {
+    for metadata in [
+        ProofMetadata::new("winterfell 0.6.4", 1_700_000_000),
+        ProofMetadata::new("", 0)
+            .with_entry("job", "42")
+            .with_entry("owner", "ünïcode")
+            .bind(),
+    ] {
+        let mut proof = build_proof(TraceLayout::new(4, [2], [3]));
+        proof.metadata = Some(metadata);
+
+        assert_eq!(proof, StarkProof::from_bytes(&proof.to_bytes()).unwrap());
+        assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+        let compressed = proof.to_bytes_compressed();
+        assert_eq!(
+            proof,
+            StarkProof::from_bytes_compressed(&compressed).unwrap()
+        );
+        let annotated = proof.to_annotated::<Blake3>().unwrap();
+        assert_eq!(proof, StarkProof::from_annotated(&annotated).unwrap());
+    }
+}
+
+#[test]
+fn metadata_accessors() {
+    let metadata = ProofMetadata::new("winterfell 0.6.4", 1_700_000_000)
+        .with_entry("job", "42")
+        .with_entry("owner", "alice");
+    assert_eq!("winterfell 0.6.4", metadata.prover_version());
+    assert_eq!(1_700_000_000, metadata.timestamp());
+    assert_eq!(Some("alice"), metadata.get("owner"));
+    assert_eq!(None, metadata.get("group"));
+    assert_eq!(2, metadata.entries().len());
+    assert!(!metadata.is_bound());
+    assert!(metadata.bind().is_bound());
+}
+
+#[test]
+#[should_panic(expected = "metadata entry with key job already exists")]
+fn metadata_duplicate_key() {
+    ProofMetadata::new("winterfell 0.6.4", 0)
+        .with_entry("job", "1")
+        .with_entry("job", "2");
+}
+
+#[test]
+#[should_panic(expected = "proof metadata cannot be serialized using proof format version 1")]
+fn metadata_unsupported_version() {
+    let mut proof = build_proof(TraceLayout::new(4, [0], [0]));
+    proof.metadata = Some(ProofMetadata::new("winterfell 0.6.4", 0));
+    proof.to_bytes_with_version(1);
+}
+
+#[test]
+fn metadata_invalid_encodings() {
+    let read = |bytes: &[u8]| ProofMetadata::read_from_bytes(bytes);
+    let metadata = ProofMetadata::new("v", 7).with_entry("a", "b");
+    let bytes = metadata.to_bytes();
+    assert_eq!(Ok(metadata), read(&bytes));
+
+    // invalid binding flag
+    let mut invalid = bytes.clone();
+    invalid[0] = 2;
+    assert!(read(&invalid).is_err());
+
+    // prover version is not valid UTF-8
+    let mut invalid = bytes.clone();
+    invalid[3] = 0xff;
+    assert!(read(&invalid).is_err());
+
+    // duplicated keys
+    let mut invalid = bytes.clone();
+    invalid[12] = 2;
+    invalid.extend_from_slice(&bytes[14..]);
+    assert!(read(&invalid).is_err());
+
+    // invalid metadata presence flag in a serialized proof
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let mut invalid = proof.to_bytes();
+    *invalid.last_mut().unwrap() = 2;
+    assert!(StarkProof::from_bytes(&invalid).is_err());
+}
+
+// PROOF VIEW TESTS
+// ================================================================================================
+
+#[test]
+fn proof_view_round_trip() {
+    let mut proof = build_proof(TraceLayout::new(4, [2], [3]));
+    proof.metadata = Some(ProofMetadata::new("test", 1).with_entry("key", "value"));
+
+    for version in MIN_PROOF_FORMAT_VERSION..=PROOF_FORMAT_VERSION {
+        let mut expected = proof.clone();
+        if version < 2 {
+            expected.metadata = None;
+        }
+        let bytes = expected.to_bytes_with_version(version);
+
+        // views borrow variable-length parts of the proof directly from the serialized bytes
+        let view = StarkProofRef::from_bytes(&bytes).unwrap();
+        let fri_layer = view.fri_proof.layers()[0];
+        assert!(bytes
+            .as_ptr_range()
+            .contains(&fri_layer.values_bytes().as_ptr()));
+
+        assert_eq!(expected.view(), view);
+        assert_eq!(expected, view.into_owned());
+    }
+
+    // bytes following the proof are rejected
+    let mut bytes = proof.to_bytes();
+    bytes.push(0);
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        StarkProofRef::from_bytes(&bytes)
+    );
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        StarkProofRef::from_bytes(&bytes[..bytes.len() - 10])
+    );
+}
+
+// PROOF FORMAT VERSION TESTS
+// ================================================================================================
+
+#[test]
+fn versioned_round_trip() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let bytes = proof.to_bytes();
+    assert_eq!(&[0, PROOF_FORMAT_VERSION], &bytes[..2]);
+    assert_eq!(
+        Ok(PROOF_FORMAT_VERSION),
+        StarkProof::read_format_version(&bytes)
+    );
+    assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+
+    // proofs serialized using older versions of the format can still be read
+    for version in MIN_PROOF_FORMAT_VERSION..PROOF_FORMAT_VERSION {
+        let bytes = proof.to_bytes_with_version(version);
+        assert_eq!(Ok(version), StarkProof::read_format_version(&bytes));
+        assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+    }
+}
+
+#[test]
+fn streamed_round_trip() {
+    use utils::{IoReader, IoWriter};
+
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let mut writer = IoWriter::with_capacity(16, Vec::new());
+    proof.write_into(&mut writer);
+    let bytes = writer.finish().unwrap();
+    assert_eq!(proof.to_bytes(), bytes);
+
+    // bytes following the proof are left unread
+    let mut source = bytes.clone();
+    source.push(7);
+    let mut reader = IoReader::with_capacity(16, source.as_slice());
+    assert_eq!(proof, StarkProof::read_from(&mut reader).unwrap());
+    assert_eq!(bytes.len(), reader.num_consumed());
+}
+
+#[test]
+fn canonical_encoding() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let bytes = proof.to_bytes();
+    assert!(StarkProof::is_canonical(&bytes));
+
+    // proofs serialized using older versions of the format are not canonical
+    for version in MIN_PROOF_FORMAT_VERSION..PROOF_FORMAT_VERSION {
+        assert!(!StarkProof::is_canonical(
+            &proof.to_bytes_with_version(version)
+        ));
+    }
+
+    // neither are encodings with trailing bytes or invalid encodings
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert!(!StarkProof::is_canonical(&extended));
+    assert!(!StarkProof::is_canonical(&bytes[..bytes.len() - 1]));
+    assert!(!StarkProof::is_canonical(&[]));
+}
+
+#[test]
+fn unversioned_proof_format() {
+    // version 0 proofs have no header and start directly with the width of the main trace
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let bytes = proof.to_bytes_with_version(0);
+    assert_eq!(4, bytes[0]);
+    assert_eq!(&proof.to_bytes_with_version(1)[2..], &bytes[..]);
+
+    // version 2 proofs end with a byte indicating whether metadata is present
+    let bytes = proof.to_bytes_with_version(2);
+    assert_eq!(
+        &proof.to_bytes_with_version(1)[2..],
+        &bytes[2..bytes.len() - 1]
+    );
+    assert_eq!(Some(&0), bytes.last());
+}
+
+#[test]
+fn unsupported_proof_format_version() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let mut bytes = proof.to_bytes();
+
+    bytes[1] = PROOF_FORMAT_VERSION + 1;
+    assert_eq!(
+        Err(DeserializationError::UnsupportedVersion(
+            PROOF_FORMAT_VERSION + 1
+        )),
+        StarkProof::from_bytes(&bytes)
+    );
+    assert_eq!(
+        Err(DeserializationError::UnsupportedVersion(
+            PROOF_FORMAT_VERSION + 1
+        )),
+        StarkProof::read_format_version(&bytes)
+    );
+
+    // version 0 must not be preceded by a version header
+    bytes[1] = 0;
+    assert_eq!(
+        Err(DeserializationError::UnsupportedVersion(0)),
+        StarkProof::from_bytes(&bytes)
+    );
+}
+
+#[test]
+fn options_extensions_format_version() {
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let options = build_proof(trace_layout.clone()).options().clone();
+    assert!(!options.has_extensions());
+
+    for options in [
+        options.clone().with_fri_folding_schedule(&[16, 8, 4]),
+        options.clone().with_query_sampling(QuerySampling::BitSlice),
+        options.clone().with_ldt(LdtKind::Stir),
+        options.clone().with_num_ood_points(2),
+        options.clone().with_fri_layer_grinding(8),
+        options.with_merkle_multi_proofs(true),
+    ] {
+        assert!(options.has_extensions());
+        let mut proof = build_proof(trace_layout.clone());
+        proof.context = Context::new::<BaseElement>(&trace_info, options);
+
+        // extended options can be read only from proofs serialized using version 4 or later
+        let bytes = proof.context.to_bytes();
+        assert!(super::read_context(&mut SliceReader::new(&bytes), 4).is_ok());
+        for version in MIN_PROOF_FORMAT_VERSION..4 {
+            assert!(matches!(
+                super::read_context(&mut SliceReader::new(&bytes), version),
+                Err(DeserializationError::InvalidValue(_))
+            ));
+        }
+    }
+}
+
+#[test]
+fn options_extensions_older_version_rejected() {
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let mut proof = build_proof(trace_layout);
+    let options = proof.options().clone().with_num_ood_points(2);
+    proof.context = Context::new::<BaseElement>(&trace_info, options);
+
+    let mut bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+    bytes[1] = 3;
+    assert!(matches!(
+        StarkProof::from_bytes(&bytes),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+    assert!(matches!(
+        StarkProofRef::from_bytes(&bytes),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+}
+
+#[test]
+#[should_panic(
+    expected = "proof options extensions cannot be serialized using proof format version 3"
+)]
+fn options_extensions_unsupported_version() {
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let mut proof = build_proof(trace_layout);
+    let options = proof
+        .options()
+        .clone()
+        .with_fri_folding_schedule(&[16, 8, 4]);
+    proof.context = Context::new::<BaseElement>(&trace_info, options);
+    proof.to_bytes_with_version(3);
+}
+
+#[test]
+#[should_panic(expected = "proof format version must be between")]
+fn to_bytes_with_unsupported_version() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    proof.to_bytes_with_version(PROOF_FORMAT_VERSION + 1);
+}
+
+// DESERIALIZATION HARDENING TESTS
+// ================================================================================================
+
+#[test]
+fn malformed_proofs_are_rejected() {
+    let mut proof = build_proof(TraceLayout::new(4, [2], [3]));
+    proof.metadata = Some(ProofMetadata::new("test", 1).with_entry("key", "value"));
+
+    // none of the parsers panic on truncated or corrupted encodings
+    for bytes in [proof.to_bytes(), proof.to_cbor()] {
+        for i in 0..bytes.len() {
+            let _ = StarkProof::from_bytes(&bytes[..i]);
+            let _ = StarkProofRef::from_bytes(&bytes[..i]);
+            let _ = StarkProof::from_cbor(&bytes[..i]);
+
+            for mask in [0x01, 0x80, 0xff] {
+                let mut corrupted = bytes.clone();
+                corrupted[i] ^= mask;
+                let _ = StarkProof::from_bytes(&corrupted);
+                let _ = StarkProofRef::from_bytes(&corrupted);
+                let _ = StarkProof::from_cbor(&corrupted);
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid_context_values() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let bytes = proof.context.to_bytes();
+    let read_context = |bytes: &[u8]| Context::read_from(&mut SliceReader::new(bytes));
+
+    // trace length which does not fit into a u32 integer
+    let trace_length_idx = proof.context.trace_layout().to_bytes().len();
+    let mut invalid = bytes.clone();
+    invalid[trace_length_idx] = 32;
+    assert!(matches!(
+        read_context(&invalid),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+
+    // invalid proof options are rejected rather than causing a panic
+    let options_idx = bytes.len() - proof.options().to_bytes().len();
+    for (offset, value) in [
+        (0, 0),
+        (1, 1),
+        (1, 6),
+        (1, 255),
+        (2, 33),
+        (4, 3),
+        (4, 32),
+        (5, 5),
+    ] {
+        let mut invalid = bytes.clone();
+        invalid[options_idx + offset] = value;
+        assert!(matches!(
+            read_context(&invalid),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+}
+
+#[test]
+fn oversized_length_prefixes() {
+    // values of a FRI layer longer than the maximum are rejected before any bytes are read
+    let mut bytes = Vec::new();
+    bytes.write_u32(FriProofLayer::MAX_NUM_BYTES as u32 + 1);
+    assert!(matches!(
+        FriProofLayer::read_from(&mut SliceReader::new(&bytes)),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+    assert!(matches!(
+        Queries::read_from(&mut SliceReader::new(&bytes)),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+
+    // number of FRI partitions which cannot be represented by a usize
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let mut bytes = proof.fri_proof.to_bytes();
+    *bytes.last_mut().unwrap() = usize::BITS as u8;
+    assert!(matches!(
+        FriProof::read_from(&mut SliceReader::new(&bytes)),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+}
+
+// COMPRESSION TESTS
+// ================================================================================================
+
+#[test]
+fn salted_queries() {
+    let positions = [1, 7, 19, 80];
+    let (queries, root) = build_salted_queries(&positions, 4);
+    assert!(queries.is_salted());
+    assert_eq!(positions.len() * 32, queries.salts_bytes().len());
+
+    // leaves of the parsed proof are hashes of query values merged with salts
+    let (merkle_proof, values) = queries
+        .view()
+        .parse::<Blake3, BaseElement>(128, positions.len(), 4)
+        .unwrap();
+    assert_eq!(positions.len(), values.num_rows());
+    assert!(MerkleTree::verify_batch(&root, &positions, &merkle_proof).is_ok());
+
+    // queries without salts do not resolve to the root of the salted tree
+    let values_bytes = queries.values_bytes().to_vec();
+    let paths_bytes = queries.paths_bytes().to_vec();
+    let unsalted = Queries::from_raw_parts(values_bytes.clone(), paths_bytes.clone());
+    assert!(!unsalted.is_salted());
+    let (merkle_proof, _) = unsalted
+        .parse::<Blake3, BaseElement>(128, positions.len(), 4)
+        .unwrap();
+    assert!(MerkleTree::verify_batch(&root, &positions, &merkle_proof).is_err());
+
+    // a salt must be provided for every query
+    let mut salts_bytes = queries.salts_bytes().to_vec();
+    salts_bytes.truncate(salts_bytes.len() - 1);
+    let invalid = Queries::from_raw_parts_salted(values_bytes, paths_bytes, salts_bytes);
+    assert!(matches!(
+        invalid.parse::<Blake3, BaseElement>(128, positions.len(), 4),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn salted_proof_round_trip() {
+    let positions = [1, 7, 19, 80];
+    let mut proof = build_proof(TraceLayout::new(4, [0], [0]));
+    proof.trace_queries[0] = build_salted_queries(&positions, 4).0;
+    proof.constraint_queries = build_salted_queries(&positions, 2).0;
+
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+    assert_eq!(
+        proof,
+        StarkProofRef::from_bytes(&bytes).unwrap().into_owned()
+    );
+    assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+    assert_eq!(
+        proof,
+        StarkProof::from_bytes_compressed(&proof.to_bytes_compressed()).unwrap()
+    );
+
+    // salts cannot be represented in the annotated proof layout
+    assert!(proof.to_annotated::<Blake3>().is_err());
+}
+
+#[test]
+#[should_panic(expected = "salted queries cannot be serialized using proof format version 2")]
+fn salted_queries_unsupported_version() {
+    let mut proof = build_proof(TraceLayout::new(4, [0], [0]));
+    proof.constraint_queries = build_salted_queries(&[1, 7, 19, 80], 2).0;
+    proof.to_bytes_with_version(2);
+}
+
+#[test]
+fn multi_proof_queries() {
+    let positions = [1, 7, 19, 80];
+    let (queries, root) = build_salted_queries(&positions, 4);
+    let multi = queries
+        .clone()
+        .into_multi_proof::<Blake3, BaseElement>(128, &positions);
+    assert_eq!(queries.values_bytes(), multi.values_bytes());
+    assert_eq!(queries.salts_bytes(), multi.salts_bytes());
+
+    // leaves of the multi-proof are hashes of query values merged with salts
+    let (merkle_proof, leaves, values) = multi
+        .view()
+        .parse_multi::<Blake3, BaseElement>(128, positions.len(), 4)
+        .unwrap();
+    assert_eq!(positions.len(), values.num_rows());
+    assert!(MerkleTree::verify_multi(&root, &positions, &leaves, &merkle_proof).is_ok());
+
+    // the multi-proof must be built for a tree over the specified domain
+    assert!(matches!(
+        multi
+            .view()
+            .parse_multi::<Blake3, BaseElement>(256, positions.len(), 4),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+
+    // batch proofs cannot be parsed as multi-proofs and vice versa
+    assert!(queries
+        .view()
+        .parse_multi::<Blake3, BaseElement>(128, positions.len(), 4)
+        .is_err());
+    assert!(multi
+        .parse::<Blake3, BaseElement>(128, positions.len(), 4)
+        .is_err());
+
+    // the multi-proof flag of the proof options survives all encodings
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let mut proof = build_proof(trace_layout);
+    let options = proof.options().clone().with_merkle_multi_proofs(true);
+    proof.context = Context::new::<BaseElement>(&trace_info, options);
+    assert_eq!(proof, StarkProof::from_bytes(&proof.to_bytes()).unwrap());
+    assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+    assert!(proof
+        .describe::<Blake3>()
+        .to_string()
+        .contains("Merkle proofs: multi-proofs"));
+}
+
+#[test]
+fn compressed_round_trip() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+
+    let compressed = proof.to_bytes_compressed();
+    assert_eq!(
+        proof,
+        StarkProof::from_bytes_compressed(&compressed).unwrap()
+    );
+
+    #[allow(unused_mut)]
+    let mut algorithms = vec![CompressionAlgorithm::None];
+    #[cfg(feature = "deflate")]
+    algorithms.push(CompressionAlgorithm::Deflate);
+    #[cfg(feature = "zstd")]
+    algorithms.push(CompressionAlgorithm::Zstd);
+
+    for algorithm in algorithms {
+        let compressed = proof.to_bytes_compressed_with(algorithm);
+        assert_eq!(algorithm as u8, compressed[0]);
+        assert_eq!(
+            proof,
+            StarkProof::from_bytes_compressed(&compressed).unwrap()
+        );
+    }
+}
+
+#[test]
+fn compression_deduplicates_digests() {
+    // all trace queries are authenticated against the same tree, and thus, all Merkle path
+    // nodes in these queries are repeated
+    let queries = build_queries(4);
+    let mut proof = build_proof(TraceLayout::new(4, [2], [3]));
+    proof.trace_queries = vec![queries.clone(), queries];
+
+    let uncompressed = proof.to_bytes();
+    let compressed = proof.to_bytes_compressed_with(CompressionAlgorithm::None);
+    let num_path_bytes = proof.trace_queries[1].paths_bytes().len();
+    assert!(compressed.len() + num_path_bytes / 2 < uncompressed.len());
+    assert_eq!(
+        proof,
+        StarkProof::from_bytes_compressed(&compressed).unwrap()
+    );
+}
+
+#[test]
+fn compressed_invalid_encodings() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let compressed = proof.to_bytes_compressed_with(CompressionAlgorithm::None);
+
+    // unknown compression algorithm
+    let mut bytes = compressed.clone();
+    bytes[0] = 3;
+    assert!(StarkProof::from_bytes_compressed(&bytes).is_err());
+
+    // empty input
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        StarkProof::from_bytes_compressed(&[])
+    );
+
+    // truncated input
+    let bytes = &compressed[..compressed.len() - 1];
+    assert!(StarkProof::from_bytes_compressed(bytes).is_err());
+}
+
+// CBOR ENCODING TESTS
+// ================================================================================================
+
+#[test]
+fn cbor_round_trip() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let encoded = proof.to_cbor();
+    assert_eq!(proof, StarkProof::from_cbor(&encoded).unwrap());
+
+    // encoding must be deterministic
+    assert_eq!(encoded, proof.clone().to_cbor());
+
+    // a proof with an auxiliary trace segment should be encoded correctly as well
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+}
+
+#[test]
+fn cddl_schema_field_extensions() {
+    // every field extension is listed in the schema with the value used by the encoding
+    for (extension, name) in [
+        (FieldExtension::None, "none"),
+        (FieldExtension::Quadratic, "quadratic"),
+        (FieldExtension::Cubic, "cubic"),
+        (FieldExtension::Quartic, "quartic"),
+    ] {
+        let entry = format!("  {}: {},", name, extension.degree());
+        assert!(CDDL_SCHEMA.contains(&entry), "schema does not list {entry}");
+    }
+}
+
+#[test]
+fn cddl_schema_queries() {
+    // queries are encoded as [values, paths, salts], and the schema lists all three fields
+    let rule = CDDL_SCHEMA.split("\nqueries = [").nth(1).unwrap();
+    let rule = &rule[..rule.find("\n]").unwrap()];
+    let fields = rule
+        .lines()
+        .filter_map(|line| line.trim().split(':').next())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["values", "paths", "salts"], fields);
+}
+
+#[test]
+fn folding_schedule_round_trip() {
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let mut proof = build_proof(trace_layout);
+    let options = proof
+        .options()
+        .clone()
+        .with_fri_folding_schedule(&[16, 8, 4]);
+    proof.context = Context::new::<BaseElement>(&trace_info, options);
+
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+    assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+}
+
+#[test]
+fn query_sampling_round_trip() {
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let mut proof = build_proof(trace_layout);
+    let options = proof
+        .options()
+        .clone()
+        .with_query_sampling(QuerySampling::BitSlice);
+    proof.context = Context::new::<BaseElement>(&trace_info, options);
+
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+    assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+}
+
+#[test]
+fn fri_layer_nonces_round_trip() {
+    let mut proof = build_proof(TraceLayout::new(4, [0], [0]));
+    proof.fri_proof = proof.fri_proof.with_layer_nonces(vec![3, u64::MAX]);
+
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+    assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+    assert_eq!(
+        proof,
+        StarkProof::from_bytes_compressed(&proof.to_bytes_compressed()).unwrap()
+    );
+}
+
+#[test]
+fn fri_layer_grinding_round_trip() {
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let mut proof = build_proof(trace_layout);
+    let options = proof.options().clone().with_fri_layer_grinding(8);
+    proof.fri_proof = proof.fri_proof.with_layer_nonces(vec![3, u64::MAX]);
+
+    // the factor is encoded with and without a preceding folding schedule
+    for options in [options.clone(), options.with_fri_folding_schedule(&[16, 8])] {
+        proof.context = Context::new::<BaseElement>(&trace_info, options);
+        let bytes = proof.to_bytes();
+        assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+        assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+        assert!(proof
+            .describe::<Blake3>()
+            .to_string()
+            .contains("FRI layer grinding factor: 8"));
+    }
+
+    // the factor must be present in CBOR if and only if the layer grinding flag is set
+    let mut options = Vec::new();
+    cbor::write_options(&mut options, proof.options());
+    let mut bytes = options.clone();
+    bytes[0] = 0x87;
+    bytes.pop();
+    assert!(cbor::read_options(&mut SliceReader::new(&bytes)).is_err());
+    // the field extension follows the array header, 28 queries encoded in 2 bytes, the blowup
+    // factor, and the grinding factor
+    let mut bytes = options;
+    bytes[5] &= !0x08;
+    assert!(cbor::read_options(&mut SliceReader::new(&bytes)).is_err());
+}
+
+#[test]
+fn stir_proof_round_trip() {
+    let trace_layout = TraceLayout::new(4, [0], [0]);
+    let trace_info = TraceInfo::new_multi_segment(trace_layout.clone(), 16, vec![1, 2, 3]);
+    let mut proof = build_proof(trace_layout);
+    let options = proof.options().clone().with_ldt(LdtKind::Stir);
+    proof.context = Context::new::<BaseElement>(&trace_info, options);
+
+    // STIR round commitments are a part of the STIR proof
+    let trace_root = rand_digest();
+    proof.commitments = Commitments::new::<Blake3>(vec![trace_root], rand_digest(), vec![]);
+    proof.fri_proof = FriProof::default();
+    let round =
+        |_| FriProofLayer::from_raw_parts(rand_vector(64), build_queries(1).paths_bytes().to_vec());
+    proof.stir_proof = Some(StirProof::from_raw_parts(
+        vec![rand_digest(), rand_digest()].to_bytes(),
+        rand_vector(8),
+        (0..2).map(round).collect(),
+        rand_vector(16),
+        Some(round(0)),
+    ));
+
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+    assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+    assert_eq!(
+        proof,
+        StarkProof::from_bytes_compressed(&proof.to_bytes_compressed()).unwrap()
+    );
+    let (trace_roots, _) = proof
+        .commitments
+        .clone()
+        .parse_without_fri::<Blake3>(1)
+        .unwrap();
+    assert_eq!(vec![trace_root], trace_roots);
+
+    // STIR proofs are described, but cannot be arranged into the annotated proof layout
+    assert!(proof
+        .describe::<Blake3>()
+        .to_string()
+        .contains("STIR:\n  2 rounds"));
+    assert!(proof.to_annotated::<Blake3>().is_err());
+}
+
+#[test]
+fn cbor_encoding_structure() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let encoded = proof.to_cbor();
+
+    // the proof is an array of 7 items, the first of which is the context (an array of 5 items)
+    // starting with the trace layout (an array of 3 items)
+    assert_eq!(&[0x87, 0x85, 0x83, 0x04, 0x80, 0x80], &encoded[..6]);
+
+    // the proof ends with the proof-of-work nonce encoded as an 8-byte unsigned integer
+    let mut nonce = vec![0x1b];
+    nonce.extend_from_slice(&proof.pow_nonce.to_be_bytes());
+    assert_eq!(&nonce[..], &encoded[encoded.len() - 9..]);
+}
+
+#[test]
+fn cbor_invalid_encodings() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let encoded = proof.to_cbor();
+
+    // trailing bytes
+    let mut bytes = encoded.clone();
+    bytes.push(0);
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        StarkProof::from_cbor(&bytes)
+    );
+
+    // truncated input
+    let bytes = &encoded[..encoded.len() - 1];
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        StarkProof::from_cbor(bytes)
+    );
+
+    // main trace width encoded in a non-shortest form
+    let mut bytes = encoded[..3].to_vec();
+    bytes.extend_from_slice(&[0x18, 0x04]);
+    bytes.extend_from_slice(&encoded[4..]);
+    assert!(StarkProof::from_cbor(&bytes).is_err());
+
+    // wrong number of items in the top-level array
+    let mut bytes = encoded.clone();
+    bytes[0] = 0x86;
+    assert!(StarkProof::from_cbor(&bytes).is_err());
+
+    // invalid trace layout: a main trace segment with no columns
+    let mut bytes = encoded;
+    bytes[3] = 0x00;
+    assert!(StarkProof::from_cbor(&bytes).is_err());
+}
+
+// SERDE TESTS
+// ================================================================================================
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_json_round_trip() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+
+    // the proof is encoded as a hex string of its binary encoding
+    let encoded = serde_json::to_string(&proof).unwrap();
+    assert_eq!(
+        format!("\"{}\"", utils::hex::encode(&proof.to_bytes())),
+        encoded
+    );
+    assert_eq!(proof, serde_json::from_str::<StarkProof>(&encoded).unwrap());
+
+    let encoded = serde_json::to_string(&proof.context).unwrap();
+    assert_eq!(proof.context, serde_json::from_str(&encoded).unwrap());
+    let encoded = serde_json::to_string(proof.options()).unwrap();
+    assert_eq!(
+        proof.options(),
+        &serde_json::from_str::<ProofOptions>(&encoded).unwrap()
+    );
+
+    // field elements and digests are encoded as hex strings as well
+    let value = BaseElement::new(5);
+    let encoded = serde_json::to_string(&value).unwrap();
+    assert_eq!("\"0x0500000000000000\"", encoded);
+    assert_eq!(value, serde_json::from_str(&encoded).unwrap());
+    let digest = rand_digest();
+    let encoded = serde_json::to_string(&digest).unwrap();
+    assert_eq!(digest, serde_json::from_str(&encoded).unwrap());
+
+    // invalid encodings are rejected
+    assert!(serde_json::from_str::<StarkProof>("\"0x0102\"").is_err());
+    assert!(serde_json::from_str::<StarkProof>("[1, 2]").is_err());
+    assert!(serde_json::from_str::<BaseElement>("\"0xffffffffffffffff\"").is_err());
+}
+
+// STONE ANNOTATED PROOF TESTS
+// ================================================================================================
+
+#[test]
+fn annotated_round_trip() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let annotated = proof.to_annotated::<Blake3>().unwrap();
+    assert_eq!(proof, StarkProof::from_annotated(&annotated).unwrap());
+
+    // the proof should survive a round trip through a JSON proof file
+    let json = annotated.to_json();
+    let parsed = AnnotatedProof::from_json(&json).unwrap();
+    assert_eq!(annotated, parsed);
+    assert_eq!(proof, StarkProof::from_annotated(&parsed).unwrap());
+}
+
+#[test]
+fn annotated_proof_structure() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let annotated = proof.to_annotated::<Blake3>().unwrap();
+    let annotations = annotated.annotations();
+
+    // the proof starts with the commitment to the main trace segment
+    let trace_root = &proof.commitments.as_bytes()[..32];
+    assert_eq!(trace_root, &annotated.proof_bytes()[..32]);
+    assert!(annotations[0].starts_with(
+        "P->V[0:32]: /winterfell/STARK/Original/Commit on Trace: Commitment: Hash(0x"
+    ));
+
+    // the proof-of-work nonce is written in big-endian byte order
+    let pow = annotations
+        .iter()
+        .find(|annotation| annotation.contains("/Proof of Work"))
+        .unwrap();
+    assert!(pow.ends_with(": Nonce: POW(0xfffffffffffffffe)"));
+
+    // main trace values are annotated one by one
+    let num_values = annotations
+        .iter()
+        .filter(|annotation| annotation.contains("/Original/Decommitment: Row "))
+        .count();
+    assert_eq!(4 * 4, num_values);
+
+    // annotations cover all proof bytes
+    let last = annotations.last().unwrap();
+    let end = format!(":{}]", annotated.proof_bytes().len());
+    assert!(last.contains(&end));
+
+    // stone parameters are derived from proof options
+    let json = annotated.to_json();
+    assert!(json.contains("\"fri_step_list\": [0, 2]"));
+    assert!(json.contains("\"last_layer_degree_bound\": 8"));
+    assert!(json.contains("\"n_queries\": 28"));
+    assert!(json.contains("\"proof_of_work_bits\": 16"));
+    assert!(json.contains("\"log_n_cosets\": 3"));
+}
+
+#[test]
+fn annotated_invalid_proofs() {
+    let proof = build_proof(TraceLayout::new(4, [0], [0]));
+    let annotated = proof.to_annotated::<Blake3>().unwrap();
+    let json = annotated.to_json();
+
+    // verifier messages are ignored
+    let with_verifier_message = json.replacen(
+        "\"annotations\": [",
+        "\"annotations\": [\n        \"V->P: /winterfell/STARK/Original: Random Element\",",
+        1,
+    );
+    let parsed = AnnotatedProof::from_json(&with_verifier_message).unwrap();
+    assert_eq!(proof, StarkProof::from_annotated(&parsed).unwrap());
+
+    // extra proof bytes which are not described by annotations
+    let trailing_bytes = json.replace("\"proof_hex\": \"0x", "\"proof_hex\": \"0x00");
+    let parsed = AnnotatedProof::from_json(&trailing_bytes).unwrap();
+    assert!(StarkProof::from_annotated(&parsed).is_err());
+
+    // annotations with unknown paths
+    let unknown_path = json.replace("/Composition/Decommitment", "/Composition/Unknown");
+    let parsed = AnnotatedProof::from_json(&unknown_path).unwrap();
+    assert!(StarkProof::from_annotated(&parsed).is_err());
+
+    // malformed JSON documents
+    assert!(AnnotatedProof::from_json(&json[..json.len() - 3]).is_err());
+    assert!(AnnotatedProof::from_json(&json.replace("\"proof_hex\"", "\"proof\"")).is_err());
+    assert!(AnnotatedProof::from_json(&format!("{json}{{}}")).is_err());
+
+    // commitments are not digests of the specified hash function
+    let mut proof = proof;
+    proof.commitments = Commitments::from_bytes(vec![1, 2, 3]);
+    assert!(proof.to_annotated::<Blake3>().is_err());
+}
+
+// PROOF DESCRIPTION TESTS
+// ================================================================================================
+
+#[test]
+fn describe_proof() {
+    let proof = build_proof(TraceLayout::new(4, [2], [3]));
+    let description = proof.describe::<Blake3>().to_string();
+
+    assert!(description.starts_with(&format!("STARK proof: {} bytes", proof.to_bytes().len())));
+    assert!(description.contains("2^4 steps, 4 main columns, 2 auxiliary columns"));
+    assert!(description.contains(&format!(
+        "conjectured: {} bits",
+        proof.security_level::<Blake3>(true)
+    )));
+
+    // all commitments are listed as digests
+    let (trace_roots, constraint_root, fri_roots) =
+        proof.commitments.clone().parse::<Blake3>(2, 1).unwrap();
+    for root in trace_roots
+        .iter()
+        .chain([&constraint_root])
+        .chain(fri_roots.iter())
+    {
+        assert!(description.contains(&root.to_hex()));
+    }
+    assert!(description.contains("FRI layer 0: "));
+    assert!(description.contains("FRI remainder: "));
+
+    // sizes of all sections add up to the size of the serialized proof
+    let sections = description
+        .split("Sections:\n")
+        .nth(1)
+        .unwrap()
+        .split("Commitments:")
+        .next()
+        .unwrap();
+    let total_size: usize = sections
+        .lines()
+        .map(|line| {
+            let size = line.split(": ").nth(1).unwrap().split(' ').next().unwrap();
+            size.parse::<usize>().unwrap()
+        })
+        .sum();
+    assert_eq!(proof.to_bytes().len(), total_size);
+
+    // commitments which are not digests of the specified hash function are printed as raw bytes
+    let description = proof.describe::<Blake3_192<BaseElement>>().to_string();
+    assert!(description.contains("could not be parsed"));
+    assert!(description.contains(&proof.commitments.to_hex()));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_proof(trace_layout: TraceLayout) -> StarkProof {
+    let trace_info = TraceInfo::new_multi_segment(trace_layout, 16, vec![1, 2, 3]);
+    let options = ProofOptions::new(28, 8, 16, FieldExtension::None, 4, 7);
+    let context = Context::new::<BaseElement>(&trace_info, options);
+
+    let num_segments = trace_info.layout().num_segments();
+    let trace_roots = (0..num_segments).map(|_| rand_digest()).collect();
+    let fri_roots = vec![rand_digest(), rand_digest()];
+    let commitments = Commitments::new::<Blake3>(trace_roots, rand_digest(), fri_roots);
+
+    let trace_queries = (0..num_segments)
+        .map(|_| build_queries(trace_info.width()))
+        .collect();
+
+    let mut ood_frame = OodFrame::default();
+    ood_frame.set_trace_states(&[
+        rand_vector::<BaseElement>(trace_info.width()),
+        rand_vector::<BaseElement>(trace_info.width()),
+    ]);
+    ood_frame.set_constraint_evaluations(&rand_vector::<BaseElement>(2));
+
+    let fri_paths = build_queries(1).paths_bytes().to_vec();
+    let fri_layer = FriProofLayer::from_raw_parts(rand_vector(64), fri_paths);
+    let fri_proof = FriProof::from_raw_parts(vec![fri_layer], rand_vector(64), 1);
+
+    StarkProof {
+        context,
+        commitments,
+        trace_queries,
+        constraint_queries: build_queries(2),
+        ood_frame,
+        fri_proof,
+        stir_proof: None,
+        pow_nonce: u64::MAX - 1,
+        metadata: None,
+    }
+}
+
+fn build_queries(values_per_query: usize) -> Queries {
+    let leaves = (0..128).map(|_| rand_digest()).collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3>::new(leaves).unwrap();
+    let positions = [1, 7, 19, 80];
+    let merkle_proof = tree.prove_batch(&positions).unwrap();
+    let values = positions
+        .iter()
+        .map(|_| rand_vector::<BaseElement>(values_per_query))
+        .collect();
+    Queries::new::<Blake3, BaseElement, MerkleTree<Blake3>>(merkle_proof, values)
+}
+
+/// Returns salted queries against a tree of 128 leaves at the specified positions, together with
+/// the root of the tree.
+fn build_salted_queries(
+    positions: &[usize],
+    values_per_query: usize,
+) -> (Queries, <Blake3 as Hasher>::Digest) {
+    let rows = (0..128)
+        .map(|_| rand_vector::<BaseElement>(values_per_query))
+        .collect::<Vec<_>>();
+    let salts = (0..128).map(|_| rand_digest()).collect::<Vec<_>>();
+    let leaves = rows
+        .iter()
+        .zip(salts.iter())
+        .map(|(row, &salt)| Blake3::merge(&[Blake3::hash_elements(row), salt]))
+        .collect();
+    let tree = MerkleTree::<Blake3>::new(leaves).unwrap();
+
+    let merkle_proof = tree.prove_batch(positions).unwrap();
+    let values = positions.iter().map(|&pos| rows[pos].clone()).collect();
+    let salts = positions.iter().map(|&pos| salts[pos]).collect();
+    let queries = Queries::new_salted::<Blake3, BaseElement>(merkle_proof, values, salts);
+    (queries, *tree.root())
+}
+
+fn rand_digest() -> <Blake3 as Hasher>::Digest {
+    Blake3::hash(&rand_vector::<u8>(32))
+}
@@ -0,0 +1,180 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_security_level, read_context, read_format_version, read_metadata, CommitmentsRef, Context,
+    OodFrameRef, ProofMetadata, QueriesRef, StarkProof,
+};
+use crate::{LdtKind, ProofOptions, TraceInfo, TraceLayout};
+use crypto::Hasher;
+use fri::{stir::StirProof, FriProofRef};
+use utils::{collections::Vec, ByteReader, Deserializable, DeserializationError, SliceReader};
+
+// STARK PROOF VIEW
+// ================================================================================================
+/// A view of a serialized STARK proof which borrows commitments, query decommitments,
+/// out-of-domain evaluations, and the FRI proof from the underlying bytes.
+///
+/// A view can be read directly from the bytes of a proof serialized via
+/// [StarkProof::to_bytes()] using [from_bytes()](StarkProofRef::from_bytes) function. Unlike
+/// [StarkProof::from_bytes()], this does not copy query values, Merkle paths, or any other
+/// variable-length parts of the proof into owned buffers; only the proof context and metadata
+/// (both of which are small) are parsed eagerly. Digests and field elements are parsed from the
+/// referenced bytes on demand (e.g., by the verifier), and thus, the cost of deserialization is
+/// not paid twice.
+///
+/// A view can also be obtained from an owned proof via [StarkProof::view()], and converted
+/// into an owned proof via [into_owned()](StarkProofRef::into_owned).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StarkProofRef<'a> {
+    /// Basic metadata about the execution of the computation described by this proof.
+    pub context: Context,
+    /// Commitments made by the prover during the commit phase of the protocol.
+    pub commitments: CommitmentsRef<'a>,
+    /// Decommitments of extended execution trace values (for all trace segments) at position
+    ///  queried by the verifier.
+    pub trace_queries: Vec<QueriesRef<'a>>,
+    /// Decommitments of constraint composition polynomial evaluations at positions queried by
+    /// the verifier.
+    pub constraint_queries: QueriesRef<'a>,
+    /// Trace and constraint polynomial evaluations at an out-of-domain point.
+    pub ood_frame: OodFrameRef<'a>,
+    /// Low-degree proof for a DEEP composition polynomial generated via FRI; if the proof options
+    /// select [LdtKind::Stir], this is an empty (default) FRI proof.
+    pub fri_proof: FriProofRef<'a>,
+    /// Low-degree proof for a DEEP composition polynomial generated via STIR; this is set if and
+    /// only if the proof options select [LdtKind::Stir]. Unlike the rest of the proof, a STIR
+    /// proof is copied into owned buffers.
+    pub stir_proof: Option<StirProof>,
+    /// Proof-of-work nonce for query seed grinding.
+    pub pow_nonce: u64,
+    /// Optional provenance information attached to the proof.
+    pub metadata: Option<ProofMetadata>,
+}
+
+impl<'a> StarkProofRef<'a> {
+    /// Returns STARK protocol parameters used to generate this proof.
+    pub fn options(&self) -> &ProofOptions {
+        self.context.options()
+    }
+
+    /// Returns a layout describing how columns of the execution trace described by this context
+    /// are arranged into segments.
+    pub fn trace_layout(&self) -> &TraceLayout {
+        self.context.trace_layout()
+    }
+
+    /// Returns trace length for the computation described by this proof.
+    pub fn trace_length(&self) -> usize {
+        self.context.trace_length()
+    }
+
+    /// Returns trace info for the computation described by this proof.
+    pub fn get_trace_info(&self) -> TraceInfo {
+        self.context.get_trace_info()
+    }
+
+    /// Returns the size of the LDE domain for the computation described by this proof.
+    pub fn lde_domain_size(&self) -> usize {
+        self.context.lde_domain_size()
+    }
+
+    /// Returns security level of this proof (in bits).
+    ///
+    /// This returns the same value as [StarkProof::security_level()] would return for the owned
+    /// proof.
+    ///
+    /// # Panics
+    /// Panics if `conjectured` is false and the `std` feature is not enabled.
+    pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
+        get_security_level::<H>(&self.context, conjectured)
+    }
+
+    /// Returns an owned copy of the proof described by this view.
+    pub fn into_owned(self) -> StarkProof {
+        StarkProof {
+            context: self.context,
+            commitments: self.commitments.into_owned(),
+            trace_queries: self
+                .trace_queries
+                .into_iter()
+                .map(QueriesRef::into_owned)
+                .collect(),
+            constraint_queries: self.constraint_queries.into_owned(),
+            ood_frame: self.ood_frame.into_owned(),
+            fri_proof: self.fri_proof.into_owned(),
+            stir_proof: self.stir_proof,
+            pow_nonce: self.pow_nonce,
+            metadata: self.metadata,
+        }
+    }
+
+    // DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a view of the STARK proof serialized in the specified `source`.
+    ///
+    /// Proofs serialized using any version of the binary proof format supported by
+    /// [StarkProof::from_bytes()] can be read.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The proof was serialized using an unsupported version of the proof format; in this case
+    ///   [DeserializationError::UnsupportedVersion] is returned.
+    /// * A valid STARK proof could not be read from the specified `source`, or the `source`
+    ///   contains bytes following the proof.
+    pub fn from_bytes(source: &'a [u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let proof = Self::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(proof)
+    }
+
+    /// Reads a view of a STARK proof from the specified `source` and returns the result.
+    ///
+    /// Unlike [from_bytes()](StarkProofRef::from_bytes), this function does not require the
+    /// `source` to be fully consumed; bytes following the proof are left unread.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The proof was serialized using an unsupported version of the proof format; in this case
+    ///   [DeserializationError::UnsupportedVersion] is returned.
+    /// * A valid STARK proof could not be read from the specified `source`.
+    pub fn read_from(source: &mut SliceReader<'a>) -> Result<Self, DeserializationError> {
+        let version = read_format_version(source)?;
+        let context = read_context(source, version)?;
+        let commitments = CommitmentsRef::read_from(source)?;
+
+        let num_trace_segments = context.trace_layout().num_segments();
+        let mut trace_queries = Vec::with_capacity(num_trace_segments);
+        for _ in 0..num_trace_segments {
+            trace_queries.push(QueriesRef::read_from_with_version(source, version)?);
+        }
+
+        let constraint_queries = QueriesRef::read_from_with_version(source, version)?;
+        let ood_frame = OodFrameRef::read_from(source)?;
+        let (fri_proof, stir_proof) = match context.options().ldt() {
+            LdtKind::Fri => (FriProofRef::read_from(source)?, None),
+            LdtKind::Stir => (FriProofRef::default(), Some(StirProof::read_from(source)?)),
+        };
+        Ok(StarkProofRef {
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            stir_proof,
+            pow_nonce: source.read_u64()?,
+            metadata: if version >= 2 {
+                read_metadata(source)?
+            } else {
+                None
+            },
+        })
+    }
+}
@@ -7,20 +7,36 @@ use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion
 use math::fields::f128;
 use rand_utils::rand_value;
 use winter_crypto::{
-    hashers::{Blake3_256, GriffinJive64_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256},
+    hashers::{
+        AnemoiJive64_256, Blake2s_256, Blake3_256, GriffinJive64_256, Monolith64_256,
+        Poseidon62_248, Poseidon64_256, Rp62_248, Rp64_256, RpJive64_256, Rpo256, Sha2_256,
+        Sha3_256, Tip5_320,
+    },
     Hasher,
 };
 
 type Blake3 = Blake3_256<f128::BaseElement>;
 type Blake3Digest = <Blake3 as Hasher>::Digest;
 
+type Blake2 = Blake2s_256<f128::BaseElement>;
+type Blake2Digest = <Blake2 as Hasher>::Digest;
+
+type Sha2 = Sha2_256<f128::BaseElement>;
+type Sha2Digest = <Sha2 as Hasher>::Digest;
+
 type Sha3 = Sha3_256<f128::BaseElement>;
 type Sha3Digest = <Sha3 as Hasher>::Digest;
 
 type Rp62_248Digest = <Rp62_248 as Hasher>::Digest;
 type Rp64_256Digest = <Rp64_256 as Hasher>::Digest;
+type Rpo256Digest = <Rpo256 as Hasher>::Digest;
 type RpJive64_256Digest = <RpJive64_256 as Hasher>::Digest;
 type GriffinJive64_256Digest = <GriffinJive64_256 as Hasher>::Digest;
+type AnemoiJive64_256Digest = <AnemoiJive64_256 as Hasher>::Digest;
+type Monolith64_256Digest = <Monolith64_256 as Hasher>::Digest;
+type Tip5_320Digest = <Tip5_320 as Hasher>::Digest;
+type Poseidon62_248Digest = <Poseidon62_248 as Hasher>::Digest;
+type Poseidon64_256Digest = <Poseidon64_256 as Hasher>::Digest;
 
 fn blake3(c: &mut Criterion) {
     let v: [Blake3Digest; 2] = [Blake3::hash(&[1u8]), Blake3::hash(&[2u8])];
@@ -42,6 +58,66 @@ fn blake3(c: &mut Criterion) {
     });
 }
 
+fn blake2s(c: &mut Criterion) {
+    let v: [Blake2Digest; 2] = [Blake2::hash(&[1u8]), Blake2::hash(&[2u8])];
+    c.bench_function("hash_blake2s (cached)", |bench| {
+        bench.iter(|| Blake2::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_blake2s (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Blake2::hash(&rand_value::<u64>().to_le_bytes()),
+                    Blake2::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Blake2::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let pairs: Vec<[Blake2Digest; 2]> = (0..1024)
+        .map(|i: u64| {
+            [
+                Blake2::hash(&i.to_le_bytes()),
+                Blake2::hash(&(i + 1024).to_le_bytes()),
+            ]
+        })
+        .collect();
+    c.bench_function("hash_blake2s (1024 pairs, one by one)", |bench| {
+        bench.iter(|| {
+            black_box(&pairs)
+                .iter()
+                .map(Blake2::merge)
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function("hash_blake2s (1024 pairs, batched)", |bench| {
+        bench.iter(|| Blake2::merge_pairs(black_box(&pairs)))
+    });
+}
+
+fn sha2(c: &mut Criterion) {
+    let v: [Sha2Digest; 2] = [Sha2::hash(&[1u8]), Sha2::hash(&[2u8])];
+    c.bench_function("hash_sha2 (cached)", |bench| {
+        bench.iter(|| Sha2::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_sha2 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Sha2::hash(&rand_value::<u64>().to_le_bytes()),
+                    Sha2::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Sha2::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 fn sha3(c: &mut Criterion) {
     let v: [Sha3Digest; 2] = [Sha3::hash(&[1u8]), Sha3::hash(&[2u8])];
     c.bench_function("hash_sha3 (cached)", |bench| {
@@ -102,6 +178,26 @@ fn rescue256(c: &mut Criterion) {
     });
 }
 
+fn rpo256(c: &mut Criterion) {
+    let v: [Rpo256Digest; 2] = [Rpo256::hash(&[1u8]), Rpo256::hash(&[2u8])];
+    c.bench_function("hash_rpo256 (cached)", |bench| {
+        bench.iter(|| Rpo256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_rpo256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Rpo256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Rpo256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Rpo256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 fn rescue_jive256(c: &mut Criterion) {
     let v: [RpJive64_256Digest; 2] = [RpJive64_256::hash(&[1u8]), RpJive64_256::hash(&[2u8])];
     c.bench_function("hash_rp_jive64_256 (cached)", |bench| {
@@ -145,13 +241,124 @@ fn griffin_jive256(c: &mut Criterion) {
     });
 }
 
+fn anemoi_jive256(c: &mut Criterion) {
+    let v: [AnemoiJive64_256Digest; 2] = [
+        AnemoiJive64_256::hash(&[1u8]),
+        AnemoiJive64_256::hash(&[2u8]),
+    ];
+    c.bench_function("hash_anemoi_jive64_256 (cached)", |bench| {
+        bench.iter(|| AnemoiJive64_256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_anemoi_jive64_256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    AnemoiJive64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                    AnemoiJive64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| AnemoiJive64_256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn monolith256(c: &mut Criterion) {
+    let v: [Monolith64_256Digest; 2] = [Monolith64_256::hash(&[1u8]), Monolith64_256::hash(&[2u8])];
+    c.bench_function("hash_monolith64_256 (cached)", |bench| {
+        bench.iter(|| Monolith64_256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_monolith64_256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Monolith64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Monolith64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Monolith64_256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn tip5_320(c: &mut Criterion) {
+    let v: [Tip5_320Digest; 2] = [Tip5_320::hash(&[1u8]), Tip5_320::hash(&[2u8])];
+    c.bench_function("hash_tip5_320 (cached)", |bench| {
+        bench.iter(|| Tip5_320::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_tip5_320 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Tip5_320::hash(&rand_value::<u64>().to_le_bytes()),
+                    Tip5_320::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Tip5_320::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn poseidon248(c: &mut Criterion) {
+    let v: [Poseidon62_248Digest; 2] = [Poseidon62_248::hash(&[1u8]), Poseidon62_248::hash(&[2u8])];
+    c.bench_function("hash_poseidon62_248 (cached)", |bench| {
+        bench.iter(|| Poseidon62_248::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_poseidon62_248 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Poseidon62_248::hash(&rand_value::<u64>().to_le_bytes()),
+                    Poseidon62_248::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Poseidon62_248::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn poseidon256(c: &mut Criterion) {
+    let v: [Poseidon64_256Digest; 2] = [Poseidon64_256::hash(&[1u8]), Poseidon64_256::hash(&[2u8])];
+    c.bench_function("hash_poseidon64_256 (cached)", |bench| {
+        bench.iter(|| Poseidon64_256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_poseidon64_256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Poseidon64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Poseidon64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Poseidon64_256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 criterion_group!(
     hash_group,
     blake3,
+    blake2s,
+    sha2,
     sha3,
     rescue248,
     rescue256,
+    rpo256,
     rescue_jive256,
     griffin_jive256,
+    anemoi_jive256,
+    poseidon248,
+    poseidon256,
+    monolith256,
+    tip5_320,
 );
 criterion_main!(hash_group);
@@ -7,7 +7,7 @@ use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use math::fields::f128::BaseElement;
 use rand_utils::rand_value;
 use utils::uninit_vector;
-use winter_crypto::{build_merkle_nodes, concurrent, hashers::Blake3_256, Hasher};
+use winter_crypto::{build_merkle_nodes, concurrent, hashers::Blake3_256, Hasher, MerkleTree};
 
 type Blake3 = Blake3_256<BaseElement>;
 type Blake3Digest = <Blake3 as Hasher>::Digest;
@@ -31,6 +31,9 @@ pub fn merkle_tree_construction(c: &mut Criterion) {
         merkle_group.bench_with_input(BenchmarkId::new("concurrent", size), &data, |b, i| {
             b.iter(|| concurrent::build_merkle_nodes::<Blake3>(&i))
         });
+        merkle_group.bench_with_input(BenchmarkId::new("build_parallel", size), &data, |b, i| {
+            b.iter(|| MerkleTree::<Blake3>::build_parallel(i.clone(), 0).unwrap())
+        });
     }
 }
 
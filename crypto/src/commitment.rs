@@ -0,0 +1,100 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Hasher;
+use core::fmt::Debug;
+use utils::{collections::Vec, ByteReader, ByteWriter, DeserializationError};
+
+// VECTOR COMMITMENT
+// ================================================================================================
+
+/// A vector commitment scheme over a vector of digests.
+///
+/// A vector commitment allows committing to an ordered vector of items (digests defined by the
+/// hash function `H`) using a single commitment value, and later opening any item (or a set of
+/// items) at a given position together with a proof that the item is indeed located at this
+/// position in the committed vector.
+///
+/// [MerkleTree](crate::MerkleTree) is the canonical implementation of this trait; however, other
+/// schemes with the same interface can be used in its place.
+///
+/// Opening proofs for multiple items must be serializable in a way which does not include the
+/// opened items: during verification, the items are reconstructed from the values opened by
+/// the prover, and so they can be omitted from the serialized proofs.
+pub trait VectorCommitment<H: Hasher>: Sized {
+    /// Proof of opening a single item of the committed vector.
+    type Proof;
+
+    /// Proof of opening multiple items of the committed vector.
+    type MultiProof;
+
+    /// Error returned by the operations of this commitment scheme.
+    type Error: Debug;
+
+    // COMMITMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Commits to the specified vector of `items` and returns the resulting commitment state.
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error>;
+
+    /// Returns the value which commits to the entire vector of items.
+    fn commitment(&self) -> H::Digest;
+
+    /// Returns the number of items in the committed vector.
+    fn domain_len(&self) -> usize;
+
+    // OPENING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the item at the specified `index` together with a proof of its opening.
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Proof), Self::Error>;
+
+    /// Returns the items at the specified `indexes` together with a single proof of their
+    /// opening. The items are returned in the same order as the indexes.
+    #[allow(clippy::type_complexity)]
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, Self::MultiProof), Self::Error>;
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the specified `item` is located at the specified `index` in the vector
+    /// committed to by the specified `commitment`.
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error>;
+
+    /// Checks that the specified `items` are located at the specified `indexes` in the vector
+    /// committed to by the specified `commitment`.
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error>;
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes the specified multi-item opening `proof` without the opened items and writes
+    /// the resulting bytes into the `target`.
+    fn write_multi_proof<W: ByteWriter>(proof: &Self::MultiProof, target: &mut W);
+
+    /// Reads a multi-item opening proof serialized via [VectorCommitment::write_multi_proof()]
+    /// from the specified `source`.
+    ///
+    /// The `items` are the opened items in the order in which they were opened, and
+    /// `domain_len` is the number of items in the committed vector.
+    fn read_multi_proof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::MultiProof, DeserializationError>;
+}
@@ -15,6 +15,8 @@ pub enum MerkleTreeError {
     TooFewLeaves(usize, usize),
     /// Number of leaves for a Merkle tree was not a power of two.
     NumberOfLeavesNotPowerOfTwo(usize),
+    /// Number of leaves for a Merkle tree with arity greater than 2 was not a power of the arity.
+    NumberOfLeavesNotPowerOfArity(usize, usize),
     /// A leaf index was greater than or equal to the number of leaves in the tree.
     LeafIndexOutOfBounds(usize, usize),
     /// A leaf index was included more than once in the list of indexes for a batch proof.
@@ -25,6 +27,12 @@ pub enum MerkleTreeError {
     TooManyLeafIndexes(usize, usize),
     /// Merkle proof is not valid for the specified position(s).
     InvalidProof,
+    /// Depth of a sparse Merkle tree was zero or exceeded the maximum depth.
+    InvalidTreeDepth(usize, usize),
+    /// A leaf of a sparse Merkle tree was already occupied by a different key.
+    KeyCollision,
+    /// A Merkle path to the leaf at the specified index is not included in a partial tree.
+    LeafNotIncluded(usize),
 }
 
 impl fmt::Display for MerkleTreeError {
@@ -42,6 +50,12 @@ impl fmt::Display for MerkleTreeError {
                     "number of leaves must be a power of two, but {num_leaves} were provided"
                 )
             }
+            Self::NumberOfLeavesNotPowerOfArity(arity, num_leaves) => {
+                write!(
+                    f,
+                    "number of leaves must be a power of {arity}, but {num_leaves} were provided"
+                )
+            }
             Self::LeafIndexOutOfBounds(expected, actual) => {
                 write!(f, "a leaf index cannot exceed {expected}, but was {actual}")
             }
@@ -60,6 +74,21 @@ impl fmt::Display for MerkleTreeError {
             Self::InvalidProof => {
                 write!(f, "Merkle proof is invalid")
             }
+            Self::InvalidTreeDepth(max_depth, depth) => {
+                write!(
+                    f,
+                    "tree depth must be between 1 and {max_depth}, but was {depth}"
+                )
+            }
+            Self::KeyCollision => {
+                write!(f, "the leaf for the key is occupied by a different key")
+            }
+            Self::LeafNotIncluded(index) => {
+                write!(
+                    f,
+                    "a path to the leaf at index {index} is not included in the tree"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,613 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher};
+use core::convert::TryInto;
+use core::ops::Range;
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 8 field elements or 64 bytes; 4 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 8;
+
+/// The state is viewed as 4 columns, each consisting of an (x, y) pair of field elements; the
+/// x-coordinates are located in elements 0 through 3, and y-coordinates in elements 4 through 7.
+const NUM_COLUMNS: usize = STATE_WIDTH / 2;
+
+/// The rate portion of the state is located in elements 0 through 4.
+const RATE_RANGE: Range<usize> = 0..4;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+// The compression makes use of the Jive mode, hence ignoring the notion of sponge capacity.
+const INPUT1_RANGE: Range<usize> = 0..4;
+const INPUT2_RANGE: Range<usize> = 4..8;
+
+/// The capacity portion of the state is located in elements 4, 5, 6 and 7.
+const CAPACITY_RANGE: Range<usize> = 4..8;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, 3 (the four elements of the rate).
+const DIGEST_RANGE: Range<usize> = 0..4;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of rounds is set to 12 to target 128-bit security level.
+const NUM_ROUNDS: usize = 12;
+
+/// Generator of the multiplicative group of the field; used as the multiplier of the quadratic
+/// functions of the Flystel S-Box.
+const GENERATOR: BaseElement = BaseElement::new(7);
+
+/// Inverse of the generator; used as the constant term of the Q_gamma quadratic function.
+const GENERATOR_INV: BaseElement = BaseElement::new(2635249152773512046);
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Anemoi hash function with 256-bit output.
+///
+/// The hash function is implemented according to the Anemoi
+/// [specifications](https://eprint.iacr.org/2022/840.pdf) with the following caveats:
+/// * When hashing a sequence of elements, implement the Hirose padding rule. However, it also
+///   means that our instantiation of Anemoi cannot be used in a stream mode as the number
+///   of elements to be hashed must be known upfront.
+/// * 2-to-1 compression is performed using the Jive mode described in the same paper.
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 8 field elements (4 columns).
+/// * Capacity size: 4 field elements.
+/// * Number of rounds: 12.
+/// * S-Box: open Flystel with degree 7 (and inverse degree 10540996611094048183), and quadratic
+///   functions Q_gamma(x) = g * x^2 + g^(-1) and Q_delta(x) = g * x^2 where g = 7.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// ## Constraint count
+/// Since the Flystel is CCZ-equivalent to a low degree function, a round of Anemoi can be
+/// verified in an AIR using the round input and output as the current and next rows of an
+/// 8-column trace. Each round requires 8 transition constraints: 4 constraints of degree 2 and
+/// 4 constraints of degree 7 (one of each per column). A single permutation thus spans 12 rows,
+/// and can fit into a 16-row cycle. For comparison, Rescue Prime requires 12 constraints of
+/// degree 7 per round for [Rp64_256](super::super::Rp64_256).
+///
+/// ## Hash output consistency
+/// Functions [hash_elements()](AnemoiJive64_256::hash_elements), [merge()](AnemoiJive64_256::merge),
+/// and [merge_with_int()](AnemoiJive64_256::merge_with_int) are not consistent. This is because
+/// the former is instantiated with a sponge construction, while the latter use the Jive
+/// compression mode and hence do not rely on the sponge construction.
+///
+/// In addition, [hash()](AnemoiJive64_256::hash) function is not consistent with the functions
+/// mentioned above. For example, if we take two field elements, serialize them to bytes and hash
+/// them using [hash()](AnemoiJive64_256::hash), the result will differ from the result obtained by
+/// hashing these elements directly using [hash_elements()](AnemoiJive64_256::hash_elements)
+/// function. The reason for this difference is that [hash()](AnemoiJive64_256::hash) function
+/// needs to be able to handle arbitrary binary strings, which may or may not encode valid field
+/// elements - and thus, deserialization procedure used by this function is different from the
+/// procedure used to deserialize valid field elements.
+///
+/// Thus, if the underlying data consists of valid field elements, it might make more sense
+/// to deserialize them into field elements and then hash them using
+/// [hash_elements()](AnemoiJive64_256::hash_elements) function rather then hashing the serialized
+/// bytes using [hash()](AnemoiJive64_256::hash) function.
+pub struct AnemoiJive64_256();
+
+impl Hasher for AnemoiJive64_256 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % 7 == 0 {
+            bytes.len() / 7
+        } else {
+            bytes.len() / 7 + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to 1 if the number of elements is not a multiple of RATE_WIDTH.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        if num_elements % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = BaseElement::ONE;
+        }
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state. we use 7-byte chunks because
+        // every 7-byte chunk is guaranteed to map to some field element.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for (index, chunk) in bytes.chunks(7).enumerate() {
+            if index < num_elements - 1 {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state; if the rate is filled up, apply the Anemoi permutation and start absorbing
+            // again from zero index.
+            state[RATE_RANGE.start + i] += BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply a final permutation after
+        // padding by appending a 1 followed by as many 0 as necessary to make the input length a
+        // multiple of the RATE_WIDTH.
+        if i > 0 {
+            state[RATE_RANGE.start + i] = BaseElement::ONE;
+            i += 1;
+            while i != RATE_WIDTH {
+                state[RATE_RANGE.start + i] = BaseElement::ZERO;
+                i += 1;
+            }
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    // We do not rely on the sponge construction to build our compression function. Instead, we use
+    // the Jive compression mode designed in https://eprint.iacr.org/2022/840.pdf.
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the state
+        let initial_state: [BaseElement; STATE_WIDTH] = Self::Digest::digests_as_elements(values)
+            .try_into()
+            .unwrap();
+        let mut state = initial_state;
+
+        // apply the Anemoi permutation and apply the final Jive summation
+        Self::apply_permutation(&mut state);
+
+        Self::apply_jive_summation(&initial_state, &state)
+    }
+
+    // We do not rely on the sponge construction to build our compression function. Instead, we use
+    // the Jive compression mode designed in https://eprint.iacr.org/2022/840.pdf.
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and set the last state element to 5 (the number of elements to be hashed).
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into state elements 5 and 6, and set the last state element
+        //   to 6.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[INPUT2_RANGE.end - 1] = BaseElement::new(DIGEST_SIZE as u64 + 1);
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[INPUT2_RANGE.end - 1] = BaseElement::new(DIGEST_SIZE as u64 + 2);
+        }
+
+        let initial_state = state;
+        // apply the Anemoi permutation and apply the final Jive summation
+        Self::apply_permutation(&mut state);
+
+        Self::apply_jive_summation(&initial_state, &state)
+    }
+}
+
+impl ElementHasher for AnemoiJive64_256 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to 1 if the number of elements is not a multiple of RATE_WIDTH.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        if elements.len() % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = BaseElement::ONE;
+        }
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the Anemoi permutation and start absorbing again; repeat until all
+        // elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply a final permutation after
+        // padding by appending a 1 followed by as many 0 as necessary to make the input length a
+        // multiple of the RATE_WIDTH.
+        if i > 0 {
+            state[RATE_RANGE.start + i] = BaseElement::ONE;
+            i += 1;
+            while i != RATE_WIDTH {
+                state[RATE_RANGE.start + i] = BaseElement::ZERO;
+                i += 1;
+            }
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl AnemoiJive64_256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 12 to target 128-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 8 field elements or 64 bytes; 4 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0, 1, 2, and 3.
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 4, 5, 6, and 7.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// MDS matrix applied to the x- and y-coordinates of the state in the linear layer.
+    pub const MDS: [[BaseElement; NUM_COLUMNS]; NUM_COLUMNS] = MDS;
+
+    /// Round constants added to the x-coordinates of the state at the start of each round.
+    pub const ARK_C: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = C;
+
+    /// Round constants added to the y-coordinates of the state at the start of each round.
+    pub const ARK_D: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = D;
+
+    // ANEMOI PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Anemoi permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+        Self::apply_linear(state);
+    }
+
+    /// Anemoi round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::add_constants(state, round);
+        Self::apply_linear(state);
+        Self::apply_sbox(state);
+    }
+
+    #[inline(always)]
+    pub fn apply_jive_summation(
+        initial_state: &[BaseElement; STATE_WIDTH],
+        final_state: &[BaseElement; STATE_WIDTH],
+    ) -> ElementDigest {
+        let mut result = [BaseElement::ZERO; DIGEST_SIZE];
+        for (i, r) in result.iter_mut().enumerate() {
+            *r = initial_state[i]
+                + initial_state[DIGEST_SIZE + i]
+                + final_state[i]
+                + final_state[DIGEST_SIZE + i];
+        }
+
+        ElementDigest::new(result)
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        let (x, y) = state.split_at_mut(NUM_COLUMNS);
+        x.iter_mut().zip(C[round]).for_each(|(s, k)| *s += k);
+        y.iter_mut().zip(D[round]).for_each(|(s, k)| *s += k);
+    }
+
+    /// Applies the Anemoi linear layer to the current hash state: the MDS matrix is applied to
+    /// the x-coordinates and to the y-coordinates rotated by one position, and the two halves
+    /// are then mixed using a pseudo-Hadamard transform.
+    #[inline(always)]
+    fn apply_linear(state: &mut [BaseElement; STATE_WIDTH]) {
+        let mut x = [BaseElement::ZERO; NUM_COLUMNS];
+        let mut y = [BaseElement::ZERO; NUM_COLUMNS];
+        for i in 0..NUM_COLUMNS {
+            for j in 0..NUM_COLUMNS {
+                x[i] += MDS[i][j] * state[j];
+                y[i] += MDS[i][j] * state[NUM_COLUMNS + (j + 1) % NUM_COLUMNS];
+            }
+        }
+
+        for i in 0..NUM_COLUMNS {
+            y[i] += x[i];
+            x[i] += y[i];
+        }
+
+        state[..NUM_COLUMNS].copy_from_slice(&x);
+        state[NUM_COLUMNS..].copy_from_slice(&y);
+    }
+
+    /// Applies the open Flystel S-Box to each (x, y) column of the current hash state.
+    #[inline(always)]
+    fn apply_sbox(state: &mut [BaseElement; STATE_WIDTH]) {
+        let (x, y) = state.split_at_mut(NUM_COLUMNS);
+        for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+            *x -= GENERATOR * y.square() + GENERATOR_INV;
+
+            let mut x_inv_d = *x;
+            pow_inv_d(&mut x_inv_d);
+            *y -= x_inv_d;
+
+            *x += GENERATOR * y.square();
+        }
+    }
+}
+
+#[inline(always)]
+fn pow_inv_d(x: &mut BaseElement) {
+    // compute base^10540996611094048183 using 72 multiplications
+    // 10540996611094048183 = b1001001001001001001001001001000110110110110110110110110110110111
+
+    // compute base^10
+    let t1 = x.square();
+
+    // compute base^100
+    let t2 = t1.square();
+
+    // compute base^100100
+    let t3 = square_assign_and_multiply::<3>(t2, t2);
+
+    // compute base^100100100100
+    let t4 = square_assign_and_multiply::<6>(t3, t3);
+
+    // compute base^100100100100100100100100
+    let t5 = square_assign_and_multiply::<12>(t4, t4);
+
+    // compute base^100100100100100100100100100100
+    let t6 = square_assign_and_multiply::<6>(t5, t3);
+
+    // compute base^1001001001001001001001001001000100100100100100100100100100100
+    let t7 = square_assign_and_multiply::<31>(t6, t6);
+
+    // compute base^1001001001001001001001001001000110110110110110110110110110110111
+    let a = (t7.square() * t6).square().square();
+    let b = t1 * t2 * *x;
+    *x = a * b;
+}
+
+#[inline(always)]
+/// Squares an element M times, then multiplies it with tail.
+fn square_assign_and_multiply<const M: usize>(base: BaseElement, tail: BaseElement) -> BaseElement {
+    let mut result = base;
+    for _ in 0..M {
+        result = result.square();
+    }
+
+    result * tail
+}
+
+// MDS
+// ================================================================================================
+/// Anemoi MDS matrix for 4 columns, as suggested in the specifications for g = 7.
+const MDS: [[BaseElement; NUM_COLUMNS]; NUM_COLUMNS] = [
+    [
+        BaseElement::new(1),
+        BaseElement::new(8),
+        BaseElement::new(7),
+        BaseElement::new(7),
+    ],
+    [
+        BaseElement::new(49),
+        BaseElement::new(56),
+        BaseElement::new(8),
+        BaseElement::new(15),
+    ],
+    [
+        BaseElement::new(49),
+        BaseElement::new(49),
+        BaseElement::new(1),
+        BaseElement::new(8),
+    ],
+    [
+        BaseElement::new(8),
+        BaseElement::new(15),
+        BaseElement::new(7),
+        BaseElement::new(8),
+    ],
+];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+// Anemoi round constants are derived from the digits of pi as described in the specifications:
+// with pi_0 = 1415926535 and pi_1 = 8979323846, the constants for round r and column i are
+//   C[r][i] = g * pi_0^(2r) + (pi_0^r + pi_1^i)^7
+//   D[r][i] = g * pi_1^(2i) + (pi_0^r + pi_1^i)^7 + g^(-1)
+
+/// Round constants added to the x-coordinates of the state at the start of each round.
+const C: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(135),
+        BaseElement::new(12616817002499211280),
+        BaseElement::new(10322855620882634095),
+        BaseElement::new(11515394017209471433),
+    ],
+    [
+        BaseElement::new(4589240930789969862),
+        BaseElement::new(16618325948943106831),
+        BaseElement::new(18200144109916626466),
+        BaseElement::new(10404409773164030300),
+    ],
+    [
+        BaseElement::new(1056806635938363454),
+        BaseElement::new(12032802661514536002),
+        BaseElement::new(10002665097632248002),
+        BaseElement::new(6412252127398148956),
+    ],
+    [
+        BaseElement::new(2033339464122141606),
+        BaseElement::new(16651387481995968952),
+        BaseElement::new(4985598636326750382),
+        BaseElement::new(16478276872291823677),
+    ],
+    [
+        BaseElement::new(17998547505995654386),
+        BaseElement::new(6416007608347979543),
+        BaseElement::new(3759814826981602401),
+        BaseElement::new(13568539573461218236),
+    ],
+    [
+        BaseElement::new(6248231951858815273),
+        BaseElement::new(10075988513384689706),
+        BaseElement::new(8639551545635388466),
+        BaseElement::new(6349442195028952653),
+    ],
+    [
+        BaseElement::new(6373743027521678262),
+        BaseElement::new(15640457269987185203),
+        BaseElement::new(7059777190151104864),
+        BaseElement::new(16234292380744713453),
+    ],
+    [
+        BaseElement::new(9496862436573416816),
+        BaseElement::new(8021320825921566955),
+        BaseElement::new(10011109434848134153),
+        BaseElement::new(15157571183736724529),
+    ],
+    [
+        BaseElement::new(4953972297419589317),
+        BaseElement::new(15709647264193197289),
+        BaseElement::new(1063287896505862446),
+        BaseElement::new(1284682883009552102),
+    ],
+    [
+        BaseElement::new(12577582626570549900),
+        BaseElement::new(2955264990711345269),
+        BaseElement::new(8561611934416731227),
+        BaseElement::new(5067004547994152727),
+    ],
+    [
+        BaseElement::new(5581374028569421360),
+        BaseElement::new(15114421678379478793),
+        BaseElement::new(1019269691093785138),
+        BaseElement::new(13699130382728479215),
+    ],
+    [
+        BaseElement::new(5672788376636630092),
+        BaseElement::new(784399856111131110),
+        BaseElement::new(8161190177550306304),
+        BaseElement::new(12680577234628258312),
+    ],
+];
+
+/// Round constants added to the y-coordinates of the state at the start of each round.
+const D: [[BaseElement; NUM_COLUMNS]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(2635249152773512181),
+        BaseElement::new(7800797122830231380),
+        BaseElement::new(9653037688956642187),
+        BaseElement::new(3310491136445819131),
+    ],
+    [
+        BaseElement::new(11637298485358322661),
+        BaseElement::new(16215114471068967684),
+        BaseElement::new(3496390510370890990),
+        BaseElement::new(6612315294195218751),
+    ],
+    [
+        BaseElement::new(9725267137655892445),
+        BaseElement::new(13249994130789573047),
+        BaseElement::new(15366058514650273039),
+        BaseElement::new(4240560595578513599),
+    ],
+    [
+        BaseElement::new(15778423628933702095),
+        BaseElement::new(4498458544950453174),
+        BaseElement::new(15425615716438806917),
+        BaseElement::new(936464934151635497),
+    ],
+    [
+        BaseElement::new(15842802120403555692),
+        BaseElement::new(15255737259727973224),
+        BaseElement::new(16745746426104584074),
+        BaseElement::new(572642154331955194),
+    ],
+    [
+        BaseElement::new(1174110789228001021),
+        BaseElement::new(15997342387725967829),
+        BaseElement::new(260363298305070260),
+        BaseElement::new(8881913068275558374),
+    ],
+    [
+        BaseElement::new(14790259923157830657),
+        BaseElement::new(16605705133180845652),
+        BaseElement::new(12171227001087753305),
+        BaseElement::new(13810657242843701500),
+    ],
+    [
+        BaseElement::new(331279510332425244),
+        BaseElement::new(9851212936652667758),
+        BaseElement::new(15987203493322222948),
+        BaseElement::new(13598580293373152930),
+    ],
+    [
+        BaseElement::new(16113664442313208359),
+        BaseElement::new(971326307229740064),
+        BaseElement::new(8917912956699977534),
+        BaseElement::new(1604222994366006796),
+    ],
+    [
+        BaseElement::new(7461308516502810908),
+        BaseElement::new(8834465917615698652),
+        BaseElement::new(140270739649488281),
+        BaseElement::new(7557322473803833708),
+    ],
+    [
+        BaseElement::new(351274238924496356),
+        BaseElement::new(2433052856292061843),
+        BaseElement::new(10930846886163940501),
+        BaseElement::new(16075622628960974184),
+    ],
+    [
+        BaseElement::new(11326755175014858375),
+        BaseElement::new(17433841691461451768),
+        BaseElement::new(10510089891229030633),
+        BaseElement::new(7494391999469322247),
+    ],
+];
@@ -0,0 +1,208 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    AnemoiJive64_256, BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, StarkField,
+    GENERATOR, GENERATOR_INV, NUM_COLUMNS, STATE_WIDTH,
+};
+use core::convert::TryInto;
+
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_pow_inv_d() {
+    let e: BaseElement = rand_value();
+    let mut e_inv = e;
+    super::pow_inv_d(&mut e_inv);
+    assert_eq!(e, e_inv.exp(7));
+}
+
+#[test]
+fn generator_inv() {
+    assert_eq!(BaseElement::ONE, GENERATOR * GENERATOR_INV);
+}
+
+#[test]
+fn sbox_constraints() {
+    // the closed Flystel relates the input (x, y) and the output (u, v) of each column via one
+    // constraint of degree 2 and one constraint of degree 7:
+    //   x - u = Q_gamma(y) - Q_delta(v)
+    //   (y - v)^7 = x - Q_gamma(y)
+    let input: [BaseElement; STATE_WIDTH] = rand_array();
+    let mut output = input;
+    AnemoiJive64_256::apply_sbox(&mut output);
+
+    for i in 0..NUM_COLUMNS {
+        let (x, y) = (input[i], input[NUM_COLUMNS + i]);
+        let (u, v) = (output[i], output[NUM_COLUMNS + i]);
+
+        let q_gamma = GENERATOR * y.square() + GENERATOR_INV;
+        let q_delta = GENERATOR * v.square();
+        assert_eq!(x - u, q_gamma - q_delta);
+        assert_eq!((y - v).exp(7), x - q_gamma);
+    }
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+    ];
+
+    AnemoiJive64_256::apply_permutation(&mut state);
+
+    // expected values are obtained by executing an independent Python implementation of the
+    // Anemoi permutation written according to the specifications
+    let expected = vec![
+        BaseElement::new(17101562256459989034),
+        BaseElement::new(15807761611747771314),
+        BaseElement::new(12221297566815186385),
+        BaseElement::new(10986630721785867644),
+        BaseElement::new(7886955801555516599),
+        BaseElement::new(4851483644891788559),
+        BaseElement::new(5611855232280840582),
+        BaseElement::new(5676817532364864286),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash() {
+    let state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+    ];
+
+    // expected values are obtained by executing the same Python implementation as above
+    let result = AnemoiJive64_256::hash_elements(&state);
+    let expected = vec![
+        BaseElement::new(13206309482370665418),
+        BaseElement::new(11845508386717579352),
+        BaseElement::new(3061690211532838774),
+        BaseElement::new(9396803498430261286),
+    ];
+    assert_eq!(expected, result.as_elements());
+
+    let result = AnemoiJive64_256::hash_elements(&state[..5]);
+    let expected = vec![
+        BaseElement::new(9052431524968574636),
+        BaseElement::new(1265133050786802655),
+        BaseElement::new(5249070151720439405),
+        BaseElement::new(3538178737731985052),
+    ];
+    assert_eq!(expected, result.as_elements());
+}
+
+#[test]
+fn merge() {
+    let digests = [
+        ElementDigest::new([0, 1, 2, 3].map(BaseElement::new)),
+        ElementDigest::new([4, 5, 6, 7].map(BaseElement::new)),
+    ];
+
+    // expected values are obtained by executing the same Python implementation as above
+    let result = AnemoiJive64_256::merge(&digests);
+    let expected = vec![
+        BaseElement::new(6541773988600921316),
+        BaseElement::new(2212501187224975558),
+        BaseElement::new(17833152799096026975),
+        BaseElement::new(16663448254150731940),
+    ];
+    assert_eq!(expected, result.as_elements());
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = AnemoiJive64_256::merge(&digests);
+    let h_result = AnemoiJive64_256::hash_elements(&elements);
+
+    // Because we use the Jive compression mode, `merge` and
+    // `hash_elements` methods are incompatible.
+    assert_ne!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = AnemoiJive64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = AnemoiJive64_256::hash_elements(&elements);
+
+    // Because we use the Jive compression mode, `merge` and
+    // `hash_elements` methods are incompatible.
+    assert_ne!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = AnemoiJive64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = AnemoiJive64_256::hash_elements(&elements);
+
+    // Because we use the Jive compression mode, `merge` and
+    // `hash_elements` methods are incompatible.
+    assert_ne!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = AnemoiJive64_256::hash(&[1_u8, 2, 3]);
+    let r2 = AnemoiJive64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = AnemoiJive64_256::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = AnemoiJive64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = AnemoiJive64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = AnemoiJive64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = AnemoiJive64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = AnemoiJive64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = AnemoiJive64_256::hash_elements(&e1);
+    let r2 = AnemoiJive64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
@@ -0,0 +1,9 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher};
+
+mod anemoi64_256_jive;
+pub use anemoi64_256_jive::AnemoiJive64_256;
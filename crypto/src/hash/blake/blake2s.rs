@@ -0,0 +1,481 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::convert::TryInto;
+use utils::{collections::Vec, ByteWriter};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Block size of BLAKE2s in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// Size of BLAKE2s-256 digest in bytes.
+const DIGEST_SIZE: usize = 32;
+
+/// Number of rounds of the compression function.
+const NUM_ROUNDS: usize = 10;
+
+/// Number of messages hashed at once by [Blake2s::digest_many()].
+const NUM_LANES: usize = 4;
+
+/// Initialization vector as defined in RFC 7693, section 2.6.
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Message word permutations as defined in RFC 7693, section 2.7.
+const SIGMA: [[usize; 16]; NUM_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+// BLAKE2s
+// ================================================================================================
+
+/// A minimal implementation of the BLAKE2s hash function with 256-bit output and no key, as
+/// specified in [RFC 7693](https://datatracker.ietf.org/doc/html/rfc7693).
+///
+/// The hasher can be used either in a streaming mode (via [update()](Blake2s::update) and
+/// [finalize()](Blake2s::finalize) functions), to hash a single message via
+/// [digest()](Blake2s::digest) function, or to hash many messages at once via
+/// [digest_many()](Blake2s::digest_many) function.
+#[derive(Clone)]
+pub struct Blake2s {
+    state: [u32; 8],
+    buffer: [u8; BLOCK_SIZE],
+    buffer_len: usize,
+    message_len: u64,
+}
+
+impl Blake2s {
+    /// Returns a new hasher instantiated with the parameter block for 32-byte digests.
+    pub fn new() -> Self {
+        Self {
+            state: initial_state(),
+            buffer: [0; BLOCK_SIZE],
+            buffer_len: 0,
+            message_len: 0,
+        }
+    }
+
+    /// Returns BLAKE2s-256 hash of the provided bytes.
+    pub fn digest(bytes: &[u8]) -> [u8; DIGEST_SIZE] {
+        let mut hasher = Self::new();
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    /// Returns BLAKE2s-256 hashes of the provided messages.
+    ///
+    /// Every consecutive group of 4 messages of the same length is hashed at once: the
+    /// compression function is applied to the blocks of all messages in the group in a single
+    /// invocation. When `std` feature is enabled on x86_64 targets with SSSE3 support, this is
+    /// done using SIMD instructions such that each message occupies a separate 32-bit lane. All
+    /// other messages are hashed one by one.
+    pub fn digest_many(messages: &[&[u8]]) -> Vec<[u8; DIGEST_SIZE]> {
+        let mut result = Vec::with_capacity(messages.len());
+        let mut groups = messages.chunks_exact(NUM_LANES);
+        for group in &mut groups {
+            if group.iter().all(|message| message.len() == group[0].len()) {
+                result.extend_from_slice(&digest_lanes(group.try_into().unwrap()));
+            } else {
+                result.extend(group.iter().map(|message| Self::digest(message)));
+            }
+        }
+        result.extend(
+            groups
+                .remainder()
+                .iter()
+                .map(|message| Self::digest(message)),
+        );
+        result
+    }
+
+    /// Absorbs the provided bytes into the hasher.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        // the last block must be compressed with the finalization flag set; thus, a full buffer
+        // is compressed only once more bytes are available
+        while !bytes.is_empty() {
+            if self.buffer_len == BLOCK_SIZE {
+                self.message_len += BLOCK_SIZE as u64;
+                let block = self.buffer;
+                compress(&mut self.state, &block, self.message_len, false);
+                self.buffer_len = 0;
+            }
+            let n = core::cmp::min(BLOCK_SIZE - self.buffer_len, bytes.len());
+            self.buffer[self.buffer_len..self.buffer_len + n].copy_from_slice(&bytes[..n]);
+            self.buffer_len += n;
+            bytes = &bytes[n..];
+        }
+    }
+
+    /// Pads the last block of the absorbed message with zeros and returns its hash.
+    pub fn finalize(mut self) -> [u8; DIGEST_SIZE] {
+        self.message_len += self.buffer_len as u64;
+        self.buffer[self.buffer_len..].fill(0);
+        let block = self.buffer;
+        compress(&mut self.state, &block, self.message_len, true);
+        state_to_bytes(&self.state)
+    }
+}
+
+impl Default for Blake2s {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ByteWriter for Blake2s {
+    fn write_u8(&mut self, value: u8) {
+        self.update(&[value]);
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        self.update(values);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the initial state of the hasher for 32-byte digests and no key.
+fn initial_state() -> [u32; 8] {
+    let mut state = IV;
+    state[0] ^= 0x01010000 ^ DIGEST_SIZE as u32;
+    state
+}
+
+/// Returns the digest for the specified final state of the hasher.
+fn state_to_bytes(state: &[u32; 8]) -> [u8; DIGEST_SIZE] {
+    let mut result = [0; DIGEST_SIZE];
+    for (chunk, word) in result.chunks_exact_mut(4).zip(state) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    result
+}
+
+/// Returns BLAKE2s-256 hashes of the provided messages; all messages must have the same length.
+fn digest_lanes(messages: &[&[u8]; NUM_LANES]) -> [[u8; DIGEST_SIZE]; NUM_LANES] {
+    let len = messages[0].len();
+    let mut states = [initial_state(); NUM_LANES];
+
+    // the last block is compressed with the finalization flag set; an empty message is hashed
+    // as a single block of zeros
+    let num_blocks = len.saturating_sub(1) / BLOCK_SIZE + 1;
+    let mut blocks = [[0u8; BLOCK_SIZE]; NUM_LANES];
+    for i in 0..num_blocks {
+        let start = i * BLOCK_SIZE;
+        let end = core::cmp::min(start + BLOCK_SIZE, len);
+        for (block, message) in blocks.iter_mut().zip(messages) {
+            block[..end - start].copy_from_slice(&message[start..end]);
+            block[end - start..].fill(0);
+        }
+        compress_lanes(&mut states, &blocks, end as u64, i == num_blocks - 1);
+    }
+
+    states.map(|state| state_to_bytes(&state))
+}
+
+/// Applies BLAKE2s compression function to the provided state and message block; `t` is the
+/// number of message bytes processed so far (including the bytes of this block).
+///
+/// When `std` feature is enabled on x86_64 targets, an SSSE3 implementation of the compression
+/// function is used if the CPU supports it; this is detected at runtime.
+#[inline(always)]
+fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_SIZE], t: u64, last: bool) {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    if std::is_x86_feature_detected!("ssse3") {
+        // SAFETY: the CPU supports SSSE3 instructions
+        unsafe { ssse3::compress(state, block, t, last) };
+        return;
+    }
+    compress_portable(state, block, t, last)
+}
+
+/// Applies BLAKE2s compression function to each of the provided states and the corresponding
+/// message blocks; all blocks must be at the same offset `t` of messages of the same length.
+///
+/// When `std` feature is enabled on x86_64 targets, the states are compressed together using
+/// SSSE3 instructions if the CPU supports them; otherwise, the states are compressed one by one.
+#[inline(always)]
+fn compress_lanes(
+    states: &mut [[u32; 8]; NUM_LANES],
+    blocks: &[[u8; BLOCK_SIZE]; NUM_LANES],
+    t: u64,
+    last: bool,
+) {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    if std::is_x86_feature_detected!("ssse3") {
+        // SAFETY: the CPU supports SSSE3 instructions
+        unsafe { ssse3::compress_lanes(states, blocks, t, last) };
+        return;
+    }
+    for (state, block) in states.iter_mut().zip(blocks) {
+        compress_portable(state, block, t, last);
+    }
+}
+
+/// Portable implementation of BLAKE2s compression function.
+pub(super) fn compress_portable(
+    state: &mut [u32; 8],
+    block: &[u8; BLOCK_SIZE],
+    t: u64,
+    last: bool,
+) {
+    let mut m = [0u32; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(state);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= t as u32;
+    v[13] ^= (t >> 32) as u32;
+    if last {
+        v[14] = !v[14];
+    }
+
+    for s in SIGMA.iter() {
+        mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        state[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// BLAKE2s mixing function G.
+#[inline(always)]
+#[allow(clippy::many_single_char_names)]
+fn mix(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(12);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(8);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(7);
+}
+
+// SSSE3 IMPLEMENTATION
+// ================================================================================================
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+pub(super) mod ssse3 {
+    use super::{BLOCK_SIZE, IV, NUM_LANES, SIGMA};
+    use core::arch::x86_64::*;
+    use core::convert::TryInto;
+
+    /// SSSE3 implementation of BLAKE2s compression function.
+    ///
+    /// Each row of the 4x4 working matrix is kept in a single 128-bit register, such that the
+    /// mixing function is applied to all four columns (or diagonals) at once.
+    ///
+    /// # Safety
+    /// The caller must ensure that the CPU supports SSSE3 instructions.
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_SIZE], t: u64, last: bool) {
+        let mut m = [0u32; 16];
+        for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        let h0 = _mm_loadu_si128(state.as_ptr() as *const __m128i);
+        let h1 = _mm_loadu_si128(state.as_ptr().add(4) as *const __m128i);
+        let f0 = if last { u32::MAX } else { 0 };
+
+        let mut a = h0;
+        let mut b = h1;
+        let mut c = _mm_loadu_si128(IV.as_ptr() as *const __m128i);
+        let mut d = _mm_xor_si128(
+            _mm_loadu_si128(IV.as_ptr().add(4) as *const __m128i),
+            _mm_setr_epi32(t as i32, (t >> 32) as i32, f0 as i32, 0),
+        );
+
+        for s in SIGMA.iter() {
+            // mix the columns
+            let x = _mm_setr_epi32(
+                m[s[0]] as i32,
+                m[s[2]] as i32,
+                m[s[4]] as i32,
+                m[s[6]] as i32,
+            );
+            let y = _mm_setr_epi32(
+                m[s[1]] as i32,
+                m[s[3]] as i32,
+                m[s[5]] as i32,
+                m[s[7]] as i32,
+            );
+            mix(&mut a, &mut b, &mut c, &mut d, x, y);
+
+            // rotate the rows such that the diagonals are aligned into columns, and mix them
+            b = _mm_shuffle_epi32(b, 0b00_11_10_01);
+            c = _mm_shuffle_epi32(c, 0b01_00_11_10);
+            d = _mm_shuffle_epi32(d, 0b10_01_00_11);
+            let x = _mm_setr_epi32(
+                m[s[8]] as i32,
+                m[s[10]] as i32,
+                m[s[12]] as i32,
+                m[s[14]] as i32,
+            );
+            let y = _mm_setr_epi32(
+                m[s[9]] as i32,
+                m[s[11]] as i32,
+                m[s[13]] as i32,
+                m[s[15]] as i32,
+            );
+            mix(&mut a, &mut b, &mut c, &mut d, x, y);
+
+            // rotate the rows back
+            b = _mm_shuffle_epi32(b, 0b10_01_00_11);
+            c = _mm_shuffle_epi32(c, 0b01_00_11_10);
+            d = _mm_shuffle_epi32(d, 0b00_11_10_01);
+        }
+
+        let h0 = _mm_xor_si128(h0, _mm_xor_si128(a, c));
+        let h1 = _mm_xor_si128(h1, _mm_xor_si128(b, d));
+        _mm_storeu_si128(state.as_mut_ptr() as *mut __m128i, h0);
+        _mm_storeu_si128(state.as_mut_ptr().add(4) as *mut __m128i, h1);
+    }
+
+    /// SSSE3 implementation of BLAKE2s compression function applied to 4 states at once.
+    ///
+    /// Unlike [compress()], each 128-bit register holds the same word of the working matrix for
+    /// all four states (one state per 32-bit lane), such that every step of the compression
+    /// function is applied to all four states at once.
+    ///
+    /// # Safety
+    /// The caller must ensure that the CPU supports SSSE3 instructions.
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn compress_lanes(
+        states: &mut [[u32; 8]; NUM_LANES],
+        blocks: &[[u8; BLOCK_SIZE]; NUM_LANES],
+        t: u64,
+        last: bool,
+    ) {
+        // transpose the message blocks such that m[i] holds the i-th word of every block
+        let mut m = [_mm_setzero_si128(); 16];
+        for (i, word) in m.iter_mut().enumerate() {
+            let lane = |j: usize| {
+                u32::from_le_bytes(blocks[j][i * 4..i * 4 + 4].try_into().unwrap()) as i32
+            };
+            *word = _mm_setr_epi32(lane(0), lane(1), lane(2), lane(3));
+        }
+
+        // transpose the states in the same way
+        let mut h = [_mm_setzero_si128(); 8];
+        for (i, word) in h.iter_mut().enumerate() {
+            let lane = |j: usize| states[j][i] as i32;
+            *word = _mm_setr_epi32(lane(0), lane(1), lane(2), lane(3));
+        }
+
+        let f0 = if last { u32::MAX } else { 0 };
+        let mut v = [_mm_setzero_si128(); 16];
+        v[..8].copy_from_slice(&h);
+        for (word, &iv) in v[8..].iter_mut().zip(IV.iter()) {
+            *word = _mm_set1_epi32(iv as i32);
+        }
+        v[12] = _mm_xor_si128(v[12], _mm_set1_epi32(t as i32));
+        v[13] = _mm_xor_si128(v[13], _mm_set1_epi32((t >> 32) as i32));
+        v[14] = _mm_xor_si128(v[14], _mm_set1_epi32(f0 as i32));
+
+        for s in SIGMA.iter() {
+            mix_lanes(&mut v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+            mix_lanes(&mut v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+            mix_lanes(&mut v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+            mix_lanes(&mut v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+            mix_lanes(&mut v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+            mix_lanes(&mut v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+            mix_lanes(&mut v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+            mix_lanes(&mut v, [3, 4, 9, 14], m[s[14]], m[s[15]]);
+        }
+
+        // compute the new states and transpose them back
+        for (i, word) in h.iter().enumerate() {
+            let word = _mm_xor_si128(*word, _mm_xor_si128(v[i], v[i + 8]));
+            let mut lanes = [0u32; NUM_LANES];
+            _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, word);
+            for (state, value) in states.iter_mut().zip(lanes) {
+                state[i] = value;
+            }
+        }
+    }
+
+    /// Applies BLAKE2s mixing function G to the specified words of the working matrices of four
+    /// states transposed into lanes.
+    #[inline(always)]
+    unsafe fn mix_lanes(v: &mut [__m128i; 16], [a, b, c, d]: [usize; 4], x: __m128i, y: __m128i) {
+        let (mut va, mut vb, mut vc, mut vd) = (v[a], v[b], v[c], v[d]);
+        mix(&mut va, &mut vb, &mut vc, &mut vd, x, y);
+        v[a] = va;
+        v[b] = vb;
+        v[c] = vc;
+        v[d] = vd;
+    }
+
+    /// Applies BLAKE2s mixing function G to the four columns of the working matrix.
+    #[inline(always)]
+    unsafe fn mix(
+        a: &mut __m128i,
+        b: &mut __m128i,
+        c: &mut __m128i,
+        d: &mut __m128i,
+        x: __m128i,
+        y: __m128i,
+    ) {
+        *a = _mm_add_epi32(_mm_add_epi32(*a, *b), x);
+        *d = rotate_right_16(_mm_xor_si128(*d, *a));
+        *c = _mm_add_epi32(*c, *d);
+        *b = rotate_right_12(_mm_xor_si128(*b, *c));
+        *a = _mm_add_epi32(_mm_add_epi32(*a, *b), y);
+        *d = rotate_right_8(_mm_xor_si128(*d, *a));
+        *c = _mm_add_epi32(*c, *d);
+        *b = rotate_right_7(_mm_xor_si128(*b, *c));
+    }
+
+    #[inline(always)]
+    unsafe fn rotate_right_16(x: __m128i) -> __m128i {
+        _mm_shuffle_epi8(
+            x,
+            _mm_setr_epi8(2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13),
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn rotate_right_12(x: __m128i) -> __m128i {
+        _mm_or_si128(_mm_srli_epi32(x, 12), _mm_slli_epi32(x, 20))
+    }
+
+    #[inline(always)]
+    unsafe fn rotate_right_8(x: __m128i) -> __m128i {
+        _mm_shuffle_epi8(
+            x,
+            _mm_setr_epi8(1, 2, 3, 0, 5, 6, 7, 4, 9, 10, 11, 8, 13, 14, 15, 12),
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn rotate_right_7(x: __m128i) -> __m128i {
+        _mm_or_si128(_mm_srli_epi32(x, 7), _mm_slli_epi32(x, 25))
+    }
+}
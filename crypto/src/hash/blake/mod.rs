@@ -4,9 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{ByteDigest, ElementHasher, Hasher};
+use blake2s::Blake2s;
 use core::{convert::TryInto, fmt::Debug, marker::PhantomData};
 use math::{FieldElement, StarkField};
-use utils::ByteWriter;
+use utils::{collections::Vec, ByteWriter};
+
+mod blake2s;
 
 #[cfg(test)]
 mod tests;
@@ -59,6 +62,16 @@ impl<B: StarkField> ElementHasher for Blake3_256<B> {
     }
 }
 
+impl<B: StarkField> Blake3_256<B> {
+    /// Returns a hash of the provided sequence of bytes computed using BLAKE3 in keyed mode.
+    ///
+    /// This can be used as a MAC or a PRF; digests computed with different keys are unrelated to
+    /// each other and to the digests computed via [hash()](Hasher::hash) function.
+    pub fn keyed_hash(key: &[u8; 32], bytes: &[u8]) -> ByteDigest<32> {
+        ByteDigest(*blake3::keyed_hash(key, bytes).as_bytes())
+    }
+}
+
 // BLAKE3 192-BIT OUTPUT
 // ================================================================================================
 
@@ -113,6 +126,206 @@ impl<B: StarkField> ElementHasher for Blake3_192<B> {
     }
 }
 
+// BLAKE3 WITH EXTENDABLE OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE3 hash function with `N`-byte
+/// output.
+///
+/// Digests are computed by reading the first `N` bytes from the extendable output of BLAKE3. Thus,
+/// for any `N`, the first bytes of a digest are the same as the first bytes of the corresponding
+/// [Blake3_256] digest. This makes it possible to derive long pseudo-random byte strings (e.g.,
+/// for sampling query positions) via a single [merge_with_int()](Hasher::merge_with_int) call.
+///
+/// Collision resistance of the function is 128 bits for digests of 32 bytes or more, and half the
+/// digest size otherwise.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blake3Xof<B: StarkField, const N: usize>(PhantomData<B>);
+
+impl<B: StarkField, const N: usize> Hasher for Blake3Xof<B, N> {
+    type Digest = ByteDigest<N>;
+
+    const COLLISION_RESISTANCE: u32 = if N < 32 { N as u32 * 4 } else { 128 };
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        let mut hasher = BlakeHasher::new();
+        hasher.write_bytes(bytes);
+        ByteDigest(hasher.finalize_xof())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        Self::hash(ByteDigest::digests_as_bytes(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut hasher = BlakeHasher::new();
+        hasher.write_bytes(&seed.0);
+        hasher.write_bytes(&value.to_le_bytes());
+        ByteDigest(hasher.finalize_xof())
+    }
+}
+
+impl<B: StarkField, const N: usize> ElementHasher for Blake3Xof<B, N> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        let mut hasher = BlakeHasher::new();
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            hasher.write_bytes(E::elements_as_bytes(elements));
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            hasher.write(elements);
+        }
+        ByteDigest(hasher.finalize_xof())
+    }
+}
+
+impl<B: StarkField, const N: usize> Blake3Xof<B, N> {
+    /// Returns an `N`-byte hash of the provided sequence of bytes computed using BLAKE3 in keyed
+    /// mode.
+    pub fn keyed_hash(key: &[u8; 32], bytes: &[u8]) -> ByteDigest<N> {
+        let mut hasher = BlakeHasher::new_keyed(key);
+        hasher.write_bytes(bytes);
+        ByteDigest(hasher.finalize_xof())
+    }
+}
+
+// BLAKE2s WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE2s hash function with 256-bit
+/// output.
+///
+/// The way field elements are serialized by [hash_elements()](Blake2s_256::hash_elements) is
+/// defined by the `P` type parameter:
+/// * [StandardPadding] (the default) serializes elements in the same way as [Blake3_256], i.e.,
+///   each base field element is encoded into `ELEMENT_BYTES` little-endian bytes.
+/// * [CairoPadding] encodes each base field element into 32 little-endian bytes (padding it with
+///   zeros), which matches the way Cairo's BLAKE2s implementation hashes field elements.
+///
+/// All other functions are the same for both padding schemes: [merge()](Blake2s_256::merge)
+/// hashes the concatenation of the two digests, and
+/// [merge_with_int()](Blake2s_256::merge_with_int) hashes the seed followed by the value encoded
+/// as 8 little-endian bytes.
+///
+/// Batch methods ([hash_many()](Blake2s_256::hash_many),
+/// [merge_pairs()](Blake2s_256::merge_pairs), and
+/// [hash_elements_many()](Blake2s_256::hash_elements_many)) hash groups of 4 inputs of the same
+/// length at once; on x86_64 targets with SSSE3 support, this is done using SIMD instructions.
+#[allow(non_camel_case_types)]
+pub struct Blake2s_256<B: StarkField, P: Blake2sPadding = StandardPadding>(PhantomData<(B, P)>);
+
+impl<B: StarkField, P: Blake2sPadding> Hasher for Blake2s_256<B, P> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(Blake2s::digest(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(Blake2s::digest(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(Blake2s::digest(&data))
+    }
+
+    fn hash_many(inputs: &[&[u8]]) -> Vec<Self::Digest> {
+        Blake2s::digest_many(inputs)
+            .into_iter()
+            .map(ByteDigest)
+            .collect()
+    }
+
+    fn merge_pairs(values: &[[Self::Digest; 2]]) -> Vec<Self::Digest> {
+        let inputs = values
+            .iter()
+            .map(|pair| ByteDigest::digests_as_bytes(pair))
+            .collect::<Vec<_>>();
+        Self::hash_many(&inputs)
+    }
+}
+
+impl<B: StarkField, P: Blake2sPadding> ElementHasher for Blake2s_256<B, P> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        let mut hasher = Blake2s::new();
+        P::write_elements(E::slice_as_base_elements(elements), &mut hasher);
+        ByteDigest(hasher.finalize())
+    }
+
+    fn hash_elements_many<E>(inputs: &[&[E]]) -> Vec<Self::Digest>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // serialize all inputs first so that they can be hashed together
+        let inputs = inputs
+            .iter()
+            .map(|elements| {
+                let mut bytes = Vec::new();
+                P::write_elements(E::slice_as_base_elements(elements), &mut bytes);
+                bytes
+            })
+            .collect::<Vec<_>>();
+        let inputs = inputs.iter().map(|bytes| &bytes[..]).collect::<Vec<_>>();
+        Self::hash_many(&inputs)
+    }
+}
+
+// BLAKE2s PADDING
+// ================================================================================================
+
+/// Defines how field elements are serialized into bytes before being hashed by [Blake2s_256].
+pub trait Blake2sPadding: Send + Sync {
+    /// Writes the bytes of the provided base field elements into the specified target.
+    fn write_elements<B: StarkField, W: ByteWriter>(elements: &[B], target: &mut W);
+}
+
+/// Serializes each base field element into `ELEMENT_BYTES` bytes using its canonical
+/// little-endian representation.
+pub struct StandardPadding;
+
+impl Blake2sPadding for StandardPadding {
+    fn write_elements<B: StarkField, W: ByteWriter>(elements: &[B], target: &mut W) {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            target.write_bytes(B::elements_as_bytes(elements));
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            target.write(elements);
+        }
+    }
+}
+
+/// Serializes each base field element into 32 bytes using its canonical little-endian
+/// representation padded with zeros, as done by Cairo's BLAKE2s implementation.
+pub struct CairoPadding;
+
+impl Blake2sPadding for CairoPadding {
+    fn write_elements<B: StarkField, W: ByteWriter>(elements: &[B], target: &mut W) {
+        assert!(
+            B::ELEMENT_BYTES <= 32,
+            "field elements must fit into 32 bytes"
+        );
+        let padding = [0u8; 32];
+        for element in elements {
+            element.write_into(target);
+            target.write_bytes(&padding[B::ELEMENT_BYTES..]);
+        }
+    }
+}
+
 // BLAKE HASHER
 // ================================================================================================
 
@@ -124,9 +337,19 @@ impl BlakeHasher {
         Self(blake3::Hasher::new())
     }
 
+    pub fn new_keyed(key: &[u8; 32]) -> Self {
+        Self(blake3::Hasher::new_keyed(key))
+    }
+
     pub fn finalize(&self) -> [u8; 32] {
         *self.0.finalize().as_bytes()
     }
+
+    pub fn finalize_xof<const N: usize>(&self) -> [u8; N] {
+        let mut result = [0; N];
+        self.0.finalize_xof().fill(&mut result);
+        result
+    }
 }
 
 impl ByteWriter for BlakeHasher {
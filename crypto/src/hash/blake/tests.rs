@@ -3,9 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Blake3_256, ElementHasher, Hasher};
+use super::{
+    Blake2s, Blake2s_256, Blake3Xof, Blake3_256, CairoPadding, ElementHasher, Hasher, StarkField,
+};
 use math::{fields::f62::BaseElement, FieldElement};
-use rand_utils::rand_array;
+use rand_utils::{rand_array, rand_vector};
+use utils::{collections::Vec, string::String};
 
 #[test]
 fn hash_padding() {
@@ -28,3 +31,250 @@ fn hash_elements_padding() {
     let r2 = Blake3_256::hash_elements(&e2);
     assert_ne!(r1, r2);
 }
+
+#[test]
+fn hash_many() {
+    type Blake3 = Blake3_256<BaseElement>;
+
+    let b1 = [1_u8, 2, 3];
+    let b2 = [4_u8, 5];
+    let expected = vec![Blake3::hash(&b1), Blake3::hash(&b2)];
+    assert_eq!(expected, Blake3::hash_many(&[&b1, &b2]));
+
+    let e1: [BaseElement; 3] = rand_array();
+    let e2: [BaseElement; 5] = rand_array();
+    let expected = vec![Blake3::hash_elements(&e1), Blake3::hash_elements(&e2)];
+    assert_eq!(expected, Blake3::hash_elements_many(&[&e1, &e2]));
+}
+
+#[test]
+fn hash_xof() {
+    type Blake3 = Blake3_256<BaseElement>;
+    type Blake3Xof64 = Blake3Xof<BaseElement, 64>;
+
+    // the first 32 bytes of an extended output should be the same as the regular output
+    let b1 = [1_u8, 2, 3];
+    let d1 = Blake3::hash(&b1);
+    let d2 = Blake3Xof64::hash(&b1);
+    assert_eq!(d1.0, d2.0[..32]);
+
+    let seed = Blake3Xof64::hash(&d1.0);
+    let mut data = seed.0.to_vec();
+    data.extend_from_slice(&42_u64.to_le_bytes());
+    assert_eq!(
+        Blake3Xof64::hash(&data),
+        Blake3Xof64::merge_with_int(seed, 42)
+    );
+
+    let e1: [BaseElement; 3] = rand_array();
+    let d1 = Blake3::hash_elements(&e1);
+    let d2 = Blake3Xof64::hash_elements(&e1);
+    assert_eq!(d1.0, d2.0[..32]);
+
+    assert_eq!(128, Blake3Xof64::COLLISION_RESISTANCE);
+    assert_eq!(48, Blake3Xof::<BaseElement, 12>::COLLISION_RESISTANCE);
+}
+
+#[test]
+fn keyed_hash() {
+    type Blake3 = Blake3_256<BaseElement>;
+
+    let key = [7_u8; 32];
+    let b1 = [1_u8, 2, 3];
+    let d1 = Blake3::keyed_hash(&key, &b1);
+    assert_eq!(*blake3::keyed_hash(&key, &b1).as_bytes(), d1.0);
+    assert_ne!(Blake3::hash(&b1), d1);
+    assert_ne!(Blake3::keyed_hash(&[8_u8; 32], &b1), d1);
+
+    let d2 = Blake3Xof::<BaseElement, 100>::keyed_hash(&key, &b1);
+    assert_eq!(d1.0, d2.0[..32]);
+}
+
+#[test]
+fn blake2s_test_vectors() {
+    // test vectors from RFC 7693 and Python hashlib
+    let expected = "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9";
+    assert_eq!(expected, to_hex(&Blake2s::digest(b"")));
+
+    let expected = "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982";
+    assert_eq!(expected, to_hex(&Blake2s::digest(b"abc")));
+
+    // a message of exactly one block
+    let message: Vec<u8> = (0..64).collect();
+    let expected = "56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e";
+    assert_eq!(expected, to_hex(&Blake2s::digest(&message)));
+
+    // a message spanning multiple blocks absorbed in uneven chunks
+    let message: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+    let expected = "1c067a5e746fb0f6734efac9a8cdb0e11061f0077f255184365c690115392501";
+    let mut hasher = Blake2s::new();
+    for chunk in message.chunks(99) {
+        hasher.update(chunk);
+    }
+    assert_eq!(expected, to_hex(&hasher.finalize()));
+}
+
+#[test]
+fn blake2s_streaming() {
+    let message: [u8; 200] = rand_array();
+    let expected = Blake2s::digest(&message);
+
+    // absorbing the message in chunks of any size should result in the same hash
+    for chunk_size in [1, 7, 63, 64, 65, 128] {
+        let mut hasher = Blake2s::new();
+        for chunk in message.chunks(chunk_size) {
+            hasher.update(chunk);
+        }
+        assert_eq!(expected, hasher.finalize());
+    }
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[test]
+fn blake2s_ssse3() {
+    if !std::is_x86_feature_detected!("ssse3") {
+        return;
+    }
+
+    // the accelerated compression function should produce the same results as the portable one
+    let block: [u8; 64] = rand_array();
+    for (t, last) in [(64, false), (1000, true), (u64::MAX, true)] {
+        let mut expected: [u32; 8] = rand_array();
+        let mut actual = expected;
+        super::blake2s::compress_portable(&mut expected, &block, t, last);
+        // SAFETY: the CPU supports SSSE3 instructions
+        unsafe { super::blake2s::ssse3::compress(&mut actual, &block, t, last) };
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn blake2s_merge() {
+    type Blake2 = Blake2s_256<BaseElement>;
+
+    // merging two zero digests should be the same as hashing 64 zero bytes
+    let zero = super::ByteDigest([0; 32]);
+    let expected = "ae09db7cd54f42b490ef09b6bc541af688e4959bb8c53f359a6f56e38ab454a3";
+    assert_eq!(expected, to_hex(&Blake2::merge(&[zero, zero]).0));
+
+    let seed = Blake2::hash(&[1, 2, 3]);
+    let mut data = seed.0.to_vec();
+    data.extend_from_slice(&42_u64.to_le_bytes());
+    assert_eq!(Blake2::hash(&data), Blake2::merge_with_int(seed, 42));
+}
+
+#[test]
+fn blake2s_hash_many() {
+    type Blake2 = Blake2s_256<BaseElement>;
+    type Blake2Cairo = Blake2s_256<BaseElement, CairoPadding>;
+
+    // groups of messages of the same length are hashed together, all other messages are hashed
+    // one by one; the results must be the same in both cases
+    let lengths = [
+        0, 0, 0, 0, 64, 64, 64, 64, 200, 200, 200, 200, 3, 65, 65, 65, 65, 7,
+    ];
+    let messages = lengths
+        .iter()
+        .map(|&len| {
+            // random vectors cannot be empty
+            let mut message = rand_vector::<u8>(len + 1);
+            message.truncate(len);
+            message
+        })
+        .collect::<Vec<_>>();
+    let messages = messages.iter().map(|m| &m[..]).collect::<Vec<_>>();
+    let expected = messages.iter().map(|m| Blake2::hash(m)).collect::<Vec<_>>();
+    assert_eq!(expected, Blake2::hash_many(&messages));
+
+    let pairs = (0..9)
+        .map(|_| [Blake2::hash(&rand_vector(8)), Blake2::hash(&rand_vector(8))])
+        .collect::<Vec<_>>();
+    let expected = pairs.iter().map(Blake2::merge).collect::<Vec<_>>();
+    assert_eq!(expected, Blake2::merge_pairs(&pairs));
+
+    let elements = (0..6)
+        .map(|_| rand_array::<BaseElement, 5>())
+        .collect::<Vec<_>>();
+    let elements = elements.iter().map(|e| &e[..]).collect::<Vec<_>>();
+    let expected = elements
+        .iter()
+        .map(|e| Blake2::hash_elements(e))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, Blake2::hash_elements_many(&elements));
+    let expected = elements
+        .iter()
+        .map(|e| Blake2Cairo::hash_elements(e))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, Blake2Cairo::hash_elements_many(&elements));
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[test]
+fn blake2s_ssse3_lanes() {
+    if !std::is_x86_feature_detected!("ssse3") {
+        return;
+    }
+
+    // compressing 4 states at once should produce the same results as compressing them one by
+    // one using the portable compression function
+    let blocks: [[u8; 64]; 4] = [rand_array(), rand_array(), rand_array(), rand_array()];
+    for (t, last) in [(64, false), (1000, true), (u64::MAX, true)] {
+        let mut expected: [[u32; 8]; 4] = [rand_array(), rand_array(), rand_array(), rand_array()];
+        let mut actual = expected;
+        for (state, block) in expected.iter_mut().zip(blocks.iter()) {
+            super::blake2s::compress_portable(state, block, t, last);
+        }
+        // SAFETY: the CPU supports SSSE3 instructions
+        unsafe { super::blake2s::ssse3::compress_lanes(&mut actual, &blocks, t, last) };
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn blake2s_hash_elements_padding() {
+    type Blake2 = Blake2s_256<BaseElement>;
+    type Blake2Cairo = Blake2s_256<BaseElement, CairoPadding>;
+
+    // by default, elements are hashed as sequences of their canonical bytes
+    let elements: [BaseElement; 3] = rand_array();
+    let mut bytes = Vec::new();
+    for element in elements.iter() {
+        bytes.extend_from_slice(&element.as_int().to_le_bytes());
+    }
+    assert_eq!(Blake2::hash(&bytes), Blake2::hash_elements(&elements));
+
+    // with Cairo padding, every element is padded with zeros to 32 bytes
+    let mut bytes = Vec::new();
+    for element in elements.iter() {
+        let mut word = [0_u8; 32];
+        word[..8].copy_from_slice(&element.as_int().to_le_bytes());
+        bytes.extend_from_slice(&word);
+    }
+    assert_eq!(Blake2::hash(&bytes), Blake2Cairo::hash_elements(&elements));
+    assert_ne!(
+        Blake2::hash_elements(&elements),
+        Blake2Cairo::hash_elements(&elements)
+    );
+
+    // other functions should not depend on the padding scheme
+    let seed = Blake2::hash(&[1, 2, 3]);
+    assert_eq!(seed, Blake2Cairo::hash(&[1, 2, 3]));
+    assert_eq!(
+        Blake2::merge_with_int(seed, 42),
+        Blake2Cairo::merge_with_int(seed, 42)
+    );
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let e2 = [elements[0], elements[1], elements[2], BaseElement::ZERO];
+    assert_ne!(
+        Blake2Cairo::hash_elements(&elements),
+        Blake2Cairo::hash_elements(&e2)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
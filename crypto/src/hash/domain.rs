@@ -0,0 +1,196 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher};
+use core::marker::PhantomData;
+use math::FieldElement;
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+// HASH DOMAIN
+// ================================================================================================
+
+/// Defines a personalization string used to separate hashes computed by a
+/// [DomainSeparatedHasher] from hashes computed by other hashers.
+///
+/// For example, a protocol may define its own domain as follows:
+/// ```
+/// # use winter_crypto::HashDomain;
+/// struct MyProtocol;
+///
+/// impl HashDomain for MyProtocol {
+///     const PERSONALIZATION: &'static [u8] = b"my-protocol-v1";
+/// }
+/// ```
+pub trait HashDomain {
+    /// Personalization string of this domain.
+    const PERSONALIZATION: &'static [u8];
+}
+
+// DOMAIN SEPARATED HASHER
+// ================================================================================================
+
+/// Tag of the domain used by [hash()](Hasher::hash) and
+/// [hash_elements()](ElementHasher::hash_elements) functions (e.g., for Merkle tree leaves).
+const LEAF_TAG: u8 = 0;
+
+/// Tag of the domain used by [merge()](Hasher::merge) function (e.g., for internal Merkle tree
+/// nodes).
+const NODE_TAG: u8 = 1;
+
+/// Tag of the domain used by [merge_with_int()](Hasher::merge_with_int) function (e.g., for
+/// drawing Fiat-Shamir challenges and grinding).
+const INT_TAG: u8 = 2;
+
+/// A wrapper around a hash function which ensures that all of its procedures use distinct
+/// domains.
+///
+/// Every input is hashed using the underlying hasher `H` after being prefixed with the length of
+/// the personalization string of domain `D` (as 8 little-endian bytes), the personalization
+/// string itself, and a single byte tag identifying the procedure:
+/// * [hash()](Hasher::hash) and [hash_elements()](ElementHasher::hash_elements) use tag 0; field
+///   elements are serialized into bytes before being hashed, and thus, hashing elements is the
+///   same as hashing their serialized bytes.
+/// * [merge()](Hasher::merge) uses tag 1.
+/// * [merge_with_int()](Hasher::merge_with_int) uses tag 2; the value is appended to the seed as
+///   8 little-endian bytes.
+///
+/// Thus, a leaf of a Merkle tree can never have the same hash as an internal node, and
+/// pseudo-random values drawn by a [RandomCoin](crate::RandomCoin) instantiated with this hasher
+/// can never coincide with commitments. Similarly, hashers instantiated with different domains
+/// produce unrelated outputs.
+///
+/// All procedures are computed via [hash()](Hasher::hash) function of the underlying hasher. For
+/// byte-oriented hash functions (e.g., BLAKE3 or SHA3) this has about the same cost as calling
+/// the corresponding procedures directly; for arithmetization-friendly hash functions this is
+/// more expensive and does not preserve the algebraic structure of the inputs.
+pub struct DomainSeparatedHasher<H: Hasher, D: HashDomain>(PhantomData<(H, D)>);
+
+impl<H: Hasher, D: HashDomain> DomainSeparatedHasher<H, D> {
+    /// Returns a buffer initialized with the domain prefix for the specified tag; the buffer has
+    /// enough capacity to fit additional `len` bytes.
+    fn prefix(tag: u8, len: usize) -> Vec<u8> {
+        let personalization = D::PERSONALIZATION;
+        let mut result = Vec::with_capacity(8 + personalization.len() + 1 + len);
+        result.extend_from_slice(&(personalization.len() as u64).to_le_bytes());
+        result.extend_from_slice(personalization);
+        result.push(tag);
+        result
+    }
+}
+
+impl<H: Hasher, D: HashDomain> Hasher for DomainSeparatedHasher<H, D> {
+    type Digest = H::Digest;
+
+    const COLLISION_RESISTANCE: u32 = H::COLLISION_RESISTANCE;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        let mut data = Self::prefix(LEAF_TAG, bytes.len());
+        data.extend_from_slice(bytes);
+        H::hash(&data)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut data = Self::prefix(NODE_TAG, 64);
+        values[0].write_into(&mut data);
+        values[1].write_into(&mut data);
+        H::hash(&data)
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = Self::prefix(INT_TAG, 40);
+        seed.write_into(&mut data);
+        data.extend_from_slice(&value.to_le_bytes());
+        H::hash(&data)
+    }
+}
+
+impl<H: ElementHasher, D: HashDomain> ElementHasher for DomainSeparatedHasher<H, D> {
+    type BaseField = H::BaseField;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        let mut data = Self::prefix(LEAF_TAG, elements.len() * E::ELEMENT_BYTES);
+        data.write(elements);
+        H::hash(&data)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DomainSeparatedHasher, ElementHasher, HashDomain, Hasher};
+    use crate::hash::Blake3_256;
+    use math::fields::f64::BaseElement;
+    use rand_utils::{rand_array, rand_value};
+    use utils::{collections::Vec, Serializable};
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    struct DomainA;
+
+    impl HashDomain for DomainA {
+        const PERSONALIZATION: &'static [u8] = b"domain-a";
+    }
+
+    struct DomainB;
+
+    impl HashDomain for DomainB {
+        const PERSONALIZATION: &'static [u8] = b"domain-b";
+    }
+
+    type HasherA = DomainSeparatedHasher<Blake3, DomainA>;
+    type HasherB = DomainSeparatedHasher<Blake3, DomainB>;
+
+    #[test]
+    fn domains_are_distinct() {
+        let d1 = HasherA::hash(&[1, 2, 3]);
+        let d2 = HasherA::hash(&[4, 5, 6]);
+
+        // hashing the concatenation of two digests should differ from merging them
+        let mut bytes = Vec::new();
+        d1.write_into(&mut bytes);
+        d2.write_into(&mut bytes);
+        assert_ne!(HasherA::hash(&bytes), HasherA::merge(&[d1, d2]));
+
+        // hashing a digest followed by an integer should differ from merging them
+        let value: u64 = rand_value();
+        let mut bytes = Vec::new();
+        d1.write_into(&mut bytes);
+        bytes.extend_from_slice(&value.to_le_bytes());
+        assert_ne!(HasherA::hash(&bytes), HasherA::merge_with_int(d1, value));
+
+        // outputs should differ from the outputs of the underlying hasher
+        assert_ne!(Blake3::hash(&[1, 2, 3]), d1);
+        assert_ne!(Blake3::merge(&[d1, d2]), HasherA::merge(&[d1, d2]));
+        assert_ne!(
+            Blake3::merge_with_int(d1, value),
+            HasherA::merge_with_int(d1, value)
+        );
+    }
+
+    #[test]
+    fn personalizations_are_distinct() {
+        let d1 = HasherA::hash(&[1, 2, 3]);
+        let d2 = HasherB::hash(&[1, 2, 3]);
+        assert_ne!(d1, d2);
+        assert_ne!(HasherA::merge(&[d1, d2]), HasherB::merge(&[d1, d2]));
+        assert_ne!(
+            HasherA::merge_with_int(d1, 1),
+            HasherB::merge_with_int(d1, 1)
+        );
+    }
+
+    #[test]
+    fn hash_elements() {
+        let elements: [BaseElement; 4] = rand_array();
+
+        // hashing elements should be the same as hashing their serialized bytes
+        let mut bytes = Vec::new();
+        elements.as_slice().write_into(&mut bytes);
+        assert_eq!(HasherA::hash(&bytes), HasherA::hash_elements(&elements));
+        assert_ne!(HasherB::hash(&bytes), HasherA::hash_elements(&elements));
+    }
+}
@@ -53,7 +53,7 @@ const NUM_ROUNDS: usize = 7;
 ///   margin used in the specifications (a 20% margin rounds up to 8 rounds). The primary
 ///   motivation for this is that having the number of rounds be one less than a power of two
 ///   simplifies AIR design for computations involving the hash function.
-/// *  When hashing a sequence of elements, implement the Hirose padding rule. However, it also
+/// * When hashing a sequence of elements, implement the Hirose padding rule. However, it also
 ///   means that our instantiation of Griffin cannot be used in a stream mode as the number
 ///   of elements to be hashed must be known upfront.
 /// * Instead of using the suggested matrix as described in Griffin paper, we use a methodology
@@ -72,6 +72,13 @@ const NUM_ROUNDS: usize = 7;
 /// The above parameters target 128-bit security level. The digest consists of four field elements
 /// and it can be serialized into 32 bytes (256 bits).
 ///
+/// ## Constraint count
+/// A round of Griffin can be verified in an AIR using the round input and output as the current
+/// and next rows of an 8-column trace. Each round requires 8 transition constraints: 2 constraints
+/// of degree 7 for the first two state elements, and 6 constraints of degree 3 for the remaining
+/// elements. A single permutation thus spans 7 rows and fits into an 8-row cycle. For comparison,
+/// Rescue Prime requires 12 constraints of degree 7 per round for [Rp64_256](super::super::Rp64_256).
+///
 /// ## Hash output consistency
 /// Functions [hash_elements()](GriffinJive64_256::hash_elements), [merge()](GriffinJive64_256::merge), and
 /// [merge_with_int()](GriffinJive64_256::merge_with_int) are not consistent. This is because the former
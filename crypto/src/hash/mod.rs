@@ -5,22 +5,45 @@
 
 use core::{fmt::Debug, slice};
 use math::{FieldElement, StarkField};
-use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
+use utils::{collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable};
 
 mod blake;
-pub use blake::{Blake3_192, Blake3_256};
+pub use blake::{
+    Blake2sPadding, Blake2s_256, Blake3Xof, Blake3_192, Blake3_256, CairoPadding, StandardPadding,
+};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Keccak_256, Sha2_256, Sha3_256};
+
+mod domain;
+pub use domain::{DomainSeparatedHasher, HashDomain};
+
+mod sponge;
+pub use sponge::{DuplexSponge, Sponge, SpongePermutation};
 
 mod mds;
 
 mod rescue;
-pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
+pub use rescue::{Rp62_248, Rp64_256, RpJive64_256, Rpo256};
 
 mod griffin;
 pub use griffin::GriffinJive64_256;
 
+mod anemoi;
+pub use anemoi::AnemoiJive64_256;
+
+mod pedersen;
+pub use pedersen::{stark_curve, Pedersen};
+
+mod poseidon;
+pub use poseidon::{Poseidon62_248, Poseidon64_256};
+
+mod monolith;
+pub use monolith::Monolith64_256;
+
+mod tip5;
+pub use tip5::Tip5_320;
+
 // HASHER TRAITS
 // ================================================================================================
 
@@ -29,6 +52,8 @@ pub use griffin::GriffinJive64_256;
 /// This trait defines hash procedures for the following inputs:
 /// * A sequence of bytes.
 /// * Two digests - this is intended for use in Merkle tree constructions.
+/// * Many pairs of digests - this is intended for computing entire levels of Merkle trees.
+/// * Any number of digests - this is intended for use in Merkle trees with arity greater than 2.
 /// * A digests and a u64 value - this intended for use in PRNG or PoW contexts.
 pub trait Hasher {
     /// Specifies a digest type returned by this hasher.
@@ -44,8 +69,41 @@ pub trait Hasher {
     /// Merkle trees.
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest;
 
+    /// Returns a hash of the provided digests. This method is intended for use in construction
+    /// of Merkle trees with arity greater than 2.
+    ///
+    /// The default implementation hashes the concatenation of serialized digests; hash functions
+    /// which can compress several digests more efficiently should override this method. The
+    /// result is not required to be the same as the result of [merge()](Hasher::merge) for two
+    /// digests.
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        let mut bytes = Vec::with_capacity(values.len() * Self::Digest::SIZE);
+        for value in values {
+            bytes.extend_from_slice(value.as_bytes().as_ref());
+        }
+        Self::hash(&bytes)
+    }
+
     /// Returns hash(`seed` || `value`). This method is intended for use in PRNG and PoW contexts.
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest;
+
+    /// Returns hashes of the provided sequences of bytes.
+    ///
+    /// The default implementation hashes the inputs one by one; hash functions which can process
+    /// several messages at once should override this method.
+    fn hash_many(inputs: &[&[u8]]) -> Vec<Self::Digest> {
+        inputs.iter().map(|bytes| Self::hash(bytes)).collect()
+    }
+
+    /// Returns hashes of the provided pairs of digests, where the hash of each pair is the same
+    /// as the one computed by [merge()](Hasher::merge). This method is intended for computing
+    /// all nodes of a Merkle tree level at once.
+    ///
+    /// The default implementation merges the pairs one by one; hash functions which can process
+    /// several messages at once should override this method.
+    fn merge_pairs(values: &[[Self::Digest; 2]]) -> Vec<Self::Digest> {
+        values.iter().map(Self::merge).collect()
+    }
 }
 
 /// Defines a cryptographic hash function for hashing field elements.
@@ -60,6 +118,20 @@ pub trait ElementHasher: Hasher {
     fn hash_elements<E>(elements: &[E]) -> Self::Digest
     where
         E: FieldElement<BaseField = Self::BaseField>;
+
+    /// Returns hashes of the provided sequences of field elements.
+    ///
+    /// The default implementation hashes the inputs one by one; hash functions which can process
+    /// several messages at once should override this method.
+    fn hash_elements_many<E>(inputs: &[&[E]]) -> Vec<Self::Digest>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        inputs
+            .iter()
+            .map(|elements| Self::hash_elements(elements))
+            .collect()
+    }
 }
 
 // DIGEST TRAIT
@@ -69,19 +141,25 @@ pub trait ElementHasher: Hasher {
 pub trait Digest:
     Debug + Default + Copy + Clone + Eq + PartialEq + Send + Sync + Serializable + Deserializable
 {
-    /// Returns this digest serialized into an array of bytes.
+    /// Number of bytes in the serialized representation of this digest.
+    const SIZE: usize;
+
+    /// Byte array type returned by [as_bytes()](Digest::as_bytes).
     ///
-    /// Ideally, the length of the returned array should be defined by an associated constant, but
-    /// using associated constants in const generics is not supported by Rust yet. Thus, we put an
-    /// upper limit on the possible digest size. For digests which are smaller than 32 bytes, the
-    /// unused bytes should be set to 0.
-    fn as_bytes(&self) -> [u8; 32];
+    /// Ideally, this would be `[u8; Self::SIZE]`, but using associated constants in const
+    /// generics is not supported by Rust yet. Thus, implementations are expected to set this type
+    /// to an array of exactly `SIZE` bytes.
+    type Bytes: AsRef<[u8]> + Copy + Debug + Eq + Send + Sync;
+
+    /// Returns this digest serialized into an array of [SIZE](Digest::SIZE) bytes.
+    fn as_bytes(&self) -> Self::Bytes;
 }
 
 // BYTE DIGEST
 // ================================================================================================
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(transparent)]
 pub struct ByteDigest<const N: usize>([u8; N]);
 
 impl<const N: usize> ByteDigest<N> {
@@ -105,10 +183,12 @@ impl<const N: usize> ByteDigest<N> {
 }
 
 impl<const N: usize> Digest for ByteDigest<N> {
-    fn as_bytes(&self) -> [u8; 32] {
-        let mut result = [0; 32];
-        result[..N].copy_from_slice(&self.0);
-        result
+    const SIZE: usize = N;
+
+    type Bytes = [u8; N];
+
+    fn as_bytes(&self) -> [u8; N] {
+        self.0
     }
 }
 
@@ -130,6 +210,20 @@ impl<const N: usize> Deserializable for ByteDigest<N> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for ByteDigest<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for ByteDigest<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{ByteDigest, Digest};
@@ -140,8 +234,10 @@ mod tests {
         assert_eq!([255_u8; 32], d.as_bytes());
 
         let d = ByteDigest::new([255_u8; 31]);
-        let mut expected = [255_u8; 32];
-        expected[31] = 0;
-        assert_eq!(expected, d.as_bytes());
+        assert_eq!([255_u8; 31], d.as_bytes());
+
+        let d = ByteDigest::new([255_u8; 64]);
+        assert_eq!(64, <ByteDigest<64> as Digest>::SIZE);
+        assert_eq!([255_u8; 64], d.as_bytes());
     }
 }
@@ -0,0 +1,451 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher, SpongePermutation};
+use core::convert::TryInto;
+use core::ops::Range;
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 4 through 11.
+const RATE_RANGE: Range<usize> = 4..12;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 4..8;
+const INPUT2_RANGE: Range<usize> = 8..12;
+
+/// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+const CAPACITY_RANGE: Range<usize> = 0..4;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 4, 5, 6, and 7 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 4..8;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of rounds is set to 6 to target 128-bit security level, as recommended in the
+/// specifications.
+const NUM_ROUNDS: usize = 6;
+
+/// The number of state elements to which the Bars layer is applied.
+const NUM_BARS: usize = 4;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Monolith hash function with 256-bit output.
+///
+/// The hash function is implemented according to the Monolith
+/// [specifications](https://eprint.iacr.org/2023/1025.pdf) with the following exceptions:
+/// * When hashing a sequence of elements, we do not use the padding scheme from the
+///   specifications. Instead, we initialize the first capacity element to the number of elements
+///   to be hashed, and pad the sequence with Fp(0) elements only. This ensures consistency of
+///   hash outputs between different hashing methods (see section below). However, it also means
+///   that our instantiation of Monolith cannot be used in a stream mode as the number of
+///   elements to be hashed must be known upfront.
+/// * We use the first 4 elements of the state for capacity and the remaining 8 elements for
+///   rate. The output of the hash function comes from the first four elements of the rate
+///   portion of the state (elements 4, 5, 6, and 7). This is the same layout as used by
+///   [Rp64_256](super::super::Rp64_256).
+///
+/// Monolith relies on a lookup-friendly non-linear layer (Bars) which makes it several times
+/// faster than Rescue Prime in native execution. Round constants are generated by sampling the
+/// output of SHAKE128 seeded with the instance parameters.
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * Number of rounds: 6.
+/// * Bars: applied to the first 4 state elements, using 8-bit limbs.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// ## Hash output consistency
+/// Functions [hash_elements()](Monolith64_256::hash_elements), [merge()](Monolith64_256::merge),
+/// and [merge_with_int()](Monolith64_256::merge_with_int) are internally consistent. That is,
+/// computing a hash for the same set of elements using these functions will always produce the
+/// same result. For example, merging two digests using [merge()](Monolith64_256::merge) will
+/// produce the same result as hashing 8 elements which make up these digests using
+/// [hash_elements()](Monolith64_256::hash_elements) function.
+///
+/// However, [hash()](Monolith64_256::hash) function is not consistent with functions mentioned
+/// above. For example, if we take two field elements, serialize them to bytes and hash them using
+/// [hash()](Monolith64_256::hash), the result will differ from the result obtained by hashing
+/// these elements directly using [hash_elements()](Monolith64_256::hash_elements) function. The
+/// reason for this difference is that [hash()](Monolith64_256::hash) function needs to be able to
+/// handle arbitrary binary strings, which may or may not encode valid field elements - and thus,
+/// deserialization procedure used by this function is different from the procedure used to
+/// deserialize valid field elements.
+pub struct Monolith64_256();
+
+impl Hasher for Monolith64_256 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % 7 == 0 {
+            bytes.len() / 7
+        } else {
+            bytes.len() / 7 + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(num_elements as u64);
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state. we use 7-byte chunks because
+        // every 7-byte chunk is guaranteed to map to some field element.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for (chunk_idx, chunk) in bytes.chunks(7).enumerate() {
+            if chunk_idx < num_elements - 1 {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state; if the rate is filled up, apply the Monolith permutation and start absorbing
+            // again from zero index.
+            state[RATE_RANGE.start + i] += BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Monolith permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the rate portion of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements), and set the first capacity element to 8 (the number of elements to
+        // be hashed).
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+        state[CAPACITY_RANGE.start] = BaseElement::new(RATE_WIDTH as u64);
+
+        // apply the Monolith permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and set the first capacity element to 5 (the number of elements to be hashed).
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and set the first capacity element
+        //   to 6.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 1);
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 2);
+        }
+
+        // apply the Monolith permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Monolith64_256 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(elements.len() as u64);
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the Monolith permutation and start absorbing again; repeat until all
+        // elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Monolith permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the rate portion of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl SpongePermutation for Monolith64_256 {
+    type State = [BaseElement; STATE_WIDTH];
+
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    fn init_state() -> Self::State {
+        [BaseElement::ZERO; STATE_WIDTH]
+    }
+
+    fn permute(state: &mut Self::State) {
+        Self::apply_permutation(state)
+    }
+
+    fn digest_as_elements(digest: &Self::Digest) -> &[Self::BaseField] {
+        digest.as_elements()
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Monolith64_256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 6 to target 128-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 4 through 11 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 4, 5, 6, and 7.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// First row of the circulant MDS matrix used for computing the Concrete layer.
+    pub const MDS: [u64; STATE_WIDTH] = MDS;
+
+    /// Round constants added to the hasher state at the end of all rounds except for the last.
+    pub const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS - 1] = ARK;
+
+    // MONOLITH PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Monolith permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        Self::apply_concrete(state);
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+    }
+
+    /// Monolith round function; round constants are not added in the last round.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::apply_bars(state);
+        Self::apply_bricks(state);
+        Self::apply_concrete(state);
+        if round < NUM_ROUNDS - 1 {
+            Self::add_constants(state, &ARK[round]);
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the Bar function to the first [NUM_BARS] elements of the state.
+    #[inline(always)]
+    fn apply_bars(state: &mut [BaseElement; STATE_WIDTH]) {
+        state[..NUM_BARS]
+            .iter_mut()
+            .for_each(|v| *v = BaseElement::new(bar(v.as_int())));
+    }
+
+    /// Applies the Bricks layer (a Feistel Type-3 construction with a square as a round function)
+    /// to the state.
+    #[inline(always)]
+    fn apply_bricks(state: &mut [BaseElement; STATE_WIDTH]) {
+        for i in (1..STATE_WIDTH).rev() {
+            state[i] += state[i - 1].square();
+        }
+    }
+
+    /// Applies the Concrete layer (multiplication by a circulant MDS matrix) to the state.
+    #[inline(always)]
+    fn apply_concrete(state: &mut [BaseElement; STATE_WIDTH]) {
+        // all matrix coefficients are small, and thus, we can accumulate each row of the product
+        // in a u128 before performing a single reduction
+        let values = state.map(|v| v.as_int() as u128);
+        for (i, s) in state.iter_mut().enumerate() {
+            let mut acc = 0u128;
+            for (j, &v) in values.iter().enumerate() {
+                acc += MDS[(STATE_WIDTH + j - i) % STATE_WIDTH] as u128 * v;
+            }
+            *s = reduce_u128(acc);
+        }
+    }
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], ark: &[BaseElement; STATE_WIDTH]) {
+        state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
+    }
+}
+
+/// Applies the 8-bit Monolith S-Box to each byte of the provided value. The S-Box is defined as
+/// S(y) = (y ^ ((!y <<< 1) & (y <<< 2) & (y <<< 3))) <<< 1, where <<< is a rotation within a byte.
+#[inline(always)]
+fn bar(value: u64) -> u64 {
+    let rot1 = ((value & 0x8080808080808080) >> 7) | ((value & 0x7f7f7f7f7f7f7f7f) << 1);
+    let rot2 = ((value & 0xc0c0c0c0c0c0c0c0) >> 6) | ((value & 0x3f3f3f3f3f3f3f3f) << 2);
+    let rot3 = ((value & 0xe0e0e0e0e0e0e0e0) >> 5) | ((value & 0x1f1f1f1f1f1f1f1f) << 3);
+    let tmp = value ^ (!rot1 & rot2 & rot3);
+    ((tmp & 0x8080808080808080) >> 7) | ((tmp & 0x7f7f7f7f7f7f7f7f) << 1)
+}
+
+/// Reduces a 128-bit integer modulo the field modulus.
+#[inline(always)]
+fn reduce_u128(value: u128) -> BaseElement {
+    let lo = BaseElement::new(value as u64);
+    let hi = BaseElement::new((value >> 64) as u64);
+    // 2^64 mod p = 2^32 - 1
+    lo + hi * BaseElement::new(u32::MAX as u64)
+}
+
+// MDS
+// ================================================================================================
+/// First row of the circulant Monolith MDS matrix for state width 12.
+const MDS: [u64; STATE_WIDTH] = [7, 23, 8, 26, 13, 10, 9, 7, 6, 22, 21, 8];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Monolith round constants.
+///
+/// The constants are obtained by reading 8-byte little-endian integers from the output of
+/// SHAKE128 seeded with "Monolith" || 12 || 6 || p || [8; 8] (state width, number of rounds,
+/// field modulus, and Bars limb sizes), and discarding the values which are not smaller than p.
+const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS - 1] = [
+    [
+        BaseElement::new(13596126580325903823),
+        BaseElement::new(5676126986831820406),
+        BaseElement::new(11349149288412960427),
+        BaseElement::new(3368797843020733411),
+        BaseElement::new(16240671731749717664),
+        BaseElement::new(9273190757374900239),
+        BaseElement::new(14446552112110239438),
+        BaseElement::new(4033077683985131644),
+        BaseElement::new(4291229347329361293),
+        BaseElement::new(13231607645683636062),
+        BaseElement::new(1383651072186713277),
+        BaseElement::new(8898815177417587567),
+    ],
+    [
+        BaseElement::new(2383619671172821638),
+        BaseElement::new(6065528368924797662),
+        BaseElement::new(16737578966352303081),
+        BaseElement::new(2661700069680749654),
+        BaseElement::new(7414030722730336790),
+        BaseElement::new(18124970299993404776),
+        BaseElement::new(9169923000283400738),
+        BaseElement::new(15832813151034110977),
+        BaseElement::new(16245117847613094506),
+        BaseElement::new(11056181639108379773),
+        BaseElement::new(10546400734398052938),
+        BaseElement::new(8443860941261719174),
+    ],
+    [
+        BaseElement::new(15799082741422909885),
+        BaseElement::new(13421235861052008152),
+        BaseElement::new(15448208253823605561),
+        BaseElement::new(2540286744040770964),
+        BaseElement::new(2895626806801935918),
+        BaseElement::new(8644593510196221619),
+        BaseElement::new(17722491003064835823),
+        BaseElement::new(5166255496419771636),
+        BaseElement::new(1015740739405252346),
+        BaseElement::new(4400043467547597488),
+        BaseElement::new(5176473243271652644),
+        BaseElement::new(4517904634837939508),
+    ],
+    [
+        BaseElement::new(18341030605319882173),
+        BaseElement::new(13366339881666916534),
+        BaseElement::new(6291492342503367536),
+        BaseElement::new(10004214885638819819),
+        BaseElement::new(4748655089269860551),
+        BaseElement::new(1520762444865670308),
+        BaseElement::new(8393589389936386108),
+        BaseElement::new(11025183333304586284),
+        BaseElement::new(5993305003203422738),
+        BaseElement::new(458912836931247573),
+        BaseElement::new(5947003897778655410),
+        BaseElement::new(17184667486285295106),
+    ],
+    [
+        BaseElement::new(15710528677110011358),
+        BaseElement::new(8929476121507374707),
+        BaseElement::new(2351989866172789037),
+        BaseElement::new(11264145846854799752),
+        BaseElement::new(14924075362538455764),
+        BaseElement::new(10107004551857451916),
+        BaseElement::new(18325221206052792232),
+        BaseElement::new(16751515052585522105),
+        BaseElement::new(15305034267720085905),
+        BaseElement::new(15639149412312342017),
+        BaseElement::new(14624541102106656564),
+        BaseElement::new(3542311898554959098),
+    ],
+];
@@ -0,0 +1,194 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Monolith64_256, MDS, NUM_BARS,
+    STATE_WIDTH,
+};
+use core::convert::TryInto;
+use math::StarkField;
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_bars() {
+    // the S-Box maps 0 to 0 and 0xff to 0xff; thus, p - 1 = 0xffffffff00000000 is a fixed point
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    state[0] = -BaseElement::ONE;
+    state[NUM_BARS] = BaseElement::new(3);
+    Monolith64_256::apply_bars(&mut state);
+    assert_eq!(-BaseElement::ONE, state[0]);
+    assert_eq!(BaseElement::new(3), state[NUM_BARS]);
+
+    // the S-Box should be a permutation on bytes
+    let mut images = [false; 256];
+    for i in 0..256_u64 {
+        let image = super::bar(i);
+        assert!(image < 256);
+        images[image as usize] = true;
+    }
+    assert!(images.iter().all(|&v| v));
+}
+
+#[test]
+fn test_concrete() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = [BaseElement::ZERO; STATE_WIDTH];
+    for (i, e) in expected.iter_mut().enumerate() {
+        for (j, &s) in state.iter().enumerate() {
+            *e += BaseElement::new(MDS[(STATE_WIDTH + j - i) % STATE_WIDTH]) * s;
+        }
+    }
+
+    let mut actual = state;
+    Monolith64_256::apply_concrete(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+        BaseElement::new(10),
+        BaseElement::new(11),
+    ];
+
+    Monolith64_256::apply_permutation(&mut state);
+
+    // expected values are obtained by executing an independent Python implementation of the
+    // permutation written according to the specifications
+    let expected = vec![
+        BaseElement::new(5867581605548782913),
+        BaseElement::new(588867029099903233),
+        BaseElement::new(6043817495575026667),
+        BaseElement::new(805786589926590032),
+        BaseElement::new(9919982299747097782),
+        BaseElement::new(6718641691835914685),
+        BaseElement::new(7951881005429661950),
+        BaseElement::new(15453177927755089358),
+        BaseElement::new(974633365445157727),
+        BaseElement::new(9654662171963364206),
+        BaseElement::new(6281307445101925412),
+        BaseElement::new(13745376999934453119),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements() {
+    let elements: [BaseElement; 10] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+    ];
+
+    // expected values are obtained by executing the same Python implementation as above
+    let expected = vec![
+        BaseElement::new(5266289418856097623),
+        BaseElement::new(15687207066437878382),
+        BaseElement::new(16705076266519878974),
+        BaseElement::new(6885041650871855846),
+    ];
+    assert_eq!(
+        expected,
+        Monolith64_256::hash_elements(&elements).as_elements()
+    );
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Monolith64_256::merge(&digests);
+    let h_result = Monolith64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Monolith64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Monolith64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Monolith64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Monolith64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Monolith64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Monolith64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Monolith64_256::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Monolith64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Monolith64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Monolith64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Monolith64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Monolith64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with inputs spanning more than one permutation
+    let r1 = Monolith64_256::hash(&[7_u8; 60]);
+    let r2 = Monolith64_256::hash(&[[7_u8; 60].as_slice(), &[0]].concat());
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Monolith64_256::hash_elements(&e1);
+    let r2 = Monolith64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
@@ -0,0 +1,178 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteDigest, ElementHasher, Hasher};
+use core::marker::PhantomData;
+use math::{
+    fields::f252::{BaseElement as Felt, U256},
+    FieldElement, StarkField,
+};
+use utils::collections::Vec;
+
+pub mod stark_curve;
+use stark_curve::{ProjectivePoint, PEDERSEN_POINTS};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of low bits of an input which are multiplied by the first constant point of the input;
+/// the remaining 4 high bits are multiplied by the second constant point.
+const LOW_PART_BITS: usize = 248;
+
+/// The number of bytes in a chunk when hashing byte strings; every 31-byte chunk is guaranteed to
+/// map to a valid field element.
+const BYTE_CHUNK_SIZE: usize = 31;
+
+// PEDERSEN HASH
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for the StarkWare Pedersen hash function
+/// over the STARK curve.
+///
+/// The STARK curve is defined over a 252-bit prime field with modulus 2^251 + 17 * 2^192 + 1 by
+/// the equation y^2 = x^3 + x + beta. A hash of two field elements a and b is the x coordinate of
+/// P0 + a_low * P1 + a_high * P2 + b_low * P3 + b_high * P4, where `_low` and `_high` denote the
+/// low 248 bits and the high 4 bits of an element, and P0, ..., P4 are the constant points
+/// published by StarkWare. The digest is the resulting field element encoded as 32 big-endian
+/// bytes.
+///
+/// The hash functions are defined as follows:
+/// * [merge()](Pedersen::merge) computes `pedersen(a, b)` for the field elements encoded by the
+///   two digests; this matches Cairo's `hash2` and can be used to verify Merkle paths with the
+///   Pedersen builtin.
+/// * [merge_with_int()](Pedersen::merge_with_int) computes `pedersen(seed, value)`.
+/// * [hash_elements()](Pedersen::hash_elements) maps each base field element to a field element
+///   of the STARK curve via its canonical integer representation, and then computes
+///   `pedersen(...pedersen(pedersen(0, e_1), e_2)..., n)`, where n is the number of base field
+///   elements. This matches the semantics of Cairo's `hash_state` module (`hash_init`,
+///   `hash_update`, `hash_finalize`).
+/// * [hash()](Pedersen::hash) splits the bytes into 31-byte chunks, interprets each chunk as a
+///   big-endian integer, and hashes the chunks the same way as
+///   [hash_elements()](Pedersen::hash_elements) does, except that the final element is the
+///   number of bytes rather than the number of chunks.
+///
+/// Computing a Pedersen hash is orders of magnitude slower than computing a hash using other
+/// available hash functions; thus, this hasher should be used only when the proof is to be
+/// verified by a Cairo program.
+pub struct Pedersen<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Pedersen<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 125;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        let mut state = Felt::ZERO;
+        for chunk in bytes.chunks(BYTE_CHUNK_SIZE) {
+            let mut buf = [0_u8; 32];
+            buf[32 - chunk.len()..].copy_from_slice(chunk);
+            state = pedersen_hash(state, felt_from_be_bytes(&buf));
+        }
+        let num_bytes = Felt::from(bytes.len() as u64);
+        ByteDigest(felt_to_be_bytes(pedersen_hash(state, num_bytes)))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let a = felt_from_be_bytes(&values[0].0);
+        let b = felt_from_be_bytes(&values[1].0);
+        ByteDigest(felt_to_be_bytes(pedersen_hash(a, b)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let seed = felt_from_be_bytes(&seed.0);
+        let value = Felt::from(value);
+        ByteDigest(felt_to_be_bytes(pedersen_hash(seed, value)))
+    }
+}
+
+impl<B: StarkField> ElementHasher for Pedersen<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        let elements = E::slice_as_base_elements(elements);
+
+        // every base field element is serialized into its canonical little-endian form; reversing
+        // the bytes gives the big-endian encoding of the same integer
+        let mut state = Felt::ZERO;
+        let mut buf = Vec::with_capacity(B::ELEMENT_BYTES);
+        for element in elements {
+            element.write_into(&mut buf);
+            let mut bytes = [0_u8; 32];
+            bytes[32 - buf.len()..].copy_from_slice(&buf);
+            bytes[32 - buf.len()..].reverse();
+            state = pedersen_hash(state, felt_from_be_bytes(&bytes));
+            buf.clear();
+        }
+        let num_elements = Felt::from(elements.len() as u64);
+        ByteDigest(felt_to_be_bytes(pedersen_hash(state, num_elements)))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes the Pedersen hash of two field elements.
+fn pedersen_hash(a: Felt, b: Felt) -> Felt {
+    let a = a.as_int().0;
+    let b = b.as_int().0;
+
+    // compute a_high * P2 + b_high * P4
+    let mut high = ProjectivePoint::INFINITY;
+    for bit in (LOW_PART_BITS..252).rev() {
+        high.double();
+        if get_bit(&a, bit) {
+            high.add_affine(&PEDERSEN_POINTS[2]);
+        }
+        if get_bit(&b, bit) {
+            high.add_affine(&PEDERSEN_POINTS[4]);
+        }
+    }
+
+    // compute a_low * P1 + b_low * P3 using a joint double-and-add loop
+    let mut result = ProjectivePoint::INFINITY;
+    for bit in (0..LOW_PART_BITS).rev() {
+        result.double();
+        if get_bit(&a, bit) {
+            result.add_affine(&PEDERSEN_POINTS[1]);
+        }
+        if get_bit(&b, bit) {
+            result.add_affine(&PEDERSEN_POINTS[3]);
+        }
+    }
+
+    if let Some(high) = high.to_affine() {
+        result.add_affine(&high);
+    }
+    result.add_affine(&PEDERSEN_POINTS[0]);
+
+    // the probability of the sum being the point at infinity is negligible
+    result
+        .to_affine()
+        .expect("Pedersen hash resulted in the point at infinity")
+        .x
+}
+
+/// Returns a field element for the specified 32-byte big-endian integer; the integer is reduced
+/// modulo the field modulus.
+fn felt_from_be_bytes(bytes: &[u8; 32]) -> Felt {
+    let mut bytes = *bytes;
+    bytes.reverse();
+    Felt::from(U256::from_le_bytes(bytes))
+}
+
+/// Returns the canonical integer representation of the specified element as 32 big-endian bytes.
+fn felt_to_be_bytes(value: Felt) -> [u8; 32] {
+    let mut bytes = value.as_int().to_le_bytes();
+    bytes.reverse();
+    bytes
+}
+
+#[inline(always)]
+fn get_bit(value: &[u64; 4], bit: usize) -> bool {
+    (value[bit / 64] >> (bit % 64)) & 1 == 1
+}
@@ -0,0 +1,241 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains parameters of the STARK curve y^2 = x^3 + x + beta defined over the
+//! [f252](math::fields::f252) field.
+//!
+//! This is the curve used by StarkWare for [Pedersen](crate::hashers::Pedersen) hashes and for
+//! ECDSA signatures; it has prime order.
+
+use math::{
+    fields::f252::{BaseElement, U256},
+    FieldElement,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Coefficient beta of the STARK curve y^2 = x^3 + x + beta.
+pub const BETA: BaseElement = BaseElement::new([
+    0xf4cdfcb99cee9e89,
+    0x609ad26c15c915c1,
+    0x150e596d72f7a8c5,
+    0x06f21413efbe40de,
+]);
+
+/// Number of points on the STARK curve; this number is prime, and thus, every point on the curve
+/// other than the point at infinity generates the entire group of points.
+pub const ORDER: U256 = U256([
+    0x1e66a241adc64d2f,
+    0xb781126dcae7b232,
+    0xffffffffffffffff,
+    0x0800000000000010,
+]);
+
+/// Generator of the STARK curve as specified by StarkWare.
+pub const GENERATOR: AffinePoint = AffinePoint::new(
+    [
+        0x3d723d8bc943cfca,
+        0xdeacfd9b0d1819e0,
+        0x7beced415a40f0c7,
+        0x01ef15c18599971b,
+    ],
+    [
+        0x2873000c36e8dc1f,
+        0xde53ecd11abe43a3,
+        0xb7be4801df46ec62,
+        0x005668060aa49730,
+    ],
+);
+
+/// Constant points of the Pedersen hash as published by StarkWare; the shift point P0 is
+/// followed by P1, P2, P3, P4 used for the low and high parts of the two inputs.
+pub const PEDERSEN_POINTS: [AffinePoint; 5] = [
+    AffinePoint::new(
+        [
+            0x551fde4050ca6804,
+            0x716b0b1022947733,
+            0x00ee1b87eb599f16,
+            0x049ee3eba8c16007,
+        ],
+        [
+            0xd0405d266e10268a,
+            0x4e621062c0e056c1,
+            0xf346d49d06ea0ed3,
+            0x03ca0cfe4b3bc6dd,
+        ],
+    ),
+    AffinePoint::new(
+        [
+            0x1080d17957ebe47b,
+            0x8fa8120b6d56eb0c,
+            0x969c748655fca9e5,
+            0x0234287dcbaffe7f,
+        ],
+        [
+            0x6ed0268ee89e5615,
+            0x940135dd7a6c94cc,
+            0x1e889527d41f4e39,
+            0x03b056f100f96fb2,
+        ],
+    ),
+    AffinePoint::new(
+        [
+            0xb7a6932dba8aa378,
+            0x99099ec1de5e3018,
+            0x3f9dab2656558f33,
+            0x04fa56f376c83db3,
+        ],
+        [
+            0x5168f4e80ff5b54d,
+            0x562761f92a7a23b4,
+            0x8113e0c0e47e4401,
+            0x03fa0984c931c9e3,
+        ],
+    ),
+    AffinePoint::new(
+        [
+            0x3aa372f0bd2d6997,
+            0x40c690c74709e90f,
+            0x764910f75b45f74b,
+            0x04ba4cc166be8dec,
+        ],
+        [
+            0x48151f27b24b219c,
+            0xcac5c59a5ce5ae7c,
+            0x4b971e46c4ede85f,
+            0x0040301cf5c1751f,
+        ],
+    ),
+    AffinePoint::new(
+        [
+            0xd36ff12c49a58202,
+            0x2ca65048d53fb325,
+            0x6e44cca8f61a63bb,
+            0x054302dcb0e6cc1c,
+        ],
+        [
+            0x879dcc77e99c2426,
+            0xce98ad783c25561a,
+            0xb348046268d8ae25,
+            0x01b77b3e37d13504,
+        ],
+    ),
+];
+
+// AFFINE POINT
+// ================================================================================================
+
+/// A point on the STARK curve in affine coordinates; the point at infinity cannot be represented.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AffinePoint {
+    pub x: BaseElement,
+    pub y: BaseElement,
+}
+
+impl AffinePoint {
+    /// Returns a point for the specified coordinates provided as little-endian 64-bit limbs.
+    pub const fn new(x: [u64; 4], y: [u64; 4]) -> Self {
+        Self {
+            x: BaseElement::new(x),
+            y: BaseElement::new(y),
+        }
+    }
+}
+
+// PROJECTIVE POINT
+// ================================================================================================
+
+/// A point on the STARK curve in Jacobian coordinates (X, Y, Z) representing the affine point
+/// (X / Z^2, Y / Z^3); the point at infinity has Z = 0.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct ProjectivePoint {
+    x: BaseElement,
+    y: BaseElement,
+    z: BaseElement,
+}
+
+impl ProjectivePoint {
+    pub const INFINITY: Self = Self {
+        x: BaseElement::ONE,
+        y: BaseElement::ONE,
+        z: BaseElement::ZERO,
+    };
+
+    pub fn is_infinity(&self) -> bool {
+        self.z == BaseElement::ZERO
+    }
+
+    /// Returns this point in affine coordinates, or None if this is the point at infinity.
+    pub fn to_affine(self) -> Option<AffinePoint> {
+        if self.is_infinity() {
+            return None;
+        }
+        let z_inv = self.z.inv();
+        let z_inv2 = z_inv.square();
+        Some(AffinePoint {
+            x: self.x * z_inv2,
+            y: self.y * z_inv2 * z_inv,
+        })
+    }
+
+    /// Doubles this point in place; uses "dbl-2007-bl" formulas for curves with a = 1.
+    pub fn double(&mut self) {
+        if self.is_infinity() || self.y == BaseElement::ZERO {
+            *self = Self::INFINITY;
+            return;
+        }
+
+        let xx = self.x.square();
+        let yy = self.y.square();
+        let yyyy = yy.square();
+        let zz = self.z.square();
+        let s = ((self.x + yy).square() - xx - yyyy).double();
+        let m = xx.double() + xx + zz.square();
+        let t = m.square() - s.double();
+
+        let y = m * (s - t) - yyyy.double().double().double();
+        let z = (self.y + self.z).square() - yy - zz;
+        self.x = t;
+        self.y = y;
+        self.z = z;
+    }
+
+    /// Adds the specified affine point to this point in place; uses "madd-2007-bl" formulas.
+    pub fn add_affine(&mut self, other: &AffinePoint) {
+        if self.is_infinity() {
+            self.x = other.x;
+            self.y = other.y;
+            self.z = BaseElement::ONE;
+            return;
+        }
+
+        let z1z1 = self.z.square();
+        let u2 = other.x * z1z1;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - self.x;
+        let r = (s2 - self.y).double();
+        if h == BaseElement::ZERO {
+            if r == BaseElement::ZERO {
+                self.double();
+            } else {
+                *self = Self::INFINITY;
+            }
+            return;
+        }
+
+        let hh = h.square();
+        let i = hh.double().double();
+        let j = h * i;
+        let v = self.x * i;
+
+        let x = r.square() - j - v.double();
+        let y = r * (v - x) - (self.y * j).double();
+        let z = (self.z + h).square() - z1z1 - hh;
+        self.x = x;
+        self.y = y;
+        self.z = z;
+    }
+}
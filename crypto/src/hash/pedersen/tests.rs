@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    felt_from_be_bytes, felt_to_be_bytes, get_bit,
+    stark_curve::{ProjectivePoint, BETA, GENERATOR, ORDER, PEDERSEN_POINTS},
+    ByteDigest, ElementHasher, Hasher, Pedersen,
+};
+use math::{
+    fields::{f64::BaseElement, QuadExtension},
+    FieldElement,
+};
+use rand_utils::rand_array;
+
+type PedersenF64 = Pedersen<BaseElement>;
+
+#[test]
+fn stark_curve_points() {
+    for point in PEDERSEN_POINTS.iter().chain([&GENERATOR]) {
+        let rhs = point.x.square() * point.x + point.x + BETA;
+        assert_eq!(point.y.square(), rhs);
+    }
+
+    // the generator multiplied by the order of the curve is the point at infinity
+    let mut result = ProjectivePoint::INFINITY;
+    for bit in (0..252).rev() {
+        result.double();
+        if get_bit(&ORDER.0, bit) {
+            result.add_affine(&GENERATOR);
+        }
+    }
+    assert!(result.is_infinity());
+}
+
+#[test]
+fn felt_byte_conversions() {
+    let value = from_hex("03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb");
+    assert_eq!(value, felt_to_be_bytes(felt_from_be_bytes(&value)));
+
+    // 2^256 - 1 should be reduced modulo the field modulus
+    let expected = from_hex("07fffffffffffdf0ffffffffffffffffffffffffffffffffffffffffffffffe0");
+    assert_eq!(expected, felt_to_be_bytes(felt_from_be_bytes(&[0xff; 32])));
+}
+
+#[test]
+fn pedersen_merge() {
+    // test vector from StarkWare reference implementation
+    let a = from_hex("03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb");
+    let b = from_hex("0208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a");
+    let expected = from_hex("030e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662");
+
+    let result = PedersenF64::merge(&[ByteDigest(a), ByteDigest(b)]);
+    assert_eq!(expected, result.0);
+
+    // hash of two zeros is the x coordinate of the shift point
+    let result = PedersenF64::merge(&[ByteDigest([0; 32]), ByteDigest([0; 32])]);
+    assert_eq!(felt_to_be_bytes(PEDERSEN_POINTS[0].x), result.0);
+}
+
+#[test]
+fn pedersen_hash_elements() {
+    // pedersen(pedersen(pedersen(pedersen(0, 1), 2), 3), 3), as computed by Cairo's hash_state
+    let elements = [
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+    ];
+    let expected = from_hex("00f9d95fbf356fbeda26538c92f7040abe51bf142350f73c9ee5ba7c660bae71");
+    assert_eq!(expected, PedersenF64::hash_elements(&elements).0);
+
+    // extension field elements are hashed as sequences of their base field coefficients
+    let e: [BaseElement; 4] = rand_array();
+    let ext = [
+        QuadExtension::new(e[0], e[1]),
+        QuadExtension::new(e[2], e[3]),
+    ];
+    assert_eq!(
+        PedersenF64::hash_elements(&e),
+        PedersenF64::hash_elements(&ext)
+    );
+}
+
+#[test]
+fn pedersen_hash() {
+    // pedersen(pedersen(0, 0x616263), 3)
+    let expected = from_hex("046d214854ffe9163df143ae46fc5bf458968bd27d5815fd005c0a332e5bdb6a");
+    assert_eq!(expected, PedersenF64::hash(b"abc").0);
+
+    // leading zero bytes should result in a different hash
+    assert_ne!(PedersenF64::hash(b"abc"), PedersenF64::hash(b"\0abc"));
+}
+
+#[test]
+fn pedersen_merge_with_int() {
+    let seed = PedersenF64::hash(b"seed");
+    let mut value = [0; 32];
+    value[24..].copy_from_slice(&7_u64.to_be_bytes());
+
+    let expected = PedersenF64::merge(&[seed, ByteDigest(value)]);
+    assert_eq!(expected, PedersenF64::merge_with_int(seed, 7));
+
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+    assert_ne!(
+        PedersenF64::hash_elements(&e1),
+        PedersenF64::hash_elements(&e2)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn from_hex(value: &str) -> [u8; 32] {
+    let mut result = [0; 32];
+    for (i, byte) in result.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+    }
+    result
+}
@@ -0,0 +1,12 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher, SpongePermutation};
+
+mod poseidon62_248;
+pub use poseidon62_248::Poseidon62_248;
+
+mod poseidon64_256;
+pub use poseidon64_256::Poseidon64_256;
@@ -0,0 +1,960 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher, SpongePermutation};
+use core::convert::TryInto;
+use core::ops::Range;
+use math::{fields::f62::BaseElement, FieldElement, StarkField};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 93 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 4 through 11.
+const RATE_RANGE: Range<usize> = 4..12;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 4..8;
+const INPUT2_RANGE: Range<usize> = 8..12;
+
+/// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+const CAPACITY_RANGE: Range<usize> = 0..4;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 31 bytes.
+///
+/// The digest is returned from state elements 4, 5, 6, and 7 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 4..8;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of full and partial rounds is set to 8 and 22 respectively to target 124-bit
+/// security level; computed using the script referenced in section 4 of
+/// <https://eprint.iacr.org/2019/458.pdf>, which includes the recommended security margin.
+const NUM_FULL_ROUNDS: usize = 8;
+const NUM_PARTIAL_ROUNDS: usize = 22;
+const NUM_ROUNDS: usize = NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS;
+
+/// Partial rounds are applied after the first half of the full rounds.
+const PARTIAL_ROUNDS: Range<usize> = NUM_FULL_ROUNDS / 2..NUM_FULL_ROUNDS / 2 + NUM_PARTIAL_ROUNDS;
+
+/// S-Box power; the constant is defined for tests only because the exponentiation in the code is
+/// unrolled for efficiency reasons.
+#[cfg(test)]
+const ALPHA: u64 = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Poseidon hash function with 248-bit output.
+///
+/// The hash function is implemented according to the Poseidon
+/// [specifications](https://eprint.iacr.org/2019/458.pdf) with the following exceptions:
+/// * When hashing a sequence of elements, we do not use the padding scheme from the
+///   specifications. Instead, we initialize the first capacity element to the number of elements
+///   to be hashed, and pad the sequence with Fp(0) elements only. This ensures consistency of
+///   hash outputs between different hashing methods (see section below). However, it also means
+///   that our instantiation of Poseidon cannot be used in a stream mode as the number of
+///   elements to be hashed must be known upfront.
+/// * We use the first 4 elements of the state for capacity and the remaining 8 elements for
+///   rate. The output of the hash function comes from the first four elements of the rate
+///   portion of the state (elements 4, 5, 6, and 7). This is the same layout as used by
+///   [Poseidon64_256](super::Poseidon64_256).
+///
+/// Round constants and the MDS matrix are generated using the Grain LFSR procedure described in
+/// appendix F of the specifications, and thus, the permutation matches the reference
+/// implementation instantiated with the parameters below.
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 62-bit prime field with modulus 2^62 - 111 * 2^39 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * Number of full rounds: 8.
+/// * Number of partial rounds: 22.
+/// * S-Box degree: 7.
+///
+/// The above parameters target 124-bit security level. The digest consists of four field elements
+/// and it can be serialized into 31 bytes (248 bits).
+///
+/// ## Hash output consistency
+/// Functions [hash_elements()](Poseidon62_248::hash_elements), [merge()](Poseidon62_248::merge),
+/// and [merge_with_int()](Poseidon62_248::merge_with_int) are internally consistent. That is,
+/// computing a hash for the same set of elements using these functions will always produce the
+/// same result. For example, merging two digests using [merge()](Poseidon62_248::merge) will
+/// produce the same result as hashing 8 elements which make up these digests using
+/// [hash_elements()](Poseidon62_248::hash_elements) function.
+///
+/// However, [hash()](Poseidon62_248::hash) function is not consistent with functions mentioned
+/// above. For example, if we take two field elements, serialize them to bytes and hash them using
+/// [hash()](Poseidon62_248::hash), the result will differ from the result obtained by hashing
+/// these elements directly using [hash_elements()](Poseidon62_248::hash_elements) function. The
+/// reason for this difference is that [hash()](Poseidon62_248::hash) function needs to be able to
+/// handle arbitrary binary strings, which may or may not encode valid field elements - and thus,
+/// deserialization procedure used by this function is different from the procedure used to
+/// deserialize valid field elements.
+pub struct Poseidon62_248();
+
+impl Hasher for Poseidon62_248 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 124;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % 7 == 0 {
+            bytes.len() / 7
+        } else {
+            bytes.len() / 7 + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(num_elements as u64);
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state. we use 7-byte chunks because
+        // every 7-byte chunk is guaranteed to map to some field element.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for (chunk_idx, chunk) in bytes.chunks(7).enumerate() {
+            if chunk_idx < num_elements - 1 {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state; if the rate is filled up, apply the Poseidon permutation and start absorbing
+            // again from zero index.
+            state[RATE_RANGE.start + i] += BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Poseidon permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the rate portion of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements), and set the first capacity element to 8 (the number of elements to
+        // be hashed).
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+        state[CAPACITY_RANGE.start] = BaseElement::new(RATE_WIDTH as u64);
+
+        // apply the Poseidon permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and set the first capacity element to 5 (the number of elements to be hashed).
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and set the first capacity element
+        //   to 6.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 1);
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 2);
+        }
+
+        // apply the Poseidon permutation and return the first four elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Poseidon62_248 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(elements.len() as u64);
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the Poseidon permutation and start absorbing again; repeat until all
+        // elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Poseidon permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the rate portion of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl SpongePermutation for Poseidon62_248 {
+    type State = [BaseElement; STATE_WIDTH];
+
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    fn init_state() -> Self::State {
+        [BaseElement::ZERO; STATE_WIDTH]
+    }
+
+    fn permute(state: &mut Self::State) {
+        Self::apply_permutation(state)
+    }
+
+    fn digest_as_elements(digest: &Self::Digest) -> &[Self::BaseField] {
+        digest.as_elements()
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Poseidon62_248 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of full rounds is set to 8 to target 124-bit security level.
+    pub const NUM_FULL_ROUNDS: usize = NUM_FULL_ROUNDS;
+
+    /// The number of partial rounds is set to 22 to target 124-bit security level.
+    pub const NUM_PARTIAL_ROUNDS: usize = NUM_PARTIAL_ROUNDS;
+
+    /// The total number of rounds in the permutation.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Indexes of partial rounds; partial rounds are applied after the first 4 full rounds.
+    pub const PARTIAL_ROUNDS: Range<usize> = PARTIAL_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 93 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 4 through 11 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 4, 5, 6, and 7.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// MDS matrix used for computing the linear layer in a Poseidon round.
+    pub const MDS: [[BaseElement; STATE_WIDTH]; STATE_WIDTH] = MDS;
+
+    /// Round constants added to the hasher state at the start of a Poseidon round.
+    pub const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = ARK;
+
+    // POSEIDON PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Poseidon permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+    }
+
+    /// Poseidon round function; the S-Box is applied to the entire state in full rounds, and to
+    /// the first element of the state in partial rounds.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::add_constants(state, &ARK[round]);
+        if PARTIAL_ROUNDS.contains(&round) {
+            state[0] = exp7(state[0]);
+        } else {
+            Self::apply_sbox(state);
+        }
+        Self::apply_mds(state);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    #[inline(always)]
+    fn apply_mds(state: &mut [BaseElement; STATE_WIDTH]) {
+        let mut result = [BaseElement::ZERO; STATE_WIDTH];
+        result.iter_mut().zip(MDS).for_each(|(r, mds_row)| {
+            state.iter().zip(mds_row).for_each(|(&s, m)| {
+                *r += m * s;
+            });
+        });
+        *state = result
+    }
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], ark: &[BaseElement; STATE_WIDTH]) {
+        state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
+    }
+
+    #[inline(always)]
+    fn apply_sbox(state: &mut [BaseElement; STATE_WIDTH]) {
+        state.iter_mut().for_each(|v| *v = exp7(*v));
+    }
+}
+
+/// Computes x^7 using 4 multiplications.
+#[inline(always)]
+fn exp7(x: BaseElement) -> BaseElement {
+    let x2 = x.square();
+    let x4 = x2.square();
+    let x3 = x2 * x;
+    x3 * x4
+}
+
+// MDS
+// ================================================================================================
+/// Poseidon MDS matrix; a Cauchy matrix generated using the Grain LFSR as described in appendix F
+/// of <https://eprint.iacr.org/2019/458.pdf>
+const MDS: [[BaseElement; STATE_WIDTH]; STATE_WIDTH] = [
+    [
+        BaseElement::new(167905991089388463),
+        BaseElement::new(1859554342170345598),
+        BaseElement::new(2872934228215068234),
+        BaseElement::new(1525864089469856218),
+        BaseElement::new(4157812316391376265),
+        BaseElement::new(2541614444926402446),
+        BaseElement::new(194419895747107204),
+        BaseElement::new(1698665575882970434),
+        BaseElement::new(670446244870535928),
+        BaseElement::new(6330388498501414),
+        BaseElement::new(2707028984747444798),
+        BaseElement::new(3739696088188666145),
+    ],
+    [
+        BaseElement::new(4482051537300291100),
+        BaseElement::new(1740832386071449771),
+        BaseElement::new(3672256104438767789),
+        BaseElement::new(1354197076052967359),
+        BaseElement::new(1222980809061458257),
+        BaseElement::new(18570971285788053),
+        BaseElement::new(3660950567959866573),
+        BaseElement::new(3011588268187253773),
+        BaseElement::new(3593264614133335145),
+        BaseElement::new(2091439324874281290),
+        BaseElement::new(2479234933430753979),
+        BaseElement::new(916173907696997038),
+    ],
+    [
+        BaseElement::new(4019541404283600873),
+        BaseElement::new(2158735585578785526),
+        BaseElement::new(4096723385952634064),
+        BaseElement::new(4374344830810670071),
+        BaseElement::new(3401922893591811417),
+        BaseElement::new(91030146343469513),
+        BaseElement::new(2228536794914692960),
+        BaseElement::new(1064079527393774233),
+        BaseElement::new(1013216459338595631),
+        BaseElement::new(1126329044127169003),
+        BaseElement::new(3408377251895429409),
+        BaseElement::new(1709103887405679614),
+    ],
+    [
+        BaseElement::new(2254572762858886973),
+        BaseElement::new(3063671066847637901),
+        BaseElement::new(2837549366415904873),
+        BaseElement::new(3267387834473054212),
+        BaseElement::new(3798676902539760322),
+        BaseElement::new(1279443960068637878),
+        BaseElement::new(2927324346238722286),
+        BaseElement::new(4469686921369670180),
+        BaseElement::new(3861021266721671024),
+        BaseElement::new(695550350572337033),
+        BaseElement::new(242821226172481912),
+        BaseElement::new(436173816531891044),
+    ],
+    [
+        BaseElement::new(272010638906893773),
+        BaseElement::new(4170715774711304845),
+        BaseElement::new(2843619864871538064),
+        BaseElement::new(1086662210530354832),
+        BaseElement::new(2933302917791772021),
+        BaseElement::new(2566011850396606489),
+        BaseElement::new(3192313817933071709),
+        BaseElement::new(1961658889549915145),
+        BaseElement::new(921505353785384606),
+        BaseElement::new(4391111874561580837),
+        BaseElement::new(646234684063424757),
+        BaseElement::new(2436910869844911617),
+    ],
+    [
+        BaseElement::new(4294667077539388201),
+        BaseElement::new(14050875986389376),
+        BaseElement::new(2661979965730227287),
+        BaseElement::new(4524203187542571357),
+        BaseElement::new(1608672512310315592),
+        BaseElement::new(4334924389059595736),
+        BaseElement::new(428181155418412037),
+        BaseElement::new(535254886151988920),
+        BaseElement::new(3492236995565063653),
+        BaseElement::new(1514298954187003648),
+        BaseElement::new(2929233414374541790),
+        BaseElement::new(1669690788326923701),
+    ],
+    [
+        BaseElement::new(4105104023854868881),
+        BaseElement::new(609323821839732228),
+        BaseElement::new(2444722212306735623),
+        BaseElement::new(385469397207331837),
+        BaseElement::new(2944356678996968420),
+        BaseElement::new(4570339408290695280),
+        BaseElement::new(2722004786966663820),
+        BaseElement::new(4545492000248732945),
+        BaseElement::new(1833985481906581708),
+        BaseElement::new(1804473875419189397),
+        BaseElement::new(589002391660568538),
+        BaseElement::new(1981111148832361099),
+    ],
+    [
+        BaseElement::new(696546759427214081),
+        BaseElement::new(2335486313073240009),
+        BaseElement::new(1139205069822092642),
+        BaseElement::new(981603625840805959),
+        BaseElement::new(1959721846801679528),
+        BaseElement::new(2472372882229179871),
+        BaseElement::new(4278969393328277256),
+        BaseElement::new(3449832916193903904),
+        BaseElement::new(3249519009362365197),
+        BaseElement::new(59433794733283884),
+        BaseElement::new(1870811286767563653),
+        BaseElement::new(4578581554412982651),
+    ],
+    [
+        BaseElement::new(3838916617511122016),
+        BaseElement::new(897483742674830564),
+        BaseElement::new(2668446459304260529),
+        BaseElement::new(597350997144774653),
+        BaseElement::new(4519624635214075778),
+        BaseElement::new(852661789912181917),
+        BaseElement::new(3406302468864706060),
+        BaseElement::new(82617446741739480),
+        BaseElement::new(4046767903519800269),
+        BaseElement::new(2660230181640752763),
+        BaseElement::new(1675492092017246576),
+        BaseElement::new(3701434921122253899),
+    ],
+    [
+        BaseElement::new(1530880114266911893),
+        BaseElement::new(1547279408728508746),
+        BaseElement::new(2554617466494662597),
+        BaseElement::new(1446577824825236833),
+        BaseElement::new(3801141361542538809),
+        BaseElement::new(639451189465037084),
+        BaseElement::new(1055914418837367200),
+        BaseElement::new(2938577767630611467),
+        BaseElement::new(832064826717652756),
+        BaseElement::new(3968262631779222764),
+        BaseElement::new(3713793412588889928),
+        BaseElement::new(909750882483135102),
+    ],
+    [
+        BaseElement::new(3757617834132056570),
+        BaseElement::new(2195581241540535898),
+        BaseElement::new(2146300459936434928),
+        BaseElement::new(3575317711791574784),
+        BaseElement::new(4370902922924365923),
+        BaseElement::new(4201884594118557279),
+        BaseElement::new(2806255912377945785),
+        BaseElement::new(2553370567357631918),
+        BaseElement::new(4104069780493524443),
+        BaseElement::new(787288063245948035),
+        BaseElement::new(2816580308443739),
+        BaseElement::new(3124084154465863256),
+    ],
+    [
+        BaseElement::new(3977416500777892019),
+        BaseElement::new(3972630805227165632),
+        BaseElement::new(683183331774150537),
+        BaseElement::new(888709942730726604),
+        BaseElement::new(4544662077593948065),
+        BaseElement::new(2881058548021347032),
+        BaseElement::new(1446626539577885732),
+        BaseElement::new(2902894133857697671),
+        BaseElement::new(3785547344398574024),
+        BaseElement::new(4225055681618542231),
+        BaseElement::new(3127770738988714632),
+        BaseElement::new(2908431915386731445),
+    ],
+];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Poseidon round constants; generated using the Grain LFSR as described in appendix F of
+/// <https://eprint.iacr.org/2019/458.pdf>
+const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(2354616197942446091),
+        BaseElement::new(145688281565523360),
+        BaseElement::new(4267063410294891928),
+        BaseElement::new(685641645060862643),
+        BaseElement::new(1787988639425127182),
+        BaseElement::new(2491631775503134001),
+        BaseElement::new(3736069468025488724),
+        BaseElement::new(827860697199951000),
+        BaseElement::new(3776960720228529598),
+        BaseElement::new(3913456560374498593),
+        BaseElement::new(3642344501239643266),
+        BaseElement::new(816112577199216337),
+    ],
+    [
+        BaseElement::new(3991283380217932154),
+        BaseElement::new(162909022627851327),
+        BaseElement::new(19561862488250914),
+        BaseElement::new(1127021317355293383),
+        BaseElement::new(2527339211923373970),
+        BaseElement::new(3792931575227842471),
+        BaseElement::new(1624188958246665440),
+        BaseElement::new(1253998829193541216),
+        BaseElement::new(1445530467556800856),
+        BaseElement::new(4139232112593178030),
+        BaseElement::new(1659063451376113352),
+        BaseElement::new(253267490385223696),
+    ],
+    [
+        BaseElement::new(1800085243220821330),
+        BaseElement::new(2524663563689442602),
+        BaseElement::new(3323331701743164683),
+        BaseElement::new(4051516192146448470),
+        BaseElement::new(345223664519088827),
+        BaseElement::new(935103862578406665),
+        BaseElement::new(1154758108488711103),
+        BaseElement::new(751719461832445883),
+        BaseElement::new(2695936286839082941),
+        BaseElement::new(3450296026901739423),
+        BaseElement::new(2044801891702983101),
+        BaseElement::new(2573124817937077062),
+    ],
+    [
+        BaseElement::new(2217953503428904345),
+        BaseElement::new(3293499727501792036),
+        BaseElement::new(1041855602571469383),
+        BaseElement::new(2079361443282685134),
+        BaseElement::new(2295214538580303252),
+        BaseElement::new(3208848372390781855),
+        BaseElement::new(3604403357241997352),
+        BaseElement::new(2350689998392386345),
+        BaseElement::new(3252244621941544041),
+        BaseElement::new(2073871216243786582),
+        BaseElement::new(1016875234950240988),
+        BaseElement::new(3838391634471478391),
+    ],
+    [
+        BaseElement::new(967931472102213263),
+        BaseElement::new(4176011259373190256),
+        BaseElement::new(1801566832593673531),
+        BaseElement::new(977472377734532877),
+        BaseElement::new(2506171807872742886),
+        BaseElement::new(1983167679945787961),
+        BaseElement::new(1188266037137942469),
+        BaseElement::new(3412447126340390393),
+        BaseElement::new(548533365219862228),
+        BaseElement::new(4511718470054354092),
+        BaseElement::new(804828353183957387),
+        BaseElement::new(288525470681412070),
+    ],
+    [
+        BaseElement::new(2300447208411256526),
+        BaseElement::new(807059375837700121),
+        BaseElement::new(3818958360062833011),
+        BaseElement::new(2084112842730084310),
+        BaseElement::new(4157583124250580831),
+        BaseElement::new(334595315631195976),
+        BaseElement::new(1013029669904182026),
+        BaseElement::new(1312632936307625458),
+        BaseElement::new(2160461754473179720),
+        BaseElement::new(4272485985812869771),
+        BaseElement::new(3498665116172188054),
+        BaseElement::new(904546472122064300),
+    ],
+    [
+        BaseElement::new(813260686173871997),
+        BaseElement::new(1794934218386778340),
+        BaseElement::new(2263033318735746515),
+        BaseElement::new(2049123541596349839),
+        BaseElement::new(3844242671005163101),
+        BaseElement::new(1647836882782055050),
+        BaseElement::new(3978777857629550118),
+        BaseElement::new(3208253551037582731),
+        BaseElement::new(4055297974023858349),
+        BaseElement::new(1160898919113270610),
+        BaseElement::new(3617037185959100132),
+        BaseElement::new(4258625366120629963),
+    ],
+    [
+        BaseElement::new(2364372184390813056),
+        BaseElement::new(923234952002609468),
+        BaseElement::new(4065570738232420864),
+        BaseElement::new(1052057183108915777),
+        BaseElement::new(1514845690010310633),
+        BaseElement::new(4545863330222379472),
+        BaseElement::new(2836559309809246976),
+        BaseElement::new(1864551684790064988),
+        BaseElement::new(165436136483506267),
+        BaseElement::new(1998220705054676256),
+        BaseElement::new(2668794921039700496),
+        BaseElement::new(1257253660885908055),
+    ],
+    [
+        BaseElement::new(1514171536303505890),
+        BaseElement::new(329788054424225589),
+        BaseElement::new(3291028631297662027),
+        BaseElement::new(2915253308481645919),
+        BaseElement::new(1678059338351188544),
+        BaseElement::new(1406771230108895719),
+        BaseElement::new(2388747395802161434),
+        BaseElement::new(3120713119653408745),
+        BaseElement::new(415687243234533037),
+        BaseElement::new(3909332525612824012),
+        BaseElement::new(71832709442512431),
+        BaseElement::new(2426030927529229336),
+    ],
+    [
+        BaseElement::new(2871723845854341882),
+        BaseElement::new(751800138408913229),
+        BaseElement::new(4368421674825816810),
+        BaseElement::new(197909207016278626),
+        BaseElement::new(197989967046111617),
+        BaseElement::new(646216342252964376),
+        BaseElement::new(1222862952899490443),
+        BaseElement::new(293846321157462428),
+        BaseElement::new(3101229595115291360),
+        BaseElement::new(3806564949543765476),
+        BaseElement::new(3571231927774959768),
+        BaseElement::new(2071522597112302223),
+    ],
+    [
+        BaseElement::new(1030989427430754518),
+        BaseElement::new(1307127409356202549),
+        BaseElement::new(4439632783970263715),
+        BaseElement::new(3759432476673154972),
+        BaseElement::new(2375026287084743457),
+        BaseElement::new(4414444468010324737),
+        BaseElement::new(233116922515742128),
+        BaseElement::new(3536299455005383004),
+        BaseElement::new(4180689022980628168),
+        BaseElement::new(2915628986889461555),
+        BaseElement::new(127373105421017418),
+        BaseElement::new(3838717286673814423),
+    ],
+    [
+        BaseElement::new(1703308429219131085),
+        BaseElement::new(2342066764511081319),
+        BaseElement::new(2315987198214346646),
+        BaseElement::new(660598948683196314),
+        BaseElement::new(4051069661354080493),
+        BaseElement::new(2127707058152147929),
+        BaseElement::new(3082067653699444794),
+        BaseElement::new(522914027766176497),
+        BaseElement::new(3683348038357598463),
+        BaseElement::new(3955836390830187364),
+        BaseElement::new(3366563627553070614),
+        BaseElement::new(716993407199043666),
+    ],
+    [
+        BaseElement::new(2112170256820715655),
+        BaseElement::new(2906944090875001355),
+        BaseElement::new(3771687099811260030),
+        BaseElement::new(4260909738093552660),
+        BaseElement::new(361069995052069435),
+        BaseElement::new(2988604499720078382),
+        BaseElement::new(4516665521011618137),
+        BaseElement::new(2490979930371492399),
+        BaseElement::new(4485239041431166006),
+        BaseElement::new(2971784781283574437),
+        BaseElement::new(325359768171624791),
+        BaseElement::new(2977050988532592067),
+    ],
+    [
+        BaseElement::new(1859345065031275415),
+        BaseElement::new(3415759921560729381),
+        BaseElement::new(4310399132442815239),
+        BaseElement::new(1323950256806263545),
+        BaseElement::new(582395997185438486),
+        BaseElement::new(2048638181196674602),
+        BaseElement::new(494586858359712877),
+        BaseElement::new(2898231938980164830),
+        BaseElement::new(834760844650291680),
+        BaseElement::new(912379751657637539),
+        BaseElement::new(3105472429309523643),
+        BaseElement::new(3572692690495103147),
+    ],
+    [
+        BaseElement::new(2938523912174871483),
+        BaseElement::new(4532999428715327426),
+        BaseElement::new(328365883822941438),
+        BaseElement::new(238399608889565689),
+        BaseElement::new(486903937496911237),
+        BaseElement::new(3611832229424746160),
+        BaseElement::new(1627821521052338774),
+        BaseElement::new(1993626539068939766),
+        BaseElement::new(4176910728684434),
+        BaseElement::new(941091491418186183),
+        BaseElement::new(3067480528350315028),
+        BaseElement::new(2110284896950336334),
+    ],
+    [
+        BaseElement::new(2942499632484526913),
+        BaseElement::new(2275817691406058485),
+        BaseElement::new(463808939798849407),
+        BaseElement::new(2003672769250125937),
+        BaseElement::new(1843716215208925252),
+        BaseElement::new(3453745195804700733),
+        BaseElement::new(20080272011741433),
+        BaseElement::new(1105741776251780422),
+        BaseElement::new(3972240860905881286),
+        BaseElement::new(1751532327635424886),
+        BaseElement::new(2785550448983920942),
+        BaseElement::new(883468902390324710),
+    ],
+    [
+        BaseElement::new(1514169321190756877),
+        BaseElement::new(3386959529270444018),
+        BaseElement::new(1700341536951956766),
+        BaseElement::new(3238084230876449943),
+        BaseElement::new(4207629065342718242),
+        BaseElement::new(967131457147749820),
+        BaseElement::new(3869451175205145208),
+        BaseElement::new(2220953615029499823),
+        BaseElement::new(2902904464708168381),
+        BaseElement::new(614198703463719202),
+        BaseElement::new(1856195378393844622),
+        BaseElement::new(3531794054038982376),
+    ],
+    [
+        BaseElement::new(1388541435684734492),
+        BaseElement::new(4116366817407277813),
+        BaseElement::new(1156413838595682591),
+        BaseElement::new(1183749818522428494),
+        BaseElement::new(2409819452675654318),
+        BaseElement::new(2328627252101015947),
+        BaseElement::new(929014532283639518),
+        BaseElement::new(1300551458210718105),
+        BaseElement::new(2016599888257745425),
+        BaseElement::new(2272229698870005077),
+        BaseElement::new(2682635005594804456),
+        BaseElement::new(4424838162056711063),
+    ],
+    [
+        BaseElement::new(871111571303039082),
+        BaseElement::new(1386521516586794012),
+        BaseElement::new(1979321692586407991),
+        BaseElement::new(1524313256094738598),
+        BaseElement::new(1888619610288380018),
+        BaseElement::new(1839844036304216048),
+        BaseElement::new(1719238415170910136),
+        BaseElement::new(2876737456325464346),
+        BaseElement::new(2751311166621590979),
+        BaseElement::new(3235522423975863299),
+        BaseElement::new(2374853184715172123),
+        BaseElement::new(748541111594441221),
+    ],
+    [
+        BaseElement::new(3095462467893364010),
+        BaseElement::new(2931392138576354654),
+        BaseElement::new(1907355741575022522),
+        BaseElement::new(3744990394660123362),
+        BaseElement::new(2961710253722153872),
+        BaseElement::new(1147991003291059221),
+        BaseElement::new(2109698464271112963),
+        BaseElement::new(2242894115677031556),
+        BaseElement::new(3000764375768120954),
+        BaseElement::new(2933248272766495983),
+        BaseElement::new(763831682973279580),
+        BaseElement::new(2504481981909030102),
+    ],
+    [
+        BaseElement::new(3766698316599910268),
+        BaseElement::new(3514974666802687163),
+        BaseElement::new(1802309534579184350),
+        BaseElement::new(152162174389936057),
+        BaseElement::new(4388419847588759319),
+        BaseElement::new(1728685464527317565),
+        BaseElement::new(1245216998073360104),
+        BaseElement::new(3806007771449679688),
+        BaseElement::new(1811434865915162097),
+        BaseElement::new(2621827551453622263),
+        BaseElement::new(1990235917841220884),
+        BaseElement::new(2415532023031074312),
+    ],
+    [
+        BaseElement::new(960546242812624167),
+        BaseElement::new(4315007358378454308),
+        BaseElement::new(2716395097205218773),
+        BaseElement::new(3368284680283091555),
+        BaseElement::new(1851849901936283657),
+        BaseElement::new(3099276501987753215),
+        BaseElement::new(111665095421721942),
+        BaseElement::new(3738970447860384261),
+        BaseElement::new(3967471053571818930),
+        BaseElement::new(3622038305562074485),
+        BaseElement::new(4181205311487165389),
+        BaseElement::new(4365677625518636229),
+    ],
+    [
+        BaseElement::new(1013181339171330942),
+        BaseElement::new(1046823069445420442),
+        BaseElement::new(4243837952611953039),
+        BaseElement::new(3380969521723469113),
+        BaseElement::new(348928075217634751),
+        BaseElement::new(1148877937719447147),
+        BaseElement::new(3956543446333582307),
+        BaseElement::new(1702454412239076787),
+        BaseElement::new(509482761232042767),
+        BaseElement::new(1552349944757498979),
+        BaseElement::new(4374526289300296066),
+        BaseElement::new(4530962128527082138),
+    ],
+    [
+        BaseElement::new(1067611580978082542),
+        BaseElement::new(4602215708108549133),
+        BaseElement::new(1947589989383457341),
+        BaseElement::new(4294547392143615290),
+        BaseElement::new(825563545824426389),
+        BaseElement::new(4408726562226829873),
+        BaseElement::new(4295864576158512154),
+        BaseElement::new(4456244054421062241),
+        BaseElement::new(213487778390971889),
+        BaseElement::new(438611891678329103),
+        BaseElement::new(3365919388823299158),
+        BaseElement::new(3848438393461202876),
+    ],
+    [
+        BaseElement::new(3573016647152495643),
+        BaseElement::new(2918084117178082870),
+        BaseElement::new(2026924581265268916),
+        BaseElement::new(2670035620186247874),
+        BaseElement::new(2391796696938277489),
+        BaseElement::new(3581726367395110875),
+        BaseElement::new(117725110654879681),
+        BaseElement::new(3574528882492229023),
+        BaseElement::new(3772090236367420951),
+        BaseElement::new(2791710225626079360),
+        BaseElement::new(1715287943204592190),
+        BaseElement::new(3947136460704679560),
+    ],
+    [
+        BaseElement::new(4351281980136750569),
+        BaseElement::new(1494491497151551603),
+        BaseElement::new(2057559963280441656),
+        BaseElement::new(2946000313620770610),
+        BaseElement::new(25408849400416298),
+        BaseElement::new(1885110679304506033),
+        BaseElement::new(3131100955053354617),
+        BaseElement::new(2735947839368580652),
+        BaseElement::new(1379367148457359118),
+        BaseElement::new(3118945666984497900),
+        BaseElement::new(597964636368549596),
+        BaseElement::new(107190999267422908),
+    ],
+    [
+        BaseElement::new(3985888339875714659),
+        BaseElement::new(245471739168262783),
+        BaseElement::new(2340137282925041318),
+        BaseElement::new(280112853401985751),
+        BaseElement::new(2331783893134066225),
+        BaseElement::new(4148941682876415628),
+        BaseElement::new(2244635085487395931),
+        BaseElement::new(1356947015031545438),
+        BaseElement::new(3808881944705911990),
+        BaseElement::new(1700787301191065654),
+        BaseElement::new(2292647092091154112),
+        BaseElement::new(2000678991993254105),
+    ],
+    [
+        BaseElement::new(4017215598728838322),
+        BaseElement::new(3688334431996163043),
+        BaseElement::new(2881993246094038458),
+        BaseElement::new(2780361266929653120),
+        BaseElement::new(2136107563628467325),
+        BaseElement::new(1112034657281330408),
+        BaseElement::new(1382632593247825747),
+        BaseElement::new(3022735899396705782),
+        BaseElement::new(4320203482199957988),
+        BaseElement::new(3018799187309047771),
+        BaseElement::new(525457298236081072),
+        BaseElement::new(1031428731710919388),
+    ],
+    [
+        BaseElement::new(2501828411692446504),
+        BaseElement::new(3757824228468517509),
+        BaseElement::new(219702589755049069),
+        BaseElement::new(1439682430483679751),
+        BaseElement::new(2581970490398708734),
+        BaseElement::new(833015416747710932),
+        BaseElement::new(2198276218782265849),
+        BaseElement::new(3955548637581050028),
+        BaseElement::new(3168092675800456819),
+        BaseElement::new(3706729906059528846),
+        BaseElement::new(2174229574736642399),
+        BaseElement::new(4040244232857660278),
+    ],
+    [
+        BaseElement::new(4585237891746048943),
+        BaseElement::new(1946246780626531402),
+        BaseElement::new(626813485817047383),
+        BaseElement::new(2305241552845234986),
+        BaseElement::new(472164456678276572),
+        BaseElement::new(4535375145600081705),
+        BaseElement::new(3331891734511299032),
+        BaseElement::new(26262903494457793),
+        BaseElement::new(2363188046313545414),
+        BaseElement::new(362722398395738073),
+        BaseElement::new(2761412665040604244),
+        BaseElement::new(970507667479938037),
+    ],
+];
@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Poseidon62_248, ALPHA,
+    STATE_WIDTH,
+};
+use core::convert::TryInto;
+use math::StarkField;
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    Poseidon62_248::apply_sbox(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] = [
+        BaseElement::new(0),
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+        BaseElement::new(7),
+        BaseElement::new(8),
+        BaseElement::new(9),
+        BaseElement::new(10),
+        BaseElement::new(11),
+    ];
+
+    Poseidon62_248::apply_permutation(&mut state);
+
+    // expected values are obtained by executing the reference implementation of the permutation
+    // instantiated with the same parameters
+    let expected = vec![
+        BaseElement::new(1575106122155333095),
+        BaseElement::new(190019241947934690),
+        BaseElement::new(1289741684738094431),
+        BaseElement::new(1107881050546625708),
+        BaseElement::new(1410329611323354498),
+        BaseElement::new(619735373136335849),
+        BaseElement::new(3526955942322128349),
+        BaseElement::new(1989584523689267719),
+        BaseElement::new(3878071956947353625),
+        BaseElement::new(50579663336159900),
+        BaseElement::new(2378582567063113102),
+        BaseElement::new(1722251966890457320),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Poseidon62_248::merge(&digests);
+    let h_result = Poseidon62_248::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Poseidon62_248::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Poseidon62_248::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Poseidon62_248::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Poseidon62_248::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Poseidon62_248::hash(&[1_u8, 2, 3]);
+    let r2 = Poseidon62_248::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Poseidon62_248::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Poseidon62_248::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Poseidon62_248::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Poseidon62_248::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Poseidon62_248::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Poseidon62_248::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with inputs spanning more than one permutation
+    let r1 = Poseidon62_248::hash(&[7_u8; 60]);
+    let r2 = Poseidon62_248::hash(&[[7_u8; 60].as_slice(), &[0]].concat());
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Poseidon62_248::hash_elements(&e1);
+    let r2 = Poseidon62_248::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
@@ -31,17 +31,21 @@ impl ElementDigest {
 }
 
 impl Digest for ElementDigest {
-    fn as_bytes(&self) -> [u8; 32] {
+    const SIZE: usize = 31;
+
+    type Bytes = [u8; 31];
+
+    fn as_bytes(&self) -> [u8; 31] {
         let v1 = self.0[0].as_int();
         let v2 = self.0[1].as_int();
         let v3 = self.0[2].as_int();
         let v4 = self.0[3].as_int();
 
-        let mut result = [0; 32];
+        let mut result = [0; 31];
         result[..8].copy_from_slice(&(v1 | (v2 << 62)).to_le_bytes());
         result[8..16].copy_from_slice(&((v2 >> 2) | (v3 << 60)).to_le_bytes());
         result[16..24].copy_from_slice(&((v3 >> 4) | (v4 << 58)).to_le_bytes());
-        result[24..].copy_from_slice(&(v4 >> 6).to_le_bytes());
+        result[24..].copy_from_slice(&(v4 >> 6).to_le_bytes()[..7]);
 
         result
     }
@@ -55,7 +59,7 @@ impl Default for ElementDigest {
 
 impl Serializable for ElementDigest {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_bytes(&self.as_bytes()[..31]);
+        target.write_bytes(&self.as_bytes());
     }
 }
 
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::super::mds::mds_f64_12x12::mds_multiply;
-use super::{exp_acc, Digest, ElementHasher, Hasher};
+use super::{exp_acc, Digest, ElementHasher, Hasher, SpongePermutation};
 use core::convert::TryInto;
 use core::ops::Range;
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
@@ -254,6 +254,24 @@ impl ElementHasher for Rp64_256 {
     }
 }
 
+impl SpongePermutation for Rp64_256 {
+    type State = [BaseElement; STATE_WIDTH];
+
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    fn init_state() -> Self::State {
+        [BaseElement::ZERO; STATE_WIDTH]
+    }
+
+    fn permute(state: &mut Self::State) {
+        Self::apply_permutation(state)
+    }
+
+    fn digest_as_elements(digest: &Self::Digest) -> &[Self::BaseField] {
+        digest.as_elements()
+    }
+}
+
 // HASH FUNCTION IMPLEMENTATION
 // ================================================================================================
 
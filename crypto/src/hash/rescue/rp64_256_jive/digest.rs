@@ -31,6 +31,10 @@ impl ElementDigest {
 }
 
 impl Digest for ElementDigest {
+    const SIZE: usize = 32;
+
+    type Bytes = [u8; 32];
+
     fn as_bytes(&self) -> [u8; 32] {
         let mut result = [0; 32];
 
@@ -6,8 +6,14 @@
 use super::{ByteDigest, ElementHasher, Hasher};
 use core::marker::PhantomData;
 use math::{FieldElement, StarkField};
+use sha256::Sha256;
 use sha3::Digest;
-use utils::ByteWriter;
+use utils::{collections::Vec, ByteWriter};
+
+mod sha256;
+
+#[cfg(test)]
+mod tests;
 
 // SHA3 WITH 256-BIT OUTPUT
 // ================================================================================================
@@ -56,6 +62,114 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
     }
 }
 
+// SHA2 WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for SHA2 hash function with 256-bit
+/// output.
+///
+/// All functions follow the same conventions as [Sha3_256] and differ from it only in the
+/// underlying hash function. Thus, the hashes can be re-computed by any environment which
+/// supports SHA-256 (e.g., hardware wallets or HSMs).
+pub struct Sha2_256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Sha2_256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(Sha256::digest(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(Sha256::digest(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(Sha256::digest(&data))
+    }
+}
+
+impl<B: StarkField> ElementHasher for Sha2_256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(Sha256::digest(bytes))
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = Sha256::new();
+            hasher.write(elements);
+            ByteDigest(hasher.finalize())
+        }
+    }
+}
+
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak hash function with 256-bit
+/// output.
+///
+/// This is the original Keccak submission (as used by Ethereum's `keccak256` opcode), which
+/// differs from SHA3 only in the padding rule. All inputs are serialized so that hashes can be
+/// re-derived exactly in EVM-based verifiers:
+/// * [merge()](Keccak_256::merge) hashes the concatenation of the two digests.
+/// * [merge_with_int()](Keccak_256::merge_with_int) hashes the seed followed by the value encoded
+///   as 8 big-endian bytes (i.e., `keccak256(abi.encodePacked(seed, uint64(value)))`).
+/// * [hash_elements()](Keccak_256::hash_elements) hashes the canonical representation of each
+///   base field element encoded as `ELEMENT_BYTES` big-endian bytes; elements in extension
+///   fields are hashed as sequences of their base field coefficients.
+#[allow(non_camel_case_types)]
+pub struct Keccak_256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak_256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_be_bytes());
+        ByteDigest(sha3::Keccak256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak_256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // serialize each base field element into its canonical little-endian form and reverse the
+        // bytes to get the big-endian encoding
+        let mut hasher = sha3::Keccak256::new();
+        let mut buf = Vec::with_capacity(B::ELEMENT_BYTES);
+        for element in E::slice_as_base_elements(elements) {
+            element.write_into(&mut buf);
+            buf.reverse();
+            hasher.update(&buf);
+            buf.clear();
+        }
+        ByteDigest(hasher.finalize().into())
+    }
+}
+
 // SHA HASHER
 // ================================================================================================
 
@@ -0,0 +1,181 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use utils::ByteWriter;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Block size of SHA-256 in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// Initial hash value H(0) as defined in FIPS 180-4, section 5.3.3.
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Round constants as defined in FIPS 180-4, section 4.2.2.
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// SHA-256
+// ================================================================================================
+
+/// A minimal implementation of the SHA-256 hash function as specified in
+/// [FIPS 180-4](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf).
+///
+/// The hasher can be used either in a streaming mode (via [update()](Sha256::update) and
+/// [finalize()](Sha256::finalize) functions), or to hash a single message via
+/// [digest()](Sha256::digest) function.
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; BLOCK_SIZE],
+    buffer_len: usize,
+    message_len: u64,
+}
+
+impl Sha256 {
+    /// Returns a new hasher instantiated with the initial hash value.
+    pub fn new() -> Self {
+        Self {
+            state: H0,
+            buffer: [0; BLOCK_SIZE],
+            buffer_len: 0,
+            message_len: 0,
+        }
+    }
+
+    /// Returns SHA-256 hash of the provided bytes.
+    pub fn digest(bytes: &[u8]) -> [u8; 32] {
+        let mut hasher = Self::new();
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    /// Absorbs the provided bytes into the hasher.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        self.message_len += bytes.len() as u64;
+
+        // fill up a partially filled buffer first
+        if self.buffer_len > 0 {
+            let n = core::cmp::min(BLOCK_SIZE - self.buffer_len, bytes.len());
+            self.buffer[self.buffer_len..self.buffer_len + n].copy_from_slice(&bytes[..n]);
+            self.buffer_len += n;
+            bytes = &bytes[n..];
+            if self.buffer_len < BLOCK_SIZE {
+                return;
+            }
+            let block = self.buffer;
+            compress(&mut self.state, &block);
+            self.buffer_len = 0;
+        }
+
+        // process full blocks directly from the input, and buffer the remainder
+        let mut blocks = bytes.chunks_exact(BLOCK_SIZE);
+        for block in &mut blocks {
+            compress(&mut self.state, block.try_into().unwrap());
+        }
+        let remainder = blocks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+        self.buffer_len = remainder.len();
+    }
+
+    /// Pads the absorbed message and returns its hash.
+    pub fn finalize(mut self) -> [u8; 32] {
+        // append a single 1 bit followed by zeros, such that the message length (in bits) can
+        // be written into the last 8 bytes of the final block
+        let bit_len = self.message_len.wrapping_mul(8);
+        self.buffer[self.buffer_len] = 0x80;
+        self.buffer[self.buffer_len + 1..].fill(0);
+        if self.buffer_len >= BLOCK_SIZE - 8 {
+            let block = self.buffer;
+            compress(&mut self.state, &block);
+            self.buffer = [0; BLOCK_SIZE];
+        }
+        self.buffer[BLOCK_SIZE - 8..].copy_from_slice(&bit_len.to_be_bytes());
+        let block = self.buffer;
+        compress(&mut self.state, &block);
+
+        let mut result = [0; 32];
+        for (chunk, word) in result.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        result
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ByteWriter for Sha256 {
+    fn write_u8(&mut self, value: u8) {
+        self.update(&[value]);
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        self.update(values);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Applies SHA-256 compression function to the provided state and message block.
+fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_SIZE]) {
+    // prepare the message schedule
+    let mut w = [0u32; 64];
+    for (word, chunk) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    // execute 64 rounds of the compression function
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (&k, &w) in K.iter().zip(w.iter()) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(k)
+            .wrapping_add(w);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    // add the compressed chunk to the current hash value
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Keccak_256, Sha256, Sha2_256};
+use math::{
+    fields::{f64::BaseElement, QuadExtension},
+    FieldElement, StarkField,
+};
+use rand_utils::{rand_array, rand_value};
+use sha3::Digest;
+use utils::{collections::Vec, string::String};
+
+type Keccak = Keccak_256<BaseElement>;
+type Sha2 = Sha2_256<BaseElement>;
+
+#[test]
+fn keccak_hash() {
+    // keccak256 of an empty string, as computed by the EVM
+    let expected = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+    assert_eq!(expected, to_hex(&Keccak::hash(&[]).0));
+}
+
+#[test]
+fn keccak_hash_elements_big_endian() {
+    let elements: [BaseElement; 3] = rand_array();
+
+    let mut bytes = Vec::new();
+    for element in elements.iter() {
+        bytes.extend_from_slice(&element.as_int().to_be_bytes());
+    }
+    let expected: [u8; 32] = sha3::Keccak256::digest(&bytes).into();
+    assert_eq!(expected, Keccak::hash_elements(&elements).0);
+
+    // extension field elements are hashed as sequences of their base field coefficients
+    let ext_elements: [QuadExtension<BaseElement>; 2] = [
+        QuadExtension::new(elements[0], elements[1]),
+        QuadExtension::new(elements[2], BaseElement::ONE),
+    ];
+    let base_elements = [elements[0], elements[1], elements[2], BaseElement::ONE];
+    assert_eq!(
+        Keccak::hash_elements(&base_elements),
+        Keccak::hash_elements(&ext_elements)
+    );
+}
+
+#[test]
+fn keccak_merge_with_int() {
+    let seed = Keccak::hash(&[1, 2, 3]);
+    let value: u64 = rand_value();
+
+    let mut bytes = seed.0.to_vec();
+    bytes.extend_from_slice(&value.to_be_bytes());
+    let expected: [u8; 32] = sha3::Keccak256::digest(&bytes).into();
+    assert_eq!(expected, Keccak::merge_with_int(seed, value).0);
+}
+
+#[test]
+fn keccak_hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let r1 = Keccak::hash_elements(&e1);
+    let r2 = Keccak::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn sha256_test_vectors() {
+    // test vectors from FIPS 180-4 examples
+    let expected = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    assert_eq!(expected, to_hex(&Sha256::digest(b"")));
+
+    let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    assert_eq!(expected, to_hex(&Sha256::digest(b"abc")));
+
+    let expected = "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1";
+    let message = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    assert_eq!(expected, to_hex(&Sha256::digest(message)));
+
+    // one million repetitions of "a" absorbed in uneven chunks
+    let expected = "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0";
+    let message = vec![b'a'; 1_000_000];
+    let mut hasher = Sha256::new();
+    for chunk in message.chunks(999) {
+        hasher.update(chunk);
+    }
+    assert_eq!(expected, to_hex(&hasher.finalize()));
+}
+
+#[test]
+fn sha256_streaming() {
+    let message: [u8; 200] = rand_array();
+    let expected = Sha256::digest(&message);
+
+    // absorbing the message in chunks of any size should result in the same hash
+    for chunk_size in [1, 7, 55, 56, 63, 64, 65, 128] {
+        let mut hasher = Sha256::new();
+        for chunk in message.chunks(chunk_size) {
+            hasher.update(chunk);
+        }
+        assert_eq!(expected, hasher.finalize());
+    }
+}
+
+#[test]
+fn sha2_merge() {
+    // expected values are obtained by computing SHA-256 of the same inputs using Python hashlib
+    let seed = Sha2::hash(&[]);
+    let expected = "2dba5dbc339e7316aea2683faf839c1b7b1ee2313db792112588118df066aa35";
+    assert_eq!(expected, to_hex(&Sha2::merge(&[seed, seed]).0));
+
+    let seed = Sha2::hash(&[1, 2, 3]);
+    let expected = "039058c6f2c0cb492c533b0a4d14ef77cc0f78abccced5287d84a1a2011cfb81";
+    assert_eq!(expected, to_hex(&seed.0));
+
+    let expected = "339e9439001a593f4ada98d48716c2931da6cd1a827ab6635c7f8a937bcc3d36";
+    assert_eq!(expected, to_hex(&Sha2::merge_with_int(seed, 5).0));
+}
+
+#[test]
+fn sha2_hash_elements() {
+    let elements: [BaseElement; 3] = rand_array();
+
+    let mut bytes = Vec::new();
+    for element in elements.iter() {
+        bytes.extend_from_slice(&element.as_int().to_le_bytes());
+    }
+    assert_eq!(Sha2::hash(&bytes), Sha2::hash_elements(&elements));
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let e2 = [elements[0], elements[1], elements[2], BaseElement::ZERO];
+    assert_ne!(Sha2::hash_elements(&elements), Sha2::hash_elements(&e2));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
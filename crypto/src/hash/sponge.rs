@@ -0,0 +1,277 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher};
+use core::ops::Range;
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// SPONGE TRAIT
+// ================================================================================================
+
+/// Defines a stateful sponge which can absorb field elements and squeeze pseudo-random field
+/// elements.
+///
+/// Unlike [Hasher] procedures, absorbing and squeezing can be interleaved arbitrarily, and thus,
+/// a sponge can be used to instantiate duplex-based Fiat-Shamir transcripts (e.g., via
+/// [SpongeRandomCoin](crate::SpongeRandomCoin)).
+pub trait Sponge: Clone + Send + Sync {
+    /// Base field of the elements absorbed and squeezed by this sponge.
+    type BaseField: StarkField;
+
+    /// Hash function which defines the digests which can be absorbed by this sponge.
+    type Hasher: ElementHasher<BaseField = Self::BaseField>;
+
+    /// Returns a new sponge with an empty state.
+    fn new() -> Self;
+
+    /// Absorbs the provided elements into the sponge state.
+    fn absorb<E: FieldElement<BaseField = Self::BaseField>>(&mut self, elements: &[E]);
+
+    /// Absorbs the provided digest into the sponge state.
+    fn absorb_digest(&mut self, digest: &<Self::Hasher as Hasher>::Digest);
+
+    /// Squeezes the specified number of pseudo-random field elements from the sponge.
+    fn squeeze(&mut self, num_elements: usize) -> Vec<Self::BaseField>;
+}
+
+// SPONGE PERMUTATION TRAIT
+// ================================================================================================
+
+/// Defines a permutation-based hash function whose permutation can be used to instantiate a
+/// [DuplexSponge].
+pub trait SpongePermutation: ElementHasher {
+    /// Type of the permutation state.
+    type State: AsRef<[Self::BaseField]> + AsMut<[Self::BaseField]> + Copy + Send + Sync;
+
+    /// Range of the state elements which comprise the rate portion of the state.
+    const RATE_RANGE: Range<usize>;
+
+    /// Returns a state with all elements set to zeros.
+    fn init_state() -> Self::State;
+
+    /// Applies the permutation to the provided state.
+    fn permute(state: &mut Self::State);
+
+    /// Returns the elements of the provided digest.
+    fn digest_as_elements(digest: &Self::Digest) -> &[Self::BaseField];
+}
+
+// DUPLEX SPONGE
+// ================================================================================================
+
+/// Duplex sponge construction instantiated with the permutation of hash function `H`.
+///
+/// The sponge works as follows:
+/// - At instantiation time, all elements of the state are set to zeros.
+/// - Absorbed elements are added to the rate portion of the state one by one; once the rate is
+///   filled up, the permutation is applied.
+/// - Before the first element is squeezed after absorbing, the absorbed elements are padded by
+///   adding Fp(1) to the next rate element, and the permutation is applied. Thus, absorbing
+///   trailing zeros always changes the squeezed elements.
+/// - Squeezed elements are read from the rate portion of the state; once all rate elements have
+///   been read, the permutation is applied.
+///
+/// The state of the sponge is initialized differently from the state used by
+/// [hash_elements()](ElementHasher::hash_elements) functions of the underlying hash functions,
+/// and thus, squeezed elements are not consistent with hashes of the absorbed elements.
+pub struct DuplexSponge<H: SpongePermutation> {
+    state: H::State,
+    position: usize,
+    squeezing: bool,
+}
+
+impl<H: SpongePermutation> DuplexSponge<H> {
+    /// Applies the permutation if the rate portion of the state has been fully used.
+    fn permute_if_full(&mut self) {
+        if self.position == H::RATE_RANGE.len() {
+            H::permute(&mut self.state);
+            self.position = 0;
+        }
+    }
+
+    /// Absorbs a single base field element.
+    fn absorb_element(&mut self, element: H::BaseField) {
+        if self.squeezing {
+            self.squeezing = false;
+            self.position = 0;
+        }
+        self.permute_if_full();
+        self.state.as_mut()[H::RATE_RANGE.start + self.position] += element;
+        self.position += 1;
+    }
+}
+
+impl<H: SpongePermutation> Clone for DuplexSponge<H> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state,
+            position: self.position,
+            squeezing: self.squeezing,
+        }
+    }
+}
+
+impl<H: SpongePermutation> Sponge for DuplexSponge<H> {
+    type BaseField = H::BaseField;
+    type Hasher = H;
+
+    fn new() -> Self {
+        Self {
+            state: H::init_state(),
+            position: 0,
+            squeezing: false,
+        }
+    }
+
+    fn absorb<E: FieldElement<BaseField = Self::BaseField>>(&mut self, elements: &[E]) {
+        for &element in E::slice_as_base_elements(elements) {
+            self.absorb_element(element);
+        }
+    }
+
+    fn absorb_digest(&mut self, digest: &H::Digest) {
+        for &element in H::digest_as_elements(digest) {
+            self.absorb_element(element);
+        }
+    }
+
+    fn squeeze(&mut self, num_elements: usize) -> Vec<Self::BaseField> {
+        if !self.squeezing {
+            // pad the absorbed elements and switch to squeezing
+            self.permute_if_full();
+            self.state.as_mut()[H::RATE_RANGE.start + self.position] += H::BaseField::ONE;
+            H::permute(&mut self.state);
+            self.position = 0;
+            self.squeezing = true;
+        }
+
+        let mut result = Vec::with_capacity(num_elements);
+        for _ in 0..num_elements {
+            self.permute_if_full();
+            result.push(self.state.as_ref()[H::RATE_RANGE.start + self.position]);
+            self.position += 1;
+        }
+        result
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DuplexSponge, Sponge};
+    use crate::hash::{Poseidon64_256, Rp64_256, Tip5_320};
+    use math::{
+        fields::{f64::BaseElement, QuadExtension},
+        FieldElement,
+    };
+    use rand_utils::rand_array;
+
+    type RpSponge = DuplexSponge<Rp64_256>;
+
+    #[test]
+    fn absorb_padding() {
+        let e1: [BaseElement; 2] = rand_array();
+        let e2 = [e1[0], e1[1], BaseElement::ZERO];
+        let e3 = [e1[0], e1[1], BaseElement::ONE];
+
+        let mut s1 = RpSponge::new();
+        s1.absorb(&e1);
+        let mut s2 = RpSponge::new();
+        s2.absorb(&e2);
+        let mut s3 = RpSponge::new();
+        s3.absorb(&e3);
+
+        let r1 = s1.squeeze(2);
+        assert_ne!(r1, s2.squeeze(2));
+        assert_ne!(r1, s3.squeeze(2));
+
+        // same as above but with the inputs filling up the whole rate
+        let e1: [BaseElement; 8] = rand_array();
+        let mut e2 = [BaseElement::ZERO; 9];
+        e2[..8].copy_from_slice(&e1);
+
+        let mut s1 = RpSponge::new();
+        s1.absorb(&e1);
+        let mut s2 = RpSponge::new();
+        s2.absorb(&e2);
+        assert_ne!(s1.squeeze(1), s2.squeeze(1));
+    }
+
+    #[test]
+    fn absorb_in_chunks() {
+        let elements: [BaseElement; 19] = rand_array();
+
+        let mut s1 = RpSponge::new();
+        s1.absorb(&elements);
+        let expected = s1.squeeze(20);
+
+        // absorbing elements in chunks of any size should result in the same state
+        for chunk_size in [1, 3, 8, 10] {
+            let mut s2 = RpSponge::new();
+            for chunk in elements.chunks(chunk_size) {
+                s2.absorb(chunk);
+            }
+            assert_eq!(expected, s2.squeeze(20));
+        }
+
+        // extension field elements are absorbed as sequences of their base field coefficients
+        let ext_elements: [QuadExtension<BaseElement>; 2] = [
+            QuadExtension::new(elements[0], elements[1]),
+            QuadExtension::new(elements[2], elements[3]),
+        ];
+        let mut s1 = RpSponge::new();
+        s1.absorb(&elements[..4]);
+        let mut s2 = RpSponge::new();
+        s2.absorb(&ext_elements);
+        assert_eq!(s1.squeeze(4), s2.squeeze(4));
+    }
+
+    #[test]
+    fn squeeze_in_chunks() {
+        let elements: [BaseElement; 5] = rand_array();
+
+        let mut s1 = RpSponge::new();
+        s1.absorb(&elements);
+        let expected = s1.squeeze(20);
+
+        // squeezing elements in chunks of any size should result in the same elements
+        let mut s2 = RpSponge::new();
+        s2.absorb(&elements);
+        let mut actual = s2.squeeze(3);
+        actual.extend(s2.squeeze(8));
+        actual.extend(s2.squeeze(9));
+        assert_eq!(expected, actual);
+
+        // squeezed elements should not repeat across permutations
+        assert_ne!(expected[..8], expected[8..16]);
+    }
+
+    #[test]
+    fn duplex() {
+        let elements: [BaseElement; 4] = rand_array();
+
+        let mut s1 = DuplexSponge::<Poseidon64_256>::new();
+        s1.absorb(&elements[..2]);
+        let r1 = s1.squeeze(1);
+        s1.absorb(&elements[2..]);
+        let r2 = s1.squeeze(1);
+
+        // absorbing after squeezing should affect subsequently squeezed elements
+        let mut s2 = DuplexSponge::<Poseidon64_256>::new();
+        s2.absorb(&elements[..2]);
+        assert_eq!(r1, s2.squeeze(1));
+        assert_ne!(r2, s2.squeeze(1));
+
+        // the same should hold for a sponge with a different rate
+        let mut s1 = DuplexSponge::<Tip5_320>::new();
+        s1.absorb(&elements);
+        let r1 = s1.squeeze(1);
+        s1.absorb(&elements);
+        assert_ne!(r1, s1.squeeze(1));
+    }
+}
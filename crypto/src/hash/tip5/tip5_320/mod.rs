@@ -0,0 +1,429 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher, SpongePermutation};
+use core::convert::TryInto;
+use core::ops::Range;
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+use utils::collections::Vec;
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 16 field elements or 128 bytes; 10 elements are reserved for rate and
+/// the remaining 6 elements are reserved for capacity.
+const STATE_WIDTH: usize = 16;
+
+/// The rate portion of the state is located in elements 0 through 9.
+const RATE_RANGE: Range<usize> = 0..10;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 0..5;
+const INPUT2_RANGE: Range<usize> = 5..10;
+
+/// The capacity portion of the state is located in elements 10 through 15.
+const CAPACITY_RANGE: Range<usize> = 10..16;
+
+/// The output of the hash function is a digest which consists of 5 field elements or 40 bytes.
+///
+/// The digest is returned from state elements 0 through 4 (the first five elements of the rate
+/// portion).
+const DIGEST_RANGE: Range<usize> = 0..5;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of rounds is set to 5 to target 160-bit security level, as specified for Tip5.
+const NUM_ROUNDS: usize = 5;
+
+/// The number of state elements to which the lookup-based S-Box is applied; the power map
+/// x^7 is applied to the remaining elements.
+const NUM_SPLIT_AND_LOOKUP: usize = 4;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Tip5 hash function with 320-bit output.
+///
+/// The hash function is implemented according to the Tip5
+/// [specifications](https://eprint.iacr.org/2023/107.pdf), and follows the sponge modes of Triton
+/// VM:
+/// * [hash_elements()](Tip5_320::hash_elements) uses the variable-length domain: the capacity is
+///   initialized to zeros, the input is padded with Fp(1) followed by as many Fp(0) as needed to
+///   make its length a multiple of the rate, and each chunk overwrites the rate portion of the
+///   state before the permutation is applied.
+/// * [merge()](Tip5_320::merge) uses the fixed-length domain: the two digests are copied into the
+///   rate portion of the state, all capacity elements are set to Fp(1), and the permutation is
+///   applied once.
+/// * [merge_with_int()](Tip5_320::merge_with_int) hashes the seed followed by the value (split
+///   into two elements if it does not fit into one) using the variable-length domain.
+///
+/// The S-Box layer applies a lookup table to each byte of the Montgomery representation of the
+/// first 4 state elements: the table is defined by T(x) = (x + 1)^3 - 1 mod 257. The power map x^7
+/// is applied to the remaining 12 elements. The linear layer is a circulant MDS matrix.
+///
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 16 field elements.
+/// * Capacity size: 6 field elements.
+/// * Number of rounds: 5.
+///
+/// The digest consists of five field elements and it can be serialized into 40 bytes (320 bits).
+///
+/// ## Interoperability
+/// The lookup table, the MDS matrix and the round constants are the same as in Triton VM, and
+/// thus, [merge()](Tip5_320::merge) and [hash_elements()](Tip5_320::hash_elements) produce the
+/// same digests as `hash_pair()` and `hash_varlen()` of Triton VM. The i-th round constant, for i
+/// in 0..80, is obtained by reducing the first 16 bytes of BLAKE3("Tip5" || i) (where i is
+/// encoded as a single byte) interpreted as a little-endian integer modulo p, and using the
+/// result as the Montgomery representation of a field element.
+///
+/// ## Hash output consistency
+/// Functions [merge()](Tip5_320::merge) and [hash_elements()](Tip5_320::hash_elements) are not
+/// consistent because they use different domains. Similarly, [hash()](Tip5_320::hash) function is
+/// not consistent with [hash_elements()](Tip5_320::hash_elements), because byte strings are split
+/// into 7-byte chunks (with a single byte of padding) before being converted into field elements.
+pub struct Tip5_320();
+
+impl Hasher for Tip5_320 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 160;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // break the string into 7-byte chunks and convert each chunk into a field element. we
+        // append a byte with value 1 to the last chunk; this pads the string in such a way that
+        // adding trailing zeros results in different hash. we use 7-byte chunks because every
+        // 8-byte chunk with the most significant byte set to 0 or 1 maps to a valid field element.
+        let num_elements = bytes.len() / 7 + 1;
+        let mut elements = Vec::with_capacity(num_elements);
+        let mut chunks = bytes.chunks_exact(7);
+        for chunk in &mut chunks {
+            let mut buf = [0_u8; 8];
+            buf[..7].copy_from_slice(chunk);
+            elements.push(BaseElement::new(u64::from_le_bytes(buf)));
+        }
+        let remainder = chunks.remainder();
+        let mut buf = [0_u8; 8];
+        buf[..remainder.len()].copy_from_slice(remainder);
+        buf[remainder.len()] = 1;
+        elements.push(BaseElement::new(u64::from_le_bytes(buf)));
+
+        Self::hash_varlen(&elements)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // and setting all capacity elements to 1 (the fixed-length domain)
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(values[0].as_elements());
+        state[INPUT2_RANGE].copy_from_slice(values[1].as_elements());
+        state[CAPACITY_RANGE].fill(BaseElement::ONE);
+
+        // apply the Tip5 permutation and return the first five elements of the rate
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // hash the seed followed by the value; if the value doesn't fit into a single field
+        // element, split it into two field elements.
+        let mut elements = [BaseElement::ZERO; DIGEST_SIZE + 2];
+        elements[..DIGEST_SIZE].copy_from_slice(seed.as_elements());
+        elements[DIGEST_SIZE] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            Self::hash_varlen(&elements[..DIGEST_SIZE + 1])
+        } else {
+            elements[DIGEST_SIZE + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            Self::hash_varlen(&elements)
+        }
+    }
+}
+
+impl ElementHasher for Tip5_320 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        Self::hash_varlen(E::slice_as_base_elements(elements))
+    }
+}
+
+impl SpongePermutation for Tip5_320 {
+    type State = [BaseElement; STATE_WIDTH];
+
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    fn init_state() -> Self::State {
+        [BaseElement::ZERO; STATE_WIDTH]
+    }
+
+    fn permute(state: &mut Self::State) {
+        Self::apply_permutation(state)
+    }
+
+    fn digest_as_elements(digest: &Self::Digest) -> &[Self::BaseField] {
+        digest.as_elements()
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Tip5_320 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 5 to target 160-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 16 field elements or 128 bytes; 10 elements are reserved for rate
+    /// and the remaining 6 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 9 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 10 through 15 (inclusive).
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0 through 4 (inclusive).
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Lookup table used by the split-and-lookup S-Box.
+    pub const LOOKUP_TABLE: [u8; 256] = LOOKUP_TABLE;
+
+    /// First column of the circulant MDS matrix used for computing the linear layer.
+    pub const MDS: [u64; STATE_WIDTH] = MDS;
+
+    /// Round constants added to the hasher state at the end of each Tip5 round.
+    pub const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = ARK;
+
+    // TIP5 PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Tip5 permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+    }
+
+    /// Tip5 round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::apply_sbox(state);
+        Self::apply_mds(state);
+        Self::add_constants(state, &ARK[round]);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Hashes the provided elements using the variable-length sponge domain.
+    fn hash_varlen(elements: &[BaseElement]) -> ElementDigest {
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+
+        // absorb all full chunks of the input by overwriting the rate portion of the state
+        let mut chunks = elements.chunks_exact(RATE_WIDTH);
+        for chunk in &mut chunks {
+            state[RATE_RANGE].copy_from_slice(chunk);
+            Self::apply_permutation(&mut state);
+        }
+
+        // absorb the remaining elements followed by Fp(1) and as many Fp(0) as needed to fill up
+        // the rate; this is always done, even if the length of the input is a multiple of the
+        // rate.
+        let remainder = chunks.remainder();
+        state[..remainder.len()].copy_from_slice(remainder);
+        state[remainder.len()] = BaseElement::ONE;
+        state[remainder.len() + 1..RATE_RANGE.end].fill(BaseElement::ZERO);
+        Self::apply_permutation(&mut state);
+
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    fn apply_sbox(state: &mut [BaseElement; STATE_WIDTH]) {
+        state[..NUM_SPLIT_AND_LOOKUP]
+            .iter_mut()
+            .for_each(split_and_lookup);
+        state[NUM_SPLIT_AND_LOOKUP..]
+            .iter_mut()
+            .for_each(|v| *v = v.exp7());
+    }
+
+    #[inline(always)]
+    fn apply_mds(state: &mut [BaseElement; STATE_WIDTH]) {
+        // all matrix coefficients are smaller than 2^16, and thus, we can accumulate each row of
+        // the product in a u128 before performing a single reduction
+        let values = state.map(|v| v.as_int() as u128);
+        for (i, s) in state.iter_mut().enumerate() {
+            let mut acc = 0u128;
+            for (j, &v) in values.iter().enumerate() {
+                acc += MDS[(STATE_WIDTH + i - j) % STATE_WIDTH] as u128 * v;
+            }
+            *s = reduce_u128(acc);
+        }
+    }
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], ark: &[BaseElement; STATE_WIDTH]) {
+        state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
+    }
+}
+
+/// Applies the lookup table to each byte of the canonical Montgomery representation of the
+/// element. The table maps 0 to 0 and 255 to 255, which guarantees that the result is a valid
+/// element.
+#[inline(always)]
+fn split_and_lookup(element: &mut BaseElement) {
+    let mut value = element.inner();
+    if value >= BaseElement::MODULUS {
+        value -= BaseElement::MODULUS;
+    }
+    let mut bytes = value.to_le_bytes();
+    bytes
+        .iter_mut()
+        .for_each(|b| *b = LOOKUP_TABLE[*b as usize]);
+    *element = BaseElement::from_mont(u64::from_le_bytes(bytes));
+}
+
+/// Reduces a 128-bit integer modulo the field modulus.
+#[inline(always)]
+fn reduce_u128(value: u128) -> BaseElement {
+    let lo = BaseElement::new(value as u64);
+    let hi = BaseElement::new((value >> 64) as u64);
+    // 2^64 mod p = 2^32 - 1
+    lo + hi * BaseElement::new(u32::MAX as u64)
+}
+
+// LOOKUP TABLE
+// ================================================================================================
+
+/// Tip5 lookup table; T(x) = (x + 1)^3 - 1 mod 257.
+const LOOKUP_TABLE: [u8; 256] = [
+    0, 7, 26, 63, 124, 215, 85, 254, 214, 228, 45, 185, 140, 173, 33, 240, 29, 177, 176, 32, 8,
+    110, 87, 202, 204, 99, 150, 106, 230, 14, 235, 128, 213, 239, 212, 138, 23, 130, 208, 6, 44,
+    71, 93, 116, 146, 189, 251, 81, 199, 97, 38, 28, 73, 179, 95, 84, 152, 48, 35, 119, 49, 88,
+    242, 3, 148, 169, 72, 120, 62, 161, 166, 83, 175, 191, 137, 19, 100, 129, 112, 55, 221, 102,
+    218, 61, 151, 237, 68, 164, 17, 147, 46, 234, 203, 216, 22, 141, 65, 57, 123, 12, 244, 54, 219,
+    231, 96, 77, 180, 154, 5, 253, 133, 165, 98, 195, 205, 134, 245, 30, 9, 188, 59, 142, 186, 197,
+    181, 144, 92, 31, 224, 163, 111, 74, 58, 69, 113, 196, 67, 246, 225, 10, 121, 50, 60, 157, 90,
+    122, 2, 250, 101, 75, 178, 159, 24, 36, 201, 11, 243, 132, 198, 190, 114, 233, 39, 52, 21, 209,
+    108, 238, 91, 187, 18, 104, 194, 37, 153, 34, 200, 143, 126, 155, 236, 118, 64, 80, 172, 89,
+    94, 193, 135, 183, 86, 107, 252, 13, 167, 206, 136, 220, 207, 103, 171, 160, 76, 182, 227, 217,
+    158, 56, 174, 4, 66, 109, 139, 162, 184, 211, 249, 47, 125, 232, 117, 43, 16, 42, 127, 20, 241,
+    25, 149, 105, 156, 51, 53, 168, 145, 247, 223, 79, 78, 226, 15, 222, 82, 115, 70, 210, 27, 41,
+    1, 170, 40, 131, 192, 229, 248, 255,
+];
+
+// MDS
+// ================================================================================================
+/// First column of the circulant Tip5 MDS matrix.
+const MDS: [u64; STATE_WIDTH] = [
+    61402, 1108, 28750, 33823, 7454, 43244, 53865, 12034, 56951, 27521, 41351, 40901, 12021, 59689,
+    26798, 17845,
+];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Tip5 round constants; the constants are listed in canonical (i.e., non-Montgomery) form.
+const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(13630775303355457758),
+        BaseElement::new(16896927574093233874),
+        BaseElement::new(10379449653650130495),
+        BaseElement::new(1965408364413093495),
+        BaseElement::new(15232538947090185111),
+        BaseElement::new(15892634398091747074),
+        BaseElement::new(3989134140024871768),
+        BaseElement::new(2851411912127730865),
+        BaseElement::new(8709136439293758776),
+        BaseElement::new(3694858669662939734),
+        BaseElement::new(12692440244315327141),
+        BaseElement::new(10722316166358076749),
+        BaseElement::new(12745429320441639448),
+        BaseElement::new(17932424223723990421),
+        BaseElement::new(7558102534867937463),
+        BaseElement::new(15551047435855531404),
+    ],
+    [
+        BaseElement::new(17532528648579384106),
+        BaseElement::new(5216785850422679555),
+        BaseElement::new(15418071332095031847),
+        BaseElement::new(11921929762955146258),
+        BaseElement::new(9738718993677019874),
+        BaseElement::new(3464580399432997147),
+        BaseElement::new(13408434769117164050),
+        BaseElement::new(264428218649616431),
+        BaseElement::new(4436247869008081381),
+        BaseElement::new(4063129435850804221),
+        BaseElement::new(2865073155741120117),
+        BaseElement::new(5749834437609765994),
+        BaseElement::new(6804196764189408435),
+        BaseElement::new(17060469201292988508),
+        BaseElement::new(9475383556737206708),
+        BaseElement::new(12876344085611465020),
+    ],
+    [
+        BaseElement::new(13835756199368269249),
+        BaseElement::new(1648753455944344172),
+        BaseElement::new(9836124473569258483),
+        BaseElement::new(12867641597107932229),
+        BaseElement::new(11254152636692960595),
+        BaseElement::new(16550832737139861108),
+        BaseElement::new(11861573970480733262),
+        BaseElement::new(1256660473588673495),
+        BaseElement::new(13879506000676455136),
+        BaseElement::new(10564103842682358721),
+        BaseElement::new(16142842524796397521),
+        BaseElement::new(3287098591948630584),
+        BaseElement::new(685911471061284805),
+        BaseElement::new(5285298776918878023),
+        BaseElement::new(18310953571768047354),
+        BaseElement::new(3142266350630002035),
+    ],
+    [
+        BaseElement::new(549990724933663297),
+        BaseElement::new(4901984846118077401),
+        BaseElement::new(11458643033696775769),
+        BaseElement::new(8706785264119212710),
+        BaseElement::new(12521758138015724072),
+        BaseElement::new(11877914062416978196),
+        BaseElement::new(11333318251134523752),
+        BaseElement::new(3933899631278608623),
+        BaseElement::new(16635128972021157924),
+        BaseElement::new(10291337173108950450),
+        BaseElement::new(4142107155024199350),
+        BaseElement::new(16973934533787743537),
+        BaseElement::new(11068111539125175221),
+        BaseElement::new(17546769694830203606),
+        BaseElement::new(5315217744825068993),
+        BaseElement::new(4609594252909613081),
+    ],
+    [
+        BaseElement::new(3350107164315270407),
+        BaseElement::new(17715942834299349177),
+        BaseElement::new(9600609149219873996),
+        BaseElement::new(12894357635820003949),
+        BaseElement::new(4597649658040514631),
+        BaseElement::new(7735563950920491847),
+        BaseElement::new(1663379455870887181),
+        BaseElement::new(13889298103638829706),
+        BaseElement::new(7375530351220884434),
+        BaseElement::new(3502022433285269151),
+        BaseElement::new(9231805330431056952),
+        BaseElement::new(9252272755288523725),
+        BaseElement::new(10014268662326746219),
+        BaseElement::new(15565031632950843234),
+        BaseElement::new(1209725273521819323),
+        BaseElement::new(6024642864597845108),
+    ],
+];
@@ -0,0 +1,242 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Tip5_320, ARK, LOOKUP_TABLE,
+    MDS, NUM_SPLIT_AND_LOOKUP, STATE_WIDTH,
+};
+use core::convert::TryInto;
+use math::StarkField;
+use rand_utils::{rand_array, rand_value};
+
+#[test]
+fn test_lookup_table() {
+    // the lookup table should be a permutation on bytes with 0 and 255 being fixed points
+    let mut images = [false; 256];
+    for &image in LOOKUP_TABLE.iter() {
+        images[image as usize] = true;
+    }
+    assert!(images.iter().all(|&v| v));
+    assert_eq!(0, LOOKUP_TABLE[0]);
+    assert_eq!(255, LOOKUP_TABLE[255]);
+
+    // the table should be defined by T(x) = (x + 1)^3 - 1 mod 257
+    for (x, &image) in LOOKUP_TABLE.iter().enumerate() {
+        assert_eq!(((x as u64 + 1).pow(3) % 257 - 1) as u8, image);
+    }
+}
+
+#[test]
+fn test_sbox() {
+    // Fp(0) and Fp(-1) (i.e., 0xffffffff00000000 in Montgomery form) are fixed points of the
+    // split-and-lookup S-Box
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    state[1] = BaseElement::from_mont(0xffffffff00000000);
+    state[NUM_SPLIT_AND_LOOKUP] = BaseElement::new(3);
+    Tip5_320::apply_sbox(&mut state);
+    assert_eq!(BaseElement::ZERO, state[0]);
+    assert_eq!(BaseElement::from_mont(0xffffffff00000000), state[1]);
+    assert_eq!(BaseElement::new(2187), state[NUM_SPLIT_AND_LOOKUP]);
+}
+
+#[test]
+fn test_mds() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = [BaseElement::ZERO; STATE_WIDTH];
+    for (i, e) in expected.iter_mut().enumerate() {
+        for (j, &s) in state.iter().enumerate() {
+            *e += BaseElement::new(MDS[(STATE_WIDTH + i - j) % STATE_WIDTH]) * s;
+        }
+    }
+
+    let mut actual = state;
+    Tip5_320::apply_mds(&mut actual);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_round_constants() {
+    // the i-th round constant is obtained by reducing the first 16 bytes of BLAKE3("Tip5" || i)
+    // modulo p and interpreting the result as the Montgomery representation of an element
+    for (i, &constant) in ARK.iter().flatten().enumerate() {
+        let digest = blake3::hash(&[b"Tip5".as_slice(), &[i as u8]].concat());
+        let value = u128::from_le_bytes(digest.as_bytes()[..16].try_into().unwrap());
+        let expected = BaseElement::from_mont((value % BaseElement::MODULUS as u128) as u64);
+        assert_eq!(expected, constant);
+    }
+}
+
+// TRITON VM TEST VECTORS
+// ------------------------------------------------------------------------------------------------
+// the tests below mirror `hash10_test_vectors` and `hash_varlen_test_vectors` tests of the Tip5
+// implementation used by Triton VM (see https://github.com/Neptune-Crypto/twenty-first), and the
+// expected values are taken from these tests.
+
+#[test]
+fn apply_permutation() {
+    // repeatedly apply the permutation to a state in the fixed-length domain, each time copying
+    // the digest into the rate portion of the next state
+    let mut input = [BaseElement::ZERO; 10];
+    for i in 0..6 {
+        let mut state = [BaseElement::ONE; STATE_WIDTH];
+        state[..10].copy_from_slice(&input);
+        Tip5_320::apply_permutation(&mut state);
+        input[i..i + 5].copy_from_slice(&state[..5]);
+    }
+    let mut state = [BaseElement::ONE; STATE_WIDTH];
+    state[..10].copy_from_slice(&input);
+    Tip5_320::apply_permutation(&mut state);
+
+    assert_eq!(HASH_PAIR_CHAIN_DIGEST.map(BaseElement::new), state[..5]);
+}
+
+#[test]
+fn hash_elements() {
+    // sum up the digests of sequences [], [0], [0, 1], ..., [0, 1, ..., 18]; the sequences cover
+    // inputs which are shorter than, equal to, and longer than the rate
+    let mut digest_sum = [BaseElement::ZERO; 5];
+    for i in 0..20 {
+        let elements = (0..i).map(BaseElement::new).collect::<Vec<_>>();
+        let digest = Tip5_320::hash_elements(&elements);
+        digest_sum
+            .iter_mut()
+            .zip(digest.as_elements())
+            .for_each(|(s, &d)| *s += d);
+    }
+
+    let expected = [
+        7610004073009036015,
+        5725198067541094245,
+        4721320565792709122,
+        1732504843634706218,
+        259800783350288362,
+    ];
+    assert_eq!(expected.map(BaseElement::new), digest_sum);
+}
+
+#[test]
+fn merge() {
+    // same as the permutation test above, but the digests are computed via merge()
+    let mut input = [BaseElement::ZERO; 10];
+    for i in 0..6 {
+        let digest = Tip5_320::merge(&to_digests(&input));
+        input[i..i + 5].copy_from_slice(digest.as_elements());
+    }
+    let digest = Tip5_320::merge(&to_digests(&input));
+
+    assert_eq!(
+        HASH_PAIR_CHAIN_DIGEST.map(BaseElement::new),
+        digest.as_elements()
+    );
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 10] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..5].try_into().unwrap()),
+        ElementDigest::new(elements[5..].try_into().unwrap()),
+    ];
+
+    // merge() uses the fixed-length domain, and thus, it is not consistent with hash_elements()
+    let m_result = Tip5_320::merge(&digests);
+    let h_result = Tip5_320::hash_elements(&elements);
+    assert_ne!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Tip5_320::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Tip5_320::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Tip5_320::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Tip5_320::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Tip5_320::hash(&[1_u8, 2, 3]);
+    let r2 = Tip5_320::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Tip5_320::hash(&[1_u8, 2, 3, 4, 5, 6]);
+    let r2 = Tip5_320::hash(&[1_u8, 2, 3, 4, 5, 6, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Tip5_320::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Tip5_320::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with multiple zeros
+    let r1 = Tip5_320::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0]);
+    let r2 = Tip5_320::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0, 0, 0, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with inputs spanning more than one permutation
+    let r1 = Tip5_320::hash(&[7_u8; 70]);
+    let r2 = Tip5_320::hash(&[[7_u8; 70].as_slice(), &[0]].concat());
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Tip5_320::hash_elements(&e1);
+    let r2 = Tip5_320::hash_elements(&e2);
+    assert_ne!(r1, r2);
+
+    // same as above but with the input filling the whole rate
+    let e1: [BaseElement; 10] = rand_array();
+    let mut e2 = [BaseElement::ZERO; 11];
+    e2[..10].copy_from_slice(&e1);
+
+    let r1 = Tip5_320::hash_elements(&e1);
+    let r2 = Tip5_320::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// The final digest of the chain of fixed-length hashes from Triton VM test vectors.
+const HASH_PAIR_CHAIN_DIGEST: [u64; 5] = [
+    10869784347448351760,
+    1853783032222938415,
+    6856460589287344822,
+    17178399545409290325,
+    7650660984651717733,
+];
+
+fn to_digests(elements: &[BaseElement; 10]) -> [ElementDigest; 2] {
+    [
+        ElementDigest::new(elements[..5].try_into().unwrap()),
+        ElementDigest::new(elements[5..].try_into().unwrap()),
+    ]
+}
@@ -11,7 +11,10 @@
 //! * **Merkle trees** - which are used as a commitment scheme in the STARK protocol. The
 //!   [MerkleTree] implementation supports concurrent tree construction as well as compact
 //!   aggregation of Merkle paths implemented using a variation of the
-//!   [Octopus](https://eprint.iacr.org/2017/933) algorithm.
+//!   [Octopus](https://eprint.iacr.org/2017/933) algorithm. The [SparseMerkleTree]
+//!   implementation can be used to commit to sparse key-value maps.
+//! * **Vector commitments** - which are defined using the [VectorCommitment] trait. The
+//!   [MerkleTree] implementation of the trait is used by the STARK prover by default.
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
 //!   elements form a seed.
@@ -23,27 +26,61 @@
 extern crate alloc;
 
 mod hash;
-pub use hash::{Digest, ElementHasher, Hasher};
+pub use hash::{
+    Digest, DomainSeparatedHasher, DuplexSponge, ElementHasher, HashDomain, Hasher, Sponge,
+    SpongePermutation,
+};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
+    pub use super::hash::AnemoiJive64_256;
+    pub use super::hash::Blake2sPadding;
+    pub use super::hash::Blake2s_256;
+    pub use super::hash::Blake3Xof;
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
+    pub use super::hash::CairoPadding;
     pub use super::hash::GriffinJive64_256;
+    pub use super::hash::Keccak_256;
+    pub use super::hash::Monolith64_256;
+    pub use super::hash::Pedersen;
+    pub use super::hash::Poseidon62_248;
+    pub use super::hash::Poseidon64_256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::RpJive64_256;
+    pub use super::hash::Rpo256;
+    pub use super::hash::Sha2_256;
     pub use super::hash::Sha3_256;
+    pub use super::hash::StandardPadding;
+    pub use super::hash::Tip5_320;
 }
 
+pub use hash::stark_curve;
+
+mod commitment;
+pub use commitment::VectorCommitment;
+
 mod merkle;
-pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
+pub use merkle::{
+    build_merkle_nodes, verify_merkle_multi_proof, verify_merkle_path, verify_merkle_paths,
+    BatchMerkleProof, MerkleMultiProof, MerkleTree, PartialMerkleTree, SaltedMerkleTree,
+    SparseMerkleProof, SparseMerkleTree, MAX_SPARSE_TREE_DEPTH,
+};
+
+#[cfg(all(feature = "mmap", unix))]
+pub use merkle::MappableDigest;
 
 #[cfg(feature = "concurrent")]
 pub use merkle::concurrent;
 
 mod random;
-pub use random::{DefaultRandomCoin, RandomCoin};
+pub use random::{
+    DefaultRandomCoin, HashRng, QuerySampler, QuerySampling, RandomCoin, SpongeRandomCoin,
+};
+
+#[cfg(feature = "transcript")]
+pub use random::transcript;
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
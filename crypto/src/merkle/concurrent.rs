@@ -12,6 +12,14 @@ use utils::{collections::Vec, iterators::*, rayon};
 
 pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 
+/// Number of parents of leaves computed by a single [Hasher::merge_pairs()] call.
+const MERGE_BATCH_SIZE: usize = 64;
+
+/// Number of sub-trees per thread into which the tree is split by
+/// [MerkleTree::build_parallel()](super::MerkleTree::build_parallel); splitting the tree into
+/// more sub-trees than there are threads lets work stealing balance the load between threads.
+pub const SUBTREES_PER_THREAD: usize = 4;
+
 // PUBLIC FUNCTIONS
 // ================================================================================================
 
@@ -19,6 +27,21 @@ pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 /// results in a single vector such that root of the tree is at position 1, nodes immediately
 /// under the root is at positions 2 and 3 etc.
 pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
+    build_merkle_nodes_with_subtrees::<H>(leaves, rayon::current_num_threads())
+}
+
+/// Builds all internal nodes of the Merkle tree in the same way as [build_merkle_nodes()], but
+/// splits the tree into the specified number of sub-trees, each of which is built as a separate
+/// task in the current thread pool.
+///
+/// The number of sub-trees controls the granularity of the work: the nodes of each sub-tree are
+/// computed level by level within a single task, and the nodes above the sub-trees are computed
+/// sequentially once all sub-trees are built. `num_subtrees` is rounded up to the next power of
+/// two, and is capped at a quarter of the number of leaves.
+pub fn build_merkle_nodes_with_subtrees<H: Hasher>(
+    leaves: &[H::Digest],
+    num_subtrees: usize,
+) -> Vec<H::Digest> {
     let n = leaves.len() / 2;
 
     // create un-initialized array to hold all intermediate nodes
@@ -29,15 +52,16 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     // build first row of internal nodes (parents of leaves)
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
     nodes[n..]
-        .par_iter_mut()
-        .zip(two_leaves.par_iter())
-        .for_each(|(target, source)| *target = H::merge(source));
+        .par_chunks_mut(MERGE_BATCH_SIZE)
+        .zip(two_leaves.par_chunks(MERGE_BATCH_SIZE))
+        .for_each(|(target, source)| target.copy_from_slice(&H::merge_pairs(source)));
 
     // calculate all other tree nodes, we can't use regular iterators  here because
     // access patterns are rather complicated - so, we use regular threads instead
 
-    // number of sub-trees must always be a power of 2
-    let num_subtrees = rayon::current_num_threads().next_power_of_two();
+    // number of sub-trees must always be a power of 2, and each sub-tree must contain at least
+    // two nodes at the level immediately above the leaves
+    let num_subtrees = num_subtrees.next_power_of_two().min(n / 2).max(1);
     let batch_size = n / num_subtrees;
 
     // re-interpret nodes as an array of two nodes fused together
@@ -51,9 +75,9 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
                 let mut batch_size = batch_size / 2;
                 let mut start_idx = n / 2 + batch_size * i;
                 while start_idx >= num_subtrees {
-                    for k in (start_idx..(start_idx + batch_size)).rev() {
-                        nodes[k] = H::merge(&two_nodes[k]);
-                    }
+                    let end_idx = start_idx + batch_size;
+                    let level = H::merge_pairs(&two_nodes[start_idx..end_idx]);
+                    nodes[start_idx..end_idx].copy_from_slice(&level);
                     start_idx /= 2;
                     batch_size /= 2;
                 }
@@ -74,7 +98,7 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
 
 #[cfg(test)]
 mod tests {
-    use crate::hash::{ByteDigest, Sha3_256};
+    use crate::hash::{Blake2s_256, ByteDigest, Sha3_256};
     use math::fields::f128::BaseElement;
     use proptest::collection::vec;
     use proptest::prelude::*;
@@ -82,10 +106,47 @@ mod tests {
     proptest! {
         #[test]
         fn build_merkle_nodes_concurrent(ref data in vec(any::<[u8; 32]>(), 256..257).no_shrink()) {
-            let leaves = ByteDigest::bytes_as_digests(&data).to_vec();
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
             let sequential = super::super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             let concurrent = super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             assert_eq!(concurrent, sequential);
         }
+
+        #[test]
+        fn build_merkle_nodes_with_subtrees(
+            ref data in vec(any::<[u8; 32]>(), 256..257).no_shrink(),
+            num_subtrees in 0..300usize,
+        ) {
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
+            let sequential = super::super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
+            let concurrent =
+                super::build_merkle_nodes_with_subtrees::<Sha3_256<BaseElement>>(&leaves, num_subtrees);
+            assert_eq!(concurrent, sequential);
+        }
+
+        #[test]
+        fn build_merkle_nodes_batched(ref data in vec(any::<[u8; 32]>(), 256..257).no_shrink()) {
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
+            let sequential = super::super::build_merkle_nodes::<Blake2s_256<BaseElement>>(&leaves);
+            let concurrent = super::build_merkle_nodes::<Blake2s_256<BaseElement>>(&leaves);
+            assert_eq!(concurrent, sequential);
+        }
+    }
+
+    #[test]
+    fn build_small_merkle_nodes_with_subtrees() {
+        let data = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+        let leaves = ByteDigest::bytes_as_digests(&data);
+        for num_leaves in [2, 4] {
+            let leaves = &leaves[..num_leaves];
+            let sequential = super::super::build_merkle_nodes::<Sha3_256<BaseElement>>(leaves);
+            for num_subtrees in [1, 2, 8] {
+                let concurrent = super::build_merkle_nodes_with_subtrees::<Sha3_256<BaseElement>>(
+                    leaves,
+                    num_subtrees,
+                );
+                assert_eq!(concurrent, sequential);
+            }
+        }
     }
 }
@@ -0,0 +1,314 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{storage::NodeStorage, MerkleTree};
+use crate::{hash::ByteDigest, Digest, Hasher};
+use core::{convert::TryFrom, marker::PhantomData, mem, ops::Deref, ptr, slice};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    os::unix::io::AsRawFd,
+    path::Path,
+    sync::Arc,
+};
+use utils::Serializable;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Magic bytes at the start of a Merkle tree file.
+const MAGIC: &[u8; 4] = b"WFMT";
+
+/// Version of the Merkle tree file format.
+const FORMAT_VERSION: u8 = 1;
+
+/// Number of bytes in the fixed part of the file header.
+const HEADER_BYTES: usize = 36;
+
+/// Tree nodes are written at an offset which is a multiple of this value; this keeps mapped
+/// nodes properly aligned in memory.
+const DATA_ALIGNMENT: usize = 64;
+
+// MAPPABLE DIGEST
+// ================================================================================================
+
+/// Marks digest types which can be written to and read from memory-mapped files as raw bytes.
+///
+/// Only trees with digests of such types can be persisted via [MerkleTree::write_to()] and
+/// loaded via [MerkleTree::load_mmap()].
+///
+/// # Safety
+/// Implementing this trait asserts that the digest type is plain bytes: its in-memory
+/// representation contains no padding bytes, and every sequence of `size_of::<Self>()` bytes is
+/// a valid value of the type. Mapped digests are read from untrusted files without validation,
+/// and thus, implementing this trait for any other type is undefined behavior.
+pub unsafe trait MappableDigest: Digest {}
+
+// SAFETY: byte digests are transparent wrappers around byte arrays
+unsafe impl<const N: usize> MappableDigest for ByteDigest<N> {}
+
+// MERKLE TREE PERSISTENCE
+// ================================================================================================
+
+impl<H: Hasher> MerkleTree<H>
+where
+    H::Digest: MappableDigest,
+{
+    /// Writes this tree into a file at the specified `path`, replacing the file if it exists.
+    ///
+    /// The file contains a small header followed by the in-memory representation of the tree
+    /// nodes and leaves. Thus, the tree can be loaded back via [MerkleTree::load_mmap()] without
+    /// parsing or copying the nodes, but the file can be loaded only by a program which uses the
+    /// same hash function `H` and runs on a platform with the same memory layout of digests.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be created or written to.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut target = BufWriter::new(File::create(path)?);
+
+        let root = self.root().to_bytes();
+        let mut header = Vec::with_capacity(data_offset(root.len()));
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&[FORMAT_VERSION, 0, 0, 0]);
+        header.extend_from_slice(&(mem::size_of::<H::Digest>() as u32).to_le_bytes());
+        header.extend_from_slice(&(mem::align_of::<H::Digest>() as u32).to_le_bytes());
+        header.extend_from_slice(&(self.nodes.len() as u64).to_le_bytes());
+        header.extend_from_slice(&(self.leaves.len() as u64).to_le_bytes());
+        header.extend_from_slice(&(root.len() as u32).to_le_bytes());
+        header.extend_from_slice(&root);
+        header.resize(data_offset(root.len()), 0);
+        target.write_all(&header)?;
+
+        target.write_all(digests_as_bytes(&self.nodes))?;
+        target.write_all(digests_as_bytes(&self.leaves))?;
+        target.flush()
+    }
+
+    /// Loads a tree previously written via [MerkleTree::write_to()] from the file at the
+    /// specified `path` by memory-mapping the file.
+    ///
+    /// Nodes and leaves of the returned tree are not read into memory; instead, they are paged
+    /// in by the operating system when they are accessed (e.g., when Merkle proofs are
+    /// generated). Modifying the tree (via [MerkleTree::update()] or [MerkleTree::append()])
+    /// copies all its nodes into memory first. The file must not be modified while the tree is
+    /// in use.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The file could not be opened or mapped into memory.
+    /// * The file does not contain a Merkle tree written by [MerkleTree::write_to()].
+    /// * The tree in the file was written using a different hash function or on a platform with
+    ///   a different memory layout of digests.
+    pub fn load_mmap<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mapping = Arc::new(Mapping::new(&File::open(path)?)?);
+        let bytes: &[u8] = &mapping;
+
+        // parse and validate the header
+        if bytes.len() < HEADER_BYTES || &bytes[..4] != MAGIC {
+            return Err(invalid_data("file does not contain a Merkle tree"));
+        }
+        if bytes[4] != FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported Merkle tree file format version {}",
+                bytes[4]
+            )));
+        }
+        let digest_size = read_u32(bytes, 8) as usize;
+        let digest_align = read_u32(bytes, 12) as usize;
+        if digest_size != mem::size_of::<H::Digest>()
+            || digest_align != mem::align_of::<H::Digest>()
+        {
+            return Err(invalid_data("memory layout of tree digests does not match"));
+        }
+        let (num_nodes, num_leaves) = match (
+            usize::try_from(read_u64(bytes, 16)),
+            usize::try_from(read_u64(bytes, 24)),
+        ) {
+            (Ok(num_nodes), Ok(num_leaves)) => (num_nodes, num_leaves),
+            _ => return Err(invalid_data("invalid Merkle tree dimensions")),
+        };
+        if num_nodes < 2 || !num_nodes.is_power_of_two() || num_leaves > num_nodes {
+            return Err(invalid_data("invalid Merkle tree dimensions"));
+        }
+        let root_size = read_u32(bytes, 32) as usize;
+        if root_size != H::Digest::SIZE {
+            return Err(invalid_data("size of tree digests does not match"));
+        }
+
+        // make sure the file contains exactly the nodes and the leaves of the tree; the sizes
+        // are read from the file, and thus, overflows must be treated as invalid data
+        let nodes_offset = data_offset(root_size);
+        let leaves_offset = num_nodes
+            .checked_mul(digest_size)
+            .and_then(|nodes_size| nodes_size.checked_add(nodes_offset))
+            .ok_or_else(|| invalid_data("invalid Merkle tree dimensions"))?;
+        let file_size = num_leaves
+            .checked_mul(digest_size)
+            .and_then(|leaves_size| leaves_size.checked_add(leaves_offset))
+            .ok_or_else(|| invalid_data("invalid Merkle tree dimensions"))?;
+        if bytes.len() != file_size {
+            return Err(invalid_data("unexpected Merkle tree file size"));
+        }
+
+        let nodes = MappedSlice::<H::Digest>::new(mapping.clone(), nodes_offset, num_nodes)?;
+        let leaves = MappedSlice::<H::Digest>::new(mapping.clone(), leaves_offset, num_leaves)?;
+
+        // the serialized root in the header must match the root located in the mapped nodes
+        if nodes[1].to_bytes() != bytes[HEADER_BYTES..HEADER_BYTES + root_size] {
+            return Err(invalid_data("root of the tree does not match"));
+        }
+
+        let tree = MerkleTree {
+            nodes: NodeStorage::Mapped(nodes),
+            leaves: NodeStorage::Mapped(leaves),
+        };
+
+        // re-computing the root from its children guards against loading trees built using a
+        // different hash function with the same digest type
+        if H::merge(&[tree.get_node(2), tree.get_node(3)]) != *tree.root() {
+            return Err(invalid_data(
+                "tree was built using a different hash function",
+            ));
+        }
+
+        Ok(tree)
+    }
+}
+
+// MEMORY MAPPING
+// ================================================================================================
+
+/// A read-only memory mapping of an entire file.
+struct Mapping {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+impl Mapping {
+    fn new(file: &File) -> io::Result<Self> {
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| invalid_data("file is too large to be mapped into memory"))?;
+        if len == 0 {
+            return Err(invalid_data("file does not contain a Merkle tree"));
+        }
+
+        // SAFETY: we map a file which we have opened for reading into a new private read-only
+        // mapping; the mapping is released in drop()
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { ptr, len })
+    }
+}
+
+impl Deref for Mapping {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the mapping is valid for reads of len bytes while self is alive
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: the pointer and the length describe a mapping created in new()
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+// SAFETY: the mapping is read-only, and thus, can be shared between threads
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+// MAPPED SLICE
+// ================================================================================================
+
+/// A slice of values located in a memory-mapped file.
+pub(super) struct MappedSlice<D> {
+    mapping: Arc<Mapping>,
+    offset: usize,
+    len: usize,
+    _values: PhantomData<D>,
+}
+
+impl<D: MappableDigest> MappedSlice<D> {
+    /// Returns a slice of `len` values located at the specified byte `offset` in the mapping.
+    ///
+    /// # Errors
+    /// Returns an error if the mapping does not contain `len` values at the offset, or if the
+    /// offset is not suitably aligned for values of type `D`.
+    fn new(mapping: Arc<Mapping>, offset: usize, len: usize) -> io::Result<Self> {
+        let end = len
+            .checked_mul(mem::size_of::<D>())
+            .and_then(|size| size.checked_add(offset));
+        if !matches!(end, Some(end) if end <= mapping.len) {
+            return Err(invalid_data("mapped values are out of bounds"));
+        }
+        if (mapping.ptr as usize + offset) % mem::align_of::<D>() != 0 {
+            return Err(invalid_data("mapped values are not aligned"));
+        }
+        Ok(Self {
+            mapping,
+            offset,
+            len,
+            _values: PhantomData,
+        })
+    }
+}
+
+impl<D: Copy> Deref for MappedSlice<D> {
+    type Target = [D];
+
+    fn deref(&self) -> &[D] {
+        // SAFETY: bounds and alignment of the slice were checked in new(), any bytes are valid
+        // values of D since D is a MappableDigest, and the mapping is kept alive for as long as
+        // this slice exists
+        unsafe {
+            let ptr = (self.mapping.ptr as *const u8).add(self.offset) as *const D;
+            slice::from_raw_parts(ptr, self.len)
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the offset at which tree nodes are written for a root of the specified size.
+fn data_offset(root_size: usize) -> usize {
+    (HEADER_BYTES + root_size + DATA_ALIGNMENT - 1) / DATA_ALIGNMENT * DATA_ALIGNMENT
+}
+
+/// Returns the in-memory representation of the specified digests.
+fn digests_as_bytes<D: MappableDigest>(digests: &[D]) -> &[u8] {
+    // SAFETY: mappable digests are plain bytes without padding, and thus, their memory can be
+    // read as bytes
+    unsafe { slice::from_raw_parts(digests.as_ptr() as *const u8, mem::size_of_val(digests)) }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
@@ -3,13 +3,39 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::MerkleTreeError, hash::Hasher};
+use crate::{errors::MerkleTreeError, hash::Hasher, VectorCommitment};
 use core::slice;
-use utils::collections::{BTreeMap, BTreeSet, Vec};
+use utils::{
+    collections::{BTreeMap, BTreeSet, Vec},
+    ByteReader, ByteWriter, DeserializationError,
+};
 
 mod proofs;
 pub use proofs::BatchMerkleProof;
 
+mod multiproof;
+pub use multiproof::MerkleMultiProof;
+
+mod partial;
+pub use partial::PartialMerkleTree;
+
+mod salted;
+pub use salted::SaltedMerkleTree;
+
+mod sparse;
+pub use sparse::{SparseMerkleProof, SparseMerkleTree, MAX_SPARSE_TREE_DEPTH};
+
+mod verification;
+pub use verification::{verify_merkle_multi_proof, verify_merkle_path, verify_merkle_paths};
+
+mod storage;
+use storage::NodeStorage;
+
+#[cfg(all(feature = "mmap", unix))]
+mod mmap;
+#[cfg(all(feature = "mmap", unix))]
+pub use mmap::MappableDigest;
+
 #[cfg(feature = "concurrent")]
 pub mod concurrent;
 
@@ -57,6 +83,25 @@ mod tests;
 /// To verify proofs, [MerkleTree::verify()] and [MerkleTree::verify_batch()] functions can be
 /// used respectively.
 ///
+/// A tree can also be modified after it has been built: [MerkleTree::update()] replaces a single
+/// leaf, and [MerkleTree::append()] adds a new leaf to the end of the tree. In both cases, only
+/// the nodes on the path from the affected leaf to the root are recomputed. When a leaf is
+/// appended to a tree in which all leaf slots are occupied, the capacity of the tree is doubled,
+/// and the unoccupied slots are treated as leaves equal to the default digest.
+///
+/// Alternatively, [MerkleTree::prove_multi()] method can be used to generate a
+/// [MerkleMultiProof], which contains the same set of internal nodes as a batch proof, but does
+/// not include leaves and does not group the nodes by paths. Such proofs can be verified using
+/// [MerkleTree::verify_multi()] function.
+///
+/// By default, a tree is binary. Trees with a higher arity can be built by specifying the
+/// `ARITY` parameter (e.g., `MerkleTree<H, 4>`); in such trees, every internal node is a hash of
+/// `ARITY` children computed using [Hasher::merge_many()], and the number of leaves must be a
+/// power of `ARITY`. A higher arity reduces the depth of the tree, and thus, the number of hash
+/// computations needed to verify a Merkle path (at the expense of larger paths). Trees with
+/// arity greater than 2 support only building the tree, generating and verifying individual
+/// Merkle paths; the remaining methods are available only for binary trees.
+///
 /// # Examples
 /// ```
 /// # use winter_crypto::{MerkleTree, Hasher, hashers::Blake3_256};
@@ -84,71 +129,55 @@ mod tests;
 /// assert!(MerkleTree::<Blake3>::verify(*tree.root(), 1, &proof).is_err());
 /// ```
 #[derive(Debug)]
-pub struct MerkleTree<H: Hasher> {
-    nodes: Vec<H::Digest>,
-    leaves: Vec<H::Digest>,
+pub struct MerkleTree<H: Hasher, const ARITY: usize = 2> {
+    nodes: NodeStorage<H::Digest>,
+    leaves: NodeStorage<H::Digest>,
 }
 
 // MERKLE TREE IMPLEMENTATION
 // ================================================================================================
 
-impl<H: Hasher> MerkleTree<H> {
-    // CONSTRUCTORS
+impl<H: Hasher, const ARITY: usize> MerkleTree<H, ARITY> {
+    // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
 
     /// Returns new Merkle tree built from the provide leaves using hash function specified by the
     /// `H` generic parameter.
     ///
-    /// When `concurrent` feature is enabled, the tree is built using multiple threads.
+    /// When `concurrent` feature is enabled, binary trees are built using multiple threads.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * Fewer than two leaves were provided.
-    /// * Number of leaves is not a power of two.
+    /// * Fewer than `ARITY` leaves were provided.
+    /// * Number of leaves is not a power of `ARITY`.
+    ///
+    /// # Panics
+    /// Panics if `ARITY` is smaller than 2.
     pub fn new(leaves: Vec<H::Digest>) -> Result<Self, MerkleTreeError> {
-        if leaves.len() < 2 {
-            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
-        }
-        if !leaves.len().is_power_of_two() {
-            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        assert!(ARITY >= 2, "arity of a Merkle tree must be at least 2");
+        if leaves.len() < ARITY {
+            return Err(MerkleTreeError::TooFewLeaves(ARITY, leaves.len()));
         }
 
-        #[cfg(not(feature = "concurrent"))]
-        let nodes = build_merkle_nodes::<H>(&leaves);
-
-        #[cfg(feature = "concurrent")]
-        let nodes = if leaves.len() <= concurrent::MIN_CONCURRENT_LEAVES {
-            build_merkle_nodes::<H>(&leaves)
+        let nodes = if ARITY == 2 {
+            if !leaves.len().is_power_of_two() {
+                return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+            }
+            build_binary_merkle_nodes::<H>(&leaves)
         } else {
-            concurrent::build_merkle_nodes::<H>(&leaves)
+            if ARITY.pow(leaves.len().ilog(ARITY)) != leaves.len() {
+                return Err(MerkleTreeError::NumberOfLeavesNotPowerOfArity(
+                    ARITY,
+                    leaves.len(),
+                ));
+            }
+            build_merkle_nodes_with_arity::<H>(&leaves, ARITY)
         };
 
-        Ok(MerkleTree { nodes, leaves })
-    }
-
-    /// Forms a MerkleTree from a list of nodes and leaves.
-    ///
-    /// Nodes are supplied as a vector where the root is stored at position 1.
-    ///
-    /// # Errors
-    /// Returns an error if:
-    /// * Fewer than two leaves were provided.
-    /// * Number of leaves is not a power of two.
-    ///
-    /// # Panics
-    /// Panics if nodes doesn't have the same length as leaves.
-    pub fn from_raw_parts(
-        nodes: Vec<H::Digest>,
-        leaves: Vec<H::Digest>,
-    ) -> Result<Self, MerkleTreeError> {
-        if leaves.len() < 2 {
-            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
-        }
-        if !leaves.len().is_power_of_two() {
-            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
-        }
-        assert_eq!(nodes.len(), leaves.len());
-        Ok(MerkleTree { nodes, leaves })
+        Ok(MerkleTree {
+            nodes: nodes.into(),
+            leaves: leaves.into(),
+        })
     }
 
     // PUBLIC ACCESSORS
@@ -162,9 +191,10 @@ impl<H: Hasher> MerkleTree<H> {
     /// Returns depth of the tree.
     ///
     /// The depth of a tree is zero-based. Thus, a tree with two leaves has depth 1, a tree with
-    /// four leaves has depth 2 etc.
+    /// four leaves has depth 2 etc. For trees with arity greater than 2, the depth is the base
+    /// `ARITY` logarithm of the number of leaves.
     pub fn depth(&self) -> usize {
-        self.leaves.len().ilog2() as usize
+        self.capacity().ilog(ARITY) as usize
     }
 
     /// Returns leaf nodes of the tree.
@@ -177,7 +207,9 @@ impl<H: Hasher> MerkleTree<H> {
 
     /// Returns a Merkle path to a leaf at the specified `index`.
     ///
-    /// The leaf itself will be the first element in the path.
+    /// The leaf itself will be the first element in the path. For every level of the tree, the
+    /// path then contains `ARITY - 1` siblings of the node on the path in the order of their
+    /// positions, starting with the siblings of the leaf.
     ///
     /// # Errors
     /// Returns an error if the specified index is greater than or equal to the number of leaves
@@ -190,17 +222,169 @@ impl<H: Hasher> MerkleTree<H> {
             ));
         }
 
-        let mut proof = vec![self.leaves[index], self.leaves[index ^ 1]];
-
-        let mut index = (index + self.nodes.len()) >> 1;
-        while index > 1 {
-            proof.push(self.nodes[index ^ 1]);
-            index >>= 1;
+        let mut position = index + self.nodes.len();
+        let mut proof = Vec::with_capacity(self.depth() * (ARITY - 1) + 1);
+        proof.push(self.leaves[index]);
+        while position > 1 {
+            let parent = (position + ARITY - 2) / ARITY;
+            let first_child = parent * ARITY + 2 - ARITY;
+            for sibling in (first_child..first_child + ARITY).filter(|&p| p != position) {
+                proof.push(self.get_node(sibling));
+            }
+            position = parent;
         }
 
         Ok(proof)
     }
 
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the `proof` for the specified `index` is valid.
+    ///
+    /// # Errors
+    /// Returns an error if the specified `proof` (which is a Merkle path) is malformed or does
+    /// not resolve to the specified `root`.
+    pub fn verify(
+        root: H::Digest,
+        index: usize,
+        proof: &[H::Digest],
+    ) -> Result<(), MerkleTreeError> {
+        if proof.len() < ARITY || (proof.len() - 1) % (ARITY - 1) != 0 {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let mut v = proof[0];
+        let mut index = index;
+        for siblings in proof[1..].chunks(ARITY - 1) {
+            let offset = index % ARITY;
+            v = if ARITY == 2 {
+                if offset == 0 {
+                    H::merge(&[v, siblings[0]])
+                } else {
+                    H::merge(&[siblings[0], v])
+                }
+            } else {
+                let mut children = Vec::with_capacity(ARITY);
+                children.extend_from_slice(&siblings[..offset]);
+                children.push(v);
+                children.extend_from_slice(&siblings[offset..]);
+                H::merge_many(&children)
+            };
+            index /= ARITY;
+        }
+
+        if v != root {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of leaf slots in the tree.
+    fn capacity(&self) -> usize {
+        (self.nodes.len() - 1) * (ARITY - 1) + 1
+    }
+
+    /// Returns a node located at the specified position in the tree.
+    ///
+    /// The root is located at position 1, and the children of the node at position p are
+    /// located at positions [p * ARITY + 2 - ARITY, p * ARITY + 2). Thus, for a binary tree,
+    /// internal nodes are located at positions [1, n), and leaves are located at positions
+    /// [n, 2n), where n is the capacity of the tree. Unoccupied leaf slots are treated as leaves
+    /// equal to the default digest.
+    fn get_node(&self, position: usize) -> H::Digest {
+        let n = self.nodes.len();
+        if position < n {
+            self.nodes[position]
+        } else {
+            self.leaves.get(position - n).copied().unwrap_or_default()
+        }
+    }
+}
+
+// BINARY MERKLE TREE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher> MerkleTree<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns new Merkle tree built from the provided leaves using hash function specified by the
+    /// `H` generic parameter and a dedicated pool of `num_threads` threads.
+    ///
+    /// Unlike [MerkleTree::new()], which uses the global thread pool and splits the tree into as
+    /// many sub-trees as there are threads in the pool, this function splits the tree into
+    /// [SUBTREES_PER_THREAD](concurrent::SUBTREES_PER_THREAD) sub-trees per thread so that the
+    /// load is balanced between threads when building large trees. If `num_threads` is zero, the
+    /// number of threads is selected in the same way as for the global thread pool.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    ///
+    /// # Panics
+    /// Panics if the thread pool could not be created.
+    #[cfg(feature = "concurrent")]
+    pub fn build_parallel(
+        leaves: Vec<H::Digest>,
+        num_threads: usize,
+    ) -> Result<Self, MerkleTreeError> {
+        if leaves.len() < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+
+        let pool = utils::rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .expect("failed to create a thread pool");
+        let num_subtrees = pool.current_num_threads() * concurrent::SUBTREES_PER_THREAD;
+        let nodes = pool
+            .install(|| concurrent::build_merkle_nodes_with_subtrees::<H>(&leaves, num_subtrees));
+
+        Ok(MerkleTree {
+            nodes: nodes.into(),
+            leaves: leaves.into(),
+        })
+    }
+
+    /// Forms a MerkleTree from a list of nodes and leaves.
+    ///
+    /// Nodes are supplied as a vector where the root is stored at position 1.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    ///
+    /// # Panics
+    /// Panics if nodes doesn't have the same length as leaves.
+    pub fn from_raw_parts(
+        nodes: Vec<H::Digest>,
+        leaves: Vec<H::Digest>,
+    ) -> Result<Self, MerkleTreeError> {
+        if leaves.len() < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+        assert_eq!(nodes.len(), leaves.len());
+        Ok(MerkleTree {
+            nodes: nodes.into(),
+            leaves: leaves.into(),
+        })
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
     /// Computes Merkle paths for the provided indexes and compresses the paths into a single proof.
     ///
     /// # Errors
@@ -222,17 +406,18 @@ impl<H: Hasher> MerkleTree<H> {
         }
 
         let index_map = map_indexes(indexes, self.depth())?;
+        self.check_max_index(&index_map)?;
         let indexes = normalize_indexes(indexes);
         let mut leaves = vec![H::Digest::default(); index_map.len()];
         let mut nodes: Vec<Vec<H::Digest>> = Vec::with_capacity(indexes.len());
 
         // populate the proof with leaf node values
-        let n = self.leaves.len();
+        let n = self.nodes.len();
         let mut next_indexes: Vec<usize> = Vec::new();
         for index in indexes {
             let missing: Vec<H::Digest> = (index..index + 2)
                 .flat_map(|i| {
-                    let v = self.leaves[i];
+                    let v = self.get_node(i + n);
                     if let Some(idx) = index_map.get(&i) {
                         leaves[*idx] = v;
                         None
@@ -274,38 +459,89 @@ impl<H: Hasher> MerkleTree<H> {
         })
     }
 
-    // VERIFICATION METHODS
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single
+    /// multi-proof.
+    ///
+    /// The leaves at the specified indexes are not included into the proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    pub fn prove_multi(&self, indexes: &[usize]) -> Result<MerkleMultiProof<H>, MerkleTreeError> {
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        let index_map = map_indexes(indexes, self.depth())?;
+        self.check_max_index(&index_map)?;
+
+        // traverse the tree one level at a time in the same order as the verifier does, and add
+        // all sibling nodes which the verifier cannot compute to the proof
+        let n = self.nodes.len();
+        let mut positions: BTreeSet<usize> = index_map.keys().map(|&index| index + n).collect();
+        let mut nodes = Vec::new();
+        for _ in 0..self.depth() {
+            let mut parents = BTreeSet::new();
+            for &position in positions.iter() {
+                let sibling = position ^ 1;
+                if !positions.contains(&sibling) {
+                    nodes.push(self.get_node(sibling));
+                }
+                parents.insert(position >> 1);
+            }
+            positions = parents;
+        }
+
+        Ok(MerkleMultiProof {
+            nodes,
+            depth: self.depth() as u8,
+        })
+    }
+
+    // MODIFICATION METHODS
     // --------------------------------------------------------------------------------------------
 
-    /// Checks whether the `proof` for the specified `index` is valid.
+    /// Replaces the leaf at the specified `index` with the provided `leaf` and updates all
+    /// internal nodes on the path from this leaf to the root.
     ///
     /// # Errors
-    /// Returns an error if the specified `proof` (which is a Merkle path) does not resolve to the
-    /// specified `root`.
-    pub fn verify(
-        root: H::Digest,
-        index: usize,
-        proof: &[H::Digest],
-    ) -> Result<(), MerkleTreeError> {
-        let r = index & 1;
-        let mut v = H::merge(&[proof[r], proof[1 - r]]);
-
-        let mut index = (index + 2usize.pow((proof.len() - 1) as u32)) >> 1;
-        for &p in proof.iter().skip(2) {
-            v = if index & 1 == 0 {
-                H::merge(&[v, p])
-            } else {
-                H::merge(&[p, v])
-            };
-            index >>= 1;
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    pub fn update(&mut self, index: usize, leaf: H::Digest) -> Result<(), MerkleTreeError> {
+        if index >= self.leaves.len() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(
+                self.leaves.len(),
+                index,
+            ));
         }
 
-        if v != root {
-            return Err(MerkleTreeError::InvalidProof);
-        }
+        self.leaves[index] = leaf;
+        self.update_path(index);
         Ok(())
     }
 
+    /// Appends the provided `leaf` to the end of the tree and updates all internal nodes on the
+    /// path from this leaf to the root.
+    ///
+    /// If all leaf slots of the tree are occupied, the capacity of the tree is doubled first:
+    /// the current tree becomes the left subtree of the new root, and the right subtree is built
+    /// from default digests. Thus, the depth of the tree is increased by one, and the root of
+    /// the tree becomes the same as the root of a tree built from the current leaves padded
+    /// with default digests to the next power of two.
+    pub fn append(&mut self, leaf: H::Digest) {
+        if self.leaves.len() == self.nodes.len() {
+            self.grow();
+        }
+
+        self.leaves.to_mut().push(leaf);
+        self.update_path(self.leaves.len() - 1);
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
     /// Checks whether the batch proof contains Merkle paths for the of the specified `indexes`.
     ///
     /// # Errors
@@ -326,6 +562,151 @@ impl<H: Hasher> MerkleTree<H> {
         }
         Ok(())
     }
+
+    /// Checks whether the multi-proof contains Merkle paths for the specified `leaves` located
+    /// at the specified `indexes`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided leaves is different from the number of provided indexes.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree from which the multi-proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * The multi-proof does not resolve to the specified `root`.
+    pub fn verify_multi(
+        root: &H::Digest,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+        proof: &MerkleMultiProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if *root != proof.get_root(indexes, leaves)? {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an error if the largest index in the provided index map does not refer to an
+    /// occupied leaf slot of the tree.
+    fn check_max_index(&self, index_map: &BTreeMap<usize, usize>) -> Result<(), MerkleTreeError> {
+        match index_map.keys().next_back() {
+            Some(&index) if index >= self.leaves.len() => Err(
+                MerkleTreeError::LeafIndexOutOfBounds(self.leaves.len(), index),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Recomputes all internal nodes on the path from the leaf at the specified `index` to the
+    /// root of the tree.
+    fn update_path(&mut self, index: usize) {
+        let mut position = (index + self.nodes.len()) >> 1;
+        while position > 0 {
+            self.nodes[position] = H::merge(&[
+                self.get_node(position << 1),
+                self.get_node((position << 1) + 1),
+            ]);
+            position >>= 1;
+        }
+    }
+
+    /// Doubles the capacity of the tree.
+    ///
+    /// The current tree becomes the left subtree of the new root, and the right subtree of the
+    /// new root is built from default digests. The root itself is not computed; it is expected
+    /// to be updated when the next leaf is appended.
+    fn grow(&mut self) {
+        let n = self.nodes.len();
+        let mut nodes = vec![H::Digest::default(); 2 * n];
+
+        // at every level, the nodes of the current tree are moved into the left half of the
+        // level, and the right half is filled with roots of subtrees built from default digests
+        let mut padding = H::Digest::default();
+        let mut level_size = n;
+        while level_size > 1 {
+            padding = H::merge(&[padding, padding]);
+            level_size >>= 1;
+            let level = &mut nodes[2 * level_size..4 * level_size];
+            level[..level_size].copy_from_slice(&self.nodes[level_size..2 * level_size]);
+            level[level_size..].fill(padding);
+        }
+
+        self.nodes = nodes.into();
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher> VectorCommitment<H> for MerkleTree<H> {
+    type Proof = Vec<H::Digest>;
+    type MultiProof = BatchMerkleProof<H>;
+    type Error = MerkleTreeError;
+
+    fn commit(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
+        MerkleTree::new(items)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Proof), Self::Error> {
+        let path = self.prove(index)?;
+        Ok((path[0], path))
+    }
+
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, Self::MultiProof), Self::Error> {
+        let proof = self.prove_batch(indexes)?;
+        Ok((proof.leaves.clone(), proof))
+    }
+
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        if proof.len() < 2 || proof[0] != item {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::<H>::verify(commitment, index, proof)
+    }
+
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        if proof.leaves != items {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::verify_batch(&commitment, indexes, proof)
+    }
+
+    fn write_multi_proof<W: ByteWriter>(proof: &Self::MultiProof, target: &mut W) {
+        target.write_bytes(&proof.serialize_nodes());
+    }
+
+    fn read_multi_proof<R: ByteReader>(
+        source: &mut R,
+        items: Vec<H::Digest>,
+        domain_len: usize,
+    ) -> Result<Self::MultiProof, DeserializationError> {
+        let depth = domain_len.next_power_of_two().ilog2() as u8;
+        BatchMerkleProof::deserialize(source, items, depth)
+    }
 }
 
 // HELPER FUNCTIONS
@@ -349,16 +730,59 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
 
     // build first row of internal nodes (parents of leaves)
-    for (i, j) in (0..n).zip(n..nodes.len()) {
-        nodes[j] = H::merge(&two_leaves[i]);
-    }
+    nodes[n..].copy_from_slice(&H::merge_pairs(two_leaves));
 
     // re-interpret nodes as an array of two nodes fused together
     let two_nodes = unsafe { slice::from_raw_parts(nodes.as_ptr() as *const [H::Digest; 2], n) };
 
+    // calculate all other tree nodes level by level; all nodes of a level are merged at once so
+    // that hash functions which can process several messages at once can do so
+    let mut level_start = n / 2;
+    while level_start > 0 {
+        let level = H::merge_pairs(&two_nodes[level_start..level_start * 2]);
+        nodes[level_start..level_start * 2].copy_from_slice(&level);
+        level_start /= 2;
+    }
+
+    nodes
+}
+
+/// Returns the internal nodes of a binary Merkle tree defined by the specified leaves; when
+/// `concurrent` feature is enabled, large trees are built using multiple threads.
+fn build_binary_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
+    #[cfg(not(feature = "concurrent"))]
+    let nodes = build_merkle_nodes::<H>(leaves);
+
+    #[cfg(feature = "concurrent")]
+    let nodes = if leaves.len() <= concurrent::MIN_CONCURRENT_LEAVES {
+        build_merkle_nodes::<H>(leaves)
+    } else {
+        concurrent::build_merkle_nodes::<H>(leaves)
+    };
+
+    nodes
+}
+
+/// Returns the internal nodes of a Merkle tree with the specified arity defined by the specified
+/// leaves.
+///
+/// The internal nodes are returned as a vector where the root is stored at position 1, and the
+/// children of the node at position p are stored at positions [p * arity + 2 - arity,
+/// p * arity + 2). Every internal node is computed using [Hasher::merge_many()].
+fn build_merkle_nodes_with_arity<H: Hasher>(leaves: &[H::Digest], arity: usize) -> Vec<H::Digest> {
+    let num_nodes = (leaves.len() - 1) / (arity - 1) + 1;
+    let mut nodes = vec![H::Digest::default(); num_nodes];
+
+    // build first row of internal nodes (parents of leaves)
+    let first_parent = num_nodes - leaves.len() / arity;
+    for (i, children) in leaves.chunks(arity).enumerate() {
+        nodes[first_parent + i] = H::merge_many(children);
+    }
+
     // calculate all other tree nodes
-    for i in (1..n).rev() {
-        nodes[i] = H::merge(&two_nodes[i]);
+    for i in (1..first_parent).rev() {
+        let first_child = i * arity + 2 - arity;
+        nodes[i] = H::merge_many(&nodes[first_child..first_child + arity]);
     }
 
     nodes
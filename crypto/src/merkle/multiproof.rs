@@ -0,0 +1,175 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    map_indexes,
+    proofs::{check_estimation_params, expected_num_digests, round},
+};
+use crate::{errors::MerkleTreeError, Digest, Hasher};
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::ToString,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// MERKLE MULTI-PROOF
+// ================================================================================================
+
+/// Multiple Merkle paths aggregated into a single proof with a flat list of internal nodes.
+///
+/// Similarly to [BatchMerkleProof](super::BatchMerkleProof), all duplicate internal nodes are
+/// removed from the proof using a variation of [Octopus](https://eprint.iacr.org/2017/933)
+/// algorithm. However, the remaining nodes are not grouped by paths; instead, they are stored
+/// in the order in which they are consumed by the verifier: level by level, starting with the
+/// leaf level, and within each level in the ascending order of node positions. Thus, the proof
+/// does not need to record the number of nodes contributed by each path.
+///
+/// Unlike batch proofs, multi-proofs do not contain the leaves themselves: the leaves must be
+/// supplied separately when the proof is verified. Also, there is no limit on the number of
+/// paths which can be aggregated into a single multi-proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleMultiProof<H: Hasher> {
+    /// Internal nodes of the proof in the order in which they are consumed by the verifier.
+    pub nodes: Vec<H::Digest>,
+    /// Depth of the tree from which the proof was generated.
+    pub depth: u8,
+}
+
+impl<H: Hasher> MerkleMultiProof<H> {
+    /// Computes a node to which all Merkle paths aggregated in this proof resolve when the
+    /// specified `leaves` are located at the specified `indexes`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided leaves is different from the number of provided indexes.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree for which this proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * The number of internal nodes in the proof is inconsistent with the specified indexes.
+    pub fn get_root(
+        &self,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+    ) -> Result<H::Digest, MerkleTreeError> {
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        if indexes.len() != leaves.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        map_indexes(indexes, self.depth as usize)?;
+
+        // place the leaves at their positions in the tree; for a tree with n leaves, the leaf
+        // at index i is located at position n + i
+        let offset = 2usize.pow(self.depth as u32);
+        let mut known: BTreeMap<usize, H::Digest> = indexes
+            .iter()
+            .zip(leaves.iter())
+            .map(|(&index, &leaf)| (index + offset, leaf))
+            .collect();
+
+        // compute nodes one level at a time, taking sibling nodes which cannot be computed from
+        // the nodes of the previous level from the proof
+        let mut proof_nodes = self.nodes.iter();
+        for _ in 0..self.depth {
+            let mut parents = BTreeMap::new();
+            let mut level = known.into_iter().peekable();
+            while let Some((position, node)) = level.next() {
+                let pair = if position & 1 == 0 {
+                    let sibling = match level.peek() {
+                        Some(&(next_position, next_node)) if next_position == position + 1 => {
+                            level.next();
+                            next_node
+                        }
+                        _ => *proof_nodes.next().ok_or(MerkleTreeError::InvalidProof)?,
+                    };
+                    [node, sibling]
+                } else {
+                    let sibling = *proof_nodes.next().ok_or(MerkleTreeError::InvalidProof)?;
+                    [sibling, node]
+                };
+                parents.insert(position >> 1, H::merge(&pair));
+            }
+            known = parents;
+        }
+
+        // all nodes in the proof must have been consumed
+        if proof_nodes.next().is_some() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        known.remove(&1).ok_or(MerkleTreeError::InvalidProof)
+    }
+
+    // SIZE ESTIMATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the expected number of bytes in a serialized multi-proof for `num_queries`
+    /// distinct positions drawn uniformly at random from a tree of the specified `depth` built
+    /// over a domain of `domain_size` leaves.
+    ///
+    /// A multi-proof contains the same internal nodes as a batch proof for the same positions
+    /// (see [BatchMerkleProof::estimate_serialized_size()](super::BatchMerkleProof::estimate_serialized_size)),
+    /// but records only the tree depth and the total number of nodes rather than the number of
+    /// nodes in each path.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two or is smaller than 2^`depth`.
+    /// * `num_queries` is greater than `domain_size`.
+    pub fn estimate_serialized_size(depth: u8, num_queries: usize, domain_size: usize) -> usize {
+        check_estimation_params(depth, num_queries, domain_size);
+
+        // +1 for the depth, +4 for the number of nodes
+        let (_, num_digests) = expected_num_digests(depth, num_queries, domain_size);
+        round(5.0 + num_digests * H::Digest::SIZE as f64)
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for MerkleMultiProof<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        assert!(self.nodes.len() <= u32::MAX as usize, "too many nodes");
+        target.write_u8(self.depth);
+        target.write_u32(self.nodes.len() as u32);
+        H::Digest::write_batch_into(&self.nodes, target);
+    }
+}
+
+impl<H: Hasher> Deserializable for MerkleMultiProof<H> {
+    /// Reads a Merkle multi-proof from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Tree depth is zero or is greater than or equal to the number of bits in `usize`.
+    /// * The `source` does not contain enough bytes for the specified number of nodes.
+    /// * Any of the nodes could not be read from the `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let depth = source.read_u8()?;
+        if depth == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "tree depth must be greater than zero".to_string(),
+            ));
+        }
+        if depth as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "tree depth must be smaller than {}, but was {}",
+                usize::BITS,
+                depth
+            )));
+        }
+
+        // make sure the source contains all nodes before allocating memory for them
+        let num_nodes = source.read_u32()? as usize;
+        source.check_eor(num_nodes.saturating_mul(H::Digest::SIZE))?;
+        let nodes = H::Digest::read_batch_from(source, num_nodes)?;
+
+        Ok(Self { nodes, depth })
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{map_indexes, proofs::MAX_PATHS, BatchMerkleProof};
+use crate::{errors::MerkleTreeError, Hasher};
+use utils::collections::{BTreeMap, Vec};
+
+// PARTIAL MERKLE TREE
+// ================================================================================================
+
+/// A subset of nodes of a Merkle tree reconstructed from batch Merkle proofs.
+///
+/// A partial tree is built from a [BatchMerkleProof] for a set of leaves, and contains all nodes
+/// of the Merkle paths aggregated in the proof. Thus, a Merkle path can be generated from a
+/// partial tree for any leaf which was opened by the proof (as well as for the siblings of the
+/// opened leaves) without access to the full tree. Additional proofs against the same root can
+/// be added to the tree to extend the set of leaves for which paths are available.
+///
+/// Node positions are the same as in a [MerkleTree](super::MerkleTree): the root is located at
+/// position 1, and for a tree with n leaves, the leaf at index i is located at position n + i.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{MerkleTree, PartialMerkleTree, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// let leaves = (0..8u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+/// let tree = MerkleTree::<Blake3>::new(leaves).unwrap();
+///
+/// // reconstruct a partial tree from a batch proof and re-open one of the leaves
+/// let proof = tree.prove_batch(&[1, 6]).unwrap();
+/// let partial =
+///     PartialMerkleTree::<Blake3>::from_batch_proof(*tree.root(), 3, &[1, 6], &proof).unwrap();
+/// assert_eq!(tree.prove(6).unwrap(), partial.prove(6).unwrap());
+/// assert!(partial.prove(3).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialMerkleTree<H: Hasher> {
+    root: H::Digest,
+    depth: usize,
+    nodes: BTreeMap<usize, H::Digest>,
+}
+
+impl<H: Hasher> PartialMerkleTree<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a partial Merkle tree built from a batch `proof` for the leaves at the specified
+    /// `indexes` of a tree with the specified `root` and `depth`.
+    ///
+    /// The depth of the tree must be provided by the caller rather than taken from the `proof`:
+    /// otherwise, a proof for a shallower tree with the same root could pass internal nodes off
+    /// as leaves.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    /// * The `proof` was generated for a tree of a different depth or does not resolve to the
+    ///   specified `root`.
+    pub fn from_batch_proof(
+        root: H::Digest,
+        depth: usize,
+        indexes: &[usize],
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<Self, MerkleTreeError> {
+        let mut tree = Self {
+            root,
+            depth,
+            nodes: BTreeMap::new(),
+        };
+        tree.add_batch_proof(indexes, proof)?;
+        Ok(tree)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.root
+    }
+
+    /// Returns depth of the tree.
+    ///
+    /// The depth of a tree is zero-based. Thus, a tree with two leaves has depth 1, a tree with
+    /// four leaves has depth 2 etc.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the leaf at the specified `index`, or None if the leaf is not included in the
+    /// partial tree.
+    pub fn get_leaf(&self, index: usize) -> Option<H::Digest> {
+        if index >= self.num_leaves() {
+            return None;
+        }
+        self.nodes.get(&(index + self.num_leaves())).copied()
+    }
+
+    /// Returns an iterator over the indexes and values of all leaves included in the partial
+    /// tree, sorted by index.
+    pub fn leaves(&self) -> impl Iterator<Item = (usize, H::Digest)> + '_ {
+        let n = self.num_leaves();
+        self.nodes
+            .range(n..)
+            .map(move |(&position, &leaf)| (position - n, leaf))
+    }
+
+    // MODIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds all nodes of a batch `proof` for the leaves at the specified `indexes` to the tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    /// * The `proof` was generated for a tree of a different depth or does not resolve to the
+    ///   root of this tree.
+    pub fn add_batch_proof(
+        &mut self,
+        indexes: &[usize],
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if proof.depth as usize != self.depth {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        map_indexes(indexes, self.depth)?;
+        if proof.get_root(indexes)? != self.root {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        // for every path, the first two elements are the leaf and its sibling, and the remaining
+        // elements are siblings of the nodes on the path from the leaf to the root
+        let paths = BatchMerkleProof::<H> {
+            leaves: proof.leaves.clone(),
+            nodes: proof.nodes.clone(),
+            depth: proof.depth,
+        }
+        .into_paths(indexes)?;
+
+        let n = self.num_leaves();
+        for (&index, path) in indexes.iter().zip(paths) {
+            let mut position = index + n;
+            let mut node = path[0];
+            self.nodes.insert(position, node);
+            for &sibling in path[1..].iter() {
+                self.nodes.insert(position ^ 1, sibling);
+                node = if position & 1 == 0 {
+                    H::merge(&[node, sibling])
+                } else {
+                    H::merge(&[sibling, node])
+                };
+                position >>= 1;
+                self.nodes.insert(position, node);
+            }
+        }
+
+        Ok(())
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a Merkle path to a leaf at the specified `index`.
+    ///
+    /// The leaf itself will be the first element in the path, and the path has the same form as
+    /// the paths returned by [MerkleTree::prove()](super::MerkleTree::prove).
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree, or if the path to the leaf is not included in the partial tree.
+    pub fn prove(&self, index: usize) -> Result<Vec<H::Digest>, MerkleTreeError> {
+        let n = self.num_leaves();
+        if index >= n {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(n, index));
+        }
+
+        let mut position = index + n;
+        let mut path = Vec::with_capacity(self.depth + 1);
+        path.push(self.get_node(index, position)?);
+        while position > 1 {
+            path.push(self.get_node(index, position ^ 1)?);
+            position >>= 1;
+        }
+
+        Ok(path)
+    }
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single
+    /// proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    /// * A path to any of the leaves is not included in the partial tree.
+    pub fn prove_batch(&self, indexes: &[usize]) -> Result<BatchMerkleProof<H>, MerkleTreeError> {
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        if indexes.len() > MAX_PATHS {
+            return Err(MerkleTreeError::TooManyLeafIndexes(
+                MAX_PATHS,
+                indexes.len(),
+            ));
+        }
+        map_indexes(indexes, self.depth)?;
+
+        let paths = indexes
+            .iter()
+            .map(|&index| self.prove(index))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BatchMerkleProof::from_paths(&paths, indexes))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of leaves in the full tree.
+    fn num_leaves(&self) -> usize {
+        1 << self.depth
+    }
+
+    /// Returns the node at the specified position, which is required to build a path to the leaf
+    /// at the specified index.
+    fn get_node(&self, index: usize, position: usize) -> Result<H::Digest, MerkleTreeError> {
+        self.nodes
+            .get(&position)
+            .copied()
+            .ok_or(MerkleTreeError::LeafNotIncluded(index))
+    }
+}
@@ -3,9 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::MerkleTreeError, Hasher};
+use super::{map_indexes, MerkleMultiProof};
+use crate::{errors::MerkleTreeError, Digest, Hasher};
 use utils::{
-    collections::{BTreeMap, Vec},
+    collections::{BTreeMap, BTreeSet, Vec},
     string::ToString,
     ByteReader, Deserializable, DeserializationError, Serializable,
 };
@@ -254,6 +255,17 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 i += 1;
             }
         }
+
+        // make sure all internal nodes included in the proof have been used; otherwise, the same
+        // proof could be encoded in more than one way by appending unused nodes to it
+        if proof_pointers
+            .iter()
+            .zip(self.nodes.iter())
+            .any(|(&pointer, nodes)| pointer != nodes.len())
+        {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
         v.remove(&1).ok_or(MerkleTreeError::InvalidProof)
     }
 
@@ -408,6 +420,58 @@ impl<H: Hasher> BatchMerkleProof<H> {
             .collect()
     }
 
+    /// Converts this proof into a [MerkleMultiProof] for the leaves at the specified `indexes`.
+    ///
+    /// The multi-proof contains the same internal nodes as this proof, but the nodes are arranged
+    /// in the order in which they are consumed by [MerkleMultiProof::get_root()]; the leaves of
+    /// this proof are not included into the multi-proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Any of the conditions described for [BatchMerkleProof::into_paths()] are not satisfied.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree for which this proof was generated.
+    /// * List of indexes contains duplicates.
+    pub fn into_multi_proof(
+        self,
+        indexes: &[usize],
+    ) -> Result<MerkleMultiProof<H>, MerkleTreeError> {
+        let depth = self.depth;
+        map_indexes(indexes, depth as usize)?;
+        let paths = self.into_paths(indexes)?;
+
+        // place all nodes of the paths at their positions in the tree; the first node of each
+        // path is the leaf, and every subsequent node is a sibling of a node on the path
+        let offset = 1usize << depth;
+        let mut tree_nodes = BTreeMap::new();
+        for (&index, path) in indexes.iter().zip(paths.iter()) {
+            let mut position = index + offset;
+            tree_nodes.insert(position, path[0]);
+            for &node in path[1..].iter() {
+                tree_nodes.insert(position ^ 1, node);
+                position >>= 1;
+            }
+        }
+
+        // traverse the tree one level at a time in the same way as MerkleTree::prove_multi()
+        // does, and take sibling nodes which cannot be computed by the verifier from the paths
+        let mut positions: BTreeSet<usize> = indexes.iter().map(|&index| index + offset).collect();
+        let mut nodes = Vec::new();
+        for _ in 0..depth {
+            let mut parents = BTreeSet::new();
+            for &position in positions.iter() {
+                let sibling = position ^ 1;
+                if !positions.contains(&sibling) {
+                    nodes.push(tree_nodes[&sibling]);
+                }
+                parents.insert(position >> 1);
+            }
+            positions = parents;
+        }
+
+        Ok(MerkleMultiProof { nodes, depth })
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
@@ -444,8 +508,11 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// Returns an error if:
     /// * No leaves were provided (i.e., `leaves` is an empty slice).
     /// * Number of provided leaves is greater than 255.
-    /// * Tree `depth` was set to zero.
-    /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
+    /// * Tree `depth` was set to zero, or is too large for a tree of this depth to be represented
+    ///   on the current platform.
+    /// * `node_bytes` could not be deserialized into a valid set of internal nodes; this includes
+    ///   the cases when `node_bytes` specify more node vectors than there are leaves, or more
+    ///   nodes in a vector than the depth of the tree.
     pub fn deserialize<R: ByteReader>(
         node_bytes: &mut R,
         leaves: Vec<H::Digest>,
@@ -456,6 +523,13 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 "tree depth must be greater than zero".to_string(),
             ));
         }
+        if depth as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "tree depth must be smaller than {}, but was {}",
+                usize::BITS,
+                depth
+            )));
+        }
         if leaves.is_empty() {
             return Err(DeserializationError::InvalidValue(
                 "at lease one leaf must be provided".to_string(),
@@ -469,11 +543,25 @@ impl<H: Hasher> BatchMerkleProof<H> {
             )));
         }
 
+        // there is at most one node vector per leaf, and each vector contains at most one node per
+        // level of the tree; these bounds are checked before any nodes are allocated
         let num_node_vectors = node_bytes.read_u8()? as usize;
+        if num_node_vectors > leaves.len() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of node vectors cannot exceed the number of leaves {}, but was {}",
+                leaves.len(),
+                num_node_vectors
+            )));
+        }
         let mut nodes = Vec::with_capacity(num_node_vectors);
         for _ in 0..num_node_vectors {
             // read the number of digests in the vector
             let num_digests = node_bytes.read_u8()? as usize;
+            if num_digests > depth as usize {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "number of nodes in a vector cannot exceed tree depth {depth}, but was {num_digests}"
+                )));
+            }
 
             // read the digests and add them to the node vector
             let digests = H::Digest::read_batch_from(node_bytes, num_digests)?;
@@ -486,11 +574,112 @@ impl<H: Hasher> BatchMerkleProof<H> {
             depth,
         })
     }
+
+    // SIZE ESTIMATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the expected number of distinct leaves opened in a tree of the specified `depth`
+    /// when `num_queries` distinct positions are drawn uniformly at random from the range
+    /// [0, `domain_size`) and every position `p` opens the leaf at index `p mod 2^depth`.
+    ///
+    /// When `domain_size` is equal to 2^`depth`, this is just `num_queries`; a larger domain
+    /// describes the case when query positions are folded into a smaller domain (e.g., as is done
+    /// for FRI layers).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two or is smaller than 2^`depth`.
+    /// * `num_queries` is greater than `domain_size`.
+    pub fn expected_num_leaves(depth: u8, num_queries: usize, domain_size: usize) -> usize {
+        check_estimation_params(depth, num_queries, domain_size);
+        round(expected_num_nodes(1 << depth, num_queries, domain_size))
+    }
+
+    /// Returns the expected number of bytes in the internal nodes of a batch Merkle proof
+    /// serialized via [serialize_nodes()](BatchMerkleProof::serialize_nodes) when leaves are
+    /// opened as described in [expected_num_leaves()](BatchMerkleProof::expected_num_leaves).
+    ///
+    /// The estimate accounts for the internal nodes shared between the opened paths, and thus,
+    /// it is usually within a few percent of the size of an actual proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two or is smaller than 2^`depth`.
+    /// * `num_queries` is greater than `domain_size`.
+    pub fn estimate_serialized_size(depth: u8, num_queries: usize, domain_size: usize) -> usize {
+        check_estimation_params(depth, num_queries, domain_size);
+        if depth == 0 {
+            return 1;
+        }
+
+        // +1 for the number of node vectors, +1 for the length of each node vector; there is one
+        // node vector per every opened pair of sibling leaves
+        let (num_leaf_pairs, num_digests) = expected_num_digests(depth, num_queries, domain_size);
+        round(1.0 + num_leaf_pairs + num_digests * H::Digest::SIZE as f64)
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Makes sure parameters passed into size estimation functions are valid.
+pub(super) fn check_estimation_params(depth: u8, num_queries: usize, domain_size: usize) {
+    assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of two"
+    );
+    assert!(
+        domain_size >> depth > 0,
+        "domain size must be at least 2^{depth}, but was {domain_size}"
+    );
+    assert!(
+        num_queries <= domain_size,
+        "number of queries cannot exceed domain size"
+    );
+}
+
+/// Returns the expected number of opened pairs of sibling leaves and the expected number of
+/// digests in a proof for `num_queries` distinct positions drawn uniformly at random from a tree
+/// of the specified `depth` over a domain of `domain_size` leaves.
+pub(super) fn expected_num_digests(
+    depth: u8,
+    num_queries: usize,
+    domain_size: usize,
+) -> (f64, f64) {
+    // compute expected number of distinct nodes at each level of the tree; a node at level i
+    // is required by the proof if one of its children is required, while a sibling of a
+    // required node must be included into the proof unless it is required too
+    let depth = depth as usize;
+    let num_nodes = (0..=depth)
+        .map(|i| expected_num_nodes(1 << (depth - i), num_queries, domain_size))
+        .collect::<Vec<_>>();
+    let num_digests = (0..depth)
+        .map(|i| 2.0 * num_nodes[i + 1] - num_nodes[i])
+        .sum::<f64>();
+    (num_nodes[1], num_digests)
+}
+
+/// Returns the expected number of distinct residues modulo `num_nodes` among `num_queries`
+/// distinct positions drawn uniformly at random from the range [0, `domain_size`).
+fn expected_num_nodes(num_nodes: usize, num_queries: usize, domain_size: usize) -> f64 {
+    // compute the probability that none of the positions maps into a given residue class; this
+    // is the same as drawing all positions from outside of the class
+    let num_outside = domain_size - domain_size / num_nodes;
+    let p_empty = (0..num_queries).fold(1.0, |acc, i| {
+        if i >= num_outside {
+            0.0
+        } else {
+            acc * (num_outside - i) as f64 / (domain_size - i) as f64
+        }
+    });
+    num_nodes as f64 * (1.0 - p_empty)
+}
+
+/// Rounds a non-negative value to the nearest integer.
+pub(super) fn round(value: f64) -> usize {
+    (value + 0.5) as usize
+}
+
 /// Two nodes are siblings if index of the left node is even and right node
 /// immediately follows the left node.
 fn are_siblings(left: usize, right: usize) -> bool {
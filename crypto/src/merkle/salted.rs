@@ -0,0 +1,231 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BatchMerkleProof, MerkleTree};
+use crate::{errors::MerkleTreeError, hash::Hasher};
+use core::marker::PhantomData;
+use rand_core::{CryptoRng, RngCore};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of random bytes from which each salt is derived.
+const SALT_SEED_BYTES: usize = 32;
+
+// SALTED MERKLE TREE
+// ================================================================================================
+
+/// A fully-balanced Merkle tree in which every leaf is combined with a random salt before it is
+/// placed into the tree.
+///
+/// For each leaf, a salt is derived by hashing 32 bytes drawn from a cryptographically secure
+/// PRNG `R`, and the leaf is replaced with `H::merge(leaf, salt)` when the tree is built. Thus,
+/// the root of the tree (as well as the internal nodes revealed in authentication paths) does not
+/// leak any information about the committed leaves; to open a leaf, the corresponding salt must
+/// be revealed alongside the authentication path.
+///
+/// Apart from the handling of salts, the tree behaves in the same way as a [MerkleTree]: the
+/// number of leaves must be a power of two, and the tree must contain at least two leaves.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{SaltedMerkleTree, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// # use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// // build a tree
+/// let leaves = [
+///     Blake3::hash(&[1u8]),
+///     Blake3::hash(&[2u8]),
+///     Blake3::hash(&[3u8]),
+///     Blake3::hash(&[4u8]),
+/// ];
+/// let mut prng = ChaCha20Rng::from_seed([7; 32]);
+/// let tree = SaltedMerkleTree::<Blake3, _>::new(leaves.to_vec(), &mut prng).unwrap();
+/// assert_eq!(leaves, tree.leaves());
+///
+/// // generate and verify a batch proof; the salts of the opened leaves are returned alongside
+/// // the proof
+/// let (proof, salts) = tree.prove_batch(&[1, 2]).unwrap();
+/// let opened = [leaves[1], leaves[2]];
+/// assert!(SaltedMerkleTree::<Blake3, ChaCha20Rng>::verify_batch(
+///     tree.root(),
+///     &[1, 2],
+///     &opened,
+///     &salts,
+///     &proof
+/// )
+/// .is_ok());
+/// ```
+#[derive(Debug)]
+pub struct SaltedMerkleTree<H: Hasher, R: RngCore + CryptoRng> {
+    tree: MerkleTree<H>,
+    leaves: Vec<H::Digest>,
+    salts: Vec<H::Digest>,
+    _prng: PhantomData<R>,
+}
+
+// SALTED MERKLE TREE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher, R: RngCore + CryptoRng> SaltedMerkleTree<H, R> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new salted Merkle tree built from the provided leaves using hash function `H`;
+    /// salts for the leaves are generated using the provided `prng`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    pub fn new(leaves: Vec<H::Digest>, prng: &mut R) -> Result<Self, MerkleTreeError> {
+        let salts = (0..leaves.len())
+            .map(|_| {
+                let mut seed = [0u8; SALT_SEED_BYTES];
+                prng.fill_bytes(&mut seed);
+                H::hash(&seed)
+            })
+            .collect::<Vec<_>>();
+
+        let salted_leaves = leaves
+            .iter()
+            .zip(salts.iter())
+            .map(|(&leaf, &salt)| Self::salt_leaf(leaf, salt))
+            .collect();
+        let tree = MerkleTree::new(salted_leaves)?;
+
+        Ok(Self {
+            tree,
+            leaves,
+            salts,
+            _prng: PhantomData,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        self.tree.root()
+    }
+
+    /// Returns depth of the tree.
+    ///
+    /// The depth of a tree is zero-based. Thus, a tree with two leaves has depth 1, a tree with
+    /// four leaves has depth 2 etc.
+    pub fn depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Returns leaves of the tree before they were combined with salts.
+    pub fn leaves(&self) -> &[H::Digest] {
+        &self.leaves
+    }
+
+    /// Returns salts of all leaves of the tree.
+    pub fn salts(&self) -> &[H::Digest] {
+        &self.salts
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a Merkle path to a leaf at the specified `index` together with the salt of this
+    /// leaf.
+    ///
+    /// The salted leaf will be the first element in the path.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    pub fn prove(&self, index: usize) -> Result<(Vec<H::Digest>, H::Digest), MerkleTreeError> {
+        let path = self.tree.prove(index)?;
+        Ok((path, self.salts[index]))
+    }
+
+    /// Computes Merkle paths for the provided indexes and compresses the paths into a single
+    /// proof; salts of the leaves at the specified indexes are returned alongside the proof in
+    /// the same order as the indexes.
+    ///
+    /// Leaves of the returned batch proof are salted leaves.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 255.
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    pub fn prove_batch(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(BatchMerkleProof<H>, Vec<H::Digest>), MerkleTreeError> {
+        let proof = self.tree.prove_batch(indexes)?;
+        let salts = indexes.iter().map(|&index| self.salts[index]).collect();
+        Ok((proof, salts))
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the `proof` for the specified `leaf` and `salt` at the specified `index`
+    /// is valid.
+    ///
+    /// # Errors
+    /// Returns an error if the first node of the `proof` is not the salted `leaf`, or if the
+    /// `proof` does not resolve to the specified `root`.
+    pub fn verify(
+        root: H::Digest,
+        index: usize,
+        leaf: H::Digest,
+        salt: H::Digest,
+        proof: &[H::Digest],
+    ) -> Result<(), MerkleTreeError> {
+        if proof.first() != Some(&Self::salt_leaf(leaf, salt)) {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::<H>::verify(root, index, proof)
+    }
+
+    /// Checks whether the batch `proof` contains Merkle paths for the specified `leaves` and
+    /// `salts` located at the specified `indexes`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of leaves or salts is different from the number of indexes.
+    /// * Leaves of the batch proof are not the salted `leaves`.
+    /// * Any of the conditions described for [MerkleTree::verify_batch()] are not satisfied.
+    pub fn verify_batch(
+        root: &H::Digest,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+        salts: &[H::Digest],
+        proof: &BatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if leaves.len() != indexes.len() || salts.len() != indexes.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        let salted_leaves = leaves
+            .iter()
+            .zip(salts.iter())
+            .map(|(&leaf, &salt)| Self::salt_leaf(leaf, salt));
+        if !proof.leaves.iter().cloned().eq(salted_leaves) {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::verify_batch(root, indexes, proof)
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the result of combining the specified `leaf` with the specified `salt`.
+    pub fn salt_leaf(leaf: H::Digest, salt: H::Digest) -> H::Digest {
+        H::merge(&[leaf, salt])
+    }
+}
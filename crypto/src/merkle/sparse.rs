@@ -0,0 +1,485 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::MerkleTreeError, hash::Digest, Hasher};
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::ToString,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum depth of a sparse Merkle tree.
+pub const MAX_SPARSE_TREE_DEPTH: usize = 256;
+
+/// Number of bytes needed to hold a path from the root of a tree of maximum depth to a leaf.
+const PATH_BYTES: usize = MAX_SPARSE_TREE_DEPTH / 8;
+
+/// Byte prepended to the key and the value of a leaf before they are hashed; internal nodes are
+/// computed via [Hasher::merge()], and thus, a leaf can never be interpreted as an internal node.
+const LEAF_DOMAIN: u8 = 0;
+
+// SPARSE MERKLE TREE
+// ================================================================================================
+
+/// A sparse Merkle tree of a fixed depth which maps keys to values.
+///
+/// Both keys and values are digests of the hash function `H`. The position of a key in the tree
+/// is defined by the first `depth` bits of its serialized representation: starting with the most
+/// significant bit of the first byte, each bit selects either the left (0) or the right (1) child
+/// on the path from the root to the leaf. A leaf which holds a key is set to the hash of the
+/// key-value pair (see [hash_leaf()](SparseMerkleTree::hash_leaf)), and all other leaves are set
+/// to the default digest.
+///
+/// Only the nodes which are different from the nodes of an empty tree are stored. Roots of empty
+/// subtrees of every height are computed once when the tree is created, and thus, a tree with
+/// `k` keys takes O(k * depth) memory, and inserting or removing a key requires `depth`
+/// hash computations regardless of the depth of the tree.
+///
+/// Proofs generated by the tree ([SparseMerkleProof]) can be used to prove both membership of a
+/// key-value pair in the tree and non-membership of a key. Siblings which are roots of empty
+/// subtrees are omitted from the proofs.
+///
+/// When the depth of the tree is smaller than the number of bits in a key, several keys may map
+/// to the same leaf; in such cases, only one of these keys can be stored in the tree at a time.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{SparseMerkleTree, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// let mut tree = SparseMerkleTree::<Blake3>::new(256).unwrap();
+/// let key = Blake3::hash(b"key");
+/// let value = Blake3::hash(b"value");
+/// tree.insert(key, value).unwrap();
+/// assert_eq!(Some(value), tree.get(&key));
+///
+/// // prove membership of the inserted key
+/// let proof = tree.prove(&key);
+/// assert!(SparseMerkleTree::<Blake3>::verify(tree.root(), 256, key, Some(value), &proof).is_ok());
+///
+/// // prove non-membership of another key
+/// let other_key = Blake3::hash(b"other key");
+/// let proof = tree.prove(&other_key);
+/// assert!(SparseMerkleTree::<Blake3>::verify(tree.root(), 256, other_key, None, &proof).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseMerkleTree<H: Hasher> {
+    depth: usize,
+    leaves: BTreeMap<NodeIndex, (H::Digest, H::Digest)>,
+    nodes: BTreeMap<NodeIndex, H::Digest>,
+    empty_roots: Vec<H::Digest>,
+}
+
+impl<H: Hasher> SparseMerkleTree<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty sparse Merkle tree of the specified depth.
+    ///
+    /// # Errors
+    /// Returns an error if the depth is zero, greater than 256, or greater than the number of bits
+    /// in the digests of hash function `H`.
+    pub fn new(depth: usize) -> Result<Self, MerkleTreeError> {
+        let max_depth = max_depth::<H>();
+        if depth == 0 || depth > max_depth {
+            return Err(MerkleTreeError::InvalidTreeDepth(max_depth, depth));
+        }
+
+        // the root of an empty subtree of height i is located at position i
+        let mut empty_roots = Vec::with_capacity(depth + 1);
+        empty_roots.push(H::Digest::default());
+        for i in 0..depth {
+            let node = empty_roots[i];
+            empty_roots.push(H::merge(&[node, node]));
+        }
+
+        Ok(Self {
+            depth,
+            leaves: BTreeMap::new(),
+            nodes: BTreeMap::new(),
+            empty_roots,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> H::Digest {
+        self.get_node(&NodeIndex::root(self.depth))
+    }
+
+    /// Returns depth of the tree.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of keys stored in the tree.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns true if the tree does not contain any keys.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Returns the value associated with the specified `key`, or None if the key is not stored in
+    /// the tree.
+    pub fn get(&self, key: &H::Digest) -> Option<H::Digest> {
+        match self.leaves.get(&NodeIndex::leaf(key, self.depth)) {
+            Some((leaf_key, value)) if leaf_key == key => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over all key-value pairs stored in the tree, sorted by the positions
+    /// of the keys in the tree.
+    pub fn entries(&self) -> impl Iterator<Item = &(H::Digest, H::Digest)> {
+        self.leaves.values()
+    }
+
+    // MODIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Associates the specified `value` with the specified `key` and updates all nodes on the
+    /// path from the corresponding leaf to the root. Returns the value which was previously
+    /// associated with the key, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the leaf for the specified key already holds a different key. This can
+    /// happen only if the depth of the tree is smaller than the number of bits in a key.
+    pub fn insert(
+        &mut self,
+        key: H::Digest,
+        value: H::Digest,
+    ) -> Result<Option<H::Digest>, MerkleTreeError> {
+        let index = NodeIndex::leaf(&key, self.depth);
+        let old_value = match self.leaves.get(&index) {
+            Some((leaf_key, _)) if *leaf_key != key => return Err(MerkleTreeError::KeyCollision),
+            Some((_, old_value)) => Some(*old_value),
+            None => None,
+        };
+
+        self.leaves.insert(index, (key, value));
+        self.update_path(index, Self::hash_leaf(&key, &value));
+        Ok(old_value)
+    }
+
+    /// Removes the specified `key` from the tree and updates all nodes on the path from the
+    /// corresponding leaf to the root. Returns the value which was associated with the key, or
+    /// None if the key was not stored in the tree.
+    pub fn remove(&mut self, key: &H::Digest) -> Option<H::Digest> {
+        let index = NodeIndex::leaf(key, self.depth);
+        match self.leaves.get(&index) {
+            Some((leaf_key, _)) if leaf_key == key => {
+                let (_, value) = self.leaves.remove(&index).expect("leaf not found");
+                self.update_path(index, H::Digest::default());
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a proof for the specified `key`.
+    ///
+    /// If the key is stored in the tree, the proof attests to membership of the key together
+    /// with its value. Otherwise, the proof attests to non-membership of the key, in which case
+    /// the proof also contains the key-value pair which occupies the leaf for the key, if any.
+    pub fn prove(&self, key: &H::Digest) -> SparseMerkleProof<H> {
+        let mut index = NodeIndex::leaf(key, self.depth);
+        let leaf = self.leaves.get(&index).copied();
+
+        let mut siblings = Vec::with_capacity(self.depth);
+        for _ in 0..self.depth {
+            siblings.push(self.nodes.get(&index.sibling(self.depth)).copied());
+            index = index.parent(self.depth);
+        }
+
+        SparseMerkleProof { leaf, siblings }
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the `proof` attests to the specified `key` being associated with the
+    /// specified `value` in a tree with the specified `root` and `depth`. If `value` is None,
+    /// checks whether the `proof` attests to non-membership of the `key`.
+    ///
+    /// The depth of the tree must be provided by the caller rather than inferred from the
+    /// `proof`: otherwise, a proof for a shallower tree could pass an internal node off as a leaf.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The specified `depth` is not valid for hash function `H`.
+    /// * The `proof` was generated for a tree of a different depth.
+    /// * The leaf in the `proof` is not consistent with the specified `key` and `value`.
+    /// * The `proof` does not resolve to the specified `root`.
+    pub fn verify(
+        root: H::Digest,
+        depth: usize,
+        key: H::Digest,
+        value: Option<H::Digest>,
+        proof: &SparseMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if depth == 0 || depth > max_depth::<H>() || proof.siblings.len() != depth {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let mut index = NodeIndex::leaf(&key, depth);
+        let mut node = match (value, proof.leaf) {
+            // membership: the leaf must hold the specified key-value pair
+            (Some(value), Some(leaf)) if leaf == (key, value) => Self::hash_leaf(&key, &value),
+            // non-membership: the leaf must be either empty or hold a different key
+            (None, None) => H::Digest::default(),
+            (None, Some((leaf_key, leaf_value)))
+                if leaf_key != key && NodeIndex::leaf(&leaf_key, depth) == index =>
+            {
+                Self::hash_leaf(&leaf_key, &leaf_value)
+            }
+            _ => return Err(MerkleTreeError::InvalidProof),
+        };
+
+        let mut empty_root = H::Digest::default();
+        for sibling in proof.siblings.iter() {
+            let sibling = sibling.unwrap_or(empty_root);
+            node = if index.is_right_child(depth) {
+                H::merge(&[sibling, node])
+            } else {
+                H::merge(&[node, sibling])
+            };
+            empty_root = H::merge(&[empty_root, empty_root]);
+            index = index.parent(depth);
+        }
+
+        if node != root {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Returns the value of a leaf which holds the specified `key` and `value`.
+    ///
+    /// The leaf is computed as a hash of a domain separation byte followed by the serialized key
+    /// and value; thus, unlike internal nodes, leaves are not computed via [Hasher::merge()].
+    pub fn hash_leaf(key: &H::Digest, value: &H::Digest) -> H::Digest {
+        let mut bytes = Vec::with_capacity(1 + 2 * H::Digest::SIZE);
+        bytes.push(LEAF_DOMAIN);
+        bytes.extend_from_slice(key.as_bytes().as_ref());
+        bytes.extend_from_slice(value.as_bytes().as_ref());
+        H::hash(&bytes)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the node at the specified index, or the root of an empty subtree if the node is
+    /// not stored in the tree.
+    fn get_node(&self, index: &NodeIndex) -> H::Digest {
+        match self.nodes.get(index) {
+            Some(&node) => node,
+            None => self.empty_roots[index.height as usize],
+        }
+    }
+
+    /// Sets the node at the specified index; nodes equal to the roots of empty subtrees are
+    /// removed from the tree rather than stored.
+    fn set_node(&mut self, index: NodeIndex, node: H::Digest) {
+        if node == self.empty_roots[index.height as usize] {
+            self.nodes.remove(&index);
+        } else {
+            self.nodes.insert(index, node);
+        }
+    }
+
+    /// Sets the leaf at the specified index and recomputes all nodes on the path from this leaf
+    /// to the root.
+    fn update_path(&mut self, mut index: NodeIndex, leaf: H::Digest) {
+        self.set_node(index, leaf);
+        let mut node = leaf;
+        for _ in 0..self.depth {
+            let sibling = self.get_node(&index.sibling(self.depth));
+            node = if index.is_right_child(self.depth) {
+                H::merge(&[sibling, node])
+            } else {
+                H::merge(&[node, sibling])
+            };
+            index = index.parent(self.depth);
+            self.set_node(index, node);
+        }
+    }
+}
+
+// SPARSE MERKLE PROOF
+// ================================================================================================
+
+/// A proof of membership or non-membership of a key in a [SparseMerkleTree].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMerkleProof<H: Hasher> {
+    /// The key-value pair held by the leaf for the proven key, or None if the leaf is empty.
+    pub leaf: Option<(H::Digest, H::Digest)>,
+    /// Siblings of the nodes on the path from the leaf to the root, starting with the sibling of
+    /// the leaf; None means that the sibling is the root of an empty subtree.
+    pub siblings: Vec<Option<H::Digest>>,
+}
+
+impl<H: Hasher> SparseMerkleProof<H> {
+    /// Returns the depth of the tree for which this proof was generated.
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+}
+
+impl<H: Hasher> Serializable for SparseMerkleProof<H> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// Siblings which are roots of empty subtrees are encoded as a single bit.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(self.depth() as u16);
+        match self.leaf {
+            Some((key, value)) => {
+                target.write_bool(true);
+                key.write_into(target);
+                value.write_into(target);
+            }
+            None => target.write_bool(false),
+        }
+
+        let mut bitmap = [0u8; PATH_BYTES];
+        for (i, sibling) in self.siblings.iter().enumerate() {
+            if sibling.is_some() {
+                bitmap[i / 8] |= 1 << (i % 8);
+            }
+        }
+        target.write_bytes(&bitmap[..(self.depth() + 7) / 8]);
+        for sibling in self.siblings.iter().flatten() {
+            sibling.write_into(target);
+        }
+    }
+}
+
+impl<H: Hasher> Deserializable for SparseMerkleProof<H> {
+    /// Reads a sparse Merkle proof from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Tree depth is zero or is greater than 256.
+    /// * Any of the digests could not be read from the `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let depth = source.read_u16()? as usize;
+        if depth == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "tree depth must be greater than zero".to_string(),
+            ));
+        }
+        if depth > MAX_SPARSE_TREE_DEPTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "tree depth cannot be greater than {MAX_SPARSE_TREE_DEPTH}, but was {depth}"
+            )));
+        }
+
+        let leaf = if source.read_bool()? {
+            Some((H::Digest::read_from(source)?, H::Digest::read_from(source)?))
+        } else {
+            None
+        };
+
+        let bitmap = source.read_vec((depth + 7) / 8)?;
+        let mut siblings = Vec::with_capacity(depth);
+        for i in 0..depth {
+            if bitmap[i / 8] & (1 << (i % 8)) != 0 {
+                siblings.push(Some(H::Digest::read_from(source)?));
+            } else {
+                siblings.push(None);
+            }
+        }
+
+        Ok(Self { leaf, siblings })
+    }
+}
+
+// NODE INDEX
+// ================================================================================================
+
+/// Position of a node in a sparse Merkle tree.
+///
+/// A node is identified by its height above the leaf level and by the path from the root to the
+/// node; bits of the path which are below the node are set to zeros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct NodeIndex {
+    height: u16,
+    path: [u8; PATH_BYTES],
+}
+
+impl NodeIndex {
+    /// Returns the index of the root of a tree of the specified depth.
+    fn root(depth: usize) -> Self {
+        Self {
+            height: depth as u16,
+            path: [0; PATH_BYTES],
+        }
+    }
+
+    /// Returns the index of the leaf for the specified key in a tree of the specified depth.
+    fn leaf<D: Digest>(key: &D, depth: usize) -> Self {
+        let bytes = key.as_bytes();
+        let bytes = bytes.as_ref();
+        let mut path = [0; PATH_BYTES];
+        let num_bytes = (depth + 7) / 8;
+        path[..num_bytes].copy_from_slice(&bytes[..num_bytes]);
+        if depth % 8 != 0 {
+            path[num_bytes - 1] &= 0xff << (8 - depth % 8);
+        }
+        Self { height: 0, path }
+    }
+
+    /// Returns the position of the bit in the path which selects this node among its siblings.
+    fn bit_position(&self, depth: usize) -> usize {
+        depth - 1 - self.height as usize
+    }
+
+    /// Returns true if this node is the right child of its parent.
+    fn is_right_child(&self, depth: usize) -> bool {
+        let bit = self.bit_position(depth);
+        self.path[bit / 8] & (0x80 >> (bit % 8)) != 0
+    }
+
+    /// Returns the index of the sibling of this node.
+    fn sibling(&self, depth: usize) -> Self {
+        let bit = self.bit_position(depth);
+        let mut path = self.path;
+        path[bit / 8] ^= 0x80 >> (bit % 8);
+        Self {
+            height: self.height,
+            path,
+        }
+    }
+
+    /// Returns the index of the parent of this node.
+    fn parent(&self, depth: usize) -> Self {
+        let bit = self.bit_position(depth);
+        let mut path = self.path;
+        path[bit / 8] &= !(0x80 >> (bit % 8));
+        Self {
+            height: self.height + 1,
+            path,
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the maximum depth of a sparse Merkle tree with digests of hash function `H`.
+fn max_depth<H: Hasher>() -> usize {
+    MAX_SPARSE_TREE_DEPTH.min(H::Digest::SIZE * 8)
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+use utils::collections::Vec;
+
+#[cfg(all(feature = "mmap", unix))]
+use super::mmap::MappedSlice;
+
+// NODE STORAGE
+// ================================================================================================
+
+/// Storage for a list of Merkle tree nodes.
+///
+/// Nodes are usually owned by the tree; however, when the `mmap` feature is enabled, the nodes
+/// of a tree loaded via [MerkleTree::load_mmap()](super::MerkleTree::load_mmap) are read directly
+/// from a memory-mapped file. Mapped nodes are read-only: mutable access to mapped nodes copies
+/// them into memory first.
+pub(super) enum NodeStorage<D: Copy> {
+    Owned(Vec<D>),
+    #[cfg(all(feature = "mmap", unix))]
+    Mapped(MappedSlice<D>),
+}
+
+impl<D: Copy> NodeStorage<D> {
+    /// Returns a mutable reference to the nodes as a vector, copying mapped nodes into memory
+    /// if needed.
+    pub fn to_mut(&mut self) -> &mut Vec<D> {
+        #[cfg(all(feature = "mmap", unix))]
+        if let Self::Mapped(nodes) = self {
+            *self = Self::Owned(nodes.to_vec());
+        }
+
+        match self {
+            Self::Owned(nodes) => nodes,
+            #[cfg(all(feature = "mmap", unix))]
+            Self::Mapped(_) => unreachable!(),
+        }
+    }
+}
+
+impl<D: Copy> Deref for NodeStorage<D> {
+    type Target = [D];
+
+    fn deref(&self) -> &[D] {
+        match self {
+            Self::Owned(nodes) => nodes,
+            #[cfg(all(feature = "mmap", unix))]
+            Self::Mapped(nodes) => nodes,
+        }
+    }
+}
+
+impl<D: Copy> DerefMut for NodeStorage<D> {
+    fn deref_mut(&mut self) -> &mut [D] {
+        self.to_mut()
+    }
+}
+
+impl<D: Copy> From<Vec<D>> for NodeStorage<D> {
+    fn from(nodes: Vec<D>) -> Self {
+        Self::Owned(nodes)
+    }
+}
+
+impl<D: Copy + fmt::Debug> fmt::Debug for NodeStorage<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.deref(), f)
+    }
+}
+
+impl<D: Copy + PartialEq> PartialEq for NodeStorage<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
@@ -4,8 +4,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::*;
+use crate::Digest;
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use utils::{Deserializable, DeserializationError, Serializable, SliceReader};
 
 type Digest256 = crate::hash::ByteDigest<32>;
 type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
@@ -91,6 +94,106 @@ fn new_tree() {
     assert_eq!(&root, tree.root());
 }
 
+#[test]
+fn build_merkle_nodes_batched() {
+    type Blake2 = crate::hash::Blake2s_256<BaseElement>;
+
+    // nodes built from entire levels at once should be the same as nodes merged one by one
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let nodes = build_merkle_nodes::<Blake2>(&leaves);
+    for i in 4..8 {
+        let expected = Blake2::merge(&[leaves[(i - 4) * 2], leaves[(i - 4) * 2 + 1]]);
+        assert_eq!(expected, nodes[i]);
+    }
+    for i in 1..4 {
+        assert_eq!(Blake2::merge(&[nodes[i * 2], nodes[i * 2 + 1]]), nodes[i]);
+    }
+}
+
+#[test]
+#[cfg(feature = "concurrent")]
+fn build_parallel() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    for num_threads in [0, 1, 3, 8] {
+        let parallel =
+            MerkleTree::<Blake3_256>::build_parallel(leaves.clone(), num_threads).unwrap();
+        assert_eq!(tree.root(), parallel.root());
+        assert_eq!(tree.nodes, parallel.nodes);
+    }
+
+    assert!(MerkleTree::<Blake3_256>::build_parallel(leaves[..1].to_vec(), 2).is_err());
+    assert!(MerkleTree::<Blake3_256>::build_parallel(leaves[..3].to_vec(), 2).is_err());
+}
+
+#[test]
+fn new_tree_with_arity() {
+    let leaves = (0..16u8)
+        .map(|i| Blake3_256::hash(&[i]))
+        .collect::<Vec<_>>();
+
+    // 4-ary tree
+    let tree = MerkleTree::<Blake3_256, 4>::new(leaves.clone()).unwrap();
+    assert_eq!(2, tree.depth());
+    assert_eq!(&leaves, tree.leaves());
+    let level1 = leaves
+        .chunks(4)
+        .map(Blake3_256::merge_many)
+        .collect::<Vec<_>>();
+    assert_eq!(Blake3_256::merge_many(&level1), *tree.root());
+
+    // 16-ary tree
+    let tree = MerkleTree::<Blake3_256, 16>::new(leaves.clone()).unwrap();
+    assert_eq!(1, tree.depth());
+    assert_eq!(Blake3_256::merge_many(&leaves), *tree.root());
+
+    // a binary tree specified explicitly is the same as the default tree
+    let tree = MerkleTree::<Blake3_256, 2>::new(leaves.clone()).unwrap();
+    assert_eq!(
+        MerkleTree::<Blake3_256>::new(leaves.clone())
+            .unwrap()
+            .root(),
+        tree.root()
+    );
+    assert_eq!(4, tree.depth());
+
+    assert_eq!(
+        Err(MerkleTreeError::TooFewLeaves(8, 4)),
+        MerkleTree::<Blake3_256, 8>::new(leaves[..4].to_vec()).map(|_| ())
+    );
+    assert_eq!(
+        Err(MerkleTreeError::NumberOfLeavesNotPowerOfArity(4, 8)),
+        MerkleTree::<Blake3_256, 4>::new(leaves[..8].to_vec()).map(|_| ())
+    );
+}
+
+#[test]
+fn prove_verify_with_arity() {
+    let leaves = (0..64u8)
+        .map(|i| Blake3_256::hash(&[i]))
+        .collect::<Vec<_>>();
+
+    let tree = MerkleTree::<Blake3_256, 4>::new(leaves.clone()).unwrap();
+    for index in [0, 5, 42, 63] {
+        let proof = tree.prove(index).unwrap();
+        assert_eq!(1 + 3 * 3, proof.len());
+        assert_eq!(leaves[index], proof[0]);
+        assert!(MerkleTree::<Blake3_256, 4>::verify(*tree.root(), index, &proof).is_ok());
+        assert!(MerkleTree::<Blake3_256, 4>::verify(*tree.root(), index ^ 1, &proof).is_err());
+        assert!(MerkleTree::<Blake3_256, 4>::verify(*tree.root(), index, &proof[1..]).is_err());
+    }
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(64, 64)),
+        tree.prove(64)
+    );
+
+    let tree = MerkleTree::<Blake3_256, 8>::new(leaves).unwrap();
+    let proof = tree.prove(19).unwrap();
+    assert_eq!(1 + 2 * 7, proof.len());
+    assert!(MerkleTree::<Blake3_256, 8>::verify(*tree.root(), 19, &proof).is_ok());
+    assert!(MerkleTree::<Blake3_256, 8>::verify(*tree.root(), 20, &proof).is_err());
+}
+
 #[test]
 fn prove() {
     // depth 4
@@ -210,7 +313,7 @@ fn prove_batch() {
 #[test]
 fn verify_batch() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
-    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
 
     let proof = tree.prove_batch(&[1]).unwrap();
     assert!(MerkleTree::verify_batch(tree.root(), &[1], &proof).is_ok());
@@ -230,6 +333,11 @@ fn verify_batch() {
 
     let proof = tree.prove_batch(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
+
+    // proofs with unused internal nodes are rejected
+    let mut proof = tree.prove_batch(&[1, 6]).unwrap();
+    proof.nodes[1].push(leaves[0]);
+    assert!(MerkleTree::verify_batch(tree.root(), &[1, 6], &proof).is_err());
 }
 
 #[test]
@@ -256,6 +364,882 @@ fn verify_into_paths() {
     assert_eq!(proof6, result[2]);
 }
 
+#[test]
+fn deserialize_batch() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let proof = tree.prove_batch(&[1, 6]).unwrap();
+    let bytes = proof.serialize_nodes();
+
+    let deserialize = |bytes: &[u8], depth: u8| {
+        let mut reader = SliceReader::new(bytes);
+        BatchMerkleProof::<Blake3_256>::deserialize(&mut reader, proof.leaves.clone(), depth)
+    };
+    assert!(deserialize(&bytes, proof.depth).unwrap() == proof);
+
+    // depth which cannot be represented on the current platform
+    assert!(deserialize(&bytes, usize::BITS as u8).is_err());
+
+    // more node vectors than leaves
+    let mut invalid = bytes.clone();
+    invalid[0] = proof.leaves.len() as u8 + 1;
+    assert!(deserialize(&invalid, proof.depth).is_err());
+
+    // more nodes in a vector than the depth of the tree
+    let mut invalid = bytes.clone();
+    invalid[1] = proof.depth + 1;
+    assert!(deserialize(&invalid, proof.depth).is_err());
+}
+
+#[test]
+fn prove_multi() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    // 1 index
+    let proof = tree.prove_multi(&[1]).unwrap();
+    let expected_nodes = vec![
+        leaves[0],
+        hash_2x1(leaves[2], leaves[3]),
+        hash_2x1(
+            hash_2x1(leaves[4], leaves[5]),
+            hash_2x1(leaves[6], leaves[7]),
+        ),
+    ];
+    assert_eq!(expected_nodes, proof.nodes);
+    assert_eq!(3, proof.depth);
+
+    // 2 indexes on opposite sides; nodes are ordered level by level
+    let proof = tree.prove_multi(&[6, 1]).unwrap();
+    let expected_nodes = vec![
+        leaves[0],
+        leaves[7],
+        hash_2x1(leaves[2], leaves[3]),
+        hash_2x1(leaves[4], leaves[5]),
+    ];
+    assert_eq!(expected_nodes, proof.nodes);
+
+    // all indexes
+    let proof = tree.prove_multi(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    assert!(proof.nodes.is_empty());
+
+    // invalid indexes
+    assert!(tree.prove_multi(&[]).is_err());
+    assert!(tree.prove_multi(&[8]).is_err());
+    assert!(tree.prove_multi(&[1, 1]).is_err());
+}
+
+#[test]
+fn batch_proof_into_multi_proof() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    // a batch proof contains the same nodes as a multi-proof, regardless of the order of indexes
+    for indexes in [
+        vec![1],
+        vec![6, 1],
+        vec![1, 3, 6],
+        vec![0, 1, 2, 3, 4, 5, 6, 7],
+    ] {
+        let proof = tree.prove_batch(&indexes).unwrap();
+        assert_eq!(
+            tree.prove_multi(&indexes).unwrap(),
+            proof.into_multi_proof(&indexes).unwrap()
+        );
+    }
+
+    let proof = || tree.prove_batch(&[1, 3]).unwrap();
+    assert!(proof().into_multi_proof(&[1]).is_err());
+    assert!(proof().into_multi_proof(&[1, 8]).is_err());
+    assert!(proof().into_multi_proof(&[1, 1]).is_err());
+}
+
+#[test]
+fn verify_multi() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let proof = tree.prove_multi(&[1]).unwrap();
+    assert!(MerkleTree::verify_multi(tree.root(), &[1], &[leaves[1]], &proof).is_ok());
+    assert!(MerkleTree::verify_multi(tree.root(), &[2], &[leaves[1]], &proof).is_err());
+    assert!(MerkleTree::verify_multi(tree.root(), &[1], &[leaves[2]], &proof).is_err());
+
+    // leaves can be provided in any order as long as they match the order of indexes
+    let proof = tree.prove_multi(&[1, 3, 6]).unwrap();
+    let values = [leaves[6], leaves[1], leaves[3]];
+    assert!(MerkleTree::verify_multi(tree.root(), &[6, 1, 3], &values, &proof).is_ok());
+    assert!(MerkleTree::verify_multi(tree.root(), &[1, 3, 6], &values, &proof).is_err());
+    assert!(MerkleTree::verify_multi(tree.root(), &[6, 1], &values[..2], &proof).is_err());
+    assert!(MerkleTree::verify_multi(tree.root(), &[6, 1, 3], &values[..2], &proof).is_err());
+
+    // proofs with missing or unused internal nodes are rejected
+    let mut invalid = tree.prove_multi(&[1, 3, 6]).unwrap();
+    invalid.nodes.pop();
+    assert!(MerkleTree::verify_multi(tree.root(), &[6, 1, 3], &values, &invalid).is_err());
+    let mut invalid = proof;
+    invalid.nodes.push(leaves[0]);
+    assert!(MerkleTree::verify_multi(tree.root(), &[6, 1, 3], &values, &invalid).is_err());
+}
+
+#[test]
+fn deserialize_multi() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let proof = tree.prove_multi(&[1, 6]).unwrap();
+    let bytes = proof.to_bytes();
+    assert!(MerkleMultiProof::read_from_bytes(&bytes).unwrap() == proof);
+
+    // both proofs contain the same 4 internal nodes; the multi-proof records the depth and the
+    // total number of nodes, while the batch proof records the number of nodes in each path
+    let batch_proof = tree.prove_batch(&[1, 6]).unwrap();
+    assert_eq!(1 + 4 + 4 * 32, bytes.len());
+    assert_eq!(1 + 2 + 4 * 32, batch_proof.serialize_nodes().len());
+
+    // depth which cannot be represented on the current platform
+    let mut invalid = bytes.clone();
+    invalid[0] = usize::BITS as u8;
+    assert!(MerkleMultiProof::<Blake3_256>::read_from_bytes(&invalid).is_err());
+
+    // more nodes than present in the source
+    let mut invalid = bytes.clone();
+    invalid[1] += 1;
+    assert!(MerkleMultiProof::<Blake3_256>::read_from_bytes(&invalid).is_err());
+
+    // a node count which cannot fit into memory is rejected before anything is allocated
+    let mut invalid = bytes;
+    invalid[1..5].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        MerkleMultiProof::<Blake3_256>::read_from_bytes(&invalid),
+        Err(DeserializationError::UnexpectedEOF)
+    ));
+}
+
+#[test]
+fn vector_commitment() {
+    type Tree = MerkleTree<Blake3_256>;
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = <Tree as VectorCommitment<Blake3_256>>::commit(leaves.clone()).unwrap();
+    assert_eq!(*tree.root(), tree.commitment());
+    assert_eq!(8, tree.domain_len());
+
+    let (item, proof) = tree.open(3).unwrap();
+    assert_eq!(leaves[3], item);
+    let commitment = tree.commitment();
+    assert!(<Tree as VectorCommitment<_>>::verify(commitment, 3, item, &proof).is_ok());
+    assert!(<Tree as VectorCommitment<_>>::verify(commitment, 3, leaves[2], &proof).is_err());
+
+    let (items, proof) = tree.open_many(&[5, 1, 6]).unwrap();
+    assert_eq!(vec![leaves[5], leaves[1], leaves[6]], items);
+    assert!(Tree::verify_many(commitment, &[5, 1, 6], &items, &proof).is_ok());
+    let invalid = [leaves[5], leaves[1], leaves[7]];
+    assert!(Tree::verify_many(commitment, &[5, 1, 6], &invalid, &proof).is_err());
+
+    // multi-proofs are serialized without the opened items
+    let mut bytes = Vec::new();
+    Tree::write_multi_proof(&proof, &mut bytes);
+    assert_eq!(proof.serialize_nodes(), bytes);
+    let mut reader = SliceReader::new(&bytes);
+    let parsed = Tree::read_multi_proof(&mut reader, items.clone(), 8).unwrap();
+    assert_eq!(proof, parsed);
+}
+
+#[test]
+fn verify_path_free_functions() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    let root = tree.root();
+
+    let proofs = (0..8).map(|i| tree.prove(i).unwrap()).collect::<Vec<_>>();
+    for (i, proof) in proofs.iter().enumerate() {
+        assert!(verify_merkle_path::<Blake3_256>(root, i, proof[0], &proof[1..]).is_ok());
+        assert!(verify_merkle_path::<Blake3_256>(root, i ^ 1, proof[0], &proof[1..]).is_err());
+        assert!(verify_merkle_path::<Blake3_256>(root, i + 8, proof[0], &proof[1..]).is_err());
+        assert!(verify_merkle_path::<Blake3_256>(root, i, proof[0], &proof[2..]).is_err());
+        assert!(verify_merkle_path::<Blake3_256>(root, i, proof[0], &[]).is_err());
+    }
+
+    let indexes = [1, 4, 6];
+    let paths = indexes.map(|i| &proofs[i][1..]);
+    let opened = indexes.map(|i| leaves[i]);
+    assert!(verify_merkle_paths::<Blake3_256>(root, &indexes, &opened, &paths).is_ok());
+    assert!(verify_merkle_paths::<Blake3_256>(root, &[1, 4, 7], &opened, &paths).is_err());
+    assert!(verify_merkle_paths::<Blake3_256>(root, &indexes, &opened[..2], &paths).is_err());
+}
+
+#[test]
+fn verify_multi_proof_free_function() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    let root = tree.root();
+
+    // indexes do not need to be sorted
+    let proof = tree.prove_multi(&[6, 1, 0, 3]).unwrap();
+    let mut indexes = [6, 1, 0, 3];
+    let mut opened = indexes.map(|i| leaves[i]);
+    assert!(verify_merkle_multi_proof::<Blake3_256>(
+        root,
+        3,
+        &mut indexes,
+        &mut opened,
+        &proof.nodes
+    )
+    .is_ok());
+
+    let verify = |indexes: &[usize], opened: &[usize], nodes: &[Digest256]| {
+        let mut indexes = indexes.to_vec();
+        let mut opened = opened.iter().map(|&i| leaves[i]).collect::<Vec<_>>();
+        verify_merkle_multi_proof::<Blake3_256>(root, 3, &mut indexes, &mut opened, nodes)
+    };
+    assert!(verify(&[0, 1, 3, 6], &[0, 1, 3, 6], &proof.nodes).is_ok());
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        verify(&[0, 1, 3, 6], &[0, 1, 3, 7], &proof.nodes)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        verify(&[0, 1, 3, 6], &[0, 1, 3, 6], &proof.nodes[1..])
+    );
+    let mut nodes = proof.nodes.clone();
+    nodes.push(leaves[0]);
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        verify(&[0, 1, 3, 6], &[0, 1, 3, 6], &nodes)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::DuplicateLeafIndex),
+        verify(&[0, 1, 3, 1], &[0, 1, 3, 1], &proof.nodes)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+        verify(&[0, 1, 3, 8], &[0, 1, 3, 6], &proof.nodes)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::TooFewLeafIndexes),
+        verify(&[], &[], &proof.nodes)
+    );
+}
+
+#[test]
+fn update_leaf() {
+    let mut leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let mut tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    leaves[5] = leaves[0];
+    tree.update(5, leaves[0]).unwrap();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_eq!(expected.root(), tree.root());
+    assert_eq!(expected.leaves(), tree.leaves());
+    assert_eq!(expected.prove(4).unwrap(), tree.prove(4).unwrap());
+
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+        tree.update(8, leaves[0])
+    );
+}
+
+#[test]
+fn append_leaf() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let mut tree = MerkleTree::<Blake3_256>::new(leaves[..4].to_vec()).unwrap();
+
+    // appending to a full tree doubles its capacity; unoccupied slots are set to default digests
+    tree.append(leaves[4]);
+    assert_eq!(3, tree.depth());
+    assert_eq!(&leaves[..5], tree.leaves());
+    let mut padded = leaves[..5].to_vec();
+    padded.resize(8, Digest256::default());
+    let expected = MerkleTree::<Blake3_256>::new(padded).unwrap();
+    assert_eq!(expected.root(), tree.root());
+
+    // proofs can be generated only for occupied slots
+    let proof = tree.prove(4).unwrap();
+    assert_eq!(expected.prove(4).unwrap(), proof);
+    assert!(MerkleTree::<Blake3_256>::verify(*tree.root(), 4, &proof).is_ok());
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(5, 5)),
+        tree.prove(5)
+    );
+    let proof = tree.prove_batch(&[1, 4]).unwrap();
+    assert_eq!(expected.prove_batch(&[1, 4]).unwrap(), proof);
+    assert!(MerkleTree::verify_batch(tree.root(), &[1, 4], &proof).is_ok());
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(5, 6)),
+        tree.prove_batch(&[1, 6])
+    );
+    assert!(tree.prove_multi(&[4, 7]).is_err());
+
+    // filling the remaining slots results in the same tree as building from all leaves
+    for &leaf in leaves[5..].iter() {
+        tree.append(leaf);
+    }
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_eq!(expected.root(), tree.root());
+    assert_eq!(expected.depth(), tree.depth());
+    assert_eq!(
+        expected.prove_batch(&[0, 5, 7]),
+        tree.prove_batch(&[0, 5, 7])
+    );
+
+    // appended leaves can also be updated
+    tree.update(6, leaves[0]).unwrap();
+    let mut updated = leaves.clone();
+    updated[6] = leaves[0];
+    let expected = MerkleTree::<Blake3_256>::new(updated).unwrap();
+    assert_eq!(expected.root(), tree.root());
+}
+
+#[test]
+#[cfg(all(feature = "mmap", unix))]
+fn write_and_load_mmap() {
+    let path = temp_file_path("write_and_load_mmap");
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    tree.write_to(&path).unwrap();
+
+    // a loaded tree is the same as the original tree
+    let mut loaded = MerkleTree::<Blake3_256>::load_mmap(&path).unwrap();
+    assert_eq!(tree.root(), loaded.root());
+    assert_eq!(tree.depth(), loaded.depth());
+    assert_eq!(tree.leaves(), loaded.leaves());
+    assert_eq!(tree.prove(5).unwrap(), loaded.prove(5).unwrap());
+    assert_eq!(
+        tree.prove_batch(&[1, 2, 7]).unwrap(),
+        loaded.prove_batch(&[1, 2, 7]).unwrap()
+    );
+
+    // modifying the loaded tree does not modify the file
+    loaded.update(5, leaves[0]).unwrap();
+    loaded.append(leaves[1]);
+    let mut updated = leaves.clone();
+    updated[5] = leaves[0];
+    updated.push(leaves[1]);
+    assert_eq!(&updated[..], loaded.leaves());
+    let reloaded = MerkleTree::<Blake3_256>::load_mmap(&path).unwrap();
+    assert_eq!(tree.root(), reloaded.root());
+
+    // partially filled trees can be written and loaded as well
+    loaded.write_to(&path).unwrap();
+    let reloaded = MerkleTree::<Blake3_256>::load_mmap(&path).unwrap();
+    assert_eq!(loaded.root(), reloaded.root());
+    assert_eq!(loaded.leaves(), reloaded.leaves());
+    assert_eq!(loaded.prove(8).unwrap(), reloaded.prove(8).unwrap());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(all(feature = "mmap", unix))]
+fn load_mmap_invalid_file() {
+    let path = temp_file_path("load_mmap_invalid_file");
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    tree.write_to(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+
+    // a tree cannot be loaded using a hash function with a different digest layout
+    assert!(MerkleTree::<crate::hash::Blake3_192<BaseElement>>::load_mmap(&path).is_err());
+
+    // a tree cannot be loaded using a hash function with the same digest type
+    assert!(MerkleTree::<crate::hash::Sha3_256<BaseElement>>::load_mmap(&path).is_err());
+
+    // truncated file
+    std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    assert!(MerkleTree::<Blake3_256>::load_mmap(&path).is_err());
+
+    // corrupted magic bytes
+    let mut corrupted = bytes.clone();
+    corrupted[0] ^= 1;
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(MerkleTree::<Blake3_256>::load_mmap(&path).is_err());
+
+    // corrupted root
+    let mut corrupted = bytes.clone();
+    corrupted[40] ^= 1;
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(MerkleTree::<Blake3_256>::load_mmap(&path).is_err());
+
+    // empty file
+    std::fs::write(&path, []).unwrap();
+    assert!(MerkleTree::<Blake3_256>::load_mmap(&path).is_err());
+
+    // dimensions for which the size of the nodes overflows
+    let mut corrupted = bytes[..128].to_vec();
+    corrupted[16..24].copy_from_slice(&(1u64 << 59).to_le_bytes());
+    corrupted[24..32].copy_from_slice(&0u64.to_le_bytes());
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(MerkleTree::<Blake3_256>::load_mmap(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn new_salted_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let salted1 = SaltedMerkleTree::<Blake3_256, _>::new(leaves.clone(), &mut prng(1)).unwrap();
+    assert_eq!(leaves, salted1.leaves());
+    assert_eq!(leaves.len(), salted1.salts().len());
+    assert_eq!(tree.depth(), salted1.depth());
+    assert_ne!(tree.root(), salted1.root());
+
+    // salts are derived from the PRNG
+    let salted2 = SaltedMerkleTree::<Blake3_256, _>::new(leaves.clone(), &mut prng(1)).unwrap();
+    assert_eq!(salted1.root(), salted2.root());
+    let salted3 = SaltedMerkleTree::<Blake3_256, _>::new(leaves.clone(), &mut prng(2)).unwrap();
+    assert_ne!(salted1.root(), salted3.root());
+
+    // the tree is built from salted leaves
+    let salted_leaves = leaves
+        .iter()
+        .zip(salted1.salts())
+        .map(|(&leaf, &salt)| hash_2x1(leaf, salt))
+        .collect();
+    let expected = MerkleTree::<Blake3_256>::new(salted_leaves).unwrap();
+    assert_eq!(expected.root(), salted1.root());
+
+    // invalid number of leaves
+    assert!(SaltedMerkleTree::<Blake3_256, _>::new(leaves[..1].to_vec(), &mut prng(1)).is_err());
+    assert!(SaltedMerkleTree::<Blake3_256, _>::new(leaves[..3].to_vec(), &mut prng(1)).is_err());
+}
+
+#[test]
+fn verify_salted() {
+    type SaltedTree = SaltedMerkleTree<Blake3_256, ChaCha20Rng>;
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = SaltedTree::new(leaves.clone(), &mut prng(1)).unwrap();
+    let salts = tree.salts();
+
+    let (path, salt) = tree.prove(5).unwrap();
+    assert_eq!(salts[5], salt);
+    assert!(SaltedTree::verify(*tree.root(), 5, leaves[5], salt, &path).is_ok());
+    assert!(SaltedTree::verify(*tree.root(), 5, leaves[4], salt, &path).is_err());
+    assert!(SaltedTree::verify(*tree.root(), 5, leaves[5], salts[4], &path).is_err());
+
+    let (proof, proof_salts) = tree.prove_batch(&[6, 1, 3]).unwrap();
+    assert_eq!(vec![salts[6], salts[1], salts[3]], proof_salts);
+    let values = [leaves[6], leaves[1], leaves[3]];
+    let root = tree.root();
+    assert!(SaltedTree::verify_batch(root, &[6, 1, 3], &values, &proof_salts, &proof).is_ok());
+
+    // wrong leaves or salts are rejected
+    let invalid = [leaves[6], leaves[1], leaves[2]];
+    assert!(SaltedTree::verify_batch(root, &[6, 1, 3], &invalid, &proof_salts, &proof).is_err());
+    let invalid = [salts[6], salts[1], salts[2]];
+    assert!(SaltedTree::verify_batch(root, &[6, 1, 3], &values, &invalid, &proof).is_err());
+    assert!(SaltedTree::verify_batch(root, &[6, 1], &values[..2], &invalid[..2], &proof).is_err());
+}
+
+#[test]
+fn partial_tree_from_batch_proof() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let proof = tree.prove_batch(&[1, 6]).unwrap();
+    let mut partial =
+        PartialMerkleTree::<Blake3_256>::from_batch_proof(*tree.root(), 3, &[1, 6], &proof)
+            .unwrap();
+    assert_eq!(tree.root(), partial.root());
+    assert_eq!(tree.depth(), partial.depth());
+
+    // paths are available for the opened leaves and their siblings
+    let expected = [0, 1, 6, 7].map(|i| (i, leaves[i]));
+    assert!(partial.leaves().eq(expected));
+    for i in [0, 1, 6, 7] {
+        assert_eq!(Some(leaves[i]), partial.get_leaf(i));
+        assert_eq!(tree.prove(i).unwrap(), partial.prove(i).unwrap());
+    }
+    assert_eq!(None, partial.get_leaf(3));
+    assert_eq!(Err(MerkleTreeError::LeafNotIncluded(3)), partial.prove(3));
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+        partial.prove(8)
+    );
+
+    // batch proofs can be generated for any subset of the included leaves
+    assert_eq!(
+        tree.prove_batch(&[0, 7]).unwrap(),
+        partial.prove_batch(&[0, 7]).unwrap()
+    );
+    assert_eq!(
+        Err(MerkleTreeError::LeafNotIncluded(2)),
+        partial.prove_batch(&[0, 2])
+    );
+
+    // absorbing another proof extends the set of available paths
+    let proof = tree.prove_batch(&[2, 3]).unwrap();
+    partial.add_batch_proof(&[2, 3], &proof).unwrap();
+    assert_eq!(tree.prove(3).unwrap(), partial.prove(3).unwrap());
+    assert_eq!(
+        tree.prove_batch(&[0, 3, 6]).unwrap(),
+        partial.prove_batch(&[0, 3, 6]).unwrap()
+    );
+    assert_eq!(Err(MerkleTreeError::LeafNotIncluded(4)), partial.prove(4));
+}
+
+#[test]
+fn partial_tree_invalid_proof() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    let proof = tree.prove_batch(&[1, 6]).unwrap();
+
+    // proof does not resolve to the root
+    let root = leaves[0];
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        PartialMerkleTree::<Blake3_256>::from_batch_proof(root, 3, &[1, 6], &proof)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        PartialMerkleTree::<Blake3_256>::from_batch_proof(*tree.root(), 3, &[1, 5], &proof)
+    );
+
+    // proof for a different tree cannot be absorbed
+    let mut partial =
+        PartialMerkleTree::<Blake3_256>::from_batch_proof(*tree.root(), 3, &[1, 6], &proof)
+            .unwrap();
+    let other = MerkleTree::<Blake3_256>::new(leaves[..4].to_vec()).unwrap();
+    let other_proof = other.prove_batch(&[2]).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        partial.add_batch_proof(&[2], &other_proof)
+    );
+    let mut leaves = leaves;
+    leaves.swap(2, 3);
+    let other = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let other_proof = other.prove_batch(&[2]).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        partial.add_batch_proof(&[2], &other_proof)
+    );
+    assert_eq!(Err(MerkleTreeError::LeafNotIncluded(2)), partial.prove(2));
+}
+
+#[test]
+fn partial_tree_truncated_proof() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    // a tree built from the parents of the leaves has the same root, and thus, its proofs pass
+    // internal nodes of the original tree off as leaves
+    let parents = (0..4)
+        .map(|i| Blake3_256::merge(&[leaves[2 * i], leaves[2 * i + 1]]))
+        .collect::<Vec<_>>();
+    let truncated = MerkleTree::<Blake3_256>::new(parents).unwrap();
+    assert_eq!(tree.root(), truncated.root());
+    let proof = truncated.prove_batch(&[0, 3]).unwrap();
+    assert_eq!(2, proof.depth);
+
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        PartialMerkleTree::<Blake3_256>::from_batch_proof(*tree.root(), 3, &[0, 3], &proof)
+    );
+    let mut partial = PartialMerkleTree::<Blake3_256>::from_batch_proof(
+        *tree.root(),
+        3,
+        &[1],
+        &tree.prove_batch(&[1]).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        partial.add_batch_proof(&[0, 3], &proof)
+    );
+}
+
+#[test]
+fn sparse_tree_insert_remove() {
+    let mut tree = SparseMerkleTree::<Blake3_256>::new(256).unwrap();
+    let empty_root = tree.root();
+    assert!(tree.is_empty());
+
+    let key1 = Blake3_256::hash(b"key1");
+    let key2 = Blake3_256::hash(b"key2");
+    let value1 = Blake3_256::hash(b"value1");
+    let value2 = Blake3_256::hash(b"value2");
+
+    assert_eq!(Ok(None), tree.insert(key1, value1));
+    assert_eq!(Ok(None), tree.insert(key2, value2));
+    assert_eq!(2, tree.len());
+    assert_eq!(Some(value1), tree.get(&key1));
+    assert_eq!(Some(value2), tree.get(&key2));
+    assert_eq!(None, tree.get(&value1));
+
+    // the root does not depend on the order of insertion
+    let mut other = SparseMerkleTree::<Blake3_256>::new(256).unwrap();
+    other.insert(key2, value2).unwrap();
+    other.insert(key1, value1).unwrap();
+    assert_eq!(tree.root(), other.root());
+
+    // updating a value returns the old value
+    assert_eq!(Ok(Some(value1)), tree.insert(key1, value2));
+    assert_eq!(Some(value2), tree.get(&key1));
+    assert_ne!(other.root(), tree.root());
+
+    // removing all keys results in an empty tree
+    assert_eq!(None, tree.remove(&value1));
+    assert_eq!(Some(value2), tree.remove(&key1));
+    assert_eq!(Some(value2), tree.remove(&key2));
+    assert!(tree.is_empty());
+    assert_eq!(empty_root, tree.root());
+
+    assert_eq!(
+        Err(MerkleTreeError::InvalidTreeDepth(256, 0)),
+        SparseMerkleTree::<Blake3_256>::new(0).map(|_| ())
+    );
+    assert_eq!(
+        Err(MerkleTreeError::InvalidTreeDepth(192, 193)),
+        SparseMerkleTree::<crate::hash::Blake3_192<BaseElement>>::new(193).map(|_| ())
+    );
+}
+
+#[test]
+fn sparse_tree_matches_merkle_tree() {
+    // in a tree of depth 3, the leaf for a key is defined by the top 3 bits of the key
+    let mut tree = SparseMerkleTree::<Blake3_256>::new(3).unwrap();
+    let values = Digest256::bytes_as_digests(&LEAVES8);
+    let mut leaves = vec![Digest256::default(); 8];
+    for i in [1, 2, 6] {
+        let key = sparse_key(i as u8);
+        tree.insert(key, values[i]).unwrap();
+        leaves[i] = SparseMerkleTree::<Blake3_256>::hash_leaf(&key, &values[i]);
+    }
+    let expected = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    assert_eq!(*expected.root(), tree.root());
+
+    // a different key mapping to an occupied leaf cannot be inserted
+    let mut bytes = [1u8; 32];
+    bytes[0] = 2 << 5;
+    let key = Digest256::new(bytes);
+    assert_eq!(
+        Err(MerkleTreeError::KeyCollision),
+        tree.insert(key, values[0])
+    );
+    assert_eq!(None, tree.get(&key));
+    assert_eq!(None, tree.remove(&key));
+    assert_eq!(*expected.root(), tree.root());
+
+    // but non-membership of such a key can be proven
+    let proof = tree.prove(&key);
+    assert_eq!(Some((sparse_key(2), values[2])), proof.leaf);
+    assert!(SparseMerkleTree::<Blake3_256>::verify(tree.root(), 3, key, None, &proof).is_ok());
+    assert!(
+        SparseMerkleTree::<Blake3_256>::verify(tree.root(), 3, sparse_key(2), None, &proof)
+            .is_err()
+    );
+}
+
+#[test]
+fn sparse_tree_prove_verify() {
+    let mut tree = SparseMerkleTree::<Blake3_256>::new(64).unwrap();
+    let keys = (0..10u8)
+        .map(|i| Blake3_256::hash(&[i]))
+        .collect::<Vec<_>>();
+    for (i, &key) in keys.iter().enumerate().take(8) {
+        tree.insert(key, Blake3_256::hash(&[i as u8, 1])).unwrap();
+    }
+    let root = tree.root();
+
+    // membership proofs
+    for (i, key) in keys.iter().enumerate().take(8) {
+        let value = Blake3_256::hash(&[i as u8, 1]);
+        let proof = tree.prove(key);
+        assert_eq!(64, proof.depth());
+        assert!(proof.siblings.iter().filter(|s| s.is_some()).count() < 16);
+        assert!(
+            SparseMerkleTree::<Blake3_256>::verify(root, 64, *key, Some(value), &proof).is_ok()
+        );
+        assert!(
+            SparseMerkleTree::<Blake3_256>::verify(root, 64, *key, Some(keys[9]), &proof).is_err()
+        );
+        assert!(SparseMerkleTree::<Blake3_256>::verify(root, 64, *key, None, &proof).is_err());
+        assert!(
+            SparseMerkleTree::<Blake3_256>::verify(keys[9], 64, *key, Some(value), &proof).is_err()
+        );
+    }
+
+    // non-membership proofs
+    for key in keys[8..].iter() {
+        let proof = tree.prove(key);
+        assert_eq!(None, proof.leaf);
+        assert!(SparseMerkleTree::<Blake3_256>::verify(root, 64, *key, None, &proof).is_ok());
+        assert!(
+            SparseMerkleTree::<Blake3_256>::verify(root, 64, *key, Some(keys[0]), &proof).is_err()
+        );
+        assert!(SparseMerkleTree::<Blake3_256>::verify(root, 64, keys[0], None, &proof).is_err());
+    }
+
+    // serialization
+    let proof = tree.prove(&keys[3]);
+    let bytes = proof.to_bytes();
+    let parsed = SparseMerkleProof::<Blake3_256>::read_from_bytes(&bytes).unwrap();
+    assert_eq!(proof, parsed);
+    let proof = tree.prove(&keys[9]);
+    let parsed = SparseMerkleProof::<Blake3_256>::read_from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(proof, parsed);
+}
+
+#[test]
+fn sparse_tree_rejects_truncated_proofs() {
+    let (tree, nodes) = build_full_sparse_tree();
+    let root = tree.root();
+
+    // a proof for a tree of depth 1 whose leaf holds the children of a child of the root resolves
+    // to the root of the tree when leaves are not domain-separated; such a proof must be rejected
+    // regardless of the key it is used for
+    for forged in build_truncated_sparse_proofs(&nodes) {
+        let (node_left, node_right) = forged.leaf.unwrap();
+        assert_eq!(root, nodes[1]);
+        assert!(nodes[2..4].contains(&Blake3_256::merge(&[node_left, node_right])));
+        for i in 0..8 {
+            let key = sparse_key(i);
+            assert!(SparseMerkleTree::<Blake3_256>::verify(root, 3, key, None, &forged).is_err());
+        }
+        assert!(SparseMerkleTree::<Blake3_256>::verify(
+            root,
+            3,
+            node_left,
+            Some(node_right),
+            &forged
+        )
+        .is_err());
+    }
+
+    // a valid proof with siblings removed is rejected as well
+    let mut proof = tree.prove(&sparse_key(5));
+    assert!(SparseMerkleTree::<Blake3_256>::verify(
+        root,
+        3,
+        sparse_key(5),
+        proof.leaf.map(|l| l.1),
+        &proof
+    )
+    .is_ok());
+    proof.siblings.pop();
+    assert!(SparseMerkleTree::<Blake3_256>::verify(
+        root,
+        3,
+        sparse_key(5),
+        proof.leaf.map(|l| l.1),
+        &proof
+    )
+    .is_err());
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        SparseMerkleTree::<Blake3_256>::verify(root, 0, sparse_key(5), None, &proof)
+    );
+}
+
+#[test]
+fn sparse_tree_rejects_internal_nodes_as_leaves() {
+    let (tree, nodes) = build_full_sparse_tree();
+    let root = tree.root();
+
+    // even if the caller provides a wrong depth, an internal node cannot be passed off as a leaf
+    // because leaves are hashed differently from internal nodes; the forged proof is placed on
+    // the correct side of the root for at least one of the children of the root
+    let mut num_placed = 0;
+    for (i, forged) in build_truncated_sparse_proofs(&nodes).iter().enumerate() {
+        let (node_left, node_right) = forged.leaf.unwrap();
+        if (node_left.as_bytes()[0] >> 7) as usize == i {
+            num_placed += 1;
+        }
+        assert_ne!(
+            nodes[2 + i],
+            SparseMerkleTree::<Blake3_256>::hash_leaf(&node_left, &node_right)
+        );
+        assert!(SparseMerkleTree::<Blake3_256>::verify(
+            root,
+            1,
+            node_left,
+            Some(node_right),
+            forged
+        )
+        .is_err());
+    }
+    assert!(num_placed > 0);
+}
+
+#[test]
+fn batch_proof_size_estimation() {
+    use rand_chacha::rand_core::RngCore;
+
+    let leaves = (0..1024u32)
+        .map(|i| Blake3_256::hash(&i.to_le_bytes()))
+        .collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    // when all leaves are opened, the estimate is exact
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree8 = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let proof = tree8.prove_batch(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    assert_eq!(
+        8,
+        BatchMerkleProof::<Blake3_256>::expected_num_leaves(3, 8, 8)
+    );
+    assert_eq!(
+        proof.serialize_nodes().len(),
+        BatchMerkleProof::<Blake3_256>::estimate_serialized_size(3, 8, 8)
+    );
+
+    // for random query positions, the estimate is close to the average size of actual proofs
+    let mut rng = prng(7);
+    let mut num_bytes = 0;
+    for _ in 0..20 {
+        let mut positions = Vec::new();
+        while positions.len() < 40 {
+            let position = (rng.next_u32() % 4096) as usize;
+            if !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+        let mut indexes = positions.iter().map(|p| p % 1024).collect::<Vec<_>>();
+        indexes.sort_unstable();
+        indexes.dedup();
+        num_bytes += tree.prove_batch(&indexes).unwrap().serialize_nodes().len();
+    }
+    let estimate = BatchMerkleProof::<Blake3_256>::estimate_serialized_size(10, 40, 4096);
+    assert!((estimate as f64 - num_bytes as f64 / 20.0).abs() < estimate as f64 * 0.05);
+    assert_eq!(
+        39,
+        BatchMerkleProof::<Blake3_256>::expected_num_leaves(10, 40, 4096)
+    );
+}
+
+#[test]
+fn multi_proof_size_estimation() {
+    use rand_chacha::rand_core::RngCore;
+
+    // when all leaves are opened, the proof contains no nodes
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree8 = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let proof = tree8.prove_multi(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    assert_eq!(
+        proof.to_bytes().len(),
+        MerkleMultiProof::<Blake3_256>::estimate_serialized_size(3, 8, 8)
+    );
+
+    // for random query positions, the estimate is close to the average size of actual proofs
+    let leaves = (0..1024u32)
+        .map(|i| Blake3_256::hash(&i.to_le_bytes()))
+        .collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let mut rng = prng(7);
+    let mut num_bytes = 0;
+    for _ in 0..20 {
+        let mut indexes = Vec::new();
+        while indexes.len() < 40 {
+            let index = (rng.next_u32() % 1024) as usize;
+            if !indexes.contains(&index) {
+                indexes.push(index);
+            }
+        }
+        num_bytes += tree.prove_multi(&indexes).unwrap().to_bytes().len();
+    }
+    let estimate = MerkleMultiProof::<Blake3_256>::estimate_serialized_size(10, 40, 1024);
+    assert!((estimate as f64 - num_bytes as f64 / 20.0).abs() < estimate as f64 * 0.05);
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
@@ -294,6 +1278,22 @@ proptest! {
         prop_assert!(proof1 == proof2);
     }
 
+    #[test]
+    fn prove_multi_n_verify(tree in random_blake3_merkle_tree(128),
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 10..20)
+    )  {
+        let mut indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(128)).collect();
+        indices.sort_unstable(); indices.dedup();
+        let values: Vec<Digest256> = indices.iter().map(|&idx| tree.leaves()[idx]).collect();
+        let proof = tree.prove_multi(&indices[..]).unwrap();
+        prop_assert!(MerkleTree::verify_multi(tree.root(), &indices[..], &values, &proof).is_ok());
+
+        // a multi-proof contains exactly the internal nodes of the batch proof
+        let batch_proof = tree.prove_batch(&indices[..]).unwrap();
+        let num_batch_nodes: usize = batch_proof.nodes.iter().map(|nodes| nodes.len()).sum();
+        prop_assert_eq!(num_batch_nodes, proof.nodes.len());
+    }
+
     #[test]
     fn into_paths(tree in random_blake3_merkle_tree(32),
                       proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 1..30)
@@ -315,6 +1315,55 @@ proptest! {
 
 // HELPER FUNCTIONS
 // --------------------------------------------------------------------------------------------
+
+#[cfg(all(feature = "mmap", unix))]
+fn temp_file_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("winter-crypto-{}-{}.bin", name, std::process::id()))
+}
+
+/// Returns a sparse tree of depth 3 with all leaves occupied, together with the nodes of the
+/// tree in the same layout as in a [MerkleTree] (the root is located at position 1).
+fn build_full_sparse_tree() -> (SparseMerkleTree<Blake3_256>, Vec<Digest256>) {
+    let mut tree = SparseMerkleTree::<Blake3_256>::new(3).unwrap();
+    let mut nodes = vec![Digest256::default(); 16];
+    for i in 0..8u8 {
+        let key = sparse_key(i);
+        let value = Blake3_256::hash(&[i]);
+        tree.insert(key, value).unwrap();
+        nodes[8 + i as usize] = SparseMerkleTree::<Blake3_256>::hash_leaf(&key, &value);
+    }
+    for i in (1..8).rev() {
+        nodes[i] = Blake3_256::merge(&[nodes[2 * i], nodes[2 * i + 1]]);
+    }
+    assert_eq!(nodes[1], tree.root());
+    (tree, nodes)
+}
+
+/// Returns proofs for a tree of depth 1 which claim that the children of a child of the root
+/// (first of the left child, then of the right child) are a key-value pair.
+fn build_truncated_sparse_proofs(nodes: &[Digest256]) -> [SparseMerkleProof<Blake3_256>; 2] {
+    [
+        SparseMerkleProof {
+            leaf: Some((nodes[4], nodes[5])),
+            siblings: vec![Some(nodes[3])],
+        },
+        SparseMerkleProof {
+            leaf: Some((nodes[6], nodes[7])),
+            siblings: vec![Some(nodes[2])],
+        },
+    ]
+}
+
+/// Returns a key which maps to the leaf at the specified index in a sparse tree of depth 3.
+fn sparse_key(index: u8) -> Digest256 {
+    let mut key = [0u8; 32];
+    key[0] = index << 5;
+    Digest256::new(key)
+}
+
+fn prng(seed: u8) -> ChaCha20Rng {
+    ChaCha20Rng::from_seed([seed; 32])
+}
 fn hash_2x1(v1: Digest256, v2: Digest256) -> Digest256 {
     Blake3_256::merge(&[v1, v2])
 }
@@ -0,0 +1,167 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::MerkleTreeError, Hasher};
+
+// MERKLE PATH VERIFICATION
+// ================================================================================================
+
+/// Checks whether the specified `leaf` is located at the specified `index` in a binary Merkle
+/// tree with the specified `root`.
+///
+/// The `path` contains siblings of the nodes on the path from the leaf to the root, starting with
+/// the sibling of the leaf; thus, a path returned by [MerkleTree::prove()](super::MerkleTree::prove)
+/// can be verified by passing its first element as the `leaf` and the remaining elements as the
+/// `path`. The depth of the tree is defined by the length of the path.
+///
+/// This function does not allocate any memory.
+///
+/// # Errors
+/// Returns an error if the path is empty, if the index is greater than or equal to the number of
+/// leaves in a tree of the depth defined by the path, or if the path does not resolve to the
+/// specified `root`.
+pub fn verify_merkle_path<H: Hasher>(
+    root: &H::Digest,
+    index: usize,
+    leaf: H::Digest,
+    path: &[H::Digest],
+) -> Result<(), MerkleTreeError> {
+    if path.is_empty() || (path.len() < usize::BITS as usize && index >> path.len() != 0) {
+        return Err(MerkleTreeError::InvalidProof);
+    }
+
+    let mut node = leaf;
+    let mut index = index;
+    for &sibling in path {
+        node = if index & 1 == 0 {
+            H::merge(&[node, sibling])
+        } else {
+            H::merge(&[sibling, node])
+        };
+        index >>= 1;
+    }
+
+    if node != *root {
+        return Err(MerkleTreeError::InvalidProof);
+    }
+    Ok(())
+}
+
+/// Checks whether each of the specified `leaves` is located at the corresponding index in a
+/// binary Merkle tree with the specified `root`.
+///
+/// Each of the `paths` is an individual Merkle path for the leaf with the same position in the
+/// `leaves` slice, in the same form as expected by [verify_merkle_path()].
+///
+/// This function does not allocate any memory.
+///
+/// # Errors
+/// Returns an error if the number of leaves or paths is different from the number of indexes,
+/// or if any of the paths is not valid as described for [verify_merkle_path()].
+pub fn verify_merkle_paths<H: Hasher>(
+    root: &H::Digest,
+    indexes: &[usize],
+    leaves: &[H::Digest],
+    paths: &[&[H::Digest]],
+) -> Result<(), MerkleTreeError> {
+    if leaves.len() != indexes.len() || paths.len() != indexes.len() {
+        return Err(MerkleTreeError::InvalidProof);
+    }
+
+    for ((&index, &leaf), path) in indexes.iter().zip(leaves).zip(paths) {
+        verify_merkle_path::<H>(root, index, leaf, path)?;
+    }
+    Ok(())
+}
+
+/// Checks whether the specified `leaves` are located at the specified `indexes` in a binary
+/// Merkle tree of the specified `depth` with the specified `root`.
+///
+/// The `nodes` must be the internal nodes of a multi-proof in the order in which they are stored
+/// in [MerkleMultiProof::nodes](super::MerkleMultiProof::nodes): level by level, starting with the
+/// leaf level, and within each level in the ascending order of node positions. Thus, this function
+/// accepts the same proofs as [MerkleTree::verify_multi()](super::MerkleTree::verify_multi), but
+/// does not require constructing a [MerkleMultiProof](super::MerkleMultiProof) object.
+///
+/// This function does not allocate any memory; instead, the `indexes` and `leaves` slices are
+/// used as working memory, and their contents are overwritten while the proof is verified.
+///
+/// # Errors
+/// Returns an error if:
+/// * No indexes were provided (i.e., `indexes` is an empty slice).
+/// * Number of provided leaves is different from the number of provided indexes.
+/// * Tree depth is zero or is greater than or equal to the number of bits in `usize`.
+/// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+///   tree.
+/// * List of indexes contains duplicates.
+/// * The `nodes` do not resolve to the specified `root`, or some of the nodes were not used.
+pub fn verify_merkle_multi_proof<H: Hasher>(
+    root: &H::Digest,
+    depth: usize,
+    indexes: &mut [usize],
+    leaves: &mut [H::Digest],
+    nodes: &[H::Digest],
+) -> Result<(), MerkleTreeError> {
+    if indexes.is_empty() {
+        return Err(MerkleTreeError::TooFewLeafIndexes);
+    }
+    if indexes.len() != leaves.len() || depth == 0 || depth >= usize::BITS as usize {
+        return Err(MerkleTreeError::InvalidProof);
+    }
+
+    // sort the leaves by their indexes; insertion sort is used so that no memory is allocated
+    for i in 1..indexes.len() {
+        let mut j = i;
+        while j > 0 && indexes[j - 1] > indexes[j] {
+            indexes.swap(j - 1, j);
+            leaves.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+
+    let num_leaves = 1usize << depth;
+    if indexes.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(MerkleTreeError::DuplicateLeafIndex);
+    }
+    if let Some(&index) = indexes.last().filter(|&&index| index >= num_leaves) {
+        return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, index));
+    }
+
+    // compute nodes one level at a time; parents of the known nodes replace the known nodes at
+    // the front of the working slices, and the sibling nodes which cannot be computed from the
+    // known nodes are taken from the proof
+    let mut proof_nodes = nodes.iter();
+    let mut num_known = indexes.len();
+    for _ in 0..depth {
+        let mut num_parents = 0;
+        let mut i = 0;
+        while i < num_known {
+            let (position, node) = (indexes[i], leaves[i]);
+            let pair = if position & 1 == 0 {
+                let sibling = if i + 1 < num_known && indexes[i + 1] == position + 1 {
+                    i += 1;
+                    leaves[i]
+                } else {
+                    *proof_nodes.next().ok_or(MerkleTreeError::InvalidProof)?
+                };
+                [node, sibling]
+            } else {
+                let sibling = *proof_nodes.next().ok_or(MerkleTreeError::InvalidProof)?;
+                [sibling, node]
+            };
+            indexes[num_parents] = position >> 1;
+            leaves[num_parents] = H::merge(&pair);
+            num_parents += 1;
+            i += 1;
+        }
+        num_known = num_parents;
+    }
+
+    // all nodes in the proof must have been consumed
+    if proof_nodes.next().is_some() || leaves[0] != *root {
+        return Err(MerkleTreeError::InvalidProof);
+    }
+    Ok(())
+}
@@ -171,7 +171,7 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     /// ```
     fn leading_zeros(&self) -> u32 {
         let bytes = self.seed.as_bytes();
-        let seed_head = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let seed_head = u64::from_le_bytes(bytes.as_ref()[..8].try_into().unwrap());
         seed_head.trailing_zeros()
     }
 
@@ -180,7 +180,7 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     fn check_leading_zeros(&self, value: u64) -> u32 {
         let new_seed = H::merge_with_int(self.seed, value);
         let bytes = new_seed.as_bytes();
-        let seed_head = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+        let seed_head = u64::from_le_bytes(bytes.as_ref()[..8].try_into().unwrap());
         seed_head.trailing_zeros()
     }
 
@@ -195,8 +195,8 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     fn draw<E: FieldElement>(&mut self) -> Result<E, RandomCoinError> {
         for _ in 0..1000 {
             // get the next pseudo-random value and take the first ELEMENT_BYTES from it
-            let value = self.next();
-            let bytes = &value.as_bytes()[..E::ELEMENT_BYTES];
+            let value = self.next().as_bytes();
+            let bytes = &value.as_ref()[..E::ELEMENT_BYTES];
 
             // check if the bytes can be converted into a valid field element; if they can,
             // return; otherwise try again
@@ -262,7 +262,7 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         let mut values = Vec::new();
         for _ in 0..1000 {
             // get the next pseudo-random value and read the first 8 bytes from it
-            let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
+            let bytes: [u8; 8] = self.next().as_bytes().as_ref()[..8].try_into().unwrap();
 
             // convert to integer and limit the integer to the number of bits which can fit
             // into the specified domain
@@ -287,4 +287,9 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
 
         Ok(values)
     }
+
+    /// Returns the next pseudo-random value hash(`seed` || `counter`) as raw bytes.
+    fn draw_bytes(&mut self) -> Vec<u8> {
+        self.next().as_bytes().as_ref().to_vec()
+    }
 }
@@ -10,6 +10,18 @@ use utils::collections::Vec;
 mod default;
 pub use default::DefaultRandomCoin;
 
+mod sponge;
+pub use sponge::SpongeRandomCoin;
+
+mod rng;
+pub use rng::HashRng;
+
+mod sampler;
+pub use sampler::{QuerySampler, QuerySampling};
+
+#[cfg(feature = "transcript")]
+pub mod transcript;
+
 // RANDOM COIN TRAIT
 // ================================================================================================
 
@@ -70,4 +82,37 @@ pub trait RandomCoin: Sync {
         num_values: usize,
         domain_size: usize,
     ) -> Result<Vec<usize>, RandomCoinError>;
+
+    /// Returns the next pseudo-random value generated by the PRNG of this coin as raw bytes.
+    ///
+    /// This is the value from which [draw_integers()](RandomCoin::draw_integers) derives
+    /// integers; [QuerySampler]s use it to derive query positions in other ways.
+    fn draw_bytes(&mut self) -> Vec<u8>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next pseudo-random element drawn coordinate-wise from the field `E`.
+    ///
+    /// Each of the `E::EXTENSION_DEGREE` coordinates of the returned element (with respect to the
+    /// base field) is drawn independently via [draw()](RandomCoin::draw) instantiated with the
+    /// base field; thus, for every coordinate, pseudo-random values which do not encode a valid
+    /// base field element are rejected, and the coordinate is drawn uniformly from the base
+    /// field. Unlike drawing an extension field element via [draw()](RandomCoin::draw), this
+    /// does not depend on how an encoding of an extension field element is truncated from the
+    /// PRNG output, and matches the way external verifiers usually draw extension field
+    /// elements. For the base field, this method is equivalent to [draw()](RandomCoin::draw).
+    ///
+    /// # Errors
+    /// Returns an error if a valid base field element could not be generated for any of the
+    /// coordinates after 1000 calls to the PRNG.
+    fn draw_ext<E: FieldElement<BaseField = Self::BaseField>>(
+        &mut self,
+    ) -> Result<E, RandomCoinError> {
+        let mut coordinates = Vec::with_capacity(E::EXTENSION_DEGREE);
+        for _ in 0..E::EXTENSION_DEGREE {
+            coordinates.push(self.draw::<Self::BaseField>()?);
+        }
+        Ok(E::slice_from_base_elements(&coordinates)[0])
+    }
 }
@@ -0,0 +1,106 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Digest, Hasher};
+use rand_core::{impls, CryptoRng, Error, RngCore, SeedableRng};
+
+// HASH RNG
+// ================================================================================================
+
+/// Deterministic random number generator built from a [Hasher].
+///
+/// The generator works as follows:
+/// - At instantiation time, the state of the generator is set to hash(`seed`).
+/// - Every block of output is computed as merge_with_int(`state`, 0), after which the state is
+///   ratcheted forward as `state` = merge_with_int(`state`, 1). Thus, the output does not reveal
+///   the state, and the state does not reveal previously generated output.
+/// - Bytes of every block (i.e., of a serialized digest) are consumed in order before the next
+///   block is generated.
+///
+/// This generator implements [RngCore] and [SeedableRng] traits, and thus, can be used wherever
+/// a generic random number generator is expected (e.g., to generate auxiliary trace randomness
+/// or test traces) when the randomness needs to be tied to the hash function used elsewhere in
+/// the protocol. The generator also implements [CryptoRng]; however, its security depends
+/// entirely on the hash function `H`, and the seed must come from a secure source of randomness
+/// if the generated values need to be unpredictable.
+///
+/// # Examples
+/// ```
+/// # use rand_core::{RngCore, SeedableRng};
+/// # use winter_crypto::{HashRng, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// let mut rng1 = HashRng::<Blake3_256<BaseElement>>::from_seed([1; 32]);
+/// let mut rng2 = HashRng::<Blake3_256<BaseElement>>::from_seed([1; 32]);
+///
+/// // generators instantiated with the same seed produce the same values
+/// assert_eq!(rng1.next_u64(), rng2.next_u64());
+///
+/// // values do not depend on how the output is split into requests
+/// let mut bytes1 = [0; 100];
+/// rng1.fill_bytes(&mut bytes1);
+/// let mut bytes2 = [0; 100];
+/// rng2.fill_bytes(&mut bytes2[..37]);
+/// rng2.fill_bytes(&mut bytes2[37..]);
+/// assert_eq!(bytes1, bytes2);
+/// ```
+pub struct HashRng<H: Hasher> {
+    state: H::Digest,
+    block: H::Digest,
+    offset: usize,
+}
+
+impl<H: Hasher> HashRng<H> {
+    /// Generates the next block of output and ratchets the state forward.
+    fn next_block(&mut self) {
+        self.block = H::merge_with_int(self.state, 0);
+        self.state = H::merge_with_int(self.state, 1);
+        self.offset = 0;
+    }
+}
+
+impl<H: Hasher> SeedableRng for HashRng<H> {
+    type Seed = [u8; 32];
+
+    /// Returns a new generator with the state set to hash(`seed`).
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self {
+            state: H::hash(&seed),
+            block: H::Digest::default(),
+            offset: H::Digest::SIZE,
+        }
+    }
+}
+
+impl<H: Hasher> RngCore for HashRng<H> {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.offset == H::Digest::SIZE {
+                self.next_block();
+            }
+            let block = self.block.as_bytes();
+            let available = &block.as_ref()[self.offset..];
+            let num_bytes = available.len().min(dest.len() - filled);
+            dest[filled..filled + num_bytes].copy_from_slice(&available[..num_bytes]);
+            self.offset += num_bytes;
+            filled += num_bytes;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<H: Hasher> CryptoRng for HashRng<H> {}
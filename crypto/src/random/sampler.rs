@@ -0,0 +1,257 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::RandomCoinError, RandomCoin};
+use core::convert::TryInto;
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of pseudo-random values drawn from a coin while sampling query positions.
+const MAX_DRAW_TRIES: usize = 1000;
+
+// QUERY SAMPLER TRAIT
+// ================================================================================================
+
+/// Defines how query positions are derived from a [RandomCoin].
+///
+/// Both the prover and the verifier (including the FRI prover and verifier) draw query positions
+/// via a sampler, and thus, a sampler fully determines which positions are queried for a given
+/// transcript state. Samplers must satisfy the following guarantees:
+/// * Exactly `num_queries` positions are returned, and all returned positions are unique.
+/// * Positions are returned in transcript order: a position derived from an earlier
+///   pseudo-random value precedes positions derived from later values. Thus, an external
+///   verifier which derives positions in the same way can reproduce the returned list exactly.
+pub trait QuerySampler {
+    /// Returns a vector of `num_queries` unique positions selected from the range
+    /// [0, `domain_size`) using pseudo-random values drawn from the `public_coin`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of unique positions could not be drawn after
+    /// 1000 pseudo-random values were drawn from the coin.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_queries` is greater than or equal to `domain_size`.
+    fn draw_positions<R: RandomCoin>(
+        &self,
+        public_coin: &mut R,
+        num_queries: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError>;
+}
+
+// QUERY SAMPLING
+// ================================================================================================
+
+/// Built-in methods for sampling query positions.
+#[repr(u8)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum QuerySampling {
+    /// Positions are drawn via [RandomCoin::draw_integers()]; that is, every position is
+    /// derived from a single pseudo-random value reduced modulo the domain size. This is the
+    /// default sampling method.
+    #[default]
+    Modulo = 0,
+    /// Every pseudo-random value drawn from the coin (see [RandomCoin::draw_bytes()]) is sliced
+    /// into 8-byte words; every word is interpreted as a big-endian integer, and its lower
+    /// log2(`domain_size`) bits are used as a position.
+    ///
+    /// Trailing bytes of a pseudo-random value which do not form a full word are ignored.
+    /// Duplicate positions are skipped, and words are drawn until the requested number of unique
+    /// positions is reached.
+    ///
+    /// Positions drawn in this way have not been checked against positions derived by StarkWare's
+    /// verifier, and thus, should not be assumed to match them.
+    BitSlice = 1,
+}
+
+impl QuerySampler for QuerySampling {
+    fn draw_positions<R: RandomCoin>(
+        &self,
+        public_coin: &mut R,
+        num_queries: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        match self {
+            Self::Modulo => public_coin.draw_integers(num_queries, domain_size),
+            Self::BitSlice => draw_bit_sliced(public_coin, num_queries, domain_size),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws `num_queries` unique positions by slicing pseudo-random values drawn from the coin into
+/// 8-byte big-endian words.
+fn draw_bit_sliced<R: RandomCoin>(
+    public_coin: &mut R,
+    num_queries: usize,
+    domain_size: usize,
+) -> Result<Vec<usize>, RandomCoinError> {
+    assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of two"
+    );
+    assert!(
+        num_queries < domain_size,
+        "number of values must be smaller than domain size"
+    );
+
+    let v_mask = (domain_size - 1) as u64;
+
+    // trailing bytes of a pseudo-random value which do not form a full word are ignored;
+    // positions are kept in the order in which they were drawn
+    let mut positions = Vec::with_capacity(num_queries);
+    for _ in 0..MAX_DRAW_TRIES {
+        for word in public_coin.draw_bytes().chunks_exact(8) {
+            let position = (u64::from_be_bytes(word.try_into().unwrap()) & v_mask) as usize;
+            if positions.len() < num_queries && !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+        if positions.len() == num_queries {
+            return Ok(positions);
+        }
+    }
+
+    Err(RandomCoinError::FailedToDrawIntegers(
+        num_queries,
+        positions.len(),
+        MAX_DRAW_TRIES,
+    ))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{QuerySampler, QuerySampling};
+    use crate::{errors::RandomCoinError, hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
+    use math::{fields::f64::BaseElement, FieldElement};
+    use utils::collections::Vec;
+
+    #[test]
+    fn modulo_sampling() {
+        let seed = [BaseElement::ONE];
+        let mut coin = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(&seed);
+        let positions = QuerySampling::Modulo
+            .draw_positions(&mut coin, 20, 64)
+            .unwrap();
+
+        let mut coin = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(&seed);
+        assert_eq!(coin.draw_integers(20, 64).unwrap(), positions);
+    }
+
+    #[test]
+    fn bit_slice_sampling() {
+        // words are big-endian, and only their lower 4 bits are used for a domain of size 16;
+        // the duplicates 3 and 5 are skipped, and so are the trailing 4 bytes of the second
+        // value (which would have resulted in position 14)
+        let mut coin = BytesCoin::new(vec![
+            vec![
+                0, 0, 0, 0, 0, 0, 0, 0x03, //
+                0xff, 0, 0, 0, 0, 0, 0, 0x15, //
+                0, 0, 0, 0, 0, 0, 0, 0x23, //
+                0, 0, 0, 0, 0, 0, 0x01, 0x0a,
+            ],
+            vec![
+                0, 0, 0, 0, 0, 0, 0, 0x05, //
+                0, 0, 0, 0, 0, 0, 0, 0x07, //
+                0, 0, 0, 0x0e,
+            ],
+            vec![0, 0, 0, 0, 0, 0, 0, 0x01, 0, 0, 0, 0, 0, 0, 0, 0x02],
+        ]);
+        let positions = QuerySampling::BitSlice
+            .draw_positions(&mut coin, 5, 16)
+            .unwrap();
+        assert_eq!(vec![3, 5, 10, 7, 1], positions);
+        assert_eq!(3, coin.num_draws);
+
+        // words left over after the requested number of positions is reached are discarded
+        let positions = QuerySampling::BitSlice
+            .draw_positions(&mut coin, 2, 1 << 20)
+            .unwrap();
+        assert_eq!(vec![0x03, 0x15], positions);
+        assert_eq!(4, coin.num_draws);
+    }
+
+    #[test]
+    fn bit_slice_sampling_fails_without_enough_unique_positions() {
+        let mut coin = BytesCoin::new(vec![vec![0; 16]]);
+        assert_eq!(
+            Err(RandomCoinError::FailedToDrawIntegers(2, 1, 1000)),
+            QuerySampling::BitSlice.draw_positions(&mut coin, 2, 16)
+        );
+        assert_eq!(1000, coin.num_draws);
+    }
+
+    // BYTES COIN
+    // --------------------------------------------------------------------------------------------
+
+    /// A coin which returns the specified values from draw_bytes() in a cycle.
+    struct BytesCoin {
+        values: Vec<Vec<u8>>,
+        num_draws: usize,
+    }
+
+    impl BytesCoin {
+        fn new(values: Vec<Vec<u8>>) -> Self {
+            Self {
+                values,
+                num_draws: 0,
+            }
+        }
+    }
+
+    impl RandomCoin for BytesCoin {
+        type BaseField = BaseElement;
+        type Hasher = Blake3_256<BaseElement>;
+
+        fn new(_seed: &[Self::BaseField]) -> Self {
+            unimplemented!()
+        }
+
+        fn reseed(&mut self, _data: <Self::Hasher as crate::Hasher>::Digest) {
+            unimplemented!()
+        }
+
+        fn reseed_with_int(&mut self, _value: u64) {
+            unimplemented!()
+        }
+
+        fn leading_zeros(&self) -> u32 {
+            unimplemented!()
+        }
+
+        fn check_leading_zeros(&self, _value: u64) -> u32 {
+            unimplemented!()
+        }
+
+        fn draw<E: FieldElement<BaseField = Self::BaseField>>(
+            &mut self,
+        ) -> Result<E, RandomCoinError> {
+            unimplemented!()
+        }
+
+        fn draw_integers(
+            &mut self,
+            _num_values: usize,
+            _domain_size: usize,
+        ) -> Result<Vec<usize>, RandomCoinError> {
+            unimplemented!()
+        }
+
+        fn draw_bytes(&mut self) -> Vec<u8> {
+            let value = self.values[self.num_draws % self.values.len()].clone();
+            self.num_draws += 1;
+            value
+        }
+    }
+}
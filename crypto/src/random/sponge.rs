@@ -0,0 +1,190 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{errors::RandomCoinError, Hasher, RandomCoin, Sponge};
+use core::convert::TryInto;
+use math::FieldElement;
+use utils::{collections::Vec, Serializable};
+
+// SPONGE RANDOM COIN IMPLEMENTATION
+// ================================================================================================
+
+/// Pseudo-random element generator for finite fields, which is instantiated with a duplex
+/// [Sponge] rather than with a [Hasher].
+///
+/// The coin works roughly as follows:
+/// - At instantiation time, the provided seed is absorbed into a new sponge.
+/// - Reseeding absorbs the provided digest (or value) into the sponge. Integer values are absorbed
+///   as two field elements, each containing 32 bits of the value.
+/// - Field elements are drawn by squeezing as many base field elements from the sponge as
+///   needed to build an element of the requested field. Since squeezed elements are always valid
+///   field elements, drawing never fails.
+/// - Integers are drawn by squeezing base field elements and taking the lower bits of their
+///   canonical representation.
+///
+/// Thus, unlike [DefaultRandomCoin](crate::DefaultRandomCoin), this coin does not need to invoke
+/// [merge_with_int()](Hasher::merge_with_int) to draw every value, and all transcript operations
+/// are performed natively over the base field.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{RandomCoin, SpongeRandomCoin, DuplexSponge, hashers::Rp64_256};
+/// # use math::fields::f64::BaseElement;
+/// // initial elements for seeding the random coin
+/// let seed = &[BaseElement::new(1), BaseElement::new(2), BaseElement::new(3), BaseElement::new(4)];
+///
+/// // instantiate a random coin using a sponge based on Rescue Prime permutation
+/// let mut coin = SpongeRandomCoin::<DuplexSponge<Rp64_256>>::new(seed);
+///
+/// // should draw different elements each time
+/// let e1 = coin.draw::<BaseElement>().unwrap();
+/// let e2 = coin.draw::<BaseElement>().unwrap();
+/// assert_ne!(e1, e2);
+///
+/// // should draw same elements for the same seed
+/// let mut coin1 = SpongeRandomCoin::<DuplexSponge<Rp64_256>>::new(seed);
+/// let mut coin2 = SpongeRandomCoin::<DuplexSponge<Rp64_256>>::new(seed);
+/// assert_eq!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+///
+/// // after reseeding should draw different elements
+/// coin2.reseed_with_int(42);
+/// assert_ne!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+/// ```
+pub struct SpongeRandomCoin<S: Sponge> {
+    sponge: S,
+}
+
+impl<S: Sponge> SpongeRandomCoin<S> {
+    /// Squeezes the next base field element from the sponge and returns the first 8 bytes of its
+    /// canonical representation as an integer.
+    fn next_u64(sponge: &mut S) -> u64 {
+        let element = sponge.squeeze(1)[0];
+        let bytes = element.to_bytes();
+        u64::from_le_bytes(bytes[..8].try_into().unwrap())
+    }
+}
+
+impl<S: Sponge> RandomCoin for SpongeRandomCoin<S> {
+    type BaseField = S::BaseField;
+    type Hasher = S::Hasher;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new random coin instantiated with the provided `seed`.
+    fn new(seed: &[Self::BaseField]) -> Self {
+        let mut sponge = S::new();
+        sponge.absorb(seed);
+        Self { sponge }
+    }
+
+    // RESEEDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Reseeds the coin by absorbing the specified digest into the sponge.
+    fn reseed(&mut self, data: <S::Hasher as Hasher>::Digest) {
+        self.sponge.absorb_digest(&data);
+    }
+
+    /// Reseeds the coin by absorbing the specified value into the sponge.
+    fn reseed_with_int(&mut self, value: u64) {
+        let elements = [
+            Self::BaseField::from(value as u32),
+            Self::BaseField::from((value >> 32) as u32),
+        ];
+        self.sponge.absorb(&elements);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of trailing zeros in the next element which would be squeezed from the
+    /// sponge; the state of the coin is not affected.
+    fn leading_zeros(&self) -> u32 {
+        let mut sponge = self.sponge.clone();
+        Self::next_u64(&mut sponge).trailing_zeros()
+    }
+
+    /// Returns the number of leading zeros the coin would have after being reseeded with the
+    /// specified `value`; the state of the coin is not affected.
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        let mut coin = Self {
+            sponge: self.sponge.clone(),
+        };
+        coin.reseed_with_int(value);
+        coin.leading_zeros()
+    }
+
+    // DRAW METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next pseudo-random field element.
+    ///
+    /// This function never returns an error since squeezed base field elements can always be
+    /// used to build an element of the requested field.
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let elements = self.sponge.squeeze(E::EXTENSION_DEGREE);
+        Ok(E::slice_from_base_elements(&elements)[0])
+    }
+
+    /// Returns a vector of unique integers selected from the range [0, domain_size).
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of unique integers could not be generated
+    /// after 1000 elements were squeezed from the sponge.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_values` is greater than or equal to `domain_size`.
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+        assert!(
+            num_values < domain_size,
+            "number of values must be smaller than domain size"
+        );
+
+        // determine how many bits are needed to represent valid values in the domain
+        let v_mask = (domain_size - 1) as u64;
+
+        // squeeze elements from the sponge until we get as many unique values as specified by
+        // num_values
+        let mut values = Vec::new();
+        for _ in 0..1000 {
+            let value = (Self::next_u64(&mut self.sponge) & v_mask) as usize;
+
+            if values.contains(&value) {
+                continue;
+            }
+            values.push(value);
+            if values.len() == num_values {
+                break;
+            }
+        }
+
+        if values.len() < num_values {
+            return Err(RandomCoinError::FailedToDrawIntegers(
+                num_values,
+                values.len(),
+                1000,
+            ));
+        }
+
+        Ok(values)
+    }
+
+    /// Squeezes the next base field element from the sponge and returns its canonical
+    /// representation as raw bytes.
+    fn draw_bytes(&mut self) -> Vec<u8> {
+        self.sponge.squeeze(1)[0].to_bytes()
+    }
+}
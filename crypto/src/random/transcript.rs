@@ -0,0 +1,447 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Recording of Fiat-Shamir transcripts for debugging.
+//!
+//! This module is available only when `transcript` feature is enabled. To record a transcript,
+//! wrap the random coin used by the prover or the verifier into a [RecordingRandomCoin] (e.g.,
+//! use `RecordingRandomCoin<DefaultRandomCoin<H>>` instead of `DefaultRandomCoin<H>`), and
+//! execute proof generation or verification via the [record()] function:
+//!
+//! ```ignore
+//! let (proof, transcript) = transcript::record(|| prover.prove(trace));
+//! ```
+//!
+//! The resulting [Transcript] contains an entry for every operation which affects the state of
+//! the coin or draws values from it. Two transcripts (e.g., one recorded during proof generation
+//! and one recorded during verification, possibly by an external verifier) can be compared via
+//! [Transcript::find_divergence()] to locate the first operation at which they differ.
+
+use crate::{Hasher, RandomCoin, RandomCoinError};
+use core::{cell::RefCell, fmt};
+use math::FieldElement;
+use utils::{
+    collections::Vec,
+    hex,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// RECORDER
+// ================================================================================================
+
+std::thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Transcript being recorded on the current thread.
+#[derive(Default)]
+struct Recorder {
+    phase: String,
+    entries: Vec<TranscriptEntry>,
+}
+
+/// Restores the previous recorder of the current thread when dropped; this ensures that a panic
+/// during recording does not leave the recorder in place.
+struct RecorderGuard(Option<Option<Recorder>>);
+
+impl RecorderGuard {
+    fn finish(mut self) -> Recorder {
+        let previous = self.0.take().expect("previous recorder already restored");
+        RECORDER
+            .with(|recorder| recorder.replace(previous))
+            .expect("transcript recorder is missing")
+    }
+}
+
+impl Drop for RecorderGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            RECORDER.with(|recorder| recorder.replace(previous));
+        }
+    }
+}
+
+/// Executes `f` and returns its result together with the transcript of all operations executed
+/// by [RecordingRandomCoin] instances on the current thread while `f` was running.
+///
+/// Nested invocations are supported: operations executed within an inner invocation are
+/// recorded only into the transcript returned by that invocation.
+pub fn record<T, F: FnOnce() -> T>(f: F) -> (T, Transcript) {
+    let previous = RECORDER.with(|recorder| recorder.replace(Some(Recorder::default())));
+    let guard = RecorderGuard(Some(previous));
+    let result = f();
+    let recorder = guard.finish();
+    (
+        result,
+        Transcript {
+            entries: recorder.entries,
+        },
+    )
+}
+
+/// Sets the label of the protocol phase for all subsequently recorded operations on the current
+/// thread.
+///
+/// This is a no-op if no transcript is being recorded on the current thread.
+pub fn set_phase(phase: &str) {
+    RECORDER.with(|recorder| {
+        if let Some(recorder) = recorder.borrow_mut().as_mut() {
+            recorder.phase = phase.to_string();
+        }
+    });
+}
+
+/// Appends an entry to the transcript being recorded on the current thread (if any).
+fn record_operation(operation: TranscriptOperation, input: Vec<u8>, output: Vec<u8>) {
+    RECORDER.with(|recorder| {
+        if let Some(recorder) = recorder.borrow_mut().as_mut() {
+            let phase = recorder.phase.clone();
+            recorder.entries.push(TranscriptEntry {
+                phase,
+                operation,
+                input,
+                output,
+            });
+        }
+    });
+}
+
+// RECORDING RANDOM COIN
+// ================================================================================================
+
+/// A random coin which records all operations executed against the wrapped coin `R`.
+///
+/// Operations are recorded only while [record()] is being executed on the current thread;
+/// otherwise, the coin behaves exactly as the wrapped coin. [RandomCoin::leading_zeros()] and
+/// [RandomCoin::check_leading_zeros()] are not recorded since they do not affect the state of
+/// the coin (the prover invokes the latter once for every candidate proof-of-work nonce).
+pub struct RecordingRandomCoin<R: RandomCoin>(R);
+
+impl<R: RandomCoin> RecordingRandomCoin<R> {
+    /// Returns a reference to the wrapped random coin.
+    pub fn inner(&self) -> &R {
+        &self.0
+    }
+}
+
+impl<R: RandomCoin> RandomCoin for RecordingRandomCoin<R> {
+    type BaseField = R::BaseField;
+    type Hasher = R::Hasher;
+
+    fn new(seed: &[Self::BaseField]) -> Self {
+        let mut input = Vec::new();
+        Self::BaseField::write_batch_into(seed, &mut input);
+        record_operation(TranscriptOperation::Init, input, Vec::new());
+        Self(R::new(seed))
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        record_operation(TranscriptOperation::Reseed, data.to_bytes(), Vec::new());
+        self.0.reseed(data);
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        let input = value.to_le_bytes().to_vec();
+        record_operation(TranscriptOperation::ReseedWithInt, input, Vec::new());
+        self.0.reseed_with_int(value);
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        self.0.leading_zeros()
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.0.check_leading_zeros(value)
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let result = self.0.draw::<E>();
+        let output = match &result {
+            Ok(value) => value.to_bytes(),
+            Err(_) => Vec::new(),
+        };
+        record_operation(TranscriptOperation::Draw, Vec::new(), output);
+        result
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let result = self.0.draw_integers(num_values, domain_size);
+
+        let mut input = Vec::with_capacity(16);
+        input.write_u64(num_values as u64);
+        input.write_u64(domain_size as u64);
+        let mut output = Vec::new();
+        if let Ok(values) = &result {
+            values
+                .iter()
+                .for_each(|&value| output.write_u64(value as u64));
+        }
+        record_operation(TranscriptOperation::DrawIntegers, input, output);
+
+        result
+    }
+
+    fn draw_bytes(&mut self) -> Vec<u8> {
+        let bytes = self.0.draw_bytes();
+        record_operation(TranscriptOperation::DrawBytes, Vec::new(), bytes.clone());
+        bytes
+    }
+}
+
+// TRANSCRIPT
+// ================================================================================================
+
+/// Operations executed against a random coin which are recorded in a [Transcript].
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TranscriptOperation {
+    /// Instantiation of the coin; the input is the serialized seed.
+    Init = 0,
+    /// Reseeding of the coin with a digest; the input is the serialized digest.
+    Reseed = 1,
+    /// Reseeding of the coin with an integer; the input is the integer in little-endian byte
+    /// order.
+    ReseedWithInt = 2,
+    /// Drawing of a field element; the output is the serialized field element.
+    Draw = 3,
+    /// Drawing of a set of integers; the input is the number of values and the domain size, and
+    /// the output contains the drawn integers (all as 8-byte little-endian integers).
+    DrawIntegers = 4,
+    /// Drawing of a raw pseudo-random value; the output contains the drawn bytes.
+    DrawBytes = 5,
+}
+
+impl fmt::Display for TranscriptOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Init => write!(f, "init"),
+            Self::Reseed => write!(f, "reseed"),
+            Self::ReseedWithInt => write!(f, "reseed_with_int"),
+            Self::Draw => write!(f, "draw"),
+            Self::DrawIntegers => write!(f, "draw_integers"),
+            Self::DrawBytes => write!(f, "draw_bytes"),
+        }
+    }
+}
+
+/// A single operation recorded in a [Transcript].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TranscriptEntry {
+    /// Label of the protocol phase during which the operation was executed (see [set_phase()]);
+    /// empty if no phase has been set.
+    pub phase: String,
+    /// The executed operation.
+    pub operation: TranscriptOperation,
+    /// Bytes absorbed by the coin or passed to the operation as parameters.
+    pub input: Vec<u8>,
+    /// Bytes of the values drawn from the coin; empty for operations which do not draw values
+    /// and for draws which failed.
+    pub output: Vec<u8>,
+}
+
+/// A log of all operations executed against a random coin.
+///
+/// The log can be serialized into a sequence of bytes via [Serializable] trait, or printed in
+/// a human-readable form (one line per operation) via [fmt::Display] trait.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Transcript {
+    entries: Vec<TranscriptEntry>,
+}
+
+impl Transcript {
+    /// Returns all operations recorded in this transcript in the order in which they were
+    /// executed.
+    pub fn entries(&self) -> &[TranscriptEntry] {
+        &self.entries
+    }
+
+    /// Returns the index of the first operation at which this transcript differs from the
+    /// `other` transcript, or `None` if the transcripts are the same.
+    ///
+    /// Phase labels are ignored for the purposes of the comparison. If one transcript is a
+    /// prefix of the other, the length of the shorter transcript is returned.
+    pub fn find_divergence(&self, other: &Transcript) -> Option<usize> {
+        let idx = self
+            .entries
+            .iter()
+            .zip(other.entries.iter())
+            .position(|(a, b)| {
+                a.operation != b.operation || a.input != b.input || a.output != b.output
+            });
+
+        match idx {
+            Some(idx) => Some(idx),
+            None if self.entries.len() != other.entries.len() => {
+                Some(self.entries.len().min(other.entries.len()))
+            }
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if !entry.phase.is_empty() {
+                write!(f, "{i}: [{}] ", entry.phase)?;
+            } else {
+                write!(f, "{i}: ")?;
+            }
+            write!(f, "{}({})", entry.operation, hex::encode(&entry.input))?;
+            if !entry.output.is_empty() {
+                write!(f, " -> {}", hex::encode(&entry.output))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serializable for Transcript {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.entries.len() as u32);
+        for entry in self.entries.iter() {
+            target.write_u16(entry.phase.len() as u16);
+            target.write_bytes(entry.phase.as_bytes());
+            target.write_u8(entry.operation as u8);
+            target.write_u32(entry.input.len() as u32);
+            target.write_bytes(&entry.input);
+            target.write_u32(entry.output.len() as u32);
+            target.write_bytes(&entry.output);
+        }
+    }
+}
+
+impl Deserializable for Transcript {
+    /// Reads a transcript from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid transcript could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_entries = source.read_u32()? as usize;
+        let mut entries = Vec::new();
+        for _ in 0..num_entries {
+            let phase_len = source.read_u16()? as usize;
+            let phase = String::from_utf8(source.read_vec(phase_len)?).map_err(|_| {
+                DeserializationError::InvalidValue("phase label is not valid UTF-8".to_string())
+            })?;
+            let operation = match source.read_u8()? {
+                0 => TranscriptOperation::Init,
+                1 => TranscriptOperation::Reseed,
+                2 => TranscriptOperation::ReseedWithInt,
+                3 => TranscriptOperation::Draw,
+                4 => TranscriptOperation::DrawIntegers,
+                5 => TranscriptOperation::DrawBytes,
+                value => {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "value {value} cannot be deserialized as a transcript operation"
+                    )))
+                }
+            };
+            let input_len = source.read_u32()? as usize;
+            let input = source.read_vec(input_len)?;
+            let output_len = source.read_u32()? as usize;
+            let output = source.read_vec(output_len)?;
+            entries.push(TranscriptEntry {
+                phase,
+                operation,
+                input,
+                output,
+            });
+        }
+        Ok(Transcript { entries })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{record, set_phase, RecordingRandomCoin, Transcript, TranscriptOperation};
+    use crate::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin};
+    use math::fields::f128::BaseElement;
+    use utils::{Deserializable, Serializable};
+
+    type Coin = RecordingRandomCoin<DefaultRandomCoin<Blake3_256<BaseElement>>>;
+
+    fn run_protocol(nonce: u64) -> (Vec<usize>, Transcript) {
+        record(|| {
+            set_phase("init");
+            let mut coin = Coin::new(&[BaseElement::new(1), BaseElement::new(2)]);
+            set_phase("commit");
+            coin.reseed(Blake3_256::<BaseElement>::hash(b"commitment"));
+            let _: BaseElement = coin.draw().unwrap();
+            set_phase("query");
+            coin.reseed_with_int(nonce);
+            coin.check_leading_zeros(123);
+            coin.draw_integers(4, 64).unwrap()
+        })
+    }
+
+    #[test]
+    fn record_transcript() {
+        let (positions, transcript) = run_protocol(42);
+        let operations = transcript
+            .entries()
+            .iter()
+            .map(|entry| (entry.phase.as_str(), entry.operation))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("init", TranscriptOperation::Init),
+                ("commit", TranscriptOperation::Reseed),
+                ("commit", TranscriptOperation::Draw),
+                ("query", TranscriptOperation::ReseedWithInt),
+                ("query", TranscriptOperation::DrawIntegers),
+            ],
+            operations
+        );
+
+        // drawn values are recorded as outputs
+        let draw_integers = &transcript.entries()[4];
+        assert_eq!(positions.len() * 8, draw_integers.output.len());
+        assert_eq!(
+            positions[0] as u64,
+            u64::from_le_bytes(draw_integers.output[..8].try_into().unwrap())
+        );
+
+        // nothing is recorded outside of record()
+        let mut coin = Coin::new(&[BaseElement::new(1)]);
+        coin.reseed_with_int(1);
+        let (_, transcript) = record(|| ());
+        assert!(transcript.entries().is_empty());
+    }
+
+    #[test]
+    fn transcript_divergence() {
+        let (_, transcript1) = run_protocol(42);
+        let (_, transcript2) = run_protocol(42);
+        let (_, transcript3) = run_protocol(43);
+        assert_eq!(None, transcript1.find_divergence(&transcript2));
+        assert_eq!(Some(3), transcript1.find_divergence(&transcript3));
+    }
+
+    #[test]
+    fn transcript_serialization() {
+        let (_, transcript) = run_protocol(42);
+        let bytes = transcript.to_bytes();
+        assert_eq!(transcript, Transcript::read_from_bytes(&bytes).unwrap());
+
+        let text = transcript.to_string();
+        assert_eq!(5, text.lines().count());
+        assert!(text
+            .lines()
+            .nth(3)
+            .unwrap()
+            .starts_with("3: [query] reseed_with_int(0x2a00000000000000)"));
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains derive macros for traits defined in other Winterfell crates.
+//!
+//! The macros are usually accessed via re-exports in the crates which define the corresponding
+//! traits (e.g., `winter_math::ToElements` when `derive` feature of `winter-math` is enabled).
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Index, LitStr, Path};
+
+// TO ELEMENTS
+// ================================================================================================
+
+/// Derives `ToElements` trait for a struct.
+///
+/// The derived implementation returns elements of all fields of the struct concatenated in the
+/// order in which the fields are declared; each field must implement `ToElements` trait for the
+/// same field element type. Fields annotated with `#[to_elements(skip)]` are not included in the
+/// result.
+///
+/// The implementation is generic over the field element type `E`, and thus, it exists for all
+/// `E` for which the types of all included fields implement `ToElements<E>`.
+///
+/// By default, the traits are referred to via `winter_math` crate. A different path can be
+/// specified via `#[to_elements(crate = "...")]` attribute on the struct (e.g.,
+/// `#[to_elements(crate = "winterfell::math")]`).
+#[proc_macro_derive(ToElements, attributes(to_elements))]
+pub fn derive_to_elements(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_to_elements(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_to_elements(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let krate = parse_crate_path(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "ToElements can be derived only for structs",
+            ))
+        }
+    };
+
+    // collect accessors and types of all fields which are not skipped
+    let mut accessors = Vec::new();
+    let mut field_types = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        if is_skipped(field)? {
+            continue;
+        }
+        let accessor = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(i);
+                quote!(#index)
+            }
+        };
+        accessors.push(accessor);
+        field_types.push(&field.ty);
+    }
+
+    // the implementation is generic over the element type, and requires all fields to be
+    // convertible into elements of that type
+    let element = format_ident!("__E");
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .push(parse_quote!(#element: #krate::FieldElement));
+    let where_clause = generics.make_where_clause();
+    for ty in field_types {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: #krate::ToElements<#element>));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let name = &input.ident;
+    Ok(quote! {
+        impl #impl_generics #krate::ToElements<#element> for #name #ty_generics #where_clause {
+            fn to_elements(&self) -> #krate::__private::Vec<#element> {
+                let mut result = #krate::__private::Vec::new();
+                #(
+                    result.extend(#krate::ToElements::<#element>::to_elements(&self.#accessors));
+                )*
+                result
+            }
+        }
+    })
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the path to the crate which defines `ToElements` trait; the path is specified via
+/// `#[to_elements(crate = "...")]` attribute and defaults to `::winter_math`.
+fn parse_crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut krate = parse_quote!(::winter_math);
+    for attr in input.attrs.iter() {
+        if !attr.path().is_ident("to_elements") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                let path: LitStr = meta.value()?.parse()?;
+                krate = path.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported to_elements attribute"))
+            }
+        })?;
+    }
+    Ok(krate)
+}
+
+/// Returns true if the field is annotated with `#[to_elements(skip)]` attribute.
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skipped = false;
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident("to_elements") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skipped = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported to_elements attribute"))
+            }
+        })?;
+    }
+    Ok(skipped)
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winter_math::{
+    fields::{f128::BaseElement, f64::BaseElement as SmallElement},
+    FieldElement, ToElements,
+};
+
+#[derive(ToElements)]
+struct PublicInputs {
+    seed: [BaseElement; 2],
+    num_steps: usize,
+    #[to_elements(skip)]
+    _label: &'static str,
+    results: Vec<[BaseElement; 2]>,
+    last: BaseElement,
+}
+
+#[derive(ToElements)]
+struct TupleInputs(SmallElement, u32);
+
+#[derive(ToElements)]
+struct GenericInputs<E: FieldElement> {
+    values: Vec<E>,
+    nested: TupleInputs,
+}
+
+#[derive(ToElements)]
+struct EmptyInputs;
+
+#[test]
+fn derive_named_struct() {
+    let inputs = PublicInputs {
+        seed: [BaseElement::new(1), BaseElement::new(2)],
+        num_steps: 3,
+        _label: "skipped",
+        results: vec![
+            [BaseElement::new(4), BaseElement::new(5)],
+            [BaseElement::new(6), BaseElement::new(7)],
+        ],
+        last: BaseElement::new(8),
+    };
+    let expected = (1..=8).map(BaseElement::new).collect::<Vec<_>>();
+    assert_eq!(expected, inputs.to_elements());
+}
+
+#[test]
+fn derive_tuple_and_generic_structs() {
+    let inputs = TupleInputs(SmallElement::new(1), 2);
+    assert_eq!(
+        vec![SmallElement::new(1), SmallElement::new(2)],
+        inputs.to_elements()
+    );
+
+    let inputs = GenericInputs {
+        values: vec![SmallElement::new(3)],
+        nested: TupleInputs(SmallElement::new(4), 5),
+    };
+    let expected = (3..=5).map(SmallElement::new).collect::<Vec<_>>();
+    assert_eq!(expected, inputs.to_elements());
+
+    assert!(ToElements::<SmallElement>::to_elements(&EmptyInputs).is_empty());
+}
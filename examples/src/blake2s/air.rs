@@ -0,0 +1,375 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compression::{
+        get_initial_digest, get_merge_initial_state, BLOCK_WIDTH, ROTATIONS, SIGMA, WORD_BITS,
+    },
+    get_row_rotations, BaseElement, Digest, FieldElement, ProofOptions, Step, CARRIES,
+    CYCLE_LENGTH, DIGEST, DIGEST_WIDTH, LEAF_INDEX, MSG, NUM_SLOTS, SLOTS, STATE, STATE_WIDTH,
+    TRACE_WIDTH,
+};
+use crate::utils::{is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+// Periodic columns: flags of the types of steps, and selectors of the message words used by the
+// mixing function.
+const FIRST_HALF_FLAG: usize = 0;
+const SECOND_HALF_FLAG: usize = 1;
+const DIAGONALIZE_FLAG: usize = 2;
+const UNDIAGONALIZE_FLAG: usize = 3;
+const FINALIZE_FLAGS: usize = 4;
+const LAST_STEP_FLAG: usize = FINALIZE_FLAGS + 4;
+const MSG_SELECTORS: usize = LAST_STEP_FLAG + 1;
+const NUM_PERIODIC_COLUMNS: usize = MSG_SELECTORS + BLOCK_WIDTH;
+
+/// Indexes of the state words placed into slots at each of the finalization steps; the first two
+/// and the last two words are XORed together to compute 2 words of the digest.
+const FINALIZE_WORDS: [[usize; NUM_SLOTS]; 4] =
+    [[0, 8, 1, 9], [2, 10, 3, 11], [4, 12, 5, 13], [6, 14, 7, 15]];
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputs {
+    pub tree_root: Digest,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.tree_root
+            .iter()
+            .map(|&word| BaseElement::from(word))
+            .collect()
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        for &word in self.tree_root.iter() {
+            target.write_u32(word);
+        }
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut tree_root = [0; DIGEST_WIDTH];
+        for word in tree_root.iter_mut() {
+            *word = source.read_u32()?;
+        }
+        Ok(PublicInputs { tree_root })
+    }
+}
+
+// BLAKE2S AIR
+// ================================================================================================
+
+/// AIR for verifying a Merkle authentication path in a tree built with Blake2s-256.
+///
+/// Every node on the path is computed by a single invocation of Blake2s compression function over
+/// a 256-step cycle. Words are stored in the trace as single field elements, and bits of up to 4
+/// words at a time are provided in the trace to perform bitwise operations: a ^ b = a + b - 2ab.
+/// Additions modulo 2^32 are enforced with the help of carries provided in the trace.
+///
+/// The column holding the index of the leaf contains, during the k-th cycle, the bits of the
+/// index starting with bit k + 1, together with a leading 1 bit; thus, the bit which specifies
+/// the position of the node computed by the k-th cycle in the block hashed by the next cycle is
+/// the difference between the value of the column and double of its value during the next cycle.
+pub struct Blake2sAir {
+    context: AirContext<BaseElement>,
+    tree_root: Digest,
+}
+
+impl Air for Blake2sAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let linear = TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]);
+        let quadratic = TransitionConstraintDegree::with_cycles(2, vec![CYCLE_LENGTH]);
+
+        // words of the state; the words updated by XOR operations have degree 2
+        let mut degrees = Vec::new();
+        for row in 0..4 {
+            for k in 0..4 {
+                if is_rotated_word(row) && receives_new_word(row, k) {
+                    degrees.push(quadratic.clone());
+                } else {
+                    degrees.push(linear.clone());
+                }
+            }
+        }
+
+        // mixing function and carries
+        degrees.extend([
+            linear.clone(),
+            linear.clone(),
+            quadratic.clone(),
+            linear.clone(),
+        ]);
+        degrees.push(TransitionConstraintDegree::new(3));
+        degrees.push(TransitionConstraintDegree::new(2));
+
+        // finalization and the digest
+        degrees.extend(vec![linear.clone(); NUM_SLOTS]);
+        degrees.extend(vec![quadratic.clone(); DIGEST_WIDTH]);
+
+        // message block and the index of the leaf
+        degrees.extend(vec![linear.clone(); BLOCK_WIDTH]);
+        degrees.extend(vec![quadratic.clone(); DIGEST_WIDTH]);
+        degrees.extend([linear, quadratic]);
+
+        // bits of slots are binary
+        degrees.extend(vec![
+            TransitionConstraintDegree::new(2);
+            NUM_SLOTS * WORD_BITS
+        ]);
+
+        Blake2sAir {
+            context: AirContext::new(trace_info, degrees, STATE_WIDTH + DIGEST_WIDTH + 1, options),
+            tree_root: pub_inputs.tree_root,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let first_half_flag = periodic_values[FIRST_HALF_FLAG];
+        let diagonalize_flag = periodic_values[DIAGONALIZE_FLAG];
+        let undiagonalize_flag = periodic_values[UNDIAGONALIZE_FLAG];
+        let second_half_flags = [
+            periodic_values[SECOND_HALF_FLAG],
+            diagonalize_flag,
+            undiagonalize_flag,
+        ];
+        let second_half_flag = second_half_flags
+            .iter()
+            .fold(E::ZERO, |acc, &flag| acc + flag);
+        let mix_flag = first_half_flag + second_half_flag;
+        let finalize_flags = &periodic_values[FINALIZE_FLAGS..FINALIZE_FLAGS + 4];
+        let last_step_flag = periodic_values[LAST_STEP_FLAG];
+        let static_flag = E::ONE - mix_flag - last_step_flag;
+
+        let slots = (0..NUM_SLOTS)
+            .map(|slot| &current[SLOTS + slot * WORD_BITS..SLOTS + (slot + 1) * WORD_BITS])
+            .collect::<Vec<_>>();
+        let slot_words = slots.iter().map(|bits| pack(bits, 0)).collect::<Vec<_>>();
+
+        let (state_result, result) = result.split_at_mut(STATE_WIDTH);
+        let (mix_result, result) = result.split_at_mut(4);
+        let (carry_result, result) = result.split_at_mut(2);
+        let (finalize_result, result) = result.split_at_mut(NUM_SLOTS);
+        let (digest_result, result) = result.split_at_mut(DIGEST_WIDTH);
+        let (msg_result, result) = result.split_at_mut(BLOCK_WIDTH);
+        let (link_result, result) = result.split_at_mut(DIGEST_WIDTH);
+        let (index_result, bits_result) = result.split_at_mut(2);
+
+        // a half of the mixing function G: slots hold bits of a + b + m, d, c + d', and b; new
+        // values of d and b are computed by rotating d ^ a' and b ^ c'
+        let ad = slots[0]
+            .iter()
+            .zip(slots[1])
+            .map(|(&a, &d)| xor(a, d))
+            .collect::<Vec<_>>();
+        let bc = slots[2]
+            .iter()
+            .zip(slots[3])
+            .map(|(&c, &b)| xor(c, b))
+            .collect::<Vec<_>>();
+        let new_words = ROTATIONS.map(|[r1, r2]| {
+            [
+                slot_words[0],
+                pack(&bc, r2 as usize),
+                slot_words[2],
+                pack(&ad, r1 as usize),
+            ]
+        });
+
+        // rows of the state are updated in place by the first half of G, and are rotated after
+        // the second half of G
+        let [_, _, diagonalize, undiagonalize] = [0, 1, 3, 7].map(get_row_rotations);
+        for row in 0..4 {
+            let words = &current[STATE + 4 * row..STATE + 4 * row + 4];
+            let updated = |half: usize, k: usize| {
+                if k == 0 {
+                    new_words[half][row]
+                } else {
+                    words[k]
+                }
+            };
+            for k in 0..4 {
+                let next_word = next[STATE + 4 * row + k];
+                let rotations = [1, diagonalize[row], undiagonalize[row]];
+                let mut constraint = first_half_flag * (next_word - updated(0, k))
+                    + static_flag * (next_word - words[k]);
+                for (&flag, rotation) in second_half_flags.iter().zip(rotations) {
+                    constraint += flag * (next_word - updated(1, (k + rotation) % 4));
+                }
+                state_result[4 * row + k] = constraint;
+            }
+        }
+
+        // slots hold a + b + m and c + d' (modulo 2^32), as well as the current values of d and b
+        let [a, b, c, d] = [0, 1, 2, 3].map(|row| current[STATE + 4 * row]);
+        let two_32 = E::from(1u64 << 32);
+        let mut m = E::ZERO;
+        for k in 0..BLOCK_WIDTH {
+            m += periodic_values[MSG_SELECTORS + k] * current[MSG + k];
+        }
+        mix_result[0] = mix_flag * (a + b - slot_words[0] - current[CARRIES] * two_32) + m;
+        mix_result[1] = mix_flag * (slot_words[1] - d);
+        mix_result[2] = first_half_flag
+            * (c + new_words[0][3] - slot_words[2] - current[CARRIES + 1] * two_32)
+            + second_half_flag
+                * (c + new_words[1][3] - slot_words[2] - current[CARRIES + 1] * two_32);
+        mix_result[3] = mix_flag * (slot_words[3] - b);
+
+        // a + b + m can overflow by at most 2, and c + d' can overflow by at most 1
+        let carry = current[CARRIES];
+        carry_result[0] = carry * (carry - E::ONE) * (carry - E::from(2u8));
+        carry_result[1] = is_binary(current[CARRIES + 1]);
+
+        // finalization: slots hold the words of the state XORed to compute the digest
+        for (slot, result) in finalize_result.iter_mut().enumerate() {
+            *result = E::ZERO;
+            for (&flag, words) in finalize_flags.iter().zip(FINALIZE_WORDS.iter()) {
+                *result += flag * (slot_words[slot] - current[STATE + words[slot]]);
+            }
+        }
+
+        let h = get_initial_digest();
+        for (i, result) in digest_result.iter_mut().enumerate() {
+            let (x, y) = (slots[i % 2 * 2], slots[i % 2 * 2 + 1]);
+            let mut word = E::ZERO;
+            for z in 0..WORD_BITS {
+                let bit = xor(x[z], y[z]);
+                let bit = if (h[i] >> z) & 1 == 1 { not(bit) } else { bit };
+                word += bit * E::from(1u64 << z);
+            }
+            let digest = current[DIGEST + i];
+            *result = next[DIGEST + i] - digest - finalize_flags[i / 2] * (word - digest);
+        }
+
+        // the message block remains the same during a cycle; at the end of the cycle, the digest
+        // is placed into the left or the right half of the next message block
+        for (k, result) in msg_result.iter_mut().enumerate() {
+            *result = not(last_step_flag) * (next[MSG + k] - current[MSG + k]);
+        }
+
+        let index_bit = current[LEAF_INDEX] - next[LEAF_INDEX].double();
+        for (i, result) in link_result.iter_mut().enumerate() {
+            let digest = current[DIGEST + i];
+            *result = last_step_flag
+                * (not(index_bit) * (next[MSG + i] - digest)
+                    + index_bit * (next[MSG + DIGEST_WIDTH + i] - digest));
+        }
+        index_result[0] = not(last_step_flag) * (next[LEAF_INDEX] - current[LEAF_INDEX]);
+        index_result[1] = last_step_flag * is_binary(index_bit);
+
+        for (result, &bit) in bits_result.iter_mut().zip(&current[SLOTS..]) {
+            *result = is_binary(bit);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the state is initialized at the start of every cycle, the last digest is the root of
+        // the tree, and all bits of the index have been used
+        let last_step = self.trace_length() - 1;
+        let mut assertions = Vec::new();
+        for (i, &word) in get_merge_initial_state().iter().enumerate() {
+            let value = BaseElement::from(word);
+            assertions.push(Assertion::periodic(STATE + i, 0, CYCLE_LENGTH, value));
+        }
+        for (i, &word) in self.tree_root.iter().enumerate() {
+            assertions.push(Assertion::single(
+                DIGEST + i,
+                last_step,
+                BaseElement::from(word),
+            ));
+        }
+        assertions.push(Assertion::single(LEAF_INDEX, last_step, BaseElement::ONE));
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut result = vec![vec![BaseElement::ZERO; CYCLE_LENGTH]; NUM_PERIODIC_COLUMNS];
+        #[allow(clippy::needless_range_loop)]
+        for cycle_step in 0..CYCLE_LENGTH {
+            let flags = match Step::at(cycle_step) {
+                Step::Mix { round, g, half } => {
+                    let flag = match (half, g) {
+                        (0, _) => FIRST_HALF_FLAG,
+                        (_, 3) => DIAGONALIZE_FLAG,
+                        (_, 7) => UNDIAGONALIZE_FLAG,
+                        _ => SECOND_HALF_FLAG,
+                    };
+                    vec![flag, MSG_SELECTORS + SIGMA[round][2 * g + half]]
+                }
+                Step::Finalize(i) => vec![FINALIZE_FLAGS + i],
+                Step::Padding if cycle_step == CYCLE_LENGTH - 1 => vec![LAST_STEP_FLAG],
+                Step::Padding => vec![],
+            };
+            for column in flags {
+                result[column][cycle_step] = BaseElement::ONE;
+            }
+        }
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if words in the specified row of the state are updated by rotating the result of
+/// a XOR operation (this is the case for words b and d of the mixing function).
+fn is_rotated_word(row: usize) -> bool {
+    row == 1 || row == 3
+}
+
+/// Returns true if the word at position `k` of the specified row of the state can receive a word
+/// computed by the mixing function.
+fn receives_new_word(row: usize, k: usize) -> bool {
+    k == 0
+        || [0, 3, 7]
+            .map(get_row_rotations)
+            .iter()
+            .any(|rotations| (k + rotations[row]) % 4 == 0)
+}
+
+/// Returns a ^ b for binary a and b.
+fn xor<E: FieldElement>(a: E, b: E) -> E {
+    a + b - (a * b).double()
+}
+
+/// Packs the provided bits into a single field element, with the least significant bit first,
+/// and rotates the resulting word right by the specified number of bits.
+fn pack<E: FieldElement>(bits: &[E], rotation: usize) -> E {
+    bits.iter().enumerate().fold(E::ZERO, |acc, (z, &bit)| {
+        acc + bit * E::from(1u64 << ((z + WORD_BITS - rotation) % WORD_BITS))
+    })
+}
@@ -0,0 +1,141 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of 32-bit words in Blake2s state.
+pub const STATE_WIDTH: usize = 16;
+
+/// Number of 32-bit words in a message block.
+pub const BLOCK_WIDTH: usize = 16;
+
+/// Number of 32-bit words in a digest.
+pub const DIGEST_WIDTH: usize = 8;
+
+/// Number of bits in a word.
+pub const WORD_BITS: usize = 32;
+
+/// Number of rounds in Blake2s compression function.
+pub const NUM_ROUNDS: usize = 10;
+
+/// Initialization vector of Blake2s.
+pub const IV: [u32; DIGEST_WIDTH] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// Permutations of message words used by the rounds of the compression function.
+pub const SIGMA: [[usize; BLOCK_WIDTH]; NUM_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Rotation offsets of the first and the second halves of the mixing function G.
+pub const ROTATIONS: [[u32; 2]; 2] = [[16, 12], [8, 7]];
+
+/// Length of a message hashed by [merge()], in bytes.
+const MERGE_INPUT_LENGTH: u64 = 64;
+
+// HASH FUNCTIONS
+// ================================================================================================
+
+/// Returns Blake2s-256 hash of the concatenation of two digests.
+///
+/// Digests are represented by 8 words containing their bytes in little-endian order. The 64-byte
+/// input is hashed by a single invocation of the compression function.
+pub fn merge(values: &[[u32; DIGEST_WIDTH]; 2]) -> [u32; DIGEST_WIDTH] {
+    let mut block = [0; BLOCK_WIDTH];
+    block[..DIGEST_WIDTH].copy_from_slice(&values[0]);
+    block[DIGEST_WIDTH..].copy_from_slice(&values[1]);
+    compress(&get_initial_digest(), &block, MERGE_INPUT_LENGTH, true)
+}
+
+/// Returns the state of Blake2s-256 before any blocks have been compressed; this corresponds to a
+/// hash function without a key and with a 32-byte digest.
+pub fn get_initial_digest() -> [u32; DIGEST_WIDTH] {
+    let mut h = IV;
+    h[0] ^= 0x01010000 ^ (DIGEST_WIDTH as u32 * 4);
+    h
+}
+
+/// Returns the state of the compression function before the first round as defined by
+/// [get_initial_digest()] and [merge()].
+pub fn get_merge_initial_state() -> [u32; STATE_WIDTH] {
+    get_initial_state(&get_initial_digest(), MERGE_INPUT_LENGTH, true)
+}
+
+// COMPRESSION FUNCTION
+// ================================================================================================
+
+/// Compresses the `block` into the digest `h`; `t` is the number of bytes hashed so far, including
+/// the block, and `last` must be set for the last block of a message.
+pub fn compress(
+    h: &[u32; DIGEST_WIDTH],
+    block: &[u32; BLOCK_WIDTH],
+    t: u64,
+    last: bool,
+) -> [u32; DIGEST_WIDTH] {
+    let mut v = get_initial_state(h, t, last);
+    for sigma in SIGMA.iter() {
+        for (i, &[a, b, c, d]) in G_INDEXES.iter().enumerate() {
+            for (half, rotations) in ROTATIONS.iter().enumerate() {
+                let m = block[sigma[2 * i + half]];
+                apply_half_mix(&mut v, [a, b, c, d], m, *rotations);
+            }
+        }
+    }
+
+    let mut result = *h;
+    for (i, word) in result.iter_mut().enumerate() {
+        *word ^= v[i] ^ v[i + 8];
+    }
+    result
+}
+
+/// Indexes of state words processed by invocations of the mixing function G within a round.
+const G_INDEXES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// Applies a half of the mixing function G to the specified words of the state.
+fn apply_half_mix(
+    v: &mut [u32; STATE_WIDTH],
+    [a, b, c, d]: [usize; 4],
+    m: u32,
+    rotations: [u32; 2],
+) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(m);
+    v[d] = (v[d] ^ v[a]).rotate_right(rotations[0]);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(rotations[1]);
+}
+
+/// Returns the state of the compression function before the first round.
+fn get_initial_state(h: &[u32; DIGEST_WIDTH], t: u64, last: bool) -> [u32; STATE_WIDTH] {
+    let mut v = [0; STATE_WIDTH];
+    v[..DIGEST_WIDTH].copy_from_slice(h);
+    v[DIGEST_WIDTH..].copy_from_slice(&IV);
+    v[12] ^= t as u32;
+    v[13] ^= (t >> 32) as u32;
+    if last {
+        v[14] = !v[14];
+    }
+    v
+}
@@ -0,0 +1,324 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{verify_with_serialized_inputs, Example, ExampleOptions, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use rand_utils::{rand_value, rand_vector};
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f64::BaseElement, FieldElement},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+pub use air::{Blake2sAir, PublicInputs};
+
+pub mod compression;
+use compression::{DIGEST_WIDTH, NUM_ROUNDS, STATE_WIDTH, WORD_BITS};
+
+mod prover;
+pub use prover::Blake2sProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+/// Number of steps needed to compute a single invocation of the compression function: each round
+/// takes 16 steps (one step per half of the mixing function G), the digest is computed in 4 more
+/// steps, and the remaining steps pad the cycle to a power of two.
+const CYCLE_LENGTH: usize = 256;
+
+/// Number of steps needed to compute a single round of the compression function.
+const ROUND_LENGTH: usize = 16;
+
+/// Number of steps needed to compute the digest from the state after the last round.
+const NUM_FINALIZATION_STEPS: usize = 4;
+
+// The trace consists of the following columns:
+// * words of the compression function state; the state is viewed as 4 rows of 4 words, and the
+//   rows are rotated so that the words processed by the mixing function are always in the first
+//   column of the state,
+// * words of the message block,
+// * words of the digest computed from the state after the last round,
+// * bits of the index of the leaf which have not been used yet, prefixed with a 1 bit; the bits
+//   specify whether the digest computed by the previous invocation of the compression function
+//   is the right (rather than the left) half of the message block,
+// * carries of the two additions performed by a half of the mixing function,
+// * slots holding bits of specific words; bitwise operations are performed over these.
+const STATE: usize = 0;
+const MSG: usize = STATE + STATE_WIDTH;
+const DIGEST: usize = MSG + compression::BLOCK_WIDTH;
+const LEAF_INDEX: usize = DIGEST + DIGEST_WIDTH;
+const CARRIES: usize = LEAF_INDEX + 1;
+const SLOTS: usize = CARRIES + 2;
+const NUM_SLOTS: usize = 4;
+const TRACE_WIDTH: usize = SLOTS + NUM_SLOTS * WORD_BITS;
+
+/// A digest produced by Blake2s-256, represented by its 8 little-endian words.
+pub type Digest = [u32; DIGEST_WIDTH];
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
+type GriffinJive64_256 = winterfell::crypto::hashers::GriffinJive64_256;
+
+// BLAKE2S MERKLE AUTHENTICATION PATH EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    tree_depth: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(Blake2sExample::<Blake3_192>::new(
+            tree_depth, options,
+        )?)),
+        HashFunction::Blake3_256 => Ok(Box::new(Blake2sExample::<Blake3_256>::new(
+            tree_depth, options,
+        )?)),
+        HashFunction::Sha3_256 => Ok(Box::new(Blake2sExample::<Sha3_256>::new(
+            tree_depth, options,
+        )?)),
+        HashFunction::Rp64_256 => Ok(Box::new(Blake2sExample::<Rp64_256>::new(
+            tree_depth, options,
+        )?)),
+        HashFunction::RpJive64_256 => Ok(Box::new(Blake2sExample::<RpJive64_256>::new(
+            tree_depth, options,
+        )?)),
+        HashFunction::GriffinJive64_256 => Ok(Box::new(Blake2sExample::<GriffinJive64_256>::new(
+            tree_depth, options,
+        )?)),
+    }
+}
+
+pub struct Blake2sExample<H: ElementHasher> {
+    options: ProofOptions,
+    tree_root: Digest,
+    value: Digest,
+    index: usize,
+    path: Vec<Digest>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> Blake2sExample<H> {
+    pub fn new(tree_depth: usize, options: ProofOptions) -> Result<Self, String> {
+        if !tree_depth.is_power_of_two() {
+            return Err("tree depth must be a power of 2".to_string());
+        }
+        let value = [42; DIGEST_WIDTH];
+        let index = (rand_value::<u64>() % (1 << tree_depth)) as usize;
+
+        // build Merkle tree of the specified depth
+        let now = Instant::now();
+        let tree = build_merkle_tree(tree_depth, value, index);
+        debug!(
+            "Built Blake2s Merkle tree of depth {} in {} ms",
+            tree_depth,
+            now.elapsed().as_millis(),
+        );
+
+        // compute Merkle path form the leaf specified by the index
+        let path = get_merkle_path(&tree, index);
+        let tree_root = tree[tree_depth][0];
+        debug_assert_eq!(tree_root, compute_merkle_root(value, index, &path));
+
+        Ok(Blake2sExample {
+            options,
+            tree_root,
+            value,
+            index,
+            path,
+            _hasher: PhantomData,
+        })
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for Blake2sExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for proving membership in a Blake2s Merkle tree of depth {}\n\
+            ---------------------",
+            self.path.len()
+        );
+
+        // create a prover
+        let prover = Blake2sProver::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.value, &self.path, self.index);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            tree_root: self.tree_root,
+        };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<Blake2sAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut tree_root = self.tree_root;
+        tree_root[0] ^= 1;
+        let pub_inputs = PublicInputs { tree_root };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<Blake2sAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        let pub_inputs = PublicInputs {
+            tree_root: self.tree_root,
+        };
+        pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<Blake2sAir, H>(proof, pub_inputs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a Merkle tree of the specified depth with Blake2s-256 used to merge nodes, and places
+/// the `value` at the leaf with the specified index. Returns all levels of the tree, starting
+/// with the leaves.
+fn build_merkle_tree(depth: usize, value: Digest, index: usize) -> Vec<Vec<Digest>> {
+    let num_leaves = 1 << depth;
+    let mut leaves = rand_vector::<u64>(num_leaves * DIGEST_WIDTH / 2)
+        .chunks(DIGEST_WIDTH / 2)
+        .map(|words| {
+            let mut leaf = [0; DIGEST_WIDTH];
+            for (i, &word) in words.iter().enumerate() {
+                leaf[2 * i] = word as u32;
+                leaf[2 * i + 1] = (word >> 32) as u32;
+            }
+            leaf
+        })
+        .collect::<Vec<_>>();
+    leaves[index] = value;
+
+    let mut tree = vec![leaves];
+    for _ in 0..depth {
+        let nodes = tree.last().unwrap();
+        let parents = nodes
+            .chunks(2)
+            .map(|pair| compression::merge(&[pair[0], pair[1]]))
+            .collect();
+        tree.push(parents);
+    }
+    tree
+}
+
+/// Returns siblings of the nodes on the path from the leaf at the specified index to the root.
+fn get_merkle_path(tree: &[Vec<Digest>], index: usize) -> Vec<Digest> {
+    tree[..tree.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(depth, nodes)| nodes[(index >> depth) ^ 1])
+        .collect()
+}
+
+/// Returns the root of the Merkle tree computed from the `value` located at the specified index
+/// and its authentication path.
+pub fn compute_merkle_root(value: Digest, index: usize, path: &[Digest]) -> Digest {
+    path.iter()
+        .enumerate()
+        .fold(value, |node, (depth, &sibling)| {
+            match (index >> depth) & 1 {
+                0 => compression::merge(&[node, sibling]),
+                _ => compression::merge(&[sibling, node]),
+            }
+        })
+}
+
+// STEP SCHEDULE
+// ================================================================================================
+
+/// Operation performed at a given step of a compression cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    /// A half of the mixing function G; `g` is the index of the invocation of G within the round,
+    /// and `half` specifies whether this is the first or the second half of G.
+    Mix {
+        round: usize,
+        g: usize,
+        half: usize,
+    },
+    /// Computation of 2 words of the digest.
+    Finalize(usize),
+    Padding,
+}
+
+impl Step {
+    /// Returns the operation performed at the specified step of a compression cycle.
+    fn at(cycle_step: usize) -> Step {
+        let num_mix_steps = NUM_ROUNDS * ROUND_LENGTH;
+        if cycle_step < num_mix_steps {
+            let step = cycle_step % ROUND_LENGTH;
+            Step::Mix {
+                round: cycle_step / ROUND_LENGTH,
+                g: step / 2,
+                half: step % 2,
+            }
+        } else if cycle_step < num_mix_steps + NUM_FINALIZATION_STEPS {
+            Step::Finalize(cycle_step - num_mix_steps)
+        } else {
+            Step::Padding
+        }
+    }
+}
+
+/// Returns the amounts by which the rows of the state are rotated at the end of the specified
+/// invocation of the mixing function G.
+///
+/// Rows are rotated by one word after every invocation to move the next column of the state into
+/// the first column. In addition, the rows are rotated by 0, 1, 2 and 3 words before the diagonal
+/// invocations (so that diagonals become columns), and are rotated back after them.
+fn get_row_rotations(g: usize) -> [usize; 4] {
+    match g {
+        3 => [1, 2, 3, 0],
+        7 => [1, 0, 3, 2],
+        _ => [1, 1, 1, 1],
+    }
+}
+
+/// Returns the index of the column holding bit `z` of the specified slot.
+fn slot_column(slot: usize, z: usize) -> usize {
+    SLOTS + slot * WORD_BITS + z
+}
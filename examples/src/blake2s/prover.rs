@@ -0,0 +1,195 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compression::{
+        self, get_initial_digest, get_merge_initial_state, BLOCK_WIDTH, ROTATIONS, SIGMA, WORD_BITS,
+    },
+    get_row_rotations, slot_column, BaseElement, Blake2sAir, DefaultRandomCoin, Digest,
+    ElementHasher, MerkleTree, PhantomData, ProofOptions, Prover, PublicInputs, Step, Trace,
+    TraceTable, CARRIES, CYCLE_LENGTH, DIGEST, DIGEST_WIDTH, LEAF_INDEX, MSG, NUM_SLOTS, STATE,
+    STATE_WIDTH, TRACE_WIDTH,
+};
+use winterfell::math::StarkField;
+
+// BLAKE2S PROVER
+// ================================================================================================
+
+pub struct Blake2sProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> Blake2sProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for computing the root of a Merkle tree from the `value` located
+    /// at the specified index and its authentication path.
+    pub fn build_trace(
+        &self,
+        value: Digest,
+        path: &[Digest],
+        index: usize,
+    ) -> TraceTable<BaseElement> {
+        assert!(
+            path.len().is_power_of_two(),
+            "length of the Merkle path must be a power of 2"
+        );
+        let trace_length = path.len() * CYCLE_LENGTH;
+        let mut trace = (0..TRACE_WIDTH)
+            .map(|_| Vec::with_capacity(trace_length))
+            .collect::<Vec<_>>();
+
+        let mut node = value;
+        let mut registers = Registers::default();
+        for (depth, &sibling) in path.iter().enumerate() {
+            let block = match (index >> depth) & 1 {
+                0 => [node, sibling],
+                _ => [sibling, node],
+            };
+            registers.init(&block, ((1 << path.len()) + index as u64) >> (depth + 1));
+            for cycle_step in 0..CYCLE_LENGTH {
+                let step = Step::at(cycle_step);
+                let (slots, carries) = registers.get_slots(step);
+                registers.write_into(&slots, &carries, &mut trace);
+                registers.apply_step(step, &slots);
+            }
+            node = registers.digest;
+            debug_assert_eq!(compression::merge(&block), node);
+        }
+
+        TraceTable::init(trace)
+    }
+}
+
+impl<H: ElementHasher> Prover for Blake2sProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = Blake2sAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        let mut tree_root = [0; DIGEST_WIDTH];
+        for (i, word) in tree_root.iter_mut().enumerate() {
+            *word = trace.get(DIGEST + i, last_step).as_int() as u32;
+        }
+        PublicInputs { tree_root }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// REGISTERS
+// ================================================================================================
+
+/// Values held in the trace columns at a given step of the computation.
+#[derive(Default)]
+struct Registers {
+    state: [u32; STATE_WIDTH],
+    msg: [u32; BLOCK_WIDTH],
+    digest: Digest,
+    index: u64,
+}
+
+impl Registers {
+    /// Initializes the registers for hashing the specified block; the digest computed by the
+    /// previous cycle remains in the registers.
+    fn init(&mut self, block: &[Digest; 2], index: u64) {
+        self.state = get_merge_initial_state();
+        self.msg[..DIGEST_WIDTH].copy_from_slice(&block[0]);
+        self.msg[DIGEST_WIDTH..].copy_from_slice(&block[1]);
+        self.index = index;
+    }
+
+    /// Returns the words placed into the slots and the carries at the specified step.
+    fn get_slots(&self, step: Step) -> ([u32; NUM_SLOTS], [u32; 2]) {
+        let v = &self.state;
+        match step {
+            Step::Mix { round, g, half } => {
+                let [r1, _] = ROTATIONS[half];
+                let m = self.msg[SIGMA[round][2 * g + half]];
+                let sum = v[0] as u64 + v[4] as u64 + m as u64;
+                let a = sum as u32;
+                let d = (v[12] ^ a).rotate_right(r1);
+                let sum2 = v[8] as u64 + d as u64;
+                let c = sum2 as u32;
+                (
+                    [a, v[12], c, v[4]],
+                    [(sum >> 32) as u32, (sum2 >> 32) as u32],
+                )
+            }
+            Step::Finalize(i) => {
+                let i = 2 * i;
+                let words = [v[i], v[i + 8], v[i + 1], v[i + 9]];
+                (words, [0; 2])
+            }
+            Step::Padding => ([0; NUM_SLOTS], [0; 2]),
+        }
+    }
+
+    /// Updates the registers by executing the specified step.
+    fn apply_step(&mut self, step: Step, slots: &[u32; NUM_SLOTS]) {
+        match step {
+            Step::Mix { g, half, .. } => {
+                let [r1, r2] = ROTATIONS[half];
+                let [a, d, c, b] = *slots;
+                let new_words = [a, (b ^ c).rotate_right(r2), c, (d ^ a).rotate_right(r1)];
+                let rotations = get_row_rotations(g);
+                for (row, &word) in new_words.iter().enumerate() {
+                    let words = &mut self.state[4 * row..4 * row + 4];
+                    words[0] = word;
+                    if half == 1 {
+                        words.rotate_left(rotations[row]);
+                    }
+                }
+            }
+            Step::Finalize(i) => {
+                let h = get_initial_digest();
+                self.digest[2 * i] = h[2 * i] ^ slots[0] ^ slots[1];
+                self.digest[2 * i + 1] = h[2 * i + 1] ^ slots[2] ^ slots[3];
+            }
+            Step::Padding => (),
+        }
+    }
+
+    /// Appends the registers, the carries, and the bits of the slots to the trace.
+    fn write_into(
+        &self,
+        slots: &[u32; NUM_SLOTS],
+        carries: &[u32; 2],
+        trace: &mut [Vec<BaseElement>],
+    ) {
+        let registers = [
+            (STATE, &self.state[..]),
+            (MSG, &self.msg[..]),
+            (DIGEST, &self.digest[..]),
+            (CARRIES, &carries[..]),
+        ];
+        for (offset, words) in registers {
+            for (i, &word) in words.iter().enumerate() {
+                trace[offset + i].push(BaseElement::from(word));
+            }
+        }
+        trace[LEAF_INDEX].push(BaseElement::from(self.index));
+        for (slot, &word) in slots.iter().enumerate() {
+            for z in 0..WORD_BITS {
+                trace[slot_column(slot, z)].push(BaseElement::from((word >> z) & 1));
+            }
+        }
+    }
+}
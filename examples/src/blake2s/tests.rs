@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{compression, BaseElement, Blake2sExample, Blake3_256, Digest};
+use winterfell::{
+    crypto::{hashers::Blake2s_256, Digest as _, Hasher},
+    FieldExtension, ProofOptions,
+};
+
+#[test]
+fn blake2s_test_basic_proof_verification() {
+    let blake2s = Box::new(Blake2sExample::<Blake3_256>::new(2, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification(blake2s);
+}
+
+#[test]
+fn blake2s_test_basic_proof_verification_extension() {
+    let blake2s = Box::new(Blake2sExample::<Blake3_256>::new(2, build_options(true)).unwrap());
+    crate::tests::test_basic_proof_verification(blake2s);
+}
+
+#[test]
+fn blake2s_test_basic_proof_verification_fail() {
+    let blake2s = Box::new(Blake2sExample::<Blake3_256>::new(2, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification_fail(blake2s);
+}
+
+#[test]
+fn blake2s_test_proof_verification_with_pub_inputs() {
+    let blake2s = Box::new(Blake2sExample::<Blake3_256>::new(2, build_options(false)).unwrap());
+    crate::tests::test_proof_verification_with_pub_inputs(blake2s);
+}
+
+#[test]
+fn blake2s_test_merge() {
+    // Blake2s-256 hash of bytes 0, 1, ..., 63 computed by the reference implementation
+    let expected = [
+        0x8b4ef356, 0x907e5596, 0x524bf2c1, 0x519dc8d0, 0x1bcf6a08, 0xcf34f600, 0x3392de1d,
+        0x3eaaeab8,
+    ];
+    let mut values = [Digest::default(); 2];
+    for (i, word) in values.iter_mut().flatten().enumerate() {
+        let byte = 4 * i as u8;
+        *word = u32::from_le_bytes([byte, byte + 1, byte + 2, byte + 3]);
+    }
+    assert_eq!(expected, compression::merge(&values));
+}
+
+#[test]
+fn blake2s_test_merge_matches_crypto_hasher() {
+    type Blake2s = Blake2s_256<BaseElement>;
+
+    // the compression function must compute the same 2-to-1 hash as the Blake2s hasher which
+    // is used for commitments outside of the AIR
+    let to_words = |digest: <Blake2s as Hasher>::Digest| -> Digest {
+        let mut result = Digest::default();
+        for (word, bytes) in result.iter_mut().zip(digest.as_bytes().chunks(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        result
+    };
+    for i in 0..32_u8 {
+        let values = [Blake2s::hash(&[i]), Blake2s::hash(&[i, i])];
+        assert_eq!(
+            to_words(Blake2s::merge(&values)),
+            compression::merge(&values.map(to_words))
+        );
+    }
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,485 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    program::{
+        biased, Execution, Program, AP_ADD, AP_ADD1, DST_REG, OP0_REG, OP1_AP, OP1_FP, OP1_IMM,
+        OPCODE_ASSERT_EQ, OPCODE_CALL, OPCODE_RET, PC_JNZ, PC_JUMP_ABS, PC_JUMP_REL, RES_ADD,
+        RES_MUL,
+    },
+    BaseElement, FieldElement, ProofOptions, StarkField, AP, AUX_TRACE_WIDTH, DST, DST_ADDR, FLAGS,
+    FP, MEMORY_PARTIAL, MEMORY_PRODUCT, MUL, NUM_FLAGS, NUM_MEMORY_ACCESSES, NUM_RC_VALUES,
+    OFFSET_BIAS, OFF_DST, OFF_OP0, OFF_OP1, OP0, OP0_ADDR, OP1, OP1_ADDR, PC, PUB_ADDR, PUB_VALUE,
+    RC_PAD, RC_PARTIAL, RC_PRODUCT, RES, SORTED_MEMORY, SORTED_RC, T0, T1, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, EvaluationResult};
+use winterfell::{
+    math::{ExtensionOf, ToElements},
+    Air, AirContext, Assertion, AuxTraceRandElements, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// A cell of the public memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToElements)]
+#[to_elements(crate = "winterfell::math")]
+pub struct MemoryCell {
+    pub address: u64,
+    pub value: BaseElement,
+}
+
+/// Public inputs of a Cairo program execution.
+///
+/// The public memory consists of the program (loaded at address [PROGRAM_BASE](super::PROGRAM_BASE))
+/// followed by the output cells (the first cells of the execution segment).
+#[derive(Debug, Clone, PartialEq, Eq, ToElements)]
+#[to_elements(crate = "winterfell::math")]
+pub struct PublicInputs {
+    pub initial_pc: u64,
+    pub initial_ap: u64,
+    pub final_pc: u64,
+    pub final_ap: u64,
+    pub rc_min: u16,
+    pub rc_max: u16,
+    pub public_memory: Vec<MemoryCell>,
+}
+
+impl PublicInputs {
+    /// Returns public inputs for the specified `execution` of the `program` which writes its
+    /// output into the first `num_outputs` cells of the execution segment.
+    ///
+    /// # Panics
+    /// Panics if the execution did not write all of the output cells.
+    pub fn from_execution(program: &Program, execution: &Execution, num_outputs: usize) -> Self {
+        let last_step = execution.steps.last().expect("no steps were executed");
+
+        let (rc_min, rc_max) = execution
+            .steps
+            .iter()
+            .flat_map(|step| {
+                let instruction = step.instruction;
+                [
+                    instruction.off_dst,
+                    instruction.off_op0,
+                    instruction.off_op1,
+                ]
+            })
+            .map(biased)
+            .fold((u16::MAX, u16::MIN), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+
+        let mut public_memory = program
+            .words()
+            .iter()
+            .zip(program.initial_pc()..)
+            .map(|(&value, address)| MemoryCell { address, value })
+            .collect::<Vec<_>>();
+        for address in program.initial_ap()..program.initial_ap() + num_outputs as u64 {
+            let value = *execution
+                .memory
+                .get(&address)
+                .expect("output cell was not written");
+            public_memory.push(MemoryCell { address, value });
+        }
+
+        PublicInputs {
+            initial_pc: program.initial_pc(),
+            initial_ap: program.initial_ap(),
+            final_pc: program.final_pc(),
+            final_ap: last_step.ap.as_int() as u64,
+            rc_min,
+            rc_max,
+            public_memory,
+        }
+    }
+
+    /// Returns values of the output cells.
+    pub fn output(&self) -> Vec<BaseElement> {
+        self.public_memory
+            .iter()
+            .filter(|cell| cell.address >= self.initial_ap)
+            .map(|cell| cell.value)
+            .collect()
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.initial_pc);
+        target.write_u64(self.initial_ap);
+        target.write_u64(self.final_pc);
+        target.write_u64(self.final_ap);
+        target.write_u16(self.rc_min);
+        target.write_u16(self.rc_max);
+        target.write_u32(self.public_memory.len() as u32);
+        for cell in self.public_memory.iter() {
+            target.write_u64(cell.address);
+            target.write(cell.value);
+        }
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let initial_pc = source.read_u64()?;
+        let initial_ap = source.read_u64()?;
+        let final_pc = source.read_u64()?;
+        let final_ap = source.read_u64()?;
+        let rc_min = source.read_u16()?;
+        let rc_max = source.read_u16()?;
+        let num_cells = source.read_u32()? as usize;
+        let mut public_memory = Vec::new();
+        for _ in 0..num_cells {
+            let address = source.read_u64()?;
+            let value = source.read()?;
+            public_memory.push(MemoryCell { address, value });
+        }
+        Ok(PublicInputs {
+            initial_pc,
+            initial_ap,
+            final_pc,
+            final_ap,
+            rc_min,
+            rc_max,
+            public_memory,
+        })
+    }
+}
+
+// CAIRO AIR
+// ================================================================================================
+
+/// AIR for execution of Cairo programs.
+///
+/// Each row of the trace describes a single step of the Cairo VM: values of `pc`, `ap`, and `fp`
+/// registers, the executed instruction (decoded into offsets and flags), and addresses and
+/// values of its operands. The constraints follow the Cairo CPU AIR described in section 4 of
+/// the [Cairo whitepaper](https://eprint.iacr.org/2021/1063), with the following simplifications:
+/// * Every step occupies a single row, and all flags are placed into separate columns.
+/// * Memory accesses of a row are sorted into 5 (address, value) pairs of the same row.
+/// * Range-checked offsets are sorted into 4 columns, each of which is a contiguous sequence of
+///   values from `rc_min` to `rc_max`. Splitting the sorted values into columns (rather than
+///   laying them out row by row) keeps continuity constraints from vanishing identically when
+///   offsets span only a few distinct values.
+///
+/// Memory consistency is enforced via a permutation argument between the accessed cells and the
+/// sorted cells, in which the sorted cells must form a contiguous, single-valued memory. Public
+/// memory is handled in the same way as in Cairo: the prover replaces public cells with dummy
+/// (0, 0) accesses, and the verifier accounts for the public cells in the expected value of the
+/// permutation product. Instruction offsets are range-checked via another permutation argument.
+///
+/// The last row of the trace is not constrained by transition constraints; it must contain the
+/// final state of the VM, and the final `jmp rel 0` instruction is repeated to pad the trace to
+/// a power of two.
+pub struct CairoAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: PublicInputs,
+}
+
+impl Air for CairoAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.layout().main_trace_width());
+        assert_eq!(AUX_TRACE_WIDTH, trace_info.layout().aux_trace_width());
+
+        let main_degrees = vec![TransitionConstraintDegree::new(2); NUM_MAIN_CONSTRAINTS];
+        let aux_degrees = vec![
+            TransitionConstraintDegree::new(4),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+        ];
+
+        CairoAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                5 + 2 * NUM_RC_VALUES,
+                4,
+                options,
+            ),
+            pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let flag = |i: usize| current[FLAGS + i];
+        let offset = |i: usize| current[i] - E::from(OFFSET_BIAS);
+        let (pc, ap, fp) = (current[PC], current[AP], current[FP]);
+        let (dst, op0, op1, res) = (current[DST], current[OP0], current[OP1], current[RES]);
+        let size = E::ONE + flag(OP1_IMM);
+
+        // flags must be binary
+        for (i, value) in result.iter_mut().take(NUM_FLAGS).enumerate() {
+            *value = is_binary(flag(i));
+        }
+
+        // operand addresses
+        let dst_base = flag(DST_REG) * fp + (E::ONE - flag(DST_REG)) * ap;
+        result[15] = are_equal(current[DST_ADDR], dst_base + offset(OFF_DST));
+        let op0_base = flag(OP0_REG) * fp + (E::ONE - flag(OP0_REG)) * ap;
+        result[16] = are_equal(current[OP0_ADDR], op0_base + offset(OFF_OP0));
+        let op1_src = flag(OP1_IMM) + flag(OP1_AP) + flag(OP1_FP);
+        let op1_base =
+            flag(OP1_IMM) * pc + flag(OP1_AP) * ap + flag(OP1_FP) * fp + (E::ONE - op1_src) * op0;
+        result[17] = are_equal(current[OP1_ADDR], op1_base + offset(OFF_OP1));
+
+        // res computation; for conditional jumps, res is not constrained here
+        result[18] = are_equal(current[MUL], op0 * op1);
+        let res_logic = flag(RES_ADD) + flag(RES_MUL) + flag(PC_JNZ);
+        result[19] = are_equal(
+            (E::ONE - flag(PC_JNZ)) * res,
+            flag(RES_ADD) * (op0 + op1) + flag(RES_MUL) * current[MUL] + (E::ONE - res_logic) * op1,
+        );
+
+        // pc update; for conditional jumps, res must be the inverse of dst when dst is not zero
+        result[20] = are_equal(current[T0], flag(PC_JNZ) * dst);
+        result[21] = are_equal(current[T1], current[T0] * res);
+        result[22] = (current[T1] - flag(PC_JNZ)) * (next[PC] - (pc + size));
+        let pc_update = flag(PC_JUMP_ABS) + flag(PC_JUMP_REL) + flag(PC_JNZ);
+        result[23] = current[T0] * (next[PC] - (pc + op1)) + (E::ONE - flag(PC_JNZ)) * next[PC]
+            - ((E::ONE - pc_update) * (pc + size)
+                + flag(PC_JUMP_ABS) * res
+                + flag(PC_JUMP_REL) * (pc + res));
+
+        // ap and fp updates
+        let two = E::ONE.double();
+        result[24] = are_equal(
+            next[AP],
+            ap + flag(AP_ADD) * res + flag(AP_ADD1) + two * flag(OPCODE_CALL),
+        );
+        let fp_update = flag(OPCODE_RET) + flag(OPCODE_CALL);
+        result[25] = are_equal(
+            next[FP],
+            flag(OPCODE_RET) * dst + flag(OPCODE_CALL) * (ap + two) + (E::ONE - fp_update) * fp,
+        );
+
+        // opcode constraints
+        result.agg_constraint(26, flag(OPCODE_CALL), are_equal(dst, fp));
+        result.agg_constraint(27, flag(OPCODE_CALL), are_equal(op0, pc + size));
+        result.agg_constraint(28, flag(OPCODE_ASSERT_EQ), are_equal(res, dst));
+
+        // sorted memory must be contiguous and single-valued; the last pair of each row is
+        // followed by the first pair of the next row
+        for i in 0..NUM_MEMORY_ACCESSES {
+            let (addr, value) = sorted_memory_pair(current, i);
+            let (next_addr, next_value) = if i + 1 < NUM_MEMORY_ACCESSES {
+                sorted_memory_pair(current, i + 1)
+            } else {
+                sorted_memory_pair(next, 0)
+            };
+            let addr_delta = next_addr - addr;
+            result[MEMORY_CONSTRAINTS + 2 * i] = addr_delta * (addr_delta - E::ONE);
+            result[MEMORY_CONSTRAINTS + 2 * i + 1] = (next_value - value) * (addr_delta - E::ONE);
+        }
+
+        // each column of sorted range-checked values must be contiguous
+        for i in 0..NUM_RC_VALUES {
+            let delta = next[SORTED_RC + i] - current[SORTED_RC + i];
+            result[RC_CONSTRAINTS + i] = delta * (delta - E::ONE);
+        }
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let main = main_frame.current();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        let rand_elements = aux_rand_elements.get_segment_elements(0);
+
+        // memory permutation argument: the product is updated by the first 3 accesses of a row
+        // in the partial column, and by the remaining 2 accesses in the next row of the product
+        // column
+        let accesses = memory_accesses(main, rand_elements);
+        let sorted = sorted_memory(main, rand_elements);
+        result[0] = aux_current[MEMORY_PARTIAL] * sorted[0] * sorted[1] * sorted[2]
+            - aux_current[MEMORY_PRODUCT] * accesses[0] * accesses[1] * accesses[2];
+        result[1] = aux_next[MEMORY_PRODUCT] * sorted[3] * sorted[4]
+            - aux_current[MEMORY_PARTIAL] * accesses[3] * accesses[4];
+
+        // range check permutation argument
+        let values = rc_values(main, rand_elements[2]);
+        let sorted = sorted_rc_values(main, rand_elements[2]);
+        result[2] = aux_current[RC_PARTIAL] * sorted[0] * sorted[1]
+            - aux_current[RC_PRODUCT] * values[0] * values[1];
+        result[3] = aux_next[RC_PRODUCT] * sorted[2] * sorted[3]
+            - aux_current[RC_PARTIAL] * values[2] * values[3];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        let pub_inputs = &self.pub_inputs;
+        let mut assertions = vec![
+            // initial and final state of the VM
+            Assertion::single(PC, 0, BaseElement::from(pub_inputs.initial_pc)),
+            Assertion::single(AP, 0, BaseElement::from(pub_inputs.initial_ap)),
+            Assertion::single(FP, 0, BaseElement::from(pub_inputs.initial_ap)),
+            Assertion::single(PC, last_step, BaseElement::from(pub_inputs.final_pc)),
+            Assertion::single(AP, last_step, BaseElement::from(pub_inputs.final_ap)),
+        ];
+
+        // bounds of range-checked values; the last row of the trace is not range-checked
+        for i in 0..NUM_RC_VALUES {
+            let (rc_min, rc_max) = (pub_inputs.rc_min, pub_inputs.rc_max);
+            assertions.push(Assertion::single(SORTED_RC + i, 0, rc_min.into()));
+            assertions.push(Assertion::single(
+                SORTED_RC + i,
+                last_step - 1,
+                rc_max.into(),
+            ));
+        }
+
+        assertions
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        let last_step = self.trace_length() - 1;
+        let rand_elements = aux_rand_elements.get_segment_elements(0);
+
+        // dummy (0, 0) accesses contribute z^|P| to the numerator of the memory permutation
+        // product, while the public cells contribute to the denominator
+        let (z, alpha) = (rand_elements[0], rand_elements[1]);
+        let public_memory = &self.pub_inputs.public_memory;
+        let numerator = z.exp((public_memory.len() as u64).into());
+        let denominator = public_memory.iter().fold(E::ONE, |acc, cell| {
+            acc * (z - (E::from(cell.address) + alpha * E::from(cell.value)))
+        });
+
+        vec![
+            Assertion::single(MEMORY_PRODUCT, 0, E::ONE),
+            Assertion::single(MEMORY_PRODUCT, last_step, numerator / denominator),
+            Assertion::single(RC_PRODUCT, 0, E::ONE),
+            Assertion::single(RC_PRODUCT, last_step, E::ONE),
+        ]
+    }
+}
+
+// CONSTRAINT INDEXES
+// ================================================================================================
+
+/// Index of the first memory constraint; there are two memory constraints per sorted pair.
+const MEMORY_CONSTRAINTS: usize = 29;
+
+/// Index of the first range check constraint; there is one constraint per sorted column.
+const RC_CONSTRAINTS: usize = MEMORY_CONSTRAINTS + 2 * NUM_MEMORY_ACCESSES;
+
+const NUM_MAIN_CONSTRAINTS: usize = RC_CONSTRAINTS + NUM_RC_VALUES;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the sorted (address, value) memory pair at the specified index of the row.
+fn sorted_memory_pair<E: FieldElement>(row: &[E], idx: usize) -> (E, E) {
+    (
+        row[SORTED_MEMORY + 2 * idx],
+        row[SORTED_MEMORY + 2 * idx + 1],
+    )
+}
+
+/// Returns z - (address + alpha * value) for all memory accesses of the row.
+pub(super) fn memory_accesses<F, E>(row: &[F], rand_elements: &[E]) -> [E; NUM_MEMORY_ACCESSES]
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let pairs = [
+        (row[PC], instruction_word(row)),
+        (row[DST_ADDR], row[DST]),
+        (row[OP0_ADDR], row[OP0]),
+        (row[OP1_ADDR], row[OP1]),
+        (row[PUB_ADDR], row[PUB_VALUE]),
+    ];
+    pairs.map(|(addr, value)| reduce_memory_pair(addr, value, rand_elements))
+}
+
+/// Returns the instruction word encoded by the biased offsets and flags of the row.
+pub(super) fn instruction_word<E: FieldElement>(row: &[E]) -> E {
+    let mut flags_word = E::ZERO;
+    for i in (0..NUM_FLAGS).rev() {
+        flags_word = flags_word.double() + row[FLAGS + i];
+    }
+    row[OFF_DST]
+        + E::from(1u64 << 16) * row[OFF_OP0]
+        + E::from(1u64 << 32) * row[OFF_OP1]
+        + E::from(1u64 << 48) * flags_word
+}
+
+/// Returns z - (address + alpha * value) for all sorted memory pairs of the row.
+pub(super) fn sorted_memory<F, E>(row: &[F], rand_elements: &[E]) -> [E; NUM_MEMORY_ACCESSES]
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let mut result = [E::ZERO; NUM_MEMORY_ACCESSES];
+    for (i, value) in result.iter_mut().enumerate() {
+        let (addr, value_) = sorted_memory_pair(row, i);
+        *value = reduce_memory_pair(addr, value_, rand_elements);
+    }
+    result
+}
+
+/// Returns z - value for all range-checked values of the row.
+pub(super) fn rc_values<F, E>(row: &[F], z: E) -> [E; NUM_RC_VALUES]
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    [row[OFF_DST], row[OFF_OP0], row[OFF_OP1], row[RC_PAD]].map(|value| z - E::from(value))
+}
+
+/// Returns z - value for all sorted range-checked values of the row.
+pub(super) fn sorted_rc_values<F, E>(row: &[F], z: E) -> [E; NUM_RC_VALUES]
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let mut result = [E::ZERO; NUM_RC_VALUES];
+    for (i, value) in result.iter_mut().enumerate() {
+        *value = z - E::from(row[SORTED_RC + i]);
+    }
+    result
+}
+
+fn reduce_memory_pair<F, E>(addr: F, value: F, rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    rand_elements[0] - (E::from(addr) + rand_elements[1] * E::from(value))
+}
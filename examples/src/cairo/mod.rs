@@ -0,0 +1,271 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    verify_with_serialized_inputs, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction,
+    Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement, StarkField},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+pub use air::{CairoAir, MemoryCell, PublicInputs};
+
+mod program;
+pub use program::{
+    Execution, Instruction, Label, Operand, Program, ProgramBuilder, Step, PROGRAM_BASE,
+};
+
+mod prover;
+pub use prover::CairoProver;
+
+mod trace;
+pub use trace::CairoTrace;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of flags in an instruction.
+const NUM_FLAGS: usize = 15;
+
+/// Bias of offsets encoded in instructions; offsets are in the range [-2^15, 2^15).
+const OFFSET_BIAS: u64 = 1 << 15;
+
+/// Number of memory accesses per row of the trace: instruction fetch, `dst`, `op0`, `op1`, and
+/// a public memory slot.
+const NUM_MEMORY_ACCESSES: usize = 5;
+
+/// Number of range-checked values per row of the trace: three instruction offsets and a padding
+/// value which fills gaps between offsets.
+const NUM_RC_VALUES: usize = 4;
+
+// Main trace columns: registers, decoded instruction, operands, and intermediate values. The
+// instruction word itself is not stored in the trace; it is computed from the offsets and flags.
+const PC: usize = 0;
+const AP: usize = 1;
+const FP: usize = 2;
+const OFF_DST: usize = 3;
+const OFF_OP0: usize = 4;
+const OFF_OP1: usize = 5;
+const FLAGS: usize = 6;
+const DST_ADDR: usize = FLAGS + NUM_FLAGS;
+const DST: usize = DST_ADDR + 1;
+const OP0_ADDR: usize = DST + 1;
+const OP0: usize = OP0_ADDR + 1;
+const OP1_ADDR: usize = OP0 + 1;
+const OP1: usize = OP1_ADDR + 1;
+const RES: usize = OP1 + 1;
+const MUL: usize = RES + 1;
+const T0: usize = MUL + 1;
+const T1: usize = T0 + 1;
+
+// Main trace columns: public memory slot, sorted memory accesses as (address, value) pairs,
+// range check padding, and sorted range-checked values.
+const PUB_ADDR: usize = T1 + 1;
+const PUB_VALUE: usize = PUB_ADDR + 1;
+const SORTED_MEMORY: usize = PUB_VALUE + 1;
+const RC_PAD: usize = SORTED_MEMORY + 2 * NUM_MEMORY_ACCESSES;
+const SORTED_RC: usize = RC_PAD + 1;
+const TRACE_WIDTH: usize = SORTED_RC + NUM_RC_VALUES;
+
+// Auxiliary trace columns: running products of the memory and range check permutation
+// arguments; each product is split into two columns to keep constraint degrees low.
+const MEMORY_PARTIAL: usize = 0;
+const MEMORY_PRODUCT: usize = 1;
+const RC_PARTIAL: usize = 2;
+const RC_PRODUCT: usize = 3;
+const AUX_TRACE_WIDTH: usize = 4;
+const AUX_TRACE_RANDS: usize = 3;
+
+/// Maximum number of steps the example program is allowed to execute.
+const MAX_STEPS: usize = 1 << 24;
+
+// CAIRO EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: &ExampleOptions, n: usize) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(CairoExample::<Blake3_192>::new(n, options)?)),
+        HashFunction::Blake3_256 => Ok(Box::new(CairoExample::<Blake3_256>::new(n, options)?)),
+        HashFunction::Sha3_256 => Ok(Box::new(CairoExample::<Sha3_256>::new(n, options)?)),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct CairoExample<H: ElementHasher> {
+    options: ProofOptions,
+    program: Program,
+    pub_inputs: PublicInputs,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> CairoExample<H> {
+    pub fn new(n: usize, options: ProofOptions) -> Result<Self, String> {
+        let program = build_factorial_program(n as i64);
+
+        // execute the program to determine public inputs of the computation
+        let now = Instant::now();
+        let execution = program.execute(MAX_STEPS)?;
+        let pub_inputs = PublicInputs::from_execution(&program, &execution, 1);
+        debug!(
+            "Executed Cairo program computing {}! in {} steps in {} ms",
+            n,
+            execution.steps.len(),
+            now.elapsed().as_millis()
+        );
+
+        let expected = (1..=n as u64).fold(BaseElement::ONE, |acc, k| acc * BaseElement::from(k));
+        if pub_inputs.output() != [expected] {
+            return Err("Cairo program computed an incorrect result".to_string());
+        }
+
+        Ok(CairoExample {
+            options,
+            program,
+            pub_inputs,
+            _hasher: PhantomData,
+        })
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for CairoExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for executing a Cairo program of {} words\n\
+            ---------------------",
+            self.program.words().len()
+        );
+
+        // create a prover
+        let prover = CairoProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.program, 1, MAX_STEPS).unwrap();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.main_trace_width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<CairoAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.pub_inputs.clone(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.pub_inputs.clone();
+        let output = pub_inputs
+            .public_memory
+            .last_mut()
+            .expect("no public memory");
+        output.value += BaseElement::ONE;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<CairoAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<CairoAir, H>(proof, pub_inputs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a program which computes n! and writes the result into the first cell of the
+/// execution segment.
+///
+/// ```text
+/// main:
+///     ap += 1                     # reserve a cell for the output
+///     [ap] = n; ap++
+///     call factorial
+///     [fp] = [ap - 1]             # write the result into the output cell
+///     jmp rel end
+///
+/// factorial:                      # [fp - 3] = n
+///     [ap] = 1; ap++              # acc = 1
+///     [ap] = [fp - 3]; ap++       # k = n
+/// loop:
+///     jmp rel body if [ap - 1] != 0
+///     [ap] = [ap - 2]; ap++       # return acc
+///     ret
+/// body:
+///     [ap] = [ap - 2] * [ap - 1]; ap++    # acc = acc * k
+///     [ap] = [ap - 2] + (-1); ap++        # k = k - 1
+///     jmp rel loop
+///
+/// end:
+///     jmp rel 0
+/// ```
+pub fn build_factorial_program(n: i64) -> Program {
+    use Operand::{Ap, Fp};
+
+    let mut builder = ProgramBuilder::new();
+    let factorial = builder.new_label();
+    let loop_start = builder.new_label();
+    let body = builder.new_label();
+
+    // main
+    builder.add_ap(1);
+    builder.assert_imm(Ap(0), n).ap_inc();
+    builder.call_rel(factorial);
+    builder.assert_copy(Fp(0), Ap(-1));
+    let end = builder.new_label();
+    builder.jmp_rel(end);
+
+    // factorial
+    builder.bind(factorial).assert_imm(Ap(0), 1).ap_inc();
+    builder.assert_copy(Ap(0), Fp(-3)).ap_inc();
+    builder.bind(loop_start).jnz_rel(body, Ap(-1));
+    builder.assert_copy(Ap(0), Ap(-2)).ap_inc();
+    builder.ret();
+    builder
+        .bind(body)
+        .assert_mul(Ap(0), Ap(-2), Ap(-1))
+        .ap_inc();
+    builder.assert_add_imm(Ap(0), Ap(-2), -1).ap_inc();
+    builder.jmp_rel(loop_start);
+
+    // the final jmp rel 0 instruction is appended by the builder
+    builder.bind(end);
+    builder.build()
+}
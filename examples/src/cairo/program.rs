@@ -0,0 +1,622 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, StarkField, NUM_FLAGS, OFFSET_BIAS};
+use std::collections::BTreeMap;
+
+// FLAGS
+// ================================================================================================
+
+// Indexes of instruction flags; these (as well as the layout of instruction words) are the same
+// as in the Cairo VM.
+pub const DST_REG: usize = 0;
+pub const OP0_REG: usize = 1;
+pub const OP1_IMM: usize = 2;
+pub const OP1_FP: usize = 3;
+pub const OP1_AP: usize = 4;
+pub const RES_ADD: usize = 5;
+pub const RES_MUL: usize = 6;
+pub const PC_JUMP_ABS: usize = 7;
+pub const PC_JUMP_REL: usize = 8;
+pub const PC_JNZ: usize = 9;
+pub const AP_ADD: usize = 10;
+pub const AP_ADD1: usize = 11;
+pub const OPCODE_CALL: usize = 12;
+pub const OPCODE_RET: usize = 13;
+pub const OPCODE_ASSERT_EQ: usize = 14;
+
+/// Address at which programs are loaded into memory; address 0 is reserved for dummy accesses
+/// of the public memory argument.
+pub const PROGRAM_BASE: u64 = 1;
+
+// INSTRUCTION
+// ================================================================================================
+
+/// A decoded Cairo instruction.
+///
+/// An instruction is encoded into a single 63-bit word as follows: bits 0..16 contain the `dst`
+/// offset, bits 16..32 contain the `op0` offset, bits 32..48 contain the `op1` offset, and bits
+/// 48..63 contain the flags. Offsets are encoded with a bias of 2^15 (i.e., offset -1 is encoded
+/// as 0x7fff).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub off_dst: i16,
+    pub off_op0: i16,
+    pub off_op1: i16,
+    pub flags: u16,
+}
+
+impl Instruction {
+    /// Returns this instruction encoded into a single word.
+    pub fn encode(&self) -> u64 {
+        biased(self.off_dst) as u64
+            | (biased(self.off_op0) as u64) << 16
+            | (biased(self.off_op1) as u64) << 32
+            | (self.flags as u64) << 48
+    }
+
+    /// Decodes an instruction from the specified word.
+    ///
+    /// # Errors
+    /// Returns an error if the word is not a valid encoding of an instruction supported by this
+    /// example: the word must fit into 63 bits, and at most one flag may be set in each of the
+    /// `op1` source, `res` logic, `pc` update, `ap` update, and opcode flag groups.
+    pub fn decode(word: BaseElement) -> Result<Self, String> {
+        let word = u64::try_from(word.as_int())
+            .ok()
+            .filter(|&word| word >> 63 == 0)
+            .ok_or_else(|| format!("{word} is not a valid instruction word"))?;
+
+        let instruction = Instruction {
+            off_dst: unbiased(word as u16),
+            off_op0: unbiased((word >> 16) as u16),
+            off_op1: unbiased((word >> 32) as u16),
+            flags: (word >> 48) as u16,
+        };
+
+        let groups: [&[usize]; 5] = [
+            &[OP1_IMM, OP1_FP, OP1_AP],
+            &[RES_ADD, RES_MUL],
+            &[PC_JUMP_ABS, PC_JUMP_REL, PC_JNZ],
+            &[AP_ADD, AP_ADD1],
+            &[OPCODE_CALL, OPCODE_RET, OPCODE_ASSERT_EQ],
+        ];
+        for group in groups {
+            if group.iter().filter(|&&flag| instruction.flag(flag)).count() > 1 {
+                return Err(format!("instruction {word:#x} has conflicting flags"));
+            }
+        }
+        if instruction.flag(PC_JNZ) && (instruction.flag(RES_ADD) || instruction.flag(RES_MUL)) {
+            return Err(format!("conditional jump {word:#x} cannot compute res"));
+        }
+        if instruction.flag(OP1_IMM) && instruction.off_op1 != 1 {
+            return Err(format!("immediate of instruction {word:#x} must follow it"));
+        }
+
+        Ok(instruction)
+    }
+
+    /// Returns true if the specified flag is set for this instruction.
+    pub fn flag(&self, flag: usize) -> bool {
+        debug_assert!(flag < NUM_FLAGS, "invalid flag index");
+        (self.flags >> flag) & 1 == 1
+    }
+
+    /// Returns the number of words occupied by this instruction (including its immediate).
+    pub fn size(&self) -> u64 {
+        1 + self.flag(OP1_IMM) as u64
+    }
+}
+
+// PROGRAM
+// ================================================================================================
+
+/// A memory operand of an instruction: `[ap + offset]` or `[fp + offset]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Ap(i16),
+    Fp(i16),
+}
+
+/// A label which relative jumps and calls can refer to; labels are created via
+/// [ProgramBuilder::new_label()] and bound to a position in the program via
+/// [ProgramBuilder::bind()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Label(usize);
+
+/// A sequence of words (instructions and immediate values) of a Cairo program.
+///
+/// The program is executed starting from its first word, and execution ends when the `pc`
+/// reaches the last instruction of the program, which is always `jmp rel 0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    words: Vec<BaseElement>,
+}
+
+impl Program {
+    /// Returns words of this program.
+    pub fn words(&self) -> &[BaseElement] {
+        &self.words
+    }
+
+    /// Returns the address of the first instruction of this program.
+    pub fn initial_pc(&self) -> u64 {
+        PROGRAM_BASE
+    }
+
+    /// Returns the address of the final `jmp rel 0` instruction of this program.
+    pub fn final_pc(&self) -> u64 {
+        PROGRAM_BASE + self.words.len() as u64 - 2
+    }
+
+    /// Returns the initial value of `ap` and `fp` registers; the execution segment of the memory
+    /// starts immediately after the program.
+    pub fn initial_ap(&self) -> u64 {
+        PROGRAM_BASE + self.words.len() as u64
+    }
+
+    /// Executes this program and returns the executed steps.
+    ///
+    /// The returned steps end with a step executing the final `jmp rel 0` instruction.
+    ///
+    /// # Errors
+    /// Returns an error if the program reads a memory cell which was not written, writes a
+    /// different value into a memory cell which was already written, attempts to execute an
+    /// invalid instruction, or does not terminate in `max_steps` steps.
+    pub fn execute(&self, max_steps: usize) -> Result<Execution, String> {
+        let mut vm = Vm::new(self);
+        let mut steps = Vec::new();
+        loop {
+            if steps.len() == max_steps {
+                return Err(format!("program did not terminate in {max_steps} steps"));
+            }
+            let step = vm.step()?;
+            let done = step.pc == BaseElement::from(self.final_pc());
+            steps.push(step);
+            if done {
+                break;
+            }
+        }
+        Ok(Execution {
+            steps,
+            memory: vm.memory,
+        })
+    }
+}
+
+/// Builds [Program]s from instructions written in (a subset of) Cairo assembly.
+///
+/// Operands which are not used by an instruction refer to `[fp - 1]` (as they do in programs
+/// compiled by the Cairo compiler); this cell always exists since it is either the last word of
+/// the program, or the return address of the current function.
+#[derive(Debug, Default)]
+pub struct ProgramBuilder {
+    words: Vec<BaseElement>,
+    labels: Vec<Option<u64>>,
+    jumps: Vec<(usize, Label)>,
+}
+
+impl ProgramBuilder {
+    /// Returns a new builder for an empty program.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // LABELS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new label which is not yet bound to any position in the program.
+    pub fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+        Label(self.labels.len() - 1)
+    }
+
+    /// Binds the specified label to the position of the next instruction.
+    ///
+    /// # Panics
+    /// Panics if the label has already been bound.
+    pub fn bind(&mut self, label: Label) -> &mut Self {
+        assert!(self.labels[label.0].is_none(), "label is already bound");
+        self.labels[label.0] = Some(self.words.len() as u64);
+        self
+    }
+
+    // INSTRUCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// `[dst] = imm`
+    pub fn assert_imm(&mut self, dst: Operand, imm: i64) -> &mut Self {
+        let flags = flags(&[OP1_IMM, OPCODE_ASSERT_EQ]) | dst_flags(dst);
+        self.push(dst.offset(), -1, 1, flags | bit(OP0_REG), Some(imm))
+    }
+
+    /// `[dst] = [src]`
+    pub fn assert_copy(&mut self, dst: Operand, src: Operand) -> &mut Self {
+        let flags = flags(&[OPCODE_ASSERT_EQ]) | dst_flags(dst) | op1_flags(src);
+        self.push(dst.offset(), -1, src.offset(), flags | bit(OP0_REG), None)
+    }
+
+    /// `[dst] = [op0] + [op1]`
+    pub fn assert_add(&mut self, dst: Operand, op0: Operand, op1: Operand) -> &mut Self {
+        let flags = flags(&[RES_ADD, OPCODE_ASSERT_EQ]) | dst_flags(dst) | op0_flags(op0);
+        self.push(
+            dst.offset(),
+            op0.offset(),
+            op1.offset(),
+            flags | op1_flags(op1),
+            None,
+        )
+    }
+
+    /// `[dst] = [op0] + imm`
+    pub fn assert_add_imm(&mut self, dst: Operand, op0: Operand, imm: i64) -> &mut Self {
+        let flags = flags(&[OP1_IMM, RES_ADD, OPCODE_ASSERT_EQ]) | dst_flags(dst);
+        self.push(
+            dst.offset(),
+            op0.offset(),
+            1,
+            flags | op0_flags(op0),
+            Some(imm),
+        )
+    }
+
+    /// `[dst] = [op0] * [op1]`
+    pub fn assert_mul(&mut self, dst: Operand, op0: Operand, op1: Operand) -> &mut Self {
+        let flags = flags(&[RES_MUL, OPCODE_ASSERT_EQ]) | dst_flags(dst) | op0_flags(op0);
+        self.push(
+            dst.offset(),
+            op0.offset(),
+            op1.offset(),
+            flags | op1_flags(op1),
+            None,
+        )
+    }
+
+    /// `ap += imm`
+    pub fn add_ap(&mut self, imm: i64) -> &mut Self {
+        let flags = flags(&[DST_REG, OP0_REG, OP1_IMM, AP_ADD]);
+        self.push(-1, -1, 1, flags, Some(imm))
+    }
+
+    /// `jmp rel label`
+    pub fn jmp_rel(&mut self, label: Label) -> &mut Self {
+        let flags = flags(&[DST_REG, OP0_REG, OP1_IMM, PC_JUMP_REL]);
+        self.push_jump(-1, -1, flags, label)
+    }
+
+    /// `jmp rel label if [cond] != 0`
+    pub fn jnz_rel(&mut self, label: Label, cond: Operand) -> &mut Self {
+        let flags = flags(&[OP0_REG, OP1_IMM, PC_JNZ]) | dst_flags(cond);
+        self.push_jump(cond.offset(), -1, flags, label)
+    }
+
+    /// `call rel label`
+    pub fn call_rel(&mut self, label: Label) -> &mut Self {
+        let flags = flags(&[OP1_IMM, PC_JUMP_REL, OPCODE_CALL]);
+        self.push_jump(0, 1, flags, label)
+    }
+
+    /// `ret`
+    pub fn ret(&mut self) -> &mut Self {
+        let flags = flags(&[DST_REG, OP0_REG, OP1_FP, PC_JUMP_ABS, OPCODE_RET]);
+        self.push(-2, -1, -1, flags, None)
+    }
+
+    /// Appends `; ap++` to the last instruction.
+    ///
+    /// # Panics
+    /// Panics if the last instruction already updates the `ap` register.
+    pub fn ap_inc(&mut self) -> &mut Self {
+        let (idx, mut instruction) = self.last_instruction();
+        assert!(
+            !instruction.flag(AP_ADD) && !instruction.flag(OPCODE_CALL),
+            "last instruction already updates ap"
+        );
+        instruction.flags |= bit(AP_ADD1);
+        self.words[idx] = BaseElement::from(instruction.encode());
+        self
+    }
+
+    /// Appends the final `jmp rel 0` instruction to the program and returns it.
+    ///
+    /// # Panics
+    /// Panics if any of the labels referred to by the instructions is not bound.
+    pub fn build(mut self) -> Program {
+        let end = self.new_label();
+        self.bind(end).jmp_rel(end);
+
+        for (imm_idx, label) in self.jumps.iter() {
+            let target = self.labels[label.0].expect("label is not bound");
+            // relative offsets are computed from the address of the instruction
+            let offset = target as i64 - (*imm_idx as i64 - 1);
+            self.words[*imm_idx] = felt(offset);
+        }
+        Program { words: self.words }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn push(
+        &mut self,
+        off_dst: i16,
+        off_op0: i16,
+        off_op1: i16,
+        flags: u16,
+        imm: Option<i64>,
+    ) -> &mut Self {
+        let instruction = Instruction {
+            off_dst,
+            off_op0,
+            off_op1,
+            flags,
+        };
+        self.words.push(BaseElement::from(instruction.encode()));
+        if let Some(imm) = imm {
+            self.words.push(felt(imm));
+        }
+        self
+    }
+
+    fn push_jump(&mut self, off_dst: i16, off_op0: i16, flags: u16, label: Label) -> &mut Self {
+        self.push(off_dst, off_op0, 1, flags, Some(0));
+        self.jumps.push((self.words.len() - 1, label));
+        self
+    }
+
+    fn last_instruction(&self) -> (usize, Instruction) {
+        let mut idx = 0;
+        let mut last = None;
+        while idx < self.words.len() {
+            let instruction = Instruction::decode(self.words[idx]).expect("invalid instruction");
+            last = Some((idx, instruction));
+            idx += instruction.size() as usize;
+        }
+        last.expect("program is empty")
+    }
+}
+
+impl Operand {
+    fn offset(&self) -> i16 {
+        match *self {
+            Operand::Ap(offset) | Operand::Fp(offset) => offset,
+        }
+    }
+}
+
+// EXECUTION
+// ================================================================================================
+
+/// A single step of program execution; contains values of all registers and operands of the
+/// executed instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub pc: BaseElement,
+    pub ap: BaseElement,
+    pub fp: BaseElement,
+    pub word: BaseElement,
+    pub instruction: Instruction,
+    pub dst_addr: BaseElement,
+    pub dst: BaseElement,
+    pub op0_addr: BaseElement,
+    pub op0: BaseElement,
+    pub op1_addr: BaseElement,
+    pub op1: BaseElement,
+    pub res: BaseElement,
+}
+
+impl Step {
+    /// Returns memory accesses made by this step as (address, value) pairs.
+    pub fn memory_accesses(&self) -> [(BaseElement, BaseElement); 4] {
+        [
+            (self.pc, self.word),
+            (self.dst_addr, self.dst),
+            (self.op0_addr, self.op0),
+            (self.op1_addr, self.op1),
+        ]
+    }
+}
+
+/// Result of executing a [Program].
+#[derive(Debug, Clone)]
+pub struct Execution {
+    pub steps: Vec<Step>,
+    pub memory: BTreeMap<u64, BaseElement>,
+}
+
+/// A minimal Cairo virtual machine.
+struct Vm {
+    memory: BTreeMap<u64, BaseElement>,
+    pc: BaseElement,
+    ap: BaseElement,
+    fp: BaseElement,
+}
+
+impl Vm {
+    fn new(program: &Program) -> Self {
+        let memory = program
+            .words
+            .iter()
+            .enumerate()
+            .map(|(i, &word)| (PROGRAM_BASE + i as u64, word))
+            .collect();
+        let initial_ap = BaseElement::from(program.initial_ap());
+        Vm {
+            memory,
+            pc: BaseElement::from(program.initial_pc()),
+            ap: initial_ap,
+            fp: initial_ap,
+        }
+    }
+
+    fn step(&mut self) -> Result<Step, String> {
+        let (pc, ap, fp) = (self.pc, self.ap, self.fp);
+        let word = self.read(pc)?;
+        let instruction = Instruction::decode(word)?;
+        let size = BaseElement::from(instruction.size());
+
+        // compute operand addresses
+        let dst_base = if instruction.flag(DST_REG) { fp } else { ap };
+        let dst_addr = dst_base + felt(instruction.off_dst as i64);
+        let op0_base = if instruction.flag(OP0_REG) { fp } else { ap };
+        let op0_addr = op0_base + felt(instruction.off_op0 as i64);
+
+        // when calling a function, op0 holds the return address
+        let op0 = if instruction.flag(OPCODE_CALL) {
+            self.write(op0_addr, pc + size)?
+        } else {
+            self.read(op0_addr)?
+        };
+
+        let op1_base = if instruction.flag(OP1_IMM) {
+            pc
+        } else if instruction.flag(OP1_AP) {
+            ap
+        } else if instruction.flag(OP1_FP) {
+            fp
+        } else {
+            op0
+        };
+        let op1_addr = op1_base + felt(instruction.off_op1 as i64);
+        let op1 = self.read(op1_addr)?;
+
+        // compute res and dst; assertions write res into dst if dst has not been written yet;
+        // for conditional jumps, res holds the inverse of dst
+        let (res, dst) = if instruction.flag(PC_JNZ) {
+            let dst = self.read(dst_addr)?;
+            (dst.inv(), dst)
+        } else {
+            let res = if instruction.flag(RES_ADD) {
+                op0 + op1
+            } else if instruction.flag(RES_MUL) {
+                op0 * op1
+            } else {
+                op1
+            };
+            let dst = if instruction.flag(OPCODE_CALL) {
+                self.write(dst_addr, fp)?
+            } else if instruction.flag(OPCODE_ASSERT_EQ) {
+                self.write(dst_addr, res)?
+            } else {
+                self.read(dst_addr)?
+            };
+            (res, dst)
+        };
+
+        // update registers
+        self.pc = if instruction.flag(PC_JUMP_ABS) {
+            res
+        } else if instruction.flag(PC_JUMP_REL) {
+            pc + res
+        } else if instruction.flag(PC_JNZ) && dst != BaseElement::ZERO {
+            pc + op1
+        } else {
+            pc + size
+        };
+        self.ap = if instruction.flag(AP_ADD) {
+            ap + res
+        } else if instruction.flag(AP_ADD1) {
+            ap + BaseElement::ONE
+        } else if instruction.flag(OPCODE_CALL) {
+            ap + BaseElement::from(2u8)
+        } else {
+            ap
+        };
+        self.fp = if instruction.flag(OPCODE_RET) {
+            dst
+        } else if instruction.flag(OPCODE_CALL) {
+            ap + BaseElement::from(2u8)
+        } else {
+            fp
+        };
+
+        Ok(Step {
+            pc,
+            ap,
+            fp,
+            word,
+            instruction,
+            dst_addr,
+            dst,
+            op0_addr,
+            op0,
+            op1_addr,
+            op1,
+            res,
+        })
+    }
+
+    fn read(&self, address: BaseElement) -> Result<BaseElement, String> {
+        let address = to_address(address)?;
+        self.memory
+            .get(&address)
+            .copied()
+            .ok_or_else(|| format!("memory cell {address} was read before being written"))
+    }
+
+    fn write(&mut self, address: BaseElement, value: BaseElement) -> Result<BaseElement, String> {
+        let address = to_address(address)?;
+        match self.memory.insert(address, value) {
+            Some(old_value) if old_value != value => Err(format!(
+                "memory cell {address} was overwritten: {old_value} != {value}"
+            )),
+            _ => Ok(value),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts a signed integer into a field element.
+pub fn felt(value: i64) -> BaseElement {
+    if value < 0 {
+        -BaseElement::from(value.unsigned_abs())
+    } else {
+        BaseElement::from(value as u64)
+    }
+}
+
+/// Returns the biased representation of the specified offset.
+pub fn biased(offset: i16) -> u16 {
+    (offset as i32 + OFFSET_BIAS as i32) as u16
+}
+
+fn unbiased(offset: u16) -> i16 {
+    (offset as i32 - OFFSET_BIAS as i32) as i16
+}
+
+fn to_address(address: BaseElement) -> Result<u64, String> {
+    u64::try_from(address.as_int()).map_err(|_| format!("{address} is not a valid address"))
+}
+
+const fn bit(flag: usize) -> u16 {
+    1 << flag
+}
+
+fn flags(flags: &[usize]) -> u16 {
+    flags.iter().fold(0, |acc, &flag| acc | bit(flag))
+}
+
+fn dst_flags(dst: Operand) -> u16 {
+    match dst {
+        Operand::Ap(_) => 0,
+        Operand::Fp(_) => bit(DST_REG),
+    }
+}
+
+fn op0_flags(op0: Operand) -> u16 {
+    match op0 {
+        Operand::Ap(_) => 0,
+        Operand::Fp(_) => bit(OP0_REG),
+    }
+}
+
+fn op1_flags(op1: Operand) -> u16 {
+    match op1 {
+        Operand::Ap(_) => bit(OP1_AP),
+        Operand::Fp(_) => bit(OP1_FP),
+    }
+}
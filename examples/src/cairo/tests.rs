@@ -0,0 +1,124 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_factorial_program, BaseElement, Blake3_256, CairoAir, CairoExample, CairoProver,
+    Instruction, Operand, ProgramBuilder, MAX_STEPS,
+};
+use winterfell::{
+    debug::ConstraintDebugger, math::fields::QuadExtension, Air, AuxTraceRandElements,
+    FieldExtension, ProofOptions, Prover, Trace,
+};
+
+#[test]
+fn cairo_test_basic_proof_verification() {
+    let cairo_eg = Box::new(CairoExample::<Blake3_256>::new(5, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification(cairo_eg);
+}
+
+#[test]
+fn cairo_test_basic_proof_verification_extension() {
+    let cairo_eg = Box::new(CairoExample::<Blake3_256>::new(5, build_options(true)).unwrap());
+    crate::tests::test_basic_proof_verification(cairo_eg);
+}
+
+#[test]
+fn cairo_test_basic_proof_verification_fail() {
+    let cairo_eg = Box::new(CairoExample::<Blake3_256>::new(5, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification_fail(cairo_eg);
+}
+
+#[test]
+fn cairo_test_proof_verification_with_pub_inputs() {
+    let cairo_eg = Box::new(CairoExample::<Blake3_256>::new(5, build_options(false)).unwrap());
+    crate::tests::test_proof_verification_with_pub_inputs(cairo_eg);
+}
+
+#[test]
+fn cairo_test_constraint_debugger() {
+    type E = QuadExtension<BaseElement>;
+
+    let options = build_options(true);
+    let prover = CairoProver::<Blake3_256>::new(options.clone());
+    let program = build_factorial_program(10);
+    let mut trace = prover.build_trace(&program, 1, MAX_STEPS).unwrap();
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    assert_eq!(vec![BaseElement::new(3628800)], pub_inputs.output());
+    let air = CairoAir::new(trace.get_info(), pub_inputs, options);
+
+    let num_rand_elements = air.trace_layout().get_aux_segment_rand_elements(0);
+    let rand_elements = (0..num_rand_elements as u128)
+        .map(|i| E::new(BaseElement::new(i + 3), BaseElement::new(i + 5)))
+        .collect::<Vec<_>>();
+    let aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    aux_rand_elements.add_segment_elements(rand_elements);
+
+    let aux_segments = [aux_segment];
+    let debugger =
+        ConstraintDebugger::with_aux_segments(&air, &trace, &aux_segments, aux_rand_elements);
+    assert!(debugger.unsatisfied_constraints().is_empty());
+}
+
+#[test]
+fn cairo_test_instruction_encoding() {
+    use Operand::{Ap, Fp};
+
+    // encodings of the same instructions produced by the Cairo compiler
+    let mut builder = ProgramBuilder::new();
+    builder.ret();
+    builder.assert_imm(Ap(0), 5).ap_inc();
+    builder.assert_copy(Fp(-2), Ap(-1));
+    let program = builder.build();
+    let expected = [
+        0x208b7fff7fff7ffe,
+        0x480680017fff8000,
+        5,
+        0x40137fff7fff7ffe,
+        0x010780017fff7fff,
+        0,
+    ];
+    assert_eq!(expected.map(BaseElement::from).as_slice(), program.words());
+
+    for &word in [expected[0], expected[1], expected[3], expected[4]].iter() {
+        let instruction = Instruction::decode(BaseElement::from(word)).unwrap();
+        assert_eq!(word, instruction.encode());
+    }
+}
+
+#[test]
+fn cairo_test_invalid_instructions() {
+    // flags of an instruction word must fit into 15 bits
+    assert!(Instruction::decode(BaseElement::from(1u64 << 63)).is_err());
+    // op1 cannot be both an immediate and an fp-relative cell
+    assert!(Instruction::decode(BaseElement::from(0x000c_8001_7fff_7fffu64)).is_err());
+    // an immediate must immediately follow the instruction
+    assert!(Instruction::decode(BaseElement::from(0x0004_8002_7fff_7fffu64)).is_err());
+}
+
+#[test]
+fn cairo_test_execution_errors() {
+    use Operand::Ap;
+
+    // conflicting writes into the same cell
+    let mut builder = ProgramBuilder::new();
+    builder.assert_imm(Ap(0), 1);
+    builder.assert_imm(Ap(0), 2);
+    assert!(builder.build().execute(MAX_STEPS).is_err());
+
+    // the program does not terminate within the allowed number of steps
+    let program = build_factorial_program(10);
+    assert!(program.execute(10).is_err());
+    assert!(program.execute(MAX_STEPS).is_ok());
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,299 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    air::{memory_accesses, rc_values, sorted_memory, sorted_rc_values},
+    program::{biased, Execution, Step, PC_JNZ},
+    BaseElement, FieldElement, PublicInputs, StarkField, AP, AUX_TRACE_RANDS, AUX_TRACE_WIDTH, DST,
+    DST_ADDR, FLAGS, FP, MEMORY_PARTIAL, MEMORY_PRODUCT, MUL, NUM_FLAGS, NUM_MEMORY_ACCESSES,
+    NUM_RC_VALUES, OFF_DST, OFF_OP0, OFF_OP1, OP0, OP0_ADDR, OP1, OP1_ADDR, PC, PUB_ADDR,
+    PUB_VALUE, RC_PAD, RC_PARTIAL, RC_PRODUCT, RES, SORTED_MEMORY, SORTED_RC, T0, T1, TRACE_WIDTH,
+};
+use winterfell::{ColMatrix, EvaluationFrame, Trace, TraceInfo, TraceLayout};
+
+// CAIRO TRACE
+// ================================================================================================
+
+/// Execution trace of a Cairo program.
+///
+/// The first rows of the trace describe the executed steps; the remaining rows repeat the final
+/// `jmp rel 0` step. Public memory cells are "removed" from the memory accessed by the trace by
+/// placing dummy (0, 0) accesses into the public memory slots of the first rows.
+pub struct CairoTrace {
+    layout: TraceLayout,
+    trace: ColMatrix<BaseElement>,
+    pub_inputs: PublicInputs,
+}
+
+impl CairoTrace {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds an execution trace from the specified `execution` of a program described by
+    /// `pub_inputs`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Memory accessed by the execution is not contiguous.
+    /// * The trace would be longer than the biggest multiplicative subgroup of the field.
+    pub fn new(execution: &Execution, pub_inputs: PublicInputs) -> Result<Self, String> {
+        let steps = &execution.steps;
+        let num_public_cells = pub_inputs.public_memory.len();
+        let rc_padding = get_rc_padding(steps, pub_inputs.rc_min, pub_inputs.rc_max);
+
+        // the last row of the trace is not constrained by transition constraints, and so it
+        // cannot hold public memory slots or range check padding values
+        let trace_length = (steps.len().max(num_public_cells).max(rc_padding.len()) + 1)
+            .next_power_of_two()
+            .max(TraceInfo::MIN_TRACE_LENGTH);
+        if trace_length.ilog2() > BaseElement::TWO_ADICITY {
+            return Err(format!(
+                "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+                BaseElement::TWO_ADICITY,
+                trace_length.ilog2()
+            ));
+        }
+
+        let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+        let mut memory = Vec::with_capacity(NUM_MEMORY_ACCESSES * trace_length);
+        let last_step = steps.last().expect("no steps were executed");
+        for (i, step) in steps
+            .iter()
+            .chain(core::iter::repeat(last_step))
+            .take(trace_length)
+            .enumerate()
+        {
+            fill_step(&mut columns, i, step);
+
+            // public memory slots of the first rows hold dummy accesses; all other slots
+            // repeat the instruction fetch
+            if i >= num_public_cells {
+                columns[PUB_ADDR][i] = step.pc;
+                columns[PUB_VALUE][i] = step.word;
+            }
+
+            // memory accessed by the last row is not a part of the permutation argument
+            if i < trace_length - 1 {
+                memory.extend(step.memory_accesses());
+                if i >= num_public_cells {
+                    memory.push((step.pc, step.word));
+                }
+            }
+
+            // range check padding values make sure each value can be placed in every column
+            columns[RC_PAD][i] = match rc_padding.get(i) {
+                Some(&value) => BaseElement::from(value),
+                None => BaseElement::from(pub_inputs.rc_min),
+            };
+        }
+
+        fill_sorted_memory(&mut columns, memory, &pub_inputs)?;
+        fill_sorted_rc(&mut columns);
+
+        Ok(CairoTrace {
+            layout: TraceLayout::new(TRACE_WIDTH, [AUX_TRACE_WIDTH], [AUX_TRACE_RANDS]),
+            trace: ColMatrix::new(columns),
+            pub_inputs,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns public inputs of the program execution described by this trace.
+    pub fn pub_inputs(&self) -> &PublicInputs {
+        &self.pub_inputs
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of this trace.
+    pub fn get(&self, column: usize, step: usize) -> BaseElement {
+        self.trace.get(column, step)
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl Trace for CairoTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.trace.read_row_into(row_idx, frame.current_mut());
+        self.trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        &self.trace
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // there is only one auxiliary segment in this example
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let trace_length = self.length();
+        let mut aux_columns = vec![vec![E::ZERO; trace_length]; AUX_TRACE_WIDTH];
+        aux_columns[MEMORY_PRODUCT][0] = E::ONE;
+        aux_columns[RC_PRODUCT][0] = E::ONE;
+
+        let mut row = vec![BaseElement::ZERO; TRACE_WIDTH];
+        for i in 0..trace_length - 1 {
+            self.trace.read_row_into(i, &mut row);
+
+            // memory permutation argument
+            let accesses = memory_accesses(&row, rand_elements);
+            let sorted = sorted_memory(&row, rand_elements);
+            let partial = aux_columns[MEMORY_PRODUCT][i] * accesses[0] * accesses[1] * accesses[2]
+                / (sorted[0] * sorted[1] * sorted[2]);
+            aux_columns[MEMORY_PARTIAL][i] = partial;
+            aux_columns[MEMORY_PRODUCT][i + 1] =
+                partial * accesses[3] * accesses[4] / (sorted[3] * sorted[4]);
+
+            // range check permutation argument
+            let values = rc_values(&row, rand_elements[2]);
+            let sorted = sorted_rc_values(&row, rand_elements[2]);
+            let partial =
+                aux_columns[RC_PRODUCT][i] * values[0] * values[1] / (sorted[0] * sorted[1]);
+            aux_columns[RC_PARTIAL][i] = partial;
+            aux_columns[RC_PRODUCT][i + 1] =
+                partial * values[2] * values[3] / (sorted[2] * sorted[3]);
+        }
+
+        Some(ColMatrix::new(aux_columns))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes registers, decoded instruction, and operands of the `step` into the specified row.
+fn fill_step(columns: &mut [Vec<BaseElement>], row: usize, step: &Step) {
+    let instruction = step.instruction;
+    columns[PC][row] = step.pc;
+    columns[AP][row] = step.ap;
+    columns[FP][row] = step.fp;
+    columns[OFF_DST][row] = BaseElement::from(biased(instruction.off_dst));
+    columns[OFF_OP0][row] = BaseElement::from(biased(instruction.off_op0));
+    columns[OFF_OP1][row] = BaseElement::from(biased(instruction.off_op1));
+    for i in 0..NUM_FLAGS {
+        columns[FLAGS + i][row] = BaseElement::from(instruction.flag(i) as u8);
+    }
+    columns[DST_ADDR][row] = step.dst_addr;
+    columns[DST][row] = step.dst;
+    columns[OP0_ADDR][row] = step.op0_addr;
+    columns[OP0][row] = step.op0;
+    columns[OP1_ADDR][row] = step.op1_addr;
+    columns[OP1][row] = step.op1;
+    columns[RES][row] = step.res;
+    columns[MUL][row] = step.op0 * step.op1;
+    columns[T0][row] = if instruction.flag(PC_JNZ) {
+        step.dst
+    } else {
+        BaseElement::ZERO
+    };
+    columns[T1][row] = columns[T0][row] * step.res;
+}
+
+/// Fills the sorted memory columns with the specified `memory` accesses (excluding the dummy
+/// accesses) and the public memory cells.
+fn fill_sorted_memory(
+    columns: &mut [Vec<BaseElement>],
+    mut memory: Vec<(BaseElement, BaseElement)>,
+    pub_inputs: &PublicInputs,
+) -> Result<(), String> {
+    for cell in pub_inputs.public_memory.iter() {
+        memory.push((BaseElement::from(cell.address), cell.value));
+    }
+    memory.sort_by_key(|&(address, _)| address.as_int());
+
+    for window in memory.windows(2) {
+        let delta = window[1].0 - window[0].0;
+        if delta != BaseElement::ZERO && delta != BaseElement::ONE {
+            return Err(format!(
+                "accessed memory is not contiguous: no cells between addresses {} and {}",
+                window[0].0, window[1].0
+            ));
+        }
+    }
+
+    let last = *memory.last().expect("no memory was accessed");
+    for (i, &(address, value)) in memory
+        .iter()
+        .chain(core::iter::repeat(&last).take(NUM_MEMORY_ACCESSES))
+        .enumerate()
+    {
+        let (row, pair) = (i / NUM_MEMORY_ACCESSES, i % NUM_MEMORY_ACCESSES);
+        columns[SORTED_MEMORY + 2 * pair][row] = address;
+        columns[SORTED_MEMORY + 2 * pair + 1][row] = value;
+    }
+
+    Ok(())
+}
+
+/// Fills the sorted range check columns with the range-checked values of all but the last row
+/// of the trace.
+///
+/// The sorted values are distributed among the columns in a round-robin fashion; since every
+/// value appears at least [NUM_RC_VALUES] times, each column is a contiguous sequence of values
+/// from `rc_min` to `rc_max`.
+fn fill_sorted_rc(columns: &mut [Vec<BaseElement>]) {
+    let trace_length = columns[PC].len();
+    let mut values = (0..trace_length - 1)
+        .flat_map(|i| [OFF_DST, OFF_OP0, OFF_OP1, RC_PAD].map(|column| columns[column][i].as_int()))
+        .collect::<Vec<_>>();
+    values.sort_unstable();
+
+    let last = *values.last().expect("no values were range-checked");
+    for (i, &value) in values
+        .iter()
+        .chain(core::iter::repeat(&last).take(NUM_RC_VALUES))
+        .enumerate()
+    {
+        columns[SORTED_RC + i % NUM_RC_VALUES][i / NUM_RC_VALUES] = BaseElement::from(value);
+    }
+}
+
+/// Returns padding values which, together with the offsets of the executed steps, contain every
+/// value in the range [rc_min, rc_max] at least [NUM_RC_VALUES] times.
+fn get_rc_padding(steps: &[Step], rc_min: u16, rc_max: u16) -> Vec<u16> {
+    let mut counts = vec![0; (rc_max - rc_min) as usize + 1];
+    for step in steps.iter() {
+        let instruction = step.instruction;
+        for offset in [
+            instruction.off_dst,
+            instruction.off_op0,
+            instruction.off_op1,
+        ] {
+            counts[(biased(offset) - rc_min) as usize] += 1;
+        }
+    }
+    (rc_min..=rc_max)
+        .zip(counts)
+        .flat_map(|(value, count)| {
+            core::iter::repeat(value).take(NUM_RC_VALUES.saturating_sub(count))
+        })
+        .collect()
+}
@@ -0,0 +1,184 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{fs, path::Path};
+
+// OPTIONS FILE
+// ================================================================================================
+
+/// Proof options read from a TOML file.
+///
+/// The file consists of `key = value` pairs, optionally placed under an `[options]` table; values
+/// are either integers or strings. For example:
+///
+/// ```toml
+/// hash_fn = "blake3_256"
+/// num_queries = 28
+/// blowup_factor = 8
+/// grinding_factor = 16
+/// field_extension = 1
+/// fri_folding_factor = 8
+/// fri_remainder_max_degree = 31
+/// ```
+///
+/// All options are optional; options which are not specified in the file are set to the
+/// defaults of the proven example.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OptionsFile {
+    pub hash_fn: Option<String>,
+    pub num_queries: Option<usize>,
+    pub blowup_factor: Option<usize>,
+    pub grinding_factor: Option<u32>,
+    pub field_extension: Option<u32>,
+    pub fri_folding_factor: Option<usize>,
+    pub fri_remainder_max_degree: Option<usize>,
+}
+
+impl OptionsFile {
+    /// Reads proof options from the TOML file at the specified `path`.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read or parsed.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        Self::parse(&source).map_err(|err| format!("failed to parse {}: {err}", path.display()))
+    }
+
+    /// Parses proof options from the specified TOML `source`.
+    ///
+    /// Only the subset of TOML needed to describe proof options is supported: comments, an
+    /// `[options]` table header, and `key = value` pairs with integer or basic string values.
+    ///
+    /// # Errors
+    /// Returns an error if the source is not valid TOML, if it contains unknown keys or tables,
+    /// if a key is specified more than once, or if a value has an unexpected type.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut result = OptionsFile::default();
+        for (line_idx, line) in source.lines().enumerate() {
+            let line_num = line_idx + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                match table.strip_suffix(']').map(str::trim) {
+                    Some("options") => continue,
+                    Some(table) => return Err(format!("line {line_num}: unknown table [{table}]")),
+                    None => return Err(format!("line {line_num}: invalid table header")),
+                }
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {line_num}: expected a key-value pair"))?;
+            let key = key.trim();
+            let value = Value::parse(value.trim())
+                .map_err(|err| format!("line {line_num}: invalid value of {key}: {err}"))?;
+            result
+                .set(key, value)
+                .map_err(|err| format!("line {line_num}: {err}"))?;
+        }
+        Ok(result)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the option identified by `key` to the specified `value`.
+    fn set(&mut self, key: &str, value: Value) -> Result<(), String> {
+        fn set_once<T>(option: &mut Option<T>, key: &str, value: T) -> Result<(), String> {
+            if option.replace(value).is_some() {
+                return Err(format!("{key} is specified more than once"));
+            }
+            Ok(())
+        }
+
+        match key {
+            "hash_fn" => set_once(&mut self.hash_fn, key, value.into_string(key)?),
+            "num_queries" => set_once(&mut self.num_queries, key, value.into_int(key)?),
+            "blowup_factor" => set_once(&mut self.blowup_factor, key, value.into_int(key)?),
+            "grinding_factor" => set_once(&mut self.grinding_factor, key, value.into_int(key)?),
+            "field_extension" => set_once(&mut self.field_extension, key, value.into_int(key)?),
+            "fri_folding_factor" => {
+                set_once(&mut self.fri_folding_factor, key, value.into_int(key)?)
+            }
+            "fri_remainder_max_degree" => set_once(
+                &mut self.fri_remainder_max_degree,
+                key,
+                value.into_int(key)?,
+            ),
+            _ => Err(format!("unknown option {key}")),
+        }
+    }
+}
+
+// VALUE
+// ================================================================================================
+
+/// Value of an option in a TOML file.
+enum Value {
+    Int(u64),
+    String(String),
+}
+
+impl Value {
+    /// Parses an integer or a basic string from the specified `source`.
+    fn parse(source: &str) -> Result<Self, String> {
+        if let Some(value) = source.strip_prefix('"') {
+            let value = value
+                .strip_suffix('"')
+                .ok_or_else(|| "unterminated string".to_string())?;
+            if value.contains(['"', '\\']) {
+                return Err("escape sequences are not supported".to_string());
+            }
+            return Ok(Value::String(value.to_string()));
+        }
+
+        // TOML allows underscores between digits of integers
+        if source.starts_with('_') || source.ends_with('_') || source.contains("__") {
+            return Err(format!("'{source}' is not a valid integer"));
+        }
+        source
+            .replace('_', "")
+            .parse()
+            .map(Value::Int)
+            .map_err(|_| format!("'{source}' is not a valid integer"))
+    }
+
+    fn into_string(self, key: &str) -> Result<String, String> {
+        match self {
+            Value::String(value) => Ok(value),
+            Value::Int(_) => Err(format!("{key} must be a string")),
+        }
+    }
+
+    fn into_int<T: TryFrom<u64>>(self, key: &str) -> Result<T, String> {
+        match self {
+            Value::Int(value) => {
+                T::try_from(value).map_err(|_| format!("value of {key} is too large"))
+            }
+            Value::String(_) => Err(format!("{key} must be an integer")),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Removes a comment (if any) from the specified line; `#` characters inside strings do not
+/// start a comment.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => (),
+        }
+    }
+    line
+}
@@ -0,0 +1,312 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    curve::{Point, CURVE_B, GENERATOR, OFFSET, SHIFTED_OFFSET},
+    is_valid_message_hash, PublicKey, ACC_X, ACC_Y, ADD_R_INV, ADD_R_SLOPE, ADD_R_X, ADD_R_Y,
+    DBL_SLOPE, DBL_X, DBL_Y, KEY_X, KEY_Y, R_ACC, R_BIT, R_X, R_Y, SCALAR_BITS,
+    SIG_CYCLE_LENGTH as SIG_CYCLE_LEN, SUB_GEN_INV, SUB_GEN_SLOPE, SUB_GEN_X, SUB_GEN_Y,
+    SUB_KEY_INV, SUB_KEY_SLOPE, S_BIT, TRACE_WIDTH, Z_ACC, Z_BIT,
+};
+use crate::utils::{are_equal, is_binary, not, read_element_arrays, EvaluationResult};
+use winterfell::{
+    math::{fields::f252::BaseElement, FieldElement, ToElements},
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    EvaluationFrame, ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public keys (as affine coordinates) and message hashes of the verified signatures.
+#[derive(Clone, ToElements)]
+#[to_elements(crate = "winterfell::math")]
+pub struct PublicInputs {
+    pub pub_keys: Vec<[BaseElement; 2]>,
+    pub message_hashes: Vec<BaseElement>,
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.pub_keys.len() as u32);
+        target.write(&self.pub_keys);
+        target.write_u32(self.message_hashes.len() as u32);
+        target.write(&self.message_hashes);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_pub_keys = source.read_u32()? as usize;
+        let pub_keys = read_element_arrays(source, num_pub_keys)?;
+        let num_message_hashes = source.read_u32()? as usize;
+        let message_hashes = BaseElement::read_batch_from(source, num_message_hashes)?;
+        Ok(PublicInputs {
+            pub_keys,
+            message_hashes,
+        })
+    }
+}
+
+// ECDSA SIGNATURE AIR
+// ================================================================================================
+
+/// AIR for verifying a batch of ECDSA signatures over the STARK curve.
+///
+/// Each signature is verified in a cycle of 256 steps; the signatures themselves are not a part
+/// of the public inputs. A signature (r, s) of message hash z under public key Q is valid if
+/// r = x(s^-1 * (z * G + r * Q)) mod n, and so, instead of computing the inverse of s modulo the
+/// order of the curve (which is not native to the field), the prover provides a point R with
+/// x(R) = r, and the AIR checks that s * R = z * G + r * Q. Within a cycle:
+/// * 2^252 * C + s * R - z * G - r * Q is computed via a double-and-add algorithm which processes
+///   a single bit of s, z, and r per step, starting with the most significant bits; C is a fixed
+///   point with unknown discrete logarithm, which makes sure that the point at infinity is not
+///   reached. Since R is chosen by the prover, the x coordinates of added points are checked to
+///   be different, so that incomplete addition formulas cannot be abused.
+/// * Bits of z and r are accumulated; the most significant bits are checked to be zero, so that
+///   the accumulated values are smaller than 2^251 (and thus, smaller than the field modulus).
+/// * After all bits are processed, the accumulated bits of r are checked to be equal to x(R),
+///   and the accumulator point and accumulated bits of z are checked to be equal to 2^252 * C
+///   and the message hash respectively via assertions. The remaining steps of the cycle are
+///   idle.
+///
+/// Note that the AIR checks x(R) = r rather than x(R) = r (mod n); these differ only when x(R)
+/// is greater than n, which happens with negligible probability.
+pub struct EcdsaAir {
+    context: AirContext<BaseElement>,
+    pub_keys: Vec<[BaseElement; 2]>,
+    message_hashes: Vec<BaseElement>,
+}
+
+impl Air for EcdsaAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert_eq!(pub_inputs.pub_keys.len(), pub_inputs.message_hashes.len());
+        assert_eq!(
+            trace_info.length(),
+            pub_inputs.pub_keys.len() * SIG_CYCLE_LEN,
+            "trace length must be equal to the number of signatures times {SIG_CYCLE_LEN}"
+        );
+        for &[x, y] in pub_inputs.pub_keys.iter() {
+            let is_valid = Point::new(x, y).and_then(PublicKey::new).is_some();
+            assert!(is_valid, "public key is not a point on the curve");
+        }
+        for &message_hash in pub_inputs.message_hashes.iter() {
+            assert!(
+                is_valid_message_hash(message_hash),
+                "message hash must be a non-zero value smaller than 2^251"
+            );
+        }
+
+        let degrees = vec![
+            // bits of s, z, and r are binary
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            // bits of z and r are accumulated
+            TransitionConstraintDegree::with_cycles(1, vec![SIG_CYCLE_LEN]),
+            TransitionConstraintDegree::with_cycles(1, vec![SIG_CYCLE_LEN]),
+            // accumulator point is doubled
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+            // R is added when the bit of s is 1
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            // generator is subtracted when the bit of z is 1
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            // public key is subtracted when the bit of r is 1
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::with_cycles(3, vec![SIG_CYCLE_LEN]),
+            TransitionConstraintDegree::with_cycles(3, vec![SIG_CYCLE_LEN]),
+            // public key and R are constant within a cycle
+            TransitionConstraintDegree::with_cycles(1, vec![SIG_CYCLE_LEN]),
+            TransitionConstraintDegree::with_cycles(1, vec![SIG_CYCLE_LEN]),
+            TransitionConstraintDegree::with_cycles(1, vec![SIG_CYCLE_LEN]),
+            TransitionConstraintDegree::with_cycles(1, vec![SIG_CYCLE_LEN]),
+            // R is a point on the curve
+            TransitionConstraintDegree::new(3),
+            // accumulated bits of r are equal to the x coordinate of R
+            TransitionConstraintDegree::with_cycles(1, vec![SIG_CYCLE_LEN]),
+        ];
+
+        EcdsaAir {
+            context: AirContext::new(trace_info, degrees, 11, options),
+            pub_keys: pub_inputs.pub_keys,
+            message_hashes: pub_inputs.message_hashes,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let last_step_flag = periodic_values[0];
+        let result_flag = periodic_values[1];
+
+        evaluate_scalar_mul(result, current, next, last_step_flag);
+
+        // public key and R remain the same until the end of the cycle
+        let flag = not(last_step_flag);
+        for (i, column) in [KEY_X, KEY_Y, R_X, R_Y].into_iter().enumerate() {
+            result.agg_constraint(20 + i, flag, are_equal(next[column], current[column]));
+        }
+
+        // R must be a point on the curve
+        let (r_x, r_y) = (current[R_X], current[R_Y]);
+        result[24] = are_equal(r_y.square(), r_x.cube() + r_x + E::from(CURVE_B));
+
+        // once all bits are processed, accumulated bits of r must be equal to the x coordinate
+        // of R
+        result.agg_constraint(25, result_flag, are_equal(current[R_ACC], r_x));
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let (key_x, key_y) = self.pub_keys.iter().map(|&[x, y]| (x, y)).unzip();
+        let (offset_x, offset_y) = to_coordinates(OFFSET);
+        let (shifted_x, shifted_y) = to_coordinates(SHIFTED_OFFSET);
+        vec![
+            // scalar multiplication starts with the offset point and empty accumulators; the
+            // most significant bits of z and r are zeros
+            Assertion::periodic(Z_BIT, 0, SIG_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::periodic(R_BIT, 0, SIG_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::periodic(Z_ACC, 0, SIG_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::periodic(R_ACC, 0, SIG_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::periodic(ACC_X, 0, SIG_CYCLE_LEN, offset_x),
+            Assertion::periodic(ACC_Y, 0, SIG_CYCLE_LEN, offset_y),
+            // public keys of all signatures
+            Assertion::sequence(KEY_X, 0, SIG_CYCLE_LEN, key_x),
+            Assertion::sequence(KEY_Y, 0, SIG_CYCLE_LEN, key_y),
+            // once all bits are processed, the accumulator point must be equal to the shifted
+            // offset point, and accumulated bits of z must be equal to the message hash
+            Assertion::periodic(ACC_X, SCALAR_BITS, SIG_CYCLE_LEN, shifted_x),
+            Assertion::periodic(ACC_Y, SCALAR_BITS, SIG_CYCLE_LEN, shifted_y),
+            Assertion::sequence(
+                Z_ACC,
+                SCALAR_BITS,
+                SIG_CYCLE_LEN,
+                self.message_hashes.clone(),
+            ),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![
+            build_mask(|step| step == SIG_CYCLE_LEN - 1),
+            build_mask(|step| step == SCALAR_BITS),
+        ]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates constraints of a single double-and-add step:
+/// acc' = 2 * acc + s_bit * R - z_bit * G - r_bit * Q.
+///
+/// Points are added using affine formulas with slopes of the lines provided in the trace, so
+/// that all constraints are of degree at most 3. When a point is added, the difference of the x
+/// coordinates of the added points must be non-zero, which is checked using its inverse provided
+/// in the trace.
+#[rustfmt::skip]
+fn evaluate_scalar_mul<E: FieldElement + From<BaseElement>>(
+    result: &mut [E],
+    current: &[E],
+    next: &[E],
+    last_step_flag: E,
+) {
+    let s_bit = current[S_BIT];
+    let z_bit = current[Z_BIT];
+    let r_bit = current[R_BIT];
+    result[0] = is_binary(s_bit);
+    result[1] = is_binary(z_bit);
+    result[2] = is_binary(r_bit);
+
+    // bits of z and r are accumulated starting with the most significant bit
+    let flag = not(last_step_flag);
+    result.agg_constraint(3, flag, are_equal(next[Z_ACC], current[Z_ACC].double() + z_bit));
+    result.agg_constraint(4, flag, are_equal(next[R_ACC], current[R_ACC].double() + r_bit));
+
+    // doubling of the accumulator point
+    let (x, y) = (current[ACC_X], current[ACC_Y]);
+    let slope = current[DBL_SLOPE];
+    let (dbl_x, dbl_y) = (current[DBL_X], current[DBL_Y]);
+    result[5] = are_equal(slope * y.double(), x.square() * E::from(3u8) + E::ONE);
+    result[6] = are_equal(dbl_x, slope.square() - x.double());
+    result[7] = are_equal(dbl_y, slope * (x - dbl_x) - y);
+
+    // when the bit of s is 1, R is added to the doubled point
+    let (r_x, r_y) = (current[R_X], current[R_Y]);
+    let slope = current[ADD_R_SLOPE];
+    let (add_x, add_y) = (current[ADD_R_X], current[ADD_R_Y]);
+    result[8] = s_bit * (slope * (r_x - dbl_x) - (r_y - dbl_y));
+    result[9] = s_bit * are_equal((r_x - dbl_x) * current[ADD_R_INV], E::ONE);
+    result[10] = are_equal(add_x, s_bit * (slope.square() - dbl_x - r_x) + not(s_bit) * dbl_x);
+    result[11] = are_equal(add_y, s_bit * (slope * (dbl_x - add_x) - dbl_y) + not(s_bit) * dbl_y);
+
+    // when the bit of z is 1, the generator is subtracted from the result
+    let (gen_x, gen_y) = to_coordinates::<E>(GENERATOR.neg());
+    let slope = current[SUB_GEN_SLOPE];
+    let (sub_x, sub_y) = (current[SUB_GEN_X], current[SUB_GEN_Y]);
+    result[12] = z_bit * (slope * (gen_x - add_x) - (gen_y - add_y));
+    result[13] = z_bit * are_equal((gen_x - add_x) * current[SUB_GEN_INV], E::ONE);
+    result[14] = are_equal(sub_x, z_bit * (slope.square() - add_x - gen_x) + not(z_bit) * add_x);
+    result[15] = are_equal(sub_y, z_bit * (slope * (add_x - sub_x) - add_y) + not(z_bit) * add_y);
+
+    // when the bit of r is 1, the public key is subtracted from the result
+    let (key_x, key_y) = (current[KEY_X], -current[KEY_Y]);
+    let slope = current[SUB_KEY_SLOPE];
+    let (next_x, next_y) = (next[ACC_X], next[ACC_Y]);
+    result[16] = r_bit * (slope * (key_x - sub_x) - (key_y - sub_y));
+    result[17] = r_bit * are_equal((key_x - sub_x) * current[SUB_KEY_INV], E::ONE);
+    let expected_x = r_bit * (slope.square() - sub_x - key_x) + not(r_bit) * sub_x;
+    result.agg_constraint(18, flag, are_equal(next_x, expected_x));
+    let expected_y = r_bit * (slope * (sub_x - next_x) - sub_y) + not(r_bit) * sub_y;
+    result.agg_constraint(19, flag, are_equal(next_y, expected_y));
+}
+
+/// Returns coordinates of the specified point (which must not be the point at infinity).
+fn to_coordinates<E: FieldElement + From<BaseElement>>(point: Point) -> (E, E) {
+    let (x, y) = point
+        .coordinates()
+        .expect("point cannot be the point at infinity");
+    (E::from(x), E::from(y))
+}
+
+/// Returns a periodic column of signature cycle length with ones at steps selected by the
+/// `predicate`, and zeros elsewhere.
+fn build_mask(predicate: impl Fn(usize) -> bool) -> Vec<BaseElement> {
+    (0..SIG_CYCLE_LEN)
+        .map(|step| {
+            if predicate(step) {
+                BaseElement::ONE
+            } else {
+                BaseElement::ZERO
+            }
+        })
+        .collect()
+}
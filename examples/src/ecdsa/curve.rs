@@ -0,0 +1,190 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use num_bigint::BigUint;
+use winterfell::{
+    crypto::stark_curve,
+    math::{
+        fields::f252::{BaseElement, U256},
+        FieldElement,
+    },
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Coefficient `b` of the curve equation y^2 = x^3 + x + b.
+pub const CURVE_B: BaseElement = stark_curve::BETA;
+
+/// Number of points on the curve; this number is prime, and thus, every point on the curve
+/// other than the point at infinity generates the entire group of points.
+pub const ORDER: U256 = stark_curve::ORDER;
+
+/// Number of bits in [ORDER]; scalars are processed by the AIR one bit per step.
+pub const ORDER_BITS: usize = 252;
+
+/// Generator of the curve as specified by StarkWare.
+pub const GENERATOR: Point = Point::Affine {
+    x: stark_curve::GENERATOR.x,
+    y: stark_curve::GENERATOR.y,
+};
+
+/// Point from which scalar multiplication is started in the AIR to avoid handling the point at
+/// infinity; this is the shift point of StarkWare's Pedersen hash, and its discrete logarithm
+/// with respect to [GENERATOR] is not known.
+pub const OFFSET: Point = Point::Affine {
+    x: stark_curve::PEDERSEN_POINTS[0].x,
+    y: stark_curve::PEDERSEN_POINTS[0].y,
+};
+
+/// 2^252 * [OFFSET]; this is the value which [OFFSET] is "shifted" to by 252 doublings.
+pub const SHIFTED_OFFSET: Point = Point::Affine {
+    x: BaseElement::new([
+        0xa22f0c13b226a85e,
+        0x58f023dea5b86523,
+        0xf5e11fdb21a7079a,
+        0x00fbce42c213b7c9,
+    ]),
+    y: BaseElement::new([
+        0x76630b1f21a8dfd8,
+        0x16cb65b09096a62e,
+        0x402efc384a5ef303,
+        0x04d4b4a9085f8479,
+    ]),
+};
+
+// POINT
+// ================================================================================================
+
+/// A point on the STARK curve y^2 = x^3 + x + b defined over the f252 field.
+///
+/// This is the curve used by StarkNet for ECDSA signatures; it has prime order, and so, there is
+/// no need to check that points belong to a subgroup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Point {
+    Infinity,
+    Affine { x: BaseElement, y: BaseElement },
+}
+
+impl Point {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a point with the specified coordinates, or None if the coordinates do not define
+    /// a point on the curve.
+    pub fn new(x: BaseElement, y: BaseElement) -> Option<Self> {
+        if is_on_curve(x, y) {
+            Some(Point::Affine { x, y })
+        } else {
+            None
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns affine coordinates of this point, or None if this is the point at infinity.
+    pub fn coordinates(&self) -> Option<(BaseElement, BaseElement)> {
+        match *self {
+            Point::Infinity => None,
+            Point::Affine { x, y } => Some((x, y)),
+        }
+    }
+
+    // ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns -self.
+    pub fn neg(&self) -> Self {
+        match *self {
+            Point::Infinity => Point::Infinity,
+            Point::Affine { x, y } => Point::Affine { x, y: -y },
+        }
+    }
+
+    /// Returns self + other.
+    pub fn add(&self, other: &Self) -> Self {
+        match (*self, *other) {
+            (Point::Infinity, _) => *other,
+            (_, Point::Infinity) => *self,
+            (Point::Affine { x: x1, y: y1 }, Point::Affine { x: x2, y: y2 }) => {
+                if x1 == x2 {
+                    if y1 + y2 == BaseElement::ZERO {
+                        Point::Infinity
+                    } else {
+                        self.double()
+                    }
+                } else {
+                    let lambda = (y2 - y1) / (x2 - x1);
+                    add_with_slope(x1, y1, x2, lambda)
+                }
+            }
+        }
+    }
+
+    /// Returns 2 * self.
+    pub fn double(&self) -> Self {
+        match *self {
+            Point::Affine { x, y } if y != BaseElement::ZERO => {
+                let lambda = (x.square() * BaseElement::from(3u8) + BaseElement::ONE) / y.double();
+                add_with_slope(x, y, x, lambda)
+            }
+            _ => Point::Infinity,
+        }
+    }
+
+    /// Returns scalar * self.
+    pub fn mul(&self, scalar: &BigUint) -> Self {
+        let mut result = Point::Infinity;
+        for i in (0..scalar.bits()).rev() {
+            result = result.double();
+            if scalar.bit(i) {
+                result = result.add(self);
+            }
+        }
+        result
+    }
+}
+
+// SCALAR CONVERSIONS
+// ================================================================================================
+
+/// Returns [ORDER] as a big integer.
+pub fn order() -> BigUint {
+    to_biguint(ORDER)
+}
+
+/// Converts a 256-bit integer into a big integer.
+pub fn to_biguint(value: U256) -> BigUint {
+    BigUint::from_bytes_le(&value.to_le_bytes())
+}
+
+/// Converts a big integer into a 256-bit integer.
+///
+/// # Panics
+/// Panics if the value is 2^256 or greater.
+pub fn to_u256(value: &BigUint) -> U256 {
+    let bytes = value.to_bytes_le();
+    assert!(bytes.len() <= 32, "value must be smaller than 2^256");
+    let mut result = [0; 32];
+    result[..bytes.len()].copy_from_slice(&bytes);
+    U256::from_le_bytes(result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if (x, y) satisfies the curve equation.
+fn is_on_curve(x: BaseElement, y: BaseElement) -> bool {
+    y.square() == x.cube() + x + CURVE_B
+}
+
+/// Returns the third point on the line with the specified slope passing through (x1, y1) and a
+/// point with x coordinate x2, reflected over the x-axis.
+fn add_with_slope(x1: BaseElement, y1: BaseElement, x2: BaseElement, lambda: BaseElement) -> Point {
+    let x3 = lambda.square() - x1 - x2;
+    let y3 = lambda * (x1 - x3) - y1;
+    Point::Affine { x: x3, y: y3 }
+}
@@ -0,0 +1,235 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{verify_with_serialized_inputs, Example, ExampleOptions, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f252::BaseElement, FieldElement},
+    FieldExtension, ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+pub use air::{EcdsaAir, PublicInputs};
+
+pub mod curve;
+
+mod prover;
+pub use prover::EcdsaProver;
+
+mod signature;
+pub use signature::{hash_message, is_valid_message_hash, PrivateKey, PublicKey, Signature};
+
+#[cfg(test)]
+mod tests;
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of steps needed to verify a single signature.
+const SIG_CYCLE_LENGTH: usize = 256;
+
+/// Number of bits in scalars by which points are multiplied; one bit is processed per step of a
+/// signature cycle, and the remaining steps of the cycle are idle.
+const SCALAR_BITS: usize = curve::ORDER_BITS;
+
+/// Number of bits in the r component of a signature and in message hashes.
+const SHORT_SCALAR_BITS: usize = 251;
+
+// Trace columns used by scalar multiplication: bits of the s and r components of the signature
+// and of the message hash, the accumulated values of r and the message hash, and the accumulator
+// point together with the intermediate points (as well as slopes of lines used to compute them,
+// and inverses of differences of x coordinates of added points) of a double-and-add step.
+const S_BIT: usize = 0;
+const Z_BIT: usize = 1;
+const R_BIT: usize = 2;
+const Z_ACC: usize = 3;
+const R_ACC: usize = 4;
+const ACC_X: usize = 5;
+const ACC_Y: usize = 6;
+const DBL_SLOPE: usize = 7;
+const DBL_X: usize = 8;
+const DBL_Y: usize = 9;
+const ADD_R_SLOPE: usize = 10;
+const ADD_R_INV: usize = 11;
+const ADD_R_X: usize = 12;
+const ADD_R_Y: usize = 13;
+const SUB_GEN_SLOPE: usize = 14;
+const SUB_GEN_INV: usize = 15;
+const SUB_GEN_X: usize = 16;
+const SUB_GEN_Y: usize = 17;
+const SUB_KEY_SLOPE: usize = 18;
+const SUB_KEY_INV: usize = 19;
+
+// Trace columns which remain constant within a signature cycle: the public key and the point R
+// such that x(R) = r.
+const KEY_X: usize = 20;
+const KEY_Y: usize = 21;
+const R_X: usize = 22;
+const R_Y: usize = 23;
+const TRACE_WIDTH: usize = 24;
+
+// ECDSA SIGNATURE BATCH VERIFICATION EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_signatures: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(EcdsaExample::<Blake3_192>::new(
+            num_signatures,
+            options,
+        )?)),
+        HashFunction::Blake3_256 => Ok(Box::new(EcdsaExample::<Blake3_256>::new(
+            num_signatures,
+            options,
+        )?)),
+        HashFunction::Sha3_256 => Ok(Box::new(EcdsaExample::<Sha3_256>::new(
+            num_signatures,
+            options,
+        )?)),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct EcdsaExample<H: ElementHasher> {
+    options: ProofOptions,
+    pub_keys: Vec<PublicKey>,
+    message_hashes: Vec<BaseElement>,
+    signatures: Vec<Signature>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> EcdsaExample<H> {
+    pub fn new(num_signatures: usize, options: ProofOptions) -> Result<Self, String> {
+        if !num_signatures.is_power_of_two() {
+            return Err("number of signatures must be a power of 2".to_string());
+        }
+        // the base field is large enough to provide the desired security level on its own, and
+        // field elements in extensions of it cannot be drawn from 32-byte digests
+        if options.field_extension() != FieldExtension::None {
+            return Err("field extensions are not supported by this example".to_string());
+        }
+
+        // generate private/public key pairs and sign messages
+        let now = Instant::now();
+        let mut pub_keys = Vec::with_capacity(num_signatures);
+        let mut message_hashes = Vec::with_capacity(num_signatures);
+        let mut signatures = Vec::with_capacity(num_signatures);
+        for i in 0..num_signatures {
+            let private_key = PrivateKey::from_seed([i as u8; 32]);
+            let message_hash = hash_message(format!("test message {i}").as_bytes());
+            signatures.push(private_key.sign(message_hash));
+            pub_keys.push(private_key.pub_key());
+            message_hashes.push(message_hash);
+        }
+        debug!(
+            "Generated {} key pairs and signed {} messages in {} ms",
+            num_signatures,
+            num_signatures,
+            now.elapsed().as_millis()
+        );
+
+        // verify signatures
+        let now = Instant::now();
+        let inputs = pub_keys.iter().zip(&message_hashes).zip(&signatures);
+        for ((pub_key, &message_hash), signature) in inputs {
+            if !pub_key.verify(message_hash, signature) {
+                return Err("generated signature is not valid".to_string());
+            }
+        }
+        debug!(
+            "Verified {} signatures in {} ms",
+            num_signatures,
+            now.elapsed().as_millis()
+        );
+
+        Ok(EcdsaExample {
+            options,
+            pub_keys,
+            message_hashes,
+            signatures,
+            _hasher: PhantomData,
+        })
+    }
+
+    fn get_pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            pub_keys: self.pub_keys.iter().map(|key| key.to_elements()).collect(),
+            message_hashes: self.message_hashes.clone(),
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for EcdsaExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for verifying {} ECDSA signatures\n\
+            ---------------------",
+            self.signatures.len(),
+        );
+
+        // create a prover
+        let prover = EcdsaProver::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.pub_keys, &self.message_hashes, &self.signatures);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<EcdsaAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.get_pub_inputs(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.get_pub_inputs();
+        pub_inputs.message_hashes[0] += BaseElement::ONE;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<EcdsaAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.get_pub_inputs().to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<EcdsaAir, H>(proof, pub_inputs)
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    curve::{to_u256, GENERATOR, OFFSET},
+    BaseElement, DefaultRandomCoin, EcdsaAir, ElementHasher, FieldElement, MerkleTree, PhantomData,
+    ProofOptions, Prover, PublicInputs, PublicKey, Signature, ACC_X, ACC_Y, ADD_R_INV, ADD_R_SLOPE,
+    ADD_R_X, ADD_R_Y, DBL_SLOPE, DBL_X, DBL_Y, KEY_X, KEY_Y, R_ACC, R_BIT, R_X, R_Y, SCALAR_BITS,
+    SIG_CYCLE_LENGTH, SUB_GEN_INV, SUB_GEN_SLOPE, SUB_GEN_X, SUB_GEN_Y, SUB_KEY_INV, SUB_KEY_SLOPE,
+    S_BIT, TRACE_WIDTH, Z_ACC, Z_BIT,
+};
+use winterfell::{
+    math::{fields::f252::U256, StarkField},
+    Trace, TraceTable,
+};
+
+#[cfg(feature = "concurrent")]
+use winterfell::iterators::*;
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+struct SignatureInfo {
+    key: (BaseElement, BaseElement),
+    r: (BaseElement, BaseElement),
+    message_hash: U256,
+    r_value: U256,
+    s: U256,
+}
+
+// ECDSA PROVER
+// ================================================================================================
+
+pub struct EcdsaProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> EcdsaProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for verifying the specified signatures.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of public keys, message hashes, and signatures is not the same, or if it is
+    ///   not a power of two.
+    /// * Any of the message hashes or signatures is out of range.
+    pub fn build_trace(
+        &self,
+        pub_keys: &[PublicKey],
+        message_hashes: &[BaseElement],
+        signatures: &[Signature],
+    ) -> TraceTable<BaseElement> {
+        assert_eq!(
+            pub_keys.len(),
+            message_hashes.len(),
+            "each message hash must have a public key"
+        );
+        assert_eq!(
+            signatures.len(),
+            message_hashes.len(),
+            "each message hash must have a signature"
+        );
+        assert!(
+            signatures.len().is_power_of_two(),
+            "number of signatures must be a power of two"
+        );
+
+        // allocate memory to hold the trace table
+        let trace_length = SIG_CYCLE_LENGTH * signatures.len();
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+        trace.fragments(SIG_CYCLE_LENGTH).for_each(|mut sig_trace| {
+            let i = sig_trace.index();
+            let sig_info = build_sig_info(&pub_keys[i], message_hashes[i], &signatures[i]);
+            sig_trace.fill(
+                |state| {
+                    init_sig_verification_state(&sig_info, state);
+                },
+                |step, state| {
+                    update_sig_verification_state(step, &sig_info, state);
+                },
+            );
+        });
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for EcdsaProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = EcdsaAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let num_signatures = trace.length() / SIG_CYCLE_LENGTH;
+        let mut pub_keys = Vec::with_capacity(num_signatures);
+        let mut message_hashes = Vec::with_capacity(num_signatures);
+        for i in 0..num_signatures {
+            let step = i * SIG_CYCLE_LENGTH;
+            pub_keys.push([trace.get(KEY_X, step), trace.get(KEY_Y, step)]);
+            message_hashes.push(trace.get(Z_ACC, step + SCALAR_BITS));
+        }
+        PublicInputs {
+            pub_keys,
+            message_hashes,
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// TRACE INITIALIZATION
+// ================================================================================================
+
+fn init_sig_verification_state(sig_info: &SignatureInfo, state: &mut [BaseElement]) {
+    // bits of s, z, and r are processed starting with the most significant bit
+    set_bits(sig_info, 0, state);
+    state[Z_ACC] = BaseElement::ZERO;
+    state[R_ACC] = BaseElement::ZERO;
+
+    // scalar multiplication starts with the offset point
+    let (x, y) = OFFSET.coordinates().unwrap();
+    state[ACC_X] = x;
+    state[ACC_Y] = y;
+
+    // values which stay the same during the entire cycle
+    state[KEY_X] = sig_info.key.0;
+    state[KEY_Y] = sig_info.key.1;
+    state[R_X] = sig_info.r.0;
+    state[R_Y] = sig_info.r.1;
+
+    fill_double_and_add(state);
+}
+
+// TRANSITION FUNCTION
+// ================================================================================================
+
+fn update_sig_verification_state(step: usize, sig_info: &SignatureInfo, state: &mut [BaseElement]) {
+    // the result of the double-and-add step becomes the new accumulator point
+    let sub_gen = (state[SUB_GEN_X], state[SUB_GEN_Y]);
+    let neg_key = (state[KEY_X], -state[KEY_Y]);
+    (state[ACC_X], state[ACC_Y]) = add_point(state[R_BIT], sub_gen, neg_key);
+    state[Z_ACC] = state[Z_ACC].double() + state[Z_BIT];
+    state[R_ACC] = state[R_ACC].double() + state[R_BIT];
+
+    // process the next bits of s, z, and r; once all bits are processed, the bits are set to
+    // zeros, and the accumulator point is only doubled
+    set_bits(sig_info, step + 1, state);
+    fill_double_and_add(state);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_sig_info(
+    pub_key: &PublicKey,
+    message_hash: BaseElement,
+    signature: &Signature,
+) -> SignatureInfo {
+    let key = pub_key.point().coordinates().unwrap();
+    let r = pub_key
+        .get_r_point(message_hash, signature)
+        .expect("signature is out of range")
+        .coordinates()
+        .unwrap();
+    SignatureInfo {
+        key,
+        r,
+        message_hash: message_hash.as_int(),
+        r_value: to_u256(&signature.r),
+        s: to_u256(&signature.s),
+    }
+}
+
+/// Sets bits of s, z, and r processed at the specified step of a signature cycle.
+fn set_bits(sig_info: &SignatureInfo, step: usize, state: &mut [BaseElement]) {
+    if step < SCALAR_BITS {
+        let bit_idx = SCALAR_BITS - 1 - step;
+        state[S_BIT] = get_bit(sig_info.s, bit_idx);
+        state[Z_BIT] = get_bit(sig_info.message_hash, bit_idx);
+        state[R_BIT] = get_bit(sig_info.r_value, bit_idx);
+    } else {
+        state[S_BIT] = BaseElement::ZERO;
+        state[Z_BIT] = BaseElement::ZERO;
+        state[R_BIT] = BaseElement::ZERO;
+    }
+}
+
+/// Fills intermediate values of a double-and-add step for the accumulator point in the
+/// specified state: the doubled point, the point with R added (when the bit of s is 1), the
+/// point with the generator subtracted (when the bit of z is 1), and slopes of the lines used to
+/// compute these points and to subtract the public key (when the bit of r is 1) together with
+/// the inverses of differences of x coordinates of added points.
+fn fill_double_and_add(state: &mut [BaseElement]) {
+    let (x, y) = (state[ACC_X], state[ACC_Y]);
+    let slope = (x.square() * BaseElement::from(3u8) + BaseElement::ONE) / y.double();
+    let dbl_x = slope.square() - x.double();
+    let dbl_y = slope * (x - dbl_x) - y;
+    state[DBL_SLOPE] = slope;
+    state[DBL_X] = dbl_x;
+    state[DBL_Y] = dbl_y;
+
+    let (r_x, r_y) = (state[R_X], state[R_Y]);
+    let (add_x, add_y) = add_point(state[S_BIT], (dbl_x, dbl_y), (r_x, r_y));
+    (state[ADD_R_SLOPE], state[ADD_R_INV]) = add_slope(state[S_BIT], (dbl_x, dbl_y), (r_x, r_y));
+    state[ADD_R_X] = add_x;
+    state[ADD_R_Y] = add_y;
+
+    let neg_gen = GENERATOR.neg().coordinates().unwrap();
+    let (sub_x, sub_y) = add_point(state[Z_BIT], (add_x, add_y), neg_gen);
+    (state[SUB_GEN_SLOPE], state[SUB_GEN_INV]) = add_slope(state[Z_BIT], (add_x, add_y), neg_gen);
+    state[SUB_GEN_X] = sub_x;
+    state[SUB_GEN_Y] = sub_y;
+
+    let neg_key = (state[KEY_X], -state[KEY_Y]);
+    (state[SUB_KEY_SLOPE], state[SUB_KEY_INV]) = add_slope(state[R_BIT], (sub_x, sub_y), neg_key);
+}
+
+/// Returns the slope of the line through points `p` and `q` and the inverse of the difference
+/// of their x coordinates when `bit` is 1, or zeros otherwise.
+fn add_slope(
+    bit: BaseElement,
+    p: (BaseElement, BaseElement),
+    q: (BaseElement, BaseElement),
+) -> (BaseElement, BaseElement) {
+    if bit == BaseElement::ONE {
+        let inv = (q.0 - p.0).inv();
+        ((q.1 - p.1) * inv, inv)
+    } else {
+        (BaseElement::ZERO, BaseElement::ZERO)
+    }
+}
+
+/// Returns `p` + `q` when `bit` is 1, or `p` otherwise.
+fn add_point(
+    bit: BaseElement,
+    p: (BaseElement, BaseElement),
+    q: (BaseElement, BaseElement),
+) -> (BaseElement, BaseElement) {
+    if bit == BaseElement::ONE {
+        let (slope, _) = add_slope(bit, p, q);
+        let x = slope.square() - p.0 - q.0;
+        (x, slope * (p.0 - x) - p.1)
+    } else {
+        p
+    }
+}
+
+fn get_bit(value: U256, bit_idx: usize) -> BaseElement {
+    BaseElement::from((value.0[bit_idx / 64] >> (bit_idx % 64)) & 1)
+}
@@ -0,0 +1,191 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    curve::{order, to_biguint, to_u256, Point, GENERATOR},
+    SHORT_SCALAR_BITS,
+};
+use num_bigint::BigUint;
+use winterfell::math::{
+    fields::f252::{BaseElement, U256},
+    FieldElement, StarkField,
+};
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+/// An ECDSA private key: a scalar d in the range [1, n), where n is the order of the curve.
+pub struct PrivateKey {
+    scalar: BigUint,
+    pub_key: PublicKey,
+}
+
+/// An ECDSA public key: Q = d * G, where G is [GENERATOR] and d is the private key scalar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(Point);
+
+/// An ECDSA signature (r, s) of a message hash z under public key Q.
+///
+/// The signature is valid if r = x(s^-1 * (z * G + r * Q)) mod n. Similarly to StarkNet, both
+/// r and z must be non-zero values smaller than 2^251, and s must be in the range [1, n).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+// PRIVATE KEY IMPLEMENTATION
+// ================================================================================================
+
+impl PrivateKey {
+    /// Returns a private key generated from the specified `seed`.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let scalar = hash_to_scalar(&[b"private key", &seed]);
+        PrivateKey {
+            pub_key: PublicKey(GENERATOR.mul(&scalar)),
+            scalar,
+        }
+    }
+
+    /// Returns a public key corresponding to this private key.
+    pub fn pub_key(&self) -> PublicKey {
+        self.pub_key
+    }
+
+    /// Signs the specified `message_hash` with this private key.
+    ///
+    /// Similarly to RFC 6979, the nonce is derived deterministically from the private key and
+    /// the message hash; nonces which result in r greater than or equal to 2^251 are skipped.
+    ///
+    /// # Panics
+    /// Panics if the message hash is not a valid hash as defined by [is_valid_message_hash()].
+    pub fn sign(&self, message_hash: BaseElement) -> Signature {
+        assert!(
+            is_valid_message_hash(message_hash),
+            "message hash must be a non-zero value smaller than 2^{SHORT_SCALAR_BITS}"
+        );
+        let n = order();
+        let z = to_biguint(message_hash.as_int());
+        let d_bytes = to_u256(&self.scalar).to_le_bytes();
+        let z_bytes = message_hash.as_int().to_le_bytes();
+
+        for counter in 0u32.. {
+            let nonce = hash_to_scalar(&[&d_bytes, &z_bytes, &counter.to_le_bytes()]);
+            let (x, _) = GENERATOR
+                .mul(&nonce)
+                .coordinates()
+                .expect("nonce is smaller than the order of the curve");
+            let r = to_biguint(x.as_int());
+            if r.bits() > SHORT_SCALAR_BITS as u64 {
+                continue;
+            }
+
+            let nonce_inv = nonce.modpow(&(&n - 2u32), &n);
+            let s = nonce_inv * (&z + &r * &self.scalar) % &n;
+            if s != BigUint::default() {
+                return Signature { r, s };
+            }
+        }
+        unreachable!("a valid nonce is found with overwhelming probability")
+    }
+}
+
+// PUBLIC KEY IMPLEMENTATION
+// ================================================================================================
+
+impl PublicKey {
+    /// Returns a public key defined by the specified point, or None if the point is the point at
+    /// infinity.
+    pub fn new(point: Point) -> Option<Self> {
+        match point {
+            Point::Infinity => None,
+            _ => Some(PublicKey(point)),
+        }
+    }
+
+    /// Returns the point defining this public key.
+    pub fn point(&self) -> Point {
+        self.0
+    }
+
+    /// Returns coordinates of this public key.
+    pub fn to_elements(&self) -> [BaseElement; 2] {
+        let (x, y) = self
+            .0
+            .coordinates()
+            .expect("public key cannot be the point at infinity");
+        [x, y]
+    }
+
+    /// Returns true if `signature` is a valid signature of the `message_hash` under this public
+    /// key.
+    pub fn verify(&self, message_hash: BaseElement, signature: &Signature) -> bool {
+        match self.get_r_point(message_hash, signature) {
+            Some(point) => {
+                let (x, _) = point.coordinates().expect("point is not at infinity");
+                to_biguint(x.as_int()) % order() == signature.r
+            }
+            None => false,
+        }
+    }
+
+    /// Returns R = s^-1 * (z * G + r * Q) for the specified message hash z and signature (r, s)
+    /// under this public key Q, or None if the inputs are out of range or R is the point at
+    /// infinity.
+    ///
+    /// The signature is valid if the x coordinate of R reduced modulo the order of the curve is
+    /// equal to r.
+    pub fn get_r_point(&self, message_hash: BaseElement, signature: &Signature) -> Option<Point> {
+        let n = order();
+        let Signature { r, s } = signature;
+        let zero = BigUint::default();
+        if !is_valid_message_hash(message_hash)
+            || *r == zero
+            || r.bits() > SHORT_SCALAR_BITS as u64
+            || *s == zero
+            || *s >= n
+        {
+            return None;
+        }
+
+        let s_inv = s.modpow(&(&n - 2u32), &n);
+        let u1 = to_biguint(message_hash.as_int()) * &s_inv % &n;
+        let u2 = r * &s_inv % &n;
+        match GENERATOR.mul(&u1).add(&self.0.mul(&u2)) {
+            Point::Infinity => None,
+            point => Some(point),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if `message_hash` is a non-zero value smaller than 2^251.
+pub fn is_valid_message_hash(message_hash: BaseElement) -> bool {
+    message_hash != BaseElement::ZERO
+        && message_hash.as_int() >> SHORT_SCALAR_BITS as u32 == U256::default()
+}
+
+/// Hashes an arbitrary message into a value smaller than 2^251 by truncating its BLAKE3 hash.
+pub fn hash_message(message: &[u8]) -> BaseElement {
+    let mut hash = *blake3::hash(message).as_bytes();
+    hash[31] &= 0xff >> (256 - SHORT_SCALAR_BITS);
+    BaseElement::from(U256::from_le_bytes(hash))
+}
+
+/// Maps the concatenation of `inputs` to a scalar in the range [1, n), where n is the order of
+/// the curve.
+///
+/// The scalar is derived from 512 bits of BLAKE3 output, and so, its bias is negligible.
+fn hash_to_scalar(inputs: &[&[u8]]) -> BigUint {
+    let mut hasher = blake3::Hasher::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    let mut output = [0; 64];
+    hasher.finalize_xof().fill(&mut output);
+    BigUint::from_bytes_le(&output) % (order() - 1u32) + 1u32
+}
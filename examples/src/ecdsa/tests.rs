@@ -0,0 +1,145 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    curve::{order, Point, GENERATOR, OFFSET, ORDER_BITS, SHIFTED_OFFSET},
+    hash_message, is_valid_message_hash, BaseElement, Blake3_256, EcdsaAir, EcdsaExample,
+    EcdsaProver, FieldElement, PrivateKey, PublicKey, Signature, SCALAR_BITS,
+};
+use winterfell::{
+    debug::{ConstraintDebugger, ConstraintId},
+    math::fields::f252::U256,
+    Air, FieldExtension, ProofOptions, Prover, Trace,
+};
+
+#[test]
+fn ecdsa_test_basic_proof_verification() {
+    let ecdsa_eg = Box::new(EcdsaExample::<Blake3_256>::new(4, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification(ecdsa_eg);
+}
+
+#[test]
+fn ecdsa_test_extension_not_supported() {
+    assert!(EcdsaExample::<Blake3_256>::new(4, build_options(true)).is_err());
+}
+
+#[test]
+fn ecdsa_test_basic_proof_verification_fail() {
+    let ecdsa_eg = Box::new(EcdsaExample::<Blake3_256>::new(4, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification_fail(ecdsa_eg);
+}
+
+#[test]
+fn ecdsa_test_proof_verification_with_pub_inputs() {
+    let ecdsa_eg = Box::new(EcdsaExample::<Blake3_256>::new(4, build_options(false)).unwrap());
+    crate::tests::test_proof_verification_with_pub_inputs(ecdsa_eg);
+}
+
+#[test]
+fn ecdsa_test_invalid_signature() {
+    let (pub_keys, message_hashes, mut signatures) = build_signatures(2);
+    signatures[1].s = (&signatures[1].s + 1u32) % order();
+    assert!(!pub_keys[1].verify(message_hashes[1], &signatures[1]));
+
+    // only the constraint checking that the x coordinate of R is equal to r is not satisfied
+    let options = build_options(false);
+    let prover = EcdsaProver::<Blake3_256>::new(options.clone());
+    let trace = prover.build_trace(&pub_keys, &message_hashes, &signatures);
+    let air = EcdsaAir::new(trace.get_info(), prover.get_pub_inputs(&trace), options);
+    let debugger = ConstraintDebugger::new(&air, &trace);
+    let unsatisfied = debugger
+        .unsatisfied_constraints()
+        .iter()
+        .map(|evaluations| evaluations.constraint())
+        .collect::<Vec<_>>();
+    assert_eq!([ConstraintId::Main(25)].as_slice(), unsatisfied.as_slice());
+}
+
+#[test]
+fn ecdsa_test_sign_verify() {
+    let private_key = PrivateKey::from_seed([1; 32]);
+    let pub_key = private_key.pub_key();
+    let message_hash = hash_message(b"test message");
+    let signature = private_key.sign(message_hash);
+    assert!(pub_key.verify(message_hash, &signature));
+
+    // signing is deterministic
+    assert_eq!(signature, private_key.sign(message_hash));
+
+    // the signature is not valid for a different message or under a different key
+    assert!(!pub_key.verify(hash_message(b"another message"), &signature));
+    let other_key = PrivateKey::from_seed([2; 32]).pub_key();
+    assert!(!other_key.verify(message_hash, &signature));
+
+    // s must be reduced, and r must be smaller than 2^251
+    let invalid = Signature {
+        r: signature.r.clone(),
+        s: &signature.s + order(),
+    };
+    assert!(!pub_key.verify(message_hash, &invalid));
+    let invalid = Signature {
+        r: &signature.r + order(),
+        s: signature.s.clone(),
+    };
+    assert!(!pub_key.verify(message_hash, &invalid));
+
+    // message hashes must be non-zero and smaller than 2^251
+    assert!(!is_valid_message_hash(BaseElement::ZERO));
+    assert!(!is_valid_message_hash(BaseElement::from(U256([
+        0,
+        0,
+        0,
+        1 << 59
+    ]))));
+    assert!(is_valid_message_hash(BaseElement::from(U256([
+        1,
+        0,
+        0,
+        1 << 58
+    ]))));
+}
+
+#[test]
+fn ecdsa_test_curve_constants() {
+    // the generator and the offset point are on the curve, and the order of the generator is
+    // equal to the order of the curve
+    let (x, y) = GENERATOR.coordinates().unwrap();
+    assert_eq!(Some(GENERATOR), Point::new(x, y));
+    let (x, y) = OFFSET.coordinates().unwrap();
+    assert_eq!(Some(OFFSET), Point::new(x, y));
+    assert_eq!(Point::Infinity, GENERATOR.mul(&order()));
+    assert_eq!(OFFSET, OFFSET.mul(&(order() + 1u32)));
+    assert_eq!(ORDER_BITS as u64, order().bits());
+
+    let shifted_offset = (0..SCALAR_BITS).fold(OFFSET, |point, _| point.double());
+    assert_eq!(SHIFTED_OFFSET, shifted_offset);
+
+    // points which are not on the curve cannot be used as public keys
+    assert!(Point::new(x, y + BaseElement::ONE).is_none());
+    assert!(PublicKey::new(Point::Infinity).is_none());
+}
+
+fn build_signatures(num_signatures: usize) -> (Vec<PublicKey>, Vec<BaseElement>, Vec<Signature>) {
+    let mut pub_keys = Vec::new();
+    let mut message_hashes = Vec::new();
+    let mut signatures = Vec::new();
+    for i in 0..num_signatures {
+        let private_key = PrivateKey::from_seed([i as u8; 32]);
+        let message_hash = hash_message(&[i as u8]);
+        pub_keys.push(private_key.pub_key());
+        message_hashes.push(message_hash);
+        signatures.push(private_key.sign(message_hash));
+    }
+    (pub_keys, message_hashes, signatures)
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -4,21 +4,24 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    verify_with_serialized_inputs, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction,
+    Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
-    crypto::{DefaultRandomCoin, ElementHasher},
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
     math::{fields::f128::BaseElement, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, TraceTable, VerifierError,
 };
 
 mod air;
 use air::FibAir;
 
 mod prover;
-use prover::FibProver;
+pub use prover::FibProver;
 
 #[cfg(test)]
 mod tests;
@@ -121,10 +124,30 @@ where
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibAir, H, DefaultRandomCoin<H>>(proof, self.result)
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<FibAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.result,
+            &acceptable_options,
+        )
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibAir, H, DefaultRandomCoin<H>>(proof, self.result + BaseElement::ONE)
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<FibAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.result + BaseElement::ONE,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.result.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<FibAir, H>(proof, pub_inputs)
     }
 }
@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, DefaultRandomCoin, ElementHasher, FibAir, FieldElement, PhantomData, ProofOptions,
-    Prover, Trace, TraceTable, TRACE_WIDTH,
+    BaseElement, DefaultRandomCoin, ElementHasher, FibAir, FieldElement, MerkleTree, PhantomData,
+    ProofOptions, Prover, Trace, TraceTable, TRACE_WIDTH,
 };
 
 // FIBONACCI PROVER
@@ -56,6 +56,7 @@ where
     type Air = FibAir;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
+    type VC = MerkleTree<H>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
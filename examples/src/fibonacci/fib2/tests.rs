@@ -31,3 +31,12 @@ fn fib2_test_basic_proof_verification_fail() {
     ));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_proof_verification_with_pub_inputs() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_proof_verification_with_pub_inputs(fib);
+}
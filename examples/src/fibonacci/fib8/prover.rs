@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, DefaultRandomCoin, ElementHasher, Fib8Air, FieldElement, PhantomData,
+    BaseElement, DefaultRandomCoin, ElementHasher, Fib8Air, FieldElement, MerkleTree, PhantomData,
     ProofOptions, Prover, Trace, TraceTable,
 };
 
@@ -71,6 +71,7 @@ where
     type Air = Fib8Air;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
+    type VC = MerkleTree<H>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
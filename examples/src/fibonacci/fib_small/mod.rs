@@ -4,21 +4,21 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Example, ExampleOptions, HashFunction};
+use crate::{verify_with_serialized_inputs, Example, ExampleOptions, HashFunction};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
-    crypto::{DefaultRandomCoin, ElementHasher},
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
     math::{fields::f64::BaseElement, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    ProofOptions, Prover, Serializable, StarkProof, Trace, TraceTable, VerifierError,
 };
 
 mod air;
-use air::FibSmall;
+pub use air::FibSmall;
 
 mod prover;
-use prover::FibSmallProver;
+pub use prover::FibSmallProver;
 
 #[cfg(test)]
 mod tests;
@@ -139,13 +139,30 @@ where
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibSmall, H, DefaultRandomCoin<H>>(proof, self.result)
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<FibSmall, H, DefaultRandomCoin<H>>(
+            proof,
+            self.result,
+            &acceptable_options,
+        )
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
         winterfell::verify::<FibSmall, H, DefaultRandomCoin<H>>(
             proof,
             self.result + BaseElement::ONE,
+            &acceptable_options,
         )
     }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.result.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<FibSmall, H>(proof, pub_inputs)
+    }
 }
@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    air::FibSmall, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, PhantomData,
-    ProofOptions, Prover, Trace, TraceTable, TRACE_WIDTH,
+    air::FibSmall, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, MerkleTree,
+    PhantomData, ProofOptions, Prover, Trace, TraceTable, TRACE_WIDTH,
 };
 
 // FIBONACCI PROVER
@@ -56,6 +56,7 @@ where
     type Air = FibSmall;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
+    type VC = MerkleTree<H>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
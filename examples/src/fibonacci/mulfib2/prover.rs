@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, DefaultRandomCoin, ElementHasher, MulFib2Air, PhantomData, ProofOptions, Prover,
-    Trace, TraceTable,
+    BaseElement, DefaultRandomCoin, ElementHasher, MerkleTree, MulFib2Air, PhantomData,
+    ProofOptions, Prover, Trace, TraceTable,
 };
 
 // FIBONACCI PROVER
@@ -52,6 +52,7 @@ where
     type Air = MulFib2Air;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
+    type VC = MerkleTree<H>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
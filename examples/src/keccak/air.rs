@@ -0,0 +1,333 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_rho_pi_source,
+    permutation::{ROUND_CONSTANTS, ROUND_CONSTANT_BITS},
+    BaseElement, FieldElement, ProofOptions, Step, CYCLE_LENGTH, LANES, LANE_BITS, NUM_LANES,
+    NUM_SLOTS, PARITIES, RHO_PI_LANES, SLOTS, THETA_LANES, TRACE_WIDTH,
+};
+use crate::utils::{is_binary, not};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+// Periodic columns: flags of the phases of a round, selectors of the lanes placed into the slots,
+// coefficients used to pack rotated lanes, and bits of round constants.
+const PARITY_FLAG: usize = 0;
+const THETA_FLAG: usize = 1;
+const RHO_PI_FLAG: usize = 2;
+const CHI_ROTATE_FLAG: usize = 3;
+const CHI_SHIFT_FLAG: usize = 4;
+const PARITY_ACC_FLAG: usize = 5;
+const LANE_SELECTORS: usize = 6;
+const THETA_SELECTORS: usize = LANE_SELECTORS + NUM_LANES;
+const ROTATION_COEFFS: usize = THETA_SELECTORS + 5;
+const ROUND_CONSTANT_FLAGS: usize = ROTATION_COEFFS + LANE_BITS;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// The state to which the chain of permutations is applied, and the resulting state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputs {
+    pub seed: [u64; NUM_LANES],
+    pub result: [u64; NUM_LANES],
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.seed
+            .iter()
+            .chain(self.result.iter())
+            .map(|&lane| BaseElement::from(lane))
+            .collect()
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        for &lane in self.seed.iter().chain(self.result.iter()) {
+            target.write_u64(lane);
+        }
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut seed = [0; NUM_LANES];
+        for lane in seed.iter_mut() {
+            *lane = source.read_u64()?;
+        }
+        let mut result = [0; NUM_LANES];
+        for lane in result.iter_mut() {
+            *lane = source.read_u64()?;
+        }
+        Ok(PublicInputs { seed, result })
+    }
+}
+
+// KECCAK AIR
+// ================================================================================================
+
+/// AIR for applying Keccak-f[1600] permutation to a state repeatedly.
+///
+/// Lanes of the state are packed into single field elements, and bitwise operations are
+/// performed over slots holding bits of up to 3 lanes at a time: a slot is bound to a lane by
+/// requiring its bits to be binary and to sum up to the lane, and a ^ b = a + b - 2 * a * b and
+/// !a & b = (1 - a) * b are used to compute bits of the resulting lane. Lanes are moved between
+/// trace columns so that the operands of every step are found in the same columns, except for
+/// the steps computing the rho and pi steps; there, the source lane is chosen via periodic
+/// selectors, and rotation is applied by packing bits of the lane with periodic coefficients.
+/// See [Step] for the description of phases of a round.
+///
+/// The trace is limited to 255 columns, so a round takes 80 steps, and the state stays the same
+/// during the last 128 steps of a 2048-step cycle.
+pub struct KeccakAir {
+    context: AirContext<BaseElement>,
+    seed: [u64; NUM_LANES],
+    result: [u64; NUM_LANES],
+}
+
+impl Air for KeccakAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let linear = TransitionConstraintDegree::with_cycles(1, vec![CYCLE_LENGTH]);
+        let quadratic = TransitionConstraintDegree::with_cycles(2, vec![CYCLE_LENGTH]);
+        let cubic = TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]);
+
+        // lanes of the state; the last one receives the result of the chi and iota steps
+        let mut degrees = vec![linear.clone(); NUM_LANES - 1];
+        degrees.push(cubic);
+        // parity buffer; the last lane receives the result of XORing two slots
+        degrees.extend(vec![linear.clone(); 4]);
+        degrees.push(quadratic.clone());
+        // theta buffer; the last lane receives D[x] computed from two slots
+        degrees.extend(vec![linear.clone(); 4]);
+        degrees.push(quadratic.clone());
+        // lanes after the rho and pi steps; the last one receives the rotated lane
+        degrees.extend(vec![linear.clone(); NUM_LANES - 1]);
+        degrees.push(quadratic);
+        // slots match the selected lanes, and bits of slots are binary
+        degrees.extend(vec![linear; NUM_SLOTS]);
+        degrees.extend(vec![
+            TransitionConstraintDegree::new(2);
+            NUM_SLOTS * LANE_BITS
+        ]);
+
+        KeccakAir {
+            context: AirContext::new(trace_info, degrees, 2 * NUM_LANES, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let parity_flag = periodic_values[PARITY_FLAG];
+        let theta_flag = periodic_values[THETA_FLAG];
+        let rho_pi_flag = periodic_values[RHO_PI_FLAG];
+        let chi_rotate_flag = periodic_values[CHI_ROTATE_FLAG];
+        let chi_shift_flag = periodic_values[CHI_SHIFT_FLAG];
+        let chi_flag = chi_rotate_flag + chi_shift_flag;
+
+        let s0 = &current[SLOTS..SLOTS + LANE_BITS];
+        let s1 = &current[SLOTS + LANE_BITS..SLOTS + 2 * LANE_BITS];
+        let s2 = &current[SLOTS + 2 * LANE_BITS..SLOTS + 3 * LANE_BITS];
+
+        let (lanes_result, result) = result.split_at_mut(NUM_LANES);
+        let (parity_result, result) = result.split_at_mut(5);
+        let (theta_result, result) = result.split_at_mut(5);
+        let (rho_pi_result, result) = result.split_at_mut(NUM_LANES);
+        let (slot_result, bits_result) = result.split_at_mut(NUM_SLOTS);
+
+        // lanes of the state are rotated while computing column parities, and the results of the
+        // chi and iota steps are pushed into them; otherwise, the state remains the same
+        let shift_flag = parity_flag + chi_flag;
+        for i in 0..NUM_LANES - 1 {
+            lanes_result[i] = next[LANES + i]
+                - shift_flag * current[LANES + i + 1]
+                - not(shift_flag) * current[LANES + i];
+        }
+
+        // chi: s0 ^ (!s1 & s2); iota: chi ^ rc = chi + rc * (1 - 2 * chi)
+        let mut chi = E::ZERO;
+        let mut iota = E::ZERO;
+        for z in 0..LANE_BITS {
+            let bit = xor(s0[z], not(s1[z]) * s2[z]);
+            chi += bit * E::from(1u64 << z);
+            if let Some(i) = ROUND_CONSTANT_BITS.iter().position(|&b| b == z) {
+                iota += periodic_values[ROUND_CONSTANT_FLAGS + i]
+                    * (E::ONE - bit.double())
+                    * E::from(1u64 << z);
+            }
+        }
+        lanes_result[NUM_LANES - 1] = next[LANES + NUM_LANES - 1]
+            - parity_flag * current[LANES]
+            - chi_flag * chi
+            - iota
+            - not(shift_flag) * current[LANES + NUM_LANES - 1];
+
+        // column parities are accumulated by XORing lanes of the state into the buffer, and the
+        // buffer is rotated while computing D[x] for the theta step
+        let buffer_flag = parity_flag + theta_flag;
+        for k in 0..4 {
+            parity_result[k] = buffer_flag * (next[PARITIES + k] - current[PARITIES + k + 1]);
+        }
+        let parity = pack(s0.iter().zip(s1).map(|(&a, &b)| xor(a, b)));
+        parity_result[4] = parity_flag * (next[PARITIES + 4] - parity)
+            + theta_flag * (next[PARITIES + 4] - current[PARITIES]);
+
+        // D[x] = C[x - 1] ^ rot(C[x + 1], 1) is pushed into the theta buffer
+        let d = pack((0..LANE_BITS).map(|z| xor(s1[z], s2[(z + LANE_BITS - 1) % LANE_BITS])));
+        for k in 0..4 {
+            theta_result[k] = next[THETA_LANES + k]
+                - theta_flag * current[THETA_LANES + k + 1]
+                - not(theta_flag) * current[THETA_LANES + k];
+        }
+        theta_result[4] =
+            next[THETA_LANES + 4] - theta_flag * d - not(theta_flag) * current[THETA_LANES + 4];
+
+        // the results of the theta, rho, and pi steps are pushed into the buffer; then, while
+        // computing the chi step, the first 5 lanes of the buffer are rotated, and once the chi
+        // step is computed for a plane of the state, the buffer is shifted by 5 lanes
+        let mut rotated = E::ZERO;
+        for z in 0..LANE_BITS {
+            rotated += periodic_values[ROTATION_COEFFS + z] * xor(s0[z], s1[z]);
+        }
+        for k in 0..NUM_LANES - 1 {
+            let plane_k = if k < 5 { (k + 1) % 5 } else { k };
+            rho_pi_result[k] = rho_pi_flag
+                * (next[RHO_PI_LANES + k] - current[RHO_PI_LANES + k + 1])
+                + chi_rotate_flag * (next[RHO_PI_LANES + k] - current[RHO_PI_LANES + plane_k]);
+            if k < NUM_LANES - 5 {
+                rho_pi_result[k] +=
+                    chi_shift_flag * (next[RHO_PI_LANES + k] - current[RHO_PI_LANES + k + 5]);
+            }
+        }
+        rho_pi_result[NUM_LANES - 1] = rho_pi_flag * next[RHO_PI_LANES + NUM_LANES - 1] - rotated
+            + chi_rotate_flag
+                * (next[RHO_PI_LANES + NUM_LANES - 1] - current[RHO_PI_LANES + NUM_LANES - 1]);
+
+        // slots hold bits of the lanes selected for the current step
+        let mut lane = E::ZERO;
+        for i in 0..NUM_LANES {
+            lane += periodic_values[LANE_SELECTORS + i] * current[LANES + i];
+        }
+        slot_result[0] = (parity_flag + rho_pi_flag + chi_flag) * pack(s0.iter().copied())
+            - lane
+            - chi_flag * current[RHO_PI_LANES];
+
+        let mut theta_lane = E::ZERO;
+        for k in 0..5 {
+            theta_lane += periodic_values[THETA_SELECTORS + k] * current[THETA_LANES + k];
+        }
+        slot_result[1] = (buffer_flag + rho_pi_flag + chi_flag) * pack(s1.iter().copied())
+            - periodic_values[PARITY_ACC_FLAG] * current[PARITIES]
+            - theta_flag * current[PARITIES + 4]
+            - theta_lane
+            - chi_flag * current[RHO_PI_LANES + 1];
+
+        slot_result[2] = (theta_flag + chi_flag) * pack(s2.iter().copied())
+            - theta_flag * current[PARITIES + 1]
+            - chi_flag * current[RHO_PI_LANES + 2];
+
+        for (result, &bit) in bits_result.iter_mut().zip(&current[SLOTS..]) {
+            *result = is_binary(bit);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        let mut assertions = Vec::new();
+        for (step, state) in [(0, &self.seed), (last_step, &self.result)] {
+            for (i, &lane) in state.iter().enumerate() {
+                assertions.push(Assertion::single(LANES + i, step, BaseElement::from(lane)));
+            }
+        }
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let num_columns = ROUND_CONSTANT_FLAGS + ROUND_CONSTANT_BITS.len();
+        let mut result = vec![vec![BaseElement::ZERO; CYCLE_LENGTH]; num_columns];
+        #[allow(clippy::needless_range_loop)]
+        for cycle_step in 0..CYCLE_LENGTH {
+            let (round, step) = Step::at(cycle_step);
+            let mut flags = Vec::new();
+            match step {
+                Step::Parity(i) => {
+                    flags.extend([PARITY_FLAG, LANE_SELECTORS]);
+                    if i >= 5 {
+                        flags.push(PARITY_ACC_FLAG);
+                    }
+                }
+                Step::Theta(_) => flags.push(THETA_FLAG),
+                Step::RhoPi(i) => {
+                    let (src, offset) = get_rho_pi_source(i);
+                    flags.extend([RHO_PI_FLAG, LANE_SELECTORS + src, THETA_SELECTORS + src % 5]);
+                    for z in 0..LANE_BITS {
+                        let coeff = BaseElement::from(1u64 << ((z + offset) % LANE_BITS));
+                        result[ROTATION_COEFFS + z][cycle_step] = coeff;
+                    }
+                }
+                Step::Chi(i) => {
+                    flags.push(if i % 5 == 4 {
+                        CHI_SHIFT_FLAG
+                    } else {
+                        CHI_ROTATE_FLAG
+                    });
+                    if i == 0 {
+                        for (j, &z) in ROUND_CONSTANT_BITS.iter().enumerate() {
+                            let bit = (ROUND_CONSTANTS[round] >> z) & 1;
+                            result[ROUND_CONSTANT_FLAGS + j][cycle_step] = BaseElement::from(bit);
+                        }
+                    }
+                }
+                Step::Padding => (),
+            }
+            for column in flags {
+                result[column][cycle_step] = BaseElement::ONE;
+            }
+        }
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a ^ b for binary a and b.
+fn xor<E: FieldElement>(a: E, b: E) -> E {
+    a + b - (a * b).double()
+}
+
+/// Packs the provided bits into a single field element, with the least significant bit first.
+fn pack<E: FieldElement>(bits: impl Iterator<Item = E>) -> E {
+    bits.zip(0..LANE_BITS)
+        .fold(E::ZERO, |acc, (bit, z)| acc + bit * E::from(1u64 << z))
+}
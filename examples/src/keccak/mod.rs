@@ -0,0 +1,256 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    verify_with_serialized_inputs, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction,
+    Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+pub use air::{KeccakAir, PublicInputs};
+
+pub mod permutation;
+use permutation::{LANE_BITS, NUM_LANES, NUM_ROUNDS, ROTATION_OFFSETS};
+
+mod prover;
+pub use prover::KeccakProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of steps needed to compute a single round of the permutation.
+const ROUND_LENGTH: usize = 80;
+
+/// Number of steps needed to compute a single permutation: all rounds of the permutation are
+/// followed by steps during which the state is left unchanged so that the cycle length is a
+/// power of two.
+const CYCLE_LENGTH: usize = 2048;
+
+// The trace consists of the following columns:
+// * lanes of the state, each packed into a single field element,
+// * a buffer of 5 lanes used to compute column parities of the state for the theta step,
+// * a buffer of 5 lanes holding values by which the columns are updated by the theta step,
+// * lanes of the state after the theta, rho and pi steps,
+// * several slots holding bits of specific lanes; bitwise operations are performed over these.
+const LANES: usize = 0;
+const PARITIES: usize = LANES + NUM_LANES;
+const THETA_LANES: usize = PARITIES + 5;
+const RHO_PI_LANES: usize = THETA_LANES + 5;
+const SLOTS: usize = RHO_PI_LANES + NUM_LANES;
+const NUM_SLOTS: usize = 3;
+const TRACE_WIDTH: usize = SLOTS + NUM_SLOTS * LANE_BITS;
+
+// KECCAK PERMUTATION CHAIN EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_permutations: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(KeccakExample::<Blake3_192>::new(
+            num_permutations,
+            options,
+        )?)),
+        HashFunction::Blake3_256 => Ok(Box::new(KeccakExample::<Blake3_256>::new(
+            num_permutations,
+            options,
+        )?)),
+        HashFunction::Sha3_256 => Ok(Box::new(KeccakExample::<Sha3_256>::new(
+            num_permutations,
+            options,
+        )?)),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct KeccakExample<H: ElementHasher> {
+    options: ProofOptions,
+    num_permutations: usize,
+    seed: [u64; NUM_LANES],
+    result: [u64; NUM_LANES],
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> KeccakExample<H> {
+    pub fn new(num_permutations: usize, options: ProofOptions) -> Result<Self, String> {
+        if !num_permutations.is_power_of_two() {
+            return Err("number of permutations must be a power of 2".to_string());
+        }
+        let mut seed = [0; NUM_LANES];
+        for (i, lane) in seed.iter_mut().enumerate() {
+            *lane = (i as u64) << 32 | i as u64;
+        }
+
+        // compute the result of applying the permutation to the seed repeatedly
+        let now = Instant::now();
+        let result = compute_permutation_chain(seed, num_permutations);
+        debug!(
+            "Computed a chain of {} Keccak-f[1600] permutations in {} ms",
+            num_permutations,
+            now.elapsed().as_millis(),
+        );
+
+        Ok(KeccakExample {
+            options,
+            num_permutations,
+            seed,
+            result,
+            _hasher: PhantomData,
+        })
+    }
+
+    fn get_pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            seed: self.seed,
+            result: self.result,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for KeccakExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for computing a chain of {} Keccak-f[1600] permutations\n\
+            ---------------------",
+            self.num_permutations
+        );
+
+        // create a prover
+        let prover = KeccakProver::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.seed, self.num_permutations);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<KeccakAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.get_pub_inputs(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.get_pub_inputs();
+        pub_inputs.result[0] ^= 1;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<KeccakAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.get_pub_inputs().to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<KeccakAir, H>(proof, pub_inputs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the result of applying Keccak-f[1600] permutation to the `seed` the specified number
+/// of times.
+pub fn compute_permutation_chain(seed: [u64; NUM_LANES], num_permutations: usize) -> [u64; 25] {
+    let mut state = seed;
+    for _ in 0..num_permutations {
+        permutation::apply_permutation(&mut state);
+    }
+    state
+}
+
+/// Returns the index of the column holding bit `z` of the specified slot.
+fn slot_column(slot: usize, z: usize) -> usize {
+    SLOTS + slot * LANE_BITS + z
+}
+
+// STEP SCHEDULE
+// ================================================================================================
+
+/// Operation performed at a given step of a permutation cycle.
+///
+/// A round of the permutation is computed in the following phases (lanes are indexed as
+/// x + 5 * y):
+/// * 25 steps accumulating column parities of the state; at step `i`, lane `i` is XORed into
+///   the parity of column i mod 5 (the state is rotated by one lane at every step),
+/// * 5 steps computing D[x] = C[x - 1] ^ rot(C[x + 1], 1) for every column x,
+/// * 25 steps computing lanes of the state after the theta, rho and pi steps; at step `i`,
+///   lane `i` of the resulting state is computed,
+/// * 25 steps computing lanes of the state after the chi and iota steps; at step `i`, lane `i`
+///   of the resulting state is computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Parity(usize),
+    Theta(usize),
+    RhoPi(usize),
+    Chi(usize),
+    Padding,
+}
+
+impl Step {
+    /// Returns the operation performed at the specified step of a permutation cycle, together
+    /// with the index of the round to which the step belongs.
+    fn at(cycle_step: usize) -> (usize, Step) {
+        let round = cycle_step / ROUND_LENGTH;
+        if round >= NUM_ROUNDS {
+            return (round, Step::Padding);
+        }
+        let step = match cycle_step % ROUND_LENGTH {
+            i @ 0..=24 => Step::Parity(i),
+            i @ 25..=29 => Step::Theta(i - 25),
+            i @ 30..=54 => Step::RhoPi(i - 30),
+            i => Step::Chi(i - 55),
+        };
+        (round, step)
+    }
+}
+
+/// Returns the index of the lane of the state after the theta step which becomes lane `i` of the
+/// state after the rho and pi steps, together with the rotation offset applied to it.
+///
+/// Lane (x, y) after the pi step is lane (x', x) rotated by the rho step, where
+/// y = 2 * x' + 3 * x (mod 5).
+fn get_rho_pi_source(i: usize) -> (usize, usize) {
+    let (x, y) = (i % 5, i / 5);
+    let src_x = 3 * (y + 2 * x) % 5;
+    (src_x + 5 * x, ROTATION_OFFSETS[src_x][x])
+}
@@ -0,0 +1,106 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of 64-bit lanes in Keccak-f[1600] state.
+pub const NUM_LANES: usize = 25;
+
+/// Number of bits in a single lane.
+pub const LANE_BITS: usize = 64;
+
+/// Number of rounds in Keccak-f[1600] permutation.
+pub const NUM_ROUNDS: usize = 24;
+
+/// Round constants added to lane (0, 0) by the iota step.
+pub const ROUND_CONSTANTS: [u64; NUM_ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Positions of the bits which may be set in [ROUND_CONSTANTS]; all other bits of round
+/// constants are always zeros.
+pub const ROUND_CONSTANT_BITS: [usize; 7] = [0, 1, 3, 7, 15, 31, 63];
+
+/// Rotation offsets of the rho step, indexed by lane coordinates as `[x][y]`.
+pub const ROTATION_OFFSETS: [[usize; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+// PERMUTATION
+// ================================================================================================
+
+/// Applies Keccak-f[1600] permutation to the provided state; lane (x, y) of the state is at
+/// index x + 5 * y.
+pub fn apply_permutation(state: &mut [u64; NUM_LANES]) {
+    for round in 0..NUM_ROUNDS {
+        apply_round(state, round);
+    }
+}
+
+/// Applies a single round of Keccak-f[1600] permutation to the provided state.
+pub fn apply_round(state: &mut [u64; NUM_LANES], round: usize) {
+    // theta
+    let c = get_column_parities(state);
+    for (i, lane) in state.iter_mut().enumerate() {
+        let x = i % 5;
+        *lane ^= c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+    }
+
+    // rho and pi
+    let mut b = [0; NUM_LANES];
+    for x in 0..5 {
+        for y in 0..5 {
+            let lane = state[x + 5 * y].rotate_left(ROTATION_OFFSETS[x][y] as u32);
+            b[y + 5 * ((2 * x + 3 * y) % 5)] = lane;
+        }
+    }
+
+    // chi
+    for x in 0..5 {
+        for y in 0..5 {
+            state[x + 5 * y] = b[x + 5 * y] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+        }
+    }
+
+    // iota
+    state[0] ^= ROUND_CONSTANTS[round];
+}
+
+/// Returns parities of the columns of the provided state: c[x] = a[x, 0] ^ ... ^ a[x, 4].
+pub fn get_column_parities(state: &[u64; NUM_LANES]) -> [u64; 5] {
+    let mut c = [0; 5];
+    for (i, &lane) in state.iter().enumerate() {
+        c[i % 5] ^= lane;
+    }
+    c
+}
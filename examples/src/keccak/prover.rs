@@ -0,0 +1,191 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_rho_pi_source, permutation::ROUND_CONSTANTS, slot_column, BaseElement, DefaultRandomCoin,
+    ElementHasher, KeccakAir, MerkleTree, PhantomData, ProofOptions, Prover, PublicInputs, Step,
+    Trace, TraceTable, CYCLE_LENGTH, LANES, LANE_BITS, NUM_LANES, NUM_SLOTS, PARITIES,
+    RHO_PI_LANES, THETA_LANES, TRACE_WIDTH,
+};
+use winterfell::math::StarkField;
+
+// KECCAK PROVER
+// ================================================================================================
+
+pub struct KeccakProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> KeccakProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for applying Keccak-f[1600] permutation to the `seed` the
+    /// specified number of times.
+    pub fn build_trace(
+        &self,
+        seed: [u64; NUM_LANES],
+        num_permutations: usize,
+    ) -> TraceTable<BaseElement> {
+        assert!(
+            num_permutations.is_power_of_two(),
+            "number of permutations must be a power of 2"
+        );
+        let trace_length = num_permutations * CYCLE_LENGTH;
+        let mut trace = (0..TRACE_WIDTH)
+            .map(|_| Vec::with_capacity(trace_length))
+            .collect::<Vec<_>>();
+
+        let mut registers = Registers::new(seed);
+        for step in 0..trace_length {
+            let (round, step) = Step::at(step % CYCLE_LENGTH);
+            let slots = registers.get_slots(step);
+            registers.write_into(&slots, &mut trace);
+            registers.apply_step(round, step, &slots);
+        }
+
+        TraceTable::init(trace)
+    }
+}
+
+impl<H: ElementHasher> Prover for KeccakProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = KeccakAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            seed: read_state(trace, 0),
+            result: read_state(trace, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// REGISTERS
+// ================================================================================================
+
+/// Lanes held in the trace columns at a given step of the computation.
+struct Registers {
+    lanes: [u64; NUM_LANES],
+    parities: [u64; 5],
+    theta: [u64; 5],
+    rho_pi: [u64; NUM_LANES],
+}
+
+impl Registers {
+    fn new(seed: [u64; NUM_LANES]) -> Self {
+        Registers {
+            lanes: seed,
+            parities: [0; 5],
+            theta: [0; 5],
+            rho_pi: [0; NUM_LANES],
+        }
+    }
+
+    /// Returns the lanes placed into slots at the specified step.
+    fn get_slots(&self, step: Step) -> [u64; NUM_SLOTS] {
+        match step {
+            Step::Parity(i) => {
+                let acc = if i < 5 { 0 } else { self.parities[0] };
+                [self.lanes[0], acc, 0]
+            }
+            Step::Theta(_) => [0, self.parities[4], self.parities[1]],
+            Step::RhoPi(i) => {
+                let (src, _) = get_rho_pi_source(i);
+                [self.lanes[src], self.theta[src % 5], 0]
+            }
+            Step::Chi(_) => [self.rho_pi[0], self.rho_pi[1], self.rho_pi[2]],
+            Step::Padding => [0; NUM_SLOTS],
+        }
+    }
+
+    /// Updates the registers by executing the specified step.
+    fn apply_step(&mut self, round: usize, step: Step, slots: &[u64; NUM_SLOTS]) {
+        match step {
+            Step::Parity(_) => {
+                push(&mut self.parities, slots[0] ^ slots[1]);
+                self.lanes.rotate_left(1);
+            }
+            Step::Theta(_) => {
+                push(&mut self.theta, slots[1] ^ slots[2].rotate_left(1));
+                self.parities.rotate_left(1);
+            }
+            Step::RhoPi(i) => {
+                let (_, offset) = get_rho_pi_source(i);
+                push(
+                    &mut self.rho_pi,
+                    (slots[0] ^ slots[1]).rotate_left(offset as u32),
+                );
+            }
+            Step::Chi(i) => {
+                let mut lane = slots[0] ^ (!slots[1] & slots[2]);
+                if i == 0 {
+                    lane ^= ROUND_CONSTANTS[round];
+                }
+                push(&mut self.lanes, lane);
+                if i % 5 == 4 {
+                    self.rho_pi.copy_within(5.., 0);
+                } else {
+                    self.rho_pi[..5].rotate_left(1);
+                }
+            }
+            Step::Padding => (),
+        }
+    }
+
+    /// Appends the registers and the bits of the slots to the trace.
+    fn write_into(&self, slots: &[u64; NUM_SLOTS], trace: &mut [Vec<BaseElement>]) {
+        let registers = [
+            (LANES, &self.lanes[..]),
+            (PARITIES, &self.parities[..]),
+            (THETA_LANES, &self.theta[..]),
+            (RHO_PI_LANES, &self.rho_pi[..]),
+        ];
+        for (offset, lanes) in registers {
+            for (i, &lane) in lanes.iter().enumerate() {
+                trace[offset + i].push(BaseElement::from(lane));
+            }
+        }
+        for (slot, &lane) in slots.iter().enumerate() {
+            for z in 0..LANE_BITS {
+                trace[slot_column(slot, z)].push(BaseElement::from((lane >> z) & 1));
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Shifts the lanes by one position towards the beginning, and places the new lane at the end.
+fn push(lanes: &mut [u64], lane: u64) {
+    lanes.rotate_left(1);
+    lanes[lanes.len() - 1] = lane;
+}
+
+/// Reads the state at the specified step of the trace.
+fn read_state(trace: &TraceTable<BaseElement>, step: usize) -> [u64; NUM_LANES] {
+    let mut state = [0; NUM_LANES];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = trace.get(LANES + i, step).as_int() as u64;
+    }
+    state
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    permutation::apply_permutation, Blake3_256, KeccakAir, KeccakExample, KeccakProver, LANES,
+    NUM_LANES,
+};
+use winterfell::{
+    debug::ConstraintDebugger, math::fields::f128::BaseElement, Air, FieldExtension, ProofOptions,
+    Prover, Trace,
+};
+
+#[test]
+fn keccak_test_basic_proof_verification() {
+    let keccak_eg = Box::new(KeccakExample::<Blake3_256>::new(1, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification(keccak_eg);
+}
+
+#[test]
+fn keccak_test_basic_proof_verification_extension() {
+    let keccak_eg = Box::new(KeccakExample::<Blake3_256>::new(1, build_options(true)).unwrap());
+    crate::tests::test_basic_proof_verification(keccak_eg);
+}
+
+#[test]
+fn keccak_test_basic_proof_verification_fail() {
+    let keccak_eg = Box::new(KeccakExample::<Blake3_256>::new(1, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification_fail(keccak_eg);
+}
+
+#[test]
+fn keccak_test_proof_verification_with_pub_inputs() {
+    let keccak_eg = Box::new(KeccakExample::<Blake3_256>::new(1, build_options(false)).unwrap());
+    crate::tests::test_proof_verification_with_pub_inputs(keccak_eg);
+}
+
+#[test]
+fn keccak_test_permutation() {
+    // Keccak-f[1600] applied to the all-zero state, from the reference intermediate values
+    // published by the Keccak team
+    let expected = [
+        0xF1258F7940E1DDE7,
+        0x84D5CCF933C0478A,
+        0xD598261EA65AA9EE,
+        0xBD1547306F80494D,
+        0x8B284E056253D057,
+    ];
+    let mut state = [0; NUM_LANES];
+    apply_permutation(&mut state);
+    assert_eq!(expected, state[..5]);
+}
+
+#[test]
+fn keccak_test_tampered_round() {
+    let options = build_options(false);
+    let prover = KeccakProver::<Blake3_256>::new(options.clone());
+    let mut trace = prover.build_trace([0; NUM_LANES], 1);
+
+    // modify a lane of the state in the middle of the permutation
+    let column = LANES + 7;
+    let value = trace.get(column, 100) + BaseElement::from(8u8);
+    trace.set(column, 100, value);
+
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let air = KeccakAir::new(trace.get_info(), pub_inputs, options);
+    let debugger = ConstraintDebugger::new(&air, &trace);
+
+    // only the constraints of the steps reading the modified lane are violated
+    let unsatisfied = debugger.unsatisfied_constraints();
+    assert!(!unsatisfied.is_empty());
+    for evaluations in unsatisfied {
+        for step in evaluations.violations() {
+            assert!(step == 99 || step == 100);
+        }
+    }
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 4, 0, extension, 4, 31)
+}
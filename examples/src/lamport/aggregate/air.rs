@@ -6,12 +6,11 @@
 use super::{
     rescue, CYCLE_LENGTH as HASH_CYCLE_LEN, SIG_CYCLE_LENGTH as SIG_CYCLE_LEN, TRACE_WIDTH,
 };
-use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
-use core_utils::flatten_slice_elements;
+use crate::utils::{are_equal, is_binary, is_zero, not, read_element_arrays, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, ToElements},
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    EvaluationFrame, ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -21,18 +20,29 @@ const TWO: BaseElement = BaseElement::new(2);
 // AGGREGATE LAMPORT PLUS SIGNATURE AIR
 // ================================================================================================
 
-#[derive(Clone)]
+#[derive(Clone, ToElements)]
+#[to_elements(crate = "winterfell::math")]
 pub struct PublicInputs {
     pub pub_keys: Vec<[BaseElement; 2]>,
     pub messages: Vec<[BaseElement; 2]>,
 }
 
-impl ToElements<BaseElement> for PublicInputs {
-    fn to_elements(&self) -> Vec<BaseElement> {
-        let mut result = Vec::new();
-        result.extend_from_slice(flatten_slice_elements(&self.pub_keys));
-        result.extend_from_slice(flatten_slice_elements(&self.messages));
-        result
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.pub_keys.len() as u32);
+        target.write(&self.pub_keys);
+        target.write_u32(self.messages.len() as u32);
+        target.write(&self.messages);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_pub_keys = source.read_u32()? as usize;
+        let pub_keys = read_element_arrays(source, num_pub_keys)?;
+        let num_messages = source.read_u32()? as usize;
+        let messages = read_element_arrays(source, num_messages)?;
+        Ok(PublicInputs { pub_keys, messages })
     }
 }
 
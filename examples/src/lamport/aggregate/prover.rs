@@ -5,8 +5,8 @@
 
 use super::{
     get_power_series, rescue, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement,
-    LamportAggregateAir, PhantomData, ProofOptions, Prover, PublicInputs, Signature, StarkField,
-    TraceTable, CYCLE_LENGTH, NUM_HASH_ROUNDS, SIG_CYCLE_LENGTH, TRACE_WIDTH,
+    LamportAggregateAir, MerkleTree, PhantomData, ProofOptions, Prover, PublicInputs, Signature,
+    StarkField, TraceTable, CYCLE_LENGTH, NUM_HASH_ROUNDS, SIG_CYCLE_LENGTH, TRACE_WIDTH,
 };
 
 #[cfg(feature = "concurrent")]
@@ -96,6 +96,7 @@ where
     type Air = LamportAggregateAir;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
+    type VC = MerkleTree<H>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 
     fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
@@ -7,11 +7,11 @@ use super::{
     super::rescue, HASH_CYCLE_LENGTH as HASH_CYCLE_LEN, SIG_CYCLE_LENGTH as SIG_CYCLE_LEN,
     TRACE_WIDTH,
 };
-use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
+use crate::utils::{are_equal, is_binary, is_zero, not, read_element_array, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    EvaluationFrame, ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -21,7 +21,8 @@ const TWO: BaseElement = BaseElement::new(2);
 // THRESHOLD LAMPORT PLUS SIGNATURE AIR
 // ================================================================================================
 
-#[derive(Clone)]
+#[derive(Clone, ToElements)]
+#[to_elements(crate = "winterfell::math")]
 pub struct PublicInputs {
     pub pub_key_root: [BaseElement; 2],
     pub num_pub_keys: usize,
@@ -29,13 +30,23 @@ pub struct PublicInputs {
     pub message: [BaseElement; 2],
 }
 
-impl ToElements<BaseElement> for PublicInputs {
-    fn to_elements(&self) -> Vec<BaseElement> {
-        let mut result = self.pub_key_root.to_vec();
-        result.push(BaseElement::from(self.num_pub_keys as u64));
-        result.push(BaseElement::from(self.num_signatures as u64));
-        result.extend_from_slice(&self.message);
-        result
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.pub_key_root[..]);
+        target.write_u64(self.num_pub_keys as u64);
+        target.write_u64(self.num_signatures as u64);
+        target.write(&self.message[..]);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(PublicInputs {
+            pub_key_root: read_element_array(source)?,
+            num_pub_keys: source.read_u64()? as usize,
+            num_signatures: source.read_u64()? as usize,
+            message: read_element_array(source)?,
+        })
     }
 }
 
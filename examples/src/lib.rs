@@ -3,23 +3,46 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use std::path::PathBuf;
 use structopt::StructOpt;
 use winterfell::{
-    crypto::hashers::{GriffinJive64_256, Rp64_256, RpJive64_256},
+    crypto::{
+        hashers::{GriffinJive64_256, Rp64_256, RpJive64_256},
+        DefaultRandomCoin, ElementHasher,
+    },
     math::fields::f128::BaseElement,
-    FieldExtension, ProofOptions, StarkProof, VerifierError,
+    Air, Deserializable, FieldExtension, ProofOptions, StarkProof, VerifierError,
 };
 
+mod config;
+pub use config::OptionsFile;
+
+#[cfg(feature = "std")]
+pub mod blake2s;
+#[cfg(feature = "std")]
+pub mod cairo;
+#[cfg(feature = "std")]
+pub mod ecdsa;
 pub mod fibonacci;
 #[cfg(feature = "std")]
+pub mod keccak;
+#[cfg(feature = "std")]
 pub mod lamport;
 #[cfg(feature = "std")]
 pub mod merkle;
+#[cfg(feature = "std")]
+pub mod range_check;
+#[cfg(feature = "std")]
+pub mod recursive;
+pub mod registry;
 pub mod rescue;
 #[cfg(feature = "std")]
 pub mod rescue_raps;
+pub mod sweep;
 pub mod utils;
 pub mod vdf;
+#[cfg(feature = "std")]
+pub mod zkvm;
 
 #[cfg(test)]
 mod tests;
@@ -35,6 +58,32 @@ pub trait Example {
     fn prove(&self) -> StarkProof;
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError>;
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError>;
+
+    /// Returns public inputs of the computation proven by this example serialized into bytes.
+    fn pub_inputs(&self) -> Vec<u8>;
+
+    /// Verifies the proof against public inputs serialized in `pub_inputs` (rather than against
+    /// public inputs of this example).
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String>;
+}
+
+/// Verifies the proof of the computation described by `A` against public inputs serialized in
+/// `pub_inputs`.
+pub(crate) fn verify_with_serialized_inputs<A, H>(
+    proof: StarkProof,
+    pub_inputs: &[u8],
+) -> Result<(), String>
+where
+    A: Air,
+    A::PublicInputs: Deserializable,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    let pub_inputs = A::PublicInputs::read_from_bytes(pub_inputs)
+        .map_err(|err| format!("failed to read public inputs: {err}"))?;
+    let acceptable_options =
+        winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+    winterfell::verify::<A, H, DefaultRandomCoin<H>>(proof, pub_inputs, &acceptable_options)
+        .map_err(|err| err.to_string())
 }
 
 // EXAMPLE OPTIONS
@@ -44,11 +93,15 @@ pub trait Example {
 #[structopt(name = "winterfell", about = "Winterfell examples")]
 pub struct ExampleOptions {
     #[structopt(subcommand)]
-    pub example: ExampleType,
+    pub command: Command,
 
-    /// Hash function used in the protocol
-    #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
-    hash_fn: String,
+    /// Path to a TOML file with proof options; options specified via flags take precedence
+    #[structopt(short = "c", long = "config", parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    /// Hash function used in the protocol [default: blake3_256]
+    #[structopt(short = "h", long = "hash_fn")]
+    hash_fn: Option<String>,
 
     /// Number of queries to include in a proof
     #[structopt(short = "q", long = "queries")]
@@ -58,48 +111,84 @@ pub struct ExampleOptions {
     #[structopt(short = "b", long = "blowup")]
     blowup_factor: Option<usize>,
 
-    /// Grinding factor for query seed
-    #[structopt(short = "g", long = "grinding", default_value = "16")]
-    grinding_factor: u32,
+    /// Grinding factor for query seed [default: 16]
+    #[structopt(short = "g", long = "grinding")]
+    grinding_factor: Option<u32>,
+
+    /// Field extension degree for composition polynomial [default: 1]
+    #[structopt(short = "e", long = "field_extension")]
+    field_extension: Option<u32>,
 
-    /// Field extension degree for composition polynomial
-    #[structopt(short = "e", long = "field_extension", default_value = "1")]
-    field_extension: u32,
+    /// Folding factor for FRI protocol [default: 8]
+    #[structopt(short = "f", long = "folding")]
+    folding_factor: Option<usize>,
 
-    /// Folding factor for FRI protocol
-    #[structopt(short = "f", long = "folding", default_value = "8")]
-    folding_factor: usize,
+    /// Maximum degree of the remainder polynomial in FRI protocol [default: 31]
+    #[structopt(short = "r", long = "remainder_degree")]
+    remainder_max_degree: Option<usize>,
 }
 
 impl ExampleOptions {
+    /// Returns options for running the specified `example` with the specified hash function and
+    /// proof options; this is equivalent to specifying all proof options via flags.
+    pub fn new(example: ExampleType, hash_fn: HashFunction, options: &ProofOptions) -> Self {
+        let fri_options = options.to_fri_options();
+        ExampleOptions {
+            command: Command::Run(example),
+            config: None,
+            hash_fn: Some(hash_fn.as_str().to_string()),
+            num_queries: Some(options.num_queries()),
+            blowup_factor: Some(options.blowup_factor()),
+            grinding_factor: Some(options.grinding_factor()),
+            field_extension: Some(options.field_extension().degree()),
+            folding_factor: Some(fri_options.folding_factor()),
+            remainder_max_degree: Some(fri_options.remainder_max_degree()),
+        }
+    }
+
+    /// Reads proof options from the file specified via `--config` flag (if any), and uses them
+    /// for the options which were not specified via flags.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read, or if it does not contain valid proof
+    /// options.
+    pub fn load_config_file(&mut self) -> Result<(), String> {
+        let path = match &self.config {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let file = OptionsFile::read(path)?;
+        self.hash_fn = self.hash_fn.take().or(file.hash_fn);
+        self.num_queries = self.num_queries.or(file.num_queries);
+        self.blowup_factor = self.blowup_factor.or(file.blowup_factor);
+        self.grinding_factor = self.grinding_factor.or(file.grinding_factor);
+        self.field_extension = self.field_extension.or(file.field_extension);
+        self.folding_factor = self.folding_factor.or(file.fri_folding_factor);
+        self.remainder_max_degree = self.remainder_max_degree.or(file.fri_remainder_max_degree);
+        Ok(())
+    }
+
     pub fn to_proof_options(&self, q: usize, b: usize) -> (ProofOptions, HashFunction) {
         let num_queries = self.num_queries.unwrap_or(q);
         let blowup_factor = self.blowup_factor.unwrap_or(b);
-        let field_extension = match self.field_extension {
+        let field_extension = match self.field_extension.unwrap_or(1) {
             1 => FieldExtension::None,
             2 => FieldExtension::Quadratic,
             3 => FieldExtension::Cubic,
+            4 => FieldExtension::Quartic,
             val => panic!("'{val}' is not a valid field extension option"),
         };
 
-        let hash_fn = match self.hash_fn.as_str() {
-            "blake3_192" => HashFunction::Blake3_192,
-            "blake3_256" => HashFunction::Blake3_256,
-            "sha3_256" => HashFunction::Sha3_256,
-            "rp64_256" => HashFunction::Rp64_256,
-            "rp_jive64_256" => HashFunction::RpJive64_256,
-            "griffin_jive64_256" => HashFunction::GriffinJive64_256,
-            val => panic!("'{val}' is not a valid hash function option"),
-        };
+        let hash_fn = self.hash_function();
 
         (
             ProofOptions::new(
                 num_queries,
                 blowup_factor,
-                self.grinding_factor,
+                self.grinding_factor.unwrap_or(16),
                 field_extension,
-                self.folding_factor,
-                31,
+                self.folding_factor.unwrap_or(8),
+                self.remainder_max_degree.unwrap_or(31),
             ),
             hash_fn,
         )
@@ -107,21 +196,68 @@ impl ExampleOptions {
 
     /// Returns security level of the input proof in bits.
     pub fn get_proof_security_level(&self, proof: &StarkProof, conjectured: bool) -> usize {
-        let security_level = match self.hash_fn.as_str() {
-            "blake3_192" => proof.security_level::<Blake3_192>(conjectured),
-            "blake3_256" => proof.security_level::<Blake3_256>(conjectured),
-            "sha3_256" => proof.security_level::<Sha3_256>(conjectured),
-            "rp64_256" => proof.security_level::<Rp64_256>(conjectured),
-            "rp_jive64_256" => proof.security_level::<RpJive64_256>(conjectured),
-            "griffin_jive64_256" => proof.security_level::<GriffinJive64_256>(conjectured),
-            val => panic!("'{val}' is not a valid hash function option"),
-        };
+        let security_level = self
+            .hash_function()
+            .proof_security_level(proof, conjectured);
 
         security_level as usize
     }
+
+    /// Returns the hash function used in the protocol.
+    ///
+    /// # Panics
+    /// Panics if the hash function specified via `--hash_fn` flag or in the options file is not
+    /// valid.
+    pub fn hash_function(&self) -> HashFunction {
+        HashFunction::from_name(self.hash_fn())
+            .unwrap_or_else(|| panic!("'{}' is not a valid hash function option", self.hash_fn()))
+    }
+
+    /// Returns the name of the hash function used in the protocol.
+    fn hash_fn(&self) -> &str {
+        self.hash_fn.as_deref().unwrap_or("blake3_256")
+    }
 }
 
 #[derive(StructOpt, Debug)]
+pub enum Command {
+    /// Generate a proof for an example and write it into a file
+    Prove {
+        /// Path of the file into which the proof is written
+        #[structopt(short = "o", long = "output", parse(from_os_str))]
+        proof_path: PathBuf,
+
+        /// Path of the file into which public inputs are written [default: <output>.inputs]
+        #[structopt(short = "p", long = "pub_inputs", parse(from_os_str))]
+        pub_inputs_path: Option<PathBuf>,
+
+        #[structopt(subcommand)]
+        example: ExampleType,
+    },
+    /// Verify a proof for an example read from a file
+    Verify {
+        /// Path of the file from which the proof is read
+        #[structopt(short = "i", long = "input", parse(from_os_str))]
+        proof_path: PathBuf,
+
+        /// Path of the file from which public inputs are read [default: <input>.inputs]
+        #[structopt(short = "p", long = "pub_inputs", parse(from_os_str))]
+        pub_inputs_path: Option<PathBuf>,
+
+        #[structopt(subcommand)]
+        example: ExampleType,
+    },
+    /// Print information about a proof read from a file
+    Inspect {
+        /// Path of the file from which the proof is read
+        #[structopt(parse(from_os_str))]
+        proof_path: PathBuf,
+    },
+    #[structopt(flatten)]
+    Run(ExampleType),
+}
+
+#[derive(StructOpt, Debug, Clone)]
 //#[structopt(about = "available examples")]
 pub enum ExampleType {
     /// Compute a Fibonacci sequence using trace table with 2 registers
@@ -200,6 +336,187 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "3")]
         num_signers: usize,
     },
+    /// Execute a Cairo program computing a factorial
+    #[cfg(feature = "std")]
+    Cairo {
+        /// Number for which to compute the factorial
+        #[structopt(short = "n", default_value = "1024")]
+        n: usize,
+    },
+    /// Compute a chain of Keccak-f[1600] permutations
+    #[cfg(feature = "std")]
+    Keccak {
+        /// Number of permutations in the chain; must be a power of two
+        #[structopt(short = "n", default_value = "16")]
+        num_permutations: usize,
+    },
+    /// Verify a batch of ECDSA signatures over the STARK curve
+    #[cfg(feature = "std")]
+    Ecdsa {
+        /// Number of signatures to verify; must be a power of two
+        #[structopt(short = "n", default_value = "4")]
+        num_signatures: usize,
+    },
+    /// Compute a root of a Merkle path using Blake2s hash function
+    #[cfg(feature = "std")]
+    Blake2s {
+        /// Depth of the Merkle tree; must be a power of two
+        #[structopt(short = "n", default_value = "8")]
+        tree_depth: usize,
+    },
+    /// Compute a sum of bytes range-checked via a lookup table
+    #[cfg(feature = "std")]
+    RangeCheck {
+        /// Number of bytes to sum; must be one less than a power of two and at least 511
+        #[structopt(short = "n", default_value = "1023")]
+        num_values: usize,
+    },
+    /// Verify trace openings of an inner proof inside an outer proof
+    #[cfg(feature = "std")]
+    Recursive {
+        /// Length of the Fibonacci sequence computed by the inner proof; must be a power of two
+        #[structopt(short = "n", default_value = "1024")]
+        sequence_length: usize,
+    },
+    /// Execute a program of a small register machine with memory and lookups
+    #[cfg(feature = "std")]
+    Zkvm {
+        /// Number of iterations of the program; each iteration writes two Fibonacci terms
+        #[structopt(short = "n", default_value = "256")]
+        n: usize,
+    },
+}
+
+impl ExampleType {
+    /// Returns the name of the subcommand which runs this example.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExampleType::Fib { .. } => "fib",
+            ExampleType::Fib8 { .. } => "fib8",
+            ExampleType::Mulfib { .. } => "mulfib",
+            ExampleType::Mulfib8 { .. } => "mulfib8",
+            ExampleType::FibSmall { .. } => "fib-small",
+            ExampleType::Vdf { .. } => "vdf",
+            ExampleType::VdfExempt { .. } => "vdf-exempt",
+            ExampleType::Rescue { .. } => "rescue",
+            #[cfg(feature = "std")]
+            ExampleType::RescueRaps { .. } => "rescue-raps",
+            #[cfg(feature = "std")]
+            ExampleType::Merkle { .. } => "merkle",
+            #[cfg(feature = "std")]
+            ExampleType::LamportA { .. } => "lamport-a",
+            #[cfg(feature = "std")]
+            ExampleType::LamportT { .. } => "lamport-t",
+            #[cfg(feature = "std")]
+            ExampleType::Cairo { .. } => "cairo",
+            #[cfg(feature = "std")]
+            ExampleType::Keccak { .. } => "keccak",
+            #[cfg(feature = "std")]
+            ExampleType::Ecdsa { .. } => "ecdsa",
+            #[cfg(feature = "std")]
+            ExampleType::Blake2s { .. } => "blake2s",
+            #[cfg(feature = "std")]
+            ExampleType::RangeCheck { .. } => "range-check",
+            #[cfg(feature = "std")]
+            ExampleType::Recursive { .. } => "recursive",
+            #[cfg(feature = "std")]
+            ExampleType::Zkvm { .. } => "zkvm",
+        }
+    }
+
+    /// Returns the size of this example (i.e., the value specified via `-n` flag).
+    pub fn size(&self) -> usize {
+        match *self {
+            ExampleType::Fib { sequence_length }
+            | ExampleType::Fib8 { sequence_length }
+            | ExampleType::Mulfib { sequence_length }
+            | ExampleType::Mulfib8 { sequence_length }
+            | ExampleType::FibSmall { sequence_length } => sequence_length,
+            ExampleType::Vdf { num_steps } | ExampleType::VdfExempt { num_steps } => num_steps,
+            ExampleType::Rescue { chain_length } => chain_length,
+            #[cfg(feature = "std")]
+            ExampleType::RescueRaps { chain_length } => chain_length,
+            #[cfg(feature = "std")]
+            ExampleType::Merkle { tree_depth } => tree_depth,
+            #[cfg(feature = "std")]
+            ExampleType::LamportA { num_signatures } => num_signatures,
+            #[cfg(feature = "std")]
+            ExampleType::LamportT { num_signers } => num_signers,
+            #[cfg(feature = "std")]
+            ExampleType::Cairo { n } => n,
+            #[cfg(feature = "std")]
+            ExampleType::Keccak { num_permutations } => num_permutations,
+            #[cfg(feature = "std")]
+            ExampleType::Ecdsa { num_signatures } => num_signatures,
+            #[cfg(feature = "std")]
+            ExampleType::Blake2s { tree_depth } => tree_depth,
+            #[cfg(feature = "std")]
+            ExampleType::RangeCheck { num_values } => num_values,
+            #[cfg(feature = "std")]
+            ExampleType::Recursive { sequence_length } => sequence_length,
+            #[cfg(feature = "std")]
+            ExampleType::Zkvm { n } => n,
+        }
+    }
+
+    /// Instantiates and prepares this example using the specified `options`.
+    ///
+    /// # Errors
+    /// Returns an error if the example could not be instantiated with the specified parameters.
+    pub fn get_example(&self, options: &ExampleOptions) -> Result<Box<dyn Example>, String> {
+        match *self {
+            ExampleType::Fib { sequence_length } => {
+                fibonacci::fib2::get_example(options, sequence_length)
+            }
+            ExampleType::Fib8 { sequence_length } => {
+                fibonacci::fib8::get_example(options, sequence_length)
+            }
+            ExampleType::Mulfib { sequence_length } => {
+                fibonacci::mulfib2::get_example(options, sequence_length)
+            }
+            ExampleType::Mulfib8 { sequence_length } => {
+                fibonacci::mulfib8::get_example(options, sequence_length)
+            }
+            ExampleType::FibSmall { sequence_length } => {
+                fibonacci::fib_small::get_example(options, sequence_length)
+            }
+            ExampleType::Vdf { num_steps } => vdf::regular::get_example(options, num_steps),
+            ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(options, num_steps),
+            ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
+            #[cfg(feature = "std")]
+            ExampleType::RescueRaps { chain_length } => {
+                rescue_raps::get_example(options, chain_length)
+            }
+            #[cfg(feature = "std")]
+            ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
+            #[cfg(feature = "std")]
+            ExampleType::LamportA { num_signatures } => {
+                lamport::aggregate::get_example(options, num_signatures)
+            }
+            #[cfg(feature = "std")]
+            ExampleType::LamportT { num_signers } => {
+                lamport::threshold::get_example(options, num_signers)
+            }
+            #[cfg(feature = "std")]
+            ExampleType::Cairo { n } => cairo::get_example(options, n),
+            #[cfg(feature = "std")]
+            ExampleType::Keccak { num_permutations } => {
+                keccak::get_example(options, num_permutations)
+            }
+            #[cfg(feature = "std")]
+            ExampleType::Ecdsa { num_signatures } => ecdsa::get_example(options, num_signatures),
+            #[cfg(feature = "std")]
+            ExampleType::Blake2s { tree_depth } => blake2s::get_example(options, tree_depth),
+            #[cfg(feature = "std")]
+            ExampleType::RangeCheck { num_values } => range_check::get_example(options, num_values),
+            #[cfg(feature = "std")]
+            ExampleType::Recursive { sequence_length } => {
+                recursive::get_example(options, sequence_length)
+            }
+            #[cfg(feature = "std")]
+            ExampleType::Zkvm { n } => zkvm::get_example(options, n),
+        }
+    }
 }
 
 /// Defines a set of hash functions available for the provided examples. Some examples may not
@@ -243,3 +560,61 @@ pub enum HashFunction {
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     GriffinJive64_256,
 }
+
+impl HashFunction {
+    /// All hash functions available for the provided examples.
+    pub const ALL: [HashFunction; 6] = [
+        Self::Blake3_192,
+        Self::Blake3_256,
+        Self::Sha3_256,
+        Self::Rp64_256,
+        Self::RpJive64_256,
+        Self::GriffinJive64_256,
+    ];
+
+    /// Returns the name under which this hash function can be specified via `--hash_fn` flag.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Blake3_192 => "blake3_192",
+            Self::Blake3_256 => "blake3_256",
+            Self::Sha3_256 => "sha3_256",
+            Self::Rp64_256 => "rp64_256",
+            Self::RpJive64_256 => "rp_jive64_256",
+            Self::GriffinJive64_256 => "griffin_jive64_256",
+        }
+    }
+
+    /// Returns the hash function with the specified name, or None if the name does not identify
+    /// any of the available hash functions.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|hash_fn| hash_fn.as_str() == name)
+    }
+
+    /// Returns security level (in bits) of the specified proof generated using this hash
+    /// function.
+    pub fn proof_security_level(&self, proof: &StarkProof, conjectured: bool) -> u32 {
+        match self {
+            Self::Blake3_192 => proof.security_level::<Blake3_192>(conjectured),
+            Self::Blake3_256 => proof.security_level::<Blake3_256>(conjectured),
+            Self::Sha3_256 => proof.security_level::<Sha3_256>(conjectured),
+            Self::Rp64_256 => proof.security_level::<Rp64_256>(conjectured),
+            Self::RpJive64_256 => proof.security_level::<RpJive64_256>(conjectured),
+            Self::GriffinJive64_256 => proof.security_level::<GriffinJive64_256>(conjectured),
+        }
+    }
+
+    /// Returns a human-readable breakdown of the specified proof generated using this hash
+    /// function (see [StarkProof::describe()]).
+    pub fn describe_proof(&self, proof: &StarkProof) -> String {
+        match self {
+            Self::Blake3_192 => proof.describe::<Blake3_192>().to_string(),
+            Self::Blake3_256 => proof.describe::<Blake3_256>().to_string(),
+            Self::Sha3_256 => proof.describe::<Sha3_256>().to_string(),
+            Self::Rp64_256 => proof.describe::<Rp64_256>().to_string(),
+            Self::RpJive64_256 => proof.describe::<RpJive64_256>().to_string(),
+            Self::GriffinJive64_256 => proof.describe::<GriffinJive64_256>().to_string(),
+        }
+    }
+}
@@ -3,15 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use log::debug;
-use std::io::Write;
-use std::time::Instant;
+use log::{debug, error};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process,
+    time::Instant,
+};
 use structopt::StructOpt;
 use winterfell::StarkProof;
 
-use examples::{fibonacci, rescue, vdf, ExampleOptions, ExampleType};
-#[cfg(feature = "std")]
-use examples::{lamport, merkle, rescue_raps};
+use examples::{Command, Example, ExampleOptions};
 
 // EXAMPLE RUNNER
 // ================================================================================================
@@ -24,50 +27,51 @@ fn main() {
         .init();
 
     // read command-line args
-    let options = ExampleOptions::from_args();
+    let mut options = ExampleOptions::from_args();
+    if let Err(err) = options.load_config_file() {
+        error!("{}", err);
+        process::exit(1);
+    }
 
     debug!("============================================================");
 
-    // instantiate and prepare the example
-    let example = match options.example {
-        ExampleType::Fib { sequence_length } => {
-            fibonacci::fib2::get_example(&options, sequence_length)
-        }
-        ExampleType::Fib8 { sequence_length } => {
-            fibonacci::fib8::get_example(&options, sequence_length)
-        }
-        ExampleType::Mulfib { sequence_length } => {
-            fibonacci::mulfib2::get_example(&options, sequence_length)
-        }
-        ExampleType::Mulfib8 { sequence_length } => {
-            fibonacci::mulfib8::get_example(&options, sequence_length)
-        }
-        ExampleType::FibSmall { sequence_length } => {
-            fibonacci::fib_small::get_example(&options, sequence_length)
-        }
-        ExampleType::Vdf { num_steps } => vdf::regular::get_example(&options, num_steps),
-        ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(&options, num_steps),
-        ExampleType::Rescue { chain_length } => rescue::get_example(&options, chain_length),
-        #[cfg(feature = "std")]
-        ExampleType::RescueRaps { chain_length } => {
-            rescue_raps::get_example(&options, chain_length)
-        }
-        #[cfg(feature = "std")]
-        ExampleType::Merkle { tree_depth } => merkle::get_example(&options, tree_depth),
-        #[cfg(feature = "std")]
-        ExampleType::LamportA { num_signatures } => {
-            lamport::aggregate::get_example(&options, num_signatures)
-        }
-        #[cfg(feature = "std")]
-        ExampleType::LamportT { num_signers } => {
-            lamport::threshold::get_example(&options, num_signers)
-        }
+    // instantiate and prepare the example, and execute the command
+    let result = match &options.command {
+        Command::Run(example) => example
+            .get_example(&options)
+            .map(|example| run(&options, example.as_ref())),
+        Command::Prove {
+            proof_path,
+            pub_inputs_path,
+            example,
+        } => example.get_example(&options).and_then(|example| {
+            let pub_inputs_path = get_pub_inputs_path(proof_path, pub_inputs_path.as_deref());
+            prove(&options, example.as_ref(), proof_path, &pub_inputs_path)
+        }),
+        Command::Verify {
+            proof_path,
+            pub_inputs_path,
+            example,
+        } => example.get_example(&options).and_then(|example| {
+            let pub_inputs_path = get_pub_inputs_path(proof_path, pub_inputs_path.as_deref());
+            verify(&options, example.as_ref(), proof_path, &pub_inputs_path)
+        }),
+        Command::Inspect { proof_path } => inspect(&options, proof_path),
+    };
+
+    if let Err(err) = result {
+        error!("{}", err);
+        process::exit(1);
     }
-    .expect("The example failed to initialize.");
+}
 
+// COMMANDS
+// ================================================================================================
+
+/// Generates a proof for the example, and then verifies it.
+fn run(options: &ExampleOptions, example: &dyn Example) {
     // generate proof
     let now = Instant::now();
-    let example = example.as_ref();
     let proof = example.prove();
     debug!(
         "---------------------\nProof generated in {} ms",
@@ -76,6 +80,10 @@ fn main() {
 
     let proof_bytes = proof.to_bytes();
     debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
+    debug!(
+        "Compressed proof size: {:.1} KB",
+        proof.to_bytes_compressed().len() as f64 / 1024f64
+    );
     let conjectured_security_level = options.get_proof_security_level(&proof, true);
 
     #[cfg(feature = "std")]
@@ -110,3 +118,98 @@ fn main() {
     }
     debug!("============================================================");
 }
+
+/// Generates a proof for the example, and writes the proof and public inputs of the example into
+/// the specified files.
+fn prove(
+    options: &ExampleOptions,
+    example: &dyn Example,
+    proof_path: &Path,
+    pub_inputs_path: &Path,
+) -> Result<(), String> {
+    let now = Instant::now();
+    let proof = example.prove();
+    debug!(
+        "---------------------\nProof generated in {} ms",
+        now.elapsed().as_millis()
+    );
+
+    let proof_bytes = proof.to_bytes();
+    debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
+    debug!(
+        "Proof security: {} bits",
+        options.get_proof_security_level(&proof, true)
+    );
+
+    write_file(proof_path, &proof_bytes)?;
+    write_file(pub_inputs_path, &example.pub_inputs())?;
+    debug!(
+        "Proof written to {}, public inputs written to {}",
+        proof_path.display(),
+        pub_inputs_path.display()
+    );
+    debug!("============================================================");
+    Ok(())
+}
+
+/// Reads a proof and public inputs from the specified files, and verifies the proof for the
+/// example against the public inputs.
+fn verify(
+    options: &ExampleOptions,
+    example: &dyn Example,
+    proof_path: &Path,
+    pub_inputs_path: &Path,
+) -> Result<(), String> {
+    let proof = read_proof(proof_path)?;
+    let pub_inputs = read_file(pub_inputs_path)?;
+    let security_level = options.get_proof_security_level(&proof, true);
+
+    let now = Instant::now();
+    example
+        .verify_with_pub_inputs(proof, &pub_inputs)
+        .map_err(|err| format!("failed to verify proof: {err}"))?;
+    debug!(
+        "Proof verified in {:.1} ms",
+        now.elapsed().as_micros() as f64 / 1000f64
+    );
+    debug!("Proof security: {} bits", security_level);
+    debug!("============================================================");
+    Ok(())
+}
+
+/// Reads a proof from the specified file and prints a human-readable breakdown of it.
+fn inspect(options: &ExampleOptions, proof_path: &Path) -> Result<(), String> {
+    let proof = read_proof(proof_path)?;
+    println!("{}", options.hash_function().describe_proof(&proof));
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the path of the file with public inputs for the proof at `proof_path`; unless
+/// specified explicitly, public inputs are stored next to the proof with `.inputs` suffix.
+fn get_pub_inputs_path(proof_path: &Path, pub_inputs_path: Option<&Path>) -> PathBuf {
+    match pub_inputs_path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let mut path = proof_path.as_os_str().to_owned();
+            path.push(".inputs");
+            path.into()
+        }
+    }
+}
+
+fn read_proof(path: &Path) -> Result<StarkProof, String> {
+    let bytes = read_file(path)?;
+    StarkProof::from_bytes(&bytes)
+        .map_err(|err| format!("failed to read proof from {}: {err}", path.display()))
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    fs::write(path, bytes).map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
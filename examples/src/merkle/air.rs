@@ -4,22 +4,33 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH};
-use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
+use crate::utils::{are_equal, is_binary, is_zero, not, read_element_array, EvaluationResult};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
     TransitionConstraintDegree,
 };
 
 // MERKLE PATH VERIFICATION AIR
 // ================================================================================================
 
+#[derive(ToElements)]
+#[to_elements(crate = "winterfell::math")]
 pub struct PublicInputs {
     pub tree_root: [BaseElement; 2],
 }
 
-impl ToElements<BaseElement> for PublicInputs {
-    fn to_elements(&self) -> Vec<BaseElement> {
-        self.tree_root.to_vec()
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.tree_root[..]);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(PublicInputs {
+            tree_root: read_element_array(source)?,
+        })
     }
 }
 
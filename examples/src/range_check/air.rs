@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions, TABLE_SIZE, TRACE_WIDTH};
+use crate::utils::are_equal;
+use winterfell::{
+    Air, AirContext, Assertion, EvaluationFrame, LookupTable, TraceInfo, TransitionConstraintDegree,
+};
+
+// RANGE CHECK AIR
+// ================================================================================================
+
+/// Proves that a sum of values was computed over values in [0, 256).
+///
+/// The range check is performed via a lookup table: the first column of the trace starts at 0,
+/// increases by at most 1 at every step, and ends at 255; the second column contains the number of
+/// times each of the table values is queried, and the third column contains the queried values. The running sum of the
+/// queried values is accumulated in the fourth column.
+pub struct RangeCheckAir {
+    context: AirContext<BaseElement>,
+    sum: BaseElement,
+}
+
+impl Air for RangeCheckAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(1),
+        ];
+        assert_eq!(TRACE_WIDTH, trace_info.layout().main_trace_width());
+
+        // values in column 2 must be present in column 0 with multiplicities in column 1
+        let range_table = LookupTable::new(vec![0], 1).with_query(vec![2]);
+        let context = AirContext::new_with_lookups(
+            trace_info,
+            degrees,
+            Vec::new(),
+            4,
+            0,
+            vec![range_table],
+            options,
+        );
+
+        RangeCheckAir {
+            context,
+            sum: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 4 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // table values either stay the same or are incremented by one at every step
+        let delta = next[0] - current[0];
+        result[0] = delta * (delta - E::ONE);
+
+        // the accumulator is incremented by the queried value at every step
+        result[1] = are_equal(next[3], current[3] + current[2]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the table covers all values in [0, 256), and the accumulator computes the sum of all
+        // queried values
+        let last_step = self.trace_length() - 1;
+        let max_value = Self::BaseField::from((TABLE_SIZE - 1) as u32);
+        vec![
+            Assertion::single(0, 0, Self::BaseField::ZERO),
+            Assertion::single(0, last_step, max_value),
+            Assertion::single(3, 0, Self::BaseField::ZERO),
+            Assertion::single(3, last_step, self.sum),
+        ]
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    verify_with_serialized_inputs, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction,
+    Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use rand_utils::rand_vector;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+use air::RangeCheckAir;
+
+mod prover;
+use prover::RangeCheckProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Columns of the trace are: table values, multiplicities of table values, queried values, and
+/// the running sum of queried values.
+const TRACE_WIDTH: usize = 4;
+
+/// Number of values in the range-check table; all queried values must be in [0, 256).
+const TABLE_SIZE: usize = 256;
+
+// RANGE CHECK EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_values: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(RangeCheckExample::<Blake3_192>::new(
+            num_values, options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(RangeCheckExample::<Blake3_256>::new(
+            num_values, options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(RangeCheckExample::<Sha3_256>::new(
+            num_values, options,
+        ))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct RangeCheckExample<H: ElementHasher> {
+    options: ProofOptions,
+    values: Vec<u8>,
+    sum: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RangeCheckExample<H> {
+    pub fn new(num_values: usize, options: ProofOptions) -> Self {
+        assert!(
+            (num_values + 1).is_power_of_two(),
+            "number of values must be one less than a power of 2"
+        );
+        assert!(
+            num_values >= 2 * TABLE_SIZE - 1,
+            "number of values must be at least {}",
+            2 * TABLE_SIZE - 1
+        );
+
+        // generate random bytes and compute their sum
+        let now = Instant::now();
+        let values = rand_vector::<u8>(num_values);
+        let sum = values
+            .iter()
+            .fold(BaseElement::ZERO, |acc, &v| acc + BaseElement::from(v));
+        debug!(
+            "Computed a sum of {} random bytes in {} ms",
+            num_values,
+            now.elapsed().as_millis()
+        );
+
+        RangeCheckExample {
+            options,
+            values,
+            sum,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for RangeCheckExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for computing a sum of {} range-checked bytes\n\
+            ---------------------",
+            self.values.len()
+        );
+
+        // create a prover
+        let prover = RangeCheckProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.values);
+
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<RangeCheckAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.sum,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<RangeCheckAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.sum + BaseElement::ONE,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.sum.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<RangeCheckAir, H>(proof, pub_inputs)
+    }
+}
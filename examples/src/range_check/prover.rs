@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, MerkleTree, PhantomData,
+    ProofOptions, Prover, RangeCheckAir, Trace, TraceTable, TABLE_SIZE, TRACE_WIDTH,
+};
+
+// RANGE CHECK PROVER
+// ================================================================================================
+
+pub struct RangeCheckProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RangeCheckProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for computing a sum of the specified values such that every
+    /// value is looked up in a table of values in [0, 256).
+    pub fn build_trace(&self, values: &[u8]) -> TraceTable<BaseElement> {
+        let trace_length = values.len() + 1;
+        assert!(
+            trace_length.is_power_of_two(),
+            "number of values must be one less than a power of 2"
+        );
+        assert!(
+            trace_length >= 2 * TABLE_SIZE,
+            "number of values must be at least {}",
+            2 * TABLE_SIZE - 1
+        );
+
+        // count how many times each table value is looked up
+        let mut counts = [0u64; TABLE_SIZE];
+        for &value in values.iter() {
+            counts[value as usize] += 1;
+        }
+
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length).with_lookup_tables(1);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ZERO;
+                state[1] = BaseElement::new(counts[0] as u128);
+                state[2] = BaseElement::from(values[0]);
+                state[3] = BaseElement::ZERO;
+            },
+            |step, state| {
+                let i = step + 1;
+                state[3] += state[2];
+                state[0] = BaseElement::new(i.min(TABLE_SIZE - 1) as u128);
+                state[1] = match counts.get(i) {
+                    Some(&count) => BaseElement::new(count as u128),
+                    None => BaseElement::ZERO,
+                };
+                state[2] = match values.get(i) {
+                    Some(&value) => BaseElement::from(value),
+                    None => BaseElement::ZERO,
+                };
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for RangeCheckProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = RangeCheckAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - 1;
+        trace.get(3, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, Blake3_256, RangeCheckAir, RangeCheckExample, RangeCheckProver};
+use winterfell::{
+    math::{fields::QuadExtension, FieldElement, StarkField},
+    Air, AuxTraceRandElements, FieldExtension, ProofOptions, Trace,
+};
+
+#[test]
+fn range_check_test_basic_proof_verification() {
+    let range_check = Box::new(RangeCheckExample::<Blake3_256>::new(
+        511,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(range_check);
+}
+
+#[test]
+fn range_check_test_basic_proof_verification_extension() {
+    let range_check = Box::new(RangeCheckExample::<Blake3_256>::new(
+        511,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(range_check);
+}
+
+#[test]
+fn range_check_test_basic_proof_verification_fail() {
+    let range_check = Box::new(RangeCheckExample::<Blake3_256>::new(
+        511,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(range_check);
+}
+
+#[test]
+fn range_check_test_proof_verification_with_pub_inputs() {
+    let range_check = Box::new(RangeCheckExample::<Blake3_256>::new(
+        511,
+        build_options(false),
+    ));
+    crate::tests::test_proof_verification_with_pub_inputs(range_check);
+}
+
+#[test]
+#[should_panic(expected = "trace does not satisfy assertion aux_trace(0, 511)")]
+fn range_check_test_value_out_of_range() {
+    type E = QuadExtension<BaseElement>;
+
+    let example = RangeCheckExample::<Blake3_256>::new(511, build_options(true));
+    let prover = RangeCheckProver::<Blake3_256>::new(example.options.clone());
+    let mut trace = prover.build_trace(&example.values);
+
+    // replace the value queried at step 5 with a value outside of the table; the accumulator
+    // and the multiplicities are updated so that only the lookup argument is violated
+    let value = trace.get(2, 5);
+    let invalid_value = BaseElement::new(300);
+    trace.set(2, 5, invalid_value);
+    for step in 6..trace.length() {
+        let sum = trace.get(3, step);
+        trace.set(3, step, sum - value + invalid_value);
+    }
+    let count_step = value.as_int() as usize;
+    let count = trace.get(1, count_step);
+    trace.set(1, count_step, count - BaseElement::ONE);
+
+    let sum = trace.get(3, trace.length() - 1);
+    let air = RangeCheckAir::new(trace.get_info(), sum, example.options.clone());
+
+    let num_rand_elements = air.trace_layout().get_aux_segment_rand_elements(0);
+    let rand_elements = (0..num_rand_elements as u128)
+        .map(|i| E::new(BaseElement::new(i + 3), BaseElement::new(i + 5)))
+        .collect::<Vec<_>>();
+    let aux_segment = trace
+        .build_aux_segment_with_lookups(&air, &[], &rand_elements)
+        .unwrap();
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    aux_rand_elements.add_segment_elements(rand_elements);
+
+    trace.validate(&air, &[aux_segment], &aux_rand_elements);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
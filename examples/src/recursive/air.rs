@@ -0,0 +1,363 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_leaf_init_state, get_num_segments, get_opening_index, get_segment_length, BaseElement,
+    Digest, FieldElement, InnerProof, ProofOptions, Rp64_256, DIGEST_SIZE, HASH_CYCLE_LEN,
+    HASH_STATE, HASH_STATE_WIDTH, INDEX_BIT, INNER_TRACE_WIDTH, NUM_HASH_ROUNDS, TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, is_zero, not, read_element_arrays, EvaluationResult};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The S-Box of Rescue Prime permutation raises elements of the state to this power.
+const ALPHA: u32 = 7;
+
+// Periodic columns: a flag set during the rounds of the permutation, a flag set when the hash
+// state is initialized for merging two nodes, and round constants of the permutation.
+const HASH_FLAG: usize = 0;
+const MERGE_FLAG: usize = 1;
+const ARK: usize = 2;
+
+/// Index of the rate element of the hash state at which the right child of a node begins.
+const RIGHT_CHILD: usize = Rp64_256::RATE_RANGE.start + DIGEST_SIZE;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputs {
+    pub trace_root: Digest,
+    pub lde_domain_size: usize,
+    pub positions: Vec<usize>,
+    pub rows: Vec<[BaseElement; INNER_TRACE_WIDTH]>,
+}
+
+impl PublicInputs {
+    /// Returns public inputs for verifying trace openings of the specified inner proof.
+    pub fn new(inner_proof: &InnerProof) -> Self {
+        PublicInputs {
+            trace_root: inner_proof.trace_root,
+            lde_domain_size: inner_proof.lde_domain_size,
+            positions: inner_proof.positions.clone(),
+            rows: inner_proof.rows.clone(),
+        }
+    }
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.trace_root.to_vec();
+        result.push(BaseElement::new(self.lde_domain_size as u64));
+        for (&position, row) in self.positions.iter().zip(self.rows.iter()) {
+            result.push(BaseElement::new(position as u64));
+            result.extend_from_slice(row);
+        }
+        result
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.trace_root[..]);
+        target.write_u64(self.lde_domain_size as u64);
+        target.write_u32(self.positions.len() as u32);
+        for &position in self.positions.iter() {
+            target.write_u64(position as u64);
+        }
+        for row in self.rows.iter() {
+            target.write(&row[..]);
+        }
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace_root = BaseElement::read_batch_from(source, DIGEST_SIZE)?
+            .try_into()
+            .unwrap_or_else(|_| unreachable!());
+        let lde_domain_size = source.read_u64()? as usize;
+        if !lde_domain_size.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "LDE domain size must be a power of two, but was {lde_domain_size}"
+            )));
+        }
+        let num_positions = source.read_u32()? as usize;
+        if num_positions == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "number of positions must be greater than zero".to_string(),
+            ));
+        }
+        let mut positions = Vec::with_capacity(num_positions);
+        for _ in 0..num_positions {
+            let position = source.read_u64()? as usize;
+            if position >= lde_domain_size {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "position {position} is outside of the LDE domain"
+                )));
+            }
+            positions.push(position);
+        }
+        let rows = read_element_arrays(source, num_positions)?;
+        Ok(PublicInputs {
+            trace_root,
+            lde_domain_size,
+            positions,
+            rows,
+        })
+    }
+}
+
+// RECURSIVE VERIFICATION AIR
+// ================================================================================================
+
+pub struct RecursiveAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: PublicInputs,
+    depth: usize,
+    segment_length: usize,
+}
+
+impl Air for RecursiveAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let depth = pub_inputs.lde_domain_size.ilog2() as usize;
+        let segment_length = get_segment_length(pub_inputs.lde_domain_size);
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert_eq!(
+            get_num_segments(pub_inputs.positions.len()) * segment_length,
+            trace_info.length()
+        );
+
+        let mut degrees = Vec::new();
+        for _ in 0..HASH_STATE_WIDTH {
+            degrees.push(TransitionConstraintDegree::with_cycles(
+                ALPHA as usize,
+                vec![HASH_CYCLE_LEN],
+            ));
+        }
+        for _ in 0..DIGEST_SIZE {
+            degrees.push(TransitionConstraintDegree::with_cycles(
+                1,
+                vec![segment_length],
+            ));
+        }
+        for _ in 0..DIGEST_SIZE {
+            degrees.push(TransitionConstraintDegree::with_cycles(
+                2,
+                vec![segment_length],
+            ));
+        }
+        degrees.push(TransitionConstraintDegree::new(2));
+
+        let num_assertions = HASH_STATE_WIDTH + DIGEST_SIZE + depth;
+        RecursiveAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            pub_inputs,
+            depth,
+            segment_length,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        let hash_flag = periodic_values[HASH_FLAG];
+        let merge_flag = periodic_values[MERGE_FLAG];
+        let ark = &periodic_values[ARK..];
+        let state = &current[HASH_STATE..HASH_STATE + HASH_STATE_WIDTH];
+        let next_state = &next[HASH_STATE..HASH_STATE + HASH_STATE_WIDTH];
+
+        // when hash_flag = 1, constraints for a round of Rescue Prime permutation are enforced
+        enforce_round(result, state, next_state, ark, hash_flag);
+
+        // when merge_flag = 1, the hash state must be initialized for merging the node computed
+        // by the previous invocation of the hash function with its sibling: the capacity holds
+        // the number of merged elements, and the node goes into the left or the right half of
+        // the rate depending on the index bit; the sibling is provided by the prover
+        let result = &mut result[HASH_STATE_WIDTH..];
+        let capacity = Rp64_256::CAPACITY_RANGE.start;
+        result.agg_constraint(
+            0,
+            merge_flag,
+            are_equal(next_state[capacity], E::from(2 * DIGEST_SIZE as u32)),
+        );
+        for i in 1..DIGEST_SIZE {
+            result.agg_constraint(i, merge_flag, is_zero(next_state[capacity + i]));
+        }
+
+        let bit = next[INDEX_BIT];
+        let not_bit = not(bit);
+        for i in 0..DIGEST_SIZE {
+            let node = state[Rp64_256::DIGEST_RANGE.start + i];
+            let left = next_state[Rp64_256::RATE_RANGE.start + i];
+            let right = next_state[RIGHT_CHILD + i];
+            result.agg_constraint(
+                DIGEST_SIZE + i,
+                merge_flag,
+                not_bit * are_equal(left, node) + bit * are_equal(right, node),
+            );
+        }
+
+        // the index bit must always be binary
+        result[2 * DIGEST_SIZE] = is_binary(current[INDEX_BIT]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let num_positions = self.pub_inputs.positions.len();
+        let openings = (0..get_num_segments(num_positions))
+            .map(|segment| get_opening_index(segment, num_positions))
+            .collect::<Vec<_>>();
+        let mut result = Vec::new();
+
+        // at the start of every segment, the hash state must be initialized for hashing the
+        // opened row into a leaf
+        let init_states = openings
+            .iter()
+            .map(|&i| get_leaf_init_state(&self.pub_inputs.rows[i]))
+            .collect::<Vec<_>>();
+        for column in 0..HASH_STATE_WIDTH {
+            let values = init_states.iter().map(|state| state[column]).collect();
+            result.push(Assertion::sequence(
+                HASH_STATE + column,
+                0,
+                self.segment_length,
+                values,
+            ));
+        }
+
+        // the index bits must be equal to the bits of the opened position
+        for depth in 0..self.depth {
+            let values = openings
+                .iter()
+                .map(|&i| BaseElement::from(((self.pub_inputs.positions[i] >> depth) & 1) as u8))
+                .collect();
+            result.push(Assertion::sequence(
+                INDEX_BIT,
+                (depth + 1) * HASH_CYCLE_LEN,
+                self.segment_length,
+                values,
+            ));
+        }
+
+        // after the last node of the authentication path has been merged in, the hash state must
+        // contain the root of the trace commitment
+        let root_step = (self.depth + 1) * HASH_CYCLE_LEN - 1;
+        for (i, &value) in self.pub_inputs.trace_root.iter().enumerate() {
+            result.push(Assertion::periodic(
+                HASH_STATE + Rp64_256::DIGEST_RANGE.start + i,
+                root_step,
+                self.segment_length,
+                value,
+            ));
+        }
+
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut hash_flags = vec![BaseElement::ONE; HASH_CYCLE_LEN];
+        hash_flags[NUM_HASH_ROUNDS] = BaseElement::ZERO;
+
+        let mut merge_flags = vec![BaseElement::ZERO; self.segment_length];
+        for flag in merge_flags[..self.segment_length - 1]
+            .iter_mut()
+            .skip(HASH_CYCLE_LEN - 1)
+            .step_by(HASH_CYCLE_LEN)
+        {
+            *flag = BaseElement::ONE;
+        }
+
+        let mut result = vec![hash_flags, merge_flags];
+        result.append(&mut get_round_constants());
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Enforces constraints for a single round of Rescue Prime permutation when `flag` is set.
+///
+/// Since the second half of the round applies the inverse S-Box, the constraints are expressed
+/// by computing the first half of the round from the current state and the inverse of the
+/// second half from the next state, and requiring the results to be equal.
+fn enforce_round<E: FieldElement + From<BaseElement>>(
+    result: &mut [E],
+    current: &[E],
+    next: &[E],
+    ark: &[E],
+    flag: E,
+) {
+    let mut step1 = [E::ZERO; HASH_STATE_WIDTH];
+    for (i, value) in step1.iter_mut().enumerate() {
+        *value = current[i].exp(ALPHA.into());
+    }
+    let mut step1 = apply_matrix(&Rp64_256::MDS, &step1);
+    for (value, &k) in step1.iter_mut().zip(ark) {
+        *value += k;
+    }
+
+    let mut step2 = [E::ZERO; HASH_STATE_WIDTH];
+    for (i, value) in step2.iter_mut().enumerate() {
+        *value = next[i] - ark[HASH_STATE_WIDTH + i];
+    }
+    let mut step2 = apply_matrix(&Rp64_256::INV_MDS, &step2);
+    for value in step2.iter_mut() {
+        *value = value.exp(ALPHA.into());
+    }
+
+    for i in 0..HASH_STATE_WIDTH {
+        result.agg_constraint(i, flag, are_equal(step2[i], step1[i]));
+    }
+}
+
+/// Multiplies the hash state by the specified matrix.
+fn apply_matrix<E: FieldElement + From<BaseElement>>(
+    matrix: &[[BaseElement; HASH_STATE_WIDTH]; HASH_STATE_WIDTH],
+    state: &[E; HASH_STATE_WIDTH],
+) -> [E; HASH_STATE_WIDTH] {
+    let mut result = [E::ZERO; HASH_STATE_WIDTH];
+    for (value, row) in result.iter_mut().zip(matrix) {
+        for (&coefficient, &element) in row.iter().zip(state) {
+            *value += E::from(coefficient) * element;
+        }
+    }
+    result
+}
+
+/// Returns round constants of Rescue Prime permutation arranged in column-major form; the
+/// constants of the first half of the round are followed by the constants of the second half.
+fn get_round_constants() -> Vec<Vec<BaseElement>> {
+    let mut constants = vec![vec![BaseElement::ZERO; HASH_CYCLE_LEN]; 2 * HASH_STATE_WIDTH];
+    for (round, (ark1, ark2)) in Rp64_256::ARK1.iter().zip(Rp64_256::ARK2.iter()).enumerate() {
+        for i in 0..HASH_STATE_WIDTH {
+            constants[i][round] = ark1[i];
+            constants[HASH_STATE_WIDTH + i][round] = ark2[i];
+        }
+    }
+    constants
+}
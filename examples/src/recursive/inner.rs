@@ -0,0 +1,219 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Inner proof verified by the recursive verification example.
+//!
+//! The inner proof attests to a computation of a Fibonacci sequence (see
+//! [FibSmall](crate::fibonacci::fib_small::FibSmall)), and commits to its execution trace using
+//! Rp64_256 hash function so that the trace openings can be verified efficiently inside the outer
+//! AIR. Query positions and trace openings are obtained from the EVM encoding of the proof (see
+//! [encode_for_evm()](winterfell::encode_for_evm)) since this encoding exposes positions drawn by
+//! the verifier together with uncompressed authentication paths.
+
+use super::{BaseElement, Digest, Rp64_256};
+use crate::fibonacci::fib_small::{FibSmall, FibSmallProver};
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, Hasher},
+    math::FieldElement,
+    FieldExtension, ProofOptions, Prover, StarkProof, VerifierError,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Width of the execution trace of the inner computation.
+pub const INNER_TRACE_WIDTH: usize = 2;
+
+/// Number of field elements in a digest produced by Rp64_256 hash function.
+pub const DIGEST_SIZE: usize = 4;
+
+/// Size of a word in the EVM encoding of a proof, in bytes.
+const WORD_SIZE: usize = 32;
+
+/// Number of words in the header of an EVM-encoded proof.
+const NUM_HEADER_WORDS: usize = 14;
+
+// Indexes of the header words and sections of an EVM-encoded proof used by this example.
+const LDE_DOMAIN_SIZE_WORD: usize = 2;
+const EXTENSION_DEGREE_WORD: usize = 6;
+const MAIN_TRACE_WIDTH_WORD: usize = 10;
+const AUX_TRACE_WIDTH_WORD: usize = 11;
+const CONSTRAINT_COLUMNS_WORD: usize = 12;
+const TRACE_COMMITMENTS_SECTION: usize = 0;
+const QUERY_POSITIONS_SECTION: usize = 5;
+const TRACE_QUERIES_SECTION: usize = 6;
+
+// INNER PROOF
+// ================================================================================================
+
+/// Trace openings of a verified inner proof.
+pub struct InnerProof {
+    /// Root of the commitment to the main segment of the inner execution trace.
+    pub trace_root: Digest,
+    /// Size of the low-degree extension domain of the inner execution trace.
+    pub lde_domain_size: usize,
+    /// Query positions drawn by the verifier of the inner proof.
+    pub positions: Vec<usize>,
+    /// Rows of the extended inner execution trace at each of the query positions.
+    pub rows: Vec<[BaseElement; INNER_TRACE_WIDTH]>,
+    /// Authentication paths of the rows against the trace commitment, ordered from the leaf level
+    /// to the root; the leaves themselves are not included.
+    pub paths: Vec<Vec<Digest>>,
+}
+
+impl InnerProof {
+    /// Verifies the specified inner proof against the specified result of the Fibonacci
+    /// computation, and returns the trace openings contained in the proof.
+    ///
+    /// # Errors
+    /// Returns an error if the proof is not valid.
+    pub fn open(proof: StarkProof, result: BaseElement) -> Result<Self, VerifierError> {
+        let encoding = winterfell::encode_for_evm::<FibSmall, Rp64_256, DefaultRandomCoin<Rp64_256>>(
+            proof, result,
+        )?;
+        let reader = EvmReader(&encoding);
+
+        let lde_domain_size = reader.read_uint(LDE_DOMAIN_SIZE_WORD) as usize;
+        let depth = lde_domain_size.ilog2() as usize;
+        assert_eq!(
+            INNER_TRACE_WIDTH,
+            reader.read_uint(MAIN_TRACE_WIDTH_WORD) as usize
+        );
+        assert_eq!(0, reader.read_uint(AUX_TRACE_WIDTH_WORD));
+        let num_constraint_values = reader.read_uint(CONSTRAINT_COLUMNS_WORD) as usize
+            * reader.read_uint(EXTENSION_DEGREE_WORD) as usize;
+
+        let trace_root = reader.read_digest(reader.get_section(TRACE_COMMITMENTS_SECTION));
+
+        let (offset, num_positions) = reader.get_section_with_length(QUERY_POSITIONS_SECTION);
+        let positions = (0..num_positions)
+            .map(|i| reader.read_uint(offset + i) as usize)
+            .collect::<Vec<_>>();
+
+        // every query contains values of the main trace segment, constraint composition values,
+        // and authentication paths for the trace and constraint commitments
+        let mut offset = reader.get_section(TRACE_QUERIES_SECTION);
+        let mut rows = Vec::with_capacity(num_positions);
+        let mut paths = Vec::with_capacity(num_positions);
+        for _ in 0..num_positions {
+            let mut row = [BaseElement::ZERO; INNER_TRACE_WIDTH];
+            for (i, value) in row.iter_mut().enumerate() {
+                *value = reader.read_element(offset + i);
+            }
+            offset += INNER_TRACE_WIDTH + num_constraint_values;
+            let path = (0..depth)
+                .map(|i| reader.read_digest(offset + i))
+                .collect::<Vec<_>>();
+            offset += 2 * depth;
+            rows.push(row);
+            paths.push(path);
+        }
+
+        let inner_proof = InnerProof {
+            trace_root,
+            lde_domain_size,
+            positions,
+            rows,
+            paths,
+        };
+        debug_assert!(inner_proof.validate_openings());
+        Ok(inner_proof)
+    }
+
+    /// Returns true if all trace openings resolve to the trace commitment root.
+    pub fn validate_openings(&self) -> bool {
+        self.positions
+            .iter()
+            .zip(self.rows.iter().zip(self.paths.iter()))
+            .all(|(&position, (row, path))| {
+                compute_merkle_root(row, position, path) == self.trace_root
+            })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns options used to generate inner proofs.
+pub fn get_inner_options() -> ProofOptions {
+    ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31)
+}
+
+/// Generates an inner proof of computing a Fibonacci sequence of the specified length, and
+/// returns it together with the last term of the sequence.
+pub fn prove(sequence_length: usize) -> (StarkProof, BaseElement) {
+    let prover = FibSmallProver::<Rp64_256>::new(get_inner_options());
+    let trace = prover.build_trace(sequence_length);
+    let result = prover.get_pub_inputs(&trace);
+    (prover.prove(trace).unwrap(), result)
+}
+
+/// Returns the root of the Merkle tree computed from the `row` of the inner execution trace
+/// located at the specified position and its authentication path.
+pub fn compute_merkle_root(
+    row: &[BaseElement; INNER_TRACE_WIDTH],
+    position: usize,
+    path: &[Digest],
+) -> Digest {
+    let leaf = Rp64_256::hash_elements(row);
+    let root = path
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (depth, &sibling)| {
+            let sibling = sibling.into();
+            match (position >> depth) & 1 {
+                0 => Rp64_256::merge(&[node, sibling]),
+                _ => Rp64_256::merge(&[sibling, node]),
+            }
+        });
+    root.as_elements().try_into().unwrap()
+}
+
+// EVM ENCODING READER
+// ================================================================================================
+
+/// Reads values from an EVM-encoded proof; all offsets are specified in words.
+struct EvmReader<'a>(&'a [u8]);
+
+impl EvmReader<'_> {
+    /// Returns the word at the specified offset.
+    fn read_word(&self, offset: usize) -> &[u8] {
+        &self.0[offset * WORD_SIZE..(offset + 1) * WORD_SIZE]
+    }
+
+    /// Reads an integer encoded as a big-endian word.
+    fn read_uint(&self, offset: usize) -> u64 {
+        let word = self.read_word(offset);
+        u64::from_be_bytes(word[WORD_SIZE - 8..].try_into().unwrap())
+    }
+
+    /// Reads a base field element encoded as its canonical integer value.
+    fn read_element(&self, offset: usize) -> BaseElement {
+        BaseElement::new(self.read_uint(offset))
+    }
+
+    /// Reads a digest encoded as is; the elements of the digest are serialized in little-endian
+    /// byte order.
+    fn read_digest(&self, offset: usize) -> Digest {
+        let word = self.read_word(offset);
+        let mut digest = [BaseElement::ZERO; DIGEST_SIZE];
+        for (element, bytes) in digest.iter_mut().zip(word.chunks(8)) {
+            *element = BaseElement::new(u64::from_le_bytes(bytes.try_into().unwrap()));
+        }
+        digest
+    }
+
+    /// Returns the offset of the first word of data in the specified section.
+    fn get_section(&self, section: usize) -> usize {
+        self.get_section_with_length(section).0
+    }
+
+    /// Returns the offset of the first word of data in the specified section together with the
+    /// number of words in the section.
+    fn get_section_with_length(&self, section: usize) -> (usize, usize) {
+        let offset = self.read_uint(NUM_HEADER_WORDS + section) as usize / WORD_SIZE;
+        (offset + 1, self.read_uint(offset) as usize)
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{verify_with_serialized_inputs, Example, ExampleOptions, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f64::BaseElement, FieldElement},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+pub use air::{PublicInputs, RecursiveAir};
+
+pub mod inner;
+use inner::{InnerProof, DIGEST_SIZE, INNER_TRACE_WIDTH};
+
+mod prover;
+pub use prover::RecursiveProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+/// Number of steps needed to compute a single invocation of Rescue Prime permutation: one step
+/// per round, followed by a step during which the hash state is initialized for the next
+/// invocation.
+const HASH_CYCLE_LEN: usize = 8;
+
+/// Number of rounds of Rescue Prime permutation used by Rp64_256 hash function.
+const NUM_HASH_ROUNDS: usize = Rp64_256::NUM_ROUNDS;
+
+/// Width of the state of Rp64_256 hash function.
+const HASH_STATE_WIDTH: usize = Rp64_256::STATE_WIDTH;
+
+// The trace consists of the following columns:
+// * hash state of Rp64_256 hash function,
+// * a bit specifying whether the node computed by the previous invocation of the hash function
+//   is the right (rather than the left) child of the node computed by the current invocation.
+const HASH_STATE: usize = 0;
+const INDEX_BIT: usize = HASH_STATE + HASH_STATE_WIDTH;
+const TRACE_WIDTH: usize = INDEX_BIT + 1;
+
+/// A digest produced by Rp64_256 hash function, represented by its field elements.
+pub type Digest = [BaseElement; DIGEST_SIZE];
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
+type GriffinJive64_256 = winterfell::crypto::hashers::GriffinJive64_256;
+
+// RECURSIVE VERIFICATION EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(RecursiveExample::<Blake3_192>::new(
+            sequence_length,
+            options,
+        )?)),
+        HashFunction::Blake3_256 => Ok(Box::new(RecursiveExample::<Blake3_256>::new(
+            sequence_length,
+            options,
+        )?)),
+        HashFunction::Sha3_256 => Ok(Box::new(RecursiveExample::<Sha3_256>::new(
+            sequence_length,
+            options,
+        )?)),
+        HashFunction::Rp64_256 => Ok(Box::new(RecursiveExample::<Rp64_256>::new(
+            sequence_length,
+            options,
+        )?)),
+        HashFunction::RpJive64_256 => Ok(Box::new(RecursiveExample::<RpJive64_256>::new(
+            sequence_length,
+            options,
+        )?)),
+        HashFunction::GriffinJive64_256 => Ok(Box::new(
+            RecursiveExample::<GriffinJive64_256>::new(sequence_length, options)?,
+        )),
+    }
+}
+
+pub struct RecursiveExample<H: ElementHasher> {
+    options: ProofOptions,
+    inner_proof: InnerProof,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RecursiveExample<H> {
+    /// Generates an inner proof of computing a Fibonacci sequence of the specified length, and
+    /// verifies it natively to obtain the trace openings which are then verified by the outer
+    /// proof.
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Result<Self, String> {
+        if !sequence_length.is_power_of_two() || sequence_length < 16 {
+            return Err("sequence length must be a power of 2 and at least 16".to_string());
+        }
+
+        // generate the inner proof
+        let now = Instant::now();
+        let (proof, result) = inner::prove(sequence_length);
+        debug!(
+            "Generated inner proof of computing a Fibonacci sequence of {} terms in {} ms",
+            sequence_length,
+            now.elapsed().as_millis(),
+        );
+
+        // verify the inner proof and extract the trace openings from it
+        let now = Instant::now();
+        let inner_proof = InnerProof::open(proof, result).map_err(|err| err.to_string())?;
+        debug!(
+            "Verified inner proof and opened trace at {} positions in {} ms",
+            inner_proof.positions.len(),
+            now.elapsed().as_millis(),
+        );
+
+        Ok(RecursiveExample {
+            options,
+            inner_proof,
+            _hasher: PhantomData,
+        })
+    }
+
+    fn get_pub_inputs(&self) -> PublicInputs {
+        PublicInputs::new(&self.inner_proof)
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for RecursiveExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for verifying {} trace openings of an inner proof\n\
+            ---------------------",
+            self.inner_proof.positions.len()
+        );
+
+        // create a prover
+        let prover = RecursiveProver::<H>::new(&self.inner_proof, self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.inner_proof);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<RecursiveAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.get_pub_inputs(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.get_pub_inputs();
+        pub_inputs.rows[0][0] += BaseElement::ONE;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<RecursiveAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.get_pub_inputs().to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<RecursiveAir, H>(proof, pub_inputs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of steps needed to verify a single trace opening: the opened row is hashed
+/// into a leaf, and the leaf is merged with every node of the authentication path; the number of
+/// invocations of the hash function is padded to the next power of two.
+fn get_segment_length(lde_domain_size: usize) -> usize {
+    let num_hashes = lde_domain_size.ilog2() as usize + 1;
+    num_hashes.next_power_of_two() * HASH_CYCLE_LEN
+}
+
+/// Returns the number of openings verified in the outer execution trace; openings are repeated
+/// so that their number is a power of two.
+fn get_num_segments(num_positions: usize) -> usize {
+    num_positions.next_power_of_two()
+}
+
+/// Returns the index of the opening verified in the specified segment of the execution trace.
+fn get_opening_index(segment: usize, num_positions: usize) -> usize {
+    segment.min(num_positions - 1)
+}
+
+/// Returns the initial hash state for hashing the specified row of the inner execution trace
+/// into a leaf of the trace commitment.
+fn get_leaf_init_state(row: &[BaseElement; INNER_TRACE_WIDTH]) -> [BaseElement; HASH_STATE_WIDTH] {
+    let mut state = [BaseElement::ZERO; HASH_STATE_WIDTH];
+    state[Rp64_256::CAPACITY_RANGE.start] = BaseElement::new(INNER_TRACE_WIDTH as u64);
+    state[Rp64_256::RATE_RANGE][..INNER_TRACE_WIDTH].copy_from_slice(row);
+    state
+}
@@ -0,0 +1,124 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    get_leaf_init_state, get_num_segments, get_opening_index, get_segment_length, BaseElement,
+    DefaultRandomCoin, ElementHasher, FieldElement, InnerProof, MerkleTree, PhantomData,
+    ProofOptions, Prover, PublicInputs, RecursiveAir, Rp64_256, TraceTable, DIGEST_SIZE,
+    HASH_CYCLE_LEN, HASH_STATE, HASH_STATE_WIDTH, INDEX_BIT, NUM_HASH_ROUNDS, TRACE_WIDTH,
+};
+
+// RECURSIVE VERIFICATION PROVER
+// ================================================================================================
+
+pub struct RecursiveProver<H: ElementHasher> {
+    options: ProofOptions,
+    pub_inputs: PublicInputs,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RecursiveProver<H> {
+    pub fn new(inner_proof: &InnerProof, options: ProofOptions) -> Self {
+        Self {
+            options,
+            pub_inputs: PublicInputs::new(inner_proof),
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for verifying all trace openings of the specified inner proof
+    /// against its trace commitment.
+    ///
+    /// The trace consists of one segment per opening; within a segment, the opened row is hashed
+    /// into a leaf, and then the leaf is merged with the nodes of its authentication path, one
+    /// invocation of Rp64_256 hash function per 8 steps. Once the root is computed, the hash
+    /// function is applied to it until the end of the segment.
+    pub fn build_trace(&self, inner_proof: &InnerProof) -> TraceTable<BaseElement> {
+        let num_positions = inner_proof.positions.len();
+        let segment_length = get_segment_length(inner_proof.lde_domain_size);
+        let num_hashes = segment_length / HASH_CYCLE_LEN;
+        let trace_length = get_num_segments(num_positions) * segment_length;
+
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+        let mut step = 0;
+        let mut row = [BaseElement::ZERO; TRACE_WIDTH];
+        for segment in 0..get_num_segments(num_positions) {
+            let opening = get_opening_index(segment, num_positions);
+            let position = inner_proof.positions[opening];
+            let path = &inner_proof.paths[opening];
+
+            let mut state = get_leaf_init_state(&inner_proof.rows[opening]);
+            let mut bit = BaseElement::ZERO;
+            for hash in 0..num_hashes {
+                if hash > 0 {
+                    // initialize the hash state for merging the node with its sibling; once the
+                    // root is computed, the node is merged with zeros
+                    let (sibling, index_bit) = match path.get(hash - 1) {
+                        Some(sibling) => (*sibling, (position >> (hash - 1)) & 1),
+                        None => ([BaseElement::ZERO; DIGEST_SIZE], 0),
+                    };
+                    state = get_merge_init_state(&state, &sibling, index_bit);
+                    bit = BaseElement::from(index_bit as u8);
+                }
+
+                for round in 0..HASH_CYCLE_LEN {
+                    row[HASH_STATE..HASH_STATE + HASH_STATE_WIDTH].copy_from_slice(&state);
+                    row[INDEX_BIT] = bit;
+                    trace.update_row(step, &row);
+                    if round < NUM_HASH_ROUNDS {
+                        Rp64_256::apply_round(&mut state, round);
+                    }
+                    step += 1;
+                }
+            }
+        }
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for RecursiveProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = RecursiveAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        self.pub_inputs.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the hash state for merging the node held in the digest portion of the specified state
+/// with its sibling; when `index_bit` is 1, the node is the right child of its parent.
+fn get_merge_init_state(
+    state: &[BaseElement; HASH_STATE_WIDTH],
+    sibling: &[BaseElement; DIGEST_SIZE],
+    index_bit: usize,
+) -> [BaseElement; HASH_STATE_WIDTH] {
+    let node = &state[Rp64_256::DIGEST_RANGE];
+    let (left, right) = match index_bit {
+        0 => (node, &sibling[..]),
+        _ => (&sibling[..], node),
+    };
+
+    let mut result = [BaseElement::ZERO; HASH_STATE_WIDTH];
+    result[Rp64_256::CAPACITY_RANGE.start] = BaseElement::new(2 * DIGEST_SIZE as u64);
+    let rate = &mut result[Rp64_256::RATE_RANGE];
+    rate[..DIGEST_SIZE].copy_from_slice(left);
+    rate[DIGEST_SIZE..].copy_from_slice(right);
+    result
+}
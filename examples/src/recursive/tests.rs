@@ -0,0 +1,84 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    inner, Blake3_256, InnerProof, PublicInputs, RecursiveAir, RecursiveExample, RecursiveProver,
+    Rp64_256, DIGEST_SIZE, HASH_CYCLE_LEN, HASH_STATE,
+};
+use winterfell::{
+    debug::ConstraintDebugger, math::FieldElement, Air, FieldExtension, ProofOptions, Trace,
+};
+
+#[test]
+fn recursive_test_basic_proof_verification() {
+    let recursive =
+        Box::new(RecursiveExample::<Blake3_256>::new(64, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification(recursive);
+}
+
+#[test]
+fn recursive_test_basic_proof_verification_extension() {
+    let recursive = Box::new(RecursiveExample::<Blake3_256>::new(64, build_options(true)).unwrap());
+    crate::tests::test_basic_proof_verification(recursive);
+}
+
+#[test]
+fn recursive_test_basic_proof_verification_fail() {
+    let recursive =
+        Box::new(RecursiveExample::<Blake3_256>::new(64, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification_fail(recursive);
+}
+
+#[test]
+fn recursive_test_proof_verification_with_pub_inputs() {
+    let recursive =
+        Box::new(RecursiveExample::<Blake3_256>::new(64, build_options(false)).unwrap());
+    crate::tests::test_proof_verification_with_pub_inputs(recursive);
+}
+
+#[test]
+fn recursive_test_open_inner_proof() {
+    let (proof, result) = inner::prove(64);
+    let inner_proof = InnerProof::open(proof.clone(), result).unwrap();
+    assert_eq!(256, inner_proof.lde_domain_size);
+    assert!(inner_proof.validate_openings());
+
+    // openings cannot be obtained from an inner proof which is not valid
+    assert!(InnerProof::open(proof, result + FieldElement::ONE).is_err());
+}
+
+#[test]
+fn recursive_test_tampered_path() {
+    let (proof, result) = inner::prove(64);
+    let mut inner_proof = InnerProof::open(proof, result).unwrap();
+    let options = build_options(false);
+
+    // replace an element of the first node in the authentication path of the first opening
+    inner_proof.paths[0][0][0] += FieldElement::ONE;
+    assert!(!inner_proof.validate_openings());
+
+    // the constraints are still satisfied by the trace, but the computed root does not match
+    // the trace commitment
+    let prover = RecursiveProver::<Blake3_256>::new(&inner_proof, options.clone());
+    let trace = prover.build_trace(&inner_proof);
+    let air = RecursiveAir::new(trace.get_info(), PublicInputs::new(&inner_proof), options);
+    let debugger = ConstraintDebugger::new(&air, &trace);
+    assert!(debugger.unsatisfied_constraints().is_empty());
+
+    let root_step = (inner_proof.paths[0].len() + 1) * HASH_CYCLE_LEN - 1;
+    let root = (0..DIGEST_SIZE)
+        .map(|i| trace.get(HASH_STATE + Rp64_256::DIGEST_RANGE.start + i, root_step))
+        .collect::<Vec<_>>();
+    assert_ne!(inner_proof.trace_root.to_vec(), root);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
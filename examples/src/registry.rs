@@ -0,0 +1,41 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::fibonacci::{
+    fib2::FibProver, fib8::Fib8Prover, fib_small::FibSmallProver, mulfib2::MulFib2Prover,
+    mulfib8::MulFib8Prover,
+};
+use winterfell::{
+    crypto::hashers::Blake3_256,
+    math::fields::{f128, f64},
+    registry::{AirRegistry, RegistryError},
+};
+
+// EXAMPLE REGISTRY
+// ================================================================================================
+
+/// Identifiers of the examples registered via [register_examples()].
+pub const EXAMPLE_AIR_IDS: [&str; 5] = ["fib", "fib8", "fib-small", "mulfib", "mulfib8"];
+
+/// Registers the Fibonacci examples in the specified `registry`.
+///
+/// The examples are registered under the names of the corresponding example subcommands (see
+/// [EXAMPLE_AIR_IDS]), and all of them use BLAKE3 with 256-bit output as the hash function. For
+/// all examples, public inputs are the serialized last term of the computed sequence.
+///
+/// # Errors
+/// Returns an error if any of the examples has already been registered in the `registry`.
+pub fn register_examples(registry: &mut AirRegistry) -> Result<(), RegistryError> {
+    type Blake3 = Blake3_256<f128::BaseElement>;
+    registry.register("fib", FibProver::<Blake3>::new)?;
+    registry.register("fib8", Fib8Prover::<Blake3>::new)?;
+    registry.register(
+        "fib-small",
+        FibSmallProver::<Blake3_256<f64::BaseElement>>::new,
+    )?;
+    registry.register("mulfib", MulFib2Prover::<Blake3>::new)?;
+    registry.register("mulfib8", MulFib8Prover::<Blake3>::new)?;
+    Ok(())
+}
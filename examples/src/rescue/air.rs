@@ -4,10 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{rescue, BaseElement, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH};
-use crate::utils::{are_equal, is_zero, not, EvaluationResult};
+use crate::utils::{are_equal, is_zero, not, read_element_array, EvaluationResult};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -36,16 +36,26 @@ const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
 // RESCUE AIR
 // ================================================================================================
 
+#[derive(ToElements)]
+#[to_elements(crate = "winterfell::math")]
 pub struct PublicInputs {
     pub seed: [BaseElement; 2],
     pub result: [BaseElement; 2],
 }
 
-impl ToElements<BaseElement> for PublicInputs {
-    fn to_elements(&self) -> Vec<BaseElement> {
-        let mut result = self.seed.to_vec();
-        result.extend_from_slice(&self.result);
-        result
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.seed[..]);
+        target.write(&self.result[..]);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(PublicInputs {
+            seed: read_element_array(source)?,
+            result: read_element_array(source)?,
+        })
     }
 }
 
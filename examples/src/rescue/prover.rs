@@ -4,8 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    rescue, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, PhantomData, ProofOptions,
-    Prover, PublicInputs, RescueAir, Trace, TraceTable, CYCLE_LENGTH, NUM_HASH_ROUNDS,
+    rescue, BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, MerkleTree, PhantomData,
+    ProofOptions, Prover, PublicInputs, RescueAir, Trace, TraceTable, CYCLE_LENGTH,
+    NUM_HASH_ROUNDS,
 };
 
 // RESCUE PROVER
@@ -68,6 +69,7 @@ where
     type Air = RescueAir;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
+    type VC = MerkleTree<H>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
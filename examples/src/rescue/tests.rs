@@ -33,6 +33,15 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_proof_verification_with_pub_inputs() {
+    let rescue_eg = Box::new(super::RescueExample::<Blake3_256>::new(
+        128,
+        build_options(false),
+    ));
+    crate::tests::test_proof_verification_with_pub_inputs(rescue_eg);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
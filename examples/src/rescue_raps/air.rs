@@ -7,10 +7,10 @@ use super::{
     rescue::{self, STATE_WIDTH},
     BaseElement, ExtensionOf, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH,
 };
-use crate::utils::{are_equal, not, EvaluationResult};
-use core_utils::flatten_slice_elements;
+use crate::utils::{are_equal, not, read_element_array, EvaluationResult};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
+    math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, ByteReader, ByteWriter,
+    Deserializable, DeserializationError, EvaluationFrame, Serializable, TraceInfo,
     TransitionConstraintDegree,
 };
 
@@ -40,13 +40,23 @@ const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
 // RESCUE AIR
 // ================================================================================================
 
+#[derive(ToElements)]
+#[to_elements(crate = "winterfell::math")]
 pub struct PublicInputs {
     pub result: [[BaseElement; 2]; 2],
 }
 
-impl ToElements<BaseElement> for PublicInputs {
-    fn to_elements(&self) -> Vec<BaseElement> {
-        flatten_slice_elements(&self.result).to_vec()
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.result[..]);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(PublicInputs {
+            result: [read_element_array(source)?, read_element_array(source)?],
+        })
     }
 }
 
@@ -5,8 +5,8 @@
 
 use super::{
     apply_rescue_round_parallel, rescue::STATE_WIDTH, BaseElement, DefaultRandomCoin,
-    ElementHasher, FieldElement, PhantomData, ProofOptions, Prover, PublicInputs, RapTraceTable,
-    RescueRapsAir, Trace, CYCLE_LENGTH, NUM_HASH_ROUNDS,
+    ElementHasher, FieldElement, MerkleTree, PhantomData, ProofOptions, Prover, PublicInputs,
+    RapTraceTable, RescueRapsAir, Trace, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
 
 // RESCUE PROVER
@@ -97,6 +97,7 @@ where
     type Air = RescueRapsAir;
     type Trace = RapTraceTable<BaseElement>;
     type HashFn = H;
+    type VC = MerkleTree<H>;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
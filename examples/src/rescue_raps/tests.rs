@@ -3,8 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{
+    BaseElement, Blake3_256, PublicInputs, RescueRapsAir, RescueRapsExample, RescueRapsProver,
+};
+use winterfell::{
+    debug::{ConstraintDebugger, ConstraintId},
+    math::{fields::QuadExtension, FieldElement},
+    Air, AuxTraceRandElements, FieldExtension, ProofOptions, Trace,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -33,6 +39,79 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_proof_verification_with_pub_inputs() {
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(
+        128,
+        build_options(false),
+    ));
+    crate::tests::test_proof_verification_with_pub_inputs(rescue_eg);
+}
+
+#[test]
+fn rescue_test_multiple_ood_points() {
+    for num_ood_points in [2, 3] {
+        let options = build_options(true).with_num_ood_points(num_ood_points);
+        let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+        crate::tests::test_basic_proof_verification(rescue_eg);
+    }
+
+    let options = build_options(false).with_num_ood_points(2);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification_fail(rescue_eg);
+}
+
+#[test]
+fn rescue_test_constraint_debugger() {
+    type E = QuadExtension<BaseElement>;
+
+    let example = RescueRapsExample::<Blake3_256>::new(16, build_options(true));
+    let prover = RescueRapsProver::<Blake3_256>::new(example.options.clone());
+    let mut trace = prover.build_trace(&example.seeds, &example.permuted_seeds, example.result);
+    let pub_inputs = PublicInputs {
+        result: example.result,
+    };
+    let air = RescueRapsAir::new(trace.get_info(), pub_inputs, example.options.clone());
+
+    let num_rand_elements = air.trace_layout().get_aux_segment_rand_elements(0);
+    let rand_elements = (0..num_rand_elements as u128)
+        .map(|i| E::new(BaseElement::new(i + 3), BaseElement::new(i + 5)))
+        .collect::<Vec<_>>();
+    let mut aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    aux_rand_elements.add_segment_elements(rand_elements);
+    let z = E::new(BaseElement::new(42), BaseElement::new(7));
+
+    let aux_segments = [aux_segment.clone()];
+    let debugger = ConstraintDebugger::with_aux_segments(
+        &air,
+        &trace,
+        &aux_segments,
+        aux_rand_elements.clone(),
+    );
+    assert!(debugger.unsatisfied_constraints().is_empty());
+    for comparison in debugger.compare_ood(z) {
+        assert!(comparison.is_consistent(), "{comparison}");
+    }
+
+    // the value of the permutation argument column at step 20 is used by the permutation
+    // constraint at steps 19 and 20
+    let value = aux_segment.get(2, 20);
+    aux_segment.set(2, 20, value + E::ONE);
+    let aux_segments = [aux_segment];
+    let debugger =
+        ConstraintDebugger::with_aux_segments(&air, &trace, &aux_segments, aux_rand_elements);
+
+    let unsatisfied = debugger.unsatisfied_constraints();
+    assert_eq!(1, unsatisfied.len());
+    assert_eq!(ConstraintId::Aux(2), unsatisfied[0].constraint());
+    assert_eq!(vec![19, 20], unsatisfied[0].violations());
+    for comparison in debugger.compare_ood(z) {
+        let is_violated = comparison.constraint == ConstraintId::Aux(2);
+        assert_eq!(!is_violated, comparison.is_consistent(), "{comparison}");
+    }
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -0,0 +1,383 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{ExampleOptions, ExampleType, HashFunction};
+use std::{
+    io,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+use winterfell::{Deserializable, FieldExtension, ProofOptions};
+
+// PARAMETER SWEEP
+// ================================================================================================
+
+/// A set of protocol parameters for which proofs of example computations are benchmarked.
+///
+/// Proofs are generated for every combination of the specified hash functions, field extensions,
+/// blowup factors, numbers of queries, grinding factors, and FRI schedules. For each combination,
+/// proving time, verification time, proof size, and security level are measured (see
+/// [Measurements]).
+///
+/// Choice of the base field is defined by the example: for instance, `fib` example uses a 128-bit
+/// field while `fib-small` example uses a 64-bit field. Thus, to compare fields, the same sweep
+/// should be run for both examples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterSweep {
+    hash_fns: Vec<HashFunction>,
+    field_extensions: Vec<FieldExtension>,
+    blowup_factors: Vec<usize>,
+    num_queries: Vec<usize>,
+    grinding_factors: Vec<u32>,
+    fri_schedules: Vec<FriSchedule>,
+    num_runs: usize,
+}
+
+impl ParameterSweep {
+    /// Returns a sweep consisting of a single configuration with BLAKE3 (256-bit output) hash
+    /// function, no field extension, blowup factor of 8, 28 queries, grinding factor of 16, FRI
+    /// folding factor of 8, and FRI remainder max degree of 31.
+    ///
+    /// The parameters to sweep can be set via `with_*()` methods.
+    pub fn new() -> Self {
+        ParameterSweep {
+            hash_fns: vec![HashFunction::Blake3_256],
+            field_extensions: vec![FieldExtension::None],
+            blowup_factors: vec![8],
+            num_queries: vec![28],
+            grinding_factors: vec![16],
+            fri_schedules: vec![FriSchedule::new(8, 31)],
+            num_runs: 1,
+        }
+    }
+
+    /// Sets hash functions to sweep.
+    pub fn with_hash_fns(mut self, hash_fns: &[HashFunction]) -> Self {
+        self.hash_fns = hash_fns.to_vec();
+        self
+    }
+
+    /// Sets field extensions to sweep.
+    pub fn with_field_extensions(mut self, field_extensions: &[FieldExtension]) -> Self {
+        self.field_extensions = field_extensions.to_vec();
+        self
+    }
+
+    /// Sets blowup factors to sweep.
+    pub fn with_blowup_factors(mut self, blowup_factors: &[usize]) -> Self {
+        self.blowup_factors = blowup_factors.to_vec();
+        self
+    }
+
+    /// Sets numbers of queries to sweep.
+    pub fn with_num_queries(mut self, num_queries: &[usize]) -> Self {
+        self.num_queries = num_queries.to_vec();
+        self
+    }
+
+    /// Sets grinding factors to sweep.
+    pub fn with_grinding_factors(mut self, grinding_factors: &[u32]) -> Self {
+        self.grinding_factors = grinding_factors.to_vec();
+        self
+    }
+
+    /// Sets FRI schedules to sweep.
+    pub fn with_fri_schedules(mut self, fri_schedules: &[FriSchedule]) -> Self {
+        self.fri_schedules = fri_schedules.to_vec();
+        self
+    }
+
+    /// Sets the number of times a proof is generated and verified for each configuration; the
+    /// shortest of the measured times is reported.
+    ///
+    /// # Panics
+    /// Panics if `num_runs` is zero.
+    pub fn with_num_runs(mut self, num_runs: usize) -> Self {
+        assert!(num_runs > 0, "number of runs must be greater than zero");
+        self.num_runs = num_runs;
+        self
+    }
+
+    /// Returns all configurations of this sweep in the order in which they are benchmarked.
+    pub fn configurations(&self) -> Vec<Configuration> {
+        let mut result = Vec::new();
+        for &hash_fn in self.hash_fns.iter() {
+            for &field_extension in self.field_extensions.iter() {
+                for &blowup_factor in self.blowup_factors.iter() {
+                    for &num_queries in self.num_queries.iter() {
+                        for &grinding_factor in self.grinding_factors.iter() {
+                            for &fri_schedule in self.fri_schedules.iter() {
+                                result.push(Configuration {
+                                    hash_fn,
+                                    field_extension,
+                                    blowup_factor,
+                                    num_queries,
+                                    grinding_factor,
+                                    fri_schedule,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Benchmarks proof generation and verification for the specified example for all
+    /// configurations of this sweep.
+    ///
+    /// A result is returned for every configuration; configurations which could not be
+    /// benchmarked (e.g., because the hash function is not supported by the example, or because
+    /// the proof options are not valid for the example) are reported with an error.
+    pub fn run(&self, example: &ExampleType) -> Vec<SweepResult> {
+        self.configurations()
+            .into_iter()
+            .map(|configuration| SweepResult {
+                example: example.clone(),
+                outcome: self.measure(example, &configuration),
+                configuration,
+            })
+            .collect()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn measure(
+        &self,
+        example: &ExampleType,
+        configuration: &Configuration,
+    ) -> Result<Measurements, String> {
+        let options = configuration.to_proof_options()?;
+        let example_options = ExampleOptions::new(example.clone(), configuration.hash_fn, &options);
+        let example = example.get_example(&example_options)?;
+
+        let mut measurements: Option<Measurements> = None;
+        for _ in 0..self.num_runs {
+            // provers panic when proof options are not compatible with the computation (e.g.,
+            // when the blowup factor is too small for the degree of constraints)
+            let now = Instant::now();
+            let proof = panic::catch_unwind(AssertUnwindSafe(|| example.prove()))
+                .map_err(|err| format!("failed to generate proof: {}", panic_message(&err)))?;
+            let proving_time = now.elapsed();
+
+            let proof_size = proof.to_bytes().len();
+            let field_modulus_bits = proof.context.num_modulus_bits();
+            let hash_fn = configuration.hash_fn;
+            let conjectured_security_level = hash_fn.proof_security_level(&proof, true);
+            let proven_security_level = hash_fn.proof_security_level(&proof, false);
+
+            let now = Instant::now();
+            example
+                .verify(proof)
+                .map_err(|err| format!("failed to verify proof: {err}"))?;
+            let verification_time = now.elapsed();
+
+            measurements = Some(match measurements {
+                Some(m) => Measurements {
+                    proving_time: m.proving_time.min(proving_time),
+                    verification_time: m.verification_time.min(verification_time),
+                    ..m
+                },
+                None => Measurements {
+                    proving_time,
+                    verification_time,
+                    proof_size,
+                    field_modulus_bits,
+                    conjectured_security_level,
+                    proven_security_level,
+                },
+            });
+        }
+        Ok(measurements.expect("no runs"))
+    }
+}
+
+impl Default for ParameterSweep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// FRI SCHEDULE
+// ================================================================================================
+
+/// Defines how the FRI protocol reduces the degree of the composition polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FriSchedule {
+    /// Factor by which the degree of the polynomial is reduced in each FRI layer.
+    pub folding_factor: usize,
+    /// Maximum degree of the remainder polynomial after which the folding stops.
+    pub remainder_max_degree: usize,
+}
+
+impl FriSchedule {
+    /// Returns a new FRI schedule with the specified folding factor and remainder max degree.
+    pub fn new(folding_factor: usize, remainder_max_degree: usize) -> Self {
+        FriSchedule {
+            folding_factor,
+            remainder_max_degree,
+        }
+    }
+}
+
+// CONFIGURATION
+// ================================================================================================
+
+/// A single combination of protocol parameters in a [ParameterSweep].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Configuration {
+    pub hash_fn: HashFunction,
+    pub field_extension: FieldExtension,
+    pub blowup_factor: usize,
+    pub num_queries: usize,
+    pub grinding_factor: u32,
+    pub fri_schedule: FriSchedule,
+}
+
+impl Configuration {
+    /// Returns proof options described by this configuration.
+    ///
+    /// # Errors
+    /// Returns an error if the parameters do not describe valid proof options.
+    pub fn to_proof_options(&self) -> Result<ProofOptions, String> {
+        // all valid parameters fit into a byte, and thus, we can rely on the deserializer to
+        // validate the parameters
+        let params = [
+            self.num_queries,
+            self.blowup_factor,
+            self.grinding_factor as usize,
+            self.field_extension.degree() as usize,
+            self.fri_schedule.folding_factor,
+            self.fri_schedule.remainder_max_degree,
+        ];
+        let mut bytes = Vec::with_capacity(params.len());
+        for param in params {
+            let byte = u8::try_from(param)
+                .map_err(|_| format!("invalid proof options: invalid value {param}"))?;
+            bytes.push(byte);
+        }
+        let options = ProofOptions::read_from_bytes(&bytes)
+            .map_err(|err| format!("invalid proof options: {err}"))?;
+
+        // the deserializer also interprets spare bits of some parameters as flags of proof
+        // options extensions; parameters with such bits set are not valid
+        if plain_params(&options) != params {
+            return Err(format!("invalid proof options: invalid values {params:?}"));
+        }
+        Ok(options)
+    }
+}
+
+// RESULTS
+// ================================================================================================
+
+/// Outcome of benchmarking an example for a single configuration of a [ParameterSweep].
+#[derive(Debug, Clone)]
+pub struct SweepResult {
+    pub example: ExampleType,
+    pub configuration: Configuration,
+    pub outcome: Result<Measurements, String>,
+}
+
+impl SweepResult {
+    /// Names of the fields of a record returned by [to_csv_record()](SweepResult::to_csv_record).
+    pub const CSV_HEADER: &'static str = "example,size,hash_fn,field_extension,blowup_factor,\
+        num_queries,grinding_factor,fri_folding_factor,fri_remainder_max_degree,\
+        field_modulus_bits,proving_time_ms,verification_time_ms,proof_size,\
+        conjectured_security_level,proven_security_level,error";
+
+    /// Returns this result formatted as a comma-separated record; measurements are left empty if
+    /// the configuration could not be benchmarked.
+    pub fn to_csv_record(&self) -> String {
+        let config = &self.configuration;
+        let mut record = format!(
+            "{},{},{},{},{},{},{},{},{},",
+            self.example.name(),
+            self.example.size(),
+            config.hash_fn.as_str(),
+            config.field_extension.degree(),
+            config.blowup_factor,
+            config.num_queries,
+            config.grinding_factor,
+            config.fri_schedule.folding_factor,
+            config.fri_schedule.remainder_max_degree,
+        );
+        match &self.outcome {
+            Ok(m) => record.push_str(&format!(
+                "{},{:.3},{:.3},{},{},{},",
+                m.field_modulus_bits,
+                m.proving_time.as_secs_f64() * 1000.0,
+                m.verification_time.as_secs_f64() * 1000.0,
+                m.proof_size,
+                m.conjectured_security_level,
+                m.proven_security_level,
+            )),
+            Err(err) => {
+                // errors are quoted because they may contain commas
+                record.push_str(&format!(",,,,,,\"{}\"", err.replace('"', "\"\"")));
+            }
+        }
+        record
+    }
+}
+
+/// Measurements of proof generation and verification for a single configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurements {
+    /// Time it took to generate the proof, including building the execution trace.
+    pub proving_time: Duration,
+    /// Time it took to verify the proof.
+    pub verification_time: Duration,
+    /// Size of the serialized proof in bytes.
+    pub proof_size: usize,
+    /// Number of bits in the modulus of the base field of the computation.
+    pub field_modulus_bits: u32,
+    /// Conjectured security level of the proof in bits.
+    pub conjectured_security_level: u32,
+    /// Proven security level of the proof in bits.
+    pub proven_security_level: u32,
+}
+
+/// Writes the specified results into `target` in CSV format, one record per line preceded by a
+/// header line.
+///
+/// # Errors
+/// Returns an error if writing into `target` fails.
+pub fn write_csv<W: io::Write>(results: &[SweepResult], target: &mut W) -> io::Result<()> {
+    writeln!(target, "{}", SweepResult::CSV_HEADER)?;
+    for result in results {
+        writeln!(target, "{}", result.to_csv_record())?;
+    }
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "prover panicked"
+    }
+}
+
+/// Returns the parameters of the [ProofOptions] constructor which describe the specified
+/// options; field extension is specified by its degree.
+fn plain_params(options: &ProofOptions) -> [usize; 6] {
+    let fri_options = options.to_fri_options();
+    [
+        options.num_queries(),
+        options.blowup_factor(),
+        options.grinding_factor() as usize,
+        options.field_extension().degree() as usize,
+        fri_options.folding_factor(),
+        fri_options.remainder_max_degree(),
+    ]
+}
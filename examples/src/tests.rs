@@ -3,10 +3,19 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Example;
+use crate::{
+    sweep::{self, FriSchedule, ParameterSweep},
+    Example, ExampleType, HashFunction, OptionsFile,
+};
+use winterfell::{FieldExtension, StarkProof};
 
 pub fn test_basic_proof_verification(e: Box<dyn Example>) {
     let proof = e.prove();
+    let compressed_proof = proof.to_bytes_compressed();
+    assert_eq!(
+        proof,
+        StarkProof::from_bytes_compressed(&compressed_proof).unwrap()
+    );
     assert!(e.verify(proof).is_ok());
 }
 
@@ -15,3 +24,118 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+pub fn test_proof_verification_with_pub_inputs(e: Box<dyn Example>) {
+    let proof = e.prove();
+    let pub_inputs = e.pub_inputs();
+    assert!(e.verify_with_pub_inputs(proof.clone(), &pub_inputs).is_ok());
+
+    let mut wrong_pub_inputs = pub_inputs.clone();
+    wrong_pub_inputs[0] ^= 1;
+    assert!(e
+        .verify_with_pub_inputs(proof.clone(), &wrong_pub_inputs)
+        .is_err());
+
+    let truncated_pub_inputs = &pub_inputs[..pub_inputs.len() - 1];
+    assert!(e
+        .verify_with_pub_inputs(proof, truncated_pub_inputs)
+        .is_err());
+}
+
+#[test]
+fn parse_options_file() {
+    let source = "
+        # options for the fib example
+        [options]
+        hash_fn = \"sha3_256\" # 128-bit hash
+        num_queries = 42
+        blowup_factor = 1_6
+        fri_remainder_max_degree = 7
+    ";
+    let expected = OptionsFile {
+        hash_fn: Some("sha3_256".to_string()),
+        num_queries: Some(42),
+        blowup_factor: Some(16),
+        fri_remainder_max_degree: Some(7),
+        ..Default::default()
+    };
+    assert_eq!(Ok(expected), OptionsFile::parse(source));
+    assert_eq!(Ok(OptionsFile::default()), OptionsFile::parse(""));
+
+    // unknown keys and tables, duplicate keys, and values of wrong types are rejected
+    for source in [
+        "num_queries = 42\nnum_queries = 42",
+        "queries = 42",
+        "[prover]\nnum_queries = 42",
+        "num_queries = \"42\"",
+        "hash_fn = 42",
+        "grinding_factor = 4294967296",
+        "blowup_factor = 8_",
+        "hash_fn = \"sha3_256",
+        "num_queries",
+    ] {
+        assert!(OptionsFile::parse(source).is_err(), "{source}");
+    }
+}
+
+#[test]
+fn sweep_proof_parameters() {
+    let sweep = ParameterSweep::new()
+        .with_hash_fns(&[HashFunction::Blake3_256, HashFunction::Rp64_256])
+        .with_blowup_factors(&[8, 16])
+        .with_num_queries(&[20])
+        .with_grinding_factors(&[0])
+        .with_fri_schedules(&[FriSchedule::new(4, 7)]);
+    assert_eq!(4, sweep.configurations().len());
+
+    // Rescue Prime works only in the 64-bit field
+    let results = sweep.run(&ExampleType::Fib {
+        sequence_length: 64,
+    });
+    assert_eq!(4, results.len());
+    assert!(results[..2].iter().all(|result| result.outcome.is_ok()));
+    assert!(results[2..].iter().all(|result| result.outcome.is_err()));
+
+    let small_results = sweep.run(&ExampleType::FibSmall {
+        sequence_length: 64,
+    });
+    assert!(small_results.iter().all(|result| result.outcome.is_ok()));
+
+    // larger blowup factor yields higher security with the same number of queries
+    let m8 = results[0].outcome.as_ref().unwrap();
+    let m16 = results[1].outcome.as_ref().unwrap();
+    assert_eq!(16, results[1].configuration.blowup_factor);
+    assert!(m16.conjectured_security_level > m8.conjectured_security_level);
+    assert_eq!(128, m8.field_modulus_bits);
+    assert_eq!(
+        64,
+        small_results[0]
+            .outcome
+            .as_ref()
+            .unwrap()
+            .field_modulus_bits
+    );
+
+    let mut csv = Vec::new();
+    sweep::write_csv(&results, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(5, lines.len());
+    assert_eq!(sweep::SweepResult::CSV_HEADER, lines[0]);
+    let num_fields = lines[0].split(',').count();
+    assert!(lines[1].starts_with("fib,64,blake3_256,1,8,20,0,4,7,128,"));
+    assert_eq!(num_fields, lines[1].split(',').count());
+
+    // invalid proof options are reported as errors
+    let results = ParameterSweep::new()
+        .with_blowup_factors(&[3])
+        .with_field_extensions(&[FieldExtension::Quadratic])
+        .run(&ExampleType::Fib {
+            sequence_length: 64,
+        });
+    assert!(results[0]
+        .outcome
+        .as_ref()
+        .unwrap_err()
+        .contains("proof options"));
+}
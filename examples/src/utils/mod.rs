@@ -6,7 +6,7 @@
 use core::ops::Range;
 use winterfell::{
     math::{FieldElement, StarkField},
-    Trace, TraceTable,
+    ByteReader, DeserializationError, Trace, TraceTable,
 };
 
 pub mod rescue;
@@ -54,6 +54,27 @@ impl<E: FieldElement> EvaluationResult<E> for Vec<E> {
     }
 }
 
+// SERIALIZATION HELPERS
+// ================================================================================================
+
+/// Reads an array of `N` field elements from the specified `source`.
+pub fn read_element_array<E: FieldElement, R: ByteReader, const N: usize>(
+    source: &mut R,
+) -> Result<[E; N], DeserializationError> {
+    let elements = E::read_batch_from(source, N)?;
+    Ok(elements.try_into().unwrap_or_else(|_| unreachable!()))
+}
+
+/// Reads `num_arrays` arrays of `N` field elements each from the specified `source`.
+pub fn read_element_arrays<E: FieldElement, R: ByteReader, const N: usize>(
+    source: &mut R,
+    num_arrays: usize,
+) -> Result<Vec<[E; N]>, DeserializationError> {
+    (0..num_arrays)
+        .map(|_| read_element_array(source))
+        .collect()
+}
+
 // OTHER FUNCTIONS
 // ================================================================================================
 
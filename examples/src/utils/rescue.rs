@@ -148,6 +148,10 @@ impl Hash {
 }
 
 impl Digest for Hash {
+    const SIZE: usize = 32;
+
+    type Bytes = [u8; 32];
+
     fn as_bytes(&self) -> [u8; 32] {
         let bytes = BaseElement::elements_as_bytes(&self.0);
         let mut result = [0; 32];
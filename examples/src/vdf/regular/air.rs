@@ -5,22 +5,33 @@
 
 use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // PUBLIC INPUTS
 // ================================================================================================
 
-#[derive(Clone)]
+#[derive(Clone, ToElements)]
+#[to_elements(crate = "winterfell::math")]
 pub struct VdfInputs {
     pub seed: BaseElement,
     pub result: BaseElement,
 }
 
-impl ToElements<BaseElement> for VdfInputs {
-    fn to_elements(&self) -> Vec<BaseElement> {
-        vec![self.seed, self.result]
+impl Serializable for VdfInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.seed);
+        target.write(self.result);
+    }
+}
+
+impl Deserializable for VdfInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(VdfInputs {
+            seed: source.read()?,
+            result: source.read()?,
+        })
     }
 }
 
@@ -33,6 +33,15 @@ fn vdf_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(fib);
 }
 
+#[test]
+fn vdf_test_proof_verification_with_pub_inputs() {
+    let fib = Box::new(super::VdfExample::<Blake3_256>::new(
+        128,
+        build_options(false),
+    ));
+    crate::tests::test_proof_verification_with_pub_inputs(fib);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -0,0 +1,710 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, Execution, FieldElement, Instruction, Opcode, Program, ProofOptions, A,
+    AUX_TRACE_WIDTH, A_INV, B, BUS_CHALLENGE, BUS_PRODUCT, CARRY, CHIPLET_BITS, CHIPLET_CYCLE_LEN,
+    CHIP_A, CHIP_ACTIVE, CHIP_A_BITS, CHIP_B, CHIP_B_BITS, CHIP_Z, CLK, COEFFICIENTS, DELTA_HI,
+    DELTA_LO, FETCH_CHALLENGE, FETCH_MULT, FETCH_SUM, FLAGS, HALTED, IMM, MEMORY_CHALLENGE,
+    MEMORY_PRODUCT, MEM_VALUE, NUM_COEFFICIENTS, NUM_OPCODES, NUM_REGISTERS, PC, RANGE_CHALLENGE,
+    RANGE_DELTA_INV, RANGE_MULT, RANGE_RES_INV, RANGE_SUM, RANGE_TABLE_LEN, RD, REGISTERS, RES,
+    RES_HI, RES_LO, RS1, RS2, S_ACTIVE, S_ADDR, S_CLK, S_SAME, S_VALUE, S_WRITE, TAKEN,
+    TRACE_WIDTH,
+};
+use crate::utils::{are_equal, is_binary, not, EvaluationResult};
+use winterfell::{
+    math::{ExtensionOf, ToElements},
+    Air, AirContext, Assertion, AuxTraceRandElements, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, EvaluationFrame, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of a program execution: the program itself and values of the registers after
+/// the execution.
+#[derive(Debug, Clone, PartialEq, Eq, ToElements)]
+#[to_elements(crate = "winterfell::math")]
+pub struct PublicInputs {
+    pub program: Program,
+    pub outputs: [u16; NUM_REGISTERS],
+}
+
+impl PublicInputs {
+    /// Returns public inputs for the specified `execution` of the `program`.
+    pub fn new(program: &Program, execution: &Execution) -> Self {
+        PublicInputs {
+            program: program.clone(),
+            outputs: execution.registers,
+        }
+    }
+}
+
+impl ToElements<BaseElement> for Program {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.instructions().to_elements()
+    }
+}
+
+impl ToElements<BaseElement> for Instruction {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![
+            BaseElement::from(self.opcode.index() as u8),
+            BaseElement::from(self.rd as u8),
+            BaseElement::from(self.rs1 as u8),
+            BaseElement::from(self.rs2 as u8),
+            BaseElement::from(self.imm),
+        ]
+    }
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let instructions = self.program.instructions();
+        target.write_u32(instructions.len() as u32);
+        for instruction in instructions.iter() {
+            target.write_u8(instruction.opcode.index() as u8);
+            target.write_u8(instruction.rd as u8);
+            target.write_u8(instruction.rs1 as u8);
+            target.write_u8(instruction.rs2 as u8);
+            target.write_u16(instruction.imm);
+        }
+        for &output in self.outputs.iter() {
+            target.write_u16(output);
+        }
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_instructions = source.read_u32()? as usize;
+        let mut instructions = Vec::new();
+        for _ in 0..num_instructions {
+            let opcode = Opcode::from_index(source.read_u8()? as usize)
+                .map_err(DeserializationError::InvalidValue)?;
+            instructions.push(Instruction {
+                opcode,
+                rd: source.read_u8()? as usize,
+                rs1: source.read_u8()? as usize,
+                rs2: source.read_u8()? as usize,
+                imm: source.read_u16()?,
+            });
+        }
+        let program = Program::new(instructions).map_err(DeserializationError::InvalidValue)?;
+
+        let mut outputs = [0; NUM_REGISTERS];
+        for output in outputs.iter_mut() {
+            *output = source.read_u16()?;
+        }
+
+        Ok(PublicInputs { program, outputs })
+    }
+}
+
+// ZKVM AIR
+// ================================================================================================
+
+/// AIR for execution of programs of a small register machine.
+///
+/// The main trace consists of three components placed side by side:
+/// * The CPU, one row per step: clock cycle, program counter, registers, the decoded instruction
+///   (one flag per opcode and one-hot selectors of the registers it refers to), its operands, and
+///   its result split into two 8-bit limbs. Once the machine halts, the remaining rows repeat its
+///   final state with all flags and selectors set to zero.
+/// * Memory accesses made by the CPU sorted by address and then by clock cycle; consecutive
+///   accesses to the same address must read the value written by the previous access, and the
+///   first access to an address must be a write.
+/// * The XOR chiplet which computes the XOR of two 16-bit words in 4 rows, 4 bits per row, from
+///   the most significant bits to the least significant ones.
+///
+/// The components are connected via the auxiliary trace segment:
+/// * All results and differences between consecutive sorted memory accesses are range-checked
+///   to 16 bits via a LogUp argument over a periodic table of 8-bit values.
+/// * Instruction fetches are looked up in the program table (described by periodic columns)
+///   via another LogUp argument.
+/// * Memory accesses of the CPU are a permutation of the sorted accesses (the memory-checking
+///   gadget).
+/// * XOR requests of the CPU are matched with the results of the chiplet via a bus, i.e., a
+///   permutation argument between the requests and the responses.
+///
+/// None of the arguments account for the last row of the trace since it is not constrained by
+/// transition constraints; the machine must halt before the last row, and the last cycle of the
+/// chiplet must be inactive.
+pub struct ZkvmAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: PublicInputs,
+}
+
+impl Air for ZkvmAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.layout().main_trace_width());
+        assert_eq!(AUX_TRACE_WIDTH, trace_info.layout().aux_trace_width());
+
+        let program_table_len = pub_inputs.program.table().len();
+        assert!(
+            program_table_len < trace_info.length(),
+            "program table must be shorter than the execution trace"
+        );
+
+        let main_degrees = get_main_degrees();
+        let aux_degrees = vec![
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::with_cycles(1, vec![RANGE_TABLE_LEN]),
+            TransitionConstraintDegree::with_cycles(2, vec![program_table_len]),
+            TransitionConstraintDegree::new(3),
+            TransitionConstraintDegree::with_cycles(3, vec![CHIPLET_CYCLE_LEN]),
+        ];
+
+        ZkvmAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                5 + 2 * NUM_REGISTERS,
+                8,
+                options,
+            ),
+            pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        evaluate_cpu(current, next, result);
+        evaluate_memory(current, next, &mut result[MEMORY_CONSTRAINTS..]);
+        evaluate_chiplet(
+            current,
+            next,
+            periodic_values,
+            &mut result[CHIPLET_CONSTRAINTS..],
+        );
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let main = main_frame.current();
+        let aux_current = aux_frame.current();
+        let aux_next = aux_frame.next();
+        let rand_elements = aux_rand_elements.get_segment_elements(0);
+
+        // helper columns hold sums of inverses of the range-checked values of a row
+        let [res_lo, res_hi, delta_lo, delta_hi] = range_checked_values(main, rand_elements);
+        result[0] = are_equal(
+            aux_current[RANGE_RES_INV] * res_lo * res_hi,
+            res_lo + res_hi,
+        );
+        result[1] = are_equal(
+            aux_current[RANGE_DELTA_INV] * delta_lo * delta_hi,
+            delta_lo + delta_hi,
+        );
+
+        // range check argument: the values looked up by a row are removed from the running sum,
+        // while the table entry is added with its multiplicity
+        let table_value = range_table_value(periodic_values, rand_elements);
+        let lookups = aux_current[RANGE_RES_INV] + aux_current[RANGE_DELTA_INV];
+        result[2] = are_equal(
+            (aux_next[RANGE_SUM] - aux_current[RANGE_SUM] + lookups) * table_value,
+            E::from(main[RANGE_MULT]),
+        );
+
+        // instruction fetch argument; halted rows do not fetch instructions
+        let fetched = fetched_instruction(main, rand_elements);
+        let table_entry = program_table_entry(periodic_values, rand_elements);
+        result[3] = are_equal(
+            (aux_next[FETCH_SUM] - aux_current[FETCH_SUM]) * table_entry * fetched,
+            E::from(main[FETCH_MULT]) * fetched - E::from(not(main[HALTED])) * table_entry,
+        );
+
+        // memory permutation argument
+        result[4] = are_equal(
+            aux_next[MEMORY_PRODUCT] * sorted_memory_access(main, rand_elements),
+            aux_current[MEMORY_PRODUCT] * memory_access(main, rand_elements),
+        );
+
+        // chiplet bus
+        result[5] = are_equal(
+            aux_next[BUS_PRODUCT] * xor_response(main, periodic_values, rand_elements),
+            aux_current[BUS_PRODUCT] * xor_request(main, rand_elements),
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        let mut assertions = vec![
+            Assertion::single(CLK, 0, BaseElement::ZERO),
+            Assertion::single(PC, 0, BaseElement::ZERO),
+            Assertion::single(HALTED, 0, BaseElement::ZERO),
+            Assertion::single(HALTED, last_step, BaseElement::ONE),
+            // the first sorted memory access must be a write
+            Assertion::single(S_WRITE, 0, BaseElement::ONE),
+        ];
+
+        // all registers are initialized to zero and must hold the outputs at the end
+        for (i, &output) in self.pub_inputs.outputs.iter().enumerate() {
+            assertions.push(Assertion::single(REGISTERS + i, 0, BaseElement::ZERO));
+            assertions.push(Assertion::single(
+                REGISTERS + i,
+                last_step,
+                BaseElement::from(output),
+            ));
+        }
+
+        assertions
+    }
+
+    fn get_aux_assertions<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(RANGE_SUM, 0, E::ZERO),
+            Assertion::single(RANGE_SUM, last_step, E::ZERO),
+            Assertion::single(FETCH_SUM, 0, E::ZERO),
+            Assertion::single(FETCH_SUM, last_step, E::ZERO),
+            Assertion::single(MEMORY_PRODUCT, 0, E::ONE),
+            Assertion::single(MEMORY_PRODUCT, last_step, E::ONE),
+            Assertion::single(BUS_PRODUCT, 0, E::ONE),
+            Assertion::single(BUS_PRODUCT, last_step, E::ONE),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        get_periodic_columns(&self.pub_inputs.program)
+    }
+}
+
+// CONSTRAINT EVALUATORS
+// ================================================================================================
+
+/// Evaluates constraints of the CPU component.
+fn evaluate_cpu<E: FieldElement + From<BaseElement>>(current: &[E], next: &[E], result: &mut [E]) {
+    let flag = |opcode: Opcode| current[FLAGS + opcode.index()];
+    let (halted, a, b, res) = (current[HALTED], current[A], current[B], current[RES]);
+    let two_16 = E::from(1u32 << 16);
+
+    // clock cycles and the halted flag; once halted, no flag is set
+    result[0] = are_equal(next[CLK], current[CLK] + E::ONE);
+    for i in 0..NUM_OPCODES {
+        result[1 + i] = is_binary(current[FLAGS + i]);
+    }
+    let flags_sum = (0..NUM_OPCODES).fold(E::ZERO, |acc, i| acc + current[FLAGS + i]);
+    result[9] = are_equal(flags_sum, not(halted));
+    result[10] = are_equal(next[HALTED], halted + flag(Opcode::Halt));
+
+    // register selectors are one-hot unless the machine is halted
+    for (i, selectors) in [RD, RS1, RS2].into_iter().enumerate() {
+        let mut sum = E::ZERO;
+        for j in 0..NUM_REGISTERS {
+            result[11 + i * NUM_REGISTERS + j] = is_binary(current[selectors + j]);
+            sum += current[selectors + j];
+        }
+        result[23 + i] = are_equal(sum, not(halted));
+    }
+    result[26] = are_equal(a, select_register(current, RS1));
+    result[27] = are_equal(b, select_register(current, RS2));
+
+    // results are 16-bit values
+    result[28] = are_equal(res, current[RES_LO] + E::from(256u32) * current[RES_HI]);
+
+    // additions wrap around modulo 2^16
+    let carry = current[CARRY];
+    result[29] = is_binary(carry);
+    result[30] = carry * not(flag(Opcode::Add) + flag(Opcode::Addi));
+    result.agg_constraint(
+        31,
+        flag(Opcode::Add),
+        are_equal(a + b, res + two_16 * carry),
+    );
+    result.agg_constraint(
+        32,
+        flag(Opcode::Addi),
+        are_equal(a + current[IMM], res + two_16 * carry),
+    );
+    result.agg_constraint(33, flag(Opcode::Movi), are_equal(res, current[IMM]));
+    let no_result = flag(Opcode::Store) + flag(Opcode::Bnz) + flag(Opcode::Halt) + halted;
+    result.agg_constraint(34, no_result, res);
+
+    // the result is written into the destination register; all other registers are unchanged
+    let write = flag(Opcode::Add)
+        + flag(Opcode::Addi)
+        + flag(Opcode::Movi)
+        + flag(Opcode::Load)
+        + flag(Opcode::Xor);
+    for i in 0..NUM_REGISTERS {
+        let register = current[REGISTERS + i];
+        result[35 + i] = are_equal(
+            next[REGISTERS + i],
+            register + write * current[RD + i] * (res - register),
+        );
+    }
+
+    // the value loaded from or stored into memory
+    result[39] = are_equal(
+        current[MEM_VALUE],
+        flag(Opcode::Load) * res + flag(Opcode::Store) * b,
+    );
+
+    // a branch is taken if and only if the first operand is not zero
+    let taken = current[TAKEN];
+    result[40] = taken * not(flag(Opcode::Bnz));
+    result[41] = flag(Opcode::Bnz) * not(taken) * a;
+    result[42] = taken * not(a * current[A_INV]);
+
+    // pc update; the pc does not change once the machine halts
+    let pc_next = current[PC] + not(halted + flag(Opcode::Halt));
+    result[43] = are_equal(next[PC], taken * current[IMM] + not(taken) * pc_next);
+}
+
+/// Evaluates constraints of the sorted memory accesses; all constraints are enforced only when
+/// the next row holds an access.
+fn evaluate_memory<E: FieldElement + From<BaseElement>>(
+    current: &[E],
+    next: &[E],
+    result: &mut [E],
+) {
+    let (active, next_active) = (current[S_ACTIVE], next[S_ACTIVE]);
+    let same = current[S_SAME];
+
+    // accesses are placed into the first rows of the trace
+    result[0] = is_binary(active);
+    result[1] = next_active * not(active);
+    result[2] = is_binary(current[S_WRITE]);
+    result[3] = is_binary(same);
+
+    // accesses are sorted by address and then by clock cycle; this is enforced by range-checking
+    // the difference between consecutive clock cycles (or addresses), minus one
+    let addr_delta = next[S_ADDR] - current[S_ADDR];
+    let clk_delta = next[S_CLK] - current[S_CLK];
+    let delta = current[DELTA_LO] + E::from(256u32) * current[DELTA_HI];
+    result[4] = next_active * same * addr_delta;
+    result[5] = next_active
+        * are_equal(
+            delta,
+            same * (clk_delta - E::ONE) + not(same) * (addr_delta - E::ONE),
+        );
+
+    // a read returns the last value written to the same address, and the first access to an
+    // address must be a write
+    let value_delta = next[S_VALUE] - current[S_VALUE];
+    result[6] = next_active * same * not(next[S_WRITE]) * value_delta;
+    result[7] = next_active * not(same) * not(next[S_WRITE]);
+}
+
+/// Evaluates constraints of the XOR chiplet.
+fn evaluate_chiplet<E: FieldElement + From<BaseElement>>(
+    current: &[E],
+    next: &[E],
+    periodic_values: &[E],
+    result: &mut [E],
+) {
+    let chip_first = periodic_values[CHIPLET_FIRST];
+    let not_last = not(periodic_values[CHIPLET_LAST]);
+    let sixteen = E::from(1u32 << CHIPLET_BITS);
+
+    for i in 0..CHIPLET_BITS {
+        result[i] = is_binary(current[CHIP_A_BITS + i]);
+        result[CHIPLET_BITS + i] = is_binary(current[CHIP_B_BITS + i]);
+    }
+    result[8] = is_binary(current[CHIP_ACTIVE]);
+    result[9] = not_last * are_equal(next[CHIP_ACTIVE], current[CHIP_ACTIVE]);
+
+    // the first row of a cycle holds the most significant bits of the operands, and every
+    // subsequent row appends the next 4 bits
+    let (a_limb, b_limb, z_limb) = chiplet_limbs(current);
+    result[10] = chip_first * are_equal(current[CHIP_A], a_limb);
+    result[11] = chip_first * are_equal(current[CHIP_B], b_limb);
+    result[12] = chip_first * are_equal(current[CHIP_Z], z_limb);
+
+    let (a_limb, b_limb, z_limb) = chiplet_limbs(next);
+    result[13] = not_last * are_equal(next[CHIP_A], sixteen * current[CHIP_A] + a_limb);
+    result[14] = not_last * are_equal(next[CHIP_B], sixteen * current[CHIP_B] + b_limb);
+    result[15] = not_last * are_equal(next[CHIP_Z], sixteen * current[CHIP_Z] + z_limb);
+}
+
+// CONSTRAINT INDEXES AND DEGREES
+// ================================================================================================
+
+/// Index of the first constraint of the sorted memory accesses.
+const MEMORY_CONSTRAINTS: usize = 44;
+
+/// Index of the first constraint of the XOR chiplet.
+const CHIPLET_CONSTRAINTS: usize = MEMORY_CONSTRAINTS + 8;
+
+const NUM_MAIN_CONSTRAINTS: usize = CHIPLET_CONSTRAINTS + 16;
+
+/// Returns degrees of the main transition constraints.
+fn get_main_degrees() -> Vec<TransitionConstraintDegree> {
+    let mut degrees = Vec::with_capacity(NUM_MAIN_CONSTRAINTS);
+
+    // CPU
+    degrees.push(TransitionConstraintDegree::new(1));
+    degrees.extend(vec![TransitionConstraintDegree::new(2); NUM_OPCODES]);
+    degrees.push(TransitionConstraintDegree::new(1));
+    degrees.push(TransitionConstraintDegree::new(1));
+    degrees.extend(vec![TransitionConstraintDegree::new(2); 3 * NUM_REGISTERS]);
+    degrees.extend(vec![TransitionConstraintDegree::new(1); 3]);
+    degrees.extend(vec![TransitionConstraintDegree::new(2); 2]);
+    degrees.push(TransitionConstraintDegree::new(1));
+    degrees.extend(vec![TransitionConstraintDegree::new(2); 6]);
+    degrees.extend(vec![TransitionConstraintDegree::new(3); NUM_REGISTERS]);
+    degrees.extend(vec![TransitionConstraintDegree::new(2); 2]);
+    degrees.extend(vec![TransitionConstraintDegree::new(3); 2]);
+    degrees.push(TransitionConstraintDegree::new(2));
+    debug_assert_eq!(MEMORY_CONSTRAINTS, degrees.len());
+
+    // sorted memory accesses
+    degrees.extend(vec![TransitionConstraintDegree::new(2); 4]);
+    degrees.extend(vec![TransitionConstraintDegree::new(3); 2]);
+    degrees.push(TransitionConstraintDegree::new(4));
+    degrees.push(TransitionConstraintDegree::new(3));
+    debug_assert_eq!(CHIPLET_CONSTRAINTS, degrees.len());
+
+    // XOR chiplet
+    let cycles = vec![CHIPLET_CYCLE_LEN];
+    degrees.extend(vec![
+        TransitionConstraintDegree::new(2);
+        2 * CHIPLET_BITS + 1
+    ]);
+    degrees.push(TransitionConstraintDegree::with_cycles(1, cycles.clone()));
+    for base in [1, 1, 2, 1, 1, 2] {
+        degrees.push(TransitionConstraintDegree::with_cycles(
+            base,
+            cycles.clone(),
+        ));
+    }
+    debug_assert_eq!(NUM_MAIN_CONSTRAINTS, degrees.len());
+
+    degrees
+}
+
+// PERIODIC COLUMNS
+// ================================================================================================
+
+// Periodic columns: the range check table, flags marking the first and the last row of a chiplet
+// cycle, and the program table (the index, opcode, register fields, and immediate value of each
+// instruction).
+const RANGE_TABLE: usize = 0;
+const CHIPLET_FIRST: usize = 1;
+const CHIPLET_LAST: usize = 2;
+const PROGRAM_TABLE: usize = 3;
+
+/// Returns values of all periodic columns for the specified program.
+pub(super) fn get_periodic_columns(program: &Program) -> Vec<Vec<BaseElement>> {
+    let range_table = (0..RANGE_TABLE_LEN as u32).map(BaseElement::from).collect();
+    let mut chiplet_first = vec![BaseElement::ZERO; CHIPLET_CYCLE_LEN];
+    chiplet_first[0] = BaseElement::ONE;
+    let mut chiplet_last = vec![BaseElement::ZERO; CHIPLET_CYCLE_LEN];
+    chiplet_last[CHIPLET_CYCLE_LEN - 1] = BaseElement::ONE;
+
+    let table = program.table();
+    let mut result = vec![range_table, chiplet_first, chiplet_last];
+    result.extend((0..NUM_COEFFICIENTS).map(|i| {
+        table
+            .iter()
+            .enumerate()
+            .map(|(pc, instruction)| instruction_fields(pc, instruction)[i])
+            .collect()
+    }));
+    result
+}
+
+/// Returns fields of an instruction located at the specified index of the program as they appear
+/// in the program table.
+fn instruction_fields(pc: usize, instruction: &Instruction) -> [BaseElement; NUM_COEFFICIENTS] {
+    [
+        pc as u64,
+        instruction.opcode.index() as u64,
+        instruction.rd as u64,
+        instruction.rs1 as u64,
+        instruction.rs2 as u64,
+        instruction.imm as u64,
+    ]
+    .map(BaseElement::from)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the value of the register selected by the one-hot selectors at the specified index.
+fn select_register<E: FieldElement>(row: &[E], selectors: usize) -> E {
+    (0..NUM_REGISTERS).fold(E::ZERO, |acc, i| {
+        acc + row[selectors + i] * row[REGISTERS + i]
+    })
+}
+
+/// Returns the index of the (one-hot) selected register or opcode.
+fn selected_index<E: FieldElement>(row: &[E], selectors: usize, num_selectors: usize) -> E {
+    (1..num_selectors).fold(E::ZERO, |acc, i| {
+        acc + E::from(i as u32) * row[selectors + i]
+    })
+}
+
+/// Returns 4-bit limbs of the chiplet operands and their XOR encoded by the bits of the row.
+fn chiplet_limbs<E: FieldElement>(row: &[E]) -> (E, E, E) {
+    let (mut a, mut b, mut z) = (E::ZERO, E::ZERO, E::ZERO);
+    for i in (0..CHIPLET_BITS).rev() {
+        let (a_bit, b_bit) = (row[CHIP_A_BITS + i], row[CHIP_B_BITS + i]);
+        a = a.double() + a_bit;
+        b = b.double() + b_bit;
+        z = z.double() + a_bit + b_bit - (a_bit * b_bit).double();
+    }
+    (a, b, z)
+}
+
+/// Combines the specified values into a single value using random coefficients.
+fn combine<F, E>(values: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    values
+        .iter()
+        .zip(&rand_elements[COEFFICIENTS..])
+        .fold(E::ZERO, |acc, (&value, &coefficient)| {
+            acc + coefficient * E::from(value)
+        })
+}
+
+/// Returns z - value for all values of the row range-checked against the 8-bit table.
+pub(super) fn range_checked_values<F, E>(row: &[F], rand_elements: &[E]) -> [E; 4]
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let z = rand_elements[RANGE_CHALLENGE];
+    [RES_LO, RES_HI, DELTA_LO, DELTA_HI].map(|column| z - E::from(row[column]))
+}
+
+/// Returns z - value for the range check table entry of the row.
+pub(super) fn range_table_value<F, E>(periodic_values: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    rand_elements[RANGE_CHALLENGE] - E::from(periodic_values[RANGE_TABLE])
+}
+
+/// Returns z - instruction for the instruction fetched by the row.
+pub(super) fn fetched_instruction<F, E>(row: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let fields = [
+        row[PC],
+        selected_index(row, FLAGS, NUM_OPCODES),
+        selected_index(row, RD, NUM_REGISTERS),
+        selected_index(row, RS1, NUM_REGISTERS),
+        selected_index(row, RS2, NUM_REGISTERS),
+        row[IMM],
+    ];
+    rand_elements[FETCH_CHALLENGE] - combine(&fields, rand_elements)
+}
+
+/// Returns z - instruction for the program table entry of the row.
+pub(super) fn program_table_entry<F, E>(periodic_values: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let fields = &periodic_values[PROGRAM_TABLE..PROGRAM_TABLE + NUM_COEFFICIENTS];
+    rand_elements[FETCH_CHALLENGE] - combine(fields, rand_elements)
+}
+
+/// Returns z - (address, clock cycle, value, is_write) for the memory access made by the CPU at
+/// the row, or one if the row does not access memory.
+pub(super) fn memory_access<F, E>(row: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let is_write = row[FLAGS + Opcode::Store.index()];
+    let flag = row[FLAGS + Opcode::Load.index()] + is_write;
+    let access = [row[A], row[CLK], row[MEM_VALUE], is_write];
+    select(
+        flag,
+        rand_elements[MEMORY_CHALLENGE] - combine(&access, rand_elements),
+    )
+}
+
+/// Returns z - (address, clock cycle, value, is_write) for the sorted memory access at the row,
+/// or one if the row does not hold an access.
+pub(super) fn sorted_memory_access<F, E>(row: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let access = [row[S_ADDR], row[S_CLK], row[S_VALUE], row[S_WRITE]];
+    select(
+        row[S_ACTIVE],
+        rand_elements[MEMORY_CHALLENGE] - combine(&access, rand_elements),
+    )
+}
+
+/// Returns z - (a, b, a ^ b) for the XOR request made by the CPU at the row, or one if the row
+/// does not make a request.
+pub(super) fn xor_request<F, E>(row: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let request = [row[A], row[B], row[RES]];
+    select(
+        row[FLAGS + Opcode::Xor.index()],
+        rand_elements[BUS_CHALLENGE] - combine(&request, rand_elements),
+    )
+}
+
+/// Returns z - (a, b, a ^ b) for the XOR result computed by the chiplet at the row, or one if the
+/// row is not the last row of an active chiplet cycle.
+pub(super) fn xor_response<F, E>(row: &[F], periodic_values: &[F], rand_elements: &[E]) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    let response = [row[CHIP_A], row[CHIP_B], row[CHIP_Z]];
+    select(
+        row[CHIP_ACTIVE] * periodic_values[CHIPLET_LAST],
+        rand_elements[BUS_CHALLENGE] - combine(&response, rand_elements),
+    )
+}
+
+/// Returns `value` when the binary `flag` is set, and one otherwise.
+fn select<F, E>(flag: F, value: E) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    E::from(flag) * value + E::from(not(flag))
+}
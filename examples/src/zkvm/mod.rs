@@ -0,0 +1,344 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    verify_with_serialized_inputs, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction,
+    Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement, StarkField},
+    ProofOptions, Prover, Serializable, StarkProof, Trace, VerifierError,
+};
+
+mod air;
+pub use air::{PublicInputs, ZkvmAir};
+
+mod program;
+pub use program::{Execution, Instruction, Opcode, Program, Step};
+
+mod prover;
+pub use prover::ZkvmProver;
+
+mod trace;
+pub use trace::ZkvmTrace;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of general-purpose registers of the machine.
+const NUM_REGISTERS: usize = 4;
+
+/// Number of opcodes supported by the machine; there is one flag column per opcode.
+const NUM_OPCODES: usize = 8;
+
+/// Number of 16-bit words processed by the XOR chiplet per row, in bits.
+const CHIPLET_BITS: usize = 4;
+
+/// Number of rows needed by the XOR chiplet to process a pair of 16-bit words.
+const CHIPLET_CYCLE_LEN: usize = 16 / CHIPLET_BITS;
+
+/// Number of values in the range check table; 16-bit values are range-checked by splitting them
+/// into two 8-bit limbs.
+const RANGE_TABLE_LEN: usize = 256;
+
+/// Minimum length of the execution trace: the table multiplicity of every 8-bit value must be
+/// placed into a row which is not the last row of the trace.
+const MIN_TRACE_LENGTH: usize = 2 * RANGE_TABLE_LEN;
+
+/// Maximum length of the execution trace; clock cycles must fit into 16 bits so that differences
+/// between them can be range-checked.
+const MAX_TRACE_LENGTH: usize = 1 << 16;
+
+// Main trace columns of the CPU: clock cycle, program counter, a flag which is set once the
+// machine halts, registers, decoded instruction (one flag per opcode, and one-hot selectors of
+// the destination and source registers), operands and result of the instruction, and helper
+// values for additions and branches.
+const CLK: usize = 0;
+const PC: usize = 1;
+const HALTED: usize = 2;
+const REGISTERS: usize = 3;
+const FLAGS: usize = REGISTERS + NUM_REGISTERS;
+const RD: usize = FLAGS + NUM_OPCODES;
+const RS1: usize = RD + NUM_REGISTERS;
+const RS2: usize = RS1 + NUM_REGISTERS;
+const IMM: usize = RS2 + NUM_REGISTERS;
+const A: usize = IMM + 1;
+const B: usize = A + 1;
+const RES: usize = B + 1;
+const RES_LO: usize = RES + 1;
+const RES_HI: usize = RES_LO + 1;
+const CARRY: usize = RES_HI + 1;
+const TAKEN: usize = CARRY + 1;
+const A_INV: usize = TAKEN + 1;
+const MEM_VALUE: usize = A_INV + 1;
+
+// Main trace columns: multiplicities of the range check table and program table entries placed
+// at the same row.
+const RANGE_MULT: usize = MEM_VALUE + 1;
+const FETCH_MULT: usize = RANGE_MULT + 1;
+
+// Main trace columns: memory accesses sorted by address and clock cycle, a flag which is set for
+// rows holding an access, a flag which is set when the next access is to the same address, and
+// 8-bit limbs of the difference between clock cycles (or addresses) of consecutive accesses.
+const S_ADDR: usize = FETCH_MULT + 1;
+const S_CLK: usize = S_ADDR + 1;
+const S_VALUE: usize = S_CLK + 1;
+const S_WRITE: usize = S_VALUE + 1;
+const S_ACTIVE: usize = S_WRITE + 1;
+const S_SAME: usize = S_ACTIVE + 1;
+const DELTA_LO: usize = S_SAME + 1;
+const DELTA_HI: usize = DELTA_LO + 1;
+
+// Main trace columns of the XOR chiplet: accumulated operands and result, bits of the operands
+// processed in the current row (least significant bit first), and a flag which is set for
+// cycles holding a request.
+const CHIP_A: usize = DELTA_HI + 1;
+const CHIP_B: usize = CHIP_A + 1;
+const CHIP_Z: usize = CHIP_B + 1;
+const CHIP_A_BITS: usize = CHIP_Z + 1;
+const CHIP_B_BITS: usize = CHIP_A_BITS + CHIPLET_BITS;
+const CHIP_ACTIVE: usize = CHIP_B_BITS + CHIPLET_BITS;
+const TRACE_WIDTH: usize = CHIP_ACTIVE + 1;
+
+// Auxiliary trace columns: running sum of the range check LogUp argument together with helper
+// columns holding the inverses of the looked up values, running sum of the instruction fetch
+// LogUp argument, and running products of the memory and chiplet bus permutation arguments.
+const RANGE_SUM: usize = 0;
+const RANGE_RES_INV: usize = 1;
+const RANGE_DELTA_INV: usize = 2;
+const FETCH_SUM: usize = 3;
+const MEMORY_PRODUCT: usize = 4;
+const BUS_PRODUCT: usize = 5;
+const AUX_TRACE_WIDTH: usize = 6;
+
+// Random elements of the auxiliary trace segment: challenges of the range check, fetch, memory,
+// and bus arguments, followed by coefficients used to combine tuples of values into a single
+// value.
+const RANGE_CHALLENGE: usize = 0;
+const FETCH_CHALLENGE: usize = 1;
+const MEMORY_CHALLENGE: usize = 2;
+const BUS_CHALLENGE: usize = 3;
+const COEFFICIENTS: usize = 4;
+const NUM_COEFFICIENTS: usize = 6;
+const AUX_TRACE_RANDS: usize = COEFFICIENTS + NUM_COEFFICIENTS;
+
+/// Maximum number of steps the example program is allowed to execute.
+const MAX_STEPS: usize = MAX_TRACE_LENGTH - 1;
+
+/// Address of the first memory cell written by the example program.
+const ARRAY_BASE: u16 = 16;
+
+/// Address of the memory cell into which the example program writes its result.
+const OUTPUT_ADDR: u16 = 0x1000;
+
+// ZKVM EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: &ExampleOptions, n: usize) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(ZkvmExample::<Blake3_192>::new(n, options)?)),
+        HashFunction::Blake3_256 => Ok(Box::new(ZkvmExample::<Blake3_256>::new(n, options)?)),
+        HashFunction::Sha3_256 => Ok(Box::new(ZkvmExample::<Sha3_256>::new(n, options)?)),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct ZkvmExample<H: ElementHasher> {
+    options: ProofOptions,
+    program: Program,
+    pub_inputs: PublicInputs,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> ZkvmExample<H> {
+    pub fn new(n: usize, options: ProofOptions) -> Result<Self, String> {
+        if n == 0 || n > 1024 {
+            return Err("number of iterations must be between 1 and 1024".to_string());
+        }
+        let program = build_checksum_program(n as u16);
+
+        // execute the program to determine public inputs of the computation
+        let now = Instant::now();
+        let execution = program.execute(MAX_STEPS)?;
+        let pub_inputs = PublicInputs::new(&program, &execution);
+        debug!(
+            "Executed a program checksumming {} Fibonacci terms in {} steps in {} ms",
+            2 * n,
+            execution.steps.len(),
+            now.elapsed().as_millis()
+        );
+
+        if pub_inputs.outputs[0] != compute_checksum(n) {
+            return Err("zkVM program computed an incorrect checksum".to_string());
+        }
+
+        Ok(ZkvmExample {
+            options,
+            program,
+            pub_inputs,
+            _hasher: PhantomData,
+        })
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for ZkvmExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for executing a zkVM program of {} instructions\n\
+            ---------------------",
+            self.program.instructions().len()
+        );
+
+        // create a prover
+        let prover = ZkvmProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.program, MAX_STEPS).unwrap();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.main_trace_width(),
+            trace.length().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<ZkvmAir, H, DefaultRandomCoin<H>>(
+            proof,
+            self.pub_inputs.clone(),
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut pub_inputs = self.pub_inputs.clone();
+        pub_inputs.outputs[0] ^= 1;
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<ZkvmAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn pub_inputs(&self) -> Vec<u8> {
+        self.pub_inputs.to_bytes()
+    }
+
+    fn verify_with_pub_inputs(&self, proof: StarkProof, pub_inputs: &[u8]) -> Result<(), String> {
+        verify_with_serialized_inputs::<ZkvmAir, H>(proof, pub_inputs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a program which writes the first 2n terms of the Fibonacci sequence (mod 2^16) into
+/// memory starting at [ARRAY_BASE], reads them back to compute their XOR, and adds [ARRAY_BASE]
+/// to the result; the result is left in r0, and is also written into (and read back from) the
+/// cell at [OUTPUT_ADDR].
+///
+/// ```text
+///         movi  r3, n             # iteration counter
+///         movi  r0, 1             # a
+///         movi  r1, 1             # b
+///         movi  r2, ARRAY_BASE    # address
+/// fill:
+///         store r2, r0            # mem[r2] = a
+///         addi  r2, r2, 1
+///         store r2, r1            # mem[r2] = b
+///         addi  r2, r2, 1
+///         add   r0, r0, r1        # a = a + b
+///         add   r1, r1, r0        # b = b + a
+///         addi  r3, r3, -1
+///         bnz   r3, fill
+///
+///         movi  r0, 0             # checksum
+///         movi  r3, ARRAY_BASE
+/// reduce:
+///         addi  r2, r2, -1
+///         load  r1, r2
+///         xor   r0, r0, r1        # checksum ^= mem[r2]
+///         xor   r1, r2, r3        # r1 = 0 iff r2 == ARRAY_BASE
+///         bnz   r1, reduce
+///
+///         add   r0, r0, r2        # checksum += ARRAY_BASE
+///         movi  r3, OUTPUT_ADDR
+///         store r3, r0
+///         load  r2, r3
+///         halt
+/// ```
+///
+/// Every register is used as a destination and as both sources, and the output cell is placed
+/// far from the array, so that none of the register selector and range-checked columns of the
+/// trace is constant (which would make the degrees of some constraints lower than expected).
+pub fn build_checksum_program(n: u16) -> Program {
+    const FILL: u16 = 4;
+    const REDUCE: u16 = 14;
+
+    Program::new(vec![
+        Instruction::movi(3, n),
+        Instruction::movi(0, 1),
+        Instruction::movi(1, 1),
+        Instruction::movi(2, ARRAY_BASE),
+        // fill
+        Instruction::store(2, 0),
+        Instruction::addi(2, 2, 1),
+        Instruction::store(2, 1),
+        Instruction::addi(2, 2, 1),
+        Instruction::add(0, 0, 1),
+        Instruction::add(1, 1, 0),
+        Instruction::addi(3, 3, u16::MAX),
+        Instruction::bnz(3, FILL),
+        Instruction::movi(0, 0),
+        Instruction::movi(3, ARRAY_BASE),
+        // reduce
+        Instruction::addi(2, 2, u16::MAX),
+        Instruction::load(1, 2),
+        Instruction::xor(0, 0, 1),
+        Instruction::xor(1, 2, 3),
+        Instruction::bnz(1, REDUCE),
+        Instruction::add(0, 0, 2),
+        Instruction::movi(3, OUTPUT_ADDR),
+        Instruction::store(3, 0),
+        Instruction::load(2, 3),
+        Instruction::halt(),
+    ])
+    .expect("example program is invalid")
+}
+
+/// Computes the checksum of the first 2n terms of the Fibonacci sequence (mod 2^16) in the same
+/// way as the program built by [build_checksum_program()].
+fn compute_checksum(n: usize) -> u16 {
+    let (mut a, mut b, mut checksum) = (1u16, 1u16, 0u16);
+    for _ in 0..n {
+        checksum ^= a ^ b;
+        a = a.wrapping_add(b);
+        b = b.wrapping_add(a);
+    }
+    checksum.wrapping_add(ARRAY_BASE)
+}
@@ -0,0 +1,303 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::NUM_REGISTERS;
+use std::collections::BTreeMap;
+
+// OPCODES
+// ================================================================================================
+
+/// Operations supported by the machine; all arithmetic is performed on 16-bit words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Opcode {
+    /// rd = rs1 + rs2 (mod 2^16)
+    Add = 0,
+    /// rd = rs1 + imm (mod 2^16)
+    Addi = 1,
+    /// rd = imm
+    Movi = 2,
+    /// rd = mem[rs1]
+    Load = 3,
+    /// mem[rs1] = rs2
+    Store = 4,
+    /// rd = rs1 ^ rs2
+    Xor = 5,
+    /// pc = imm if rs1 != 0
+    Bnz = 6,
+    /// stops the execution
+    Halt = 7,
+}
+
+impl Opcode {
+    /// Returns the opcode with the specified index.
+    ///
+    /// # Errors
+    /// Returns an error if the index does not correspond to any opcode.
+    pub fn from_index(index: usize) -> Result<Self, String> {
+        match index {
+            0 => Ok(Opcode::Add),
+            1 => Ok(Opcode::Addi),
+            2 => Ok(Opcode::Movi),
+            3 => Ok(Opcode::Load),
+            4 => Ok(Opcode::Store),
+            5 => Ok(Opcode::Xor),
+            6 => Ok(Opcode::Bnz),
+            7 => Ok(Opcode::Halt),
+            _ => Err(format!("{index} is not a valid opcode")),
+        }
+    }
+
+    /// Returns the index of this opcode; the index is also the index of the flag set for this
+    /// opcode in the execution trace.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns true if instructions with this opcode write their result into a register.
+    pub fn writes_register(&self) -> bool {
+        matches!(
+            self,
+            Opcode::Add | Opcode::Addi | Opcode::Movi | Opcode::Load | Opcode::Xor
+        )
+    }
+}
+
+// INSTRUCTION
+// ================================================================================================
+
+/// An instruction of the machine.
+///
+/// Every instruction specifies a destination register `rd`, two source registers `rs1` and
+/// `rs2`, and a 16-bit immediate value; fields which are not used by an instruction are set to
+/// zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub rd: usize,
+    pub rs1: usize,
+    pub rs2: usize,
+    pub imm: u16,
+}
+
+impl Instruction {
+    fn new(opcode: Opcode, rd: usize, rs1: usize, rs2: usize, imm: u16) -> Self {
+        Instruction {
+            opcode,
+            rd,
+            rs1,
+            rs2,
+            imm,
+        }
+    }
+
+    /// Returns an instruction which computes rd = rs1 + rs2 (mod 2^16).
+    pub fn add(rd: usize, rs1: usize, rs2: usize) -> Self {
+        Self::new(Opcode::Add, rd, rs1, rs2, 0)
+    }
+
+    /// Returns an instruction which computes rd = rs1 + imm (mod 2^16); subtraction is performed
+    /// by adding the two's complement of the subtrahend.
+    pub fn addi(rd: usize, rs1: usize, imm: u16) -> Self {
+        Self::new(Opcode::Addi, rd, rs1, 0, imm)
+    }
+
+    /// Returns an instruction which sets rd = imm.
+    pub fn movi(rd: usize, imm: u16) -> Self {
+        Self::new(Opcode::Movi, rd, 0, 0, imm)
+    }
+
+    /// Returns an instruction which loads the value at address rs1 into rd.
+    pub fn load(rd: usize, rs1: usize) -> Self {
+        Self::new(Opcode::Load, rd, rs1, 0, 0)
+    }
+
+    /// Returns an instruction which stores the value of rs2 at address rs1.
+    pub fn store(rs1: usize, rs2: usize) -> Self {
+        Self::new(Opcode::Store, 0, rs1, rs2, 0)
+    }
+
+    /// Returns an instruction which computes rd = rs1 ^ rs2.
+    pub fn xor(rd: usize, rs1: usize, rs2: usize) -> Self {
+        Self::new(Opcode::Xor, rd, rs1, rs2, 0)
+    }
+
+    /// Returns an instruction which jumps to the instruction at the `target` index when rs1 is
+    /// not zero.
+    pub fn bnz(rs1: usize, target: u16) -> Self {
+        Self::new(Opcode::Bnz, 0, rs1, 0, target)
+    }
+
+    /// Returns an instruction which stops the execution.
+    pub fn halt() -> Self {
+        Self::new(Opcode::Halt, 0, 0, 0, 0)
+    }
+}
+
+// PROGRAM
+// ================================================================================================
+
+/// A sequence of instructions of the machine.
+///
+/// The program is executed starting from its first instruction with all registers set to zero
+/// and an empty memory; the execution ends when a `halt` instruction is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    /// Returns a program consisting of the specified instructions.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The program is empty or contains more than 2^16 instructions.
+    /// * An instruction refers to a register which does not exist.
+    /// * A branch targets an instruction outside of the program.
+    pub fn new(instructions: Vec<Instruction>) -> Result<Self, String> {
+        if instructions.is_empty() || instructions.len() > 1 << 16 {
+            return Err(format!(
+                "number of instructions must be between 1 and 2^16, but was {}",
+                instructions.len()
+            ));
+        }
+        for (pc, instruction) in instructions.iter().enumerate() {
+            if [instruction.rd, instruction.rs1, instruction.rs2]
+                .iter()
+                .any(|&register| register >= NUM_REGISTERS)
+            {
+                return Err(format!("instruction {pc} refers to an invalid register"));
+            }
+            if instruction.opcode == Opcode::Bnz && instruction.imm as usize >= instructions.len() {
+                return Err(format!("instruction {pc} branches outside of the program"));
+            }
+        }
+        Ok(Program { instructions })
+    }
+
+    /// Returns instructions of this program.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Returns instructions of this program padded with `halt` instructions to the next power of
+    /// two (but at least 2); this is the program table against which instruction fetches are
+    /// looked up.
+    pub fn table(&self) -> Vec<Instruction> {
+        let table_len = self.instructions.len().next_power_of_two().max(2);
+        let mut table = self.instructions.clone();
+        table.resize(table_len, Instruction::halt());
+        table
+    }
+
+    /// Executes this program and returns the executed steps.
+    ///
+    /// The returned steps end with a step executing a `halt` instruction.
+    ///
+    /// # Errors
+    /// Returns an error if the program reads a memory cell which was not written, runs past its
+    /// last instruction, or does not halt in `max_steps` steps.
+    pub fn execute(&self, max_steps: usize) -> Result<Execution, String> {
+        let mut registers = [0u16; NUM_REGISTERS];
+        let mut memory = BTreeMap::new();
+        let mut steps = Vec::new();
+        let mut pc = 0;
+        loop {
+            if steps.len() == max_steps {
+                return Err(format!("program did not halt in {max_steps} steps"));
+            }
+
+            let instruction = *self
+                .instructions
+                .get(pc)
+                .ok_or("execution ran past the end of the program")?;
+            let a = registers[instruction.rs1];
+            let b = registers[instruction.rs2];
+            let mut step = Step {
+                pc: pc as u16,
+                instruction,
+                registers,
+                a,
+                b,
+                res: 0,
+                carry: false,
+                taken: false,
+            };
+
+            pc += 1;
+            match instruction.opcode {
+                Opcode::Add => (step.res, step.carry) = a.overflowing_add(b),
+                Opcode::Addi => (step.res, step.carry) = a.overflowing_add(instruction.imm),
+                Opcode::Movi => step.res = instruction.imm,
+                Opcode::Load => {
+                    step.res = *memory.get(&a).ok_or_else(|| {
+                        format!(
+                            "instruction {} reads memory cell {a} which was not written",
+                            step.pc
+                        )
+                    })?;
+                }
+                Opcode::Store => {
+                    memory.insert(a, b);
+                }
+                Opcode::Xor => step.res = a ^ b,
+                Opcode::Bnz => {
+                    if a != 0 {
+                        step.taken = true;
+                        pc = instruction.imm as usize;
+                    }
+                }
+                Opcode::Halt => pc -= 1,
+            }
+            if instruction.opcode.writes_register() {
+                registers[instruction.rd] = step.res;
+            }
+
+            steps.push(step);
+            if instruction.opcode == Opcode::Halt {
+                break;
+            }
+        }
+
+        Ok(Execution { steps, registers })
+    }
+}
+
+// EXECUTION
+// ================================================================================================
+
+/// A single step of program execution; contains values of all registers before the step, and
+/// operands and result of the executed instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub pc: u16,
+    pub instruction: Instruction,
+    pub registers: [u16; NUM_REGISTERS],
+    pub a: u16,
+    pub b: u16,
+    pub res: u16,
+    pub carry: bool,
+    pub taken: bool,
+}
+
+impl Step {
+    /// Returns the memory access made by this step as an (address, value, is_write) tuple, or
+    /// None if the step does not access memory.
+    pub fn memory_access(&self) -> Option<(u16, u16, bool)> {
+        match self.instruction.opcode {
+            Opcode::Load => Some((self.a, self.res, false)),
+            Opcode::Store => Some((self.a, self.b, true)),
+            _ => None,
+        }
+    }
+}
+
+/// Result of executing a [Program].
+#[derive(Debug, Clone)]
+pub struct Execution {
+    pub steps: Vec<Step>,
+    /// Values of the registers after the execution.
+    pub registers: [u16; NUM_REGISTERS],
+}
@@ -0,0 +1,57 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, MerkleTree, PhantomData, Program, ProofOptions,
+    Prover, PublicInputs, ZkvmAir, ZkvmTrace,
+};
+
+// ZKVM PROVER
+// ================================================================================================
+
+pub struct ZkvmProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> ZkvmProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Executes the `program` and builds an execution trace of this execution.
+    ///
+    /// # Errors
+    /// Returns an error if the program could not be executed in at most `max_steps` steps, or
+    /// if the execution trace could not be built for the execution.
+    pub fn build_trace(&self, program: &Program, max_steps: usize) -> Result<ZkvmTrace, String> {
+        let execution = program.execute(max_steps)?;
+        let pub_inputs = PublicInputs::new(program, &execution);
+        ZkvmTrace::new(&execution, pub_inputs)
+    }
+}
+
+impl<H: ElementHasher> Prover for ZkvmProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = ZkvmAir;
+    type Trace = ZkvmTrace;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        trace.pub_inputs().clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_checksum_program, compute_checksum, BaseElement, Blake3_256, Instruction, Program,
+    ZkvmAir, ZkvmExample, ZkvmProver, MAX_STEPS,
+};
+use winterfell::{
+    debug::ConstraintDebugger, math::fields::QuadExtension, Air, AuxTraceRandElements,
+    FieldExtension, ProofOptions, Prover, Trace,
+};
+
+#[test]
+fn zkvm_test_basic_proof_verification() {
+    let zkvm = Box::new(ZkvmExample::<Blake3_256>::new(8, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification(zkvm);
+}
+
+#[test]
+fn zkvm_test_basic_proof_verification_extension() {
+    let zkvm = Box::new(ZkvmExample::<Blake3_256>::new(8, build_options(true)).unwrap());
+    crate::tests::test_basic_proof_verification(zkvm);
+}
+
+#[test]
+fn zkvm_test_basic_proof_verification_fail() {
+    let zkvm = Box::new(ZkvmExample::<Blake3_256>::new(8, build_options(false)).unwrap());
+    crate::tests::test_basic_proof_verification_fail(zkvm);
+}
+
+#[test]
+fn zkvm_test_proof_verification_with_pub_inputs() {
+    let zkvm = Box::new(ZkvmExample::<Blake3_256>::new(8, build_options(false)).unwrap());
+    crate::tests::test_proof_verification_with_pub_inputs(zkvm);
+}
+
+#[test]
+fn zkvm_test_constraint_debugger() {
+    type E = QuadExtension<BaseElement>;
+
+    let options = build_options(true);
+    let prover = ZkvmProver::<Blake3_256>::new(options.clone());
+    let program = build_checksum_program(40);
+    let mut trace = prover.build_trace(&program, MAX_STEPS).unwrap();
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    assert_eq!(compute_checksum(40), pub_inputs.outputs[0]);
+    let air = ZkvmAir::new(trace.get_info(), pub_inputs, options);
+
+    let num_rand_elements = air.trace_layout().get_aux_segment_rand_elements(0);
+    let rand_elements = (0..num_rand_elements as u128)
+        .map(|i| E::new(BaseElement::new(i + 3), BaseElement::new(i + 5)))
+        .collect::<Vec<_>>();
+    let aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    aux_rand_elements.add_segment_elements(rand_elements);
+
+    let aux_segments = [aux_segment];
+    let debugger =
+        ConstraintDebugger::with_aux_segments(&air, &trace, &aux_segments, aux_rand_elements);
+    assert!(debugger.unsatisfied_constraints().is_empty());
+}
+
+#[test]
+fn zkvm_test_execution_errors() {
+    // reading a memory cell which was not written
+    let program = Program::new(vec![Instruction::load(0, 1), Instruction::halt()]).unwrap();
+    assert!(program.execute(MAX_STEPS).is_err());
+
+    // running past the end of the program
+    let program = Program::new(vec![Instruction::movi(0, 1)]).unwrap();
+    assert!(program.execute(MAX_STEPS).is_err());
+
+    // the program does not halt within the allowed number of steps
+    let program = build_checksum_program(10);
+    assert!(program.execute(10).is_err());
+    assert!(program.execute(MAX_STEPS).is_ok());
+
+    // invalid programs
+    assert!(Program::new(vec![]).is_err());
+    assert!(Program::new(vec![Instruction::movi(4, 1)]).is_err());
+    assert!(Program::new(vec![Instruction::bnz(0, 1)]).is_err());
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
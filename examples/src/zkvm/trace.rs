@@ -0,0 +1,322 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    air::{
+        fetched_instruction, get_periodic_columns, memory_access, program_table_entry,
+        range_checked_values, range_table_value, sorted_memory_access, xor_request, xor_response,
+    },
+    BaseElement, Execution, FieldElement, Opcode, PublicInputs, StarkField, Step, A,
+    AUX_TRACE_RANDS, AUX_TRACE_WIDTH, A_INV, B, BUS_PRODUCT, CARRY, CHIPLET_BITS,
+    CHIPLET_CYCLE_LEN, CHIP_A, CHIP_ACTIVE, CHIP_A_BITS, CHIP_B, CHIP_B_BITS, CHIP_Z, CLK,
+    DELTA_HI, DELTA_LO, FETCH_MULT, FETCH_SUM, FLAGS, HALTED, IMM, MAX_TRACE_LENGTH,
+    MEMORY_PRODUCT, MEM_VALUE, MIN_TRACE_LENGTH, NUM_REGISTERS, PC, RANGE_DELTA_INV, RANGE_MULT,
+    RANGE_RES_INV, RANGE_SUM, RANGE_TABLE_LEN, RD, REGISTERS, RES, RES_HI, RES_LO, RS1, RS2,
+    S_ACTIVE, S_ADDR, S_CLK, S_SAME, S_VALUE, S_WRITE, TAKEN, TRACE_WIDTH,
+};
+use winterfell::{ColMatrix, EvaluationFrame, Trace, TraceLayout};
+
+// ZKVM TRACE
+// ================================================================================================
+
+/// Execution trace of a program.
+///
+/// The first rows of the CPU component describe the executed steps; the remaining rows hold the
+/// final state of the machine with the `halted` flag set. The sorted memory accesses and the XOR
+/// chiplet cycles are placed into the first rows of their components; the remaining rows are
+/// inactive.
+pub struct ZkvmTrace {
+    layout: TraceLayout,
+    trace: ColMatrix<BaseElement>,
+    pub_inputs: PublicInputs,
+}
+
+impl ZkvmTrace {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds an execution trace from the specified `execution` of a program described by
+    /// `pub_inputs`.
+    ///
+    /// # Errors
+    /// Returns an error if the trace would be longer than 2^16 steps.
+    pub fn new(execution: &Execution, pub_inputs: PublicInputs) -> Result<Self, String> {
+        let steps = &execution.steps;
+        let num_xors = steps
+            .iter()
+            .filter(|step| step.instruction.opcode == Opcode::Xor)
+            .count();
+        let program_table_len = pub_inputs.program.table().len();
+
+        // the last row of the trace is not constrained by transition constraints, and so it
+        // cannot hold an executed step, a program table entry, or an active chiplet cycle
+        let trace_length = (steps.len() + 1)
+            .max(program_table_len + 1)
+            .max((num_xors + 1) * CHIPLET_CYCLE_LEN)
+            .next_power_of_two()
+            .max(MIN_TRACE_LENGTH);
+        if trace_length > MAX_TRACE_LENGTH {
+            return Err(format!(
+                "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+                MAX_TRACE_LENGTH.ilog2(),
+                trace_length.ilog2()
+            ));
+        }
+
+        let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+        fill_cpu(&mut columns, steps, execution.registers);
+        fill_sorted_memory(&mut columns, steps);
+        fill_chiplet(&mut columns, steps);
+        fill_multiplicities(&mut columns, steps);
+
+        Ok(ZkvmTrace {
+            layout: TraceLayout::new(TRACE_WIDTH, [AUX_TRACE_WIDTH], [AUX_TRACE_RANDS]),
+            trace: ColMatrix::new(columns),
+            pub_inputs,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns public inputs of the program execution described by this trace.
+    pub fn pub_inputs(&self) -> &PublicInputs {
+        &self.pub_inputs
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of this trace.
+    pub fn get(&self, column: usize, step: usize) -> BaseElement {
+        self.trace.get(column, step)
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl Trace for ZkvmTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.num_rows()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.trace.read_row_into(row_idx, frame.current_mut());
+        self.trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        &self.trace
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // there is only one auxiliary segment in this example
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let trace_length = self.length();
+        let periodic_columns = get_periodic_columns(&self.pub_inputs.program);
+        let mut periodic_values = vec![BaseElement::ZERO; periodic_columns.len()];
+        let mut aux_columns = vec![vec![E::ZERO; trace_length]; AUX_TRACE_WIDTH];
+        aux_columns[MEMORY_PRODUCT][0] = E::ONE;
+        aux_columns[BUS_PRODUCT][0] = E::ONE;
+
+        let mut row = vec![BaseElement::ZERO; TRACE_WIDTH];
+        for i in 0..trace_length {
+            self.trace.read_row_into(i, &mut row);
+            for (value, column) in periodic_values.iter_mut().zip(periodic_columns.iter()) {
+                *value = column[i % column.len()];
+            }
+
+            // inverses of the range-checked values are needed for every row, but the running
+            // sums and products do not include the last row
+            let [res_lo, res_hi, delta_lo, delta_hi] = range_checked_values(&row, rand_elements);
+            let res_inv = res_lo.inv() + res_hi.inv();
+            let delta_inv = delta_lo.inv() + delta_hi.inv();
+            aux_columns[RANGE_RES_INV][i] = res_inv;
+            aux_columns[RANGE_DELTA_INV][i] = delta_inv;
+            if i == trace_length - 1 {
+                break;
+            }
+
+            // range check argument
+            let table_value = range_table_value(&periodic_values, rand_elements);
+            aux_columns[RANGE_SUM][i + 1] = aux_columns[RANGE_SUM][i] - res_inv - delta_inv
+                + E::from(row[RANGE_MULT]) / table_value;
+
+            // instruction fetch argument
+            let table_entry = program_table_entry(&periodic_values, rand_elements);
+            let mut fetch_sum = aux_columns[FETCH_SUM][i] + E::from(row[FETCH_MULT]) / table_entry;
+            if row[HALTED] == BaseElement::ZERO {
+                fetch_sum -= fetched_instruction(&row, rand_elements).inv();
+            }
+            aux_columns[FETCH_SUM][i + 1] = fetch_sum;
+
+            // memory permutation argument
+            aux_columns[MEMORY_PRODUCT][i + 1] = aux_columns[MEMORY_PRODUCT][i]
+                * memory_access(&row, rand_elements)
+                / sorted_memory_access(&row, rand_elements);
+
+            // chiplet bus
+            aux_columns[BUS_PRODUCT][i + 1] = aux_columns[BUS_PRODUCT][i]
+                * xor_request(&row, rand_elements)
+                / xor_response(&row, &periodic_values, rand_elements);
+        }
+
+        Some(ColMatrix::new(aux_columns))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Fills the CPU component with the executed `steps`; the rows following the last step hold the
+/// final state of the machine.
+fn fill_cpu(columns: &mut [Vec<BaseElement>], steps: &[Step], registers: [u16; NUM_REGISTERS]) {
+    let trace_length = columns[CLK].len();
+    let final_pc = steps.last().expect("no steps were executed").pc;
+    for i in 0..trace_length {
+        columns[CLK][i] = BaseElement::from(i as u32);
+        match steps.get(i) {
+            Some(step) => fill_step(columns, i, step),
+            None => {
+                columns[PC][i] = BaseElement::from(final_pc);
+                columns[HALTED][i] = BaseElement::ONE;
+                for (j, &register) in registers.iter().enumerate() {
+                    columns[REGISTERS + j][i] = BaseElement::from(register);
+                }
+            }
+        }
+    }
+}
+
+/// Writes registers, decoded instruction, and operands of the `step` into the specified row.
+fn fill_step(columns: &mut [Vec<BaseElement>], row: usize, step: &Step) {
+    let instruction = step.instruction;
+    columns[PC][row] = BaseElement::from(step.pc);
+    for (i, &register) in step.registers.iter().enumerate() {
+        columns[REGISTERS + i][row] = BaseElement::from(register);
+    }
+    columns[FLAGS + instruction.opcode.index()][row] = BaseElement::ONE;
+    columns[RD + instruction.rd][row] = BaseElement::ONE;
+    columns[RS1 + instruction.rs1][row] = BaseElement::ONE;
+    columns[RS2 + instruction.rs2][row] = BaseElement::ONE;
+    columns[IMM][row] = BaseElement::from(instruction.imm);
+
+    let a = BaseElement::from(step.a);
+    columns[A][row] = a;
+    columns[B][row] = BaseElement::from(step.b);
+    columns[RES][row] = BaseElement::from(step.res);
+    columns[RES_LO][row] = BaseElement::from(step.res as u8);
+    columns[RES_HI][row] = BaseElement::from((step.res >> 8) as u8);
+    columns[CARRY][row] = BaseElement::from(step.carry as u8);
+    columns[TAKEN][row] = BaseElement::from(step.taken as u8);
+    columns[A_INV][row] = a.inv();
+    if let Some((_, value, _)) = step.memory_access() {
+        columns[MEM_VALUE][row] = BaseElement::from(value);
+    }
+}
+
+/// Fills the sorted memory component with memory accesses made by the executed `steps`.
+///
+/// Inactive rows hold a dummy write so that the first sorted access is a write even when the
+/// program does not access memory.
+fn fill_sorted_memory(columns: &mut [Vec<BaseElement>], steps: &[Step]) {
+    let mut accesses = steps
+        .iter()
+        .enumerate()
+        .filter_map(|(clk, step)| {
+            step.memory_access()
+                .map(|(addr, value, is_write)| (addr, clk as u16, value, is_write))
+        })
+        .collect::<Vec<_>>();
+    accesses.sort_unstable_by_key(|&(addr, clk, _, _)| (addr, clk));
+
+    columns[S_WRITE].fill(BaseElement::ONE);
+    for (i, &(addr, clk, value, is_write)) in accesses.iter().enumerate() {
+        columns[S_ADDR][i] = BaseElement::from(addr);
+        columns[S_CLK][i] = BaseElement::from(clk);
+        columns[S_VALUE][i] = BaseElement::from(value);
+        columns[S_WRITE][i] = BaseElement::from(is_write as u8);
+        columns[S_ACTIVE][i] = BaseElement::ONE;
+
+        if let Some(&(next_addr, next_clk, _, _)) = accesses.get(i + 1) {
+            let same = next_addr == addr;
+            let delta = if same {
+                next_clk - clk - 1
+            } else {
+                next_addr - addr - 1
+            };
+            columns[S_SAME][i] = BaseElement::from(same as u8);
+            columns[DELTA_LO][i] = BaseElement::from(delta as u8);
+            columns[DELTA_HI][i] = BaseElement::from((delta >> 8) as u8);
+        }
+    }
+}
+
+/// Fills the XOR chiplet with one cycle per XOR instruction executed by the `steps`.
+fn fill_chiplet(columns: &mut [Vec<BaseElement>], steps: &[Step]) {
+    let requests = steps
+        .iter()
+        .filter(|step| step.instruction.opcode == Opcode::Xor)
+        .map(|step| (step.a, step.b));
+    for (cycle, (a, b)) in requests.enumerate() {
+        let (mut acc_a, mut acc_b) = (0u16, 0u16);
+        for j in 0..CHIPLET_CYCLE_LEN {
+            let row = cycle * CHIPLET_CYCLE_LEN + j;
+            let shift = 16 - CHIPLET_BITS * (j + 1);
+            let (limb_a, limb_b) = ((a >> shift) & 0xf, (b >> shift) & 0xf);
+            acc_a = (acc_a << CHIPLET_BITS) | limb_a;
+            acc_b = (acc_b << CHIPLET_BITS) | limb_b;
+
+            columns[CHIP_A][row] = BaseElement::from(acc_a);
+            columns[CHIP_B][row] = BaseElement::from(acc_b);
+            columns[CHIP_Z][row] = BaseElement::from(acc_a ^ acc_b);
+            for k in 0..CHIPLET_BITS {
+                columns[CHIP_A_BITS + k][row] = BaseElement::from(((limb_a >> k) & 1) as u8);
+                columns[CHIP_B_BITS + k][row] = BaseElement::from(((limb_b >> k) & 1) as u8);
+            }
+            columns[CHIP_ACTIVE][row] = BaseElement::ONE;
+        }
+    }
+}
+
+/// Fills multiplicities of the range check table and program table entries looked up by all but
+/// the last row of the trace; the multiplicity of an entry is placed into the first row holding
+/// the entry.
+fn fill_multiplicities(columns: &mut [Vec<BaseElement>], steps: &[Step]) {
+    let trace_length = columns[CLK].len();
+
+    let mut range_counts = [0u32; RANGE_TABLE_LEN];
+    for i in 0..trace_length - 1 {
+        for column in [RES_LO, RES_HI, DELTA_LO, DELTA_HI] {
+            range_counts[columns[column][i].as_int() as usize] += 1;
+        }
+    }
+    for (value, &count) in range_counts.iter().enumerate() {
+        columns[RANGE_MULT][value] = BaseElement::from(count);
+    }
+
+    for step in steps.iter() {
+        columns[FETCH_MULT][step.pc as usize] += BaseElement::ONE;
+    }
+}
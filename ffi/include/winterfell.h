@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+/*
+ * C bindings for Winterfell STARK prover and verifier.
+ *
+ * Proofs are generated and verified for computations registered in a global registry of the
+ * library; the Fibonacci examples are registered under "fib", "fib8", "fib-small", "mulfib",
+ * and "mulfib8" identifiers by default. Execution traces, public inputs, and proofs are passed
+ * across the boundary in their serialized form.
+ */
+
+#ifndef WINTERFELL_H
+#define WINTERFELL_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Outcome of a call to a function of this library. */
+typedef enum WinterStatus {
+    WINTER_STATUS_OK = 0,
+    WINTER_STATUS_NULL_POINTER = 1,
+    WINTER_STATUS_INVALID_UTF8 = 2,
+    WINTER_STATUS_UNKNOWN_AIR = 3,
+    WINTER_STATUS_INVALID_OPTIONS = 4,
+    WINTER_STATUS_INVALID_TRACE = 5,
+    WINTER_STATUS_INVALID_PUBLIC_INPUTS = 6,
+    WINTER_STATUS_INCONSISTENT_PUBLIC_INPUTS = 7,
+    WINTER_STATUS_INVALID_PROOF = 8,
+    WINTER_STATUS_PROOF_GENERATION_FAILED = 9,
+    WINTER_STATUS_VERIFICATION_FAILED = 10,
+    WINTER_STATUS_PANIC = 11,
+    WINTER_STATUS_INSUFFICIENT_SECURITY = 12,
+} WinterStatus;
+
+/* A byte buffer allocated by the library; must be released via winter_buffer_free(). */
+typedef struct WinterBuffer {
+    uint8_t *data;
+    size_t len;
+} WinterBuffer;
+
+/* STARK protocol parameters; field extension is specified by its degree (1, 2, 3, or 4). */
+typedef struct WinterProofOptions {
+    uint32_t num_queries;
+    uint32_t blowup_factor;
+    uint32_t grinding_factor;
+    uint32_t field_extension;
+    uint32_t fri_folding_factor;
+    uint32_t fri_remainder_max_degree;
+} WinterProofOptions;
+
+/* Returns a statically allocated message describing the specified status. */
+const char *winter_status_message(WinterStatus status);
+
+/* Returns true if a computation has been registered under the specified identifier. */
+bool winter_has_air(const char *air_id);
+
+/*
+ * Generates a proof of the computation registered under air_id for the serialized execution
+ * trace and public inputs, and writes the serialized proof into proof_out. On failure, an empty
+ * buffer is written into proof_out.
+ */
+WinterStatus winter_prove(const char *air_id,
+                          const uint8_t *trace,
+                          size_t trace_len,
+                          const uint8_t *pub_inputs,
+                          size_t pub_inputs_len,
+                          const WinterProofOptions *options,
+                          WinterBuffer *proof_out);
+
+/*
+ * Verifies the serialized proof of the computation registered under air_id against the
+ * serialized public inputs. Proofs with conjectured security level below min_security_level
+ * bits are rejected with WINTER_STATUS_INSUFFICIENT_SECURITY. If security_level_out is not
+ * NULL, conjectured security level of the proof (in bits) is written into it whenever the proof
+ * could be deserialized.
+ */
+WinterStatus winter_verify(const char *air_id,
+                           const uint8_t *proof,
+                           size_t proof_len,
+                           const uint8_t *pub_inputs,
+                           size_t pub_inputs_len,
+                           uint32_t min_security_level,
+                           uint32_t *security_level_out);
+
+/* Releases a buffer allocated by the library and resets it to an empty buffer. */
+void winter_buffer_free(WinterBuffer *buffer);
+
+#ifdef __cplusplus
+} /* extern "C" */
+#endif
+
+#endif /* WINTERFELL_H */
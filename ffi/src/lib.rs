@@ -0,0 +1,436 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains C bindings for Winterfell STARK prover and verifier.
+//!
+//! The bindings are declared in `include/winterfell.h` header. Proofs are generated and verified
+//! for computations registered in a global [AirRegistry] of the library. The Fibonacci examples
+//! from the `examples` crate are registered by default (see
+//! [examples::registry::EXAMPLE_AIR_IDS]); other computations can be registered via
+//! [register_air()] and [register_air_handler()] functions by Rust crates which embed this
+//! crate.
+//!
+//! All functions report their outcome via a [WinterStatus] code. Buffers returned by the library
+//! are owned by the caller, and must be released via [winter_buffer_free()]. Panics do not cross
+//! the FFI boundary; instead [WinterStatus::Panic] is returned.
+
+use std::{
+    ffi::{c_char, CStr},
+    panic::{catch_unwind, UnwindSafe},
+    ptr, slice,
+    sync::RwLock,
+};
+use winterfell::{
+    registry::{AirHandler, AirRegistry, RegistryError},
+    AcceptableOptions, Air, Deserializable, ProofOptions, Prover, StarkProof, TraceTable,
+    VerifierError,
+};
+
+#[cfg(test)]
+mod tests;
+
+// GLOBAL REGISTRY
+// ================================================================================================
+
+/// Computations which can be proven and verified via functions of this crate; the registry is
+/// initialized with the example computations on first access.
+static REGISTRY: RwLock<Option<AirRegistry>> = RwLock::new(None);
+
+/// Registers the computation proven by provers of type `P` under the specified `air_id` in the
+/// global registry of this library.
+///
+/// # Errors
+/// Returns an error if a computation with the same identifier has already been registered.
+pub fn register_air<P, F>(air_id: &str, build_prover: F) -> Result<(), RegistryError>
+where
+    P: Prover<Trace = TraceTable<<P as Prover>::BaseField>> + 'static,
+    <P::Air as Air>::PublicInputs: Deserializable,
+    F: Fn(ProofOptions) -> P + Send + Sync + 'static,
+{
+    with_registry_mut(|registry| registry.register(air_id, build_prover))
+}
+
+/// Registers the computation handled by the specified `handler` under the specified `air_id`
+/// in the global registry of this library.
+///
+/// # Errors
+/// Returns an error if a computation with the same identifier has already been registered.
+pub fn register_air_handler(
+    air_id: &str,
+    handler: Box<dyn AirHandler>,
+) -> Result<(), RegistryError> {
+    with_registry_mut(|registry| registry.register_handler(air_id, handler))
+}
+
+// TYPES
+// ================================================================================================
+
+/// Outcome of a call to a function of this library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinterStatus {
+    /// The function completed successfully.
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// An AIR identifier was not a valid UTF-8 string.
+    InvalidUtf8 = 2,
+    /// No computation has been registered under the specified identifier.
+    UnknownAir = 3,
+    /// The specified parameters did not describe valid proof options.
+    InvalidOptions = 4,
+    /// The execution trace could not be deserialized.
+    InvalidTrace = 5,
+    /// The public inputs could not be deserialized.
+    InvalidPublicInputs = 6,
+    /// The public inputs were different from the public inputs of the computation described by
+    /// the execution trace.
+    InconsistentPublicInputs = 7,
+    /// The proof could not be deserialized.
+    InvalidProof = 8,
+    /// The prover failed to generate a proof.
+    ProofGenerationFailed = 9,
+    /// The proof did not attest to a correct execution of the computation for the specified
+    /// public inputs.
+    VerificationFailed = 10,
+    /// The library panicked while executing the function.
+    Panic = 11,
+    /// The conjectured security level of the proof was below the required minimum.
+    InsufficientSecurity = 12,
+}
+
+impl WinterStatus {
+    /// Returns a message describing this status as a null-terminated string.
+    fn message(&self) -> &'static CStr {
+        let message: &'static [u8] = match self {
+            Self::Ok => b"ok\0",
+            Self::NullPointer => b"a required pointer was null\0",
+            Self::InvalidUtf8 => b"AIR identifier is not a valid UTF-8 string\0",
+            Self::UnknownAir => b"computation has not been registered\0",
+            Self::InvalidOptions => b"invalid proof options\0",
+            Self::InvalidTrace => b"failed to read execution trace\0",
+            Self::InvalidPublicInputs => b"failed to read public inputs\0",
+            Self::InconsistentPublicInputs => {
+                b"public inputs are inconsistent with the execution trace\0"
+            }
+            Self::InvalidProof => b"failed to read proof\0",
+            Self::ProofGenerationFailed => b"failed to generate proof\0",
+            Self::VerificationFailed => b"proof verification failed\0",
+            Self::Panic => b"library panicked\0",
+            Self::InsufficientSecurity => b"proof security level is below the required minimum\0",
+        };
+        CStr::from_bytes_with_nul(message).expect("invalid status message")
+    }
+}
+
+impl From<RegistryError> for WinterStatus {
+    fn from(err: RegistryError) -> Self {
+        match err {
+            RegistryError::UnknownAir(_) | RegistryError::DuplicateAir(_) => Self::UnknownAir,
+            RegistryError::InvalidExecutionTrace(_) => Self::InvalidTrace,
+            RegistryError::InvalidPublicInputs(_) => Self::InvalidPublicInputs,
+            RegistryError::InconsistentPublicInputs => Self::InconsistentPublicInputs,
+            RegistryError::InsufficientMemory(..) | RegistryError::ProverError(_) => {
+                Self::ProofGenerationFailed
+            }
+            RegistryError::VerifierError(
+                VerifierError::InsufficientConjecturedSecurity(..)
+                | VerifierError::InsufficientProvenSecurity(..)
+                | VerifierError::ProvenSecurityUnavailable(_)
+                | VerifierError::UnacceptableProofOptions,
+            ) => Self::InsufficientSecurity,
+            RegistryError::VerifierError(_) => Self::VerificationFailed,
+        }
+    }
+}
+
+/// A byte buffer allocated by this library.
+///
+/// Buffers are owned by the caller and must be released via [winter_buffer_free()].
+#[repr(C)]
+#[derive(Debug)]
+pub struct WinterBuffer {
+    /// Pointer to the first byte of the buffer; null for empty buffers.
+    pub data: *mut u8,
+    /// Number of bytes in the buffer.
+    pub len: usize,
+}
+
+impl WinterBuffer {
+    /// Returns an empty buffer.
+    pub const fn empty() -> Self {
+        WinterBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    /// Transfers ownership of the specified bytes to a new buffer.
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        if bytes.is_empty() {
+            return Self::empty();
+        }
+        let len = bytes.len();
+        let data = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+        WinterBuffer { data, len }
+    }
+}
+
+/// STARK protocol parameters; field extension is specified by its degree (i.e., 1, 2, 3, or 4).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WinterProofOptions {
+    pub num_queries: u32,
+    pub blowup_factor: u32,
+    pub grinding_factor: u32,
+    pub field_extension: u32,
+    pub fri_folding_factor: u32,
+    pub fri_remainder_max_degree: u32,
+}
+
+impl TryFrom<&WinterProofOptions> for ProofOptions {
+    type Error = WinterStatus;
+
+    fn try_from(options: &WinterProofOptions) -> Result<Self, Self::Error> {
+        // all valid parameters fit into a byte, and thus, we can rely on the deserializer to
+        // validate the parameters
+        let params = [
+            options.num_queries,
+            options.blowup_factor,
+            options.grinding_factor,
+            options.field_extension,
+            options.fri_folding_factor,
+            options.fri_remainder_max_degree,
+        ];
+        let mut bytes = Vec::with_capacity(params.len());
+        for param in params {
+            bytes.push(u8::try_from(param).map_err(|_| WinterStatus::InvalidOptions)?);
+        }
+        let result =
+            ProofOptions::read_from_bytes(&bytes).map_err(|_| WinterStatus::InvalidOptions)?;
+
+        // the deserializer also interprets spare bits of some parameters as flags of proof
+        // options extensions; parameters with such bits set are not valid
+        if plain_params(&result) != params {
+            return Err(WinterStatus::InvalidOptions);
+        }
+        Ok(result)
+    }
+}
+
+// EXPORTED FUNCTIONS
+// ================================================================================================
+
+/// Returns a null-terminated message describing the specified status.
+///
+/// The returned string is statically allocated and must not be released by the caller.
+#[no_mangle]
+pub extern "C" fn winter_status_message(status: WinterStatus) -> *const c_char {
+    status.message().as_ptr()
+}
+
+/// Returns true if a computation has been registered under the specified identifier.
+///
+/// # Safety
+/// `air_id` must be either null or a pointer to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn winter_has_air(air_id: *const c_char) -> bool {
+    let air_id = match read_air_id(air_id) {
+        Ok(air_id) => air_id,
+        Err(_) => return false,
+    };
+    guard(|| Ok(with_registry(|registry| registry.contains(air_id)))).unwrap_or(false)
+}
+
+/// Generates a proof of the computation registered under `air_id` for the serialized execution
+/// trace and public inputs, and writes the serialized proof into `proof_out`.
+///
+/// On success, ownership of the proof buffer is transferred to the caller; on failure, an empty
+/// buffer is written into `proof_out` (if it is not null).
+///
+/// # Safety
+/// * `air_id` must be either null or a pointer to a null-terminated string.
+/// * `trace` and `pub_inputs` must be either null or pointers to `trace_len` and
+///   `pub_inputs_len` readable bytes respectively.
+/// * `options` must be either null or a pointer to a valid [WinterProofOptions] struct.
+/// * `proof_out` must be either null or a pointer to a writable [WinterBuffer] struct.
+#[no_mangle]
+pub unsafe extern "C" fn winter_prove(
+    air_id: *const c_char,
+    trace: *const u8,
+    trace_len: usize,
+    pub_inputs: *const u8,
+    pub_inputs_len: usize,
+    options: *const WinterProofOptions,
+    proof_out: *mut WinterBuffer,
+) -> WinterStatus {
+    if proof_out.is_null() {
+        return WinterStatus::NullPointer;
+    }
+    proof_out.write(WinterBuffer::empty());
+
+    let result = (|| {
+        let air_id = read_air_id(air_id)?;
+        let trace = read_bytes(trace, trace_len)?;
+        let pub_inputs = read_bytes(pub_inputs, pub_inputs_len)?;
+        let options = options.as_ref().ok_or(WinterStatus::NullPointer)?;
+        let options = ProofOptions::try_from(options)?;
+        guard(|| {
+            let proof =
+                with_registry(|registry| registry.prove(air_id, trace, pub_inputs, options))?;
+            Ok(proof.to_bytes())
+        })
+    })();
+
+    match result {
+        Ok(proof) => {
+            proof_out.write(WinterBuffer::from_vec(proof));
+            WinterStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Verifies the serialized proof of the computation registered under `air_id` against the
+/// serialized public inputs.
+///
+/// The proof is rejected with [WinterStatus::InsufficientSecurity] if its conjectured security
+/// level is below `min_security_level` bits. If `security_level_out` is not null, conjectured
+/// security level of the proof (in bits) is written into it whenever the proof could be
+/// deserialized, regardless of whether the proof is valid.
+///
+/// # Safety
+/// * `air_id` must be either null or a pointer to a null-terminated string.
+/// * `proof` and `pub_inputs` must be either null or pointers to `proof_len` and
+///   `pub_inputs_len` readable bytes respectively.
+/// * `security_level_out` must be either null or a pointer to a writable `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn winter_verify(
+    air_id: *const c_char,
+    proof: *const u8,
+    proof_len: usize,
+    pub_inputs: *const u8,
+    pub_inputs_len: usize,
+    min_security_level: u32,
+    security_level_out: *mut u32,
+) -> WinterStatus {
+    let result = (|| {
+        let air_id = read_air_id(air_id)?;
+        let proof = read_bytes(proof, proof_len)?;
+        let pub_inputs = read_bytes(pub_inputs, pub_inputs_len)?;
+        guard(|| {
+            let proof = StarkProof::from_bytes(proof).map_err(|_| WinterStatus::InvalidProof)?;
+            with_registry(|registry| {
+                let security_level = registry.security_level(air_id, &proof)?;
+                if !security_level_out.is_null() {
+                    // the pointer was checked to be non-null, and is writable per the contract
+                    // of this function
+                    unsafe { security_level_out.write(security_level) };
+                }
+                let acceptable_options =
+                    AcceptableOptions::MinConjecturedSecurity(min_security_level);
+                registry.verify(air_id, proof, pub_inputs, &acceptable_options)
+            })?;
+            Ok(())
+        })
+    })();
+
+    match result {
+        Ok(()) => WinterStatus::Ok,
+        Err(status) => status,
+    }
+}
+
+/// Releases the memory of a buffer allocated by this library, and resets the buffer to an empty
+/// buffer; calling this function on an empty buffer has no effect.
+///
+/// # Safety
+/// `buffer` must be either null or a pointer to a buffer allocated by this library which has
+/// not been modified by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn winter_buffer_free(buffer: *mut WinterBuffer) {
+    if let Some(buffer) = buffer.as_mut() {
+        if !buffer.data.is_null() {
+            let bytes = ptr::slice_from_raw_parts_mut(buffer.data, buffer.len);
+            drop(Box::from_raw(bytes));
+        }
+        *buffer = WinterBuffer::empty();
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Invokes `f` with the global registry of this library, initializing the registry with example
+/// computations if needed.
+///
+/// Only a shared lock on the registry is held while `f` is executed, and thus, multiple proofs
+/// can be generated and verified concurrently.
+fn with_registry<T>(f: impl FnOnce(&AirRegistry) -> T) -> T {
+    // panics are caught at the FFI boundary, and a panic while the registry is locked cannot
+    // leave the registry in an inconsistent state; thus, poisoning can be ignored
+    let registry = REGISTRY.read().unwrap_or_else(|err| err.into_inner());
+    if let Some(registry) = registry.as_ref() {
+        return f(registry);
+    }
+    drop(registry);
+    with_registry_mut(|registry| f(registry))
+}
+
+/// Invokes `f` with an exclusive lock on the global registry of this library, initializing the
+/// registry with example computations if needed.
+fn with_registry_mut<T>(f: impl FnOnce(&mut AirRegistry) -> T) -> T {
+    let mut registry = REGISTRY.write().unwrap_or_else(|err| err.into_inner());
+    let registry = registry.get_or_insert_with(|| {
+        let mut registry = AirRegistry::new();
+        examples::registry::register_examples(&mut registry).expect("failed to register examples");
+        registry
+    });
+    f(registry)
+}
+
+/// Executes `f` converting panics into [WinterStatus::Panic].
+fn guard<T, F>(f: F) -> Result<T, WinterStatus>
+where
+    F: FnOnce() -> Result<T, WinterStatus> + UnwindSafe,
+{
+    catch_unwind(f).unwrap_or(Err(WinterStatus::Panic))
+}
+
+/// Reads an AIR identifier from a null-terminated string.
+unsafe fn read_air_id<'a>(air_id: *const c_char) -> Result<&'a str, WinterStatus> {
+    if air_id.is_null() {
+        return Err(WinterStatus::NullPointer);
+    }
+    CStr::from_ptr(air_id)
+        .to_str()
+        .map_err(|_| WinterStatus::InvalidUtf8)
+}
+
+/// Returns a slice of `len` bytes starting at `data`; `data` can be null only if `len` is 0.
+unsafe fn read_bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], WinterStatus> {
+    if data.is_null() {
+        return if len == 0 {
+            Ok(&[])
+        } else {
+            Err(WinterStatus::NullPointer)
+        };
+    }
+    Ok(slice::from_raw_parts(data, len))
+}
+
+/// Returns the parameters of the `ProofOptions` constructor which describe the specified options;
+/// field extension is specified by its degree.
+fn plain_params(options: &ProofOptions) -> [u32; 6] {
+    let fri_options = options.to_fri_options();
+    [
+        options.num_queries() as u32,
+        options.blowup_factor() as u32,
+        options.grinding_factor(),
+        options.field_extension().degree(),
+        fri_options.folding_factor() as u32,
+        fri_options.remainder_max_degree() as u32,
+    ]
+}
@@ -0,0 +1,321 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    winter_buffer_free, winter_has_air, winter_prove, winter_status_message, winter_verify,
+    WinterBuffer, WinterProofOptions, WinterStatus,
+};
+use examples::fibonacci::fib2::FibProver;
+use std::{ffi::CStr, ptr};
+use winterfell::{
+    crypto::hashers::Blake3_256, math::fields::f128::BaseElement, ProofOptions, Serializable,
+    StarkProof, Trace, TraceTable,
+};
+
+const FIB: &[u8] = b"fib\0";
+const OPTIONS: WinterProofOptions = WinterProofOptions {
+    num_queries: 32,
+    blowup_factor: 8,
+    grinding_factor: 0,
+    field_extension: 1,
+    fri_folding_factor: 4,
+    fri_remainder_max_degree: 7,
+};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_and_verify() {
+    let (trace, pub_inputs) = build_fib_inputs();
+
+    let proof = prove(FIB, &trace, &pub_inputs, &OPTIONS).unwrap();
+    let mut security_level = 0;
+    let status = unsafe {
+        winter_verify(
+            FIB.as_ptr().cast(),
+            proof.as_ptr(),
+            proof.len(),
+            pub_inputs.as_ptr(),
+            pub_inputs.len(),
+            security_level_of(&OPTIONS),
+            &mut security_level,
+        )
+    };
+    assert_eq!(WinterStatus::Ok, status);
+    assert_eq!(security_level_of(&OPTIONS), security_level);
+
+    // proofs with security level below the required minimum are rejected
+    let status = unsafe {
+        winter_verify(
+            FIB.as_ptr().cast(),
+            proof.as_ptr(),
+            proof.len(),
+            pub_inputs.as_ptr(),
+            pub_inputs.len(),
+            security_level + 1,
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(WinterStatus::InsufficientSecurity, status);
+
+    // verify against wrong public inputs
+    let wrong_inputs = BaseElement::new(42).to_bytes();
+    assert_eq!(
+        WinterStatus::VerificationFailed,
+        verify(FIB, &proof, &wrong_inputs)
+    );
+
+    // verify a truncated proof
+    let truncated_proof = &proof[..proof.len() - 1];
+    assert_eq!(
+        WinterStatus::InvalidProof,
+        verify(FIB, truncated_proof, &pub_inputs)
+    );
+    assert_eq!(
+        WinterStatus::InvalidPublicInputs,
+        verify(FIB, &proof, &pub_inputs[1..])
+    );
+    assert_eq!(
+        WinterStatus::UnknownAir,
+        verify(b"foo\0", &proof, &pub_inputs)
+    );
+}
+
+#[test]
+fn prove_invalid_inputs() {
+    let (trace, pub_inputs) = build_fib_inputs();
+
+    let err = prove(b"foo\0", &trace, &pub_inputs, &OPTIONS).unwrap_err();
+    assert_eq!(WinterStatus::UnknownAir, err);
+    let err = prove(b"\xff\0", &trace, &pub_inputs, &OPTIONS).unwrap_err();
+    assert_eq!(WinterStatus::InvalidUtf8, err);
+
+    let err = prove(FIB, &trace[1..], &pub_inputs, &OPTIONS).unwrap_err();
+    assert_eq!(WinterStatus::InvalidTrace, err);
+    let err = prove(FIB, &trace, &pub_inputs[1..], &OPTIONS).unwrap_err();
+    assert_eq!(WinterStatus::InvalidPublicInputs, err);
+    let wrong_inputs = BaseElement::new(42).to_bytes();
+    let err = prove(FIB, &trace, &wrong_inputs, &OPTIONS).unwrap_err();
+    assert_eq!(WinterStatus::InconsistentPublicInputs, err);
+
+    let options = WinterProofOptions {
+        blowup_factor: 3,
+        ..OPTIONS
+    };
+    let err = prove(FIB, &trace, &pub_inputs, &options).unwrap_err();
+    assert_eq!(WinterStatus::InvalidOptions, err);
+    let options = WinterProofOptions {
+        num_queries: 256,
+        ..OPTIONS
+    };
+    let err = prove(FIB, &trace, &pub_inputs, &options).unwrap_err();
+    assert_eq!(WinterStatus::InvalidOptions, err);
+
+    // values with bits reserved for proof options extensions are rejected as well
+    for options in [
+        WinterProofOptions {
+            blowup_factor: 9,
+            ..OPTIONS
+        },
+        WinterProofOptions {
+            grinding_factor: 0x80,
+            ..OPTIONS
+        },
+        WinterProofOptions {
+            field_extension: 0x11,
+            ..OPTIONS
+        },
+    ] {
+        let err = prove(FIB, &trace, &pub_inputs, &options).unwrap_err();
+        assert_eq!(WinterStatus::InvalidOptions, err);
+    }
+}
+
+#[test]
+fn null_pointers() {
+    let (trace, pub_inputs) = build_fib_inputs();
+    let mut proof = WinterBuffer::empty();
+    unsafe {
+        let status = winter_prove(
+            FIB.as_ptr().cast(),
+            trace.as_ptr(),
+            trace.len(),
+            pub_inputs.as_ptr(),
+            pub_inputs.len(),
+            &OPTIONS,
+            ptr::null_mut(),
+        );
+        assert_eq!(WinterStatus::NullPointer, status);
+
+        let status = winter_prove(
+            FIB.as_ptr().cast(),
+            trace.as_ptr(),
+            trace.len(),
+            pub_inputs.as_ptr(),
+            pub_inputs.len(),
+            ptr::null(),
+            &mut proof,
+        );
+        assert_eq!(WinterStatus::NullPointer, status);
+        assert!(proof.data.is_null());
+
+        let status = winter_verify(
+            ptr::null(),
+            ptr::null(),
+            0,
+            ptr::null(),
+            0,
+            0,
+            ptr::null_mut(),
+        );
+        assert_eq!(WinterStatus::NullPointer, status);
+        let status = winter_verify(
+            FIB.as_ptr().cast(),
+            ptr::null(),
+            1,
+            ptr::null(),
+            0,
+            0,
+            ptr::null_mut(),
+        );
+        assert_eq!(WinterStatus::NullPointer, status);
+        let status = winter_verify(
+            FIB.as_ptr().cast(),
+            ptr::null(),
+            0,
+            ptr::null(),
+            0,
+            0,
+            ptr::null_mut(),
+        );
+        assert_eq!(WinterStatus::InvalidProof, status);
+
+        assert!(!winter_has_air(ptr::null()));
+        winter_buffer_free(ptr::null_mut());
+        winter_buffer_free(&mut proof);
+    }
+}
+
+#[test]
+fn registered_airs() {
+    unsafe {
+        assert!(winter_has_air(FIB.as_ptr().cast()));
+        assert!(winter_has_air(b"fib-small\0".as_ptr().cast()));
+        assert!(!winter_has_air(b"foo\0".as_ptr().cast()));
+    }
+}
+
+#[test]
+fn status_messages() {
+    for status in [
+        WinterStatus::Ok,
+        WinterStatus::Panic,
+        WinterStatus::InvalidProof,
+        WinterStatus::InsufficientSecurity,
+    ] {
+        let message = unsafe { CStr::from_ptr(winter_status_message(status)) };
+        assert!(!message.to_bytes().is_empty());
+    }
+}
+
+#[test]
+fn header_declares_all_functions() {
+    // make sure the header is kept in sync with functions and types exported by the library
+    let header = include_str!("../include/winterfell.h");
+    let source = include_str!("lib.rs");
+    let mut num_functions = 0;
+    for line in source.lines() {
+        if let Some(rest) = line.split("extern \"C\" fn ").nth(1) {
+            let name = rest.split('(').next().unwrap();
+            assert!(
+                header.contains(&format!("{name}(")),
+                "{name} is not declared in the header"
+            );
+            num_functions += 1;
+        }
+    }
+    assert_eq!(5, num_functions);
+
+    for (status, name) in [
+        (WinterStatus::Ok, "OK"),
+        (WinterStatus::InvalidUtf8, "INVALID_UTF8"),
+        (
+            WinterStatus::InconsistentPublicInputs,
+            "INCONSISTENT_PUBLIC_INPUTS",
+        ),
+        (WinterStatus::Panic, "PANIC"),
+        (WinterStatus::InsufficientSecurity, "INSUFFICIENT_SECURITY"),
+    ] {
+        let declaration = format!("WINTER_STATUS_{name} = {},", status as u32);
+        assert!(
+            header.contains(&declaration),
+            "{declaration} is not declared in the header"
+        );
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_fib_inputs() -> (Vec<u8>, Vec<u8>) {
+    let options = ProofOptions::try_from(&OPTIONS).unwrap();
+    let trace: TraceTable<BaseElement> =
+        FibProver::<Blake3_256<BaseElement>>::new(options).build_trace(64);
+    let result = trace.get(1, trace.length() - 1);
+    (trace.to_bytes(), result.to_bytes())
+}
+
+fn prove(
+    air_id: &[u8],
+    trace: &[u8],
+    pub_inputs: &[u8],
+    options: &WinterProofOptions,
+) -> Result<Vec<u8>, WinterStatus> {
+    let mut buffer = WinterBuffer::empty();
+    let status = unsafe {
+        winter_prove(
+            air_id.as_ptr().cast(),
+            trace.as_ptr(),
+            trace.len(),
+            pub_inputs.as_ptr(),
+            pub_inputs.len(),
+            options,
+            &mut buffer,
+        )
+    };
+    if status != WinterStatus::Ok {
+        assert!(buffer.data.is_null());
+        return Err(status);
+    }
+    let proof = unsafe { std::slice::from_raw_parts(buffer.data, buffer.len) }.to_vec();
+    unsafe { winter_buffer_free(&mut buffer) };
+    assert!(buffer.data.is_null());
+    Ok(proof)
+}
+
+fn verify(air_id: &[u8], proof: &[u8], pub_inputs: &[u8]) -> WinterStatus {
+    unsafe {
+        winter_verify(
+            air_id.as_ptr().cast(),
+            proof.as_ptr(),
+            proof.len(),
+            pub_inputs.as_ptr(),
+            pub_inputs.len(),
+            0,
+            ptr::null_mut(),
+        )
+    }
+}
+
+/// Returns conjectured security level of a proof of the Fibonacci example generated with the
+/// specified options.
+fn security_level_of(options: &WinterProofOptions) -> u32 {
+    let (trace, pub_inputs) = build_fib_inputs();
+    let proof = prove(FIB, &trace, &pub_inputs, options).unwrap();
+    StarkProof::from_bytes(&proof)
+        .unwrap()
+        .security_level::<Blake3_256<BaseElement>>(true)
+}
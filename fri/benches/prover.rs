@@ -27,7 +27,7 @@ pub fn build_layers(c: &mut Criterion) {
             BenchmarkId::new("build_layers", domain_size),
             &evaluations,
             |b, e| {
-                let mut prover = FriProver::new(options.clone());
+                let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
                 b.iter_batched(
                     || e.clone(),
                     |evaluations| {
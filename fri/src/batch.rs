@@ -0,0 +1,245 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains components for proving low degree of multiple polynomials with a single FRI proof.
+//!
+//! Given evaluations of polynomials *f<sub>0</sub>*, ..., *f<sub>k</sub>* over the same domain,
+//! where polynomial *f<sub>i</sub>* is claimed to have degree at most *d<sub>i</sub>*, the
+//! polynomials are combined into a single polynomial of degree at most *D* =
+//! max(*d<sub>i</sub>*) as follows:
+//!
+//! *f*(x) = Σ *f<sub>i</sub>*(x) * (α<sub>i</sub> + β<sub>i</sub> * x<sup>*D* -
+//! *d<sub>i</sub>*</sup>)
+//!
+//! where α<sub>i</sub> and β<sub>i</sub> are drawn uniformly at random from the entire field by
+//! the verifier. The degree-correction terms x<sup>*D* - *d<sub>i</sub>*</sup> make sure that a
+//! polynomial whose degree exceeds its bound raises the degree of *f* above *D*; without them,
+//! the excess degree of a polynomial with a small bound could go undetected. Thus, with high
+//! probability, *f* has degree at most *D* only if every *f<sub>i</sub>* has degree at most
+//! *d<sub>i</sub>*, and a single FRI proof for *f* attests to the degrees of all polynomials.
+//!
+//! The prover combines the evaluations via [BatchCombiner::combine_evaluations()] and passes the
+//! result to a [FriProver](crate::FriProver). The verifier draws the same coefficients, combines
+//! the values of the polynomials at the queried positions via
+//! [BatchCombiner::combine_queries()], and passes the result to a
+//! [FriVerifier](crate::FriVerifier) instantiated for degree *D*.
+
+use crypto::{RandomCoin, RandomCoinError};
+use math::{get_power_series_with_offset, Domain, FieldElement};
+use utils::{collections::Vec, iter_mut};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// BATCH COMBINER
+// ================================================================================================
+
+/// Combines evaluations of multiple polynomials with distinct degree bounds into evaluations of
+/// a single polynomial which can be proven to be of low degree with a single FRI proof.
+///
+/// See the [module documentation](self) for a description of the combination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchCombiner<E: FieldElement> {
+    degree_bounds: Vec<usize>,
+    max_degree: usize,
+    coefficients: Vec<(E, E)>,
+}
+
+impl<E: FieldElement> BatchCombiner<E> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new combiner for polynomials with the specified degree bounds instantiated with
+    /// the provided combination coefficients.
+    ///
+    /// The combined polynomial has degree at most `max_degree`; each pair of coefficients
+    /// (α<sub>i</sub>, β<sub>i</sub>) is applied to the polynomial with degree bound
+    /// `degree_bounds[i]`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `degree_bounds` is empty.
+    /// * Any of the degree bounds is greater than `max_degree`.
+    /// * The number of coefficient pairs is not equal to the number of degree bounds.
+    pub fn new(degree_bounds: Vec<usize>, max_degree: usize, coefficients: Vec<(E, E)>) -> Self {
+        assert!(
+            !degree_bounds.is_empty(),
+            "at least one polynomial must be combined"
+        );
+        for &degree_bound in degree_bounds.iter() {
+            assert!(
+                degree_bound <= max_degree,
+                "degree bound {degree_bound} cannot be greater than max degree {max_degree}"
+            );
+        }
+        assert_eq!(
+            degree_bounds.len(),
+            coefficients.len(),
+            "number of coefficient pairs must be equal to the number of polynomials"
+        );
+        BatchCombiner {
+            degree_bounds,
+            max_degree,
+            coefficients,
+        }
+    }
+
+    /// Returns a new combiner for polynomials with the specified degree bounds with coefficients
+    /// drawn from the provided `public_coin`.
+    ///
+    /// Two coefficients are drawn for each polynomial: α<sub>i</sub> and then β<sub>i</sub>;
+    /// both the prover and the verifier must draw the coefficients from coins in the same state.
+    ///
+    /// # Errors
+    /// Returns an error if a valid coefficient could not be drawn from the coin.
+    ///
+    /// # Panics
+    /// Panics if `degree_bounds` is empty, or if any of the degree bounds is greater than
+    /// `max_degree`.
+    pub fn draw<R>(
+        degree_bounds: Vec<usize>,
+        max_degree: usize,
+        public_coin: &mut R,
+    ) -> Result<Self, RandomCoinError>
+    where
+        R: RandomCoin<BaseField = E::BaseField>,
+    {
+        let mut coefficients = Vec::with_capacity(degree_bounds.len());
+        for _ in 0..degree_bounds.len() {
+            coefficients.push((public_coin.draw()?, public_coin.draw()?));
+        }
+        Ok(Self::new(degree_bounds, max_degree, coefficients))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of polynomials combined by this combiner.
+    pub fn num_polys(&self) -> usize {
+        self.degree_bounds.len()
+    }
+
+    /// Returns the degree bounds of the polynomials combined by this combiner.
+    pub fn degree_bounds(&self) -> &[usize] {
+        &self.degree_bounds
+    }
+
+    /// Returns the maximum degree of the combined polynomial; this is the degree for which the
+    /// combined polynomial should be proven by FRI.
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    /// Returns the coefficients used to combine the polynomials.
+    pub fn coefficients(&self) -> &[(E, E)] {
+        &self.coefficients
+    }
+
+    // COMBINATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns evaluations of the combined polynomial over the domain over which `evaluations`
+    /// were computed.
+    ///
+    /// `evaluations[i]` must contain evaluations of i-th polynomial over the domain of size
+    /// `evaluations[i].len()` shifted by `domain_offset` in natural order.
+    ///
+    /// When `concurrent` feature is enabled, the evaluations are combined in multiple threads.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of evaluation vectors is not equal to the number of polynomials.
+    /// * The evaluation vectors have different lengths, or their length is not a power of two.
+    pub fn combine_evaluations(
+        &self,
+        evaluations: &[Vec<E>],
+        domain_offset: E::BaseField,
+    ) -> Vec<E> {
+        assert_eq!(
+            self.num_polys(),
+            evaluations.len(),
+            "expected evaluations of {} polynomials, but received {}",
+            self.num_polys(),
+            evaluations.len()
+        );
+        let domain = Domain::with_offset(evaluations[0].len(), domain_offset);
+        for evaluations in evaluations.iter() {
+            assert_eq!(
+                domain.size(),
+                evaluations.len(),
+                "all polynomials must be evaluated over the same domain"
+            );
+        }
+
+        let mut result = E::zeroed_vector(domain.size());
+        for ((evaluations, &degree_bound), &(alpha, beta)) in evaluations
+            .iter()
+            .zip(self.degree_bounds.iter())
+            .zip(self.coefficients.iter())
+        {
+            // x^(D - d_i) over the domain is a power series of g^(D - d_i) with offset
+            // s^(D - d_i), where g is the domain generator and s is the domain offset
+            let correction = self.max_degree - degree_bound;
+            let correction_powers = get_power_series_with_offset(
+                domain.generator().exp((correction as u64).into()),
+                domain_offset.exp((correction as u64).into()),
+                domain.size(),
+            );
+            iter_mut!(result)
+                .zip(evaluations)
+                .zip(correction_powers)
+                .for_each(|((r, &value), x)| *r += value * (alpha + beta.mul_base(x)));
+        }
+        result
+    }
+
+    /// Returns values of the combined polynomial at the specified positions of the domain of size
+    /// `domain_size` shifted by `domain_offset`.
+    ///
+    /// `values[i]` must contain values of i-th polynomial at the specified `positions`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of value vectors is not equal to the number of polynomials.
+    /// * The number of values for any of the polynomials is not equal to the number of positions.
+    /// * `domain_size` is not a power of two.
+    pub fn combine_queries(
+        &self,
+        values: &[Vec<E>],
+        positions: &[usize],
+        domain_size: usize,
+        domain_offset: E::BaseField,
+    ) -> Vec<E> {
+        assert_eq!(
+            self.num_polys(),
+            values.len(),
+            "expected values of {} polynomials, but received {}",
+            self.num_polys(),
+            values.len()
+        );
+        for values in values.iter() {
+            assert_eq!(
+                positions.len(),
+                values.len(),
+                "number of values must be equal to the number of positions"
+            );
+        }
+        let domain = Domain::with_offset(domain_size, domain_offset);
+
+        let mut result = E::zeroed_vector(positions.len());
+        for (i, (r, &position)) in result.iter_mut().zip(positions).enumerate() {
+            let x = domain.element_at(position);
+            for ((values, &degree_bound), &(alpha, beta)) in values
+                .iter()
+                .zip(self.degree_bounds.iter())
+                .zip(self.coefficients.iter())
+            {
+                let correction = x.exp(((self.max_degree - degree_bound) as u64).into());
+                *r += values[i] * (alpha + beta.mul_base(correction));
+            }
+        }
+        result
+    }
+}
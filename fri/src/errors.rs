@@ -6,6 +6,7 @@
 use core::fmt;
 
 use crypto::RandomCoinError;
+use utils::string::String;
 
 // VERIFIER ERROR
 // ================================================================================================
@@ -16,10 +17,20 @@ pub enum VerifierError {
     /// Attempt to draw a random value from a public coin failed.
     RandomCoinError(RandomCoinError),
     /// Folding factor specified for the protocol is not supported. Currently, supported folding
-    /// factors are: 4, 8, and 16.
+    /// factors are: 2, 4, 8, and 16.
     UnsupportedFoldingFactor(usize),
+    /// Number of FRI layer commitments read from the channel does not match the number of FRI
+    /// layers implied by the protocol options (the commitment to the remainder is included).
+    NumLayerCommitmentsMismatch(usize, usize),
+    /// Number of FRI layer proof-of-work nonces read from the channel does not match the number
+    /// of FRI layers for which proof-of-work is required by the protocol options.
+    NumLayerNoncesMismatch(usize, usize),
+    /// Proof-of-work nonce for one of the FRI layers did not satisfy the layer grinding factor.
+    LayerPowVerificationFailed(usize),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
+    /// A query position is outside of the evaluation domain.
+    QueryPositionOutOfBounds(usize, usize),
     /// Evaluations at queried positions did not match layer commitment made by the prover.
     LayerCommitmentMismatch,
     /// Degree-respecting projection was not performed correctly at one of the layers.
@@ -28,12 +39,17 @@ pub enum VerifierError {
     RemainderCommitmentMismatch,
     /// Degree-respecting projection was not performed correctly at the last layer.
     InvalidRemainderFolding,
+    /// A value queried at one of the STIR rounds is at a point where the quotient of the round
+    /// polynomial is undefined.
+    UndefinedRoundQuotient(usize),
     /// FRI remainder expected degree is greater than number of remainder values.
     RemainderDegreeNotValid,
     /// FRI remainder degree is greater than the polynomial degree expected for the last layer.
     RemainderDegreeMismatch(usize),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
+    /// A part of a proof read by the verifier could not be parsed.
+    ProofDeserializationError(String),
 }
 
 impl fmt::Display for VerifierError {
@@ -46,9 +62,21 @@ impl fmt::Display for VerifierError {
             Self::UnsupportedFoldingFactor(value) => {
                 write!(f, "folding factor {value} is not currently supported")
             }
+            Self::NumLayerCommitmentsMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI layer commitments, but {actual} were provided")
+            }
+            Self::NumLayerNoncesMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI layer proof-of-work nonces, but {actual} were provided")
+            }
+            Self::LayerPowVerificationFailed(layer) => {
+                write!(f, "proof-of-work verification failed at FRI layer {layer}")
+            }
             Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
                 "the number of query positions must be the same as the number of polynomial evaluations, but {num_positions} and {num_evaluations} were provided"
             ),
+            Self::QueryPositionOutOfBounds(position, domain_size) => {
+                write!(f, "query position {position} is outside of the domain of size {domain_size}")
+            }
             Self::LayerCommitmentMismatch => {
                 write!(f, "FRI queries did not match layer commitment made by the prover")
             }
@@ -61,6 +89,9 @@ impl fmt::Display for VerifierError {
             Self::InvalidRemainderFolding => {
                 write!(f, "degree-respecting projection is inconsistent at the last FRI layer")
             }
+            Self::UndefinedRoundQuotient(round) => {
+                write!(f, "round quotient is undefined at a point queried at STIR round {round}")
+            }
             Self::RemainderDegreeNotValid => {
                 write!(f, "FRI remainder expected degree is greater than number of remainder values")
             }
@@ -70,6 +101,9 @@ impl fmt::Display for VerifierError {
             Self::DegreeTruncation(degree, folding, layer) => {
                 write!(f, "degree reduction from {degree} by {folding} at layer {layer} results in degree truncation")
             }
+            Self::ProofDeserializationError(msg) => {
+                write!(f, "proof deserialization failed: {msg}")
+            }
         }
     }
 }
@@ -13,7 +13,7 @@ use utils::iterators::*;
 
 use math::{
     fft::{get_inv_twiddles, serial_fft},
-    get_power_series_with_offset, polynom, FieldElement, StarkField,
+    get_power_series_with_offset, linear_combination, Domain, FieldElement, StarkField,
 };
 use utils::{collections::Vec, iter_mut, uninit_vector};
 
@@ -109,8 +109,9 @@ where
                 offset *= domain_offset;
             }
 
-            // evaluate the polynomial at alpha, and save the result
-            *result = polynom::eval(&poly, alpha)
+            // evaluate the polynomial at alpha (i.e., combine its coefficients using powers of
+            // alpha), and save the result
+            *result = linear_combination(&poly, alpha)
         });
 
     result
@@ -181,7 +182,11 @@ fn get_inv_offsets<B>(domain_size: usize, domain_offset: B, folding_factor: usiz
 where
     B: StarkField,
 {
-    let n = domain_size * folding_factor;
-    let g = B::get_root_of_unity(n.ilog2());
-    get_power_series_with_offset(g.inv(), domain_offset.inv(), domain_size)
+    // the offsets are inverses of the first domain_size elements of the source domain
+    let source_domain = Domain::with_offset(domain_size * folding_factor, domain_offset).inv();
+    get_power_series_with_offset(
+        source_domain.generator(),
+        source_domain.offset(),
+        domain_size,
+    )
 }
@@ -20,12 +20,19 @@
 //!    the size of the domain over which the polynomial is evaluated falls under
 //!    `max_remainder_size` parameter. While performing the reduction, the prover writes a set of
 //!    layer commitments into the [ProverChannel]. These commitments should be recorded and sent
-//!    to the verifier as they will be needed during the proof verification procedure.
+//!    to the verifier as they will be needed during the proof verification procedure. The
+//!    remaining evaluations are interpolated into a remainder polynomial; the last commitment is
+//!    a hash of the coefficients of this polynomial rather than a Merkle tree root.
 //! 2. Then, the query phase of the protocol is executed via
 //!    [build_proof()](prover::FriProver::build_proof()) function. The output of this function is
 //!    an instance of the [FriProof] struct. When FRI is executed as a part of the STARK protocol,
 //!    FRI proof is included into a STARK proof.
 //!
+//! Evaluations of FRI layers are kept in a [FriLayerStorage] between the two phases. By default,
+//! they are kept in memory; when the crate is compiled with `std` feature enabled, they can also
+//! be spilled to disk via [FileLayerStorage]. Evaluations of the first layer can be provided via
+//! an iterator using [build_layers_from_iter()](prover::FriProver::build_layers_from_iter()).
+//!
 //! When the crate is compiled with `concurrent` feature enabled, proof generation will be
 //! performed in multiple threads (usually, as many threads as there are logical cores on the
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment variable.
@@ -42,7 +49,30 @@
 //! 3. Finally, the query phase of the FRI protocol should be executed via
 //!    [verify()](FriVerifier::verify()) function. Note that query values at the first FRI layer
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
-//!    remaining layers, the verifier reads from the specified verifier channel.
+//!    remaining layers, the verifier reads from the specified verifier channel. The remainder
+//!    polynomial is read from the channel in coefficient form; the verifier checks it against
+//!    the remainder commitment, makes sure its degree does not exceed the degree implied by the
+//!    folding factors, and evaluates it directly.
+//!
+//! All of these steps are also performed by the [verify_proof()] function, which can be used to
+//! verify FRI proofs outside of the STARK protocol (e.g., when FRI is used as a component of a
+//! polynomial commitment scheme). Proof components obtained by other means can be read by the
+//! verifier via [DefaultVerifierChannel::from_raw_parts()].
+//!
+//! # Batching
+//! Multiple polynomials with different degree bounds can be proven to be of low degree with a
+//! single FRI proof using the [batch] module. [BatchCombiner](batch::BatchCombiner) combines
+//! evaluations of the polynomials into evaluations of a single polynomial using random
+//! coefficients and degree-correction terms; the prover generates a FRI proof for the combined
+//! evaluations, and the verifier combines the values of the individual polynomials at the
+//! queried positions in the same way.
+//!
+//! # STIR
+//! The [stir] module contains an implementation of the STIR protocol, a low-degree test which
+//! reduces the degree of a polynomial by the folding factor in every round while shrinking the
+//! evaluation domain only by a factor of 2; this allows the verifier to make fewer queries in
+//! later rounds. A STARK proof uses STIR in place of FRI when its proof options select STIR as
+//! the low-degree test. See the [module documentation](stir) for details.
 //!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
@@ -52,14 +82,19 @@
 //! * Extension field,
 //! * Domain blowup factor,
 //! * Hash function (used for Merkle tree commitments),
-//! * Folding factor (used for degree reduction for each FRI layer),
-//! * Maximum size of the last FRI layer.
+//! * Folding factor (used for degree reduction for each FRI layer); folding factors 2, 4, 8, and
+//!   16 are supported, and different layers can be folded by different factors via a folding
+//!   schedule (see [FriOptions::with_folding_schedule()]),
+//! * Maximum size of the last FRI layer,
+//! * Number of bits of proof-of-work required before each folding challenge is drawn (see
+//!   [FriOptions::with_layer_grinding()]); by default, no proof-of-work is required.
 //!
 //! # References
 //! * StarkWare's blog post on [Low Degree Testing](https://medium.com/starkware/low-degree-testing-f7614f5172db)
 //! * [Fast Reed-Solomon Interactive Oracle Proofs of Proximity](https://eccc.weizmann.ac.il/report/2017/134/)
 //! * [DEEP-FRI: Sampling Outside the Box Improves Soundness](https://eprint.iacr.org/2019/336)
 //! * Swastik Kooparty's [talk on DEEP-FRI](https://www.youtube.com/watch?v=txo_kPSn59Y&list=PLcIyXLwiPilWvjvNkhMn283LV370Pk5CT&index=6)
+//! * [STIR: Reed–Solomon Proximity Testing with Fewer Queries](https://eprint.iacr.org/2024/390)
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -67,19 +102,25 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod batch;
 pub mod folding;
+pub mod stir;
 
 mod prover;
-pub use prover::{DefaultProverChannel, FriProver, ProverChannel};
+#[cfg(feature = "std")]
+pub use prover::FileLayerStorage;
+pub use prover::{
+    DefaultProverChannel, FriLayerStorage, FriProver, MemoryLayerStorage, ProverChannel,
+};
 
 mod verifier;
-pub use verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel};
+pub use verifier::{verify_proof, DefaultVerifierChannel, FriVerifier, VerifierChannel};
 
 mod options;
 pub use options::FriOptions;
 
 mod proof;
-pub use proof::FriProof;
+pub use proof::{FriProof, FriProofLayer, FriProofLayerRef, FriProofRef};
 
 mod errors;
 pub use errors::VerifierError;
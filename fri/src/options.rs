@@ -4,16 +4,36 @@
 // LICENSE file in the root directory of this source tree.
 
 use math::StarkField;
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Folding factors supported by the FRI prover and verifier.
+pub(crate) const SUPPORTED_FOLDING_FACTORS: [usize; 4] = [2, 4, 8, 16];
 
 // FRI OPTIONS
 // ================================================================================================
 
 /// FRI protocol config options for proof generation and verification.
+///
+/// By default, the degree of a polynomial is reduced by the same folding factor at every FRI
+/// layer. Alternatively, a folding schedule can be specified via
+/// [with_folding_schedule()](FriOptions::with_folding_schedule): in this case, the first layers
+/// are folded by the factors specified in the schedule, and all remaining layers are folded by the
+/// last factor of the schedule. For example, schedule `[8, 4]` folds the first layer by 8 and all
+/// subsequent layers by 4. Larger folding factors result in fewer layers (and thus, fewer
+/// commitments the verifier needs to check), but in larger proofs.
+///
+/// Optionally, the prover can be required to perform a proof-of-work after committing to each
+/// FRI layer (see [with_layer_grinding()](FriOptions::with_layer_grinding)).
 #[derive(Clone, PartialEq, Eq)]
 pub struct FriOptions {
     folding_factor: usize,
+    folding_schedule: Vec<usize>,
     remainder_max_degree: usize,
     blowup_factor: usize,
+    layer_grinding_factor: u32,
 }
 
 impl FriOptions {
@@ -30,17 +50,77 @@ impl FriOptions {
             "blowup factor must be a power of two, but was {blowup_factor}"
         );
         assert!(
-            folding_factor == 2
-                || folding_factor == 4
-                || folding_factor == 8
-                || folding_factor == 16,
+            SUPPORTED_FOLDING_FACTORS.contains(&folding_factor),
             "folding factor {folding_factor} is not supported"
         );
         FriOptions {
             folding_factor,
+            folding_schedule: Vec::new(),
             remainder_max_degree,
             blowup_factor,
+            layer_grinding_factor: 0,
+        }
+    }
+
+    /// Returns a new [FriOptions] struct instantiated with the specified folding schedule.
+    ///
+    /// The first FRI layers are folded by the factors in `folding_schedule` (i.e., layer `i` is
+    /// folded by `folding_schedule[i]`), and all layers after that are folded by the last factor in
+    /// the schedule. A schedule in which all factors are the same is equivalent to a uniform
+    /// folding factor specified via [new()](FriOptions::new).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `blowup_factor` is not a power of two.
+    /// - `folding_schedule` is empty.
+    /// - Any of the factors in `folding_schedule` is not 2, 4, 8, or 16.
+    pub fn with_folding_schedule(
+        blowup_factor: usize,
+        folding_schedule: &[usize],
+        remainder_max_degree: usize,
+    ) -> Self {
+        assert!(
+            !folding_schedule.is_empty(),
+            "folding schedule must contain at least one folding factor"
+        );
+        for &folding_factor in folding_schedule {
+            assert!(
+                SUPPORTED_FOLDING_FACTORS.contains(&folding_factor),
+                "folding factor {folding_factor} is not supported"
+            );
         }
+
+        // the last factor applies to all remaining layers; we drop trailing factors equal to it
+        // so that equivalent schedules are represented the same way
+        let folding_factor = folding_schedule[folding_schedule.len() - 1];
+        let mut schedule = folding_schedule.to_vec();
+        while schedule.last() == Some(&folding_factor) {
+            schedule.pop();
+        }
+
+        let mut result = Self::new(blowup_factor, folding_factor, remainder_max_degree);
+        result.folding_schedule = schedule;
+        result
+    }
+
+    /// Returns these options updated to require a proof-of-work with the specified grinding
+    /// factor for each FRI layer.
+    ///
+    /// After committing to a FRI layer (but before drawing the α used to fold the layer), the
+    /// prover must find a nonce such that reseeding the public coin with the nonce results in a
+    /// seed with at least `grinding_factor` leading zeros; the nonces for all layers are recorded
+    /// in the [FriProof](crate::FriProof). This is required by some external verifiers (e.g.,
+    /// verifiers following ethSTARK). A grinding factor of zero disables layer proof-of-work.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 32.
+    pub fn with_layer_grinding(mut self, grinding_factor: u32) -> Self {
+        assert!(
+            grinding_factor <= 32,
+            "layer grinding factor cannot be greater than 32, but was {grinding_factor}"
+        );
+        self.layer_grinding_factor = grinding_factor;
+        self
     }
 
     /// Returns the offset by which the evaluation domain is shifted.
@@ -57,10 +137,44 @@ impl FriOptions {
     ///
     /// In combination with `remainder_max_degree_plus_1` this property defines how many FRI layers are
     /// needed for an evaluation domain of a given size.
+    ///
+    /// If a folding schedule has been specified, this is the factor by which the layers following
+    /// the schedule are folded (i.e., the last factor of the schedule); see
+    /// [folding_factor_at()](FriOptions::folding_factor_at) for the factor of a specific layer.
     pub fn folding_factor(&self) -> usize {
         self.folding_factor
     }
 
+    /// Returns the factor by which the degree of a polynomial is reduced at the FRI layer with
+    /// the specified index.
+    pub fn folding_factor_at(&self, layer_idx: usize) -> usize {
+        self.folding_schedule
+            .get(layer_idx)
+            .copied()
+            .unwrap_or(self.folding_factor)
+    }
+
+    /// Returns folding factors of the first FRI layers which are folded differently from the
+    /// [folding_factor()](FriOptions::folding_factor); for uniform folding, this is empty.
+    pub fn folding_schedule(&self) -> &[usize] {
+        &self.folding_schedule
+    }
+
+    /// Returns true if all FRI layers are folded by the same factor.
+    pub fn is_uniform(&self) -> bool {
+        self.folding_schedule.is_empty()
+    }
+
+    /// Returns folding factors for all FRI layers required for a domain of the specified size.
+    ///
+    /// The length of the returned vector is equal to the number of FRI layers returned by
+    /// [num_fri_layers()](FriOptions::num_fri_layers).
+    pub fn layer_folding_factors(&self, domain_size: usize) -> Vec<usize> {
+        (0..self.num_fri_layers(domain_size))
+            .map(|layer_idx| self.folding_factor_at(layer_idx))
+            .collect()
+    }
+
     /// Returns maximum allowed remainder polynomial degree.
     ///
     /// In combination with `folding_factor` this property defines how many FRI layers are needed
@@ -69,6 +183,13 @@ impl FriOptions {
         self.remainder_max_degree
     }
 
+    /// Returns the number of leading zeros required in the seed of the public coin after the
+    /// prover's proof-of-work for each FRI layer; zero means that layer proof-of-work is
+    /// disabled.
+    pub fn layer_grinding_factor(&self) -> u32 {
+        self.layer_grinding_factor
+    }
+
     /// Returns a blowup factor of the evaluation domain.
     ///
     /// Specifically, if the polynomial for which the FRI protocol is executed is of degree `d`
@@ -86,7 +207,7 @@ impl FriOptions {
         let mut result = 0;
         let max_remainder_size = (self.remainder_max_degree + 1) * self.blowup_factor;
         while domain_size > max_remainder_size {
-            domain_size /= self.folding_factor;
+            domain_size /= self.folding_factor_at(result);
             result += 1;
         }
         result
@@ -3,13 +3,21 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use crate::FriOptions;
+use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree, VectorCommitment};
 use math::FieldElement;
 use utils::{
     collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
     DeserializationError, Serializable, SliceReader,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Flag set in the serialized number of partitions if the proof contains FRI layer proof-of-work
+/// nonces; proofs without nonces are serialized the same way as before nonces were introduced.
+const LAYER_NONCES_FLAG: u8 = 0x80;
+
 // FRI PROOF
 // ================================================================================================
 
@@ -23,16 +31,22 @@ use utils::{
 /// A proof consists of zero or more layers and a remainder polynomial. Each layer contains a set of
 /// polynomial evaluations at positions queried by the verifier as well as Merkle authentication
 /// paths for these evaluations (the Merkle paths are compressed into a batch Merkle proof). The
-/// remainder polynomial is given by its list of coefficients i.e. field elements.
+/// remainder polynomial is given by its list of coefficients i.e. field elements. If layer
+/// proof-of-work is required by [FriOptions], the proof also contains a proof-of-work nonce for
+/// each layer.
 ///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
 /// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
 /// and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// A default proof contains no layers and an empty remainder; such a proof can be used as a
+/// placeholder when a different low-degree test (e.g., [STIR](crate::stir)) is used.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
 pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
     num_partitions: u8, // stored as power of 2
+    layer_nonces: Vec<u64>,
 }
 
 impl FriProof {
@@ -70,9 +84,50 @@ impl FriProof {
             layers,
             remainder: remainder.to_bytes(),
             num_partitions: num_partitions.trailing_zeros() as u8,
+            layer_nonces: Vec::new(),
         }
     }
 
+    /// Creates a new FRI proof from the provided layers, serialized remainder values, and the
+    /// number of partitions.
+    ///
+    /// This is intended to be used by alternative proof encodings which need to reconstruct a
+    /// proof from its raw parts; no validation of the remainder bytes is performed.
+    ///
+    /// # Panics
+    /// Panics if `num_partitions` is zero or is not a power of two.
+    pub fn from_raw_parts(
+        layers: Vec<FriProofLayer>,
+        remainder: Vec<u8>,
+        num_partitions: usize,
+    ) -> Self {
+        assert!(
+            num_partitions.is_power_of_two(),
+            "number of partitions must be a power of two, but was {num_partitions}"
+        );
+        FriProof {
+            layers,
+            remainder,
+            num_partitions: num_partitions.trailing_zeros() as u8,
+            layer_nonces: Vec::new(),
+        }
+    }
+
+    /// Returns this proof with the specified FRI layer proof-of-work nonces.
+    ///
+    /// # Panics
+    /// Panics if the number of nonces is greater than 255.
+    pub fn with_layer_nonces(mut self, layer_nonces: Vec<u64>) -> Self {
+        assert!(
+            layer_nonces.len() <= u8::MAX as usize,
+            "number of layer nonces cannot be greater than {}, but was {}",
+            u8::MAX,
+            layer_nonces.len()
+        );
+        self.layer_nonces = layer_nonces;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -81,6 +136,16 @@ impl FriProof {
         self.layers.len()
     }
 
+    /// Returns the layers of this proof.
+    pub fn layers(&self) -> &[FriProofLayer] {
+        &self.layers
+    }
+
+    /// Returns the serialized remainder values of this proof.
+    pub fn remainder_bytes(&self) -> &[u8] {
+        &self.remainder
+    }
+
     /// Returns the number of remainder elements in this proof.
     ///
     /// The number of elements is computed by dividing the number of remainder bytes by the size
@@ -94,12 +159,87 @@ impl FriProof {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns FRI layer proof-of-work nonces of this proof; this is empty if layer
+    /// proof-of-work was not required during proof generation.
+    pub fn layer_nonces(&self) -> &[u64] {
+        &self.layer_nonces
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +2 for remainder length, +1 for number of partitions, and
+        // +1 for number of layer nonces if there are any
+        let nonces_size = if self.layer_nonces.is_empty() {
+            0
+        } else {
+            self.layer_nonces.len() * 8 + 1
+        };
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(self.remainder.len() + 4 + nonces_size, |acc, layer| {
+                acc + layer.size()
+            })
+    }
+
+    // SIZE ESTIMATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the expected size (in bytes) of a proof generated for an evaluation domain of the
+    /// specified size using the specified `options` and number of queries.
+    ///
+    /// This can be used to predict the size of a proof without running the prover. The estimate
+    /// assumes that layers are committed to using Merkle trees with hash function `H`, that the
+    /// proof is generated for evaluations in field `E`, and that `num_queries` distinct query
+    /// positions are drawn uniformly at random from the domain; it accounts for positions which
+    /// collapse into the same row when folded, and for internal Merkle nodes shared between
+    /// authentication paths.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `num_queries` is greater than `domain_size`.
+    pub fn estimated_size<E: FieldElement, H: Hasher>(
+        options: &FriOptions,
+        domain_size: usize,
+        num_queries: usize,
+    ) -> usize {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+        assert!(
+            num_queries <= domain_size,
+            "number of queries cannot exceed domain size"
+        );
+
+        // +1 for number of layers, +2 for remainder length, +1 for number of partitions
+        let mut result = 4;
+
+        let folding_factors = options.layer_folding_factors(domain_size);
+        let mut layer_domain_size = domain_size;
+        for &folding_factor in folding_factors.iter() {
+            // every query position opens a row of `folding_factor` evaluations
+            let num_rows = layer_domain_size / folding_factor;
+            let depth = num_rows.trailing_zeros() as u8;
+            let num_values =
+                BatchMerkleProof::<H>::expected_num_leaves(depth, num_queries, domain_size);
+            let paths_size =
+                BatchMerkleProof::<H>::estimate_serialized_size(depth, num_queries, domain_size);
+
+            // +4 for length of values, +4 for length of paths
+            result += num_values * folding_factor * E::ELEMENT_BYTES + 4 + paths_size + 4;
+            layer_domain_size = num_rows;
+        }
+
+        // the remainder polynomial is sent in full
+        result += layer_domain_size / options.blowup_factor() * E::ELEMENT_BYTES;
+
+        // +1 for number of layer nonces, and 8 bytes for each nonce
+        if options.layer_grinding_factor() > 0 && !folding_factors.is_empty() {
+            result += 1 + folding_factors.len() * 8;
+        }
+
+        result
     }
 
     // PARSING
@@ -108,49 +248,55 @@ impl FriProof {
     /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
     /// authentication paths for each query (grouped into batch Merkle proofs).
     ///
+    /// The number of values in each layer query is determined by the folding factor of the layer
+    /// as specified by `options` (see [FriOptions::folding_factor_at()]).
+    ///
     /// # Panics
-    /// Panics if:
-    /// * `domain_size` is not a power of two.
-    /// * `folding_factor` is smaller than two or is not a power of two.
+    /// Panics if `domain_size` is not a power of two.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * This proof is not consistent with the specified `domain_size` and folding factors.
     /// * Any of the layers could not be parsed successfully.
     #[allow(clippy::type_complexity)]
     pub fn parse_layers<H, E>(
         self,
-        mut domain_size: usize,
-        folding_factor: usize,
+        domain_size: usize,
+        options: &FriOptions,
     ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
     {
-        assert!(
-            domain_size.is_power_of_two(),
-            "domain size must be a power of two"
-        );
-        assert!(
-            folding_factor.is_power_of_two(),
-            "folding factor must be a power of two"
-        );
-        assert!(folding_factor > 1, "folding factor must be greater than 1");
-
-        let mut layer_proofs = Vec::new();
-        let mut layer_queries = Vec::new();
-
-        // parse all layers
-        for (i, layer) in self.layers.into_iter().enumerate() {
-            domain_size /= folding_factor;
-            let (qv, mp) = layer.parse(domain_size, folding_factor).map_err(|err| {
-                DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
-            })?;
-            layer_proofs.push(mp);
-            layer_queries.push(qv);
-        }
+        self.view().parse_layers(domain_size, options)
+    }
 
-        Ok((layer_queries, layer_proofs))
+    /// Decomposes this proof into vectors of query values for each layer and corresponding
+    /// opening proofs of vector commitment `V` for each layer.
+    ///
+    /// This is the same as [parse_layers()](FriProof::parse_layers), but for proofs generated by
+    /// a prover which commits to FRI layers using vector commitment `V` rather than Merkle trees.
+    ///
+    /// # Panics
+    /// Panics if `domain_size` is not a power of two.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This proof is not consistent with the specified `domain_size` and folding factors.
+    /// * Any of the layers could not be parsed successfully.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_layers_with<H, V, E>(
+        self,
+        domain_size: usize,
+        options: &FriOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<V::MultiProof>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        self.view()
+            .parse_layers_with::<H, V, E>(domain_size, options)
     }
 
     /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
@@ -162,20 +308,21 @@ impl FriProof {
     /// * Any of the remainder values could not be parsed correctly.
     /// * Not all bytes have been consumed while parsing remainder values.
     pub fn parse_remainder<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
-        let num_elements = self.num_remainder_elements::<E>();
-        if !num_elements.is_power_of_two() {
-            return Err(DeserializationError::InvalidValue(format!(
-                "number of remainder values must be a power of two, but {num_elements} was implied"
-            )));
-        }
-        let mut reader = SliceReader::new(&self.remainder);
-        let remainder = E::read_batch_from(&mut reader, num_elements).map_err(|err| {
-            DeserializationError::InvalidValue(format!("failed to parse FRI remainder: {err}"))
-        })?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
+        self.view().parse_remainder()
+    }
+
+    // VIEW
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a view of this proof which borrows the serialized layers and remainder values
+    /// from this proof.
+    pub fn view(&self) -> FriProofRef<'_> {
+        FriProofRef {
+            layers: self.layers.iter().map(FriProofLayer::view).collect(),
+            remainder: &self.remainder,
+            num_partitions: self.num_partitions,
+            layer_nonces: self.layer_nonces.clone(),
         }
-        Ok(remainder)
     }
 }
 
@@ -195,8 +342,8 @@ impl Serializable for FriProof {
         target.write_u16(self.remainder.len() as u16);
         target.write_bytes(&self.remainder);
 
-        // write number of partitions
-        target.write_u8(self.num_partitions);
+        // write number of partitions, followed by layer nonces if there are any
+        write_num_partitions_and_nonces(target, self.num_partitions, &self.layer_nonces);
     }
 }
 
@@ -214,13 +361,14 @@ impl Deserializable for FriProof {
         let num_remainder_bytes = source.read_u16()? as usize;
         let remainder = source.read_vec(num_remainder_bytes)?;
 
-        // read number of partitions
-        let num_partitions = source.read_u8()?;
+        // read number of partitions and layer nonces
+        let (num_partitions, layer_nonces) = read_num_partitions_and_nonces(source)?;
 
         Ok(FriProof {
             layers,
             remainder,
             num_partitions,
+            layer_nonces,
         })
     }
 }
@@ -228,6 +376,10 @@ impl Deserializable for FriProof {
 // FRI PROOF LAYER
 // ================================================================================================
 
+/// A single layer of a FRI proof.
+///
+/// A layer contains polynomial evaluations at positions queried by the verifier together with
+/// Merkle authentication paths for these evaluations; both are stored as vectors of bytes.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FriProofLayer {
     values: Vec<u8>,
@@ -235,33 +387,60 @@ pub struct FriProofLayer {
 }
 
 impl FriProofLayer {
+    /// Maximum number of bytes in either serialized query values or serialized Merkle paths of
+    /// a proof layer which can be read from a serialized proof.
+    ///
+    /// This bounds the amount of memory allocated when a proof is read from an untrusted source;
+    /// the bound is well above the size of layers in proofs generated with valid proof options.
+    pub const MAX_NUM_BYTES: usize = 1 << 24;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new proof layer from the specified query values and the corresponding Merkle
-    /// paths aggregated into a single batch Merkle proof.
+    /// Creates a new proof layer from the specified query values and the corresponding proof of
+    /// opening items of vector commitment `V` (for Merkle trees, this is a batch Merkle proof).
     ///
     /// # Panics
     /// Panics if `query_values` is an empty slice.
-    pub(crate) fn new<H: Hasher, E: FieldElement, const N: usize>(
+    pub(crate) fn new<H: Hasher, V: VectorCommitment<H>, E: FieldElement, const N: usize>(
         query_values: Vec<[E; N]>,
-        merkle_proof: BatchMerkleProof<H>,
+        opening_proof: V::MultiProof,
     ) -> Self {
         assert!(!query_values.is_empty(), "query values cannot be empty");
 
         // TODO: add debug check that values actually hash into the leaf nodes of the batch proof
 
-        // concatenate all query values and all internal Merkle proof nodes into vectors of bytes;
-        // we care about internal nodes only because leaf nodes can be reconstructed from hashes
-        // of query values
+        // concatenate all query values and the opening proof into vectors of bytes; the opened
+        // items are not serialized because they can be reconstructed from hashes of query values
+        let mut paths = Vec::new();
+        V::write_multi_proof(&opening_proof, &mut paths);
         FriProofLayer {
             values: query_values.to_bytes(),
-            paths: merkle_proof.serialize_nodes(),
+            paths,
         }
     }
 
+    /// Creates a new proof layer from serialized query values and serialized internal nodes of
+    /// the corresponding batch Merkle proof.
+    ///
+    /// No validation of the provided bytes is performed; the bytes are parsed only when
+    /// [parse()](FriProofLayer::parse) is invoked.
+    pub fn from_raw_parts(values: Vec<u8>, paths: Vec<u8>) -> Self {
+        FriProofLayer { values, paths }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns serialized query values of this proof layer.
+    pub fn values_bytes(&self) -> &[u8] {
+        &self.values
+    }
+
+    /// Returns serialized internal nodes of the batch Merkle proof for this proof layer.
+    pub fn paths_bytes(&self) -> &[u8] {
+        &self.paths
+    }
+
     /// Returns the size of this proof layer in bytes.
     pub fn size(&self) -> usize {
         // +4 for length of values, +4 for length of paths
@@ -286,6 +465,372 @@ impl FriProofLayer {
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.view().parse(domain_size, folding_factor)
+    }
+
+    /// Decomposes this layer into a combination of query values and the corresponding opening
+    /// proof of vector commitment `V`.
+    ///
+    /// This is the same as [parse()](FriProofLayer::parse), but for layers committed to using
+    /// vector commitment `V` rather than a Merkle tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This layer does not contain at least one query.
+    /// * Parsing of any of the query values or the corresponding opening proof fails.
+    /// * Not all bytes have been consumed while parsing this layer.
+    pub fn parse_with<H, V, E>(
+        self,
+        domain_size: usize,
+        folding_factor: usize,
+    ) -> Result<(Vec<E>, V::MultiProof), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        self.view()
+            .parse_with::<H, V, E>(domain_size, folding_factor)
+    }
+
+    // VIEW
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a view of this proof layer which borrows the serialized query values and Merkle
+    /// paths from this layer.
+    pub fn view(&self) -> FriProofLayerRef<'_> {
+        FriProofLayerRef {
+            values: &self.values,
+            paths: &self.paths,
+        }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for FriProofLayer {
+    /// Serializes this proof layer and writes the resulting bytes to the specified `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // write value bytes
+        target.write_u32(self.values.len() as u32);
+        target.write_bytes(&self.values);
+
+        // write path bytes
+        target.write_u32(self.paths.len() as u32);
+        target.write_bytes(&self.paths);
+    }
+}
+
+impl Deserializable for FriProofLayer {
+    /// Reads a single proof layer form the `source` and returns it.
+    ///
+    /// # Errors
+    /// Returns an error if a valid layer could not be read from the specified source.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // read values
+        let num_value_bytes = read_layer_len(source, "query values")?;
+        if num_value_bytes == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a FRI proof layer must contain at least one queried evaluation".to_string(),
+            ));
+        }
+        let values = source.read_vec(num_value_bytes)?;
+
+        // read paths
+        let num_paths_bytes = read_layer_len(source, "Merkle paths")?;
+        let paths = source.read_vec(num_paths_bytes)?;
+
+        Ok(FriProofLayer { values, paths })
+    }
+}
+
+// FRI PROOF VIEW
+// ================================================================================================
+
+/// A view of a serialized [FriProof] which borrows layer values and Merkle paths from the
+/// underlying bytes.
+///
+/// A view can be read directly from serialized bytes via [read_from()](FriProofRef::read_from)
+/// without copying any of the query values or Merkle paths, or it can be obtained from an owned
+/// proof via [FriProof::view()]. In either case, the values are parsed only when
+/// [parse_layers()](FriProofRef::parse_layers) and
+/// [parse_remainder()](FriProofRef::parse_remainder) methods are invoked.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct FriProofRef<'a> {
+    layers: Vec<FriProofLayerRef<'a>>,
+    remainder: &'a [u8],
+    num_partitions: u8, // stored as power of 2
+    layer_nonces: Vec<u64>,
+}
+
+impl<'a> FriProofRef<'a> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of layers in this proof.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns the layers of this proof.
+    pub fn layers(&self) -> &[FriProofLayerRef<'a>] {
+        &self.layers
+    }
+
+    /// Returns the serialized remainder values of this proof.
+    pub fn remainder_bytes(&self) -> &'a [u8] {
+        self.remainder
+    }
+
+    /// Returns the number of remainder elements in this proof.
+    ///
+    /// The number of elements is computed by dividing the number of remainder bytes by the size
+    /// of the field element specified by `E` type parameter.
+    pub fn num_remainder_elements<E: FieldElement>(&self) -> usize {
+        self.remainder.len() / E::ELEMENT_BYTES
+    }
+
+    /// Returns the number of partitions used during proof generation.
+    pub fn num_partitions(&self) -> usize {
+        2usize.pow(self.num_partitions as u32)
+    }
+
+    /// Returns FRI layer proof-of-work nonces of this proof; this is empty if layer
+    /// proof-of-work was not required during proof generation.
+    pub fn layer_nonces(&self) -> &[u64] {
+        &self.layer_nonces
+    }
+
+    /// Returns an owned copy of the proof described by this view.
+    pub fn into_owned(self) -> FriProof {
+        FriProof {
+            layers: self
+                .layers
+                .into_iter()
+                .map(FriProofLayerRef::into_owned)
+                .collect(),
+            remainder: self.remainder.to_vec(),
+            num_partitions: self.num_partitions,
+            layer_nonces: self.layer_nonces,
+        }
+    }
+
+    // PARSING
+    // --------------------------------------------------------------------------------------------
+
+    /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
+    /// authentication paths for each query (grouped into batch Merkle proofs).
+    ///
+    /// The number of values in each layer query is determined by the folding factor of the layer
+    /// as specified by `options` (see [FriOptions::folding_factor_at()]).
+    ///
+    /// # Panics
+    /// Panics if `domain_size` is not a power of two.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This proof is not consistent with the specified `domain_size` and folding factors.
+    /// * Any of the layers could not be parsed successfully.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_layers<H, E>(
+        &self,
+        domain_size: usize,
+        options: &FriOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.parse_layers_with::<H, MerkleTree<H>, E>(domain_size, options)
+    }
+
+    /// Decomposes this proof into vectors of query values for each layer and corresponding
+    /// opening proofs of vector commitment `V` for each layer.
+    ///
+    /// This is the same as [parse_layers()](FriProofRef::parse_layers), but for proofs generated
+    /// by a prover which commits to FRI layers using vector commitment `V` rather than Merkle
+    /// trees.
+    ///
+    /// # Panics
+    /// Panics if `domain_size` is not a power of two.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This proof is not consistent with the specified `domain_size` and folding factors.
+    /// * Any of the layers could not be parsed successfully.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_layers_with<H, V, E>(
+        &self,
+        mut domain_size: usize,
+        options: &FriOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<V::MultiProof>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+
+        let mut layer_proofs = Vec::new();
+        let mut layer_queries = Vec::new();
+
+        // parse all layers
+        for (i, layer) in self.layers.iter().enumerate() {
+            let folding_factor = options.folding_factor_at(i);
+            domain_size /= folding_factor;
+            let (qv, mp) = layer
+                .parse_with::<H, V, E>(domain_size, folding_factor)
+                .map_err(|err| {
+                    DeserializationError::InvalidValue(format!(
+                        "failed to parse FRI layer {i}: {err}"
+                    ))
+                })?;
+            layer_proofs.push(mp);
+            layer_queries.push(qv);
+        }
+
+        Ok((layer_queries, layer_proofs))
+    }
+
+    /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of remainder values implied by a combination of `E` type parameter and
+    ///   the number of remainder bytes in this proof is not a power of two.
+    /// * Any of the remainder values could not be parsed correctly.
+    /// * Not all bytes have been consumed while parsing remainder values.
+    pub fn parse_remainder<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        let num_elements = self.num_remainder_elements::<E>();
+        if !num_elements.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of remainder values must be a power of two, but {num_elements} was implied"
+            )));
+        }
+        let mut reader = SliceReader::new(self.remainder);
+        let remainder = E::read_batch_from(&mut reader, num_elements).map_err(|err| {
+            DeserializationError::InvalidValue(format!("failed to parse FRI remainder: {err}"))
+        })?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(remainder)
+    }
+
+    // DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads a view of a FRI proof from the specified `source` and returns the result.
+    ///
+    /// The bytes are expected to be in the same format as the one produced by serializing a
+    /// [FriProof]; the returned view borrows the layers and remainder bytes from the `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source.
+    pub fn read_from(source: &mut SliceReader<'a>) -> Result<Self, DeserializationError> {
+        // read layers
+        let num_layers = source.read_u8()? as usize;
+        let mut layers = Vec::with_capacity(num_layers);
+        for _ in 0..num_layers {
+            layers.push(FriProofLayerRef::read_from(source)?);
+        }
+
+        // read remainder
+        let num_remainder_bytes = source.read_u16()? as usize;
+        let remainder = source.read_slice(num_remainder_bytes)?;
+
+        // read number of partitions and layer nonces
+        let (num_partitions, layer_nonces) = read_num_partitions_and_nonces(source)?;
+
+        Ok(FriProofRef {
+            layers,
+            remainder,
+            num_partitions,
+            layer_nonces,
+        })
+    }
+}
+
+// FRI PROOF LAYER VIEW
+// ================================================================================================
+
+/// A view of a single serialized [FriProofLayer] which borrows query values and Merkle paths
+/// from the underlying bytes.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct FriProofLayerRef<'a> {
+    values: &'a [u8],
+    paths: &'a [u8],
+}
+
+impl<'a> FriProofLayerRef<'a> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns serialized query values of this proof layer.
+    pub fn values_bytes(&self) -> &'a [u8] {
+        self.values
+    }
+
+    /// Returns serialized internal nodes of the batch Merkle proof for this proof layer.
+    pub fn paths_bytes(&self) -> &'a [u8] {
+        self.paths
+    }
+
+    /// Returns an owned copy of the proof layer described by this view.
+    pub fn into_owned(self) -> FriProofLayer {
+        FriProofLayer {
+            values: self.values.to_vec(),
+            paths: self.paths.to_vec(),
+        }
+    }
+
+    // PARSING
+    // --------------------------------------------------------------------------------------------
+    /// Decomposes this layer into a combination of query values and corresponding Merkle
+    /// authentication paths (grouped together into a single batch Merkle proof).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This layer does not contain at least one query.
+    /// * Parsing of any of the query values or the corresponding Merkle paths fails.
+    /// * Not all bytes have been consumed while parsing this layer.
+    pub fn parse<H, E>(
+        &self,
+        domain_size: usize,
+        folding_factor: usize,
+    ) -> Result<(Vec<E>, BatchMerkleProof<H>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        self.parse_with::<H, MerkleTree<H>, E>(domain_size, folding_factor)
+    }
+
+    /// Decomposes this layer into a combination of query values and the corresponding opening
+    /// proof of vector commitment `V`.
+    ///
+    /// This is the same as [parse()](FriProofLayerRef::parse), but for layers committed to using
+    /// vector commitment `V` rather than a Merkle tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This layer does not contain at least one query.
+    /// * Parsing of any of the query values or the corresponding opening proof fails.
+    /// * Not all bytes have been consumed while parsing this layer.
+    pub fn parse_with<H, V, E>(
+        &self,
+        domain_size: usize,
+        folding_factor: usize,
+    ) -> Result<(Vec<E>, V::MultiProof), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
     {
         // make sure the number of value bytes can be parsed into a whole number of queries
         let num_query_bytes = E::ELEMENT_BYTES * folding_factor;
@@ -306,8 +851,8 @@ impl FriProofLayer {
         let mut query_values = Vec::with_capacity(num_queries * folding_factor);
 
         // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
-        let mut reader = SliceReader::new(&self.values);
+        // and also hash them to build the opened items of the opening proof
+        let mut reader = SliceReader::new(self.values);
         for query_hash in hashed_queries.iter_mut() {
             let mut qe = E::read_batch_from(&mut reader, folding_factor)?;
             *query_hash = H::hash_elements(&qe);
@@ -317,53 +862,105 @@ impl FriProofLayer {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        // build batch Merkle proof
-        let mut reader = SliceReader::new(&self.paths);
-        let tree_depth = domain_size.ilog2() as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        // build the opening proof
+        let mut reader = SliceReader::new(self.paths);
+        let opening_proof = V::read_multi_proof(&mut reader, hashed_queries, domain_size)?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        Ok((query_values, merkle_proof))
+        Ok((query_values, opening_proof))
     }
-}
 
-// SERIALIZATION / DESERIALIZATION
-// ------------------------------------------------------------------------------------------------
-
-impl Serializable for FriProofLayer {
-    /// Serializes this proof layer and writes the resulting bytes to the specified `target`.
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        // write value bytes
-        target.write_u32(self.values.len() as u32);
-        target.write_bytes(&self.values);
-
-        // write path bytes
-        target.write_u32(self.paths.len() as u32);
-        target.write_bytes(&self.paths);
-    }
-}
+    // DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
 
-impl Deserializable for FriProofLayer {
-    /// Reads a single proof layer form the `source` and returns it.
+    /// Reads a view of a single proof layer from the `source` and returns it.
     ///
     /// # Errors
     /// Returns an error if a valid layer could not be read from the specified source.
-    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+    pub fn read_from(source: &mut SliceReader<'a>) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
+        let num_value_bytes = read_layer_len(source, "query values")?;
         if num_value_bytes == 0 {
             return Err(DeserializationError::InvalidValue(
                 "a FRI proof layer must contain at least one queried evaluation".to_string(),
             ));
         }
-        let values = source.read_vec(num_value_bytes as usize)?;
+        let values = source.read_slice(num_value_bytes)?;
 
         // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_vec(num_paths_bytes as usize)?;
+        let num_paths_bytes = read_layer_len(source, "Merkle paths")?;
+        let paths = source.read_slice(num_paths_bytes)?;
 
-        Ok(FriProofLayer { values, paths })
+        Ok(FriProofLayerRef { values, paths })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the number of partitions (stored as a power of two) into the `target`; if there are
+/// any layer nonces, [LAYER_NONCES_FLAG] is set and the nonces are written after the number of
+/// partitions.
+fn write_num_partitions_and_nonces<W: ByteWriter>(
+    target: &mut W,
+    num_partitions: u8,
+    layer_nonces: &[u64],
+) {
+    if layer_nonces.is_empty() {
+        target.write_u8(num_partitions);
+    } else {
+        target.write_u8(num_partitions | LAYER_NONCES_FLAG);
+        target.write_u8(layer_nonces.len() as u8);
+        for &nonce in layer_nonces {
+            target.write_u64(nonce);
+        }
+    }
+}
+
+/// Reads the number of partitions (stored as a power of two) and layer nonces from the `source`
+/// making sure the number of partitions can be represented by a `usize` on the current platform.
+fn read_num_partitions_and_nonces<R: ByteReader>(
+    source: &mut R,
+) -> Result<(u8, Vec<u64>), DeserializationError> {
+    let value = source.read_u8()?;
+    let num_partitions = value & !LAYER_NONCES_FLAG;
+    if num_partitions as u32 >= usize::BITS {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of partitions cannot be greater than 2^{}, but was 2^{}",
+            usize::BITS - 1,
+            num_partitions
+        )));
+    }
+
+    let mut layer_nonces = Vec::new();
+    if value & LAYER_NONCES_FLAG != 0 {
+        let num_nonces = source.read_u8()? as usize;
+        if num_nonces == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a FRI proof with layer nonces must contain at least one nonce".to_string(),
+            ));
+        }
+        for _ in 0..num_nonces {
+            layer_nonces.push(source.read_u64()?);
+        }
+    }
+    Ok((num_partitions, layer_nonces))
+}
+
+/// Reads the number of bytes in a serialized part of a proof layer from the `source` making sure
+/// the number does not exceed [FriProofLayer::MAX_NUM_BYTES].
+fn read_layer_len<R: ByteReader>(
+    source: &mut R,
+    name: &str,
+) -> Result<usize, DeserializationError> {
+    let len = source.read_u32()? as usize;
+    if len > FriProofLayer::MAX_NUM_BYTES {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{name} of a FRI proof layer cannot be longer than {} bytes, but were {len} bytes",
+            FriProofLayer::MAX_NUM_BYTES
+        )));
     }
+    Ok(len)
 }
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::marker::PhantomData;
-use crypto::{ElementHasher, Hasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, QuerySampler, QuerySampling, RandomCoin};
 use math::FieldElement;
 use utils::collections::Vec;
 
@@ -45,6 +45,15 @@ pub trait ProverChannel<E: FieldElement> {
     /// prover, in the non-interactive version, the α is pseudo-randomly generated based on the
     /// values the prover previously wrote into the channel.
     fn draw_fri_alpha(&mut self) -> E;
+
+    /// Determines a nonce which, when hashed with the current state of the channel, results in
+    /// a value with at least `grinding_factor` leading zeros, absorbs the nonce into the channel,
+    /// and returns it.
+    ///
+    /// The prover calls this after committing to a FRI layer and before drawing α for the layer
+    /// when the FRI options require layer proof-of-work (see
+    /// [FriOptions::with_layer_grinding()](crate::FriOptions::with_layer_grinding)).
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64;
 }
 
 // DEFAULT PROVER CHANNEL IMPLEMENTATION
@@ -64,6 +73,7 @@ where
     commitments: Vec<H::Digest>,
     domain_size: usize,
     num_queries: usize,
+    query_sampling: QuerySampling,
     _field_element: PhantomData<E>,
 }
 
@@ -97,23 +107,31 @@ where
             commitments: Vec::new(),
             domain_size,
             num_queries,
+            query_sampling: QuerySampling::Modulo,
             _field_element: PhantomData,
         }
     }
 
+    /// Updates this channel to draw query positions using the specified sampling method; by
+    /// default, query positions are drawn via [QuerySampling::Modulo].
+    pub fn with_query_sampling(mut self, query_sampling: QuerySampling) -> Self {
+        self.query_sampling = query_sampling;
+        self
+    }
+
     /// Draws a set of positions at which the polynomial evaluations committed at the first FRI
     /// layer should be queried.
     ///
     /// The positions are pseudo-randomly generated based on the values the prover has written
-    /// into this channel.
+    /// into this channel, and are listed in the order in which they were drawn.
     ///
     /// # Panics
     /// Panics if the specified number of unique positions could not be drawn from the specified
     /// domain. Both number of queried positions and domain size are specified during
     /// construction of the channel.
     pub fn draw_query_positions(&mut self) -> Vec<usize> {
-        self.public_coin
-            .draw_integers(self.num_queries, self.domain_size)
+        self.query_sampling
+            .draw_positions(&mut self.public_coin, self.num_queries, self.domain_size)
             .expect("failed to draw query position")
     }
 
@@ -139,4 +157,12 @@ where
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
 }
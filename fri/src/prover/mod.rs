@@ -10,13 +10,18 @@ use crate::{
     FriOptions,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, Hasher, MerkleTree};
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment};
 use math::{fft, FieldElement, StarkField};
 use utils::{collections::Vec, flatten_vector_elements, group_slice_elements, transpose_slice};
 
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
 
+mod storage;
+#[cfg(feature = "std")]
+pub use storage::FileLayerStorage;
+pub use storage::{FriLayerStorage, MemoryLayerStorage};
+
 #[cfg(test)]
 mod tests;
 
@@ -39,6 +44,10 @@ mod tests;
 /// * `C` specifies the type used to simulate prover-verifier interaction.
 /// * `H` specifies the hash function used to build layer Merkle trees. The same hash function
 ///   must be used in the prover channel to generate pseudo random values.
+/// * `V` specifies the vector commitment scheme used to commit to the evaluations at each FRI
+///   layer. By default, layers are committed to using Merkle trees.
+/// * `S` specifies where evaluations of FRI layers are kept until the query phase (see
+///   [FriLayerStorage]). By default, evaluations are kept in memory.
 ///
 /// Proof generation is performed in two phases: commit phase and query phase.
 ///
@@ -64,8 +73,13 @@ mod tests;
 /// sends it to the prover. In the non-interactive version, α is pseudo-randomly generated based
 /// on the values the prover has written into the channel up to that point.
 ///
-/// The prover keeps all FRI layers (consisting of evaluations and corresponding Merkle trees) in
-/// its internal state.
+/// The prover keeps commitments to all FRI layers in its internal state, and passes evaluations of
+/// each layer to the layer storage as soon as the layer has been folded. Evaluations of a layer
+/// in their original order are dropped before the next layer is computed; thus, when layers are
+/// stored in files (see [FileLayerStorage]), only the evaluations of the layer being folded are
+/// held in memory. Evaluations of the first layer can also be provided via an iterator (see
+/// [build_layers_from_iter()](FriProver::build_layers_from_iter())), in which case the prover
+/// never holds these evaluations in their original order.
 ///
 /// # Query phase
 /// In the query phase, which is executed via [build_proof()](FriProver::build_proof()) function,
@@ -86,23 +100,27 @@ mod tests;
 ///
 /// Calling [build_layers()](FriProver::build_layers()) when the internal state is dirty, or
 /// calling [build_proof()](FriProver::build_proof()) on a clean state will result in a panic.
-pub struct FriProver<B, E, C, H>
+pub struct FriProver<B, E, C, H, V = MerkleTree<H>, S = MemoryLayerStorage<E>>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     C: ProverChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+    S: FriLayerStorage<E>,
 {
     options: FriOptions,
-    layers: Vec<FriLayer<B, E, H>>,
+    layers: Vec<FriLayer<H, V>>,
+    layer_nonces: Vec<u64>,
+    storage: S,
     remainder_poly: FriRemainder<E>,
     _channel: PhantomData<C>,
 }
 
-struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
-    tree: MerkleTree<H>,
-    evaluations: Vec<E>,
-    _base_field: PhantomData<B>,
+struct FriLayer<H: Hasher, V: VectorCommitment<H>> {
+    commitment: V,
+    domain_size: usize,
+    _hasher: PhantomData<H>,
 }
 
 struct FriRemainder<E: FieldElement>(Vec<E>);
@@ -110,20 +128,45 @@ struct FriRemainder<E: FieldElement>(Vec<E>);
 // PROVER IMPLEMENTATION
 // ================================================================================================
 
-impl<B, E, C, H> FriProver<B, E, C, H>
+impl<B, E, C, H, V> FriProver<B, E, C, H, V>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     C: ProverChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new FRI prover instantiated with the provided `options`.
+    ///
+    /// Evaluations of FRI layers are kept in memory by this prover.
     pub fn new(options: FriOptions) -> Self {
+        Self::with_storage(options, MemoryLayerStorage::new())
+    }
+}
+
+impl<B, E, C, H, V, S> FriProver<B, E, C, H, V, S>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+    V: VectorCommitment<H>,
+    S: FriLayerStorage<E>,
+{
+    /// Returns a new FRI prover instantiated with the provided `options` which keeps evaluations
+    /// of FRI layers in the provided `storage`.
+    ///
+    /// # Panics
+    /// Panics if the `storage` is not empty.
+    pub fn with_storage(options: FriOptions, storage: S) -> Self {
+        assert_eq!(storage.num_layers(), 0, "FRI layer storage must be empty");
         FriProver {
             options,
             layers: Vec::new(),
+            layer_nonces: Vec::new(),
+            storage,
             remainder_poly: FriRemainder(vec![]),
             _channel: PhantomData,
         }
@@ -133,6 +176,9 @@ where
     // --------------------------------------------------------------------------------------------
 
     /// Returns folding factor for this prover.
+    ///
+    /// If the prover was instantiated with a folding schedule, this is the factor for the layers
+    /// following the schedule; see [FriOptions::folding_factor_at()].
     pub fn folding_factor(&self) -> usize {
         self.options.folding_factor()
     }
@@ -148,9 +194,12 @@ where
         self.layers.len()
     }
 
-    /// Clears a vector of internally stored layers.
+    /// Clears a vector of internally stored layers, and removes layer evaluations from the layer
+    /// storage.
     pub fn reset(&mut self) {
         self.layers.clear();
+        self.layer_nonces.clear();
+        self.storage.clear();
         self.remainder_poly.0.clear();
     }
 
@@ -161,57 +210,141 @@ where
     /// During this phase we repeatedly apply a degree-respecting projection (DRP) to
     /// `evaluations` which contain evaluations of some function *f* over domain *D*. With every
     /// application of the DRP the degree of the function (and size of the domain) is reduced by
-    /// `folding_factor` until the remaining evaluations can be represented by a remainder polynomial
+    /// the folding factor of the layer (see [FriOptions::folding_factor_at()]) until the remaining
+    /// evaluations can be represented by a remainder polynomial
     /// with at most `remainder_max_degree_plus_1` number of coefficients.
-    /// At each layer of reduction the current evaluations are committed to using a Merkle tree,
-    /// and the root of this tree is written into the channel. After this the prover draws a random
-    /// field element α from the channel, and uses it in the next application of the DRP.
+    /// At each layer of reduction the current evaluations are committed to using the vector
+    /// commitment `V` (a Merkle tree by default), and the commitment is written into the channel. After this the prover draws a random
+    /// field element α from the channel, and uses it in the next application of the DRP. If
+    /// layer proof-of-work is required by the options, the prover grinds a nonce for the layer
+    /// before drawing α.
     ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
-    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        assert!(
+            self.layers.is_empty(),
+            "a prior proof generation request has not been completed yet"
+        );
+        self.build_remaining_layers(channel, evaluations);
+    }
+
+    /// Executes the commit phase of the FRI protocol for evaluations provided via an iterator.
+    ///
+    /// This is equivalent to [build_layers()](FriProver::build_layers()), but evaluations of the
+    /// first layer are written directly into the transposed form in which the layer is committed
+    /// to. Thus, evaluations of *f* in their original order never need to be held in memory
+    /// (e.g., they can be computed on the fly, or read from disk).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The prover state is dirty (the vector of layers is not empty).
+    /// * The number of evaluations yielded by the iterator is different from the length reported
+    ///   by the iterator, or the length is not a power of two.
+    pub fn build_layers_from_iter<I>(&mut self, channel: &mut C, evaluations: I)
+    where
+        I: IntoIterator<Item = E>,
+        I::IntoIter: ExactSizeIterator,
+    {
         assert!(
             self.layers.is_empty(),
             "a prior proof generation request has not been completed yet"
         );
+        let evaluations = evaluations.into_iter();
+        let domain_size = evaluations.len();
+        assert!(
+            domain_size.is_power_of_two(),
+            "number of evaluations must be a power of two, but was {domain_size}"
+        );
 
-        // reduce the degree by folding_factor at each iteration until the remaining polynomial
+        if self.options.num_fri_layers(domain_size) == 0 {
+            let evaluations = evaluations.collect::<Vec<_>>();
+            assert_eq!(
+                domain_size,
+                evaluations.len(),
+                "unexpected number of evaluations"
+            );
+            return self.build_remaining_layers(channel, evaluations);
+        }
+
+        // commit to and fold the first layer, and then proceed in the same way as for
+        // evaluations provided as a vector
+        let folding_factor = self.options.folding_factor_at(0);
+        let evaluations = match folding_factor {
+            2 => self.fold_layer::<2>(channel, transpose_iter(evaluations, domain_size)),
+            4 => self.fold_layer::<4>(channel, transpose_iter(evaluations, domain_size)),
+            8 => self.fold_layer::<8>(channel, transpose_iter(evaluations, domain_size)),
+            16 => self.fold_layer::<16>(channel, transpose_iter(evaluations, domain_size)),
+            _ => unimplemented!("folding factor {} is not supported", folding_factor),
+        };
+        self.build_remaining_layers(channel, evaluations);
+    }
+
+    /// Builds FRI layers following the ones already built, and sets the remainder polynomial.
+    fn build_remaining_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+        // reduce the degree by the folding factor of each layer until the remaining polynomial
         // has small enough degree
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) {
-            match self.folding_factor() {
-                2 => self.build_layer::<2>(channel, &mut evaluations),
-                4 => self.build_layer::<4>(channel, &mut evaluations),
-                8 => self.build_layer::<8>(channel, &mut evaluations),
-                16 => self.build_layer::<16>(channel, &mut evaluations),
-                _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
-            }
+        let num_layers = self.layers.len() + self.options.num_fri_layers(evaluations.len());
+        for layer_idx in self.layers.len()..num_layers {
+            let folding_factor = self.options.folding_factor_at(layer_idx);
+            evaluations = match folding_factor {
+                2 => self.build_layer::<2>(channel, evaluations),
+                4 => self.build_layer::<4>(channel, evaluations),
+                8 => self.build_layer::<8>(channel, evaluations),
+                16 => self.build_layer::<16>(channel, evaluations),
+                _ => unimplemented!("folding factor {} is not supported", folding_factor),
+            };
         }
 
         self.set_remainder(channel, &mut evaluations);
     }
 
-    /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
-    /// alpha from the channel and use it to perform degree-respecting projection.
-    fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: &mut Vec<E>) {
-        // commit to the evaluations at the current layer; we do this by first transposing the
-        // evaluations into a matrix of N columns, and then building a Merkle tree from the
-        // rows of this matrix; we do this so that we could de-commit to N values with a single
-        // Merkle authentication path.
-        let transposed_evaluations = transpose_slice(evaluations);
+    /// Builds a single FRI layer from the `evaluations` and returns the evaluations of the next
+    /// layer.
+    fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: Vec<E>) -> Vec<E> {
+        // transpose the evaluations into a matrix of N columns; we do this so that we could
+        // de-commit to N values with a single opening proof. evaluations in their original order
+        // are no longer needed after this.
+        let transposed_evaluations = transpose_slice(&evaluations);
+        drop(evaluations);
+        self.fold_layer::<N>(channel, transposed_evaluations)
+    }
+
+    /// Commits to the `transposed_evaluations` of a single FRI layer, then draws a random alpha
+    /// from the channel and uses it to perform degree-respecting projection. The evaluations are
+    /// moved into the layer storage, and evaluations of the next layer are returned.
+    fn fold_layer<const N: usize>(
+        &mut self,
+        channel: &mut C,
+        transposed_evaluations: Vec<[E; N]>,
+    ) -> Vec<E> {
+        // commit to the evaluations at the current layer by committing to the hashes of the rows
+        // of the transposed matrix
+        let domain_size = transposed_evaluations.len() * N;
         let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
-        let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
-        channel.commit_fri_layer(*evaluation_tree.root());
+        let evaluation_commitment =
+            V::commit(hashed_evaluations).expect("failed to construct FRI layer commitment");
+        channel.commit_fri_layer(evaluation_commitment.commitment());
+
+        // perform proof-of-work for the layer if required
+        let grinding_factor = self.options.layer_grinding_factor();
+        if grinding_factor > 0 {
+            self.layer_nonces
+                .push(channel.grind_fri_layer(grinding_factor));
+        }
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
         let alpha = channel.draw_fri_alpha();
-        *evaluations = apply_drp(&transposed_evaluations, self.domain_offset(), alpha);
+        let folded_evaluations = apply_drp(&transposed_evaluations, self.domain_offset(), alpha);
+        self.storage
+            .store_layer(flatten_vector_elements(transposed_evaluations));
         self.layers.push(FriLayer {
-            tree: evaluation_tree,
-            evaluations: flatten_vector_elements(transposed_evaluations),
-            _base_field: PhantomData,
+            commitment: evaluation_commitment,
+            domain_size,
+            _hasher: PhantomData,
         });
+        folded_evaluations
     }
 
     /// Creates remainder polynomial in coefficient form from a vector of `evaluations` over a domain.
@@ -246,35 +379,37 @@ where
 
         if !self.layers.is_empty() {
             let mut positions = positions.to_vec();
-            let mut domain_size = self.layers[0].evaluations.len();
-            let folding_factor = self.options.folding_factor();
 
             // for all FRI layers, except the last one, record tree root, determine a set of query
             // positions, and query the layer at these positions.
             for i in 0..self.layers.len() {
+                let domain_size = self.layers[i].domain_size;
+                let folding_factor = self.options.folding_factor_at(i);
                 positions = fold_positions(&positions, domain_size, folding_factor);
 
                 // sort of a static dispatch for folding_factor parameter
+                let layer = &self.layers[i];
+                let storage = &self.storage;
                 let proof_layer = match folding_factor {
-                    2 => query_layer::<B, E, H, 2>(&self.layers[i], &positions),
-                    4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
-                    8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
-                    16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
+                    2 => query_layer::<E, H, V, S, 2>(layer, storage, i, &positions),
+                    4 => query_layer::<E, H, V, S, 4>(layer, storage, i, &positions),
+                    8 => query_layer::<E, H, V, S, 8>(layer, storage, i, &positions),
+                    16 => query_layer::<E, H, V, S, 16>(layer, storage, i, &positions),
                     _ => unimplemented!("folding factor {} is not supported", folding_factor),
                 };
 
                 layers.push(proof_layer);
-                domain_size /= folding_factor;
             }
         }
 
         // use the remaining polynomial values directly as proof
         let remainder = self.remainder_poly.0.clone();
+        let layer_nonces = self.layer_nonces.clone();
 
         // clear layers so that another proof can be generated
         self.reset();
 
-        FriProof::new(layers, remainder, 1)
+        FriProof::new(layers, remainder, 1).with_layer_nonces(layer_nonces)
     }
 }
 
@@ -282,25 +417,59 @@ where
 // ================================================================================================
 
 /// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
-/// specified positions.
-fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N: usize>(
-    layer: &FriLayer<B, E, H>,
+/// specified positions; the evaluations of the layer are read from the `storage`.
+fn query_layer<E, H, V, S, const N: usize>(
+    layer: &FriLayer<H, V>,
+    storage: &S,
+    layer_idx: usize,
     positions: &[usize],
-) -> FriProofLayer {
-    // build Merkle authentication paths for all query positions
-    let proof = layer
-        .tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for FRI layer queries");
+) -> FriProofLayer
+where
+    E: FieldElement,
+    H: Hasher,
+    V: VectorCommitment<H>,
+    S: FriLayerStorage<E>,
+{
+    // build an opening proof for all query positions
+    let (_, proof) = layer
+        .commitment
+        .open_many(positions)
+        .expect("failed to generate an opening proof for FRI layer queries");
 
     // build a list of polynomial evaluations at each position; since evaluations in FRI layers
     // are stored in transposed form, a position refers to N evaluations which are committed
     // in a single leaf
-    let evaluations: &[[E; N]] = group_slice_elements(&layer.evaluations);
-    let mut queried_values: Vec<[E; N]> = Vec::with_capacity(positions.len());
-    for &position in positions.iter() {
-        queried_values.push(evaluations[position]);
-    }
+    let evaluations = storage.read_rows(layer_idx, N, positions);
+    let queried_values: Vec<[E; N]> = group_slice_elements(&evaluations).to_vec();
 
-    FriProofLayer::new(queried_values, proof)
+    FriProofLayer::new::<H, V, E, N>(queried_values, proof)
+}
+
+/// Writes evaluations yielded by the iterator into a matrix of N columns in the same way as
+/// [transpose_slice] does; i.e., the evaluation at index i is written into row i % (n / N) and
+/// column i / (n / N), where n is the number of evaluations.
+///
+/// # Panics
+/// Panics if the iterator does not yield exactly `domain_size` evaluations.
+fn transpose_iter<E, I, const N: usize>(evaluations: I, domain_size: usize) -> Vec<[E; N]>
+where
+    E: FieldElement,
+    I: Iterator<Item = E>,
+{
+    let num_rows = domain_size / N;
+    let mut result = vec![[E::ZERO; N]; num_rows];
+    let mut num_evaluations = 0;
+    for (i, value) in evaluations.enumerate() {
+        assert!(
+            i < domain_size,
+            "iterator yielded more than {domain_size} evaluations"
+        );
+        result[i % num_rows][i / num_rows] = value;
+        num_evaluations += 1;
+    }
+    assert_eq!(
+        domain_size, num_evaluations,
+        "iterator yielded {num_evaluations} evaluations, but {domain_size} were expected"
+    );
+    result
 }
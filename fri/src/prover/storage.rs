@@ -0,0 +1,204 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::FieldElement;
+use utils::collections::Vec;
+
+#[cfg(feature = "std")]
+use std::{
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "std")]
+use utils::SliceReader;
+
+// FRI LAYER STORAGE
+// ================================================================================================
+
+/// Defines how a [FriProver](super::FriProver) stores evaluations of FRI layers between the
+/// commit phase and the query phase of the protocol.
+///
+/// Evaluations of a layer are passed to the storage right after the layer has been committed to
+/// and folded; after that, the prover only needs to read the rows of the layer which are queried
+/// by the verifier. Evaluations of a layer are stored in transposed form: every consecutive
+/// `row_width` values form a single row of the committed matrix, where `row_width` is the folding
+/// factor of the layer.
+pub trait FriLayerStorage<E: FieldElement> {
+    /// Stores the (transposed) evaluations of the next FRI layer.
+    fn store_layer(&mut self, evaluations: Vec<E>);
+
+    /// Returns the number of layers currently in this storage.
+    fn num_layers(&self) -> usize;
+
+    /// Returns the rows at the specified `positions` of the layer at index `layer_idx`; the rows
+    /// are concatenated into a single vector of `positions.len() * row_width` values.
+    ///
+    /// # Panics
+    /// Panics if the layer does not exist, or if any of the positions is out of bounds.
+    fn read_rows(&self, layer_idx: usize, row_width: usize, positions: &[usize]) -> Vec<E>;
+
+    /// Removes all layers from this storage.
+    fn clear(&mut self);
+}
+
+// MEMORY LAYER STORAGE
+// ================================================================================================
+
+/// Keeps evaluations of all FRI layers in memory.
+///
+/// This is the storage used by a [FriProver](super::FriProver) by default.
+pub struct MemoryLayerStorage<E: FieldElement> {
+    layers: Vec<Vec<E>>,
+}
+
+impl<E: FieldElement> MemoryLayerStorage<E> {
+    /// Returns a new empty in-memory layer storage.
+    pub fn new() -> Self {
+        MemoryLayerStorage { layers: Vec::new() }
+    }
+}
+
+impl<E: FieldElement> Default for MemoryLayerStorage<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: FieldElement> FriLayerStorage<E> for MemoryLayerStorage<E> {
+    fn store_layer(&mut self, evaluations: Vec<E>) {
+        self.layers.push(evaluations);
+    }
+
+    fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    fn read_rows(&self, layer_idx: usize, row_width: usize, positions: &[usize]) -> Vec<E> {
+        let evaluations = &self.layers[layer_idx];
+        let mut result = Vec::with_capacity(positions.len() * row_width);
+        for &position in positions.iter() {
+            let start = position * row_width;
+            result.extend_from_slice(&evaluations[start..start + row_width]);
+        }
+        result
+    }
+
+    fn clear(&mut self) {
+        self.layers.clear();
+    }
+}
+
+// FILE LAYER STORAGE
+// ================================================================================================
+
+/// Identifies instances of [FileLayerStorage] created by this process; used to make sure that
+/// different instances never write into the same files.
+#[cfg(feature = "std")]
+static NEXT_STORAGE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Spills evaluations of FRI layers to files on disk.
+///
+/// Every layer is written into a separate file in the specified directory, and reading the rows
+/// of a layer reads only these rows from the file. This way, evaluations of a layer occupy memory
+/// only while the layer is being committed to and folded. Layer commitments (e.g., Merkle trees)
+/// are still kept in memory by the prover.
+///
+/// The files are deleted when the storage is cleared (which the prover does after a proof has
+/// been built), or when the storage is dropped.
+///
+/// This storage is available only when the crate is compiled with `std` feature enabled.
+///
+/// # Panics
+/// All methods of this storage panic if an I/O error is encountered.
+#[cfg(feature = "std")]
+pub struct FileLayerStorage<E: FieldElement> {
+    dir: PathBuf,
+    storage_id: usize,
+    layers: Vec<(PathBuf, File)>,
+    _field_element: core::marker::PhantomData<E>,
+}
+
+#[cfg(feature = "std")]
+impl<E: FieldElement> FileLayerStorage<E> {
+    /// Returns a new layer storage which writes layer evaluations into files in the specified
+    /// directory; the directory must exist.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        FileLayerStorage {
+            dir: dir.into(),
+            storage_id: NEXT_STORAGE_ID.fetch_add(1, Ordering::Relaxed),
+            layers: Vec::new(),
+            _field_element: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the directory into which layer evaluations are written.
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: FieldElement> FriLayerStorage<E> for FileLayerStorage<E> {
+    fn store_layer(&mut self, evaluations: Vec<E>) {
+        let file_name = format!(
+            "fri-layer-{}-{}-{}.bin",
+            std::process::id(),
+            self.storage_id,
+            self.layers.len()
+        );
+        let path = self.dir.join(file_name);
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .expect("failed to create a file for FRI layer evaluations");
+        file.write_all(E::elements_as_bytes(&evaluations))
+            .expect("failed to write FRI layer evaluations");
+        self.layers.push((path, file));
+    }
+
+    fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    fn read_rows(&self, layer_idx: usize, row_width: usize, positions: &[usize]) -> Vec<E> {
+        let mut file = &self.layers[layer_idx].1;
+        let row_bytes = row_width * E::ELEMENT_BYTES;
+        let mut buffer = vec![0u8; row_bytes];
+        let mut result = Vec::with_capacity(positions.len() * row_width);
+        for &position in positions.iter() {
+            file.seek(SeekFrom::Start((position * row_bytes) as u64))
+                .expect("failed to seek to a FRI layer row");
+            file.read_exact(&mut buffer)
+                .expect("failed to read a FRI layer row");
+            let row = E::read_batch_from(&mut SliceReader::new(&buffer), row_width)
+                .expect("failed to parse a FRI layer row");
+            result.extend_from_slice(&row);
+        }
+        result
+    }
+
+    fn clear(&mut self) {
+        for (path, file) in self.layers.drain(..) {
+            drop(file);
+            fs::remove_file(path).expect("failed to remove a FRI layer file");
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: FieldElement> Drop for FileLayerStorage<E> {
+    fn drop(&mut self) {
+        // ignore errors here since panicking during drop could abort the process
+        for (path, file) in self.layers.drain(..) {
+            drop(file);
+            let _ = fs::remove_file(path);
+        }
+    }
+}
@@ -3,12 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{DefaultProverChannel, FriProver};
+use super::{DefaultProverChannel, FriProver, MemoryLayerStorage};
 use crate::{
+    batch::BatchCombiner,
     verifier::{DefaultVerifierChannel, FriVerifier},
     FriOptions, FriProof, VerifierError,
 };
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin};
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, MerkleTree, RandomCoin};
 use math::{fft, fields::f128::BaseElement, FieldElement};
 use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
 
@@ -45,6 +46,387 @@ fn fri_folding_4() {
     )
 }
 
+#[test]
+fn fri_verifier_public_coin_in_sync_with_prover() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 255);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // after the commit phase, the verifier's public coin must be in the same state as the
+    // prover's; thus, the verifier must draw the same query positions as the prover did
+    let domain_size = trace_length * lde_blowup;
+    let mut verifier_channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof,
+        channel.layer_commitments().to_vec(),
+        domain_size,
+        &options,
+    )
+    .unwrap();
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    FriVerifier::new(&mut verifier_channel, &mut coin, options, trace_length - 1).unwrap();
+    assert_eq!(positions, coin.draw_integers(32, domain_size).unwrap());
+}
+
+#[test]
+fn fri_folding_schedule() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let max_remainder_degree = 7;
+    let options = FriOptions::with_folding_schedule(lde_blowup, &[8, 4], max_remainder_degree);
+    assert_eq!(
+        vec![8, 4, 4, 4],
+        options.layer_folding_factors(trace_length * lde_blowup)
+    );
+    fri_prove_verify_with_options(trace_length, options)
+}
+
+#[test]
+fn fri_folding_schedule_uniform() {
+    let options = FriOptions::with_folding_schedule(8, &[4, 4, 4], 255);
+    assert!(options.is_uniform());
+    assert!(options == FriOptions::new(8, 4, 255));
+}
+
+#[test]
+fn fri_folding_schedule_mismatch() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::with_folding_schedule(lde_blowup, &[8, 4], 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options);
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // the proof should not be parsable by a verifier which expects a different folding schedule
+    let commitments = channel.layer_commitments().to_vec();
+    let result = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof,
+        commitments,
+        trace_length * lde_blowup,
+        &FriOptions::with_folding_schedule(lde_blowup, &[4, 8], 7),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_remainder_commitment_mismatch() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // replace the remainder with a polynomial of the same degree
+    let mut remainder = proof.parse_remainder::<BaseElement>().unwrap();
+    remainder[0] += BaseElement::ONE;
+    let proof = FriProof::from_raw_parts(
+        proof.layers().to_vec(),
+        remainder.to_bytes(),
+        proof.num_partitions(),
+    );
+
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::RemainderCommitmentMismatch), result);
+}
+
+#[test]
+fn fri_layer_grinding() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 31).with_layer_grinding(8);
+    fri_prove_verify_with_options(trace_length, options.clone());
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let num_layers = options.num_fri_layers(trace_length * lde_blowup);
+    assert_eq!(num_layers, proof.layer_nonces().len());
+    let commitments = channel.layer_commitments().to_vec();
+
+    // a proof with an invalid nonce is rejected
+    let mut layer_nonces = proof.layer_nonces().to_vec();
+    layer_nonces[0] = 0;
+    let invalid_proof = FriProof::from_raw_parts(
+        proof.layers().to_vec(),
+        proof.remainder_bytes().to_vec(),
+        proof.num_partitions(),
+    );
+    let result = verify_proof(
+        invalid_proof.clone().with_layer_nonces(layer_nonces),
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::LayerPowVerificationFailed(0)), result);
+
+    // so is a proof without nonces
+    let result = verify_proof(
+        invalid_proof,
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert_eq!(
+        Err(VerifierError::NumLayerNoncesMismatch(num_layers, 0)),
+        result
+    );
+
+    // nonces are not accepted when layer proof-of-work is not required
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options.with_layer_grinding(0),
+    );
+    assert_eq!(
+        Err(VerifierError::NumLayerNoncesMismatch(0, num_layers)),
+        result
+    );
+}
+
+#[test]
+fn fri_build_layers_from_iter() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::with_folding_schedule(lde_blowup, &[8, 4], 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let expected = prover.build_proof(&positions);
+
+    // the proof does not depend on how the evaluations were provided
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover: FriProver<_, _, _, _> =
+        FriProver::with_storage(options.clone(), MemoryLayerStorage::new());
+    prover.build_layers_from_iter(&mut channel, evaluations.iter().copied());
+    assert_eq!(positions, channel.draw_query_positions());
+    assert_eq!(expected, prover.build_proof(&positions));
+}
+
+#[test]
+fn fri_proof_size_estimation() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let domain_size = trace_length * lde_blowup;
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    for options in [
+        FriOptions::new(lde_blowup, 2, 7),
+        FriOptions::new(lde_blowup, 4, 255),
+        FriOptions::with_folding_schedule(lde_blowup, &[8, 4], 7),
+        FriOptions::new(lde_blowup, 8, 31).with_layer_grinding(4),
+    ] {
+        let mut channel = build_prover_channel(trace_length, &options);
+        let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+
+        let proof_size = proof.to_bytes().len();
+        assert_eq!(proof_size, proof.size());
+
+        // the estimate is within 5% of the actual proof size
+        let estimate = FriProof::estimated_size::<BaseElement, Blake3>(&options, domain_size, 32);
+        let difference = (estimate as f64 - proof_size as f64).abs();
+        assert!(
+            difference < proof_size as f64 * 0.05,
+            "estimated size {estimate} is too far from actual size {proof_size}"
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn fri_file_layer_storage() {
+    use super::FileLayerStorage;
+
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let expected = prover.build_proof(&positions);
+
+    // layers spilled to disk produce the same proof
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover: FriProver<_, _, _, _, MerkleTree<Blake3>, FileLayerStorage<_>> =
+        FriProver::with_storage(options.clone(), FileLayerStorage::new(std::env::temp_dir()));
+    prover.build_layers_from_iter(&mut channel, evaluations.iter().copied());
+    assert_eq!(positions, channel.draw_query_positions());
+    assert_eq!(expected, prover.build_proof(&positions));
+    assert_eq!(0, prover.num_layers());
+}
+
+#[test]
+fn fri_verifier_channel_from_raw_parts() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // build the verifier channel from the parsed components of the proof
+    let parse_layers = || {
+        proof
+            .view()
+            .parse_layers_with::<Blake3, MerkleTree<Blake3>, BaseElement>(domain_size, &options)
+            .unwrap()
+    };
+    let build_channel = |layer_queries| {
+        DefaultVerifierChannel::<BaseElement, Blake3, MerkleTree<Blake3>>::from_raw_parts(
+            channel.layer_commitments().to_vec(),
+            layer_queries,
+            parse_layers().1,
+            proof.layer_nonces().to_vec(),
+            proof.parse_remainder().unwrap(),
+            proof.num_partitions(),
+        )
+    };
+    let queried_evaluations = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+
+    let (layer_queries, _) = parse_layers();
+    let mut verifier_channel = build_channel(layer_queries.clone());
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = FriVerifier::new(
+        &mut verifier_channel,
+        &mut coin,
+        options.clone(),
+        trace_length - 1,
+    )
+    .unwrap();
+    assert!(verifier
+        .verify(&mut verifier_channel, &queried_evaluations, &positions)
+        .is_ok());
+
+    // query values which do not hash into the opened items of the layer proof are rejected
+    let mut invalid_queries = layer_queries;
+    invalid_queries[1][0] += BaseElement::ONE;
+    let mut verifier_channel = build_channel(invalid_queries);
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier =
+        FriVerifier::new(&mut verifier_channel, &mut coin, options, trace_length - 1).unwrap();
+    assert_eq!(
+        Err(VerifierError::LayerCommitmentMismatch),
+        verifier.verify(&mut verifier_channel, &queried_evaluations, &positions)
+    );
+}
+
+// BATCHED PROVE/VERIFY TEST
+// ================================================================================================
+
+#[test]
+fn fri_batch_prove_verify() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let degree_bounds = vec![trace_length - 1, 255, 1023];
+    assert!(batch_prove_verify(trace_length, lde_blowup, &degree_bounds, &degree_bounds).is_ok());
+
+    // a polynomial which exceeds its degree bound should be detected even though its degree is
+    // smaller than the degree of the combined polynomial
+    let claimed_bounds = vec![trace_length - 1, 255, 511];
+    assert!(batch_prove_verify(trace_length, lde_blowup, &degree_bounds, &claimed_bounds).is_err());
+}
+
+fn batch_prove_verify(
+    trace_length: usize,
+    lde_blowup: usize,
+    degrees: &[usize],
+    claimed_bounds: &[usize],
+) -> Result<(), VerifierError> {
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let domain_size = trace_length * lde_blowup;
+    let max_degree = trace_length - 1;
+    let evaluations = degrees
+        .iter()
+        .map(|&degree| build_evaluations(degree + 1, domain_size / (degree + 1)))
+        .collect::<Vec<_>>();
+
+    // combine the evaluations using coefficients drawn from a public coin
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::ONE]);
+    let combiner = BatchCombiner::draw(claimed_bounds.to_vec(), max_degree, &mut coin).unwrap();
+    let combined = combiner.combine_evaluations(&evaluations, options.domain_offset());
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, combined);
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // the verifier combines the values of individual polynomials at the queried positions
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::ONE]);
+    let combiner = BatchCombiner::draw(claimed_bounds.to_vec(), max_degree, &mut coin).unwrap();
+    let values = evaluations
+        .iter()
+        .map(|evaluations| positions.iter().map(|&p| evaluations[p]).collect())
+        .collect::<Vec<_>>();
+    let combined =
+        combiner.combine_queries(&values, &positions, domain_size, options.domain_offset());
+
+    let commitments = channel.layer_commitments().to_vec();
+    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof,
+        commitments,
+        domain_size,
+        &options,
+    )
+    .unwrap();
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier = FriVerifier::new(&mut channel, &mut coin, options, max_degree)?;
+    verifier.verify(&mut channel, &combined, &positions)
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -85,20 +467,24 @@ pub fn verify_proof(
     let proof = FriProof::read_from(&mut reader).unwrap();
 
     // verify the proof
-    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
-        proof,
-        commitments,
+    assert_eq!(
         domain_size,
-        options.folding_factor(),
-    )
-    .unwrap();
+        max_degree.next_power_of_two() * options.blowup_factor()
+    );
     let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
-    let verifier = FriVerifier::new(&mut channel, &mut coin, options.clone(), max_degree)?;
     let queried_evaluations = positions
         .iter()
         .map(|&p| evaluations[p])
         .collect::<Vec<_>>();
-    verifier.verify(&mut channel, &queried_evaluations, positions)
+    crate::verify_proof(
+        proof,
+        commitments,
+        &queried_evaluations,
+        positions,
+        max_degree,
+        options,
+        &mut coin,
+    )
 }
 
 fn fri_prove_verify(
@@ -112,11 +498,16 @@ fn fri_prove_verify(
     let folding_factor = 1 << folding_factor_e;
 
     let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_degree);
+    fri_prove_verify_with_options(trace_length, options)
+}
+
+fn fri_prove_verify_with_options(trace_length: usize, options: FriOptions) {
+    let lde_blowup = options.blowup_factor();
     let mut channel = build_prover_channel(trace_length, &options);
     let evaluations = build_evaluations(trace_length, lde_blowup);
 
     // instantiate the prover and generate the proof
-    let mut prover = FriProver::new(options.clone());
+    let mut prover: FriProver<_, _, _, _> = FriProver::new(options.clone());
     prover.build_layers(&mut channel, evaluations.clone());
     let positions = channel.draw_query_positions();
     let proof = prover.build_proof(&positions);
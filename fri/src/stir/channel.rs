@@ -0,0 +1,114 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::marker::PhantomData;
+use crypto::{ElementHasher, Hasher, RandomCoin};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// STIR PROVER CHANNEL TRAIT
+// ================================================================================================
+
+/// Defines an interface for a channel over which a STIR prover communicates with a verifier.
+///
+/// In every round, the prover uses this channel to send a commitment to the evaluations of the
+/// folded polynomial and its value at an out-of-domain point, and to draw the randomness used
+/// for folding, for picking the out-of-domain point, and for combining the constraints on the
+/// next polynomial, as well as the positions of the shift queries. In the last round, the prover
+/// sends the final polynomial in coefficient form instead of a commitment.
+///
+/// In the interactive version of the protocol, the verifier chooses all random values uniformly
+/// at random. In the non-interactive version, the values are drawn pseudo-randomly based on the
+/// messages the prover has written into the channel up to this point.
+pub trait StirProverChannel<E: FieldElement> {
+    /// Hash function used by the prover to commit to polynomial evaluations.
+    type Hasher: ElementHasher<BaseField = E::BaseField>;
+
+    /// Sends a commitment to the evaluations of a round polynomial to the verifier.
+    ///
+    /// Similarly to FRI layer commitments, the commitment is a root of a Merkle tree in which
+    /// each leaf contains the `folding_factor` evaluations needed to fold the polynomial at a
+    /// single point.
+    fn commit_stir_round(&mut self, commitment: <Self::Hasher as Hasher>::Digest);
+
+    /// Sends the value of the folded polynomial at the out-of-domain point to the verifier.
+    fn send_ood_answer(&mut self, answer: E);
+
+    /// Sends the final polynomial in coefficient form to the verifier.
+    fn send_final_poly(&mut self, poly: &[E]);
+
+    /// Returns a random value drawn uniformly at random from the entire field.
+    fn draw_stir_challenge(&mut self) -> E;
+
+    /// Returns a set of `num_queries` unique positions in the range [0, `domain_size`) at which
+    /// the committed evaluations of the current round should be queried.
+    fn draw_stir_queries(&mut self, num_queries: usize, domain_size: usize) -> Vec<usize>;
+}
+
+// DEFAULT STIR PROVER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+/// Provides a default implementation of the [StirProverChannel] trait.
+///
+/// All messages sent by the prover are absorbed into a public coin: commitments directly, and
+/// field elements via their hash. A [StirVerifier](super::StirVerifier) instantiated with a
+/// coin in the same initial state draws the same values.
+pub struct DefaultStirProverChannel<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    public_coin: R,
+    _field_element: PhantomData<E>,
+}
+
+impl<E, H, R> DefaultStirProverChannel<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    /// Returns a new prover channel which draws random values from the specified `public_coin`.
+    pub fn new(public_coin: R) -> Self {
+        DefaultStirProverChannel {
+            public_coin,
+            _field_element: PhantomData,
+        }
+    }
+}
+
+impl<E, H, R> StirProverChannel<E> for DefaultStirProverChannel<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    type Hasher = H;
+
+    fn commit_stir_round(&mut self, commitment: H::Digest) {
+        self.public_coin.reseed(commitment);
+    }
+
+    fn send_ood_answer(&mut self, answer: E) {
+        self.public_coin.reseed(H::hash_elements(&[answer]));
+    }
+
+    fn send_final_poly(&mut self, poly: &[E]) {
+        self.public_coin.reseed(H::hash_elements(poly));
+    }
+
+    fn draw_stir_challenge(&mut self) -> E {
+        self.public_coin
+            .draw()
+            .expect("failed to draw STIR challenge")
+    }
+
+    fn draw_stir_queries(&mut self, num_queries: usize, domain_size: usize) -> Vec<usize> {
+        self.public_coin
+            .draw_integers(num_queries, domain_size)
+            .expect("failed to draw STIR query positions")
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains an implementation of the STIR protocol, a low-degree test which can be used as an
+//! alternative to FRI.
+//!
+//! Similarly to FRI, STIR reduces the degree of a polynomial by a folding factor *k* in every
+//! round. However, instead of shrinking the evaluation domain by the same factor, STIR shrinks it
+//! only by a factor of 2 (the polynomial of the next round is evaluated over a fresh domain).
+//! Thus, the rate of the code improves with every round, and the verifier needs fewer queries
+//! in later rounds to achieve the same soundness. This is described in "STIR: Reed–Solomon
+//! Proximity Testing with Fewer Queries" by G. Arnon, A. Chiesa, G. Fenzi, and E. Yogev.
+//!
+//! A [StirProver] generates a [StirProof] via a [StirProverChannel], and a [StirVerifier]
+//! verifies the proof by replaying the transcript with a public coin. Unlike a
+//! [FriProof](crate::FriProof), a STIR proof contains commitments to the evaluations of all
+//! round polynomials, including the commitment to the evaluations of the polynomial being
+//! proven; query positions of all rounds are drawn within the protocol.
+//!
+//! Protocol parameters are specified via [StirOptions]; the number of shift queries in each
+//! round is derived from the number of queries in the first round and the rate of the round
+//! (see [StirOptions::num_queries()]).
+//!
+//! A STARK prover uses STIR in place of FRI when proof options select STIR as the low-degree
+//! test (via `ProofOptions::with_ldt()` in the `winter-air` crate). In this case, the STARK
+//! prover executes all rounds via [StirProver::build_rounds()] before it draws query positions,
+//! and then builds the proof via [StirProver::build_proof()], which queries evaluations of the
+//! first round at these positions. The STARK verifier checks the rounds via
+//! [StirVerifier::verify()], and then checks that the queried evaluations match the DEEP
+//! composition polynomial via [StirVerifier::verify_evaluations()].
+
+mod options;
+pub use options::StirOptions;
+
+mod channel;
+pub use channel::{DefaultStirProverChannel, StirProverChannel};
+
+mod prover;
+pub use prover::StirProver;
+
+mod verifier;
+pub use verifier::StirVerifier;
+
+mod proof;
+pub use proof::StirProof;
+
+#[cfg(test)]
+mod tests;
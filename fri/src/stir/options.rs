@@ -0,0 +1,173 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::options::SUPPORTED_FOLDING_FACTORS;
+use math::StarkField;
+use utils::collections::Vec;
+
+// STIR OPTIONS
+// ================================================================================================
+
+/// STIR protocol config options for proof generation and verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StirOptions {
+    folding_factor: usize,
+    remainder_max_degree: usize,
+    blowup_factor: usize,
+    num_queries: usize,
+}
+
+impl StirOptions {
+    /// Returns a new [StirOptions] struct instantiated with the specified parameters.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `blowup_factor` is smaller than 2 or is not a power of two.
+    /// - `folding_factor` is not 2, 4, 8, or 16.
+    /// - `remainder_max_degree` is not a power of two minus 1.
+    /// - `num_queries` is zero.
+    pub fn new(
+        blowup_factor: usize,
+        folding_factor: usize,
+        remainder_max_degree: usize,
+        num_queries: usize,
+    ) -> Self {
+        assert!(
+            blowup_factor.is_power_of_two(),
+            "blowup factor must be a power of two, but was {blowup_factor}"
+        );
+        assert!(
+            blowup_factor >= 2,
+            "blowup factor must be at least 2, but was {blowup_factor}"
+        );
+        assert!(
+            SUPPORTED_FOLDING_FACTORS.contains(&folding_factor),
+            "folding factor {folding_factor} is not supported"
+        );
+        assert!(
+            (remainder_max_degree + 1).is_power_of_two(),
+            "remainder max degree must be one less than a power of two, but was {remainder_max_degree}"
+        );
+        assert!(
+            num_queries > 0,
+            "number of queries must be greater than zero"
+        );
+        StirOptions {
+            folding_factor,
+            remainder_max_degree,
+            blowup_factor,
+            num_queries,
+        }
+    }
+
+    /// Returns the offset by which the evaluation domain of the first round is shifted.
+    ///
+    /// The offset is hard-coded to be the primitive element in the field specified by type
+    /// parameter `B`. Evaluation domains of all subsequent rounds are shifted by the same offset,
+    /// which makes sure that they are disjoint from the domains from which shift queries are
+    /// drawn.
+    pub fn domain_offset<B: StarkField>(&self) -> B {
+        B::GENERATOR
+    }
+
+    /// Returns the factor by which the degree of a polynomial is reduced in each STIR round.
+    pub fn folding_factor(&self) -> usize {
+        self.folding_factor
+    }
+
+    /// Returns maximum allowed remainder polynomial degree.
+    pub fn remainder_max_degree(&self) -> usize {
+        self.remainder_max_degree
+    }
+
+    /// Returns a blowup factor of the evaluation domain of the first round.
+    ///
+    /// The evaluation domain shrinks by a factor of 2 in every round while the degree of the
+    /// polynomial is reduced by the folding factor; thus, the blowup factor of the domain in
+    /// round `i` is `blowup_factor * (folding_factor / 2)^i`.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Returns the number of shift queries made by the verifier in the first STIR round.
+    ///
+    /// Since the rate of the code improves with every round, fewer queries are needed in later
+    /// rounds to achieve the same soundness: the number of queries in round `i` is
+    /// `num_queries * log2(blowup_factor) / log2(blowup_factor_i)` rounded up, where
+    /// `blowup_factor_i` is the blowup factor of the domain in round `i`.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    /// Computes and returns the number of STIR rounds required for a polynomial of the specified
+    /// degree.
+    ///
+    /// The degree is reduced by the folding factor in each round until the polynomial resulting
+    /// from the last round has at most `remainder_max_degree + 1` coefficients, or until the
+    /// degree becomes too small to be reduced further given the number of shift queries in the
+    /// round. In the latter case, the final polynomial has more than `remainder_max_degree + 1`
+    /// coefficients.
+    pub fn num_rounds(&self, max_degree: usize) -> usize {
+        self.round_params(max_degree).len()
+    }
+
+    /// Returns parameters of each STIR round executed for a polynomial of the specified degree.
+    ///
+    /// The evaluation domain of round `i` has size `n / 2^i` and the polynomial in round `i` has
+    /// fewer than `d / folding_factor^i` coefficients, where `n` and `d` are the domain size and
+    /// degree bound of the first round; the number of shift queries in each round is described
+    /// in [num_queries()](StirOptions::num_queries).
+    ///
+    /// A round is the last one if the degree bound resulting from it is at most
+    /// `remainder_max_degree + 1`, or if it is not greater than the number of points at which
+    /// the next polynomial would be constrained (the shift queries and an out-of-domain point).
+    pub(crate) fn round_params(&self, max_degree: usize) -> Vec<RoundParams> {
+        let mut degree_bound = (max_degree + 1).next_power_of_two();
+        let mut domain_size = degree_bound * self.blowup_factor;
+        let base_log_blowup = self.blowup_factor.ilog2() as usize;
+
+        let mut result = Vec::new();
+        loop {
+            let log_blowup = (domain_size / degree_bound).ilog2() as usize;
+            let num_queries = ((self.num_queries * base_log_blowup + log_blowup - 1) / log_blowup)
+                .min((domain_size / self.folding_factor).saturating_sub(1));
+            result.push(RoundParams {
+                domain_size,
+                degree_bound,
+                num_queries,
+            });
+
+            let next_degree_bound = degree_bound / self.folding_factor;
+            if next_degree_bound <= self.remainder_max_degree + 1
+                || next_degree_bound <= num_queries + 1
+            {
+                return result;
+            }
+            degree_bound = next_degree_bound;
+            domain_size /= 2;
+        }
+    }
+
+    /// Returns the number of coefficients of the final polynomial sent by the prover for a
+    /// polynomial of the specified degree.
+    pub(crate) fn final_poly_len(&self, max_degree: usize) -> usize {
+        let rounds = self.round_params(max_degree);
+        (rounds[rounds.len() - 1].degree_bound / self.folding_factor).max(1)
+    }
+}
+
+// ROUND PARAMETERS
+// ================================================================================================
+
+/// Parameters of a single STIR round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RoundParams {
+    /// Size of the evaluation domain of the round.
+    pub domain_size: usize,
+    /// Number of coefficients of the polynomial folded in the round.
+    pub degree_bound: usize,
+    /// Number of shift queries made in the round.
+    pub num_queries: usize,
+}
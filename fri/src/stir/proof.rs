@@ -0,0 +1,373 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::StirOptions;
+use crate::FriProofLayer;
+use crypto::{BatchMerkleProof, Digest, Hasher};
+use math::FieldElement;
+use utils::{
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, SliceReader,
+};
+
+// STIR PROOF
+// ================================================================================================
+
+/// A proof generated by a STIR prover.
+///
+/// A STIR proof contains information proving that a function *f* is a polynomial of some bounded
+/// degree *d*. STIR proofs cannot be instantiated directly - they must be generated by an instance
+/// of a [StirProver](super::StirProver), and can be verified by an instance of a
+/// [StirVerifier](super::StirVerifier).
+///
+/// A proof consists of:
+/// * Commitments to the evaluations of the polynomials in each round; the first commitment is
+///   to the evaluations of *f* itself.
+/// * Values of the folded polynomials at out-of-domain points (one for each round but the last).
+/// * Evaluations queried in each round together with Merkle authentication paths for these
+///   evaluations; these are stored in the same format as [FriProofLayer]s.
+/// * The final polynomial given by its list of coefficients.
+/// * Optionally, evaluations of *f* at positions requested after the protocol was executed (see
+///   [StirProver::build_proof()](super::StirProver::build_proof)); these are used to check that
+///   the committed evaluations are consistent with evaluations computed by the verifier (e.g.,
+///   from DEEP composition of a STARK proof).
+///
+/// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
+/// they can be used.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StirProof {
+    commitments: Vec<u8>,
+    ood_answers: Vec<u8>,
+    rounds: Vec<FriProofLayer>,
+    final_poly: Vec<u8>,
+    input_queries: Option<FriProofLayer>,
+}
+
+impl StirProof {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new STIR proof from the provided round commitments, out-of-domain answers,
+    /// round queries, and the final polynomial.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of commitments is not equal to the number of rounds.
+    /// * The number of out-of-domain answers is not equal to the number of rounds minus one.
+    /// * The final polynomial is empty.
+    pub(crate) fn new<H: Hasher, E: FieldElement>(
+        commitments: &[H::Digest],
+        ood_answers: &[E],
+        rounds: Vec<FriProofLayer>,
+        final_poly: &[E],
+    ) -> Self {
+        assert_eq!(
+            commitments.len(),
+            rounds.len(),
+            "number of commitments must be equal to the number of rounds"
+        );
+        assert_eq!(
+            ood_answers.len() + 1,
+            rounds.len(),
+            "number of out-of-domain answers must be one less than the number of rounds"
+        );
+        assert!(!final_poly.is_empty(), "final polynomial cannot be empty");
+        StirProof {
+            commitments: commitments.to_bytes(),
+            ood_answers: ood_answers.to_bytes(),
+            rounds,
+            final_poly: final_poly.to_bytes(),
+            input_queries: None,
+        }
+    }
+
+    /// Creates a new STIR proof from its raw parts.
+    ///
+    /// This is intended to be used by alternative proof encodings which need to reconstruct a
+    /// proof from its raw parts; no validation of the parts is performed.
+    pub fn from_raw_parts(
+        commitments: Vec<u8>,
+        ood_answers: Vec<u8>,
+        rounds: Vec<FriProofLayer>,
+        final_poly: Vec<u8>,
+        input_queries: Option<FriProofLayer>,
+    ) -> Self {
+        StirProof {
+            commitments,
+            ood_answers,
+            rounds,
+            final_poly,
+            input_queries,
+        }
+    }
+
+    /// Returns this proof with the specified queries of the first round evaluations.
+    pub(crate) fn with_input_queries(mut self, input_queries: FriProofLayer) -> Self {
+        self.input_queries = Some(input_queries);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rounds in this proof.
+    pub fn num_rounds(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Returns round queries contained in this proof.
+    pub fn rounds(&self) -> &[FriProofLayer] {
+        &self.rounds
+    }
+
+    /// Returns serialized round commitments contained in this proof.
+    pub fn commitments_bytes(&self) -> &[u8] {
+        &self.commitments
+    }
+
+    /// Returns serialized out-of-domain answers contained in this proof.
+    pub fn ood_answers_bytes(&self) -> &[u8] {
+        &self.ood_answers
+    }
+
+    /// Returns serialized coefficients of the final polynomial contained in this proof.
+    pub fn final_poly_bytes(&self) -> &[u8] {
+        &self.final_poly
+    }
+
+    /// Returns queries of the first round evaluations at the positions requested after the
+    /// protocol was executed, or `None` if no such positions were requested.
+    pub fn input_queries(&self) -> Option<&FriProofLayer> {
+        self.input_queries.as_ref()
+    }
+
+    /// Returns the size of this proof in bytes.
+    pub fn size(&self) -> usize {
+        // +1 for number of rounds, +2 for length of each of the byte vectors, +1 for the input
+        // queries flag
+        let input_queries_size = self
+            .input_queries
+            .as_ref()
+            .map_or(0, |queries| queries.size());
+        self.rounds.iter().fold(
+            self.commitments.len()
+                + self.ood_answers.len()
+                + self.final_poly.len()
+                + input_queries_size
+                + 8,
+            |acc, round| acc + round.size(),
+        )
+    }
+
+    // SIZE ESTIMATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the expected size (in bytes) of a proof generated for an evaluation domain of the
+    /// specified size using the specified `options`, with the first round evaluations queried at
+    /// `num_input_queries` positions.
+    ///
+    /// This can be used to predict the size of a proof without running the prover. The estimate
+    /// assumes that round evaluations are committed to using Merkle trees with hash function `H`,
+    /// that the proof is generated for evaluations in field `E`, and that all query positions are
+    /// drawn uniformly at random; it accounts for positions which collapse into the same row, and
+    /// for internal Merkle nodes shared between authentication paths.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `domain_size` is smaller than twice the folding factor, or smaller than the blowup
+    ///   factor.
+    /// * `num_input_queries` is greater than `domain_size`.
+    pub fn estimated_size<E: FieldElement, H: Hasher>(
+        options: &StirOptions,
+        domain_size: usize,
+        num_input_queries: usize,
+    ) -> usize {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+        assert!(
+            domain_size >= 2 * options.folding_factor() && domain_size >= options.blowup_factor(),
+            "domain size {domain_size} is too small for the protocol options"
+        );
+        assert!(
+            num_input_queries <= domain_size,
+            "number of queries cannot exceed domain size"
+        );
+
+        let folding_factor = options.folding_factor();
+        let max_degree = domain_size / options.blowup_factor() - 1;
+        let rounds = options.round_params(max_degree);
+
+        // +1 for number of rounds, +2 for length of each of the byte vectors, +1 for the input
+        // queries flag
+        let mut result = 8 + rounds.len() * H::Digest::SIZE;
+        result += (rounds.len() - 1) * E::ELEMENT_BYTES;
+        result += options.final_poly_len(max_degree) * E::ELEMENT_BYTES;
+
+        // every query opens a row of `folding_factor` evaluations; shift queries are drawn from
+        // the rows directly, while input queries are drawn from the entire domain of the first
+        // round; +4 for length of values, +4 for length of paths
+        let mut queries = rounds
+            .iter()
+            .map(|round| {
+                (
+                    round.domain_size,
+                    round.num_queries,
+                    round.domain_size / folding_factor,
+                )
+            })
+            .collect::<Vec<_>>();
+        if num_input_queries > 0 {
+            queries.push((domain_size, num_input_queries, domain_size));
+        }
+        for (round_domain_size, num_queries, source_domain_size) in queries {
+            let num_rows = round_domain_size / folding_factor;
+            let depth = num_rows.trailing_zeros() as u8;
+            let num_values =
+                BatchMerkleProof::<H>::expected_num_leaves(depth, num_queries, source_domain_size);
+            let paths_size = BatchMerkleProof::<H>::estimate_serialized_size(
+                depth,
+                num_queries,
+                source_domain_size,
+            );
+            result += num_values * folding_factor * E::ELEMENT_BYTES + 4 + paths_size + 4;
+        }
+
+        result
+    }
+
+    // PARSING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a vector of round commitments parsed from this proof.
+    ///
+    /// The first commitment is the commitment to the evaluations of the polynomial being proven;
+    /// a user of the protocol who has committed to these evaluations by other means can check
+    /// this commitment against their own.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of commitments is not equal to the number of rounds in this proof.
+    /// * Any of the commitments could not be parsed correctly.
+    pub fn parse_commitments<H: Hasher>(&self) -> Result<Vec<H::Digest>, DeserializationError> {
+        let mut reader = SliceReader::new(&self.commitments);
+        let commitments = H::Digest::read_batch_from(&mut reader, self.rounds.len())?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(commitments)
+    }
+
+    /// Returns a vector of out-of-domain answers parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of answers is not one less than the number of rounds in this proof.
+    /// * Any of the answers could not be parsed correctly.
+    pub fn parse_ood_answers<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        let num_answers = self.rounds.len().saturating_sub(1);
+        let mut reader = SliceReader::new(&self.ood_answers);
+        let answers = E::read_batch_from(&mut reader, num_answers)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(answers)
+    }
+
+    /// Returns coefficients of the final polynomial parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of final polynomial bytes does not divide into a whole number of field
+    ///   elements specified by `E` type parameter.
+    /// * Any of the coefficients could not be parsed correctly.
+    pub fn parse_final_poly<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        if self.final_poly.len() % E::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of final polynomial bytes ({}) does not divide into whole number of field elements",
+                self.final_poly.len()
+            )));
+        }
+        let num_elements = self.final_poly.len() / E::ELEMENT_BYTES;
+        let mut reader = SliceReader::new(&self.final_poly);
+        E::read_batch_from(&mut reader, num_elements)
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for StirProof {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // write round queries
+        target.write_u8(self.rounds.len() as u8);
+        for round in self.rounds.iter() {
+            round.write_into(target);
+        }
+
+        // write commitments, out-of-domain answers, and the final polynomial
+        target.write_u16(self.commitments.len() as u16);
+        target.write_bytes(&self.commitments);
+        target.write_u16(self.ood_answers.len() as u16);
+        target.write_bytes(&self.ood_answers);
+        target.write_u16(self.final_poly.len() as u16);
+        target.write_bytes(&self.final_poly);
+
+        // write queries of the first round evaluations, if any
+        match self.input_queries.as_ref() {
+            Some(queries) => {
+                target.write_u8(1);
+                queries.write_into(target);
+            }
+            None => target.write_u8(0),
+        }
+    }
+}
+
+impl Deserializable for StirProof {
+    /// Reads a STIR proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // read round queries
+        let num_rounds = source.read_u8()? as usize;
+        if num_rounds == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a STIR proof must contain at least one round".to_string(),
+            ));
+        }
+        let rounds = FriProofLayer::read_batch_from(source, num_rounds)?;
+
+        // read commitments, out-of-domain answers, and the final polynomial
+        let num_commitment_bytes = source.read_u16()? as usize;
+        let commitments = source.read_vec(num_commitment_bytes)?;
+        let num_answer_bytes = source.read_u16()? as usize;
+        let ood_answers = source.read_vec(num_answer_bytes)?;
+        let num_final_poly_bytes = source.read_u16()? as usize;
+        let final_poly = source.read_vec(num_final_poly_bytes)?;
+
+        // read queries of the first round evaluations, if any
+        let input_queries = match source.read_u8()? {
+            0 => None,
+            1 => Some(FriProofLayer::read_from(source)?),
+            value => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "value {value} cannot be deserialized as input queries flag"
+                )))
+            }
+        };
+
+        Ok(StirProof {
+            commitments,
+            ood_answers,
+            rounds,
+            final_poly,
+            input_queries,
+        })
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{StirOptions, StirProof, StirProverChannel};
+use crate::{folding::fold_positions, utils::hash_values, FriProofLayer};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, MerkleTree};
+use math::{fft, polynom, Domain, FieldElement, StarkField};
+use utils::{collections::Vec, transpose_slice};
+
+// STIR PROVER
+// ================================================================================================
+
+/// Implements the prover component of the STIR protocol.
+///
+/// Given evaluations of a function *f* over domain *D* (`evaluations`), a STIR prover generates
+/// a proof that *f* is a polynomial of some bounded degree *d*, such that *d* < |*D*| /
+/// *blowup_factor*.
+///
+/// The prover is parametrized with the following types:
+///
+/// * `B` specifies the base field of the STARK protocol.
+/// * `E` specifies the field in which the STIR protocol is executed.
+/// * `C` specifies the type used to simulate prover-verifier interaction.
+/// * `H` specifies the hash function used to build Merkle trees of round evaluations.
+///
+/// In each round, the prover folds the current polynomial by the folding factor *k* (in the same
+/// way as FRI does), and commits to the evaluations of the folded polynomial *g* over a domain
+/// which is only half the size of the current domain. The verifier then queries the current
+/// evaluations at a few cosets, which determines the values of *g* at the corresponding shift
+/// points, and *g* is also evaluated at a random out-of-domain point. The polynomial for the next
+/// round is the quotient of *g* by the polynomial vanishing at all of these points (with the
+/// known values of *g* subtracted), multiplied by a degree-correction factor. The verifier never
+/// sees this polynomial explicitly: its evaluations are computed from the committed evaluations
+/// of *g*. After the last round, the folded polynomial is sent in coefficient form.
+///
+/// Similarly to [FriProver](crate::FriProver), a proof can be generated in two steps: first, all
+/// rounds are executed via [build_rounds()](StirProver::build_rounds), and then the proof is
+/// built via [build_proof()](StirProver::build_proof). The latter also queries evaluations of
+/// the first round at positions drawn after the protocol was executed; this way, a STARK prover
+/// can use STIR in place of FRI to prove that evaluations of a DEEP composition polynomial are
+/// of low degree.
+pub struct StirProver<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: StirProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    options: StirOptions,
+    first_layer: Option<RoundLayer<E, H>>,
+    proof: Option<StirProof>,
+    _channel: PhantomData<C>,
+}
+
+impl<B, E, C, H> StirProver<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: StirProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new STIR prover instantiated with the provided `options`.
+    pub fn new(options: StirOptions) -> Self {
+        StirProver {
+            options,
+            first_layer: None,
+            proof: None,
+            _channel: PhantomData,
+        }
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns options used by this prover.
+    pub fn options(&self) -> &StirOptions {
+        &self.options
+    }
+
+    // PROOF GENERATION
+    // --------------------------------------------------------------------------------------------
+    /// Generates a proof that `evaluations` are evaluations of a polynomial of degree smaller than
+    /// `evaluations.len()` / `blowup_factor` over the domain of size `evaluations.len()` shifted
+    /// by the [domain offset](StirOptions::domain_offset).
+    ///
+    /// All messages of the prover are written into the `channel`, and all random values are
+    /// drawn from it. The returned proof does not contain queries of the first round evaluations.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The prover state is dirty (rounds have been built, but a proof has not been built yet).
+    /// * The number of evaluations is not a power of two.
+    /// * The number of evaluations is smaller than twice the folding factor, or smaller than the
+    ///   blowup factor.
+    pub fn prove(&mut self, channel: &mut C, evaluations: Vec<E>) -> StirProof {
+        self.build_rounds(channel, evaluations);
+        self.build_proof(&[])
+    }
+
+    /// Executes all rounds of the STIR protocol for the specified `evaluations`; the proof can
+    /// then be built via [build_proof()](StirProver::build_proof).
+    ///
+    /// `evaluations` must be evaluations of a polynomial of degree smaller than
+    /// `evaluations.len()` / `blowup_factor` over the domain of size `evaluations.len()` shifted
+    /// by the [domain offset](StirOptions::domain_offset). All messages of the prover are written
+    /// into the `channel`, and all random values are drawn from it.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The prover state is dirty (rounds have been built, but a proof has not been built yet).
+    /// * The number of evaluations is not a power of two.
+    /// * The number of evaluations is smaller than twice the folding factor, or smaller than the
+    ///   blowup factor.
+    pub fn build_rounds(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        assert!(
+            self.proof.is_none(),
+            "a prior proof generation request has not been completed yet"
+        );
+        assert!(
+            evaluations.len().is_power_of_two(),
+            "number of evaluations must be a power of two, but was {}",
+            evaluations.len()
+        );
+        assert!(
+            evaluations.len() >= 2 * self.options.folding_factor()
+                && evaluations.len() >= self.options.blowup_factor(),
+            "number of evaluations {} is too small for the protocol options",
+            evaluations.len()
+        );
+
+        // static dispatch for folding factor parameter
+        let (first_layer, proof) = match self.options.folding_factor() {
+            2 => self.prove_rounds::<2>(channel, evaluations),
+            4 => self.prove_rounds::<4>(channel, evaluations),
+            8 => self.prove_rounds::<8>(channel, evaluations),
+            16 => self.prove_rounds::<16>(channel, evaluations),
+            folding_factor => unimplemented!("folding factor {} is not supported", folding_factor),
+        };
+        self.first_layer = Some(first_layer);
+        self.proof = Some(proof);
+    }
+
+    /// Builds a proof from the rounds executed via [build_rounds()](StirProver::build_rounds).
+    ///
+    /// If `positions` is not empty, evaluations of the first round at these positions are
+    /// recorded into the proof together with Merkle authentication paths from the root of the
+    /// first round commitment. Positions are indexes into the evaluations passed to
+    /// [build_rounds()](StirProver::build_rounds); similarly to FRI, each position opens the row
+    /// of `folding_factor` evaluations containing it.
+    ///
+    /// # Panics
+    /// Panics if the prover state is clean (no rounds have been built yet).
+    pub fn build_proof(&mut self, positions: &[usize]) -> StirProof {
+        let first_layer = self
+            .first_layer
+            .take()
+            .expect("STIR rounds have not been built yet");
+        let proof = self
+            .proof
+            .take()
+            .expect("STIR rounds have not been built yet");
+        if positions.is_empty() {
+            return proof;
+        }
+
+        // static dispatch for folding factor parameter
+        let domain_size = first_layer.evaluations.len();
+        let folding_factor = self.options.folding_factor();
+        let rows = fold_positions(positions, domain_size, folding_factor);
+        let input_queries = match folding_factor {
+            2 => first_layer.query::<2>(&rows),
+            4 => first_layer.query::<4>(&rows),
+            8 => first_layer.query::<8>(&rows),
+            16 => first_layer.query::<16>(&rows),
+            folding_factor => unimplemented!("folding factor {} is not supported", folding_factor),
+        };
+        proof.with_input_queries(input_queries)
+    }
+
+    /// Executes all STIR rounds; this takes the folding factor as a generic parameter N.
+    ///
+    /// Returns the commitment to the evaluations of the first round together with a proof which
+    /// does not contain queries of these evaluations.
+    fn prove_rounds<const N: usize>(
+        &self,
+        channel: &mut C,
+        evaluations: Vec<E>,
+    ) -> (RoundLayer<E, H>, StirProof) {
+        let domain_offset = self.options.domain_offset::<B>();
+        let max_degree = evaluations.len() / self.options.blowup_factor() - 1;
+        let rounds = self.options.round_params(max_degree);
+
+        // interpolate the evaluations into a polynomial, and commit to the evaluations
+        let mut poly = evaluations.clone();
+        let inv_twiddles = fft::get_inv_twiddles::<B>(poly.len());
+        fft::interpolate_poly_with_offset(&mut poly, &inv_twiddles, domain_offset);
+        let first_layer = RoundLayer::<E, H>::new::<N>(&evaluations);
+        channel.commit_stir_round(*first_layer.commitment.root());
+        let mut layer = None;
+
+        let mut commitments = Vec::with_capacity(rounds.len());
+        let mut ood_answers = Vec::with_capacity(rounds.len() - 1);
+        let mut queries = Vec::with_capacity(rounds.len());
+        for (i, round) in rounds.iter().enumerate() {
+            let current_layer = layer.as_ref().unwrap_or(&first_layer);
+            commitments.push(*current_layer.commitment.root());
+
+            // fold the polynomial of this round; evaluations of a polynomial of an honest prover
+            // never exceed the degree bound, and thus, the polynomial is padded rather than
+            // truncated here
+            poly.resize(round.degree_bound, E::ZERO);
+            let folding_randomness = channel.draw_stir_challenge();
+            let folded_poly = fold_poly(&poly, N, folding_randomness);
+
+            // in the last round, send the folded polynomial to the verifier and answer the
+            // queries against the evaluations of this round
+            if i == rounds.len() - 1 {
+                channel.send_final_poly(&folded_poly);
+                let positions = channel.draw_stir_queries(round.num_queries, round.domain_size / N);
+                queries.push(current_layer.query::<N>(&positions));
+                let proof =
+                    StirProof::new::<H, E>(&commitments, &ood_answers, queries, &folded_poly);
+                return (first_layer, proof);
+            }
+
+            // commit to the evaluations of the folded polynomial over the domain for the next
+            // round; the domain is half the size of the current one
+            let next_domain_size = round.domain_size / 2;
+            let twiddles = fft::get_twiddles::<B>(folded_poly.len());
+            let next_evaluations = fft::evaluate_poly_with_offset(
+                &folded_poly,
+                &twiddles,
+                domain_offset,
+                next_domain_size / folded_poly.len(),
+            );
+            let next_layer = RoundLayer::<E, H>::new::<N>(&next_evaluations);
+            channel.commit_stir_round(*next_layer.commitment.root());
+
+            // evaluate the folded polynomial at an out-of-domain point
+            let ood_point = channel.draw_stir_challenge();
+            let ood_answer = polynom::eval(&folded_poly, ood_point);
+            channel.send_ood_answer(ood_answer);
+            ood_answers.push(ood_answer);
+
+            // answer the shift queries; values of the folded polynomial at the shift points are
+            // implied by the queried evaluations of this round
+            let comb_randomness = channel.draw_stir_challenge();
+            let positions = channel.draw_stir_queries(round.num_queries, round.domain_size / N);
+            queries.push(current_layer.query::<N>(&positions));
+
+            // build the polynomial for the next round from the folded polynomial and its values
+            // at the out-of-domain point and the shift points
+            let domain = Domain::with_offset(round.domain_size, domain_offset);
+            let mut points = Vec::with_capacity(positions.len() + 1);
+            points.push(ood_point);
+            for &position in positions.iter() {
+                points.push(E::from(domain.element_at(position).exp((N as u64).into())));
+            }
+            poly = quotient_with_correction(&folded_poly, &points, comb_randomness);
+            layer = Some(next_layer);
+        }
+
+        unreachable!("the last STIR round always returns a proof")
+    }
+}
+
+// ROUND LAYER
+// ================================================================================================
+
+/// Evaluations of a round polynomial committed to by the prover.
+struct RoundLayer<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    commitment: MerkleTree<H>,
+    evaluations: Vec<E>,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> RoundLayer<E, H> {
+    /// Commits to the specified evaluations by transposing them into a matrix of N columns and
+    /// building a Merkle tree from hashes of the rows; a row contains evaluations over a single
+    /// coset of the subgroup of size N.
+    fn new<const N: usize>(evaluations: &[E]) -> Self {
+        let transposed_evaluations = transpose_slice::<E, N>(evaluations);
+        let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
+        let commitment =
+            MerkleTree::new(hashed_evaluations).expect("failed to construct STIR round commitment");
+        RoundLayer {
+            commitment,
+            evaluations: evaluations.to_vec(),
+        }
+    }
+
+    /// Returns the rows of the committed matrix at the specified positions together with their
+    /// authentication paths.
+    fn query<const N: usize>(&self, positions: &[usize]) -> FriProofLayer {
+        let transposed_evaluations = transpose_slice::<E, N>(&self.evaluations);
+        let queried_values = positions
+            .iter()
+            .map(|&position| transposed_evaluations[position])
+            .collect::<Vec<_>>();
+        let proof = self
+            .commitment
+            .prove_batch(positions)
+            .expect("failed to generate a Merkle proof for STIR round queries");
+        FriProofLayer::new::<H, MerkleTree<H>, E, N>(queried_values, proof)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Folds the polynomial `poly` by `folding_factor` using the specified randomness.
+///
+/// Writing `poly` as Σ x<sup>i</sup> * p<sub>i</sub>(x<sup>k</sup>) for i in [0, k), the folded
+/// polynomial is Σ r<sup>i</sup> * p<sub>i</sub>(x), where k is the folding factor and r is the
+/// randomness. This is the polynomial evaluated by a FRI verifier when folding a layer.
+fn fold_poly<E: FieldElement>(poly: &[E], folding_factor: usize, randomness: E) -> Vec<E> {
+    poly.chunks(folding_factor)
+        .map(|chunk| polynom::eval(chunk, randomness))
+        .collect()
+}
+
+/// Returns the polynomial (`poly` - a(x)) / v(x) * Σ (r * x)<sup>i</sup> for i in [0, |S|],
+/// where S is the set of `points`, a(x) is the polynomial interpolating `poly` over S, v(x) is
+/// the polynomial vanishing on S, and r is `comb_randomness`.
+///
+/// The degree correction factor makes sure that the result has the same degree bound as `poly`.
+fn quotient_with_correction<E: FieldElement>(
+    poly: &[E],
+    points: &[E],
+    comb_randomness: E,
+) -> Vec<E> {
+    let answers = polynom::eval_many(poly, points);
+    let answers_poly = polynom::interpolate(points, &answers, false);
+    let numerator = polynom::sub(poly, &answers_poly);
+
+    // the numerator vanishes on S, and thus, it is either zero, or its degree is at least |S|
+    if polynom::degree_of(&numerator) < points.len() {
+        return vec![E::ZERO; poly.len()];
+    }
+    let vanishing_poly = points.iter().fold(vec![E::ONE], |acc, &point| {
+        polynom::mul(&acc, &[-point, E::ONE])
+    });
+    let quotient = polynom::div(&numerator, &vanishing_poly);
+
+    let mut correction = Vec::with_capacity(points.len() + 1);
+    let mut power = E::ONE;
+    for _ in 0..=points.len() {
+        correction.push(power);
+        power *= comb_randomness;
+    }
+    polynom::mul(&quotient, &correction)
+}
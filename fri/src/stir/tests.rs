@@ -0,0 +1,215 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    verifier::RoundQuotient, DefaultStirProverChannel, StirOptions, StirProof, StirProver,
+    StirVerifier,
+};
+use crate::VerifierError;
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
+use math::{fft, fields::f128::BaseElement, FieldElement};
+use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROVE/VERIFY TESTS
+// ================================================================================================
+
+#[test]
+fn stir_folding_2() {
+    let options = StirOptions::new(8, 2, 7, 16);
+    stir_prove_verify(1 << 10, options)
+}
+
+#[test]
+fn stir_folding_4() {
+    let options = StirOptions::new(8, 4, 7, 16);
+    assert_eq!(5, options.num_rounds((1 << 12) - 1));
+    stir_prove_verify(1 << 12, options)
+}
+
+#[test]
+fn stir_folding_8() {
+    let options = StirOptions::new(4, 8, 15, 24);
+    stir_prove_verify(1 << 12, options)
+}
+
+#[test]
+fn stir_folding_16() {
+    let options = StirOptions::new(2, 16, 0, 8);
+    stir_prove_verify(1 << 8, options)
+}
+
+#[test]
+fn stir_high_degree() {
+    let trace_length = 1 << 10;
+    let options = StirOptions::new(8, 4, 7, 16);
+
+    // evaluations of a polynomial of degree 2 * trace_length - 1 are rejected
+    let evaluations = build_evaluations(2 * trace_length, 4, &options);
+    let proof = build_proof(evaluations, &options);
+    let result = verify_proof(&proof, trace_length - 1, &options);
+    assert_eq!(Err(VerifierError::InvalidRemainderFolding), result);
+}
+
+#[test]
+fn stir_invalid_final_poly() {
+    let trace_length = 1 << 10;
+    let options = StirOptions::new(8, 4, 7, 16);
+    let evaluations = build_evaluations(trace_length, 8, &options);
+    let proof = build_proof(evaluations, &options);
+
+    // a proof with a modified final polynomial is rejected; the final polynomial is followed
+    // by the input queries flag
+    let mut proof_bytes = proof.to_bytes();
+    let num_bytes = proof_bytes.len();
+    proof_bytes[num_bytes - 2] ^= 1;
+    let proof = StirProof::read_from(&mut SliceReader::new(&proof_bytes)).unwrap();
+    assert!(verify_proof(&proof, trace_length - 1, &options).is_err());
+
+    // so is a proof verified against a different degree
+    let proof = StirProof::read_from(&mut SliceReader::new(&proof.to_bytes())).unwrap();
+    assert!(verify_proof(&proof, 2 * trace_length - 1, &options).is_err());
+}
+
+#[test]
+fn stir_input_queries() {
+    let trace_length = 1 << 10;
+    let options = StirOptions::new(8, 4, 7, 16);
+    let evaluations = build_evaluations(trace_length, 8, &options);
+
+    // build a proof which also queries the evaluations at positions drawn after the protocol
+    // is executed
+    let mut channel =
+        DefaultStirProverChannel::<BaseElement, Blake3, _>::new(DefaultRandomCoin::<Blake3>::new(
+            &[BaseElement::ONE],
+        ));
+    let mut prover = StirProver::new(options.clone());
+    prover.build_rounds(&mut channel, evaluations.clone());
+    let positions = vec![1, 4097, 17, 8000, 1];
+    let proof = prover.build_proof(&positions);
+    assert!(proof.input_queries().is_some());
+
+    let proof_bytes = proof.to_bytes();
+    assert_eq!(proof_bytes.len(), proof.size());
+    let proof = StirProof::read_from(&mut SliceReader::new(&proof_bytes)).unwrap();
+    assert!(verify_proof(&proof, trace_length - 1, &options).is_ok());
+
+    // the queried evaluations must agree with the expected ones
+    let verifier = StirVerifier::<BaseElement, Blake3, DefaultRandomCoin<Blake3>>::new(
+        options.clone(),
+        trace_length - 1,
+    );
+    let mut queried = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        Ok(()),
+        verifier.verify_evaluations(&proof, &positions, &queried)
+    );
+
+    queried[3] += BaseElement::ONE;
+    assert_eq!(
+        Err(VerifierError::InvalidLayerFolding(0)),
+        verifier.verify_evaluations(&proof, &positions, &queried)
+    );
+
+    // positions outside of the evaluation domain are rejected
+    let mut out_of_bounds = positions.clone();
+    out_of_bounds[2] = 8192;
+    assert_eq!(
+        Err(VerifierError::QueryPositionOutOfBounds(8192, 8192)),
+        verifier.verify_evaluations(&proof, &out_of_bounds, &queried)
+    );
+
+    // a proof without input queries is rejected
+    let proof = build_proof(evaluations, &options);
+    assert!(proof.input_queries().is_none());
+    assert!(verifier
+        .verify_evaluations(&proof, &positions, &queried)
+        .is_err());
+}
+
+#[test]
+fn stir_round_quotient_undefined() {
+    let points = vec![BaseElement::new(3), BaseElement::new(5)];
+    let answers = [BaseElement::ONE, BaseElement::new(2)];
+    let quotient = RoundQuotient::new(points, &answers, BaseElement::new(7));
+
+    // the quotient is undefined at the points at which the answers are given
+    assert!(quotient
+        .evaluate(BaseElement::new(4), BaseElement::ONE)
+        .is_some());
+    assert_eq!(
+        None,
+        quotient.evaluate(BaseElement::new(5), BaseElement::ONE)
+    );
+}
+
+#[test]
+fn stir_estimated_size() {
+    let options = StirOptions::new(8, 4, 7, 16);
+    let trace_length = 1 << 10;
+    let domain_size = trace_length * options.blowup_factor();
+    let evaluations = build_evaluations(trace_length, options.blowup_factor(), &options);
+    let proof = build_proof(evaluations, &options);
+
+    // the estimate is within 10% of the actual proof size
+    let estimate = StirProof::estimated_size::<BaseElement, Blake3>(&options, domain_size, 0);
+    let actual = proof.size();
+    assert!(
+        estimate.abs_diff(actual) * 10 < actual,
+        "estimate {estimate} is too far from actual size {actual}"
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn stir_prove_verify(trace_length: usize, options: StirOptions) {
+    let evaluations = build_evaluations(trace_length, options.blowup_factor(), &options);
+    let proof = build_proof(evaluations, &options);
+
+    // make sure the proof can be serialized and de-serialized correctly
+    let proof_bytes = proof.to_bytes();
+    assert_eq!(proof_bytes.len(), proof.size());
+    let proof_copy = StirProof::read_from(&mut SliceReader::new(&proof_bytes)).unwrap();
+    assert_eq!(proof, proof_copy);
+
+    let result = verify_proof(&proof_copy, trace_length - 1, &options);
+    assert!(result.is_ok(), "{result:?}");
+}
+
+fn build_evaluations(
+    trace_length: usize,
+    lde_blowup: usize,
+    options: &StirOptions,
+) -> Vec<BaseElement> {
+    let p = (0..trace_length as u128)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>();
+    let twiddles = fft::get_twiddles::<BaseElement>(trace_length);
+    fft::evaluate_poly_with_offset(&p, &twiddles, options.domain_offset(), lde_blowup)
+}
+
+fn build_proof(evaluations: Vec<BaseElement>, options: &StirOptions) -> StirProof {
+    let mut channel =
+        DefaultStirProverChannel::<BaseElement, Blake3, _>::new(DefaultRandomCoin::<Blake3>::new(
+            &[BaseElement::ONE],
+        ));
+    let mut prover = StirProver::new(options.clone());
+    prover.prove(&mut channel, evaluations)
+}
+
+fn verify_proof(
+    proof: &StirProof,
+    max_degree: usize,
+    options: &StirOptions,
+) -> Result<(), VerifierError> {
+    let mut public_coin = DefaultRandomCoin::<Blake3>::new(&[BaseElement::ONE]);
+    let verifier = StirVerifier::<BaseElement, Blake3, _>::new(options.clone(), max_degree);
+    verifier.verify(proof, &mut public_coin)
+}
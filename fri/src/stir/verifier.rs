@@ -0,0 +1,389 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{StirOptions, StirProof};
+use crate::{folding::fold_positions, VerifierError};
+use core::{convert::TryInto, marker::PhantomData};
+use crypto::{ElementHasher, MerkleTree, RandomCoin};
+use math::{polynom, Domain, FieldElement};
+use utils::{collections::Vec, group_slice_elements, string::ToString};
+
+// STIR VERIFIER
+// ================================================================================================
+
+/// Implements the verifier component of the STIR protocol.
+///
+/// Given a STIR proof, a STIR verifier determines whether the committed function *f* is a
+/// polynomial of some bounded degree *d*. The verifier replays the transcript of the prover (see
+/// [StirProver](super::StirProver)) by drawing all random values from a public coin, and for
+/// each round:
+///
+/// 1. Checks that the queried evaluations are consistent with the commitment of the round.
+/// 2. Computes the values of the round polynomial at the queried positions; for all rounds but
+///    the first one, these are computed from the committed evaluations of the folded polynomial
+///    of the previous round and the values of that polynomial at the out-of-domain point and the
+///    shift points.
+/// 3. Folds these values to get the values of the folded polynomial at the shift points.
+///
+/// Finally, the verifier checks that the final polynomial has the expected number of
+/// coefficients, and that it agrees with the folded values at the shift points of the last
+/// round.
+///
+/// When STIR is used in place of FRI in a STARK proof, the verifier also needs to make sure that
+/// the committed function agrees with the evaluations it computed itself; this is done via
+/// [verify_evaluations()](StirVerifier::verify_evaluations).
+pub struct StirVerifier<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    options: StirOptions,
+    max_poly_degree: usize,
+    _field_element: PhantomData<E>,
+    _public_coin: PhantomData<R>,
+}
+
+impl<E, H, R> StirVerifier<E, H, R>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new STIR verifier for polynomials of degree at most `max_poly_degree`
+    /// instantiated with the provided `options`.
+    ///
+    /// # Panics
+    /// Panics if the evaluation domain implied by `max_poly_degree` and the blowup factor is
+    /// smaller than twice the folding factor.
+    pub fn new(options: StirOptions, max_poly_degree: usize) -> Self {
+        let domain_size = (max_poly_degree + 1).next_power_of_two() * options.blowup_factor();
+        assert!(
+            domain_size >= 2 * options.folding_factor(),
+            "domain size {domain_size} is too small for the protocol options"
+        );
+        StirVerifier {
+            options,
+            max_poly_degree,
+            _field_element: PhantomData,
+            _public_coin: PhantomData,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns maximum degree of a polynomial accepted by this verifier.
+    pub fn max_poly_degree(&self) -> usize {
+        self.max_poly_degree
+    }
+
+    /// Returns options used by this verifier.
+    pub fn options(&self) -> &StirOptions {
+        &self.options
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+    /// Returns `Ok(())` if the evaluations committed to by the first commitment of the `proof`
+    /// are evaluations of a polynomial with degree <= `max_poly_degree`.
+    ///
+    /// The `public_coin` must be in the same state as the coin used by the prover's channel at
+    /// the start of proof generation.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of rounds in the proof, or any of its parts could not be parsed.
+    /// * Queried evaluations don't match the commitment value at any of the rounds.
+    /// * The final polynomial has a different number of coefficients than implied by the options
+    ///   and `max_poly_degree`, or it does not agree with the folded values of the last round.
+    /// * An error was encountered while drawing a random value from the coin.
+    pub fn verify(&self, proof: &StirProof, public_coin: &mut R) -> Result<(), VerifierError> {
+        let rounds = self.options.round_params(self.max_poly_degree);
+        if proof.num_rounds() != rounds.len() {
+            return Err(VerifierError::NumLayerCommitmentsMismatch(
+                rounds.len(),
+                proof.num_rounds(),
+            ));
+        }
+
+        let final_poly = proof
+            .parse_final_poly::<E>()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let final_poly_len = self.options.final_poly_len(self.max_poly_degree);
+        if final_poly.len() != final_poly_len {
+            return Err(VerifierError::RemainderDegreeMismatch(final_poly_len - 1));
+        }
+
+        // static dispatch for folding factor parameter
+        match self.options.folding_factor() {
+            2 => self.verify_rounds::<2>(proof, &final_poly, public_coin),
+            4 => self.verify_rounds::<4>(proof, &final_poly, public_coin),
+            8 => self.verify_rounds::<8>(proof, &final_poly, public_coin),
+            16 => self.verify_rounds::<16>(proof, &final_poly, public_coin),
+            folding_factor => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+        }
+    }
+
+    /// Returns `Ok(())` if the first round evaluations queried in the `proof` are consistent with
+    /// the first commitment of the `proof`, and agree with the specified `evaluations` at the
+    /// specified `positions`.
+    ///
+    /// The positions must be the same as the ones passed to
+    /// [StirProver::build_proof()](super::StirProver::build_proof) by the prover; evaluations
+    /// must be listed in the same order as the positions.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of positions is not equal to the number of evaluations.
+    /// * The proof does not contain queries of the first round evaluations, or they could not
+    ///   be parsed.
+    /// * Queried evaluations don't match the first round commitment.
+    /// * Any of the positions is outside of the evaluation domain of the first round.
+    /// * Any of the queried evaluations is different from the corresponding value in
+    ///   `evaluations`.
+    pub fn verify_evaluations(
+        &self,
+        proof: &StirProof,
+        positions: &[usize],
+        evaluations: &[E],
+    ) -> Result<(), VerifierError> {
+        if positions.len() != evaluations.len() {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                positions.len(),
+                evaluations.len(),
+            ));
+        }
+
+        // static dispatch for folding factor parameter
+        match self.options.folding_factor() {
+            2 => self.verify_input_queries::<2>(proof, positions, evaluations),
+            4 => self.verify_input_queries::<4>(proof, positions, evaluations),
+            8 => self.verify_input_queries::<8>(proof, positions, evaluations),
+            16 => self.verify_input_queries::<16>(proof, positions, evaluations),
+            folding_factor => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+        }
+    }
+
+    /// Verifies queries of the first round evaluations; this takes the folding factor as a
+    /// generic parameter N.
+    fn verify_input_queries<const N: usize>(
+        &self,
+        proof: &StirProof,
+        positions: &[usize],
+        evaluations: &[E],
+    ) -> Result<(), VerifierError> {
+        let domain_size = self.options.round_params(self.max_poly_degree)[0].domain_size;
+        let num_rows = domain_size / N;
+        if let Some(&position) = positions.iter().find(|&&position| position >= domain_size) {
+            return Err(VerifierError::QueryPositionOutOfBounds(
+                position,
+                domain_size,
+            ));
+        }
+        let rows = fold_positions(positions, domain_size, N);
+
+        // read the queried evaluations and make sure they are consistent with the commitment
+        let queries = proof.input_queries().ok_or_else(|| {
+            VerifierError::ProofDeserializationError(
+                "STIR proof does not contain queries of the first round evaluations".to_string(),
+            )
+        })?;
+        let (values, merkle_proof) = queries
+            .view()
+            .parse::<H, E>(num_rows, N)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        if values.len() != rows.len() * N {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                rows.len(),
+                values.len() / N,
+            ));
+        }
+        let commitments = proof
+            .parse_commitments::<H>()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        MerkleTree::<H>::verify_batch(&commitments[0], &rows, &merkle_proof)
+            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+
+        // make sure the queried evaluations agree with the specified ones; a position p is at
+        // index p / num_rows of the row p % num_rows
+        let row_values = group_slice_elements::<E, N>(&values);
+        for (&position, &evaluation) in positions.iter().zip(evaluations) {
+            let row_idx = rows
+                .iter()
+                .position(|&row| row == position % num_rows)
+                .expect("position was not folded into a row");
+            if row_values[row_idx][position / num_rows] != evaluation {
+                return Err(VerifierError::InvalidLayerFolding(0));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies all STIR rounds; this takes the folding factor as a generic parameter N.
+    fn verify_rounds<const N: usize>(
+        &self,
+        proof: &StirProof,
+        final_poly: &[E],
+        public_coin: &mut R,
+    ) -> Result<(), VerifierError> {
+        let rounds = self.options.round_params(self.max_poly_degree);
+        let commitments = proof
+            .parse_commitments::<H>()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let ood_answers = proof
+            .parse_ood_answers::<E>()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+        // pre-compute roots of unity used in computing x coordinates of the queried cosets
+        let folding_roots = Domain::<E::BaseField>::new(N).to_vec();
+        let domain_offset = self.options.domain_offset::<E::BaseField>();
+
+        public_coin.reseed(commitments[0]);
+        let mut quotient: Option<RoundQuotient<E>> = None;
+        for (i, (round, queries)) in rounds.iter().zip(proof.rounds()).enumerate() {
+            // replay the transcript of the round
+            let folding_randomness: E =
+                public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+            let next_round = if i == rounds.len() - 1 {
+                public_coin.reseed(H::hash_elements(final_poly));
+                None
+            } else {
+                public_coin.reseed(commitments[i + 1]);
+                let ood_point: E = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+                public_coin.reseed(H::hash_elements(&[ood_answers[i]]));
+                let comb_randomness: E =
+                    public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+                Some((ood_point, ood_answers[i], comb_randomness))
+            };
+            let num_rows = round.domain_size / N;
+            let positions = public_coin
+                .draw_integers(round.num_queries, num_rows)
+                .map_err(VerifierError::RandomCoinError)?;
+
+            // read the queried evaluations and make sure they are consistent with the commitment
+            let (values, merkle_proof) = queries
+                .view()
+                .parse::<H, E>(num_rows, N)
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+            if values.len() != positions.len() * N {
+                return Err(VerifierError::NumPositionEvaluationMismatch(
+                    positions.len(),
+                    values.len() / N,
+                ));
+            }
+            MerkleTree::<H>::verify_batch(&commitments[i], &positions, &merkle_proof)
+                .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+            let mut rows = group_slice_elements::<E, N>(&values).to_vec();
+
+            // compute the values of the round polynomial at the queried positions
+            let domain = Domain::with_offset(round.domain_size, domain_offset);
+            if let Some(quotient) = quotient.as_ref() {
+                for (row, &position) in rows.iter_mut().zip(positions.iter()) {
+                    for (j, value) in row.iter_mut().enumerate() {
+                        let x = domain.element_at(position + j * num_rows);
+                        *value = quotient
+                            .evaluate(x, *value)
+                            .ok_or(VerifierError::UndefinedRoundQuotient(i))?;
+                    }
+                }
+            }
+
+            // fold the values at each queried coset
+            #[rustfmt::skip]
+            let xs = positions.iter().map(|&position| {
+                let xe = domain.element_at(position);
+                folding_roots.iter()
+                    .map(|&r| E::from(xe * r))
+                    .collect::<Vec<_>>().try_into().unwrap()
+            })
+            .collect::<Vec<_>>();
+            let row_polys = polynom::interpolate_batch(&xs, &rows);
+            let folded_values = row_polys
+                .iter()
+                .map(|p| polynom::eval(p, folding_randomness))
+                .collect::<Vec<_>>();
+            let shift_points = positions
+                .iter()
+                .map(|&position| E::from(domain.element_at(position).exp((N as u64).into())))
+                .collect::<Vec<_>>();
+
+            match next_round {
+                // in the last round, the folded values must agree with the final polynomial
+                None => {
+                    for (&point, &value) in shift_points.iter().zip(folded_values.iter()) {
+                        if polynom::eval(final_poly, point) != value {
+                            return Err(VerifierError::InvalidRemainderFolding);
+                        }
+                    }
+                }
+                // otherwise, the folded values define the polynomial of the next round
+                Some((ood_point, ood_answer, comb_randomness)) => {
+                    let mut points = Vec::with_capacity(shift_points.len() + 1);
+                    points.push(ood_point);
+                    points.extend_from_slice(&shift_points);
+                    let mut answers = Vec::with_capacity(folded_values.len() + 1);
+                    answers.push(ood_answer);
+                    answers.extend_from_slice(&folded_values);
+                    quotient = Some(RoundQuotient::new(points, &answers, comb_randomness));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ROUND QUOTIENT
+// ================================================================================================
+
+/// Describes how values of the polynomial of a round are computed from the values of the folded
+/// polynomial of the previous round.
+pub(super) struct RoundQuotient<E: FieldElement> {
+    points: Vec<E>,
+    answers_poly: Vec<E>,
+    comb_randomness: E,
+}
+
+impl<E: FieldElement> RoundQuotient<E> {
+    /// Returns a new quotient for the specified points at which the folded polynomial takes the
+    /// specified values.
+    pub(super) fn new(points: Vec<E>, answers: &[E], comb_randomness: E) -> Self {
+        let answers_poly = polynom::interpolate(&points, answers, false);
+        RoundQuotient {
+            points,
+            answers_poly,
+            comb_randomness,
+        }
+    }
+
+    /// Returns the value of the round polynomial at `x` given the `value` of the folded
+    /// polynomial at `x`, or `None` if `x` is one of the points of this quotient (in which case
+    /// the quotient is undefined).
+    pub(super) fn evaluate(&self, x: E::BaseField, value: E) -> Option<E> {
+        let x = E::from(x);
+        let vanishing_value = self
+            .points
+            .iter()
+            .fold(E::ONE, |acc, &point| acc * (x - point));
+        if vanishing_value == E::ZERO {
+            return None;
+        }
+        let quotient = (value - polynom::eval(&self.answers_poly, x)) / vanishing_value;
+
+        // the degree correction factor is a geometric series Σ (r * x)^i for i in [0, |S|]
+        let num_terms = (self.points.len() + 1) as u64;
+        let rx = self.comb_randomness * x;
+        let correction = if rx == E::ONE {
+            E::from(num_terms)
+        } else {
+            (E::ONE - rx.exp(num_terms.into())) / (E::ONE - rx)
+        };
+        Some(quotient * correction)
+    }
+}
@@ -3,8 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{FriProof, VerifierError};
-use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
+use crate::{utils::hash_values, FriOptions, FriProof, VerifierError};
+use crypto::{ElementHasher, Hasher, MerkleTree, VectorCommitment};
 use math::FieldElement;
 use utils::{collections::Vec, group_vector_elements, DeserializationError};
 
@@ -23,6 +23,10 @@ pub trait VerifierChannel<E: FieldElement> {
     /// Hash function used by the prover to commit to polynomial evaluations.
     type Hasher: ElementHasher<BaseField = E::BaseField>;
 
+    /// Vector commitment scheme used by the prover to commit to evaluations of FRI layers; this
+    /// must be the same scheme as the one used by the [FriProver](crate::FriProver).
+    type VectorCommitment: VectorCommitment<Self::Hasher>;
+
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -40,6 +44,12 @@ pub trait VerifierChannel<E: FieldElement> {
         &mut self,
     ) -> Vec<<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest>;
 
+    /// Reads and removes from the channel all FRI layer proof-of-work nonces sent by the prover.
+    ///
+    /// The nonces are expected to be present only if layer proof-of-work is required by the FRI
+    /// options; in this case, there is one nonce for each FRI layer (excluding the remainder).
+    fn read_fri_layer_nonces(&mut self) -> Vec<u64>;
+
     /// Reads and removes from the channel evaluations of the polynomial at the queried positions
     /// for the next FRI layer.
     ///
@@ -51,16 +61,17 @@ pub trait VerifierChannel<E: FieldElement> {
     /// paths.
     fn take_next_fri_layer_queries(&mut self) -> Vec<E>;
 
-    /// Reads and removes from the channel Merkle authentication paths for queried evaluations for
-    /// the next FRI layer.
+    /// Reads and removes from the channel the opening proof for queried evaluations for the next
+    /// FRI layer; for Merkle trees, this is a batch of Merkle authentication paths.
     ///
-    /// In the interactive version of the protocol, these authentication paths are sent from the
-    /// prover to the verifier during the query phase of the FRI protocol.
+    /// In the interactive version of the protocol, these opening proofs are sent from the prover
+    /// to the verifier during the query phase of the FRI protocol.
     ///
     /// It is expected that layer proofs and layer queries at the same FRI layer are consistent.
-    /// That is, query values hash into the leaf nodes of corresponding Merkle authentication
-    /// paths.
-    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<Self::Hasher>;
+    /// That is, query values hash into the items opened by the corresponding opening proof.
+    fn take_next_fri_layer_proof(
+        &mut self,
+    ) -> <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::MultiProof;
 
     /// Reads and removes the remainder polynomial from the channel.
     fn take_fri_remainder(&mut self) -> Vec<E>;
@@ -71,7 +82,8 @@ pub trait VerifierChannel<E: FieldElement> {
     /// Returns FRI query values at the specified positions from the current FRI layer and advances
     /// layer pointer by one.
     ///
-    /// This also checks if the values are valid against the provided FRI layer commitment.
+    /// This also checks if the values are valid against the provided FRI layer commitment; that
+    /// is, the query values must hash into the items opened by the layer proof.
     ///
     /// # Errors
     /// Returns an error if query values did not match layer commitment.
@@ -81,18 +93,40 @@ pub trait VerifierChannel<E: FieldElement> {
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
         let layer_proof = self.take_next_fri_layer_proof();
-        MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
-            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        let layer_queries = self.take_next_fri_layer_queries();
+        if layer_queries.len() != positions.len() * N {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
+        let layer_queries: Vec<[E; N]> = group_vector_elements(layer_queries);
 
-        // TODO: make sure layer queries hash into leaves of layer proof
+        let items = hash_values::<Self::Hasher, E, N>(&layer_queries);
+        <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::verify_many(
+            *commitment,
+            positions,
+            &items,
+            &layer_proof,
+        )
+        .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
 
-        let layer_queries = self.take_next_fri_layer_queries();
-        Ok(group_vector_elements(layer_queries))
+        Ok(layer_queries)
     }
 
     /// Returns FRI remainder polynomial read from this channel.
-    fn read_remainder(&mut self) -> Result<Vec<E>, VerifierError> {
+    ///
+    /// The remainder is sent as a list of polynomial coefficients, and the prover commits to it
+    /// by hashing the coefficients; this also checks that the remainder is valid against the
+    /// provided remainder commitment.
+    ///
+    /// # Errors
+    /// Returns an error if the remainder did not match the remainder commitment.
+    fn read_remainder(
+        &mut self,
+        commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
+    ) -> Result<Vec<E>, VerifierError> {
         let remainder = self.take_fri_remainder();
+        if <Self::Hasher as ElementHasher>::hash_elements(&remainder) != *commitment {
+            return Err(VerifierError::RemainderCommitmentMismatch);
+        }
 
         Ok(remainder)
     }
@@ -103,22 +137,32 @@ pub trait VerifierChannel<E: FieldElement> {
 
 /// Provides a default implementation of the [VerifierChannel] trait.
 ///
-/// Default verifier channel can be instantiated directly from a [FriProof] struct.
+/// Default verifier channel can be instantiated directly from a [FriProof] struct, or from
+/// already parsed proof components via [from_raw_parts()](DefaultVerifierChannel::from_raw_parts).
+///
+/// The channel is parametrized by the vector commitment scheme `V` used to commit to FRI layers;
+/// by default, this is a Merkle tree.
 ///
 /// Though this implementation is primarily intended for testing purposes, it can be used in
 /// production use cases as well.
-pub struct DefaultVerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+pub struct DefaultVerifierChannel<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
     layer_commitments: Vec<H::Digest>,
-    layer_proofs: Vec<BatchMerkleProof<H>>,
+    layer_proofs: Vec<V::MultiProof>,
     layer_queries: Vec<Vec<E>>,
+    layer_nonces: Vec<u64>,
     remainder: Vec<E>,
     num_partitions: usize,
 }
 
-impl<E, H> DefaultVerifierChannel<E, H>
+impl<E, H, V> DefaultVerifierChannel<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     /// Builds a new verifier channel from the specified [FriProof].
     ///
@@ -128,30 +172,70 @@ where
         proof: FriProof,
         layer_commitments: Vec<H::Digest>,
         domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
     ) -> Result<Self, DeserializationError> {
         let num_partitions = proof.num_partitions();
+        let layer_nonces = proof.layer_nonces().to_vec();
 
         let remainder = proof.parse_remainder()?;
         let (layer_queries, layer_proofs) =
-            proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+            proof.parse_layers_with::<H, V, E>(domain_size, options)?;
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
             layer_proofs,
             layer_queries,
+            layer_nonces,
             remainder,
             num_partitions,
         })
     }
+
+    /// Builds a new verifier channel from the specified parts of a FRI proof.
+    ///
+    /// This can be used when FRI proof data is obtained by means other than a [FriProof] (e.g.,
+    /// when FRI is used as a component of a different protocol with its own proof format). The
+    /// parts are:
+    /// * `layer_commitments` - commitments to all FRI layers, including the commitment to the
+    ///   remainder polynomial.
+    /// * `layer_queries` - queried evaluations for each FRI layer (excluding the remainder);
+    ///   evaluations committed to in a single leaf must be consecutive.
+    /// * `layer_proofs` - opening proofs of vector commitment `V` for the queried evaluations of
+    ///   each FRI layer (excluding the remainder).
+    /// * `layer_nonces` - proof-of-work nonces for each FRI layer; this should be empty if layer
+    ///   proof-of-work is not required.
+    /// * `remainder` - coefficients of the remainder polynomial.
+    /// * `num_partitions` - the number of partitions used during proof generation.
+    ///
+    /// No validation of the parts is performed here; inconsistent parts are detected during
+    /// verification.
+    pub fn from_raw_parts(
+        layer_commitments: Vec<H::Digest>,
+        layer_queries: Vec<Vec<E>>,
+        layer_proofs: Vec<V::MultiProof>,
+        layer_nonces: Vec<u64>,
+        remainder: Vec<E>,
+        num_partitions: usize,
+    ) -> Self {
+        DefaultVerifierChannel {
+            layer_commitments,
+            layer_proofs,
+            layer_queries,
+            layer_nonces,
+            remainder,
+            num_partitions,
+        }
+    }
 }
 
-impl<E, H> VerifierChannel<E> for DefaultVerifierChannel<E, H>
+impl<E, H, V> VerifierChannel<E> for DefaultVerifierChannel<E, H, V>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
 {
     type Hasher = H;
+    type VectorCommitment = V;
 
     fn read_fri_num_partitions(&self) -> usize {
         self.num_partitions
@@ -161,7 +245,11 @@ where
         self.layer_commitments.drain(..).collect()
     }
 
-    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
+    fn read_fri_layer_nonces(&mut self) -> Vec<u64> {
+        core::mem::take(&mut self.layer_nonces)
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> V::MultiProof {
         self.layer_proofs.remove(0)
     }
 
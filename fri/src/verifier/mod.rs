@@ -5,15 +5,64 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
-use core::{convert::TryInto, marker::PhantomData, mem};
+use crate::{
+    folding::fold_positions, utils::map_positions_to_indexes, FriOptions, FriProof, VerifierError,
+};
+use core::{convert::TryInto, marker::PhantomData};
 use crypto::{ElementHasher, RandomCoin};
-use math::{polynom, FieldElement, StarkField};
-use utils::collections::Vec;
+use math::{linear_combination, polynom, Domain, FieldElement};
+use utils::{collections::Vec, string::ToString};
 
 mod channel;
 pub use channel::{DefaultVerifierChannel, VerifierChannel};
 
+// STANDALONE VERIFICATION
+// ================================================================================================
+
+/// Returns `Ok(())` if the `proof` attests that `evaluations` are evaluations of a polynomial of
+/// degree <= `max_poly_degree` at the specified `positions`.
+///
+/// This function allows using FRI as a building block of other protocols (e.g., polynomial
+/// commitment schemes) without relying on the STARK verifier. It reads the `proof` via a
+/// [DefaultVerifierChannel], executes the commit phase of the protocol by instantiating a
+/// [FriVerifier], and then executes the query phase via [FriVerifier::verify()].
+///
+/// The parameters are expected to be as follows:
+/// * `layer_commitments` are the commitments written by the prover into its channel during the
+///   commit phase (including the commitment to the remainder polynomial).
+/// * `evaluations` are the values of the polynomial at `positions` in the evaluation domain; the
+///   size of the domain is implied by `max_poly_degree` and the blowup factor specified in the
+///   `options` (see [FriVerifier::domain_size()]).
+/// * `public_coin` must be in the same state as the public coin of the prover was before the
+///   first layer commitment was written into the channel. The `positions` are not drawn by this
+///   function; it is the responsibility of the caller to derive them from the public coin of the
+///   protocol in which FRI is used.
+///
+/// # Errors
+/// Returns an error if the `proof` could not be parsed, or any of the errors returned by
+/// [FriVerifier::new()] and [FriVerifier::verify()].
+pub fn verify_proof<E, H, R>(
+    proof: FriProof,
+    layer_commitments: Vec<H::Digest>,
+    evaluations: &[E],
+    positions: &[usize],
+    max_poly_degree: usize,
+    options: &FriOptions,
+    public_coin: &mut R,
+) -> Result<(), VerifierError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+{
+    let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
+    let mut channel =
+        DefaultVerifierChannel::<E, H>::new(proof, layer_commitments, domain_size, options)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let verifier = FriVerifier::new(&mut channel, public_coin, options.clone(), max_poly_degree)?;
+    verifier.verify(&mut channel, evaluations, positions)
+}
+
 // FRI VERIFIER
 // ================================================================================================
 /// Implements the verifier component of the FRI protocol.
@@ -41,7 +90,8 @@ pub use channel::{DefaultVerifierChannel, VerifierChannel};
 /// from the prover (via [VerifierChannel]). After each received commitment, the verifier
 /// draws a random value α from the entire field, and sends it to the prover. In the
 /// non-interactive version of the protocol, α values are derived pseudo-randomly from FRI
-/// layer commitments.
+/// layer commitments. If layer proof-of-work is required by the options, the verifier also
+/// checks the proof-of-work nonce of each layer before drawing α for the layer.
 ///
 /// # Query phase
 /// During the query phase, which is executed via [verify()](FriVerifier::verify()) function,
@@ -53,8 +103,8 @@ pub use channel::{DefaultVerifierChannel, VerifierChannel};
 /// * The evaluations are consistent across FRI layers (i.e., the degree-respecting projection
 ///   was applied correctly).
 /// * The degree of the polynomial implied by evaluations at the last FRI layer (the remainder)
-///   is smaller than the degree resulting from reducing degree *d* by `folding_factor` at each
-///   FRI layer.
+///   is smaller than the degree resulting from reducing degree *d* by the folding factor of each
+///   FRI layer (see [FriOptions::folding_factor_at()]).
 pub struct FriVerifier<E, C, H, R>
 where
     E: FieldElement,
@@ -63,8 +113,7 @@ where
     R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
 {
     max_poly_degree: usize,
-    domain_size: usize,
-    domain_generator: E::BaseField,
+    domain: Domain<E::BaseField>,
     layer_commitments: Vec<H::Digest>,
     layer_alphas: Vec<E>,
     options: FriOptions,
@@ -89,15 +138,21 @@ where
     /// Creating a FRI verifier executes the commit phase of the FRI protocol from the verifier's
     /// perspective. Specifically, the verifier reads FRI layer commitments from the `channel`,
     /// and for each commitment, updates the `public_coin` with this commitment and then draws
-    /// a random value α from the coin.
+    /// a random value α from the coin (except for the commitment to the remainder, which is not
+    /// folded).
     ///
     /// The verifier stores layer commitments and corresponding α values in its internal state,
     /// and, thus, an instance of FRI verifier can be used to verify only a single proof.
     ///
     /// # Errors
     /// Returns an error if:
+    /// * The number of FRI layer commitments read from the channel is inconsistent with the
+    ///   number of FRI layers implied by the `options` for the evaluation domain.
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
-    ///   and `folding_factor` specified in the `options` parameter.
+    ///   and folding factors specified in the `options` parameter.
+    /// * The number of layer proof-of-work nonces read from the channel is inconsistent with the
+    ///   layer grinding factor specified in the `options`, or any of the nonces does not satisfy
+    ///   the grinding factor.
     /// * An error was encountered while drawing a random α value from the coin.
     pub fn new(
         channel: &mut C,
@@ -107,37 +162,68 @@ where
     ) -> Result<Self, VerifierError> {
         // infer evaluation domain info
         let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
-        let domain_generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
+        let domain = Domain::with_offset(domain_size, options.domain_offset());
 
         let num_partitions = channel.read_fri_num_partitions();
 
-        // read layer commitments from the channel and use them to build a list of alphas
+        // read layer commitments from the channel and make sure there is a commitment for each
+        // FRI layer, plus a commitment to the remainder
         let layer_commitments = channel.read_fri_layer_commitments();
+        let num_layers = options.num_fri_layers(domain_size);
+        if layer_commitments.len() != num_layers + 1 {
+            return Err(VerifierError::NumLayerCommitmentsMismatch(
+                num_layers + 1,
+                layer_commitments.len(),
+            ));
+        }
+
+        // read layer proof-of-work nonces and make sure there is a nonce for each FRI layer if
+        // layer proof-of-work is required
+        let grinding_factor = options.layer_grinding_factor();
+        let layer_nonces = channel.read_fri_layer_nonces();
+        let num_nonces = if grinding_factor > 0 { num_layers } else { 0 };
+        if layer_nonces.len() != num_nonces {
+            return Err(VerifierError::NumLayerNoncesMismatch(
+                num_nonces,
+                layer_nonces.len(),
+            ));
+        }
+
+        // use the commitments to build a list of alphas
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
             public_coin.reseed(*commitment);
-            let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
-            layer_alphas.push(alpha);
+
+            // the remainder is not folded, and thus, the prover does not draw alpha for it;
+            // we skip the draw as well to keep the verifier's coin in sync with the prover's
+            if depth != layer_commitments.len() - 1 {
+                if let Some(&nonce) = layer_nonces.get(depth) {
+                    public_coin.reseed_with_int(nonce);
+                    if public_coin.leading_zeros() < grinding_factor {
+                        return Err(VerifierError::LayerPowVerificationFailed(depth));
+                    }
+                }
+                let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+                layer_alphas.push(alpha);
+            }
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
-            {
+            let folding_factor = options.folding_factor_at(depth);
+            if depth != layer_commitments.len() - 1 && max_degree_plus_1 % folding_factor != 0 {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
-                    options.folding_factor(),
+                    folding_factor,
                     depth,
                 ));
             }
-            max_degree_plus_1 /= options.folding_factor();
+            max_degree_plus_1 /= folding_factor;
         }
 
         Ok(FriVerifier {
             max_poly_degree,
-            domain_size,
-            domain_generator,
+            domain,
             layer_commitments,
             layer_alphas,
             options,
@@ -161,7 +247,7 @@ where
     /// The domain size can be computed by rounding `max_poly_degree` to the next power of two
     /// and multiplying the result by the `blowup_factor` from the protocol options.
     pub fn domain_size(&self) -> usize {
-        self.domain_size
+        self.domain.size()
     }
 
     /// Returns number of partitions used during FRI proof generation.
@@ -194,11 +280,13 @@ where
     /// # Errors
     /// Returns an error if:
     /// * The length of `evaluations` is not equal to the length of `positions`.
-    /// * An unsupported folding factor was specified by the `options` for this verifier.
+    /// * An unsupported folding factor was specified for any of the FRI layers by the `options`
+    ///   for this verifier.
     /// * Decommitments to polynomial evaluations don't match the commitment value at any of the
     ///   FRI layers.
     /// * The verifier detects an error in how the degree-respecting projection was applied
     ///   at any of the FRI layers.
+    /// * The remainder polynomial does not match the remainder commitment.
     /// * The degree of the remainder at the last FRI layer is greater than the degree implied by
     ///   `max_poly_degree` reduced by the folding factor at each FRI layer.
     pub fn verify(
@@ -214,114 +302,45 @@ where
             ));
         }
 
-        // static dispatch for folding factor parameter
-        let folding_factor = self.options.folding_factor();
-        match folding_factor {
-            2 => self.verify_generic::<2>(channel, evaluations, positions),
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
-            _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
-        }
-    }
-
-    /// This is the actual implementation of the verification procedure described above, but it
-    /// also takes folding factor as a generic parameter N.
-    fn verify_generic<const N: usize>(
-        &self,
-        channel: &mut C,
-        evaluations: &[E],
-        positions: &[usize],
-    ) -> Result<(), VerifierError> {
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| {
-                self.domain_generator
-                    .exp_vartime(((self.domain_size / N * i) as u64).into())
-            })
-            .collect::<Vec<_>>();
-
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
-        let mut domain_generator = self.domain_generator;
-        let mut domain_size = self.domain_size;
-        let mut max_degree_plus_1 = self.max_poly_degree + 1;
-        let mut positions = positions.to_vec();
-        let mut evaluations = evaluations.to_vec();
-
-        for depth in 0..self.options.num_fri_layers(self.domain_size) {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
-            // determine where these evaluations are in the commitment Merkle tree
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                self.options.folding_factor(),
-                self.num_partitions,
-            );
-            // read query values from the specified indexes in the Merkle tree
-            let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
-            let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
+        let mut layer = VerifierLayerState {
+            domain: self.domain,
+            max_degree_plus_1: self.max_poly_degree + 1,
+            positions: positions.to_vec(),
+            evaluations: evaluations.to_vec(),
+        };
+
+        for depth in 0..self.options.num_fri_layers(self.domain.size()) {
+            // static dispatch for folding factor parameter
+            let folding_factor = self.options.folding_factor_at(depth);
+            match folding_factor {
+                2 => self.verify_layer::<2>(channel, depth, &mut layer)?,
+                4 => self.verify_layer::<4>(channel, depth, &mut layer)?,
+                8 => self.verify_layer::<8>(channel, depth, &mut layer)?,
+                16 => self.verify_layer::<16>(channel, depth, &mut layer)?,
+                _ => return Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
             }
-
-            // build a set of x coordinates for each row polynomial
-            #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
-
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
-
-            // calculate the pseudo-random value used for linear combination in layer folding
-            let alpha = self.layer_alphas[depth];
-
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
-
-            // make sure next degree reduction does not result in degree truncation
-            if max_degree_plus_1 % N != 0 {
-                return Err(VerifierError::DegreeTruncation(
-                    max_degree_plus_1 - 1,
-                    N,
-                    depth,
-                ));
-            }
-
-            // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp_vartime((N as u32).into());
-            max_degree_plus_1 /= N;
-            domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
         }
+        let VerifierLayerState {
+            domain,
+            max_degree_plus_1,
+            positions,
+            evaluations,
+        } = layer;
 
         // 2 ----- verify the remainder polynomial of the FRI proof -------------------------------
 
         // read the remainder polynomial from the channel and make sure it agrees with the evaluations
-        // from the previous layer.
-        let remainder_poly = channel.read_remainder()?;
+        // from the previous layer; the remainder commitment is the last layer commitment.
+        let remainder_commitment = self.layer_commitments[self.layer_commitments.len() - 1];
+        let remainder_poly = channel.read_remainder(&remainder_commitment)?;
         if remainder_poly.len() > max_degree_plus_1 {
             return Err(VerifierError::RemainderDegreeMismatch(
                 max_degree_plus_1 - 1,
             ));
         }
-        let offset: E::BaseField = self.options().domain_offset();
-
         for (&position, evaluation) in positions.iter().zip(evaluations) {
-            let comp_eval = eval_horner::<E>(
-                &remainder_poly,
-                offset * domain_generator.exp_vartime((position as u64).into()),
-            );
+            let comp_eval = eval_horner::<E>(&remainder_poly, domain.element_at(position));
             if comp_eval != evaluation {
                 return Err(VerifierError::InvalidRemainderFolding);
             }
@@ -329,6 +348,90 @@ where
 
         Ok(())
     }
+
+    /// Verifies a single FRI layer at the specified depth, and updates the `layer` state so that
+    /// it describes the next layer; this takes folding factor of the layer as a generic
+    /// parameter N.
+    fn verify_layer<const N: usize>(
+        &self,
+        channel: &mut C,
+        depth: usize,
+        layer: &mut VerifierLayerState<E>,
+    ) -> Result<(), VerifierError> {
+        // pre-compute roots of unity used in computing x coordinates in the folded domain; these
+        // are the elements of the subgroup of size N
+        let folding_roots = Domain::<E::BaseField>::new(N).to_vec();
+        let domain = layer.domain;
+
+        // determine which evaluations were queried in the folded layer
+        let folded_positions = fold_positions(&layer.positions, domain.size(), N);
+        // determine where these evaluations are in the commitment Merkle tree
+        let position_indexes =
+            map_positions_to_indexes(&folded_positions, domain.size(), N, self.num_partitions);
+        // read query values from the specified indexes in the Merkle tree
+        let layer_commitment = self.layer_commitments[depth];
+        // TODO: add layer depth to the potential error message
+        let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
+        let query_values = get_query_values::<E, N>(
+            &layer_values,
+            &layer.positions,
+            &folded_positions,
+            domain.size(),
+        );
+        if layer.evaluations != query_values {
+            return Err(VerifierError::InvalidLayerFolding(depth));
+        }
+
+        // build a set of x coordinates for each row polynomial
+        #[rustfmt::skip]
+        let xs = folded_positions.iter().map(|&i| {
+            let xe = domain.element_at(i);
+            folding_roots.iter()
+                .map(|&r| E::from(xe * r))
+                .collect::<Vec<_>>().try_into().unwrap()
+        })
+        .collect::<Vec<_>>();
+
+        // interpolate x and y values into row polynomials
+        let row_polys = polynom::interpolate_batch(&xs, &layer_values);
+
+        // calculate the pseudo-random value used for linear combination in layer folding
+        let alpha = self.layer_alphas[depth];
+
+        // check that when the polynomials are evaluated at alpha, the result is equal to
+        // the corresponding column value
+        layer.evaluations = row_polys
+            .iter()
+            .map(|p| linear_combination(p, alpha))
+            .collect();
+
+        // make sure next degree reduction does not result in degree truncation
+        if layer.max_degree_plus_1 % N != 0 {
+            return Err(VerifierError::DegreeTruncation(
+                layer.max_degree_plus_1 - 1,
+                N,
+                depth,
+            ));
+        }
+
+        // update the state for the next layer
+        layer.domain = domain.fold(N);
+        layer.max_degree_plus_1 /= N;
+        layer.positions = folded_positions;
+
+        Ok(())
+    }
+}
+
+// VERIFIER LAYER STATE
+// ================================================================================================
+
+/// State of the query phase which is carried over from one FRI layer to the next.
+struct VerifierLayerState<E: FieldElement> {
+    domain: Domain<E::BaseField>,
+    max_degree_plus_1: usize,
+    positions: Vec<usize>,
+    evaluations: Vec<E>,
 }
 
 // HELPER FUNCTIONS
@@ -0,0 +1,53 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#![no_main]
+
+use crypto::{hashers::Blake3_256, BatchMerkleProof, Hasher, MerkleTree};
+use libfuzzer_sys::fuzz_target;
+use math::fields::f64::BaseElement;
+use utils::{collections::Vec, SliceReader};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+fuzz_target!(|data: &[u8]| {
+    // the input is interpreted as: tree depth (1 byte), number of leaves (1 byte), and an index
+    // of each leaf (1 byte per leaf) followed by serialized internal nodes of the proof; leaves
+    // are derived from their indexes so that the inputs do not have to contain digests
+    if data.len() < 2 {
+        return;
+    }
+    let depth = data[0];
+    let num_leaves = data[1] as usize;
+    if data.len() < 2 + num_leaves {
+        return;
+    }
+    let mut indexes = data[2..2 + num_leaves]
+        .iter()
+        .map(|&index| index as usize)
+        .collect::<Vec<_>>();
+    let leaves = indexes
+        .iter()
+        .map(|&index| Blake3::hash(&[index as u8]))
+        .collect();
+    let node_bytes = &data[2 + num_leaves..];
+
+    let mut reader = SliceReader::new(node_bytes);
+    let proof = match BatchMerkleProof::<Blake3>::deserialize(&mut reader, leaves, depth) {
+        Ok(proof) => proof,
+        Err(_) => return,
+    };
+
+    // serialized nodes of a parsed proof must match the consumed part of the input
+    let serialized = proof.serialize_nodes();
+    assert_eq!(&node_bytes[..serialized.len()], serialized.as_slice());
+
+    // computing the root or verifying the proof must not panic for any leaf indexes
+    let _ = proof.get_root(&indexes);
+    let _ = MerkleTree::<Blake3>::verify_batch(&Blake3::hash(node_bytes), &indexes, &proof);
+    indexes.sort_unstable();
+    indexes.dedup();
+    let _ = proof.into_paths(&indexes);
+});
@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#![no_main]
+
+use air::proof::{StarkProof, StarkProofRef};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // binary encoding: owned proofs and proof views must agree on every input
+    let proof = StarkProof::from_bytes(data);
+    let view = StarkProofRef::from_bytes(data);
+    match (proof, view) {
+        (Ok(proof), Ok(view)) => {
+            assert_eq!(proof.view(), view);
+
+            // a successfully parsed proof must survive a round trip
+            let bytes = proof.to_bytes();
+            assert_eq!(proof, StarkProof::from_bytes(&bytes).unwrap());
+            let _ = proof.lde_domain_size();
+            let _ = proof.get_trace_info();
+        }
+        (Err(_), Err(_)) => (),
+        (proof, view) => panic!("parsers disagree: {proof:?} vs. {view:?}"),
+    }
+
+    // CBOR encoding
+    if let Ok(proof) = StarkProof::from_cbor(data) {
+        assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+        let _ = proof.get_trace_info();
+    }
+});
@@ -0,0 +1,50 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#![no_main]
+
+use air::proof::StarkProof;
+use libfuzzer_sys::fuzz_target;
+use winter_fuzz::{example_proofs, mutate_proof, registry};
+use winterfell::AcceptableOptions;
+
+fuzz_target!(|data: &[u8]| {
+    // the input is interpreted as: an index of one of the example proofs (1 byte) followed by
+    // mutations to apply to this proof (5 bytes per mutation, see mutate_proof()); this way,
+    // almost every input is a slightly corrupted valid proof, and thus, reaches deep into the
+    // verifier rather than being rejected by the parser
+    let Some((&proof_idx, mutations)) = data.split_first() else {
+        return;
+    };
+    let proofs = example_proofs();
+    let example = &proofs[proof_idx as usize % proofs.len()];
+    let bytes = mutate_proof(&example.proof, mutations);
+
+    let proof = match StarkProof::from_bytes(&bytes) {
+        Ok(proof) => proof,
+        Err(_) => return,
+    };
+
+    // AIR constructors are allowed to panic on trace layouts they were not designed for (e.g.,
+    // all example AIRs assert the width of the trace), and thus, proofs with a different trace
+    // layout are skipped; all other parts of the proof context are mutated freely
+    let original = StarkProof::from_bytes(&example.proof).expect("invalid example proof");
+    if proof.trace_layout() != original.trace_layout() {
+        return;
+    }
+
+    // verification of a mutated proof must not panic; an unmodified proof must be accepted.
+    // proofs are accepted at any security level so that mutated proof options reach the verifier
+    let acceptable_options = AcceptableOptions::MinConjecturedSecurity(0);
+    let result = registry().verify(
+        example.air_id,
+        proof,
+        &example.pub_inputs,
+        &acceptable_options,
+    );
+    if bytes == example.proof {
+        result.expect("failed to verify unmodified proof");
+    }
+});
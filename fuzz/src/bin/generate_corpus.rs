@@ -0,0 +1,42 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Writes corpus seeds for all fuzz targets into `fuzz/corpus/<target>` directories.
+
+use std::{fs, io, path::Path};
+use winter_fuzz::{example_proofs, merkle_proof_seeds};
+
+fn main() -> io::Result<()> {
+    let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus");
+    let proofs = example_proofs();
+
+    // valid proofs of the example computations
+    for proof in proofs {
+        write_seed(&corpus_dir, "proof_from_bytes", proof.name, &proof.proof)?;
+    }
+
+    // every example proof without mutations and with a single mutation in the middle
+    for (i, proof) in proofs.iter().enumerate() {
+        let offset = (proof.proof.len() as u32 / 2).to_le_bytes();
+        let mutation = [&[i as u8], &offset[..], &[1]].concat();
+        write_seed(&corpus_dir, "verify_mutated_proof", proof.name, &[i as u8])?;
+        let name = format!("{}-mutated", proof.name);
+        write_seed(&corpus_dir, "verify_mutated_proof", &name, &mutation)?;
+    }
+
+    // batch Merkle proofs against trees of different depths
+    for (name, seed) in merkle_proof_seeds() {
+        write_seed(&corpus_dir, "batch_merkle_proof", &name, &seed)?;
+    }
+
+    println!("corpus seeds written into {}", corpus_dir.display());
+    Ok(())
+}
+
+fn write_seed(corpus_dir: &Path, target: &str, name: &str, bytes: &[u8]) -> io::Result<()> {
+    let target_dir = corpus_dir.join(target);
+    fs::create_dir_all(&target_dir)?;
+    fs::write(target_dir.join(format!("seed-{name}")), bytes)
+}
@@ -0,0 +1,191 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Functionality shared by the fuzz targets and the corpus generator.
+//!
+//! Valid proofs are generated from the Fibonacci examples registered via
+//! [register_examples()](examples::registry::register_examples), and are verified via the same
+//! [AirRegistry] which is used by the `winterfell` binary. The proofs are small (short traces,
+//! few queries) so that verification of a single fuzz input takes a fraction of a millisecond.
+
+use crypto::{hashers::Blake3_256, Hasher, MerkleTree};
+use examples::{
+    fibonacci::{
+        fib2::FibProver, fib8::Fib8Prover, fib_small::FibSmallProver, mulfib2::MulFib2Prover,
+        mulfib8::MulFib8Prover,
+    },
+    registry::register_examples,
+};
+use math::fields::{f128, f64};
+use std::sync::OnceLock;
+use winterfell::{registry::AirRegistry, Air, FieldExtension, ProofOptions, Prover, Serializable};
+
+type Blake3 = Blake3_256<f128::BaseElement>;
+type Blake3Small = Blake3_256<f64::BaseElement>;
+
+// EXAMPLE PROOFS
+// ================================================================================================
+
+/// A valid proof of one of the example computations.
+pub struct ExampleProof {
+    /// Name of the proof; used as the name of the corpus file generated from this proof.
+    pub name: &'static str,
+    /// Identifier under which the computation is registered in the [registry()].
+    pub air_id: &'static str,
+    /// Serialized public inputs of the computation.
+    pub pub_inputs: Vec<u8>,
+    /// The proof serialized via [StarkProof::to_bytes()](winterfell::StarkProof::to_bytes).
+    pub proof: Vec<u8>,
+}
+
+/// Returns a registry with all Fibonacci examples registered in it.
+pub fn registry() -> &'static AirRegistry {
+    static REGISTRY: OnceLock<AirRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry = AirRegistry::new();
+        register_examples(&mut registry).expect("failed to register examples");
+        registry
+    })
+}
+
+/// Returns valid proofs of the Fibonacci examples; the proofs are generated on the first call.
+///
+/// Proofs are generated with and without field extension, and with different FRI folding
+/// factors, so that the verifier code paths for all of these are reachable by mutating the
+/// proofs.
+pub fn example_proofs() -> &'static [ExampleProof] {
+    static PROOFS: OnceLock<Vec<ExampleProof>> = OnceLock::new();
+    PROOFS.get_or_init(|| {
+        let base = build_options(FieldExtension::None, 4);
+        let quad = build_options(FieldExtension::Quadratic, 8);
+        let cube = build_options(FieldExtension::Cubic, 2);
+
+        vec![
+            build_proof("fib", "fib", FibProver::<Blake3>::new(base.clone()), |p| {
+                p.build_trace(64)
+            }),
+            build_proof(
+                "fib-quad",
+                "fib",
+                FibProver::<Blake3>::new(quad.clone()),
+                |p| p.build_trace(128),
+            ),
+            build_proof(
+                "fib8",
+                "fib8",
+                Fib8Prover::<Blake3>::new(base.clone()),
+                |p| p.build_trace(128),
+            ),
+            build_proof(
+                "fib-small",
+                "fib-small",
+                FibSmallProver::<Blake3Small>::new(base.clone()),
+                |p| p.build_trace(64),
+            ),
+            build_proof(
+                "fib-small-cube",
+                "fib-small",
+                FibSmallProver::<Blake3Small>::new(cube),
+                |p| p.build_trace(64),
+            ),
+            build_proof(
+                "mulfib",
+                "mulfib",
+                MulFib2Prover::<Blake3>::new(base.clone()),
+                |p| p.build_trace(64),
+            ),
+            build_proof(
+                "mulfib8",
+                "mulfib8",
+                MulFib8Prover::<Blake3>::new(quad),
+                |p| p.build_trace(128),
+            ),
+        ]
+    })
+}
+
+/// Returns a copy of `proof` with `mutations` applied to it.
+///
+/// Mutations are read from the `mutations` bytes in chunks of 5 bytes: the first 4 bytes of a
+/// chunk encode (in little-endian byte order) an offset into the proof which is reduced modulo
+/// the proof length, and the last byte is XORed into the proof byte at this offset. An
+/// incomplete chunk at the end of `mutations` is ignored.
+pub fn mutate_proof(proof: &[u8], mutations: &[u8]) -> Vec<u8> {
+    let mut result = proof.to_vec();
+    if result.is_empty() {
+        return result;
+    }
+    for chunk in mutations.chunks_exact(5) {
+        let offset = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as usize;
+        result[offset % proof.len()] ^= chunk[4];
+    }
+    result
+}
+
+// MERKLE PROOFS
+// ================================================================================================
+
+/// Returns valid batch Merkle proofs encoded in the input format of `batch_merkle_proof` target.
+///
+/// The input format is: tree depth (1 byte), number of leaves (1 byte), and an index of each
+/// leaf (1 byte per leaf) followed by serialized internal nodes of the proof. Leaf at index `i`
+/// is a BLAKE3 digest of the single byte `i`.
+pub fn merkle_proof_seeds() -> Vec<(String, Vec<u8>)> {
+    let configurations: [(u8, &[u8]); 5] = [
+        (1, &[0]),
+        (3, &[1, 6]),
+        (5, &[0, 1, 2, 31]),
+        (8, &[7]),
+        (8, &[0, 17, 18, 100, 128, 129, 255]),
+    ];
+
+    configurations
+        .iter()
+        .map(|&(depth, indexes)| {
+            let leaves = (0..1usize << depth)
+                .map(|i| Blake3::hash(&[i as u8]))
+                .collect::<Vec<_>>();
+            let tree = MerkleTree::<Blake3>::new(leaves).expect("failed to build Merkle tree");
+            let indexes = indexes.iter().map(|&i| i as usize).collect::<Vec<_>>();
+            let proof = tree
+                .prove_batch(&indexes)
+                .expect("failed to build Merkle proof");
+
+            let mut seed = vec![depth, indexes.len() as u8];
+            seed.extend(indexes.iter().map(|&i| i as u8));
+            seed.extend(proof.serialize_nodes());
+            (format!("depth{depth}-leaves{}", indexes.len()), seed)
+        })
+        .collect()
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options(field_extension: FieldExtension, folding_factor: usize) -> ProofOptions {
+    ProofOptions::new(4, 8, 0, field_extension, folding_factor, 7)
+}
+
+fn build_proof<P, F>(
+    name: &'static str,
+    air_id: &'static str,
+    prover: P,
+    build_trace: F,
+) -> ExampleProof
+where
+    P: Prover,
+    <P::Air as Air>::PublicInputs: Serializable,
+    F: FnOnce(&P) -> P::Trace,
+{
+    let trace = build_trace(&prover);
+    let pub_inputs = prover.get_pub_inputs(&trace).to_bytes();
+    let proof = prover.prove(trace).expect("failed to generate proof");
+    ExampleProof {
+        name,
+        air_id,
+        pub_inputs,
+        proof: proof.to_bytes(),
+    }
+}
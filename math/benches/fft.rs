@@ -8,7 +8,7 @@ use rand_utils::rand_vector;
 use std::time::Duration;
 use winter_math::{
     fft,
-    fields::{f128, f62, f64, CubeExtension, QuadExtension},
+    fields::{f128, f31, f62, f64, m31, CubeExtension, QuadExtension},
     FieldElement, StarkField,
 };
 
@@ -98,21 +98,42 @@ fn get_twiddles(c: &mut Criterion) {
     group.finish();
 }
 
+fn circle_evaluate_poly(c: &mut Criterion) {
+    let mut group = c.benchmark_group("m31/circle_evaluate_poly");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let blowup_factor = 8;
+
+    for &size in SIZES.iter() {
+        let p: Vec<m31::BaseElement> = rand_vector(size / blowup_factor);
+        let domain = fft::circle::get_domain(size);
+        group.bench_function(BenchmarkId::new("extend", size), |bench| {
+            bench.iter_with_large_drop(|| fft::circle::evaluate_poly(&p, &domain));
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_fft(c: &mut Criterion) {
     fft_evaluate_poly::<f62::BaseElement, f62::BaseElement>(c, "f62");
     fft_evaluate_poly::<f64::BaseElement, f64::BaseElement>(c, "f64");
     fft_evaluate_poly::<f128::BaseElement, f128::BaseElement>(c, "f128");
+    fft_evaluate_poly::<f31::BaseElement, f31::BaseElement>(c, "f31");
 
     fft_evaluate_poly::<f62::BaseElement, QuadExtension<f62::BaseElement>>(c, "f62_quad");
     fft_evaluate_poly::<f64::BaseElement, QuadExtension<f64::BaseElement>>(c, "f64_quad");
     fft_evaluate_poly::<f128::BaseElement, QuadExtension<f128::BaseElement>>(c, "f128_quad");
 
     fft_evaluate_poly::<f64::BaseElement, CubeExtension<f64::BaseElement>>(c, "f64_cube");
+    fft_evaluate_poly::<f31::BaseElement, CubeExtension<f31::BaseElement>>(c, "f31_cube");
 
     fft_interpolate_poly::<f62::BaseElement, f62::BaseElement>(c, "f62");
     fft_interpolate_poly::<f64::BaseElement, f64::BaseElement>(c, "f64");
     fft_interpolate_poly::<f128::BaseElement, f128::BaseElement>(c, "f128");
+    fft_interpolate_poly::<f31::BaseElement, f31::BaseElement>(c, "f31");
 }
 
-criterion_group!(fft_group, bench_fft, get_twiddles);
+criterion_group!(fft_group, bench_fft, circle_evaluate_poly, get_twiddles);
 criterion_main!(fft_group);
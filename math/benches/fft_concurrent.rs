@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand_utils::rand_vector;
+use std::{thread::available_parallelism, time::Duration};
+use utils::rayon::ThreadPoolBuilder;
+use winter_math::{
+    fft,
+    fields::{f64, QuadExtension},
+    FieldElement, StarkField,
+};
+
+const SIZES: [usize; 2] = [1 << 22, 1 << 24];
+
+/// Measures how the concurrent FFT scales with the number of threads; the thread counts are
+/// powers of two up to the number of available cores.
+fn fft_scaling<B, E>(c: &mut Criterion, field_name: &str)
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let max_threads = available_parallelism().map_or(1, |n| n.get());
+    let num_threads = (0..)
+        .map(|i| 1_usize << i)
+        .take_while(|&n| n <= max_threads)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group(format!("{field_name}/fft_evaluate_poly_scaling"));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    for &size in SIZES.iter() {
+        let p: Vec<E> = rand_vector(size);
+        let twiddles: Vec<B> = fft::get_twiddles(size);
+        for &threads in num_threads.iter() {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let id = BenchmarkId::new(format!("{size}"), threads);
+            group.bench_function(id, |bench| {
+                bench.iter_batched_ref(
+                    || p.clone(),
+                    |p| pool.install(|| fft::evaluate_poly(p, &twiddles)),
+                    BatchSize::LargeInput,
+                );
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_fft_scaling(c: &mut Criterion) {
+    fft_scaling::<f64::BaseElement, f64::BaseElement>(c, "f64");
+    fft_scaling::<f64::BaseElement, QuadExtension<f64::BaseElement>>(c, "f64_quad");
+}
+
+criterion_group!(fft_scaling_group, bench_fft_scaling);
+criterion_main!(fft_scaling_group);
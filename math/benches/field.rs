@@ -12,7 +12,7 @@ use rand_utils::{rand_array, rand_value, rand_vector};
 use std::time::Duration;
 use winter_math::{
     batch_inversion,
-    fields::{f128, f62, f64},
+    fields::{f128, f252, f31, f62, f64},
     fields::{CubeExtension, QuadExtension},
     ExtensibleField, FieldElement, StarkField,
 };
@@ -281,6 +281,8 @@ fn bench_field_ops(c: &mut Criterion) {
     field_ops::<f62::BaseElement>(c, "f62");
     field_ops::<f64::BaseElement>(c, "f64");
     field_ops::<f128::BaseElement>(c, "f128");
+    field_ops::<f31::BaseElement>(c, "f31");
+    field_ops::<f252::BaseElement>(c, "f252");
 }
 
 // CRITERION BOILERPLATE
@@ -0,0 +1,212 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{field::StarkField, utils::get_power_series_with_offset};
+use core::iter::FusedIterator;
+use utils::collections::Vec;
+
+// DOMAIN
+// ================================================================================================
+
+/// A multiplicative subgroup of a STARK field, or a coset of such a subgroup.
+///
+/// A domain of size `n` with offset `s` consists of elements `s * g^i` for `i` in `0..n`, where
+/// `g` is the generator of the multiplicative subgroup of size `n` (i.e., the `n`th root of unity
+/// returned by [StarkField::get_root_of_unity()]). When the offset is ONE, the domain is the
+/// subgroup itself.
+///
+/// All components of the protocol which work with evaluation domains (e.g., trace, constraint
+/// evaluation, and low-degree extension domains, as well as domains of FRI layers) can use this
+/// type to derive domain elements, which guarantees that all of them compute the same elements
+/// for the same positions.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{Domain, StarkField, FieldElement, fields::f128::BaseElement};
+/// let domain = Domain::with_offset(8, BaseElement::GENERATOR);
+/// let g = BaseElement::get_root_of_unity(3);
+///
+/// assert_eq!(BaseElement::GENERATOR * g.exp(5), domain.element_at(5));
+/// assert_eq!(domain.to_vec(), domain.elements().collect::<Vec<_>>());
+///
+/// // folding by 4 yields a domain of size 2 with the same offset
+/// let folded = domain.fold(4);
+/// assert_eq!(domain.element_at(4), folded.element_at(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Domain<B: StarkField> {
+    size: usize,
+    generator: B,
+    offset: B,
+}
+
+impl<B: StarkField> Domain<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the multiplicative subgroup of the specified size.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `size` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of the specified size.
+    pub fn new(size: usize) -> Self {
+        Self::with_offset(size, B::ONE)
+    }
+
+    /// Returns the coset of the multiplicative subgroup of the specified size shifted by the
+    /// specified offset.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `size` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of the specified size.
+    /// * `offset` is ZERO.
+    pub fn with_offset(size: usize, offset: B) -> Self {
+        assert!(size.is_power_of_two(), "domain size must be a power of 2");
+        assert_ne!(offset, B::ZERO, "domain offset cannot be zero");
+        // the subgroup of size 1 consists of ONE only, and thus, ONE is its generator
+        let generator = match size {
+            1 => B::ONE,
+            _ => B::get_root_of_unity(size.ilog2()),
+        };
+        Domain {
+            size,
+            generator,
+            offset,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of elements in this domain.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the generator of the multiplicative subgroup underlying this domain.
+    pub fn generator(&self) -> B {
+        self.generator
+    }
+
+    /// Returns the offset by which the multiplicative subgroup underlying this domain is shifted.
+    pub fn offset(&self) -> B {
+        self.offset
+    }
+
+    /// Returns the element of this domain at the specified position, i.e., `s * g^position`.
+    ///
+    /// Positions which are greater than or equal to the size of the domain wrap around, since
+    /// `g^size` = 1.
+    pub fn element_at(&self, position: usize) -> B {
+        let position = position & (self.size - 1);
+        self.generator.exp_vartime((position as u64).into()) * self.offset
+    }
+
+    /// Returns an iterator over all elements of this domain in natural order.
+    pub fn elements(&self) -> DomainElements<B> {
+        DomainElements {
+            generator: self.generator,
+            next: self.offset,
+            remaining: self.size,
+        }
+    }
+
+    /// Returns a vector containing all elements of this domain in natural order.
+    ///
+    /// When `concurrent` feature is enabled, the elements are computed concurrently in multiple
+    /// threads.
+    pub fn to_vec(&self) -> Vec<B> {
+        get_power_series_with_offset(self.generator, self.offset, self.size)
+    }
+
+    // DOMAIN TRANSFORMATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a domain with the same underlying subgroup as this domain, but with the offset
+    /// multiplied by the specified `shift`.
+    ///
+    /// # Panics
+    /// Panics if `shift` is ZERO.
+    pub fn shift(&self, shift: B) -> Self {
+        assert_ne!(shift, B::ZERO, "domain shift cannot be zero");
+        Domain {
+            size: self.size,
+            generator: self.generator,
+            offset: self.offset * shift,
+        }
+    }
+
+    /// Returns the domain of a FRI layer folded from this domain by the specified factor.
+    ///
+    /// The folded domain is the subgroup of size `size / folding_factor` (generated by
+    /// `g^folding_factor`) shifted by the same offset as this domain; this matches how domains of
+    /// FRI layers are defined in Winterfell FRI prover and verifier.
+    ///
+    /// # Panics
+    /// Panics if `folding_factor` is not a power of two or is greater than the size of this
+    /// domain.
+    pub fn fold(&self, folding_factor: usize) -> Self {
+        assert!(
+            folding_factor.is_power_of_two(),
+            "folding factor must be a power of 2"
+        );
+        assert!(
+            folding_factor <= self.size,
+            "folding factor cannot be greater than domain size"
+        );
+        Domain {
+            size: self.size / folding_factor,
+            generator: self.generator.exp_vartime((folding_factor as u64).into()),
+            offset: self.offset,
+        }
+    }
+
+    /// Returns the domain consisting of inverses of elements of this domain in the same order,
+    /// i.e., the subgroup generated by `g^-1` shifted by `s^-1`.
+    pub fn inv(&self) -> Self {
+        Domain {
+            size: self.size,
+            generator: self.generator.inv(),
+            offset: self.offset.inv(),
+        }
+    }
+}
+
+// DOMAIN ELEMENTS ITERATOR
+// ================================================================================================
+
+/// An iterator over elements of a [Domain] in natural order.
+///
+/// This struct is created by [Domain::elements()] method.
+#[derive(Debug, Clone)]
+pub struct DomainElements<B: StarkField> {
+    generator: B,
+    next: B,
+    remaining: usize,
+}
+
+impl<B: StarkField> Iterator for DomainElements<B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let result = self.next;
+        self.next *= self.generator;
+        self.remaining -= 1;
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<B: StarkField> ExactSizeIterator for DomainElements<B> {}
+
+impl<B: StarkField> FusedIterator for DomainElements<B> {}
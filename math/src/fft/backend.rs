@@ -0,0 +1,117 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{evaluate_poly, evaluate_poly_with_offset, interpolate_poly};
+use crate::{field::FieldElement, polynom};
+use utils::{collections::Vec, iter};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// COMPUTE BACKEND
+// ================================================================================================
+
+/// Defines how FFT-based operations over polynomials with coefficients in `E` are executed.
+///
+/// The trait makes it possible to offload number-theoretic transforms, low-degree extensions, and
+/// evaluations of polynomials at arbitrary points to hardware accelerators (e.g., GPUs): an
+/// implementation can override any of the methods and dispatch the work to a device, while the
+/// remaining methods fall back to the CPU implementations in this crate. Device implementations
+/// are not provided by this crate. All methods take twiddles computed for the base field of `E`
+/// (see [get_twiddles()](super::get_twiddles) and [get_inv_twiddles()](super::get_inv_twiddles))
+/// so that an implementation can reuse them (or its own pre-computed equivalents) across calls.
+///
+/// The trait is object safe, and thus, a backend can be selected at runtime via
+/// `&dyn ComputeBackend<E>`. [CpuBackend] uses the default implementations of all methods.
+pub trait ComputeBackend<E: FieldElement>: Sync {
+    /// Evaluates polynomial `p` in place over the multiplicative subgroup of size `p.len()`;
+    /// the evaluations are written into `p` in natural order.
+    ///
+    /// The default implementation calls [evaluate_poly()](super::evaluate_poly).
+    ///
+    /// # Panics
+    /// Panics if the conditions described in [evaluate_poly()](super::evaluate_poly) are not
+    /// satisfied.
+    fn ntt(&self, p: &mut [E], twiddles: &[E::BaseField]) {
+        evaluate_poly(p, twiddles);
+    }
+
+    /// Interpolates evaluations of a polynomial over the multiplicative subgroup of size
+    /// `evaluations.len()` in place; the coefficients of the polynomial are written into
+    /// `evaluations`.
+    ///
+    /// The default implementation calls [interpolate_poly()](super::interpolate_poly).
+    ///
+    /// # Panics
+    /// Panics if the conditions described in [interpolate_poly()](super::interpolate_poly) are
+    /// not satisfied.
+    fn intt(&self, evaluations: &mut [E], inv_twiddles: &[E::BaseField]) {
+        interpolate_poly(evaluations, inv_twiddles);
+    }
+
+    /// Evaluates polynomial `p` over the multiplicative subgroup of size `p.len()` *
+    /// `blowup_factor` shifted by `domain_offset` and returns the result.
+    ///
+    /// The default implementation calls
+    /// [evaluate_poly_with_offset()](super::evaluate_poly_with_offset).
+    ///
+    /// # Panics
+    /// Panics if the conditions described in
+    /// [evaluate_poly_with_offset()](super::evaluate_poly_with_offset) are not satisfied.
+    fn coset_lde(
+        &self,
+        p: &[E],
+        twiddles: &[E::BaseField],
+        domain_offset: E::BaseField,
+        blowup_factor: usize,
+    ) -> Vec<E> {
+        evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor)
+    }
+
+    /// Evaluates all polynomials in `polys` over the multiplicative subgroup of size
+    /// `polys[i].len()` * `blowup_factor` shifted by `domain_offset` and returns the results
+    /// (one vector of evaluations per polynomial).
+    ///
+    /// All polynomials are expected to have the same number of coefficients. Batching the
+    /// evaluations makes it possible to amortize the cost of moving data to and from a device.
+    ///
+    /// The default implementation calls [ComputeBackend::coset_lde()] for each polynomial; when
+    /// `concurrent` feature is enabled, polynomials are evaluated in multiple threads.
+    fn coset_lde_batch(
+        &self,
+        polys: &[Vec<E>],
+        twiddles: &[E::BaseField],
+        domain_offset: E::BaseField,
+        blowup_factor: usize,
+    ) -> Vec<Vec<E>> {
+        iter!(polys)
+            .map(|p| self.coset_lde(p, twiddles, domain_offset, blowup_factor))
+            .collect()
+    }
+
+    /// Evaluates all polynomials in `polys` at each of the points in `xs` and returns the
+    /// results; the j-th element of the i-th returned vector is the evaluation of `polys[i]` at
+    /// `xs[j]`.
+    ///
+    /// Unlike the other methods of this trait, the points do not need to form a domain, and the
+    /// polynomials may have different numbers of coefficients.
+    ///
+    /// The default implementation calls [polynom::eval_many()] for each polynomial; when
+    /// `concurrent` feature is enabled, polynomials are evaluated in multiple threads.
+    fn eval_batch(&self, polys: &[Vec<E>], xs: &[E]) -> Vec<Vec<E>> {
+        iter!(polys).map(|p| polynom::eval_many(p, xs)).collect()
+    }
+}
+
+// CPU BACKEND
+// ================================================================================================
+
+/// Compute backend which executes all operations on the CPU.
+///
+/// When `concurrent` feature is enabled, the operations are executed in multiple threads.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuBackend;
+
+impl<E: FieldElement> ComputeBackend<E> for CpuBackend {}
@@ -0,0 +1,408 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Circle FFT over the Mersenne-31 field.
+//!
+//! The field with modulus $p = 2^{31} - 1$ does not have large multiplicative subgroups of
+//! power-of-two order, and thus, the regular FFT cannot be used for polynomials over this field.
+//! However, points (x, y) which satisfy x<sup>2</sup> + y<sup>2</sup> = 1 form a cyclic group of
+//! order $p + 1 = 2^{31}$ (the circle group), and functions in this module evaluate and
+//! interpolate polynomials over cosets of subgroups of the circle group in O(`n` log `n`) time,
+//! where `n` is the domain size. This follows the approach described in
+//! [Circle STARKs](https://eprint.iacr.org/2024/278) by Haböck, Levit, and Papini.
+//!
+//! Polynomials are represented via their coefficients in the circle FFT basis. For a domain of
+//! size 2<sup>k</sup>, a basis element at index j is a product of factors y, x, π(x),
+//! π<sup>2</sup>(x), ..., π<sup>k-2</sup>(x) where π(x) = 2x<sup>2</sup> - 1, and bit k - 1 - t of
+//! j defines whether the t-th factor is included into the product. Thus, the most significant bit
+//! of j corresponds to y, and the least significant bit corresponds to π<sup>k-2</sup>(x).
+//!
+//! A polynomial interpolated over a domain of size `n` can be evaluated over a larger domain of
+//! size `n` * `b` by passing its coefficients directly to [evaluate_poly()]; this is equivalent
+//! to spreading the coefficients with stride `b`, and can be used to compute low-degree
+//! extensions of polynomials.
+
+use crate::{
+    field::{m31::BaseElement, FieldElement},
+    utils::batch_inversion,
+};
+use core::ops::{Add, Mul, Neg};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Log2 of the order of the circle group.
+pub const MAX_LOG_ORDER: u32 = 31;
+
+/// Generator of the circle group, i.e., a point of order 2^31.
+const GENERATOR: CirclePoint = CirclePoint {
+    x: BaseElement::new(2),
+    y: BaseElement::new(1268011823),
+};
+
+// CIRCLE POINT
+// ================================================================================================
+
+/// A point (x, y) on the circle x<sup>2</sup> + y<sup>2</sup> = 1 over the Mersenne-31 field.
+///
+/// Points form a group with the identity (1, 0) and the group operation defined as
+/// (x<sub>0</sub>, y<sub>0</sub>) + (x<sub>1</sub>, y<sub>1</sub>) =
+/// (x<sub>0</sub> * x<sub>1</sub> - y<sub>0</sub> * y<sub>1</sub>,
+/// x<sub>0</sub> * y<sub>1</sub> + y<sub>0</sub> * x<sub>1</sub>).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CirclePoint {
+    pub x: BaseElement,
+    pub y: BaseElement,
+}
+
+impl CirclePoint {
+    /// Identity element of the circle group.
+    pub const IDENTITY: Self = Self {
+        x: BaseElement::ONE,
+        y: BaseElement::ZERO,
+    };
+
+    /// Returns a point which generates a subgroup of the circle group of order 2^`log_order`.
+    ///
+    /// # Panics
+    /// Panics if `log_order` is greater than 31.
+    pub fn get_generator(log_order: u32) -> Self {
+        assert!(
+            log_order <= MAX_LOG_ORDER,
+            "order cannot exceed 2^{MAX_LOG_ORDER}, but was 2^{log_order}"
+        );
+        let mut result = GENERATOR;
+        for _ in log_order..MAX_LOG_ORDER {
+            result = result.double();
+        }
+        result
+    }
+
+    /// Returns this point added to itself.
+    pub fn double(self) -> Self {
+        self + self
+    }
+
+    /// Returns true if this point lies on the circle x<sup>2</sup> + y<sup>2</sup> = 1.
+    pub fn is_on_circle(&self) -> bool {
+        self.x.square() + self.y.square() == BaseElement::ONE
+    }
+}
+
+impl Add for CirclePoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x * rhs.x - self.y * rhs.y,
+            y: self.x * rhs.y + self.y * rhs.x,
+        }
+    }
+}
+
+impl Mul<u64> for CirclePoint {
+    type Output = Self;
+
+    /// Returns this point added to itself `rhs` times.
+    fn mul(self, rhs: u64) -> Self {
+        let mut result = Self::IDENTITY;
+        let mut base = self;
+        let mut n = rhs;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result + base;
+            }
+            base = base.double();
+            n >>= 1;
+        }
+        result
+    }
+}
+
+impl Neg for CirclePoint {
+    type Output = Self;
+
+    /// Returns the inverse of this point in the circle group, i.e., (x, -y).
+    fn neg(self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+        }
+    }
+}
+
+// DOMAIN
+// ================================================================================================
+
+/// Returns a circle domain of the specified size.
+///
+/// The domain is a coset Q + ⟨G⟩ of a subgroup of the circle group of size `domain_size`, where
+/// G generates the subgroup and Q is a point of order 2 * `domain_size` such that 2 * Q = G. The
+/// domain points are returned in the natural order, i.e., point at index i is Q + i * G. Such a
+/// domain is closed under the group inversion: the inverse of the point at index i is the point
+/// at index `domain_size` - 1 - i.
+///
+/// # Panics
+/// Panics if `domain_size` is not a power of two or is greater than 2^30.
+pub fn get_domain(domain_size: usize) -> Vec<CirclePoint> {
+    assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of 2, but was {domain_size}"
+    );
+    let log_size = domain_size.ilog2();
+    assert!(
+        log_size < MAX_LOG_ORDER,
+        "domain size cannot exceed 2^{}, but was 2^{log_size}",
+        MAX_LOG_ORDER - 1
+    );
+
+    let shift = CirclePoint::get_generator(log_size + 1);
+    let g = shift.double();
+    let mut result = Vec::with_capacity(domain_size);
+    let mut point = shift;
+    for _ in 0..domain_size {
+        result.push(point);
+        point = point + g;
+    }
+    result
+}
+
+// POLYNOMIAL EVALUATION
+// ================================================================================================
+
+/// Evaluates a polynomial on all points of the specified `domain` using the circle FFT algorithm.
+///
+/// The polynomial `p` is expected to be in coefficient form using the circle FFT basis for a
+/// domain of size `p.len()`. If `p` is shorter than the `domain`, the polynomial is evaluated
+/// over the `domain` without changing its degree (i.e., a low-degree extension is computed).
+///
+/// The `domain` must be a domain returned by [get_domain()].
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is not a power of two or is greater than the length of the `domain`.
+/// * Length of the `domain` is not a power of two.
+pub fn evaluate_poly<E>(p: &[E], domain: &[CirclePoint]) -> Vec<E>
+where
+    E: FieldElement<BaseField = BaseElement>,
+{
+    assert!(
+        p.len().is_power_of_two(),
+        "number of coefficients must be a power of 2, but was {}",
+        p.len()
+    );
+    assert!(
+        domain.len().is_power_of_two(),
+        "domain size must be a power of 2, but was {}",
+        domain.len()
+    );
+    assert!(
+        p.len() <= domain.len(),
+        "number of coefficients ({}) cannot exceed domain size ({})",
+        p.len(),
+        domain.len()
+    );
+
+    // spread the coefficients over the basis of the larger domain
+    let blowup = domain.len() / p.len();
+    let mut coefficients = E::zeroed_vector(domain.len());
+    for (i, &c) in p.iter().enumerate() {
+        coefficients[i * blowup] = c;
+    }
+
+    let n = domain.len();
+    if n == 1 {
+        return coefficients;
+    }
+
+    // evaluate the parts of the polynomial which do not depend on y over the x coordinates of
+    // the first half of the domain, and then combine them: f(x, y) = f_0(x) + y * f_1(x)
+    let xs = domain[..n / 2]
+        .iter()
+        .map(|point| point.x)
+        .collect::<Vec<_>>();
+    let f0 = evaluate_line(&coefficients[..n / 2], &xs);
+    let f1 = evaluate_line(&coefficients[n / 2..], &xs);
+
+    let mut result = E::zeroed_vector(n);
+    for (i, point) in domain[..n / 2].iter().enumerate() {
+        let t = f1[i].mul_base(point.y);
+        result[i] = f0[i] + t;
+        result[n - 1 - i] = f0[i] - t;
+    }
+    result
+}
+
+/// Evaluates a polynomial at the specified `point`.
+///
+/// The polynomial `p` is expected to be in coefficient form using the circle FFT basis for a
+/// domain of size `p.len()`.
+///
+/// The complexity of evaluation is O(`n`), where `n` is the length of `p`.
+///
+/// # Panics
+/// Panics if length of `p` is not a power of two.
+pub fn eval<E>(p: &[E], point: CirclePoint) -> E
+where
+    E: FieldElement<BaseField = BaseElement>,
+{
+    assert!(
+        p.len().is_power_of_two(),
+        "number of coefficients must be a power of 2, but was {}",
+        p.len()
+    );
+
+    // build basis factors ordered from the most significant bit of the coefficient index
+    let num_factors = p.len().ilog2() as usize;
+    let mut factors = Vec::with_capacity(num_factors);
+    if num_factors > 0 {
+        factors.push(point.y);
+        let mut x = point.x;
+        for _ in 1..num_factors {
+            factors.push(x);
+            x = x.square().double() - BaseElement::ONE;
+        }
+    }
+
+    let mut result = E::ZERO;
+    for (j, &c) in p.iter().enumerate() {
+        let mut basis = BaseElement::ONE;
+        for (t, &factor) in factors.iter().enumerate() {
+            if (j >> (num_factors - 1 - t)) & 1 == 1 {
+                basis *= factor;
+            }
+        }
+        result += c.mul_base(basis);
+    }
+    result
+}
+
+// POLYNOMIAL INTERPOLATION
+// ================================================================================================
+
+/// Interpolates `evaluations` over the specified `domain` into a polynomial in coefficient form
+/// using the circle FFT algorithm.
+///
+/// The coefficients of the returned polynomial are in the circle FFT basis for a domain of size
+/// `evaluations.len()`.
+///
+/// The `domain` must be a domain returned by [get_domain()].
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is not a power of two.
+/// * Length of `evaluations` is not equal to the length of the `domain`.
+pub fn interpolate_poly<E>(evaluations: &[E], domain: &[CirclePoint]) -> Vec<E>
+where
+    E: FieldElement<BaseField = BaseElement>,
+{
+    assert!(
+        evaluations.len().is_power_of_two(),
+        "number of evaluations must be a power of 2, but was {}",
+        evaluations.len()
+    );
+    assert_eq!(
+        evaluations.len(),
+        domain.len(),
+        "number of evaluations must be equal to the domain size"
+    );
+
+    let n = evaluations.len();
+    if n == 1 {
+        return evaluations.to_vec();
+    }
+
+    // split the polynomial as f(x, y) = f_0(x) + y * f_1(x); since the inverse of point (x, y)
+    // is (x, -y), both parts can be computed from evaluations at a point and its inverse
+    let ys = domain[..n / 2]
+        .iter()
+        .map(|point| point.y.double())
+        .collect::<Vec<_>>();
+    let inv_ys = batch_inversion(&ys);
+    let inv_two = BaseElement::ONE.double().inv();
+
+    let mut f0 = E::zeroed_vector(n / 2);
+    let mut f1 = E::zeroed_vector(n / 2);
+    for i in 0..n / 2 {
+        let (a, b) = (evaluations[i], evaluations[n - 1 - i]);
+        f0[i] = (a + b).mul_base(inv_two);
+        f1[i] = (a - b).mul_base(inv_ys[i]);
+    }
+
+    let xs = domain[..n / 2]
+        .iter()
+        .map(|point| point.x)
+        .collect::<Vec<_>>();
+    let mut result = interpolate_line(&f0, &xs);
+    result.extend_from_slice(&interpolate_line(&f1, &xs));
+    result
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates a univariate polynomial in the basis x, π(x), π^2(x), ... over the specified `xs`.
+///
+/// The `xs` must be such that xs[i] = -xs[len - 1 - i] for all i, and the first half of the `xs`
+/// mapped via π(x) = 2x^2 - 1 must have the same property; this is the case for x coordinates of
+/// the first half of a domain returned by [get_domain()].
+fn evaluate_line<E>(p: &[E], xs: &[BaseElement]) -> Vec<E>
+where
+    E: FieldElement<BaseField = BaseElement>,
+{
+    let m = xs.len();
+    if m == 1 {
+        return p.to_vec();
+    }
+
+    // g(x) = g_0(π(x)) + x * g_1(π(x))
+    let next_xs = xs[..m / 2]
+        .iter()
+        .map(|&x| x.square().double() - BaseElement::ONE)
+        .collect::<Vec<_>>();
+    let g0 = evaluate_line(&p[..m / 2], &next_xs);
+    let g1 = evaluate_line(&p[m / 2..], &next_xs);
+
+    let mut result = E::zeroed_vector(m);
+    for (i, &x) in xs[..m / 2].iter().enumerate() {
+        let t = g1[i].mul_base(x);
+        result[i] = g0[i] + t;
+        result[m - 1 - i] = g0[i] - t;
+    }
+    result
+}
+
+/// Interpolates `evaluations` over the specified `xs` into a univariate polynomial in the basis
+/// x, π(x), π^2(x), ...; this is the inverse of [evaluate_line()].
+fn interpolate_line<E>(evaluations: &[E], xs: &[BaseElement]) -> Vec<E>
+where
+    E: FieldElement<BaseField = BaseElement>,
+{
+    let m = xs.len();
+    if m == 1 {
+        return evaluations.to_vec();
+    }
+
+    let doubled_xs = xs[..m / 2].iter().map(|&x| x.double()).collect::<Vec<_>>();
+    let inv_xs = batch_inversion(&doubled_xs);
+    let inv_two = BaseElement::ONE.double().inv();
+
+    let mut g0 = E::zeroed_vector(m / 2);
+    let mut g1 = E::zeroed_vector(m / 2);
+    for i in 0..m / 2 {
+        let (a, b) = (evaluations[i], evaluations[m - 1 - i]);
+        g0[i] = (a + b).mul_base(inv_two);
+        g1[i] = (a - b).mul_base(inv_xs[i]);
+    }
+
+    let next_xs = xs[..m / 2]
+        .iter()
+        .map(|&x| x.square().double() - BaseElement::ONE)
+        .collect::<Vec<_>>();
+    let mut result = interpolate_line(&g0, &next_xs);
+    result.extend_from_slice(&interpolate_line(&g1, &next_xs));
+    result
+}
@@ -81,8 +81,8 @@ where
         .for_each(|(i, batch)| {
             let mut offset = domain_offset.exp(((i * batch_size) as u64).into()) * inv_len;
             for coeff in batch.iter_mut() {
-                *coeff = *coeff * offset;
-                offset = offset * domain_offset;
+                *coeff *= offset;
+                offset *= domain_offset;
             }
         });
 }
@@ -116,11 +116,35 @@ pub fn permute<E: FieldElement>(v: &mut [E]) {
 // SPLIT-RADIX FFT
 // ================================================================================================
 
+/// Minimum length of a row FFT within [split_radix_fft()] which is itself computed via the
+/// four-step algorithm. Shorter rows fit into L2 cache, and thus, FFTs over them are computed by
+/// a single thread without further splitting.
+const MIN_RECURSIVE_FFT_LEN: usize = 1 << 12;
+
 /// In-place recursive FFT with permuted output.
+///
+/// This is a four-step FFT: the values are viewed as a matrix which is transposed, FFTs over
+/// its rows are computed, the matrix is transposed back, and after multiplying by twiddle
+/// factors, FFTs over the rows are computed again. Both the row FFTs and the transposes are
+/// executed in multiple threads. Rows which are too large to fit into cache are split in the
+/// same way, and thus, the work is split across threads at every level of the recursion.
+///
 /// Adapted from: https://github.com/0xProject/OpenZKP/tree/master/algebra/primefield/src/fft
 pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
     values: &mut [E],
     twiddles: &[B],
+) {
+    four_step_fft(values, twiddles, MIN_RECURSIVE_FFT_LEN);
+}
+
+/// Computes the FFT described in [split_radix_fft()]; FFTs over rows with at least
+/// `min_recursive_len` elements are computed by calling this function recursively.
+///
+/// `twiddles` must contain exactly `values.len() / 2` twiddles.
+pub(super) fn four_step_fft<B: StarkField, E: FieldElement<BaseField = B>>(
+    values: &mut [E],
+    twiddles: &[B],
+    min_recursive_len: usize,
 ) {
     // generator of the domain should be in the middle of twiddles
     let n = values.len();
@@ -139,7 +163,7 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
     // apply inner FFTs
     values
         .par_chunks_mut(outer_len)
-        .for_each(|row| row.fft_in_place_raw(&twiddles, stretch, stretch, 0));
+        .for_each(|row| interleaved_row_fft(row, twiddles, stretch, min_recursive_len));
 
     // transpose inner x inner x stretch square matrix
     transpose_square_stretch(values, inner_len, stretch);
@@ -155,56 +179,139 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
                 let mut outer_twiddle = inner_twiddle;
                 for element in row.iter_mut().skip(1) {
                     *element = (*element).mul_base(outer_twiddle);
-                    outer_twiddle = outer_twiddle * inner_twiddle;
+                    outer_twiddle *= inner_twiddle;
                 }
             }
-            row.fft_in_place(&twiddles);
+            row_fft(row, twiddles, min_recursive_len);
         });
 }
 
-// TRANSPOSING
-// ================================================================================================
-
-fn transpose_square_stretch<T>(matrix: &mut [T], size: usize, stretch: usize) {
-    assert_eq!(matrix.len(), size * size * stretch);
-    match stretch {
-        1 => transpose_square_1(matrix, size),
-        2 => transpose_square_2(matrix, size),
-        _ => unimplemented!("only stretch sizes 1 and 2 are supported"),
+/// Computes FFT with permuted output over a row of the matrix in place; `twiddles` may contain
+/// twiddles for a domain larger than the row.
+///
+/// Rows with at least `min_recursive_len` elements are split using the four-step algorithm.
+#[inline(always)]
+fn row_fft<B: StarkField, E: FieldElement<BaseField = B>>(
+    row: &mut [E],
+    twiddles: &[B],
+    min_recursive_len: usize,
+) {
+    if row.len() < min_recursive_len {
+        row.fft_in_place(twiddles);
+    } else {
+        // twiddles are in bit-reversed order, and thus, their prefix contains twiddles for the
+        // smaller domain
+        four_step_fft(row, &twiddles[..row.len() / 2], min_recursive_len);
     }
 }
 
-fn transpose_square_1<T>(matrix: &mut [T], size: usize) {
-    debug_assert_eq!(matrix.len(), size * size);
-    if size % 2 != 0 {
-        unimplemented!("odd sizes are not supported");
+/// Computes `stretch` interleaved FFTs with permuted output over a row of the matrix in place;
+/// that is, the i-th FFT is computed over elements at positions i, i + stretch, i + 2 * stretch
+/// etc.
+///
+/// If each of the FFTs has at least `min_recursive_len` elements, the inputs are first gathered
+/// into contiguous chunks so that they can be split using the four-step algorithm, and the
+/// results are scattered back into their original positions.
+fn interleaved_row_fft<B: StarkField, E: FieldElement<BaseField = B>>(
+    row: &mut [E],
+    twiddles: &[B],
+    stretch: usize,
+    min_recursive_len: usize,
+) {
+    if stretch == 1 {
+        row_fft(row, twiddles, min_recursive_len);
+        return;
+    }
+    let fft_len = row.len() / stretch;
+    if fft_len < min_recursive_len {
+        row.fft_in_place_raw(twiddles, stretch, stretch, 0);
+        return;
     }
 
-    // iterate over upper-left triangle, working in 2x2 blocks
-    for row in (0..size).step_by(2) {
-        let i = row * size + row;
-        matrix.swap(i + 1, i + size);
-        for col in (row..size).step_by(2).skip(1) {
-            let i = row * size + col;
-            let j = col * size + row;
-            matrix.swap(i, j);
-            matrix.swap(i + 1, j + size);
-            matrix.swap(i + size, j + 1);
-            matrix.swap(i + size + 1, j + size + 1);
+    let mut inputs = unsafe { uninit_vector::<E>(row.len()) };
+    for (i, cell) in row.chunks_exact(stretch).enumerate() {
+        for (k, &value) in cell.iter().enumerate() {
+            inputs[k * fft_len + i] = value;
+        }
+    }
+    inputs
+        .par_chunks_mut(fft_len)
+        .for_each(|input| row_fft(input, twiddles, min_recursive_len));
+    for (i, cell) in row.chunks_exact_mut(stretch).enumerate() {
+        for (k, value) in cell.iter_mut().enumerate() {
+            *value = inputs[k * fft_len + i];
         }
     }
 }
 
-fn transpose_square_2<T>(matrix: &mut [T], size: usize) {
-    debug_assert_eq!(matrix.len(), 2 * size * size);
+// TRANSPOSING
+// ================================================================================================
+
+/// Number of rows and columns (in cells) of a tile used by the cache-blocked transpose.
+const TRANSPOSE_TILE_SIZE: usize = 16;
 
-    // iterate over upper-left triangle, working in 1x2 blocks
-    for row in 0..size {
-        for col in (row..size).skip(1) {
-            let i = (row * size + col) * 2;
-            let j = (col * size + row) * 2;
-            matrix.swap(i, j);
-            matrix.swap(i + 1, j + 1);
+/// Transposes a `size` x `size` square matrix in place, where each cell of the matrix consists of
+/// `stretch` consecutive elements.
+///
+/// The matrix is split into square tiles such that a pair of tiles being swapped fits into L1
+/// cache, and the pairs of tiles are swapped concurrently in multiple threads.
+pub(super) fn transpose_square_stretch<T: Send>(matrix: &mut [T], size: usize, stretch: usize) {
+    assert_eq!(matrix.len(), size * size * stretch);
+    assert!(size.is_power_of_two(), "matrix size must be a power of two");
+
+    let tile_size = TRANSPOSE_TILE_SIZE.min(size);
+    let num_tiles = size / tile_size;
+    if num_tiles == 1 {
+        transpose_tile_pair(matrix, size, stretch, tile_size, 0, 0);
+        return;
+    }
+
+    // a task for row i of tiles swaps all tiles (i, j) with tiles (j, i) for j >= i; to balance
+    // the work across tasks, each task handles rows i and num_tiles - i - 1 so that every task
+    // processes the same number of tiles
+    rayon::scope(|s| {
+        for row in 0..num_tiles / 2 {
+            // create another mutable reference to the matrix to use in a new thread; this is OK
+            // because tiles handled by different tasks never overlap
+            let matrix = unsafe { &mut *(&mut matrix[..] as *mut [T]) };
+            s.spawn(move |_| {
+                for tile_row in [row, num_tiles - row - 1] {
+                    for tile_col in tile_row..num_tiles {
+                        transpose_tile_pair(matrix, size, stretch, tile_size, tile_row, tile_col);
+                    }
+                }
+            });
+        }
+    });
+}
+
+/// Swaps the tile at position (`tile_row`, `tile_col`) with the tile at position (`tile_col`,
+/// `tile_row`) while transposing both tiles; if the two positions are the same, the tile is
+/// transposed in place.
+#[inline(always)]
+fn transpose_tile_pair<T>(
+    matrix: &mut [T],
+    size: usize,
+    stretch: usize,
+    tile_size: usize,
+    tile_row: usize,
+    tile_col: usize,
+) {
+    let row_start = tile_row * tile_size;
+    let col_start = tile_col * tile_size;
+    for row in row_start..row_start + tile_size {
+        // for a tile on the diagonal, only the cells above the diagonal need to be swapped
+        let col_start = if tile_row == tile_col {
+            row + 1
+        } else {
+            col_start
+        };
+        for col in col_start..(tile_col + 1) * tile_size {
+            let i = (row * size + col) * stretch;
+            let j = (col * size + row) * stretch;
+            for k in 0..stretch {
+                matrix.swap(i + k, j + k);
+            }
         }
     }
 }
@@ -222,7 +329,7 @@ fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset:
             let mut factor = offset.exp(((i * batch_size) as u64).into());
             for (s, d) in source.iter().zip(destination.iter_mut()) {
                 *d = (*s).mul_base(factor);
-                factor = factor * offset;
+                factor *= offset;
             }
         });
 }
@@ -25,6 +25,22 @@ pub trait FftInputs<E: FieldElement> {
     /// twiddle factor into a larger NTT.
     fn butterfly_twiddle(&mut self, twiddle: E::BaseField, offset: usize, stride: usize);
 
+    /// Applies [FftInputs::butterfly_twiddle()] with the same twiddle factor to `count`
+    /// consecutive offsets starting at `offset`.
+    ///
+    /// Specialized implementations of this function may be faster than the generic implementation.
+    fn butterfly_twiddle_batch(
+        &mut self,
+        twiddle: E::BaseField,
+        offset: usize,
+        stride: usize,
+        count: usize,
+    ) {
+        for offset in offset..(offset + count) {
+            self.butterfly_twiddle(twiddle, offset, stride);
+        }
+    }
+
     /// Swaps the element at index i with the element at index j. Specifically:
     ///
     /// elem_i <-> elem_j
@@ -122,6 +138,23 @@ impl<E: FieldElement> FftInputs<E> for [E] {
         self[j] = temp - self[j];
     }
 
+    #[inline(always)]
+    fn butterfly_twiddle_batch(
+        &mut self,
+        twiddle: E::BaseField,
+        offset: usize,
+        stride: usize,
+        count: usize,
+    ) {
+        debug_assert!(count <= stride, "count cannot exceed stride");
+        let (left, right) = self.split_at_mut(offset + stride);
+        E::butterfly_twiddle_slices(
+            &mut left[offset..offset + count],
+            &mut right[..count],
+            twiddle,
+        );
+    }
+
     fn swap(&mut self, i: usize, j: usize) {
         self.swap(i, j)
     }
@@ -136,10 +169,7 @@ impl<E: FieldElement> FftInputs<E> for [E] {
     }
 
     fn shift_by(&mut self, offset: E::BaseField) {
-        let offset = E::from(offset);
-        for d in self.iter_mut() {
-            *d *= offset;
-        }
+        E::mul_slice_in_place(self, E::from(offset));
     }
 }
 
@@ -249,8 +279,6 @@ fn fft_in_place<E, I>(
         .enumerate()
         .skip(1)
     {
-        for j in offset..(offset + count) {
-            I::butterfly_twiddle(values, twiddles[i], j, stride);
-        }
+        I::butterfly_twiddle_batch(values, twiddles[i], offset, stride, count);
     }
 }
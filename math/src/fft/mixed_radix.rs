@@ -0,0 +1,267 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Mixed-radix FFT over multiplicative subgroups whose sizes are not powers of two.
+//!
+//! Functions in this module can be used to evaluate and interpolate polynomials over domains of
+//! size `n` = 2<sup>a</sup> * 3<sup>b</sup> * 5<sup>c</sup>, as long as the multiplicative group
+//! of the field contains a subgroup of size `n` (i.e., `n` divides `p - 1`). For example, the
+//! 64-bit field supports domains of size 3 * 2<sup>k</sup> for all `k` up to 32, and thus, trace
+//! columns of such length don't need to be padded to the next power of two before being
+//! interpolated.
+//!
+//! The FFT is a recursive decimation-in-time Cooley-Tukey FFT which splits the domain by factors
+//! of 2, 3, and 5. Its runtime complexity is O(`n` log `n`), but it is not as optimized as the
+//! radix-2 FFT in the parent module; thus, for domains which are powers of two, the functions
+//! in the parent module should be used instead.
+
+use crate::field::{FieldElement, StarkField};
+use crate::utils::get_power_series;
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Radixes supported by the mixed-radix FFT.
+const RADIXES: [usize; 3] = [2, 3, 5];
+
+/// Largest of the supported radixes.
+const MAX_RADIX: usize = 5;
+
+// POLYNOMIAL EVALUATION
+// ================================================================================================
+
+/// Evaluates polynomial `p` over the multiplicative subgroup of size `p.len()` and returns the
+/// result.
+///
+/// The evaluations are returned in natural order: i-th evaluation is the value of the
+/// polynomial at `g^i`, where `g` is the root of unity returned by [get_root_of_unity()] for
+/// the domain size.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is zero or has prime factors other than 2, 3, and 5.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size `p.len()`.
+pub fn evaluate_poly<B, E>(p: &[E]) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let root = get_root_of_unity::<B>(p.len());
+    let mut result = E::zeroed_vector(p.len());
+    fft(p, 1, root, &mut result);
+    result
+}
+
+/// Evaluates polynomial `p` over the multiplicative subgroup of size `p.len()` * `blowup_factor`
+/// shifted by `domain_offset` and returns the result.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` * `blowup_factor` is zero or has prime factors other than 2, 3, and 5.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `p.len()` * `blowup_factor`.
+/// * `domain_offset` is ZERO.
+pub fn evaluate_poly_with_offset<B, E>(p: &[E], domain_offset: B, blowup_factor: usize) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+    let domain_size = p.len() * blowup_factor;
+    let root = get_root_of_unity::<B>(domain_size);
+
+    // multiply the coefficients by powers of the offset and pad them with zeros to the size of
+    // the domain
+    let mut coefficients = E::zeroed_vector(domain_size);
+    let mut offset = E::ONE;
+    for (c, &v) in coefficients.iter_mut().zip(p) {
+        *c = v * offset;
+        offset = offset.mul_base(domain_offset);
+    }
+
+    let mut result = E::zeroed_vector(domain_size);
+    fft(&coefficients, 1, root, &mut result);
+    result
+}
+
+// POLYNOMIAL INTERPOLATION
+// ================================================================================================
+
+/// Interpolates evaluations of a polynomial over the multiplicative subgroup of size
+/// `evaluations.len()` into a polynomial in coefficient form and returns the result.
+///
+/// The evaluations are expected to be in natural order (see [evaluate_poly()]).
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is zero or has prime factors other than 2, 3, and 5.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `evaluations.len()`.
+pub fn interpolate_poly<B, E>(evaluations: &[E]) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let root = get_root_of_unity::<B>(evaluations.len());
+    let mut result = E::zeroed_vector(evaluations.len());
+    fft(evaluations, 1, root.inv(), &mut result);
+
+    let inv_length = B::from(evaluations.len() as u64).inv();
+    E::mul_slice_in_place(&mut result, E::from(inv_length));
+    result
+}
+
+/// Interpolates evaluations of a polynomial over the multiplicative subgroup of size
+/// `evaluations.len()` shifted by `domain_offset` into a polynomial in coefficient form and
+/// returns the result.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is zero or has prime factors other than 2, 3, and 5.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `evaluations.len()`.
+/// * `domain_offset` is ZERO.
+pub fn interpolate_poly_with_offset<B, E>(evaluations: &[E], domain_offset: B) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+    let root = get_root_of_unity::<B>(evaluations.len());
+    let mut result = E::zeroed_vector(evaluations.len());
+    fft(evaluations, 1, root.inv(), &mut result);
+
+    // divide the coefficients by the domain size and by powers of the offset
+    let inv_offset = domain_offset.inv();
+    let mut factor = B::from(evaluations.len() as u64).inv();
+    for c in result.iter_mut() {
+        *c = c.mul_base(factor);
+        factor *= inv_offset;
+    }
+    result
+}
+
+// ROOTS OF UNITY
+// ================================================================================================
+
+/// Returns a primitive root of unity of order `n` in the field specified by `B`.
+///
+/// The root is computed as `GENERATOR^((p - 1) / n)`, where `p` is the field modulus, and thus
+/// `n` does not need to be a power of two.
+///
+/// # Panics
+/// Panics if `n` is zero or if the field does not contain a multiplicative subgroup of size `n`.
+pub fn get_root_of_unity<B: StarkField>(n: usize) -> B {
+    assert!(n != 0, "cannot get root of unity for n = 0");
+
+    // compute (p - 1) / n as little-endian bytes; the modulus is odd, and thus subtracting 1
+    // from it does not require a borrow
+    let mut power = B::get_modulus_le_bytes();
+    power[0] -= 1;
+    let mut remainder = 0u128;
+    for byte in power.iter_mut().rev() {
+        let value = (remainder << 8) | *byte as u128;
+        *byte = (value / n as u128) as u8;
+        remainder = value % n as u128;
+    }
+    assert_eq!(
+        remainder, 0,
+        "multiplicative subgroup of size {n} does not exist in the specified base field"
+    );
+
+    // raise the generator to the computed power using square-and-multiply over the bits of
+    // the power, starting from the most significant one
+    let mut result = B::ONE;
+    for byte in power.iter().rev() {
+        for i in (0..8).rev() {
+            result = result.square();
+            if (byte >> i) & 1 == 1 {
+                result *= B::GENERATOR;
+            }
+        }
+    }
+    result
+}
+
+/// Returns true if a domain of size `n` can be used with the mixed-radix FFT, i.e., if `n` is
+/// not zero and has no prime factors other than 2, 3, and 5.
+///
+/// This does not check whether the multiplicative subgroup of size `n` exists in a given field.
+pub fn is_supported_size(n: usize) -> bool {
+    if n == 0 {
+        return false;
+    }
+    let mut n = n;
+    for radix in RADIXES {
+        while n % radix == 0 {
+            n /= radix;
+        }
+    }
+    n == 1
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes the DFT of `n` = `result.len()` values `input[0]`, `input[stride]`, ...,
+/// `input[(n - 1) * stride]` over the subgroup generated by `root` (which must have order `n`),
+/// and writes the result into `result` in natural order.
+fn fft<B, E>(input: &[E], stride: usize, root: B, result: &mut [E])
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let n = result.len();
+    if n == 1 {
+        result[0] = input[0];
+        return;
+    }
+
+    let radix = RADIXES
+        .into_iter()
+        .find(|&radix| n % radix == 0)
+        .unwrap_or_else(|| panic!("domain size {n} has prime factors other than 2, 3, and 5"));
+    let m = n / radix;
+
+    // compute DFTs of the `radix` interleaved sub-sequences of the input; j-th sub-sequence
+    // consists of inputs j, j + radix, j + 2 * radix etc., and its DFT is written into the j-th
+    // chunk of the result
+    let sub_root = root.exp((radix as u64).into());
+    for (j, chunk) in result.chunks_mut(m).enumerate() {
+        fft(&input[j * stride..], stride * radix, sub_root, chunk);
+    }
+
+    // combine the sub-DFTs using radix-point DFTs; for each k, the values at positions
+    // k + j * m are read and written, and thus the combination can be done in place
+    let radix_root = root.exp((m as u64).into());
+    let radix_powers = get_power_series(radix_root, radix);
+    let mut root_k = B::ONE;
+    let mut t = [E::ZERO; MAX_RADIX];
+    for k in 0..m {
+        // multiply inputs by twiddles root^(j * k)
+        let mut twiddle = B::ONE;
+        for (j, value) in t.iter_mut().take(radix).enumerate() {
+            *value = result[j * m + k].mul_base(twiddle);
+            twiddle *= root_k;
+        }
+
+        // radix-point DFT
+        if radix == 2 {
+            result[k] = t[0] + t[1];
+            result[k + m] = t[0] - t[1];
+        } else {
+            for q in 0..radix {
+                let mut acc = t[0];
+                for j in 1..radix {
+                    acc += t[j].mul_base(radix_powers[(j * q) % radix]);
+                }
+                result[k + q * m] = acc;
+            }
+        }
+
+        root_k *= root;
+    }
+}
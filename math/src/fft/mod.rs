@@ -17,10 +17,18 @@ use crate::{
     utils::get_power_series,
 };
 
+pub mod circle;
 pub mod fft_inputs;
+pub mod mixed_radix;
 pub mod real_u64;
 mod serial;
 
+mod planner;
+pub use planner::{FftPlan, FftPlanner};
+
+mod backend;
+pub use backend::{ComputeBackend, CpuBackend};
+
 #[cfg(feature = "concurrent")]
 mod concurrent;
 
@@ -0,0 +1,231 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    evaluate_poly, evaluate_poly_with_offset, get_inv_twiddles, get_twiddles, interpolate_poly,
+    interpolate_poly_with_offset, FftInputs,
+};
+use crate::field::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// FFT PLAN
+// ================================================================================================
+
+/// Precomputed data for FFT-based evaluation and interpolation of polynomials over a single
+/// domain.
+///
+/// The domain is defined by its size `n` and offset `s` and consists of elements `s * g^i` for
+/// `i` in `0..n`, where `g` is the generator of the multiplicative subgroup of size `n` in the
+/// base field `B`. Both twiddles and inverse twiddles for the domain are computed once when the
+/// plan is created, and can then be used to evaluate or interpolate any number of polynomials
+/// (including polynomials over extensions of `B`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FftPlan<B: StarkField> {
+    domain_offset: B,
+    twiddles: Vec<B>,
+    inv_twiddles: Vec<B>,
+}
+
+impl<B: StarkField> FftPlan<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new plan for the domain of the specified size with the specified offset.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two or is smaller than 2.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size `domain_size`.
+    /// * `domain_offset` is ZERO.
+    pub fn new(domain_size: usize, domain_offset: B) -> Self {
+        assert!(domain_size >= 2, "domain size must be at least 2");
+        assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+        FftPlan {
+            domain_offset,
+            twiddles: get_twiddles(domain_size),
+            inv_twiddles: get_inv_twiddles(domain_size),
+        }
+    }
+
+    /// Returns a new plan for the domain with the specified offset built from the provided
+    /// twiddles; size of the domain is assumed to be twice the number of twiddles.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Length of `twiddles` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size
+    ///   `twiddles.len()` * 2.
+    /// * `domain_offset` is ZERO.
+    pub fn from_twiddles(twiddles: Vec<B>, domain_offset: B) -> Self {
+        assert!(
+            twiddles.len().is_power_of_two(),
+            "number of twiddles must be a power of 2"
+        );
+        assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+        let inv_twiddles = get_inv_twiddles(twiddles.len() * 2);
+        FftPlan {
+            domain_offset,
+            twiddles,
+            inv_twiddles,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the size of the domain for this plan.
+    pub fn domain_size(&self) -> usize {
+        self.twiddles.len() * 2
+    }
+
+    /// Returns the offset of the domain for this plan.
+    pub fn domain_offset(&self) -> B {
+        self.domain_offset
+    }
+
+    /// Returns twiddles which can be used to evaluate polynomials over the domain.
+    pub fn twiddles(&self) -> &[B] {
+        &self.twiddles
+    }
+
+    /// Returns inverse twiddles which can be used to interpolate polynomials over the domain.
+    pub fn inv_twiddles(&self) -> &[B] {
+        &self.inv_twiddles
+    }
+
+    // IN-PLACE EVALUATION AND INTERPOLATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates polynomial `p` over the domain of this plan in place.
+    ///
+    /// The polynomial is assumed to be in coefficient form; after the function returns, `p`
+    /// contains evaluations of the polynomial over the domain in natural order.
+    ///
+    /// # Panics
+    /// Panics if length of `p` is not equal to the size of the domain.
+    pub fn evaluate_in_place<E>(&self, p: &mut [E])
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        assert_eq!(
+            p.len(),
+            self.domain_size(),
+            "number of coefficients must be equal to domain size"
+        );
+        if self.domain_offset != B::ONE {
+            p.shift_by_series(B::ONE, self.domain_offset);
+        }
+        evaluate_poly(p, &self.twiddles);
+    }
+
+    /// Interpolates evaluations of a polynomial over the domain of this plan in place.
+    ///
+    /// The evaluations are assumed to be in natural order; after the function returns,
+    /// `evaluations` contains coefficients of the interpolated polynomial.
+    ///
+    /// # Panics
+    /// Panics if length of `evaluations` is not equal to the size of the domain.
+    pub fn interpolate_in_place<E>(&self, evaluations: &mut [E])
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        assert_eq!(
+            evaluations.len(),
+            self.domain_size(),
+            "number of evaluations must be equal to domain size"
+        );
+        if self.domain_offset == B::ONE {
+            interpolate_poly(evaluations, &self.inv_twiddles);
+        } else {
+            interpolate_poly_with_offset(evaluations, &self.inv_twiddles, self.domain_offset);
+        }
+    }
+
+    // OUT-OF-PLACE EVALUATION AND INTERPOLATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates polynomial `p` over the domain of size `p.len()` * `blowup_factor` shifted by
+    /// the offset of this plan and returns the result.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Length of `p` is not equal to the size of the domain.
+    /// * `blowup_factor` is not a power of two.
+    /// * Field specified by `B` does not contain a multiplicative subgroup of size
+    ///   `p.len()` * `blowup_factor`.
+    pub fn evaluate<E>(&self, p: &[E], blowup_factor: usize) -> Vec<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        assert_eq!(
+            p.len(),
+            self.domain_size(),
+            "number of coefficients must be equal to domain size"
+        );
+        evaluate_poly_with_offset(p, &self.twiddles, self.domain_offset, blowup_factor)
+    }
+
+    /// Interpolates evaluations of a polynomial over the domain of this plan and returns
+    /// coefficients of the interpolated polynomial.
+    ///
+    /// # Panics
+    /// Panics if length of `evaluations` is not equal to the size of the domain.
+    pub fn interpolate<E>(&self, evaluations: &[E]) -> Vec<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let mut result = evaluations.to_vec();
+        self.interpolate_in_place(&mut result);
+        result
+    }
+}
+
+// FFT PLANNER
+// ================================================================================================
+
+/// A cache of [FftPlan]s for the base field `B`.
+///
+/// Plans are keyed by domain size and domain offset: the first request for a given domain
+/// builds a plan for it, and all subsequent requests for the same domain return the cached plan.
+/// Since the planner is parameterized by the base field, plans for different fields are always
+/// kept in different planners.
+#[derive(Debug, Clone, Default)]
+pub struct FftPlanner<B: StarkField> {
+    plans: Vec<FftPlan<B>>,
+}
+
+impl<B: StarkField> FftPlanner<B> {
+    /// Returns a new planner with no cached plans.
+    pub fn new() -> Self {
+        FftPlanner { plans: Vec::new() }
+    }
+
+    /// Returns a plan for the domain of the specified size with the specified offset, building
+    /// the plan if it has not been requested from this planner before.
+    ///
+    /// # Panics
+    /// Panics if the plan needs to be built and any of the conditions described in
+    /// [FftPlan::new()] are not satisfied.
+    pub fn plan(&mut self, domain_size: usize, domain_offset: B) -> &FftPlan<B> {
+        // the number of distinct domains used in a single computation is expected to be small,
+        // and thus, a linear search is sufficient here
+        let position = self.plans.iter().position(|plan| {
+            plan.domain_size() == domain_size && plan.domain_offset() == domain_offset
+        });
+        let index = match position {
+            Some(index) => index,
+            None => {
+                self.plans.push(FftPlan::new(domain_size, domain_offset));
+                self.plans.len() - 1
+            }
+        };
+        &self.plans[index]
+    }
+
+    /// Returns the number of plans cached in this planner.
+    pub fn num_plans(&self) -> usize {
+        self.plans.len()
+    }
+}
@@ -4,8 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    fft::fft_inputs::FftInputs,
-    field::{f128::BaseElement, StarkField},
+    fft::{
+        circle, fft_inputs::FftInputs, mixed_radix, ComputeBackend, CpuBackend, FftPlan, FftPlanner,
+    },
+    field::{f128::BaseElement, f31, f64, m31, FieldElement, QuadExtension, StarkField},
     polynom,
     utils::get_power_series,
 };
@@ -58,6 +60,27 @@ fn fft_in_place() {
     assert_eq!(expected, p);
 }
 
+#[test]
+fn fft_evaluate_interpolate_f64() {
+    // the 64-bit field provides specialized slice operations used by the FFT (these are packed
+    // when the crate is compiled with `simd` feature); make sure the results are as expected
+    for n in [2, 16, 1024] {
+        let p: Vec<f64::BaseElement> = rand_vector(n);
+        let g = f64::BaseElement::get_root_of_unity(n.ilog2());
+        let domain = get_power_series(g, n);
+        let expected = polynom::eval_many(&p, &domain);
+
+        let twiddles = super::get_twiddles::<f64::BaseElement>(n);
+        let mut evaluations = p.clone();
+        super::evaluate_poly(&mut evaluations, &twiddles);
+        assert_eq!(expected, evaluations);
+
+        let inv_twiddles = super::get_inv_twiddles::<f64::BaseElement>(n);
+        super::interpolate_poly(&mut evaluations, &inv_twiddles);
+        assert_eq!(p, evaluations);
+    }
+}
+
 #[test]
 fn fft_get_twiddles() {
     let n = super::MIN_CONCURRENT_SIZE * 2;
@@ -70,6 +93,330 @@ fn fft_get_twiddles() {
     assert_eq!(expected, twiddles);
 }
 
+// FFT PLANNER
+// ================================================================================================
+
+#[test]
+fn fft_plan_evaluate_interpolate() {
+    let n = 64;
+    let offset = BaseElement::GENERATOR;
+    let plan = FftPlan::new(n, offset);
+    assert_eq!(n, plan.domain_size());
+    assert_eq!(super::get_twiddles::<BaseElement>(n), plan.twiddles());
+    assert_eq!(
+        super::get_inv_twiddles::<BaseElement>(n),
+        plan.inv_twiddles()
+    );
+
+    // in-place evaluation over the coset
+    let p: Vec<QuadExtension<BaseElement>> = rand_vector(n);
+    let domain = build_domain(n)
+        .into_iter()
+        .map(|x| x * offset)
+        .collect::<Vec<_>>();
+    let expected = domain
+        .iter()
+        .map(|&x| polynom::eval(&p, QuadExtension::from(x)))
+        .collect::<Vec<_>>();
+    let mut evaluations = p.clone();
+    plan.evaluate_in_place(&mut evaluations);
+    assert_eq!(expected, evaluations);
+
+    // in-place and out-of-place interpolation
+    assert_eq!(p, plan.interpolate(&evaluations));
+    plan.interpolate_in_place(&mut evaluations);
+    assert_eq!(p, evaluations);
+
+    // out-of-place evaluation with blowup
+    let expected = super::evaluate_poly_with_offset(&p, plan.twiddles(), offset, 4);
+    assert_eq!(expected, plan.evaluate(&p, 4));
+
+    // evaluation over the subgroup itself
+    let plan = FftPlan::from_twiddles(super::get_twiddles::<BaseElement>(n), BaseElement::ONE);
+    let mut evaluations = p.clone();
+    plan.evaluate_in_place(&mut evaluations);
+    let expected = build_domain(n)
+        .into_iter()
+        .map(|x| polynom::eval(&p, QuadExtension::from(x)))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, evaluations);
+    plan.interpolate_in_place(&mut evaluations);
+    assert_eq!(p, evaluations);
+}
+
+#[test]
+fn fft_planner_caching() {
+    let mut planner = FftPlanner::<BaseElement>::new();
+    assert_eq!(0, planner.num_plans());
+
+    let twiddles = planner.plan(16, BaseElement::ONE).twiddles().to_vec();
+    assert_eq!(super::get_twiddles::<BaseElement>(16), twiddles);
+    assert_eq!(1, planner.num_plans());
+
+    // the same domain reuses the cached plan
+    planner.plan(16, BaseElement::ONE);
+    assert_eq!(1, planner.num_plans());
+
+    // a different size or offset builds a new plan
+    assert_eq!(32, planner.plan(32, BaseElement::ONE).domain_size());
+    let plan = planner.plan(16, BaseElement::GENERATOR);
+    assert_eq!(BaseElement::GENERATOR, plan.domain_offset());
+    assert_eq!(3, planner.num_plans());
+}
+
+#[test]
+fn cpu_backend_ntt() {
+    let n = 256;
+    let p: Vec<BaseElement> = rand_vector(n);
+    let twiddles = super::get_twiddles::<BaseElement>(n);
+    let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+    let backend: &dyn ComputeBackend<BaseElement> = &CpuBackend;
+
+    let mut evaluations = p.clone();
+    backend.ntt(&mut evaluations, &twiddles);
+    assert_eq!(polynom::eval_many(&p, &build_domain(n)), evaluations);
+    backend.intt(&mut evaluations, &inv_twiddles);
+    assert_eq!(p, evaluations);
+
+    let offset = BaseElement::GENERATOR;
+    let expected = super::evaluate_poly_with_offset(&p, &twiddles, offset, 4);
+    assert_eq!(expected, backend.coset_lde(&p, &twiddles, offset, 4));
+    let batch = backend.coset_lde_batch(&[p.clone(), p], &twiddles, offset, 4);
+    assert_eq!(vec![expected.clone(), expected], batch);
+}
+
+#[test]
+fn cpu_backend_eval_batch() {
+    let polys: Vec<Vec<BaseElement>> = vec![rand_vector(16), rand_vector(7), Vec::new()];
+    let xs: Vec<BaseElement> = rand_vector(5);
+    let backend: &dyn ComputeBackend<BaseElement> = &CpuBackend;
+
+    let expected = polys
+        .iter()
+        .map(|p| xs.iter().map(|&x| polynom::eval(p, x)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, backend.eval_batch(&polys, &xs));
+}
+
+// CONCURRENT FFT
+// ================================================================================================
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn concurrent_transpose_square_stretch() {
+    for size in [1, 2, 16, 64, 256] {
+        for stretch in [1, 2] {
+            let matrix = (0..size * size * stretch).collect::<Vec<_>>();
+            let mut expected = matrix.clone();
+            for row in 0..size {
+                for col in 0..size {
+                    for k in 0..stretch {
+                        expected[(col * size + row) * stretch + k] =
+                            matrix[(row * size + col) * stretch + k];
+                    }
+                }
+            }
+
+            let mut actual = matrix;
+            super::concurrent::transpose_square_stretch(&mut actual, size, stretch);
+            assert_eq!(expected, actual);
+        }
+    }
+}
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn concurrent_evaluate_interpolate_poly() {
+    // both even and odd powers of two are tested since for the latter the FFT is computed over
+    // a matrix with cells of two elements
+    for n in [1 << 10, 1 << 13, 1 << 16] {
+        let p: Vec<BaseElement> = rand_vector(n);
+        let twiddles = super::get_twiddles::<BaseElement>(n);
+        let inv_twiddles = super::get_inv_twiddles::<BaseElement>(n);
+
+        let mut expected = p.clone();
+        super::serial::evaluate_poly(&mut expected, &twiddles);
+        let mut actual = p.clone();
+        super::concurrent::evaluate_poly(&mut actual, &twiddles);
+        assert_eq!(expected, actual);
+
+        super::concurrent::interpolate_poly(&mut actual, &inv_twiddles);
+        assert_eq!(p, actual);
+    }
+}
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn concurrent_recursive_four_step_fft() {
+    // with a small recursion threshold, row FFTs (including interleaved row FFTs for odd powers
+    // of two) are split recursively over several levels
+    for n in [1 << 8, 1 << 11, 1 << 14, 1 << 15] {
+        let p: Vec<BaseElement> = rand_vector(n);
+        let twiddles = super::get_twiddles::<BaseElement>(n);
+
+        let mut expected = p.clone();
+        expected.fft_in_place(&twiddles);
+        for min_recursive_len in [4, 16, 64] {
+            let mut actual = p.clone();
+            super::concurrent::four_step_fft(&mut actual, &twiddles, min_recursive_len);
+            assert_eq!(expected, actual);
+        }
+    }
+}
+
+// MIXED-RADIX FFT
+// ================================================================================================
+
+#[test]
+fn mixed_radix_get_root_of_unity() {
+    for n in [3, 5, 6, 15, 3 << 20, 5 << 10] {
+        let root = mixed_radix::get_root_of_unity::<f64::BaseElement>(n);
+        assert_eq!(f64::BaseElement::ONE, root.exp(n as u64));
+        for radix in [2, 3, 5] {
+            if n % radix == 0 {
+                let power = (n / radix) as u64;
+                assert_ne!(f64::BaseElement::ONE, root.exp(power));
+            }
+        }
+    }
+
+    // for powers of two, the root is a generator of the same subgroup as the two-adic root
+    let root = mixed_radix::get_root_of_unity::<BaseElement>(1 << 10);
+    assert_eq!(BaseElement::ONE, root.exp(1024u32.into()));
+    assert_ne!(BaseElement::ONE, root.exp(512u32.into()));
+
+    assert!(mixed_radix::is_supported_size(3 * 5 * 64));
+    assert!(!mixed_radix::is_supported_size(7 * 64));
+    assert!(!mixed_radix::is_supported_size(0));
+}
+
+#[test]
+fn mixed_radix_evaluate_interpolate_poly() {
+    for n in [1, 2, 3, 5, 6, 12, 15, 60, 96, 240] {
+        let p: Vec<f64::BaseElement> = rand_vector(n);
+        let root = mixed_radix::get_root_of_unity::<f64::BaseElement>(n);
+        let domain = get_power_series(root, n);
+        let expected = polynom::eval_many(&p, &domain);
+
+        let evaluations = mixed_radix::evaluate_poly(&p);
+        assert_eq!(expected, evaluations);
+        assert_eq!(p, mixed_radix::interpolate_poly(&evaluations));
+    }
+
+    // polynomials over an extension field and a field with a different modulus
+    let n = 3 * 5 * 16;
+    let p: Vec<QuadExtension<f31::BaseElement>> = rand_vector(n);
+    let evaluations = mixed_radix::evaluate_poly(&p);
+    assert_eq!(p, mixed_radix::interpolate_poly(&evaluations));
+}
+
+#[test]
+fn mixed_radix_evaluate_interpolate_poly_with_offset() {
+    let n = 48;
+    let blowup_factor = 5;
+    let offset = f64::BaseElement::GENERATOR;
+    let p: Vec<f64::BaseElement> = rand_vector(n);
+
+    let root = mixed_radix::get_root_of_unity::<f64::BaseElement>(n * blowup_factor);
+    let domain = get_power_series(root, n * blowup_factor)
+        .into_iter()
+        .map(|x| x * offset)
+        .collect::<Vec<_>>();
+    let expected = polynom::eval_many(&p, &domain);
+
+    let evaluations = mixed_radix::evaluate_poly_with_offset(&p, offset, blowup_factor);
+    assert_eq!(expected, evaluations);
+
+    let mut coefficients = mixed_radix::interpolate_poly_with_offset(&evaluations, offset);
+    assert!(coefficients[n..]
+        .iter()
+        .all(|&c| c == f64::BaseElement::ZERO));
+    coefficients.truncate(n);
+    assert_eq!(p, coefficients);
+}
+
+// CIRCLE FFT
+// ================================================================================================
+
+#[test]
+fn circle_get_domain() {
+    let g = circle::CirclePoint::get_generator(circle::MAX_LOG_ORDER);
+    assert!(g.is_on_circle());
+    assert_ne!(circle::CirclePoint::IDENTITY, g * (1 << 30));
+    assert_eq!(circle::CirclePoint::IDENTITY, g * (1 << 31));
+
+    let n = 16;
+    let domain = circle::get_domain(n);
+    assert_eq!(n, domain.len());
+    for (i, &point) in domain.iter().enumerate() {
+        assert!(point.is_on_circle());
+        assert_eq!(-point, domain[n - 1 - i]);
+    }
+}
+
+#[test]
+fn circle_evaluate_poly() {
+    let n = 32;
+    let domain = circle::get_domain(n);
+    let p: Vec<m31::BaseElement> = rand_vector(n);
+    let expected = domain
+        .iter()
+        .map(|&point| circle::eval(&p, point))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, circle::evaluate_poly(&p, &domain));
+
+    // the most significant bit of coefficient index corresponds to y, and the next bit
+    // corresponds to x
+    let mut p = vec![m31::BaseElement::ZERO; n];
+    p[n / 2] = m31::BaseElement::ONE;
+    let expected = domain.iter().map(|point| point.y).collect::<Vec<_>>();
+    assert_eq!(expected, circle::evaluate_poly(&p, &domain));
+
+    let mut p = vec![m31::BaseElement::ZERO; n];
+    p[n / 4] = m31::BaseElement::ONE;
+    let expected = domain.iter().map(|point| point.x).collect::<Vec<_>>();
+    assert_eq!(expected, circle::evaluate_poly(&p, &domain));
+}
+
+#[test]
+fn circle_interpolate_poly() {
+    for n in [1, 2, 4, 64] {
+        let domain = circle::get_domain(n);
+        let p: Vec<QuadExtension<m31::BaseElement>> = rand_vector(n);
+        let evaluations = circle::evaluate_poly(&p, &domain);
+        assert_eq!(p, circle::interpolate_poly(&evaluations, &domain));
+    }
+}
+
+#[test]
+fn circle_extend_poly() {
+    let n = 16;
+    let blowup = 8;
+    let domain = circle::get_domain(n);
+    let lde_domain = circle::get_domain(n * blowup);
+
+    let evaluations: Vec<m31::BaseElement> = rand_vector(n);
+    let p = circle::interpolate_poly(&evaluations, &domain);
+    let extended = circle::evaluate_poly(&p, &lde_domain);
+
+    // the extended evaluations are evaluations of the same polynomial
+    let expected = lde_domain
+        .iter()
+        .map(|&point| circle::eval(&p, point))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, extended);
+
+    // and interpolating them yields a polynomial with coefficients spread with stride blowup
+    let extended_p = circle::interpolate_poly(&extended, &lde_domain);
+    for (i, c) in extended_p.into_iter().enumerate() {
+        if i % blowup == 0 {
+            assert_eq!(p[i / blowup], c);
+        } else {
+            assert_eq!(m31::BaseElement::ZERO, c);
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
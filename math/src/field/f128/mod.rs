@@ -313,6 +313,31 @@ impl ExtensibleField<3> for BaseElement {
     }
 }
 
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Quartic extension for this field is not implemented as quadratic extension already provides
+/// sufficient security level.
+impl ExtensibleField<4> for BaseElement {
+    fn mul(_a: [Self; 4], _b: [Self; 4]) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 4], _b: Self) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 4]) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
 // TYPE CONVERSIONS
 // ================================================================================================
 
@@ -362,7 +387,7 @@ impl From<[u8; 16]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = String;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -411,6 +436,20 @@ impl Deserializable for BaseElement {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for BaseElement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BaseElement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
 // FINITE FIELD ARITHMETIC
 // ================================================================================================
 
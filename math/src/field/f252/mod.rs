@@ -0,0 +1,734 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An implementation of the 252-bit STARK-friendly prime field with modulus
+//! $2^{251} + 17 \cdot 2^{192} + 1$.
+//!
+//! This is the native field of Cairo and StarkNet (sometimes referred to as Stark252). Proofs
+//! generated in this field can support security level of well over 100 bits without using field
+//! extensions, and thus, AIRs describing Cairo programs can be proven in this field directly.
+//!
+//! All operations in this field are implemented using Montgomery arithmetic with Montgomery radix
+//! $2^{256}$. Base elements are stored in the Montgomery form using four `u64` limbs in
+//! little-endian order as the backing type, and are always kept in the canonical range [0, M).
+//! Since M = 1 mod 2^64, Montgomery reduction does not require multiplication by the Montgomery
+//! constant.
+
+use super::{ExtensibleField, FieldElement, StarkField};
+use core::{
+    cmp::Ordering,
+    convert::{TryFrom, TryInto},
+    fmt::{Debug, Display, Formatter},
+    mem,
+    ops::{
+        Add, AddAssign, BitAnd, Div, DivAssign, Mul, MulAssign, Neg, Shl, Shr, ShrAssign, Sub,
+        SubAssign,
+    },
+    slice,
+};
+use utils::{
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Randomizable, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Field modulus = 2^251 + 17 * 2^192 + 1
+const M: [u64; 4] = [1, 0, 0, 0x0800000000000011];
+
+/// 2^512 mod M; this is used for conversion of elements into Montgomery representation.
+const R2: [u64; 4] = [
+    0xfffffd737e000401,
+    0x00000001330fffff,
+    0xffffffffff6f8000,
+    0x07ffd4ab5e008810,
+];
+
+/// Number of bytes needed to represent field element
+const ELEMENT_BYTES: usize = 32;
+
+// 2^192 root of unity
+const G: [u64; 4] = [
+    0x6070024f42f8ef94,
+    0xad187148e11a6161,
+    0x3f0464519c8b0fa5,
+    0x005282db87529cfa,
+];
+
+// FIELD ELEMENT
+// ================================================================================================
+
+/// Represents base field element in the field.
+///
+/// Internal values are stored in Montgomery representation and are always in the range [0, M).
+/// The backing type is `[u64; 4]` with limbs in little-endian order.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BaseElement([u64; 4]);
+
+impl BaseElement {
+    /// Creates a new field element from the provided `value` specified as four 64-bit limbs in
+    /// little-endian order; the value is converted into Montgomery representation. If the value
+    /// is greater than or equal to the field modulus, modular reduction is silently performed.
+    pub const fn new(value: [u64; 4]) -> BaseElement {
+        // multiply the value with R2 to convert to Montgomery representation; this is OK because
+        // the product of R2 and any 256-bit value is smaller than 2^256 * M
+        BaseElement(mul(&value, &R2))
+    }
+}
+
+impl FieldElement for BaseElement {
+    type PositiveInteger = U256;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = BaseElement::new([0, 0, 0, 0]);
+    const ONE: Self = BaseElement::new([1, 0, 0, 0]);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+    const IS_CANONICAL: bool = false;
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[inline]
+    fn double(self) -> Self {
+        Self(add(&self.0, &self.0))
+    }
+
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1} for all x != 0; for x = 0, this returns 0
+        let (power, _) = sbb(&M, &[2, 0, 0, 0]);
+        self.exp(U256(power))
+    }
+
+    fn conjugate(&self) -> Self {
+        BaseElement(self.0)
+    }
+
+    // BASE ELEMENT CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // TODO: take endianness into account
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        if (p as usize) % mem::align_of::<u64>() != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+
+    // UTILITIES
+    // --------------------------------------------------------------------------------------------
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // this uses a specialized vector initialization code which requests zero-filled memory
+        // from the OS; unfortunately, this works only for built-in types and we can't use
+        // Self::ZERO here as much less efficient initialization procedure will be invoked.
+        // We also use u64 to make sure the memory is aligned correctly for our element size.
+        let result = vec![0u64; n * 4];
+
+        // translate a zero-filled vector of u64s into a vector of base field elements
+        let mut v = core::mem::ManuallyDrop::new(result);
+        let p = v.as_mut_ptr();
+        let len = v.len() / 4;
+        let cap = v.capacity() / 4;
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+}
+
+impl StarkField for BaseElement {
+    /// sage: MODULUS = 2^251 + 17 * 2^192 + 1 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True \
+    /// sage: GF(MODULUS).order() \
+    /// 3618502788666131213697322783095070105623107215331596699973092056135872020481
+    const MODULUS: Self::PositiveInteger = U256(M);
+    const MODULUS_BITS: u32 = 252;
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 3
+    const GENERATOR: Self = BaseElement::new([3, 0, 0, 0]);
+
+    /// sage: is_odd((MODULUS - 1) / 2^192) \
+    /// True
+    const TWO_ADICITY: u32 = 192;
+
+    /// sage: k = (MODULUS - 1) / 2^192 \
+    /// sage: GF(MODULUS).primitive_element()^k \
+    /// 145784604816374866144131285430889962727208297722245411306711449302875041684
+    const TWO_ADIC_ROOT_OF_UNITY: Self = BaseElement::new(G);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        U256(M).to_le_bytes().to_vec()
+    }
+
+    #[inline]
+    fn as_int(&self) -> Self::PositiveInteger {
+        // convert from Montgomery representation by multiplying by 1
+        U256(mul(&self.0, &[1, 0, 0, 0]))
+    }
+}
+
+impl Randomizable for BaseElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        // clear the 4 most significant bits so that a random value is a valid field element
+        // with probability close to 1/2
+        let mut bytes: [u8; ELEMENT_BYTES] = bytes.get(..ELEMENT_BYTES)?.try_into().ok()?;
+        bytes[ELEMENT_BYTES - 1] &= 0x0f;
+        Self::try_from(&bytes[..]).ok()
+    }
+}
+
+impl Display for BaseElement {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_int())
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl Add for BaseElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(add(&self.0, &rhs.0))
+    }
+}
+
+impl AddAssign for BaseElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for BaseElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(sub(&self.0, &rhs.0))
+    }
+}
+
+impl SubAssign for BaseElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BaseElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul(&self.0, &rhs.0))
+    }
+}
+
+impl MulAssign for BaseElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for BaseElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for BaseElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for BaseElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(sub(&[0, 0, 0, 0], &self.0))
+    }
+}
+
+// QUADRATIC EXTENSION
+// ================================================================================================
+
+/// Defines a quadratic extension of the base field over an irreducible polynomial x<sup>2</sup> -
+/// 3. Thus, an extension element is defined as α + β * φ, where φ is a root of this polynomial,
+/// and α and β are base field elements.
+impl ExtensibleField<2> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        let z = (a[0] + a[1]) * (b[0] + b[1]);
+        [a0b0 + a1b1.double() + a1b1, z - a0b0 - a1b1]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 2], b: Self) -> [Self; 2] {
+        [a[0] * b, a[1] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 2]) -> [Self; 2] {
+        [x[0], -x[1]]
+    }
+}
+
+// CUBIC EXTENSION
+// ================================================================================================
+
+/// Cubic extension for this field is not implemented as quadratic extension already provides
+/// sufficient security level.
+impl ExtensibleField<3> for BaseElement {
+    fn mul(_a: [Self; 3], _b: [Self; 3]) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 3], _b: Self) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 3]) -> [Self; 3] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Quartic extension for this field is not implemented as quadratic extension already provides
+/// sufficient security level.
+impl ExtensibleField<4> for BaseElement {
+    fn mul(_a: [Self; 4], _b: [Self; 4]) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 4], _b: Self) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 4]) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl From<U256> for BaseElement {
+    /// Converts a 256-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed.
+    fn from(value: U256) -> Self {
+        BaseElement::new(value.0)
+    }
+}
+
+impl From<u128> for BaseElement {
+    /// Converts a 128-bit value into a field element.
+    fn from(value: u128) -> Self {
+        BaseElement::new([value as u64, (value >> 64) as u64, 0, 0])
+    }
+}
+
+impl From<u64> for BaseElement {
+    /// Converts a 64-bit value into a field element.
+    fn from(value: u64) -> Self {
+        BaseElement::new([value, 0, 0, 0])
+    }
+}
+
+impl From<u32> for BaseElement {
+    /// Converts a 32-bit value into a field element.
+    fn from(value: u32) -> Self {
+        BaseElement::new([value as u64, 0, 0, 0])
+    }
+}
+
+impl From<u16> for BaseElement {
+    /// Converts a 16-bit value into a field element.
+    fn from(value: u16) -> Self {
+        BaseElement::new([value as u64, 0, 0, 0])
+    }
+}
+
+impl From<u8> for BaseElement {
+    /// Converts an 8-bit value into a field element.
+    fn from(value: u8) -> Self {
+        BaseElement::new([value as u64, 0, 0, 0])
+    }
+}
+
+impl TryFrom<&[u8]> for BaseElement {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to encode the element in the canonical
+    /// representation in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let value = bytes
+            .try_into()
+            .map(U256::from_le_bytes)
+            .map_err(|error| DeserializationError::UnknownError(format!("{error}")))?;
+        if value >= U256(M) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement::new(value.0))
+    }
+}
+
+impl AsBytes for BaseElement {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const BaseElement = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for BaseElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // convert from Montgomery representation into canonical representation
+        target.write_bytes(&self.as_int().to_le_bytes());
+    }
+}
+
+impl Deserializable for BaseElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = U256::from_le_bytes(source.read_array()?);
+        if value >= U256(M) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement::new(value.0))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BaseElement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BaseElement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
+// POSITIVE INTEGER
+// ================================================================================================
+
+/// A 256-bit unsigned integer represented by four 64-bit limbs in little-endian order.
+///
+/// This type is used to represent the field modulus as well as canonical representations of
+/// field elements, and supports only the operations needed for exponentiation of field elements.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct U256(pub [u64; 4]);
+
+impl U256 {
+    /// Returns a little-endian byte representation of this integer.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut result = [0; 32];
+        for (chunk, limb) in result.chunks_mut(8).zip(self.0) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        result
+    }
+
+    /// Returns an integer encoded by the provided little-endian `bytes`.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let mut result = [0; 4];
+        for (limb, chunk) in result.iter_mut().zip(bytes.chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().expect("chunk is 8 bytes long"));
+        }
+        Self(result)
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Shl<u32> for U256 {
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self {
+        let (limbs, bits) = ((rhs / 64) as usize, rhs % 64);
+        let mut result = [0; 4];
+        for (i, limb) in result.iter_mut().enumerate().skip(limbs) {
+            *limb = self.0[i - limbs] << bits;
+            if bits > 0 && i > limbs {
+                *limb |= self.0[i - limbs - 1] >> (64 - bits);
+            }
+        }
+        Self(result)
+    }
+}
+
+impl Shr<u32> for U256 {
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self {
+        let (limbs, bits) = ((rhs / 64) as usize, rhs % 64);
+        let mut result = [0; 4];
+        for (i, limb) in result
+            .iter_mut()
+            .enumerate()
+            .take(4usize.saturating_sub(limbs))
+        {
+            *limb = self.0[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < 4 {
+                *limb |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+        Self(result)
+    }
+}
+
+impl ShrAssign for U256 {
+    fn shr_assign(&mut self, rhs: Self) {
+        // shifting by 256 or more bits yields zero
+        *self = match u32::try_from(rhs.0[0]) {
+            Ok(shift) if rhs.0[1..] == [0, 0, 0] && shift < 256 => *self >> shift,
+            _ => Self::default(),
+        };
+    }
+}
+
+impl BitAnd for U256 {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self([
+            self.0[0] & rhs.0[0],
+            self.0[1] & rhs.0[1],
+            self.0[2] & rhs.0[2],
+            self.0[3] & rhs.0[3],
+        ])
+    }
+}
+
+impl From<u32> for U256 {
+    fn from(value: u32) -> Self {
+        Self([value as u64, 0, 0, 0])
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+}
+
+impl Display for U256 {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "0x{:016x}{:016x}{:016x}{:016x}",
+            self.0[3], self.0[2], self.0[1], self.0[0]
+        )
+    }
+}
+
+// FINITE FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes (a + b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+fn add(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    // since M < 2^252, the sum cannot overflow
+    let (z, _) = adc(a, b);
+    let (r, borrow) = sbb(&z, &M);
+    if borrow {
+        z
+    } else {
+        r
+    }
+}
+
+/// Computes (a - b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+fn sub(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let (z, borrow) = sbb(a, b);
+    if borrow {
+        adc(&z, &M).0
+    } else {
+        z
+    }
+}
+
+/// Computes (a * b * 2^-256) reduced by M such that the output is in [0, M) range; the product
+/// of a and b is assumed to be smaller than 2^256 * M.
+///
+/// This uses the coarsely integrated operand scanning (CIOS) method; since M = 1 mod 2^64, the
+/// Montgomery constant -M^{-1} mod 2^64 is equal to 2^64 - 1, and thus, the reduction factor for
+/// each limb is the negation of the lowest limb of the accumulator.
+#[inline(always)]
+const fn mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    let mut i = 0;
+    while i < 4 {
+        // t += a * b[i]
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 4 {
+            let z = t[j] as u128 + (a[j] as u128) * (b[i] as u128) + carry as u128;
+            t[j] = z as u64;
+            carry = (z >> 64) as u64;
+            j += 1;
+        }
+        let z = t[4] as u128 + carry as u128;
+        t[4] = z as u64;
+        t[5] = (z >> 64) as u64;
+
+        // t = (t + m * M) / 2^64
+        let m = t[0].wrapping_neg();
+        let z = t[0] as u128 + (m as u128) * (M[0] as u128);
+        let mut carry = (z >> 64) as u64;
+        let mut j = 1;
+        while j < 4 {
+            let z = t[j] as u128 + (m as u128) * (M[j] as u128) + carry as u128;
+            t[j - 1] = z as u64;
+            carry = (z >> 64) as u64;
+            j += 1;
+        }
+        let z = t[4] as u128 + carry as u128;
+        t[3] = z as u64;
+        t[4] = t[5] + (z >> 64) as u64;
+        i += 1;
+    }
+
+    // the result is smaller than 2 * M, and thus, a single subtraction is sufficient
+    let r = [t[0], t[1], t[2], t[3]];
+    let (reduced, borrow) = sbb(&r, &M);
+    if borrow {
+        r
+    } else {
+        reduced
+    }
+}
+
+/// Computes a + b and returns the result together with the carry flag.
+#[inline(always)]
+const fn adc(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], bool) {
+    let mut result = [0; 4];
+    let mut carry = false;
+    let mut i = 0;
+    while i < 4 {
+        let (z, c1) = a[i].overflowing_add(b[i]);
+        let (z, c2) = z.overflowing_add(carry as u64);
+        result[i] = z;
+        carry = c1 | c2;
+        i += 1;
+    }
+    (result, carry)
+}
+
+/// Computes a - b and returns the result together with the borrow flag.
+#[inline(always)]
+const fn sbb(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], bool) {
+    let mut result = [0; 4];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < 4 {
+        let (z, b1) = a[i].overflowing_sub(b[i]);
+        let (z, b2) = z.overflowing_sub(borrow as u64);
+        result[i] = z;
+        borrow = b1 | b2;
+        i += 1;
+    }
+    (result, borrow)
+}
@@ -0,0 +1,388 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    AsBytes, BaseElement, ByteReader, Deserializable, DeserializationError, FieldElement,
+    Serializable, StarkField, U256,
+};
+use crate::field::{ExtensionOf, QuadExtension};
+use core::convert::TryFrom;
+use num_bigint::BigUint;
+use proptest::prelude::*;
+use rand_utils::rand_value;
+use utils::SliceReader;
+
+// MANUAL TESTS
+// ================================================================================================
+
+#[test]
+fn add() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r + BaseElement::ZERO);
+
+    // test addition within bounds
+    assert_eq!(
+        BaseElement::from(5u8),
+        BaseElement::from(2u8) + BaseElement::from(3u8)
+    );
+
+    // test overflow
+    let t = BaseElement::from(modulus_minus(1));
+    assert_eq!(BaseElement::ZERO, t + BaseElement::ONE);
+    assert_eq!(BaseElement::ONE, t + BaseElement::from(2u8));
+}
+
+#[test]
+fn sub() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r - BaseElement::ZERO);
+
+    // test subtraction within bounds
+    assert_eq!(
+        BaseElement::from(2u8),
+        BaseElement::from(5u8) - BaseElement::from(3u8)
+    );
+
+    // test underflow
+    let expected = BaseElement::from(modulus_minus(2));
+    assert_eq!(expected, BaseElement::from(3u8) - BaseElement::from(5u8));
+}
+
+#[test]
+fn mul() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(BaseElement::ZERO, r * BaseElement::ZERO);
+    assert_eq!(r, r * BaseElement::ONE);
+
+    // test multiplication within bounds
+    assert_eq!(
+        BaseElement::from(15u8),
+        BaseElement::from(5u8) * BaseElement::from(3u8)
+    );
+
+    // test overflow
+    let t = BaseElement::from(modulus_minus(1));
+    assert_eq!(BaseElement::ONE, t * t);
+    assert_eq!(
+        BaseElement::from(modulus_minus(2)),
+        t * BaseElement::from(2u8)
+    );
+    assert_eq!(
+        BaseElement::from(modulus_minus(4)),
+        t * BaseElement::from(4u8)
+    );
+
+    let t = BaseElement::from(BaseElement::MODULUS >> 1) + BaseElement::ONE;
+    assert_eq!(BaseElement::ONE, t * BaseElement::from(2u8));
+}
+
+#[test]
+fn exp() {
+    let a = BaseElement::ZERO;
+    assert_eq!(a.exp(U256::from(0u32)), BaseElement::ONE);
+    assert_eq!(a.exp(U256::from(1u32)), BaseElement::ZERO);
+
+    let a = BaseElement::ONE;
+    assert_eq!(a.exp(U256::from(0u32)), BaseElement::ONE);
+    assert_eq!(a.exp(U256::from(1u32)), BaseElement::ONE);
+    assert_eq!(a.exp(U256::from(3u32)), BaseElement::ONE);
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a.exp(U256::from(3u32)), a * a * a);
+}
+
+#[test]
+fn inv() {
+    // identity
+    assert_eq!(BaseElement::ONE, BaseElement::inv(BaseElement::ONE));
+    assert_eq!(BaseElement::ZERO, BaseElement::inv(BaseElement::ZERO));
+}
+
+#[test]
+fn element_as_int() {
+    let v = U256([u64::MAX; 4]);
+    let e = BaseElement::from(v);
+    assert_eq!(to_biguint(v) % modulus(), to_biguint(e.as_int()));
+}
+
+#[test]
+fn equals() {
+    let a = BaseElement::ONE;
+    let b = BaseElement::from(modulus_minus(1)) * BaseElement::from(modulus_minus(1));
+
+    // elements are equal
+    assert_eq!(a, b);
+    assert_eq!(a.as_int(), b.as_int());
+    assert_eq!(a.to_bytes(), b.to_bytes());
+    assert_eq!(a.as_bytes(), b.as_bytes());
+}
+
+// QUADRATIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quad_mul() {
+    // test multiplication within bounds: (3 + 2x) * (5 + 4x) = 15 + 22x + 8x^2, x^2 = 3
+    let a = <QuadExtension<BaseElement>>::new(BaseElement::from(3u8), BaseElement::from(2u8));
+    let b = <QuadExtension<BaseElement>>::new(BaseElement::from(5u8), BaseElement::from(4u8));
+    let expected =
+        <QuadExtension<BaseElement>>::new(BaseElement::from(39u8), BaseElement::from(22u8));
+    assert_eq!(expected, a * b);
+}
+
+#[test]
+fn quad_mul_base() {
+    let a = <QuadExtension<BaseElement>>::new(rand_value(), rand_value());
+    let b0 = rand_value();
+    let b = <QuadExtension<BaseElement>>::new(b0, BaseElement::ZERO);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+// ROOTS OF UNITY
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn get_root_of_unity() {
+    let root_192 = BaseElement::get_root_of_unity(192);
+    assert_eq!(BaseElement::TWO_ADIC_ROOT_OF_UNITY, root_192);
+    assert_eq!(BaseElement::ONE, root_192.exp(U256::from(1u32) << 192));
+    assert_ne!(BaseElement::ONE, root_192.exp(U256::from(1u32) << 191));
+
+    let root_20 = BaseElement::get_root_of_unity(20);
+    assert_eq!(BaseElement::ONE, root_20.exp(U256::from(1u32 << 20)));
+    assert_ne!(BaseElement::ONE, root_20.exp(U256::from(1u32 << 19)));
+}
+
+// SERIALIZATION AND DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn try_from_slice() {
+    let mut bytes = vec![0; 32];
+    bytes[0] = 1;
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_ok());
+    assert_eq!(U256::from(1u32), result.unwrap().as_int());
+
+    let result = BaseElement::try_from(&bytes[..31]);
+    assert!(result.is_err());
+
+    bytes.push(0);
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+
+    let bytes = BaseElement::get_modulus_le_bytes();
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+}
+
+#[test]
+fn serialization() {
+    let a: BaseElement = rand_value();
+    let bytes = a.to_bytes();
+    assert_eq!(a.as_int().to_le_bytes().to_vec(), bytes);
+
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(a, BaseElement::read_from(&mut reader).unwrap());
+    assert!(!reader.has_more_bytes());
+
+    let bytes = BaseElement::get_modulus_le_bytes();
+    let mut reader = SliceReader::new(&bytes);
+    assert!(BaseElement::read_from(&mut reader).is_err());
+}
+
+#[test]
+fn bytes_as_elements() {
+    let elements = vec![
+        BaseElement::from(1u8),
+        BaseElement::from(2u8),
+        BaseElement::from(3u8),
+        BaseElement::from(4u8),
+    ];
+
+    let mut bytes = BaseElement::elements_as_bytes(&elements).to_vec();
+    bytes.extend_from_slice(BaseElement::from(5u8).as_bytes());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..128]) };
+    assert!(result.is_ok());
+    assert_eq!(elements, result.unwrap());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..129]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[1..129]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
+// INITIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn zeroed_vector() {
+    let result = BaseElement::zeroed_vector(4);
+    assert_eq!(4, result.len());
+    for element in result.into_iter() {
+        assert_eq!(BaseElement::ZERO, element);
+    }
+}
+
+// POSITIVE INTEGER
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn u256_ops() {
+    let v = U256([1, 2, 3, 4]);
+    assert_eq!(U256([2, 4, 6, 8]), v << 1);
+    assert_eq!(U256([0, 1, 2, 3]), v << 64);
+    assert_eq!(U256([1 << 63 | 1, 1, 2, 0]), v >> 65);
+    assert_eq!(U256([0; 4]), v >> 255 >> 1);
+
+    let mut w = v;
+    w >>= U256::from(64u32);
+    assert_eq!(U256([2, 3, 4, 0]), w);
+    w >>= U256([0, 1, 0, 0]);
+    assert_eq!(U256([0; 4]), w);
+
+    assert!(U256([0, 0, 0, 1]) > U256([u64::MAX, u64::MAX, u64::MAX, 0]));
+    assert_eq!(v, U256::from_le_bytes(v.to_le_bytes()));
+}
+
+// CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "bigint")]
+#[test]
+fn biguint_conversions() {
+    assert_eq!(modulus(), BaseElement::get_modulus_biguint());
+
+    let r: BaseElement = rand_value();
+    assert_eq!(to_biguint(r.as_int()), r.as_biguint());
+    assert_eq!(r, BaseElement::from_biguint(&r.as_biguint()));
+    assert_eq!(Some(r), BaseElement::try_from_biguint(&r.as_biguint()));
+
+    // values which are not smaller than the modulus are reduced by from_biguint() and rejected
+    // by try_from_biguint()
+    let v = modulus() + BigUint::from(5u8);
+    assert_eq!(BaseElement::from(5u8), BaseElement::from_biguint(&v));
+    assert_eq!(None, BaseElement::try_from_biguint(&v));
+    assert_eq!(None, BaseElement::try_from_biguint(&modulus()));
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn from_other_field() {
+    use crate::fields::{f128, f31, f64, m31};
+
+    // elements of smaller fields are converted into elements with the same integer value
+    let a: f64::BaseElement = rand_value();
+    let b = BaseElement::from_other(a);
+    assert_eq!(BaseElement::from(a.as_int()), b);
+    assert_eq!(Some(a), f64::BaseElement::try_from_other(b));
+    assert_eq!(a, f64::BaseElement::from_other(b));
+
+    let a: f128::BaseElement = rand_value();
+    assert_eq!(BaseElement::from(a.as_int()), BaseElement::from_other(a));
+
+    let a = f31::BaseElement::from(5u32);
+    assert_eq!(BaseElement::from(5u8), BaseElement::from_other(a));
+    let a = m31::BaseElement::from(5u32);
+    assert_eq!(Some(BaseElement::from(5u8)), BaseElement::try_from_other(a));
+
+    // elements of larger fields are reduced modulo the modulus of the smaller field, unless
+    // the conversion is checked
+    let v = BaseElement::from(u64::MAX);
+    assert_eq!(None, f64::BaseElement::try_from_other(v));
+    assert_eq!(
+        f64::BaseElement::from(u64::MAX),
+        f64::BaseElement::from_other(v)
+    );
+    let v = f64::BaseElement::from(m31::BaseElement::MODULUS + 3);
+    assert_eq!(m31::BaseElement::from(3u8), m31::BaseElement::from_other(v));
+}
+
+// RANDOMIZED TESTS
+// ================================================================================================
+
+proptest! {
+
+    #[test]
+    fn add_proptest(a in any::<[u64; 4]>(), b in any::<[u64; 4]>()) {
+        let result = BaseElement::new(a) + BaseElement::new(b);
+        let expected = (to_biguint(U256(a)) + to_biguint(U256(b))) % modulus();
+        prop_assert_eq!(expected, to_biguint(result.as_int()));
+    }
+
+    #[test]
+    fn sub_proptest(a in any::<[u64; 4]>(), b in any::<[u64; 4]>()) {
+        let result = BaseElement::new(a) - BaseElement::new(b);
+        let m = modulus();
+        let expected = (to_biguint(U256(a)) % &m + &m - to_biguint(U256(b)) % &m) % &m;
+        prop_assert_eq!(expected, to_biguint(result.as_int()));
+    }
+
+    #[test]
+    fn mul_proptest(a in any::<[u64; 4]>(), b in any::<[u64; 4]>()) {
+        let result = BaseElement::new(a) * BaseElement::new(b);
+        let expected = (to_biguint(U256(a)) * to_biguint(U256(b))) % modulus();
+        prop_assert_eq!(expected, to_biguint(result.as_int()));
+    }
+
+    #[test]
+    fn exp_proptest(a in any::<[u64; 4]>(), b in any::<[u64; 4]>()) {
+        let result = BaseElement::new(a).exp(U256(b));
+        let expected = to_biguint(U256(a)).modpow(&to_biguint(U256(b)), &modulus());
+        prop_assert_eq!(expected, to_biguint(result.as_int()));
+    }
+
+    #[test]
+    fn inv_proptest(a in any::<[u64; 4]>()) {
+        let a = BaseElement::new(a);
+        let b = a.inv();
+
+        let expected = if a == BaseElement::ZERO { BaseElement::ZERO } else { BaseElement::ONE };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    #[test]
+    fn from_u128_proptest(v in any::<u128>()) {
+        let e = BaseElement::from(v);
+        prop_assert_eq!(BigUint::from(v), to_biguint(e.as_int()));
+    }
+
+    // QUADRATIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn quad_mul_inv_proptest(a0 in any::<[u64; 4]>(), a1 in any::<[u64; 4]>()) {
+        let a = QuadExtension::<BaseElement>::new(BaseElement::new(a0), BaseElement::new(a1));
+        let b = a.inv();
+
+        let expected = if a == QuadExtension::<BaseElement>::ZERO {
+            QuadExtension::<BaseElement>::ZERO
+        } else {
+            QuadExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_biguint(value: U256) -> BigUint {
+    BigUint::from_bytes_le(&value.to_le_bytes())
+}
+
+fn modulus() -> BigUint {
+    to_biguint(BaseElement::MODULUS)
+}
+
+fn modulus_minus(value: u64) -> U256 {
+    U256(super::sbb(&super::M, &[value, 0, 0, 0]).0)
+}
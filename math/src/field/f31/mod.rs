@@ -0,0 +1,612 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An implementation of the 31-bit BabyBear prime field with modulus $2^{31} - 2^{27} + 1$.
+//!
+//! All operations in this field are implemented using Montgomery arithmetic with Montgomery radix
+//! $2^{32}$. Base elements are stored in the Montgomery form using `u32` as the backing type, and
+//! are always kept in the canonical range [0, M). Since the backing type is a plain `u32`, slices
+//! of elements can be processed by the compiler using vector instructions, and elements can be
+//! shared with other implementations of this field (e.g., the ones used by RISC Zero and Plonky3)
+//! once converted into the canonical representation.
+//!
+//! Extensions of degree 2, 3, 4, and 5 are defined over binomials of the form x<sup>N</sup> - w.
+//! The quartic extension uses the same irreducible polynomial as Plonky3 (x<sup>4</sup> - 11).
+
+use super::{ExtensibleField, FieldElement, StarkField};
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt::{Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use utils::{
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Randomizable, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Field modulus = 2^31 - 2^27 + 1
+const M: u32 = 2013265921;
+
+/// 2^64 mod M; this is used for conversion of elements into Montgomery representation.
+const R2: u32 = 1172168163;
+
+/// -M^{-1} mod 2^32; this is used during element multiplication.
+const U: u32 = 2013265919;
+
+/// Number of bytes needed to represent field element
+const ELEMENT_BYTES: usize = core::mem::size_of::<u32>();
+
+// 2^27 root of unity
+const G: u32 = 440564289;
+
+// FIELD ELEMENT
+// ================================================================================================
+
+/// Represents base field element in the field.
+///
+/// Internal values are stored in Montgomery representation and are always in the range [0, M).
+/// The backing type is `u32`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct BaseElement(u32);
+
+impl BaseElement {
+    /// Creates a new field element from the provided `value`; the value is converted into
+    /// Montgomery representation. If the value is greater than or equal to the field modulus,
+    /// modular reduction is silently performed.
+    pub const fn new(value: u32) -> BaseElement {
+        // multiply the value with R2 to convert to Montgomery representation; this is OK because
+        // the product of R2 and any 32-bit value is smaller than 2^32 * M
+        BaseElement(mul(value, R2))
+    }
+}
+
+impl FieldElement for BaseElement {
+    type PositiveInteger = u64;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = BaseElement::new(0);
+    const ONE: Self = BaseElement::new(1);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+    const IS_CANONICAL: bool = false;
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[inline]
+    fn double(self) -> Self {
+        Self(add(self.0, self.0))
+    }
+
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        let mut b = self;
+
+        if power == 0 {
+            return Self::ONE;
+        } else if b == Self::ZERO {
+            return Self::ZERO;
+        }
+
+        let mut r = if power & 1 == 1 { b } else { Self::ONE };
+        for i in 1..64 - power.leading_zeros() {
+            b = b.square();
+            if (power >> i) & 1 == 1 {
+                r *= b;
+            }
+        }
+
+        r
+    }
+
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1} for all x != 0; for x = 0, this returns 0
+        self.exp(M as u64 - 2)
+    }
+
+    fn conjugate(&self) -> Self {
+        BaseElement(self.0)
+    }
+
+    // BASE ELEMENT CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // TODO: take endianness into account
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        if (p as usize) % mem::align_of::<u32>() != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+
+    // UTILITIES
+    // --------------------------------------------------------------------------------------------
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // this uses a specialized vector initialization code which requests zero-filled memory
+        // from the OS; unfortunately, this works only for built-in types and we can't use
+        // Self::ZERO here as much less efficient initialization procedure will be invoked.
+        // We also use u32 to make sure the memory is aligned correctly for our element size.
+        let result = vec![0u32; n];
+
+        // translate a zero-filled vector of u32s into a vector of base field elements
+        let mut v = core::mem::ManuallyDrop::new(result);
+        let p = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+}
+
+impl StarkField for BaseElement {
+    /// sage: MODULUS = 2^31 - 2^27 + 1 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True \
+    /// sage: GF(MODULUS).order() \
+    /// 2013265921
+    const MODULUS: Self::PositiveInteger = M as u64;
+    const MODULUS_BITS: u32 = 31;
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 31
+    const GENERATOR: Self = BaseElement::new(31);
+
+    /// sage: is_odd((MODULUS - 1) / 2^27) \
+    /// True
+    const TWO_ADICITY: u32 = 27;
+
+    /// sage: k = (MODULUS - 1) / 2^27 \
+    /// sage: GF(MODULUS).primitive_element()^k \
+    /// 440564289
+    const TWO_ADIC_ROOT_OF_UNITY: Self = BaseElement::new(G);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        M.to_le_bytes().to_vec()
+    }
+
+    #[inline]
+    fn as_int(&self) -> Self::PositiveInteger {
+        // convert from Montgomery representation by multiplying by 1
+        mul(self.0, 1) as u64
+    }
+}
+
+impl Randomizable for BaseElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl Display for BaseElement {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_int())
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl Add for BaseElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(add(self.0, rhs.0))
+    }
+}
+
+impl AddAssign for BaseElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for BaseElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(sub(self.0, rhs.0))
+    }
+}
+
+impl SubAssign for BaseElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BaseElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul(self.0, rhs.0))
+    }
+}
+
+impl MulAssign for BaseElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for BaseElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for BaseElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for BaseElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(sub(0, self.0))
+    }
+}
+
+// QUADRATIC EXTENSION
+// ================================================================================================
+
+/// Defines a quadratic extension of the base field over an irreducible polynomial x<sup>2</sup> -
+/// 11. Thus, an extension element is defined as α + β * φ, where φ is a root of this polynomial,
+/// and α and β are base field elements.
+impl ExtensibleField<2> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+        binomial_mul(a, b, BaseElement::new(11))
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 2], b: Self) -> [Self; 2] {
+        [a[0] * b, a[1] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 2]) -> [Self; 2] {
+        [x[0], -x[1]]
+    }
+}
+
+// CUBIC EXTENSION
+// ================================================================================================
+
+/// Defines a cubic extension of the base field over an irreducible polynomial x<sup>3</sup> - 2.
+/// Thus, an extension element is defined as α + β * φ + γ * φ^2, where φ is a root of this
+/// polynomial, and α, β and γ are base field elements.
+impl ExtensibleField<3> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 3], b: [Self; 3]) -> [Self; 3] {
+        binomial_mul(a, b, BaseElement::new(2))
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 3], b: Self) -> [Self; 3] {
+        [a[0] * b, a[1] * b, a[2] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 3]) -> [Self; 3] {
+        // coefficients are 2^(i * (M - 1) / 3) for i = 0, 1, 2
+        binomial_frobenius(x, [1, 1314723123, 698542797])
+    }
+}
+
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Defines a quartic extension of the base field over an irreducible polynomial x<sup>4</sup> -
+/// 11. Thus, an extension element is defined as a sum of a<sub>i</sub> * φ<sup>i</sup> for i in
+/// [0, 4), where φ is a root of this polynomial, and a<sub>i</sub> are base field elements.
+impl ExtensibleField<4> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+        binomial_mul(a, b, BaseElement::new(11))
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 4], b: Self) -> [Self; 4] {
+        [a[0] * b, a[1] * b, a[2] * b, a[3] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 4]) -> [Self; 4] {
+        // coefficients are 11^(i * (M - 1) / 4) for i = 0, 1, 2, 3
+        binomial_frobenius(x, [1, 1728404513, 2013265920, 284861408])
+    }
+}
+
+// QUINTIC EXTENSION
+// ================================================================================================
+
+/// Defines a quintic extension of the base field over an irreducible polynomial x<sup>5</sup> -
+/// 2. Thus, an extension element is defined as a sum of a<sub>i</sub> * φ<sup>i</sup> for i in
+/// [0, 5), where φ is a root of this polynomial, and a<sub>i</sub> are base field elements.
+impl ExtensibleField<5> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 5], b: [Self; 5]) -> [Self; 5] {
+        binomial_mul(a, b, BaseElement::new(2))
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 5], b: Self) -> [Self; 5] {
+        [a[0] * b, a[1] * b, a[2] * b, a[3] * b, a[4] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 5]) -> [Self; 5] {
+        // coefficients are 2^(i * (M - 1) / 5) for i = 0, 1, 2, 3, 4
+        binomial_frobenius(x, [1, 815036133, 609564788, 1956349769, 645581151])
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl From<u128> for BaseElement {
+    /// Converts a 128-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed.
+    fn from(value: u128) -> Self {
+        BaseElement::new((value % M as u128) as u32)
+    }
+}
+
+impl From<u64> for BaseElement {
+    /// Converts a 64-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed.
+    fn from(value: u64) -> Self {
+        BaseElement::new((value % M as u64) as u32)
+    }
+}
+
+impl From<u32> for BaseElement {
+    /// Converts a 32-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed.
+    fn from(value: u32) -> Self {
+        BaseElement::new(value)
+    }
+}
+
+impl From<u16> for BaseElement {
+    /// Converts a 16-bit value into a field element.
+    fn from(value: u16) -> Self {
+        BaseElement::new(value as u32)
+    }
+}
+
+impl From<u8> for BaseElement {
+    /// Converts an 8-bit value into a field element.
+    fn from(value: u8) -> Self {
+        BaseElement::new(value as u32)
+    }
+}
+
+impl From<[u8; 4]> for BaseElement {
+    /// Converts the value encoded in an array of 4 bytes into a field element. The bytes are
+    /// assumed to encode the element in the canonical representation in little-endian byte order.
+    /// If the value is greater than or equal to the field modulus, modular reduction is silently
+    /// performed.
+    fn from(bytes: [u8; 4]) -> Self {
+        let value = u32::from_le_bytes(bytes);
+        BaseElement::new(value)
+    }
+}
+
+impl TryFrom<&[u8]> for BaseElement {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to encode the element in the canonical
+    /// representation in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let value = bytes
+            .try_into()
+            .map(u32::from_le_bytes)
+            .map_err(|error| DeserializationError::UnknownError(format!("{error}")))?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement::new(value))
+    }
+}
+
+impl AsBytes for BaseElement {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const BaseElement = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for BaseElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // convert from Montgomery representation into canonical representation
+        target.write_bytes(&(self.as_int() as u32).to_le_bytes());
+    }
+}
+
+impl Deserializable for BaseElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = source.read_u32()?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement::new(value))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BaseElement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BaseElement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
+// FINITE FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes (a + b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+fn add(a: u32, b: u32) -> u32 {
+    // since M < 2^31, the sum cannot overflow
+    let z = a + b;
+    if z >= M {
+        z - M
+    } else {
+        z
+    }
+}
+
+/// Computes (a - b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+fn sub(a: u32, b: u32) -> u32 {
+    if a < b {
+        M - b + a
+    } else {
+        a - b
+    }
+}
+
+/// Computes (a * b * 2^-32) reduced by M such that the output is in [0, M) range; the product of
+/// a and b is assumed to be smaller than 2^32 * M.
+#[inline(always)]
+const fn mul(a: u32, b: u32) -> u32 {
+    let z = (a as u64) * (b as u64);
+    let q = (z as u32).wrapping_mul(U);
+    let z = (z + (q as u64) * (M as u64)) >> 32;
+    let z = z as u32;
+    if z >= M {
+        z - M
+    } else {
+        z
+    }
+}
+
+/// Returns a product of `a` and `b` in an extension of the base field defined over an irreducible
+/// polynomial x<sup>N</sup> - `w`.
+#[inline(always)]
+fn binomial_mul<const N: usize>(
+    a: [BaseElement; N],
+    b: [BaseElement; N],
+    w: BaseElement,
+) -> [BaseElement; N] {
+    // compute the product of polynomials a and b, and then reduce the terms of degree N and
+    // higher using x^N = w
+    let mut low = [BaseElement::ZERO; N];
+    let mut high = [BaseElement::ZERO; N];
+    for (i, &a_i) in a.iter().enumerate() {
+        for (j, &b_j) in b.iter().enumerate() {
+            if i + j < N {
+                low[i + j] += a_i * b_j;
+            } else {
+                high[i + j - N] += a_i * b_j;
+            }
+        }
+    }
+    for (low, high) in low.iter_mut().zip(high) {
+        *low += w * high;
+    }
+    low
+}
+
+/// Returns the Frobenius automorphism of `x` in an extension of the base field defined over an
+/// irreducible polynomial x<sup>N</sup> - w; `coefficients` must be equal to
+/// w<sup>i * (M - 1) / N</sup> for all i in [0, N).
+#[inline(always)]
+fn binomial_frobenius<const N: usize>(
+    x: [BaseElement; N],
+    coefficients: [u32; N],
+) -> [BaseElement; N] {
+    let mut result = x;
+    for (value, coefficient) in result.iter_mut().zip(coefficients) {
+        *value *= BaseElement::new(coefficient);
+    }
+    result
+}
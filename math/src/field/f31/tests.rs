@@ -0,0 +1,521 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    AsBytes, BaseElement, DeserializationError, ExtensibleField, FieldElement, Serializable,
+    StarkField,
+};
+use crate::field::{CubeExtension, ExtensionOf, QuadExtension};
+use core::convert::TryFrom;
+use num_bigint::BigUint;
+use proptest::prelude::*;
+use rand_utils::rand_value;
+
+// MANUAL TESTS
+// ================================================================================================
+
+#[test]
+fn add() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r + BaseElement::ZERO);
+
+    // test addition within bounds
+    assert_eq!(
+        BaseElement::from(5u8),
+        BaseElement::from(2u8) + BaseElement::from(3u8)
+    );
+
+    // test overflow
+    let t = BaseElement::from(BaseElement::MODULUS - 1);
+    assert_eq!(BaseElement::ZERO, t + BaseElement::ONE);
+    assert_eq!(BaseElement::ONE, t + BaseElement::from(2u8));
+}
+
+#[test]
+fn sub() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(r, r - BaseElement::ZERO);
+
+    // test subtraction within bounds
+    assert_eq!(
+        BaseElement::from(2u8),
+        BaseElement::from(5u8) - BaseElement::from(3u8)
+    );
+
+    // test underflow
+    let expected = BaseElement::from(BaseElement::MODULUS - 2);
+    assert_eq!(expected, BaseElement::from(3u8) - BaseElement::from(5u8));
+}
+
+#[test]
+fn mul() {
+    // identity
+    let r: BaseElement = rand_value();
+    assert_eq!(BaseElement::ZERO, r * BaseElement::ZERO);
+    assert_eq!(r, r * BaseElement::ONE);
+
+    // test multiplication within bounds
+    assert_eq!(
+        BaseElement::from(15u8),
+        BaseElement::from(5u8) * BaseElement::from(3u8)
+    );
+
+    // test overflow
+    let m = BaseElement::MODULUS;
+    let t = BaseElement::from(m - 1);
+    assert_eq!(BaseElement::ONE, t * t);
+    assert_eq!(BaseElement::from(m - 2), t * BaseElement::from(2u8));
+    assert_eq!(BaseElement::from(m - 4), t * BaseElement::from(4u8));
+
+    let t = (m + 1) / 2;
+    assert_eq!(
+        BaseElement::ONE,
+        BaseElement::from(t) * BaseElement::from(2u8)
+    );
+}
+
+#[test]
+fn exp() {
+    let a = BaseElement::ZERO;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(1), BaseElement::ZERO);
+
+    let a = BaseElement::ONE;
+    assert_eq!(a.exp(0), BaseElement::ONE);
+    assert_eq!(a.exp(1), BaseElement::ONE);
+    assert_eq!(a.exp(3), BaseElement::ONE);
+
+    let a: BaseElement = rand_value();
+    assert_eq!(a.exp(3), a * a * a);
+}
+
+#[test]
+fn inv() {
+    // identity
+    assert_eq!(BaseElement::ONE, BaseElement::inv(BaseElement::ONE));
+    assert_eq!(BaseElement::ZERO, BaseElement::inv(BaseElement::ZERO));
+}
+
+#[test]
+fn element_as_int() {
+    let v = u32::MAX;
+    let e = BaseElement::new(v);
+    assert_eq!((v % super::M) as u64, e.as_int());
+}
+
+#[test]
+fn equals() {
+    let a = BaseElement::ONE;
+    let b = BaseElement::new(super::M - 1) * BaseElement::new(super::M - 1);
+
+    // elements are equal
+    assert_eq!(a, b);
+    assert_eq!(a.as_int(), b.as_int());
+    assert_eq!(a.to_bytes(), b.to_bytes());
+
+    // and so is their internal representation
+    assert_eq!(a.0, b.0);
+    assert_eq!(a.as_bytes(), b.as_bytes());
+}
+
+// QUADRATIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quad_mul() {
+    // identity
+    let r: QuadExtension<BaseElement> = rand_value();
+    assert_eq!(
+        <QuadExtension<BaseElement>>::ZERO,
+        r * <QuadExtension<BaseElement>>::ZERO
+    );
+    assert_eq!(r, r * <QuadExtension<BaseElement>>::ONE);
+
+    // test multiplication within bounds: (3 + 2x) * (5 + 4x) = 15 + 22x + 8x^2, x^2 = 11
+    let a = <QuadExtension<BaseElement>>::new(BaseElement::new(3), BaseElement::new(2));
+    let b = <QuadExtension<BaseElement>>::new(BaseElement::new(5), BaseElement::new(4));
+    let expected = <QuadExtension<BaseElement>>::new(BaseElement::new(103), BaseElement::new(22));
+    assert_eq!(expected, a * b);
+}
+
+#[test]
+fn quad_mul_base() {
+    let a = <QuadExtension<BaseElement>>::new(rand_value(), rand_value());
+    let b0 = rand_value();
+    let b = <QuadExtension<BaseElement>>::new(b0, BaseElement::ZERO);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+// CUBIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn cube_mul() {
+    // identity
+    let r: CubeExtension<BaseElement> = rand_value();
+    assert_eq!(
+        <CubeExtension<BaseElement>>::ZERO,
+        r * <CubeExtension<BaseElement>>::ZERO
+    );
+    assert_eq!(r, r * <CubeExtension<BaseElement>>::ONE);
+
+    // test multiplication within bounds: x^3 = 2
+    let a = <CubeExtension<BaseElement>>::new(
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+    );
+    let b = <CubeExtension<BaseElement>>::new(
+        BaseElement::new(4),
+        BaseElement::new(5),
+        BaseElement::new(6),
+    );
+    let expected = <CubeExtension<BaseElement>>::new(
+        BaseElement::new(58),
+        BaseElement::new(49),
+        BaseElement::new(28),
+    );
+    assert_eq!(expected, a * b);
+}
+
+#[test]
+fn cube_mul_base() {
+    let a = <CubeExtension<BaseElement>>::new(rand_value(), rand_value(), rand_value());
+    let b0 = rand_value();
+    let b = <CubeExtension<BaseElement>>::new(b0, BaseElement::ZERO, BaseElement::ZERO);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+// QUARTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quartic_mul() {
+    let one = [
+        BaseElement::ONE,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+    ];
+    let r: [BaseElement; 4] = rand_value_array();
+    assert_eq!(r, <BaseElement as ExtensibleField<4>>::mul(r, one));
+
+    // x^3 * x = x^4 = 11
+    let x = [
+        BaseElement::ZERO,
+        BaseElement::ONE,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+    ];
+    let x3 = [
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ONE,
+    ];
+    let expected = [
+        BaseElement::new(11),
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+    ];
+    assert_eq!(expected, <BaseElement as ExtensibleField<4>>::mul(x3, x));
+}
+
+#[test]
+fn quartic_frobenius() {
+    let a: [BaseElement; 4] = rand_value_array();
+    assert_eq!(
+        ext_exp(a, super::M as u64),
+        <BaseElement as ExtensibleField<4>>::frobenius(a)
+    );
+}
+
+// QUINTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn quintic_mul() {
+    let mut one = [BaseElement::ZERO; 5];
+    one[0] = BaseElement::ONE;
+    let r: [BaseElement; 5] = rand_value_array();
+    assert_eq!(r, <BaseElement as ExtensibleField<5>>::mul(r, one));
+
+    // x^4 * x^2 = x^6 = 2x
+    let mut x2 = [BaseElement::ZERO; 5];
+    x2[2] = BaseElement::ONE;
+    let mut x4 = [BaseElement::ZERO; 5];
+    x4[4] = BaseElement::ONE;
+    let mut expected = [BaseElement::ZERO; 5];
+    expected[1] = BaseElement::new(2);
+    assert_eq!(expected, <BaseElement as ExtensibleField<5>>::mul(x4, x2));
+}
+
+#[test]
+fn quintic_frobenius() {
+    let a: [BaseElement; 5] = rand_value_array();
+    assert_eq!(
+        ext_exp(a, super::M as u64),
+        <BaseElement as ExtensibleField<5>>::frobenius(a)
+    );
+}
+
+// ROOTS OF UNITY
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn get_root_of_unity() {
+    let root_27 = BaseElement::get_root_of_unity(27);
+    assert_eq!(BaseElement::TWO_ADIC_ROOT_OF_UNITY, root_27);
+    assert_eq!(BaseElement::ONE, root_27.exp(1u64 << 27));
+
+    let root_26 = BaseElement::get_root_of_unity(26);
+    let expected = root_27.exp(2);
+    assert_eq!(expected, root_26);
+    assert_eq!(BaseElement::ONE, root_26.exp(1u64 << 26));
+}
+
+// SERIALIZATION AND DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn from_u128() {
+    let v = u128::MAX;
+    let e = BaseElement::from(v);
+    assert_eq!((v % super::M as u128) as u64, e.as_int());
+}
+
+#[test]
+fn try_from_slice() {
+    let bytes = vec![1, 0, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_ok());
+    assert_eq!(1, result.unwrap().as_int());
+
+    let bytes = vec![1, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+
+    let bytes = vec![1, 0, 0, 0, 0];
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+
+    let bytes = super::M.to_le_bytes().to_vec();
+    let result = BaseElement::try_from(bytes.as_slice());
+    assert!(result.is_err());
+}
+
+#[test]
+fn elements_as_bytes() {
+    let source = vec![
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+    ];
+
+    let mut expected = vec![];
+    expected.extend_from_slice(&source[0].0.to_le_bytes());
+    expected.extend_from_slice(&source[1].0.to_le_bytes());
+    expected.extend_from_slice(&source[2].0.to_le_bytes());
+    expected.extend_from_slice(&source[3].0.to_le_bytes());
+
+    assert_eq!(expected, BaseElement::elements_as_bytes(&source));
+}
+
+#[test]
+fn bytes_as_elements() {
+    let elements = vec![
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+        BaseElement::new(4),
+    ];
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&elements[0].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[1].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[2].0.to_le_bytes());
+    bytes.extend_from_slice(&elements[3].0.to_le_bytes());
+    bytes.extend_from_slice(&BaseElement::new(5).0.to_le_bytes());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..16]) };
+    assert!(result.is_ok());
+    assert_eq!(elements, result.unwrap());
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[..17]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+    let result = unsafe { BaseElement::bytes_as_elements(&bytes[1..17]) };
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
+// INITIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn zeroed_vector() {
+    let result = BaseElement::zeroed_vector(4);
+    assert_eq!(4, result.len());
+    for element in result.into_iter() {
+        assert_eq!(BaseElement::ZERO, element);
+    }
+}
+
+// RANDOMIZED TESTS
+// ================================================================================================
+
+proptest! {
+
+    #[test]
+    fn add_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let m = super::M as u64;
+        let v1 = BaseElement::from(a);
+        let v2 = BaseElement::from(b);
+        let result = v1 + v2;
+
+        let expected = (a % m + b % m) % m;
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn sub_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let m = super::M as u64;
+        let v1 = BaseElement::from(a);
+        let v2 = BaseElement::from(b);
+        let result = v1 - v2;
+
+        let a = a % m;
+        let b = b % m;
+        let expected = if a < b { m - b + a } else { a - b };
+
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn mul_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let v1 = BaseElement::from(a);
+        let v2 = BaseElement::from(b);
+        let result = v1 * v2;
+
+        let expected = (((a as u128) * (b as u128)) % super::M as u128) as u64;
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn exp_proptest(a in any::<u64>(), b in any::<u64>()) {
+        let result = BaseElement::from(a).exp(b);
+
+        let b = BigUint::from(b);
+        let m = BigUint::from(super::M);
+        let expected = BigUint::from(a).modpow(&b, &m).to_u64_digits().first().copied().unwrap_or(0);
+        prop_assert_eq!(expected, result.as_int());
+    }
+
+    #[test]
+    fn inv_proptest(a in any::<u64>()) {
+        let a = BaseElement::from(a);
+        let b = a.inv();
+
+        let expected = if a == BaseElement::ZERO { BaseElement::ZERO } else { BaseElement::ONE };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    #[test]
+    fn element_as_int_proptest(a in any::<u32>()) {
+        let e = BaseElement::new(a);
+        prop_assert_eq!((a % super::M) as u64, e.as_int());
+    }
+
+    #[test]
+    fn from_u128_proptest(v in any::<u128>()) {
+        let e = BaseElement::from(v);
+        assert_eq!((v % super::M as u128) as u64, e.as_int());
+    }
+
+    // QUADRATIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn quad_mul_inv_proptest(a0 in any::<u64>(), a1 in any::<u64>()) {
+        let a = QuadExtension::<BaseElement>::new(BaseElement::from(a0), BaseElement::from(a1));
+        let b = a.inv();
+
+        let expected = if a == QuadExtension::<BaseElement>::ZERO {
+            QuadExtension::<BaseElement>::ZERO
+        } else {
+            QuadExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    // CUBIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn cube_mul_inv_proptest(a0 in any::<u64>(), a1 in any::<u64>(), a2 in any::<u64>()) {
+        let a = CubeExtension::<BaseElement>::new(BaseElement::from(a0), BaseElement::from(a1), BaseElement::from(a2));
+        let b = a.inv();
+
+        let expected = if a == CubeExtension::<BaseElement>::ZERO {
+            CubeExtension::<BaseElement>::ZERO
+        } else {
+            CubeExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
+
+    // QUARTIC AND QUINTIC EXTENSIONS
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn quartic_mul_commutative_proptest(a in any::<[u32; 4]>(), b in any::<[u32; 4]>()) {
+        let a = a.map(BaseElement::new);
+        let b = b.map(BaseElement::new);
+        prop_assert_eq!(
+            <BaseElement as ExtensibleField<4>>::mul(a, b),
+            <BaseElement as ExtensibleField<4>>::mul(b, a)
+        );
+    }
+
+    #[test]
+    fn quintic_mul_commutative_proptest(a in any::<[u32; 5]>(), b in any::<[u32; 5]>()) {
+        let a = a.map(BaseElement::new);
+        let b = b.map(BaseElement::new);
+        prop_assert_eq!(
+            <BaseElement as ExtensibleField<5>>::mul(a, b),
+            <BaseElement as ExtensibleField<5>>::mul(b, a)
+        );
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn rand_value_array<const N: usize>() -> [BaseElement; N] {
+    [BaseElement::ZERO; N].map(|_| rand_value())
+}
+
+/// Computes a^power in the extension of degree N using square-and-multiply.
+fn ext_exp<const N: usize>(a: [BaseElement; N], power: u64) -> [BaseElement; N]
+where
+    BaseElement: ExtensibleField<N>,
+{
+    let mut result = [BaseElement::ZERO; N];
+    result[0] = BaseElement::ONE;
+    let mut base = a;
+    let mut power = power;
+    while power > 0 {
+        if power & 1 == 1 {
+            result = <BaseElement as ExtensibleField<N>>::mul(result, base);
+        }
+        base = <BaseElement as ExtensibleField<N>>::square(base);
+        power >>= 1;
+    }
+    result
+}
@@ -27,6 +27,11 @@ use utils::{
     DeserializationError, Randomizable, Serializable,
 };
 
+#[cfg(feature = "simd")]
+mod packed;
+#[cfg(feature = "simd")]
+pub use packed::PackedBaseElement;
+
 #[cfg(test)]
 mod tests;
 
@@ -232,6 +237,17 @@ impl FieldElement for BaseElement {
         let cap = v.capacity();
         unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
     }
+
+    #[cfg(feature = "simd")]
+    fn mul_slice_in_place(values: &mut [Self], k: Self) {
+        packed::mul_slice_in_place(values, k)
+    }
+
+    #[cfg(feature = "simd")]
+    fn butterfly_twiddle_slices(a: &mut [Self], b: &mut [Self], twiddle: Self) {
+        assert_eq!(a.len(), b.len(), "slices must have the same length");
+        packed::butterfly_twiddle_slices(a, b, twiddle)
+    }
 }
 
 impl StarkField for BaseElement {
@@ -505,6 +521,31 @@ impl ExtensibleField<3> for BaseElement {
     }
 }
 
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Quartic extension for this field is not implemented as cubic extension already provides
+/// sufficient security level.
+impl ExtensibleField<4> for BaseElement {
+    fn mul(_a: [Self; 4], _b: [Self; 4]) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 4], _b: Self) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 4]) -> [Self; 4] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
 // TYPE CONVERSIONS
 // ================================================================================================
 
@@ -557,7 +598,7 @@ impl From<[u8; 8]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -621,6 +662,20 @@ impl Deserializable for BaseElement {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for BaseElement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BaseElement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
 /// Squares the base N number of times and multiplies the result by the tail value.
 #[inline(always)]
 fn exp_acc<const N: usize>(base: BaseElement, tail: BaseElement) -> BaseElement {
@@ -0,0 +1,500 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Packed (SIMD) arithmetic for the 64-bit field.
+//!
+//! The backend is selected at compile time based on the enabled target features:
+//! * AVX-512 (`avx512f`) - 8 elements per packed value.
+//! * AVX2 (`avx2`) - 4 elements per packed value.
+//! * NEON (`neon` on `aarch64`) - 2 elements per packed value.
+//!
+//! If none of these features are enabled, a portable implementation which processes 4 elements
+//! per packed value one-by-one is used. To select a SIMD backend, the crate must be compiled
+//! with the appropriate target features enabled (e.g., via `RUSTFLAGS="-C target-cpu=native"`).
+//!
+//! All backends compute exactly the same internal representation of elements as the scalar
+//! implementation of [BaseElement] arithmetic.
+
+use super::BaseElement;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of field elements in a single packed value.
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+const WIDTH: usize = 8;
+
+/// Number of field elements in a single packed value.
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f")
+))]
+const WIDTH: usize = 4;
+
+/// Number of field elements in a single packed value.
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+const WIDTH: usize = 2;
+
+/// Number of field elements in a single packed value.
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "avx2"),
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+const WIDTH: usize = 4;
+
+// PACKED FIELD ELEMENT
+// ================================================================================================
+
+/// Represents a vector of [BaseElement]s on which arithmetic operations are performed lane-wise.
+///
+/// The number of elements in the vector depends on the SIMD instruction set available on the
+/// target (see [PackedBaseElement::WIDTH]). Elements are kept in the same Montgomery
+/// representation as [BaseElement], and thus, slices of base elements can be re-interpreted as
+/// slices of packed elements without any conversion.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PackedBaseElement([BaseElement; WIDTH]);
+
+impl PackedBaseElement {
+    /// Number of base field elements in a single packed element.
+    pub const WIDTH: usize = WIDTH;
+
+    /// Returns a new packed element instantiated from the provided base elements.
+    pub const fn new(elements: [BaseElement; WIDTH]) -> Self {
+        Self(elements)
+    }
+
+    /// Returns a packed element with all lanes set to the provided `value`.
+    pub const fn broadcast(value: BaseElement) -> Self {
+        Self([value; WIDTH])
+    }
+
+    /// Returns base elements comprising this packed element.
+    pub const fn to_elements(self) -> [BaseElement; WIDTH] {
+        self.0
+    }
+
+    /// Splits the provided slice of base elements into a slice of packed elements and a slice
+    /// of the remaining base elements (fewer than [PackedBaseElement::WIDTH]) which do not fill
+    /// a packed element.
+    pub fn pack_slice(elements: &[BaseElement]) -> (&[Self], &[BaseElement]) {
+        let num_packed = elements.len() / WIDTH;
+        let (packed, remainder) = elements.split_at(num_packed * WIDTH);
+        // this is safe because PackedBaseElement is a transparent wrapper around an array of
+        // base elements, and thus, has the same alignment as a base element
+        let packed =
+            unsafe { core::slice::from_raw_parts(packed.as_ptr() as *const Self, num_packed) };
+        (packed, remainder)
+    }
+
+    /// Splits the provided mutable slice of base elements into a slice of packed elements and a
+    /// slice of the remaining base elements (fewer than [PackedBaseElement::WIDTH]) which do not
+    /// fill a packed element.
+    pub fn pack_slice_mut(elements: &mut [BaseElement]) -> (&mut [Self], &mut [BaseElement]) {
+        let num_packed = elements.len() / WIDTH;
+        let (packed, remainder) = elements.split_at_mut(num_packed * WIDTH);
+        // this is safe for the same reason as in pack_slice() above
+        let packed = unsafe {
+            core::slice::from_raw_parts_mut(packed.as_mut_ptr() as *mut Self, num_packed)
+        };
+        (packed, remainder)
+    }
+
+    /// Returns this element multiplied by itself.
+    #[inline(always)]
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    #[inline(always)]
+    fn to_lanes(self) -> [u64; WIDTH] {
+        self.0.map(|element| element.inner())
+    }
+
+    #[inline(always)]
+    fn from_lanes(lanes: [u64; WIDTH]) -> Self {
+        Self(lanes.map(BaseElement::from_mont))
+    }
+}
+
+// OVERLOADED OPERATORS
+// ------------------------------------------------------------------------------------------------
+
+impl Add for PackedBaseElement {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_lanes(backend::add(self.to_lanes(), rhs.to_lanes()))
+    }
+}
+
+impl AddAssign for PackedBaseElement {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for PackedBaseElement {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_lanes(backend::sub(self.to_lanes(), rhs.to_lanes()))
+    }
+}
+
+impl SubAssign for PackedBaseElement {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs
+    }
+}
+
+impl Mul for PackedBaseElement {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_lanes(backend::mul(self.to_lanes(), rhs.to_lanes()))
+    }
+}
+
+impl MulAssign for PackedBaseElement {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Neg for PackedBaseElement {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::default() - self
+    }
+}
+
+impl From<BaseElement> for PackedBaseElement {
+    fn from(value: BaseElement) -> Self {
+        Self::broadcast(value)
+    }
+}
+
+// SLICE OPERATIONS
+// ================================================================================================
+
+/// Multiplies all elements in `values` by `k`.
+pub(super) fn mul_slice_in_place(values: &mut [BaseElement], k: BaseElement) {
+    let (packed, remainder) = PackedBaseElement::pack_slice_mut(values);
+    let packed_k = PackedBaseElement::broadcast(k);
+    for value in packed.iter_mut() {
+        *value *= packed_k;
+    }
+    for value in remainder.iter_mut() {
+        *value *= k;
+    }
+}
+
+/// Applies a butterfly with a twiddle factor to all pairs of elements in `a` and `b`.
+pub(super) fn butterfly_twiddle_slices(
+    a: &mut [BaseElement],
+    b: &mut [BaseElement],
+    twiddle: BaseElement,
+) {
+    let (a_packed, a_remainder) = PackedBaseElement::pack_slice_mut(a);
+    let (b_packed, b_remainder) = PackedBaseElement::pack_slice_mut(b);
+    let packed_twiddle = PackedBaseElement::broadcast(twiddle);
+    for (a, b) in a_packed.iter_mut().zip(b_packed.iter_mut()) {
+        let temp = *a;
+        *b *= packed_twiddle;
+        *a = temp + *b;
+        *b = temp - *b;
+    }
+    for (a, b) in a_remainder.iter_mut().zip(b_remainder.iter_mut()) {
+        let temp = *a;
+        *b *= twiddle;
+        *a = temp + *b;
+        *b = temp - *b;
+    }
+}
+
+// AVX-512 BACKEND
+// ================================================================================================
+
+// AVX-512 intrinsics are available only in Rust 1.89 or newer; thus, this backend requires a more
+// recent compiler than the rest of the crate.
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[allow(unused_unsafe, clippy::incompatible_msrv)]
+mod backend {
+    use super::{super::M, WIDTH};
+    use core::arch::x86_64::*;
+    use core::mem::transmute;
+
+    #[inline(always)]
+    pub fn add(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: __m512i = transmute(a);
+            let b: __m512i = transmute(b);
+            // compute a + b = a - (M - b); if this underflows, subtract 2^32 - 1
+            let m_minus_b = _mm512_sub_epi64(_mm512_set1_epi64(M as i64), b);
+            let x = _mm512_sub_epi64(a, m_minus_b);
+            let underflow = _mm512_cmplt_epu64_mask(a, m_minus_b);
+            transmute(_mm512_mask_sub_epi64(x, underflow, x, epsilon()))
+        }
+    }
+
+    #[inline(always)]
+    pub fn sub(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: __m512i = transmute(a);
+            let b: __m512i = transmute(b);
+            let x = _mm512_sub_epi64(a, b);
+            let underflow = _mm512_cmplt_epu64_mask(a, b);
+            transmute(_mm512_mask_sub_epi64(x, underflow, x, epsilon()))
+        }
+    }
+
+    #[inline(always)]
+    pub fn mul(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: __m512i = transmute(a);
+            let b: __m512i = transmute(b);
+
+            // compute the 128-bit product from four 32-bit partial products
+            let a_hi = _mm512_srli_epi64::<32>(a);
+            let b_hi = _mm512_srli_epi64::<32>(b);
+            let ll = _mm512_mul_epu32(a, b);
+            let lh = _mm512_mul_epu32(a, b_hi);
+            let hl = _mm512_mul_epu32(a_hi, b);
+            let hh = _mm512_mul_epu32(a_hi, b_hi);
+
+            let lo_mask = _mm512_set1_epi64(0xFFFFFFFF);
+            let t = _mm512_add_epi64(hl, _mm512_srli_epi64::<32>(ll));
+            let u = _mm512_add_epi64(lh, _mm512_and_si512(t, lo_mask));
+            let x_lo = _mm512_or_si512(_mm512_slli_epi64::<32>(u), _mm512_and_si512(ll, lo_mask));
+            let x_hi = _mm512_add_epi64(
+                _mm512_add_epi64(hh, _mm512_srli_epi64::<32>(t)),
+                _mm512_srli_epi64::<32>(u),
+            );
+
+            // Montgomery reduction; this mirrors mont_red_cst() function
+            let s = _mm512_add_epi64(x_lo, _mm512_slli_epi64::<32>(x_lo));
+            let overflow = _mm512_cmplt_epu64_mask(s, x_lo);
+            let s = _mm512_sub_epi64(s, _mm512_srli_epi64::<32>(s));
+            let s = _mm512_mask_sub_epi64(s, overflow, s, _mm512_set1_epi64(1));
+            let r = _mm512_sub_epi64(x_hi, s);
+            let underflow = _mm512_cmplt_epu64_mask(x_hi, s);
+            transmute(_mm512_mask_sub_epi64(r, underflow, r, epsilon()))
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn epsilon() -> __m512i {
+        _mm512_set1_epi64(0xFFFFFFFF)
+    }
+}
+
+// AVX2 BACKEND
+// ================================================================================================
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f")
+))]
+#[allow(unused_unsafe)]
+mod backend {
+    use super::{super::M, WIDTH};
+    use core::arch::x86_64::*;
+    use core::mem::transmute;
+
+    #[inline(always)]
+    pub fn add(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: __m256i = transmute(a);
+            let b: __m256i = transmute(b);
+            // compute a + b = a - (M - b); if this underflows, subtract 2^32 - 1
+            let m_minus_b = _mm256_sub_epi64(_mm256_set1_epi64x(M as i64), b);
+            let x = _mm256_sub_epi64(a, m_minus_b);
+            let underflow = lt(a, m_minus_b);
+            transmute(_mm256_sub_epi64(x, _mm256_and_si256(underflow, epsilon())))
+        }
+    }
+
+    #[inline(always)]
+    pub fn sub(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: __m256i = transmute(a);
+            let b: __m256i = transmute(b);
+            let x = _mm256_sub_epi64(a, b);
+            let underflow = lt(a, b);
+            transmute(_mm256_sub_epi64(x, _mm256_and_si256(underflow, epsilon())))
+        }
+    }
+
+    #[inline(always)]
+    pub fn mul(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: __m256i = transmute(a);
+            let b: __m256i = transmute(b);
+
+            // compute the 128-bit product from four 32-bit partial products
+            let a_hi = _mm256_srli_epi64::<32>(a);
+            let b_hi = _mm256_srli_epi64::<32>(b);
+            let ll = _mm256_mul_epu32(a, b);
+            let lh = _mm256_mul_epu32(a, b_hi);
+            let hl = _mm256_mul_epu32(a_hi, b);
+            let hh = _mm256_mul_epu32(a_hi, b_hi);
+
+            let t = _mm256_add_epi64(hl, _mm256_srli_epi64::<32>(ll));
+            let u = _mm256_add_epi64(lh, _mm256_and_si256(t, epsilon()));
+            let x_lo = _mm256_or_si256(_mm256_slli_epi64::<32>(u), _mm256_and_si256(ll, epsilon()));
+            let x_hi = _mm256_add_epi64(
+                _mm256_add_epi64(hh, _mm256_srli_epi64::<32>(t)),
+                _mm256_srli_epi64::<32>(u),
+            );
+
+            // Montgomery reduction; this mirrors mont_red_cst() function. the overflow mask is
+            // all ones (i.e., -1) in the lanes which overflowed, and thus, adding it subtracts 1
+            let s = _mm256_add_epi64(x_lo, _mm256_slli_epi64::<32>(x_lo));
+            let overflow = lt(s, x_lo);
+            let s = _mm256_sub_epi64(s, _mm256_srli_epi64::<32>(s));
+            let s = _mm256_add_epi64(s, overflow);
+            let r = _mm256_sub_epi64(x_hi, s);
+            let underflow = lt(x_hi, s);
+            transmute(_mm256_sub_epi64(r, _mm256_and_si256(underflow, epsilon())))
+        }
+    }
+
+    /// Returns a mask with all bits set in the lanes where `a` < `b` (as unsigned integers).
+    #[inline(always)]
+    unsafe fn lt(a: __m256i, b: __m256i) -> __m256i {
+        // AVX2 supports only signed comparisons; flipping the sign bits of both operands maps
+        // unsigned comparison onto signed comparison
+        let sign = _mm256_set1_epi64x(i64::MIN);
+        _mm256_cmpgt_epi64(_mm256_xor_si256(b, sign), _mm256_xor_si256(a, sign))
+    }
+
+    #[inline(always)]
+    unsafe fn epsilon() -> __m256i {
+        _mm256_set1_epi64x(0xFFFFFFFF)
+    }
+}
+
+// NEON BACKEND
+// ================================================================================================
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[allow(unused_unsafe)]
+mod backend {
+    use super::{super::M, WIDTH};
+    use core::arch::aarch64::*;
+    use core::mem::transmute;
+
+    #[inline(always)]
+    pub fn add(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: uint64x2_t = transmute(a);
+            let b: uint64x2_t = transmute(b);
+            // compute a + b = a - (M - b); if this underflows, subtract 2^32 - 1
+            let m_minus_b = vsubq_u64(vdupq_n_u64(M), b);
+            let x = vsubq_u64(a, m_minus_b);
+            let underflow = vcltq_u64(a, m_minus_b);
+            transmute(vsubq_u64(x, vandq_u64(underflow, epsilon())))
+        }
+    }
+
+    #[inline(always)]
+    pub fn sub(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: uint64x2_t = transmute(a);
+            let b: uint64x2_t = transmute(b);
+            let x = vsubq_u64(a, b);
+            let underflow = vcltq_u64(a, b);
+            transmute(vsubq_u64(x, vandq_u64(underflow, epsilon())))
+        }
+    }
+
+    #[inline(always)]
+    pub fn mul(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        unsafe {
+            let a: uint64x2_t = transmute(a);
+            let b: uint64x2_t = transmute(b);
+
+            // compute the 128-bit product from four 32-bit partial products
+            let a_lo = vmovn_u64(a);
+            let a_hi = vshrn_n_u64::<32>(a);
+            let b_lo = vmovn_u64(b);
+            let b_hi = vshrn_n_u64::<32>(b);
+            let ll = vmull_u32(a_lo, b_lo);
+            let lh = vmull_u32(a_lo, b_hi);
+            let hl = vmull_u32(a_hi, b_lo);
+            let hh = vmull_u32(a_hi, b_hi);
+
+            let t = vaddq_u64(hl, vshrq_n_u64::<32>(ll));
+            let u = vaddq_u64(lh, vandq_u64(t, epsilon()));
+            let x_lo = vorrq_u64(vshlq_n_u64::<32>(u), vandq_u64(ll, epsilon()));
+            let x_hi = vaddq_u64(vaddq_u64(hh, vshrq_n_u64::<32>(t)), vshrq_n_u64::<32>(u));
+
+            // Montgomery reduction; this mirrors mont_red_cst() function. the overflow mask is
+            // all ones (i.e., -1) in the lanes which overflowed, and thus, adding it subtracts 1
+            let s = vaddq_u64(x_lo, vshlq_n_u64::<32>(x_lo));
+            let overflow = vcltq_u64(s, x_lo);
+            let s = vsubq_u64(s, vshrq_n_u64::<32>(s));
+            let s = vaddq_u64(s, overflow);
+            let r = vsubq_u64(x_hi, s);
+            let underflow = vcltq_u64(x_hi, s);
+            transmute(vsubq_u64(r, vandq_u64(underflow, epsilon())))
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn epsilon() -> uint64x2_t {
+        vdupq_n_u64(0xFFFFFFFF)
+    }
+}
+
+// PORTABLE BACKEND
+// ================================================================================================
+
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "avx2"),
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+mod backend {
+    use super::{BaseElement, WIDTH};
+
+    #[inline(always)]
+    pub fn add(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        apply(a, b, |a, b| a + b)
+    }
+
+    #[inline(always)]
+    pub fn sub(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        apply(a, b, |a, b| a - b)
+    }
+
+    #[inline(always)]
+    pub fn mul(a: [u64; WIDTH], b: [u64; WIDTH]) -> [u64; WIDTH] {
+        apply(a, b, |a, b| a * b)
+    }
+
+    #[inline(always)]
+    fn apply<F>(a: [u64; WIDTH], b: [u64; WIDTH], op: F) -> [u64; WIDTH]
+    where
+        F: Fn(BaseElement, BaseElement) -> BaseElement,
+    {
+        let mut result = [0; WIDTH];
+        for (r, (&a, &b)) in result.iter_mut().zip(a.iter().zip(b.iter())) {
+            *r = op(BaseElement::from_mont(a), BaseElement::from_mont(b)).inner();
+        }
+        result
+    }
+}
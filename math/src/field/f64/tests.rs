@@ -8,7 +8,10 @@ use crate::field::{CubeExtension, ExtensionOf, QuadExtension};
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
-use rand_utils::rand_value;
+use rand_utils::{rand_value, rand_vector};
+
+#[cfg(feature = "simd")]
+use super::PackedBaseElement;
 
 // MANUAL TESTS
 // ================================================================================================
@@ -120,6 +123,19 @@ fn inv() {
     assert_eq!(BaseElement::ZERO, BaseElement::inv(BaseElement::ZERO));
 }
 
+#[test]
+fn batch_inverse() {
+    let mut values: Vec<BaseElement> = rand_vector(2048);
+    values[0] = BaseElement::ZERO;
+    values[1000] = BaseElement::ZERO;
+    let expected = values.iter().map(|v| v.inv()).collect::<Vec<_>>();
+
+    assert_eq!(expected, BaseElement::batch_inverse(&values));
+
+    BaseElement::batch_inverse_in_place(&mut values);
+    assert_eq!(expected, values);
+}
+
 #[test]
 fn element_as_int() {
     let v = u64::MAX;
@@ -391,6 +407,46 @@ fn cube_mul_base() {
     assert_eq!(expected, a.mul_base(b0));
 }
 
+// SLICE OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn mul_slice_in_place() {
+    let k: BaseElement = rand_value();
+    for n in [1, 7, 64, 67] {
+        let values: Vec<BaseElement> = rand_vector(n);
+        let expected = values.iter().map(|&v| v * k).collect::<Vec<_>>();
+
+        let mut actual = values;
+        BaseElement::mul_slice_in_place(&mut actual, k);
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn butterfly_twiddle_slices() {
+    let twiddle: BaseElement = rand_value();
+    for n in [1, 7, 64, 67] {
+        let a: Vec<BaseElement> = rand_vector(n);
+        let b: Vec<BaseElement> = rand_vector(n);
+        let expected_a = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&a, &b)| a + b * twiddle)
+            .collect::<Vec<_>>();
+        let expected_b = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&a, &b)| a - b * twiddle)
+            .collect::<Vec<_>>();
+
+        let (mut actual_a, mut actual_b) = (a, b);
+        BaseElement::butterfly_twiddle_slices(&mut actual_a, &mut actual_b, twiddle);
+        assert_eq!(expected_a, actual_a);
+        assert_eq!(expected_b, actual_b);
+    }
+}
+
 // RANDOMIZED TESTS
 // ================================================================================================
 
@@ -533,3 +589,53 @@ proptest! {
         prop_assert_eq!(expected, a.square());
     }
 }
+
+// PACKED ELEMENTS
+// ================================================================================================
+
+#[cfg(feature = "simd")]
+proptest! {
+    #[test]
+    fn packed_ops_proptest(
+        a in prop::collection::vec(any::<u64>(), PackedBaseElement::WIDTH),
+        b in prop::collection::vec(any::<u64>(), PackedBaseElement::WIDTH),
+    ) {
+        let a = a.into_iter().map(BaseElement::from_mont).collect::<Vec<_>>();
+        let b = b.into_iter().map(BaseElement::from_mont).collect::<Vec<_>>();
+        let packed_a = PackedBaseElement::new(a.clone().try_into().unwrap());
+        let packed_b = PackedBaseElement::new(b.clone().try_into().unwrap());
+
+        // packed operations must produce the same internal representation as scalar operations
+        let check = |packed: PackedBaseElement, op: fn(BaseElement, BaseElement) -> BaseElement| {
+            let expected = a.iter().zip(b.iter()).map(|(&a, &b)| op(a, b).inner());
+            let actual = packed.to_elements().map(|e| e.inner());
+            expected.eq(actual)
+        };
+        prop_assert!(check(packed_a + packed_b, |a, b| a + b));
+        prop_assert!(check(packed_a - packed_b, |a, b| a - b));
+        prop_assert!(check(packed_a * packed_b, |a, b| a * b));
+        prop_assert!(check(-packed_b, |_, b| -b));
+    }
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn packed_slices() {
+    let values: Vec<BaseElement> = rand_vector(4 * PackedBaseElement::WIDTH + 1);
+    let (packed, remainder) = PackedBaseElement::pack_slice(&values);
+    assert_eq!(4, packed.len());
+    assert_eq!(&values[values.len() - 1..], remainder);
+    for (i, p) in packed.iter().enumerate() {
+        let start = i * PackedBaseElement::WIDTH;
+        assert_eq!(
+            &values[start..start + PackedBaseElement::WIDTH],
+            &p.to_elements()
+        );
+    }
+
+    let k: BaseElement = rand_value();
+    assert_eq!(
+        [k; PackedBaseElement::WIDTH],
+        PackedBaseElement::broadcast(k).to_elements()
+    );
+}
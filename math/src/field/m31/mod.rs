@@ -0,0 +1,575 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An implementation of the 31-bit Mersenne prime field with modulus $2^{31} - 1$.
+//!
+//! Modular reduction in this field requires only shifts and additions. Base elements are stored
+//! in the canonical form using `u32` as the backing type.
+//!
+//! The multiplicative group of this field has two-adicity of only 1, and thus, the field does
+//! not support FFT-based polynomial evaluation and interpolation over large multiplicative
+//! subgroups. Instead, polynomials over this field can be evaluated and interpolated over the
+//! circle group of order $2^{31}$ using functions in the [circle FFT](crate::fft::circle)
+//! module.
+//!
+//! The quadratic extension is defined over an irreducible polynomial x<sup>2</sup> + 1, and the
+//! cubic extension is defined over an irreducible polynomial x<sup>3</sup> - 5. The quartic
+//! extension is defined as a quadratic extension of the quadratic extension over an irreducible
+//! polynomial u<sup>2</sup> - (2 + i), where i<sup>2</sup> = -1.
+
+use super::{ExtensibleField, FieldElement, StarkField};
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt::{Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use utils::{
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Randomizable, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Field modulus = 2^31 - 1
+const M: u32 = 2147483647;
+
+/// Number of bytes needed to represent field element
+const ELEMENT_BYTES: usize = core::mem::size_of::<u32>();
+
+// FIELD ELEMENT
+// ================================================================================================
+
+/// Represents base field element in the field.
+///
+/// Internal values are stored in the canonical representation and are always in the range
+/// [0, M). The backing type is `u32`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct BaseElement(u32);
+
+impl BaseElement {
+    /// Creates a new field element from the provided `value`. If the value is greater than or
+    /// equal to the field modulus, modular reduction is silently performed.
+    pub const fn new(value: u32) -> BaseElement {
+        BaseElement(reduce(value as u64))
+    }
+}
+
+impl FieldElement for BaseElement {
+    type PositiveInteger = u64;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = BaseElement::new(0);
+    const ONE: Self = BaseElement::new(1);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+    const IS_CANONICAL: bool = true;
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[inline]
+    fn double(self) -> Self {
+        Self(add(self.0, self.0))
+    }
+
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        let mut b = self;
+
+        if power == 0 {
+            return Self::ONE;
+        } else if b == Self::ZERO {
+            return Self::ZERO;
+        }
+
+        let mut r = if power & 1 == 1 { b } else { Self::ONE };
+        for i in 1..64 - power.leading_zeros() {
+            b = b.square();
+            if (power >> i) & 1 == 1 {
+                r *= b;
+            }
+        }
+
+        r
+    }
+
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^(M - 2) = x^{-1} for all x != 0; for x = 0, this returns 0
+        self.exp(M as u64 - 2)
+    }
+
+    fn conjugate(&self) -> Self {
+        BaseElement(self.0)
+    }
+
+    // BASE ELEMENT CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // TODO: take endianness into account
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        if (p as usize) % mem::align_of::<u32>() != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+
+    // UTILITIES
+    // --------------------------------------------------------------------------------------------
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // this uses a specialized vector initialization code which requests zero-filled memory
+        // from the OS; unfortunately, this works only for built-in types and we can't use
+        // Self::ZERO here as much less efficient initialization procedure will be invoked.
+        // We also use u32 to make sure the memory is aligned correctly for our element size.
+        let result = vec![0u32; n];
+
+        // translate a zero-filled vector of u32s into a vector of base field elements
+        let mut v = core::mem::ManuallyDrop::new(result);
+        let p = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+}
+
+impl StarkField for BaseElement {
+    /// sage: MODULUS = 2^31 - 1 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True \
+    /// sage: GF(MODULUS).order() \
+    /// 2147483647
+    const MODULUS: Self::PositiveInteger = M as u64;
+    const MODULUS_BITS: u32 = 31;
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 7
+    const GENERATOR: Self = BaseElement::new(7);
+
+    /// sage: is_odd((MODULUS - 1) / 2) \
+    /// True
+    const TWO_ADICITY: u32 = 1;
+
+    /// sage: k = (MODULUS - 1) / 2 \
+    /// sage: GF(MODULUS).primitive_element()^k \
+    /// 2147483646
+    const TWO_ADIC_ROOT_OF_UNITY: Self = BaseElement::new(M - 1);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        M.to_le_bytes().to_vec()
+    }
+
+    #[inline]
+    fn as_int(&self) -> Self::PositiveInteger {
+        self.0 as u64
+    }
+}
+
+impl Randomizable for BaseElement {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl Display for BaseElement {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_int())
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl Add for BaseElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(add(self.0, rhs.0))
+    }
+}
+
+impl AddAssign for BaseElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for BaseElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(sub(self.0, rhs.0))
+    }
+}
+
+impl SubAssign for BaseElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for BaseElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(reduce(self.0 as u64 * rhs.0 as u64))
+    }
+}
+
+impl MulAssign for BaseElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for BaseElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for BaseElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for BaseElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(sub(0, self.0))
+    }
+}
+
+// QUADRATIC EXTENSION
+// ================================================================================================
+
+/// Defines a quadratic extension of the base field over an irreducible polynomial
+/// x<sup>2</sup> + 1. Thus, an extension element is defined as α + β * i, where i is a root of
+/// this polynomial, and α and β are base field elements.
+impl ExtensibleField<2> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        let z = (a[0] + a[1]) * (b[0] + b[1]);
+        [a0b0 - a1b1, z - a0b0 - a1b1]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 2], b: Self) -> [Self; 2] {
+        [a[0] * b, a[1] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 2]) -> [Self; 2] {
+        [x[0], -x[1]]
+    }
+}
+
+// CUBIC EXTENSION
+// ================================================================================================
+
+/// Defines a cubic extension of the base field over an irreducible polynomial x<sup>3</sup> - 5.
+/// Thus, an extension element is defined as α + β * φ + γ * φ^2, where φ is a root of this
+/// polynomial, and α, β and γ are base field elements.
+impl ExtensibleField<3> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 3], b: [Self; 3]) -> [Self; 3] {
+        // compute the product of polynomials a and b, and then reduce the terms of degree 3 and
+        // higher using x^3 = 5
+        let w = BaseElement::new(5);
+        let a0b0 = a[0] * b[0];
+        let a1b1 = a[1] * b[1];
+        let a2b2 = a[2] * b[2];
+        [
+            a0b0 + w * (a[1] * b[2] + a[2] * b[1]),
+            a[0] * b[1] + a[1] * b[0] + w * a2b2,
+            a[0] * b[2] + a1b1 + a[2] * b[0],
+        ]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 3], b: Self) -> [Self; 3] {
+        [a[0] * b, a[1] * b, a[2] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 3]) -> [Self; 3] {
+        // φ^M = φ * 5^((M - 1) / 3), and thus, the coefficient of φ^i is multiplied by
+        // 5^(i * (M - 1) / 3)
+        [
+            x[0],
+            x[1] * BaseElement::new(1513477735),
+            x[2] * BaseElement::new(634005911),
+        ]
+    }
+}
+
+// QUARTIC EXTENSION
+// ================================================================================================
+
+/// Defines a quartic extension of the base field as a quadratic extension of the quadratic
+/// extension over an irreducible polynomial u<sup>2</sup> - (2 + i). Thus, an extension element
+/// is defined as (a<sub>0</sub> + a<sub>1</sub> * i) + (a<sub>2</sub> + a<sub>3</sub> * i) * u,
+/// where a<sub>0</sub>, a<sub>1</sub>, a<sub>2</sub>, and a<sub>3</sub> are base field elements.
+///
+/// This is the same extension as the one used by Circle STARKs (sometimes referred to as QM31).
+impl ExtensibleField<4> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+        // (a0 + a1 * u) * (b0 + b1 * u) = (a0 * b0 + a1 * b1 * (2 + i)) + (a0 * b1 + a1 * b0) * u
+        let (a0, a1) = ([a[0], a[1]], [a[2], a[3]]);
+        let (b0, b1) = ([b[0], b[1]], [b[2], b[3]]);
+        let c0 = <Self as ExtensibleField<2>>::mul(a0, b0);
+        let c1 = <Self as ExtensibleField<2>>::mul(a1, b1);
+        let c1 = <Self as ExtensibleField<2>>::mul(c1, [BaseElement::new(2), BaseElement::ONE]);
+        let c2 = <Self as ExtensibleField<2>>::mul(a0, b1);
+        let c3 = <Self as ExtensibleField<2>>::mul(a1, b0);
+        [c0[0] + c1[0], c0[1] + c1[1], c2[0] + c3[0], c2[1] + c3[1]]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 4], b: Self) -> [Self; 4] {
+        [a[0] * b, a[1] * b, a[2] * b, a[3] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 4]) -> [Self; 4] {
+        // the Frobenius automorphism conjugates both coefficients in the quadratic extension, and
+        // maps u to u * (2 + i)^((M - 1) / 2)
+        let c0 = <Self as ExtensibleField<2>>::frobenius([x[0], x[1]]);
+        let c1 = <Self as ExtensibleField<2>>::frobenius([x[2], x[3]]);
+        let c1 = <Self as ExtensibleField<2>>::mul(
+            c1,
+            [BaseElement::new(21189756), BaseElement::new(42379512)],
+        );
+        [c0[0], c0[1], c1[0], c1[1]]
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl From<u128> for BaseElement {
+    /// Converts a 128-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed.
+    fn from(value: u128) -> Self {
+        BaseElement((value % M as u128) as u32)
+    }
+}
+
+impl From<u64> for BaseElement {
+    /// Converts a 64-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed.
+    fn from(value: u64) -> Self {
+        BaseElement((value % M as u64) as u32)
+    }
+}
+
+impl From<u32> for BaseElement {
+    /// Converts a 32-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed.
+    fn from(value: u32) -> Self {
+        BaseElement::new(value)
+    }
+}
+
+impl From<u16> for BaseElement {
+    /// Converts a 16-bit value into a field element.
+    fn from(value: u16) -> Self {
+        BaseElement(value as u32)
+    }
+}
+
+impl From<u8> for BaseElement {
+    /// Converts an 8-bit value into a field element.
+    fn from(value: u8) -> Self {
+        BaseElement(value as u32)
+    }
+}
+
+impl From<[u8; 4]> for BaseElement {
+    /// Converts the value encoded in an array of 4 bytes into a field element. The bytes are
+    /// assumed to encode the element in the canonical representation in little-endian byte order.
+    /// If the value is greater than or equal to the field modulus, modular reduction is silently
+    /// performed.
+    fn from(bytes: [u8; 4]) -> Self {
+        let value = u32::from_le_bytes(bytes);
+        BaseElement::new(value)
+    }
+}
+
+impl TryFrom<&[u8]> for BaseElement {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to encode the element in the canonical
+    /// representation in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let value = bytes
+            .try_into()
+            .map(u32::from_le_bytes)
+            .map_err(|error| DeserializationError::UnknownError(format!("{error}")))?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement(value))
+    }
+}
+
+impl AsBytes for BaseElement {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const BaseElement = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for BaseElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.0.to_le_bytes());
+    }
+}
+
+impl Deserializable for BaseElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = source.read_u32()?;
+        if value >= M {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(BaseElement(value))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BaseElement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        utils::serde_compat::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BaseElement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        utils::serde_compat::deserialize(deserializer)
+    }
+}
+
+// FINITE FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes (a + b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+fn add(a: u32, b: u32) -> u32 {
+    // since M < 2^31, the sum cannot overflow
+    let z = a + b;
+    if z >= M {
+        z - M
+    } else {
+        z
+    }
+}
+
+/// Computes (a - b) reduced by M such that the output is in [0, M) range; a and b are assumed to
+/// be in [0, M).
+#[inline(always)]
+fn sub(a: u32, b: u32) -> u32 {
+    if a < b {
+        M - b + a
+    } else {
+        a - b
+    }
+}
+
+/// Reduces `x` by M such that the output is in [0, M) range; `x` is assumed to be smaller than
+/// 2^62.
+///
+/// Since 2^31 = 1 mod M, `x` can be reduced by adding its high and low 31-bit parts.
+#[inline(always)]
+const fn reduce(x: u64) -> u32 {
+    // for x < 2^62, the first step produces a value smaller than 2^32, and the second step
+    // produces a value which is at most M
+    let x = (x & M as u64) + (x >> 31);
+    let x = (x & M as u64) + (x >> 31);
+    let x = x as u32;
+    if x >= M {
+        x - M
+    } else {
+        x
+    }
+}
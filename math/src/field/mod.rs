@@ -7,8 +7,11 @@ mod traits;
 pub use traits::{ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
 
 pub mod f128;
+pub mod f252;
+pub mod f31;
 pub mod f62;
 pub mod f64;
+pub mod m31;
 
 mod extensions;
-pub use extensions::{CubeExtension, QuadExtension};
+pub use extensions::{CubeExtension, QuadExtension, QuartExtension};
@@ -15,6 +15,11 @@ use utils::{
     collections::Vec, AsBytes, Deserializable, DeserializationError, Randomizable, Serializable,
 };
 
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+#[cfg(feature = "bigint")]
+use utils::SliceReader;
+
 // FIELD ELEMENT
 // ================================================================================================
 /// Defines an element in a finite field.
@@ -25,7 +30,7 @@ use utils::{
 /// Moreover, it defines interfaces for serializing and deserializing field elements.
 ///
 /// The elements could be in a prime field or an extension of a prime field. Currently, only
-/// quadratic and cubic field extensions are supported.
+/// quadratic, cubic, and quartic field extensions are supported.
 pub trait FieldElement:
     Copy
     + Clone
@@ -219,6 +224,49 @@ pub trait FieldElement:
     fn zeroed_vector(n: usize) -> Vec<Self> {
         vec![Self::ZERO; n]
     }
+
+    /// Multiplies every element in the provided slice by `k`.
+    ///
+    /// Specialized implementations of this function may be faster than the generic implementation.
+    fn mul_slice_in_place(values: &mut [Self], k: Self) {
+        for value in values.iter_mut() {
+            *value *= k;
+        }
+    }
+
+    /// Applies a radix-2 butterfly with the specified twiddle factor to all pairs of elements in
+    /// `a` and `b`. Specifically, for all i:
+    ///
+    /// (a_i, b_i) = (a_i + b_i * twiddle, a_i - b_i * twiddle)
+    ///
+    /// Specialized implementations of this function may be faster than the generic implementation.
+    ///
+    /// # Panics
+    /// Panics if `a` and `b` have different lengths.
+    fn butterfly_twiddle_slices(a: &mut [Self], b: &mut [Self], twiddle: Self::BaseField) {
+        assert_eq!(a.len(), b.len(), "slices must have the same length");
+        for (a, b) in a.iter_mut().zip(b.iter_mut()) {
+            let temp = *a;
+            *b = b.mul_base(twiddle);
+            *a = temp + *b;
+            *b = temp - *b;
+        }
+    }
+
+    /// Returns multiplicative inverses of all elements in the provided slice using batch
+    /// inversion method (Montgomery's trick). ZERO elements are mapped to ZERO.
+    ///
+    /// This is significantly faster than inverting elements one-by-one because it essentially
+    /// transforms `n` inversions into `3 * n` multiplications + 1 inversion.
+    fn batch_inverse(values: &[Self]) -> Vec<Self> {
+        crate::batch_inversion(values)
+    }
+
+    /// Replaces each element in the provided slice with its multiplicative inverse using batch
+    /// inversion method (Montgomery's trick). ZERO elements are left unchanged.
+    fn batch_inverse_in_place(values: &mut [Self]) {
+        crate::batch_inversion_in_place(values)
+    }
 }
 
 // STARK FIELD
@@ -267,6 +315,62 @@ pub trait StarkField: FieldElement<BaseField = Self> {
 
     /// Returns a canonical integer representation of this field element.
     fn as_int(&self) -> Self::PositiveInteger;
+
+    // ARBITRARY-PRECISION CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the field modulus as an arbitrary-precision integer.
+    #[cfg(feature = "bigint")]
+    fn get_modulus_biguint() -> BigUint {
+        BigUint::from_bytes_le(&Self::get_modulus_le_bytes())
+    }
+
+    /// Returns a canonical integer representation of this field element as an arbitrary-precision
+    /// integer.
+    #[cfg(feature = "bigint")]
+    fn as_biguint(&self) -> BigUint {
+        BigUint::from_bytes_le(&self.to_bytes())
+    }
+
+    /// Returns a field element equal to `value` reduced modulo the field modulus.
+    #[cfg(feature = "bigint")]
+    fn from_biguint(value: &BigUint) -> Self {
+        let value = value % Self::get_modulus_biguint();
+        Self::try_from_biguint(&value).expect("reduced value must be a valid field element")
+    }
+
+    /// Returns a field element equal to `value`, or None if `value` is not smaller than the field
+    /// modulus.
+    #[cfg(feature = "bigint")]
+    fn try_from_biguint(value: &BigUint) -> Option<Self> {
+        if *value >= Self::get_modulus_biguint() {
+            return None;
+        }
+        // canonical encodings of field elements are little-endian and always take up exactly
+        // ELEMENT_BYTES bytes
+        let mut bytes = value.to_bytes_le();
+        bytes.resize(Self::ELEMENT_BYTES, 0);
+        Self::read_from(&mut SliceReader::new(&bytes)).ok()
+    }
+
+    /// Returns an element of this field whose canonical integer representation is equal to the
+    /// canonical integer representation of `value` reduced modulo the modulus of this field.
+    ///
+    /// For example, this can be used to convert elements of the 64-bit field into elements of the
+    /// 252-bit field; since the modulus of the 252-bit field is larger, the integer value is
+    /// preserved.
+    #[cfg(feature = "bigint")]
+    fn from_other<F: StarkField>(value: F) -> Self {
+        Self::from_biguint(&value.as_biguint())
+    }
+
+    /// Returns an element of this field whose canonical integer representation is equal to the
+    /// canonical integer representation of `value`, or None if the integer representation of
+    /// `value` is not smaller than the modulus of this field.
+    #[cfg(feature = "bigint")]
+    fn try_from_other<F: StarkField>(value: F) -> Option<Self> {
+        Self::try_from_biguint(&value.as_biguint())
+    }
 }
 
 // EXTENSIBLE FIELD
@@ -328,7 +432,15 @@ impl<E: FieldElement> ExtensionOf<E> for E {
 // ================================================================================================
 
 /// Defines how to convert a struct to a vector of field elements.
+///
+/// This conversion defines the canonical encoding of a value as a sequence of field elements,
+/// and is used, for example, to seed the public coin with public inputs of a computation.
+/// Implementations are provided for field elements, unsigned integers (each integer is mapped to
+/// a single element), as well as for arrays, slices, and vectors of values implementing this
+/// trait (elements of all values are concatenated; the length is not encoded). For structs, the
+/// trait can be derived via `ToElements` derive macro when `derive` feature is enabled.
 pub trait ToElements<E: FieldElement> {
+    /// Returns the elements encoding `self`.
     fn to_elements(&self) -> Vec<E>;
 }
 
@@ -343,3 +455,35 @@ impl<E: FieldElement> ToElements<E> for E {
         vec![*self]
     }
 }
+
+macro_rules! impl_to_elements_for_uint {
+    ($($t:ty),*) => {
+        $(
+            impl<E: FieldElement> ToElements<E> for $t {
+                fn to_elements(&self) -> Vec<E> {
+                    vec![E::from(*self as u64)]
+                }
+            }
+        )*
+    };
+}
+
+impl_to_elements_for_uint!(u8, u16, u32, u64, usize);
+
+impl<E: FieldElement, T: ToElements<E>, const N: usize> ToElements<E> for [T; N] {
+    fn to_elements(&self) -> Vec<E> {
+        self.as_slice().to_elements()
+    }
+}
+
+impl<E: FieldElement, T: ToElements<E>> ToElements<E> for [T] {
+    fn to_elements(&self) -> Vec<E> {
+        self.iter().flat_map(|value| value.to_elements()).collect()
+    }
+}
+
+impl<E: FieldElement, T: ToElements<E>> ToElements<E> for Vec<T> {
+    fn to_elements(&self) -> Vec<E> {
+        self.as_slice().to_elements()
+    }
+}
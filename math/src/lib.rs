@@ -16,7 +16,7 @@
 //! * Drawing random and pseudo-random elements from the field.
 //! * Computing roots of unity of a given order.
 //!
-//! Currently, there are two implementations of finite fields:
+//! Currently, there are six implementations of finite fields:
 //!
 //! * A 128-bit field with modulus 2<sup>128</sup> - 45 * 2<sup>40</sup> + 1. This field was not
 //!   chosen with any significant thought given to performance, and the implementation of most
@@ -32,29 +32,61 @@
 //!   properties. To achieve adequate security (i.e. ~100 bits), proofs must be generated in a
 //!   quadratic extension of this field. For higher levels of security, a cubic extension field
 //!   should be used.
+//! * A 31-bit field with modulus 2<sup>31</sup> - 2<sup>27</sup> + 1 (also known as BabyBear).
+//!   Elements of this field are backed by `u32` values, which makes arithmetic on slices of
+//!   elements amenable to vectorization. To achieve adequate security, proofs must be generated
+//!   in an extension of this field.
+//! * A 31-bit field with modulus 2<sup>31</sup> - 1 (Mersenne-31). This field supports very
+//!   fast modular reduction, but its multiplicative group has two-adicity of 1. Thus, polynomials
+//!   over this field must be evaluated and interpolated over the circle group using functions
+//!   in the [circle](crate::fft::circle) module.
+//! * A 252-bit field with modulus 2<sup>251</sup> + 17 * 2<sup>192</sup> + 1 (Stark252). This is
+//!   the native field of Cairo, and thus, AIRs describing Cairo programs can be proven in this
+//!   field directly. Proofs generated in this field can support high levels of security without
+//!   using field extensions.
 //!
 //! ## Extension fields
 //!
-//! Currently, the library provides a generic way to create quadratic and cubic extensions of
-//! supported STARK fields. This can be done by implementing [ExtensibleField] trait for
-//! degrees 2 and 3.
+//! Currently, the library provides a generic way to create quadratic, cubic, and quartic
+//! extensions of supported STARK fields. This can be done by implementing [ExtensibleField] trait
+//! for degrees 2, 3, and 4.
 //!
 //! Quadratic extension fields are defined using the following irreducible polynomials:
 //! * For [f62](crate::fields::f62) field, the polynomial is x<sup>2</sup> - x - 1.
 //! * For [f64](crate::fields::f64) field, the polynomial is x<sup>2</sup> - x + 2.
 //! * For [f128](crate::fields::f128) field, the polynomial is x<sup>2</sup> - x - 1.
+//! * For [f31](crate::fields::f31) field, the polynomial is x<sup>2</sup> - 11.
+//! * For [m31](crate::fields::m31) field, the polynomial is x<sup>2</sup> + 1.
+//! * For [f252](crate::fields::f252) field, the polynomial is x<sup>2</sup> - 3.
 //!
 //! Cubic extension fields are defined using the following irreducible polynomials:
 //! * For [f62](crate::fields::f62) field, the polynomial is x<sup>3</sup> + 2x + 2.
 //! * For [f64](crate::fields::f64) field, the polynomial is x<sup>3</sup> - x - 1.
 //! * For [f128](crate::fields::f128) field, cubic extensions are not supported.
+//! * For [f31](crate::fields::f31) field, the polynomial is x<sup>3</sup> - 2.
+//! * For [m31](crate::fields::m31) field, the polynomial is x<sup>3</sup> - 5.
+//! * For [f252](crate::fields::f252) field, cubic extensions are not supported.
+//!
+//! Quartic extension fields are defined as follows:
+//! * For [f31](crate::fields::f31) field, the polynomial is x<sup>4</sup> - 11.
+//! * For [m31](crate::fields::m31) field, the extension is a tower: a quadratic extension of the
+//!   quadratic extension over polynomial u<sup>2</sup> - (2 + i).
+//! * For [f62](crate::fields::f62), [f64](crate::fields::f64), [f128](crate::fields::f128), and
+//!   [f252](crate::fields::f252) fields, quartic extensions are not supported.
+//!
+//! For [f31](crate::fields::f31) field, [ExtensibleField] is also implemented for degree 5 using
+//! polynomial x<sup>5</sup> - 2.
 //!
 //! # Polynomials
 //! [Polynomials](polynom) module implements basic polynomial operations such as:
 //!
-//! * Evaluation of a polynomial at a single or multiple point.
+//! * Evaluation of a polynomial at a single or multiple point, including fast multi-point
+//!   evaluation using sub-product trees.
+//! * Evaluation of a polynomial given by its evaluations over a multiplicative subgroup using
+//!   the [barycentric](https://en.wikipedia.org/wiki/Lagrange_polynomial#Barycentric_form) formula.
 //! * Interpolation of a polynomial from a set of points (using
-//!   [Lagrange](https://en.wikipedia.org/wiki/Lagrange_polynomial) interpolation).
+//!   [Lagrange](https://en.wikipedia.org/wiki/Lagrange_polynomial) interpolation, or fast
+//!   interpolation using sub-product trees).
 //! * Addition, multiplication, subtraction, and division of polynomials.
 //! * Synthetic polynomial division (using
 //!   [Ruffini's](https://en.wikipedia.org/wiki/Ruffini%27s_rule) method).
@@ -64,6 +96,16 @@
 //! field (also called [Number-theoretic transform](https://en.wikipedia.org/wiki/Discrete_Fourier_transform_(general)#Number-theoretic_transform)).
 //! This can be used to interpolate and evaluate polynomials in *O(n log n)* time as long as
 //! the domain of the polynomial is a multiplicative subgroup with size which is a power of 2.
+//! When many polynomials need to be evaluated or interpolated over the same domain, an
+//! [FftPlanner](fft::FftPlanner) can be used to compute twiddles for the domain only once.
+//! Domains with sizes of the form 2<sup>a</sup> * 3<sup>b</sup> * 5<sup>c</sup> are supported by
+//! the mixed-radix FFT in the [mixed_radix](fft::mixed_radix) module.
+//! FFT-based operations can also be dispatched through the [ComputeBackend](fft::ComputeBackend)
+//! trait, which makes it possible to offload them to hardware accelerators.
+//!
+//! Multiplicative subgroups and their cosets (i.e., domains over which polynomials are evaluated)
+//! are described by the [Domain] type, which can be used to derive domain elements at specific
+//! positions.
 //!
 //! # Concurrent execution
 //!
@@ -76,7 +118,9 @@
 //!   - [get_power_series_with_offset()]
 //!   - [add_in_place()]
 //!   - [mul_acc()]
+//!   - [random_linear_combo()]
 //!   - [batch_inversion()]
+//!   - [batch_inversion_in_place()]
 //! * `fft` module:
 //!   - [evaluate_poly()](fft::evaluate_poly())
 //!   - [evaluate_poly_with_offset()](fft::evaluate_poly_with_offset())
@@ -96,8 +140,23 @@ extern crate alloc;
 pub mod fft;
 pub mod polynom;
 
+mod domain;
+pub use domain::{Domain, DomainElements};
+
 mod field;
 pub use field::{ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
+
+#[cfg(feature = "derive")]
+pub use winter_derive::ToElements;
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigUint;
+
+#[doc(hidden)]
+pub mod __private {
+    //! Items used by code generated via derive macros; not a part of the public API.
+    pub use utils::collections::Vec;
+}
 pub mod fields {
     //! Finite field implementations.
     //!
@@ -105,13 +164,18 @@ pub mod fields {
     //! of these field.
 
     pub use super::field::f128;
+    pub use super::field::f252;
+    pub use super::field::f31;
     pub use super::field::f62;
     pub use super::field::f64;
+    pub use super::field::m31;
     pub use super::field::CubeExtension;
     pub use super::field::QuadExtension;
+    pub use super::field::QuartExtension;
 }
 
 mod utils;
 pub use crate::utils::{
-    add_in_place, batch_inversion, get_power_series, get_power_series_with_offset, log2, mul_acc,
+    add_in_place, batch_inversion, batch_inversion_in_place, get_power_series,
+    get_power_series_with_offset, linear_combination, log2, mul_acc, random_linear_combo,
 };
@@ -0,0 +1,247 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{degree_of, mul};
+use crate::{
+    fft,
+    field::{FieldElement, StarkField},
+};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Polynomials with fewer coefficients than this are multiplied and divided using schoolbook
+/// algorithms by [mul()](super::mul), [div()](super::div), and [rem()], since for small
+/// polynomials these are faster than FFT-based algorithms.
+pub(super) const MIN_FFT_SIZE: usize = 64;
+
+// FFT-BASED MULTIPLICATION
+// ================================================================================================
+
+/// Returns a polynomial resulting from multiplying two polynomials together using FFT.
+///
+/// Both polynomials are evaluated over a multiplicative subgroup of the base field of size
+/// `n` = (`a.len()` + `b.len()` - 1) rounded up to the next power of two, the evaluations are
+/// multiplied pointwise, and the result is interpolated back into coefficient form. This takes
+/// O(`n` log `n`) time as compared to O(`a.len()` * `b.len()`) time for schoolbook
+/// multiplication. The length of the returned vector will be `a.len()` + `b.len()` - 1.
+///
+/// [mul()](super::mul) uses this function automatically when both polynomials are large enough.
+///
+/// # Panics
+/// Panics if:
+/// * Either of the polynomials is empty.
+/// * The base field of `E` does not contain a multiplicative subgroup of size `n`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(100);
+/// let b: Vec<BaseElement> = rand_vector(50);
+///
+/// assert_eq!(mul(&a, &b), mul_fft(&a, &b));
+/// ```
+pub fn mul_fft<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert!(
+        !a.is_empty() && !b.is_empty(),
+        "cannot multiply empty polynomials"
+    );
+    let result_len = a.len() + b.len() - 1;
+    let domain_size = result_len.next_power_of_two().max(2);
+
+    let mut a_evaluations = E::zeroed_vector(domain_size);
+    a_evaluations[..a.len()].copy_from_slice(a);
+    let mut b_evaluations = E::zeroed_vector(domain_size);
+    b_evaluations[..b.len()].copy_from_slice(b);
+
+    let twiddles = fft::get_twiddles::<E::BaseField>(domain_size);
+    fft::evaluate_poly(&mut a_evaluations, &twiddles);
+    fft::evaluate_poly(&mut b_evaluations, &twiddles);
+    for (a, b) in a_evaluations.iter_mut().zip(b_evaluations) {
+        *a *= b;
+    }
+
+    let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(domain_size);
+    fft::interpolate_poly(&mut a_evaluations, &inv_twiddles);
+    a_evaluations.truncate(result_len);
+    a_evaluations
+}
+
+// FFT-BASED DIVISION
+// ================================================================================================
+
+/// Returns a polynomial resulting from dividing one polynomial by another using FFT-based
+/// multiplication.
+///
+/// The result is the same as for [div()](super::div): if the polynomials don't divide evenly,
+/// the remainder is ignored, and the length of the returned vector will be
+/// deg(`a`) - deg(`b`) + 1. The quotient is computed as the reversal of
+/// rev(`a`) * rev(`b`)<sup>-1</sup> mod x<sup>k</sup>, where the inverse power series is
+/// computed via Newton iteration; this takes O(`n` log `n`) time, where `n` is the length of `a`.
+///
+/// [div()](super::div) uses this function automatically when both the divisor and the quotient
+/// are large enough.
+///
+/// # Panics
+/// Panics if:
+/// * Polynomial `b` is empty.
+/// * Degree of polynomial `b` is zero and the constant coefficient is ZERO.
+/// * The degree of polynomial `b` is greater than the degree of polynomial `a`.
+/// * The base field of `E` does not contain multiplicative subgroups large enough to multiply
+///   polynomials of length `a.len()`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(100);
+/// let b: Vec<BaseElement> = rand_vector(50);
+///
+/// let p = mul(&a, &b);
+/// assert_eq!(a, div_fft(&p, &b));
+/// ```
+pub fn div_fft<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    let a_degree = degree_of(a);
+    let b_degree = degree_of(b);
+    assert!(
+        a_degree >= b_degree,
+        "cannot divide by polynomial of higher degree"
+    );
+    if b_degree == 0 {
+        assert!(!b.is_empty(), "cannot divide by empty polynomial");
+        assert!(b[0] != E::ZERO, "cannot divide polynomial by zero");
+    }
+
+    // reverse the top quotient length coefficients of a, and all coefficients of b; the
+    // constant coefficient of reversed b is the leading coefficient of b, and thus, is non-zero
+    let quot_len = a_degree - b_degree + 1;
+    let a_rev = a[..=a_degree]
+        .iter()
+        .rev()
+        .take(quot_len)
+        .copied()
+        .collect::<Vec<_>>();
+    let b_rev = b[..=b_degree].iter().rev().copied().collect::<Vec<_>>();
+
+    let mut result = mul(&a_rev, &inv_series(&b_rev, quot_len));
+    result.truncate(quot_len);
+    result.reverse();
+    result
+}
+
+// REMAINDER
+// ================================================================================================
+
+/// Returns the remainder of dividing one polynomial by another.
+///
+/// Specifically, returns polynomial `r` of degree smaller than deg(`b`) such that `a` = `q` * `b`
+/// \+ `r` for some polynomial `q`. The length of the returned vector will be deg(`b`). For large
+/// polynomials, the quotient is computed using [div_fft()]; otherwise, schoolbook long division
+/// is used.
+///
+/// # Panics
+/// Panics if:
+/// * Polynomial `b` is empty.
+/// * Degree of polynomial `b` is zero and the constant coefficient is ZERO.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p(x) = x^3 + x^2 + 2 * x + 3
+/// let p = [
+///     BaseElement::new(3),
+///     BaseElement::new(2),
+///     BaseElement::new(1),
+///     BaseElement::new(1),
+/// ];
+/// // d(x) = x^2 + 2
+/// let d = [BaseElement::new(2), BaseElement::ZERO, BaseElement::ONE];
+///
+/// // p(x) = (x + 1) * d(x) + 1
+/// assert_eq!(vec![BaseElement::ONE, BaseElement::ZERO], rem(&p, &d));
+/// ```
+pub fn rem<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    let b_degree = degree_of(b);
+    if b_degree == 0 {
+        assert!(!b.is_empty(), "cannot divide by empty polynomial");
+        assert!(b[0] != E::ZERO, "cannot divide polynomial by zero");
+    }
+    let b = &b[..=b_degree];
+
+    let a_degree = degree_of(a);
+    if a.is_empty() || a_degree < b_degree {
+        let mut result = a.to_vec();
+        result.resize(b_degree, E::ZERO);
+        return result;
+    }
+    let a = &a[..=a_degree];
+
+    if use_fft::<E>(b.len(), a.len() - b_degree, a.len()) {
+        // the remainder is a - quot * b
+        let product = mul(&div_fft(a, b), b);
+        a[..b_degree]
+            .iter()
+            .zip(product)
+            .map(|(&a, p)| a - p)
+            .collect()
+    } else {
+        // schoolbook long division
+        let mut result = a.to_vec();
+        let inv_lead = b[b_degree].inv();
+        for i in (b_degree..result.len()).rev() {
+            let quot = result[i] * inv_lead;
+            for (r, &c) in result[i - b_degree..i].iter_mut().zip(b) {
+                *r -= c * quot;
+            }
+        }
+        result.truncate(b_degree);
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if multiplying (or dividing) polynomials with the specified lengths should be
+/// done using FFT, i.e., if both polynomials are large enough for FFT to be faster, and the base
+/// field contains a subgroup large enough to hold the product of length `result_len`.
+pub(super) fn use_fft<E: FieldElement>(a_len: usize, b_len: usize, result_len: usize) -> bool {
+    a_len.min(b_len) >= MIN_FFT_SIZE
+        && result_len.next_power_of_two().ilog2() <= E::BaseField::TWO_ADICITY
+}
+
+/// Returns the inverse of power series `f` modulo x^`k`; the constant coefficient of `f` is
+/// assumed to be non-zero.
+fn inv_series<E: FieldElement>(f: &[E], k: usize) -> Vec<E> {
+    // each Newton iteration doubles the number of correct coefficients: g' = g * (2 - f * g)
+    let mut result = vec![f[0].inv()];
+    let mut precision = 1;
+    while precision < k {
+        precision = (precision * 2).min(k);
+        let mut e = mul(&f[..f.len().min(precision)], &result);
+        e.truncate(precision);
+        for c in e.iter_mut() {
+            *c = -*c;
+        }
+        e[0] += E::ONE + E::ONE;
+        result = mul(&result, &e);
+        result.truncate(precision);
+    }
+    result
+}
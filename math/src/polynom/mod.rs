@@ -6,9 +6,12 @@
 //! Basic polynomial operations.
 //!
 //! This module provides a set of function for basic polynomial operations, including:
-//! - Polynomial evaluation using Horner method.
+//! - Polynomial evaluation using Horner method, and evaluation of polynomials given by their
+//!   evaluations over a multiplicative subgroup using barycentric formula.
 //! - Polynomial interpolation using Lagrange method.
-//! - Polynomial addition, subtraction, multiplication, and division.
+//! - Multi-point evaluation and interpolation using sub-product trees.
+//! - Polynomial addition, subtraction, multiplication, and division; for large polynomials,
+//!   multiplication and division are performed using FFT.
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
 //!
@@ -24,10 +27,19 @@
 //! let p = [BaseElement::new(3), BaseElement::ZERO, BaseElement::new(4)];
 //! ```
 
-use crate::{field::FieldElement, utils::batch_inversion};
+use crate::{
+    field::{FieldElement, StarkField},
+    utils::batch_inversion,
+};
 use core::mem;
 use utils::{collections::Vec, group_vector_elements};
 
+mod fast;
+pub use fast::{div_fft, mul_fft, rem};
+
+mod subproduct;
+pub use subproduct::{eval_many_fast, interpolate_fast};
+
 #[cfg(test)]
 mod tests;
 
@@ -85,6 +97,90 @@ where
     xs.iter().map(|x| eval(p, *x)).collect()
 }
 
+/// Evaluates a polynomial given by its evaluations over a multiplicative subgroup (or a coset
+/// of a subgroup) at a single point and returns the result.
+///
+/// `evaluations` are assumed to be values of a polynomial of degree smaller than `n` =
+/// `evaluations.len()` at points `s * g^i` for all `i` in `0..n`, where `g` is the generator of
+/// the multiplicative subgroup of size `n` in the base field and `s` is the `domain_offset`. The
+/// polynomial is evaluated at `x` using the barycentric formula:
+///
+/// p(x) = (x^n - s^n) / (n * s^n) * sum(evaluations\[i\] * s * g^i / (x - s * g^i))
+///
+/// which requires O(`n`) operations and no interpolation of the polynomial.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `evaluations` is not a power of two.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size
+///   `evaluations.len()`.
+/// * `domain_offset` is ZERO.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, get_power_series, FieldElement, StarkField};
+/// # use rand_utils::rand_vector;
+/// let n = 16;
+/// let p: Vec<BaseElement> = rand_vector(n);
+///
+/// // evaluate the polynomial over a coset of the subgroup of size n
+/// let offset = BaseElement::GENERATOR;
+/// let g = BaseElement::get_root_of_unity(n.ilog2());
+/// let domain = get_power_series(g, n).into_iter().map(|x| x * offset).collect::<Vec<_>>();
+/// let evaluations = eval_many(&p, &domain);
+///
+/// let x = BaseElement::new(42);
+/// assert_eq!(eval(&p, x), eval_barycentric(&evaluations, offset, x));
+/// ```
+pub fn eval_barycentric<B, E>(evaluations: &[B], domain_offset: B::BaseField, x: E) -> E
+where
+    B: FieldElement,
+    E: FieldElement<BaseField = B::BaseField> + From<B>,
+{
+    let n = evaluations.len();
+    assert!(
+        n.is_power_of_two(),
+        "number of evaluations must be a power of 2"
+    );
+    assert_ne!(
+        domain_offset,
+        B::BaseField::ZERO,
+        "domain offset cannot be zero"
+    );
+
+    // compute x - s * g^i for all points in the domain; if x is one of the points, the value
+    // of the polynomial at x is already known
+    let g = B::BaseField::get_root_of_unity(n.ilog2());
+    let mut points = Vec::with_capacity(n);
+    let mut point = domain_offset;
+    for _ in 0..n {
+        points.push(point);
+        point *= g;
+    }
+    let mut denominators = Vec::with_capacity(n);
+    for (&point, &value) in points.iter().zip(evaluations) {
+        let denominator = x - E::from(point);
+        if denominator == E::ZERO {
+            return E::from(value);
+        }
+        denominators.push(denominator);
+    }
+    E::batch_inverse_in_place(&mut denominators);
+
+    let sum = evaluations
+        .iter()
+        .zip(points)
+        .zip(denominators)
+        .fold(E::ZERO, |acc, ((&value, point), d)| {
+            acc + (E::from(value) * d).mul_base(point)
+        });
+
+    let offset_n = domain_offset.exp((n as u64).into());
+    let scale = (offset_n * B::BaseField::from(n as u64)).inv();
+    (x.exp((n as u64).into()) - E::from(offset_n)) * sum.mul_base(scale)
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
@@ -305,6 +401,9 @@ where
 /// polynomial will be in the coefficient form as well. The length of the returned vector
 /// will be a.len() + b.len() - 1.
 ///
+/// When both polynomials have at least 64 coefficients, the product is computed using
+/// [mul_fft()]; otherwise, schoolbook multiplication is used.
+///
 /// # Examples
 /// ```
 /// # use winter_math::polynom::*;
@@ -328,6 +427,10 @@ where
     E: FieldElement,
 {
     let result_len = a.len() + b.len() - 1;
+    if fast::use_fft::<E>(a.len(), b.len(), result_len) {
+        return mul_fft(a, b);
+    }
+
     let mut result = E::zeroed_vector(result_len);
     for i in 0..a.len() {
         for j in 0..b.len() {
@@ -365,10 +468,8 @@ pub fn mul_by_scalar<E>(p: &[E], k: E) -> Vec<E>
 where
     E: FieldElement,
 {
-    let mut result = Vec::with_capacity(p.len());
-    for coeff in p {
-        result.push(*coeff * k);
-    }
+    let mut result = p.to_vec();
+    E::mul_slice_in_place(&mut result, k);
     result
 }
 
@@ -379,6 +480,9 @@ where
 /// be in the coefficient form, and the returned polynomial will be in the coefficient form as
 /// well. The length of the returned vector will be a.len() - b.len() + 1.
 ///
+/// When both the divisor and the quotient have at least 64 coefficients, the quotient is
+/// computed using [div_fft()]; otherwise, schoolbook long division is used.
+///
 /// # Panics
 /// Panics if:
 /// * Polynomial `b` is empty.
@@ -417,6 +521,10 @@ where
         assert!(b[0] != E::ZERO, "cannot divide polynomial by zero");
     }
 
+    if fast::use_fft::<E>(bpos + 1, apos - bpos + 1, apos + 1) {
+        return div_fft(&a, b);
+    }
+
     let mut result = E::zeroed_vector(apos - bpos + 1);
     for i in (0..result.len()).rev() {
         let quot = a[apos] / b[bpos];
@@ -0,0 +1,205 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{eval, eval_many, interpolate, mul, rem};
+use crate::{field::FieldElement, utils::batch_inversion_in_place};
+use utils::collections::Vec;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sub-trees covering this many points or fewer are evaluated using Horner's method instead of
+/// being descended into further.
+const MAX_LEAF_SIZE: usize = 32;
+
+// MULTI-POINT EVALUATION
+// ================================================================================================
+
+/// Evaluates a polynomial at multiple points using a sub-product tree and returns the results.
+///
+/// The polynomial `p` is reduced modulo the products of `(x - xs[i])` for successively smaller
+/// subsets of `xs`, and once a subset is small enough, the reduced polynomial is evaluated at
+/// the points of the subset directly. Using FFT-based multiplication and division of
+/// polynomials, this takes O(`n` log<sup>2</sup> `n`) time, where `n` is the larger of the
+/// number of coefficients of `p` and the number of points, as compared to O(`n`<sup>2</sup>) time
+/// for [eval_many()](super::eval_many).
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let p: Vec<BaseElement> = rand_vector(1024);
+/// let xs: Vec<BaseElement> = rand_vector(1024);
+///
+/// assert_eq!(eval_many(&p, &xs), eval_many_fast(&p, &xs));
+/// ```
+pub fn eval_many_fast<E>(p: &[E], xs: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    if xs.len() <= MAX_LEAF_SIZE {
+        return eval_many(p, xs);
+    }
+
+    let tree = SubproductTree::new(xs);
+    let mut result = E::zeroed_vector(xs.len());
+    let p = rem(p, tree.root());
+    tree.eval_node(&p, tree.levels.len() - 1, 0, xs, &mut result);
+    result
+}
+
+// FAST INTERPOLATION
+// ================================================================================================
+
+/// Returns a polynomial in coefficient form interpolated from a set of X and Y coordinates using
+/// a sub-product tree.
+///
+/// This is the same as [interpolate()](super::interpolate) with `remove_leading_zeros` set to
+/// false, but takes O(`n` log<sup>2</sup> `n`) rather than O(`n`<sup>2</sup>) time, where `n` is
+/// the number of points. The length of the returned vector is equal to the number of points.
+///
+/// # Panics
+/// Panics if:
+/// * The number of X and Y coordinates is not the same.
+/// * X coordinates are not distinct.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let xs: Vec<BaseElement> = rand_vector(1024);
+/// let ys: Vec<BaseElement> = rand_vector(1024);
+///
+/// let p = interpolate_fast(&xs, &ys);
+/// assert_eq!(ys, eval_many_fast(&p, &xs));
+/// ```
+pub fn interpolate_fast<E>(xs: &[E], ys: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert_eq!(
+        xs.len(),
+        ys.len(),
+        "number of X and Y coordinates must be the same"
+    );
+    if xs.len() <= MAX_LEAF_SIZE {
+        let mut result = interpolate(xs, ys, false);
+        result.resize(xs.len(), E::ZERO);
+        return result;
+    }
+
+    // the polynomial is sum of ys[i] / m'(xs[i]) * m(x) / (x - xs[i]), where m(x) is the
+    // product of all (x - xs[i]); so, first evaluate m'(x) at all xs
+    let tree = SubproductTree::new(xs);
+    let derivative = derivative(tree.root());
+    let mut weights = E::zeroed_vector(xs.len());
+    tree.eval_node(&derivative, tree.levels.len() - 1, 0, xs, &mut weights);
+    assert!(
+        weights.iter().all(|&w| w != E::ZERO),
+        "X coordinates must be distinct"
+    );
+    batch_inversion_in_place(&mut weights);
+
+    // then combine the weighted Y coordinates going up the tree: a node's polynomial is
+    // left * m_right + right * m_left, where m_left and m_right are the sub-products of the
+    // node's children
+    let mut polys = ys
+        .iter()
+        .zip(weights)
+        .map(|(&y, w)| vec![y * w])
+        .collect::<Vec<_>>();
+    for level in tree.levels.iter().take(tree.levels.len() - 1) {
+        polys = polys
+            .chunks(2)
+            .zip(level.chunks(2))
+            .map(|(polys, nodes)| match (polys, nodes) {
+                ([left, right], [m_left, m_right]) => {
+                    let mut result = mul(left, m_right);
+                    for (r, v) in result.iter_mut().zip(mul(right, m_left)) {
+                        *r += v;
+                    }
+                    result
+                }
+                _ => polys[0].clone(),
+            })
+            .collect();
+    }
+
+    let mut result = polys.remove(0);
+    result.resize(xs.len(), E::ZERO);
+    result
+}
+
+// SUB-PRODUCT TREE
+// ================================================================================================
+
+/// A binary tree of polynomials in which the leaves are polynomials `(x - xs[i])`, and each
+/// internal node is the product of its children.
+///
+/// Node `j` at level `k` is the product of `(x - xs[i])` for `i` in `j * 2^k..(j + 1) * 2^k`;
+/// if the number of nodes at a level is odd, the last node is carried over to the next level
+/// unchanged.
+struct SubproductTree<E: FieldElement> {
+    levels: Vec<Vec<Vec<E>>>,
+}
+
+impl<E: FieldElement> SubproductTree<E> {
+    /// Builds a sub-product tree for the specified points.
+    fn new(xs: &[E]) -> Self {
+        let mut levels = Vec::new();
+        levels.push(xs.iter().map(|&x| vec![-x, E::ONE]).collect::<Vec<_>>());
+        while levels[levels.len() - 1].len() > 1 {
+            let level = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|nodes| match nodes {
+                    [left, right] => mul(left, right),
+                    _ => nodes[0].clone(),
+                })
+                .collect();
+            levels.push(level);
+        }
+        SubproductTree { levels }
+    }
+
+    /// Returns the product of `(x - xs[i])` for all points.
+    fn root(&self) -> &[E] {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Evaluates polynomial `p` (which is assumed to be reduced modulo the polynomial at the
+    /// specified node) at all points covered by the node, and writes the results into `result`.
+    fn eval_node(&self, p: &[E], level: usize, index: usize, xs: &[E], result: &mut [E]) {
+        let start = index << level;
+        let end = ((index + 1) << level).min(xs.len());
+        if end - start <= MAX_LEAF_SIZE {
+            for (r, &x) in result[start..end].iter_mut().zip(&xs[start..end]) {
+                *r = eval(p, x);
+            }
+            return;
+        }
+
+        let children = &self.levels[level - 1];
+        for child in [2 * index, 2 * index + 1] {
+            if child < children.len() {
+                let p = rem(p, &children[child]);
+                self.eval_node(&p, level - 1, child, xs, result);
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the formal derivative of polynomial `p`.
+fn derivative<E: FieldElement>(p: &[E]) -> Vec<E> {
+    p.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| c * E::from(i as u64))
+        .collect()
+}
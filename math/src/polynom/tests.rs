@@ -5,9 +5,10 @@
 
 use super::remove_leading_zeros;
 use crate::{
-    field::{f128::BaseElement, FieldElement, StarkField},
+    field::{f128::BaseElement, FieldElement, QuadExtension, StarkField},
     utils::get_power_series,
 };
+use rand_utils::rand_vector;
 use utils::collections::Vec;
 
 #[test]
@@ -43,6 +44,37 @@ fn eval() {
     );
 }
 
+#[test]
+fn eval_barycentric() {
+    let n = 32;
+    let p: Vec<BaseElement> = rand_vector(n);
+    let g = BaseElement::get_root_of_unity(n.ilog2());
+    let x: QuadExtension<BaseElement> = rand_utils::rand_value();
+
+    // evaluations over the subgroup
+    let domain = get_power_series(g, n);
+    let evaluations = super::eval_many(&p, &domain);
+    assert_eq!(
+        super::eval(&p, x),
+        super::eval_barycentric(&evaluations, BaseElement::ONE, x)
+    );
+
+    // evaluations over a coset of the subgroup
+    let offset = BaseElement::GENERATOR;
+    let domain = domain.into_iter().map(|x| x * offset).collect::<Vec<_>>();
+    let evaluations = super::eval_many(&p, &domain);
+    assert_eq!(
+        super::eval(&p, x),
+        super::eval_barycentric(&evaluations, offset, x)
+    );
+
+    // evaluation at a point of the domain
+    assert_eq!(
+        evaluations[5],
+        super::eval_barycentric(&evaluations, offset, domain[5])
+    );
+}
+
 #[test]
 fn add() {
     let poly1: [BaseElement; 3] = [
@@ -174,6 +206,51 @@ fn div() {
     );
 }
 
+#[test]
+fn mul_fft() {
+    for (a_len, b_len) in [(1, 1), (3, 7), (64, 64), (100, 300), (1000, 1)] {
+        let a: Vec<BaseElement> = rand_vector(a_len);
+        let b: Vec<BaseElement> = rand_vector(b_len);
+        let expected = naive_mul(&a, &b);
+        assert_eq!(expected, super::mul_fft(&a, &b));
+        assert_eq!(expected, super::mul(&a, &b));
+    }
+
+    let a: Vec<QuadExtension<BaseElement>> = rand_vector(200);
+    let b: Vec<QuadExtension<BaseElement>> = rand_vector(100);
+    assert_eq!(naive_mul(&a, &b), super::mul(&a, &b));
+}
+
+#[test]
+fn div_fft() {
+    for (q_len, b_len) in [(1, 2), (5, 3), (100, 300), (300, 100), (1000, 64)] {
+        let q: Vec<BaseElement> = rand_vector(q_len);
+        let b: Vec<BaseElement> = rand_vector(b_len);
+        let r: Vec<BaseElement> = rand_vector(b_len - 1);
+        let a = super::add(&super::mul(&q, &b), &r);
+        assert_eq!(q, super::div_fft(&a, &b));
+        assert_eq!(q, super::div(&a, &b));
+        assert_eq!(r, super::rem(&a, &b));
+    }
+
+    // polynomials with leading zeros
+    let q: Vec<BaseElement> = rand_vector(150);
+    let mut b: Vec<BaseElement> = rand_vector(80);
+    let mut a = super::mul(&q, &b);
+    a.extend_from_slice(&[BaseElement::ZERO; 3]);
+    b.push(BaseElement::ZERO);
+    assert_eq!(q, super::div_fft(&a, &b));
+    assert_eq!(q, super::div(&a, &b));
+    assert_eq!(vec![BaseElement::ZERO; 79], super::rem(&a, &b));
+
+    // remainder of a polynomial of smaller degree is the polynomial itself
+    let a: Vec<BaseElement> = rand_vector(10);
+    let b: Vec<BaseElement> = rand_vector(20);
+    let mut expected = a.clone();
+    expected.resize(19, BaseElement::ZERO);
+    assert_eq!(expected, super::rem(&a, &b));
+}
+
 #[test]
 fn syn_div() {
     // ----- division by degree 1 polynomial ------------------------------------------------------
@@ -256,3 +333,49 @@ fn syn_div() {
     let result = super::syn_div(&poly, 4, root.exp(4));
     assert_eq!(poly, remove_leading_zeros(&super::mul(&result, &z_poly)));
 }
+
+#[test]
+fn eval_many_fast() {
+    for (num_coefficients, num_points) in [(10, 5), (100, 300), (1000, 257), (200, 1024)] {
+        let p: Vec<BaseElement> = rand_vector(num_coefficients);
+        let xs: Vec<BaseElement> = rand_vector(num_points);
+        assert_eq!(super::eval_many(&p, &xs), super::eval_many_fast(&p, &xs));
+    }
+
+    let p: Vec<QuadExtension<BaseElement>> = rand_vector(500);
+    let xs: Vec<QuadExtension<BaseElement>> = rand_vector(500);
+    assert_eq!(super::eval_many(&p, &xs), super::eval_many_fast(&p, &xs));
+}
+
+#[test]
+fn interpolate_fast() {
+    for n in [1, 20, 33, 300, 512] {
+        let xs: Vec<BaseElement> = rand_vector(n);
+        let ys: Vec<BaseElement> = rand_vector(n);
+        let p = super::interpolate_fast(&xs, &ys);
+        assert_eq!(super::interpolate(&xs, &ys, false), p);
+        assert_eq!(ys, super::eval_many(&p, &xs));
+    }
+}
+
+#[test]
+#[should_panic(expected = "X coordinates must be distinct")]
+fn interpolate_fast_duplicate_points() {
+    let mut xs: Vec<BaseElement> = rand_vector(100);
+    xs[50] = xs[10];
+    let ys: Vec<BaseElement> = rand_vector(100);
+    super::interpolate_fast(&xs, &ys);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn naive_mul<E: FieldElement>(a: &[E], b: &[E]) -> Vec<E> {
+    let mut result = vec![E::ZERO; a.len() + b.len() - 1];
+    for (i, &a) in a.iter().enumerate() {
+        for (j, &b) in b.iter().enumerate() {
+            result[i + j] += a * b;
+        }
+    }
+    result
+}
@@ -152,6 +152,88 @@ where
     iter_mut!(a).zip(b).for_each(|(a, &b)| *a += c.mul_base(b));
 }
 
+/// Computes a linear combination of `values` with successive powers of `alpha`.
+///
+/// More precisely, computes `values[0]` + `values[1]` * `alpha` + ... + `values[n - 1]` *
+/// `alpha`^(n - 1) using Horner's method. This is the same as evaluating a polynomial with
+/// coefficients `values` at `alpha`, and can be used to batch multiple values into a single
+/// value using a random `alpha`.
+///
+/// # Examples
+/// ```
+/// # use winter_math::linear_combination;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::{rand_value, rand_vector};
+/// let values: Vec<BaseElement> = rand_vector(16);
+/// let alpha: BaseElement = rand_value();
+///
+/// let expected = values
+///     .iter()
+///     .enumerate()
+///     .fold(BaseElement::ZERO, |acc, (i, &v)| acc + v * alpha.exp(i as u128));
+/// assert_eq!(expected, linear_combination(&values, alpha));
+/// ```
+pub fn linear_combination<E>(values: &[E], alpha: E) -> E
+where
+    E: FieldElement,
+{
+    values
+        .iter()
+        .rev()
+        .fold(E::ZERO, |acc, &value| acc * alpha + value)
+}
+
+/// Computes a linear combination of columns using the provided coefficients.
+///
+/// More precisely, computes `result[j]` = sum(`columns[i][j]` * `coefficients[i]`) for all `i`
+/// and `j`. The coefficients are usually drawn at random, in which case the combination batches
+/// all columns into a single column.
+///
+/// When `concurrent` feature is enabled, the computation is performed concurrently in multiple
+/// threads.
+///
+/// # Panics
+/// Panics if:
+/// * No columns were provided.
+/// * The number of coefficients is not equal to the number of columns.
+/// * Not all columns have the same length.
+///
+/// # Examples
+/// ```
+/// # use winter_math::random_linear_combo;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let columns: Vec<Vec<BaseElement>> = (0..4).map(|_| rand_vector(2048)).collect();
+/// let coefficients: Vec<BaseElement> = rand_vector(4);
+///
+/// let result = random_linear_combo(&columns, &coefficients);
+/// for (j, &r) in result.iter().enumerate() {
+///     let expected = columns
+///         .iter()
+///         .zip(&coefficients)
+///         .fold(BaseElement::ZERO, |acc, (c, &k)| acc + c[j] * k);
+///     assert_eq!(expected, r);
+/// }
+/// ```
+pub fn random_linear_combo<F, E>(columns: &[Vec<F>], coefficients: &[E]) -> Vec<E>
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+{
+    assert!(!columns.is_empty(), "at least one column must be provided");
+    assert_eq!(
+        columns.len(),
+        coefficients.len(),
+        "number of coefficients must be equal to the number of columns"
+    );
+
+    let mut result = E::zeroed_vector(columns[0].len());
+    for (column, &coefficient) in columns.iter().zip(coefficients) {
+        mul_acc(&mut result, column, coefficient);
+    }
+    result
+}
+
 /// Computes a multiplicative inverse of a sequence of elements using batch inversion method.
 ///
 /// Any ZEROs in the provided sequence are ignored.
@@ -187,6 +269,37 @@ where
     result
 }
 
+/// Replaces each element in the provided slice with its multiplicative inverse using batch
+/// inversion method.
+///
+/// Any ZEROs in the provided slice are left unchanged.
+///
+/// This is an in-place version of [batch_inversion()] which avoids allocating the result vector.
+/// When `concurrent` feature is enabled, the inversion is performed concurrently in multiple
+/// threads.
+///
+/// # Examples
+/// ```
+/// # use winter_math::batch_inversion_in_place;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(2048);
+/// let mut b = a.clone();
+/// batch_inversion_in_place(&mut b);
+///
+/// for (&a, &b) in a.iter().zip(b.iter()) {
+///     assert_eq!(a.inv(), b);
+/// }
+/// ```
+pub fn batch_inversion_in_place<E>(values: &mut [E])
+where
+    E: FieldElement,
+{
+    batch_iter_mut!(values, 1024, |batch: &mut [E], _batch_offset: usize| {
+        serial_batch_inversion_in_place(batch);
+    });
+}
+
 /// Returns base 2 logarithm of `n`, where `n` is a power of two.
 ///
 /// # Panics
@@ -236,3 +349,24 @@ fn serial_batch_inversion<E: FieldElement>(values: &[E], result: &mut [E]) {
         }
     }
 }
+
+fn serial_batch_inversion_in_place<E: FieldElement>(values: &mut [E]) {
+    let mut prefixes: Vec<E> = unsafe { uninit_vector(values.len()) };
+    let mut last = E::ONE;
+    for (prefix, &value) in prefixes.iter_mut().zip(values.iter()) {
+        *prefix = last;
+        if value != E::ZERO {
+            last *= value;
+        }
+    }
+
+    last = last.inv();
+
+    for (value, &prefix) in values.iter_mut().zip(prefixes.iter()).rev() {
+        if *value != E::ZERO {
+            let v = *value;
+            *value = prefix * last;
+            last *= v;
+        }
+    }
+}
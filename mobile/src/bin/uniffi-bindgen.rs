@@ -0,0 +1,8 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -0,0 +1,308 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains [UniFFI](https://mozilla.github.io/uniffi-rs/) bindings for Winterfell
+//! STARK verifier which make it possible to verify proofs in Kotlin (Android) and Swift (iOS)
+//! applications.
+//!
+//! Proofs are verified for computations registered in a global [AirRegistry] of the library.
+//! The Fibonacci examples from the `examples` crate are registered by default (see
+//! [examples::registry::EXAMPLE_AIR_IDS]).
+//!
+//! Proofs can also be generated via [prove()] function; however, since mobile devices have
+//! limited memory, proving is restricted to execution traces of at most [MAX_TRACE_SIZE] bytes.
+//!
+//! All functions exported by this crate are blocking, and thus, should be called from a
+//! background thread of the application.
+
+use core::fmt;
+use std::sync::RwLock;
+use winterfell::{
+    registry::{AirRegistry, RegistryError},
+    AcceptableOptions, Deserializable, StarkProof,
+};
+
+uniffi::setup_scaffolding!();
+
+#[cfg(test)]
+mod tests;
+
+// GLOBAL REGISTRY
+// ================================================================================================
+
+/// Computations which can be proven and verified via functions of this library; the registry is
+/// initialized with the example computations on first access.
+static REGISTRY: RwLock<Option<AirRegistry>> = RwLock::new(None);
+
+/// Invokes `f` with the global registry of this library, initializing the registry with example
+/// computations if needed.
+///
+/// Only a shared lock on the registry is held while `f` is executed, and thus, multiple proofs
+/// can be generated and verified concurrently.
+fn with_registry<T>(f: impl FnOnce(&AirRegistry) -> T) -> T {
+    // a panic while the registry is locked cannot leave the registry in an inconsistent state,
+    // and thus, poisoning can be ignored
+    let registry = REGISTRY.read().unwrap_or_else(|err| err.into_inner());
+    if let Some(registry) = registry.as_ref() {
+        return f(registry);
+    }
+    drop(registry);
+    with_registry_mut(|registry| f(registry))
+}
+
+/// Invokes `f` with an exclusive lock on the global registry of this library, initializing the
+/// registry with example computations if needed.
+fn with_registry_mut<T>(f: impl FnOnce(&mut AirRegistry) -> T) -> T {
+    let mut registry = REGISTRY.write().unwrap_or_else(|err| err.into_inner());
+    let registry = registry.get_or_insert_with(|| {
+        let mut registry = AirRegistry::new();
+        examples::registry::register_examples(&mut registry).expect("failed to register examples");
+        registry
+    });
+    f(registry)
+}
+
+/// Registers the computation handled by the specified `handler` under the specified `air_id`
+/// in the global registry of this library.
+///
+/// This function is not exported to Kotlin and Swift; it is meant to be called by crates which
+/// embed this crate to make their own computations verifiable by the applications.
+///
+/// # Errors
+/// Returns an error if a computation with the same identifier has already been registered.
+pub fn register_air_handler(
+    air_id: &str,
+    handler: Box<dyn winterfell::registry::AirHandler>,
+) -> Result<(), RegistryError> {
+    with_registry_mut(|registry| registry.register_handler(air_id, handler))
+}
+
+/// Returns identifiers of all registered computations.
+#[uniffi::export]
+pub fn air_ids() -> Vec<String> {
+    with_registry(|registry| registry.air_ids().into_iter().map(String::from).collect())
+}
+
+// VERIFICATION
+// ================================================================================================
+
+/// Verifies the serialized `proof` of the computation registered under `air_id` against public
+/// inputs serialized in `pub_inputs`, and returns conjectured security level of the proof (in
+/// bits).
+///
+/// # Errors
+/// Returns an error if the computation has not been registered, if the proof or the public
+/// inputs could not be deserialized, if the proof is not valid, or if conjectured security level
+/// of the proof is below `min_security_level`.
+#[uniffi::export]
+pub fn verify(
+    air_id: String,
+    proof: Vec<u8>,
+    pub_inputs: Vec<u8>,
+    min_security_level: u32,
+) -> Result<u32, WinterError> {
+    let proof = StarkProof::from_bytes(&proof).map_err(|err| WinterError::InvalidProof {
+        reason: err.to_string(),
+    })?;
+    let security_level = with_registry(|registry| -> Result<u32, RegistryError> {
+        let security_level = registry.security_level(&air_id, &proof)?;
+        let acceptable_options = AcceptableOptions::MinConjecturedSecurity(min_security_level);
+        registry.verify(&air_id, proof, &pub_inputs, &acceptable_options)?;
+        Ok(security_level)
+    })?;
+    Ok(security_level)
+}
+
+/// Returns conjectured security level (in bits) of the serialized `proof` as a proof of the
+/// computation registered under `air_id` without verifying the proof.
+///
+/// # Errors
+/// Returns an error if the computation has not been registered, or if the proof could not be
+/// deserialized.
+#[uniffi::export]
+pub fn security_level(air_id: String, proof: Vec<u8>) -> Result<u32, WinterError> {
+    let proof = StarkProof::from_bytes(&proof).map_err(|err| WinterError::InvalidProof {
+        reason: err.to_string(),
+    })?;
+    let security_level = with_registry(|registry| registry.security_level(&air_id, &proof))?;
+    Ok(security_level)
+}
+
+// PROVING
+// ================================================================================================
+
+/// Maximum size (in bytes) of a serialized execution trace accepted by [prove()].
+///
+/// A proof requires several times more memory than the trace it is generated for; this limit
+/// keeps peak memory usage of the prover at a few hundred megabytes.
+pub const MAX_TRACE_SIZE: u64 = 1 << 24;
+
+/// STARK protocol parameters; field extension is specified by its degree (i.e., 1, 2, 3, or 4).
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ProofOptions {
+    pub num_queries: u32,
+    pub blowup_factor: u32,
+    pub grinding_factor: u32,
+    pub field_extension: u32,
+    pub fri_folding_factor: u32,
+    pub fri_remainder_max_degree: u32,
+}
+
+impl TryFrom<ProofOptions> for winterfell::ProofOptions {
+    type Error = WinterError;
+
+    fn try_from(options: ProofOptions) -> Result<Self, WinterError> {
+        // all valid parameters fit into a byte, and thus, we can rely on the deserializer to
+        // validate the parameters
+        let params = [
+            options.num_queries,
+            options.blowup_factor,
+            options.grinding_factor,
+            options.field_extension,
+            options.fri_folding_factor,
+            options.fri_remainder_max_degree,
+        ];
+        let mut bytes = Vec::with_capacity(params.len());
+        for param in params {
+            let byte = u8::try_from(param).map_err(|_| WinterError::InvalidOptions {
+                reason: format!("invalid value {param}"),
+            })?;
+            bytes.push(byte);
+        }
+        let result = winterfell::ProofOptions::read_from_bytes(&bytes).map_err(|err| {
+            WinterError::InvalidOptions {
+                reason: err.to_string(),
+            }
+        })?;
+
+        // the deserializer also interprets spare bits of some parameters as flags of proof
+        // options extensions; parameters with such bits set are not valid
+        if plain_params(&result) != params {
+            return Err(WinterError::InvalidOptions {
+                reason: format!("invalid values {params:?}"),
+            });
+        }
+        Ok(result)
+    }
+}
+
+/// Returns the maximum size (in bytes) of a serialized execution trace accepted by [prove()].
+#[uniffi::export]
+pub fn max_trace_size() -> u64 {
+    MAX_TRACE_SIZE
+}
+
+/// Generates a proof of the computation registered under `air_id` for the execution trace
+/// serialized in `trace` and public inputs serialized in `pub_inputs`, and returns the
+/// serialized proof.
+///
+/// # Errors
+/// Returns an error if the trace is larger than [MAX_TRACE_SIZE] bytes, if the computation has
+/// not been registered, if the trace, the public inputs, or the options are invalid, or if the
+/// prover failed to generate the proof.
+#[uniffi::export]
+pub fn prove(
+    air_id: String,
+    trace: Vec<u8>,
+    pub_inputs: Vec<u8>,
+    options: ProofOptions,
+) -> Result<Vec<u8>, WinterError> {
+    if trace.len() as u64 > MAX_TRACE_SIZE {
+        return Err(WinterError::TraceTooLarge {
+            size: trace.len() as u64,
+            max_size: MAX_TRACE_SIZE,
+        });
+    }
+    let options = options.try_into()?;
+    let proof = with_registry(|registry| registry.prove(&air_id, &trace, &pub_inputs, options))?;
+    Ok(proof.to_bytes())
+}
+
+// ERRORS
+// ================================================================================================
+
+/// Represents an error returned by functions of this library; the errors are thrown as
+/// exceptions in Kotlin and Swift.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Error)]
+pub enum WinterError {
+    /// No computation has been registered under the specified identifier.
+    UnknownAir { air_id: String },
+    /// The proof could not be deserialized.
+    InvalidProof { reason: String },
+    /// The public inputs could not be deserialized.
+    InvalidPublicInputs { reason: String },
+    /// The execution trace could not be deserialized, or is inconsistent with the public inputs.
+    InvalidTrace { reason: String },
+    /// The proof options are not valid.
+    InvalidOptions { reason: String },
+    /// The execution trace is too large to be proven on a mobile device.
+    TraceTooLarge { size: u64, max_size: u64 },
+    /// The prover failed to generate the proof.
+    ProofGenerationFailed { reason: String },
+    /// The proof is not a valid proof of the computation for the specified public inputs.
+    VerificationFailed { reason: String },
+}
+
+impl fmt::Display for WinterError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAir { air_id } => write!(f, "computation {air_id} has not been registered"),
+            Self::InvalidProof { reason } => write!(f, "failed to read proof: {reason}"),
+            Self::InvalidPublicInputs { reason } => write!(f, "failed to read public inputs: {reason}"),
+            Self::InvalidTrace { reason } => write!(f, "invalid execution trace: {reason}"),
+            Self::InvalidOptions { reason } => write!(f, "invalid proof options: {reason}"),
+            Self::TraceTooLarge { size, max_size } => {
+                write!(f, "execution trace of {size} bytes exceeds the maximum of {max_size} bytes")
+            }
+            Self::ProofGenerationFailed { reason } => write!(f, "failed to generate proof: {reason}"),
+            Self::VerificationFailed { reason } => write!(f, "failed to verify proof: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for WinterError {}
+
+impl From<RegistryError> for WinterError {
+    fn from(err: RegistryError) -> Self {
+        match err {
+            RegistryError::UnknownAir(air_id) | RegistryError::DuplicateAir(air_id) => {
+                Self::UnknownAir { air_id }
+            }
+            RegistryError::InvalidExecutionTrace(err) => Self::InvalidTrace {
+                reason: err.to_string(),
+            },
+            RegistryError::InvalidPublicInputs(err) => Self::InvalidPublicInputs {
+                reason: err.to_string(),
+            },
+            RegistryError::InconsistentPublicInputs => Self::InvalidTrace {
+                reason: RegistryError::InconsistentPublicInputs.to_string(),
+            },
+            RegistryError::InsufficientMemory(required, limit) => Self::ProofGenerationFailed {
+                reason: RegistryError::InsufficientMemory(required, limit).to_string(),
+            },
+            RegistryError::ProverError(err) => Self::ProofGenerationFailed {
+                reason: err.to_string(),
+            },
+            RegistryError::VerifierError(err) => Self::VerificationFailed {
+                reason: err.to_string(),
+            },
+        }
+    }
+}
+
+/// Returns the parameters of the `ProofOptions` constructor which describe the specified options;
+/// field extension is specified by its degree.
+fn plain_params(options: &winterfell::ProofOptions) -> [u32; 6] {
+    let fri_options = options.to_fri_options();
+    [
+        options.num_queries() as u32,
+        options.blowup_factor() as u32,
+        options.grinding_factor(),
+        options.field_extension().degree(),
+        fri_options.folding_factor() as u32,
+        fri_options.remainder_max_degree() as u32,
+    ]
+}
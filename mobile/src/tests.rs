@@ -0,0 +1,87 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{air_ids, max_trace_size, prove, security_level, verify, ProofOptions, WinterError};
+use examples::fibonacci::fib2::FibProver;
+use winterfell::{
+    crypto::hashers::Blake3_256, math::fields::f128::BaseElement, Serializable, Trace, TraceTable,
+};
+
+const OPTIONS: ProofOptions = ProofOptions {
+    num_queries: 32,
+    blowup_factor: 8,
+    grinding_factor: 0,
+    field_extension: 1,
+    fri_folding_factor: 4,
+    fri_remainder_max_degree: 7,
+};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_and_verify() {
+    assert!(air_ids().contains(&"fib".to_string()));
+
+    let (trace, pub_inputs) = build_fib_inputs();
+    let proof = prove("fib".into(), trace, pub_inputs.clone(), OPTIONS).unwrap();
+
+    let level = verify("fib".into(), proof.clone(), pub_inputs.clone(), 0).unwrap();
+    assert!(level > 0);
+    assert_eq!(Ok(level), security_level("fib".into(), proof.clone()));
+
+    // verify with required security level above the security level of the proof
+    let err = verify("fib".into(), proof.clone(), pub_inputs.clone(), level + 1).unwrap_err();
+    assert!(matches!(err, WinterError::VerificationFailed { .. }));
+
+    // verify against wrong public inputs
+    let wrong_inputs = BaseElement::new(42).to_bytes();
+    let err = verify("fib".into(), proof.clone(), wrong_inputs, 0).unwrap_err();
+    assert!(matches!(err, WinterError::VerificationFailed { .. }));
+
+    // verify a truncated proof
+    let truncated_proof = proof[..proof.len() - 1].to_vec();
+    let err = verify("fib".into(), truncated_proof, pub_inputs, 0).unwrap_err();
+    assert!(matches!(err, WinterError::InvalidProof { .. }));
+}
+
+#[test]
+fn prove_invalid_inputs() {
+    let (trace, pub_inputs) = build_fib_inputs();
+
+    let err = prove("unknown".into(), trace.clone(), pub_inputs.clone(), OPTIONS).unwrap_err();
+    assert_eq!(
+        WinterError::UnknownAir {
+            air_id: "unknown".to_string()
+        },
+        err
+    );
+
+    let options = ProofOptions {
+        field_extension: 5,
+        ..OPTIONS
+    };
+    let err = prove("fib".into(), trace.clone(), pub_inputs, options).unwrap_err();
+    assert!(matches!(err, WinterError::InvalidOptions { .. }));
+
+    let wrong_inputs = BaseElement::new(42).to_bytes();
+    let err = prove("fib".into(), trace, wrong_inputs, OPTIONS).unwrap_err();
+    assert!(matches!(err, WinterError::InvalidTrace { .. }));
+
+    let large_trace = vec![0; max_trace_size() as usize + 1];
+    let err = prove("fib".into(), large_trace, Vec::new(), OPTIONS).unwrap_err();
+    assert!(matches!(err, WinterError::TraceTooLarge { .. }));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_fib_inputs() -> (Vec<u8>, Vec<u8>) {
+    let options = OPTIONS.try_into().unwrap();
+    let trace: TraceTable<BaseElement> =
+        FibProver::<Blake3_256<BaseElement>>::new(options).build_trace(64);
+    let result = trace.get(1, trace.length() - 1);
+    (trace.to_bytes(), result.to_bytes())
+}
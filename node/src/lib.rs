@@ -0,0 +1,279 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains N-API bindings for Winterfell STARK prover and verifier.
+//!
+//! Proofs are generated and verified for computations registered in a global [AirRegistry] of
+//! the addon. The Fibonacci examples from the `examples` crate are registered by default (see
+//! [examples::registry::EXAMPLE_AIR_IDS]).
+//!
+//! Unlike the WebAssembly bindings, the addon runs the native prover, and thus, proof
+//! generation is not limited by the size of WebAssembly memory. `prove()` and `verify()`
+//! functions run on the libuv thread pool and return promises, so that the event loop is not
+//! blocked while proofs are generated; synchronous versions of the functions are available as
+//! well.
+
+use napi::{bindgen_prelude::*, Task};
+use napi_derive::napi;
+use std::sync::RwLock;
+use winterfell::{registry::AirRegistry, AcceptableOptions, Deserializable, StarkProof};
+
+// GLOBAL REGISTRY
+// ================================================================================================
+
+/// Computations which can be proven and verified via functions of this addon; the registry is
+/// initialized with the example computations on first access.
+static REGISTRY: RwLock<Option<AirRegistry>> = RwLock::new(None);
+
+/// Invokes `f` with the global registry of this addon, initializing the registry with example
+/// computations if needed.
+///
+/// Only a shared lock on the registry is held while `f` is executed, and thus, multiple proofs
+/// can be generated and verified concurrently.
+fn with_registry<T>(f: impl FnOnce(&AirRegistry) -> T) -> T {
+    // a panic while the registry is locked cannot leave the registry in an inconsistent state,
+    // and thus, poisoning can be ignored
+    let registry = REGISTRY.read().unwrap_or_else(|err| err.into_inner());
+    if let Some(registry) = registry.as_ref() {
+        return f(registry);
+    }
+    drop(registry);
+    with_registry_mut(|registry| f(registry))
+}
+
+/// Invokes `f` with an exclusive lock on the global registry of this addon, initializing the
+/// registry with example computations if needed.
+fn with_registry_mut<T>(f: impl FnOnce(&mut AirRegistry) -> T) -> T {
+    let mut registry = REGISTRY.write().unwrap_or_else(|err| err.into_inner());
+    let registry = registry.get_or_insert_with(|| {
+        let mut registry = AirRegistry::new();
+        examples::registry::register_examples(&mut registry).expect("failed to register examples");
+        registry
+    });
+    f(registry)
+}
+
+/// Returns identifiers of all registered computations.
+#[napi]
+pub fn air_ids() -> Vec<String> {
+    with_registry(|registry| registry.air_ids().into_iter().map(String::from).collect())
+}
+
+// PROOF OPTIONS
+// ================================================================================================
+
+/// STARK protocol parameters; field extension is specified by its degree (i.e., 1, 2, 3, or 4).
+#[napi(object)]
+pub struct ProofOptions {
+    pub num_queries: u32,
+    pub blowup_factor: u32,
+    pub grinding_factor: u32,
+    pub field_extension: u32,
+    pub fri_folding_factor: u32,
+    pub fri_remainder_max_degree: u32,
+}
+
+impl TryFrom<ProofOptions> for winterfell::ProofOptions {
+    type Error = Error;
+
+    fn try_from(options: ProofOptions) -> Result<Self> {
+        // all valid parameters fit into a byte, and thus, we can rely on the deserializer to
+        // validate the parameters
+        let params = [
+            options.num_queries,
+            options.blowup_factor,
+            options.grinding_factor,
+            options.field_extension,
+            options.fri_folding_factor,
+            options.fri_remainder_max_degree,
+        ];
+        let mut bytes = Vec::with_capacity(params.len());
+        for param in params {
+            let byte = u8::try_from(param).map_err(|_| {
+                invalid_arg(format!("invalid proof options: invalid value {param}"))
+            })?;
+            bytes.push(byte);
+        }
+        let result = winterfell::ProofOptions::read_from_bytes(&bytes)
+            .map_err(|err| invalid_arg(format!("invalid proof options: {err}")))?;
+
+        // the deserializer also interprets spare bits of some parameters as flags of proof
+        // options extensions; parameters with such bits set are not valid
+        if plain_params(&result) != params {
+            return Err(invalid_arg(format!(
+                "invalid proof options: invalid values {params:?}"
+            )));
+        }
+        Ok(result)
+    }
+}
+
+// PROVING
+// ================================================================================================
+
+/// Generates a proof of the computation registered under `air_id` on the libuv thread pool.
+pub struct ProveTask {
+    air_id: String,
+    trace: Vec<u8>,
+    pub_inputs: Vec<u8>,
+    options: winterfell::ProofOptions,
+}
+
+impl Task for ProveTask {
+    type Output = Vec<u8>;
+    type JsValue = Buffer;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        generate_proof(
+            &self.air_id,
+            &self.trace,
+            &self.pub_inputs,
+            self.options.clone(),
+        )
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// Generates a proof of the computation registered under `air_id` for the execution trace
+/// serialized in `trace` and public inputs serialized in `pub_inputs`; the returned promise
+/// resolves to the serialized proof.
+#[napi(ts_return_type = "Promise<Buffer>")]
+pub fn prove(
+    air_id: String,
+    trace: Buffer,
+    pub_inputs: Buffer,
+    options: ProofOptions,
+) -> Result<AsyncTask<ProveTask>> {
+    Ok(AsyncTask::new(ProveTask {
+        air_id,
+        trace: trace.to_vec(),
+        pub_inputs: pub_inputs.to_vec(),
+        options: options.try_into()?,
+    }))
+}
+
+/// Generates a proof in the same way as `prove()`, but blocks the event loop until the proof
+/// is generated.
+#[napi]
+pub fn prove_sync(
+    air_id: String,
+    trace: Buffer,
+    pub_inputs: Buffer,
+    options: ProofOptions,
+) -> Result<Buffer> {
+    generate_proof(&air_id, &trace, &pub_inputs, options.try_into()?).map(Buffer::from)
+}
+
+// VERIFICATION
+// ================================================================================================
+
+/// Verifies a proof of the computation registered under `air_id` on the libuv thread pool.
+pub struct VerifyTask {
+    air_id: String,
+    proof: Vec<u8>,
+    pub_inputs: Vec<u8>,
+    min_security_level: u32,
+}
+
+impl Task for VerifyTask {
+    type Output = u32;
+    type JsValue = u32;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        verify_proof(
+            &self.air_id,
+            &self.proof,
+            &self.pub_inputs,
+            self.min_security_level,
+        )
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Verifies the serialized `proof` of the computation registered under `air_id` against public
+/// inputs serialized in `pub_inputs`; the returned promise resolves to conjectured security
+/// level of the proof (in bits), and is rejected if the proof is not valid or if its security
+/// level is below `min_security_level`.
+#[napi(ts_return_type = "Promise<number>")]
+pub fn verify(
+    air_id: String,
+    proof: Buffer,
+    pub_inputs: Buffer,
+    min_security_level: u32,
+) -> AsyncTask<VerifyTask> {
+    AsyncTask::new(VerifyTask {
+        air_id,
+        proof: proof.to_vec(),
+        pub_inputs: pub_inputs.to_vec(),
+        min_security_level,
+    })
+}
+
+/// Verifies a proof in the same way as `verify()`, but blocks the event loop until the proof
+/// is verified.
+#[napi]
+pub fn verify_sync(
+    air_id: String,
+    proof: Buffer,
+    pub_inputs: Buffer,
+    min_security_level: u32,
+) -> Result<u32> {
+    verify_proof(&air_id, &proof, &pub_inputs, min_security_level)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn generate_proof(
+    air_id: &str,
+    trace: &[u8],
+    pub_inputs: &[u8],
+    options: winterfell::ProofOptions,
+) -> Result<Vec<u8>> {
+    let proof = with_registry(|registry| registry.prove(air_id, trace, pub_inputs, options))
+        .map_err(|err| Error::from_reason(err.to_string()))?;
+    Ok(proof.to_bytes())
+}
+
+fn verify_proof(
+    air_id: &str,
+    proof: &[u8],
+    pub_inputs: &[u8],
+    min_security_level: u32,
+) -> Result<u32> {
+    let proof = StarkProof::from_bytes(proof)
+        .map_err(|err| invalid_arg(format!("failed to read proof: {err}")))?;
+    with_registry(|registry| {
+        let security_level = registry.security_level(air_id, &proof)?;
+        let acceptable_options = AcceptableOptions::MinConjecturedSecurity(min_security_level);
+        registry.verify(air_id, proof, pub_inputs, &acceptable_options)?;
+        Ok(security_level)
+    })
+    .map_err(|err: winterfell::registry::RegistryError| Error::from_reason(err.to_string()))
+}
+
+fn invalid_arg(message: String) -> Error {
+    Error::new(Status::InvalidArg, message)
+}
+
+/// Returns the parameters of the `ProofOptions` constructor which describe the specified options;
+/// field extension is specified by its degree.
+fn plain_params(options: &winterfell::ProofOptions) -> [u32; 6] {
+    let fri_options = options.to_fri_options();
+    [
+        options.num_queries() as u32,
+        options.blowup_factor() as u32,
+        options.grinding_factor(),
+        options.field_extension().degree(),
+        fri_options.folding_factor() as u32,
+        fri_options.remainder_max_degree() as u32,
+    ]
+}
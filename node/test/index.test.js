@@ -0,0 +1,30 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+const assert = require("node:assert");
+const test = require("node:test");
+const winterfell = require("..");
+
+const options = {
+  numQueries: 32,
+  blowupFactor: 8,
+  grindingFactor: 0,
+  fieldExtension: 1,
+  friFoldingFactor: 4,
+  friRemainderMaxDegree: 7,
+};
+
+test("example computations are registered", () => {
+  assert.ok(winterfell.airIds().includes("fib"));
+});
+
+test("invalid inputs are rejected", async () => {
+  assert.throws(() => winterfell.proveSync("fib", Buffer.alloc(0), Buffer.alloc(16), options));
+  assert.throws(() =>
+    winterfell.proveSync("fib", Buffer.alloc(0), Buffer.alloc(16), { ...options, blowupFactor: 3 })
+  );
+  await assert.rejects(winterfell.verify("fib", Buffer.alloc(16), Buffer.alloc(16), 100));
+  await assert.rejects(winterfell.verify("unknown", Buffer.alloc(16), Buffer.alloc(16), 100));
+});
@@ -4,17 +4,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{
-    proof::{Commitments, Context, OodFrame, Queries, StarkProof},
+    proof::{Commitments, Context, OodFrame, ProofMetadata, Queries, StarkProof},
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
-use fri::{self, FriProof};
-use math::{FieldElement, ToElements};
-use utils::collections::Vec;
+use crypto::{ElementHasher, QuerySampler, RandomCoin};
+use fri::{self, stir::StirProof, FriProof};
+use math::FieldElement;
+use utils::{collections::Vec, Serializable};
 
-#[cfg(feature = "concurrent")]
-use utils::iterators::*;
+use crate::{DefaultPowStrategy, PowStrategy};
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -32,6 +31,7 @@ where
     commitments: Commitments,
     ood_frame: OodFrame,
     pow_nonce: u64,
+    metadata: Option<ProofMetadata>,
     _field_element: PhantomData<E>,
 }
 
@@ -47,23 +47,35 @@ where
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new prover channel for the specified `air` and public inputs.
-    pub fn new(air: &'a A, mut pub_inputs_elements: Vec<A::BaseField>) -> Self {
+    /// Creates a new prover channel for the specified `air`, public inputs, and proof metadata.
+    ///
+    /// If the metadata is bound, a hash of the serialized metadata is absorbed into the public
+    /// coin right after the coin is instantiated.
+    pub fn new(
+        air: &'a A,
+        pub_inputs_elements: Vec<A::BaseField>,
+        metadata: Option<ProofMetadata>,
+    ) -> Self {
         let context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone());
 
         // build a seed for the public coin; the initial seed is a hash of the proof context and
         // the public inputs, but as the protocol progresses, the coin will be reseeded with the
         // info sent to the verifier
-        let mut coin_seed_elements = context.to_elements();
-        coin_seed_elements.append(&mut pub_inputs_elements);
+        let coin_seed_elements = context.get_public_coin_seed(&pub_inputs_elements);
+
+        let mut public_coin = R::new(&coin_seed_elements);
+        if let Some(metadata) = metadata.as_ref().filter(|metadata| metadata.is_bound()) {
+            public_coin.reseed(H::hash(&metadata.to_bytes()));
+        }
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed_elements),
+            public_coin,
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
             pow_nonce: 0,
+            metadata,
             _field_element: PhantomData,
         }
     }
@@ -91,7 +103,7 @@ where
     }
 
     /// Saves the evaluations of constraint composition polynomial columns at the out-of-domain
-    /// point. This also reseeds the public coin wit the hash of the evaluations.
+    /// points. This also reseeds the public coin wit the hash of the evaluations.
     pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
         self.ood_frame.set_constraint_evaluations(evaluations);
         self.public_coin.reseed(H::hash_elements(evaluations));
@@ -119,9 +131,18 @@ where
             .expect("failed to draw composition coefficients")
     }
 
-    /// Returns an out-of-domain point drawn uniformly at random from the public coin.
-    pub fn get_ood_point(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw OOD point")
+    /// Returns out-of-domain points drawn uniformly at random from the public coin.
+    ///
+    /// The number of points is specified by the proof options; all points are drawn one after
+    /// another before any evaluations at these points are sent to the verifier.
+    pub fn get_ood_points(&mut self) -> Vec<E> {
+        (0..self.context.options().num_ood_points())
+            .map(|_| {
+                self.public_coin
+                    .draw_ext()
+                    .expect("failed to draw OOD point")
+            })
+            .collect()
     }
 
     /// Returns a set of coefficients for constructing a DEEP composition polynomial.
@@ -136,30 +157,30 @@ where
     /// Returns a set of positions in the LDE domain against which the evaluations of trace and
     /// constraint composition polynomials should be queried.
     ///
-    /// The positions are drawn from the public coin uniformly at random.
+    /// The positions are drawn from the public coin uniformly at random using the query sampling
+    /// method specified in the proof options, and are listed in the order in which they were
+    /// drawn.
     pub fn get_query_positions(&mut self) -> Vec<usize> {
         let num_queries = self.context.options().num_queries();
         let lde_domain_size = self.context.lde_domain_size();
-        self.public_coin
-            .draw_integers(num_queries, lde_domain_size)
+        self.context
+            .options()
+            .query_sampling()
+            .draw_positions(&mut self.public_coin, num_queries, lde_domain_size)
             .expect("failed to draw query position")
     }
 
     /// Determines a nonce, which when hashed with the current seed of the public coin results
     /// in a new seed with the number of leading zeros equal to the grinding_factor specified
-    /// in the proof options.
-    pub fn grind_query_seed(&mut self) {
+    /// in the proof options; the nonce is searched for using the specified `strategy`.
+    pub fn grind_query_seed(&mut self, strategy: &dyn PowStrategy) {
         let grinding_factor = self.context.options().grinding_factor();
 
-        #[cfg(not(feature = "concurrent"))]
-        let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
-
-        #[cfg(feature = "concurrent")]
-        let nonce = (1..u64::MAX)
-            .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+        let public_coin = &self.public_coin;
+        let nonce = strategy
+            .find_nonce(grinding_factor, &|nonce| {
+                public_coin.check_leading_zeros(nonce)
+            })
             .expect("nonce not found");
 
         self.pow_nonce = nonce;
@@ -170,11 +191,15 @@ where
     // --------------------------------------------------------------------------------------------
     /// Builds a proof from the previously committed values as well as values passed into
     /// this method.
+    ///
+    /// When STIR is used as the low-degree test, `fri_proof` is expected to be an empty
+    /// placeholder, and `stir_proof` contains the low-degree proof.
     pub fn build_proof(
         self,
         trace_queries: Vec<Queries>,
         constraint_queries: Queries,
         fri_proof: FriProof,
+        stir_proof: Option<StirProof>,
     ) -> StarkProof {
         StarkProof {
             context: self.context,
@@ -183,7 +208,9 @@ where
             trace_queries,
             constraint_queries,
             fri_proof,
+            stir_proof,
             pow_nonce: self.pow_nonce,
+            metadata: self.metadata,
         }
     }
 }
@@ -210,4 +237,59 @@ where
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    /// Determines a proof-of-work nonce for a FRI layer using [DefaultPowStrategy].
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let public_coin = &self.public_coin;
+        let nonce = DefaultPowStrategy
+            .find_nonce(grinding_factor, &|nonce| {
+                public_coin.check_leading_zeros(nonce)
+            })
+            .expect("nonce not found");
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
+}
+
+// STIR PROVER CHANNEL IMPLEMENTATION
+// ================================================================================================
+
+impl<'a, A, E, H, R> fri::stir::StirProverChannel<E> for ProverChannel<'a, A, E, H, R>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+{
+    type Hasher = H;
+
+    /// Reseeds the public coin with the commitment to a STIR round; the commitment itself is
+    /// a part of the STIR proof.
+    fn commit_stir_round(&mut self, commitment: H::Digest) {
+        self.public_coin.reseed(commitment);
+    }
+
+    /// Reseeds the public coin with the hash of the out-of-domain answer.
+    fn send_ood_answer(&mut self, answer: E) {
+        self.public_coin.reseed(H::hash_elements(&[answer]));
+    }
+
+    /// Reseeds the public coin with the hash of the final polynomial.
+    fn send_final_poly(&mut self, poly: &[E]) {
+        self.public_coin.reseed(H::hash_elements(poly));
+    }
+
+    /// Returns a new STIR challenge drawn from the public coin.
+    fn draw_stir_challenge(&mut self) -> E {
+        self.public_coin
+            .draw()
+            .expect("failed to draw STIR challenge")
+    }
+
+    /// Returns positions of STIR shift queries drawn from the public coin.
+    fn draw_stir_queries(&mut self, num_queries: usize, domain_size: usize) -> Vec<usize> {
+        self.public_coin
+            .draw_integers(num_queries, domain_size)
+            .expect("failed to draw STIR query positions")
+    }
 }
@@ -5,7 +5,9 @@
 
 use super::{constraints::CompositionPoly, StarkDomain, TracePolyTable};
 use air::DeepCompositionCoefficients;
-use math::{add_in_place, fft, mul_acc, polynom, ExtensionOf, FieldElement, StarkField};
+use math::{
+    add_in_place, fft, mul_acc, polynom, random_linear_combo, ExtensionOf, FieldElement, StarkField,
+};
 use utils::{collections::Vec, iter_mut};
 
 #[cfg(feature = "concurrent")]
@@ -16,20 +18,24 @@ use utils::iterators::*;
 pub struct DeepCompositionPoly<E: FieldElement> {
     coefficients: Vec<E>,
     cc: DeepCompositionCoefficients<E>,
-    z: E,
+    ood_points: Vec<E>,
 }
 
 impl<E: FieldElement> DeepCompositionPoly<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new DEEP composition polynomial. Initially, this polynomial will be empty, and
-    /// the intent is to populate the coefficients via add_trace_polys() and add_constraint_polys()
-    /// methods.
-    pub fn new(z: E, cc: DeepCompositionCoefficients<E>) -> Self {
+    /// Returns a new DEEP composition polynomial for the specified out-of-domain points.
+    /// Initially, this polynomial will be empty, and the intent is to populate the coefficients
+    /// via add_trace_polys() and add_constraint_polys() methods.
+    pub fn new(ood_points: Vec<E>, cc: DeepCompositionCoefficients<E>) -> Self {
+        assert!(
+            !ood_points.is_empty(),
+            "at least one OOD point must be provided"
+        );
         DeepCompositionPoly {
             coefficients: vec![],
             cc,
-            z,
+            ood_points,
         }
     }
 
@@ -58,24 +64,31 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///   combination as T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is
     ///   the coefficient for the random linear combination drawn from the public coin.
     ///
-    /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_trace_state`
-    /// parameter.
+    /// If more than one out-of-domain point is used, the above is done for every point z_j, and
+    /// the resulting polynomials are added together.
+    ///
+    /// Note that evaluations of T_i(z_j) and T_i(z_j * g) are passed in via the
+    /// `ood_trace_states` parameter; these are expected to contain two rows for every point z_j.
     pub fn add_trace_polys(
         &mut self,
         trace_polys: TracePolyTable<E>,
         ood_trace_states: Vec<Vec<E>>,
     ) {
         assert!(self.coefficients.is_empty());
+        assert_eq!(2 * self.ood_points.len(), ood_trace_states.len());
 
-        // compute a second out-of-domain point offset from z by exactly trace generator; this
-        // point defines the "next" computation state in relation to point z
+        // compute out-of-domain points offset from each z_j by exactly trace generator; these
+        // points define the "next" computation state in relation to points z_j
         let trace_length = trace_polys.poly_size();
         let g = E::from(E::BaseField::get_root_of_unity(trace_length.ilog2()));
-        let next_z = self.z * g;
+        let divisors = self
+            .ood_points
+            .iter()
+            .flat_map(|&z| [z, z * g])
+            .collect::<Vec<_>>();
 
-        // combine trace polynomials into 2 composition polynomials T'(x) and T''(x)
-        let mut t1_composition = E::zeroed_vector(trace_length);
-        let mut t2_composition = E::zeroed_vector(trace_length);
+        // combine trace polynomials into 2 composition polynomials T'(x) and T''(x) for each z_j
+        let mut compositions = vec![E::zeroed_vector(trace_length); ood_trace_states.len()];
 
         // index of a trace polynomial; we declare it here so that we can maintain index continuity
         // across all trace segments
@@ -83,55 +96,35 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
         // --- merge polynomials of the main trace segment ----------------------------------------
         for poly in trace_polys.main_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states[0][i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states[1][i],
-                self.cc.trace[i],
-            );
+            // compute T'(x) = T(x) - T(z_j) and T''(x) = T(x) - T(z_j * g), multiply them by a
+            // pseudo-random coefficient, and add the results into composition polynomials
+            for (composition, ood_state) in compositions.iter_mut().zip(ood_trace_states.iter()) {
+                acc_trace_poly::<E::BaseField, E>(
+                    composition,
+                    poly,
+                    ood_state[i],
+                    self.cc.trace[i],
+                );
+            }
 
             i += 1;
         }
 
         // --- merge polynomials of the auxiliary trace segments ----------------------------------
         for poly in trace_polys.aux_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states[0][i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states[1][i],
-                self.cc.trace[i],
-            );
+            // compute T'(x) = T(x) - T(z_j) and T''(x) = T(x) - T(z_j * g), multiply them by a
+            // pseudo-random coefficient, and add the results into composition polynomials
+            for (composition, ood_state) in compositions.iter_mut().zip(ood_trace_states.iter()) {
+                acc_trace_poly::<E, E>(composition, poly, ood_state[i], self.cc.trace[i]);
+            }
 
             i += 1;
         }
 
-        // divide the composition polynomials by (x - z) and (x - z * g), respectively,
+        // divide the composition polynomials by (x - z_j) and (x - z_j * g), respectively,
         // and add the resulting polynomials together; the output of this step
         // is a single trace polynomial T(x) and deg(T(x)) = trace_length - 2.
-        let trace_poly =
-            merge_trace_compositions(vec![t1_composition, t2_composition], vec![self.z, next_z]);
+        let trace_poly = merge_trace_compositions(compositions, divisors);
 
         // set the coefficients of the DEEP composition polynomial
         self.coefficients = trace_poly;
@@ -140,17 +133,20 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
     // CONSTRAINT POLYNOMIAL COMPOSITION
     // --------------------------------------------------------------------------------------------
-    /// Divides out OOD point z from the constraint composition polynomial and saves the result
+    /// Divides out OOD points z_j from the constraint composition polynomial and saves the result
     /// into the DEEP composition polynomial. This method is intended to be called only after the
     /// add_trace_polys() method has been executed. The composition is done as follows:
     ///
-    /// - For each H_i(x), compute H'_i(x) = (H_i(x) - H(z)) / (x - z), where H_i(x) is the
-    ///   ith composition polynomial column.
-    /// - Then, combine all H_i(x) polynomials together by computing H(x) = sum(H_i(x) * cc_i) for
-    ///   all i, where cc_i is the coefficient for the random linear combination drawn from the
-    ///   public coin.
+    /// - Combine all H_i(x) polynomials together by computing H(x) = sum(H_i(x) * cc_i) for all
+    ///   i, where H_i(x) is the ith composition polynomial column and cc_i is the coefficient for
+    ///   the random linear combination drawn from the public coin.
+    /// - Then, for each z_j, compute H'_j(x) = (H(x) - H(z_j)) / (x - z_j), and add all H'_j(x)
+    ///   polynomials together. Since division by (x - z_j) is linear, this is the same as
+    ///   dividing out z_j from every column polynomial individually.
     ///
-    /// Note that evaluations of H_i(x) at z are passed in via the `ood_evaluations` parameter.
+    /// Note that evaluations of H_i(x) at z_j are passed in via the `ood_evaluations` parameter;
+    /// these are expected to contain evaluations of all columns at z_1, followed by evaluations
+    /// of all columns at z_2 etc.
     pub fn add_composition_poly(
         &mut self,
         composition_poly: CompositionPoly<E>,
@@ -158,22 +154,29 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ) {
         assert!(!self.coefficients.is_empty());
 
-        let z = self.z;
-
-        let mut column_polys = composition_poly.into_columns();
-
-        // Divide out the OOD point z from column polynomials
-        iter_mut!(column_polys)
-            .zip(ood_evaluations)
-            .for_each(|(poly, value_at_z)| {
-                // compute H'_i(x) = (H_i(x) - H_i(z)) / (x - z)
-                poly[0] -= value_at_z;
-                polynom::syn_div_in_place(poly, 1, z);
-            });
-
-        // add H'_i(x) * cc_i for all i into the DEEP composition polynomial
-        for (i, poly) in column_polys.into_iter().enumerate() {
-            mul_acc::<E, E>(&mut self.coefficients, &poly, self.cc.constraints[i]);
+        let num_columns = self.cc.constraints.len();
+        assert_eq!(num_columns * self.ood_points.len(), ood_evaluations.len());
+
+        // compute H(x) = sum(H_i(x) * cc_i) for all i
+        let column_polys = composition_poly.into_columns();
+        let composition = random_linear_combo::<E, E>(&column_polys, &self.cc.constraints);
+
+        // divide out each OOD point z_j from H(x) and add the results into the DEEP composition
+        // polynomial
+        for (&z, values_at_z) in self
+            .ood_points
+            .iter()
+            .zip(ood_evaluations.chunks(num_columns))
+        {
+            // compute H'_j(x) = (H(x) - H(z_j)) / (x - z_j)
+            let value_at_z = values_at_z
+                .iter()
+                .zip(self.cc.constraints.iter())
+                .fold(E::ZERO, |acc, (&value, &cc)| acc + value * cc);
+            let mut poly = composition.clone();
+            poly[0] -= value_at_z;
+            polynom::syn_div_in_place(&mut poly, 1, z);
+            add_in_place(&mut self.coefficients, &poly);
         }
         assert_eq!(self.poly_size() - 2, self.degree());
     }
@@ -5,8 +5,8 @@
 
 use super::StarkDomain;
 use air::{Air, AuxTraceRandElements, ConstraintDivisor};
-use math::{fft, ExtensionOf, FieldElement};
-use utils::collections::{BTreeMap, Vec};
+use math::{fft::FftPlanner, ExtensionOf, FieldElement};
+use utils::collections::Vec;
 
 // CONSTANTS
 // ================================================================================================
@@ -39,20 +39,18 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         // get constraints from the AIR instance
         let source = air.get_boundary_constraints(aux_rand_elements, composition_coefficients);
 
-        // initialize a map of twiddles here so that we can keep track of already computed
+        // initialize an FFT planner here so that we can keep track of already computed
         // twiddles; this helps us avoid building twiddles over and over again for constraints
         // defined over the same domain. twiddles are relevant only for large polynomial
         // constraints.
-        let mut twiddle_map = BTreeMap::new();
+        let mut planner = FftPlanner::new();
 
         // transform constraints against the main segment of the execution trace into specialized
         // constraints
         let mut result = source
             .main_constraints()
             .iter()
-            .map(|group| {
-                BoundaryConstraintGroup::from_main_constraints(group, air, &mut twiddle_map)
-            })
+            .map(|group| BoundaryConstraintGroup::from_main_constraints(group, air, &mut planner))
             .collect::<Vec<BoundaryConstraintGroup<E>>>();
 
         // transform constraints against auxiliary trace segments (if any) into specialized
@@ -62,10 +60,10 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         // that we always end up with a single constraint group for the same divisor.
         for group in source.aux_constraints() {
             match result.iter_mut().find(|g| &g.divisor == group.divisor()) {
-                Some(x) => x.add_aux_constraints(group, air, &mut twiddle_map),
+                Some(x) => x.add_aux_constraints(group, air, &mut planner),
                 None => {
                     let group =
-                        BoundaryConstraintGroup::from_aux_constraints(group, air, &mut twiddle_map);
+                        BoundaryConstraintGroup::from_aux_constraints(group, air, &mut planner);
                     result.push(group);
                 }
             };
@@ -159,12 +157,12 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     /// the main segment of an execution trace. Constraints against auxiliary trace segment in this
     /// group will be empty.
     ///
-    /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
+    /// FFT planner and [Air] instance are passed in for evaluating large polynomial constraints
     /// (if any).
     pub fn from_main_constraints<A: Air<BaseField = E::BaseField>>(
         source: &air::BoundaryConstraintGroup<E::BaseField, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        planner: &mut FftPlanner<E::BaseField>,
     ) -> Self {
         let mut result = Self::new(source.divisor().clone());
 
@@ -176,7 +174,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
                 let constraint = SmallPolyConstraint::new(constraint);
                 result.main_small_poly.push(constraint);
             } else {
-                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map);
+                let constraint = LargePolyConstraint::new(constraint, air, planner);
                 result.main_large_poly.push(constraint);
             }
         }
@@ -188,15 +186,15 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     /// auxiliary segments of an execution trace. Constraints against the main trace segment in this
     /// group will be empty.
     ///
-    /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
+    /// FFT planner and [Air] instance are passed in for evaluating large polynomial constraints
     /// (if any).
     pub fn from_aux_constraints<A: Air<BaseField = E::BaseField>>(
         group: &air::BoundaryConstraintGroup<E, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        planner: &mut FftPlanner<E::BaseField>,
     ) -> Self {
         let mut result = Self::new(group.divisor().clone());
-        result.add_aux_constraints(group, air, twiddle_map);
+        result.add_aux_constraints(group, air, planner);
         result
     }
 
@@ -206,7 +204,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     /// Adds the provided constraints against auxiliary segments of an execution trace to this
     /// group.
     ///
-    /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
+    /// FFT planner and [Air] instance are passed in for evaluating large polynomial constraints
     /// (if any).
     ///
     /// # Panics
@@ -215,7 +213,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
         &mut self,
         group: &air::BoundaryConstraintGroup<E, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        planner: &mut FftPlanner<E::BaseField>,
     ) {
         assert_eq!(
             group.divisor(),
@@ -231,7 +229,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
                 let constraint = SmallPolyConstraint::new(constraint);
                 self.aux_small_poly.push(constraint);
             } else {
-                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map);
+                let constraint = LargePolyConstraint::new(constraint, air, planner);
                 self.aux_large_poly.push(constraint);
             }
         }
@@ -412,25 +410,18 @@ where
     pub fn new<A: Air<BaseField = F::BaseField>>(
         source: &air::BoundaryConstraint<F, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
+        planner: &mut FftPlanner<F::BaseField>,
     ) -> Self {
         debug_assert!(
             source.poly().len() >= SMALL_POLY_DEGREE,
             "not a large poly constraint"
         );
         // evaluate the polynomial over the entire constraint evaluation domain; first
-        // get twiddles for the evaluation; if twiddles haven't been built yet, build them
+        // get an FFT plan for the evaluation; if the plan hasn't been built yet, build it
         let poly_length = source.poly().len();
-        let twiddles = twiddle_map
-            .entry(poly_length)
-            .or_insert_with(|| fft::get_twiddles(poly_length));
-
-        let values = fft::evaluate_poly_with_offset(
-            source.poly(),
-            twiddles,
-            air.domain_offset(),
-            air.ce_domain_size() / poly_length,
-        );
+        let values = planner
+            .plan(poly_length, air.domain_offset())
+            .evaluate(source.poly(), air.ce_domain_size() / poly_length);
 
         LargePolyConstraint {
             column: source.column(),
@@ -4,10 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::RowMatrix;
+use crate::matrix::RowCommitment;
 use air::proof::Queries;
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, MerkleTree, VectorCommitment};
 use math::FieldElement;
-use utils::collections::Vec;
 
 // CONSTRAINT COMMITMENT
 // ================================================================================================
@@ -16,20 +16,32 @@ use utils::collections::Vec;
 ///
 /// The commitment consists of two components:
 /// * Evaluations of composition polynomial columns over the LDE domain.
-/// * Merkle tree where each leaf in the tree corresponds to a row in the composition polynomial
-///   evaluation matrix.
-pub struct ConstraintCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+/// * Vector commitment (a Merkle tree by default) where each committed item corresponds to a
+///   (possibly salted) row in the composition polynomial evaluation matrix.
+pub struct ConstraintCommitment<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
     evaluations: RowMatrix<E>,
-    commitment: MerkleTree<H>,
+    commitment: RowCommitment<H, V>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintCommitment<E, H> {
+impl<E, H, V> ConstraintCommitment<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
     /// Creates a new constraint evaluation commitment from the provided composition polynomial
-    /// evaluations and the corresponding Merkle tree commitment.
-    pub fn new(evaluations: RowMatrix<E>, commitment: MerkleTree<H>) -> ConstraintCommitment<E, H> {
+    /// evaluations and the corresponding vector commitment.
+    pub fn new(
+        evaluations: RowMatrix<E>,
+        commitment: RowCommitment<H, V>,
+    ) -> ConstraintCommitment<E, H, V> {
         assert_eq!(
             evaluations.num_rows(),
-            commitment.leaves().len(),
+            commitment.num_leaves(),
             "number of rows in constraint evaluation matrix must be the same as number of leaves in constraint commitment"
         );
         ConstraintCommitment {
@@ -38,33 +50,20 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
         }
     }
 
-    /// Returns the root of the commitment Merkle tree.
+    /// Returns the root of the commitment (e.g., the root of a Merkle tree).
     pub fn root(&self) -> H::Digest {
-        *self.commitment.root()
+        self.commitment.root()
     }
 
-    /// Returns the depth of the commitment Merkle tree.
+    /// Returns the depth of the commitment (e.g., the depth of a Merkle tree).
     #[allow(unused)]
     pub fn tree_depth(&self) -> usize {
         self.commitment.depth()
     }
 
-    /// Returns constraint evaluations at the specified positions along with Merkle authentication
-    /// paths from the root of the commitment to these evaluations.
+    /// Returns constraint evaluations at the specified positions along with a proof of opening
+    /// these evaluations against the commitment (e.g., Merkle authentication paths).
     pub fn query(self, positions: &[usize]) -> Queries {
-        // build Merkle authentication paths to the leaves specified by positions
-        let merkle_proof = self
-            .commitment
-            .prove_batch(positions)
-            .expect("failed to generate a Merkle proof for constraint queries");
-
-        // determine a set of evaluations corresponding to each position
-        let mut evaluations = Vec::new();
-        for &position in positions {
-            let row = self.evaluations.row(position).to_vec();
-            evaluations.push(row);
-        }
-
-        Queries::new(merkle_proof, evaluations)
+        self.commitment.query(&self.evaluations, positions)
     }
 }
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::ColMatrix;
-use math::{polynom::degree_of, FieldElement};
+use math::{fft::ComputeBackend, polynom::degree_of, FieldElement};
 use utils::collections::Vec;
 
 // COMPOSITION POLYNOMIAL
@@ -65,6 +65,18 @@ impl<E: FieldElement> CompositionPoly<E> {
         self.data.evaluate_columns_at(z)
     }
 
+    /// Returns evaluations of all composition polynomial columns at each of the points in `zs`
+    /// computed using the specified compute backend.
+    ///
+    /// The evaluations are listed point by point; that is, evaluations of all columns at `zs[0]`
+    /// are followed by evaluations of all columns at `zs[1]` etc.
+    pub fn evaluate_at_with_backend(&self, zs: &[E], backend: &dyn ComputeBackend<E>) -> Vec<E> {
+        let evaluations = self.data.evaluate_columns_at_with_backend(zs, backend);
+        (0..zs.len())
+            .flat_map(|j| evaluations.iter().map(move |column| column[j]))
+            .collect()
+    }
+
     /// Returns a reference to the matrix of individual column polynomials.
     pub fn data(&self) -> &ColMatrix<E> {
         &self.data
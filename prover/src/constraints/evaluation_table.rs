@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{CompositionPoly, ConstraintDivisor, ProverError, StarkDomain};
-use math::{batch_inversion, fft, FieldElement, StarkField};
+use math::{fft, FieldElement, StarkField};
 use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
 
 #[cfg(debug_assertions)]
@@ -104,7 +104,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     /// Break the table into the number of specified fragments. All fragments can be updated
     /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<E>> {
+    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<'_, E>> {
         let fragment_size = self.num_rows() / num_fragments;
         assert!(
             fragment_size >= MIN_FRAGMENT_SIZE,
@@ -407,7 +407,8 @@ fn get_inv_evaluation<B: StarkField>(
     );
 
     // compute 1 / (x^a - b)
-    batch_inversion(&evaluations)
+    B::batch_inverse_in_place(&mut evaluations);
+    evaluations
 }
 
 // DEBUG HELPERS
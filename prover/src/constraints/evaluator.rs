@@ -296,7 +296,7 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
 
         // evaluate transition constraints over auxiliary trace segments and save the results into
         // evaluations buffer
-        self.air.evaluate_aux_transition(
+        self.air.evaluate_aux_transition_with_lookups(
             main_frame,
             aux_frame,
             periodic_values,
@@ -4,11 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::Air;
-use math::{fft, StarkField};
-use utils::{
-    collections::{BTreeMap, Vec},
-    uninit_vector,
-};
+use math::{fft::FftPlanner, StarkField};
+use utils::{collections::Vec, uninit_vector};
 
 pub struct PeriodicValueTable<B: StarkField> {
     values: Vec<B>,
@@ -38,9 +35,9 @@ impl<B: StarkField> PeriodicValueTable<B> {
         // because if we get here, there must be at least one polynomial in the set.
         let max_poly_size = polys.iter().max_by_key(|p| p.len()).unwrap().len();
 
-        // cache twiddles used for polynomial evaluation here so that we don't have to re-build
-        // them for polynomials of the same size
-        let mut twiddle_map = BTreeMap::new();
+        // cache FFT plans used for polynomial evaluation here so that we don't have to re-build
+        // twiddles for polynomials of the same size
+        let mut planner = FftPlanner::new();
 
         let evaluations = polys
             .iter()
@@ -48,11 +45,9 @@ impl<B: StarkField> PeriodicValueTable<B> {
                 let poly_size = poly.len();
                 let num_cycles = (air.trace_length() / poly_size) as u64;
                 let offset = air.domain_offset().exp(num_cycles.into());
-                let twiddles = twiddle_map
-                    .entry(poly_size)
-                    .or_insert_with(|| fft::get_twiddles(poly_size));
-
-                fft::evaluate_poly_with_offset(poly, twiddles, offset, air.ce_blowup_factor())
+                planner
+                    .plan(poly_size, offset)
+                    .evaluate(poly, air.ce_blowup_factor())
             })
             .collect::<Vec<_>>();
 
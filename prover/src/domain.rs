@@ -4,16 +4,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::Air;
-use math::{fft, get_power_series, StarkField};
+use math::{fft::FftPlan, Domain, StarkField};
 use utils::collections::Vec;
 
 // TYPES AND INTERFACES
 // ================================================================================================
 
 pub struct StarkDomain<B: StarkField> {
-    /// Twiddles which can be used to evaluate polynomials in the trace domain. Length of this
-    /// vector is half the length of the trace domain size.
-    trace_twiddles: Vec<B>,
+    /// FFT plan for the trace domain; this contains twiddles and inverse twiddles which can be
+    /// used to evaluate and interpolate polynomials in the trace domain.
+    trace_plan: FftPlan<B>,
 
     /// [g^i for i in (0..ce_domain_size)] where g is the constraint evaluation domain generator.
     ce_domain: Vec<B>,
@@ -36,14 +36,13 @@ pub struct StarkDomain<B: StarkField> {
 impl<B: StarkField> StarkDomain<B> {
     /// Returns a new STARK domain initialized with the provided `context`.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
-        let trace_twiddles = fft::get_twiddles(air.trace_length());
+        let trace_plan = FftPlan::new(air.trace_length(), B::ONE);
 
         // build constraint evaluation domain
-        let domain_gen = B::get_root_of_unity(air.ce_domain_size().ilog2());
-        let ce_domain = get_power_series(domain_gen, air.ce_domain_size());
+        let ce_domain = Domain::new(air.ce_domain_size()).to_vec();
 
         StarkDomain {
-            trace_twiddles,
+            trace_plan,
             ce_domain,
             ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
             ce_domain_mod_mask: air.ce_domain_size() - 1,
@@ -64,11 +63,10 @@ impl<B: StarkField> StarkDomain<B> {
         );
 
         let ce_domain_size = trace_twiddles.len() * blowup_factor * 2;
-        let domain_gen = B::get_root_of_unity(ce_domain_size.ilog2());
-        let ce_domain = get_power_series(domain_gen, ce_domain_size);
+        let ce_domain = Domain::new(ce_domain_size).to_vec();
 
         StarkDomain {
-            trace_twiddles,
+            trace_plan: FftPlan::from_twiddles(trace_twiddles, B::ONE),
             ce_domain,
             ce_to_lde_blowup: 1,
             ce_domain_mod_mask: ce_domain_size - 1,
@@ -81,12 +79,24 @@ impl<B: StarkField> StarkDomain<B> {
 
     /// Returns length of the execution trace for this computation.
     pub fn trace_length(&self) -> usize {
-        &self.trace_twiddles.len() * 2
+        self.trace_plan.domain_size()
+    }
+
+    /// Returns the trace domain, i.e., the multiplicative subgroup of size equal to the length
+    /// of the execution trace.
+    pub fn trace_domain(&self) -> Domain<B> {
+        Domain::new(self.trace_length())
     }
 
     /// Returns twiddles which can be used to evaluate trace polynomials.
     pub fn trace_twiddles(&self) -> &[B] {
-        &self.trace_twiddles
+        self.trace_plan.twiddles()
+    }
+
+    /// Returns FFT plan which can be used to evaluate and interpolate polynomials over the trace
+    /// domain.
+    pub fn trace_plan(&self) -> &FftPlan<B> {
+        &self.trace_plan
     }
 
     /// Returns blowup factor from trace to constraint evaluation domain.
@@ -108,9 +118,17 @@ impl<B: StarkField> StarkDomain<B> {
         self.ce_domain.len()
     }
 
+    /// Returns the constraint evaluation domain shifted by the LDE domain offset.
+    ///
+    /// Elements of the returned domain are the same as the values returned by
+    /// [get_ce_x_at()](StarkDomain::get_ce_x_at).
+    pub fn ce_domain(&self) -> Domain<B> {
+        Domain::with_offset(self.ce_domain_size(), self.domain_offset)
+    }
+
     /// Returns the generator of constraint evaluation domain.
     pub fn ce_domain_generator(&self) -> B {
-        B::get_root_of_unity(self.ce_domain_size().ilog2())
+        self.ce_domain().generator()
     }
 
     /// Returns blowup factor from constraint evaluation to LDE domain.
@@ -149,6 +167,12 @@ impl<B: StarkField> StarkDomain<B> {
         self.ce_domain_size() * self.ce_to_lde_blowup()
     }
 
+    /// Returns the low-degree extension domain, i.e., the multiplicative subgroup of size equal
+    /// to the size of the LDE domain shifted by the LDE domain offset.
+    pub fn lde_domain(&self) -> Domain<B> {
+        Domain::with_offset(self.lde_domain_size(), self.domain_offset)
+    }
+
     /// Returns LDE domain offset.
     pub fn offset(&self) -> B {
         self.domain_offset
@@ -44,39 +44,49 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TraceLayout, TransitionConstraintDegree,
+    proof::{ProofDescription, ProofHeader, ProofMetadata, Queries, StarkProof},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension, LdtKind, LookupTable, ProofOptions, TraceInfo, TraceLayout,
+    TransitionConstraintDegree,
 };
 pub use utils::{
     iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
     SliceReader,
 };
 
-use fri::FriProver;
+use fri::{stir::StirProver, FriProof, FriProver};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 use utils::collections::Vec;
 
 pub use math;
 use math::{
-    fft::infer_degree,
-    fields::{CubeExtension, QuadExtension},
+    fft::{infer_degree, ComputeBackend},
+    fields::{CubeExtension, QuadExtension, QuartExtension},
     ExtensibleField, FieldElement, StarkField, ToElements,
 };
 
 pub use crypto;
-use crypto::{ElementHasher, MerkleTree, RandomCoin};
+use crypto::{ElementHasher, RandomCoin, VectorCommitment};
 
 #[cfg(feature = "std")]
 use log::debug;
-#[cfg(feature = "std")]
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
 use std::time::Instant;
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+use timing::Instant;
+
+#[cfg(all(feature = "std", target_arch = "wasm32", target_os = "unknown"))]
+mod timing;
 
 mod domain;
 pub use domain::StarkDomain;
 
 pub mod matrix;
-pub use matrix::{ColMatrix, RowMatrix};
+pub use matrix::{ColMatrix, RowCommitment, RowMatrix};
 
 mod constraints;
 use constraints::ConstraintEvaluator;
@@ -86,12 +96,22 @@ mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
+#[cfg(feature = "export")]
+pub use trace::TraceExport;
 pub use trace::{Trace, TraceTable, TraceTableFragment};
 use trace::{TraceCommitment, TraceLde, TracePolyTable};
 
 mod channel;
 use channel::ProverChannel;
 
+mod memory;
+
+mod observer;
+pub use observer::{ProverObserver, ProvingPhase};
+
+mod pow;
+pub use pow::{DefaultPowStrategy, PowStrategy, SequentialPowStrategy};
+
 mod errors;
 pub use errors::ProverError;
 
@@ -122,7 +142,7 @@ const DEFAULT_SEGMENT_WIDTH: usize = 8;
 /// return from [Prover::options] method.
 pub trait Prover {
     /// Base field for the computation described by this prover.
-    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3>;
+    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<4>;
 
     /// Algebraic intermediate representation (AIR) for the computation described by this prover.
     type Air: Air<BaseField = Self::BaseField>;
@@ -133,6 +153,13 @@ pub trait Prover {
     /// Hash function to be used.
     type HashFn: ElementHasher<BaseField = Self::BaseField>;
 
+    /// Vector commitment scheme to be used for committing to the execution trace, constraint
+    /// evaluations, and FRI layers.
+    ///
+    /// [MerkleTree](crypto::MerkleTree) is the commitment scheme supported by the verifier; other
+    /// schemes can be used with verifiers which know how to verify their opening proofs.
+    type VC: VectorCommitment<Self::HashFn>;
+
     /// PRNG to be used for generating random field elements.
     type RandomCoin: RandomCoin<BaseField = Self::BaseField, Hasher = Self::HashFn>;
 
@@ -155,6 +182,78 @@ pub trait Prover {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns [ProofMetadata] which this prover attaches to the generated STARK proofs.
+    ///
+    /// Bound metadata (see [ProofMetadata::bind()]) is absorbed into the public coin, and thus,
+    /// cannot be modified without invalidating the proof. The default implementation returns
+    /// None, and thus, no metadata is attached to the proofs.
+    fn metadata(&self) -> Option<ProofMetadata> {
+        None
+    }
+
+    /// Returns a seed for the PRNG used to salt trace and constraint commitments.
+    ///
+    /// When a seed is returned, each row of the trace LDE segments and of the constraint
+    /// evaluation matrix is hashed and merged with a random salt before it becomes a leaf of the
+    /// corresponding commitment (see [SaltedMerkleTree](crypto::SaltedMerkleTree)), and salts of
+    /// the queried rows are included in the proof. Salted commitments are always built using
+    /// Merkle trees, regardless of [Prover::VC]. Thus, roots of the commitments and Merkle
+    /// authentication paths in the proof do not reveal anything about the rows which were not
+    /// queried. The seed must be generated using a cryptographically secure source of randomness
+    /// and must not be reused across proofs.
+    ///
+    /// Salting commitments is necessary, but not sufficient, for the proofs to be zero-knowledge;
+    /// for example, the out-of-domain evaluations of the trace polynomials are not hidden.
+    ///
+    /// The default implementation returns None, and thus, commitments are not salted.
+    fn commitment_salt_seed(&self) -> Option<[u8; 32]> {
+        None
+    }
+
+    /// Returns the strategy used to search for a proof-of-work nonce during query seed grinding.
+    ///
+    /// The default implementation returns [DefaultPowStrategy], which searches for the nonce
+    /// using all available threads when `concurrent` feature is enabled.
+    fn pow_strategy(&self) -> &dyn PowStrategy {
+        &DefaultPowStrategy
+    }
+
+    /// Returns the compute backend used to build low-degree extensions of execution trace
+    /// segments and of the constraint composition polynomial with elements in `E`, and to
+    /// evaluate the constraint composition polynomial at out-of-domain points, or None if the
+    /// built-in implementation should be used.
+    ///
+    /// The built-in implementation evaluates polynomials in batches directly into row-major
+    /// matrices on the CPU, which is usually faster than [CpuBackend](math::fft::CpuBackend).
+    /// Thus, this method needs to be overridden only to offload low-degree extensions to a
+    /// hardware accelerator (e.g., a GPU).
+    ///
+    /// The default implementation returns None.
+    fn compute_backend<E>(&self) -> Option<&dyn ComputeBackend<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        None
+    }
+
+    /// Returns an estimate of the peak amount of memory (in bytes) which [Prover::prove()] uses
+    /// to generate a proof for the provided trace.
+    ///
+    /// The estimate includes the trace itself and the largest data structures built by the
+    /// prover (i.e., low-degree extensions, constraint evaluations, FRI layers, and Merkle trees),
+    /// but does not account for small temporary allocations and allocator overhead. This can be
+    /// used to reject traces which are too large for the environment before proof generation
+    /// exhausts available memory.
+    fn estimate_peak_memory(&self, trace: &Self::Trace) -> u64 {
+        let air = Self::Air::new(
+            trace.get_info(),
+            self.get_pub_inputs(trace),
+            self.options().clone(),
+        );
+        let salted = self.commitment_salt_seed().is_some();
+        memory::estimate_peak_memory::<Self::Air, Self::HashFn>(&air, salted)
+    }
+
     /// Returns a STARK proof attesting to a correct execution of a computation defined by the
     /// provided trace.
     ///
@@ -162,35 +261,86 @@ pub trait Prover {
     /// the computation described by [Self::Air](Prover::Air) and generated using some set of
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
-    #[rustfmt::skip]
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        self.prove_with_observer(trace, &mut ())
+    }
+
+    /// Returns a STARK proof in the same way as [Prover::prove()], and notifies the specified
+    /// `observer` when each phase of proof generation starts and ends.
+    fn prove_with_observer(
+        &self,
+        trace: Self::Trace,
+        observer: &mut dyn ProverObserver,
+    ) -> Result<StarkProof, ProverError> {
+        self.dispatch_proof(trace, observer, None)
+    }
+
+    /// Returns a STARK proof in the same way as [Prover::prove()], but derives all randomness
+    /// used during proof generation from the specified `seed`.
+    ///
+    /// Specifically, if this prover salts commitments (i.e., [Prover::commitment_salt_seed()]
+    /// returns a seed), the salts are generated using a PRNG instantiated with `seed` instead,
+    /// and the proof-of-work nonce is searched for using [SequentialPowStrategy] regardless of
+    /// [Prover::pow_strategy()]. All other values in a proof are derived from the trace and the
+    /// public coin. Thus, proofs generated for the same trace and seed are identical across runs,
+    /// which is useful for regression testing and for replaying proof generation during audits.
+    /// A seed must not be reused for proofs which rely on salts to hide the trace.
+    fn prove_with_seed(
+        &self,
+        trace: Self::Trace,
+        seed: [u8; 32],
+    ) -> Result<StarkProof, ProverError> {
+        self.dispatch_proof(trace, &mut (), Some(seed))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a proof over the field extension specified in the proof options; if `seed` is
+    /// provided, all randomness of the proof generation procedure is derived from it.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    fn dispatch_proof(
+        &self,
+        trace: Self::Trace,
+        observer: &mut dyn ProverObserver,
+        seed: Option<[u8; 32]>,
+    ) -> Result<StarkProof, ProverError> {
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash function.
         match self.options().field_extension() {
-            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace),
+            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace, observer, seed),
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
-                self.generate_proof::<QuadExtension<Self::BaseField>>(trace)
+                self.generate_proof::<QuadExtension<Self::BaseField>>(trace, observer, seed)
             }
             FieldExtension::Cubic => {
                 if !<CubeExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
-                self.generate_proof::<CubeExtension<Self::BaseField>>(trace)
+                self.generate_proof::<CubeExtension<Self::BaseField>>(trace, observer, seed)
+            }
+            FieldExtension::Quartic => {
+                if !<QuartExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(4));
+                }
+                self.generate_proof::<QuartExtension<Self::BaseField>>(trace, observer, seed)
             }
         }
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
-
     /// Performs the actual proof generation procedure, generating the proof that the provided
     /// execution `trace` is valid against this prover's AIR.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E>(
+        &self,
+        mut trace: Self::Trace,
+        observer: &mut dyn ProverObserver,
+        seed: Option<[u8; 32]>,
+    ) -> Result<StarkProof, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
@@ -205,15 +355,28 @@ pub trait Prover {
         // execution of the computation for the provided public inputs.
         let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
 
+        #[cfg(feature = "transcript")]
+        crypto::transcript::set_phase("trace commitment");
+
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
         let mut channel = ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin>::new(
             &air,
             pub_inputs_elements,
+            self.metadata(),
         );
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
+        observer.on_phase_start(ProvingPhase::TraceCommitment);
+
+        // instantiate the PRNG used to salt trace and constraint commitments (if any); when an
+        // explicit seed is provided, the salts are derived from it
+        let salt_seed = match seed {
+            Some(seed) => self.commitment_salt_seed().map(|_| seed),
+            None => self.commitment_salt_seed(),
+        };
+        let mut salt_prng = salt_seed.map(ChaCha20Rng::from_seed);
 
         // build computation domain; this is used later for polynomial evaluations
         #[cfg(feature = "std")]
@@ -227,12 +390,16 @@ pub trait Prover {
         );
 
         // extend the main execution trace and build a Merkle tree from the extended trace
-        let (main_trace_lde, main_trace_tree, main_trace_polys) =
-            self.build_trace_commitment::<Self::BaseField>(trace.main_segment(), &domain);
+        let (main_trace_lde, main_trace_tree, main_trace_polys) = self
+            .build_trace_commitment::<Self::BaseField>(
+                trace.main_segment(),
+                &domain,
+                salt_prng.as_mut(),
+            );
 
         // commit to the LDE of the main trace by writing the root of its Merkle tree into
         // the channel
-        channel.commit_trace(*main_trace_tree.root());
+        channel.commit_trace(main_trace_tree.root());
 
         // initialize trace commitment and trace polynomial table structs with the main trace
         // data; for multi-segment traces these structs will be used as accumulators of all
@@ -257,7 +424,7 @@ pub trait Prover {
 
             // build the trace segment
             let aux_segment = trace
-                .build_aux_segment(&aux_trace_segments, &rand_elements)
+                .build_aux_segment_with_lookups(&air, &aux_trace_segments, &rand_elements)
                 .expect("failed build auxiliary trace segment");
             #[cfg(feature = "std")]
             debug!(
@@ -269,11 +436,11 @@ pub trait Prover {
 
             // extend the auxiliary trace segment and build a Merkle tree from the extended trace
             let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
-                self.build_trace_commitment::<E>(&aux_segment, &domain);
+                self.build_trace_commitment::<E>(&aux_segment, &domain, salt_prng.as_mut());
 
             // commit to the LDE of the extended auxiliary trace segment  by writing the root of
             // its Merkle tree into the channel
-            channel.commit_trace(*aux_segment_tree.root());
+            channel.commit_trace(aux_segment_tree.root());
 
             // append the segment to the trace commitment and trace polynomial table structs
             trace_commitment.add_segment(aux_segment_lde, aux_segment_tree);
@@ -287,6 +454,7 @@ pub trait Prover {
         // mode only because this is a very expensive operation.
         #[cfg(debug_assertions)]
         trace.validate(&air, &aux_trace_segments, &aux_trace_rand_elements);
+        observer.on_phase_end(ProvingPhase::TraceCommitment);
 
         // 2 ----- evaluate constraints -----------------------------------------------------------
         // evaluate constraints specified by the AIR over the constraint evaluation domain, and
@@ -295,6 +463,7 @@ pub trait Prover {
         // identical denominators are merged together. the results are saved into a constraint
         // evaluation table where each column contains merged evaluations of constraints with
         // identical denominators.
+        observer.on_phase_start(ProvingPhase::ConstraintEvaluation);
         #[cfg(feature = "std")]
         let now = Instant::now();
         let constraint_coeffs = channel.get_constraint_composition_coeffs();
@@ -307,7 +476,10 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
+        observer.on_phase_end(ProvingPhase::ConstraintEvaluation);
+
         // 3 ----- commit to constraint evaluations -----------------------------------------------
+        observer.on_phase_start(ProvingPhase::ConstraintCommitment);
 
         // first, build constraint composition polynomial from the constraint evaluation table:
         // - divide all constraint evaluation columns by their respective divisors
@@ -329,38 +501,57 @@ pub trait Prover {
 
         // then, build a commitment to the evaluations of the composition polynomial columns
         let constraint_commitment =
-            self.build_constraint_commitment::<E>(&composition_poly, &domain);
+            self.build_constraint_commitment::<E>(&composition_poly, &domain, salt_prng.as_mut());
 
         // then, commit to the evaluations of constraints by writing the root of the constraint
         // Merkle tree into the channel
+        #[cfg(feature = "transcript")]
+        crypto::transcript::set_phase("constraint commitment");
         channel.commit_constraints(constraint_commitment.root());
+        observer.on_phase_end(ProvingPhase::ConstraintCommitment);
 
         // 4 ----- build DEEP composition polynomial ----------------------------------------------
+        observer.on_phase_start(ProvingPhase::DeepComposition);
         #[cfg(feature = "std")]
         let now = Instant::now();
 
-        // draw an out-of-domain point z. Depending on the type of E, the point is drawn either
+        // draw out-of-domain points z_j. Depending on the type of E, the points are drawn either
         // from the base field or from an extension field defined by E.
         //
         // The purpose of sampling from the extension field here (instead of the base field) is to
-        // increase security. Soundness is limited by the size of the field that the random point
-        // is drawn from, and we can potentially save on performance by only drawing this point
-        // from an extension field, rather than increasing the size of the field overall.
-        let z = channel.get_ood_point();
-
-        // evaluate trace and constraint polynomials at the OOD point z, and send the results to
-        // the verifier. the trace polynomials are actually evaluated over two points: z and z * g,
-        // where g is the generator of the trace domain.
-        let ood_trace_states = trace_polys.get_ood_frame(z);
+        // increase security. Soundness is limited by the size of the field that the random points
+        // are drawn from, and we can potentially save on performance by only drawing these points
+        // from an extension field, rather than increasing the size of the field overall. Usually,
+        // a single point is drawn, but drawing more points further reduces the soundness error
+        // of this step.
+        let ood_points = channel.get_ood_points();
+
+        // evaluate trace and constraint polynomials at the OOD points z_j, and send the results
+        // to the verifier. the trace polynomials are actually evaluated over two points for each
+        // z_j: z_j and z_j * g, where g is the generator of the trace domain.
+        #[cfg(feature = "transcript")]
+        crypto::transcript::set_phase("out-of-domain evaluation");
+        let ood_trace_states = ood_points
+            .iter()
+            .flat_map(|&z| trace_polys.get_ood_frame(z))
+            .collect::<Vec<_>>();
         channel.send_ood_trace_states(&ood_trace_states);
 
-        let ood_evaluations = composition_poly.evaluate_at(z);
+        let ood_evaluations = match self.compute_backend::<E>() {
+            Some(backend) => composition_poly.evaluate_at_with_backend(&ood_points, backend),
+            None => ood_points
+                .iter()
+                .flat_map(|&z| composition_poly.evaluate_at(z))
+                .collect::<Vec<_>>(),
+        };
         channel.send_ood_constraint_evaluations(&ood_evaluations);
 
         // draw random coefficients to use during DEEP polynomial composition, and use them to
         // initialize the DEEP composition polynomial
+        #[cfg(feature = "transcript")]
+        crypto::transcript::set_phase("FRI commitment");
         let deep_coefficients = channel.get_deep_composition_coeffs();
-        let mut deep_composition_poly = DeepCompositionPoly::new(z, deep_coefficients);
+        let mut deep_composition_poly = DeepCompositionPoly::new(ood_points, deep_coefficients);
 
         // combine all trace polynomials together and merge them into the DEEP composition
         // polynomial
@@ -397,24 +588,53 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
-        // 6 ----- compute FRI layers for the composition polynomial ------------------------------
+        observer.on_phase_end(ProvingPhase::DeepComposition);
+
+        // 6 ----- compute FRI layers (or STIR rounds) for the composition polynomial -----------
+        observer.on_phase_start(ProvingPhase::FriCommitment);
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let mut fri_prover = FriProver::new(air.options().to_fri_options());
-        fri_prover.build_layers(&mut channel, deep_evaluations);
-        #[cfg(feature = "std")]
-        debug!(
-            "Computed {} FRI layers from composition polynomial evaluations in {} ms",
-            fri_prover.num_layers(),
-            now.elapsed().as_millis()
-        );
+        let mut fri_prover = FriProver::<_, _, _, _, Self::VC>::new(air.options().to_fri_options());
+        let mut stir_prover =
+            StirProver::<_, _, _, Self::HashFn>::new(air.options().to_stir_options());
+        match air.options().ldt() {
+            LdtKind::Fri => {
+                fri_prover.build_layers(&mut channel, deep_evaluations);
+                #[cfg(feature = "std")]
+                debug!(
+                    "Computed {} FRI layers from composition polynomial evaluations in {} ms",
+                    fri_prover.num_layers(),
+                    now.elapsed().as_millis()
+                );
+            }
+            LdtKind::Stir => {
+                stir_prover.build_rounds(&mut channel, deep_evaluations);
+                #[cfg(feature = "std")]
+                debug!(
+                    "Computed STIR rounds from composition polynomial evaluations in {} ms",
+                    now.elapsed().as_millis()
+                );
+            }
+        }
+
+        observer.on_phase_end(ProvingPhase::FriCommitment);
 
         // 7 ----- determine query positions ------------------------------------------------------
+        observer.on_phase_start(ProvingPhase::QueryGeneration);
         #[cfg(feature = "std")]
         let now = Instant::now();
 
+        #[cfg(feature = "transcript")]
+        crypto::transcript::set_phase("query positions");
+
         // apply proof-of-work to the query seed
-        channel.grind_query_seed();
+        // when an explicit seed is provided, the nonce is searched for sequentially so that the
+        // proof does not depend on thread scheduling
+        let pow_strategy = match seed {
+            Some(_) => &SequentialPowStrategy,
+            None => self.pow_strategy(),
+        };
+        channel.grind_query_seed(pow_strategy);
 
         // generate pseudo-random query positions
         let query_positions = channel.get_query_positions();
@@ -425,26 +645,58 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
+        observer.on_phase_end(ProvingPhase::QueryGeneration);
+
         // 8 ----- build proof object -------------------------------------------------------------
+        observer.on_phase_start(ProvingPhase::ProofAssembly);
         #[cfg(feature = "std")]
         let now = Instant::now();
 
-        // generate FRI proof
-        let fri_proof = fri_prover.build_proof(&query_positions);
+        // generate FRI proof; when STIR is used, the FRI proof is left empty and the STIR proof
+        // queries the evaluations of its first round at the same positions as the trace
+        let (fri_proof, stir_proof) = match air.options().ldt() {
+            LdtKind::Fri => (fri_prover.build_proof(&query_positions), None),
+            LdtKind::Stir => (
+                FriProof::default(),
+                Some(stir_prover.build_proof(&query_positions)),
+            ),
+        };
 
         // query the execution trace at the selected position; for each query, we need the
         // state of the trace at that position + Merkle authentication path
-        let trace_queries = trace_commitment.query(&query_positions);
+        let mut trace_queries = trace_commitment.query(&query_positions);
 
         // query the constraint commitment at the selected positions; for each query, we need just
         // a Merkle authentication path. this is because constraint evaluations for each step are
         // merged into a single value and Merkle authentication paths contain these values already
-        let constraint_queries = constraint_commitment.query(&query_positions);
+        let mut constraint_queries = constraint_commitment.query(&query_positions);
+
+        // if requested by the proof options, replace batch Merkle proofs of the queries with
+        // Merkle multi-proofs; values in the main trace segment are in the base field, while
+        // values in auxiliary segments and constraint evaluations are in the extension field
+        if air.options().merkle_multi_proofs() {
+            let lde_domain_size = domain.lde_domain_size();
+            trace_queries = trace_queries
+                .into_iter()
+                .enumerate()
+                .map(|(i, queries)| match i {
+                    0 => queries.into_multi_proof::<Self::HashFn, Self::BaseField>(
+                        lde_domain_size,
+                        &query_positions,
+                    ),
+                    _ => queries
+                        .into_multi_proof::<Self::HashFn, E>(lde_domain_size, &query_positions),
+                })
+                .collect();
+            constraint_queries = constraint_queries
+                .into_multi_proof::<Self::HashFn, E>(lde_domain_size, &query_positions);
+        }
 
         // build the proof object
-        let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof);
+        let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof, stir_proof);
         #[cfg(feature = "std")]
         debug!("Built proof object in {} ms", now.elapsed().as_millis());
+        observer.on_phase_end(ProvingPhase::ProofAssembly);
 
         Ok(proof)
     }
@@ -457,21 +709,41 @@ pub trait Prover {
     /// domain.
     ///
     /// Trace commitment is computed by hashing each row of the extended execution trace, and then
-    /// building a Merkle tree from the resulting hashes.
+    /// committing to the resulting hashes using [Prover::VC] commitment scheme. If `salt_prng` is
+    /// provided, the hashes are salted using salts generated by the PRNG and are committed to
+    /// using a salted Merkle tree.
+    #[allow(clippy::type_complexity)]
     fn build_trace_commitment<E>(
         &self,
         trace: &ColMatrix<E>,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (RowMatrix<E>, MerkleTree<Self::HashFn>, ColMatrix<E>)
+        salt_prng: Option<&mut ChaCha20Rng>,
+    ) -> (
+        RowMatrix<E>,
+        RowCommitment<Self::HashFn, Self::VC>,
+        ColMatrix<E>,
+    )
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // extend the execution trace
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_polys = trace.interpolate_columns();
-        let trace_lde =
-            RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&trace_polys, domain);
+        let (trace_polys, trace_lde) = match self.compute_backend::<E>() {
+            Some(backend) => {
+                let inv_twiddles = domain.trace_plan().inv_twiddles();
+                let trace_polys = trace.interpolate_columns_with_backend(inv_twiddles, backend);
+                let trace_lde =
+                    RowMatrix::evaluate_polys_with_backend(&trace_polys, domain, backend);
+                (trace_polys, trace_lde)
+            }
+            None => {
+                let trace_polys = trace.interpolate_columns_with(domain.trace_plan());
+                let trace_lde =
+                    RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&trace_polys, domain);
+                (trace_polys, trace_lde)
+            }
+        };
         #[cfg(feature = "std")]
         debug!(
             "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
@@ -485,7 +757,10 @@ pub trait Prover {
         // build trace commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_tree = trace_lde.commit_to_rows();
+        let trace_tree = match salt_prng {
+            Some(prng) => trace_lde.commit_to_rows_salted(prng).into(),
+            None => RowCommitment::Plain(trace_lde.commit_to_rows()),
+        };
         #[cfg(feature = "std")]
         debug!(
             "Computed execution trace commitment (Merkle tree of depth {}) in {} ms",
@@ -502,23 +777,31 @@ pub trait Prover {
     /// The evaluation is done by evaluating each composition polynomial column over the LDE
     /// domain.
     ///
-    /// The commitment is computed by hashing each row in the evaluation matrix, and then building
-    /// a Merkle tree from the resulting hashes.
+    /// The commitment is computed by hashing each row in the evaluation matrix, and then
+    /// committing to the resulting hashes using [Prover::VC] commitment scheme. If `salt_prng` is
+    /// provided, the hashes are salted using salts generated by the PRNG and are committed to
+    /// using a salted Merkle tree.
     fn build_constraint_commitment<E>(
         &self,
         composition_poly: &CompositionPoly<E>,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> ConstraintCommitment<E, Self::HashFn>
+        salt_prng: Option<&mut ChaCha20Rng>,
+    ) -> ConstraintCommitment<E, Self::HashFn, Self::VC>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // evaluate composition polynomial columns over the LDE domain
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let composed_evaluations = RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(
-            composition_poly.data(),
-            domain,
-        );
+        let composed_evaluations = match self.compute_backend::<E>() {
+            Some(backend) => {
+                RowMatrix::evaluate_polys_with_backend(composition_poly.data(), domain, backend)
+            }
+            None => RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(
+                composition_poly.data(),
+                domain,
+            ),
+        };
         #[cfg(feature = "std")]
         debug!(
             "Evaluated {} composition polynomial columns over LDE domain (2^{} elements) in {} ms",
@@ -530,7 +813,10 @@ pub trait Prover {
         // build constraint evaluation commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let commitment = composed_evaluations.commit_to_rows();
+        let commitment = match salt_prng {
+            Some(prng) => composed_evaluations.commit_to_rows_salted(prng).into(),
+            None => RowCommitment::Plain(composed_evaluations.commit_to_rows()),
+        };
         let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
         #[cfg(feature = "std")]
         debug!(
@@ -6,7 +6,10 @@
 use crate::StarkDomain;
 use core::{iter::FusedIterator, slice};
 use crypto::{ElementHasher, MerkleTree};
-use math::{fft, polynom, FieldElement};
+use math::{
+    fft::{self, ComputeBackend, FftPlan},
+    polynom, FieldElement,
+};
 use utils::{batch_iter_mut, collections::Vec, iter, iter_mut, uninit_vector};
 
 #[cfg(feature = "concurrent")]
@@ -171,12 +174,12 @@ impl<E: FieldElement> ColMatrix<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns an iterator over the columns of this matrix.
-    pub fn columns(&self) -> ColumnIter<E> {
+    pub fn columns(&self) -> ColumnIter<'_, E> {
         ColumnIter::new(self)
     }
 
     /// Returns a mutable iterator over the columns of this matrix.
-    pub fn columns_mut(&mut self) -> ColumnIterMut<E> {
+    pub fn columns_mut(&mut self) -> ColumnIterMut<'_, E> {
         ColumnIterMut::new(self)
     }
 
@@ -194,11 +197,44 @@ impl<E: FieldElement> ColMatrix<E> {
     /// * The resulting polynomials are returned as a single matrix where each column contains
     ///   coefficients of a degree `num_rows - 1` polynomial.
     pub fn interpolate_columns(&self) -> Self {
-        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
+        let plan = FftPlan::new(self.num_rows(), E::BaseField::ONE);
+        self.interpolate_columns_with(&plan)
+    }
+
+    /// Interpolates columns of the matrix into polynomials in coefficient form using the
+    /// specified FFT plan and returns the result.
+    ///
+    /// This is the same as [ColMatrix::interpolate_columns()], but the twiddles are taken from
+    /// the provided plan rather than computed anew. Each column is interpreted as evaluations of
+    /// a polynomial over the domain of the plan.
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not equal to the domain size of the plan.
+    pub fn interpolate_columns_with(&self, plan: &FftPlan<E::BaseField>) -> Self {
+        let columns = iter!(self.columns)
+            .map(|evaluations| plan.interpolate(evaluations))
+            .collect();
+        Self { columns }
+    }
+
+    /// Interpolates columns of the matrix into polynomials in coefficient form using the
+    /// specified compute backend and returns the result.
+    ///
+    /// This is the same as [ColMatrix::interpolate_columns()], but the interpolation of each
+    /// column is dispatched to [ComputeBackend::intt()] using the provided inverse twiddles.
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not equal to twice the number of inverse
+    /// twiddles.
+    pub fn interpolate_columns_with_backend(
+        &self,
+        inv_twiddles: &[E::BaseField],
+        backend: &dyn ComputeBackend<E>,
+    ) -> Self {
         let columns = iter!(self.columns)
             .map(|evaluations| {
                 let mut column = evaluations.clone();
-                fft::interpolate_poly(&mut column, &inv_twiddles);
+                backend.intt(&mut column, inv_twiddles);
                 column
             })
             .collect();
@@ -216,8 +252,8 @@ impl<E: FieldElement> ColMatrix<E> {
     /// * The resulting polynomials are returned as a single matrix where each column contains
     ///   coefficients of a degree `num_rows - 1` polynomial.
     pub fn interpolate_columns_into(mut self) -> Self {
-        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
-        iter_mut!(self.columns).for_each(|column| fft::interpolate_poly(column, &inv_twiddles));
+        let plan = FftPlan::new(self.num_rows(), E::BaseField::ONE);
+        iter_mut!(self.columns).for_each(|column| plan.interpolate_in_place(column));
         self
     }
 
@@ -245,6 +281,25 @@ impl<E: FieldElement> ColMatrix<E> {
         Self { columns }
     }
 
+    /// Evaluates polynomials contained in the columns of this matrix over the specified domain
+    /// using the specified compute backend and returns the result.
+    ///
+    /// This is the same as [ColMatrix::evaluate_columns_over()], but the evaluations are
+    /// dispatched to [ComputeBackend::coset_lde_batch()].
+    pub fn evaluate_columns_with_backend(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        backend: &dyn ComputeBackend<E>,
+    ) -> Self {
+        let columns = backend.coset_lde_batch(
+            &self.columns,
+            domain.trace_twiddles(),
+            domain.offset(),
+            domain.trace_to_lde_blowup(),
+        );
+        Self { columns }
+    }
+
     /// Evaluates polynomials contained in the columns of this matrix at a single point `x`.
     pub fn evaluate_columns_at<F>(&self, x: F) -> Vec<F>
     where
@@ -253,6 +308,19 @@ impl<E: FieldElement> ColMatrix<E> {
         iter!(self.columns).map(|p| polynom::eval(p, x)).collect()
     }
 
+    /// Evaluates polynomials contained in the columns of this matrix at each of the points in
+    /// `xs` using the specified compute backend and returns the result.
+    ///
+    /// The evaluations are dispatched to [ComputeBackend::eval_batch()]; the j-th element of the
+    /// i-th returned vector is the evaluation of the polynomial in the i-th column at `xs[j]`.
+    pub fn evaluate_columns_at_with_backend(
+        &self,
+        xs: &[E],
+        backend: &dyn ComputeBackend<E>,
+    ) -> Vec<Vec<E>> {
+        backend.eval_batch(&self.columns, xs)
+    }
+
     // COMMITMENTS
     // --------------------------------------------------------------------------------------------
 
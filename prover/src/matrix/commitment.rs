@@ -0,0 +1,121 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::RowMatrix;
+use air::proof::Queries;
+use crypto::{ElementHasher, Hasher, MerkleTree, SaltedMerkleTree, VectorCommitment};
+use math::FieldElement;
+use rand_chacha::ChaCha20Rng;
+use utils::collections::Vec;
+
+// ROW COMMITMENT
+// ================================================================================================
+
+/// Commitment to the rows of a [RowMatrix].
+///
+/// Committed items are hashes of the matrix rows. Unsalted commitments are built using the vector
+/// commitment scheme `V` (a Merkle tree by default). When the commitment is salted, each row hash
+/// is merged with a random salt before it becomes a leaf in a [SaltedMerkleTree], and the salts
+/// of the queried rows are included in the queries built from the commitment; salted commitments
+/// are always Merkle tree commitments regardless of `V`.
+#[derive(Debug)]
+pub enum RowCommitment<H: Hasher, V: VectorCommitment<H> = MerkleTree<H>> {
+    /// A commitment in which committed items are hashes of the matrix rows.
+    Plain(V),
+    /// A commitment in which hashes of the matrix rows are merged with random salts.
+    Salted(SaltedMerkleTree<H, ChaCha20Rng>),
+}
+
+impl<H: Hasher, V: VectorCommitment<H>> RowCommitment<H, V> {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the value which commits to all rows of the matrix (e.g., the root of a Merkle
+    /// tree).
+    pub fn root(&self) -> H::Digest {
+        match self {
+            Self::Plain(commitment) => commitment.commitment(),
+            Self::Salted(tree) => *tree.root(),
+        }
+    }
+
+    /// Returns the depth of the commitment, i.e., the base 2 logarithm of the number of committed
+    /// items. For Merkle tree commitments, this is the same as the depth of the tree.
+    pub fn depth(&self) -> usize {
+        self.num_leaves().ilog2() as usize
+    }
+
+    /// Returns the number of committed items (e.g., the number of leaves in a Merkle tree).
+    pub fn num_leaves(&self) -> usize {
+        match self {
+            Self::Plain(commitment) => commitment.domain_len(),
+            Self::Salted(tree) => tree.leaves().len(),
+        }
+    }
+
+    /// Returns true if leaves of this commitment are salted.
+    pub fn is_salted(&self) -> bool {
+        matches!(self, Self::Salted(_))
+    }
+
+    // QUERIES
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the rows of the committed `matrix` at the specified positions along with a proof
+    /// of opening the hashes of these rows against the commitment (for Merkle trees, these are
+    /// authentication paths from the commitment root to these rows).
+    ///
+    /// # Panics
+    /// Panics if the number of rows in the `matrix` is different from the number of leaves in
+    /// this commitment, or if an opening proof could not be generated for the specified
+    /// positions.
+    pub fn query<E>(&self, matrix: &RowMatrix<E>, positions: &[usize]) -> Queries
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        assert_eq!(
+            matrix.num_rows(),
+            self.num_leaves(),
+            "number of rows in the matrix must be the same as number of leaves in the commitment"
+        );
+
+        // for each position, get the corresponding row from the matrix
+        let rows = positions
+            .iter()
+            .map(|&pos| matrix.row(pos).to_vec())
+            .collect::<Vec<_>>();
+
+        // build an opening proof (e.g., Merkle authentication paths) for the specified positions
+        match self {
+            Self::Plain(commitment) => {
+                let (_, proof) = commitment
+                    .open_many(positions)
+                    .expect("failed to generate an opening proof for queries");
+                Queries::new::<H, E, V>(proof, rows)
+            }
+            Self::Salted(tree) => {
+                let (proof, salts) = tree
+                    .prove_batch(positions)
+                    .expect("failed to generate a Merkle proof for queries");
+                Queries::new_salted(proof, rows, salts)
+            }
+        }
+    }
+}
+
+impl<H: Hasher> From<MerkleTree<H>> for RowCommitment<H> {
+    fn from(tree: MerkleTree<H>) -> Self {
+        Self::Plain(tree)
+    }
+}
+
+impl<H: Hasher, V: VectorCommitment<H>> From<SaltedMerkleTree<H, ChaCha20Rng>>
+    for RowCommitment<H, V>
+{
+    fn from(tree: SaltedMerkleTree<H, ChaCha20Rng>) -> Self {
+        Self::Salted(tree)
+    }
+}
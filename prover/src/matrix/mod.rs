@@ -12,5 +12,8 @@ pub use col_matrix::{ColMatrix, ColumnIter, MultiColumnIter};
 mod segments;
 pub use segments::Segment;
 
+mod commitment;
+pub use commitment::RowCommitment;
+
 #[cfg(test)]
 mod tests;
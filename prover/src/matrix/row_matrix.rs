@@ -5,8 +5,12 @@
 
 use super::{ColMatrix, Segment};
 use crate::StarkDomain;
-use crypto::{ElementHasher, MerkleTree};
-use math::{fft, FieldElement, StarkField};
+use crypto::{ElementHasher, SaltedMerkleTree, VectorCommitment};
+use math::{
+    fft::{self, ComputeBackend},
+    FieldElement, StarkField,
+};
+use rand_chacha::rand_core::{CryptoRng, RngCore};
 use utils::collections::Vec;
 use utils::{batch_iter_mut, flatten_vector_elements, uninit_vector};
 
@@ -97,6 +101,35 @@ impl<E: FieldElement> RowMatrix<E> {
         Self::from_segments(segments, polys.num_base_cols())
     }
 
+    /// Returns a new [RowMatrix] constructed by evaluating the provided polynomials over the
+    /// specified [StarkDomain] using the specified compute backend.
+    ///
+    /// This is the same as [RowMatrix::evaluate_polys_over()], but the polynomials are
+    /// evaluated via [ComputeBackend::coset_lde_batch()], and the resulting columns are then
+    /// transposed into a row-major matrix.
+    pub fn evaluate_polys_with_backend(
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+        backend: &dyn ComputeBackend<E>,
+    ) -> Self {
+        let evaluations = polys.evaluate_columns_with_backend(domain, backend);
+
+        // copy the evaluations into a single row-major vector of base field elements
+        let row_width = evaluations.num_base_cols();
+        let mut data = Vec::with_capacity(evaluations.num_rows() * row_width);
+        let mut row = E::zeroed_vector(evaluations.num_cols());
+        for row_idx in 0..evaluations.num_rows() {
+            evaluations.read_row_into(row_idx, &mut row);
+            data.extend_from_slice(E::slice_as_base_elements(&row));
+        }
+
+        RowMatrix {
+            data,
+            row_width,
+            elements_per_row: row_width,
+        }
+    }
+
     /// Returns a new [RowMatrix] instantiated from the specified matrix segments.
     ///
     /// `elements_per_row` specifies how many base field elements are considered to form a single
@@ -175,30 +208,58 @@ impl<E: FieldElement> RowMatrix<E> {
     ///
     /// The commitment is built as follows:
     /// * Each row of the matrix is hashed into a single digest of the specified hash function.
-    /// * The resulting values are used to build a binary Merkle tree such that each row digest
-    ///   becomes a leaf in the tree. Thus, the number of leaves in the tree is equal to the
-    ///   number of rows in the matrix.
-    /// * The resulting Merkle tree is returned as the commitment to the entire matrix.
-    pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
+    /// * The resulting values are committed to using the vector commitment scheme `V` such that
+    ///   each row digest becomes an item of the committed vector (e.g., a leaf in a Merkle tree).
+    ///   Thus, the number of committed items is equal to the number of rows in the matrix.
+    /// * The resulting vector commitment is returned as the commitment to the entire matrix.
+    pub fn commit_to_rows<H, V>(&self) -> V
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        // build the commitment out of hashed rows
+        V::commit(self.hash_rows::<H>()).expect("failed to construct trace commitment")
+    }
+
+    /// Returns a salted commitment to this matrix.
+    ///
+    /// The commitment is built in the same way as in [RowMatrix::commit_to_rows()], but each row
+    /// digest is merged with a random salt generated using the provided `prng` before it becomes
+    /// a leaf in the tree (see [SaltedMerkleTree]).
+    pub fn commit_to_rows_salted<H, R>(&self, prng: &mut R) -> SaltedMerkleTree<H, R>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        R: RngCore + CryptoRng,
+    {
+        SaltedMerkleTree::new(self.hash_rows::<H>(), prng)
+            .expect("failed to construct trace Merkle tree")
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns hashes of all rows of this matrix computed using hash function `H`.
+    fn hash_rows<H>(&self) -> Vec<H::Digest>
     where
         H: ElementHasher<BaseField = E::BaseField>,
     {
         // allocate vector to store row hashes
         let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
 
-        // iterate though matrix rows, hashing each row
+        // iterate though matrix rows, hashing all rows of a batch at once; this lets hash
+        // functions which support multi-message hashing process several rows in parallel
         batch_iter_mut!(
             &mut row_hashes,
             128, // min batch size
             |batch: &mut [H::Digest], batch_offset: usize| {
-                for (i, row_hash) in batch.iter_mut().enumerate() {
-                    *row_hash = H::hash_elements(self.row(batch_offset + i));
-                }
+                let rows = (batch_offset..batch_offset + batch.len())
+                    .map(|row_idx| self.row(row_idx))
+                    .collect::<Vec<_>>();
+                batch.copy_from_slice(&H::hash_elements_many(&rows));
             }
         );
 
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+        row_hashes
     }
 }
 
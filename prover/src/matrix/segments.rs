@@ -178,10 +178,9 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         poly_offset: usize,
         offsets: &[B],
     ) {
-        for row_idx in 0..dest.len() {
-            for i in 0..N {
-                let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+        for (row_idx, (row, &offset)) in dest.iter_mut().zip(offsets).enumerate() {
+            for (i, value) in row.iter_mut().enumerate() {
+                *value = polys.get_base_element(poly_offset + i, row_idx) * offset;
             }
         }
     }
@@ -198,10 +197,9 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         offsets: &[B],
     ) {
         debug_assert!(num_polys < N);
-        for row_idx in 0..dest.len() {
-            for i in 0..num_polys {
-                let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+        for (row_idx, (row, &offset)) in dest.iter_mut().zip(offsets).enumerate() {
+            for (i, value) in row.iter_mut().take(num_polys).enumerate() {
+                *value = polys.get_base_element(poly_offset + i, row_idx) * offset;
             }
         }
     }
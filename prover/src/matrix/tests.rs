@@ -4,8 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    math::{fields::f64::BaseElement, get_power_series, polynom, StarkField},
-    ColMatrix, RowMatrix,
+    math::{
+        fft::{self, CpuBackend},
+        fields::{f64::BaseElement, QuadExtension},
+        get_power_series, polynom, StarkField,
+    },
+    ColMatrix, RowMatrix, StarkDomain,
 };
 use rand_utils::rand_vector;
 use utils::collections::Vec;
@@ -39,6 +43,47 @@ fn test_eval_poly_with_offset_matrix() {
     }
 }
 
+#[test]
+fn test_eval_polys_with_backend() {
+    let n = 128;
+    let num_polys = 5;
+    let blowup_factor = 4;
+
+    let columns: Vec<Vec<QuadExtension<BaseElement>>> =
+        (0..num_polys).map(|_| rand_vector(n)).collect();
+    let evaluations = ColMatrix::new(columns);
+    let twiddles = fft::get_twiddles::<BaseElement>(n);
+    let domain = StarkDomain::from_twiddles(twiddles, blowup_factor, BaseElement::GENERATOR);
+
+    // interpolation via the backend must match the built-in interpolation
+    let inv_twiddles = domain.trace_plan().inv_twiddles();
+    let polys = evaluations.interpolate_columns_with_backend(inv_twiddles, &CpuBackend);
+    assert_eq!(
+        evaluations.interpolate_columns().into_columns(),
+        polys.clone().into_columns()
+    );
+
+    // evaluation via the backend must match the built-in batched evaluation
+    let expected = RowMatrix::evaluate_polys_over::<8>(&polys, &domain);
+    let actual = RowMatrix::evaluate_polys_with_backend(&polys, &domain, &CpuBackend);
+    assert_eq!(expected.num_rows(), actual.num_rows());
+    assert_eq!(expected.num_cols(), actual.num_cols());
+    for row in 0..expected.num_rows() {
+        assert_eq!(expected.row(row), actual.row(row));
+    }
+
+    // evaluation at arbitrary points via the backend must match evaluation at each point
+    let xs: Vec<QuadExtension<BaseElement>> = rand_vector(3);
+    let actual = polys.evaluate_columns_at_with_backend(&xs, &CpuBackend);
+    for (j, &x) in xs.iter().enumerate() {
+        let expected = polys.evaluate_columns_at(x);
+        assert_eq!(
+            expected,
+            actual.iter().map(|column| column[j]).collect::<Vec<_>>()
+        );
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,125 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::Air;
+use crypto::Hasher;
+use math::FieldElement;
+use utils::collections::Vec;
+
+use crate::DEFAULT_SEGMENT_WIDTH;
+
+// MEMORY ESTIMATION
+// ================================================================================================
+
+/// Returns an estimate of the peak amount of memory (in bytes) used by the prover to generate a
+/// proof for the computation described by `air` using `H` as the hash function; `salted` specifies
+/// whether trace and constraint commitments are salted.
+///
+/// The estimate includes the execution trace, the trace polynomials, the low-degree extensions
+/// of the trace and of the constraint composition polynomial, the constraint evaluation table,
+/// all FRI layers, and the Merkle trees built for these structures. Sizes of the largest
+/// structures alive at the same time are added together; smaller temporary allocations (e.g.,
+/// twiddles) and allocator overhead are not accounted for.
+pub fn estimate_peak_memory<A: Air, H: Hasher>(air: &A, salted: bool) -> u64 {
+    let base_bytes = A::BaseField::ELEMENT_BYTES as u64;
+    let extension_degree = air.options().field_extension().degree() as u64;
+    let ext_bytes = base_bytes * extension_degree;
+    let digest_bytes = core::mem::size_of::<H::Digest>() as u64;
+
+    let trace_length = air.trace_length() as u64;
+    let lde_domain_size = air.lde_domain_size() as u64;
+    let ce_domain_size = air.ce_domain_size() as u64;
+
+    // a Merkle tree with n leaves consists of n leaf digests and n internal node digests; a
+    // salted tree additionally keeps n salts and n unsalted leaves
+    let tree_bytes = |num_leaves: u64| 2 * num_leaves * digest_bytes;
+    let commitment_bytes = |num_leaves: u64| {
+        if salted {
+            2 * tree_bytes(num_leaves)
+        } else {
+            tree_bytes(num_leaves)
+        }
+    };
+
+    // low-degree extensions are evaluated in segments of DEFAULT_SEGMENT_WIDTH base field
+    // columns, and thus, rows of the extended matrices are padded to a multiple of this width;
+    // when there is more than one segment, the segments are transposed into a new matrix which
+    // temporarily doubles the amount of memory used by the extension
+    let segment_width = DEFAULT_SEGMENT_WIDTH as u64;
+    let lde_bytes = |num_base_cols: u64| {
+        let num_segments = (num_base_cols + segment_width - 1) / segment_width;
+        lde_domain_size * num_segments * segment_width * base_bytes
+    };
+    let lde_transpose_bytes = |num_base_cols: u64| {
+        if num_base_cols > segment_width {
+            lde_bytes(num_base_cols)
+        } else {
+            0
+        }
+    };
+
+    // the execution trace, the trace polynomials, and the trace LDE are kept in memory until
+    // the DEEP composition polynomial is built
+    let layout = air.trace_layout();
+    let mut segment_base_cols = vec![layout.main_trace_width() as u64];
+    for i in 0..layout.num_aux_segments() {
+        segment_base_cols.push(layout.get_aux_segment_width(i) as u64 * extension_degree);
+    }
+    let trace_base_cols: u64 = segment_base_cols.iter().sum();
+    let trace_bytes = 2 * trace_length * trace_base_cols * base_bytes;
+    let trace_lde_bytes: u64 = segment_base_cols
+        .iter()
+        .map(|&num_base_cols| lde_bytes(num_base_cols) + commitment_bytes(lde_domain_size))
+        .sum();
+    let trace_transpose_bytes = segment_base_cols
+        .iter()
+        .map(|&num_base_cols| lde_transpose_bytes(num_base_cols))
+        .max()
+        .unwrap_or(0);
+
+    // constraint evaluation table contains a column for transition constraints and a column for
+    // each group of assertions with the same divisor; the columns are merged into a single
+    // column of the combined constraint polynomial
+    let mut divisors = Vec::new();
+    for assertion in air.get_assertions() {
+        let divisor = (
+            assertion.first_step(),
+            assertion.stride(),
+            assertion.values().len(),
+        );
+        if !divisors.contains(&divisor) {
+            divisors.push(divisor);
+        }
+    }
+    let num_evaluation_columns = 1 + divisors.len() as u64;
+    let evaluation_bytes = (num_evaluation_columns + 1) * ce_domain_size * ext_bytes;
+
+    // after the combined constraint polynomial is built, its evaluations over the LDE domain are
+    // committed to, and then DEEP composition polynomial evaluations are folded into FRI layers
+    let composition_base_cols =
+        air.context().num_constraint_composition_columns() as u64 * extension_degree;
+    let composition_bytes = ce_domain_size * ext_bytes
+        + lde_bytes(composition_base_cols)
+        + commitment_bytes(lde_domain_size);
+    let composition_transpose_bytes =
+        ce_domain_size * ext_bytes + lde_transpose_bytes(composition_base_cols);
+
+    let fri_options = air.options().to_fri_options();
+    let mut fri_bytes = 0;
+    let mut domain_size = lde_domain_size;
+    for folding_factor in fri_options.layer_folding_factors(lde_domain_size as usize) {
+        let folding_factor = folding_factor as u64;
+        fri_bytes += domain_size * ext_bytes + tree_bytes(domain_size / folding_factor);
+        domain_size /= folding_factor;
+    }
+    fri_bytes += domain_size * ext_bytes;
+
+    trace_bytes
+        + trace_lde_bytes
+        + trace_transpose_bytes
+            .max(evaluation_bytes)
+            .max(composition_transpose_bytes)
+            .max(composition_bytes + fri_bytes)
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// PROVER OBSERVER
+// ================================================================================================
+
+/// Receives notifications about the progress of proof generation.
+///
+/// An observer can be passed to [Prover::prove_with_observer()](crate::Prover::prove_with_observer)
+/// to report progress of long-running proof generation (e.g., to a user interface). Methods of
+/// the observer are invoked synchronously from the thread which generates the proof, and thus,
+/// they should return quickly. The default implementations of all methods do nothing.
+pub trait ProverObserver {
+    /// Invoked when the prover starts the specified phase of proof generation.
+    fn on_phase_start(&mut self, _phase: ProvingPhase) {}
+
+    /// Invoked when the prover completes the specified phase of proof generation.
+    fn on_phase_end(&mut self, _phase: ProvingPhase) {}
+}
+
+/// An observer which ignores all notifications.
+impl ProverObserver for () {}
+
+// PROVING PHASE
+// ================================================================================================
+
+/// Defines phases of proof generation reported to a [ProverObserver].
+///
+/// The phases are executed in the order in which they are listed in [ProvingPhase::ALL].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingPhase {
+    /// Extension of the execution trace (including auxiliary trace segments, if any) over the
+    /// LDE domain and commitment to the extended trace.
+    TraceCommitment,
+    /// Evaluation of constraints over the constraint evaluation domain.
+    ConstraintEvaluation,
+    /// Construction of the constraint composition polynomial and commitment to its evaluations
+    /// over the LDE domain.
+    ConstraintCommitment,
+    /// Construction of the DEEP composition polynomial and its evaluation over the LDE domain.
+    DeepComposition,
+    /// Computation of FRI layers and commitments to them.
+    FriCommitment,
+    /// Proof-of-work grinding and generation of query positions.
+    QueryGeneration,
+    /// Construction of the proof from queried values and their decommitments.
+    ProofAssembly,
+}
+
+impl ProvingPhase {
+    /// All phases of proof generation in the order in which they are executed.
+    pub const ALL: [ProvingPhase; 7] = [
+        Self::TraceCommitment,
+        Self::ConstraintEvaluation,
+        Self::ConstraintCommitment,
+        Self::DeepComposition,
+        Self::FriCommitment,
+        Self::QueryGeneration,
+        Self::ProofAssembly,
+    ];
+
+    /// Returns the name of this phase.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TraceCommitment => "TraceCommitment",
+            Self::ConstraintEvaluation => "ConstraintEvaluation",
+            Self::ConstraintCommitment => "ConstraintCommitment",
+            Self::DeepComposition => "DeepComposition",
+            Self::FriCommitment => "FriCommitment",
+            Self::QueryGeneration => "QueryGeneration",
+            Self::ProofAssembly => "ProofAssembly",
+        }
+    }
+}
+
+impl core::fmt::Display for ProvingPhase {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
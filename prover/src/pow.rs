@@ -0,0 +1,103 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// POW STRATEGY
+// ================================================================================================
+
+/// Defines how the prover searches for a proof-of-work nonce.
+///
+/// The proof-of-work condition itself is defined by the random coin used by the prover: a nonce
+/// is valid if [RandomCoin::check_leading_zeros()](crypto::RandomCoin::check_leading_zeros)
+/// returns a value which is greater than or equal to the grinding factor. Thus, a strategy only
+/// determines how candidate nonces are enumerated and checked, and the hash function used for
+/// proof-of-work is selected via the coin. Any strategy can be combined with any coin.
+///
+/// A strategy can be specified for a prover via [Prover::pow_strategy()](crate::Prover::pow_strategy).
+pub trait PowStrategy: Sync {
+    /// Returns a nonce for which `leading_zeros(nonce)` is greater than or equal to
+    /// `grinding_factor`, or None if no such nonce could be found.
+    fn find_nonce(
+        &self,
+        grinding_factor: u32,
+        leading_zeros: &(dyn Fn(u64) -> u32 + Sync),
+    ) -> Option<u64>;
+}
+
+// DEFAULT STRATEGY
+// ================================================================================================
+
+/// Proof-of-work strategy which checks all nonces starting from 1.
+///
+/// When `concurrent` feature is enabled, nonces are checked using all available threads;
+/// otherwise, nonces are checked one by one in the current thread.
+pub struct DefaultPowStrategy;
+
+impl PowStrategy for DefaultPowStrategy {
+    #[cfg(not(feature = "concurrent"))]
+    fn find_nonce(
+        &self,
+        grinding_factor: u32,
+        leading_zeros: &(dyn Fn(u64) -> u32 + Sync),
+    ) -> Option<u64> {
+        SequentialPowStrategy.find_nonce(grinding_factor, leading_zeros)
+    }
+
+    #[cfg(feature = "concurrent")]
+    fn find_nonce(
+        &self,
+        grinding_factor: u32,
+        leading_zeros: &(dyn Fn(u64) -> u32 + Sync),
+    ) -> Option<u64> {
+        (1..u64::MAX)
+            .into_par_iter()
+            .find_any(|&nonce| leading_zeros(nonce) >= grinding_factor)
+    }
+}
+
+// SEQUENTIAL STRATEGY
+// ================================================================================================
+
+/// Proof-of-work strategy which checks nonces one by one starting from 1 in the current thread.
+///
+/// Unlike [DefaultPowStrategy] with `concurrent` feature enabled, this strategy always returns
+/// the smallest valid nonce, and thus, proofs generated using it are reproducible.
+pub struct SequentialPowStrategy;
+
+impl PowStrategy for SequentialPowStrategy {
+    fn find_nonce(
+        &self,
+        grinding_factor: u32,
+        leading_zeros: &(dyn Fn(u64) -> u32 + Sync),
+    ) -> Option<u64> {
+        (1..u64::MAX).find(|&nonce| leading_zeros(nonce) >= grinding_factor)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DefaultPowStrategy, PowStrategy, SequentialPowStrategy};
+    use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
+    use math::fields::f128::BaseElement;
+
+    #[test]
+    fn find_nonce() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let coin = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(&seed);
+        let leading_zeros = |nonce| coin.check_leading_zeros(nonce);
+
+        let nonce = SequentialPowStrategy.find_nonce(8, &leading_zeros).unwrap();
+        assert!(coin.check_leading_zeros(nonce) >= 8);
+        assert!((1..nonce).all(|nonce| coin.check_leading_zeros(nonce) < 8));
+
+        let nonce = DefaultPowStrategy.find_nonce(8, &leading_zeros).unwrap();
+        assert!(coin.check_leading_zeros(nonce) >= 8);
+    }
+}
@@ -3,14 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::TraceTable;
+use crate::{Prover, Trace, TraceTable};
 use air::{
-    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    proof::ProofMetadata, Air, AirContext, Assertion, EvaluationFrame, FieldExtension,
+    ProofOptions, TraceInfo, TransitionConstraintDegree,
 };
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree};
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
 use utils::collections::Vec;
 
+mod prover;
+
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
 
@@ -104,6 +107,98 @@ impl Air for MockAir {
     }
 }
 
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for the Fibonacci sequence computed by [build_fib_trace()]; the last computed term is the
+/// public input.
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+pub struct FibProver {
+    pub options: ProofOptions,
+    pub metadata: Option<ProofMetadata>,
+    pub salt_seed: Option<[u8; 32]>,
+}
+
+impl FibProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            metadata: None,
+            salt_seed: None,
+        }
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = MerkleTree<Self::HashFn>;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn metadata(&self) -> Option<ProofMetadata> {
+        self.metadata.clone()
+    }
+
+    fn commitment_salt_seed(&self) -> Option<[u8; 32]> {
+        self.salt_seed
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,200 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, FibAir, FibProver};
+use crate::{Prover, StarkProof, Trace};
+use air::{proof::ProofMetadata, Air, FieldExtension, LdtKind, ProofOptions};
+use crypto::{hashers::Blake3_256, QuerySampling};
+use math::fields::f128::BaseElement;
+
+// PROOF CONTENTS TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_metadata() {
+    let metadata = ProofMetadata::new("fib", 1_700_000_000).with_entry("job", "42");
+    let mut prover = FibProver::new(build_options(false));
+    prover.metadata = Some(metadata.clone());
+    let proof = prover.prove(build_fib_trace(64)).unwrap();
+    assert_eq!(Some(&metadata), proof.metadata.as_ref());
+}
+
+#[test]
+fn prove_with_salted_commitments() {
+    let unsalted_proof = prove_fib(build_options(false), 64);
+    assert!(!unsalted_proof.trace_queries[0].is_salted());
+    assert!(!unsalted_proof.constraint_queries.is_salted());
+
+    let mut prover = FibProver::new(build_options(false));
+    prover.salt_seed = Some([1; 32]);
+    let proof = prover.prove(build_fib_trace(64)).unwrap();
+    assert!(proof.trace_queries[0].is_salted());
+    assert!(proof.constraint_queries.is_salted());
+    assert_ne!(unsalted_proof.commitments, proof.commitments);
+
+    // salts are derived from the seed
+    prover.salt_seed = Some([2; 32]);
+    let other_proof = prover.prove(build_fib_trace(64)).unwrap();
+    assert_ne!(proof.commitments, other_proof.commitments);
+}
+
+#[test]
+fn prove_with_seed() {
+    let mut prover = FibProver::new(build_options(false));
+    prover.salt_seed = Some([1; 32]);
+    let salted_proof = prover.prove(build_fib_trace(64)).unwrap();
+
+    // proofs generated with the same seed are identical
+    let proof1 = prover
+        .prove_with_seed(build_fib_trace(64), [3; 32])
+        .unwrap();
+    let proof2 = prover
+        .prove_with_seed(build_fib_trace(64), [3; 32])
+        .unwrap();
+    assert_eq!(proof1.to_bytes(), proof2.to_bytes());
+
+    // salts are derived from the explicit seed rather than from the prover's salt seed
+    assert_ne!(salted_proof.commitments, proof1.commitments);
+    let other_proof = prover
+        .prove_with_seed(build_fib_trace(64), [4; 32])
+        .unwrap();
+    assert_ne!(proof1.commitments, other_proof.commitments);
+}
+
+#[test]
+fn prove_with_bit_slice_query_sampling() {
+    let proof = prove_fib(build_options(false), 64);
+
+    // proofs with bit-slice query sampling open the positions drawn by the bit-slice sampler
+    let options = build_options(false).with_query_sampling(QuerySampling::BitSlice);
+    let bit_slice_proof = prove_fib(options, 64);
+    assert_eq!(
+        QuerySampling::BitSlice,
+        bit_slice_proof.options().query_sampling()
+    );
+    assert_ne!(proof.trace_queries, bit_slice_proof.trace_queries);
+}
+
+#[test]
+fn prove_with_multiple_ood_points() {
+    let single_point_proof = prove_fib(build_options(true), 64);
+    let proof = prove_fib(build_options(true).with_num_ood_points(2), 64);
+    assert_eq!(2, proof.options().num_ood_points());
+
+    // the out-of-domain frame contains trace and constraint evaluations for both points
+    assert_eq!(
+        2 * single_point_proof.ood_frame.trace_states_bytes().len() - 1,
+        proof.ood_frame.trace_states_bytes().len()
+    );
+    assert_eq!(
+        2 * single_point_proof.ood_frame.evaluations_bytes().len(),
+        proof.ood_frame.evaluations_bytes().len()
+    );
+}
+
+#[test]
+fn prove_with_fri_layer_grinding() {
+    let proof = prove_fib(build_options(false).with_fri_layer_grinding(8), 64);
+    assert_eq!(8, proof.options().fri_layer_grinding_factor());
+    assert_eq!(
+        proof.fri_proof.num_layers(),
+        proof.fri_proof.layer_nonces().len()
+    );
+}
+
+#[test]
+fn prove_with_stir() {
+    for use_extension_field in [false, true] {
+        let proof = prove_fib(
+            build_options(use_extension_field).with_ldt(LdtKind::Stir),
+            1024,
+        );
+        assert_eq!(LdtKind::Stir, proof.options().ldt());
+        assert!(proof.stir_proof.is_some());
+        assert_eq!(0, proof.fri_proof.num_layers());
+    }
+}
+
+#[test]
+fn prove_with_merkle_multi_proofs() {
+    let options = build_options(false).with_merkle_multi_proofs(true);
+    let prover = FibProver::new(options.clone());
+    let trace = build_fib_trace(1024);
+    let air = FibAir::new(trace.get_info(), prover.get_pub_inputs(&trace), options);
+    let proof = prover.prove(trace).unwrap();
+    assert!(proof.options().merkle_multi_proofs());
+
+    // paths of trace and constraint queries are serialized as Merkle multi-proofs
+    let num_queries = air.options().num_queries();
+    let (trace_proof, leaves, _) = proof.trace_queries[0]
+        .view()
+        .parse_multi::<Blake3_256<BaseElement>, BaseElement>(
+            air.lde_domain_size(),
+            num_queries,
+            air.trace_layout().main_trace_width(),
+        )
+        .unwrap();
+    assert_eq!(num_queries, leaves.len());
+    assert_eq!(air.lde_domain_size().ilog2() as u8, trace_proof.depth);
+    assert!(proof
+        .constraint_queries
+        .view()
+        .parse_multi::<Blake3_256<BaseElement>, BaseElement>(
+            air.lde_domain_size(),
+            num_queries,
+            air.context().num_constraint_composition_columns(),
+        )
+        .is_ok());
+}
+
+#[test]
+fn proof_size_estimation() {
+    for (ldt, multi_proofs) in [
+        (LdtKind::Fri, false),
+        (LdtKind::Fri, true),
+        (LdtKind::Stir, false),
+    ] {
+        for use_extension_field in [false, true] {
+            let options = build_options(use_extension_field)
+                .with_ldt(ldt)
+                .with_merkle_multi_proofs(multi_proofs);
+            let prover = FibProver::new(options.clone());
+            let trace = build_fib_trace(1024);
+            let air = FibAir::new(
+                trace.get_info(),
+                prover.get_pub_inputs(&trace),
+                options.clone(),
+            );
+            let proof_size = prover.prove(trace).unwrap().to_bytes().len();
+
+            // the estimate is close to the actual proof size; the actual size depends on how many
+            // Merkle nodes are shared between authentication paths of the drawn query positions
+            let estimate = StarkProof::estimate_size::<_, Blake3_256<BaseElement>>(&air, &options);
+            let difference = (estimate as f64 - proof_size as f64).abs();
+            assert!(
+                difference < proof_size as f64 * 0.1,
+                "estimated size {estimate} is too far from actual size {proof_size}"
+            );
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 7)
+}
+
+fn prove_fib(options: ProofOptions, sequence_length: usize) -> StarkProof {
+    FibProver::new(options)
+        .prove(build_fib_trace(sequence_length))
+        .unwrap()
+}
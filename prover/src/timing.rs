@@ -0,0 +1,28 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Replacement for [std::time::Instant] on `wasm32-unknown-unknown` target.
+//!
+//! The standard library is available on this target, but there is no clock, and calling
+//! [std::time::Instant::now()] panics. Thus, when the prover is compiled with `std` feature
+//! (e.g., to enable multi-threaded proof generation in a browser), durations of proof generation
+//! steps are not measured and are always reported as 0.
+
+use std::time::Duration;
+
+/// A measurement of a clock which never advances.
+pub struct Instant;
+
+impl Instant {
+    /// Returns an instant corresponding to "now".
+    pub fn now() -> Self {
+        Instant
+    }
+
+    /// Returns the amount of time elapsed since this instant was created; this is always 0.
+    pub fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
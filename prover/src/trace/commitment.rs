@@ -3,9 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::RowMatrix;
+use crate::{RowCommitment, RowMatrix};
 use air::proof::Queries;
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, MerkleTree, VectorCommitment};
 use math::FieldElement;
 use utils::collections::Vec;
 
@@ -18,26 +18,36 @@ use super::TraceLde;
 ///
 /// The describes one or more trace segments, each consisting of the following components:
 /// * Evaluations of a trace segment's polynomials over the LDE domain.
-/// * Merkle tree where each leaf in the tree corresponds to a row in the trace LDE matrix.
-pub struct TraceCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+/// * Vector commitment (a Merkle tree by default) where each committed item corresponds to a
+///   (possibly salted) row in the trace LDE matrix.
+pub struct TraceCommitment<
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H> = MerkleTree<H>,
+> {
     trace_lde: TraceLde<E>,
-    main_segment_tree: MerkleTree<H>,
-    aux_segment_trees: Vec<MerkleTree<H>>,
+    main_segment_tree: RowCommitment<H, V>,
+    aux_segment_trees: Vec<RowCommitment<H, V>>,
 }
 
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitment<E, H> {
+impl<E, H, V> TraceCommitment<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new trace commitment from the provided main trace low-degree extension and the
-    /// corresponding Merkle tree commitment.
+    /// corresponding vector commitment.
     pub fn new(
         main_trace_lde: RowMatrix<E::BaseField>,
-        main_trace_tree: MerkleTree<H>,
+        main_trace_tree: RowCommitment<H, V>,
         blowup: usize,
     ) -> Self {
         assert_eq!(
             main_trace_lde.num_rows(),
-            main_trace_tree.leaves().len(),
+            main_trace_tree.num_leaves(),
             "number of rows in trace LDE must be the same as number of leaves in trace commitment"
         );
         Self {
@@ -50,11 +60,16 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
-    /// Adds the provided auxiliary segment trace LDE and Merkle tree to this trace commitment.
-    pub fn add_segment(&mut self, aux_segment_lde: RowMatrix<E>, aux_segment_tree: MerkleTree<H>) {
+    /// Adds the provided auxiliary segment trace LDE and vector commitment to this trace
+    /// commitment.
+    pub fn add_segment(
+        &mut self,
+        aux_segment_lde: RowMatrix<E>,
+        aux_segment_tree: RowCommitment<H, V>,
+    ) {
         assert_eq!(
             aux_segment_lde.num_rows(),
-            aux_segment_tree.leaves().len(),
+            aux_segment_tree.num_leaves(),
             "number of rows in trace LDE must be the same as number of leaves in trace commitment"
         );
 
@@ -74,20 +89,18 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
 
     // QUERY TRACE
     // --------------------------------------------------------------------------------------------
-    /// Returns trace table rows at the specified positions along with Merkle authentication paths
-    /// from the commitment root to these rows.
+    /// Returns trace table rows at the specified positions along with proofs of opening these
+    /// rows against the segment commitments (e.g., Merkle authentication paths).
     pub fn query(&self, positions: &[usize]) -> Vec<Queries> {
         // build queries for the main trace segment
-        let mut result = vec![build_segment_queries(
-            self.trace_lde.get_main_segment(),
-            &self.main_segment_tree,
-            positions,
-        )];
+        let mut result = vec![self
+            .main_segment_tree
+            .query(self.trace_lde.get_main_segment(), positions)];
 
         // build queries for auxiliary trace segments
         for (i, segment_tree) in self.aux_segment_trees.iter().enumerate() {
             let segment_lde = self.trace_lde.get_aux_segment(i);
-            result.push(build_segment_queries(segment_lde, segment_tree, positions));
+            result.push(segment_tree.query(segment_lde, positions));
         }
 
         result
@@ -96,10 +109,10 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
     // TEST HELPERS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns the root of the commitment Merkle tree.
+    /// Returns the root of the main trace segment commitment.
     #[cfg(test)]
     pub fn main_trace_root(&self) -> H::Digest {
-        *self.main_segment_tree.root()
+        self.main_segment_tree.root()
     }
 
     /// Returns the entire trace for the column at the specified index.
@@ -111,30 +124,3 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
             .collect()
     }
 }
-
-// HELPER FUNCTIONS
-// ================================================================================================
-
-fn build_segment_queries<E, H>(
-    segment_lde: &RowMatrix<E>,
-    segment_tree: &MerkleTree<H>,
-    positions: &[usize],
-) -> Queries
-where
-    E: FieldElement,
-    H: ElementHasher<BaseField = E::BaseField>,
-{
-    // for each position, get the corresponding row from the trace segment LDE and put all these
-    // rows into a single vector
-    let trace_states = positions
-        .iter()
-        .map(|&pos| segment_lde.row(pos).to_vec())
-        .collect::<Vec<_>>();
-
-    // build Merkle authentication paths to the leaves specified by positions
-    let trace_proof = segment_tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for trace queries");
-
-    Queries::new(trace_proof, trace_states)
-}
@@ -0,0 +1,545 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ColMatrix;
+use math::{FieldElement, StarkField};
+use std::io::{self, Write};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the column containing trace step indexes in exported traces.
+const STEP_COLUMN_NAME: &str = "step";
+
+/// Magic bytes at the start and at the end of a Parquet file.
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+// TRACE EXPORT
+// ================================================================================================
+
+/// Writes the main segment and (optionally) auxiliary segments of an execution trace into a file
+/// in CSV or Parquet formats.
+///
+/// The exported table contains a `step` column followed by one column per column of the main
+/// trace segment and one column per base field component of each column of auxiliary segments.
+/// Unless names are specified via [with_column_names()](TraceExport::with_column_names) and
+/// [with_aux_column_names()](TraceExport::with_aux_column_names), main trace columns are named
+/// `main_{i}` and auxiliary trace columns are named `aux_{i}`, where `i` is the index of the
+/// column in the respective segments; components of extension field elements are exported as
+/// separate columns with `_{k}` appended to the column name.
+///
+/// Values are exported as canonical integer representations of base field elements. In Parquet
+/// files, the values are stored as unsigned 64-bit integers for fields with moduli of up to 64
+/// bits, and as decimal strings for larger fields.
+pub struct TraceExport<'a, B: StarkField, E: FieldElement<BaseField = B>> {
+    main_segment: &'a ColMatrix<B>,
+    main_column_names: Vec<String>,
+    aux_segments: &'a [ColMatrix<E>],
+    aux_column_names: Vec<String>,
+}
+
+impl<'a, B: StarkField> TraceExport<'a, B, B> {
+    /// Returns a new export of the specified main trace segment.
+    pub fn new(main_segment: &'a ColMatrix<B>) -> Self {
+        TraceExport {
+            main_segment,
+            main_column_names: default_column_names("main", main_segment.num_cols()),
+            aux_segments: &[],
+            aux_column_names: Vec::new(),
+        }
+    }
+}
+
+impl<'a, B: StarkField, E: FieldElement<BaseField = B>> TraceExport<'a, B, E> {
+    /// Adds the specified auxiliary trace segments to this export.
+    ///
+    /// # Panics
+    /// Panics if the length of any of the segments differs from the length of the main segment.
+    pub fn with_aux_segments<F>(self, aux_segments: &'a [ColMatrix<F>]) -> TraceExport<'a, B, F>
+    where
+        F: FieldElement<BaseField = B>,
+    {
+        let num_rows = self.main_segment.num_rows();
+        for (i, segment) in aux_segments.iter().enumerate() {
+            assert_eq!(
+                num_rows,
+                segment.num_rows(),
+                "length of auxiliary segment {i} must be {num_rows}, but was {}",
+                segment.num_rows()
+            );
+        }
+        let num_aux_columns = aux_segments.iter().map(|s| s.num_cols()).sum();
+        TraceExport {
+            main_segment: self.main_segment,
+            main_column_names: self.main_column_names,
+            aux_segments,
+            aux_column_names: default_column_names("aux", num_aux_columns),
+        }
+    }
+
+    /// Sets names of the columns of the main trace segment.
+    ///
+    /// # Panics
+    /// Panics if the number of names differs from the number of columns in the main segment.
+    pub fn with_column_names(mut self, names: &[&str]) -> Self {
+        assert_eq!(
+            self.main_segment.num_cols(),
+            names.len(),
+            "expected {} main trace column names, but received {}",
+            self.main_segment.num_cols(),
+            names.len()
+        );
+        self.main_column_names = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Sets names of the columns of auxiliary trace segments; the names are listed for all
+    /// auxiliary segments in the order in which the segments were added.
+    ///
+    /// # Panics
+    /// Panics if the number of names differs from the number of columns in all auxiliary
+    /// segments.
+    pub fn with_aux_column_names(mut self, names: &[&str]) -> Self {
+        assert_eq!(
+            self.aux_column_names.len(),
+            names.len(),
+            "expected {} auxiliary trace column names, but received {}",
+            self.aux_column_names.len(),
+            names.len()
+        );
+        self.aux_column_names = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns names of all columns of the exported table, including the `step` column.
+    pub fn column_names(&self) -> Vec<String> {
+        let mut result = vec![STEP_COLUMN_NAME.to_string()];
+        result.extend(self.main_column_names.iter().cloned());
+        for name in self.aux_column_names.iter() {
+            if E::EXTENSION_DEGREE == 1 {
+                result.push(name.clone());
+            } else {
+                result.extend((0..E::EXTENSION_DEGREE).map(|k| format!("{name}_{k}")));
+            }
+        }
+        result
+    }
+
+    /// Returns the number of rows in the exported table.
+    pub fn num_rows(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    // EXPORT METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the trace into `target` in CSV format.
+    ///
+    /// The first line contains column names, and each subsequent line contains values of a
+    /// single trace step.
+    ///
+    /// # Errors
+    /// Returns an error if writing into `target` fails.
+    pub fn write_csv<W: Write>(&self, target: &mut W) -> io::Result<()> {
+        let names = self
+            .column_names()
+            .iter()
+            .map(|name| csv_field(name))
+            .collect::<Vec<_>>();
+        writeln!(target, "{}", names.join(","))?;
+
+        let mut row = Vec::with_capacity(names.len() - 1);
+        for step in 0..self.num_rows() {
+            row.clear();
+            self.read_row(step, &mut row);
+            write!(target, "{step}")?;
+            for value in row.iter() {
+                write!(target, ",{value}")?;
+            }
+            writeln!(target)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the trace into `target` as a Parquet file.
+    ///
+    /// The file contains a single row group with one uncompressed, plain-encoded data page per
+    /// column; all columns are required (i.e., non-nullable).
+    ///
+    /// # Errors
+    /// Returns an error if writing into `target` fails, or if data of a single column exceeds
+    /// the maximum size of a Parquet data page (2 GB).
+    pub fn write_parquet<W: Write>(&self, target: &mut W) -> io::Result<()> {
+        let value_type = if B::MODULUS_BITS <= 64 {
+            ParquetType::UInt64
+        } else {
+            ParquetType::String
+        };
+
+        let mut offset = PARQUET_MAGIC.len();
+        target.write_all(PARQUET_MAGIC)?;
+
+        let mut columns = Vec::new();
+        let steps = encode_steps(self.num_rows());
+        let chunk = encode_column_chunk(steps, ParquetType::Int64, self.num_rows(), offset)?;
+        offset += chunk.bytes.len();
+        target.write_all(&chunk.bytes)?;
+        columns.push(chunk);
+
+        for column_idx in 0..self.column_names().len() - 1 {
+            let values = (0..self.num_rows()).map(|step| self.get_base_value(column_idx, step));
+            let data = encode_field_values(values, value_type);
+            let chunk = encode_column_chunk(data, value_type, self.num_rows(), offset)?;
+            offset += chunk.bytes.len();
+            target.write_all(&chunk.bytes)?;
+            columns.push(chunk);
+        }
+
+        let metadata = encode_file_metadata(&self.column_names(), &columns, self.num_rows());
+        target.write_all(&metadata)?;
+        target.write_all(&(metadata.len() as u32).to_le_bytes())?;
+        target.write_all(PARQUET_MAGIC)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends base field components of all values at the specified step (except for the step
+    /// itself) to `row`.
+    fn read_row(&self, step: usize, row: &mut Vec<B>) {
+        row.extend(self.main_segment.columns().map(|column| column[step]));
+        for segment in self.aux_segments.iter() {
+            for column in segment.columns() {
+                let value = column[step];
+                row.extend((0..E::EXTENSION_DEGREE).map(|k| value.base_element(k)));
+            }
+        }
+    }
+
+    /// Returns the value of the specified column at the specified step; columns are indexed
+    /// without the step column, and components of extension field elements are counted as
+    /// separate columns.
+    fn get_base_value(&self, column_idx: usize, step: usize) -> B {
+        let num_main_columns = self.main_segment.num_cols();
+        if column_idx < num_main_columns {
+            return self.main_segment.get(column_idx, step);
+        }
+
+        let aux_column_idx = column_idx - num_main_columns;
+        let component_idx = aux_column_idx % E::EXTENSION_DEGREE;
+        let mut column_idx = aux_column_idx / E::EXTENSION_DEGREE;
+        for segment in self.aux_segments.iter() {
+            if column_idx < segment.num_cols() {
+                return segment.get(column_idx, step).base_element(component_idx);
+            }
+            column_idx -= segment.num_cols();
+        }
+        unreachable!("column index out of bounds")
+    }
+}
+
+// PARQUET ENCODING
+// ================================================================================================
+
+/// Types of values in Parquet columns written by [TraceExport::write_parquet()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParquetType {
+    /// Signed 64-bit integers (physical type INT64).
+    Int64,
+    /// Unsigned 64-bit integers (physical type INT64 annotated as UINT_64).
+    UInt64,
+    /// UTF-8 strings (physical type BYTE_ARRAY annotated as UTF8).
+    String,
+}
+
+impl ParquetType {
+    fn physical_type(&self) -> i32 {
+        match self {
+            Self::Int64 | Self::UInt64 => 2,
+            Self::String => 6,
+        }
+    }
+
+    fn converted_type(&self) -> Option<i32> {
+        match self {
+            Self::Int64 => None,
+            Self::UInt64 => Some(14),
+            Self::String => Some(0),
+        }
+    }
+}
+
+/// Returns plain encoding of step indexes of a trace with the specified number of steps.
+fn encode_steps(num_steps: usize) -> Vec<u8> {
+    (0..num_steps as u64)
+        .flat_map(|step| step.to_le_bytes())
+        .collect()
+}
+
+/// Returns plain encoding of the specified field elements as values of the specified type.
+fn encode_field_values<B: StarkField>(
+    values: impl Iterator<Item = B>,
+    value_type: ParquetType,
+) -> Vec<u8> {
+    let mut result = Vec::new();
+    for value in values {
+        match value_type {
+            // serialized canonical representations of field elements are little-endian, and
+            // thus, for fields with moduli of up to 64 bits, the first 8 bytes of the
+            // representation are the plain encoding of the value
+            ParquetType::Int64 | ParquetType::UInt64 => {
+                let mut bytes = value.to_bytes();
+                bytes.resize(8, 0);
+                result.extend_from_slice(&bytes[..8]);
+            }
+            ParquetType::String => {
+                let value = value.to_string();
+                result.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                result.extend_from_slice(value.as_bytes());
+            }
+        }
+    }
+    result
+}
+
+/// An encoded column chunk together with the information needed to describe it in the file
+/// metadata.
+struct ColumnChunk {
+    bytes: Vec<u8>,
+    value_type: ParquetType,
+    num_values: usize,
+    offset: usize,
+}
+
+/// Builds a column chunk consisting of a single data page from plain-encoded `data` of
+/// `num_values` values; `offset` is the position of the chunk in the file.
+fn encode_column_chunk(
+    data: Vec<u8>,
+    value_type: ParquetType,
+    num_values: usize,
+    offset: usize,
+) -> io::Result<ColumnChunk> {
+    let page_size = i32::try_from(data.len()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "column data does not fit into a single Parquet data page",
+        )
+    })?;
+
+    // page header: DATA_PAGE with PLAIN values; levels use RLE encoding but are not written
+    // because all columns are required and not nested
+    let mut header = ThriftWriter::new();
+    header.write_i32(1, 0);
+    header.write_i32(2, page_size);
+    header.write_i32(3, page_size);
+    header.begin_struct(5);
+    header.write_i32(1, num_values as i32);
+    header.write_i32(2, 0);
+    header.write_i32(3, 3);
+    header.write_i32(4, 3);
+    header.end_struct();
+    let mut bytes = header.finish();
+    bytes.extend_from_slice(&data);
+
+    Ok(ColumnChunk {
+        bytes,
+        value_type,
+        num_values,
+        offset,
+    })
+}
+
+/// Encodes file metadata describing a single row group consisting of the specified column
+/// chunks.
+fn encode_file_metadata(names: &[String], columns: &[ColumnChunk], num_rows: usize) -> Vec<u8> {
+    let mut writer = ThriftWriter::new();
+    writer.write_i32(1, 1);
+
+    // schema: the root element followed by one element per column
+    writer.begin_list(2, ThriftWriter::STRUCT, names.len() + 1);
+    writer.begin_list_struct();
+    writer.write_string(4, "schema");
+    writer.write_i32(5, names.len() as i32);
+    writer.end_struct();
+    for (name, column) in names.iter().zip(columns) {
+        writer.begin_list_struct();
+        writer.write_i32(1, column.value_type.physical_type());
+        writer.write_i32(3, 0);
+        writer.write_string(4, name);
+        if let Some(converted_type) = column.value_type.converted_type() {
+            writer.write_i32(6, converted_type);
+        }
+        writer.end_struct();
+    }
+    writer.write_i64(3, num_rows as i64);
+
+    // a single row group
+    let total_size = columns.iter().map(|c| c.bytes.len()).sum::<usize>();
+    writer.begin_list(4, ThriftWriter::STRUCT, 1);
+    writer.begin_list_struct();
+    writer.begin_list(1, ThriftWriter::STRUCT, columns.len());
+    for (name, column) in names.iter().zip(columns) {
+        writer.begin_list_struct();
+        writer.write_i64(2, column.offset as i64);
+        writer.begin_struct(3);
+        writer.write_i32(1, column.value_type.physical_type());
+        writer.begin_list(2, ThriftWriter::I32, 1);
+        writer.write_list_i32(0);
+        writer.begin_list(3, ThriftWriter::BINARY, 1);
+        writer.write_list_string(name);
+        writer.write_i32(4, 0);
+        writer.write_i64(5, column.num_values as i64);
+        writer.write_i64(6, column.bytes.len() as i64);
+        writer.write_i64(7, column.bytes.len() as i64);
+        writer.write_i64(9, column.offset as i64);
+        writer.end_struct();
+        writer.end_struct();
+    }
+    writer.write_i64(2, total_size as i64);
+    writer.write_i64(3, num_rows as i64);
+    writer.end_struct();
+
+    writer.write_string(6, "winterfell");
+    writer.finish()
+}
+
+// THRIFT WRITER
+// ================================================================================================
+
+/// A minimal writer of structs in Thrift compact protocol, which is used to encode Parquet
+/// metadata.
+///
+/// The writer starts with an open top-level struct; the struct is closed by
+/// [finish()](ThriftWriter::finish).
+struct ThriftWriter {
+    bytes: Vec<u8>,
+    /// IDs of the last fields written into each of the currently open structs.
+    last_field_ids: Vec<i16>,
+}
+
+impl ThriftWriter {
+    const I32: u8 = 5;
+    const I64: u8 = 6;
+    const BINARY: u8 = 8;
+    const LIST: u8 = 9;
+    const STRUCT: u8 = 12;
+
+    fn new() -> Self {
+        ThriftWriter {
+            bytes: Vec::new(),
+            last_field_ids: vec![0],
+        }
+    }
+
+    fn write_i32(&mut self, field_id: i16, value: i32) {
+        self.write_field_header(field_id, Self::I32);
+        self.write_varint(zigzag(value as i64));
+    }
+
+    fn write_i64(&mut self, field_id: i16, value: i64) {
+        self.write_field_header(field_id, Self::I64);
+        self.write_varint(zigzag(value));
+    }
+
+    fn write_string(&mut self, field_id: i16, value: &str) {
+        self.write_field_header(field_id, Self::BINARY);
+        self.write_list_string(value);
+    }
+
+    /// Starts a struct-valued field; fields of the struct are written until
+    /// [end_struct()](ThriftWriter::end_struct) is called.
+    fn begin_struct(&mut self, field_id: i16) {
+        self.write_field_header(field_id, Self::STRUCT);
+        self.last_field_ids.push(0);
+    }
+
+    /// Starts a list-valued field; list elements must be written right after this call.
+    fn begin_list(&mut self, field_id: i16, element_type: u8, size: usize) {
+        self.write_field_header(field_id, Self::LIST);
+        if size < 15 {
+            self.bytes.push(((size as u8) << 4) | element_type);
+        } else {
+            self.bytes.push(0xf0 | element_type);
+            self.write_varint(size as u64);
+        }
+    }
+
+    /// Starts a struct which is an element of a list.
+    fn begin_list_struct(&mut self) {
+        self.last_field_ids.push(0);
+    }
+
+    fn write_list_i32(&mut self, value: i32) {
+        self.write_varint(zigzag(value as i64));
+    }
+
+    fn write_list_string(&mut self, value: &str) {
+        self.write_varint(value.len() as u64);
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    fn end_struct(&mut self) {
+        self.bytes.push(0);
+        self.last_field_ids.pop();
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.end_struct();
+        debug_assert!(
+            self.last_field_ids.is_empty(),
+            "not all structs were closed"
+        );
+        self.bytes
+    }
+
+    fn write_field_header(&mut self, field_id: i16, field_type: u8) {
+        let last_field_id = self.last_field_ids.last_mut().expect("no open struct");
+        let delta = field_id - *last_field_id;
+        *last_field_id = field_id;
+        if delta > 0 && delta <= 15 {
+            self.bytes.push(((delta as u8) << 4) | field_type);
+        } else {
+            self.bytes.push(field_type);
+            self.write_varint(zigzag(field_id as i64));
+        }
+    }
+
+    fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns names `{prefix}_{i}` for the specified number of columns.
+fn default_column_names(prefix: &str, num_columns: usize) -> Vec<String> {
+    (0..num_columns).map(|i| format!("{prefix}_{i}")).collect()
+}
+
+/// Quotes the specified CSV field if it contains characters which have special meaning in CSV.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Maps a signed integer into an unsigned one as done in Thrift compact protocol.
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
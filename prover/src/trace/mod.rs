@@ -4,8 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{matrix::MultiColumnIter, ColMatrix};
-use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
-use math::{polynom, FieldElement, StarkField};
+use air::{Air, AuxTraceRandElements, EvaluationFrame, LookupTable, TraceInfo, TraceLayout};
+use math::{batch_inversion, FieldElement, StarkField};
+use utils::collections::Vec;
 
 mod trace_lde;
 pub use trace_lde::TraceLde;
@@ -19,6 +20,11 @@ pub use trace_table::{TraceTable, TraceTableFragment};
 mod commitment;
 pub use commitment::TraceCommitment;
 
+#[cfg(feature = "export")]
+mod export;
+#[cfg(feature = "export")]
+pub use export::TraceExport;
+
 #[cfg(test)]
 mod tests;
 
@@ -93,6 +99,56 @@ pub trait Trace: Sized {
         self.layout().aux_trace_width()
     }
 
+    /// Builds and returns the next auxiliary trace segment including running-sum columns for
+    /// lookup tables defined by the specified AIR. If there are no more segments to build, None
+    /// is returned.
+    ///
+    /// Columns of the segment (if any) are built via [build_aux_segment()](Trace::build_aux_segment)
+    /// function. Running-sum columns of lookup tables are then appended to the first auxiliary
+    /// segment; if all columns of the first segment are running-sum columns,
+    /// [build_aux_segment()](Trace::build_aux_segment) is not invoked for this segment.
+    ///
+    /// # Panics
+    /// Panics if the segment built via [build_aux_segment()](Trace::build_aux_segment) does not
+    /// leave exactly enough columns for the running-sum columns of all lookup tables.
+    fn build_aux_segment_with_lookups<A, E>(
+        &mut self,
+        air: &A,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        A: Air<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let lookup_tables = air.context().lookup_tables();
+        if !aux_segments.is_empty() || lookup_tables.is_empty() {
+            return self.build_aux_segment(aux_segments, rand_elements);
+        }
+
+        let num_columns = self.layout().get_aux_segment_width(0) - lookup_tables.len();
+        let mut columns = if num_columns == 0 {
+            Vec::new()
+        } else {
+            self.build_aux_segment(aux_segments, rand_elements)?
+                .into_columns()
+        };
+        assert_eq!(
+            columns.len(),
+            num_columns,
+            "expected {} auxiliary columns in addition to lookup table columns, but was {}",
+            num_columns,
+            columns.len()
+        );
+
+        let (alpha, beta) = LookupTable::get_rand_elements(rand_elements);
+        for table in lookup_tables {
+            columns.push(build_running_sum(self.main_segment(), table, alpha, beta));
+        }
+
+        Some(ColMatrix::new(columns))
+    }
+
     // VALIDATION
     // --------------------------------------------------------------------------------------------
     /// Checks if this trace is valid against the specified AIR, and panics if not.
@@ -132,7 +188,7 @@ pub trait Trace: Sized {
         }
 
         // then, check assertions against auxiliary trace segments
-        for assertion in air.get_aux_assertions(aux_rand_elements) {
+        for assertion in air.get_aux_assertions_with_lookups(aux_rand_elements) {
             // find which segment the assertion is for and remap assertion column index to the
             // column index in the context of this segment
             let mut column_idx = assertion.column();
@@ -160,10 +216,11 @@ pub trait Trace: Sized {
 
         // --- 2. make sure this trace satisfies all transition constraints -----------------------
 
-        // collect the info needed to build periodic values for a specific step
+        // collect the info needed to build periodic values for a specific step; over the trace
+        // domain, values of periodic columns can be read directly from the column definitions
         let g = air.trace_domain_generator();
-        let periodic_values_polys = air.get_periodic_column_polys();
-        let mut periodic_values = vec![Self::BaseField::ZERO; periodic_values_polys.len()];
+        let periodic_columns = air.get_periodic_column_values();
+        let mut periodic_values = vec![Self::BaseField::ZERO; periodic_columns.len()];
 
         // initialize buffers to hold evaluation frames and results of constraint evaluations
         let mut x = Self::BaseField::ONE;
@@ -181,10 +238,8 @@ pub trait Trace: Sized {
         // number of steps exempt from transition constraints (guaranteed to be at least 1)
         for step in 0..self.length() - air.context().num_transition_exemptions() {
             // build periodic values
-            for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
-                let num_cycles = air.trace_length() / p.len();
-                let x = x.exp((num_cycles as u32).into());
-                *v = polynom::eval(p, x);
+            for (column, v) in periodic_columns.iter().zip(periodic_values.iter_mut()) {
+                *v = column[step % column.len()];
             }
 
             // evaluate transition constraints for the main trace segment and make sure they all
@@ -202,7 +257,7 @@ pub trait Trace: Sized {
             // sure they all evaluate to zeros
             if let Some(ref mut aux_frame) = aux_frame {
                 read_aux_frame(aux_segments, step, aux_frame);
-                air.evaluate_aux_transition(
+                air.evaluate_aux_transition_with_lookups(
                     &main_frame,
                     aux_frame,
                     &periodic_values,
@@ -226,6 +281,43 @@ pub trait Trace: Sized {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Builds the running-sum column for the specified lookup table over the main trace segment.
+fn build_running_sum<B, E>(
+    main_segment: &ColMatrix<B>,
+    table: &LookupTable,
+    alpha: E,
+    beta: E,
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let num_rows = main_segment.num_rows();
+    let num_fractions = table.num_queries() + 1;
+
+    // compute denominators of all fractions in all rows and invert them in a single batch
+    let mut row = vec![B::ZERO; main_segment.num_cols()];
+    let mut denominators = E::zeroed_vector(num_rows * num_fractions);
+    for (i, row_denominators) in denominators.chunks_mut(num_fractions).enumerate() {
+        main_segment.read_row_into(i, &mut row);
+        table.evaluate_denominators(&row, alpha, beta, row_denominators);
+    }
+    let inverses = batch_inversion(&denominators);
+
+    // the running sum starts at zero and accumulates fractions of the previous row
+    let mut result = E::zeroed_vector(num_rows);
+    for i in 0..num_rows - 1 {
+        let fractions = &inverses[i * num_fractions..(i + 1) * num_fractions];
+        let multiplicity = main_segment.get(table.multiplicity_column(), i);
+        let delta = fractions[..table.num_queries()]
+            .iter()
+            .fold(E::ZERO, |acc, &value| acc + value)
+            - fractions[table.num_queries()].mul_base(multiplicity);
+        result[i + 1] = result[i] + delta;
+    }
+    result
+}
+
 /// Reads an evaluation frame from the set of provided auxiliary segments. This expects that
 /// `aux_segments` contains at least one entry.
 ///
@@ -6,14 +6,14 @@
 use crate::{
     tests::{build_fib_trace, MockAir},
     trace::TracePolyTable,
-    RowMatrix, StarkDomain, Trace, TraceCommitment,
+    RowMatrix, StarkDomain, Trace, TraceCommitment, TraceTable,
 };
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
     fields::f128::BaseElement, get_power_series, get_power_series_with_offset, polynom,
     FieldElement, StarkField,
 };
-use utils::collections::Vec;
+use utils::{collections::Vec, Deserializable, Serializable};
 
 type Blake3 = Blake3_256<BaseElement>;
 
@@ -38,6 +38,29 @@ fn new_trace_table() {
     assert_eq!(expected, trace.get_column(1));
 }
 
+#[test]
+fn trace_table_serialization() {
+    let mut trace = build_fib_trace(16);
+    trace.set_meta(vec![1, 2, 3]);
+
+    let bytes = trace.to_bytes();
+    let result = TraceTable::<BaseElement>::read_from_bytes(&bytes).unwrap();
+    assert_eq!(trace.main_trace_width(), result.main_trace_width());
+    assert_eq!(trace.length(), result.length());
+    assert_eq!(trace.meta(), result.meta());
+    for i in 0..trace.main_trace_width() {
+        assert_eq!(trace.get_column(i), result.get_column(i));
+    }
+
+    // truncated traces and traces with invalid dimensions are rejected
+    assert!(TraceTable::<BaseElement>::read_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    for (idx, value) in [(0, 0), (1, 2), (1, 41), (1, 255)] {
+        let mut invalid = bytes.clone();
+        invalid[idx] = value;
+        assert!(TraceTable::<BaseElement>::read_from_bytes(&invalid).is_err());
+    }
+}
+
 #[test]
 fn extend_trace_table() {
     // build the trace and the domain
@@ -49,10 +72,10 @@ fn extend_trace_table() {
     // build extended trace commitment
     let trace_polys = trace.main_segment().interpolate_columns();
     let trace_lde = RowMatrix::evaluate_polys_over::<8>(&trace_polys, &domain);
-    let trace_tree = trace_lde.commit_to_rows::<Blake3>();
+    let trace_tree = trace_lde.commit_to_rows::<Blake3, MerkleTree<Blake3>>();
     let trace_comm = TraceCommitment::<BaseElement, Blake3>::new(
         trace_lde,
-        trace_tree,
+        trace_tree.into(),
         domain.trace_to_lde_blowup(),
     );
     let trace_polys = TracePolyTable::<BaseElement>::new(trace_polys);
@@ -102,10 +125,10 @@ fn commit_trace_table() {
     // build extended trace commitment
     let trace_polys = trace.main_segment().interpolate_columns();
     let trace_lde = RowMatrix::evaluate_polys_over::<8>(&trace_polys, &domain);
-    let trace_tree = trace_lde.commit_to_rows::<Blake3>();
+    let trace_tree = trace_lde.commit_to_rows::<Blake3, MerkleTree<Blake3>>();
     let trace_comm = TraceCommitment::<BaseElement, Blake3>::new(
         trace_lde,
-        trace_tree,
+        trace_tree.into(),
         domain.trace_to_lde_blowup(),
     );
 
@@ -127,6 +150,86 @@ fn commit_trace_table() {
     assert_eq!(*expected_tree.root(), trace_comm.main_trace_root())
 }
 
+#[cfg(feature = "export")]
+#[test]
+fn export_trace_csv() {
+    use crate::ColMatrix;
+    use math::fields::QuadExtension;
+    use utils::string::String;
+
+    let trace = build_fib_trace(16);
+    let mut csv = Vec::new();
+    trace.export_csv(&mut csv).unwrap();
+    let expected = "step,main_0,main_1\n0,1,1\n1,2,3\n2,5,8\n3,13,21\n4,34,55\n5,89,144\n\
+        6,233,377\n7,610,987\n";
+    assert_eq!(expected, String::from_utf8(csv).unwrap());
+
+    // components of extension field elements are exported as separate columns
+    let aux_segments = [ColMatrix::new(vec![(0..8u32)
+        .map(|i| QuadExtension::new(BaseElement::from(i), BaseElement::from(i * 7)))
+        .collect::<Vec<_>>()])];
+    let export = trace
+        .export()
+        .with_column_names(&["a", "b,c"])
+        .with_aux_segments(&aux_segments)
+        .with_aux_column_names(&["p"]);
+    assert_eq!(
+        vec!["step", "a", "b,c", "p_0", "p_1"],
+        export.column_names()
+    );
+
+    let mut csv = Vec::new();
+    export.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(9, lines.len());
+    assert_eq!("step,a,\"b,c\",p_0,p_1", lines[0]);
+    assert_eq!("3,13,21,3,21", lines[4]);
+}
+
+#[cfg(feature = "export")]
+#[test]
+fn export_trace_parquet() {
+    use math::fields::f64::BaseElement as SmallElement;
+
+    fn read_u32(bytes: &[u8], offset: usize) -> usize {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize
+    }
+
+    fn contains(bytes: &[u8], value: &[u8]) -> bool {
+        bytes.windows(value.len()).any(|window| window == value)
+    }
+
+    // file starts and ends with magic bytes, and metadata length precedes the last magic bytes
+    let trace = build_fib_trace(16);
+    let mut file = Vec::new();
+    trace.export_parquet(&mut file).unwrap();
+    assert_eq!(b"PAR1", &file[..4]);
+    assert_eq!(b"PAR1", &file[file.len() - 4..]);
+    let metadata_len = read_u32(&file, file.len() - 8);
+    let metadata = &file[file.len() - 8 - metadata_len..file.len() - 8];
+    for name in ["step", "main_0", "main_1"] {
+        assert!(contains(metadata, name.as_bytes()));
+    }
+
+    // values in 128-bit fields are stored as strings, while step indexes are stored as integers
+    let data = &file[4..file.len() - 8 - metadata_len];
+    let steps = (0..8u64).flat_map(|i| i.to_le_bytes()).collect::<Vec<_>>();
+    assert!(contains(data, &steps));
+    assert!(contains(data, b"\x03\0\0\x00610"));
+
+    // values in 64-bit fields are stored as integers
+    let values = [0, 1, 2, 3, 4, 5, 6, u64::MAX - (1 << 32)];
+    let trace = TraceTable::init(vec![values.iter().map(|&v| SmallElement::new(v)).collect()]);
+    let mut file = Vec::new();
+    trace.export_parquet(&mut file).unwrap();
+    let expected = values
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect::<Vec<_>>();
+    assert!(contains(&file, &expected));
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -4,9 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{ColMatrix, Trace};
-use air::{EvaluationFrame, TraceInfo, TraceLayout};
+use air::{EvaluationFrame, LookupTable, TraceInfo, TraceLayout};
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::{
+    collections::Vec, uninit_vector, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable,
+};
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
@@ -14,6 +17,9 @@ use utils::collections::vec;
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
 
+#[cfg(feature = "export")]
+use super::TraceExport;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -188,6 +194,29 @@ impl<B: StarkField> TraceTable<B> {
         }
     }
 
+    /// Returns this execution trace with an auxiliary trace segment consisting of running-sum
+    /// columns for the specified number of lookup tables.
+    ///
+    /// The running-sum columns are built by the prover for lookup tables defined in the AIR
+    /// context of the computation (see [LookupTable]). The number of lookup tables is not
+    /// preserved when the trace is serialized.
+    ///
+    /// # Panics
+    /// Panics if `num_lookup_tables` is zero, or if the total number of columns in the trace
+    /// would exceed 255.
+    pub fn with_lookup_tables(mut self, num_lookup_tables: usize) -> Self {
+        assert!(
+            num_lookup_tables > 0,
+            "number of lookup tables must be greater than zero"
+        );
+        self.layout = TraceLayout::new(
+            self.layout.main_trace_width(),
+            [num_lookup_tables],
+            [LookupTable::NUM_RAND_ELEMENTS],
+        );
+        self
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -259,7 +288,10 @@ impl<B: StarkField> TraceTable<B> {
     /// Panics if `fragment_length` is smaller than 2, greater than the length of the trace,
     /// or is not a power of two.
     #[cfg(not(feature = "concurrent"))]
-    pub fn fragments(&mut self, fragment_length: usize) -> vec::IntoIter<TraceTableFragment<B>> {
+    pub fn fragments(
+        &mut self,
+        fragment_length: usize,
+    ) -> vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_iter()
     }
 
@@ -281,7 +313,7 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<'_, B>> {
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
@@ -338,6 +370,121 @@ impl<B: StarkField> TraceTable<B> {
     pub fn read_row_into(&self, step: usize, target: &mut [B]) {
         self.trace.read_row_into(step, target);
     }
+
+    // EXPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a [TraceExport] of this execution trace.
+    ///
+    /// The export can be extended with auxiliary trace segments and column names before it is
+    /// written into a file.
+    #[cfg(feature = "export")]
+    pub fn export(&self) -> TraceExport<'_, B, B> {
+        TraceExport::new(&self.trace)
+    }
+
+    /// Writes this execution trace into `target` in CSV format.
+    ///
+    /// This is a shortcut for `self.export().write_csv(target)`; see [TraceExport] for details.
+    ///
+    /// # Errors
+    /// Returns an error if writing into `target` fails.
+    #[cfg(feature = "export")]
+    pub fn export_csv<W: std::io::Write>(&self, target: &mut W) -> std::io::Result<()> {
+        self.export().write_csv(target)
+    }
+
+    /// Writes this execution trace into `target` as a Parquet file.
+    ///
+    /// This is a shortcut for `self.export().write_parquet(target)`; see [TraceExport] for
+    /// details.
+    ///
+    /// # Errors
+    /// Returns an error if writing into `target` fails, or if the trace is too large to be
+    /// exported.
+    #[cfg(feature = "export")]
+    pub fn export_parquet<W: std::io::Write>(&self, target: &mut W) -> std::io::Result<()> {
+        self.export().write_parquet(target)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<B: StarkField> Serializable for TraceTable<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// The trace is serialized as its width, the logarithm of its length, and its metadata
+    /// followed by the values of all columns (one column after another).
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.width() as u8);
+        target.write_u8(self.length().ilog2() as u8);
+        target.write_u16(self.meta.len() as u16);
+        target.write_bytes(&self.meta);
+        for column in self.trace.columns() {
+            for &value in column {
+                target.write(value);
+            }
+        }
+    }
+}
+
+impl<B: StarkField> Deserializable for TraceTable<B> {
+    /// Reads an execution trace from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid execution trace could not be read from the specified `source`;
+    /// this includes the cases when the width, the length or the metadata of the trace do not
+    /// satisfy the requirements of [TraceTable::with_meta()] function.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let width = source.read_u8()? as usize;
+        if width == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "execution trace must consist of at least one column".into(),
+            ));
+        }
+
+        let length_log2 = source.read_u8()? as u32;
+        if length_log2 < TraceInfo::MIN_TRACE_LENGTH.ilog2()
+            || length_log2 > B::TWO_ADICITY
+            || length_log2 >= usize::BITS
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "execution trace length must be between 2^{} and 2^{} steps, but was 2^{}",
+                TraceInfo::MIN_TRACE_LENGTH.ilog2(),
+                B::TWO_ADICITY.min(usize::BITS - 1),
+                length_log2
+            )));
+        }
+        let length = 1_usize << length_log2;
+
+        let num_meta_bytes = source.read_u16()? as usize;
+        if num_meta_bytes > TraceInfo::MAX_META_LENGTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of metadata bytes cannot be greater than {}, but was {}",
+                TraceInfo::MAX_META_LENGTH,
+                num_meta_bytes
+            )));
+        }
+        let meta = source.read_vec(num_meta_bytes)?;
+
+        // make sure the source contains all trace values before allocating memory for them
+        let num_value_bytes = (width * B::ELEMENT_BYTES)
+            .checked_mul(length)
+            .ok_or_else(|| {
+                DeserializationError::InvalidValue("execution trace is too large".into())
+            })?;
+        source.check_eor(num_value_bytes)?;
+        let columns = (0..width)
+            .map(|_| B::read_batch_from(source, length))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            layout: TraceLayout::new(width, [0], [0]),
+            trace: ColMatrix::new(columns),
+            meta,
+        })
+    }
 }
 
 // TRACE TRAIT IMPLEMENTATION
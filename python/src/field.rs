@@ -0,0 +1,164 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use pyo3::{
+    exceptions::{PyValueError, PyZeroDivisionError},
+    prelude::*,
+    types::PyBytes,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use winterfell::{
+    math::{
+        fields::{f128, f64},
+        FieldElement, StarkField,
+    },
+    Deserializable, Serializable,
+};
+
+// FIELD ELEMENTS
+// ================================================================================================
+
+/// Defines a Python class for elements of the specified base field; `$int` is the type of the
+/// integer representation of the elements.
+macro_rules! field_element {
+    ($name:ident, $element:ty, $int:ty, $doc:literal) => {
+        #[doc = $doc]
+        #[pyclass(module = "winterfell", frozen)]
+        #[derive(Clone)]
+        pub struct $name($element);
+
+        #[pymethods]
+        impl $name {
+            /// Modulus of the field.
+            #[classattr]
+            const MODULUS: $int = <$element>::MODULUS;
+
+            /// Size of a serialized element in bytes.
+            #[classattr]
+            const ELEMENT_BYTES: usize = <$element>::ELEMENT_BYTES;
+
+            /// Returns an element with the specified integer value; the value must be smaller than
+            /// the field modulus.
+            #[new]
+            fn new(value: $int) -> PyResult<Self> {
+                if value >= <$element>::MODULUS {
+                    return Err(PyValueError::new_err(format!(
+                        "value {value} is greater than or equal to the field modulus"
+                    )));
+                }
+                Ok(Self(<$element>::new(value)))
+            }
+
+            /// Returns the additive identity of the field.
+            #[staticmethod]
+            fn zero() -> Self {
+                Self(<$element>::ZERO)
+            }
+
+            /// Returns the multiplicative identity of the field.
+            #[staticmethod]
+            fn one() -> Self {
+                Self(<$element>::ONE)
+            }
+
+            /// Returns the multiplicative inverse of this element.
+            ///
+            /// Raises `ZeroDivisionError` if this element is zero.
+            fn inv(&self) -> PyResult<Self> {
+                if self.0 == <$element>::ZERO {
+                    return Err(PyZeroDivisionError::new_err("zero has no inverse"));
+                }
+                Ok(Self(self.0.inv()))
+            }
+
+            /// Returns this element raised to the specified power.
+            fn exp(&self, power: $int) -> Self {
+                Self(self.0.exp(power))
+            }
+
+            /// Returns this element serialized into bytes.
+            fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+                PyBytes::new(py, &self.0.to_bytes())
+            }
+
+            /// Returns an element deserialized from the specified bytes.
+            ///
+            /// Raises `ValueError` if the bytes do not encode a valid field element.
+            #[staticmethod]
+            fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+                <$element>::read_from_bytes(bytes)
+                    .map(Self)
+                    .map_err(|err| PyValueError::new_err(err.to_string()))
+            }
+
+            fn __add__(&self, other: &Self) -> Self {
+                Self(self.0 + other.0)
+            }
+
+            fn __sub__(&self, other: &Self) -> Self {
+                Self(self.0 - other.0)
+            }
+
+            fn __mul__(&self, other: &Self) -> Self {
+                Self(self.0 * other.0)
+            }
+
+            fn __truediv__(&self, other: &Self) -> PyResult<Self> {
+                if other.0 == <$element>::ZERO {
+                    return Err(PyZeroDivisionError::new_err("division by zero"));
+                }
+                Ok(Self(self.0 / other.0))
+            }
+
+            fn __pow__(&self, power: $int, modulo: Option<&PyAny>) -> PyResult<Self> {
+                if modulo.is_some() {
+                    return Err(PyValueError::new_err("modulo is not supported"));
+                }
+                Ok(self.exp(power))
+            }
+
+            fn __neg__(&self) -> Self {
+                Self(-self.0)
+            }
+
+            fn __eq__(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+
+            fn __hash__(&self) -> u64 {
+                let mut hasher = DefaultHasher::new();
+                self.0.as_int().hash(&mut hasher);
+                hasher.finish()
+            }
+
+            fn __int__(&self) -> $int {
+                self.0.as_int()
+            }
+
+            fn __repr__(&self) -> String {
+                format!("{}({})", stringify!($name), self.0.as_int())
+            }
+
+            fn __str__(&self) -> String {
+                self.0.as_int().to_string()
+            }
+        }
+    };
+}
+
+field_element!(
+    F64,
+    f64::BaseElement,
+    u64,
+    "An element of the prime field with modulus 2^64 - 2^32 + 1."
+);
+
+field_element!(
+    F128,
+    f128::BaseElement,
+    u128,
+    "An element of the prime field with modulus 2^128 - 45 * 2^40 + 1."
+);
@@ -0,0 +1,189 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+use winterfell::{
+    crypto::{
+        hashers::{Blake3_256, Sha3_256},
+        Digest, Hasher,
+    },
+    math::fields::f128::BaseElement,
+    Deserializable,
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Sha3 = Sha3_256<BaseElement>;
+
+/// Hash functions available in this module; digests of all of them are 32 bytes long.
+#[derive(Clone, Copy)]
+enum HashFunction {
+    Blake3_256,
+    Sha3_256,
+}
+
+impl HashFunction {
+    fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "blake3_256" => Ok(Self::Blake3_256),
+            "sha3_256" => Ok(Self::Sha3_256),
+            _ => Err(PyValueError::new_err(format!(
+                "unsupported hash function {name}; expected blake3_256 or sha3_256"
+            ))),
+        }
+    }
+}
+
+// HASHING
+// ================================================================================================
+
+/// Returns a digest of the specified bytes.
+#[pyfunction]
+#[pyo3(signature = (data, hash_fn = "blake3_256"))]
+pub fn hash<'py>(py: Python<'py>, data: &[u8], hash_fn: &str) -> PyResult<&'py PyBytes> {
+    let digest = match HashFunction::parse(hash_fn)? {
+        HashFunction::Blake3_256 => Blake3::hash(data).as_bytes(),
+        HashFunction::Sha3_256 => Sha3::hash(data).as_bytes(),
+    };
+    Ok(PyBytes::new(py, &digest))
+}
+
+/// Returns a digest of two concatenated digests; this is the function used to compute internal
+/// nodes of Merkle trees.
+#[pyfunction]
+#[pyo3(signature = (left, right, hash_fn = "blake3_256"))]
+pub fn merge<'py>(
+    py: Python<'py>,
+    left: &[u8],
+    right: &[u8],
+    hash_fn: &str,
+) -> PyResult<&'py PyBytes> {
+    let digest = match HashFunction::parse(hash_fn)? {
+        HashFunction::Blake3_256 => merge_digests::<Blake3>(left, right)?,
+        HashFunction::Sha3_256 => merge_digests::<Sha3>(left, right)?,
+    };
+    Ok(PyBytes::new(py, &digest))
+}
+
+// MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree with 32-byte leaves.
+#[pyclass(module = "winterfell", frozen)]
+pub struct MerkleTree {
+    tree: Tree,
+}
+
+enum Tree {
+    Blake3_256(winterfell::crypto::MerkleTree<Blake3>),
+    Sha3_256(winterfell::crypto::MerkleTree<Sha3>),
+}
+
+#[pymethods]
+impl MerkleTree {
+    /// Returns a Merkle tree built from the specified leaves; the number of leaves must be a
+    /// power of two greater than or equal to two.
+    #[new]
+    #[pyo3(signature = (leaves, hash_fn = "blake3_256"))]
+    fn new(leaves: Vec<Vec<u8>>, hash_fn: &str) -> PyResult<Self> {
+        let tree = match HashFunction::parse(hash_fn)? {
+            HashFunction::Blake3_256 => Tree::Blake3_256(build_tree(&leaves)?),
+            HashFunction::Sha3_256 => Tree::Sha3_256(build_tree(&leaves)?),
+        };
+        Ok(MerkleTree { tree })
+    }
+
+    /// Root of the tree.
+    #[getter]
+    fn root<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        let root = match &self.tree {
+            Tree::Blake3_256(tree) => tree.root().as_bytes(),
+            Tree::Sha3_256(tree) => tree.root().as_bytes(),
+        };
+        PyBytes::new(py, &root)
+    }
+
+    /// Number of levels of the tree, not counting the root.
+    #[getter]
+    fn depth(&self) -> usize {
+        match &self.tree {
+            Tree::Blake3_256(tree) => tree.depth(),
+            Tree::Sha3_256(tree) => tree.depth(),
+        }
+    }
+
+    /// Returns a Merkle path to the leaf at the specified index; the first element of the path
+    /// is the leaf itself.
+    fn prove<'py>(&self, py: Python<'py>, index: usize) -> PyResult<Vec<&'py PyBytes>> {
+        let path = match &self.tree {
+            Tree::Blake3_256(tree) => prove_leaf(tree, index)?,
+            Tree::Sha3_256(tree) => prove_leaf(tree, index)?,
+        };
+        Ok(path.iter().map(|node| PyBytes::new(py, node)).collect())
+    }
+
+    /// Returns true if the specified Merkle path to the leaf at the specified index resolves
+    /// to the specified root.
+    #[staticmethod]
+    #[pyo3(signature = (root, index, path, hash_fn = "blake3_256"))]
+    fn verify(root: &[u8], index: usize, path: Vec<Vec<u8>>, hash_fn: &str) -> PyResult<bool> {
+        match HashFunction::parse(hash_fn)? {
+            HashFunction::Blake3_256 => verify_path::<Blake3>(root, index, &path),
+            HashFunction::Sha3_256 => verify_path::<Sha3>(root, index, &path),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn read_digest<H: Hasher>(bytes: &[u8]) -> PyResult<H::Digest> {
+    if bytes.len() != H::Digest::SIZE {
+        return Err(PyValueError::new_err(format!(
+            "digests must be {} bytes long",
+            H::Digest::SIZE
+        )));
+    }
+    H::Digest::read_from_bytes(bytes).map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+fn read_digests<H: Hasher>(values: &[Vec<u8>]) -> PyResult<Vec<H::Digest>> {
+    values.iter().map(|value| read_digest::<H>(value)).collect()
+}
+
+fn merge_digests<H>(left: &[u8], right: &[u8]) -> PyResult<[u8; 32]>
+where
+    H: Hasher,
+    H::Digest: Digest<Bytes = [u8; 32]>,
+{
+    let values = [read_digest::<H>(left)?, read_digest::<H>(right)?];
+    Ok(H::merge(&values).as_bytes())
+}
+
+fn build_tree<H: Hasher>(leaves: &[Vec<u8>]) -> PyResult<winterfell::crypto::MerkleTree<H>> {
+    winterfell::crypto::MerkleTree::new(read_digests::<H>(leaves)?)
+        .map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+fn prove_leaf<H>(tree: &winterfell::crypto::MerkleTree<H>, index: usize) -> PyResult<Vec<[u8; 32]>>
+where
+    H: Hasher,
+    H::Digest: Digest<Bytes = [u8; 32]>,
+{
+    let path = tree
+        .prove(index)
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+    Ok(path.iter().map(Digest::as_bytes).collect())
+}
+
+fn verify_path<H: Hasher>(root: &[u8], index: usize, path: &[Vec<u8>]) -> PyResult<bool> {
+    let root = read_digest::<H>(root)?;
+    let path = read_digests::<H>(path)?;
+    // a path contains at least the leaf and its sibling, and the index must refer to one of the
+    // leaves of a tree of the corresponding depth
+    if path.len() < 2 || path.len() > usize::BITS as usize || index >> (path.len() - 1) != 0 {
+        return Ok(false);
+    }
+    Ok(winterfell::crypto::MerkleTree::<H>::verify(root, index, &path).is_ok())
+}
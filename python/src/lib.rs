@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains Python bindings for Winterfell STARK prover and verifier.
+//!
+//! The bindings are exposed as the `winterfell` Python module which contains:
+//! * `F64` and `F128` classes for elements of the 64-bit and 128-bit base fields.
+//! * `hash()` and `merge()` functions, and `MerkleTree` class for BLAKE3 and SHA3 hash
+//!   functions.
+//! * `StarkProof` class for (de)serialization and inspection of proofs.
+//! * `verify()` function for verification of proofs of registered computations; the Fibonacci
+//!   examples from the `examples` crate are registered by default.
+
+// pyo3 0.20 macros expand #[pymethods] into impl blocks nested in functions
+#![allow(non_local_definitions)]
+
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+use std::sync::RwLock;
+use winterfell::registry::AirRegistry;
+
+mod field;
+mod hash;
+mod proof;
+
+// MODULE
+// ================================================================================================
+
+create_exception!(
+    winterfell,
+    VerificationError,
+    PyException,
+    "Raised when a proof could not be verified."
+);
+
+#[pymodule]
+#[pyo3(name = "winterfell")]
+fn winterfell_module(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<field::F64>()?;
+    m.add_class::<field::F128>()?;
+    m.add_class::<hash::MerkleTree>()?;
+    m.add_class::<proof::StarkProof>()?;
+    m.add_function(wrap_pyfunction!(hash::hash, m)?)?;
+    m.add_function(wrap_pyfunction!(hash::merge, m)?)?;
+    m.add_function(wrap_pyfunction!(proof::air_ids, m)?)?;
+    m.add_function(wrap_pyfunction!(proof::verify, m)?)?;
+    m.add("VerificationError", py.get_type::<VerificationError>())?;
+    Ok(())
+}
+
+// GLOBAL REGISTRY
+// ================================================================================================
+
+/// Computations for which proofs can be verified via `verify()` function; the registry is
+/// initialized with the example computations on first access.
+static REGISTRY: RwLock<Option<AirRegistry>> = RwLock::new(None);
+
+/// Invokes `f` with the global registry of this module, initializing the registry with example
+/// computations if needed.
+///
+/// Only a shared lock on the registry is held while `f` is executed, and thus, multiple proofs
+/// can be generated and verified concurrently.
+fn with_registry<T>(f: impl FnOnce(&AirRegistry) -> T) -> T {
+    // a panic while the registry is locked cannot leave the registry in an inconsistent state,
+    // and thus, poisoning can be ignored
+    let registry = REGISTRY.read().unwrap_or_else(|err| err.into_inner());
+    if let Some(registry) = registry.as_ref() {
+        return f(registry);
+    }
+    drop(registry);
+    with_registry_mut(|registry| f(registry))
+}
+
+/// Invokes `f` with an exclusive lock on the global registry of this module, initializing the
+/// registry with example computations if needed.
+fn with_registry_mut<T>(f: impl FnOnce(&mut AirRegistry) -> T) -> T {
+    let mut registry = REGISTRY.write().unwrap_or_else(|err| err.into_inner());
+    let registry = registry.get_or_insert_with(|| {
+        let mut registry = AirRegistry::new();
+        examples::registry::register_examples(&mut registry).expect("failed to register examples");
+        registry
+    });
+    f(registry)
+}
@@ -0,0 +1,164 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{with_registry, VerificationError};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+use winterfell::{registry::RegistryError, AcceptableOptions};
+
+// STARK PROOF
+// ================================================================================================
+
+/// A STARK proof.
+#[pyclass(module = "winterfell", frozen)]
+#[derive(Clone)]
+pub struct StarkProof(winterfell::StarkProof);
+
+#[pymethods]
+impl StarkProof {
+    /// Returns a proof deserialized from the specified bytes.
+    ///
+    /// Raises `ValueError` if the bytes do not encode a valid proof.
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        winterfell::StarkProof::from_bytes(bytes)
+            .map(Self)
+            .map_err(|err| PyValueError::new_err(format!("failed to read proof: {err}")))
+    }
+
+    /// Returns this proof serialized into bytes.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+        PyBytes::new(py, &self.0.to_bytes())
+    }
+
+    /// Length of the execution trace of the computation described by this proof.
+    #[getter]
+    fn trace_length(&self) -> usize {
+        self.0.trace_length()
+    }
+
+    /// Width of the main segment of the execution trace.
+    #[getter]
+    fn main_trace_width(&self) -> usize {
+        self.0.trace_layout().main_trace_width()
+    }
+
+    /// Total width of the auxiliary segments of the execution trace.
+    #[getter]
+    fn aux_trace_width(&self) -> usize {
+        self.0.trace_layout().aux_trace_width()
+    }
+
+    /// Size of the low-degree extension domain.
+    #[getter]
+    fn lde_domain_size(&self) -> usize {
+        self.0.lde_domain_size()
+    }
+
+    /// Number of queries made by the verifier.
+    #[getter]
+    fn num_queries(&self) -> usize {
+        self.0.options().num_queries()
+    }
+
+    /// Blowup factor of the low-degree extension domain.
+    #[getter]
+    fn blowup_factor(&self) -> usize {
+        self.0.options().blowup_factor()
+    }
+
+    /// Number of bits of proof-of-work required for query seed grinding.
+    #[getter]
+    fn grinding_factor(&self) -> u32 {
+        self.0.options().grinding_factor()
+    }
+
+    /// Degree of the field extension used for random values drawn by the verifier.
+    #[getter]
+    fn field_extension(&self) -> u32 {
+        self.0.options().field_extension().degree()
+    }
+
+    /// Factor by which the degree of a polynomial is reduced with each FRI layer.
+    #[getter]
+    fn fri_folding_factor(&self) -> usize {
+        self.0.options().to_fri_options().folding_factor()
+    }
+
+    /// Maximum degree of the FRI remainder polynomial.
+    #[getter]
+    fn fri_remainder_max_degree(&self) -> usize {
+        self.0.options().to_fri_options().remainder_max_degree()
+    }
+
+    /// Number of layers of the FRI proof.
+    #[getter]
+    fn num_fri_layers(&self) -> usize {
+        self.0.fri_proof.num_layers()
+    }
+
+    /// Returns conjectured security level of this proof (in bits) as a proof of the computation
+    /// registered under `air_id`.
+    ///
+    /// Raises `ValueError` if no computation has been registered under `air_id`.
+    fn security_level(&self, air_id: &str) -> PyResult<u32> {
+        with_registry(|registry| registry.security_level(air_id, &self.0))
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.to_bytes().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "StarkProof(trace_length={}, trace_width={}, num_queries={}, blowup_factor={})",
+            self.0.trace_length(),
+            self.0.trace_layout().main_trace_width() + self.0.trace_layout().aux_trace_width(),
+            self.0.options().num_queries(),
+            self.0.options().blowup_factor(),
+        )
+    }
+}
+
+// VERIFICATION
+// ================================================================================================
+
+/// Returns identifiers of computations for which proofs can be verified.
+#[pyfunction]
+pub fn air_ids() -> Vec<String> {
+    with_registry(|registry| registry.air_ids().into_iter().map(String::from).collect())
+}
+
+/// Verifies the proof of the computation registered under `air_id` against public inputs
+/// serialized in `pub_inputs`; the proof can be specified either as a `StarkProof` or as bytes.
+///
+/// The proof is accepted only if its conjectured security level is at least
+/// `min_security_level` bits.
+///
+/// Raises `VerificationError` if the proof is not valid or its security level is below
+/// `min_security_level`, and `ValueError` if the inputs could not be read.
+#[pyfunction]
+pub fn verify(
+    py: Python<'_>,
+    air_id: &str,
+    proof: &PyAny,
+    pub_inputs: &[u8],
+    min_security_level: u32,
+) -> PyResult<()> {
+    let proof = match proof.extract::<StarkProof>() {
+        Ok(proof) => proof.0,
+        Err(_) => StarkProof::from_bytes(proof.extract::<&[u8]>()?)?.0,
+    };
+    let acceptable_options = AcceptableOptions::MinConjecturedSecurity(min_security_level);
+    // verification does not touch Python objects, and thus, the GIL can be released for its
+    // duration
+    py.allow_threads(|| {
+        with_registry(|registry| registry.verify(air_id, proof, pub_inputs, &acceptable_options))
+    })
+    .map_err(|err| match err {
+        RegistryError::VerifierError(err) => VerificationError::new_err(err.to_string()),
+        err => PyValueError::new_err(err.to_string()),
+    })
+}
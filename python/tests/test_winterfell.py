@@ -0,0 +1,50 @@
+# Copyright (c) Facebook, Inc. and its affiliates.
+#
+# This source code is licensed under the MIT license found in the
+# LICENSE file in the root directory of this source tree.
+
+import pytest
+import winterfell
+
+
+def test_field_arithmetic():
+    a = winterfell.F128(5)
+    b = winterfell.F128(winterfell.F128.MODULUS - 1)
+    assert int(a + b) == 4
+    assert a * a.inv() == winterfell.F128.one()
+    assert (a / a) == winterfell.F128.one()
+    assert a ** 3 == a * a * a
+    assert -winterfell.F128.zero() == winterfell.F128.zero()
+    assert winterfell.F128.from_bytes(a.to_bytes()) == a
+
+    with pytest.raises(ValueError):
+        winterfell.F64(winterfell.F64.MODULUS)
+    with pytest.raises(ZeroDivisionError):
+        winterfell.F64.zero().inv()
+
+
+def test_merkle_tree():
+    leaves = [winterfell.hash(bytes([i])) for i in range(8)]
+    for hash_fn in ["blake3_256", "sha3_256"]:
+        tree = winterfell.MerkleTree(leaves, hash_fn=hash_fn)
+        assert tree.depth == 3
+        path = tree.prove(5)
+        assert path[0] == leaves[5]
+        assert winterfell.MerkleTree.verify(tree.root, 5, path, hash_fn=hash_fn)
+        assert not winterfell.MerkleTree.verify(tree.root, 4, path, hash_fn=hash_fn)
+
+    root = winterfell.merge(leaves[0], leaves[1])
+    assert winterfell.MerkleTree([leaves[0], leaves[1]]).root == root
+
+    with pytest.raises(ValueError):
+        winterfell.MerkleTree(leaves[:3])
+    with pytest.raises(ValueError):
+        winterfell.hash(b"", hash_fn="md5")
+
+
+def test_proof_deserialization():
+    assert "fib" in winterfell.air_ids()
+    with pytest.raises(ValueError):
+        winterfell.StarkProof.from_bytes(b"\x00" * 16)
+    with pytest.raises(ValueError):
+        winterfell.verify("fib", b"\x00" * 16, winterfell.F128(1).to_bytes(), 100)
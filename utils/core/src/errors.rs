@@ -18,6 +18,8 @@ pub enum DeserializationError {
     UnexpectedEOF,
     /// Deserialization has finished but not all bytes have been consumed.
     UnconsumedBytes,
+    /// Input has been encoded using a format version which is not supported by the decoder.
+    UnsupportedVersion(u8),
     /// An unknown error has occurred.
     UnknownError(String),
 }
@@ -35,6 +37,9 @@ impl fmt::Display for DeserializationError {
             Self::UnconsumedBytes => {
                 write!(f, "not all bytes were consumed")
             }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "format version {version} is not supported")
+            }
             Self::UnknownError(err_msg) => {
                 write!(f, "unknown error: {err_msg}")
             }
@@ -0,0 +1,61 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Canonical hex encoding used for string representations of serialized values.
+//!
+//! Values are encoded as lowercase hex strings prefixed with `0x` (e.g., `0x01ab`). For
+//! convenience, decoding also accepts strings without the prefix and strings containing
+//! uppercase digits.
+
+use super::{collections::Vec, string::String, DeserializationError};
+
+// ENCODING / DECODING
+// ================================================================================================
+
+/// Encodes the specified bytes as a lowercase hex string prefixed with `0x`.
+pub fn encode(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut result = String::with_capacity(2 + bytes.len() * 2);
+    result.push_str("0x");
+    for byte in bytes {
+        result.push(DIGITS[(byte >> 4) as usize] as char);
+        result.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+/// Decodes a hex string, optionally prefixed with `0x`, into a vector of bytes.
+///
+/// # Errors
+/// Returns an error if the `source` contains an odd number of digits, or if any of the digits is
+/// not a valid hex digit.
+pub fn decode(source: &str) -> Result<Vec<u8>, DeserializationError> {
+    let digits = source.strip_prefix("0x").unwrap_or(source).as_bytes();
+    if digits.len() % 2 != 0 {
+        return Err(DeserializationError::InvalidValue(
+            "hex string must have an even number of digits".into(),
+        ));
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| Ok(decode_digit(pair[0])? << 4 | decode_digit(pair[1])?))
+        .collect()
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn decode_digit(digit: u8) -> Result<u8, DeserializationError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(DeserializationError::InvalidValue(format!(
+            "invalid hex digit {}",
+            digit as char
+        ))),
+    }
+}
@@ -13,15 +13,22 @@
 extern crate alloc;
 
 pub mod collections;
+pub mod hex;
 pub mod iterators;
 pub mod string;
 
+#[cfg(feature = "serde")]
+pub mod serde_compat;
+
 use collections::Vec;
 use core::{convert::TryInto, mem, slice};
 
 mod serde;
 pub use serde::{ByteReader, ByteWriter, Deserializable, Serializable, SliceReader};
 
+#[cfg(feature = "std")]
+pub use serde::{IoReader, IoWriter, DEFAULT_IO_BUFFER_SIZE};
+
 mod errors;
 pub use errors::DeserializationError;
 
@@ -130,6 +130,21 @@ impl<'a> SliceReader<'a> {
     pub fn new(source: &'a [u8]) -> Self {
         SliceReader { source, pos: 0 }
     }
+
+    /// Returns a slice of the next `len` bytes of the underlying source and advances the reader
+    /// past these bytes.
+    ///
+    /// Unlike [ByteReader::read_vec()], the bytes are not copied; the returned slice borrows
+    /// directly from the source the reader was created with.
+    ///
+    /// # Errors
+    /// Returns a [DeserializationError] if fewer than `len` bytes remain in the source.
+    pub fn read_slice(&mut self, len: usize) -> Result<&'a [u8], DeserializationError> {
+        self.check_eor(len)?;
+        let result = &self.source[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(result)
+    }
 }
 
 impl<'a> ByteReader for SliceReader<'a> {
@@ -161,7 +176,8 @@ impl<'a> ByteReader for SliceReader<'a> {
     }
 
     fn check_eor(&self, num_bytes: usize) -> Result<(), DeserializationError> {
-        if self.pos + num_bytes > self.source.len() {
+        // pos never exceeds the length of the source, and thus, this cannot underflow
+        if num_bytes > self.source.len() - self.pos {
             return Err(DeserializationError::UnexpectedEOF);
         }
         Ok(())
@@ -0,0 +1,353 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteReader, ByteWriter, DeserializationError, Vec};
+use core::cell::RefCell;
+use std::io::{self, ErrorKind, Read, Write};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Default size of internal buffers of [IoReader] and [IoWriter] (in bytes).
+pub const DEFAULT_IO_BUFFER_SIZE: usize = 8 * 1024;
+
+// IO READER
+// ================================================================================================
+
+/// Implements [ByteReader] trait for any [std::io::Read] source (e.g., a file or a socket).
+///
+/// Bytes are read from the source in chunks of the specified capacity and are buffered
+/// internally; thus, the source does not need to be buffered. Since [ByteReader] needs to look
+/// ahead (e.g., via [ByteReader::peek_u8()] or [ByteReader::check_eor()]), the reader may read
+/// more bytes from the source than have been consumed by the caller.
+///
+/// The number of bytes which can be consumed from the source can be limited via
+/// [with_limit()](IoReader::with_limit); attempts to read past the limit fail with
+/// [DeserializationError::UnexpectedEOF] without reading anything from the source, and no bytes
+/// past the limit are ever read from the source.
+///
+/// I/O errors other than an unexpected end of the source are reported as
+/// [DeserializationError::UnknownError].
+pub struct IoReader<R: Read> {
+    state: RefCell<ReaderState<R>>,
+}
+
+struct ReaderState<R: Read> {
+    source: R,
+    buffer: Vec<u8>,
+    pos: usize,
+    capacity: usize,
+    limit: Option<usize>,
+    num_consumed: usize,
+    eof: bool,
+}
+
+impl<R: Read> IoReader<R> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new reader for the specified `source` with the internal buffer of
+    /// [DEFAULT_IO_BUFFER_SIZE] bytes.
+    pub fn new(source: R) -> Self {
+        Self::with_capacity(DEFAULT_IO_BUFFER_SIZE, source)
+    }
+
+    /// Returns a new reader for the specified `source` which requests bytes from the source in
+    /// chunks of at least `capacity` bytes.
+    ///
+    /// When `capacity` is 0, only the bytes needed to satisfy each request are read from the
+    /// source.
+    pub fn with_capacity(capacity: usize, source: R) -> Self {
+        IoReader {
+            state: RefCell::new(ReaderState {
+                source,
+                buffer: Vec::new(),
+                pos: 0,
+                capacity,
+                limit: None,
+                num_consumed: 0,
+                eof: false,
+            }),
+        }
+    }
+
+    /// Limits the number of bytes which can be consumed from this reader to `limit` and returns
+    /// the updated reader; bytes consumed prior to this call count toward the limit.
+    pub fn with_limit(self, limit: usize) -> Self {
+        self.state.borrow_mut().limit = Some(limit);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bytes consumed from this reader.
+    pub fn num_consumed(&self) -> usize {
+        self.state.borrow().num_consumed
+    }
+}
+
+impl<R: Read> ReaderState<R> {
+    /// Returns the number of buffered bytes which have not been consumed yet.
+    fn num_available(&self) -> usize {
+        self.buffer.len() - self.pos
+    }
+
+    /// Makes sure at least `num_bytes` unconsumed bytes are in the buffer.
+    fn fill(&mut self, num_bytes: usize) -> Result<(), DeserializationError> {
+        let num_available = self.num_available();
+        if num_available >= num_bytes {
+            return Ok(());
+        }
+
+        // make sure the request does not exceed the limit; the remaining allowance is also used
+        // to make sure no bytes past the limit are read from the source
+        let allowance = match self.limit {
+            Some(limit) => {
+                let remaining = limit.saturating_sub(self.num_consumed);
+                if num_bytes > remaining {
+                    return Err(DeserializationError::UnexpectedEOF);
+                }
+                remaining - num_available
+            }
+            None => usize::MAX,
+        };
+        if self.eof {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+
+        // discard consumed bytes and read more bytes from the source until the request can be
+        // satisfied; the buffer is grown gradually so that a request for a large number of bytes
+        // does not result in a large allocation unless the source actually contains the bytes
+        self.buffer.drain(..self.pos);
+        self.pos = 0;
+        while self.buffer.len() < num_bytes {
+            let num_missing = num_bytes - self.buffer.len();
+            let chunk_size = num_missing
+                .max(self.capacity)
+                .min(DEFAULT_IO_BUFFER_SIZE.max(self.capacity))
+                .min(allowance - (self.buffer.len() - num_available));
+            let start = self.buffer.len();
+            self.buffer.resize(start + chunk_size, 0);
+            match self.source.read(&mut self.buffer[start..]) {
+                Ok(0) => {
+                    self.buffer.truncate(start);
+                    self.eof = true;
+                    return Err(DeserializationError::UnexpectedEOF);
+                }
+                Ok(n) => self.buffer.truncate(start + n),
+                Err(err) if err.kind() == ErrorKind::Interrupted => self.buffer.truncate(start),
+                Err(err) => {
+                    self.buffer.truncate(start);
+                    return Err(map_io_error(err));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes `num_bytes` bytes from the buffer and returns them; the bytes must have been
+    /// made available via [fill()](Self::fill) beforehand.
+    fn consume(&mut self, num_bytes: usize) -> &[u8] {
+        let start = self.pos;
+        self.pos += num_bytes;
+        self.num_consumed += num_bytes;
+        &self.buffer[start..self.pos]
+    }
+}
+
+impl<R: Read> ByteReader for IoReader<R> {
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        let state = self.state.get_mut();
+        state.fill(1)?;
+        Ok(state.consume(1)[0])
+    }
+
+    fn peek_u8(&self) -> Result<u8, DeserializationError> {
+        let mut state = self.state.borrow_mut();
+        state.fill(1)?;
+        Ok(state.buffer[state.pos])
+    }
+
+    fn read_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializationError> {
+        let state = self.state.get_mut();
+        state.fill(len)?;
+        Ok(state.consume(len).to_vec())
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        let state = self.state.get_mut();
+        state.fill(N)?;
+        let mut result = [0_u8; N];
+        result.copy_from_slice(state.consume(N));
+        Ok(result)
+    }
+
+    fn check_eor(&self, num_bytes: usize) -> Result<(), DeserializationError> {
+        self.state.borrow_mut().fill(num_bytes)
+    }
+
+    fn has_more_bytes(&self) -> bool {
+        self.state.borrow_mut().fill(1).is_ok()
+    }
+}
+
+// IO WRITER
+// ================================================================================================
+
+/// Implements [ByteWriter] trait for any [std::io::Write] target (e.g., a file or a socket).
+///
+/// Bytes are accumulated in an internal buffer of the specified capacity and are written into
+/// the target whenever the buffer is full; thus, the target does not need to be buffered.
+///
+/// Since [ByteWriter] methods cannot return errors, the first error returned by the target is
+/// recorded, and all subsequent writes are ignored. The recorded error is returned by
+/// [finish()](IoWriter::finish) which must be called to flush the buffer and to make sure all
+/// bytes have been written successfully. If the writer is dropped without calling `finish()`,
+/// the buffer is flushed on a best-effort basis, and errors are ignored.
+///
+/// The number of bytes which can be written can be limited via
+/// [with_limit()](IoWriter::with_limit); writes exceeding the limit result in an error of
+/// [std::io::ErrorKind::WriteZero] kind being recorded, and the bytes of such writes are not
+/// written into the target.
+pub struct IoWriter<W: Write> {
+    target: Option<W>,
+    buffer: Vec<u8>,
+    capacity: usize,
+    limit: Option<usize>,
+    num_written: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> IoWriter<W> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new writer for the specified `target` with the internal buffer of
+    /// [DEFAULT_IO_BUFFER_SIZE] bytes.
+    pub fn new(target: W) -> Self {
+        Self::with_capacity(DEFAULT_IO_BUFFER_SIZE, target)
+    }
+
+    /// Returns a new writer for the specified `target` with internal buffer of `capacity`
+    /// bytes.
+    ///
+    /// When `capacity` is 0, bytes are written into the target immediately.
+    pub fn with_capacity(capacity: usize, target: W) -> Self {
+        IoWriter {
+            target: Some(target),
+            buffer: Vec::with_capacity(capacity),
+            capacity,
+            limit: None,
+            num_written: 0,
+            error: None,
+        }
+    }
+
+    /// Limits the number of bytes which can be written into this writer to `limit` and returns
+    /// the updated writer; bytes written prior to this call count toward the limit.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bytes written into this writer (including the bytes which are
+    /// still buffered).
+    pub fn num_written(&self) -> usize {
+        self.num_written
+    }
+
+    // FINALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Flushes the internal buffer and the target, and returns the target.
+    ///
+    /// # Errors
+    /// Returns an error if any of the writes into the target failed, if the limit on the number
+    /// of bytes has been exceeded, or if the target could not be flushed.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_buffer();
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let mut target = self.target.take().expect("target already taken");
+        target.flush()?;
+        Ok(target)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the buffered bytes into the target unless an error has already occurred.
+    fn flush_buffer(&mut self) {
+        if self.error.is_none() && !self.buffer.is_empty() {
+            if let Some(target) = self.target.as_mut() {
+                if let Err(err) = target.write_all(&self.buffer) {
+                    self.error = Some(err);
+                }
+            }
+        }
+        self.buffer.clear();
+    }
+}
+
+impl<W: Write> ByteWriter for IoWriter<W> {
+    fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Some(limit) = self.limit {
+            if self.num_written + values.len() > limit {
+                self.error = Some(io::Error::new(
+                    ErrorKind::WriteZero,
+                    format!("cannot write more than {limit} bytes"),
+                ));
+                return;
+            }
+        }
+        self.num_written += values.len();
+
+        if self.buffer.len() + values.len() > self.capacity {
+            self.flush_buffer();
+        }
+        if values.len() > self.capacity {
+            // values which do not fit into the buffer are written into the target directly
+            if self.error.is_none() {
+                if let Some(target) = self.target.as_mut() {
+                    if let Err(err) = target.write_all(values) {
+                        self.error = Some(err);
+                    }
+                }
+            }
+        } else {
+            self.buffer.extend_from_slice(values);
+        }
+    }
+}
+
+impl<W: Write> Drop for IoWriter<W> {
+    fn drop(&mut self) {
+        if self.target.is_some() {
+            self.flush_buffer();
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn map_io_error(err: io::Error) -> DeserializationError {
+    match err.kind() {
+        ErrorKind::UnexpectedEof => DeserializationError::UnexpectedEOF,
+        _ => DeserializationError::UnknownError(err.to_string()),
+    }
+}
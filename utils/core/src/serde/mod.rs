@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{flatten_slice_elements, DeserializationError, Vec};
+use super::{flatten_slice_elements, hex, string::String, DeserializationError, Vec};
 
 mod byte_reader;
 pub use byte_reader::{ByteReader, SliceReader};
@@ -11,6 +11,11 @@ pub use byte_reader::{ByteReader, SliceReader};
 mod byte_writer;
 pub use byte_writer::ByteWriter;
 
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+pub use io::{IoReader, IoWriter, DEFAULT_IO_BUFFER_SIZE};
+
 // SERIALIZABLE TRAIT
 // ================================================================================================
 
@@ -31,6 +36,14 @@ pub trait Serializable: Sized {
         result
     }
 
+    /// Serializes `self` into a lowercase hex string prefixed with `0x`.
+    ///
+    /// The string encodes the same bytes as would be returned by [to_bytes()](Self::to_bytes)
+    /// function (see [hex] module for details).
+    fn to_hex(&self) -> String {
+        hex::encode(&self.to_bytes())
+    }
+
     /// Serializes all elements of the `source` and writes these bytes into the `target`.
     ///
     /// This method does not write any metadata (e.g. number of serialized elements) into the
@@ -125,6 +138,26 @@ pub trait Deserializable: Sized {
         Self::read_from(&mut SliceReader::new(bytes))
     }
 
+    /// Attempts to deserialize `Self` from the provided hex string and returns the result.
+    ///
+    /// The `source` is expected to encode the bytes of the serialized value as described in the
+    /// [hex] module, and thus, would usually be produced by [Serializable::to_hex()] function.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `source` is not a valid hex string.
+    /// * The decoded bytes do not represent a valid value for `Self`.
+    /// * Not all of the decoded bytes have been consumed.
+    fn from_hex(source: &str) -> Result<Self, DeserializationError> {
+        let bytes = hex::decode(source)?;
+        let mut reader = SliceReader::new(&bytes);
+        let result = Self::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(result)
+    }
+
     /// Reads a sequence of bytes from the provided `source`, attempts to deserialize these bytes
     /// into a vector with the specified number of `Self` elements, and returns the result.
     ///
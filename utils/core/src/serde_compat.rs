@@ -0,0 +1,133 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Helpers for implementing [serde](https://serde.rs) traits for types which implement
+//! [Serializable] and [Deserializable].
+//!
+//! Values are serialized into the same bytes as produced by [Serializable::to_bytes()]. For
+//! human-readable formats (e.g., JSON), the bytes are encoded as a canonical hex string (see
+//! [hex](crate::hex)); for binary formats, the bytes are written as a byte array. Thus, the
+//! serde encoding of a value never diverges from its native binary encoding.
+
+use super::{collections::Vec, hex, Deserializable, Serializable};
+use core::fmt;
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    Deserializer, Serializer,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of bytes pre-allocated when deserializing a byte sequence; this prevents
+/// malicious inputs from triggering large allocations via fake size hints.
+const MAX_PREALLOCATED_BYTES: usize = 4096;
+
+// VALUE SERIALIZATION
+// ================================================================================================
+
+/// Serializes the specified value using the provided serializer.
+///
+/// This function can be used to implement [serde::Serialize] for types which implement
+/// [Serializable].
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serializable,
+    S: Serializer,
+{
+    serialize_bytes(&value.to_bytes(), serializer)
+}
+
+/// Deserializes a value using the provided deserializer.
+///
+/// This function can be used to implement [serde::Deserialize] for types which implement
+/// [Deserializable].
+///
+/// # Errors
+/// Returns an error if the deserializer does not contain a valid byte sequence, or if a valid
+/// value of type `T` could not be read from the bytes.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserializable,
+    D: Deserializer<'de>,
+{
+    let bytes = deserialize_bytes(deserializer)?;
+    T::read_from_bytes(&bytes).map_err(D::Error::custom)
+}
+
+// BYTE SERIALIZATION
+// ================================================================================================
+
+/// Serializes the specified bytes using the provided serializer.
+///
+/// For human-readable formats, the bytes are serialized as a hex string; otherwise, the bytes
+/// are serialized as a byte array.
+pub fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserializes a sequence of bytes serialized via [serialize_bytes()] using the provided
+/// deserializer.
+///
+/// # Errors
+/// Returns an error if the deserializer does not contain a valid byte sequence.
+pub fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(HexVisitor)
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+// VISITORS
+// ================================================================================================
+
+/// Reads bytes from a hex string.
+struct HexVisitor;
+
+impl<'de> Visitor<'de> for HexVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        hex::decode(value).map_err(E::custom)
+    }
+}
+
+/// Reads bytes from a byte array; sequences of integers are accepted as well since some binary
+/// formats do not have a dedicated representation for byte arrays.
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let capacity = seq.size_hint().unwrap_or(0).min(MAX_PREALLOCATED_BYTES);
+        let mut result = Vec::with_capacity(capacity);
+        while let Some(byte) = seq.next_element()? {
+            result.push(byte);
+        }
+        Ok(result)
+    }
+}
@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{collections::Vec, ByteReader, ByteWriter, Serializable, SliceReader};
+use super::{
+    collections::Vec, hex, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
+};
 
 // VECTOR UTILS TESTS
 // ================================================================================================
@@ -97,6 +100,12 @@ impl Serializable for u128 {
     }
 }
 
+impl Deserializable for u128 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u128()
+    }
+}
+
 #[test]
 fn write_serializable() {
     let mut target: Vec<u8> = Vec::new();
@@ -147,3 +156,123 @@ fn write_serializable_array_batch() {
         assert_eq!(i, reader.read_u128().unwrap());
     }
 }
+
+// HEX ENCODING TESTS
+// ================================================================================================
+
+#[test]
+fn hex_encoding() {
+    assert_eq!("0x", hex::encode(&[]));
+    assert_eq!("0x00ff1a", hex::encode(&[0, 255, 26]));
+
+    assert_eq!(Ok(vec![0, 255, 26]), hex::decode("0x00ff1a"));
+    assert_eq!(Ok(vec![0, 255, 26]), hex::decode("00FF1a"));
+    assert_eq!(Ok(vec![]), hex::decode("0x"));
+    assert!(hex::decode("0x0").is_err());
+    assert!(hex::decode("0x0g").is_err());
+    assert!(hex::decode("0X00").is_err());
+}
+
+#[test]
+fn hex_serialization() {
+    let value = 0x0102u128;
+    let encoded = value.to_hex();
+    assert_eq!("0x02010000000000000000000000000000", encoded);
+    assert_eq!(Ok(value), u128::from_hex(&encoded));
+
+    // all decoded bytes must be consumed
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        u128::from_hex(&format!("{encoded}00"))
+    );
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        u128::from_hex(&encoded[..encoded.len() - 2])
+    );
+}
+
+// IO ADAPTER TESTS
+// ================================================================================================
+
+#[cfg(feature = "std")]
+#[test]
+fn io_round_trip() {
+    use super::{IoReader, IoWriter};
+
+    let values = [1u128, 2, 3, u128::MAX];
+    let mut buffer = Vec::new();
+    for capacity in [0, 1, 7, 8192] {
+        let mut writer = IoWriter::with_capacity(capacity, Vec::new());
+        writer.write_u8(42);
+        writer.write_u16(0x0102);
+        writer.write(&values[..]);
+        writer.write_bytes(&[9; 100]);
+        assert_eq!(167, writer.num_written());
+        let bytes = writer.finish().unwrap();
+
+        // the bytes must be the same as the ones written into a vector directly
+        if buffer.is_empty() {
+            buffer.write_u8(42);
+            buffer.write_u16(0x0102);
+            buffer.write(&values[..]);
+            buffer.write_bytes(&[9; 100]);
+        }
+        assert_eq!(buffer, bytes);
+
+        let mut reader = IoReader::with_capacity(capacity, bytes.as_slice());
+        assert_eq!(Ok(42), reader.peek_u8());
+        assert_eq!(Ok(42), reader.read_u8());
+        assert_eq!(Ok(0x0102), reader.read_u16());
+        assert_eq!(Ok(values.to_vec()), u128::read_batch_from(&mut reader, 4));
+        assert!(reader.check_eor(100).is_ok());
+        assert!(reader.check_eor(101).is_err());
+        assert_eq!(Ok(vec![9; 100]), reader.read_vec(100));
+        assert!(!reader.has_more_bytes());
+        assert_eq!(Err(DeserializationError::UnexpectedEOF), reader.read_u8());
+        assert_eq!(167, reader.num_consumed());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_reader_limit() {
+    use super::IoReader;
+
+    let bytes = [1u8, 2, 3, 4, 5, 6];
+    let mut reader = IoReader::new(&bytes[..]).with_limit(4);
+    assert_eq!(Ok([1, 2]), reader.read_array::<2>());
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), reader.read_u32());
+    assert_eq!(Ok(0x0403), reader.read_u16());
+    assert!(!reader.has_more_bytes());
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), reader.read_u8());
+
+    // bytes past the limit are not read from the source
+    let mut source = &bytes[..];
+    let mut reader = IoReader::new(&mut source).with_limit(3);
+    assert_eq!(Ok(vec![1, 2, 3]), reader.read_vec(3));
+    drop(reader);
+    assert_eq!(&[4, 5, 6], source);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_writer_errors() {
+    use super::IoWriter;
+    use std::io::ErrorKind;
+
+    // writes exceeding the limit are rejected
+    let mut writer = IoWriter::new(Vec::new()).with_limit(3);
+    writer.write_u16(1);
+    assert_eq!(2, writer.num_written());
+    writer.write_u16(2);
+    writer.write_u8(3);
+    assert_eq!(2, writer.num_written());
+    assert_eq!(ErrorKind::WriteZero, writer.finish().unwrap_err().kind());
+
+    // errors returned by the target are reported by finish()
+    let mut target = [0u8; 4];
+    let mut writer = IoWriter::with_capacity(2, &mut target[..]);
+    writer.write_bytes(&[1, 2, 3]);
+    writer.write_bytes(&[4, 5, 6]);
+    assert_eq!(ErrorKind::WriteZero, writer.finish().unwrap_err().kind());
+}
@@ -5,18 +5,21 @@
 
 use crate::VerifierError;
 use air::{
-    proof::{Queries, StarkProof, Table},
-    Air, EvaluationFrame,
+    proof::{QueriesRef, StarkProofRef, Table},
+    Air, EvaluationFrame, LdtKind,
 };
-use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
-use fri::VerifierChannel as FriVerifierChannel;
+use crypto::{
+    BatchMerkleProof, ElementHasher, Hasher, MerkleMultiProof, MerkleTree, MerkleTreeError,
+};
+use fri::{stir::StirProof, VerifierChannel as FriVerifierChannel};
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, string::ToString};
+use utils::{collections::Vec, string::ToString, DeserializationError};
 
 // VERIFIER CHANNEL
 // ================================================================================================
 
-/// A view into a [StarkProof] for a computation structured to simulate an "interactive" channel.
+/// A view into a [StarkProofRef] for a computation structured to simulate an "interactive"
+/// channel.
 ///
 /// A channel is instantiated for a specific proof, which is parsed into structs over the
 /// appropriate field (specified by type parameter `E`). This also validates that the proof is
@@ -32,8 +35,11 @@ pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::Base
     fri_roots: Option<Vec<H::Digest>>,
     fri_layer_proofs: Vec<BatchMerkleProof<H>>,
     fri_layer_queries: Vec<Vec<E>>,
+    fri_layer_nonces: Vec<u64>,
     fri_remainder: Option<Vec<E>>,
     fri_num_partitions: usize,
+    // STIR proof
+    stir_proof: Option<StirProof>,
     // out-of-domain frame
     ood_trace_frame: Option<TraceOodFrame<E>>,
     ood_constraint_evaluations: Option<Vec<E>>,
@@ -47,16 +53,20 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     /// Creates and returns a new [VerifierChannel] initialized from the specified `proof`.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
-        proof: StarkProof,
+        proof: StarkProofRef<'_>,
     ) -> Result<Self, VerifierError> {
-        let StarkProof {
+        let StarkProofRef {
             context,
             commitments,
             trace_queries,
             constraint_queries,
             ood_frame,
             fri_proof,
+            stir_proof,
             pow_nonce,
+            // metadata is not needed by the channel since bound metadata is absorbed into the
+            // public coin before the channel is instantiated
+            metadata: _,
         } = proof;
 
         // make sure AIR and proof base fields are the same
@@ -70,34 +80,106 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         let aux_trace_width = air.trace_layout().aux_trace_width();
         let lde_domain_size = air.lde_domain_size();
         let fri_options = air.options().to_fri_options();
+        let num_ood_points = air.options().num_ood_points();
+
+        // --- validate STIR proof -----------------------------------------------------------------
+        // a STIR proof must be present if and only if the proof options select STIR; STIR round
+        // commitments are a part of the STIR proof, and thus, commitments do not include FRI
+        // layer commitments in this case
+        let use_stir = air.options().ldt() == LdtKind::Stir;
+        match (use_stir, stir_proof.is_some()) {
+            (true, false) => {
+                return Err(VerifierError::ProofDeserializationError(
+                    "proof options select STIR, but the proof does not contain a STIR proof"
+                        .to_string(),
+                ))
+            }
+            (false, true) => {
+                return Err(VerifierError::ProofDeserializationError(
+                    "proof options select FRI, but the proof contains a STIR proof".to_string(),
+                ))
+            }
+            _ => (),
+        }
+        let stir_folding_factor = air.options().to_stir_options().folding_factor();
+        if use_stir && lde_domain_size < 2 * stir_folding_factor {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "LDE domain of size {lde_domain_size} is too small for STIR folding factor \
+                {stir_folding_factor}"
+            )));
+        }
 
         // --- parse commitments ------------------------------------------------------------------
-        let (trace_roots, constraint_root, fri_roots) = commitments
-            .parse::<H>(
-                num_trace_segments,
-                fri_options.num_fri_layers(lde_domain_size),
-            )
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let (trace_roots, constraint_root, fri_roots) = if use_stir {
+            let (trace_roots, constraint_root) = commitments
+                .parse_without_fri::<H>(num_trace_segments)
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+            (trace_roots, constraint_root, Vec::new())
+        } else {
+            commitments
+                .parse::<H>(
+                    num_trace_segments,
+                    fri_options.num_fri_layers(lde_domain_size),
+                )
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?
+        };
 
         // --- parse trace and constraint queries -------------------------------------------------
         let trace_queries = TraceQueries::new(trace_queries, air)?;
         let constraint_queries = ConstraintQueries::new(constraint_queries, air)?;
 
         // --- parse FRI proofs -------------------------------------------------------------------
-        let fri_num_partitions = fri_proof.num_partitions();
-        let fri_remainder = fri_proof
-            .parse_remainder()
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let (fri_layer_queries, fri_layer_proofs) = fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        // the number of layers is implied by the proof options; layers beyond that would not be
+        // checked by the FRI verifier, and thus, are rejected to keep the proof encoding unique;
+        // when STIR is used, the FRI proof is an empty placeholder and is not parsed
+        let mut fri_num_partitions = 1;
+        let mut fri_layer_nonces = Vec::new();
+        let mut fri_remainder = Vec::new();
+        let mut fri_layer_queries = Vec::new();
+        let mut fri_layer_proofs = Vec::new();
+        if !use_stir {
+            let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+            if fri_proof.num_layers() != num_fri_layers {
+                return Err(VerifierError::ProofDeserializationError(format!(
+                    "expected {} FRI layers, but was {}",
+                    num_fri_layers,
+                    fri_proof.num_layers()
+                )));
+            }
+            fri_num_partitions = fri_proof.num_partitions();
+            fri_layer_nonces = fri_proof.layer_nonces().to_vec();
+            fri_remainder = fri_proof
+                .parse_remainder()
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+            (fri_layer_queries, fri_layer_proofs) = fri_proof
+                .parse_layers::<H, E>(lde_domain_size, &fri_options)
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        }
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
+        // the frame contains two trace rows and a row of constraint evaluations for every
+        // out-of-domain point
         let (ood_trace_evaluations, ood_constraint_evaluations) = ood_frame
-            .parse(main_trace_width, aux_trace_width, constraint_frame_width)
+            .parse(
+                main_trace_width,
+                aux_trace_width,
+                constraint_frame_width * num_ood_points,
+            )
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let ood_trace_frame =
-            TraceOodFrame::new(ood_trace_evaluations, main_trace_width, aux_trace_width);
+        let num_ood_trace_evaluations = (main_trace_width + aux_trace_width) * 2 * num_ood_points;
+        if ood_trace_evaluations.len() != num_ood_trace_evaluations {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected {} out-of-domain trace evaluations, but was {}",
+                num_ood_trace_evaluations,
+                ood_trace_evaluations.len()
+            )));
+        }
+        let ood_trace_frame = TraceOodFrame::new(
+            ood_trace_evaluations,
+            main_trace_width,
+            aux_trace_width,
+            num_ood_points,
+        );
 
         Ok(VerifierChannel {
             // trace queries
@@ -110,8 +192,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             fri_roots: Some(fri_roots),
             fri_layer_proofs,
             fri_layer_queries,
+            fri_layer_nonces,
             fri_remainder: Some(fri_remainder),
             fri_num_partitions,
+            // STIR proof
+            stir_proof,
             // out-of-domain evaluation
             ood_trace_frame: Some(ood_trace_frame),
             ood_constraint_evaluations: Some(ood_constraint_evaluations),
@@ -136,8 +221,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         self.constraint_root
     }
 
-    /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
-    /// generator of the LDE domain.
+    /// Returns trace polynomial evaluations at out-of-domain points z_j and z_j * g, where g is
+    /// the generator of the LDE domain.
     ///
     /// For computations requiring multiple trace segments, evaluations of auxiliary trace
     /// polynomials are also included.
@@ -145,14 +230,23 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         self.ood_trace_frame.take().expect("already read")
     }
 
-    /// Returns evaluations of composition polynomial columns at z^m, where z is the out-of-domain
-    /// point, and m is the number of composition polynomial columns.
+    /// Returns evaluations of composition polynomial columns at z_j^m, where z_j are the
+    /// out-of-domain points, and m is the number of composition polynomial columns.
+    ///
+    /// Evaluations of all columns at the first point are followed by evaluations of all columns
+    /// at the second point etc.
     pub fn read_ood_constraint_evaluations(&mut self) -> Vec<E> {
         self.ood_constraint_evaluations
             .take()
             .expect("already read")
     }
 
+    /// Returns the STIR proof sent by the prover; this is set only if the proof options select
+    /// STIR as the low-degree test.
+    pub fn take_stir_proof(&mut self) -> Option<StirProof> {
+        self.stir_proof.take()
+    }
+
     /// Returns query proof-of-work nonce sent by the prover.
     pub fn read_pow_nonce(&self) -> u64 {
         self.pow_nonce
@@ -173,7 +267,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 
         // make sure the states included in the proof correspond to the trace commitment
         for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
-            MerkleTree::verify_batch(root, positions, proof)
+            proof
+                .verify(root, positions)
                 .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
         }
 
@@ -189,7 +284,9 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     ) -> Result<Table<E>, VerifierError> {
         let queries = self.constraint_queries.take().expect("already read");
 
-        MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs)
+        queries
+            .query_proofs
+            .verify(&self.constraint_root, positions)
             .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
 
         Ok(queries.evaluations)
@@ -205,6 +302,7 @@ where
     H: ElementHasher<BaseField = E::BaseField>,
 {
     type Hasher = H;
+    type VectorCommitment = MerkleTree<H>;
 
     fn read_fri_num_partitions(&self) -> usize {
         self.fri_num_partitions
@@ -214,6 +312,10 @@ where
         self.fri_roots.take().expect("already read")
     }
 
+    fn read_fri_layer_nonces(&mut self) -> Vec<u64> {
+        core::mem::take(&mut self.fri_layer_nonces)
+    }
+
     fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
         self.fri_layer_proofs.remove(0)
     }
@@ -236,7 +338,7 @@ where
 ///
 /// Trace states for all auxiliary segments are stored in a single table.
 struct TraceQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    query_proofs: Vec<BatchMerkleProof<H>>,
+    query_proofs: Vec<QueryProof<H>>,
     main_states: Table<E::BaseField>,
     aux_states: Option<Table<E>>,
 }
@@ -245,7 +347,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
     /// Parses the provided trace queries into trace states in the specified field and
     /// corresponding Merkle authentication paths.
     pub fn new<A: Air<BaseField = E::BaseField>>(
-        mut queries: Vec<Queries>,
+        mut queries: Vec<QueriesRef<'_>>,
         air: &A,
     ) -> Result<Self, VerifierError> {
         assert_eq!(
@@ -257,18 +359,24 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
         );
 
         let num_queries = air.options().num_queries();
+        let multi_proofs = air.options().merkle_multi_proofs();
 
         // parse main trace segment queries; parsing also validates that hashes of each table row
         // form the leaves of Merkle authentication paths in the proofs
         let main_segment_width = air.trace_layout().main_trace_width();
         let main_segment_queries = queries.remove(0);
-        let (main_segment_query_proofs, main_segment_states) = main_segment_queries
-            .parse::<H, E::BaseField>(air.lde_domain_size(), num_queries, main_segment_width)
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "main trace segment query deserialization failed: {err}"
-                ))
-            })?;
+        let (main_segment_query_proofs, main_segment_states) = QueryProof::parse::<E::BaseField>(
+            &main_segment_queries,
+            multi_proofs,
+            air.lde_domain_size(),
+            num_queries,
+            main_segment_width,
+        )
+        .map_err(|err| {
+            VerifierError::ProofDeserializationError(format!(
+                "main trace segment query deserialization failed: {err}"
+            ))
+        })?;
 
         // all query proofs will be aggregated into a single vector
         let mut query_proofs = vec![main_segment_query_proofs];
@@ -280,13 +388,18 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
             let mut aux_trace_states = Vec::new();
             for (i, segment_queries) in queries.into_iter().enumerate() {
                 let segment_width = air.trace_layout().get_aux_segment_width(i);
-                let (segment_query_proof, segment_trace_states) = segment_queries
-                    .parse::<H, E>(air.lde_domain_size(), num_queries, segment_width)
-                    .map_err(|err| {
-                        VerifierError::ProofDeserializationError(format!(
-                            "auxiliary trace segment query deserialization failed: {err}"
-                        ))
-                    })?;
+                let (segment_query_proof, segment_trace_states) = QueryProof::parse::<E>(
+                    &segment_queries,
+                    multi_proofs,
+                    air.lde_domain_size(),
+                    num_queries,
+                    segment_width,
+                )
+                .map_err(|err| {
+                    VerifierError::ProofDeserializationError(format!(
+                        "auxiliary trace segment query deserialization failed: {err}"
+                    ))
+                })?;
 
                 query_proofs.push(segment_query_proof);
                 aux_trace_states.push(segment_trace_states);
@@ -313,7 +426,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
 /// * Queried constraint evaluation values.
 /// * Merkle authentication paths for all queries.
 struct ConstraintQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    query_proofs: BatchMerkleProof<H>,
+    query_proofs: QueryProof<H>,
     evaluations: Table<E>,
 }
 
@@ -321,19 +434,24 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
     /// Parses the provided constraint queries into evaluations in the specified field and
     /// corresponding Merkle authentication paths.
     pub fn new<A: Air<BaseField = E::BaseField>>(
-        queries: Queries,
+        queries: QueriesRef<'_>,
         air: &A,
     ) -> Result<Self, VerifierError> {
         let num_queries = air.options().num_queries();
         let constraint_frame_width = air.context().num_constraint_composition_columns();
 
-        let (query_proofs, evaluations) = queries
-            .parse::<H, E>(air.lde_domain_size(), num_queries, constraint_frame_width)
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "constraint evaluation query deserialization failed: {err}"
-                ))
-            })?;
+        let (query_proofs, evaluations) = QueryProof::parse::<E>(
+            &queries,
+            air.options().merkle_multi_proofs(),
+            air.lde_domain_size(),
+            num_queries,
+            constraint_frame_width,
+        )
+        .map_err(|err| {
+            VerifierError::ProofDeserializationError(format!(
+                "constraint evaluation query deserialization failed: {err}"
+            ))
+        })?;
 
         Ok(Self {
             query_proofs,
@@ -342,6 +460,54 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
     }
 }
 
+// QUERY PROOFS
+// ================================================================================================
+
+/// Merkle authentication paths for a set of queries against a single commitment.
+///
+/// Depending on the proof options, the paths are either a batch Merkle proof (which contains the
+/// queried leaves), or a Merkle multi-proof accompanied by the queried leaves.
+enum QueryProof<H: Hasher> {
+    Batch(BatchMerkleProof<H>),
+    Multi(MerkleMultiProof<H>, Vec<H::Digest>),
+}
+
+impl<H: Hasher> QueryProof<H> {
+    /// Parses the provided queries into values in the specified field and the corresponding
+    /// Merkle authentication paths; the paths are parsed as a Merkle multi-proof if
+    /// `multi_proof` is true, and as a batch Merkle proof otherwise.
+    fn parse<E>(
+        queries: &QueriesRef<'_>,
+        multi_proof: bool,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+    ) -> Result<(Self, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        if multi_proof {
+            let (proof, leaves, values) =
+                queries.parse_multi::<H, E>(domain_size, num_queries, values_per_query)?;
+            Ok((Self::Multi(proof, leaves), values))
+        } else {
+            let (proof, values) =
+                queries.parse::<H, E>(domain_size, num_queries, values_per_query)?;
+            Ok((Self::Batch(proof), values))
+        }
+    }
+
+    /// Checks whether the queried leaves at the specified `positions` resolve to the specified
+    /// `root`.
+    fn verify(&self, root: &H::Digest, positions: &[usize]) -> Result<(), MerkleTreeError> {
+        match self {
+            Self::Batch(proof) => MerkleTree::verify_batch(root, positions, proof),
+            Self::Multi(proof, leaves) => MerkleTree::verify_multi(root, positions, leaves, proof),
+        }
+    }
+}
+
 // TRACE OUT-OF-DOMAIN FRAME
 // ================================================================================================
 
@@ -349,14 +515,21 @@ pub struct TraceOodFrame<E: FieldElement> {
     values: Vec<E>,
     main_trace_width: usize,
     aux_trace_width: usize,
+    num_ood_points: usize,
 }
 
 impl<E: FieldElement> TraceOodFrame<E> {
-    pub fn new(values: Vec<E>, main_trace_width: usize, aux_trace_width: usize) -> Self {
+    pub fn new(
+        values: Vec<E>,
+        main_trace_width: usize,
+        aux_trace_width: usize,
+        num_ood_points: usize,
+    ) -> Self {
         Self {
             values,
             main_trace_width,
             aux_trace_width,
+            num_ood_points,
         }
     }
 
@@ -364,11 +537,15 @@ impl<E: FieldElement> TraceOodFrame<E> {
         &self.values
     }
 
+    pub fn num_ood_points(&self) -> usize {
+        self.num_ood_points
+    }
+
     // The out-of-domain frame is stored as one vector of interleaved values, one from the
-    // current row and the other from the next row. See `OodFrame::set_trace_states`.
-    // Thus we need to untangle the current and next rows stored in `Self::values` and we
-    // do that for the main and auxiliary traces separately.
-    // Pictorially, for the main trace portion:
+    // current row and the other from the next row for each out-of-domain point. See
+    // `OodFrame::set_trace_states`. Thus we need to untangle the current and next rows stored in
+    // `Self::values` and we do that for the main and auxiliary traces separately.
+    // Pictorially, for the main trace portion and a single out-of-domain point:
     //
     // Input vector: [a1, b1, a2, b2, ..., an, bn, c1, d1, c2, d2, ..., cm, dm]
     // with n being the main trace width and m the auxiliary trace width.
@@ -379,18 +556,21 @@ impl<E: FieldElement> TraceOodFrame<E> {
     //          +-------+-------+-------+-------+-------+
     //          |  b1   |   b2  |   b3  |  ...  |   bn  |
     //          +-------+-------+-------+-------+-------+
-    pub fn main_frame(&self) -> EvaluationFrame<E> {
+    //
+    // With k out-of-domain points, every column is described by 2k values, and the frame for
+    // the jth point is built from the values at indexes 2j and 2j + 1 of each column.
+    pub fn main_frame(&self, point_idx: usize) -> EvaluationFrame<E> {
         let mut current = vec![E::ZERO; self.main_trace_width];
         let mut next = vec![E::ZERO; self.main_trace_width];
 
         for (i, a) in self
             .values
-            .chunks(2)
+            .chunks(2 * self.num_ood_points)
             .take(self.main_trace_width)
             .enumerate()
         {
-            current[i] = a[0];
-            next[i] = a[1];
+            current[i] = a[2 * point_idx];
+            next[i] = a[2 * point_idx + 1];
         }
 
         EvaluationFrame::from_rows(current, next)
@@ -398,7 +578,7 @@ impl<E: FieldElement> TraceOodFrame<E> {
 
     // Similar to `Self::main_frame`, the following untangles the current and next rows stored
     // in `Self::values` for the auxiliary trace portion when it exists else it returns `None`.
-    // Pictorially:
+    // Pictorially, for a single out-of-domain point:
     //
     // Input vector: [a1, b1, a2, b2, ..., an, bn, c1, d1, c2, d2, ..., cm, dm]
     // with n being the main trace width and m the auxiliary trace width.
@@ -409,7 +589,7 @@ impl<E: FieldElement> TraceOodFrame<E> {
     //          +-------+-------+-------+-------+-------+
     //          |  d1   |   d2  |   d3  |  ...  |   dm  |
     //          +-------+-------+-------+-------+-------+
-    pub fn aux_frame(&self) -> Option<EvaluationFrame<E>> {
+    pub fn aux_frame(&self, point_idx: usize) -> Option<EvaluationFrame<E>> {
         if self.aux_trace_width == 0 {
             None
         } else {
@@ -418,12 +598,12 @@ impl<E: FieldElement> TraceOodFrame<E> {
 
             for (i, a) in self
                 .values
-                .chunks(2)
+                .chunks(2 * self.num_ood_points)
                 .skip(self.main_trace_width)
                 .enumerate()
             {
-                current_aux[i] = a[0];
-                next_aux[i] = a[1];
+                current_aux[i] = a[2 * point_idx];
+                next_aux[i] = a[2 * point_idx + 1];
             }
             Some(EvaluationFrame::from_rows(current_aux, next_aux))
         }
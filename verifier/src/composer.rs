@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{proof::Table, Air, DeepCompositionCoefficients, EvaluationFrame};
-use math::{batch_inversion, FieldElement};
+use math::FieldElement;
 use utils::collections::Vec;
 
 // DEEP COMPOSER
@@ -13,29 +13,33 @@ use utils::collections::Vec;
 pub struct DeepComposer<E: FieldElement> {
     cc: DeepCompositionCoefficients<E>,
     x_coordinates: Vec<E>,
-    z: [E; 2],
+    z: Vec<E>,
 }
 
 impl<E: FieldElement> DeepComposer<E> {
-    /// Creates a new composer for computing DEEP composition polynomial values.
+    /// Creates a new composer for computing DEEP composition polynomial values at the specified
+    /// out-of-domain points.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
         query_positions: &[usize],
-        z: E,
+        ood_points: &[E],
         cc: DeepCompositionCoefficients<E>,
     ) -> Self {
         // compute LDE domain coordinates for all query positions
-        let g_lde = air.lde_domain_generator();
-        let domain_offset = air.domain_offset();
+        let lde_domain = air.lde_domain();
         let x_coordinates: Vec<E> = query_positions
             .iter()
-            .map(|&p| E::from(g_lde.exp_vartime((p as u64).into()) * domain_offset))
+            .map(|&p| E::from(lde_domain.element_at(p)))
             .collect();
 
+        // every out-of-domain point z_j is followed by z_j * g
+        let g = E::from(air.trace_domain().generator());
+        let z = ood_points.iter().flat_map(|&z| [z, z * g]).collect();
+
         DeepComposer {
             cc,
             x_coordinates,
-            z: [z, z * E::from(air.trace_domain_generator())],
+            z,
         }
     }
 
@@ -50,86 +54,82 @@ impl<E: FieldElement> DeepComposer<E> {
     ///   T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is the coefficient for
     ///   for the random linear combination drawn from the public coin.
     ///
-    /// Note that values of T_i(z) and T_i(z * g) are received from the prover and passed into
-    /// this function via the `ood_main_frame` and `ood_aux_frame` parameters.
+    /// If more than one out-of-domain point is used, the above is done for every point z_j, and
+    /// the resulting values are added together.
+    ///
+    /// Note that values of T_i(z_j) and T_i(z_j * g) are received from the prover and passed
+    /// into this function via the `ood_main_frames` and `ood_aux_frames` parameters.
     pub fn compose_trace_columns(
         &self,
         queried_main_trace_states: Table<E::BaseField>,
         queried_aux_trace_states: Option<Table<E>>,
-        ood_main_frame: EvaluationFrame<E>,
-        ood_aux_frame: Option<EvaluationFrame<E>>,
+        ood_main_frames: Vec<EvaluationFrame<E>>,
+        ood_aux_frames: Vec<Option<EvaluationFrame<E>>>,
     ) -> Vec<E> {
-        let ood_main_trace_states = [ood_main_frame.current(), ood_main_frame.next()];
+        let ood_main_trace_states = ood_main_frames
+            .iter()
+            .flat_map(|frame| [frame.current(), frame.next()])
+            .collect::<Vec<_>>();
+        assert_eq!(ood_main_trace_states.len(), self.z.len());
 
         // compose columns of of the main trace segment; we do this separately for numerators of
-        // each query; we also track common denominator for each query separately; this way we can
-        // use a batch inversion in the end.
+        // each query and each of the points z_j and z_j * g; this way we can compute a common
+        // denominator for each query and use a batch inversion in the end.
         let n = queried_main_trace_states.num_rows();
-        let mut result_num = Vec::<E>::with_capacity(n);
-        let mut result_den = Vec::<E>::with_capacity(n);
-
-        for ((_, row), &x) in (0..n)
-            .zip(queried_main_trace_states.rows())
-            .zip(&self.x_coordinates)
-        {
-            let mut t1_num = E::ZERO;
-            let mut t2_num = E::ZERO;
+        let mut t_nums = Vec::<Vec<E>>::with_capacity(n);
 
+        for row in queried_main_trace_states.rows() {
+            let mut row_nums = vec![E::ZERO; self.z.len()];
             for (i, &value) in row.iter().enumerate() {
                 let value = E::from(value);
-                // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t1_num += (value - ood_main_trace_states[0][i]) * self.cc.trace[i];
-
-                // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t2_num += (value - ood_main_trace_states[1][i]) * self.cc.trace[i];
+                // compute the numerators of T'_i(x) as (T_i(x) - T_i(z_j)) and of T''_i(x) as
+                // (T_i(x) - T_i(z_j * g)), multiply them by a composition coefficient, and add
+                // the results to the numerator aggregators
+                for (num, ood_state) in row_nums.iter_mut().zip(ood_main_trace_states.iter()) {
+                    *num += (value - ood_state[i]) * self.cc.trace[i];
+                }
             }
-            // compute the common denominator as (x - z) * (x - z * g)
-            let t1_den = x - self.z[0];
-            let t2_den = x - self.z[1];
-            result_den.push(t1_den * t2_den);
-
-            // add the numerators of T'_i(x) and T''_i(x) together; we can do this because later on
-            // we'll use the common denominator computed above.
-            result_num.push(t1_num * t2_den + t2_num * t1_den);
+            t_nums.push(row_nums);
         }
 
         // if the trace has auxiliary segments, compose columns from these segments as well; we
-        // also do this separately for numerators and denominators.
+        // also do this separately for numerators of each point.
         if let Some(queried_aux_trace_states) = queried_aux_trace_states {
-            let ood_aux_frame = ood_aux_frame.expect("missing auxiliary OOD frame");
-            let ood_aux_trace_states = [ood_aux_frame.current(), ood_aux_frame.next()];
+            let ood_aux_trace_states = ood_aux_frames
+                .iter()
+                .flat_map(|frame| {
+                    let frame = frame.as_ref().expect("missing auxiliary OOD frame");
+                    [frame.current(), frame.next()]
+                })
+                .collect::<Vec<_>>();
 
             // we define this offset here because composition of the main trace columns has
             // consumed some number of composition coefficients already.
             let cc_offset = queried_main_trace_states.num_columns();
 
-            for ((j, row), &x) in (0..n)
-                .zip(queried_aux_trace_states.rows())
-                .zip(&self.x_coordinates)
-            {
-                let mut t1_num = E::ZERO;
-                let mut t2_num = E::ZERO;
+            for (row, row_nums) in queried_aux_trace_states.rows().zip(t_nums.iter_mut()) {
                 for (i, &value) in row.iter().enumerate() {
-                    // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t1_num += (value - ood_aux_trace_states[0][i]) * self.cc.trace[cc_offset + i];
-
-                    // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t2_num += (value - ood_aux_trace_states[1][i]) * self.cc.trace[cc_offset + i];
+                    // compute the numerators of T'_i(x) and T''_i(x), multiply them by a
+                    // composition coefficient, and add the results to the numerator aggregators
+                    for (num, ood_state) in row_nums.iter_mut().zip(ood_aux_trace_states.iter()) {
+                        *num += (value - ood_state[i]) * self.cc.trace[cc_offset + i];
+                    }
                 }
-
-                // compute the common denominators (x - z) and (x - z * g), and use the to aggregate
-                // numerators into the common numerator computed for the main trace of this query
-                let t1_den = x - self.z[0];
-                let t2_den = x - self.z[1];
-                result_num[j] += t1_num * t2_den + t2_num * t1_den;
             }
         }
 
-        result_den = batch_inversion(&result_den);
+        // compute the common denominator for each query as the product of (x - z_j) and
+        // (x - z_j * g) for all j, and add the numerators together adjusting them to the common
+        // denominator.
+        let mut result_num = Vec::<E>::with_capacity(n);
+        let mut result_den = Vec::<E>::with_capacity(n);
+        for (row_nums, &x) in t_nums.iter().zip(&self.x_coordinates) {
+            let (num, den) = sum_fractions(x, row_nums, &self.z);
+            result_num.push(num);
+            result_den.push(den);
+        }
+
+        E::batch_inverse_in_place(&mut result_den);
         result_num
             .iter()
             .zip(result_den)
@@ -146,7 +146,10 @@ impl<E: FieldElement> DeepComposer<E> {
     ///   all i, where cc_i is the coefficient for the random linear combination drawn from the
     ///   public coin.
     ///
-    /// Note that values of H_i(z) are received from the prover and passed into this function
+    /// If more than one out-of-domain point is used, the above is done for every point z_j, and
+    /// the resulting values are added together.
+    ///
+    /// Note that values of H_i(z_j) are received from the prover and passed into this function
     /// via the `ood_evaluations` parameter.
     pub fn compose_constraint_evaluations(
         &self,
@@ -159,22 +162,30 @@ impl<E: FieldElement> DeepComposer<E> {
         let mut result_num = Vec::<E>::with_capacity(n);
         let mut result_den = Vec::<E>::with_capacity(n);
 
-        let z = self.z[0];
+        // the constraint composition polynomial is opened only at points z_j
+        let z = self.z.iter().step_by(2).copied().collect::<Vec<_>>();
+        let num_columns = queried_evaluations.num_columns();
 
         // combine composition polynomial columns separately for numerators and denominators;
         // this way we can use batch inversion in the end.
         for (query_values, &x) in queried_evaluations.rows().zip(&self.x_coordinates) {
-            let mut composition_num = E::ZERO;
-            for (i, &evaluation) in query_values.iter().enumerate() {
-                // compute the numerator of H'_i(x) as (H_i(x) - H_i(z)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                composition_num += (evaluation - ood_evaluations[i]) * self.cc.constraints[i];
+            let mut composition_nums = vec![E::ZERO; z.len()];
+            for (composition_num, ood_evaluations) in composition_nums
+                .iter_mut()
+                .zip(ood_evaluations.chunks(num_columns))
+            {
+                for (i, &evaluation) in query_values.iter().enumerate() {
+                    // compute the numerator of H'_i(x) as (H_i(x) - H_i(z_j)), multiply it by a
+                    // composition coefficient, and add the result to the numerator aggregator
+                    *composition_num += (evaluation - ood_evaluations[i]) * self.cc.constraints[i];
+                }
             }
-            result_num.push(composition_num);
-            result_den.push(x - z);
+            let (num, den) = sum_fractions(x, &composition_nums, &z);
+            result_num.push(num);
+            result_den.push(den);
         }
 
-        result_den = batch_inversion(&result_den);
+        E::batch_inverse_in_place(&mut result_den);
         result_num
             .iter()
             .zip(result_den)
@@ -197,3 +208,19 @@ impl<E: FieldElement> DeepComposer<E> {
         result
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes sum(num_j / (x - z_j)) for all j, and returns the numerator and the denominator of the
+/// result; the denominator is the product of (x - z_j) for all j.
+fn sum_fractions<E: FieldElement>(x: E, nums: &[E], z: &[E]) -> (E, E) {
+    let mut result_num = E::ZERO;
+    let mut result_den = E::ONE;
+    for (&num, &z) in nums.iter().zip(z) {
+        let den = x - z;
+        result_num = result_num * den + num * result_den;
+        result_den *= den;
+    }
+    (result_num, result_den)
+}
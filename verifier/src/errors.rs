@@ -41,6 +41,21 @@ pub enum VerifierError {
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when the conjectured security level of a proof is below the minimum
+    /// security level required by the verifier; the first value is the required security level,
+    /// and the second value is the security level of the proof (both in bits).
+    InsufficientConjecturedSecurity(u32, u32),
+    /// This error occurs when the proven security level of a proof is below the minimum security
+    /// level required by the verifier; the first value is the required security level, and the
+    /// second value is the security level of the proof (both in bits).
+    InsufficientProvenSecurity(u32, u32),
+    /// This error occurs when the verifier requires a minimum proven security level, but proven
+    /// security levels cannot be estimated because the crate is compiled without `std` feature;
+    /// the value is the required security level (in bits).
+    ProvenSecurityUnavailable(u32),
+    /// This error occurs when the options used to generate a proof are not in the set of options
+    /// accepted by the verifier.
+    UnacceptableProofOptions,
 }
 
 impl fmt::Display for VerifierError {
@@ -74,6 +89,18 @@ impl fmt::Display for VerifierError {
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {err}")
             }
+            Self::InsufficientConjecturedSecurity(min_security_level, security_level) => {
+                write!(f, "conjectured security level of the proof is {security_level} bits, but at least {min_security_level} bits are required")
+            }
+            Self::InsufficientProvenSecurity(min_security_level, security_level) => {
+                write!(f, "proven security level of the proof is {security_level} bits, but at least {min_security_level} bits are required")
+            }
+            Self::ProvenSecurityUnavailable(min_security_level) => {
+                write!(f, "proven security level of at least {min_security_level} bits is required, but proven security cannot be estimated without std feature")
+            }
+            Self::UnacceptableProofOptions => {
+                write!(f, "proof options are not in the set of acceptable options")
+            }
         }
     }
 }
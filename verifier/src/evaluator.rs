@@ -24,14 +24,16 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     // initialize a buffer to hold transition constraint evaluations
     let t_constraints = air.get_transition_constraints(&composition_coefficients.transition);
 
-    // compute values of periodic columns at x
+    // compute values of periodic columns at x; values of each column over a single cycle are
+    // evaluations of the column polynomial (in x^num_cycles) over a subgroup of size equal to
+    // the cycle length, and thus, we can evaluate the polynomial without interpolating it
     let periodic_values = air
-        .get_periodic_column_polys()
+        .get_periodic_column_values()
         .iter()
-        .map(|poly| {
-            let num_cycles = air.trace_length() / poly.len();
+        .map(|values| {
+            let num_cycles = air.trace_length() / values.len();
             let x = x.exp_vartime((num_cycles as u32).into());
-            polynom::eval(poly, x)
+            polynom::eval_barycentric(values, E::BaseField::ONE, x)
         })
         .collect::<Vec<_>>();
 
@@ -42,7 +44,7 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     // evaluate transition constraints for auxiliary trace segments (if any)
     let mut t_evaluations2 = E::zeroed_vector(t_constraints.num_aux_constraints());
     if let Some(aux_trace_frame) = aux_trace_frame {
-        air.evaluate_aux_transition(
+        air.evaluate_aux_transition_with_lookups(
             main_trace_frame,
             aux_trace_frame,
             &periodic_values,
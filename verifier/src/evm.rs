@@ -0,0 +1,416 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{verify_proof, VerifierError};
+use air::{
+    proof::{StarkProof, Table},
+    Air, FieldExtension, LdtKind,
+};
+use crypto::{Digest, ElementHasher, RandomCoin};
+use fri::{folding::fold_positions, utils::map_positions_to_indexes};
+use math::{
+    fields::{CubeExtension, QuadExtension, QuartExtension},
+    FieldElement, StarkField,
+};
+use utils::{collections::Vec, string::ToString};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the EVM proof encoding produced by [encode_for_evm()].
+pub const EVM_ENCODING_VERSION: u64 = 1;
+
+/// Number of words in the header of an EVM-encoded proof.
+const NUM_HEADER_WORDS: usize = 14;
+
+/// Number of sections in an EVM-encoded proof.
+const NUM_SECTIONS: usize = 9;
+
+/// Size of an EVM word in bytes.
+const WORD_SIZE: usize = 32;
+
+// EVM ENCODING
+// ================================================================================================
+/// Verifies the specified `proof` and encodes it into a layout optimized for parsing by EVM smart
+/// contracts (e.g., for passing the proof as calldata to a Solidity verifier).
+///
+/// In contrast to the compact binary format, every value in this layout occupies a separate
+/// 32-byte word, all Merkle authentication paths are uncompressed, and all data for a given query
+/// position is grouped together. Thus, a contract can read any value in the proof with a single
+/// `calldataload` at an offset which can be computed directly.
+///
+/// Query positions are pseudo-randomly derived from the proof, and thus, the proof is verified
+/// against the specified public inputs before it is encoded.
+///
+/// # Layout
+/// Integers are encoded as big-endian unsigned 256-bit integers, field elements of the base field
+/// are encoded as their canonical integer values, elements of extension fields are encoded as
+/// one word per base field coefficient, and digests are written as is (digests smaller than 32
+/// bytes are padded with zeros on the right).
+///
+/// The encoding starts with a header containing the following words:
+/// 0. Encoding version (see [EVM_ENCODING_VERSION]).
+/// 1. Trace length.
+/// 2. LDE domain size.
+/// 3. Number of queries.
+/// 4. Blowup factor.
+/// 5. Grinding factor.
+/// 6. Field extension degree.
+/// 7. FRI folding factor.
+/// 8. Number of FRI layers.
+/// 9. Number of FRI partitions.
+/// 10. Width of the main trace segment.
+/// 11. Total width of auxiliary trace segments.
+/// 12. Number of constraint composition columns.
+/// 13. Proof-of-work nonce.
+///
+/// The header is followed by an offsets table with 9 words containing byte offsets (from the
+/// start of the encoding) of the following sections:
+/// 0. Trace segment commitments.
+/// 1. Constraint composition commitment.
+/// 2. FRI layer commitments followed by the FRI remainder commitment.
+/// 3. Out-of-domain trace frame, row by row (values of the main trace segment are followed by
+///    values of the auxiliary trace segments in each row).
+/// 4. Out-of-domain constraint composition evaluations.
+/// 5. Query positions in the order in which they were drawn by the verifier.
+/// 6. Trace and constraint queries. For each query position: main trace values, auxiliary trace
+///    values, constraint composition values, and then authentication paths for all trace
+///    segments followed by the authentication path for the constraint composition commitment.
+/// 7. FRI layer queries. For each layer: the number of queries in the layer, and then for each
+///    query: index of the queried leaf, all values of the queried coset, and the authentication
+///    path for the leaf.
+/// 8. FRI remainder values.
+///
+/// Each section starts with a word containing the number of words in the section (not counting
+/// the length word itself). Authentication paths do not include the leaf nodes since these can
+/// be computed by hashing the queried values, and are ordered from the leaf level to the root.
+///
+/// # Errors
+/// Returns an error if the proof does not attest to a correct execution of the computation
+/// specified by `AIR` against the specified public inputs (see [verify()](crate::verify)), or if
+/// trace or constraint commitments of the proof are salted; salted leaves cannot be computed from
+/// the queried values alone, and thus, are not supported by this layout. Proofs generated with a
+/// FRI folding schedule (i.e., with different folding factors for different FRI layers) are not
+/// supported either, since the header of this layout holds a single FRI folding factor; neither
+/// are proofs with more than one out-of-domain point, proofs with FRI layer proof-of-work nonces,
+/// proofs which use STIR as the low-degree test, nor proofs with Merkle multi-proofs.
+pub fn encode_for_evm<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<Vec<u8>, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let (air, query_positions) = verify_proof::<AIR, HashFn, RandCoin>(proof.view(), pub_inputs)?;
+    if proof.trace_queries.iter().any(|q| q.is_salted()) || proof.constraint_queries.is_salted() {
+        return Err(VerifierError::ProofDeserializationError(
+            "proofs with salted commitments cannot be encoded for EVM".to_string(),
+        ));
+    }
+    if !air.options().to_fri_options().is_uniform() {
+        return Err(VerifierError::ProofDeserializationError(
+            "proofs with FRI folding schedules cannot be encoded for EVM".to_string(),
+        ));
+    }
+    if air.options().ldt() == LdtKind::Stir {
+        return Err(VerifierError::ProofDeserializationError(
+            "proofs with STIR low-degree tests cannot be encoded for EVM".to_string(),
+        ));
+    }
+    if air.options().fri_layer_grinding_factor() > 0 {
+        return Err(VerifierError::ProofDeserializationError(
+            "proofs with FRI layer grinding cannot be encoded for EVM".to_string(),
+        ));
+    }
+    if air.options().merkle_multi_proofs() {
+        return Err(VerifierError::ProofDeserializationError(
+            "proofs with Merkle multi-proofs cannot be encoded for EVM".to_string(),
+        ));
+    }
+    if air.options().num_ood_points() > 1 {
+        return Err(VerifierError::ProofDeserializationError(
+            "proofs with multiple out-of-domain points cannot be encoded for EVM".to_string(),
+        ));
+    }
+
+    // support for field extensions has already been checked during verification
+    match air.options().field_extension() {
+        FieldExtension::None => {
+            encode_proof::<AIR, AIR::BaseField, HashFn>(&air, proof, &query_positions)
+        }
+        FieldExtension::Quadratic => encode_proof::<AIR, QuadExtension<AIR::BaseField>, HashFn>(
+            &air,
+            proof,
+            &query_positions,
+        ),
+        FieldExtension::Cubic => encode_proof::<AIR, CubeExtension<AIR::BaseField>, HashFn>(
+            &air,
+            proof,
+            &query_positions,
+        ),
+        FieldExtension::Quartic => encode_proof::<AIR, QuartExtension<AIR::BaseField>, HashFn>(
+            &air,
+            proof,
+            &query_positions,
+        ),
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Encodes a proof which has already been verified against the specified `air` using query
+/// positions drawn during the verification.
+fn encode_proof<A, E, H>(
+    air: &A,
+    proof: StarkProof,
+    query_positions: &[usize],
+) -> Result<Vec<u8>, VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    let StarkProof {
+        context,
+        commitments,
+        trace_queries,
+        constraint_queries,
+        ood_frame,
+        fri_proof,
+        pow_nonce,
+        ..
+    } = proof;
+
+    let trace_layout = air.trace_layout();
+    let main_trace_width = trace_layout.main_trace_width();
+    let aux_trace_width = trace_layout.aux_trace_width();
+    let num_constraint_columns = air.context().num_constraint_composition_columns();
+    let lde_domain_size = air.lde_domain_size();
+    let num_queries = query_positions.len();
+    let fri_options = air.options().to_fri_options();
+    let folding_factor = fri_options.folding_factor();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+    let num_fri_partitions = fri_proof.num_partitions();
+
+    // --- header ---------------------------------------------------------------------------------
+    let mut result = Vec::new();
+    for value in [
+        EVM_ENCODING_VERSION,
+        context.trace_length() as u64,
+        lde_domain_size as u64,
+        num_queries as u64,
+        air.options().blowup_factor() as u64,
+        air.options().grinding_factor() as u64,
+        E::EXTENSION_DEGREE as u64,
+        folding_factor as u64,
+        num_fri_layers as u64,
+        num_fri_partitions as u64,
+        main_trace_width as u64,
+        aux_trace_width as u64,
+        num_constraint_columns as u64,
+        pow_nonce,
+    ] {
+        write_uint(&mut result, value);
+    }
+    debug_assert_eq!(NUM_HEADER_WORDS * WORD_SIZE, result.len());
+
+    let mut sections: Vec<Vec<u8>> = Vec::with_capacity(NUM_SECTIONS);
+
+    // --- commitments ----------------------------------------------------------------------------
+    let (trace_roots, constraint_root, fri_roots) = commitments
+        .parse::<H>(trace_layout.num_segments(), num_fri_layers)
+        .map_err(to_error)?;
+
+    let mut section = Vec::new();
+    trace_roots
+        .iter()
+        .for_each(|root| write_digest(&mut section, root));
+    sections.push(section);
+
+    let mut section = Vec::new();
+    write_digest(&mut section, &constraint_root);
+    sections.push(section);
+
+    let mut section = Vec::new();
+    fri_roots
+        .iter()
+        .for_each(|root| write_digest(&mut section, root));
+    sections.push(section);
+
+    // --- out-of-domain frame --------------------------------------------------------------------
+    let (ood_trace_states, ood_evaluations) = ood_frame
+        .parse::<E>(main_trace_width, aux_trace_width, num_constraint_columns)
+        .map_err(to_error)?;
+
+    // OOD trace states are stored column by column; re-arrange them into rows
+    let trace_width = main_trace_width + aux_trace_width;
+    let frame_size = ood_trace_states.len() / trace_width;
+    let mut section = Vec::new();
+    for row_idx in 0..frame_size {
+        for col_idx in 0..trace_width {
+            write_elements(
+                &mut section,
+                &[ood_trace_states[col_idx * frame_size + row_idx]],
+            );
+        }
+    }
+    sections.push(section);
+
+    let mut section = Vec::new();
+    write_elements(&mut section, &ood_evaluations);
+    sections.push(section);
+
+    // --- query positions ------------------------------------------------------------------------
+    let mut section = Vec::new();
+    for &position in query_positions {
+        write_uint(&mut section, position as u64);
+    }
+    sections.push(section);
+
+    // --- trace and constraint queries -----------------------------------------------------------
+    let mut trace_queries = trace_queries.into_iter();
+    let mut trace_states = Vec::new();
+    let mut trace_paths = Vec::new();
+
+    let (main_proof, main_states) = trace_queries
+        .next()
+        .expect("no main trace segment queries")
+        .parse::<H, A::BaseField>(lde_domain_size, num_queries, main_trace_width)
+        .map_err(to_error)?;
+    trace_paths.push(main_proof.into_paths(query_positions).map_err(to_error)?);
+
+    for (i, segment_queries) in trace_queries.enumerate() {
+        let segment_width = trace_layout.get_aux_segment_width(i);
+        let (segment_proof, segment_states) = segment_queries
+            .parse::<H, E>(lde_domain_size, num_queries, segment_width)
+            .map_err(to_error)?;
+        trace_states.push(segment_states);
+        trace_paths.push(
+            segment_proof
+                .into_paths(query_positions)
+                .map_err(to_error)?,
+        );
+    }
+    let aux_states = (!trace_states.is_empty()).then(|| Table::merge(trace_states));
+
+    let (constraint_proof, constraint_evaluations) = constraint_queries
+        .parse::<H, E>(lde_domain_size, num_queries, num_constraint_columns)
+        .map_err(to_error)?;
+    let constraint_paths = constraint_proof
+        .into_paths(query_positions)
+        .map_err(to_error)?;
+
+    let mut section = Vec::new();
+    for query_idx in 0..num_queries {
+        write_elements(&mut section, main_states.get_row(query_idx));
+        if let Some(aux_states) = aux_states.as_ref() {
+            write_elements(&mut section, aux_states.get_row(query_idx));
+        }
+        write_elements(&mut section, constraint_evaluations.get_row(query_idx));
+        for segment_paths in trace_paths.iter() {
+            write_path(&mut section, &segment_paths[query_idx]);
+        }
+        write_path(&mut section, &constraint_paths[query_idx]);
+    }
+    sections.push(section);
+
+    // --- FRI layer queries ----------------------------------------------------------------------
+    let fri_remainder = fri_proof.parse_remainder::<E>().map_err(to_error)?;
+    let (layer_queries, layer_proofs) = fri_proof
+        .parse_layers::<H, E>(lde_domain_size, &fri_options)
+        .map_err(to_error)?;
+
+    let mut section = Vec::new();
+    let mut positions = query_positions.to_vec();
+    let mut domain_size = lde_domain_size;
+    for (layer_values, layer_proof) in layer_queries.into_iter().zip(layer_proofs) {
+        let folded_positions = fold_positions(&positions, domain_size, folding_factor);
+        let position_indexes = map_positions_to_indexes(
+            &folded_positions,
+            domain_size,
+            folding_factor,
+            num_fri_partitions,
+        );
+        let layer_paths = layer_proof
+            .into_paths(&position_indexes)
+            .map_err(to_error)?;
+
+        write_uint(&mut section, position_indexes.len() as u64);
+        for ((index, values), path) in position_indexes
+            .iter()
+            .zip(layer_values.chunks(folding_factor))
+            .zip(layer_paths.iter())
+        {
+            write_uint(&mut section, *index as u64);
+            write_elements(&mut section, values);
+            write_path(&mut section, path);
+        }
+
+        positions = folded_positions;
+        domain_size /= folding_factor;
+    }
+    sections.push(section);
+
+    // --- FRI remainder --------------------------------------------------------------------------
+    let mut section = Vec::new();
+    write_elements(&mut section, &fri_remainder);
+    sections.push(section);
+
+    // --- offsets table and sections -------------------------------------------------------------
+    debug_assert_eq!(NUM_SECTIONS, sections.len());
+    let mut offset = (NUM_HEADER_WORDS + NUM_SECTIONS) * WORD_SIZE;
+    for section in sections.iter() {
+        write_uint(&mut result, offset as u64);
+        offset += WORD_SIZE + section.len();
+    }
+    for section in sections {
+        write_uint(&mut result, (section.len() / WORD_SIZE) as u64);
+        result.extend_from_slice(&section);
+    }
+
+    Ok(result)
+}
+
+/// Writes the specified integer into the `target` as a big-endian 256-bit word.
+fn write_uint(target: &mut Vec<u8>, value: u64) {
+    target.extend_from_slice(&[0; WORD_SIZE - 8]);
+    target.extend_from_slice(&value.to_be_bytes());
+}
+
+/// Writes each of the specified field elements into the `target` as one word per base field
+/// coefficient; each coefficient is encoded as a big-endian integer in canonical form.
+fn write_elements<E: FieldElement>(target: &mut Vec<u8>, elements: &[E]) {
+    for element in E::slice_as_base_elements(elements) {
+        write_base_element(target, element);
+    }
+}
+
+fn write_base_element<B: StarkField>(target: &mut Vec<u8>, element: &B) {
+    // canonical serialization of base field elements is little-endian
+    let mut bytes = element.to_bytes();
+    bytes.reverse();
+    target.extend_from_slice(&[0; WORD_SIZE][bytes.len()..]);
+    target.extend_from_slice(&bytes);
+}
+
+fn write_digest<D: Digest>(target: &mut Vec<u8>, digest: &D) {
+    target.extend_from_slice(digest.as_bytes().as_ref());
+}
+
+/// Writes an authentication path into the `target` skipping the leaf node (which is always the
+/// first node of the path).
+fn write_path<D: Digest>(target: &mut Vec<u8>, path: &[D]) {
+    for node in path.iter().skip(1) {
+        write_digest(target, node);
+    }
+}
+
+/// Converts a proof parsing error into a [VerifierError].
+fn to_error<E: ToString>(err: E) -> VerifierError {
+    VerifierError::ProofDeserializationError(err.to_string())
+}
@@ -13,7 +13,9 @@
 //! 1. Define an *algebraic intermediate representation* (AIR) for you computation. This can be
 //!    done by implementing [Air] trait.
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
-//!    [StarkProof] and related public inputs as parameters.
+//!    [StarkProof], related public inputs, and [AcceptableOptions] as parameters. Acceptable
+//!    options define the minimum security level (or the exact set of proof options) a proof must
+//!    satisfy to be accepted.
 //!
 //! # Performance
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
@@ -33,16 +35,17 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    proof::{ProofMetadata, StarkProof, StarkProofRef},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension, LdtKind, LookupTable, ProofOptions, SecurityEstimate,
+    TraceInfo, TransitionConstraintDegree,
 };
 
 pub use math;
 use math::{
-    fields::{CubeExtension, QuadExtension},
-    FieldElement, ToElements,
+    fields::{CubeExtension, QuadExtension, QuartExtension},
+    FieldElement, StarkField,
 };
 
 pub use utils::{
@@ -50,9 +53,13 @@ pub use utils::{
 };
 
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, QuerySampler, RandomCoin};
 
-use fri::FriVerifier;
+use fri::{
+    stir::{StirProof, StirVerifier},
+    FriVerifier,
+};
+use utils::{collections::Vec, string::ToString};
 
 mod channel;
 use channel::VerifierChannel;
@@ -66,6 +73,12 @@ use composer::DeepComposer;
 mod errors;
 pub use errors::VerifierError;
 
+mod evm;
+pub use evm::{encode_for_evm, EVM_ENCODING_VERSION};
+
+#[cfg(test)]
+mod tests;
+
 // VERIFIER
 // ================================================================================================
 /// Verifies that the specified computation was executed correctly against the specified inputs.
@@ -74,16 +87,198 @@ pub use errors::VerifierError;
 /// `proof` attests to the correct execution of the computation against public inputs specified
 /// by `pub_inputs`. If the verification is successful, `Ok(())` is returned.
 ///
+/// Before the proof is verified, the parameters used to generate the proof are checked against
+/// `acceptable_options`; this way, proofs generated with inadequate parameters (e.g., with too
+/// few queries) are rejected even if they are otherwise valid.
+///
 /// # Errors
-/// Returns an error if combination of the provided proof and public inputs does not attest to
-/// a correct execution of the computation. This could happen for many various reasons, including:
+/// Returns an error if the proof was generated with options not acceptable under
+/// `acceptable_options`, or if combination of the provided proof and public inputs does not
+/// attest to a correct execution of the computation. This could happen for many various reasons,
+/// including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
-#[rustfmt::skip]
 pub fn verify<AIR, HashFn, RandCoin>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
-) -> Result<(), VerifierError> 
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    acceptable_options.validate::<AIR::BaseField, HashFn>(proof.options(), proof.trace_length())?;
+    verify_proof::<AIR, HashFn, RandCoin>(proof.view(), pub_inputs)?;
+    Ok(())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using a borrowed view of the proof.
+///
+/// Verification is performed in the same way as by the [verify()] function. However, since
+/// [StarkProofRef] borrows query decommitments and the FRI proof directly from the serialized
+/// proof (see [StarkProofRef::from_bytes()]), a proof does not need to be deserialized into an
+/// owned [StarkProof] before it can be verified.
+///
+/// # Errors
+/// Returns an error if the proof was generated with options not acceptable under
+/// `acceptable_options`, or if combination of the provided proof and public inputs does not
+/// attest to a correct execution of the computation.
+pub fn verify_ref<AIR, HashFn, RandCoin>(
+    proof: StarkProofRef<'_>,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    acceptable_options.validate::<AIR::BaseField, HashFn>(proof.options(), proof.trace_length())?;
+    verify_proof::<AIR, HashFn, RandCoin>(proof, pub_inputs)?;
+    Ok(())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns metadata attached to the proof.
+///
+/// Verification is performed in the same way as by the [verify()] function. Only bound metadata
+/// (see [ProofMetadata::is_bound()]) is authenticated by the proof; unbound metadata is returned
+/// as is, and thus, could have been modified after the proof was generated.
+///
+/// # Errors
+/// Returns an error if the proof was generated with options not acceptable under
+/// `acceptable_options`, or if combination of the provided proof and public inputs does not
+/// attest to a correct execution of the computation.
+pub fn verify_with_metadata<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<Option<ProofMetadata>, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    acceptable_options.validate::<AIR::BaseField, HashFn>(proof.options(), proof.trace_length())?;
+    verify_proof::<AIR, HashFn, RandCoin>(proof.view(), pub_inputs)?;
+    let metadata = proof.metadata;
+    Ok(metadata)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using a proof compressed via [StarkProof::to_bytes_compressed()].
+///
+/// This decompresses the proof from `proof_bytes` and then performs verification in the same way
+/// as the [verify()] function does.
+///
+/// # Errors
+/// Returns an error if the proof could not be decompressed, if the proof was generated with
+/// options not acceptable under `acceptable_options`, or if combination of the decompressed
+/// proof and public inputs does not attest to a correct execution of the computation.
+pub fn verify_compressed<AIR, HashFn, RandCoin>(
+    proof_bytes: &[u8],
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let proof = StarkProof::from_bytes_compressed(proof_bytes)
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    verify::<AIR, HashFn, RandCoin>(proof, pub_inputs, acceptable_options)
+}
+
+// ACCEPTABLE OPTIONS
+// ================================================================================================
+/// Defines which proofs are accepted by the verifier based on the parameters used to generate
+/// them.
+///
+/// The soundness of a proof depends on the options used to generate it, and these options are
+/// chosen by the prover. Thus, a verifier must make sure that the options of a proof provide an
+/// adequate security level; otherwise, a dishonest prover could generate a valid proof for an
+/// invalid statement using options with very few queries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AcceptableOptions {
+    /// Proofs with conjectured security level of at least the specified number of bits are
+    /// accepted (see [SecurityEstimate::Conjectured]).
+    MinConjecturedSecurity(u32),
+    /// Proofs with proven security level of at least the specified number of bits are accepted
+    /// (see [SecurityEstimate::Proven]).
+    ///
+    /// Proven security level can be estimated only when the crate is compiled with `std`
+    /// feature enabled; otherwise, all proofs are rejected with
+    /// [VerifierError::ProvenSecurityUnavailable].
+    MinProvenSecurity(u32),
+    /// Proofs generated with any of the specified options are accepted.
+    OptionSet(Vec<ProofOptions>),
+}
+
+impl AcceptableOptions {
+    /// Returns `Ok(())` if a proof generated with the specified `options` for a computation with
+    /// the specified trace length in the base field `B` using hash function `H` is acceptable.
+    ///
+    /// # Errors
+    /// Returns an error if the security level of such a proof is below the required minimum, if
+    /// `options` are not in the set of acceptable options, or if the minimum proven security level
+    /// is required and the crate is compiled without `std` feature.
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is not a power of two.
+    pub fn validate<B: StarkField, H: Hasher>(
+        &self,
+        options: &ProofOptions,
+        trace_length: usize,
+    ) -> Result<(), VerifierError> {
+        match self {
+            Self::MinConjecturedSecurity(min_security_level) => {
+                let security_level =
+                    options.security_level::<B, H>(trace_length, SecurityEstimate::Conjectured);
+                if security_level < *min_security_level {
+                    return Err(VerifierError::InsufficientConjecturedSecurity(
+                        *min_security_level,
+                        security_level,
+                    ));
+                }
+            }
+            Self::MinProvenSecurity(min_security_level) => {
+                #[cfg(not(feature = "std"))]
+                return Err(VerifierError::ProvenSecurityUnavailable(
+                    *min_security_level,
+                ));
+
+                #[cfg(feature = "std")]
+                {
+                    let security_level =
+                        options.security_level::<B, H>(trace_length, SecurityEstimate::Proven);
+                    if security_level < *min_security_level {
+                        return Err(VerifierError::InsufficientProvenSecurity(
+                            *min_security_level,
+                            security_level,
+                        ));
+                    }
+                }
+            }
+            Self::OptionSet(option_set) => {
+                if !option_set.contains(options) {
+                    return Err(VerifierError::UnacceptableProofOptions);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the specified `proof` against the specified public inputs, and returns the AIR
+/// instance for the computation together with query positions drawn during verification.
+#[rustfmt::skip]
+pub(crate) fn verify_proof<AIR, HashFn, RandCoin>(
+    proof: StarkProofRef<'_>,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<(AIR, Vec<usize>), VerifierError> 
 where 
     AIR: Air, 
     HashFn: ElementHasher<BaseField = AIR::BaseField>,
@@ -92,35 +287,52 @@ where
     // build a seed for the public coin; the initial seed is a hash of the proof context and the
     // public inputs, but as the protocol progresses, the coin will be reseeded with the info
     // received from the prover
-    let mut public_coin_seed = proof.context.to_elements();
-    public_coin_seed.append(&mut pub_inputs.to_elements());
+    let public_coin_seed = proof.context.get_public_coin_seed(&pub_inputs);
     
     // create AIR instance for the computation specified in the proof
     let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
 
+    #[cfg(feature = "transcript")]
+    crypto::transcript::set_phase("trace commitment");
+
+    // instantiate the public coin; if the proof carries bound metadata, the metadata is absorbed
+    // into the coin before anything else
+    let mut public_coin = RandCoin::new(&public_coin_seed);
+    if let Some(metadata) = proof.metadata.as_ref().filter(|metadata| metadata.is_bound()) {
+        public_coin.reseed(HashFn::hash(&metadata.to_bytes()));
+    }
+
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => {
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, channel, public_coin)
+            let query_positions = perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(&air, channel, public_coin)?;
+            Ok((air, query_positions))
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(2));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let query_positions = perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(&air, channel, public_coin)?;
+            Ok((air, query_positions))
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(3));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let query_positions = perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(&air, channel, public_coin)?;
+            Ok((air, query_positions))
+        },
+        FieldExtension::Quartic => {
+            if !<QuartExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(4));
+            }
+            let channel = VerifierChannel::new(&air, proof)?;
+            let query_positions = perform_verification::<AIR, QuartExtension<AIR::BaseField>, HashFn, RandCoin>(&air, channel, public_coin)?;
+            Ok((air, query_positions))
         },
     }
 }
@@ -128,12 +340,13 @@ where
 // VERIFICATION PROCEDURE
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
-/// attests to a correct execution of the computation specified by the provided `air`.
+/// attests to a correct execution of the computation specified by the provided `air`. If the
+/// verification is successful, positions of the LDE domain queried by the verifier are returned.
 fn perform_verification<A, E, H, R>(
-    air: A,
+    air: &A,
     mut channel: VerifierChannel<E, H>,
     mut public_coin: R,
-) -> Result<(), VerifierError>
+) -> Result<Vec<usize>, VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
@@ -171,63 +384,90 @@ where
 
     // 2 ----- constraint commitment --------------------------------------------------------------
     // read the commitment to evaluations of the constraint composition polynomial over the LDE
-    // domain sent by the prover, use it to update the public coin, and draw an out-of-domain point
-    // z from the coin; in the interactive version of the protocol, the verifier sends this point z
-    // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
-    // and sends the results back to the verifier.
+    // domain sent by the prover, use it to update the public coin, and draw out-of-domain points
+    // z_j from the coin (usually, just a single point z); in the interactive version of the
+    // protocol, the verifier sends these points to the prover, and the prover evaluates trace and
+    // constraint composition polynomials at every z_j, and sends the results back to the verifier.
+    #[cfg(feature = "transcript")]
+    crypto::transcript::set_phase("constraint commitment");
     let constraint_commitment = channel.read_constraint_commitment();
     public_coin.reseed(constraint_commitment);
-    let z = public_coin
-        .draw::<E>()
+    let ood_points = (0..air.options().num_ood_points())
+        .map(|_| public_coin.draw_ext::<E>())
+        .collect::<Result<Vec<_>, _>>()
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
-    // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
+    // make sure that evaluations obtained by evaluating constraints over the out-of-domain frames
     // are consistent with the evaluations of composition polynomial columns sent by the prover
 
     // read the out-of-domain trace frames (the main trace frame and auxiliary trace frame, if
-    // provided) sent by the prover and evaluate constraints over them; also, reseed the public
-    // coin with the OOD frames received from the prover.
+    // provided) sent by the prover and evaluate constraints over them at every point z_j; also,
+    // reseed the public coin with the OOD frames received from the prover.
+    #[cfg(feature = "transcript")]
+    crypto::transcript::set_phase("out-of-domain evaluation");
     let ood_trace_frame = channel.read_ood_trace_frame();
-    let ood_main_trace_frame = ood_trace_frame.main_frame();
-    let ood_aux_trace_frame = ood_trace_frame.aux_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(
-        &air,
-        constraint_coeffs,
-        &ood_main_trace_frame,
-        &ood_aux_trace_frame,
-        aux_trace_rand_elements,
-        z,
-    );
+    let ood_main_trace_frames = (0..ood_trace_frame.num_ood_points())
+        .map(|j| ood_trace_frame.main_frame(j))
+        .collect::<Vec<_>>();
+    let ood_aux_trace_frames = (0..ood_trace_frame.num_ood_points())
+        .map(|j| ood_trace_frame.aux_frame(j))
+        .collect::<Vec<_>>();
+    let ood_constraint_evaluations_1 = ood_points
+        .iter()
+        .zip(
+            ood_main_trace_frames
+                .iter()
+                .zip(ood_aux_trace_frames.iter()),
+        )
+        .map(|(&z, (ood_main_trace_frame, ood_aux_trace_frame))| {
+            evaluate_constraints(
+                air,
+                constraint_coeffs.clone(),
+                ood_main_trace_frame,
+                ood_aux_trace_frame,
+                aux_trace_rand_elements.clone(),
+                z,
+            )
+        })
+        .collect::<Vec<_>>();
     public_coin.reseed(H::hash_elements(ood_trace_frame.values()));
 
-    // read evaluations of composition polynomial columns sent by the prover, and reduce them into
-    // a single value by computing \sum_{i=0}^{m-1}(z^(i * l) * value_i), where value_i is the
-    // evaluation of the ith column polynomial H_i(X) at z, l is the trace length and m is
-    // the number of composition column polynomials. This computes H(z) (i.e.
+    // read evaluations of composition polynomial columns sent by the prover, and for every z_j,
+    // reduce them into a single value by computing \sum_{i=0}^{m-1}(z^(i * l) * value_i), where
+    // value_i is the evaluation of the ith column polynomial H_i(X) at z, l is the trace length
+    // and m is the number of composition column polynomials. This computes H(z) (i.e.
     // the evaluation of the composition polynomial at z) using the fact that
     // H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X).
     // Also, reseed the public coin with the OOD constraint evaluations received from the prover.
     let ood_constraint_evaluations = channel.read_ood_constraint_evaluations();
-    let ood_constraint_evaluation_2 =
-        ood_constraint_evaluations
-            .iter()
-            .enumerate()
-            .fold(E::ZERO, |result, (i, &value)| {
-                result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
-            });
+    let num_columns = ood_constraint_evaluations.len() / ood_points.len();
+    let ood_constraint_evaluations_2 = ood_points
+        .iter()
+        .zip(ood_constraint_evaluations.chunks(num_columns))
+        .map(|(&z, evaluations)| {
+            evaluations
+                .iter()
+                .enumerate()
+                .fold(E::ZERO, |result, (i, &value)| {
+                    result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
+                })
+        })
+        .collect::<Vec<_>>();
     public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
 
     // finally, make sure the values are the same
-    if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
+    if ood_constraint_evaluations_1 != ood_constraint_evaluations_2 {
         return Err(VerifierError::InconsistentOodConstraintEvaluations);
     }
 
-    // 4 ----- FRI commitments --------------------------------------------------------------------
+    // 4 ----- FRI commitments (or STIR rounds) --------------------------------------------------
     // draw coefficients for computing DEEP composition polynomial from the public coin; in the
     // interactive version of the protocol, the verifier sends these coefficients to the prover
     // and the prover uses them to compute the DEEP composition polynomial. the prover, then
     // applies FRI protocol to the evaluations of the DEEP composition polynomial.
+    #[cfg(feature = "transcript")]
+    crypto::transcript::set_phase("FRI commitment");
     let deep_coefficients = air
         .get_deep_composition_coefficients::<E, R>(&mut public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
@@ -237,17 +477,40 @@ where
     // The verifier uses these commitments to update the public coin and draw random points alpha
     // from them; in the interactive version of the protocol, the verifier sends these alphas to
     // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
-    let fri_verifier = FriVerifier::new(
-        &mut channel,
-        &mut public_coin,
-        air.options().to_fri_options(),
-        air.trace_poly_degree(),
-    )
-    .map_err(VerifierError::FriVerificationFailed)?;
-    // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+    //
+    // when STIR is used instead of FRI, all rounds of the STIR protocol are verified at this
+    // point, as rounds are executed before the query positions are drawn; the only remaining
+    // check is whether the committed first round evaluations agree with the DEEP composition
+    // polynomial evaluations computed by the verifier at the query positions.
+    let ldt_verifier = match air.options().ldt() {
+        LdtKind::Fri => {
+            let fri_verifier = FriVerifier::new(
+                &mut channel,
+                &mut public_coin,
+                air.options().to_fri_options(),
+                air.trace_poly_degree(),
+            )
+            .map_err(VerifierError::FriVerificationFailed)?;
+            // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+            LdtVerifier::Fri(fri_verifier)
+        }
+        LdtKind::Stir => {
+            let stir_proof = channel
+                .take_stir_proof()
+                .expect("STIR proof presence is validated by the channel");
+            let stir_verifier =
+                StirVerifier::new(air.options().to_stir_options(), air.trace_poly_degree());
+            stir_verifier
+                .verify(&stir_proof, &mut public_coin)
+                .map_err(VerifierError::FriVerificationFailed)?;
+            LdtVerifier::Stir(stir_verifier, stir_proof)
+        }
+    };
 
     // 5 ----- trace and constraint queries -------------------------------------------------------
     // read proof-of-work nonce sent by the prover and update the public coin with it
+    #[cfg(feature = "transcript")]
+    crypto::transcript::set_phase("query positions");
     let pow_nonce = channel.read_pow_nonce();
     public_coin.reseed_with_int(pow_nonce);
 
@@ -260,8 +523,14 @@ where
     // interactive version of the protocol, the verifier sends these query positions to the prover,
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
-    let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size())
+    let query_positions = air
+        .options()
+        .query_sampling()
+        .draw_positions(
+            &mut public_coin,
+            air.options().num_queries(),
+            air.lde_domain_size(),
+        )
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // read evaluations of trace and constraint composition polynomials at the queried positions;
@@ -272,12 +541,12 @@ where
 
     // 6 ----- DEEP composition -------------------------------------------------------------------
     // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
+    let composer = DeepComposer::new(air, &query_positions, &ood_points, deep_coefficients);
     let t_composition = composer.compose_trace_columns(
         queried_main_trace_states,
         queried_aux_trace_states,
-        ood_main_trace_frame,
-        ood_aux_trace_frame,
+        ood_main_trace_frames,
+        ood_aux_trace_frames,
     );
     let c_composition = composer
         .compose_constraint_evaluations(queried_constraint_evaluations, ood_constraint_evaluations);
@@ -286,7 +555,21 @@ where
     // 7 ----- Verify low-degree proof -------------------------------------------------------------
     // make sure that evaluations of the DEEP composition polynomial we computed in the previous
     // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+    match ldt_verifier {
+        LdtVerifier::Fri(fri_verifier) => fri_verifier
+            .verify(&mut channel, &deep_evaluations, &query_positions)
+            .map_err(VerifierError::FriVerificationFailed)?,
+        LdtVerifier::Stir(stir_verifier, stir_proof) => stir_verifier
+            .verify_evaluations(&stir_proof, &query_positions, &deep_evaluations)
+            .map_err(VerifierError::FriVerificationFailed)?,
+    }
+
+    Ok(query_positions)
+}
+
+/// A low-degree test verifier instantiated during the commit phase of the protocol, which
+/// completes the verification once the DEEP composition polynomial evaluations are computed.
+enum LdtVerifier<F, S> {
+    Fri(F),
+    Stir(S, StirProof),
 }
@@ -0,0 +1,527 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    encode_for_evm, verify, verify_ref, verify_with_metadata, AcceptableOptions, Air, AirContext,
+    Assertion, EvaluationFrame, FieldExtension, LdtKind, ProofMetadata, ProofOptions,
+    SecurityEstimate, Serializable, StarkProof, StarkProofRef, TraceInfo,
+    TransitionConstraintDegree, VerifierError, EVM_ENCODING_VERSION,
+};
+use air::proof::Queries;
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{Prover, Trace, TraceTable};
+
+type Blake3 = Blake3_256<BaseElement>;
+type RandomCoin = DefaultRandomCoin<Blake3>;
+
+const TRACE_LENGTH: usize = 1024;
+
+/// Options accepted by the verifier in tests which do not check the security of proofs.
+const ANY_OPTIONS: AcceptableOptions = AcceptableOptions::MinConjecturedSecurity(0);
+
+// ACCEPTABLE OPTIONS TESTS
+// ================================================================================================
+
+#[test]
+fn acceptable_options_min_conjectured_security() {
+    let options = build_options(FieldExtension::None);
+    let security_level =
+        options.security_level::<BaseElement, Blake3>(TRACE_LENGTH, SecurityEstimate::Conjectured);
+
+    // options are accepted if they provide the required security level
+    let acceptable_options = AcceptableOptions::MinConjecturedSecurity(security_level);
+    assert_eq!(Ok(()), validate(&acceptable_options, &options));
+
+    // and are rejected otherwise
+    let acceptable_options = AcceptableOptions::MinConjecturedSecurity(security_level + 1);
+    assert_eq!(
+        Err(VerifierError::InsufficientConjecturedSecurity(
+            security_level + 1,
+            security_level
+        )),
+        validate(&acceptable_options, &options)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn acceptable_options_min_proven_security() {
+    let options = build_options(FieldExtension::None);
+    let security_level =
+        options.security_level::<BaseElement, Blake3>(TRACE_LENGTH, SecurityEstimate::Proven);
+
+    let acceptable_options = AcceptableOptions::MinProvenSecurity(security_level);
+    assert_eq!(Ok(()), validate(&acceptable_options, &options));
+
+    let acceptable_options = AcceptableOptions::MinProvenSecurity(security_level + 1);
+    assert_eq!(
+        Err(VerifierError::InsufficientProvenSecurity(
+            security_level + 1,
+            security_level
+        )),
+        validate(&acceptable_options, &options)
+    );
+}
+
+#[cfg(not(feature = "std"))]
+#[test]
+fn acceptable_options_min_proven_security() {
+    // proven security cannot be estimated without std, and thus, no options are accepted
+    let options = build_options(FieldExtension::None);
+    let acceptable_options = AcceptableOptions::MinProvenSecurity(0);
+    assert_eq!(
+        Err(VerifierError::ProvenSecurityUnavailable(0)),
+        validate(&acceptable_options, &options)
+    );
+}
+
+#[test]
+fn acceptable_options_option_set() {
+    let options = build_options(FieldExtension::None);
+    let other_options = build_options(FieldExtension::Quadratic);
+
+    let acceptable_options = AcceptableOptions::OptionSet(vec![other_options.clone()]);
+    assert_eq!(
+        Err(VerifierError::UnacceptableProofOptions),
+        validate(&acceptable_options, &options)
+    );
+
+    let acceptable_options = AcceptableOptions::OptionSet(vec![other_options, options.clone()]);
+    assert_eq!(Ok(()), validate(&acceptable_options, &options));
+}
+
+// PROOF METADATA TESTS
+// ================================================================================================
+
+#[test]
+fn verify_proof_metadata() {
+    for bound in [false, true] {
+        let build_metadata = |timestamp: u64| {
+            let metadata = ProofMetadata::new("fib", timestamp).with_entry("job", "42");
+            if bound {
+                metadata.bind()
+            } else {
+                metadata
+            }
+        };
+        let metadata = build_metadata(1_700_000_000);
+
+        let mut prover = FibProver::new(build_fib_options(false));
+        prover.metadata = Some(metadata.clone());
+        let (proof, result) = prove_fib(&prover, 64);
+
+        let verified =
+            verify_with_metadata::<FibAir, Blake3, RandomCoin>(proof.clone(), result, &ANY_OPTIONS);
+        assert_eq!(Ok(Some(metadata)), verified);
+
+        // bound metadata cannot be modified or removed without invalidating the proof, while
+        // unbound metadata is not authenticated by the proof
+        let mut modified_proof = proof.clone();
+        modified_proof.metadata = Some(build_metadata(1_700_000_001));
+        let verified = verify_with_metadata::<FibAir, Blake3, RandomCoin>(
+            modified_proof,
+            result,
+            &ANY_OPTIONS,
+        );
+        assert_eq!(!bound, verified.is_ok());
+
+        let mut stripped_proof = proof;
+        stripped_proof.metadata = None;
+        let verified = verify_with_metadata::<FibAir, Blake3, RandomCoin>(
+            stripped_proof,
+            result,
+            &ANY_OPTIONS,
+        );
+        assert_eq!(!bound, verified.is_ok());
+    }
+}
+
+// SALTED COMMITMENT TESTS
+// ================================================================================================
+
+#[test]
+fn verify_salted_commitments() {
+    let mut prover = FibProver::new(build_fib_options(false));
+    prover.salt_seed = Some([1; 32]);
+    let (proof, result) = prove_fib(&prover, 64);
+
+    // salted proofs are verified in the same way as proofs without salts
+    let proof_bytes = proof.to_bytes();
+    let proof_ref = StarkProofRef::from_bytes(&proof_bytes).unwrap();
+    assert!(verify_ref::<FibAir, Blake3, RandomCoin>(proof_ref, result, &ANY_OPTIONS).is_ok());
+
+    // modified salts invalidate the proof
+    let salts = proof.trace_queries[0].salts_bytes().to_vec();
+    let mut modified_proof = proof.clone();
+    modified_proof.trace_queries[0] = Queries::from_raw_parts_salted(
+        proof.trace_queries[0].values_bytes().to_vec(),
+        proof.trace_queries[0].paths_bytes().to_vec(),
+        salts.iter().map(|b| b ^ 1).collect(),
+    );
+    assert!(verify::<FibAir, Blake3, RandomCoin>(modified_proof, result, &ANY_OPTIONS).is_err());
+
+    // salts cannot be stripped from the proof
+    let mut stripped_proof = proof.clone();
+    stripped_proof.constraint_queries = Queries::from_raw_parts(
+        proof.constraint_queries.values_bytes().to_vec(),
+        proof.constraint_queries.paths_bytes().to_vec(),
+    );
+    assert!(verify::<FibAir, Blake3, RandomCoin>(stripped_proof, result, &ANY_OPTIONS).is_err());
+
+    // salted proofs are not supported by the EVM encoding
+    assert!(encode_for_evm::<FibAir, Blake3, RandomCoin>(proof, result).is_err());
+}
+
+// MERKLE MULTI-PROOF TESTS
+// ================================================================================================
+
+#[test]
+fn verify_merkle_multi_proofs() {
+    for use_extension_field in [false, true] {
+        let options = build_fib_options(use_extension_field).with_merkle_multi_proofs(true);
+        let (proof, result) = prove_fib(&FibProver::new(options), 64);
+
+        // the proof survives all encodings
+        let bytes = proof.to_bytes();
+        assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+        let proof_ref = StarkProofRef::from_bytes(&bytes).unwrap();
+        assert!(verify_ref::<FibAir, Blake3, RandomCoin>(proof_ref, result, &ANY_OPTIONS).is_ok());
+
+        // a modified node of a multi-proof invalidates the proof
+        let mut paths = proof.constraint_queries.paths_bytes().to_vec();
+        *paths.last_mut().unwrap() ^= 1;
+        let mut modified_proof = proof.clone();
+        modified_proof.constraint_queries =
+            Queries::from_raw_parts(proof.constraint_queries.values_bytes().to_vec(), paths);
+        assert!(matches!(
+            verify::<FibAir, Blake3, RandomCoin>(modified_proof, result, &ANY_OPTIONS),
+            Err(VerifierError::ConstraintQueryDoesNotMatchCommitment)
+        ));
+
+        // multi-proofs are not supported by the EVM encoding
+        assert!(encode_for_evm::<FibAir, Blake3, RandomCoin>(proof, result).is_err());
+    }
+
+    // salted commitments can be opened with multi-proofs as well
+    let options = build_fib_options(false).with_merkle_multi_proofs(true);
+    let mut prover = FibProver::new(options);
+    prover.salt_seed = Some([1; 32]);
+    let (proof, result) = prove_fib(&prover, 64);
+    assert!(verify::<FibAir, Blake3, RandomCoin>(proof, result, &ANY_OPTIONS).is_ok());
+}
+
+// OUT-OF-DOMAIN FRAME TESTS
+// ================================================================================================
+
+#[test]
+fn verify_multiple_ood_points() {
+    let (single_point_proof, _) = prove_fib(&FibProver::new(build_fib_options(true)), 64);
+    let options = build_fib_options(true).with_num_ood_points(2);
+    let (proof, result) = prove_fib(&FibProver::new(options), 64);
+
+    let wrong_result = result + BaseElement::ONE;
+    assert!(
+        verify::<FibAir, Blake3, RandomCoin>(proof.clone(), wrong_result, &ANY_OPTIONS).is_err()
+    );
+
+    // out-of-domain frames with evaluations for a different number of points are rejected
+    let mut truncated_proof = proof.clone();
+    truncated_proof.ood_frame = single_point_proof.ood_frame;
+    assert!(verify::<FibAir, Blake3, RandomCoin>(truncated_proof, result, &ANY_OPTIONS).is_err());
+
+    // such proofs cannot be encoded for EVM
+    assert!(encode_for_evm::<FibAir, Blake3, RandomCoin>(proof, result).is_err());
+}
+
+// LOW-DEGREE TEST TESTS
+// ================================================================================================
+
+#[test]
+fn verify_fri_layer_grinding() {
+    let options = build_fib_options(false).with_fri_layer_grinding(8);
+    let (mut proof, result) = prove_fib(&FibProver::new(options), 64);
+
+    // a proof with a modified layer nonce is rejected
+    let mut layer_nonces = proof.fri_proof.layer_nonces().to_vec();
+    layer_nonces[0] ^= 1;
+    proof.fri_proof = proof.fri_proof.with_layer_nonces(layer_nonces);
+    assert!(verify::<FibAir, Blake3, RandomCoin>(proof, result, &ANY_OPTIONS).is_err());
+}
+
+#[test]
+fn verify_stir_proof() {
+    for use_extension_field in [false, true] {
+        let options = build_fib_options(use_extension_field).with_ldt(LdtKind::Stir);
+        let prover = FibProver::new(options);
+        let (proof, result) = prove_fib(&prover, 1024);
+
+        // the proof survives all encodings, and is verified against the STIR proof it contains
+        let bytes = proof.to_bytes();
+        assert_eq!(proof, StarkProof::from_cbor(&proof.to_cbor()).unwrap());
+        let proof_ref = StarkProofRef::from_bytes(&bytes).unwrap();
+        assert!(verify_ref::<FibAir, Blake3, RandomCoin>(proof_ref, result, &ANY_OPTIONS).is_ok());
+        let wrong_result = result + BaseElement::ONE;
+        assert!(
+            verify::<FibAir, Blake3, RandomCoin>(proof.clone(), wrong_result, &ANY_OPTIONS)
+                .is_err()
+        );
+
+        // a proof without a STIR proof, or with a STIR proof of another computation, is rejected
+        let mut stripped_proof = proof.clone();
+        stripped_proof.stir_proof = None;
+        assert!(
+            verify::<FibAir, Blake3, RandomCoin>(stripped_proof, result, &ANY_OPTIONS).is_err()
+        );
+        let mut swapped_proof = proof.clone();
+        swapped_proof.stir_proof = prove_fib(&prover, 512).0.stir_proof;
+        assert!(verify::<FibAir, Blake3, RandomCoin>(swapped_proof, result, &ANY_OPTIONS).is_err());
+
+        // such proofs cannot be encoded for EVM
+        assert!(encode_for_evm::<FibAir, Blake3, RandomCoin>(proof, result).is_err());
+    }
+}
+
+// PROOF ENCODING TESTS
+// ================================================================================================
+
+#[test]
+fn verify_borrowed_proof() {
+    for use_extension_field in [false, true] {
+        let prover = FibProver::new(build_fib_options(use_extension_field));
+        let (proof, result) = prove_fib(&prover, 64);
+        let proof_bytes = proof.to_bytes();
+
+        let proof = StarkProofRef::from_bytes(&proof_bytes).unwrap();
+        assert!(
+            verify_ref::<FibAir, Blake3, RandomCoin>(proof.clone(), result, &ANY_OPTIONS).is_ok()
+        );
+
+        let wrong_result = result + BaseElement::ONE;
+        assert!(
+            verify_ref::<FibAir, Blake3, RandomCoin>(proof, wrong_result, &ANY_OPTIONS).is_err()
+        );
+    }
+}
+
+#[test]
+fn verify_unused_proof_data_rejected() {
+    let (proof, result) = prove_fib(&FibProver::new(build_fib_options(false)), 64);
+
+    // a FRI layer which is not checked by the verifier cannot be appended to the proof; the FRI
+    // proof is serialized right before the proof-of-work nonce and the metadata presence flag
+    let proof_bytes = proof.to_bytes();
+    let fri_bytes = proof.fri_proof.to_bytes();
+    let fri_start = proof_bytes.len() - 9 - fri_bytes.len();
+    let layers_end = 1 + proof
+        .fri_proof
+        .layers()
+        .iter()
+        .map(|layer| layer.to_bytes().len())
+        .sum::<usize>();
+
+    let mut modified_bytes = proof_bytes[..fri_start].to_vec();
+    modified_bytes.push(fri_bytes[0] + 1);
+    modified_bytes.extend_from_slice(&fri_bytes[1..layers_end]);
+    modified_bytes.extend_from_slice(&proof.fri_proof.layers()[0].to_bytes());
+    modified_bytes.extend_from_slice(&proof_bytes[fri_start + layers_end..]);
+
+    let modified_proof = StarkProof::from_bytes(&modified_bytes).unwrap();
+    assert_eq!(
+        proof.fri_proof.num_layers() + 1,
+        modified_proof.fri_proof.num_layers()
+    );
+    assert!(verify::<FibAir, Blake3, RandomCoin>(modified_proof, result, &ANY_OPTIONS).is_err());
+}
+
+#[test]
+fn encode_proof_for_evm() {
+    for use_extension_field in [false, true] {
+        let prover = FibProver::new(build_fib_options(use_extension_field));
+        let (proof, result) = prove_fib(&prover, 64);
+        let encoded = encode_for_evm::<FibAir, Blake3, RandomCoin>(proof.clone(), result).unwrap();
+
+        // check the header
+        let word = |idx: usize| {
+            let word = &encoded[idx * 32..(idx + 1) * 32];
+            assert!(word[..24].iter().all(|&b| b == 0));
+            u64::from_be_bytes(word[24..].try_into().unwrap()) as usize
+        };
+        assert_eq!(EVM_ENCODING_VERSION as usize, word(0));
+        assert_eq!(32, word(1));
+        assert_eq!(256, word(2));
+        assert_eq!(28, word(3));
+        assert_eq!(1 + use_extension_field as usize, word(6));
+        assert_eq!(1, word(8));
+        assert_eq!(proof.pow_nonce as usize, word(13));
+
+        // sections follow each other and cover the rest of the encoding
+        let mut offset = (14 + 9) * 32;
+        let mut section_lengths = Vec::new();
+        for section_idx in 0..9 {
+            assert_eq!(offset, word(14 + section_idx));
+            let length = word(offset / 32);
+            section_lengths.push(length);
+            offset += (length + 1) * 32;
+        }
+        assert_eq!(encoded.len(), offset);
+
+        // one trace commitment, one constraint commitment, and two FRI commitments
+        assert_eq!(&[1, 1, 2], &section_lengths[..3]);
+
+        // query positions are all in the LDE domain, and query data is grouped per position
+        let positions_offset = word(14 + 5) / 32 + 1;
+        assert_eq!(28, section_lengths[5]);
+        assert!((0..28).all(|i| word(positions_offset + i) < 256));
+        assert_eq!(0, section_lengths[6] % 28);
+
+        // proofs which are not valid for the specified public inputs cannot be encoded
+        let wrong_result = result + BaseElement::ONE;
+        assert!(encode_for_evm::<FibAir, Blake3, RandomCoin>(proof, wrong_result).is_err());
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options(field_extension: FieldExtension) -> ProofOptions {
+    ProofOptions::new(28, 8, 16, field_extension, 4, 7)
+}
+
+fn validate(
+    acceptable_options: &AcceptableOptions,
+    options: &ProofOptions,
+) -> Result<(), VerifierError> {
+    acceptable_options.validate::<BaseElement, Blake3>(options, TRACE_LENGTH)
+}
+
+fn build_fib_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 7)
+}
+
+/// Proves computation of the specified number of Fibonacci terms, checks that the proof is
+/// accepted by the verifier, and returns the proof together with the last computed term.
+fn prove_fib(prover: &FibProver, sequence_length: usize) -> (StarkProof, BaseElement) {
+    let trace = prover.build_trace(sequence_length);
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert_eq!(
+        Ok(()),
+        verify::<FibAir, Blake3, RandomCoin>(proof.clone(), result, &ANY_OPTIONS)
+    );
+    (proof, result)
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for computing a Fibonacci sequence such that each row of the trace advances the sequence
+/// by 2 terms; the last computed term is the public input.
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+struct FibProver {
+    options: ProofOptions,
+    metadata: Option<ProofMetadata>,
+    salt_seed: Option<[u8; 32]>,
+}
+
+impl FibProver {
+    fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            metadata: None,
+            salt_seed: None,
+        }
+    }
+
+    fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(2, sequence_length / 2);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+            },
+            |_, state| {
+                state[0] += state[1];
+                state[1] += state[0];
+            },
+        );
+        trace
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type VC = MerkleTree<Blake3>;
+    type RandomCoin = RandomCoin;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn metadata(&self) -> Option<ProofMetadata> {
+        self.metadata.clone()
+    }
+
+    fn commitment_salt_seed(&self) -> Option<[u8; 32]> {
+        self.salt_seed
+    }
+}
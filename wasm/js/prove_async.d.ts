@@ -0,0 +1,25 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+import type {
+  AirId,
+  ProgressCallback,
+  ProofOptionsObject,
+  SerializedProof,
+  SerializedPubInputs,
+  SerializedTrace,
+} from "../pkg/winter_wasm.js";
+
+/**
+ * Generates a proof in a Web Worker; the returned promise resolves to the serialized proof, and
+ * is rejected if the proof could not be generated.
+ */
+export function proveAsync(
+  airId: AirId,
+  trace: SerializedTrace,
+  pubInputs: SerializedPubInputs,
+  options: ProofOptionsObject,
+  onProgress?: ProgressCallback,
+): Promise<SerializedProof>;
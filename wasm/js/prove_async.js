@@ -0,0 +1,37 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// Generates a proof in a Web Worker so that the event loop of the calling thread is not blocked
+// while the proof is generated. The returned promise resolves to the serialized proof, and
+// onProgress (if provided) is invoked with the name of each proving phase and the estimated
+// percentage of completed work when the phase starts.
+//
+// A new worker is created for every proof, and is terminated once the proof is generated; this
+// also releases the memory used by the prover.
+export function proveAsync(airId, trace, pubInputs, options, onProgress) {
+  return new Promise((resolve, reject) => {
+    const worker = new Worker(new URL("./prove_worker.js", import.meta.url), {
+      type: "module",
+    });
+    worker.onmessage = (event) => {
+      const message = event.data;
+      if (message.type === "progress") {
+        onProgress?.(message.phase, message.percent);
+        return;
+      }
+      worker.terminate();
+      if (message.type === "done") {
+        resolve(message.proof);
+      } else {
+        reject(new Error(message.message));
+      }
+    };
+    worker.onerror = (event) => {
+      worker.terminate();
+      reject(new Error(event.message));
+    };
+    worker.postMessage({ airId, trace, pubInputs, options });
+  });
+}
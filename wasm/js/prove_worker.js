@@ -0,0 +1,29 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// Generates a single proof in a dedicated worker using the module built by wasm-pack into the
+// pkg directory (see README.md), and reports the progress of proof generation to the creator
+// of the worker.
+
+import init, { ProofOptions, proveWithProgress } from "../pkg/winter_wasm.js";
+
+const ready = init();
+
+self.onmessage = async (event) => {
+  const { airId, trace, pubInputs, options } = event.data;
+  try {
+    await ready;
+    const proof = proveWithProgress(
+      airId,
+      trace,
+      pubInputs,
+      ProofOptions.fromObject(options),
+      (phase, percent) => self.postMessage({ type: "progress", phase, percent }),
+    );
+    self.postMessage({ type: "done", proof }, [proof.buffer]);
+  } catch (err) {
+    self.postMessage({ type: "error", message: String(err?.message ?? err) });
+  }
+};
@@ -0,0 +1,174 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Conversions between Winterfell types and JavaScript values.
+
+use crate::{build_options, element_to_string, parse_element, typescript::PROOF_OPTION_NAMES};
+use js_sys::{BigInt, Object, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+use winterfell::{
+    math::{
+        fields::{f128, f64},
+        StarkField,
+    },
+    ProofOptions, StarkProof,
+};
+
+// TRAITS
+// ================================================================================================
+
+/// Defines how a value is converted into a JavaScript value.
+pub trait IntoJsValue {
+    /// Converts this value into a JavaScript value.
+    fn into_js_value(self) -> JsValue;
+}
+
+/// Defines how a value is read from a JavaScript value.
+pub trait FromJsValue: Sized {
+    /// Reads a value from the specified JavaScript value.
+    ///
+    /// # Errors
+    /// Returns an error carrying a message which describes why the conversion failed; when
+    /// returned to JavaScript, the error is thrown as an exception.
+    fn from_js_value(value: &JsValue) -> Result<Self, JsError>;
+}
+
+// STARK PROOF
+// ================================================================================================
+
+/// Proofs are represented as `Uint8Array` containing the proof serialized via
+/// [StarkProof::to_bytes()].
+impl IntoJsValue for StarkProof {
+    fn into_js_value(self) -> JsValue {
+        Uint8Array::from(self.to_bytes().as_slice()).into()
+    }
+}
+
+impl FromJsValue for StarkProof {
+    fn from_js_value(value: &JsValue) -> Result<Self, JsError> {
+        let bytes = value
+            .dyn_ref::<Uint8Array>()
+            .ok_or_else(|| JsError::new("proof must be a Uint8Array"))?
+            .to_vec();
+        StarkProof::from_bytes(&bytes)
+            .map_err(|err| JsError::new(&format!("failed to read proof: {err}")))
+    }
+}
+
+// PROOF OPTIONS
+// ================================================================================================
+
+/// Proof options are represented as plain objects with numeric properties named as the
+/// parameters of the `ProofOptions` constructor; field extension is specified by its degree.
+impl IntoJsValue for ProofOptions {
+    fn into_js_value(self) -> JsValue {
+        let values = [
+            self.num_queries() as u32,
+            self.blowup_factor() as u32,
+            self.grinding_factor(),
+            self.field_extension().degree(),
+            self.to_fri_options().folding_factor() as u32,
+            self.to_fri_options().remainder_max_degree() as u32,
+        ];
+        let object = Object::new();
+        for (name, value) in PROOF_OPTION_NAMES.iter().zip(values) {
+            // setting a property on a plain object cannot fail
+            Reflect::set(&object, &JsValue::from_str(name), &JsValue::from(value))
+                .expect("failed to set property");
+        }
+        object.into()
+    }
+}
+
+impl FromJsValue for ProofOptions {
+    fn from_js_value(value: &JsValue) -> Result<Self, JsError> {
+        if !value.is_object() {
+            return Err(JsError::new("proof options must be an object"));
+        }
+        let mut values = [0u32; 6];
+        for (name, value_ref) in PROOF_OPTION_NAMES.iter().zip(values.iter_mut()) {
+            let property = Reflect::get(value, &JsValue::from_str(name))
+                .map_err(|_| JsError::new(&format!("failed to read proof option {name}")))?;
+            *value_ref = property
+                .as_f64()
+                .filter(|v| v.fract() == 0.0 && *v >= 0.0 && *v <= u32::MAX as f64)
+                .ok_or_else(|| {
+                    JsError::new(&format!(
+                        "proof option {name} must be a non-negative integer"
+                    ))
+                })? as u32;
+        }
+        build_options(
+            values[0], values[1], values[2], values[3], values[4], values[5],
+        )
+        .map_err(|err| JsError::new(&format!("invalid proof options: {err}")))
+    }
+}
+
+// FIELD ELEMENTS
+// ================================================================================================
+
+/// Field elements are represented as `BigInt` values; when read from JavaScript values, field
+/// elements can also be specified as safe integer numbers or decimal strings.
+impl IntoJsValue for f128::BaseElement {
+    fn into_js_value(self) -> JsValue {
+        element_into_js_value(self)
+    }
+}
+
+impl FromJsValue for f128::BaseElement {
+    fn from_js_value(value: &JsValue) -> Result<Self, JsError> {
+        element_from_js_value(value)
+    }
+}
+
+impl IntoJsValue for f64::BaseElement {
+    fn into_js_value(self) -> JsValue {
+        element_into_js_value(self)
+    }
+}
+
+impl FromJsValue for f64::BaseElement {
+    fn from_js_value(value: &JsValue) -> Result<Self, JsError> {
+        element_from_js_value(value)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the decimal representation of an integer specified by a JavaScript value which can
+/// be a `BigInt`, a safe integer number, or a string.
+pub(super) fn read_integer_string(value: &JsValue) -> Result<String, JsError> {
+    if let Some(value) = value.as_string() {
+        Ok(value)
+    } else if let Some(value) = value.dyn_ref::<BigInt>() {
+        let value = value
+            .to_string(10)
+            .map_err(|_| JsError::new("failed to convert BigInt to string"))?;
+        Ok(value.into())
+    } else if let Some(value) = value.as_f64() {
+        if value.fract() != 0.0 || value.abs() > 9007199254740991.0 {
+            return Err(JsError::new(&format!("{value} is not a safe integer")));
+        }
+        Ok(format!("{}", value as i64))
+    } else {
+        Err(JsError::new(
+            "value must be a BigInt, a number, or a string",
+        ))
+    }
+}
+
+fn element_into_js_value<B: StarkField>(value: B) -> JsValue {
+    // a decimal representation of an integer is always a valid BigInt
+    BigInt::new(&JsValue::from_str(&element_to_string(value)))
+        .expect("failed to create BigInt")
+        .into()
+}
+
+fn element_from_js_value<B: StarkField>(value: &JsValue) -> Result<B, JsError> {
+    let value = read_integer_string(value)?;
+    parse_element(&value).map_err(|err| JsError::new(&err.to_string()))
+}
@@ -0,0 +1,289 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! JavaScript bindings for the functions defined at the root of this crate.
+
+use super::{StreamingVerifier, VerificationPolicy, VerificationResult, Verifier};
+use wasm_bindgen::{prelude::*, JsCast};
+use winterfell::{ProofOptions, ProvingPhase};
+
+pub mod convert;
+use convert::{read_integer_string, FromJsValue, IntoJsValue};
+
+// TYPESCRIPT DEFINITIONS
+// ================================================================================================
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_DEFINITIONS: &'static str = crate::typescript::TYPESCRIPT_DEFINITIONS;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A plain object describing proof options.
+    #[wasm_bindgen(typescript_type = "ProofOptionsObject")]
+    pub type ProofOptionsObject;
+}
+
+// PROOF OPTIONS
+// ================================================================================================
+
+/// STARK protocol parameters.
+#[wasm_bindgen(js_name = ProofOptions)]
+pub struct JsProofOptions(ProofOptions);
+
+#[wasm_bindgen(js_class = ProofOptions)]
+impl JsProofOptions {
+    /// Returns proof options built from the specified parameters; field extension is specified
+    /// by its degree (i.e., 1, 2, 3, or 4).
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        num_queries: u32,
+        blowup_factor: u32,
+        grinding_factor: u32,
+        #[wasm_bindgen(unchecked_param_type = "FieldExtensionDegree")] field_extension: u32,
+        #[wasm_bindgen(unchecked_param_type = "FriFoldingFactor")] fri_folding_factor: u32,
+        fri_remainder_max_degree: u32,
+    ) -> Result<JsProofOptions, JsError> {
+        let options = super::build_options(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            fri_remainder_max_degree,
+        )
+        .map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(JsProofOptions(options))
+    }
+
+    /// Returns proof options read from a plain object with properties named as the parameters
+    /// of the constructor.
+    #[wasm_bindgen(js_name = fromObject)]
+    pub fn from_object(value: ProofOptionsObject) -> Result<JsProofOptions, JsError> {
+        ProofOptions::from_js_value(&value).map(JsProofOptions)
+    }
+
+    /// Returns a plain object with properties named as the parameters of the constructor.
+    #[wasm_bindgen(js_name = toObject)]
+    pub fn to_object(&self) -> ProofOptionsObject {
+        self.0.clone().into_js_value().unchecked_into()
+    }
+}
+
+// PROVER
+// ================================================================================================
+
+/// Returns identifiers of all registered computations.
+#[wasm_bindgen(js_name = airIds, unchecked_return_type = "AirId[]")]
+pub fn air_ids() -> Vec<JsValue> {
+    super::air_ids()
+        .iter()
+        .map(|id| JsValue::from_str(id))
+        .collect()
+}
+
+/// Returns the number of threads used to generate proofs.
+#[wasm_bindgen(js_name = numThreads)]
+pub fn num_threads() -> usize {
+    super::num_threads()
+}
+
+/// Returns bytes of a proof of the computation registered under `air_id` for the execution
+/// trace serialized in `trace` and public inputs serialized in `pub_inputs`.
+#[wasm_bindgen(unchecked_return_type = "SerializedProof")]
+pub fn prove(
+    #[wasm_bindgen(unchecked_param_type = "AirId")] air_id: &str,
+    #[wasm_bindgen(unchecked_param_type = "SerializedTrace")] trace: &[u8],
+    #[wasm_bindgen(unchecked_param_type = "SerializedPubInputs")] pub_inputs: &[u8],
+    options: &JsProofOptions,
+) -> Result<Vec<u8>, JsError> {
+    super::prove(air_id, trace, pub_inputs, options.0.clone())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Returns bytes of a proof in the same way as `prove()`, but throws without attempting to
+/// generate the proof if proof generation is expected to allocate more than `maxMemory` bytes.
+#[wasm_bindgen(js_name = proveWithMemoryLimit, unchecked_return_type = "SerializedProof")]
+pub fn prove_with_memory_limit(
+    #[wasm_bindgen(unchecked_param_type = "AirId")] air_id: &str,
+    #[wasm_bindgen(unchecked_param_type = "SerializedTrace")] trace: &[u8],
+    #[wasm_bindgen(unchecked_param_type = "SerializedPubInputs")] pub_inputs: &[u8],
+    options: &JsProofOptions,
+    max_memory: f64,
+) -> Result<Vec<u8>, JsError> {
+    if !(max_memory.is_finite() && max_memory >= 0.0) {
+        return Err(JsError::new(&format!("invalid memory limit {max_memory}")));
+    }
+    // the conversion saturates at u64::MAX for limits which are too large
+    let max_memory = max_memory as u64;
+    super::prove_with_memory_limit(air_id, trace, pub_inputs, options.0.clone(), max_memory)
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Returns bytes of a proof in the same way as `prove()`, and invokes `on_progress` with the
+/// name of each proving phase and the estimated percentage of completed work when the phase
+/// starts; exceptions thrown by `on_progress` are ignored.
+#[wasm_bindgen(js_name = proveWithProgress, unchecked_return_type = "SerializedProof")]
+pub fn prove_with_progress(
+    #[wasm_bindgen(unchecked_param_type = "AirId")] air_id: &str,
+    #[wasm_bindgen(unchecked_param_type = "SerializedTrace")] trace: &[u8],
+    #[wasm_bindgen(unchecked_param_type = "SerializedPubInputs")] pub_inputs: &[u8],
+    options: &JsProofOptions,
+    #[wasm_bindgen(unchecked_param_type = "ProgressCallback")] on_progress: &js_sys::Function,
+) -> Result<Vec<u8>, JsError> {
+    let report = |phase: ProvingPhase, percent: u8| {
+        let phase = JsValue::from_str(phase.as_str());
+        let _ = on_progress.call2(&JsValue::NULL, &phase, &JsValue::from(percent));
+    };
+    super::prove_with_progress(air_id, trace, pub_inputs, options.0.clone(), report)
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Returns the amount of memory (in bytes) which can still be allocated by the module.
+#[wasm_bindgen(js_name = availableMemory)]
+pub fn available_memory() -> f64 {
+    super::available_memory() as f64
+}
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Returns serialized public inputs of the example computation registered under `air_id` given
+/// the last term of the computed sequence as a `BigInt`, a number, or a decimal string.
+#[wasm_bindgen(js_name = encodePubInputs, unchecked_return_type = "SerializedPubInputs")]
+pub fn encode_pub_inputs(
+    #[wasm_bindgen(unchecked_param_type = "AirId")] air_id: &str,
+    #[wasm_bindgen(unchecked_param_type = "ExamplePubInputs")] value: JsValue,
+) -> Result<Vec<u8>, JsError> {
+    let value = read_integer_string(&value)?;
+    super::encode_example_pub_inputs(air_id, &value).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Returns the last term of the sequence computed by the example registered under `air_id` as
+/// a `BigInt` given serialized public inputs of the example.
+#[wasm_bindgen(js_name = decodePubInputs, unchecked_return_type = "bigint")]
+pub fn decode_pub_inputs(
+    #[wasm_bindgen(unchecked_param_type = "AirId")] air_id: &str,
+    #[wasm_bindgen(unchecked_param_type = "SerializedPubInputs")] pub_inputs: &[u8],
+) -> Result<JsValue, JsError> {
+    let value = super::decode_example_pub_inputs(air_id, pub_inputs)
+        .map_err(|err| JsError::new(&err.to_string()))?;
+    js_sys::BigInt::new(&JsValue::from_str(&value))
+        .map(JsValue::from)
+        .map_err(|_| JsError::new("failed to create BigInt"))
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifier of proofs of a registered computation.
+#[wasm_bindgen(js_name = Verifier)]
+pub struct JsVerifier(Verifier);
+
+#[wasm_bindgen(js_class = Verifier)]
+impl JsVerifier {
+    /// Returns a verifier for proofs of the computation registered under `air_id` which accepts
+    /// proofs with conjectured security level of at least `min_security_level` bits generated
+    /// using either any options (when `allowed_options` is empty) or one of `allowed_options`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        #[wasm_bindgen(unchecked_param_type = "AirId")] air_id: &str,
+        min_security_level: u32,
+        allowed_options: Vec<JsProofOptions>,
+    ) -> Result<JsVerifier, JsError> {
+        let policy = allowed_options.into_iter().fold(
+            VerificationPolicy::new(min_security_level),
+            |policy, options| policy.with_allowed_options(options.0),
+        );
+        let verifier =
+            Verifier::new(air_id, policy).map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(JsVerifier(verifier))
+    }
+
+    /// Verifies the proof serialized in `proof` against public inputs serialized in
+    /// `pub_inputs`.
+    pub fn verify(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "SerializedProof")] proof: &[u8],
+        #[wasm_bindgen(unchecked_param_type = "SerializedPubInputs")] pub_inputs: &[u8],
+    ) -> JsVerificationResult {
+        JsVerificationResult(self.0.verify(proof, pub_inputs))
+    }
+
+    /// Returns a streaming verifier which verifies a proof received in chunks using this
+    /// verifier.
+    pub fn stream(&self) -> JsStreamingVerifier {
+        JsStreamingVerifier(self.0.stream())
+    }
+}
+
+/// Verifier of a proof received in chunks.
+#[wasm_bindgen(js_name = StreamingVerifier)]
+pub struct JsStreamingVerifier(StreamingVerifier);
+
+#[wasm_bindgen(js_class = StreamingVerifier)]
+impl JsStreamingVerifier {
+    /// Appends `chunk` to the proof received so far; returns false if the proof has already been
+    /// rejected, in which case the remaining chunks do not need to be fed into the verifier.
+    pub fn feed(&mut self, chunk: &[u8]) -> bool {
+        self.0.feed(chunk)
+    }
+
+    /// Number of proof bytes received so far.
+    #[wasm_bindgen(getter, js_name = receivedBytes)]
+    pub fn received_bytes(&self) -> usize {
+        self.0.num_received_bytes()
+    }
+
+    /// Result describing why the proof has been rejected before it was received in full, if
+    /// it has been rejected.
+    #[wasm_bindgen(getter)]
+    pub fn rejection(&self) -> Option<JsVerificationResult> {
+        self.0.rejection().cloned().map(JsVerificationResult)
+    }
+
+    /// Verifies the received proof against public inputs serialized in `pub_inputs`; the
+    /// streaming verifier cannot be used afterwards.
+    pub fn finalize(
+        self,
+        #[wasm_bindgen(unchecked_param_type = "SerializedPubInputs")] pub_inputs: &[u8],
+    ) -> JsVerificationResult {
+        JsVerificationResult(self.0.finalize(pub_inputs))
+    }
+}
+
+/// Outcome of verifying a proof.
+#[wasm_bindgen(js_name = VerificationResult)]
+pub struct JsVerificationResult(VerificationResult);
+
+#[wasm_bindgen(js_class = VerificationResult)]
+impl JsVerificationResult {
+    /// True if the proof was valid and acceptable to the verifier.
+    #[wasm_bindgen(getter)]
+    pub fn ok(&self) -> bool {
+        self.0.is_ok()
+    }
+
+    /// Conjectured security level of the proof (in bits).
+    #[wasm_bindgen(getter, js_name = securityLevel)]
+    pub fn security_level(&self) -> u32 {
+        self.0.security_level()
+    }
+
+    /// Kind of the error due to which the proof was rejected, if any.
+    #[wasm_bindgen(
+        getter,
+        js_name = errorKind,
+        unchecked_return_type = "VerificationErrorKind | undefined"
+    )]
+    pub fn error_kind(&self) -> Option<String> {
+        self.0.error_kind().map(|kind| kind.as_str().to_string())
+    }
+
+    /// Message describing the error due to which the proof was rejected, if any.
+    #[wasm_bindgen(getter, js_name = errorMessage)]
+    pub fn error_message(&self) -> Option<String> {
+        self.0.error_message().map(String::from)
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use examples::registry::EXAMPLE_AIR_IDS;
+use winterfell::{
+    math::{
+        fields::{f128, f64},
+        StarkField,
+    },
+    registry::RegistryError,
+    Deserializable, DeserializationError, Serializable,
+};
+
+// FIELD ELEMENTS
+// ================================================================================================
+
+/// Returns the decimal representation of the integer value of the specified field element.
+///
+/// # Panics
+/// Panics if elements of the field are more than 16 bytes long.
+pub fn element_to_string<B: StarkField>(value: B) -> String {
+    assert!(
+        B::ELEMENT_BYTES <= 16,
+        "field elements longer than 16 bytes are not supported"
+    );
+    let mut bytes = [0u8; 16];
+    bytes[..B::ELEMENT_BYTES].copy_from_slice(&value.to_bytes());
+    u128::from_le_bytes(bytes).to_string()
+}
+
+/// Returns a field element with the integer value specified by its decimal representation.
+///
+/// # Errors
+/// Returns an error if `value` is not a decimal representation of an integer smaller than the
+/// field modulus, or if elements of the field are more than 16 bytes long.
+pub fn parse_element<B: StarkField>(value: &str) -> Result<B, DeserializationError> {
+    if B::ELEMENT_BYTES > 16 {
+        return Err(DeserializationError::InvalidValue(
+            "field elements longer than 16 bytes are not supported".to_string(),
+        ));
+    }
+    let int = value.parse::<u128>().map_err(|_| {
+        DeserializationError::InvalidValue(format!("{value} is not a valid field element"))
+    })?;
+    let bytes = int.to_le_bytes();
+    if bytes[B::ELEMENT_BYTES..].iter().any(|&byte| byte != 0) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "invalid field element: value {value} is greater than or equal to the field modulus"
+        )));
+    }
+    B::read_from_bytes(&bytes[..B::ELEMENT_BYTES])
+}
+
+// EXAMPLE PUBLIC INPUTS
+// ================================================================================================
+
+/// Returns serialized public inputs of the example computation registered under `air_id` given
+/// the decimal representation of the last term of the computed sequence.
+///
+/// # Errors
+/// Returns an error if `air_id` is not an identifier of one of the examples (see
+/// [EXAMPLE_AIR_IDS]), or if `value` is not a valid element of the field of the example.
+pub fn encode_example_pub_inputs(air_id: &str, value: &str) -> Result<Vec<u8>, RegistryError> {
+    match get_example_field(air_id)? {
+        ExampleField::F64 => parse_element::<f64::BaseElement>(value).map(|e| e.to_bytes()),
+        ExampleField::F128 => parse_element::<f128::BaseElement>(value).map(|e| e.to_bytes()),
+    }
+    .map_err(RegistryError::InvalidPublicInputs)
+}
+
+/// Returns the decimal representation of the last term of the sequence computed by the example
+/// registered under `air_id` given serialized public inputs of the example.
+///
+/// # Errors
+/// Returns an error if `air_id` is not an identifier of one of the examples (see
+/// [EXAMPLE_AIR_IDS]), or if `pub_inputs` could not be deserialized.
+pub fn decode_example_pub_inputs(air_id: &str, pub_inputs: &[u8]) -> Result<String, RegistryError> {
+    match get_example_field(air_id)? {
+        ExampleField::F64 => f64::BaseElement::read_from_bytes(pub_inputs).map(element_to_string),
+        ExampleField::F128 => f128::BaseElement::read_from_bytes(pub_inputs).map(element_to_string),
+    }
+    .map_err(RegistryError::InvalidPublicInputs)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Fields in which the example computations are defined.
+enum ExampleField {
+    F64,
+    F128,
+}
+
+fn get_example_field(air_id: &str) -> Result<ExampleField, RegistryError> {
+    match air_id {
+        "fib-small" => Ok(ExampleField::F64),
+        _ if EXAMPLE_AIR_IDS.contains(&air_id) => Ok(ExampleField::F128),
+        _ => Err(RegistryError::UnknownAir(air_id.to_string())),
+    }
+}
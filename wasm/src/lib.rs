@@ -0,0 +1,307 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains WebAssembly bindings for Winterfell STARK prover.
+//!
+//! Proofs are generated for computations registered in a global [AirRegistry] of the module.
+//! The Fibonacci examples from the `examples` crate are registered by default (see
+//! [examples::registry::EXAMPLE_AIR_IDS]); other computations can be registered via
+//! [register_air()] and [register_air_handler()] functions. Proofs of registered computations
+//! can be verified via a [Verifier] which also checks that the proofs are acceptable under a
+//! [VerificationPolicy]; proofs received in chunks can be verified via a [StreamingVerifier]
+//! without buffering them outside of the module.
+//!
+//! Public inputs of the examples are field elements; they can be converted to and from their
+//! decimal representations via [encode_example_pub_inputs()] and [decode_example_pub_inputs()].
+//! When the crate is compiled for `wasm32` architecture, `IntoJsValue` and `FromJsValue` traits
+//! are also available to convert proofs, proof options, and field elements to and from
+//! JavaScript values.
+//!
+//! The functions defined at the root of this crate are the Rust equivalents of the functions
+//! exported to JavaScript when the crate is compiled for `wasm32` architecture; they are
+//! available on all architectures to simplify testing and reuse by other crates.
+
+use std::sync::Mutex;
+use winterfell::{
+    registry::{AirHandler, AirRegistry, RegistryError},
+    Air, Deserializable, DeserializationError, ProofOptions, Prover, ProverObserver, ProvingPhase,
+    TraceTable,
+};
+
+mod convert;
+pub use convert::{
+    decode_example_pub_inputs, element_to_string, encode_example_pub_inputs, parse_element,
+};
+
+pub mod typescript;
+
+mod verifier;
+pub use verifier::{
+    ErrorKind, StreamingVerifier, VerificationPolicy, VerificationResult, Verifier,
+};
+
+#[cfg(target_arch = "wasm32")]
+mod bindings;
+#[cfg(target_arch = "wasm32")]
+pub use bindings::convert::{FromJsValue, IntoJsValue};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum size (in bytes) of linear memory of a `wasm32` module.
+#[cfg(target_arch = "wasm32")]
+const MAX_WASM_MEMORY: u64 = 1 << 32;
+
+/// Size (in bytes) of a page of linear memory of a WebAssembly module.
+#[cfg(target_arch = "wasm32")]
+const WASM_PAGE_SIZE: u64 = 1 << 16;
+
+// GLOBAL REGISTRY
+// ================================================================================================
+
+/// Computations which can be proven via [prove()] function; the registry is initialized with the
+/// example computations on first access.
+static REGISTRY: Mutex<Option<AirRegistry>> = Mutex::new(None);
+
+/// Registers the computation proven by provers of type `P` under the specified `air_id` in the
+/// global registry of this module.
+///
+/// # Errors
+/// Returns an error if a computation with the same identifier has already been registered.
+pub fn register_air<P, F>(air_id: &str, build_prover: F) -> Result<(), RegistryError>
+where
+    P: Prover<Trace = TraceTable<<P as Prover>::BaseField>> + 'static,
+    <P::Air as Air>::PublicInputs: Deserializable,
+    F: Fn(ProofOptions) -> P + Send + Sync + 'static,
+{
+    with_registry(|registry| registry.register(air_id, build_prover))
+}
+
+/// Registers the computation handled by the specified `handler` under the specified `air_id`
+/// in the global registry of this module.
+///
+/// # Errors
+/// Returns an error if a computation with the same identifier has already been registered.
+pub fn register_air_handler(
+    air_id: &str,
+    handler: Box<dyn AirHandler>,
+) -> Result<(), RegistryError> {
+    with_registry(|registry| registry.register_handler(air_id, handler))
+}
+
+/// Returns identifiers of all computations registered in the global registry of this module.
+pub fn air_ids() -> Vec<String> {
+    with_registry(|registry| registry.air_ids().into_iter().map(String::from).collect())
+}
+
+// PROVING
+// ================================================================================================
+
+/// Returns the number of threads used to generate proofs.
+///
+/// When the crate is compiled with `concurrent` feature, this is the number of threads in the
+/// global [rayon](utils::rayon) thread pool; otherwise, proofs are always generated in a single
+/// thread, and 1 is returned.
+pub fn num_threads() -> usize {
+    #[cfg(feature = "concurrent")]
+    return utils::rayon::current_num_threads();
+
+    #[cfg(not(feature = "concurrent"))]
+    1
+}
+
+/// Returns proof options built from the specified parameters.
+///
+/// Field extension is specified by its degree (i.e., 1, 2, 3, or 4).
+///
+/// # Errors
+/// Returns an error if the parameters do not describe valid proof options; unlike
+/// [ProofOptions::new()], this function does not panic.
+pub fn build_options(
+    num_queries: u32,
+    blowup_factor: u32,
+    grinding_factor: u32,
+    field_extension: u32,
+    fri_folding_factor: u32,
+    fri_remainder_max_degree: u32,
+) -> Result<ProofOptions, DeserializationError> {
+    // all valid parameters fit into a byte, and thus, we can rely on the deserializer to
+    // validate the parameters
+    let params = [
+        num_queries,
+        blowup_factor,
+        grinding_factor,
+        field_extension,
+        fri_folding_factor,
+        fri_remainder_max_degree,
+    ];
+    let mut bytes = Vec::with_capacity(params.len());
+    for param in params {
+        let byte = u8::try_from(param).map_err(|_| {
+            DeserializationError::InvalidValue(format!("invalid proof option value {param}"))
+        })?;
+        bytes.push(byte);
+    }
+    let options = ProofOptions::read_from_bytes(&bytes)?;
+
+    // the deserializer also interprets spare bits of some parameters as flags of proof options
+    // extensions; parameters with such bits set are not valid
+    if plain_params(&options) != params {
+        return Err(DeserializationError::InvalidValue(format!(
+            "invalid proof option values {params:?}"
+        )));
+    }
+    Ok(options)
+}
+
+/// Returns the amount of memory (in bytes) which can still be allocated by this module.
+///
+/// On `wasm32` architecture, this is the difference between the maximum size of linear memory
+/// (4 GB) and its current size; some JavaScript engines limit linear memory to a smaller size,
+/// in which case [prove_with_memory_limit()] should be used instead of [prove()]. On other
+/// architectures, memory is assumed to be unlimited, and [u64::MAX] is returned.
+pub fn available_memory() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    return MAX_WASM_MEMORY - core::arch::wasm32::memory_size::<0>() as u64 * WASM_PAGE_SIZE;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    u64::MAX
+}
+
+/// Generates a proof of the computation registered under `air_id` for the execution trace
+/// serialized in `trace` and public inputs serialized in `pub_inputs`, and returns the
+/// serialized proof.
+///
+/// This is equivalent to [prove_with_memory_limit()] with the limit set to
+/// [available_memory()].
+///
+/// # Errors
+/// Returns an error if the computation has not been registered, if the trace or public inputs
+/// are invalid, if proof generation would exceed available memory, or if the prover failed to
+/// generate the proof.
+pub fn prove(
+    air_id: &str,
+    trace: &[u8],
+    pub_inputs: &[u8],
+    options: ProofOptions,
+) -> Result<Vec<u8>, RegistryError> {
+    prove_with_memory_limit(air_id, trace, pub_inputs, options, available_memory())
+}
+
+/// Generates a proof in the same way as [prove()], but fails without attempting to generate the
+/// proof if proof generation is expected to allocate more than `max_memory` bytes.
+///
+/// Running out of memory aborts a WebAssembly module (usually with a `memory access out of
+/// bounds` or `unreachable` error) and leaves it unusable; this function makes it possible to
+/// reject traces which are too large with a recoverable error instead.
+///
+/// # Errors
+/// Returns an error in the same cases as [prove()], and also if proof generation is expected to
+/// allocate more than `max_memory` bytes.
+pub fn prove_with_memory_limit(
+    air_id: &str,
+    trace: &[u8],
+    pub_inputs: &[u8],
+    options: ProofOptions,
+    max_memory: u64,
+) -> Result<Vec<u8>, RegistryError> {
+    let proof = with_registry(|registry| {
+        registry.prove_with_memory_limit(air_id, trace, pub_inputs, options, max_memory)
+    })?;
+    Ok(proof.to_bytes())
+}
+
+/// Generates a proof in the same way as [prove()], and invokes `on_progress` when each phase of
+/// proof generation starts.
+///
+/// `on_progress` receives the phase which is starting and an estimate of the percentage of
+/// proof generation work completed before the phase (see [phase_progress()]).
+///
+/// # Errors
+/// Returns an error in the same cases as [prove()].
+pub fn prove_with_progress<F>(
+    air_id: &str,
+    trace: &[u8],
+    pub_inputs: &[u8],
+    options: ProofOptions,
+    on_progress: F,
+) -> Result<Vec<u8>, RegistryError>
+where
+    F: FnMut(ProvingPhase, u8),
+{
+    let mut observer = ProgressObserver(on_progress);
+    let max_memory = available_memory();
+    let proof = with_registry(|registry| {
+        registry.prove_with_observer(
+            air_id,
+            trace,
+            pub_inputs,
+            options,
+            Some(max_memory),
+            &mut observer,
+        )
+    })?;
+    Ok(proof.to_bytes())
+}
+
+/// Returns an estimate of the percentage of proof generation work completed before the
+/// specified `phase` starts.
+///
+/// The estimate is based on typical relative durations of proving phases; actual durations
+/// depend on the computation and on proof options.
+pub fn phase_progress(phase: ProvingPhase) -> u8 {
+    match phase {
+        ProvingPhase::TraceCommitment => 0,
+        ProvingPhase::ConstraintEvaluation => 30,
+        ProvingPhase::ConstraintCommitment => 50,
+        ProvingPhase::DeepComposition => 70,
+        ProvingPhase::FriCommitment => 80,
+        ProvingPhase::QueryGeneration => 92,
+        ProvingPhase::ProofAssembly => 97,
+    }
+}
+
+/// A [ProverObserver] which reports the start of each proving phase to a closure.
+struct ProgressObserver<F>(F);
+
+impl<F: FnMut(ProvingPhase, u8)> ProverObserver for ProgressObserver<F> {
+    fn on_phase_start(&mut self, phase: ProvingPhase) {
+        (self.0)(phase, phase_progress(phase))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Invokes `f` with the global registry of this module, initializing the registry with example
+/// computations if needed.
+fn with_registry<T>(f: impl FnOnce(&mut AirRegistry) -> T) -> T {
+    // a panic while the registry is locked cannot leave the registry in an inconsistent state,
+    // and thus, poisoning can be ignored
+    let mut registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+    let registry = registry.get_or_insert_with(|| {
+        let mut registry = AirRegistry::new();
+        examples::registry::register_examples(&mut registry).expect("failed to register examples");
+        registry
+    });
+    f(registry)
+}
+
+/// Returns the parameters of the `ProofOptions` constructor which describe the specified options;
+/// field extension is specified by its degree.
+fn plain_params(options: &ProofOptions) -> [u32; 6] {
+    let fri_options = options.to_fri_options();
+    [
+        options.num_queries() as u32,
+        options.blowup_factor() as u32,
+        options.grinding_factor(),
+        options.field_extension().degree(),
+        fri_options.folding_factor() as u32,
+        fri_options.remainder_max_degree() as u32,
+    ]
+}
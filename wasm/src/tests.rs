@@ -0,0 +1,331 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    air_ids, build_options, decode_example_pub_inputs, element_to_string,
+    encode_example_pub_inputs, num_threads, parse_element, phase_progress, prove,
+    prove_with_memory_limit, prove_with_progress, register_air,
+    typescript::{PROOF_OPTION_NAMES, TYPESCRIPT_DEFINITIONS},
+    ErrorKind, VerificationPolicy, Verifier,
+};
+use examples::{
+    fibonacci::fib2::FibProver,
+    registry::{register_examples, EXAMPLE_AIR_IDS},
+};
+use winterfell::{
+    crypto::hashers::Blake3_256,
+    math::{
+        fields::{f128::BaseElement, f64},
+        StarkField,
+    },
+    registry::{AirRegistry, RegistryError},
+    AcceptableOptions, Deserializable, ProofOptions, Prover, ProvingPhase, Serializable,
+    StarkProof, Trace, TraceTable,
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_example() {
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+
+    let proof = prove("fib", &trace, &pub_inputs, options.clone()).unwrap();
+    let proof = StarkProof::from_bytes(&proof).unwrap();
+
+    let mut registry = AirRegistry::new();
+    register_examples(&mut registry).unwrap();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options]);
+    assert_eq!(
+        Ok(()),
+        registry.verify("fib", proof, &pub_inputs, &acceptable_options)
+    );
+}
+
+#[test]
+fn register_custom_air() {
+    register_air("custom-fib", FibProver::<Blake3>::new).unwrap();
+    assert!(air_ids().iter().any(|id| id == "custom-fib"));
+    assert!(air_ids().iter().any(|id| id == "fib"));
+
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+    assert!(prove("custom-fib", &trace, &pub_inputs, options).is_ok());
+
+    let err = register_air("fib", FibProver::<Blake3>::new).unwrap_err();
+    assert_eq!(RegistryError::DuplicateAir("fib".to_string()), err);
+}
+
+#[test]
+fn prove_invalid_inputs() {
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+
+    let err = prove("unknown", &trace, &pub_inputs, options.clone()).unwrap_err();
+    assert_eq!(RegistryError::UnknownAir("unknown".to_string()), err);
+
+    let err = prove(
+        "fib",
+        &trace[..trace.len() - 1],
+        &pub_inputs,
+        options.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, RegistryError::InvalidExecutionTrace(_)));
+
+    let err = prove("fib", &trace, &pub_inputs[1..], options.clone()).unwrap_err();
+    assert!(matches!(err, RegistryError::InvalidPublicInputs(_)));
+
+    let wrong_inputs = BaseElement::new(42).to_bytes();
+    let err = prove("fib", &trace, &wrong_inputs, options).unwrap_err();
+    assert_eq!(RegistryError::InconsistentPublicInputs, err);
+}
+
+#[test]
+fn prove_with_limited_memory() {
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+
+    let trace_table = TraceTable::<BaseElement>::read_from_bytes(&trace).unwrap();
+    let prover = FibProver::<Blake3>::new(options.clone());
+    let required = prover.estimate_peak_memory(&trace_table);
+
+    // the estimate must account for at least the LDE of the trace
+    let lde_bytes = trace_table.width() * trace_table.length() * options.blowup_factor() * 16;
+    assert!(required > lde_bytes as u64);
+
+    let err = prove_with_memory_limit("fib", &trace, &pub_inputs, options.clone(), required - 1)
+        .unwrap_err();
+    assert_eq!(
+        RegistryError::InsufficientMemory(required, required - 1),
+        err
+    );
+    assert!(prove_with_memory_limit("fib", &trace, &pub_inputs, options, required).is_ok());
+}
+
+#[test]
+fn prove_with_progress_events() {
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+
+    let mut events = Vec::new();
+    let proof = prove_with_progress("fib", &trace, &pub_inputs, options, |phase, percent| {
+        events.push((phase, percent))
+    })
+    .unwrap();
+    let verifier = Verifier::new("fib", VerificationPolicy::new(0)).unwrap();
+    assert!(verifier.verify(&proof, &pub_inputs).is_ok());
+
+    // all phases are reported in order with increasing progress
+    let phases = events.iter().map(|(phase, _)| *phase).collect::<Vec<_>>();
+    assert_eq!(ProvingPhase::ALL.to_vec(), phases);
+    assert_eq!(0, events[0].1);
+    assert!(events.windows(2).all(|pair| pair[0].1 < pair[1].1));
+    assert!(ProvingPhase::ALL
+        .iter()
+        .all(|&phase| phase_progress(phase) < 100));
+}
+
+#[test]
+fn prove_concurrently() {
+    assert!(num_threads() >= 1);
+    #[cfg(not(feature = "concurrent"))]
+    assert_eq!(1, num_threads());
+
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+    let proof = prove("fib", &trace, &pub_inputs, options).unwrap();
+    let verifier = Verifier::new("fib", VerificationPolicy::new(0)).unwrap();
+    assert!(verifier.verify(&proof, &pub_inputs).is_ok());
+}
+
+#[test]
+fn build_invalid_options() {
+    assert!(build_options(32, 3, 0, 1, 4, 7).is_err());
+    assert!(build_options(32, 8, 0, 5, 4, 7).is_err());
+    assert!(build_options(256, 8, 0, 1, 4, 7).is_err());
+    assert!(build_options(0, 8, 0, 1, 4, 7).is_err());
+
+    // values with bits reserved for proof options extensions are rejected as well
+    assert!(build_options(32, 9, 0, 1, 4, 7).is_err());
+    assert!(build_options(32, 8, 0x80, 1, 4, 7).is_err());
+    assert!(build_options(32, 8, 0, 0x11, 4, 7).is_err());
+}
+
+#[test]
+fn verify_example() {
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+    let proof = prove("fib", &trace, &pub_inputs, options.clone()).unwrap();
+
+    let verifier = Verifier::new("fib", VerificationPolicy::new(0)).unwrap();
+    let result = verifier.verify(&proof, &pub_inputs);
+    assert!(result.is_ok());
+    assert_eq!(None, result.error_kind());
+    let security_level = result.security_level();
+    assert!(security_level > 0);
+
+    // proof options allowed by the policy
+    let policy = VerificationPolicy::new(security_level).with_allowed_options(options);
+    let verifier = Verifier::new("fib", policy).unwrap();
+    assert!(verifier.verify(&proof, &pub_inputs).is_ok());
+
+    // proof options not allowed by the policy
+    let other_options = build_options(40, 8, 0, 1, 4, 7).unwrap();
+    let policy = VerificationPolicy::new(0).with_allowed_options(other_options);
+    let result = Verifier::new("fib", policy)
+        .unwrap()
+        .verify(&proof, &pub_inputs);
+    assert_eq!(Some(ErrorKind::UnacceptableOptions), result.error_kind());
+    assert_eq!(security_level, result.security_level());
+
+    // insufficient security level
+    let policy = VerificationPolicy::new(security_level + 1);
+    let result = Verifier::new("fib", policy)
+        .unwrap()
+        .verify(&proof, &pub_inputs);
+    assert!(!result.is_ok());
+    assert_eq!(Some(ErrorKind::InsufficientSecurity), result.error_kind());
+}
+
+#[test]
+fn verify_streamed_proof() {
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+    let proof = prove("fib", &trace, &pub_inputs, options).unwrap();
+
+    let verifier = Verifier::new("fib", VerificationPolicy::new(0)).unwrap();
+    let mut stream = verifier.stream();
+    for chunk in proof.chunks(100) {
+        assert!(stream.feed(chunk));
+    }
+    assert_eq!(proof.len(), stream.num_received_bytes());
+    assert_eq!(None, stream.rejection());
+    let result = stream.finalize(&pub_inputs);
+    assert_eq!(verifier.verify(&proof, &pub_inputs), result);
+    assert!(result.is_ok());
+
+    // proofs which are not acceptable are rejected as soon as their header is received
+    let policy = VerificationPolicy::new(result.security_level() + 1);
+    let mut stream = Verifier::new("fib", policy).unwrap().stream();
+    let num_accepted_chunks = proof
+        .chunks(100)
+        .take_while(|chunk| stream.feed(chunk))
+        .count();
+    assert!(num_accepted_chunks < proof.len() / 100);
+    assert_eq!(0, stream.num_received_bytes());
+    let rejection = stream.rejection().cloned().unwrap();
+    assert_eq!(
+        Some(ErrorKind::InsufficientSecurity),
+        rejection.error_kind()
+    );
+    assert_eq!(rejection, stream.finalize(&pub_inputs));
+
+    // truncated proofs are rejected when the stream is finalized
+    let mut stream = verifier.stream();
+    assert!(stream.feed(&proof[..proof.len() - 1]));
+    let result = stream.finalize(&pub_inputs);
+    assert_eq!(Some(ErrorKind::InvalidProof), result.error_kind());
+}
+
+#[test]
+fn verify_invalid_proof() {
+    let options = build_test_options();
+    let (trace, pub_inputs) = build_fib_inputs(&options);
+    let proof = prove("fib", &trace, &pub_inputs, options).unwrap();
+    let verifier = Verifier::new("fib", VerificationPolicy::new(0)).unwrap();
+
+    let result = verifier.verify(&proof[..proof.len() - 1], &pub_inputs);
+    assert_eq!(Some(ErrorKind::InvalidProof), result.error_kind());
+    assert_eq!(0, result.security_level());
+    assert!(result.error_message().is_some());
+
+    let result = verifier.verify(&proof, &pub_inputs[1..]);
+    assert_eq!(Some(ErrorKind::InvalidPublicInputs), result.error_kind());
+
+    let wrong_inputs = BaseElement::new(42).to_bytes();
+    let result = verifier.verify(&proof, &wrong_inputs);
+    assert_eq!(Some(ErrorKind::VerificationFailed), result.error_kind());
+
+    let err = Verifier::new("unknown", VerificationPolicy::new(0)).unwrap_err();
+    assert_eq!(RegistryError::UnknownAir("unknown".to_string()), err);
+}
+
+#[test]
+fn convert_elements() {
+    let value = BaseElement::new(BaseElement::MODULUS - 1);
+    let string = element_to_string(value);
+    assert_eq!((BaseElement::MODULUS - 1).to_string(), string);
+    assert_eq!(Ok(value), parse_element::<BaseElement>(&string));
+
+    let value = f64::BaseElement::new(12345);
+    assert_eq!("12345", element_to_string(value));
+    assert_eq!(Ok(value), parse_element::<f64::BaseElement>("12345"));
+
+    assert!(parse_element::<BaseElement>(&BaseElement::MODULUS.to_string()).is_err());
+    assert!(parse_element::<f64::BaseElement>(&f64::BaseElement::MODULUS.to_string()).is_err());
+    assert!(parse_element::<f64::BaseElement>(&u128::MAX.to_string()).is_err());
+    assert!(parse_element::<BaseElement>("-1").is_err());
+    assert!(parse_element::<BaseElement>("0x10").is_err());
+    assert!(parse_element::<BaseElement>("").is_err());
+}
+
+#[test]
+fn convert_example_pub_inputs() {
+    let options = build_test_options();
+    let (_, pub_inputs) = build_fib_inputs(&options);
+
+    let value = decode_example_pub_inputs("fib", &pub_inputs).unwrap();
+    assert_eq!(
+        Ok(pub_inputs.clone()),
+        encode_example_pub_inputs("fib", &value)
+    );
+
+    let bytes = encode_example_pub_inputs("fib-small", "42").unwrap();
+    assert_eq!(f64::BaseElement::new(42).to_bytes(), bytes);
+    assert_eq!(
+        Ok("42".to_string()),
+        decode_example_pub_inputs("fib-small", &bytes)
+    );
+
+    let err = encode_example_pub_inputs("unknown", "42").unwrap_err();
+    assert_eq!(RegistryError::UnknownAir("unknown".to_string()), err);
+    let err = encode_example_pub_inputs("fib-small", &u64::MAX.to_string()).unwrap_err();
+    assert!(matches!(err, RegistryError::InvalidPublicInputs(_)));
+    let err = decode_example_pub_inputs("fib", &pub_inputs[1..]).unwrap_err();
+    assert!(matches!(err, RegistryError::InvalidPublicInputs(_)));
+}
+
+#[test]
+fn typescript_definitions() {
+    for air_id in EXAMPLE_AIR_IDS {
+        assert!(TYPESCRIPT_DEFINITIONS.contains(&format!("\"{air_id}\"")));
+    }
+    for kind in ErrorKind::ALL {
+        assert!(TYPESCRIPT_DEFINITIONS.contains(&format!("\"{kind}\"")));
+    }
+    for phase in ProvingPhase::ALL {
+        assert!(TYPESCRIPT_DEFINITIONS.contains(&format!("\"{phase}\"")));
+    }
+    for name in PROOF_OPTION_NAMES {
+        assert!(TYPESCRIPT_DEFINITIONS.contains(&format!("  {name}: ")));
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_test_options() -> ProofOptions {
+    build_options(32, 8, 0, 1, 4, 7).unwrap()
+}
+
+fn build_fib_inputs(options: &ProofOptions) -> (Vec<u8>, Vec<u8>) {
+    let trace: TraceTable<BaseElement> = FibProver::<Blake3>::new(options.clone()).build_trace(64);
+    let result = trace.get(1, trace.length() - 1);
+    (trace.to_bytes(), result.to_bytes())
+}
@@ -0,0 +1,86 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! TypeScript definitions of the values accepted and returned by the JavaScript bindings.
+//!
+//! wasm-bindgen declares parameters and return values which are not exported Rust types as
+//! `any`; the types defined here are appended to the generated `.d.ts` file and are referenced
+//! by the bindings instead. The definitions are checked against the corresponding Rust types by
+//! the tests of this crate.
+
+/// Names of the properties of JavaScript objects representing proof options, in the order of
+/// parameters of [build_options()](crate::build_options).
+pub const PROOF_OPTION_NAMES: [&str; 6] = [
+    "numQueries",
+    "blowupFactor",
+    "grindingFactor",
+    "fieldExtension",
+    "friFoldingFactor",
+    "friRemainderMaxDegree",
+];
+
+/// TypeScript definitions appended to the bindings generated by wasm-bindgen.
+pub const TYPESCRIPT_DEFINITIONS: &str = r#"
+/**
+ * Identifier of a registered computation; the examples are registered by default, and other
+ * computations can be registered by crates which embed the bindings.
+ */
+export type AirId = "fib" | "fib8" | "fib-small" | "mulfib" | "mulfib8" | (string & {});
+
+/** Degree of the field extension used for random values drawn by the verifier. */
+export type FieldExtensionDegree = 1 | 2 | 3 | 4;
+
+/** Factor by which the degree of a polynomial is reduced with each FRI layer. */
+export type FriFoldingFactor = 2 | 4 | 8 | 16;
+
+/** STARK protocol parameters represented as a plain object. */
+export interface ProofOptionsObject {
+  numQueries: number;
+  blowupFactor: number;
+  grindingFactor: number;
+  fieldExtension: FieldExtensionDegree;
+  friFoldingFactor: FriFoldingFactor;
+  friRemainderMaxDegree: number;
+}
+
+/** Execution trace serialized via `TraceTable::to_bytes()`. */
+export type SerializedTrace = Uint8Array;
+
+/**
+ * Public inputs of a computation serialized via `Serializable::to_bytes()`; use
+ * `encodePubInputs()` to build public inputs of the examples.
+ */
+export type SerializedPubInputs = Uint8Array & { readonly __kind?: "SerializedPubInputs" };
+
+/** STARK proof serialized via `StarkProof::to_bytes()`. */
+export type SerializedProof = Uint8Array & { readonly __kind?: "SerializedProof" };
+
+/** Public inputs of the examples: the last term of the computed sequence. */
+export type ExamplePubInputs = bigint | number | string;
+
+/** Phase of proof generation reported to progress callbacks. */
+export type ProvingPhase =
+  | "TraceCommitment"
+  | "ConstraintEvaluation"
+  | "ConstraintCommitment"
+  | "DeepComposition"
+  | "FriCommitment"
+  | "QueryGeneration"
+  | "ProofAssembly";
+
+/**
+ * Callback invoked when a phase of proof generation starts; `percent` is an estimate of the
+ * percentage of work completed before the phase.
+ */
+export type ProgressCallback = (phase: ProvingPhase, percent: number) => void;
+
+/** Reason for which a proof was rejected by a `Verifier`. */
+export type VerificationErrorKind =
+  | "InvalidProof"
+  | "InvalidPublicInputs"
+  | "UnacceptableOptions"
+  | "InsufficientSecurity"
+  | "VerificationFailed";
+"#;
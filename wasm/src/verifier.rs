@@ -0,0 +1,360 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::with_registry;
+use core::fmt;
+use winterfell::{
+    registry::RegistryError, AcceptableOptions, DeserializationError, ProofOptions, StarkProof,
+};
+
+// VERIFICATION POLICY
+// ================================================================================================
+
+/// Defines which proofs are acceptable to a [Verifier] regardless of whether they are valid.
+///
+/// A proof is acceptable if its conjectured security level is at least the specified minimum,
+/// and, if a set of allowed proof options is specified, the proof was generated using one of the
+/// allowed options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationPolicy {
+    min_security_level: u32,
+    allowed_options: Vec<ProofOptions>,
+}
+
+impl VerificationPolicy {
+    /// Returns a new policy which accepts proofs with conjectured security level of at least
+    /// `min_security_level` bits generated using any proof options.
+    pub fn new(min_security_level: u32) -> Self {
+        VerificationPolicy {
+            min_security_level,
+            allowed_options: Vec::new(),
+        }
+    }
+
+    /// Adds `options` to the set of options with which acceptable proofs can be generated and
+    /// returns the updated policy.
+    ///
+    /// Once at least one set of proof options has been allowed, proofs generated using any other
+    /// options are rejected.
+    pub fn with_allowed_options(mut self, options: ProofOptions) -> Self {
+        self.allowed_options.push(options);
+        self
+    }
+
+    /// Returns the minimum conjectured security level (in bits) of acceptable proofs.
+    pub fn min_security_level(&self) -> u32 {
+        self.min_security_level
+    }
+
+    /// Returns proof options with which acceptable proofs can be generated; an empty list means
+    /// that proofs generated using any options are acceptable.
+    pub fn allowed_options(&self) -> &[ProofOptions] {
+        &self.allowed_options
+    }
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies serialized proofs of a specific computation registered in the global registry of
+/// this module against a [VerificationPolicy].
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    air_id: String,
+    policy: VerificationPolicy,
+}
+
+impl Verifier {
+    /// Returns a new verifier for proofs of the computation registered under `air_id`.
+    ///
+    /// # Errors
+    /// Returns an error if no computation has been registered under `air_id`.
+    pub fn new(air_id: &str, policy: VerificationPolicy) -> Result<Self, RegistryError> {
+        if !with_registry(|registry| registry.contains(air_id)) {
+            return Err(RegistryError::UnknownAir(air_id.to_string()));
+        }
+        Ok(Verifier {
+            air_id: air_id.to_string(),
+            policy,
+        })
+    }
+
+    /// Returns the identifier of the computation verified by this verifier.
+    pub fn air_id(&self) -> &str {
+        &self.air_id
+    }
+
+    /// Returns the policy against which proofs are checked by this verifier.
+    pub fn policy(&self) -> &VerificationPolicy {
+        &self.policy
+    }
+
+    /// Verifies the proof serialized in `proof` against public inputs serialized in
+    /// `pub_inputs`.
+    ///
+    /// Unlike most verification functions in Winterfell, this function does not return an error;
+    /// instead, the outcome of the verification is described by the returned result.
+    pub fn verify(&self, proof: &[u8], pub_inputs: &[u8]) -> VerificationResult {
+        let proof = match StarkProof::from_bytes(proof) {
+            Ok(proof) => proof,
+            Err(err) => return VerificationResult::rejected(0, ErrorKind::InvalidProof, err),
+        };
+
+        with_registry(|registry| {
+            // the computation was registered when the verifier was created, and computations
+            // cannot be removed from the registry
+            let security_level = registry
+                .security_level(&self.air_id, &proof)
+                .expect("computation not registered");
+            if let Some(rejection) = self.check_policy(proof.options(), security_level) {
+                return rejection;
+            }
+
+            let acceptable_options =
+                AcceptableOptions::MinConjecturedSecurity(self.policy.min_security_level());
+            match registry.verify(&self.air_id, proof, pub_inputs, &acceptable_options) {
+                Ok(()) => VerificationResult::accepted(security_level),
+                Err(err) => {
+                    let kind = match err {
+                        RegistryError::InvalidPublicInputs(_) => ErrorKind::InvalidPublicInputs,
+                        _ => ErrorKind::VerificationFailed,
+                    };
+                    VerificationResult::rejected(security_level, kind, err)
+                }
+            }
+        })
+    }
+
+    /// Returns a [StreamingVerifier] which verifies a proof received in chunks using this
+    /// verifier.
+    pub fn stream(&self) -> StreamingVerifier {
+        StreamingVerifier {
+            verifier: self.clone(),
+            buffer: Vec::new(),
+            header_checked: false,
+            rejection: None,
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a rejection if a proof generated with the specified `options` and having the
+    /// specified `security_level` is not acceptable under the policy of this verifier.
+    fn check_policy(
+        &self,
+        options: &ProofOptions,
+        security_level: u32,
+    ) -> Option<VerificationResult> {
+        let allowed_options = self.policy.allowed_options();
+        if !allowed_options.is_empty() && !allowed_options.contains(options) {
+            return Some(VerificationResult::rejected(
+                security_level,
+                ErrorKind::UnacceptableOptions,
+                "proof options are not allowed by the verification policy",
+            ));
+        }
+        if security_level < self.policy.min_security_level() {
+            return Some(VerificationResult::rejected(
+                security_level,
+                ErrorKind::InsufficientSecurity,
+                format!(
+                    "security level of {security_level} bits is below the minimum of {} bits",
+                    self.policy.min_security_level()
+                ),
+            ));
+        }
+        None
+    }
+}
+
+// STREAMING VERIFIER
+// ================================================================================================
+
+/// Verifies a serialized proof which is received in chunks (e.g., while it is being downloaded).
+///
+/// Chunks are appended to a buffer owned by the verifier, and thus, the caller does not need to
+/// keep received chunks. As soon as the header of the proof has been received, the proof is
+/// checked against the [VerificationPolicy] of the verifier; a proof which is not acceptable
+/// under the policy is rejected before the rest of the proof is received. Once all chunks have
+/// been received, the proof is verified via [finalize()](StreamingVerifier::finalize).
+#[derive(Debug, Clone)]
+pub struct StreamingVerifier {
+    verifier: Verifier,
+    buffer: Vec<u8>,
+    header_checked: bool,
+    rejection: Option<VerificationResult>,
+}
+
+impl StreamingVerifier {
+    /// Appends `chunk` to the proof received so far.
+    ///
+    /// Returns false if the proof has already been rejected (either because it could not be
+    /// deserialized, or because it is not acceptable under the policy of the verifier); in this
+    /// case, the chunk is discarded, and the remaining chunks do not need to be fed into the
+    /// verifier.
+    pub fn feed(&mut self, chunk: &[u8]) -> bool {
+        if self.rejection.is_some() {
+            return false;
+        }
+        self.buffer.extend_from_slice(chunk);
+        if !self.header_checked {
+            self.check_header();
+        }
+        self.rejection.is_none()
+    }
+
+    /// Returns the number of proof bytes received so far.
+    pub fn num_received_bytes(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the result describing why the proof has been rejected before it was received in
+    /// full, if it has been rejected.
+    pub fn rejection(&self) -> Option<&VerificationResult> {
+        self.rejection.as_ref()
+    }
+
+    /// Verifies the received proof against public inputs serialized in `pub_inputs`.
+    ///
+    /// If the proof has already been rejected, the rejection is returned without verifying the
+    /// proof.
+    pub fn finalize(self, pub_inputs: &[u8]) -> VerificationResult {
+        match self.rejection {
+            Some(rejection) => rejection,
+            None => self.verifier.verify(&self.buffer, pub_inputs),
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks the header of the proof against the policy of the verifier if the header has been
+    /// received in full.
+    fn check_header(&mut self) {
+        let header = match StarkProof::read_context(&self.buffer) {
+            Ok(header) => header,
+            // the rest of the header has not been received yet
+            Err(DeserializationError::UnexpectedEOF) => return,
+            Err(err) => {
+                self.reject(VerificationResult::rejected(
+                    0,
+                    ErrorKind::InvalidProof,
+                    err,
+                ));
+                return;
+            }
+        };
+        self.header_checked = true;
+
+        let security_level = with_registry(|registry| {
+            registry.header_security_level(&self.verifier.air_id, &header)
+        })
+        .expect("computation not registered");
+        if let Some(rejection) = self.verifier.check_policy(header.options(), security_level) {
+            self.reject(rejection);
+        }
+    }
+
+    /// Records the `rejection` of the proof and releases the received bytes.
+    fn reject(&mut self, rejection: VerificationResult) {
+        self.rejection = Some(rejection);
+        self.buffer = Vec::new();
+    }
+}
+
+// VERIFICATION RESULT
+// ================================================================================================
+
+/// Describes the outcome of verifying a proof by a [Verifier].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationResult {
+    security_level: u32,
+    error: Option<(ErrorKind, String)>,
+}
+
+impl VerificationResult {
+    fn accepted(security_level: u32) -> Self {
+        VerificationResult {
+            security_level,
+            error: None,
+        }
+    }
+
+    fn rejected(security_level: u32, kind: ErrorKind, message: impl fmt::Display) -> Self {
+        VerificationResult {
+            security_level,
+            error: Some((kind, message.to_string())),
+        }
+    }
+
+    /// Returns true if the proof was valid and acceptable to the verifier.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns conjectured security level (in bits) of the proof; 0 is returned if the proof
+    /// could not be deserialized.
+    pub fn security_level(&self) -> u32 {
+        self.security_level
+    }
+
+    /// Returns the kind of the error due to which the proof was rejected, if any.
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        self.error.as_ref().map(|(kind, _)| *kind)
+    }
+
+    /// Returns a message describing the error due to which the proof was rejected, if any.
+    pub fn error_message(&self) -> Option<&str> {
+        self.error.as_ref().map(|(_, message)| message.as_str())
+    }
+}
+
+// ERROR KIND
+// ================================================================================================
+
+/// Describes why a proof was rejected by a [Verifier].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The proof could not be deserialized.
+    InvalidProof,
+    /// The public inputs could not be deserialized.
+    InvalidPublicInputs,
+    /// The proof was generated using options not allowed by the verification policy.
+    UnacceptableOptions,
+    /// Security level of the proof is below the minimum required by the verification policy.
+    InsufficientSecurity,
+    /// The proof does not attest to a correct execution of the computation for the specified
+    /// public inputs.
+    VerificationFailed,
+}
+
+impl ErrorKind {
+    /// All error kinds.
+    pub const ALL: [ErrorKind; 5] = [
+        Self::InvalidProof,
+        Self::InvalidPublicInputs,
+        Self::UnacceptableOptions,
+        Self::InsufficientSecurity,
+        Self::VerificationFailed,
+    ];
+
+    /// Returns the name of this error kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidProof => "InvalidProof",
+            Self::InvalidPublicInputs => "InvalidPublicInputs",
+            Self::UnacceptableOptions => "UnacceptableOptions",
+            Self::InsufficientSecurity => "InsufficientSecurity",
+            Self::VerificationFailed => "VerificationFailed",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
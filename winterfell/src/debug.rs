@@ -0,0 +1,717 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Utilities for debugging transition constraints of an AIR against an execution trace.
+//!
+//! When an execution trace does not satisfy the constraints of a computation, proof generation
+//! usually fails with little information about the cause (e.g., the degree of the composition
+//! polynomial does not match the expected degree, or the verifier rejects the proof because
+//! constraint evaluations at the out-of-domain point are inconsistent). [ConstraintDebugger]
+//! makes it possible to examine each transition constraint in isolation:
+//!
+//! * [ConstraintDebugger::evaluate()] evaluates a single constraint over a window of trace steps;
+//!   the resulting [ConstraintEvaluations] list steps at which the constraint is violated and
+//!   can be plotted as text.
+//! * [ConstraintDebugger::unsatisfied_constraints()] evaluates all constraints over the entire
+//!   trace and returns evaluations of the constraints which are violated at least at one step.
+//! * [ConstraintDebugger::compare_ood()] evaluates each constraint at an out-of-domain point in
+//!   the same way as the prover does (by interpolating constraint quotients over the constraint
+//!   evaluation domain) and in the same way as the verifier does (by evaluating the constraint
+//!   over trace polynomials evaluated at the point). For constraints which are satisfied by the
+//!   trace and whose degrees are declared correctly, the two values are the same.
+//!
+//! All computations are done directly over the trace, without committing to it, and are
+//! intended for use only during development of an AIR.
+
+use crate::{
+    math::{fft, polynom, ExtensionOf, FieldElement, StarkField},
+    Air, AuxTraceRandElements, ColMatrix, ConstraintDivisor, EvaluationFrame, Trace,
+    TransitionConstraintDegree,
+};
+use core::{fmt, ops::Range};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of trace steps displayed in a single line of a constraint evaluation plot.
+const PLOT_LINE_WIDTH: usize = 64;
+
+// CONSTRAINT ID
+// ================================================================================================
+
+/// Identifies a transition constraint of an AIR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConstraintId {
+    /// Constraint at the specified index among the constraints evaluated by
+    /// [Air::evaluate_transition()].
+    Main(usize),
+    /// Constraint at the specified index among the constraints evaluated by
+    /// [Air::evaluate_aux_transition()]; constraints of lookup tables follow these constraints.
+    Aux(usize),
+}
+
+impl fmt::Display for ConstraintId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Main(idx) => write!(f, "main transition constraint {idx}"),
+            Self::Aux(idx) => write!(f, "auxiliary transition constraint {idx}"),
+        }
+    }
+}
+
+// CONSTRAINT DEBUGGER
+// ================================================================================================
+
+/// Evaluates transition constraints of an AIR over an execution trace.
+///
+/// `E` is the field in which auxiliary trace segments are defined, and in which out-of-domain
+/// evaluations are performed. For computations without auxiliary trace segments, a debugger can
+/// be instantiated via [ConstraintDebugger::new()], in which case `E` is the base field of the
+/// computation.
+pub struct ConstraintDebugger<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> {
+    air: &'a A,
+    main_segment: &'a ColMatrix<A::BaseField>,
+    aux_segments: &'a [ColMatrix<E>],
+    aux_rand_elements: AuxTraceRandElements<E>,
+    periodic_column_polys: Vec<Vec<A::BaseField>>,
+    main_degrees: Vec<TransitionConstraintDegree>,
+    aux_degrees: Vec<TransitionConstraintDegree>,
+    divisor: ConstraintDivisor<A::BaseField>,
+}
+
+impl<'a, A: Air> ConstraintDebugger<'a, A, A::BaseField> {
+    /// Returns a debugger for the main segment of the specified `trace`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The AIR describes auxiliary trace segments.
+    /// * The width or the length of the trace is not consistent with the AIR.
+    pub fn new<T: Trace<BaseField = A::BaseField>>(air: &'a A, trace: &'a T) -> Self {
+        assert!(
+            !air.trace_info().is_multi_segment(),
+            "the AIR describes auxiliary trace segments; use with_aux_segments() instead"
+        );
+        Self::with_aux_segments(air, trace, &[], AuxTraceRandElements::new())
+    }
+}
+
+impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintDebugger<'a, A, E> {
+    /// Returns a debugger for the main segment of the specified `trace` and the specified
+    /// auxiliary trace segments.
+    ///
+    /// Auxiliary segments are expected to be built via
+    /// [Trace::build_aux_segment_with_lookups()] using `aux_rand_elements`; the same random
+    /// elements are passed to the auxiliary transition constraints of the AIR.
+    ///
+    /// # Panics
+    /// Panics if the width or the length of the trace or of any of the auxiliary segments is not
+    /// consistent with the AIR.
+    pub fn with_aux_segments<T: Trace<BaseField = A::BaseField>>(
+        air: &'a A,
+        trace: &'a T,
+        aux_segments: &'a [ColMatrix<E>],
+        aux_rand_elements: AuxTraceRandElements<E>,
+    ) -> Self {
+        let layout = air.trace_layout();
+        assert_eq!(
+            layout.main_trace_width(),
+            trace.main_trace_width(),
+            "inconsistent main trace width: expected {}, but was {}",
+            layout.main_trace_width(),
+            trace.main_trace_width()
+        );
+        assert_eq!(
+            air.trace_length(),
+            trace.length(),
+            "inconsistent trace length: expected {}, but was {}",
+            air.trace_length(),
+            trace.length()
+        );
+        assert_eq!(
+            layout.num_aux_segments(),
+            aux_segments.len(),
+            "inconsistent number of auxiliary trace segments: expected {}, but was {}",
+            layout.num_aux_segments(),
+            aux_segments.len()
+        );
+        for (i, segment) in aux_segments.iter().enumerate() {
+            assert_eq!(
+                layout.get_aux_segment_width(i),
+                segment.num_cols(),
+                "inconsistent width of auxiliary trace segment {i}: expected {}, but was {}",
+                layout.get_aux_segment_width(i),
+                segment.num_cols()
+            );
+            assert_eq!(
+                air.trace_length(),
+                segment.num_rows(),
+                "inconsistent length of auxiliary trace segment {i}: expected {}, but was {}",
+                air.trace_length(),
+                segment.num_rows()
+            );
+        }
+
+        // composition coefficients do not affect degrees of constraints or their divisor
+        let num_constraints = air.context().num_transition_constraints();
+        let constraints = air.get_transition_constraints(&vec![E::ONE; num_constraints]);
+
+        ConstraintDebugger {
+            air,
+            main_segment: trace.main_segment(),
+            aux_segments,
+            aux_rand_elements,
+            periodic_column_polys: air.get_periodic_column_polys(),
+            main_degrees: constraints.main_constraint_degrees().to_vec(),
+            aux_degrees: constraints.aux_constraint_degrees().to_vec(),
+            divisor: constraints.divisor().clone(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns identifiers of all transition constraints of the AIR.
+    pub fn constraint_ids(&self) -> Vec<ConstraintId> {
+        let main_ids = (0..self.main_degrees.len()).map(ConstraintId::Main);
+        let aux_ids = (0..self.aux_degrees.len()).map(ConstraintId::Aux);
+        main_ids.chain(aux_ids).collect()
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the specified transition constraint at all trace steps in `steps`.
+    ///
+    /// Frames at the last steps of the trace wrap around to the first step; the constraint is
+    /// evaluated at steps which are exempt from transition constraints as well, but violations
+    /// at such steps are not reported.
+    ///
+    /// # Panics
+    /// Panics if the constraint does not exist, or if `steps` is not a range within the trace.
+    pub fn evaluate(
+        &self,
+        constraint: ConstraintId,
+        steps: Range<usize>,
+    ) -> ConstraintEvaluations<E> {
+        self.check_constraint(constraint);
+        assert!(
+            steps.start <= steps.end && steps.end <= self.air.trace_length(),
+            "steps {}..{} are not within the trace of {} steps",
+            steps.start,
+            steps.end,
+            self.air.trace_length()
+        );
+
+        let values = steps
+            .clone()
+            .map(|step| self.evaluate_at_step(step)[constraint_idx(self, constraint)])
+            .collect();
+
+        ConstraintEvaluations {
+            constraint,
+            steps,
+            values,
+            first_exempt_step: self.first_exempt_step(),
+        }
+    }
+
+    /// Evaluates all transition constraints over the entire trace and returns evaluations of the
+    /// constraints which are not satisfied at least at one step.
+    pub fn unsatisfied_constraints(&self) -> Vec<ConstraintEvaluations<E>> {
+        let trace_length = self.air.trace_length();
+        let step_evaluations = (0..trace_length)
+            .map(|step| self.evaluate_at_step(step))
+            .collect::<Vec<_>>();
+
+        self.constraint_ids()
+            .into_iter()
+            .map(|constraint| {
+                let idx = constraint_idx(self, constraint);
+                ConstraintEvaluations {
+                    constraint,
+                    steps: 0..trace_length,
+                    values: step_evaluations.iter().map(|values| values[idx]).collect(),
+                    first_exempt_step: self.first_exempt_step(),
+                }
+            })
+            .filter(|evaluations| !evaluations.is_satisfied())
+            .collect()
+    }
+
+    // OUT-OF-DOMAIN EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates transition constraint quotients at the out-of-domain point `z` as the prover
+    /// and as the verifier would, and returns the results for all constraints.
+    ///
+    /// The prover-side value of a constraint is computed by evaluating the constraint over the
+    /// constraint evaluation domain, dividing the evaluations by the constraint divisor, and
+    /// interpolating the quotient into a polynomial which is then evaluated at `z`. The
+    /// verifier-side value is computed by evaluating the constraint over the trace polynomials
+    /// evaluated at `z` and `z * g`, and dividing the result by the divisor evaluated at `z`.
+    ///
+    /// # Panics
+    /// Panics if `z` is a point of the trace domain at which transition constraints are
+    /// enforced.
+    pub fn compare_ood(&self, z: E) -> Vec<OodComparison<E>> {
+        let divisor_at_z = self.divisor.evaluate_at(z);
+        assert!(
+            divisor_at_z != E::ZERO,
+            "out-of-domain point must not be in the trace domain"
+        );
+
+        // interpolate trace columns into polynomials
+        let main_polys = self.main_segment.interpolate_columns();
+        let aux_polys = self
+            .aux_segments
+            .iter()
+            .map(|segment| segment.interpolate_columns())
+            .collect::<Vec<_>>();
+
+        let prover_quotients = self.build_constraint_quotients(&main_polys, &aux_polys);
+        let verifier_values = self.evaluate_ood_quotients(&main_polys, &aux_polys, z);
+        let expected_degrees = self.expected_quotient_degrees();
+
+        self.constraint_ids()
+            .into_iter()
+            .zip(prover_quotients)
+            .zip(verifier_values)
+            .zip(expected_degrees)
+            .map(
+                |(((constraint, quotient), verifier_value), expected_degree)| OodComparison {
+                    constraint,
+                    prover_value: polynom::eval(&quotient, z),
+                    verifier_value,
+                    expected_degree,
+                    actual_degree: polynom::degree_of(&quotient),
+                },
+            )
+            .collect()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Panics if the specified constraint does not exist.
+    fn check_constraint(&self, constraint: ConstraintId) {
+        let (idx, num_constraints) = match constraint {
+            ConstraintId::Main(idx) => (idx, self.main_degrees.len()),
+            ConstraintId::Aux(idx) => (idx, self.aux_degrees.len()),
+        };
+        assert!(
+            idx < num_constraints,
+            "{constraint} does not exist; the AIR has {num_constraints} such constraints"
+        );
+    }
+
+    /// Returns the first step starting from which transition constraints are not enforced.
+    fn first_exempt_step(&self) -> usize {
+        self.air.trace_length() - self.air.context().num_transition_exemptions()
+    }
+
+    /// Evaluates all transition constraints at the specified step of the trace; evaluations of
+    /// main constraints are followed by evaluations of auxiliary constraints.
+    fn evaluate_at_step(&self, step: usize) -> Vec<E> {
+        let trace_length = self.air.trace_length();
+        let next_step = (step + 1) % trace_length;
+        let x = self.air.trace_domain_generator().exp((step as u64).into());
+
+        let main_frame = EvaluationFrame::from_rows(
+            read_row(self.main_segment, step),
+            read_row(self.main_segment, next_step),
+        );
+        let periodic_values = self.evaluate_periodic_columns(x);
+
+        let mut main_evaluations = vec![A::BaseField::ZERO; self.main_degrees.len()];
+        self.air
+            .evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
+
+        let mut result = main_evaluations
+            .into_iter()
+            .map(E::from)
+            .collect::<Vec<_>>();
+        if !self.aux_degrees.is_empty() {
+            let aux_frame = EvaluationFrame::from_rows(
+                read_aux_row(self.aux_segments, step),
+                read_aux_row(self.aux_segments, next_step),
+            );
+            result.extend(self.evaluate_aux_constraints(&main_frame, &aux_frame, &periodic_values));
+        }
+        result
+    }
+
+    /// Evaluates polynomials of all periodic columns at the specified point.
+    fn evaluate_periodic_columns<F>(&self, x: F) -> Vec<F>
+    where
+        F: FieldElement<BaseField = A::BaseField>,
+    {
+        self.periodic_column_polys
+            .iter()
+            .map(|poly| {
+                let num_cycles = self.air.trace_length() / poly.len();
+                polynom::eval(poly, x.exp((num_cycles as u64).into()))
+            })
+            .collect()
+    }
+
+    /// Evaluates auxiliary transition constraints over the specified frames.
+    fn evaluate_aux_constraints<F>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+    ) -> Vec<E>
+    where
+        F: FieldElement<BaseField = A::BaseField>,
+        E: ExtensionOf<F>,
+    {
+        let mut result = vec![E::ZERO; self.aux_degrees.len()];
+        self.air.evaluate_aux_transition_with_lookups(
+            main_frame,
+            aux_frame,
+            periodic_values,
+            &self.aux_rand_elements,
+            &mut result,
+        );
+        result
+    }
+
+    /// Evaluates all transition constraints over the constraint evaluation domain, divides the
+    /// evaluations by the constraint divisor, and interpolates the results into polynomials.
+    fn build_constraint_quotients(
+        &self,
+        main_polys: &ColMatrix<A::BaseField>,
+        aux_polys: &[ColMatrix<E>],
+    ) -> Vec<Vec<E>> {
+        let trace_length = self.air.trace_length();
+        let ce_domain_size = self.air.ce_domain_size();
+        let blowup_factor = self.air.ce_blowup_factor();
+        let offset = self.air.domain_offset();
+
+        // extend trace columns onto the constraint evaluation domain
+        let twiddles = fft::get_twiddles::<A::BaseField>(trace_length);
+        let main_ce = main_polys
+            .columns()
+            .map(|poly| fft::evaluate_poly_with_offset(poly, &twiddles, offset, blowup_factor))
+            .collect::<Vec<_>>();
+        let aux_ce = aux_polys
+            .iter()
+            .flat_map(|segment| segment.columns())
+            .map(|poly| fft::evaluate_poly_with_offset(poly, &twiddles, offset, blowup_factor))
+            .collect::<Vec<_>>();
+
+        // evaluate constraints at every point of the domain; the next row of a frame is located
+        // `blowup_factor` rows ahead in the extended trace
+        let g = A::BaseField::get_root_of_unity(ce_domain_size.ilog2());
+        let mut x = offset;
+        let mut quotients = vec![Vec::with_capacity(ce_domain_size); self.constraint_ids().len()];
+        for row in 0..ce_domain_size {
+            let next_row = (row + blowup_factor) % ce_domain_size;
+            let main_frame = EvaluationFrame::from_rows(
+                main_ce.iter().map(|column| column[row]).collect(),
+                main_ce.iter().map(|column| column[next_row]).collect(),
+            );
+            let periodic_values = self.evaluate_periodic_columns(x);
+
+            let mut main_evaluations = vec![A::BaseField::ZERO; self.main_degrees.len()];
+            self.air
+                .evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
+            let mut evaluations = main_evaluations
+                .into_iter()
+                .map(E::from)
+                .collect::<Vec<_>>();
+            if !self.aux_degrees.is_empty() {
+                let aux_frame = EvaluationFrame::from_rows(
+                    aux_ce.iter().map(|column| column[row]).collect(),
+                    aux_ce.iter().map(|column| column[next_row]).collect(),
+                );
+                evaluations.extend(self.evaluate_aux_constraints(
+                    &main_frame,
+                    &aux_frame,
+                    &periodic_values,
+                ));
+            }
+
+            let divisor = E::from(self.divisor.evaluate_at(x));
+            for (quotient, evaluation) in quotients.iter_mut().zip(evaluations) {
+                quotient.push(evaluation / divisor);
+            }
+            x *= g;
+        }
+
+        // interpolate the quotients into polynomials
+        let inv_twiddles = fft::get_inv_twiddles::<A::BaseField>(ce_domain_size);
+        for quotient in quotients.iter_mut() {
+            fft::interpolate_poly_with_offset(quotient, &inv_twiddles, offset);
+        }
+        quotients
+    }
+
+    /// Evaluates all transition constraint quotients at the point `z` from the values of trace
+    /// polynomials at `z` and `z * g`.
+    fn evaluate_ood_quotients(
+        &self,
+        main_polys: &ColMatrix<A::BaseField>,
+        aux_polys: &[ColMatrix<E>],
+        z: E,
+    ) -> Vec<E> {
+        let z_next = z * E::from(self.air.trace_domain_generator());
+        let main_frame = EvaluationFrame::from_rows(
+            main_polys.evaluate_columns_at(z),
+            main_polys.evaluate_columns_at(z_next),
+        );
+        let periodic_values = self.evaluate_periodic_columns(z);
+
+        let mut evaluations = vec![E::ZERO; self.main_degrees.len()];
+        self.air
+            .evaluate_transition(&main_frame, &periodic_values, &mut evaluations);
+        if !self.aux_degrees.is_empty() {
+            let aux_frame = EvaluationFrame::from_rows(
+                aux_polys
+                    .iter()
+                    .flat_map(|segment| segment.evaluate_columns_at(z))
+                    .collect(),
+                aux_polys
+                    .iter()
+                    .flat_map(|segment| segment.evaluate_columns_at(z_next))
+                    .collect(),
+            );
+            evaluations.extend(self.evaluate_aux_constraints::<E>(
+                &main_frame,
+                &aux_frame,
+                &periodic_values,
+            ));
+        }
+
+        let divisor = self.divisor.evaluate_at(z);
+        evaluations
+            .into_iter()
+            .map(|value| value / divisor)
+            .collect()
+    }
+
+    /// Returns degrees of transition constraint quotients implied by the constraint degrees
+    /// declared by the AIR.
+    fn expected_quotient_degrees(&self) -> Vec<usize> {
+        let trace_length = self.air.trace_length();
+        self.main_degrees
+            .iter()
+            .chain(self.aux_degrees.iter())
+            .map(|degree| {
+                degree
+                    .get_evaluation_degree(trace_length)
+                    .saturating_sub(self.divisor.degree())
+            })
+            .collect()
+    }
+}
+
+// CONSTRAINT EVALUATIONS
+// ================================================================================================
+
+/// Evaluations of a single transition constraint over a window of trace steps.
+///
+/// When formatted via [Display](fmt::Display), the evaluations are plotted as text (see
+/// [ConstraintEvaluations::plot()]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintEvaluations<E: FieldElement> {
+    constraint: ConstraintId,
+    steps: Range<usize>,
+    values: Vec<E>,
+    first_exempt_step: usize,
+}
+
+impl<E: FieldElement> ConstraintEvaluations<E> {
+    /// Returns the identifier of the evaluated constraint.
+    pub fn constraint(&self) -> ConstraintId {
+        self.constraint
+    }
+
+    /// Returns the range of trace steps at which the constraint was evaluated.
+    pub fn steps(&self) -> Range<usize> {
+        self.steps.clone()
+    }
+
+    /// Returns evaluations of the constraint; the first value is the evaluation at the first
+    /// step of [steps()](ConstraintEvaluations::steps).
+    pub fn values(&self) -> &[E] {
+        &self.values
+    }
+
+    /// Returns the evaluation of the constraint at the specified trace step, or None if the
+    /// constraint was not evaluated at the step.
+    pub fn get(&self, step: usize) -> Option<E> {
+        if self.steps.contains(&step) {
+            Some(self.values[step - self.steps.start])
+        } else {
+            None
+        }
+    }
+
+    /// Returns trace steps at which the constraint is enforced but did not evaluate to ZERO.
+    pub fn violations(&self) -> Vec<usize> {
+        self.steps
+            .clone()
+            .zip(self.values.iter())
+            .filter(|&(step, &value)| !self.is_exempt(step) && value != E::ZERO)
+            .map(|(step, _)| step)
+            .collect()
+    }
+
+    /// Returns true if the constraint evaluated to ZERO at all steps at which it is enforced.
+    pub fn is_satisfied(&self) -> bool {
+        self.violations().is_empty()
+    }
+
+    /// Returns a text plot of the evaluations.
+    ///
+    /// Each step is displayed as `.` if the constraint evaluated to ZERO, `#` if it did not, and
+    /// `-` if the step is exempt from transition constraints; steps are displayed 64 per line.
+    /// The plot is followed by the values of the constraint at the first few violating steps.
+    pub fn plot(&self) -> String {
+        self.to_string()
+    }
+
+    fn is_exempt(&self, step: usize) -> bool {
+        step >= self.first_exempt_step
+    }
+}
+
+impl<E: FieldElement> fmt::Display for ConstraintEvaluations<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MAX_LISTED_VIOLATIONS: usize = 8;
+
+        writeln!(
+            f,
+            "{} at steps {}..{} (. zero, # non-zero, - exempt):",
+            self.constraint, self.steps.start, self.steps.end
+        )?;
+        let width = self.steps.end.max(1).to_string().len();
+        for (line_idx, line) in self.values.chunks(PLOT_LINE_WIDTH).enumerate() {
+            let first_step = self.steps.start + line_idx * PLOT_LINE_WIDTH;
+            let plot = line
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| {
+                    if self.is_exempt(first_step + i) {
+                        '-'
+                    } else if value == E::ZERO {
+                        '.'
+                    } else {
+                        '#'
+                    }
+                })
+                .collect::<String>();
+            writeln!(f, "  {first_step:>width$} | {plot}")?;
+        }
+
+        let violations = self.violations();
+        match violations.len() {
+            0 => write!(f, "no violations"),
+            num_violations => {
+                write!(f, "{num_violations} violation(s)")?;
+                for &step in violations.iter().take(MAX_LISTED_VIOLATIONS) {
+                    let value = self.values[step - self.steps.start];
+                    write!(f, "\n  step {step}: {value}")?;
+                }
+                if num_violations > MAX_LISTED_VIOLATIONS {
+                    write!(f, "\n  ...")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// OUT-OF-DOMAIN COMPARISON
+// ================================================================================================
+
+/// Prover-side and verifier-side evaluations of a transition constraint quotient at an
+/// out-of-domain point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OodComparison<E: FieldElement> {
+    /// Identifier of the evaluated constraint.
+    pub constraint: ConstraintId,
+    /// Value of the constraint quotient interpolated over the constraint evaluation domain.
+    pub prover_value: E,
+    /// Value of the constraint quotient computed from trace polynomials evaluated at the point.
+    pub verifier_value: E,
+    /// Degree of the constraint quotient implied by the degree declared by the AIR.
+    pub expected_degree: usize,
+    /// Degree of the constraint quotient interpolated over the constraint evaluation domain.
+    pub actual_degree: usize,
+}
+
+impl<E: FieldElement> OodComparison<E> {
+    /// Returns true if the prover-side and verifier-side values are equal.
+    ///
+    /// The values differ if the constraint is not satisfied by the trace, or if the actual degree
+    /// of the constraint exceeds the degree which can be evaluated over the constraint
+    /// evaluation domain.
+    pub fn is_consistent(&self) -> bool {
+        self.prover_value == self.verifier_value
+    }
+
+    /// Returns true if the degree of the interpolated constraint quotient is equal to the degree
+    /// implied by the AIR.
+    ///
+    /// For constraints which are satisfied, a lower degree means that the constraint degree
+    /// declared by the AIR is too high; for constraints which are not satisfied, the degree of
+    /// the interpolated quotient is usually close to the size of the constraint evaluation
+    /// domain.
+    pub fn has_expected_degree(&self) -> bool {
+        self.expected_degree == self.actual_degree
+    }
+}
+
+impl<E: FieldElement> fmt::Display for OodComparison<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.is_consistent() {
+            "ok"
+        } else {
+            "MISMATCH"
+        };
+        write!(
+            f,
+            "{}: {status}; prover: {}, verifier: {}, degree: {} (expected {})",
+            self.constraint,
+            self.prover_value,
+            self.verifier_value,
+            self.actual_degree,
+            self.expected_degree
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the position of the specified constraint in the vector of evaluations returned by
+/// [ConstraintDebugger::evaluate_at_step()].
+fn constraint_idx<A, E>(debugger: &ConstraintDebugger<A, E>, constraint: ConstraintId) -> usize
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+{
+    match constraint {
+        ConstraintId::Main(idx) => idx,
+        ConstraintId::Aux(idx) => debugger.main_degrees.len() + idx,
+    }
+}
+
+/// Reads the specified row of a matrix.
+fn read_row<E: FieldElement>(matrix: &ColMatrix<E>, row_idx: usize) -> Vec<E> {
+    let mut row = vec![E::ZERO; matrix.num_cols()];
+    matrix.read_row_into(row_idx, &mut row);
+    row
+}
+
+/// Reads the specified row of all auxiliary trace segments into a single vector.
+fn read_aux_row<E: FieldElement>(segments: &[ColMatrix<E>], row_idx: usize) -> Vec<E> {
+    segments
+        .iter()
+        .flat_map(|segment| read_row(segment, row_idx))
+        .collect()
+}
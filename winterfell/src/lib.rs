@@ -46,7 +46,8 @@
 //! 1. Define an *algebraic intermediate representation* (AIR) for you computation. This AIR
 //!    must be the same as the one used during proof generation process.
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
-//!    [StarkProof] and related public inputs as parameters.
+//!    [StarkProof], related public inputs, and [AcceptableOptions] as parameters. Acceptable
+//!    options define the minimum security level a proof must have to be accepted.
 //!
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
@@ -256,7 +257,8 @@
 //! ```no_run
 //! use winterfell::{
 //!     math::{fields::f128::BaseElement, FieldElement, ToElements},
-//!     ProofOptions, Prover, Trace, TraceTable, crypto::{hashers::Blake3_256, DefaultRandomCoin}
+//!     ProofOptions, Prover, Trace, TraceTable,
+//!     crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
 //! };
 //!
 //! # use winterfell::{
@@ -339,6 +341,7 @@
 //!     type Air = WorkAir;
 //!     type Trace = TraceTable<Self::BaseField>;
 //!     type HashFn = Blake3_256<Self::BaseField>;
+//!     type VC = MerkleTree<Self::HashFn>;
 //!     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 //!
 //!     // Our public inputs consist of the first and last value in the execution trace.
@@ -368,7 +371,7 @@
 //! #    math::{fields::f128::BaseElement, FieldElement, ToElements},
 //! #    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, TraceInfo,
 //! #    TransitionConstraintDegree, TraceTable, FieldExtension, Prover, ProofOptions,
-//! #    StarkProof, Trace, crypto::{hashers::Blake3_256, DefaultRandomCoin},
+//! #    StarkProof, Trace, crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
 //! # };
 //! #
 //! # pub fn build_do_work_trace(start: BaseElement, n: usize) -> TraceTable<BaseElement> {
@@ -456,6 +459,7 @@
 //! #    type Air = WorkAir;
 //! #    type Trace = TraceTable<Self::BaseField>;
 //! #    type HashFn = Blake3_256<Self::BaseField>;
+//! #    type VC = MerkleTree<Self::HashFn>;
 //! #    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 //! #
 //! #    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
@@ -497,10 +501,12 @@
 //! // Verify the proof. The number of steps and options are encoded in the proof itself,
 //! // so we don't need to pass them explicitly to the verifier.
 //! let pub_inputs = PublicInputs { start, result };
+//! // The verifier also specifies the minimum security level a proof must have to be accepted.
+//! let acceptable_options = winterfell::AcceptableOptions::MinConjecturedSecurity(95);
 //! assert!(winterfell::verify::<WorkAir,
 //!                              Blake3_256<BaseElement>,
 //!                              DefaultRandomCoin<Blake3_256<BaseElement>>
-//!                             >(proof, pub_inputs).is_ok());
+//!                             >(proof, pub_inputs, &acceptable_options).is_ok());
 //! ```
 //!
 //! That's all there is to it!
@@ -528,12 +534,30 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "export")]
+pub use prover::TraceExport;
 pub use prover::{
     crypto, iterators, math, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
     BoundaryConstraintGroup, ByteReader, ByteWriter, ColMatrix, ConstraintCompositionCoefficients,
-    ConstraintDivisor, DeepCompositionCoefficients, Deserializable, DeserializationError,
-    EvaluationFrame, FieldExtension, ProofOptions, Prover, ProverError, Serializable, SliceReader,
+    ConstraintDivisor, DeepCompositionCoefficients, DefaultPowStrategy, Deserializable,
+    DeserializationError, EvaluationFrame, FieldExtension, LdtKind, LookupTable, PowStrategy,
+    ProofDescription, ProofHeader, ProofMetadata, ProofOptions, Prover, ProverError,
+    ProverObserver, ProvingPhase, Queries, SequentialPowStrategy, Serializable, SliceReader,
     StarkProof, Trace, TraceInfo, TraceLayout, TraceTable, TraceTableFragment,
     TransitionConstraintDegree,
 };
-pub use verifier::{verify, VerifierError};
+pub use verifier::{
+    encode_for_evm, verify, verify_compressed, verify_ref, verify_with_metadata, AcceptableOptions,
+    SecurityEstimate, StarkProofRef, VerifierError, EVM_ENCODING_VERSION,
+};
+
+pub mod registry;
+
+#[cfg(feature = "std")]
+pub mod debug;
+
+#[cfg(feature = "std")]
+pub mod testing;
+
+#[cfg(all(test, feature = "std"))]
+mod tests;
@@ -0,0 +1,390 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! A registry of computations which can be proven and verified using serialized inputs.
+//!
+//! Proof generation and verification in Winterfell are generic over the AIR, the hash function,
+//! and the random coin of a computation. This makes them hard to use from environments which
+//! can pass only bytes across the boundary (e.g., JavaScript via WebAssembly, or C via FFI).
+//! [AirRegistry] bridges this gap: a computation is registered once under a string identifier,
+//! after which proofs for the computation can be generated and verified using only the
+//! identifier and serialized execution traces, public inputs, and proofs.
+
+use crate::{
+    math::ToElements, verify, AcceptableOptions, Air, Deserializable, DeserializationError,
+    ProofHeader, ProofOptions, Prover, ProverError, ProverObserver, StarkProof, TraceTable,
+    VerifierError,
+};
+use core::{fmt, marker::PhantomData};
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::{String, ToString},
+    Box,
+};
+
+// AIR HANDLER
+// ================================================================================================
+
+/// Defines how proofs for a specific computation are generated and verified from serialized
+/// inputs.
+///
+/// Handlers for computations described by a [Prover] can be registered via
+/// [AirRegistry::register()]; this trait needs to be implemented directly only for computations
+/// which require custom handling of their inputs.
+pub trait AirHandler: Send + Sync {
+    /// Generates a proof of the computation for the execution trace serialized in `trace` and
+    /// public inputs serialized in `pub_inputs`, using the specified proof `options`.
+    ///
+    /// If `max_memory` is provided, an error should be returned without attempting to generate
+    /// the proof when proof generation is expected to use more than `max_memory` bytes. The
+    /// `observer` should be notified about the progress of proof generation.
+    fn prove(
+        &self,
+        trace: &[u8],
+        pub_inputs: &[u8],
+        options: ProofOptions,
+        max_memory: Option<u64>,
+        observer: &mut dyn ProverObserver,
+    ) -> Result<StarkProof, RegistryError>;
+
+    /// Verifies the `proof` of the computation against public inputs serialized in
+    /// `pub_inputs`; the proof is accepted only if it was generated with options which are
+    /// acceptable according to `acceptable_options`.
+    fn verify(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+        acceptable_options: &AcceptableOptions,
+    ) -> Result<(), RegistryError>;
+
+    /// Returns conjectured security level (in bits) of the `proof` of the computation.
+    fn security_level(&self, proof: &StarkProof) -> u32;
+
+    /// Returns conjectured security level (in bits) of a proof of the computation with the
+    /// specified `header`.
+    fn header_security_level(&self, header: &ProofHeader) -> u32;
+}
+
+// AIR REGISTRY
+// ================================================================================================
+
+/// A collection of computations which can be proven and verified using serialized inputs.
+///
+/// Each computation is registered under a unique identifier. For computations registered via
+/// [register()](AirRegistry::register):
+/// * Execution traces are expected to be serialized [TraceTable] structs.
+/// * Public inputs are expected to be serialized values of the AIR's public inputs type.
+#[derive(Default)]
+pub struct AirRegistry {
+    handlers: BTreeMap<String, Box<dyn AirHandler>>,
+}
+
+impl AirRegistry {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new registry without any registered computations.
+    pub const fn new() -> Self {
+        AirRegistry {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    // REGISTRATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers the computation proven by provers of type `P` under the specified `air_id`.
+    ///
+    /// A new prover is instantiated via `build_prover` for every proof to be generated. Proofs are
+    /// verified using the AIR, the hash function, and the random coin of the prover.
+    ///
+    /// # Errors
+    /// Returns an error if a computation with the same identifier has already been registered.
+    pub fn register<P, F>(&mut self, air_id: &str, build_prover: F) -> Result<(), RegistryError>
+    where
+        P: Prover<Trace = TraceTable<<P as Prover>::BaseField>> + 'static,
+        <P::Air as Air>::PublicInputs: Deserializable,
+        F: Fn(ProofOptions) -> P + Send + Sync + 'static,
+    {
+        let handler = ProverHandler {
+            build_prover,
+            _prover: PhantomData,
+        };
+        self.register_handler(air_id, Box::new(handler))
+    }
+
+    /// Registers the computation handled by the specified `handler` under the specified
+    /// `air_id`.
+    ///
+    /// # Errors
+    /// Returns an error if a computation with the same identifier has already been registered.
+    pub fn register_handler(
+        &mut self,
+        air_id: &str,
+        handler: Box<dyn AirHandler>,
+    ) -> Result<(), RegistryError> {
+        if self.handlers.contains_key(air_id) {
+            return Err(RegistryError::DuplicateAir(air_id.to_string()));
+        }
+        self.handlers.insert(air_id.to_string(), handler);
+        Ok(())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if a computation with the specified identifier has been registered.
+    pub fn contains(&self, air_id: &str) -> bool {
+        self.handlers.contains_key(air_id)
+    }
+
+    /// Returns identifiers of all registered computations in lexicographic order.
+    pub fn air_ids(&self) -> Vec<&str> {
+        self.handlers.keys().map(|id| id.as_str()).collect()
+    }
+
+    // PROVING AND VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a proof of the computation registered under `air_id` for the execution trace
+    /// serialized in `trace` and public inputs serialized in `pub_inputs`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No computation has been registered under `air_id`.
+    /// * The execution trace or the public inputs could not be deserialized.
+    /// * The public inputs are not the public inputs of the computation described by the trace.
+    /// * The prover failed to generate the proof.
+    pub fn prove(
+        &self,
+        air_id: &str,
+        trace: &[u8],
+        pub_inputs: &[u8],
+        options: ProofOptions,
+    ) -> Result<StarkProof, RegistryError> {
+        self.get_handler(air_id)?
+            .prove(trace, pub_inputs, options, None, &mut ())
+    }
+
+    /// Generates a proof in the same way as [prove()](AirRegistry::prove), but fails early if
+    /// proof generation is expected to use more than `max_memory` bytes.
+    ///
+    /// This is meant for environments with hard memory limits (e.g., WebAssembly), where running
+    /// out of memory aborts the program instead of returning an error. The expected memory usage
+    /// is estimated via [Prover::estimate_peak_memory()] after the trace is deserialized.
+    ///
+    /// # Errors
+    /// Returns an error in the same cases as [prove()](AirRegistry::prove), and also if proof
+    /// generation is expected to use more than `max_memory` bytes.
+    pub fn prove_with_memory_limit(
+        &self,
+        air_id: &str,
+        trace: &[u8],
+        pub_inputs: &[u8],
+        options: ProofOptions,
+        max_memory: u64,
+    ) -> Result<StarkProof, RegistryError> {
+        self.get_handler(air_id)?
+            .prove(trace, pub_inputs, options, Some(max_memory), &mut ())
+    }
+
+    /// Generates a proof in the same way as [prove()](AirRegistry::prove), and notifies the
+    /// specified `observer` when each phase of proof generation starts and ends.
+    ///
+    /// If `max_memory` is provided, this fails early in the same way as
+    /// [prove_with_memory_limit()](AirRegistry::prove_with_memory_limit).
+    ///
+    /// # Errors
+    /// Returns an error in the same cases as
+    /// [prove_with_memory_limit()](AirRegistry::prove_with_memory_limit).
+    pub fn prove_with_observer(
+        &self,
+        air_id: &str,
+        trace: &[u8],
+        pub_inputs: &[u8],
+        options: ProofOptions,
+        max_memory: Option<u64>,
+        observer: &mut dyn ProverObserver,
+    ) -> Result<StarkProof, RegistryError> {
+        self.get_handler(air_id)?
+            .prove(trace, pub_inputs, options, max_memory, observer)
+    }
+
+    /// Verifies the `proof` of the computation registered under `air_id` against public inputs
+    /// serialized in `pub_inputs`.
+    ///
+    /// The proof is accepted only if the options it was generated with are acceptable according
+    /// to `acceptable_options` (see [verify()](crate::verify)); otherwise, a dishonest prover could
+    /// generate a valid proof for an invalid statement using options with very few queries.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No computation has been registered under `air_id`.
+    /// * The public inputs could not be deserialized.
+    /// * The options of the proof are not acceptable.
+    /// * The proof is not a valid proof of the computation for the specified public inputs.
+    pub fn verify(
+        &self,
+        air_id: &str,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+        acceptable_options: &AcceptableOptions,
+    ) -> Result<(), RegistryError> {
+        self.get_handler(air_id)?
+            .verify(proof, pub_inputs, acceptable_options)
+    }
+
+    /// Returns conjectured security level (in bits) of the `proof` of the computation registered
+    /// under `air_id`.
+    ///
+    /// # Errors
+    /// Returns an error if no computation has been registered under `air_id`.
+    pub fn security_level(&self, air_id: &str, proof: &StarkProof) -> Result<u32, RegistryError> {
+        Ok(self.get_handler(air_id)?.security_level(proof))
+    }
+
+    /// Returns conjectured security level (in bits) of a proof of the computation registered
+    /// under `air_id` with the specified `header`.
+    ///
+    /// This makes it possible to determine security level of a proof before the proof is read
+    /// in full (see [StarkProof::read_context()]).
+    ///
+    /// # Errors
+    /// Returns an error if no computation has been registered under `air_id`.
+    pub fn header_security_level(
+        &self,
+        air_id: &str,
+        header: &ProofHeader,
+    ) -> Result<u32, RegistryError> {
+        Ok(self.get_handler(air_id)?.header_security_level(header))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn get_handler(&self, air_id: &str) -> Result<&dyn AirHandler, RegistryError> {
+        self.handlers
+            .get(air_id)
+            .map(|handler| handler.as_ref())
+            .ok_or_else(|| RegistryError::UnknownAir(air_id.to_string()))
+    }
+}
+
+// PROVER HANDLER
+// ================================================================================================
+
+/// An [AirHandler] for computations described by a [Prover].
+struct ProverHandler<P, F> {
+    build_prover: F,
+    // fn() -> P is used so that the handler is Send and Sync regardless of the prover type
+    _prover: PhantomData<fn() -> P>,
+}
+
+impl<P, F> AirHandler for ProverHandler<P, F>
+where
+    P: Prover<Trace = TraceTable<<P as Prover>::BaseField>>,
+    <P::Air as Air>::PublicInputs: Deserializable,
+    F: Fn(ProofOptions) -> P + Send + Sync,
+{
+    fn prove(
+        &self,
+        trace: &[u8],
+        pub_inputs: &[u8],
+        options: ProofOptions,
+        max_memory: Option<u64>,
+        observer: &mut dyn ProverObserver,
+    ) -> Result<StarkProof, RegistryError> {
+        let trace =
+            TraceTable::read_from_bytes(trace).map_err(RegistryError::InvalidExecutionTrace)?;
+        let pub_inputs = <P::Air as Air>::PublicInputs::read_from_bytes(pub_inputs)
+            .map_err(RegistryError::InvalidPublicInputs)?;
+
+        // make sure the trace describes the computation for the specified public inputs; without
+        // this check, the prover would generate a proof for whatever inputs the trace implies
+        let prover = (self.build_prover)(options);
+        if prover.get_pub_inputs(&trace).to_elements() != pub_inputs.to_elements() {
+            return Err(RegistryError::InconsistentPublicInputs);
+        }
+
+        if let Some(max_memory) = max_memory {
+            let required_memory = prover.estimate_peak_memory(&trace);
+            if required_memory > max_memory {
+                return Err(RegistryError::InsufficientMemory(
+                    required_memory,
+                    max_memory,
+                ));
+            }
+        }
+
+        prover
+            .prove_with_observer(trace, observer)
+            .map_err(RegistryError::ProverError)
+    }
+
+    fn verify(
+        &self,
+        proof: StarkProof,
+        pub_inputs: &[u8],
+        acceptable_options: &AcceptableOptions,
+    ) -> Result<(), RegistryError> {
+        let pub_inputs = <P::Air as Air>::PublicInputs::read_from_bytes(pub_inputs)
+            .map_err(RegistryError::InvalidPublicInputs)?;
+        verify::<P::Air, P::HashFn, P::RandomCoin>(proof, pub_inputs, acceptable_options)
+            .map_err(RegistryError::VerifierError)
+    }
+
+    fn security_level(&self, proof: &StarkProof) -> u32 {
+        proof.security_level::<P::HashFn>(true)
+    }
+
+    fn header_security_level(&self, header: &ProofHeader) -> u32 {
+        header.security_level::<P::HashFn>(true)
+    }
+}
+
+// REGISTRY ERROR
+// ================================================================================================
+
+/// Represents an error returned by an [AirRegistry].
+#[derive(Debug, PartialEq)]
+pub enum RegistryError {
+    /// This error occurs when no computation has been registered under the specified identifier.
+    UnknownAir(String),
+    /// This error occurs when a computation with the same identifier has already been registered.
+    DuplicateAir(String),
+    /// This error occurs when an execution trace could not be deserialized.
+    InvalidExecutionTrace(DeserializationError),
+    /// This error occurs when public inputs could not be deserialized.
+    InvalidPublicInputs(DeserializationError),
+    /// This error occurs when the specified public inputs are different from the public inputs
+    /// of the computation described by the execution trace.
+    InconsistentPublicInputs,
+    /// This error occurs when proof generation is expected to use more memory (in bytes) than
+    /// the specified limit.
+    InsufficientMemory(u64, u64),
+    /// This error occurs when the prover fails to generate a proof.
+    ProverError(ProverError),
+    /// This error occurs when a proof could not be verified.
+    VerifierError(VerifierError),
+}
+
+impl fmt::Display for RegistryError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAir(air_id) => write!(f, "computation {air_id} has not been registered"),
+            Self::DuplicateAir(air_id) => write!(f, "computation {air_id} has already been registered"),
+            Self::InvalidExecutionTrace(err) => write!(f, "failed to read execution trace: {err}"),
+            Self::InvalidPublicInputs(err) => write!(f, "failed to read public inputs: {err}"),
+            Self::InconsistentPublicInputs => {
+                write!(f, "public inputs are inconsistent with the execution trace")
+            }
+            Self::InsufficientMemory(required, limit) => {
+                write!(f, "proof generation requires {required} bytes of memory, but only {limit} bytes are available")
+            }
+            Self::ProverError(err) => write!(f, "failed to generate proof: {err}"),
+            Self::VerifierError(err) => write!(f, "failed to verify proof: {err}"),
+        }
+    }
+}
@@ -0,0 +1,365 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Helpers for testing AIRs and provers of computations.
+//!
+//! This module contains checks which are useful for almost every computation, and which would
+//! otherwise need to be re-implemented in test suites of every crate which defines an AIR:
+//!
+//! * [assert_constraint_degrees()] checks that constraint degrees declared by an AIR match the
+//!   actual degrees of the constraints evaluated over a valid trace.
+//! * [prove_and_verify_roundtrip()] generates a proof, checks that it survives serialization,
+//!   and verifies it.
+//! * [assert_transcripts_match()] (available with `transcript` feature) checks that the prover
+//!   and the verifier execute the same Fiat-Shamir transcript.
+//! * [boundary_mutations()], [random_mutations()], and [assert_mutations_rejected()] check that
+//!   modifications of a valid trace (in particular, at the first and the last steps of the trace
+//!   where boundary constraints and transition exemptions apply) result in proofs which are not
+//!   accepted by the verifier.
+//!
+//! All helpers panic with a descriptive message when a check fails, and are intended to be used
+//! in tests only.
+
+use crate::{
+    crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin},
+    debug::ConstraintDebugger,
+    math::{
+        fields::{CubeExtension, QuadExtension, QuartExtension},
+        ExtensibleField, FieldElement, StarkField,
+    },
+    verify, AcceptableOptions, Air, AuxTraceRandElements, ColMatrix, FieldExtension, Prover,
+    StarkProof, Trace, TraceTable,
+};
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+// CONSTRAINT DEGREES
+// ================================================================================================
+
+/// Checks that the specified `trace` satisfies transition constraints of the `air`, and that the
+/// degrees of the constraints declared by the `air` match their actual degrees.
+///
+/// Auxiliary trace segments (if any) are built via [Trace::build_aux_segment_with_lookups()]
+/// using random elements drawn from a deterministically seeded random coin; the segments are
+/// defined over the field extension specified by the proof options of the `air`.
+///
+/// # Panics
+/// Panics if any of the transition constraints is not satisfied by the trace, or if the actual
+/// degree of any of the constraints differs from the declared degree.
+pub fn assert_constraint_degrees<A, T>(air: &A, trace: &mut T)
+where
+    A: Air,
+    A::BaseField: ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<4>,
+    T: Trace<BaseField = A::BaseField>,
+{
+    match air.options().field_extension() {
+        FieldExtension::None => check_constraint_degrees::<A, A::BaseField, T>(air, trace),
+        FieldExtension::Quadratic => {
+            check_constraint_degrees::<A, QuadExtension<A::BaseField>, T>(air, trace)
+        }
+        FieldExtension::Cubic => {
+            check_constraint_degrees::<A, CubeExtension<A::BaseField>, T>(air, trace)
+        }
+        FieldExtension::Quartic => {
+            check_constraint_degrees::<A, QuartExtension<A::BaseField>, T>(air, trace)
+        }
+    }
+}
+
+fn check_constraint_degrees<A, E, T>(air: &A, trace: &mut T)
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    T: Trace<BaseField = A::BaseField>,
+{
+    let (aux_segments, aux_rand_elements) = build_aux_segments::<A, E, T>(air, trace);
+    let debugger =
+        ConstraintDebugger::with_aux_segments(air, trace, &aux_segments, aux_rand_elements);
+
+    let unsatisfied = debugger.unsatisfied_constraints();
+    assert!(
+        unsatisfied.is_empty(),
+        "trace does not satisfy transition constraints:\n{}",
+        join_lines(&unsatisfied)
+    );
+
+    // the out-of-domain point is derived from a fixed seed so that failures are reproducible
+    let mut coin = DefaultRandomCoin::<Blake3_256<A::BaseField>>::new(&[A::BaseField::ONE]);
+    let z = coin
+        .draw_ext::<E>()
+        .expect("failed to draw out-of-domain point");
+    let mismatches = debugger
+        .compare_ood(z)
+        .into_iter()
+        .filter(|comparison| !comparison.has_expected_degree())
+        .collect::<Vec<_>>();
+    assert!(
+        mismatches.is_empty(),
+        "declared constraint degrees do not match actual degrees:\n{}",
+        join_lines(&mismatches)
+    );
+}
+
+/// Builds auxiliary trace segments of the `trace` using random elements drawn from a coin with
+/// a fixed seed.
+fn build_aux_segments<A, E, T>(
+    air: &A,
+    trace: &mut T,
+) -> (Vec<ColMatrix<E>>, AuxTraceRandElements<E>)
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    T: Trace<BaseField = A::BaseField>,
+{
+    let mut coin = DefaultRandomCoin::<Blake3_256<A::BaseField>>::new(&[A::BaseField::ZERO]);
+    let mut aux_segments = Vec::new();
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    for i in 0..air.trace_layout().num_aux_segments() {
+        let rand_elements = air
+            .get_aux_trace_segment_random_elements::<E, _>(i, &mut coin)
+            .expect("failed to draw random elements for an auxiliary trace segment");
+        let segment = trace
+            .build_aux_segment_with_lookups(air, &aux_segments, &rand_elements)
+            .expect("failed to build auxiliary trace segment");
+        aux_segments.push(segment);
+        aux_rand_elements.add_segment_elements(rand_elements);
+    }
+    (aux_segments, aux_rand_elements)
+}
+
+// PROOF ROUNDTRIP
+// ================================================================================================
+
+/// Generates a proof for the specified `trace` using the `prover`, checks that the proof is the
+/// same after serialization and deserialization, verifies the deserialized proof against the
+/// public inputs of the trace, and returns the proof.
+///
+/// # Panics
+/// Panics if the proof could not be generated, does not survive serialization, or is rejected by
+/// the verifier.
+pub fn prove_and_verify_roundtrip<P: Prover>(prover: &P, trace: P::Trace) -> StarkProof {
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover
+        .prove(trace)
+        .unwrap_or_else(|err| panic!("failed to generate proof: {err}"));
+
+    let proof_bytes = proof.to_bytes();
+    let parsed_proof = StarkProof::from_bytes(&proof_bytes)
+        .unwrap_or_else(|err| panic!("failed to deserialize proof: {err}"));
+    assert!(
+        proof == parsed_proof,
+        "deserialized proof is not the same as the original proof"
+    );
+    assert_eq!(
+        proof_bytes,
+        parsed_proof.to_bytes(),
+        "re-serialized proof is not the same as the original proof"
+    );
+
+    let acceptable_options = AcceptableOptions::OptionSet(vec![prover.options().clone()]);
+    verify::<P::Air, P::HashFn, P::RandomCoin>(parsed_proof, pub_inputs, &acceptable_options)
+        .unwrap_or_else(|err| panic!("failed to verify proof: {err}"));
+    proof
+}
+
+// TRANSCRIPTS
+// ================================================================================================
+
+/// Generates and verifies a proof for the specified `trace` while recording Fiat-Shamir
+/// transcripts of the prover and the verifier, and checks that the transcripts are the same.
+///
+/// Transcripts are recorded only if the random coin of the `prover` is a
+/// [RecordingRandomCoin](crate::crypto::transcript::RecordingRandomCoin).
+///
+/// # Panics
+/// Panics if the proof could not be generated or verified, if the random coin of the prover does
+/// not record transcripts, or if the transcripts differ.
+#[cfg(feature = "transcript")]
+pub fn assert_transcripts_match<P: Prover>(prover: &P, trace: P::Trace) {
+    use crate::crypto::transcript;
+
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let (proof, prover_transcript) = transcript::record(|| prover.prove(trace));
+    let proof = proof.unwrap_or_else(|err| panic!("failed to generate proof: {err}"));
+    assert!(
+        !prover_transcript.entries().is_empty(),
+        "no transcript was recorded; the random coin of the prover must be a RecordingRandomCoin"
+    );
+
+    let acceptable_options = AcceptableOptions::OptionSet(vec![prover.options().clone()]);
+    let (result, verifier_transcript) = transcript::record(|| {
+        verify::<P::Air, P::HashFn, P::RandomCoin>(proof, pub_inputs, &acceptable_options)
+    });
+    result.unwrap_or_else(|err| panic!("failed to verify proof: {err}"));
+
+    if let Some(idx) = prover_transcript.find_divergence(&verifier_transcript) {
+        let describe = |transcript: &transcript::Transcript| match transcript.entries().get(idx) {
+            Some(entry) => format!("{entry:?}"),
+            None => "end of transcript".to_string(),
+        };
+        panic!(
+            "prover and verifier transcripts diverge at operation {idx}:\n  prover: {}\n  \
+            verifier: {}",
+            describe(&prover_transcript),
+            describe(&verifier_transcript)
+        );
+    }
+}
+
+// TRACE MUTATIONS
+// ================================================================================================
+
+/// A modification of a single cell of an execution trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceMutation<B: StarkField> {
+    /// Index of the modified column.
+    pub column: usize,
+    /// Index of the modified step.
+    pub step: usize,
+    /// Value added to the cell; this is never ZERO.
+    pub delta: B,
+}
+
+impl<B: StarkField> TraceMutation<B> {
+    /// Returns a copy of the specified `trace` with this mutation applied to it.
+    ///
+    /// # Panics
+    /// Panics if the mutated cell is not in the trace.
+    pub fn apply(&self, trace: &TraceTable<B>) -> TraceTable<B> {
+        let columns = (0..trace.width())
+            .map(|i| trace.get_column(i).to_vec())
+            .collect::<Vec<_>>();
+        let mut result = TraceTable::init(columns);
+        result.set_meta(trace.meta().to_vec());
+        let value = result.get(self.column, self.step);
+        result.set(self.column, self.step, value + self.delta);
+        result
+    }
+}
+
+impl<B: StarkField> fmt::Display for TraceMutation<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "trace({}, {}) += {}", self.column, self.step, self.delta)
+    }
+}
+
+/// Returns mutations of every column of the specified `trace` at the first two and the last two
+/// steps of the trace.
+///
+/// These steps are the most likely to be left unconstrained by mistake: boundary constraints are
+/// usually placed against the first and the last steps, and transition constraints are not
+/// enforced at the last steps of the trace. Deltas of the mutations are derived from `seed`.
+pub fn boundary_mutations<B: StarkField>(
+    trace: &TraceTable<B>,
+    seed: u64,
+) -> Vec<TraceMutation<B>> {
+    let length = trace.length();
+    let mut rng = SplitMix64(seed);
+    let mut result = Vec::new();
+    for step in [0, 1, length - 2, length - 1] {
+        for column in 0..trace.width() {
+            result.push(TraceMutation {
+                column,
+                step,
+                delta: rng.next_delta(),
+            });
+        }
+    }
+    result
+}
+
+/// Returns `num_mutations` mutations of randomly chosen cells of the specified `trace`; the
+/// cells and the deltas are derived from `seed`.
+pub fn random_mutations<B: StarkField>(
+    trace: &TraceTable<B>,
+    num_mutations: usize,
+    seed: u64,
+) -> Vec<TraceMutation<B>> {
+    let mut rng = SplitMix64(seed);
+    (0..num_mutations)
+        .map(|_| TraceMutation {
+            column: (rng.next_u64() % trace.width() as u64) as usize,
+            step: (rng.next_u64() % trace.length() as u64) as usize,
+            delta: rng.next_delta(),
+        })
+        .collect()
+}
+
+/// Checks that for every mutation in `mutations`, a proof for the mutated `trace` is not
+/// accepted by the verifier against the public inputs of the original `trace`.
+///
+/// A mutation is considered rejected if the prover fails to generate a proof (including by
+/// panicking, as the prover does in debug mode when the trace does not satisfy the constraints),
+/// or if the verifier rejects the generated proof.
+///
+/// # Panics
+/// Panics if a proof for any of the mutated traces is accepted by the verifier.
+pub fn assert_mutations_rejected<P>(
+    prover: &P,
+    trace: &TraceTable<P::BaseField>,
+    mutations: &[TraceMutation<P::BaseField>],
+) where
+    P: Prover<Trace = TraceTable<<P as Prover>::BaseField>>,
+{
+    let acceptable_options = AcceptableOptions::OptionSet(vec![prover.options().clone()]);
+    let accepted = mutations
+        .iter()
+        .filter(|mutation| {
+            let mutated_trace = mutation.apply(trace);
+            let pub_inputs = prover.get_pub_inputs(trace);
+            match panic::catch_unwind(AssertUnwindSafe(|| prover.prove(mutated_trace))) {
+                Ok(Ok(proof)) => verify::<P::Air, P::HashFn, P::RandomCoin>(
+                    proof,
+                    pub_inputs,
+                    &acceptable_options,
+                )
+                .is_ok(),
+                _ => false,
+            }
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        accepted.is_empty(),
+        "proofs for mutated traces were accepted: {}",
+        accepted
+            .iter()
+            .map(|mutation| mutation.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Joins the specified items into lines.
+fn join_lines<T: fmt::Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A simple deterministic pseudo-random number generator.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random non-zero field element; the element is smaller than 2^32 and is thus
+    /// non-zero in all fields with moduli greater than 2^32.
+    fn next_delta<B: StarkField>(&mut self) -> B {
+        B::from((self.next_u64() as u32).max(1))
+    }
+}
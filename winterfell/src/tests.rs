@@ -0,0 +1,248 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
+    debug::{ConstraintDebugger, ConstraintId},
+    math::{fields::f128::BaseElement, FieldElement},
+    testing, Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, Prover,
+    Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// CONSTRAINT DEBUGGER TESTS
+// ================================================================================================
+
+#[test]
+fn constraint_debugger() {
+    let prover = FibProver::new();
+    let mut trace = build_fib_trace(64);
+    let air = FibAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        build_options(),
+    );
+    let z = BaseElement::new(0x1234_5678_9abc_def0);
+
+    // a valid trace satisfies all constraints, and prover-side and verifier-side evaluations of
+    // the constraints at an out-of-domain point are the same
+    let debugger = ConstraintDebugger::new(&air, &trace);
+    assert_eq!(
+        vec![ConstraintId::Main(0), ConstraintId::Main(1)],
+        debugger.constraint_ids()
+    );
+    assert!(debugger.unsatisfied_constraints().is_empty());
+    for comparison in debugger.compare_ood(z) {
+        assert!(comparison.is_consistent(), "{comparison}");
+        assert!(comparison.has_expected_degree(), "{comparison}");
+    }
+
+    // the first term in the row at step 10 is used by the first constraint at steps 9 and 10,
+    // and by the second constraint at step 9
+    let value = trace.get(0, 10);
+    trace.set(0, 10, value + BaseElement::ONE);
+    let debugger = ConstraintDebugger::new(&air, &trace);
+
+    let unsatisfied = debugger.unsatisfied_constraints();
+    assert_eq!(2, unsatisfied.len());
+    assert_eq!(ConstraintId::Main(0), unsatisfied[0].constraint());
+    assert_eq!(vec![9, 10], unsatisfied[0].violations());
+    assert_eq!(ConstraintId::Main(1), unsatisfied[1].constraint());
+    assert_eq!(vec![9], unsatisfied[1].violations());
+
+    let evaluations = debugger.evaluate(ConstraintId::Main(0), 8..12);
+    assert_eq!(8..12, evaluations.steps());
+    assert_eq!(Some(BaseElement::ONE), evaluations.get(9));
+    assert_eq!(Some(-BaseElement::ONE), evaluations.get(10));
+    assert_eq!(None, evaluations.get(12));
+    let expected_plot = format!(
+        "main transition constraint 0 at steps 8..12 (. zero, # non-zero, - exempt):\n   \
+        8 | .##.\n2 violation(s)\n  step 9: 1\n  step 10: {}",
+        -BaseElement::ONE
+    );
+    assert_eq!(expected_plot, evaluations.plot());
+
+    // the last step is exempt from transition constraints
+    let evaluations = debugger.evaluate(ConstraintId::Main(1), 28..32);
+    assert!(evaluations.plot().contains("| ...-\n"));
+    assert!(evaluations.is_satisfied());
+
+    // violated constraints cannot be interpolated into polynomials of the expected degree
+    for comparison in debugger.compare_ood(z) {
+        assert!(!comparison.is_consistent(), "{comparison}");
+        assert!(!comparison.has_expected_degree(), "{comparison}");
+    }
+}
+
+// TESTING UTILITIES TESTS
+// ================================================================================================
+
+#[test]
+fn air_properties() {
+    let prover = FibProver::new();
+    let mut trace = build_fib_trace(64);
+    let air = FibAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        build_options(),
+    );
+    testing::assert_constraint_degrees(&air, &mut trace);
+
+    // every cell of the trace is constrained, and thus, modifying any of the cells invalidates
+    // the proof
+    let mut mutations = testing::boundary_mutations(&trace, 1);
+    assert_eq!(2 * 4, mutations.len());
+    mutations.extend(testing::random_mutations(&trace, 4, 2));
+    testing::assert_mutations_rejected(&prover, &trace, &mutations);
+
+    let proof = testing::prove_and_verify_roundtrip(&prover, trace);
+    assert_eq!(32, proof.trace_length());
+}
+
+#[test]
+#[should_panic(expected = "trace does not satisfy transition constraints")]
+fn air_properties_invalid_trace() {
+    let prover = FibProver::new();
+    let trace = build_fib_trace(64);
+    let air = FibAir::new(
+        trace.get_info(),
+        prover.get_pub_inputs(&trace),
+        build_options(),
+    );
+    let mutation = testing::random_mutations(&trace, 1, 0)[0];
+    testing::assert_constraint_degrees(&air, &mut mutation.apply(&trace));
+}
+
+#[cfg(feature = "transcript")]
+#[test]
+fn transcripts_match() {
+    use super::crypto::transcript::RecordingRandomCoin;
+
+    /// A prover which records transcripts of its random coin.
+    struct RecordingFibProver(FibProver);
+
+    impl Prover for RecordingFibProver {
+        type BaseField = BaseElement;
+        type Air = FibAir;
+        type Trace = TraceTable<BaseElement>;
+        type HashFn = Blake3;
+        type VC = MerkleTree<Blake3>;
+        type RandomCoin = RecordingRandomCoin<DefaultRandomCoin<Blake3>>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+            self.0.get_pub_inputs(trace)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            self.0.options()
+        }
+    }
+
+    let prover = RecordingFibProver(FibProver::new());
+    testing::assert_transcripts_match(&prover, build_fib_trace(64));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7)
+}
+
+/// Builds a trace for computing a Fibonacci sequence of the specified length such that each row
+/// advances the sequence by 2 terms.
+fn build_fib_trace(sequence_length: usize) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(2, sequence_length / 2);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ONE;
+            state[1] = BaseElement::ONE;
+        },
+        |_, state| {
+            state[0] += state[1];
+            state[1] += state[0];
+        },
+    );
+    trace
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+struct FibProver {
+    options: ProofOptions,
+}
+
+impl FibProver {
+    fn new() -> Self {
+        Self {
+            options: build_options(),
+        }
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type VC = MerkleTree<Blake3>;
+    type RandomCoin = DefaultRandomCoin<Blake3>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}